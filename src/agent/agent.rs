@@ -9,6 +9,7 @@ use crate::observability::{self, Observer, ObserverEvent};
 use crate::providers::{self, ChatMessage, ChatRequest, ConversationMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::tools::error::{ToolError, ToolErrorKind};
 use crate::tools::{self, Tool, ToolSpec};
 use anyhow::Result;
 use std::collections::HashMap;
@@ -37,6 +38,7 @@ pub struct Agent {
     classification_config: crate::config::QueryClassificationConfig,
     available_hints: Vec<String>,
     route_model_by_hint: HashMap<String, String>,
+    auto_model_config: crate::config::AutoModelConfig,
 }
 
 pub struct AgentBuilder {
@@ -58,6 +60,7 @@ pub struct AgentBuilder {
     classification_config: Option<crate::config::QueryClassificationConfig>,
     available_hints: Option<Vec<String>>,
     route_model_by_hint: Option<HashMap<String, String>>,
+    auto_model_config: Option<crate::config::AutoModelConfig>,
 }
 
 impl AgentBuilder {
@@ -81,6 +84,7 @@ impl AgentBuilder {
             classification_config: None,
             available_hints: None,
             route_model_by_hint: None,
+            auto_model_config: None,
         }
     }
 
@@ -180,6 +184,11 @@ impl AgentBuilder {
         self
     }
 
+    pub fn auto_model_config(mut self, auto_model_config: crate::config::AutoModelConfig) -> Self {
+        self.auto_model_config = Some(auto_model_config);
+        self
+    }
+
     pub fn build(self) -> Result<Agent> {
         let tools = self
             .tools
@@ -223,6 +232,7 @@ impl AgentBuilder {
             classification_config: self.classification_config.unwrap_or_default(),
             available_hints: self.available_hints.unwrap_or_default(),
             route_model_by_hint: self.route_model_by_hint.unwrap_or_default(),
+            auto_model_config: self.auto_model_config.unwrap_or_default(),
         })
     }
 }
@@ -335,8 +345,9 @@ impl Agent {
             .classification_config(config.query_classification.clone())
             .available_hints(available_hints)
             .route_model_by_hint(route_model_by_hint)
+            .auto_model_config(config.models.auto.clone())
             .identity_config(config.identity.clone())
-            .skills(crate::skills::load_skills_with_config(
+            .skills(crate::skills::load_prompt_skills(
                 &config.workspace_dir,
                 config,
             ))
@@ -386,10 +397,17 @@ impl Agent {
         self.prompt_builder.build(&ctx)
     }
 
+    /// Report a failed call and render the envelope the model receives.
+    fn tool_error(&self, tool: &str, error: ToolError) -> String {
+        error.record(self.observer.as_ref(), tool);
+        error.to_json()
+    }
+
     async fn execute_tool_call(&self, call: &ParsedToolCall) -> ToolExecutionResult {
         let start = Instant::now();
 
         let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
+            crate::skills::usage::record_tool_call(&call.name, &call.arguments);
             match tool.execute(call.arguments.clone()).await {
                 Ok(r) => {
                     self.observer.record_event(&ObserverEvent::ToolCall {
@@ -400,7 +418,7 @@ impl Agent {
                     if r.success {
                         r.output
                     } else {
-                        format!("Error: {}", r.error.unwrap_or(r.output))
+                        self.tool_error(&call.name, ToolError::parse(&r.error.unwrap_or(r.output)))
                     }
                 }
                 Err(e) => {
@@ -409,11 +427,20 @@ impl Agent {
                         duration: start.elapsed(),
                         success: false,
                     });
-                    format!("Error executing {}: {e}", call.name)
+                    self.tool_error(
+                        &call.name,
+                        ToolError::classify(&format!("Error executing {}: {e}", call.name)),
+                    )
                 }
             }
         } else {
-            format!("Unknown tool: {}", call.name)
+            self.tool_error(
+                &call.name,
+                ToolError::new(
+                    ToolErrorKind::NotFound,
+                    format!("Unknown tool: {}", call.name),
+                ),
+            )
         };
 
         ToolExecutionResult {
@@ -461,6 +488,43 @@ impl Agent {
                 return format!("hint:{}", decision.hint);
             }
         }
+
+        let context_chars: usize = self
+            .history
+            .iter()
+            .map(|message| match message {
+                ConversationMessage::Chat(chat) if chat.role == "system" => 0,
+                ConversationMessage::Chat(chat) => chat.content.len(),
+                ConversationMessage::AssistantToolCalls { text, .. } => {
+                    text.as_deref().map_or(0, str::len)
+                }
+                ConversationMessage::ToolResults(results) => {
+                    results.iter().map(|result| result.content.len()).sum()
+                }
+            })
+            .sum();
+        let tools_involved = self.history.iter().any(|message| {
+            matches!(
+                message,
+                ConversationMessage::AssistantToolCalls { .. }
+                    | ConversationMessage::ToolResults(_)
+            )
+        });
+        if let Some(decision) = super::classifier::select_auto_model(
+            &self.auto_model_config,
+            context_chars,
+            tools_involved,
+        ) {
+            tracing::info!(
+                target: "query_classification",
+                tier = decision.tier.as_str(),
+                model = decision.model.as_str(),
+                estimated_tokens = decision.estimated_tokens,
+                "Auto-selected model for turn"
+            );
+            return decision.model;
+        }
+
         self.model_name.clone()
     }
 
@@ -473,6 +537,8 @@ impl Agent {
                 )));
         }
 
+        crate::skills::usage::record_prompt_inclusion();
+
         if self.auto_save {
             let _ = self
                 .memory
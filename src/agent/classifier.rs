@@ -1,4 +1,7 @@
-use crate::config::schema::QueryClassificationConfig;
+use crate::config::schema::{AutoModelConfig, QueryClassificationConfig};
+
+/// Rough chars-per-token ratio used for context size estimates.
+pub(crate) const APPROX_CHARS_PER_TOKEN: usize = 4;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ClassificationDecision {
@@ -65,6 +68,72 @@ pub fn classify_with_decision(
     None
 }
 
+/// Model tier chosen by automatic model selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoModelTier {
+    Cheap,
+    Strong,
+}
+
+impl AutoModelTier {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cheap => "cheap",
+            Self::Strong => "strong",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoModelDecision {
+    pub model: String,
+    pub tier: AutoModelTier,
+    pub estimated_tokens: usize,
+}
+
+/// Estimate the token count of `chars` characters of prompt text.
+pub fn estimate_tokens(chars: usize) -> usize {
+    chars.div_ceil(APPROX_CHARS_PER_TOKEN)
+}
+
+/// Pick the cheap or strong model for a turn based on `[models.auto]`.
+///
+/// `context_chars` is the size of the non-system turns that will be sent:
+/// the kept conversation history plus the new message. Callers leave out the
+/// system prompt and tool definitions, so this is not the full request size.
+/// `tools_involved` marks turns where the conversation already used tools. Returns `None` when automatic
+/// selection is disabled or the selected tier has no model configured, in
+/// which case the caller keeps its current model.
+pub fn select_auto_model(
+    config: &AutoModelConfig,
+    context_chars: usize,
+    tools_involved: bool,
+) -> Option<AutoModelDecision> {
+    if !config.enabled {
+        return None;
+    }
+
+    let estimated_tokens = estimate_tokens(context_chars);
+    let tier = if tools_involved || estimated_tokens >= config.threshold_tokens {
+        AutoModelTier::Strong
+    } else {
+        AutoModelTier::Cheap
+    };
+
+    let model = match tier {
+        AutoModelTier::Cheap => config.cheap.as_deref(),
+        AutoModelTier::Strong => config.strong.as_deref(),
+    }
+    .map(str::trim)
+    .filter(|model| !model.is_empty())?;
+
+    Some(AutoModelDecision {
+        model: model.to_string(),
+        tier,
+        estimated_tokens,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,4 +282,53 @@ mod tests {
         assert_eq!(decision.hint, "code");
         assert_eq!(decision.priority, 10);
     }
+
+    fn auto_config() -> AutoModelConfig {
+        AutoModelConfig {
+            enabled: true,
+            cheap: Some("cheap-model".into()),
+            strong: Some("strong-model".into()),
+            threshold_tokens: 100,
+        }
+    }
+
+    #[test]
+    fn auto_model_disabled_returns_none() {
+        let config = AutoModelConfig {
+            enabled: false,
+            ..auto_config()
+        };
+        assert_eq!(select_auto_model(&config, 10, false), None);
+    }
+
+    #[test]
+    fn auto_model_short_tool_free_turn_uses_cheap() {
+        let decision = select_auto_model(&auto_config(), 40, false).unwrap();
+        assert_eq!(decision.model, "cheap-model");
+        assert_eq!(decision.tier, AutoModelTier::Cheap);
+        assert_eq!(decision.estimated_tokens, 10);
+    }
+
+    #[test]
+    fn auto_model_long_context_uses_strong() {
+        let decision = select_auto_model(&auto_config(), 400, false).unwrap();
+        assert_eq!(decision.model, "strong-model");
+        assert_eq!(decision.tier, AutoModelTier::Strong);
+    }
+
+    #[test]
+    fn auto_model_tool_activity_uses_strong() {
+        let decision = select_auto_model(&auto_config(), 40, true).unwrap();
+        assert_eq!(decision.tier, AutoModelTier::Strong);
+    }
+
+    #[test]
+    fn auto_model_unset_tier_keeps_current_model() {
+        let config = AutoModelConfig {
+            cheap: None,
+            ..auto_config()
+        };
+        assert_eq!(select_auto_model(&config, 40, false), None);
+        assert!(select_auto_model(&config, 400, false).is_some());
+    }
 }
@@ -8,6 +8,7 @@ use crate::providers::{
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::tools::error::{ToolError, ToolErrorKind};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
@@ -271,6 +272,30 @@ async fn build_context(mem: &dyn Memory, user_msg: &str, min_relevance_score: f6
     context
 }
 
+static MATH_MESSAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\d\s*[+*/^×÷]\s*[\d(]|\d\s*%|\d\s+-\s+\d|\d\s*[a-z°$€£]+\s+(?:to|in|into)\s+[a-z°$€£]|\b(?:convert|calculate|compute|how\s+many|how\s+much|percent|sqrt|average)\b",
+    )
+    .unwrap()
+});
+
+/// Nudge for messages that need arithmetic or conversions, so the model
+/// reaches for the `calc` tool instead of estimating numbers. Empty unless
+/// `calc` is among `tool_names`, the tools the model can call this turn.
+pub(crate) fn calc_hint<'a>(
+    user_msg: &str,
+    mut tool_names: impl Iterator<Item = &'a str>,
+) -> &'static str {
+    if user_msg.chars().any(|c| c.is_ascii_digit())
+        && MATH_MESSAGE_REGEX.is_match(user_msg)
+        && tool_names.any(|name| name == "calc")
+    {
+        "[Tool hint] This message involves math or unit conversion: compute every number with the `calc` tool instead of estimating.\n"
+    } else {
+        ""
+    }
+}
+
 /// Build hardware datasheet context from RAG when peripherals are enabled.
 /// Includes pin-alias lookup (e.g. "red_led" → 13) when query matches, plus retrieved chunks.
 fn build_hardware_context(
@@ -1858,6 +1883,154 @@ pub(crate) fn is_tool_loop_cancelled(err: &anyhow::Error) -> bool {
     err.chain().any(|source| source.is::<ToolLoopCancelled>())
 }
 
+/// Openings of prompt-guided tool-call markup. Streamed text is withheld from
+/// the first marker on so raw tool calls never reach the terminal.
+const STREAM_TOOL_MARKERS: [&str; 7] = [
+    "<tool_call",
+    "<toolcall",
+    "<tool-call",
+    "<invoke",
+    "<minimax:",
+    "<FunctionCall",
+    "```tool",
+];
+
+/// Accumulates streamed response text and decides what may be shown.
+#[derive(Debug, Default)]
+struct StreamRelay {
+    text: String,
+    /// Byte length of the prefix of `text` already forwarded.
+    forwarded: usize,
+    /// Set once tool-call markup starts; nothing more is forwarded.
+    withheld: bool,
+}
+
+impl StreamRelay {
+    /// Append a delta and return the text that can be forwarded now. A tail
+    /// that could be the start of a tool-call marker is held back until the
+    /// next delta decides it.
+    fn push(&mut self, delta: &str) -> String {
+        self.text.push_str(delta);
+        if self.withheld {
+            return String::new();
+        }
+        let pending = &self.text[self.forwarded..];
+        let end = if let Some(pos) = STREAM_TOOL_MARKERS
+            .iter()
+            .filter_map(|marker| pending.find(marker))
+            .min()
+        {
+            self.withheld = true;
+            pos
+        } else {
+            (0..pending.len())
+                .filter(|&i| pending.is_char_boundary(i))
+                .find(|&i| {
+                    STREAM_TOOL_MARKERS
+                        .iter()
+                        .any(|marker| marker.starts_with(&pending[i..]))
+                })
+                .unwrap_or(pending.len())
+        };
+        let ready = pending[..end].to_string();
+        self.forwarded += end;
+        ready
+    }
+
+    /// Text not yet forwarded, once the response is known to hold no tool calls.
+    fn remainder(&self) -> &str {
+        &self.text[self.forwarded..]
+    }
+}
+
+/// Response text streamed to the terminal while it was generated.
+struct StreamedResponse {
+    response: crate::providers::ChatResponse,
+    /// Text not shown because it might have been tool-call markup.
+    withheld: String,
+    /// Whether any text reached `on_delta`.
+    shown: bool,
+}
+
+/// Stream a response without native tools, forwarding text to `on_delta` as
+/// it arrives. Returns `None` when the provider produced nothing, e.g. because
+/// it cannot stream with history, so the caller can fall back to `chat`.
+/// Dropping the stream on cancellation aborts the provider request.
+async fn stream_response_text(
+    provider: &dyn Provider,
+    messages: &[ChatMessage],
+    model: &str,
+    temperature: f64,
+    on_delta: &tokio::sync::mpsc::Sender<String>,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<Option<StreamedResponse>> {
+    use futures_util::StreamExt;
+
+    let mut stream = provider.stream_chat_with_history(
+        messages,
+        model,
+        temperature,
+        crate::providers::traits::StreamOptions::new(true),
+    );
+    let mut relay = StreamRelay::default();
+    let mut shown = false;
+    loop {
+        let next = if let Some(token) = cancellation_token {
+            tokio::select! {
+                () = token.cancelled() => return Err(ToolLoopCancelled.into()),
+                next = stream.next() => next,
+            }
+        } else {
+            stream.next().await
+        };
+        let chunk = match next {
+            None => break,
+            Some(Ok(chunk)) => chunk,
+            Some(Err(e)) if relay.text.is_empty() => {
+                tracing::debug!("Streaming unavailable, falling back to chat: {e}");
+                return Ok(None);
+            }
+            Some(Err(e)) => return Err(e.into()),
+        };
+        if chunk.is_final {
+            // Providers without history streaming send a final chunk carrying an error.
+            if !chunk.delta.is_empty() {
+                if relay.text.is_empty() {
+                    tracing::debug!(
+                        "Streaming unavailable, falling back to chat: {}",
+                        chunk.delta
+                    );
+                    return Ok(None);
+                }
+                anyhow::bail!("{}", chunk.delta);
+            }
+            break;
+        }
+        let ready = relay.push(&chunk.delta);
+        if ready.is_empty() {
+            continue;
+        }
+        if !shown {
+            let _ = on_delta.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
+            shown = true;
+        }
+        let _ = on_delta.send(ready).await;
+    }
+    if relay.text.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(StreamedResponse {
+        withheld: relay.remainder().to_string(),
+        response: crate::providers::ChatResponse {
+            text: Some(relay.text),
+            tool_calls: Vec::new(),
+            usage: None,
+            reasoning_content: None,
+        },
+        shown,
+    }))
+}
+
 /// Execute a single turn of the agent loop: send messages, parse tool calls,
 /// execute tools, and loop until the LLM produces a final text response.
 /// When `silent` is true, suppresses stdout (for channel use).
@@ -1908,21 +2081,21 @@ async fn execute_one_tool(
     let start = Instant::now();
 
     let Some(tool) = find_tool(tools_registry, call_name) else {
-        let reason = format!("Unknown tool: {call_name}");
         let duration = start.elapsed();
         observer.record_event(&ObserverEvent::ToolCall {
             tool: call_name.to_string(),
             duration,
             success: false,
         });
-        return Ok(ToolExecutionOutcome {
-            output: reason.clone(),
-            success: false,
-            error_reason: Some(scrub_credentials(&reason)),
-            duration,
-        });
+        let error = ToolError::new(
+            ToolErrorKind::NotFound,
+            format!("Unknown tool: {call_name}"),
+        )
+        .with_hint("Call one of the tools listed in the system prompt.");
+        return Ok(tool_error_outcome(observer, call_name, error, duration));
     };
 
+    crate::skills::usage::record_tool_call(call_name, &call_arguments);
     let tool_future = tool.execute(call_arguments);
     let tool_result = if let Some(token) = cancellation_token {
         tokio::select! {
@@ -1950,12 +2123,8 @@ async fn execute_one_tool(
                 })
             } else {
                 let reason = r.error.unwrap_or(r.output);
-                Ok(ToolExecutionOutcome {
-                    output: format!("Error: {reason}"),
-                    success: false,
-                    error_reason: Some(scrub_credentials(&reason)),
-                    duration,
-                })
+                let error = ToolError::parse(&reason);
+                Ok(tool_error_outcome(observer, call_name, error, duration))
             }
         }
         Err(e) => {
@@ -1965,17 +2134,28 @@ async fn execute_one_tool(
                 duration,
                 success: false,
             });
-            let reason = format!("Error executing {call_name}: {e}");
-            Ok(ToolExecutionOutcome {
-                output: reason.clone(),
-                success: false,
-                error_reason: Some(scrub_credentials(&reason)),
-                duration,
-            })
+            let error = ToolError::classify(&format!("Error executing {call_name}: {e}"));
+            Ok(tool_error_outcome(observer, call_name, error, duration))
         }
     }
 }
 
+/// Outcome for a failed call: the model gets the [`ToolError`] envelope.
+fn tool_error_outcome(
+    observer: &dyn Observer,
+    tool: &str,
+    error: ToolError,
+    duration: Duration,
+) -> ToolExecutionOutcome {
+    error.record(observer, tool);
+    ToolExecutionOutcome {
+        output: scrub_credentials(&error.to_json()),
+        success: false,
+        error_reason: Some(scrub_credentials(&error.to_string())),
+        duration,
+    }
+}
+
 struct ToolExecutionOutcome {
     output: String,
     success: bool,
@@ -1992,7 +2172,10 @@ fn should_execute_tools_in_parallel(
     }
 
     if let Some(mgr) = approval {
-        if tool_calls.iter().any(|call| mgr.needs_approval(&call.name)) {
+        if tool_calls
+            .iter()
+            .any(|call| mgr.needs_approval_for_call(&call.name, &call.arguments))
+        {
             // Approval-gated calls must keep sequential handling so the caller can
             // enforce CLI prompt/deny policy consistently.
             return false;
@@ -2094,6 +2277,17 @@ pub(crate) async fn run_tool_call_loop(
         .map(|tool| tool.spec())
         .collect();
     let use_native_tools = provider.supports_native_tools() && !tool_specs.is_empty();
+    // The terminal shows tokens as they arrive. Native tool calls need the full
+    // structured response, so those turns relay the answer once it is complete.
+    let stream_tokens = channel_name == "cli"
+        && on_delta.is_some()
+        && !use_native_tools
+        && provider.supports_streaming();
+    // Provider outcomes feed the `providers` subsystem of `/healthz`.
+    let provider_component = format!(
+        "{}{provider_name}",
+        crate::health::uptime::PROVIDER_COMPONENT_PREFIX
+    );
     let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
 
@@ -2164,32 +2358,56 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &prepared_messages.messages,
-                tools: request_tools,
+        // Text already shown by token streaming, and any tail withheld as possible markup.
+        let mut streamed: Option<(bool, String)> = None;
+        let streamed_result = match on_delta.as_ref().filter(|_| stream_tokens) {
+            Some(tx) => match stream_response_text(
+                provider,
+                &prepared_messages.messages,
+                model,
+                temperature,
+                tx,
+                cancellation_token.as_ref(),
+            )
+            .await
+            {
+                Ok(Some(response)) => {
+                    streamed = Some((response.shown, response.withheld));
+                    Some(Ok(response.response))
+                }
+                Ok(None) => None,
+                Err(e) if is_tool_loop_cancelled(&e) => return Err(e),
+                Err(e) => Some(Err(e)),
             },
-            model,
-            temperature,
-        );
+            None => None,
+        };
 
-        let chat_result = if let Some(token) = cancellation_token.as_ref() {
-            tokio::select! {
-                () = token.cancelled() => return Err(ToolLoopCancelled.into()),
-                result = chat_future => result,
-            }
+        let chat_result = if let Some(result) = streamed_result {
+            result
         } else {
-            chat_future.await
+            let chat_future = provider.chat(
+                ChatRequest {
+                    messages: &prepared_messages.messages,
+                    tools: request_tools,
+                },
+                model,
+                temperature,
+            );
+            if let Some(token) = cancellation_token.as_ref() {
+                tokio::select! {
+                    () = token.cancelled() => return Err(ToolLoopCancelled.into()),
+                    result = chat_future => result,
+                }
+            } else {
+                chat_future.await
+            }
         };
+        let streamed_shown = streamed.as_ref().is_some_and(|(shown, _)| *shown);
 
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
                 Ok(resp) => {
-                    let (resp_input_tokens, resp_output_tokens) = resp
-                        .usage
-                        .as_ref()
-                        .map(|u| (u.input_tokens, u.output_tokens))
-                        .unwrap_or((None, None));
+                    let usage = resp.usage.clone().unwrap_or_default();
 
                     observer.record_event(&ObserverEvent::LlmResponse {
                         provider: provider_name.to_string(),
@@ -2197,9 +2415,12 @@ pub(crate) async fn run_tool_call_loop(
                         duration: llm_started_at.elapsed(),
                         success: true,
                         error_message: None,
-                        input_tokens: resp_input_tokens,
-                        output_tokens: resp_output_tokens,
+                        input_tokens: usage.input_tokens,
+                        output_tokens: usage.output_tokens,
+                        reasoning_tokens: usage.reasoning_tokens,
+                        cached_input_tokens: usage.cached_input_tokens,
                     });
+                    crate::health::mark_component_ok(&provider_component);
 
                     let response_text = resp.text_or_empty().to_string();
                     // First try native structured tool calls (OpenAI-format).
@@ -2248,8 +2469,10 @@ pub(crate) async fn run_tool_call_loop(
                         serde_json::json!({
                             "iteration": iteration + 1,
                             "duration_ms": llm_started_at.elapsed().as_millis(),
-                            "input_tokens": resp_input_tokens,
-                            "output_tokens": resp_output_tokens,
+                            "input_tokens": usage.input_tokens,
+                            "output_tokens": usage.output_tokens,
+                            "reasoning_tokens": usage.reasoning_tokens,
+                            "cached_input_tokens": usage.cached_input_tokens,
                             "raw_response": scrub_credentials(&response_text),
                             "native_tool_calls": resp.tool_calls.len(),
                             "parsed_tool_calls": calls.len(),
@@ -2297,7 +2520,10 @@ pub(crate) async fn run_tool_call_loop(
                         error_message: Some(safe_error.clone()),
                         input_tokens: None,
                         output_tokens: None,
+                        reasoning_tokens: None,
+                        cached_input_tokens: None,
                     });
+                    crate::health::mark_component_error(&provider_component, &safe_error);
                     runtime_trace::record_event(
                         "llm_response",
                         Some(channel_name),
@@ -2325,6 +2551,9 @@ pub(crate) async fn run_tool_call_loop(
         if let Some(ref tx) = on_delta {
             let llm_secs = llm_started_at.elapsed().as_secs();
             if !tool_calls.is_empty() {
+                if streamed_shown {
+                    let _ = tx.send("\n".to_string()).await;
+                }
                 let _ = tx
                     .send(format!(
                         "\u{1f4ac} Got {} tool call(s) ({llm_secs}s)\n",
@@ -2351,7 +2580,12 @@ pub(crate) async fn run_tool_call_loop(
             // No tool calls — this is the final response.
             // If a streaming sender is provided, relay the text in small chunks
             // so the channel can progressively update the draft message.
-            if let Some(ref tx) = on_delta {
+            if let (Some(tx), Some((true, withheld))) = (on_delta.as_ref(), streamed.as_ref()) {
+                // Already streamed; only text held back as possible markup is left.
+                if !withheld.is_empty() {
+                    let _ = tx.send(withheld.clone()).await;
+                }
+            } else if let Some(ref tx) = on_delta {
                 // Clear accumulated progress lines before streaming the final answer.
                 let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
                 // Split on whitespace boundaries, accumulating chunks of at least
@@ -2379,8 +2613,9 @@ pub(crate) async fn run_tool_call_loop(
             return Ok(display_text);
         }
 
-        // Print any text the LLM produced alongside tool calls (unless silent)
-        if !silent && !display_text.is_empty() {
+        // Print any text the LLM produced alongside tool calls (unless silent
+        // or already streamed)
+        if !silent && !streamed_shown && !display_text.is_empty() {
             print!("{display_text}");
             let _ = std::io::stdout().flush();
         }
@@ -2443,9 +2678,35 @@ pub(crate) async fn run_tool_call_loop(
                 }
             }
 
+            // Excluded tools are hidden from the model; refuse calls it makes anyway.
+            if excluded_tools.contains(&tool_name) {
+                let refused = format!("Tool '{tool_name}' is not available on this channel.");
+                runtime_trace::record_event(
+                    "tool_call_result",
+                    Some(channel_name),
+                    Some(provider_name),
+                    Some(model),
+                    Some(&turn_id),
+                    Some(false),
+                    Some(&refused),
+                    serde_json::json!({
+                        "iteration": iteration + 1,
+                        "tool": tool_name.clone(),
+                        "arguments": scrub_credentials(&tool_args.to_string()),
+                    }),
+                );
+                let error = ToolError::new(ToolErrorKind::Permission, refused);
+                ordered_results[idx] = Some((
+                    tool_name.clone(),
+                    call.tool_call_id.clone(),
+                    tool_error_outcome(observer, &tool_name, error, Duration::ZERO),
+                ));
+                continue;
+            }
+
             // ── Approval hook ────────────────────────────────
             if let Some(mgr) = approval {
-                if mgr.needs_approval(&tool_name) {
+                if mgr.needs_approval_for_call(&tool_name, &tool_args) {
                     let request = ApprovalRequest {
                         tool_name: tool_name.clone(),
                         arguments: tool_args.clone(),
@@ -2476,15 +2737,12 @@ pub(crate) async fn run_tool_call_loop(
                                 "arguments": scrub_credentials(&tool_args.to_string()),
                             }),
                         );
+                        let error = ToolError::new(ToolErrorKind::Permission, denied)
+                            .with_hint("The user declined this call; do not retry it.");
                         ordered_results[idx] = Some((
                             tool_name.clone(),
                             call.tool_call_id.clone(),
-                            ToolExecutionOutcome {
-                                output: denied.clone(),
-                                success: false,
-                                error_reason: Some(denied),
-                                duration: Duration::ZERO,
-                            },
+                            tool_error_outcome(observer, &tool_name, error, Duration::ZERO),
                         ));
                         continue;
                     }
@@ -2789,6 +3047,7 @@ pub async fn run(
     if !peripheral_tools.is_empty() {
         tracing::info!(count = peripheral_tools.len(), "Peripheral tools added");
         tools_registry.extend(peripheral_tools);
+        tools::remove_disabled(&mut tools_registry, &config.tools);
     }
 
     // ── Resolve provider ─────────────────────────────────────────
@@ -2808,6 +3067,9 @@ pub async fn run(
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning_effort: config.runtime.reasoning_effort.clone(),
+        thinking_budget_tokens: config.runtime.thinking_budget_tokens,
+        debug_capture: providers::capture::DebugCapture::from_config(&config),
     };
 
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
@@ -2846,8 +3108,12 @@ pub async fn run(
         .collect();
 
     // ── Build system prompt from workspace MD files (OpenClaw framework) ──
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+    let skills = crate::skills::load_prompt_skills(&config.workspace_dir, &config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
+        (
+            "calc",
+            "Evaluate arithmetic and convert units or currencies deterministically. Use when: any answer depends on a calculation, percentage, or conversion. Never do math in your head.",
+        ),
         (
             "shell",
             "Execute terminal commands. Use when: running local checks, build/test commands, diagnostics. Don't use when: a safer dedicated tool exists, or command is destructive without approval.",
@@ -2891,20 +3157,118 @@ pub async fn run(
         "Force-run a cron job immediately and record a run history entry.",
     ));
     tool_descs.push(("cron_runs", "Show recent run history for a cron job."));
-    tool_descs.push((
-        "screenshot",
-        "Capture a screenshot of the current screen. Returns file path and base64-encoded PNG. Use when: visual verification, UI inspection, debugging displays.",
-    ));
+    if config.screenshot.enabled {
+        tool_descs.push((
+            "screenshot",
+            "Capture a screenshot of the screen or a named window. Returns file path and base64-encoded PNG, or the on-screen text with ocr=true. Use when: visual verification, UI inspection, reading text from apps.",
+        ));
+    }
     tool_descs.push((
         "image_info",
         "Read image file metadata (format, dimensions, size) and optionally base64-encode it. Use when: inspecting images, preparing visual data for analysis.",
     ));
+    tool_descs.push((
+        "wiki_lookup",
+        "Wikipedia summary plus Wikidata facts with source URLs, in any language edition. Use when: answering encyclopedic questions (people, places, events, definitions). Prefer over web search for these.",
+    ));
+    if config.runtime.kind == "native" {
+        tool_descs.push((
+            "system_info",
+            "Report CPU, memory, disk, uptime, battery, and top processes. Use when: the machine is slow, hot, noisy, or low on space.",
+        ));
+        tool_descs.push((
+            "process",
+            "List processes or send a signal to one by PID. Use when: finding or stopping a runaway process. Signalling needs approved=true in supervised mode.",
+        ));
+    }
     if config.browser.enabled {
         tool_descs.push((
             "browser_open",
             "Open approved HTTPS URLs in system browser (allowlist-only, no scraping)",
         ));
     }
+    if config.network_diag.enabled {
+        tool_descs.push((
+            "network_diag",
+            "Ping, DNS lookup, HTTP HEAD timing, or traceroute to a host. Use when: checking whether the internet or just one site is down.",
+        ));
+    }
+    if config.weather.enabled {
+        tool_descs.push((
+            "weather",
+            "Current conditions and daily forecast for a place name or coordinates. Use when: the user asks about weather. Don't use when: web search would be needed for anything else.",
+        ));
+    }
+    if config.translate.enabled {
+        tool_descs.push((
+            "translate",
+            "Translate text into another language, preserving formatting. Use when: the user asks for a translation or a message must go out in another language.",
+        ));
+    }
+    if config.finance.enabled {
+        tool_descs.push((
+            "finance",
+            "Live stock, ETF, index, or crypto quotes and closing-price history. Use when: the user asks about prices, portfolio moves, or market performance. Don't use when: the question is about news or fundamentals.",
+        ));
+    }
+    if config.news.enabled {
+        tool_descs.push((
+            "news",
+            "Top headlines or stories on a topic, with sources and links; can restrict to named sources. Use when: the user asks what is happening, for news on a topic, or for a briefing.",
+        ));
+    }
+    if config.goals.enabled {
+        tool_descs.push((
+            "goals",
+            "Create, list, and show the user's goals with milestones and due dates. Use when: the user sets a goal or asks how their goals are going.",
+        ));
+        tool_descs.push((
+            "goal_update",
+            "Record progress on a goal: a note, a finished milestone, or a new status. Use when: the user mentions progress on an active goal, even in passing.",
+        ));
+    }
+    if !config.device_actions.is_empty() {
+        tool_descs.push((
+            "device_actions",
+            "Run a configured device preset (Wake-on-LAN or a fixed SSH command) by name. Use when: the user asks to wake a machine or run a routine chore like restarting a service. Presets marked [needs confirmation] need approved=true after the user confirms.",
+        ));
+    }
+    if config.reports.enabled {
+        tool_descs.push((
+            "report_render",
+            "Fill an HTML template from the workspace templates folder with structured data and save it as HTML or PDF. Use when: the user wants a formatted report such as a weekly summary or an invoice. Send the file with the returned [DOCUMENT:<path>] marker.",
+        ));
+    }
+    if config.send_later.enabled {
+        tool_descs.push((
+            "send_later",
+            "Schedule a message to someone for later, or list and cancel scheduled messages. Use when: the user asks to text or message someone at a future time (\"at 9am tomorrow text Jamie happy birthday\"). In supervised mode show the resolved time and resend with approved=true only after the user confirms.",
+        ));
+    }
+    if config.send_email.enabled {
+        tool_descs.push((
+            "send_email",
+            "Send an email from the user's configured address, with a body or a workspace template and optional workspace attachments. Use when: the user asks to email something to someone. In supervised mode show the returned preview and resend with approved=true only after the user confirms.",
+        ));
+    }
+    if config.bluebubbles.enabled {
+        tool_descs.push((
+            "bluebubbles_group",
+            "Create, rename, or leave iMessage group chats and add or remove participants through BlueBubbles. Use when: the user asks to start a group with people or change one. Look names up with contacts_lookup first. In supervised mode show the described change and resend with approved=true only after the user confirms.",
+        ));
+    }
+    if config.contacts.enabled {
+        tool_descs.push((
+            "contacts_lookup",
+            "Look up people in the user's address book by name, nickname, organisation, email, or phone number. Use when: the user asks for someone's number, email, or birthday, or refers to a person you need to reach.",
+        ));
+    }
+    if config.workspace_search.enabled {
+        tool_descs.push((
+            "workspace_search",
+            "Full-text search over the user's own files in the workspace: notes, exported conversations, knowledge-base documents. Use when: the user asks about something they wrote down or saved. Cheaper than reading files one by one; open a hit with file_read.",
+        ));
+    }
     if config.composio.enabled {
         tool_descs.push((
             "composio",
@@ -2915,6 +3279,26 @@ pub async fn run(
         "schedule",
         "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
     ));
+    tool_descs.push((
+        "timer_start",
+        "Start a countdown timer that messages this conversation when it ends, or a stopwatch. Use when: the user says 'set a 20 minute timer' or 'time this'.",
+    ));
+    tool_descs.push((
+        "lists",
+        "Named checklists such as shopping or packing lists: add, check off, remove, show, export to markdown. Use when: the user adds to or asks about a list. Use shared=true for the group chat's list.",
+    ));
+    tool_descs.push((
+        "ics_create",
+        "Create a calendar invite (.ics file) that works in any calendar app. Use when: the user schedules a meeting or event and wants an invite, or asks to add something to their calendar without a calendar integration. Send it with the returned [DOCUMENT:<path>] marker or attach it with send_email.",
+    ));
+    tool_descs.push((
+        "preferences",
+        "Store the user's lasting preferences (tone, verbosity, units, language, formatting). Use when: the user says 'always use metric' or 'keep answers short'. Stored preferences are already applied; don't re-ask.",
+    ));
+    tool_descs.push((
+        "timer_status",
+        "List running timers and stopwatches, or cancel/stop one by id or label.",
+    ));
     tool_descs.push((
         "model_routing_config",
         "Configure default model, scenario routing, and delegate agents. Use for natural-language requests like: 'set conversation to kimi and coding to gpt-5.3-codex'.",
@@ -2955,21 +3339,15 @@ pub async fn run(
             "Query connected hardware for reported GPIO pins and LED pin. Use when: user asks what pins are available.",
         ));
     }
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
+    let disabled = &config.tools.disabled;
+    tool_descs.retain(|(name, _)| !disabled.iter().any(|d| d == name));
     let native_tools = provider.supports_native_tools();
-    let mut system_prompt = crate::channels::build_system_prompt_with_mode(
-        &config.workspace_dir,
+    let mut system_prompt = crate::channels::build_configured_system_prompt(
+        &config,
         model_name,
         &tool_descs,
         &skills,
-        Some(&config.identity),
-        bootstrap_max_chars,
         native_tools,
-        config.skills.prompt_injection_mode,
     );
 
     // Append structured tool-use instructions with schemas (only for non-native providers)
@@ -2977,9 +3355,30 @@ pub async fn run(
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
 
+    let (prefs_channel, prefs_sender) = crate::tools::preferences::LOCAL_IDENTITY;
+    if let Ok(prefs) =
+        crate::channels::preferences::get_all(&config.workspace_dir, prefs_channel, prefs_sender)
+    {
+        if !prefs.is_empty() {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(&crate::channels::preferences::prompt_section(&prefs));
+        }
+    }
+    if config.goals.enabled {
+        match crate::goals::active_prompt_section(&config.workspace_dir, config.goals.prompt_limit)
+        {
+            Ok(section) if !section.is_empty() => {
+                system_prompt.push_str("\n\n");
+                system_prompt.push_str(&section);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to load goals for the prompt: {e}"),
+        }
+    }
+
     // ── Approval manager (supervised mode) ───────────────────────
     let approval_manager = if interactive {
-        Some(ApprovalManager::from_config(&config.autonomy))
+        Some(ApprovalManager::from_config(&config.autonomy).with_grant_store(&config.workspace_dir))
     } else {
         None
     };
@@ -3007,7 +3406,8 @@ pub async fn run(
             .as_ref()
             .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
             .unwrap_or_default();
-        let context = format!("{mem_context}{hw_context}");
+        let math_hint = calc_hint(&msg, tools_registry.iter().map(|tool| tool.name()));
+        let context = format!("{mem_context}{hw_context}{math_hint}");
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
         let enriched = if context.is_empty() {
             format!("[{now}] {msg}")
@@ -3020,6 +3420,7 @@ pub async fn run(
             ChatMessage::user(&enriched),
         ];
 
+        crate::skills::usage::record_prompt_inclusion();
         let response = run_tool_call_loop(
             provider.as_ref(),
             &mut history,
@@ -3045,7 +3446,6 @@ pub async fn run(
     } else {
         println!("🦀 ZeroClaw Interactive Mode");
         println!("Type /help for commands.\n");
-        let cli = crate::channels::CliChannel::new();
 
         // Persistent conversation history across turns
         let mut history = vec![ChatMessage::system(&system_prompt)];
@@ -3054,8 +3454,18 @@ pub async fn run(
             print!("> ");
             let _ = std::io::stdout().flush();
 
+            // Once a turn has listened for Ctrl-C the default handler is gone,
+            // so exit explicitly while waiting at the prompt.
+            let exit_on_interrupt = tokio::spawn(async {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    println!();
+                    std::process::exit(130);
+                }
+            });
             let mut input = String::new();
-            match std::io::stdin().read_line(&mut input) {
+            let read = std::io::stdin().read_line(&mut input);
+            exit_on_interrupt.abort();
+            match read {
                 Ok(0) => break,
                 Ok(_) => {}
                 Err(e) => {
@@ -3132,7 +3542,8 @@ pub async fn run(
                 .as_ref()
                 .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
                 .unwrap_or_default();
-            let context = format!("{mem_context}{hw_context}");
+            let math_hint = calc_hint(&user_input, tools_registry.iter().map(|tool| tool.name()));
+            let context = format!("{mem_context}{hw_context}{math_hint}");
             let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
             let enriched = if context.is_empty() {
                 format!("[{now}] {user_input}")
@@ -3140,9 +3551,25 @@ pub async fn run(
                 format!("{context}[{now}] {user_input}")
             };
 
+            let history_len_before_turn = history.len();
             history.push(ChatMessage::user(&enriched));
 
-            let response = match run_tool_call_loop(
+            // Progress and answer text are rendered as they arrive; Ctrl-C
+            // cancels the turn, including an in-flight provider stream.
+            let (delta_tx, delta_rx) = tokio::sync::mpsc::channel::<String>(64);
+            let renderer = crate::channels::cli::spawn_terminal_renderer(delta_rx);
+            let cancel = CancellationToken::new();
+            let cancel_on_interrupt = {
+                let cancel = cancel.clone();
+                tokio::spawn(async move {
+                    if tokio::signal::ctrl_c().await.is_ok() {
+                        cancel.cancel();
+                    }
+                })
+            };
+
+            crate::skills::usage::record_prompt_inclusion();
+            let result = run_tool_call_loop(
                 provider.as_ref(),
                 &mut history,
                 &tools_registry,
@@ -3155,28 +3582,29 @@ pub async fn run(
                 channel_name,
                 &config.multimodal,
                 config.agent.max_tool_iterations,
-                None,
-                None,
+                Some(cancel),
+                Some(delta_tx),
                 None,
                 &[],
             )
-            .await
-            {
+            .await;
+            cancel_on_interrupt.abort();
+            let _ = renderer.await;
+
+            let response = match result {
                 Ok(resp) => resp,
+                Err(e) if is_tool_loop_cancelled(&e) => {
+                    history.truncate(history_len_before_turn);
+                    println!("\n\u{23f9} Cancelled\n");
+                    continue;
+                }
                 Err(e) => {
                     eprintln!("\nError: {e}\n");
                     continue;
                 }
             };
-            final_output = response.clone();
-            if let Err(e) = crate::channels::Channel::send(
-                &cli,
-                &crate::channels::traits::SendMessage::new(format!("\n{response}\n"), "user"),
-            )
-            .await
-            {
-                eprintln!("\nError sending CLI response: {e}\n");
-            }
+            final_output = response;
+            println!("\n");
             observer.record_event(&ObserverEvent::TurnComplete);
 
             // Auto-compaction before hard trimming to preserve long-context signal.
@@ -3206,13 +3634,25 @@ pub async fn run(
         tokens_used: None,
         cost_usd: None,
     });
+    crate::skills::usage::flush().await;
 
     Ok(final_output)
 }
 
-/// Process a single message through the full agent (with tools, peripherals, memory).
-/// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
-pub async fn process_message(config: Config, message: &str) -> Result<String> {
+/// Tools, provider, and system prompt for [`process_message`].
+struct MessageAgent {
+    observer: Arc<dyn Observer>,
+    mem: Arc<dyn Memory>,
+    tools_registry: Vec<Box<dyn Tool>>,
+    provider: Box<dyn Provider>,
+    provider_name: String,
+    model_name: String,
+    hardware_rag: Option<crate::rag::HardwareRag>,
+    board_names: Vec<String>,
+    system_prompt: String,
+}
+
+async fn build_message_agent(config: &Config) -> Result<MessageAgent> {
     let observer: Arc<dyn Observer> =
         Arc::from(observability::create_observer(&config.observability));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
@@ -3249,11 +3689,12 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         &config.workspace_dir,
         &config.agents,
         config.api_key.as_deref(),
-        &config,
+        config,
     );
     let peripheral_tools: Vec<Box<dyn Tool>> =
         crate::peripherals::create_peripheral_tools(&config.peripherals).await?;
     tools_registry.extend(peripheral_tools);
+    tools::remove_disabled(&mut tools_registry, &config.tools);
 
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
     let model_name = config
@@ -3266,6 +3707,9 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning_effort: config.runtime.reasoning_effort.clone(),
+        thinking_budget_tokens: config.runtime.thinking_budget_tokens,
+        debug_capture: providers::capture::DebugCapture::from_config(config),
     };
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
         provider_name,
@@ -3292,8 +3736,12 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .map(|b| b.board.clone())
         .collect();
 
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+    let skills = crate::skills::load_prompt_skills(&config.workspace_dir, config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
+        (
+            "calc",
+            "Do math and unit/currency conversions (never compute mentally).",
+        ),
         ("shell", "Execute terminal commands."),
         ("file_read", "Read file contents."),
         ("file_write", "Write file contents."),
@@ -3304,12 +3752,65 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
             "model_routing_config",
             "Configure default model, scenario routing, and delegate agents.",
         ),
-        ("screenshot", "Capture a screenshot."),
         ("image_info", "Read image metadata."),
+        ("wiki_lookup", "Wikipedia/Wikidata facts with citations."),
     ];
+    if config.screenshot.enabled {
+        tool_descs.push(("screenshot", "Capture a screenshot."));
+    }
+    if config.runtime.kind == "native" {
+        tool_descs.push((
+            "system_info",
+            "Report host CPU, memory, disk, and processes.",
+        ));
+        tool_descs.push(("process", "List or signal processes."));
+    }
     if config.browser.enabled {
         tool_descs.push(("browser_open", "Open approved URLs in browser."));
     }
+    if config.network_diag.enabled {
+        tool_descs.push(("network_diag", "Ping, DNS, HTTP, or traceroute a host."));
+    }
+    if config.weather.enabled {
+        tool_descs.push(("weather", "Weather and forecast for a place."));
+    }
+    if config.translate.enabled {
+        tool_descs.push(("translate", "Translate text between languages."));
+    }
+    if config.finance.enabled {
+        tool_descs.push(("finance", "Stock and crypto quotes and price history."));
+    }
+    if config.news.enabled {
+        tool_descs.push(("news", "Current news headlines with links."));
+    }
+    if config.goals.enabled {
+        tool_descs.push(("goals", "Create and list the user's goals."));
+        tool_descs.push(("goal_update", "Record progress on a goal."));
+    }
+    if !config.device_actions.is_empty() {
+        tool_descs.push(("device_actions", "Run a configured device preset by name."));
+    }
+    if config.reports.enabled {
+        tool_descs.push(("report_render", "Render a report template to HTML or PDF."));
+    }
+    if config.send_later.enabled {
+        tool_descs.push(("send_later", "Schedule a message to someone for later."));
+    }
+    if config.send_email.enabled {
+        tool_descs.push(("send_email", "Send an email, after the user confirms it."));
+    }
+    if config.bluebubbles.enabled {
+        tool_descs.push((
+            "bluebubbles_group",
+            "Manage an iMessage group chat, after the user confirms it.",
+        ));
+    }
+    if config.contacts.enabled {
+        tool_descs.push(("contacts_lookup", "Look up a person in the address book."));
+    }
+    if config.workspace_search.enabled {
+        tool_descs.push(("workspace_search", "Search the user's workspace files."));
+    }
     if config.composio.enabled {
         tool_descs.push(("composio", "Execute actions on 1000+ apps via Composio."));
     }
@@ -3340,33 +3841,61 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
             "Query connected hardware for reported GPIO pins and LED pin. Use when user asks what pins are available.",
         ));
     }
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
+    let disabled = &config.tools.disabled;
+    tool_descs.retain(|(name, _)| !disabled.iter().any(|d| d == name));
     let native_tools = provider.supports_native_tools();
-    let mut system_prompt = crate::channels::build_system_prompt_with_mode(
-        &config.workspace_dir,
+    let mut system_prompt = crate::channels::build_configured_system_prompt(
+        config,
         &model_name,
         &tool_descs,
         &skills,
-        Some(&config.identity),
-        bootstrap_max_chars,
         native_tools,
-        config.skills.prompt_injection_mode,
     );
     if !native_tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
 
+    Ok(MessageAgent {
+        observer,
+        mem,
+        tools_registry,
+        provider,
+        provider_name: provider_name.to_string(),
+        model_name,
+        hardware_rag,
+        board_names,
+        system_prompt,
+    })
+}
+
+/// System prompt [`process_message`] sends with the current config (`zeroclaw prompt show`).
+pub async fn system_prompt_preview(config: &Config) -> Result<String> {
+    Ok(build_message_agent(config).await?.system_prompt)
+}
+
+/// Process a single message through the full agent (with tools, peripherals, memory).
+/// Used by channels (Telegram, Discord, etc.) to enable hardware and tool use.
+pub async fn process_message(config: Config, message: &str) -> Result<String> {
+    let MessageAgent {
+        observer,
+        mem,
+        tools_registry,
+        provider,
+        provider_name,
+        model_name,
+        hardware_rag,
+        board_names,
+        system_prompt,
+    } = build_message_agent(&config).await?;
+
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
     let hw_context = hardware_rag
         .as_ref()
         .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
         .unwrap_or_default();
-    let context = format!("{mem_context}{hw_context}");
+    let math_hint = calc_hint(message, tools_registry.iter().map(|tool| tool.name()));
+    let context = format!("{mem_context}{hw_context}{math_hint}");
     let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
     let enriched = if context.is_empty() {
         format!("[{now}] {message}")
@@ -3379,19 +3908,22 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         ChatMessage::user(&enriched),
     ];
 
-    agent_turn(
+    crate::skills::usage::record_prompt_inclusion();
+    let reply = agent_turn(
         provider.as_ref(),
         &mut history,
         &tools_registry,
         observer.as_ref(),
-        provider_name,
+        &provider_name,
         &model_name,
         config.default_temperature,
         true,
         &config.multimodal,
         config.agent.max_tool_iterations,
     )
-    .await
+    .await;
+    crate::skills::usage::flush().await;
+    reply
 }
 
 #[cfg(test)]
@@ -3673,6 +4205,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn execute_one_tool_reports_failures_as_error_envelope() {
+        let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
+        let outcome = execute_one_tool(
+            "no_such_tool",
+            serde_json::json!({}),
+            &tools_registry,
+            &NoopObserver,
+            None,
+        )
+        .await
+        .expect("unknown tool is reported, not raised");
+
+        assert!(!outcome.success);
+        let envelope: serde_json::Value = serde_json::from_str(&outcome.output).unwrap();
+        assert_eq!(envelope["error"]["category"], "not_found");
+        assert_eq!(envelope["error"]["retryable"], false);
+        assert!(envelope["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown tool: no_such_tool"));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_returns_structured_error_for_non_vision_provider() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -4003,6 +4558,176 @@ mod tests {
         assert!(tool_results.content.contains("Skipped duplicate tool call"));
     }
 
+    struct StreamingProvider {
+        rounds: Mutex<VecDeque<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl Provider for StreamingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("streaming provider only streams");
+        }
+
+        fn supports_streaming(&self) -> bool {
+            true
+        }
+
+        fn stream_chat_with_history(
+            &self,
+            _messages: &[ChatMessage],
+            _model: &str,
+            _temperature: f64,
+            _options: crate::providers::traits::StreamOptions,
+        ) -> futures_util::stream::BoxStream<
+            'static,
+            crate::providers::traits::StreamResult<crate::providers::traits::StreamChunk>,
+        > {
+            use crate::providers::traits::StreamChunk;
+            use futures_util::StreamExt;
+            let deltas = self.rounds.lock().unwrap().pop_front().unwrap_or_default();
+            let chunks: Vec<_> = deltas
+                .into_iter()
+                .map(|delta| Ok(StreamChunk::delta(delta)))
+                .chain(std::iter::once(Ok(StreamChunk::final_chunk())))
+                .collect();
+            futures_util::stream::iter(chunks).boxed()
+        }
+    }
+
+    #[test]
+    fn stream_relay_withholds_tool_call_markup() {
+        let mut relay = StreamRelay::default();
+        assert_eq!(relay.push("Let me check. <"), "Let me check. ");
+        assert_eq!(relay.push("b>bold</b> <too"), "<b>bold</b> ");
+        assert_eq!(relay.push("l_call>{\"name\":\"shell\"}"), "");
+        assert_eq!(relay.push(" more"), "");
+        assert_eq!(relay.remainder(), "<tool_call>{\"name\":\"shell\"} more");
+
+        let mut relay = StreamRelay::default();
+        assert_eq!(relay.push("a <"), "a ");
+        assert_eq!(relay.remainder(), "<");
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_streams_text_to_cli_without_tool_markup() {
+        let provider = StreamingProvider {
+            rounds: Mutex::new(VecDeque::from([
+                vec![
+                    "Counting",
+                    " now.\n<tool_",
+                    "call>\n{\"name\":\"count_tool\",\"arguments\":{\"value\":\"A\"}}\n</tool_call>",
+                ],
+                vec!["do", "ne"],
+            ])),
+        };
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("count"),
+        ];
+        let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &NoopObserver,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "cli",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            Some(tx),
+            None,
+            &[],
+        )
+        .await
+        .expect("streamed turn should finish");
+
+        assert_eq!(result, "done");
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+        let mut deltas = Vec::new();
+        while let Ok(delta) = rx.try_recv() {
+            deltas.push(delta);
+        }
+        let shown: String = deltas
+            .iter()
+            .filter(|delta| *delta != DRAFT_CLEAR_SENTINEL)
+            .map(String::as_str)
+            .collect();
+        assert!(shown.contains("Counting now.\n"));
+        assert!(shown.ends_with("done"));
+        assert!(!shown.contains("<tool_"));
+        assert!(deltas.iter().any(|delta| delta == "do"));
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_refuses_excluded_tools() {
+        let provider = ScriptedProvider::from_text_responses(vec![
+            r#"<tool_call>
+{"name":"count_tool","arguments":{"value":"A"}}
+</tool_call>"#,
+            "done",
+        ]);
+
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool calls"),
+        ];
+        let observer = NoopObserver;
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "discord",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            None,
+            None,
+            &["count_tool".to_string()],
+        )
+        .await
+        .expect("loop should finish after refusing the excluded tool");
+
+        assert_eq!(result, "done");
+        assert_eq!(invocations.load(Ordering::SeqCst), 0);
+        let tool_results = history
+            .iter()
+            .find(|msg| msg.role == "user" && msg.content.starts_with("[Tool results]"))
+            .expect("prompt-mode tool result payload should be present");
+        assert!(tool_results
+            .content
+            .contains("not available on this channel"));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_native_mode_preserves_fallback_tool_call_ids() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -4733,6 +5458,30 @@ Tail"#;
         assert!(recalled.iter().any(|entry| entry.content.contains("45")));
     }
 
+    #[test]
+    fn calc_hint_flags_math_messages_only_when_calc_is_available() {
+        for msg in [
+            "what is 17.5% of 2340?",
+            "convert 5 km to miles",
+            "how many seconds in 3 days",
+            "12 * (4 + 1)",
+            "split 84 - 19 between us",
+        ] {
+            assert!(
+                !calc_hint(msg, ["shell", "calc"].into_iter()).is_empty(),
+                "{msg}"
+            );
+            assert!(calc_hint(msg, ["shell"].into_iter()).is_empty(), "{msg}");
+        }
+        for msg in [
+            "remind me at 5pm",
+            "meeting on 2024-05-01",
+            "how much do you know about rust?",
+        ] {
+            assert!(calc_hint(msg, ["calc"].into_iter()).is_empty(), "{msg}");
+        }
+    }
+
     #[tokio::test]
     async fn build_context_ignores_legacy_assistant_autosave_entries() {
         let tmp = TempDir::new().unwrap();
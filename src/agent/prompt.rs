@@ -350,6 +350,8 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                parameters: std::collections::BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: None,
@@ -388,6 +390,8 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                parameters: std::collections::BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: Some(Path::new("/tmp/workspace/skills/deploy/SKILL.md").to_path_buf()),
@@ -448,6 +452,8 @@ mod tests {
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: std::collections::HashMap::new(),
+                parameters: std::collections::BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
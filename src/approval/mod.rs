@@ -1,15 +1,20 @@
 //! Interactive approval workflow for supervised mode.
 //!
 //! Provides a pre-execution hook that prompts the user before tool calls,
-//! with session-scoped "Always" allowlists and audit logging.
+//! with session-scoped "Always" allowlists, persistent per-command grants
+//! (see [`grants`]), and audit logging.
+
+pub mod grants;
 
 use crate::config::AutonomyConfig;
 use crate::security::AutonomyLevel;
 use chrono::Utc;
+use grants::{ApprovalGrant, GrantScope};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -22,7 +27,7 @@ pub struct ApprovalRequest {
 
 /// The user's response to an approval request.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum ApprovalResponse {
     /// Execute this one call.
     Yes,
@@ -30,6 +35,11 @@ pub enum ApprovalResponse {
     No,
     /// Execute and add tool to session-scoped allowlist.
     Always,
+    /// Execute and persist a grant for this exact shell command.
+    AlwaysCommand,
+    /// Execute and persist a grant for the skill command prefix this shell
+    /// command starts with.
+    AlwaysPrefix,
 }
 
 /// A single audit log entry for an approval decision.
@@ -48,6 +58,7 @@ pub struct ApprovalLogEntry {
 ///
 /// - Checks config-level `auto_approve` / `always_ask` lists
 /// - Maintains a session-scoped "always" allowlist
+/// - Applies persistent per-command grants
 /// - Records an audit trail of all decisions
 pub struct ApprovalManager {
     /// Tools that never need approval (from config).
@@ -58,6 +69,12 @@ pub struct ApprovalManager {
     autonomy_level: AutonomyLevel,
     /// Session-scoped allowlist built from "Always" responses.
     session_allowlist: Mutex<HashSet<String>>,
+    /// Unexpired command grants.
+    grants: Mutex<Vec<ApprovalGrant>>,
+    /// Workspace whose grants file new grants persist to.
+    grant_store: Option<PathBuf>,
+    /// Lifetime of new grants in hours (`0` = until revoked).
+    grant_ttl_hours: u64,
     /// Audit trail of approval decisions.
     audit_log: Mutex<Vec<ApprovalLogEntry>>,
 }
@@ -70,10 +87,21 @@ impl ApprovalManager {
             always_ask: config.always_ask.iter().cloned().collect(),
             autonomy_level: config.level,
             session_allowlist: Mutex::new(HashSet::new()),
+            grants: Mutex::new(Vec::new()),
+            grant_store: None,
+            grant_ttl_hours: config.approval_grant_ttl_hours,
             audit_log: Mutex::new(Vec::new()),
         }
     }
 
+    /// Load and persist command grants in `workspace_dir`. Without a store,
+    /// command grants only last for the session.
+    pub fn with_grant_store(mut self, workspace_dir: &Path) -> Self {
+        self.grants = Mutex::new(grants::load_grants(workspace_dir));
+        self.grant_store = Some(workspace_dir.to_path_buf());
+        self
+    }
+
     /// Check whether a tool call requires interactive approval.
     ///
     /// Returns `true` if the call needs a prompt, `false` if it can proceed.
@@ -108,6 +136,27 @@ impl ApprovalManager {
         true
     }
 
+    /// Like [`Self::needs_approval`], but also honours command grants for
+    /// the call's arguments.
+    pub fn needs_approval_for_call(&self, tool_name: &str, args: &serde_json::Value) -> bool {
+        if !self.needs_approval(tool_name) {
+            return false;
+        }
+        // always_ask tools prompt even when a grant exists.
+        if self.always_ask.contains(tool_name) {
+            return true;
+        }
+        let Some(command) = grantable_command(tool_name, args) else {
+            return true;
+        };
+        let now = Utc::now();
+        !self
+            .grants
+            .lock()
+            .iter()
+            .any(|grant| !grant.is_expired(now) && grant.covers(tool_name, command))
+    }
+
     /// Record an approval decision and update session state.
     pub fn record_decision(
         &self,
@@ -116,10 +165,16 @@ impl ApprovalManager {
         decision: ApprovalResponse,
         channel: &str,
     ) {
-        // If "Always", add to session allowlist.
-        if decision == ApprovalResponse::Always {
-            let mut allowlist = self.session_allowlist.lock();
-            allowlist.insert(tool_name.to_string());
+        match decision {
+            // If "Always", add to session allowlist.
+            ApprovalResponse::Always => {
+                let mut allowlist = self.session_allowlist.lock();
+                allowlist.insert(tool_name.to_string());
+            }
+            ApprovalResponse::AlwaysCommand | ApprovalResponse::AlwaysPrefix => {
+                self.grant_command(tool_name, args, decision, channel);
+            }
+            ApprovalResponse::Yes | ApprovalResponse::No => {}
         }
 
         // Append to audit log.
@@ -140,6 +195,45 @@ impl ApprovalManager {
         self.audit_log.lock().clone()
     }
 
+    /// Get the current unexpired command grants.
+    pub fn grants(&self) -> Vec<ApprovalGrant> {
+        self.grants.lock().clone()
+    }
+
+    fn grant_command(
+        &self,
+        tool_name: &str,
+        args: &serde_json::Value,
+        decision: ApprovalResponse,
+        channel: &str,
+    ) {
+        let Some(command) = grantable_command(tool_name, args) else {
+            return;
+        };
+        let skill_prefix = (decision == ApprovalResponse::AlwaysPrefix)
+            .then(|| crate::skills::usage::skill_for_command(command))
+            .flatten();
+        let (scope, skill) = match skill_prefix {
+            Some((skill, prefix)) => (GrantScope::Prefix(prefix), Some(skill)),
+            None => (GrantScope::Exact(command.trim().to_string()), None),
+        };
+        let grant = ApprovalGrant::new(tool_name, scope, skill, channel, self.grant_ttl_hours);
+
+        let mut current = self.grants.lock();
+        match self.grant_store.as_deref() {
+            Some(workspace_dir) => match grants::add_grant(workspace_dir, grant) {
+                Ok(persisted) => *current = persisted,
+                Err(err) => tracing::warn!("Failed to persist approval grant: {err}"),
+            },
+            None => {
+                current.retain(|existing| {
+                    existing.tool_name != grant.tool_name || existing.scope != grant.scope
+                });
+                current.push(grant);
+            }
+        }
+    }
+
     /// Get the current session allowlist.
     pub fn session_allowlist(&self) -> HashSet<String> {
         self.session_allowlist.lock().clone()
@@ -150,18 +244,40 @@ impl ApprovalManager {
     /// For non-CLI channels, returns `Yes` automatically (interactive
     /// approval is only supported on CLI for now).
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
-        prompt_cli_interactive(request)
+        let command = grantable_command(&request.tool_name, &request.arguments);
+        let skill = command.and_then(crate::skills::usage::skill_for_command);
+        prompt_cli_interactive(request, command.is_some(), skill.as_ref())
+    }
+}
+
+/// The shell command of a call that command grants can cover.
+fn grantable_command<'a>(tool_name: &str, args: &'a serde_json::Value) -> Option<&'a str> {
+    if tool_name != "shell" {
+        return None;
     }
+    args.get("command")
+        .and_then(serde_json::Value::as_str)
+        .filter(|command| !command.trim().is_empty())
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
 
 /// Display the approval prompt and read user input from stdin.
-fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
+fn prompt_cli_interactive(
+    request: &ApprovalRequest,
+    offer_command_grant: bool,
+    skill_prefix: Option<&(String, String)>,
+) -> ApprovalResponse {
     let summary = summarize_args(&request.arguments);
     eprintln!();
     eprintln!("🔧 Agent wants to execute: {}", request.tool_name);
     eprintln!("   {summary}");
+    if offer_command_grant {
+        eprintln!("   [C]ommand: always allow this exact command");
+    }
+    if let Some((skill, prefix)) = skill_prefix {
+        eprintln!("   [P]refix: always allow `{prefix}…` for skill {skill}");
+    }
     eprint!("   [Y]es / [N]o / [A]lways for {}: ", request.tool_name);
     let _ = io::stderr().flush();
 
@@ -174,6 +290,8 @@ fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
     match line.trim().to_ascii_lowercase().as_str() {
         "y" | "yes" => ApprovalResponse::Yes,
         "a" | "always" => ApprovalResponse::Always,
+        "c" | "command" if offer_command_grant => ApprovalResponse::AlwaysCommand,
+        "p" | "prefix" if skill_prefix.is_some() => ApprovalResponse::AlwaysPrefix,
         _ => ApprovalResponse::No,
     }
 }
@@ -323,6 +441,55 @@ mod tests {
         assert!(mgr.needs_approval("file_write"));
     }
 
+    // ── command grants ───────────────────────────────────────
+
+    #[test]
+    fn always_command_grants_only_that_command() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mgr =
+            ApprovalManager::from_config(&AutonomyConfig::default()).with_grant_store(tmp.path());
+        let args = serde_json::json!({"command": "cargo test"});
+        assert!(mgr.needs_approval_for_call("shell", &args));
+
+        mgr.record_decision("shell", &args, ApprovalResponse::AlwaysCommand, "cli");
+
+        assert!(!mgr.needs_approval_for_call("shell", &args));
+        assert!(
+            mgr.needs_approval_for_call("shell", &serde_json::json!({"command": "cargo publish"}))
+        );
+        assert!(mgr.needs_approval("shell"));
+
+        let grant = &mgr.grants()[0];
+        assert_eq!(grant.scope, GrantScope::Exact("cargo test".into()));
+        assert_eq!(grant.channel, "cli");
+        assert!(grant.expires_at.is_some());
+
+        // Grants survive into the next session.
+        let next =
+            ApprovalManager::from_config(&AutonomyConfig::default()).with_grant_store(tmp.path());
+        assert!(!next.needs_approval_for_call("shell", &args));
+    }
+
+    #[test]
+    fn always_ask_ignores_command_grants() {
+        let mgr = ApprovalManager::from_config(&supervised_config());
+        let args = serde_json::json!({"command": "ls"});
+        mgr.record_decision("shell", &args, ApprovalResponse::AlwaysCommand, "cli");
+        assert_eq!(mgr.grants().len(), 1);
+        assert!(mgr.needs_approval_for_call("shell", &args));
+    }
+
+    #[test]
+    fn prefix_grant_without_skill_falls_back_to_exact_command() {
+        let mgr = ApprovalManager::from_config(&AutonomyConfig::default());
+        let args = serde_json::json!({"command": "curl wttr.in/London"});
+        mgr.record_decision("shell", &args, ApprovalResponse::AlwaysPrefix, "cli");
+        assert_eq!(
+            mgr.grants()[0].scope,
+            GrantScope::Exact("curl wttr.in/London".into())
+        );
+    }
+
     // ── audit log ────────────────────────────────────────────
 
     #[test]
@@ -407,6 +574,8 @@ mod tests {
     fn approval_response_serde_roundtrip() {
         let json = serde_json::to_string(&ApprovalResponse::Always).unwrap();
         assert_eq!(json, "\"always\"");
+        let json = serde_json::to_string(&ApprovalResponse::AlwaysCommand).unwrap();
+        assert_eq!(json, "\"always_command\"");
         let parsed: ApprovalResponse = serde_json::from_str("\"no\"").unwrap();
         assert_eq!(parsed, ApprovalResponse::No);
     }
@@ -0,0 +1,255 @@
+//! Content-addressed artifact store (`[artifacts]`).
+//!
+//! Large tool outputs and downloaded files are written to
+//! `<workspace>/state/artifacts/<sha256>` with a `<sha256>.json` metadata file
+//! next to them. Identical content is stored once. The store is bounded by
+//! `max_total_mb`; the least recently used artifacts are removed first.
+//! Artifacts are referred to by the first [`SHORT_ID_LEN`] hex characters of
+//! their hash, and any unambiguous prefix of at least [`MIN_PREFIX_LEN`]
+//! characters is accepted.
+
+use crate::config::ArtifactsConfig;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Length of the id shown to the model.
+pub const SHORT_ID_LEN: usize = 12;
+/// Shortest prefix accepted when looking an artifact up.
+pub const MIN_PREFIX_LEN: usize = 8;
+
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Metadata stored next to each artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactMeta {
+    /// Hex SHA-256 of the content.
+    pub id: String,
+    /// What produced it: a tool name such as `"shell"` or `"http_request"`.
+    pub source: String,
+    /// Human-readable origin, e.g. the URL a body was downloaded from.
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+}
+
+impl ArtifactMeta {
+    pub fn short_id(&self) -> &str {
+        &self.id[..SHORT_ID_LEN.min(self.id.len())]
+    }
+}
+
+pub struct ArtifactStore {
+    dir: PathBuf,
+    max_artifact_bytes: u64,
+    max_total_bytes: u64,
+}
+
+impl ArtifactStore {
+    pub fn new(dir: PathBuf, max_artifact_bytes: u64, max_total_bytes: u64) -> Self {
+        Self {
+            dir,
+            max_artifact_bytes,
+            max_total_bytes,
+        }
+    }
+
+    pub fn from_config(config: &ArtifactsConfig, workspace_dir: &Path) -> Self {
+        Self::new(
+            workspace_dir.join("state").join("artifacts"),
+            config.max_artifact_mb.saturating_mul(BYTES_PER_MB),
+            config.max_total_mb.saturating_mul(BYTES_PER_MB),
+        )
+    }
+
+    /// Largest artifact [`Self::put`] accepts.
+    pub fn max_artifact_bytes(&self) -> u64 {
+        self.max_artifact_bytes
+    }
+
+    /// Path of an artifact's content on disk.
+    pub fn content_path(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+
+    fn meta_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// Store `content`, or refresh the existing copy of identical content.
+    pub fn put(
+        &self,
+        source: &str,
+        label: Option<&str>,
+        content_type: Option<&str>,
+        content: &[u8],
+    ) -> Result<ArtifactMeta> {
+        let size_bytes = content.len() as u64;
+        if size_bytes > self.max_artifact_bytes {
+            bail!(
+                "artifact of {size_bytes} bytes exceeds the {} byte limit (artifacts.max_artifact_mb)",
+                self.max_artifact_bytes
+            );
+        }
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+
+        let id = hex::encode(Sha256::digest(content));
+        let now = Utc::now();
+        let meta = match self.read_meta(&id) {
+            Some(existing) if self.content_path(&id).exists() => ArtifactMeta {
+                last_used_at: now,
+                ..existing
+            },
+            _ => {
+                crate::state::write_atomic(&self.content_path(&id), content)
+                    .with_context(|| format!("failed to write artifact {id}"))?;
+                ArtifactMeta {
+                    id: id.clone(),
+                    source: source.to_string(),
+                    label: label.map(str::to_string),
+                    content_type: content_type.map(str::to_string),
+                    size_bytes,
+                    created_at: now,
+                    last_used_at: now,
+                }
+            }
+        };
+        self.write_meta(&meta)?;
+        self.evict(&id)?;
+        Ok(meta)
+    }
+
+    /// Content and metadata of the artifact whose id starts with `id`.
+    pub fn get(&self, id: &str) -> Result<Option<(ArtifactMeta, Vec<u8>)>> {
+        let Some(mut meta) = self.resolve(id)? else {
+            return Ok(None);
+        };
+        let content = std::fs::read(self.content_path(&meta.id))
+            .with_context(|| format!("failed to read artifact {}", meta.short_id()))?;
+        meta.last_used_at = Utc::now();
+        self.write_meta(&meta)?;
+        Ok(Some((meta, content)))
+    }
+
+    /// Every stored artifact, most recently used first.
+    pub fn list(&self) -> Result<Vec<ArtifactMeta>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e).context("failed to list artifacts"),
+        };
+        let mut artifacts: Vec<ArtifactMeta> = entries
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name();
+                let id = name.to_str()?.strip_suffix(".json")?;
+                self.read_meta(id)
+            })
+            .collect();
+        artifacts.sort_by_key(|meta| std::cmp::Reverse(meta.last_used_at));
+        Ok(artifacts)
+    }
+
+    fn resolve(&self, id: &str) -> Result<Option<ArtifactMeta>> {
+        let id = id.trim().to_ascii_lowercase();
+        if id.len() < MIN_PREFIX_LEN || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("artifact ids are at least {MIN_PREFIX_LEN} hex characters, got '{id}'");
+        }
+        let mut matches = self
+            .list()?
+            .into_iter()
+            .filter(|meta| meta.id.starts_with(&id));
+        let first = matches.next();
+        if matches.next().is_some() {
+            bail!("artifact id '{id}' is ambiguous; use more characters");
+        }
+        Ok(first)
+    }
+
+    fn read_meta(&self, id: &str) -> Option<ArtifactMeta> {
+        let raw = std::fs::read_to_string(self.meta_path(id)).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    fn write_meta(&self, meta: &ArtifactMeta) -> Result<()> {
+        crate::state::write_atomic(&self.meta_path(&meta.id), serde_json::to_vec_pretty(meta)?)
+            .with_context(|| format!("failed to write metadata for {}", meta.short_id()))
+    }
+
+    /// Remove least recently used artifacts, other than `keep`, until the
+    /// store fits in `max_total_bytes`.
+    fn evict(&self, keep: &str) -> Result<()> {
+        let mut artifacts = self.list()?;
+        let mut total: u64 = artifacts.iter().map(|meta| meta.size_bytes).sum();
+        while total > self.max_total_bytes {
+            let Some(oldest) = artifacts.pop() else {
+                break;
+            };
+            if oldest.id == keep {
+                continue;
+            }
+            let _ = std::fs::remove_file(self.content_path(&oldest.id));
+            let _ = std::fs::remove_file(self.meta_path(&oldest.id));
+            total = total.saturating_sub(oldest.size_bytes);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(dir: &Path, max_total_bytes: u64) -> ArtifactStore {
+        ArtifactStore::new(dir.join("artifacts"), 1024, max_total_bytes)
+    }
+
+    #[test]
+    fn identical_content_is_stored_once_and_found_by_prefix() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = store(tmp.path(), 4096);
+
+        let first = store
+            .put("shell", None, Some("text/plain"), b"hello")
+            .unwrap();
+        let second = store
+            .put("http_request", Some("https://x"), None, b"hello")
+            .unwrap();
+        assert_eq!(first.id, second.id);
+        assert_eq!(second.source, "shell");
+        assert_eq!(store.list().unwrap().len(), 1);
+
+        let (meta, content) = store.get(first.short_id()).unwrap().unwrap();
+        assert_eq!(meta.size_bytes, 5);
+        assert_eq!(content, b"hello");
+        assert!(store.get("00000000").unwrap().is_none());
+        assert!(store.get("abc").is_err());
+    }
+
+    #[test]
+    fn oversized_content_is_refused_and_store_is_bounded() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = store(tmp.path(), 20);
+
+        assert!(store.put("shell", None, None, &[b'x'; 2048]).is_err());
+
+        let old = store.put("shell", None, None, &[b'a'; 10]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let recent = store.put("shell", None, None, &[b'b'; 10]).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        // Reading `old` makes `recent` the least recently used.
+        store.get(&old.id).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let newest = store.put("shell", None, None, &[b'c'; 10]).unwrap();
+
+        let ids: Vec<String> = store.list().unwrap().into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![newest.id, old.id]);
+        assert!(!store.content_path(&recent.id).exists());
+    }
+}
@@ -0,0 +1,386 @@
+//! Provider latency benchmark (`zeroclaw bench providers`).
+//!
+//! Sends the same short prompt to every configured provider/model — the
+//! default, `[reliability] fallback_providers`, `[[model_routes]]`, and
+//! `[agents.<name>]` — and reports p50/p95 latency, time to first token for
+//! providers that stream, and the error rate. Calls go to each provider
+//! directly, without retries or fallbacks, so the numbers describe that
+//! provider and the network path to it.
+
+use crate::config::Config;
+use crate::providers::{self, Provider, ProviderRuntimeOptions};
+use anyhow::{bail, Result};
+use std::time::{Duration, Instant};
+
+const BENCH_SYSTEM_PROMPT: &str = "You are a latency probe. Answer in as few words as possible.";
+const BENCH_PROMPT: &str = "Reply with the single word: pong";
+/// Upper bound for one call, so a hung provider cannot stall the run.
+const CALL_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    /// Where the provider/model comes from in config.
+    label: String,
+    provider: String,
+    model: String,
+    api_key: Option<String>,
+    api_url: Option<String>,
+}
+
+/// Every configured provider/model pair, first occurrence wins.
+fn targets(config: &Config, provider_filter: Option<&str>) -> Vec<Target> {
+    let default_provider = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".into());
+    let default_model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+    let non_empty = |key: &Option<String>| {
+        key.as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+    };
+
+    let mut all = vec![Target {
+        label: "default".into(),
+        provider: default_provider.clone(),
+        model: default_model.clone(),
+        api_key: config.api_key.clone(),
+        api_url: config.api_url.clone(),
+    }];
+    for fallback in &config.reliability.fallback_providers {
+        all.push(Target {
+            label: "fallback".into(),
+            provider: fallback.clone(),
+            model: default_model.clone(),
+            api_key: config.api_key.clone(),
+            api_url: None,
+        });
+    }
+    for route in &config.model_routes {
+        all.push(Target {
+            label: format!("hint:{}", route.hint),
+            provider: route.provider.clone(),
+            model: route.model.clone(),
+            api_key: non_empty(&route.api_key).or_else(|| config.api_key.clone()),
+            api_url: (route.provider == default_provider)
+                .then(|| config.api_url.clone())
+                .flatten(),
+        });
+    }
+    let mut agents: Vec<_> = config.agents.iter().collect();
+    agents.sort_by(|a, b| a.0.cmp(b.0));
+    for (name, agent) in agents {
+        all.push(Target {
+            label: format!("agent:{name}"),
+            provider: agent.provider.clone(),
+            model: agent.model.clone(),
+            api_key: non_empty(&agent.api_key).or_else(|| config.api_key.clone()),
+            api_url: None,
+        });
+    }
+
+    let mut unique: Vec<Target> = Vec::new();
+    for target in all {
+        if provider_filter.is_some_and(|filter| !target.provider.eq_ignore_ascii_case(filter)) {
+            continue;
+        }
+        if !unique
+            .iter()
+            .any(|seen| seen.provider == target.provider && seen.model == target.model)
+        {
+            unique.push(target);
+        }
+    }
+    unique
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    total: Duration,
+    first_token: Option<Duration>,
+}
+
+async fn measure(provider: &dyn Provider, model: &str, temperature: f64) -> Result<Sample, String> {
+    let started = Instant::now();
+    let call = async {
+        if !provider.supports_streaming() {
+            provider
+                .chat_with_system(Some(BENCH_SYSTEM_PROMPT), BENCH_PROMPT, model, temperature)
+                .await
+                .map_err(|e| e.to_string())?;
+            return Ok(None);
+        }
+
+        use futures_util::StreamExt;
+        let mut stream = provider.stream_chat_with_system(
+            Some(BENCH_SYSTEM_PROMPT),
+            BENCH_PROMPT,
+            model,
+            temperature,
+            providers::traits::StreamOptions::new(true),
+        );
+        let mut first_token = None;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| e.to_string())?;
+            if chunk.is_final {
+                // A final chunk carrying text is an error report.
+                if !chunk.delta.is_empty() {
+                    return Err(chunk.delta);
+                }
+                break;
+            }
+            if first_token.is_none() && !chunk.delta.is_empty() {
+                first_token = Some(started.elapsed());
+            }
+        }
+        Ok(first_token)
+    };
+    match tokio::time::timeout(CALL_TIMEOUT, call).await {
+        Ok(Ok(first_token)) => Ok(Sample {
+            total: started.elapsed(),
+            first_token,
+        }),
+        Ok(Err(error)) => Err(providers::sanitize_api_error(&error)),
+        Err(_) => Err(format!("timed out after {}s", CALL_TIMEOUT.as_secs())),
+    }
+}
+
+/// Nearest-rank percentile of an ascending list.
+fn percentile(sorted: &[Duration], pct: usize) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    runs: usize,
+    errors: usize,
+    p50: Option<Duration>,
+    p95: Option<Duration>,
+    first_token_p50: Option<Duration>,
+    first_token_p95: Option<Duration>,
+    last_error: Option<String>,
+}
+
+fn summarize(outcomes: &[Result<Sample, String>]) -> Summary {
+    let mut totals: Vec<Duration> = outcomes
+        .iter()
+        .filter_map(|o| o.as_ref().ok().map(|s| s.total))
+        .collect();
+    let mut first_tokens: Vec<Duration> = outcomes
+        .iter()
+        .filter_map(|o| o.as_ref().ok().and_then(|s| s.first_token))
+        .collect();
+    totals.sort();
+    first_tokens.sort();
+    Summary {
+        runs: outcomes.len(),
+        errors: outcomes.iter().filter(|o| o.is_err()).count(),
+        p50: percentile(&totals, 50),
+        p95: percentile(&totals, 95),
+        first_token_p50: percentile(&first_tokens, 50),
+        first_token_p95: percentile(&first_tokens, 95),
+        last_error: outcomes.iter().rev().find_map(|o| o.clone().err()),
+    }
+}
+
+fn format_ms(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".into(), |d| format!("{}ms", d.as_millis()))
+}
+
+pub async fn run_providers(
+    config: &Config,
+    iterations: usize,
+    provider_filter: Option<&str>,
+) -> Result<()> {
+    if iterations == 0 {
+        bail!("--iterations must be at least 1");
+    }
+    let targets = targets(config, provider_filter);
+    if targets.is_empty() {
+        match provider_filter {
+            Some(filter) => bail!("No configured model uses provider '{filter}'"),
+            None => bail!("No providers configured"),
+        }
+    }
+
+    let options = ProviderRuntimeOptions {
+        auth_profile_override: None,
+        provider_api_url: config.api_url.clone(),
+        zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning_effort: config.runtime.reasoning_effort.clone(),
+        thinking_budget_tokens: config.runtime.thinking_budget_tokens,
+        debug_capture: None,
+    };
+
+    println!("⏱  ZeroClaw Bench — Provider Latency");
+    println!("  Targets: {}", targets.len());
+    println!("  Runs per target: {iterations}");
+    println!("  Prompt: {BENCH_PROMPT:?}");
+    println!();
+
+    let mut rows = Vec::new();
+    for target in &targets {
+        println!("  [{}] {}/{}", target.label, target.provider, target.model);
+        let provider = match providers::create_provider_with_url_and_options(
+            &target.provider,
+            target.api_key.as_deref(),
+            target.api_url.as_deref(),
+            &options,
+        ) {
+            Ok(provider) => provider,
+            Err(e) => {
+                println!("    ❌ could not create provider: {e}");
+                rows.push((
+                    target,
+                    Summary {
+                        runs: iterations,
+                        errors: iterations,
+                        last_error: Some(e.to_string()),
+                        ..Summary::default()
+                    },
+                ));
+                continue;
+            }
+        };
+        // Establish the connection so the first measured run is not a cold start.
+        let _ = provider.warmup().await;
+
+        let mut outcomes = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let outcome =
+                measure(provider.as_ref(), &target.model, config.default_temperature).await;
+            match &outcome {
+                Ok(sample) => println!(
+                    "    ✅ {} (first token {})",
+                    format_ms(Some(sample.total)),
+                    format_ms(sample.first_token)
+                ),
+                Err(error) => println!("    ❌ {error}"),
+            }
+            outcomes.push(outcome);
+        }
+        rows.push((target, summarize(&outcomes)));
+    }
+
+    println!();
+    println!(
+        "  {:<32} {:>9} {:>9} {:>10} {:>10} {:>7}",
+        "PROVIDER/MODEL", "P50", "P95", "TTFT P50", "TTFT P95", "ERRORS"
+    );
+    for (target, summary) in &rows {
+        let name = format!("{}/{}", target.provider, target.model);
+        println!(
+            "  {:<32} {:>9} {:>9} {:>10} {:>10} {:>6}%",
+            name,
+            format_ms(summary.p50),
+            format_ms(summary.p95),
+            format_ms(summary.first_token_p50),
+            format_ms(summary.first_token_p95),
+            summary.errors * 100 / summary.runs.max(1)
+        );
+    }
+    let failing: Vec<_> = rows
+        .iter()
+        .filter_map(|(target, summary)| {
+            summary
+                .last_error
+                .as_ref()
+                .map(|error| (target.label.as_str(), error))
+        })
+        .collect();
+    if !failing.is_empty() {
+        println!();
+        for (label, error) in failing {
+            println!("  [{label}] last error: {error}");
+        }
+    }
+    println!();
+    println!("  TTFT is time to first token; '-' means the provider does not stream.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{DelegateAgentConfig, ModelRouteConfig};
+
+    #[test]
+    fn targets_cover_configured_models_once() {
+        let mut config = Config {
+            default_provider: Some("openrouter".into()),
+            default_model: Some("model-a".into()),
+            ..Config::default()
+        };
+        config.reliability.fallback_providers = vec!["groq".into()];
+        config.model_routes = vec![
+            ModelRouteConfig {
+                hint: "fast".into(),
+                provider: "groq".into(),
+                model: "model-a".into(),
+                api_key: Some("route-key".into()),
+            },
+            ModelRouteConfig {
+                hint: "same".into(),
+                provider: "openrouter".into(),
+                model: "model-a".into(),
+                api_key: None,
+            },
+        ];
+        config.agents.insert(
+            "coder".into(),
+            toml::from_str::<DelegateAgentConfig>("provider = \"ollama\"\nmodel = \"qwen\"")
+                .unwrap(),
+        );
+
+        let labels: Vec<String> = targets(&config, None)
+            .into_iter()
+            .map(|t| format!("{} {}/{}", t.label, t.provider, t.model))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "default openrouter/model-a",
+                "fallback groq/model-a",
+                "agent:coder ollama/qwen",
+            ]
+        );
+
+        let ollama = targets(&config, Some("OLLAMA"));
+        assert_eq!(ollama.len(), 1);
+        assert_eq!(ollama[0].label, "agent:coder");
+    }
+
+    #[test]
+    fn summary_reports_percentiles_and_errors() {
+        let sample = |ms: u64, first: Option<u64>| {
+            Ok(Sample {
+                total: Duration::from_millis(ms),
+                first_token: first.map(Duration::from_millis),
+            })
+        };
+        let outcomes = vec![
+            sample(300, Some(100)),
+            sample(100, Some(40)),
+            Err("rate limited".to_string()),
+            sample(200, None),
+        ];
+        let summary = summarize(&outcomes);
+        assert_eq!(summary.runs, 4);
+        assert_eq!(summary.errors, 1);
+        assert_eq!(summary.p50, Some(Duration::from_millis(200)));
+        assert_eq!(summary.p95, Some(Duration::from_millis(300)));
+        assert_eq!(summary.first_token_p50, Some(Duration::from_millis(40)));
+        assert_eq!(summary.last_error.as_deref(), Some("rate limited"));
+        assert_eq!(percentile(&[], 50), None);
+    }
+}
@@ -0,0 +1,428 @@
+//! Allowlist changes at runtime (`[channels_config.access]`).
+//!
+//! Admins send `/allow <sender>`, `/block <sender>`, or `/allowlist show` from
+//! chat, where `<sender>` is an id on the admin's own channel or
+//! `channel:sender`. Each change is written to `config.toml` after the old
+//! file is copied to `config-backups/`, then applied to running channels
+//! through overrides they check before their own allowlist.
+
+use super::traits::ChannelMessage;
+use crate::config::{ChannelsConfig, Config};
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Channels whose allowlists can be changed at runtime.
+pub const SUPPORTED_CHANNELS: &[&str] = &["telegram", "discord", "slack", "mattermost", "signal"];
+
+/// Config copies kept in `config-backups/`.
+const BACKUPS_KEPT: usize = 10;
+
+/// An admin chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccessCommand {
+    Allow(String),
+    Block(String),
+    Show,
+}
+
+#[derive(Default)]
+struct AccessState {
+    config_path: Option<PathBuf>,
+    admins: Vec<String>,
+    /// `true` allows and `false` blocks, by channel and sender.
+    overrides: HashMap<(String, String), bool>,
+}
+
+static STATE: LazyLock<RwLock<AccessState>> = LazyLock::new(|| RwLock::new(AccessState::default()));
+
+/// Called by `start_channels` with the loaded config.
+pub(super) fn register(config: &Config) {
+    let access = &config.channels_config.access;
+    let overrides = access
+        .blocked
+        .iter()
+        .map(String::as_str)
+        .filter_map(split_target)
+        .map(|target| (target, false))
+        .collect();
+    *STATE.write() = AccessState {
+        config_path: Some(config.config_path.clone()),
+        admins: access.admins.clone(),
+        overrides,
+    };
+}
+
+/// Whether `sender` may talk on `channel`, given whether the channel's own
+/// allowlist (`listed`) accepts them. Runtime changes win; senders paired
+/// while the channels run are accepted too.
+pub fn check(channel: &str, sender: &str, listed: bool) -> bool {
+    match override_for(channel, sender) {
+        Some(allowed) => allowed,
+        None => listed || super::pairing::is_paired(channel, sender),
+    }
+}
+
+/// Whether `sender` on `channel` was blocked.
+pub fn is_blocked(channel: &str, sender: &str) -> bool {
+    override_for(channel, sender) == Some(false)
+}
+
+fn override_for(channel: &str, sender: &str) -> Option<bool> {
+    let state = STATE.read();
+    if state.overrides.is_empty() {
+        return None;
+    }
+    state
+        .overrides
+        .get(&(channel.to_string(), sender.to_string()))
+        .copied()
+}
+
+/// Apply a change to running channels; a no-op outside `start_channels`.
+fn set_override(channel: &str, sender: &str, allowed: bool) {
+    let mut state = STATE.write();
+    if state.config_path.is_some() {
+        state
+            .overrides
+            .insert((channel.to_string(), sender.to_string()), allowed);
+    }
+}
+
+/// Whether `msg` comes from an allowlist admin.
+pub fn is_admin(msg: &ChannelMessage) -> bool {
+    let qualified = format!("{}:{}", msg.channel, msg.sender);
+    STATE.read().admins.iter().any(|admin| admin == &qualified)
+}
+
+/// Parse an admin chat command, ignoring `@bot` suffixes.
+pub fn parse_command(content: &str) -> Option<AccessCommand> {
+    let trimmed = content.trim();
+    let (head, rest) = trimmed
+        .split_once(char::is_whitespace)
+        .unwrap_or((trimmed, ""));
+    let command = head.split('@').next().unwrap_or(head).to_ascii_lowercase();
+    let arg = rest.trim();
+    match command.as_str() {
+        "/allow" if !arg.is_empty() => Some(AccessCommand::Allow(arg.to_string())),
+        "/block" if !arg.is_empty() => Some(AccessCommand::Block(arg.to_string())),
+        "/allowlist" if arg.is_empty() || arg.eq_ignore_ascii_case("show") => {
+            Some(AccessCommand::Show)
+        }
+        _ => None,
+    }
+}
+
+/// Apply an admin's chat command and return the reply.
+pub async fn handle_command(command: AccessCommand, msg: &ChannelMessage) -> String {
+    let Some(config_path) = STATE.read().config_path.clone() else {
+        return "Allowlist changes are unavailable.".into();
+    };
+    let result = match command {
+        AccessCommand::Show => show(&config_path).await,
+        AccessCommand::Allow(arg) => match resolve_target(&arg, &msg.channel) {
+            Ok((channel, sender)) => allow(&config_path, &channel, &sender)
+                .await
+                .map(|()| format!("✅ Allowed {channel} sender {sender}.")),
+            Err(e) => Err(e),
+        },
+        AccessCommand::Block(arg) => match resolve_target(&arg, &msg.channel) {
+            Ok((channel, sender)) if STATE.read().admins.contains(&format!("{channel}:{sender}")) => {
+                Err(anyhow::anyhow!(
+                    "{channel}:{sender} is an admin; remove it from channels_config.access.admins first"
+                ))
+            }
+            Ok((channel, sender)) => block(&config_path, &channel, &sender)
+                .await
+                .map(|()| format!("🚫 Blocked {channel} sender {sender}.")),
+            Err(e) => Err(e),
+        },
+    };
+    match result {
+        Ok(reply) => {
+            tracing::info!(admin = %msg.sender, channel = %msg.channel, "{reply}");
+            reply
+        }
+        Err(e) => format!("⚠️ {e:#}"),
+    }
+}
+
+/// Add `sender` to the allowlist of `channel` and lift any block, in the
+/// config file and in running channels.
+pub async fn allow(config_path: &Path, channel: &str, sender: &str) -> Result<()> {
+    let entry = format!("{channel}:{sender}");
+    update_config(config_path, |config| {
+        let blocked = &mut config.channels_config.access.blocked;
+        let before = blocked.len();
+        blocked.retain(|b| b != &entry);
+        let mut changed = blocked.len() != before;
+
+        let Some(allowlist) = allowlist_mut(&mut config.channels_config, channel) else {
+            bail!("Channel '{channel}' is not configured or has no allowlist");
+        };
+        if !allowlist.iter().any(|u| u == "*" || u == sender) {
+            allowlist.push(sender.to_string());
+            changed = true;
+        }
+        Ok(changed)
+    })
+    .await?;
+    set_override(channel, sender, true);
+    Ok(())
+}
+
+/// Remove `sender` from the allowlist of `channel` and block them, in the
+/// config file and in running channels.
+pub async fn block(config_path: &Path, channel: &str, sender: &str) -> Result<()> {
+    let entry = format!("{channel}:{sender}");
+    update_config(config_path, |config| {
+        let Some(allowlist) = allowlist_mut(&mut config.channels_config, channel) else {
+            bail!("Channel '{channel}' is not configured or has no allowlist");
+        };
+        let before = allowlist.len();
+        allowlist.retain(|u| u != sender);
+        let mut changed = allowlist.len() != before;
+
+        let blocked = &mut config.channels_config.access.blocked;
+        if !blocked.contains(&entry) {
+            blocked.push(entry);
+            changed = true;
+        }
+        Ok(changed)
+    })
+    .await?;
+    set_override(channel, sender, false);
+    Ok(())
+}
+
+/// `/allowlist show` reply: the saved allowlists and blocked senders.
+async fn show(config_path: &Path) -> Result<String> {
+    let mut config = load_config(config_path).await?.1;
+    let mut out = String::from("Allowlists:");
+    for channel in SUPPORTED_CHANNELS {
+        if let Some(allowlist) = allowlist_mut(&mut config.channels_config, channel) {
+            let entries = if allowlist.is_empty() {
+                "(empty)".to_string()
+            } else {
+                allowlist.join(", ")
+            };
+            let _ = write!(out, "\n- {channel}: {entries}");
+        }
+    }
+    let blocked = &config.channels_config.access.blocked;
+    if !blocked.is_empty() {
+        let _ = write!(out, "\nBlocked: {}", blocked.join(", "));
+    }
+    Ok(out)
+}
+
+/// `channel:sender` from a command argument; a bare sender is on
+/// `default_channel`.
+fn resolve_target(arg: &str, default_channel: &str) -> Result<(String, String)> {
+    let (channel, sender) = split_target(arg)
+        .filter(|(channel, _)| SUPPORTED_CHANNELS.contains(&channel.as_str()))
+        .unwrap_or_else(|| {
+            (
+                default_channel.to_string(),
+                normalize_sender(default_channel, arg),
+            )
+        });
+    if !SUPPORTED_CHANNELS.contains(&channel.as_str()) {
+        bail!(
+            "Allowlists on {channel} cannot be changed from chat (supported: {})",
+            SUPPORTED_CHANNELS.join(", ")
+        );
+    }
+    if sender.is_empty() {
+        bail!("Sender must not be empty");
+    }
+    Ok((channel, sender))
+}
+
+fn split_target(entry: &str) -> Option<(String, String)> {
+    let (channel, sender) = entry.trim().split_once(':')?;
+    let channel = channel.trim().to_ascii_lowercase();
+    let sender = normalize_sender(&channel, sender);
+    (!channel.is_empty() && !sender.is_empty()).then_some((channel, sender))
+}
+
+/// Telegram allowlists store usernames without `@`.
+fn normalize_sender(channel: &str, sender: &str) -> String {
+    let sender = sender.trim();
+    if channel == "telegram" {
+        sender.trim_start_matches('@').to_string()
+    } else {
+        sender.to_string()
+    }
+}
+
+/// The allowlist of `channel` in `channels`, if the channel is configured.
+fn allowlist_mut<'a>(
+    channels: &'a mut ChannelsConfig,
+    channel: &str,
+) -> Option<&'a mut Vec<String>> {
+    match channel {
+        "telegram" => channels.telegram.as_mut().map(|c| &mut c.allowed_users),
+        "discord" => channels.discord.as_mut().map(|c| &mut c.allowed_users),
+        "slack" => channels.slack.as_mut().map(|c| &mut c.allowed_users),
+        "mattermost" => channels.mattermost.as_mut().map(|c| &mut c.allowed_users),
+        "signal" => channels.signal.as_mut().map(|c| &mut c.allowed_from),
+        _ => None,
+    }
+}
+
+/// Read the config file without environment overrides, so they are not
+/// written back. Returns the raw text too.
+async fn load_config(config_path: &Path) -> Result<(String, Config)> {
+    let contents = tokio::fs::read_to_string(config_path)
+        .await
+        .with_context(|| format!("Failed to read config file: {}", config_path.display()))?;
+    let mut config: Config = toml::from_str(&contents)
+        .context("Failed to parse config.toml for the allowlist update")?;
+    config.config_path = config_path.to_path_buf();
+    Ok((contents, config))
+}
+
+/// Apply `edit` to the config file. When it reports a change, the current
+/// file is backed up first.
+async fn update_config(
+    config_path: &Path,
+    edit: impl FnOnce(&mut Config) -> Result<bool>,
+) -> Result<()> {
+    let (contents, mut config) = load_config(config_path).await?;
+    if !edit(&mut config)? {
+        return Ok(());
+    }
+    backup_config(config_path, &contents).await?;
+    config.save().await
+}
+
+/// Copy the config text to `config-backups/` next to it, keeping the newest
+/// [`BACKUPS_KEPT`] copies.
+async fn backup_config(config_path: &Path, contents: &str) -> Result<PathBuf> {
+    let dir = config_path
+        .parent()
+        .context("Config path must have a parent directory")?
+        .join("config-backups");
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .with_context(|| format!("Failed to create backup directory: {}", dir.display()))?;
+    let path = dir.join(format!(
+        "config-{}.toml",
+        Utc::now().format("%Y%m%dT%H%M%S%.6fZ")
+    ));
+    tokio::fs::write(&path, contents)
+        .await
+        .with_context(|| format!("Failed to write config backup: {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+        let _ = tokio::fs::set_permissions(&path, Permissions::from_mode(0o600)).await;
+    }
+
+    let mut backups = Vec::new();
+    let mut entries = tokio::fs::read_dir(&dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with("config-") && name.ends_with(".toml") {
+            backups.push(entry.path());
+        }
+    }
+    backups.sort();
+    let excess = backups.len().saturating_sub(BACKUPS_KEPT);
+    for old in &backups[..excess] {
+        let _ = tokio::fs::remove_file(old).await;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = "default_temperature = 0.7\n[channels_config]\ncli = true\n[channels_config.telegram]\nbot_token = \"t\"\nallowed_users = [\"alice\"]\n[channels_config.access]\nadmins = [\"telegram:alice\"]\n";
+
+    #[test]
+    fn commands_and_targets_parse() {
+        assert_eq!(
+            parse_command("/allow@zc_bot @bob"),
+            Some(AccessCommand::Allow("@bob".into()))
+        );
+        assert_eq!(
+            parse_command("/block discord:42"),
+            Some(AccessCommand::Block("discord:42".into()))
+        );
+        assert_eq!(parse_command("/allowlist"), Some(AccessCommand::Show));
+        assert_eq!(parse_command("/allowlist show"), Some(AccessCommand::Show));
+        assert_eq!(parse_command("/allow"), None);
+
+        assert_eq!(
+            resolve_target("@bob", "telegram").unwrap(),
+            ("telegram".into(), "bob".into())
+        );
+        assert_eq!(
+            resolve_target("Discord:42", "telegram").unwrap(),
+            ("discord".into(), "42".into())
+        );
+        assert_eq!(
+            resolve_target("uuid:abc", "signal").unwrap(),
+            ("signal".into(), "uuid:abc".into())
+        );
+        assert!(resolve_target("x", "email").is_err());
+    }
+
+    #[tokio::test]
+    async fn allow_and_block_edit_the_config_with_backups() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        std::fs::write(&config_path, CONFIG).unwrap();
+        let saved = || -> Config {
+            toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap()
+        };
+
+        allow(&config_path, "telegram", "bob").await.unwrap();
+        assert_eq!(
+            saved().channels_config.telegram.unwrap().allowed_users,
+            ["alice", "bob"]
+        );
+        assert!(show(&config_path)
+            .await
+            .unwrap()
+            .contains("- telegram: alice, bob"));
+
+        block(&config_path, "telegram", "bob").await.unwrap();
+        let config = saved();
+        assert_eq!(
+            config.channels_config.telegram.unwrap().allowed_users,
+            ["alice"]
+        );
+        assert_eq!(config.channels_config.access.blocked, ["telegram:bob"]);
+
+        allow(&config_path, "telegram", "bob").await.unwrap();
+        assert!(saved().channels_config.access.blocked.is_empty());
+        assert!(allow(&config_path, "slack", "U1").await.is_err());
+
+        let backups = std::fs::read_dir(tmp.path().join("config-backups"))
+            .unwrap()
+            .count();
+        assert_eq!(backups, 3);
+    }
+
+    #[tokio::test]
+    async fn old_backups_are_pruned() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        for _ in 0..BACKUPS_KEPT + 2 {
+            backup_config(&config_path, CONFIG).await.unwrap();
+        }
+        let backups = std::fs::read_dir(tmp.path().join("config-backups"))
+            .unwrap()
+            .count();
+        assert_eq!(backups, BACKUPS_KEPT);
+    }
+}
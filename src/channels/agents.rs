@@ -0,0 +1,184 @@
+//! Multi-agent routing for channel conversations (`[channels_config.agents]`).
+//!
+//! One daemon can serve several named agents, each with its own model, role
+//! prompt, skills, tools, and memory namespace. `[[channels_config.agent_routes]]`
+//! rules pick the agent for each message by channel, group (reply target), and
+//! sender; the first matching rule wins and unmatched conversations use the
+//! default agent built from the top-level config.
+
+use super::prompt::SharedPrompt;
+use super::traits::ChannelMessage;
+use super::ChannelRuntimeContext;
+use crate::config::ChannelAgentRouteConfig;
+use crate::memory::Memory;
+use crate::skills::Skill;
+use crate::tools::Tool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A named agent with its prompt, tools, and memory already built.
+pub(super) struct ChannelAgent {
+    pub name: String,
+    /// Provider override; `None` uses the default provider.
+    pub provider: Option<String>,
+    /// Model override; `None` uses the default model.
+    pub model: Option<String>,
+    pub system_prompt: SharedPrompt,
+    pub memory: Arc<dyn Memory>,
+    pub tools_registry: Arc<Vec<Box<dyn Tool>>>,
+}
+
+/// Picks the agent for each inbound message.
+#[derive(Default)]
+pub(super) struct AgentRouter {
+    agents: HashMap<String, Arc<ChannelAgent>>,
+    routes: Vec<ChannelAgentRouteConfig>,
+}
+
+impl AgentRouter {
+    pub fn new(agents: Vec<ChannelAgent>, routes: Vec<ChannelAgentRouteConfig>) -> Self {
+        Self {
+            agents: agents
+                .into_iter()
+                .map(|agent| (agent.name.clone(), Arc::new(agent)))
+                .collect(),
+            routes,
+        }
+    }
+
+    /// The routed agent for `msg`, or `None` for the default agent.
+    pub fn select(&self, msg: &ChannelMessage) -> Option<Arc<ChannelAgent>> {
+        self.routes
+            .iter()
+            .find(|route| route_matches(route, msg))
+            .and_then(|route| self.agents.get(&route.agent))
+            .cloned()
+    }
+}
+
+pub(super) fn route_matches(route: &ChannelAgentRouteConfig, msg: &ChannelMessage) -> bool {
+    route.channel.as_deref().is_none_or(|c| c == msg.channel)
+        && route.group.as_deref().is_none_or(|g| g == msg.reply_target)
+        && route.sender.as_deref().is_none_or(|s| s == msg.sender)
+}
+
+/// Runtime context for `msg`: the default one, or a copy using the routed agent.
+pub(super) fn routed_context(
+    ctx: &Arc<ChannelRuntimeContext>,
+    msg: &ChannelMessage,
+) -> Arc<ChannelRuntimeContext> {
+    let Some(agent) = ctx.agents.select(msg) else {
+        return Arc::clone(ctx);
+    };
+    tracing::debug!(
+        channel = %msg.channel,
+        agent = %agent.name,
+        "Routing channel message to agent"
+    );
+    let mut routed = ChannelRuntimeContext::clone(ctx);
+    routed.system_prompt = agent.system_prompt.clone();
+    routed.memory = Arc::clone(&agent.memory);
+    routed.tools_registry = Arc::clone(&agent.tools_registry);
+    routed.agent = Some(agent);
+    Arc::new(routed)
+}
+
+/// Skills offered to an agent: all of them, or the named subset.
+pub(super) fn select_skills(skills: &[Skill], allowed: Option<&[String]>) -> Vec<Skill> {
+    skills
+        .iter()
+        .filter(|skill| allowed.is_none_or(|names| names.contains(&skill.name)))
+        .cloned()
+        .collect()
+}
+
+/// Drop tools outside `allowed` and tools contributed by skills the agent
+/// was not given.
+pub(super) fn retain_tools(
+    registry: &mut Vec<Box<dyn Tool>>,
+    allowed: Option<&[String]>,
+    all_skills: &[Skill],
+    agent_skills: &[Skill],
+) {
+    let hidden_skill_tools: Vec<&str> = all_skills
+        .iter()
+        .filter(|skill| !agent_skills.iter().any(|s| s.name == skill.name))
+        .flat_map(|skill| skill.tools.iter().map(|tool| tool.name.as_str()))
+        .collect();
+    registry.retain(|tool| {
+        let name = tool.name();
+        allowed.is_none_or(|names| names.iter().any(|n| n == name))
+            && !hidden_skill_tools.contains(&name)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::NoneMemory;
+
+    fn agent(name: &str) -> ChannelAgent {
+        ChannelAgent {
+            name: name.into(),
+            provider: None,
+            model: Some(format!("{name}-model")),
+            system_prompt: SharedPrompt::new(format!("{name} prompt")),
+            memory: Arc::new(NoneMemory::new()),
+            tools_registry: Arc::new(Vec::new()),
+        }
+    }
+
+    fn message(channel: &str, reply_target: &str, sender: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "1".into(),
+            sender: sender.into(),
+            reply_target: reply_target.into(),
+            content: "hi".into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+            location: None,
+            payload: None,
+        }
+    }
+
+    #[test]
+    fn first_matching_route_wins_and_unmatched_messages_use_default() {
+        let router = AgentRouter::new(
+            vec![agent("coder"), agent("family")],
+            vec![
+                ChannelAgentRouteConfig {
+                    agent: "family".into(),
+                    channel: Some("discord".into()),
+                    group: Some("kitchen".into()),
+                    ..ChannelAgentRouteConfig::default()
+                },
+                ChannelAgentRouteConfig {
+                    agent: "coder".into(),
+                    channel: Some("discord".into()),
+                    ..ChannelAgentRouteConfig::default()
+                },
+                ChannelAgentRouteConfig {
+                    agent: "family".into(),
+                    sender: Some("mom".into()),
+                    ..ChannelAgentRouteConfig::default()
+                },
+            ],
+        );
+
+        let pick = |msg: ChannelMessage| router.select(&msg).map(|a| a.name.clone());
+        assert_eq!(
+            pick(message("discord", "kitchen", "alice")).as_deref(),
+            Some("family")
+        );
+        assert_eq!(
+            pick(message("discord", "dev", "alice")).as_deref(),
+            Some("coder")
+        );
+        assert_eq!(
+            pick(message("imessage", "mom", "mom")).as_deref(),
+            Some("family")
+        );
+        assert_eq!(pick(message("telegram", "42", "bob")), None);
+    }
+}
@@ -0,0 +1,240 @@
+//! One message to many recipients across channels.
+//!
+//! Targets are `channel:recipient` strings. Each channel renders the message
+//! itself when it sends, so the same text arrives as Telegram HTML, Slack
+//! mrkdwn, and so on. Channels are sent to concurrently; sends on one channel
+//! are spaced by `[broadcast].per_channel_interval_ms` to stay under platform
+//! rate limits.
+
+use crate::config::{BroadcastConfig, Config};
+use anyhow::{bail, Result};
+use std::fmt::Write;
+use std::future::Future;
+use std::time::Duration;
+
+/// One recipient on one channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BroadcastTarget {
+    pub channel: String,
+    pub to: String,
+}
+
+impl BroadcastTarget {
+    /// Parse `channel:recipient`. The recipient may itself contain `:`.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let Some((channel, to)) = raw.trim().split_once(':') else {
+            bail!("Broadcast target '{raw}' must be 'channel:recipient'");
+        };
+        let (channel, to) = (channel.trim(), to.trim());
+        if channel.is_empty() || to.is_empty() {
+            bail!("Broadcast target '{raw}' must be 'channel:recipient'");
+        }
+        Ok(Self {
+            channel: channel.to_ascii_lowercase(),
+            to: to.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for BroadcastTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.channel, self.to)
+    }
+}
+
+/// Parse and de-duplicate targets, keeping their order.
+pub fn parse_targets<S: AsRef<str>>(
+    raw: &[S],
+    config: &BroadcastConfig,
+) -> Result<Vec<BroadcastTarget>> {
+    let mut targets: Vec<BroadcastTarget> = Vec::new();
+    for entry in raw {
+        let target = BroadcastTarget::parse(entry.as_ref())?;
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    if targets.is_empty() {
+        bail!("Broadcast needs at least one recipient");
+    }
+    if targets.len() > config.max_recipients {
+        bail!(
+            "Broadcast has {} recipients; [broadcast].max_recipients is {}",
+            targets.len(),
+            config.max_recipients
+        );
+    }
+    Ok(targets)
+}
+
+/// Outcome of a broadcast, in target order.
+#[derive(Debug, Default)]
+pub struct BroadcastReport {
+    pub results: Vec<(BroadcastTarget, Result<(), String>)>,
+}
+
+impl BroadcastReport {
+    pub fn delivered(&self) -> usize {
+        self.results.iter().filter(|(_, r)| r.is_ok()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.delivered()
+    }
+
+    /// Human-readable summary with one line per failed target.
+    pub fn summary(&self) -> String {
+        let mut out = format!(
+            "Broadcast delivered to {} of {} recipients.",
+            self.delivered(),
+            self.results.len()
+        );
+        for (target, result) in &self.results {
+            if let Err(e) = result {
+                let _ = write!(out, "\n- {target}: {e}");
+            }
+        }
+        out
+    }
+}
+
+/// Send to every target with `send`: channels in parallel, targets within a
+/// channel one after another with `interval` between them.
+async fn fan_out<F, Fut>(
+    targets: Vec<BroadcastTarget>,
+    interval: Duration,
+    send: F,
+) -> BroadcastReport
+where
+    F: Fn(BroadcastTarget) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut groups: Vec<(String, Vec<(usize, BroadcastTarget)>)> = Vec::new();
+    for (index, target) in targets.iter().cloned().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|(channel, _)| *channel == target.channel)
+        {
+            Some((_, group)) => group.push((index, target)),
+            None => groups.push((target.channel.clone(), vec![(index, target)])),
+        }
+    }
+
+    let send = &send;
+    let per_channel = groups.into_iter().map(|(_, group)| async move {
+        let mut results = Vec::with_capacity(group.len());
+        for (position, (index, target)) in group.into_iter().enumerate() {
+            if position > 0 && !interval.is_zero() {
+                tokio::time::sleep(interval).await;
+            }
+            let result = send(target).await.map_err(|e| e.to_string());
+            results.push((index, result));
+        }
+        results
+    });
+
+    let mut indexed: Vec<(usize, Result<(), String>)> = futures_util::future::join_all(per_channel)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    indexed.sort_by_key(|(index, _)| *index);
+    BroadcastReport {
+        results: targets
+            .into_iter()
+            .zip(indexed.into_iter().map(|(_, result)| result))
+            .collect(),
+    }
+}
+
+/// Broadcast `message` to `targets` through the configured channels.
+/// Targets outside `[broadcast].allowed_targets` fail without sending.
+pub async fn broadcast(
+    config: &Config,
+    targets: Vec<BroadcastTarget>,
+    message: &str,
+) -> BroadcastReport {
+    let interval = Duration::from_millis(config.broadcast.per_channel_interval_ms);
+    let report = fan_out(targets, interval, |target| async move {
+        if !config.broadcast.allows(&target.channel, &target.to) {
+            bail!("not in [broadcast].allowed_targets");
+        }
+        crate::cron::scheduler::deliver_announcement(config, &target.channel, &target.to, message)
+            .await
+    })
+    .await;
+    tracing::info!(
+        delivered = report.delivered(),
+        failed = report.failed(),
+        "Broadcast finished"
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    fn targets(raw: &[&str]) -> Vec<BroadcastTarget> {
+        parse_targets(raw, &BroadcastConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn targets_are_parsed_deduplicated_and_capped() {
+        let parsed = targets(&[
+            "Telegram:123",
+            "slack:C1",
+            "telegram:123",
+            "matrix:!room:hs",
+        ]);
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0].to_string(), "telegram:123");
+        assert_eq!(parsed[2].to, "!room:hs");
+
+        assert!(BroadcastTarget::parse("telegram").is_err());
+        assert!(BroadcastTarget::parse("telegram: ").is_err());
+        let capped = BroadcastConfig {
+            max_recipients: 1,
+            ..BroadcastConfig::default()
+        };
+        assert!(parse_targets(&["a:1", "b:2"], &capped).is_err());
+        assert!(parse_targets::<&str>(&[], &capped).is_err());
+    }
+
+    #[tokio::test]
+    async fn fan_out_paces_each_channel_and_keeps_order() {
+        let sent = Mutex::new(Vec::new());
+        let started = Instant::now();
+        let report = fan_out(
+            targets(&["slack:a", "telegram:1", "slack:b", "telegram:2"]),
+            Duration::from_millis(40),
+            |target| {
+                let sent = &sent;
+                async move {
+                    sent.lock().unwrap().push(target.to_string());
+                    if target.to == "b" {
+                        bail!("channel_not_found");
+                    }
+                    Ok(())
+                }
+            },
+        )
+        .await;
+
+        // Two channels run side by side, so only one pause per channel.
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(40));
+        assert!(elapsed < Duration::from_millis(120), "{elapsed:?}");
+        assert_eq!(sent.lock().unwrap().len(), 4);
+
+        let order: Vec<String> = report.results.iter().map(|(t, _)| t.to_string()).collect();
+        assert_eq!(order, ["slack:a", "telegram:1", "slack:b", "telegram:2"]);
+        assert_eq!(report.delivered(), 3);
+        assert_eq!(
+            report.summary(),
+            "Broadcast delivered to 3 of 4 recipients.\n- slack:b: channel_not_found"
+        );
+    }
+}
@@ -1,8 +1,19 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::agent::loop_::DRAFT_CLEAR_SENTINEL;
 use async_trait::async_trait;
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
 use tokio::io::{self, AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_INTERVAL: Duration = Duration::from_millis(80);
+/// Prefix of the agent loop's "Thinking..." progress line.
+const THINKING_PREFIX: &str = "\u{1f914}";
+/// Clears the current terminal line.
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
 /// CLI channel — stdin/stdout, always available, zero deps
 pub struct CliChannel;
 
@@ -48,6 +59,8 @@ impl Channel for CliChannel {
                     .unwrap_or_default()
                     .as_secs(),
                 thread_ts: None,
+                location: None,
+                payload: None,
             };
 
             if tx.send(msg).await.is_err() {
@@ -58,6 +71,58 @@ impl Channel for CliChannel {
     }
 }
 
+/// Print agent-loop progress and streamed answer text to stdout as it
+/// arrives. On a terminal, "Thinking" is shown as a spinner until the next
+/// update. The task ends when the sender is dropped.
+pub fn spawn_terminal_renderer(rx: mpsc::Receiver<String>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut out = std::io::stdout();
+        let animate = out.is_terminal();
+        render_deltas(rx, &mut out, animate).await;
+    })
+}
+
+async fn render_deltas(mut rx: mpsc::Receiver<String>, out: &mut impl Write, animate: bool) {
+    // Status animated with the spinner until the next message arrives.
+    let mut status: Option<String> = None;
+    let mut frame = 0;
+    let mut ticker = tokio::time::interval(SPINNER_INTERVAL);
+    loop {
+        let message = tokio::select! {
+            message = rx.recv() => message,
+            _ = ticker.tick(), if status.is_some() => {
+                if let Some(line) = &status {
+                    let spinner = SPINNER_FRAMES[frame % SPINNER_FRAMES.len()];
+                    let _ = write!(out, "{CLEAR_LINE}{spinner} {line}");
+                    let _ = out.flush();
+                    frame += 1;
+                }
+                continue;
+            }
+        };
+        if status.take().is_some() {
+            let _ = write!(out, "{CLEAR_LINE}");
+        }
+        let Some(message) = message else {
+            break;
+        };
+        if message == DRAFT_CLEAR_SENTINEL {
+            // Progress lines stay in the scrollback; answer text follows.
+            continue;
+        }
+        if animate {
+            if let Some(line) = message.strip_prefix(THINKING_PREFIX) {
+                status = Some(line.trim().to_string());
+                frame = 0;
+                continue;
+            }
+        }
+        let _ = write!(out, "{message}");
+        let _ = out.flush();
+    }
+    let _ = out.flush();
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +141,8 @@ mod tests {
                 recipient: "user".into(),
                 subject: None,
                 thread_ts: None,
+                in_reply_to_id: None,
+                buttons: Vec::new(),
             })
             .await;
         assert!(result.is_ok());
@@ -90,11 +157,46 @@ mod tests {
                 recipient: String::new(),
                 subject: None,
                 thread_ts: None,
+                in_reply_to_id: None,
+                buttons: Vec::new(),
             })
             .await;
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn terminal_renderer_prints_progress_and_streamed_text() {
+        let (tx, rx) = mpsc::channel(8);
+        for delta in [
+            "\u{1f914} Thinking...\n",
+            "\u{23f3} shell: ls\n",
+            DRAFT_CLEAR_SENTINEL,
+            "Hello",
+            " world",
+        ] {
+            tx.send(delta.to_string()).await.unwrap();
+        }
+        drop(tx);
+        let mut plain = Vec::new();
+        render_deltas(rx, &mut plain, false).await;
+        assert_eq!(
+            String::from_utf8(plain).unwrap(),
+            "\u{1f914} Thinking...\n\u{23f3} shell: ls\nHello world"
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        tx.send("\u{1f914} Thinking...\n".to_string())
+            .await
+            .unwrap();
+        tx.send("Hi".to_string()).await.unwrap();
+        drop(tx);
+        let mut animated = Vec::new();
+        render_deltas(rx, &mut animated, true).await;
+        let animated = String::from_utf8(animated).unwrap();
+        assert!(!animated.contains(THINKING_PREFIX));
+        assert!(animated.ends_with(&format!("{CLEAR_LINE}Hi")));
+    }
+
     #[tokio::test]
     async fn cli_channel_health_check() {
         let ch = CliChannel::new();
@@ -111,6 +213,8 @@ mod tests {
             channel: "cli".into(),
             timestamp: 1_234_567_890,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -130,6 +234,8 @@ mod tests {
             channel: "ch".into(),
             timestamp: 0,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -0,0 +1,376 @@
+//! Conversation index with generated titles.
+//!
+//! Every channel conversation (one per conversation key, until `/new`) is
+//! recorded in `<workspace>/channels/conversations.db` with a short id and a
+//! turn count. Once it has `[channels_config.titles].after_turns` user turns,
+//! a cheap model call names it. Users list their own conversations with
+//! `/conversations`; operators list all of them with `zeroclaw history list`.
+
+use crate::config::Config;
+use crate::memory::encryption::{open_text, seal_text};
+use crate::providers::{ChatMessage, Provider};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fmt::Write;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Longest title kept.
+const MAX_TITLE_CHARS: usize = 60;
+/// Characters of each turn sent to the title model.
+const MAX_TURN_CHARS: usize = 500;
+/// Conversations listed by `/conversations`.
+const CHAT_LIST_LIMIT: usize = 10;
+
+const TITLER_SYSTEM: &str = "You name chat conversations. Reply with a title of at most six words that says what the conversation is about, in the language of the conversation. No quotes, no trailing punctuation.";
+
+/// An indexed conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversation {
+    /// Short stable id shown to users.
+    pub id: String,
+    pub channel: String,
+    pub sender: String,
+    pub title: Option<String>,
+    /// User turns so far.
+    pub turns: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Count a user turn in the active conversation for `key`, starting a new
+/// one if there is none.
+pub fn record_turn(
+    workspace_dir: &Path,
+    key: &str,
+    channel: &str,
+    sender: &str,
+) -> Result<Conversation> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(workspace_dir, |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE conversations SET turns = turns + 1, updated_at = ?2 WHERE key = ?1",
+                params![key, now],
+            )
+            .context("Failed to update conversation")?;
+        if updated == 0 {
+            let id = Uuid::new_v4().simple().to_string()[..8].to_string();
+            conn.execute(
+                "INSERT INTO conversations (id, key, channel, sender, title, turns,
+                                            created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, NULL, 1, ?5, ?5)",
+                params![id, key, channel, sender, now],
+            )
+            .context("Failed to record conversation")?;
+        }
+        conn.query_row(
+            &format!("{SELECT} WHERE key = ?1"),
+            params![key],
+            row_to_conversation,
+        )
+        .context("Failed to read conversation")
+    })
+    .and_then(|conversation| opened(workspace_dir, conversation))
+}
+
+/// Detach the active conversation for `key` so the next turn starts a new
+/// one. Used by `/new`.
+pub fn close(workspace_dir: &Path, key: &str) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "UPDATE conversations SET key = NULL WHERE key = ?1",
+            params![key],
+        )
+        .context("Failed to close conversation")?;
+        Ok(())
+    })
+}
+
+pub fn set_title(workspace_dir: &Path, id: &str, title: &str) -> Result<()> {
+    let title = seal_text(workspace_dir, title)?;
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "UPDATE conversations SET title = ?2 WHERE id = ?1",
+            params![id, title],
+        )
+        .context("Failed to save conversation title")?;
+        Ok(())
+    })
+}
+
+/// Most recently active conversations, optionally for one channel and sender.
+pub fn list(
+    workspace_dir: &Path,
+    channel: Option<&str>,
+    sender: Option<&str>,
+    limit: usize,
+) -> Result<Vec<Conversation>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{SELECT} WHERE (?1 IS NULL OR channel = ?1) AND (?2 IS NULL OR sender = ?2)
+             ORDER BY updated_at DESC LIMIT ?3"
+        ))?;
+        let rows = stmt.query_map(
+            params![channel, sender, i64::try_from(limit).unwrap_or(i64::MAX)],
+            row_to_conversation,
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list conversations")
+    })?
+    .into_iter()
+    .map(|conversation| opened(workspace_dir, conversation))
+    .collect()
+}
+
+pub fn find(workspace_dir: &Path, id: &str) -> Result<Option<Conversation>> {
+    with_connection(workspace_dir, |conn| {
+        conn.query_row(
+            &format!("{SELECT} WHERE id = ?1"),
+            params![id],
+            row_to_conversation,
+        )
+        .optional()
+        .context("Failed to read conversation")
+    })?
+    .map(|conversation| opened(workspace_dir, conversation))
+    .transpose()
+}
+
+/// `conversation` with its title decrypted.
+fn opened(workspace_dir: &Path, mut conversation: Conversation) -> Result<Conversation> {
+    conversation.title = conversation
+        .title
+        .map(|title| open_text(workspace_dir, &title))
+        .transpose()?;
+    Ok(conversation)
+}
+
+/// Ask the model for a short title for `turns`. `None` when the call fails
+/// or returns nothing usable.
+pub async fn generate_title(
+    provider: &dyn Provider,
+    model: &str,
+    turns: &[ChatMessage],
+) -> Option<String> {
+    let mut transcript = String::new();
+    for turn in turns
+        .iter()
+        .filter(|t| t.role == "user" || t.role == "assistant")
+    {
+        let _ = writeln!(
+            transcript,
+            "{}: {}",
+            turn.role,
+            truncate_with_ellipsis(turn.content.trim(), MAX_TURN_CHARS)
+        );
+    }
+    let raw = provider
+        .chat_with_system(Some(TITLER_SYSTEM), &transcript, model, 0.2)
+        .await
+        .map_err(|e| tracing::debug!("Conversation title failed: {e}"))
+        .ok()?;
+    clean_title(&raw)
+}
+
+fn clean_title(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let title = line
+        .trim_start_matches("Title:")
+        .trim()
+        .trim_matches(['"', '\'', '*'])
+        .trim_end_matches(['.', '!'])
+        .trim();
+    (!title.is_empty()).then(|| truncate_with_ellipsis(title, MAX_TITLE_CHARS))
+}
+
+/// Title or a placeholder for conversations not titled yet.
+fn display_title(conversation: &Conversation) -> &str {
+    conversation.title.as_deref().unwrap_or("(untitled)")
+}
+
+/// `/conversations` reply for one sender.
+pub fn command_response(workspace_dir: &Path, channel: &str, sender: &str) -> String {
+    let entries = match list(workspace_dir, Some(channel), Some(sender), CHAT_LIST_LIMIT) {
+        Ok(entries) => entries,
+        Err(e) => return format!("Conversations are unavailable: {e}"),
+    };
+    if entries.is_empty() {
+        return "No conversations yet.".into();
+    }
+    let mut out = String::from("Recent conversations:");
+    for entry in &entries {
+        let _ = write!(
+            out,
+            "\n- `{}` {} ({} turns, {})",
+            entry.id,
+            display_title(entry),
+            entry.turns,
+            entry.updated_at.format("%Y-%m-%d")
+        );
+    }
+    out
+}
+
+pub fn handle_command(command: crate::HistoryCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::HistoryCommands::List {
+            channel,
+            sender,
+            limit,
+        } => {
+            let entries = list(
+                &config.workspace_dir,
+                channel.as_deref(),
+                sender.as_deref(),
+                limit,
+            )?;
+            if entries.is_empty() {
+                println!("No conversations recorded yet.");
+                return Ok(());
+            }
+
+            println!("💬 Conversations ({}):", entries.len());
+            for entry in entries {
+                println!(
+                    "- {} | {} | {} / {} | {} turns | updated {}",
+                    entry.id,
+                    display_title(&entry),
+                    entry.channel,
+                    entry.sender,
+                    entry.turns,
+                    entry.updated_at.to_rfc3339(),
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+const SELECT: &str =
+    "SELECT id, channel, sender, title, turns, created_at, updated_at FROM conversations";
+
+fn row_to_conversation(row: &rusqlite::Row<'_>) -> rusqlite::Result<Conversation> {
+    let created_at: String = row.get(5)?;
+    let updated_at: String = row.get(6)?;
+    Ok(Conversation {
+        id: row.get(0)?,
+        channel: row.get(1)?,
+        sender: row.get(2)?,
+        title: row.get(3)?,
+        turns: row.get(4)?,
+        created_at: parse_timestamp(&created_at),
+        updated_at: parse_timestamp(&updated_at),
+    })
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("conversations.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open conversations DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS conversations (
+            id         TEXT PRIMARY KEY,
+            key        TEXT UNIQUE,
+            channel    TEXT NOT NULL,
+            sender     TEXT NOT NULL,
+            title      TEXT,
+            turns      INTEGER NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_conversations_sender
+            ON conversations(channel, sender, updated_at);",
+    )
+    .context("Failed to initialize conversations schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use tempfile::TempDir;
+
+    struct TitleProvider;
+
+    #[async_trait]
+    impl Provider for TitleProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            assert!(message.contains("user: plan a trip"));
+            Ok("\"Weekend trip to Porto.\"\n".into())
+        }
+    }
+
+    #[test]
+    fn turns_accumulate_until_the_conversation_is_closed() {
+        let tmp = TempDir::new().unwrap();
+        let first = record_turn(tmp.path(), "telegram_alice", "telegram", "alice").unwrap();
+        let again = record_turn(tmp.path(), "telegram_alice", "telegram", "alice").unwrap();
+        assert_eq!(first.id, again.id);
+        assert_eq!(again.turns, 2);
+
+        set_title(tmp.path(), &first.id, "Trip planning").unwrap();
+        close(tmp.path(), "telegram_alice").unwrap();
+        let fresh = record_turn(tmp.path(), "telegram_alice", "telegram", "alice").unwrap();
+        assert_ne!(fresh.id, first.id);
+        assert_eq!(fresh.turns, 1);
+        record_turn(tmp.path(), "slack_bob", "slack", "bob").unwrap();
+
+        let mine = list(tmp.path(), Some("telegram"), Some("alice"), 10).unwrap();
+        assert_eq!(mine.len(), 2);
+        assert_eq!(
+            find(tmp.path(), &first.id)
+                .unwrap()
+                .unwrap()
+                .title
+                .as_deref(),
+            Some("Trip planning")
+        );
+        assert_eq!(list(tmp.path(), None, None, 10).unwrap().len(), 3);
+
+        let reply = command_response(tmp.path(), "telegram", "alice");
+        assert!(reply.contains(&format!("`{}` Trip planning (2 turns", first.id)));
+        assert!(reply.contains(&format!("`{}` (untitled) (1 turns", fresh.id)));
+        assert_eq!(
+            command_response(tmp.path(), "discord", "alice"),
+            "No conversations yet."
+        );
+    }
+
+    #[tokio::test]
+    async fn titles_are_generated_and_cleaned() {
+        let turns = [
+            ChatMessage::system("ignored"),
+            ChatMessage::user("plan a trip"),
+            ChatMessage::assistant("Sure, where to?"),
+        ];
+        assert_eq!(
+            generate_title(&TitleProvider, "cheap", &turns)
+                .await
+                .as_deref(),
+            Some("Weekend trip to Porto")
+        );
+        assert_eq!(
+            clean_title("Title: Budget review"),
+            Some("Budget review".into())
+        );
+        assert_eq!(clean_title("  \n"), None);
+    }
+}
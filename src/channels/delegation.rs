@@ -0,0 +1,239 @@
+//! Delegation to remote agents over a shared channel.
+//!
+//! The `delegate` tool posts a task as `[zc-task:<id>] <task>` in a chat it
+//! shares with a remote agent (`[remote_agents.<name>] transport = "channel"`)
+//! and waits for the answer. A remote ZeroClaw instance starts its reply with
+//! `[zc-reply:<id>]`, which correlates it exactly; replies from other bots
+//! are matched by sender and chat, oldest pending task first. Matched replies
+//! are consumed here and never reach the local agent.
+
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+const TASK_TAG: &str = "[zc-task:";
+const REPLY_TAG: &str = "[zc-reply:";
+
+/// Why a delegated task got no answer.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DelegationError {
+    #[error("channels are not running in this process; start them with `zeroclaw daemon`")]
+    NotRunning,
+    #[error("channel `{0}` is not running")]
+    UnknownChannel(String),
+    #[error("failed to send the task: {0}")]
+    Send(String),
+    #[error("no reply within {0}s")]
+    Timeout(u64),
+}
+
+struct Pending {
+    id: String,
+    channel: String,
+    recipient: String,
+    sender: String,
+    reply: oneshot::Sender<String>,
+}
+
+type ChannelMap = HashMap<String, Arc<dyn Channel>>;
+
+static CHANNELS: LazyLock<Mutex<Option<ChannelMap>>> = LazyLock::new(|| Mutex::new(None));
+/// Tasks awaiting a reply, oldest first.
+static PENDING: LazyLock<Mutex<Vec<Pending>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Called by `start_channels` with the running channels.
+pub(super) fn register(channels: ChannelMap) {
+    *CHANNELS.lock() = Some(channels);
+}
+
+/// A channel running in this process, for other in-process senders such as
+/// scheduled deliveries.
+pub fn running_channel(name: &str) -> Option<Arc<dyn Channel>> {
+    CHANNELS.lock().as_ref()?.get(name).cloned()
+}
+
+/// Removes a task from [`PENDING`] when the request ends for any reason.
+struct PendingGuard(String);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        PENDING.lock().retain(|pending| pending.id != self.0);
+    }
+}
+
+/// Post `task` to `recipient` on `channel` and wait for `sender` to answer.
+pub async fn request(
+    channel: &str,
+    recipient: &str,
+    sender: &str,
+    task: &str,
+    timeout: Duration,
+) -> Result<String, DelegationError> {
+    let target = {
+        let channels = CHANNELS.lock();
+        let channels = channels.as_ref().ok_or(DelegationError::NotRunning)?;
+        channels
+            .get(channel)
+            .cloned()
+            .ok_or_else(|| DelegationError::UnknownChannel(channel.to_string()))?
+    };
+
+    let id = Uuid::new_v4().simple().to_string()[..12].to_string();
+    let (tx, rx) = oneshot::channel();
+    PENDING.lock().push(Pending {
+        id: id.clone(),
+        channel: channel.to_string(),
+        recipient: recipient.to_string(),
+        sender: sender.to_string(),
+        reply: tx,
+    });
+    let _guard = PendingGuard(id.clone());
+
+    target
+        .send(&SendMessage::new(
+            format!("{TASK_TAG}{id}] {task}"),
+            recipient,
+        ))
+        .await
+        .map_err(|e| DelegationError::Send(e.to_string()))?;
+
+    match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(reply)) => Ok(reply),
+        Ok(Err(_)) => Err(DelegationError::NotRunning),
+        Err(_) => Err(DelegationError::Timeout(timeout.as_secs())),
+    }
+}
+
+/// Deliver `msg` to the task it answers. Returns `true` if it was consumed.
+pub(super) fn resolve(msg: &ChannelMessage) -> bool {
+    let mut pending = PENDING.lock();
+    if pending.is_empty() {
+        return false;
+    }
+    let from_contact = |p: &Pending| p.channel == msg.channel && p.sender == msg.sender;
+
+    let tagged = parse_tag(&msg.content, REPLY_TAG);
+    let index = match tagged {
+        Some((id, _)) => pending.iter().position(|p| p.id == id && from_contact(p)),
+        None => pending
+            .iter()
+            .position(|p| from_contact(p) && p.recipient == msg.reply_target),
+    };
+    let Some(index) = index else {
+        return false;
+    };
+    let answered = pending.remove(index);
+    let reply = tagged.map_or(msg.content.as_str(), |(_, rest)| rest);
+    let _ = answered.reply.send(reply.trim().to_string());
+    true
+}
+
+/// The tag to start a reply with when `content` is a delegated task.
+pub(super) fn reply_tag(content: &str) -> Option<String> {
+    parse_tag(content, TASK_TAG).map(|(id, _)| format!("{REPLY_TAG}{id}]"))
+}
+
+/// Split a leading `<tag><id>]` marker into the id and the rest of the text.
+fn parse_tag<'a>(content: &'a str, tag: &str) -> Option<(&'a str, &'a str)> {
+    let rest = content.trim_start().strip_prefix(tag)?;
+    let (id, rest) = rest.split_once(']')?;
+    (!id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric())).then_some((id, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct Outbox(Mutex<Vec<SendMessage>>);
+
+    #[async_trait]
+    impl Channel for Outbox {
+        fn name(&self) -> &str {
+            "matrix"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.0.lock().push(message.clone());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn inbound(sender: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "m".into(),
+            sender: sender.into(),
+            reply_target: "!room".into(),
+            content: content.into(),
+            channel: "matrix".into(),
+            timestamp: 0,
+            thread_ts: None,
+            location: None,
+            payload: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn tagged_replies_resolve_the_matching_task() {
+        let outbox = Arc::new(Outbox::default());
+        register(HashMap::from([(
+            "matrix".to_string(),
+            outbox.clone() as Arc<dyn Channel>,
+        )]));
+
+        let task = tokio::spawn(request(
+            "matrix",
+            "!room",
+            "@vps:example.org",
+            "check disk usage",
+            Duration::from_secs(5),
+        ));
+        let sent = loop {
+            if let Some(sent) = outbox.0.lock().first().cloned() {
+                break sent;
+            }
+            tokio::task::yield_now().await;
+        };
+        let tag = reply_tag(&sent.content).unwrap();
+        assert!(sent.content.ends_with("] check disk usage"));
+
+        assert!(!resolve(&inbound(
+            "@mallory:example.org",
+            &format!("{tag} 0%")
+        )));
+        assert!(!resolve(&inbound(
+            "@vps:example.org",
+            "[zc-reply:000000000000] x"
+        )));
+        assert!(resolve(&inbound(
+            "@vps:example.org",
+            &format!("{tag} 42% used")
+        )));
+        assert_eq!(task.await.unwrap().unwrap(), "42% used");
+        assert!(PENDING.lock().is_empty());
+
+        let timed_out = request(
+            "matrix",
+            "!room",
+            "@vps:example.org",
+            "again",
+            Duration::from_millis(10),
+        )
+        .await;
+        assert_eq!(timed_out, Err(DelegationError::Timeout(0)));
+        assert!(PENDING.lock().is_empty());
+        assert!(reply_tag("no task here").is_none());
+    }
+}
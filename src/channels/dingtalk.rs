@@ -275,6 +275,8 @@ impl Channel for DingTalkChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        location: None,
+                        payload: None,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
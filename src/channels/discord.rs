@@ -1,11 +1,16 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::discord_voice::{self, VoiceSession};
+use super::emoji::{self, EmojiTarget};
+use super::links;
+use super::rich_text::{self, RichTextFormat};
+use super::traits::{
+    Channel, ChannelMessage, MessagePayload, Poll, PollVote, Reaction, SendMessage, Sticker,
+};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
-use parking_lot::Mutex;
 use reqwest::multipart::{Form, Part};
 use serde_json::json;
-use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
@@ -16,7 +21,11 @@ pub struct DiscordChannel {
     allowed_users: Vec<String>,
     listen_to_bots: bool,
     mention_only: bool,
-    typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    link_previews: bool,
+    /// Custom status shown on the bot; sent on identify and when it changes.
+    presence: tokio::sync::watch::Sender<Option<String>>,
+    /// Voice announcements; `None` unless `[channels_config.discord.voice]` is set.
+    voice: Option<(crate::config::DiscordVoiceConfig, crate::config::TtsConfig)>,
 }
 
 impl DiscordChannel {
@@ -33,10 +42,29 @@ impl DiscordChannel {
             allowed_users,
             listen_to_bots,
             mention_only,
-            typing_handles: Mutex::new(HashMap::new()),
+            link_previews: true,
+            presence: tokio::sync::watch::channel(None).0,
+            voice: None,
         }
     }
 
+    /// Let Discord embed links in outgoing messages. When disabled, URLs are
+    /// wrapped in `<...>`.
+    pub fn with_link_previews(mut self, enabled: bool) -> Self {
+        self.link_previews = enabled;
+        self
+    }
+
+    /// Join `voice.channel_id` and speak announcements there with `tts`.
+    pub fn with_voice(
+        mut self,
+        voice: crate::config::DiscordVoiceConfig,
+        tts: crate::config::TtsConfig,
+    ) -> Self {
+        self.voice = Some((voice, tts));
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
@@ -45,7 +73,98 @@ impl DiscordChannel {
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        let listed = self.allowed_users.iter().any(|u| u == "*" || u == user_id);
+        super::access::check("discord", user_id, listed)
+    }
+
+    /// Build a message from a `MESSAGE_POLL_VOTE_ADD` event. Votes carry only
+    /// the answer id, so the option is labelled by number. Mention-only
+    /// channels ignore votes, since they cannot mention the bot.
+    fn parse_poll_vote(&self, d: &serde_json::Value, bot_user_id: &str) -> Option<ChannelMessage> {
+        if self.mention_only {
+            return None;
+        }
+        let field = |key: &str| d.get(key).and_then(serde_json::Value::as_str);
+        let user_id = field("user_id")?;
+        if user_id == bot_user_id || !self.is_user_allowed(user_id) {
+            return None;
+        }
+        if let (Some(gid), Some(msg_guild)) = (self.guild_id.as_deref(), field("guild_id")) {
+            if gid != msg_guild {
+                return None;
+            }
+        }
+        let channel_id = field("channel_id")?;
+        let message_id = field("message_id")?;
+        let answer_id = d.get("answer_id").and_then(serde_json::Value::as_u64)?;
+        let payload = MessagePayload::PollVote(PollVote {
+            poll_id: message_id.to_string(),
+            options: vec![format!("option {answer_id}")],
+        });
+
+        Some(ChannelMessage {
+            id: format!("discord_{message_id}"),
+            sender: user_id.to_string(),
+            reply_target: channel_id.to_string(),
+            content: payload.to_string(),
+            channel: "discord".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: field("guild_id").map(|_| channel_id.to_string()),
+            location: None,
+            payload: Some(payload),
+        })
+    }
+
+    /// Build a message from a `MESSAGE_REACTION_ADD`/`_REMOVE` event. Custom
+    /// guild emoji are labelled `:name:`.
+    fn parse_reaction(
+        &self,
+        d: &serde_json::Value,
+        bot_user_id: &str,
+        removed: bool,
+    ) -> Option<ChannelMessage> {
+        let field = |key: &str| d.get(key).and_then(serde_json::Value::as_str);
+        let user_id = field("user_id")?;
+        if user_id == bot_user_id || !self.is_user_allowed(user_id) {
+            return None;
+        }
+        if let (Some(gid), Some(msg_guild)) = (self.guild_id.as_deref(), field("guild_id")) {
+            if gid != msg_guild {
+                return None;
+            }
+        }
+        let channel_id = field("channel_id")?;
+        let message_id = field("message_id")?;
+        let emoji = d.get("emoji")?;
+        let name = emoji.get("name").and_then(serde_json::Value::as_str)?;
+        let emoji = if emoji.get("id").is_some_and(|id| !id.is_null()) {
+            format!(":{name}:")
+        } else {
+            name.to_string()
+        };
+        let payload = MessagePayload::Reaction(Reaction {
+            message_id: message_id.to_string(),
+            emoji,
+            removed,
+        });
+
+        Some(ChannelMessage {
+            id: format!("discord_{message_id}"),
+            sender: user_id.to_string(),
+            reply_target: channel_id.to_string(),
+            content: payload.to_string(),
+            channel: "discord".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: field("guild_id").map(|_| channel_id.to_string()),
+            location: None,
+            payload: Some(payload),
+        })
     }
 
     fn bot_user_id_from_token(token: &str) -> Option<String> {
@@ -230,14 +349,28 @@ fn with_inline_attachment_urls(
     lines.join("\n")
 }
 
+/// Message create payload, quoting `in_reply_to_id` (a `discord_`-prefixed or raw id) when set.
+fn discord_message_payload(content: &str, in_reply_to_id: Option<&str>) -> serde_json::Value {
+    let mut payload = json!({ "content": content });
+    if let Some(id) = in_reply_to_id {
+        let raw_id = id.strip_prefix("discord_").unwrap_or(id);
+        payload["message_reference"] = json!({
+            "message_id": raw_id,
+            "fail_if_not_exists": false
+        });
+    }
+    payload
+}
+
 async fn send_discord_message_json(
     client: &reqwest::Client,
     bot_token: &str,
     recipient: &str,
     content: &str,
-) -> anyhow::Result<()> {
+    in_reply_to_id: Option<&str>,
+) -> anyhow::Result<Option<String>> {
     let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
-    let body = json!({ "content": content });
+    let body = discord_message_payload(content, in_reply_to_id);
 
     let resp = client
         .post(&url)
@@ -255,7 +388,7 @@ async fn send_discord_message_json(
         anyhow::bail!("Discord send message failed ({status}): {err}");
     }
 
-    Ok(())
+    Ok(sent_message_id(resp).await)
 }
 
 async fn send_discord_message_with_files(
@@ -264,10 +397,14 @@ async fn send_discord_message_with_files(
     recipient: &str,
     content: &str,
     files: &[PathBuf],
-) -> anyhow::Result<()> {
+    in_reply_to_id: Option<&str>,
+) -> anyhow::Result<Option<String>> {
     let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
 
-    let mut form = Form::new().text("payload_json", json!({ "content": content }).to_string());
+    let mut form = Form::new().text(
+        "payload_json",
+        discord_message_payload(content, in_reply_to_id).to_string(),
+    );
 
     for (idx, path) in files.iter().enumerate() {
         let bytes = tokio::fs::read(path).await.map_err(|error| {
@@ -303,7 +440,25 @@ async fn send_discord_message_with_files(
         anyhow::bail!("Discord send message with files failed ({status}): {err}");
     }
 
-    Ok(())
+    Ok(sent_message_id(resp).await)
+}
+
+/// Id of the message created by a successful send, from the response body.
+async fn sent_message_id(resp: reqwest::Response) -> Option<String> {
+    let body: serde_json::Value = resp.json().await.ok()?;
+    body.get("id")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+}
+
+/// Gateway presence showing `status` as the bot's custom status.
+fn presence_payload(status: &str) -> serde_json::Value {
+    json!({
+        "since": null,
+        "activities": [{ "name": "Custom Status", "type": 4, "state": status }],
+        "status": "online",
+        "afk": false
+    })
 }
 
 const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
@@ -362,21 +517,8 @@ fn split_message_for_discord(message: &str) -> Vec<String> {
     chunks
 }
 
-fn pick_uniform_index(len: usize) -> usize {
-    debug_assert!(len > 0);
-    let upper = len as u64;
-    let reject_threshold = (u64::MAX / upper) * upper;
-
-    loop {
-        let value = rand::random::<u64>();
-        if value < reject_threshold {
-            return (value % upper) as usize;
-        }
-    }
-}
-
 fn random_discord_ack_reaction() -> &'static str {
-    DISCORD_ACK_REACTIONS[pick_uniform_index(DISCORD_ACK_REACTIONS.len())]
+    emoji::random_from_pool(DISCORD_ACK_REACTIONS)
 }
 
 /// URL-encode a Unicode emoji for use in Discord reaction API paths.
@@ -398,12 +540,55 @@ fn encode_emoji_for_discord(emoji: &str) -> String {
 
 fn discord_reaction_url(channel_id: &str, message_id: &str, emoji: &str) -> String {
     let raw_id = message_id.strip_prefix("discord_").unwrap_or(message_id);
-    let encoded_emoji = encode_emoji_for_discord(emoji);
+    // Reactions take Unicode; accept `:shortcode:` from callers too.
+    let emoji = emoji::for_target(emoji, EmojiTarget::Unicode).unwrap_or_default();
+    let encoded_emoji = encode_emoji_for_discord(&emoji);
     format!(
         "https://discord.com/api/v10/channels/{channel_id}/messages/{raw_id}/reactions/{encoded_emoji}/@me"
     )
 }
 
+/// Structured form of a message's sticker or poll, if it has one.
+fn parse_message_payload(d: &serde_json::Value) -> Option<MessagePayload> {
+    if let Some(poll) = d.get("poll") {
+        let options = poll
+            .get("answers")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|answer| {
+                answer
+                    .get("poll_media")
+                    .and_then(|media| media.get("text"))
+                    .and_then(serde_json::Value::as_str)
+            })
+            .map(str::to_string)
+            .collect();
+        return Some(MessagePayload::Poll(Poll {
+            question: poll
+                .get("question")
+                .and_then(|question| question.get("text"))
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            options,
+            allows_multiple_answers: poll
+                .get("allow_multiselect")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false),
+        }));
+    }
+
+    let sticker = d.get("sticker_items")?.as_array()?.first()?;
+    Some(MessagePayload::Sticker(Sticker {
+        emoji: None,
+        name: sticker
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string),
+    }))
+}
+
 fn mention_tags(bot_user_id: &str) -> [String; 2] {
     [format!("<@{bot_user_id}>"), format!("<@!{bot_user_id}>")]
 }
@@ -486,8 +671,18 @@ impl Channel for DiscordChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.send_tracked(message).await.map(|_| ())
+    }
+
+    /// Returns the id of the first message sent, which reaction events refer to.
+    async fn send_tracked(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         let raw_content = super::strip_tool_call_tags(&message.content);
         let (cleaned_content, parsed_attachments) = parse_attachment_markers(&raw_content);
+        let mut cleaned_content =
+            rich_text::render(&cleaned_content, RichTextFormat::DiscordMarkdown);
+        if !self.link_previews {
+            cleaned_content = links::suppress_discord_embeds(&cleaned_content);
+        }
         let (mut local_files, remote_urls, unresolved_markers) =
             classify_outgoing_attachments(&parsed_attachments);
 
@@ -511,20 +706,33 @@ impl Channel for DiscordChannel {
             with_inline_attachment_urls(&cleaned_content, &remote_urls, &unresolved_markers);
         let chunks = split_message_for_discord(&content);
         let client = self.http_client();
+        let mut first_id = None;
 
         for (i, chunk) in chunks.iter().enumerate() {
-            if i == 0 && !local_files.is_empty() {
+            // Only the first chunk quotes the message being answered.
+            let in_reply_to_id = message.in_reply_to_id.as_deref().filter(|_| i == 0);
+            let sent_id = if i == 0 && !local_files.is_empty() {
                 send_discord_message_with_files(
                     &client,
                     &self.bot_token,
                     &message.recipient,
                     chunk,
                     &local_files,
+                    in_reply_to_id,
                 )
-                .await?;
+                .await?
             } else {
-                send_discord_message_json(&client, &self.bot_token, &message.recipient, chunk)
-                    .await?;
+                send_discord_message_json(
+                    &client,
+                    &self.bot_token,
+                    &message.recipient,
+                    chunk,
+                    in_reply_to_id,
+                )
+                .await?
+            };
+            if i == 0 {
+                first_id = sent_id;
             }
 
             if i < chunks.len() - 1 {
@@ -532,7 +740,7 @@ impl Channel for DiscordChannel {
             }
         }
 
-        Ok(())
+        Ok(first_id)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -569,12 +777,33 @@ impl Channel for DiscordChannel {
             .and_then(serde_json::Value::as_u64)
             .unwrap_or(41250);
 
+        // Payloads other components send on this gateway connection (voice joins).
+        let (gateway_tx, mut gateway_rx) = tokio::sync::mpsc::unbounded_channel();
+        let voice = self.voice.as_ref().and_then(|(voice, tts)| {
+            let guild_id = voice.guild_id.clone().or_else(|| self.guild_id.clone())?;
+            let session = Arc::new(VoiceSession::new(
+                voice.clone(),
+                guild_id,
+                bot_user_id.clone(),
+                tts.clone(),
+                gateway_tx.clone(),
+            ));
+            discord_voice::register(Arc::clone(&session));
+            Some(session)
+        });
+        // GUILDS | GUILD_MESSAGES | GUILD_MESSAGE_REACTIONS | DIRECT_MESSAGES | DIRECT_MESSAGE_REACTIONS | MESSAGE_CONTENT | GUILD_MESSAGE_POLLS | DIRECT_MESSAGE_POLLS
+        let mut intents: u64 = 50_378_241;
+        if voice.is_some() {
+            intents |= discord_voice::GUILD_VOICE_STATES_INTENT;
+        }
+
         // Send Identify (opcode 2)
-        let identify = json!({
+        let mut presence_rx = self.presence.subscribe();
+        let mut identify = json!({
             "op": 2,
             "d": {
                 "token": self.bot_token,
-                "intents": 37377, // GUILDS | GUILD_MESSAGES | MESSAGE_CONTENT | DIRECT_MESSAGES
+                "intents": intents,
                 "properties": {
                     "os": "linux",
                     "browser": "zeroclaw",
@@ -582,6 +811,10 @@ impl Channel for DiscordChannel {
                 }
             }
         });
+        let status = presence_rx.borrow_and_update().clone();
+        if let Some(status) = status {
+            identify["d"]["presence"] = presence_payload(&status);
+        }
         write
             .send(Message::Text(identify.to_string().into()))
             .await?;
@@ -617,6 +850,21 @@ impl Channel for DiscordChannel {
                         break;
                     }
                 }
+                Ok(()) = presence_rx.changed() => {
+                    let Some(status) = presence_rx.borrow_and_update().clone() else {
+                        continue;
+                    };
+                    // Op 3: Presence Update
+                    let update = json!({"op": 3, "d": presence_payload(&status)});
+                    if write.send(Message::Text(update.to_string().into())).await.is_err() {
+                        break;
+                    }
+                }
+                Some(payload) = gateway_rx.recv() => {
+                    if write.send(Message::Text(payload.to_string().into())).await.is_err() {
+                        break;
+                    }
+                }
                 msg = read.next() => {
                     let msg = match msg {
                         Some(Ok(Message::Text(t))) => t,
@@ -661,6 +909,42 @@ impl Channel for DiscordChannel {
 
                     // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
+                    if let Some(voice) = voice.as_ref() {
+                        match event_type {
+                            "READY" => {
+                                voice.join();
+                                continue;
+                            }
+                            "VOICE_STATE_UPDATE" | "VOICE_SERVER_UPDATE" => {
+                                if let Some(d) = event.get("d") {
+                                    voice.handle_dispatch(event_type, d).await;
+                                }
+                                continue;
+                            }
+                            _ => {}
+                        }
+                    }
+                    if event_type == "MESSAGE_POLL_VOTE_ADD" {
+                        let vote = event.get("d").and_then(|d| self.parse_poll_vote(d, &bot_user_id));
+                        if let Some(vote) = vote {
+                            if tx.send(vote).await.is_err() {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+                    if matches!(event_type, "MESSAGE_REACTION_ADD" | "MESSAGE_REACTION_REMOVE") {
+                        let removed = event_type == "MESSAGE_REACTION_REMOVE";
+                        let reaction = event
+                            .get("d")
+                            .and_then(|d| self.parse_reaction(d, &bot_user_id, removed));
+                        if let Some(reaction) = reaction {
+                            if tx.send(reaction).await.is_err() {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
                     if event_type != "MESSAGE_CREATE" {
                         continue;
                     }
@@ -683,6 +967,11 @@ impl Channel for DiscordChannel {
                     // Sender validation
                     if !self.is_user_allowed(author_id) {
                         tracing::warn!("Discord: ignoring message from unauthorized user: {author_id}");
+                        if let Some(channel_id) = d.get("channel_id").and_then(serde_json::Value::as_str) {
+                            if let Some(reply) = super::pairing::challenge("discord", author_id, channel_id).await {
+                                let _ = self.send(&SendMessage::new(reply, channel_id)).await;
+                            }
+                        }
                         continue;
                     }
 
@@ -698,10 +987,44 @@ impl Channel for DiscordChannel {
                     }
 
                     let content = d.get("content").and_then(|c| c.as_str()).unwrap_or("");
-                    let Some(clean_content) =
-                        normalize_incoming_content(content, self.mention_only, &bot_user_id)
-                    else {
+                    if let (Some(voice), Some(text)) = (voice.as_ref(), discord_voice::parse_speak_command(content)) {
+                        let member_roles: Vec<String> = d
+                            .pointer("/member/roles")
+                            .and_then(serde_json::Value::as_array)
+                            .map(|roles| roles.iter().filter_map(|r| r.as_str().map(str::to_string)).collect())
+                            .unwrap_or_default();
+                        let channel_id = d.get("channel_id").and_then(serde_json::Value::as_str).unwrap_or("").to_string();
+                        let reply_channel = DiscordChannel::new(
+                            self.bot_token.clone(),
+                            self.guild_id.clone(),
+                            self.allowed_users.clone(),
+                            self.listen_to_bots,
+                            self.mention_only,
+                        );
+                        let voice = Arc::clone(voice);
+                        let text = text.to_string();
+                        // Speech synthesis takes seconds; keep the gateway loop responsive.
+                        tokio::spawn(async move {
+                            let reply = voice.speak_command(&member_roles, &text).await;
+                            if !channel_id.is_empty() {
+                                let _ = reply_channel.send(&SendMessage::new(reply, &channel_id)).await;
+                            }
+                        });
                         continue;
+                    }
+                    let payload = parse_message_payload(d);
+                    // Sticker- or poll-only messages have no text, so they are
+                    // placeholders unless mention_only requires a mention.
+                    let clean_content = match (
+                        normalize_incoming_content(content, self.mention_only, &bot_user_id),
+                        &payload,
+                    ) {
+                        (Some(text), Some(payload)) => format!("{text}\n\n{payload}"),
+                        (Some(text), None) => text,
+                        (None, Some(payload)) if content.is_empty() && !self.mention_only => {
+                            payload.to_string()
+                        }
+                        (None, _) => continue,
                     };
 
                     let attachment_text = {
@@ -770,7 +1093,15 @@ impl Channel for DiscordChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
-                        thread_ts: None,
+                        // Guild channels and threads each keep their own conversation
+                        // context; Discord threads have their own channel id.
+                        thread_ts: d
+                            .get("guild_id")
+                            .and_then(serde_json::Value::as_str)
+                            .filter(|_| !channel_id.is_empty())
+                            .map(|_| channel_id.clone()),
+                        location: None,
+                        payload,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -794,36 +1125,31 @@ impl Channel for DiscordChannel {
     }
 
     async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
-        self.stop_typing(recipient).await?;
-
-        let client = self.http_client();
-        let token = self.bot_token.clone();
-        let channel_id = recipient.to_string();
-
-        let handle = tokio::spawn(async move {
-            let url = format!("https://discord.com/api/v10/channels/{channel_id}/typing");
-            loop {
-                let _ = client
-                    .post(&url)
-                    .header("Authorization", format!("Bot {token}"))
-                    .send()
-                    .await;
-                tokio::time::sleep(std::time::Duration::from_secs(8)).await;
-            }
-        });
+        let url = format!("https://discord.com/api/v10/channels/{recipient}/typing");
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Discord trigger typing failed ({})", resp.status());
+        }
+        Ok(())
+    }
 
-        let mut guard = self.typing_handles.lock();
-        guard.insert(recipient.to_string(), handle);
+    fn supports_presence(&self) -> bool {
+        true
+    }
 
+    async fn set_presence(&self, status: &str) -> anyhow::Result<()> {
+        self.presence.send_replace(Some(status.to_string()));
         Ok(())
     }
 
-    async fn stop_typing(&self, recipient: &str) -> anyhow::Result<()> {
-        let mut guard = self.typing_handles.lock();
-        if let Some(handle) = guard.remove(recipient) {
-            handle.abort();
-        }
-        Ok(())
+    /// Discord shows typing for about 10 seconds after each trigger.
+    fn typing_refresh_interval(&self) -> Option<std::time::Duration> {
+        Some(std::time::Duration::from_secs(8))
     }
 
     async fn add_reaction(
@@ -880,6 +1206,33 @@ impl Channel for DiscordChannel {
 
         Ok(())
     }
+
+    fn supports_message_deletion(&self) -> bool {
+        true
+    }
+
+    async fn delete_message(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        let raw_id = message_id.strip_prefix("discord_").unwrap_or(message_id);
+        let url = format!("https://discord.com/api/v10/channels/{recipient}/messages/{raw_id}");
+
+        let resp = self
+            .http_client()
+            .delete(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp
+                .text()
+                .await
+                .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            anyhow::bail!("Discord delete message failed ({status}): {err}");
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1014,6 +1367,94 @@ mod tests {
         assert!(cleaned.is_none());
     }
 
+    #[test]
+    fn parse_message_payload_reads_polls_and_stickers() {
+        let poll = parse_message_payload(&json!({
+            "content": "",
+            "poll": {
+                "question": { "text": "Lunch?" },
+                "answers": [
+                    { "answer_id": 1, "poll_media": { "text": "Pizza" } },
+                    { "answer_id": 2, "poll_media": { "text": "Sushi" } }
+                ],
+                "allow_multiselect": false
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            poll.to_string(),
+            "[Poll: Lunch? — options: 1. Pizza | 2. Sushi]"
+        );
+
+        let sticker = parse_message_payload(&json!({
+            "sticker_items": [{ "id": "1", "name": "wave", "format_type": 1 }]
+        }))
+        .unwrap();
+        assert_eq!(sticker.to_string(), "[Sticker: wave]");
+
+        assert!(parse_message_payload(&json!({ "content": "hi" })).is_none());
+    }
+
+    #[test]
+    fn parse_poll_vote_respects_allowlist_and_mention_only() {
+        let vote = json!({
+            "user_id": "42",
+            "channel_id": "900",
+            "message_id": "555",
+            "guild_id": "1",
+            "answer_id": 2
+        });
+
+        let ch = DiscordChannel::new("t".into(), None, vec!["42".into()], false, false);
+        let msg = ch.parse_poll_vote(&vote, "7").unwrap();
+        assert_eq!(msg.content, "[Poll vote: option 2]");
+        assert_eq!(msg.reply_target, "900");
+        assert_eq!(msg.thread_ts.as_deref(), Some("900"));
+        assert_eq!(msg.id, "discord_555");
+
+        let stranger = DiscordChannel::new("t".into(), None, vec!["1".into()], false, false);
+        assert!(stranger.parse_poll_vote(&vote, "7").is_none());
+        let mention_only = DiscordChannel::new("t".into(), None, vec!["*".into()], false, true);
+        assert!(mention_only.parse_poll_vote(&vote, "7").is_none());
+    }
+
+    #[test]
+    fn parse_reaction_skips_bot_and_labels_custom_emoji() {
+        let ch = DiscordChannel::new("t".into(), None, vec!["*".into()], false, false);
+        let event = |user: &str, emoji: serde_json::Value| {
+            json!({
+                "user_id": user,
+                "channel_id": "900",
+                "message_id": "555",
+                "emoji": emoji
+            })
+        };
+
+        let added = ch
+            .parse_reaction(
+                &event("42", json!({ "id": null, "name": "👍" })),
+                "7",
+                false,
+            )
+            .unwrap();
+        assert_eq!(added.content, "[Reaction: 👍]");
+        assert_eq!(added.reply_target, "900");
+        assert!(added.thread_ts.is_none());
+
+        let custom = ch
+            .parse_reaction(
+                &event("42", json!({ "id": "1", "name": "partyblob" })),
+                "7",
+                true,
+            )
+            .unwrap();
+        assert_eq!(custom.content, "[Reaction removed: :partyblob:]");
+
+        assert!(ch
+            .parse_reaction(&event("7", json!({ "id": null, "name": "👀" })), "7", false)
+            .is_none());
+    }
+
     // Message splitting tests
 
     #[test]
@@ -1164,54 +1605,28 @@ mod tests {
     }
 
     #[test]
-    fn typing_handles_start_empty() {
-        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
-        let guard = ch.typing_handles.lock();
-        assert!(guard.is_empty());
-    }
-
-    #[tokio::test]
-    async fn start_typing_sets_handle() {
-        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
-        let _ = ch.start_typing("123456").await;
-        let guard = ch.typing_handles.lock();
-        assert!(guard.contains_key("123456"));
+    fn presence_payload_sets_custom_status() {
+        let payload = presence_payload("idle");
+        assert_eq!(payload["activities"][0]["type"], 4);
+        assert_eq!(payload["activities"][0]["state"], "idle");
+        assert_eq!(payload["status"], "online");
     }
 
-    #[tokio::test]
-    async fn stop_typing_clears_handle() {
+    #[test]
+    fn typing_refreshes_before_discord_expires_it() {
         let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
-        let _ = ch.start_typing("123456").await;
-        let _ = ch.stop_typing("123456").await;
-        let guard = ch.typing_handles.lock();
-        assert!(!guard.contains_key("123456"));
+        assert!(ch
+            .typing_refresh_interval()
+            .is_some_and(|interval| interval < std::time::Duration::from_secs(10)));
     }
 
     #[tokio::test]
-    async fn stop_typing_is_idempotent() {
+    async fn stop_typing_is_a_no_op() {
         let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
         assert!(ch.stop_typing("123456").await.is_ok());
         assert!(ch.stop_typing("123456").await.is_ok());
     }
 
-    #[tokio::test]
-    async fn concurrent_typing_handles_are_independent() {
-        let ch = DiscordChannel::new("fake".into(), None, vec![], false, false);
-        let _ = ch.start_typing("111").await;
-        let _ = ch.start_typing("222").await;
-        {
-            let guard = ch.typing_handles.lock();
-            assert_eq!(guard.len(), 2);
-            assert!(guard.contains_key("111"));
-            assert!(guard.contains_key("222"));
-        }
-        // Stopping one does not affect the other
-        let _ = ch.stop_typing("111").await;
-        let guard = ch.typing_handles.lock();
-        assert_eq!(guard.len(), 1);
-        assert!(guard.contains_key("222"));
-    }
-
     // ── Emoji encoding for reactions ──────────────────────────────
 
     #[test]
@@ -1253,6 +1668,18 @@ mod tests {
             url,
             "https://discord.com/api/v10/channels/123/messages/456/reactions/%F0%9F%91%80/@me"
         );
+        assert_eq!(discord_reaction_url("123", "456", ":eyes:"), url);
+    }
+
+    #[test]
+    fn discord_message_payload_adds_message_reference_for_replies() {
+        let payload = discord_message_payload("hi", Some("discord_456"));
+        assert_eq!(payload["content"], "hi");
+        assert_eq!(payload["message_reference"]["message_id"], "456");
+        assert_eq!(payload["message_reference"]["fail_if_not_exists"], false);
+
+        let payload = discord_message_payload("hi", None);
+        assert!(payload.get("message_reference").is_none());
     }
 
     // ── Message ID edge cases ─────────────────────────────────────
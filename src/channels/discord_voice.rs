@@ -0,0 +1,329 @@
+//! Discord voice announcements (`[channels_config.discord.voice]`).
+//!
+//! The Discord channel asks to join the configured voice channel over its
+//! gateway connection (op 4) and passes the voice session and server details
+//! it gets back to a songbird driver, which holds the voice connection.
+//! Announcements are synthesized with `[tts]` and played in that channel:
+//! cron and alert deliveries to the `discord_voice` channel, and
+//! `/speak <text>` from guild members with one of `admin_roles`.
+//!
+//! songbird is heavy, so the voice connection is behind the
+//! `channel-discord-voice` build feature. Without it, announcements fail
+//! with a message explaining how to enable it.
+
+use crate::config::{DiscordVoiceConfig, TtsConfig};
+use anyhow::{bail, Result};
+use parking_lot::Mutex;
+use serde_json::json;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Gateway intent that delivers voice state events (`GUILD_VOICE_STATES`).
+pub const GUILD_VOICE_STATES_INTENT: u64 = 1 << 7;
+
+/// Text after `/speak`, if `content` is a speak command.
+pub fn parse_speak_command(content: &str) -> Option<&str> {
+    let rest = content.trim().strip_prefix("/speak")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(rest.trim())
+}
+
+/// Whether a member with `member_roles` may use `/speak`.
+pub fn is_voice_admin(member_roles: &[String], admin_roles: &[String]) -> bool {
+    member_roles.iter().any(|role| admin_roles.contains(role))
+}
+
+/// Gateway op 4 payload joining a voice channel.
+fn voice_state_update(guild_id: &str, channel_id: &str) -> serde_json::Value {
+    json!({
+        "op": 4,
+        "d": {
+            "guild_id": guild_id,
+            "channel_id": channel_id,
+            "self_mute": false,
+            "self_deaf": true
+        }
+    })
+}
+
+/// Voice session and server details collected from gateway dispatches.
+#[derive(Debug, Default)]
+struct PendingConnection {
+    session_id: Option<String>,
+    token: Option<String>,
+    endpoint: Option<String>,
+}
+
+/// The bot's voice presence in one guild, fed by the Discord gateway loop.
+pub struct VoiceSession {
+    config: DiscordVoiceConfig,
+    guild_id: String,
+    bot_user_id: String,
+    tts: TtsConfig,
+    /// Payloads the gateway loop sends on the main connection.
+    gateway: UnboundedSender<serde_json::Value>,
+    pending: Mutex<PendingConnection>,
+    #[cfg(feature = "channel-discord-voice")]
+    driver: tokio::sync::Mutex<songbird::Driver>,
+    #[cfg(feature = "channel-discord-voice")]
+    connected: std::sync::atomic::AtomicBool,
+}
+
+impl VoiceSession {
+    pub fn new(
+        config: DiscordVoiceConfig,
+        guild_id: String,
+        bot_user_id: String,
+        tts: TtsConfig,
+        gateway: UnboundedSender<serde_json::Value>,
+    ) -> Self {
+        Self {
+            config,
+            guild_id,
+            bot_user_id,
+            tts,
+            gateway,
+            pending: Mutex::default(),
+            #[cfg(feature = "channel-discord-voice")]
+            driver: tokio::sync::Mutex::new(songbird::Driver::new(songbird::Config::default())),
+            #[cfg(feature = "channel-discord-voice")]
+            connected: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    pub fn channel_id(&self) -> &str {
+        &self.config.channel_id
+    }
+
+    /// Ask the gateway to join the voice channel. Called after every
+    /// (re)identify, since a new gateway session drops the voice state.
+    pub fn join(&self) {
+        *self.pending.lock() = PendingConnection::default();
+        if cfg!(not(feature = "channel-discord-voice")) {
+            return;
+        }
+        let payload = voice_state_update(&self.guild_id, &self.config.channel_id);
+        if self.gateway.send(payload).is_err() {
+            tracing::warn!("Discord voice: gateway closed before joining the voice channel");
+        }
+    }
+
+    /// Handle a `VOICE_STATE_UPDATE` or `VOICE_SERVER_UPDATE` dispatch, and
+    /// connect once both halves of the connection details have arrived.
+    pub async fn handle_dispatch(&self, event_type: &str, d: &serde_json::Value) {
+        let str_field = |key: &str| d.get(key).and_then(serde_json::Value::as_str);
+        if str_field("guild_id") != Some(self.guild_id.as_str()) {
+            return;
+        }
+        {
+            let mut pending = self.pending.lock();
+            match event_type {
+                "VOICE_STATE_UPDATE" if str_field("user_id") == Some(self.bot_user_id.as_str()) => {
+                    pending.session_id = str_field("session_id").map(str::to_string);
+                }
+                "VOICE_SERVER_UPDATE" => {
+                    pending.token = str_field("token").map(str::to_string);
+                    // A null endpoint means the voice server is being reallocated.
+                    pending.endpoint = str_field("endpoint").map(str::to_string);
+                }
+                _ => return,
+            }
+        }
+        self.connect_if_ready().await;
+    }
+
+    #[cfg(feature = "channel-discord-voice")]
+    async fn connect_if_ready(&self) {
+        use songbird::id::{ChannelId, GuildId, UserId};
+        use std::num::NonZeroU64;
+        use std::sync::atomic::Ordering;
+
+        let (session_id, token, endpoint) = {
+            let pending = self.pending.lock();
+            match (&pending.session_id, &pending.token, &pending.endpoint) {
+                (Some(session_id), Some(token), Some(endpoint)) => {
+                    (session_id.clone(), token.clone(), endpoint.clone())
+                }
+                _ => return,
+            }
+        };
+        let id = |raw: &str| raw.parse::<u64>().ok().and_then(NonZeroU64::new);
+        let (Some(guild), Some(channel), Some(user)) = (
+            id(&self.guild_id),
+            id(&self.config.channel_id),
+            id(&self.bot_user_id),
+        ) else {
+            tracing::warn!("Discord voice: guild, channel, or bot id is not a Discord snowflake");
+            return;
+        };
+        let info = songbird::ConnectionInfo {
+            channel_id: Some(ChannelId::from(channel)),
+            endpoint,
+            guild_id: GuildId::from(guild),
+            session_id,
+            token,
+            user_id: UserId::from(user),
+        };
+        let mut driver = self.driver.lock().await;
+        match driver.connect(info).await {
+            Ok(()) => {
+                self.connected.store(true, Ordering::Relaxed);
+                tracing::info!(
+                    "Discord voice: connected to channel {}",
+                    self.config.channel_id
+                );
+            }
+            Err(e) => {
+                self.connected.store(false, Ordering::Relaxed);
+                tracing::warn!("Discord voice: failed to connect: {e}");
+            }
+        }
+    }
+
+    #[cfg(not(feature = "channel-discord-voice"))]
+    #[allow(clippy::unused_async)]
+    async fn connect_if_ready(&self) {
+        let pending = self.pending.lock();
+        if pending.session_id.is_some() && pending.token.is_some() && pending.endpoint.is_some() {
+            tracing::warn!(
+                "Discord voice: this build was compiled without `channel-discord-voice`"
+            );
+        }
+    }
+
+    /// Synthesize `text` and play it in the voice channel.
+    #[cfg_attr(not(feature = "channel-discord-voice"), allow(clippy::unused_async))]
+    pub async fn speak(&self, text: &str) -> Result<()> {
+        #[cfg(feature = "channel-discord-voice")]
+        {
+            use std::sync::atomic::Ordering;
+
+            if !self.connected.load(Ordering::Relaxed) {
+                bail!(
+                    "not connected to voice channel {} yet",
+                    self.config.channel_id
+                );
+            }
+            let audio = super::tts::synthesize_speech(text, &self.tts).await?;
+            self.driver
+                .lock()
+                .await
+                .play_input(songbird::input::Input::from(audio));
+            Ok(())
+        }
+        #[cfg(not(feature = "channel-discord-voice"))]
+        {
+            let _ = (text, &self.tts);
+            bail!(
+                "Discord voice support is not compiled in; rebuild with `--features channel-discord-voice`"
+            )
+        }
+    }
+
+    /// Reply to `/speak <text>` from a member with `member_roles`.
+    pub async fn speak_command(&self, member_roles: &[String], text: &str) -> String {
+        if !is_voice_admin(member_roles, &self.config.admin_roles) {
+            return "⛔ /speak is limited to voice admin roles.".to_string();
+        }
+        if text.is_empty() {
+            return "Usage: /speak <text>".to_string();
+        }
+        match self.speak(text).await {
+            Ok(()) => format!("🔊 Speaking in <#{}>.", self.config.channel_id),
+            Err(e) => format!("⚠️ Could not speak: {e}"),
+        }
+    }
+}
+
+fn active_session() -> &'static Mutex<Option<Arc<VoiceSession>>> {
+    static SESSION: OnceLock<Mutex<Option<Arc<VoiceSession>>>> = OnceLock::new();
+    SESSION.get_or_init(|| Mutex::new(None))
+}
+
+/// Make `session` the target of `discord_voice` deliveries.
+pub fn register(session: Arc<VoiceSession>) {
+    *active_session().lock() = Some(session);
+}
+
+/// Speak `text` in voice channel `channel_id` (the `discord_voice` delivery
+/// channel). Needs the daemon's Discord channel to be connected to it.
+pub async fn announce(channel_id: &str, text: &str) -> Result<()> {
+    let Some(session) = active_session().lock().clone() else {
+        bail!(
+            "Discord voice is not running; it needs `zeroclaw daemon` with [channels_config.discord.voice]"
+        );
+    };
+    if session.channel_id() != channel_id {
+        bail!(
+            "Discord voice is joined to channel {}, not {channel_id}",
+            session.channel_id()
+        );
+    }
+    session.speak(text).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speak_command_parsing_and_role_gate() {
+        assert_eq!(
+            parse_speak_command(" /speak dinner is ready "),
+            Some("dinner is ready")
+        );
+        assert_eq!(parse_speak_command("/speak"), Some(""));
+        assert_eq!(parse_speak_command("/speaker on"), None);
+        assert_eq!(parse_speak_command("please /speak"), None);
+
+        let admins = vec!["42".to_string()];
+        assert!(is_voice_admin(&["7".into(), "42".into()], &admins));
+        assert!(!is_voice_admin(&["7".into()], &admins));
+        assert!(!is_voice_admin(&["42".into()], &[]));
+    }
+
+    #[tokio::test]
+    async fn collects_connection_details_for_this_guild_only() {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let session = VoiceSession::new(
+            DiscordVoiceConfig {
+                channel_id: "200".into(),
+                ..DiscordVoiceConfig::default()
+            },
+            "100".into(),
+            "300".into(),
+            TtsConfig::default(),
+            tx,
+        );
+        session.join();
+        if cfg!(feature = "channel-discord-voice") {
+            let join = rx.try_recv().unwrap();
+            assert_eq!(join["op"], 4);
+            assert_eq!(join["d"]["channel_id"], "200");
+        }
+
+        session
+            .handle_dispatch(
+                "VOICE_STATE_UPDATE",
+                &json!({ "guild_id": "999", "user_id": "300", "session_id": "other" }),
+            )
+            .await;
+        session
+            .handle_dispatch(
+                "VOICE_STATE_UPDATE",
+                &json!({ "guild_id": "100", "user_id": "555", "session_id": "someone" }),
+            )
+            .await;
+        assert!(session.pending.lock().session_id.is_none());
+
+        session
+            .handle_dispatch(
+                "VOICE_STATE_UPDATE",
+                &json!({ "guild_id": "100", "user_id": "300", "session_id": "mine" }),
+            )
+            .await;
+        assert_eq!(session.pending.lock().session_id.as_deref(), Some("mine"));
+    }
+}
@@ -460,6 +460,8 @@ impl EmailChannel {
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
                 thread_ts: None,
+                location: None,
+                payload: None,
             };
 
             if tx.send(msg).await.is_err() {
@@ -0,0 +1,404 @@
+//! Shared emoji handling for channels.
+//!
+//! Models often write GitHub/Slack-style shortcodes (`:rocket:`), and
+//! platforms disagree on what they accept: Slack renders shortcodes itself,
+//! Telegram only allows a fixed set of reaction emoji, and most other
+//! platforms need plain Unicode. This module converts shortcodes, validates
+//! Telegram reactions, and maps unsupported emoji to close alternatives.
+
+use std::borrow::Cow;
+
+/// Common shortcodes (GitHub/Slack names) and their Unicode emoji.
+const SHORTCODES: &[(&str, &str)] = &[
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("100", "💯"),
+    ("airplane", "✈️"),
+    ("alarm_clock", "⏰"),
+    ("alien", "👽"),
+    ("angry", "😠"),
+    ("arrow_down", "⬇️"),
+    ("arrow_left", "⬅️"),
+    ("arrow_right", "➡️"),
+    ("arrow_up", "⬆️"),
+    ("bell", "🔔"),
+    ("birthday", "🎂"),
+    ("blush", "😊"),
+    ("books", "📚"),
+    ("brain", "🧠"),
+    ("broken_heart", "💔"),
+    ("bug", "🐛"),
+    ("bulb", "💡"),
+    ("calendar", "📅"),
+    ("chart_with_upwards_trend", "📈"),
+    ("chart_with_downwards_trend", "📉"),
+    ("checkered_flag", "🏁"),
+    ("clap", "👏"),
+    ("clipboard", "📋"),
+    ("coffee", "☕"),
+    ("computer", "💻"),
+    ("confused", "😕"),
+    ("construction", "🚧"),
+    ("crab", "🦀"),
+    ("cry", "😢"),
+    ("crossed_fingers", "🤞"),
+    ("dart", "🎯"),
+    ("dollar", "💵"),
+    ("email", "📧"),
+    ("exclamation", "❗"),
+    ("eyes", "👀"),
+    ("face_with_monocle", "🧐"),
+    ("fire", "🔥"),
+    ("footprints", "👣"),
+    ("gear", "⚙️"),
+    ("ghost", "👻"),
+    ("gift", "🎁"),
+    ("globe_with_meridians", "🌐"),
+    ("grin", "😁"),
+    ("grinning", "😀"),
+    ("hammer", "🔨"),
+    ("handshake", "🤝"),
+    ("heart", "❤️"),
+    ("heart_eyes", "😍"),
+    ("heavy_check_mark", "✔️"),
+    ("hourglass", "⌛"),
+    ("house", "🏠"),
+    ("hugs", "🤗"),
+    ("information_source", "ℹ️"),
+    ("joy", "😂"),
+    ("key", "🔑"),
+    ("laughing", "😆"),
+    ("link", "🔗"),
+    ("lock", "🔒"),
+    ("mag", "🔍"),
+    ("memo", "📝"),
+    ("moneybag", "💰"),
+    ("muscle", "💪"),
+    ("neutral_face", "😐"),
+    ("no_entry", "⛔"),
+    ("ok_hand", "👌"),
+    ("package", "📦"),
+    ("partying_face", "🥳"),
+    ("pencil2", "✏️"),
+    ("phone", "☎️"),
+    ("pizza", "🍕"),
+    ("point_down", "👇"),
+    ("point_right", "👉"),
+    ("point_up", "☝️"),
+    ("poop", "💩"),
+    ("pray", "🙏"),
+    ("pushpin", "📌"),
+    ("question", "❓"),
+    ("rage", "😡"),
+    ("rainbow", "🌈"),
+    ("raised_hands", "🙌"),
+    ("recycle", "♻️"),
+    ("robot", "🤖"),
+    ("rocket", "🚀"),
+    ("rotating_light", "🚨"),
+    ("scream", "😱"),
+    ("see_no_evil", "🙈"),
+    ("shrug", "🤷"),
+    ("skull", "💀"),
+    ("sleeping", "😴"),
+    ("slightly_smiling_face", "🙂"),
+    ("smile", "😄"),
+    ("smiley", "😃"),
+    ("snowflake", "❄️"),
+    ("sob", "😭"),
+    ("sparkles", "✨"),
+    ("star", "⭐"),
+    ("star_struck", "🤩"),
+    ("stop_sign", "🛑"),
+    ("sunglasses", "😎"),
+    ("sunny", "☀️"),
+    ("sweat_smile", "😅"),
+    ("tada", "🎉"),
+    ("thinking", "🤔"),
+    ("thumbsdown", "👎"),
+    ("thumbsup", "👍"),
+    ("trophy", "🏆"),
+    ("unicorn", "🦄"),
+    ("warning", "⚠️"),
+    ("wave", "👋"),
+    ("white_check_mark", "✅"),
+    ("wink", "😉"),
+    ("wrench", "🔧"),
+    ("x", "❌"),
+    ("zap", "⚡"),
+    ("zzz", "💤"),
+];
+
+/// Emoji accepted by Telegram's `setMessageReaction` for bots
+/// (`ReactionTypeEmoji`), without variation selectors.
+const TELEGRAM_REACTIONS: &[&str] = &[
+    "👍",
+    "👎",
+    "❤",
+    "🔥",
+    "🥰",
+    "👏",
+    "😁",
+    "🤔",
+    "🤯",
+    "😱",
+    "🤬",
+    "😢",
+    "🎉",
+    "🤩",
+    "🤮",
+    "💩",
+    "🙏",
+    "👌",
+    "🕊",
+    "🤡",
+    "🥱",
+    "🥴",
+    "😍",
+    "🐳",
+    "❤\u{200d}🔥",
+    "🌚",
+    "🌭",
+    "💯",
+    "🤣",
+    "⚡",
+    "🍌",
+    "🏆",
+    "💔",
+    "🤨",
+    "😐",
+    "🍓",
+    "🍾",
+    "💋",
+    "🖕",
+    "😈",
+    "😴",
+    "😭",
+    "🤓",
+    "👻",
+    "👨\u{200d}💻",
+    "👀",
+    "🎃",
+    "🙈",
+    "😇",
+    "😨",
+    "🤝",
+    "✍",
+    "🤗",
+    "🫡",
+    "🎅",
+    "🎄",
+    "☃",
+    "💅",
+    "🤪",
+    "🗿",
+    "🆒",
+    "💘",
+    "🙉",
+    "🦄",
+    "😘",
+    "💊",
+    "🙊",
+    "😎",
+    "👾",
+    "🤷\u{200d}♂",
+    "🤷",
+    "🤷\u{200d}♀",
+    "😡",
+];
+
+/// Close alternatives for emoji a platform rejects, tried in order.
+const ALTERNATIVES: &[(&str, &[&str])] = &[
+    ("✅", &["👍", "👌"]),
+    ("✔", &["👍", "👌"]),
+    ("☑", &["👍", "👌"]),
+    ("❌", &["👎"]),
+    ("⚠", &["🤔", "😱"]),
+    ("❗", &["😱", "🤯"]),
+    ("❓", &["🤔", "🤨"]),
+    ("🚀", &["⚡", "🔥"]),
+    ("✨", &["🤩", "⚡"]),
+    ("🎊", &["🎉"]),
+    ("🥳", &["🎉", "🤩"]),
+    ("😂", &["🤣", "😁"]),
+    ("😄", &["😁"]),
+    ("😃", &["😁"]),
+    ("🙂", &["😁", "😇"]),
+    ("😊", &["🥰", "😇"]),
+    ("🙌", &["👏", "🙏"]),
+    ("💪", &["👍", "🔥"]),
+    ("👣", &["👀"]),
+    ("🧐", &["🤔", "🤓"]),
+    ("🦀", &["👾"]),
+    ("🤖", &["👾", "🤓"]),
+    ("🐛", &["👾"]),
+    ("💀", &["👻", "😱"]),
+    ("😠", &["😡", "🤬"]),
+    ("⏳", &["😴"]),
+    ("⌛", &["😴"]),
+];
+
+/// Where an emoji is going, which decides what is acceptable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmojiTarget {
+    /// Any Unicode emoji (Discord reactions, message text).
+    Unicode,
+    /// Telegram `setMessageReaction`, limited to [`TELEGRAM_REACTIONS`].
+    TelegramReaction,
+}
+
+/// Unicode emoji for a shortcode name (without colons).
+pub fn shortcode(name: &str) -> Option<&'static str> {
+    SHORTCODES
+        .iter()
+        .find(|(code, _)| *code == name)
+        .map(|(_, emoji)| *emoji)
+}
+
+/// Accept either `:name:` or a Unicode emoji and return the Unicode form.
+/// Unknown shortcodes are returned unchanged.
+pub fn to_unicode(emoji: &str) -> Cow<'_, str> {
+    let trimmed = emoji.trim();
+    trimmed
+        .strip_prefix(':')
+        .and_then(|rest| rest.strip_suffix(':'))
+        .and_then(shortcode)
+        .map_or(Cow::Borrowed(trimmed), Cow::Borrowed)
+}
+
+/// Replace known `:shortcode:` tokens in text with Unicode emoji.
+/// Anything else between colons (times, unknown names) is left alone.
+pub fn expand_shortcodes(text: &str) -> Cow<'_, str> {
+    if !text.contains(':') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut replaced = false;
+    while let Some(start) = rest.find(':') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let name_len = after
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-')))
+            .unwrap_or(after.len());
+        let emoji = after[name_len..]
+            .starts_with(':')
+            .then(|| shortcode(&after[..name_len]))
+            .flatten();
+        if let Some(emoji) = emoji {
+            out.push_str(emoji);
+            rest = &after[name_len + 1..];
+            replaced = true;
+        } else {
+            out.push(':');
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+
+    if replaced {
+        Cow::Owned(out)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Strip variation selectors so `⚡️` and `⚡` compare equal.
+fn bare(emoji: &str) -> String {
+    emoji.chars().filter(|c| *c != '\u{fe0f}').collect()
+}
+
+fn is_supported(emoji: &str, target: EmojiTarget) -> bool {
+    match target {
+        EmojiTarget::Unicode => !emoji.is_empty(),
+        EmojiTarget::TelegramReaction => TELEGRAM_REACTIONS.contains(&emoji),
+    }
+}
+
+/// Resolve an emoji (Unicode or `:shortcode:`) to a form `target` accepts,
+/// falling back to a close alternative. `None` means nothing suitable exists
+/// and the caller should skip the reaction.
+pub fn for_target(emoji: &str, target: EmojiTarget) -> Option<String> {
+    let unicode = to_unicode(emoji);
+    if target == EmojiTarget::Unicode {
+        return (!unicode.is_empty()).then(|| unicode.into_owned());
+    }
+
+    let bare = bare(&unicode);
+    if is_supported(&bare, target) {
+        return Some(bare);
+    }
+    ALTERNATIVES
+        .iter()
+        .find(|(emoji, _)| *emoji == bare)
+        .and_then(|(_, alternatives)| {
+            alternatives
+                .iter()
+                .find(|alternative| is_supported(alternative, target))
+        })
+        .map(|alternative| (*alternative).to_string())
+}
+
+/// Uniformly random entry of a non-empty pool (e.g. ACK reactions).
+pub fn random_from_pool(pool: &'static [&'static str]) -> &'static str {
+    pool[rand::random_range(0..pool.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_shortcodes_replaces_known_names_only() {
+        assert_eq!(
+            expand_shortcodes("Shipped :rocket: at 10:30:45 :not_a_code: :+1:"),
+            "Shipped 🚀 at 10:30:45 :not_a_code: 👍"
+        );
+        assert!(matches!(expand_shortcodes("no codes"), Cow::Borrowed(_)));
+        assert_eq!(expand_shortcodes("::tada::"), ":🎉:");
+    }
+
+    #[test]
+    fn to_unicode_accepts_shortcodes_and_unicode() {
+        assert_eq!(to_unicode(":eyes:"), "👀");
+        assert_eq!(to_unicode("👀"), "👀");
+        assert_eq!(to_unicode(":unknown:"), ":unknown:");
+    }
+
+    #[test]
+    fn telegram_reactions_are_validated_and_mapped() {
+        let telegram = EmojiTarget::TelegramReaction;
+        assert_eq!(for_target("👀", telegram).as_deref(), Some("👀"));
+        // Variation selectors are dropped to match Telegram's list.
+        assert_eq!(for_target("⚡️", telegram).as_deref(), Some("⚡"));
+        assert_eq!(
+            for_target(":white_check_mark:", telegram).as_deref(),
+            Some("👍")
+        );
+        assert_eq!(for_target("⚠️", telegram).as_deref(), Some("🤔"));
+        assert_eq!(for_target("🧀", telegram), None);
+    }
+
+    #[test]
+    fn unicode_target_keeps_any_emoji() {
+        assert_eq!(
+            for_target(":crab:", EmojiTarget::Unicode).as_deref(),
+            Some("🦀")
+        );
+        assert_eq!(
+            for_target("✅", EmojiTarget::Unicode).as_deref(),
+            Some("✅")
+        );
+        assert_eq!(for_target("  ", EmojiTarget::Unicode), None);
+    }
+
+    #[test]
+    fn alternatives_only_suggest_supported_emoji() {
+        for (_, alternatives) in ALTERNATIVES {
+            assert!(alternatives
+                .iter()
+                .any(|emoji| is_supported(emoji, EmojiTarget::TelegramReaction)));
+        }
+    }
+}
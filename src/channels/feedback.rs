@@ -0,0 +1,449 @@
+//! Reactions on agent replies as a feedback signal.
+//!
+//! When a user reacts to one of the agent's replies on a channel that
+//! delivers reaction events (Telegram, Discord), the reaction is stored in
+//! `<workspace>/channels/feedback.db` together with the outbox entry of the
+//! reply it belongs to. `zeroclaw feedback stats` summarizes them, and with
+//! `[feedback].prompt_limit` set the sender's recent reactions are listed in
+//! the channel system prompt.
+
+use super::outbox;
+use super::traits::Reaction;
+use crate::config::Config;
+use crate::memory::encryption::{open_text, seal_text};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Only reactions from this many recent days are shown in the prompt.
+const PROMPT_WINDOW_DAYS: i64 = 30;
+
+/// Characters of each reply preview shown in the prompt.
+const PROMPT_PREVIEW_CHARS: usize = 80;
+
+const POSITIVE: &[&str] = &[
+    "👍", "❤", "😍", "🥰", "😂", "🤣", "🎉", "🔥", "👏", "🙏", "💯", "✅", "⭐", "🤩", "😊", "👌",
+    "💪", "🏆", "🫡",
+];
+
+const NEGATIVE: &[&str] = &[
+    "👎", "😡", "😠", "🤬", "😢", "😭", "💩", "🤮", "❌", "😕", "🙄", "😒", "🥱", "💔", "🤦", "😐",
+    "🤡",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Sentiment {
+    Positive,
+    Negative,
+    Neutral,
+}
+
+impl Sentiment {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Positive => "positive",
+            Self::Negative => "negative",
+            Self::Neutral => "neutral",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "positive" => Self::Positive,
+            "negative" => Self::Negative,
+            _ => Self::Neutral,
+        }
+    }
+
+    /// Classify a reaction emoji. Variation selectors and skin tones are
+    /// ignored, so `👍🏽` counts like `👍`.
+    pub fn of(emoji: &str) -> Self {
+        let bare: String = super::emoji::to_unicode(emoji)
+            .chars()
+            .filter(|c| *c != '\u{fe0f}' && !('\u{1f3fb}'..='\u{1f3ff}').contains(c))
+            .collect();
+        if POSITIVE.contains(&bare.as_str()) {
+            Self::Positive
+        } else if NEGATIVE.contains(&bare.as_str()) {
+            Self::Negative
+        } else {
+            Self::Neutral
+        }
+    }
+}
+
+/// A stored reaction on an agent reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackEntry {
+    pub channel: String,
+    pub sender: String,
+    /// Outbox entry of the reply that was reacted to.
+    pub outbox_id: String,
+    pub reply_preview: String,
+    pub emoji: String,
+    pub sentiment: Sentiment,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Reaction counts over a period.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedbackStats {
+    pub positive: usize,
+    pub negative: usize,
+    pub neutral: usize,
+    /// Count per emoji, most used first.
+    pub by_emoji: Vec<(String, usize)>,
+    /// `(positive, negative, neutral)` per channel.
+    pub by_channel: BTreeMap<String, (usize, usize, usize)>,
+}
+
+impl FeedbackStats {
+    pub fn total(&self) -> usize {
+        self.positive + self.negative + self.neutral
+    }
+}
+
+/// Store or withdraw a reaction. Reactions on messages that are not agent
+/// replies in the outbox are ignored. Returns `true` when something changed.
+pub fn record_reaction(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    reaction: &Reaction,
+) -> Result<bool> {
+    let Some(reply) = outbox::find_by_platform_id(workspace_dir, channel, &reaction.message_id)?
+    else {
+        return Ok(false);
+    };
+
+    with_connection(workspace_dir, |conn| {
+        let changed = if reaction.removed {
+            conn.execute(
+                "DELETE FROM feedback
+                 WHERE channel = ?1 AND outbox_id = ?2 AND sender = ?3 AND emoji = ?4",
+                params![channel, reply.id, sender, reaction.emoji],
+            )?
+        } else {
+            conn.execute(
+                "INSERT OR IGNORE INTO feedback
+                     (id, channel, sender, outbox_id, reply_preview, emoji, sentiment, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    Uuid::new_v4().to_string(),
+                    channel,
+                    sender,
+                    reply.id,
+                    seal_text(workspace_dir, &reply.preview)?,
+                    reaction.emoji,
+                    Sentiment::of(&reaction.emoji).as_str(),
+                    Utc::now().to_rfc3339(),
+                ],
+            )?
+        };
+        Ok(changed > 0)
+    })
+}
+
+/// Most recent reactions since `since`, newest first.
+pub fn recent(
+    workspace_dir: &Path,
+    channel: Option<&str>,
+    sender: Option<&str>,
+    since: DateTime<Utc>,
+    limit: usize,
+) -> Result<Vec<FeedbackEntry>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT channel, sender, outbox_id, reply_preview, emoji, sentiment, created_at
+             FROM feedback
+             WHERE (?1 IS NULL OR channel = ?1)
+               AND (?2 IS NULL OR sender = ?2)
+               AND created_at >= ?3
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?4",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = stmt.query_map(params![channel, sender, since.to_rfc3339(), limit], |row| {
+            let sentiment: String = row.get(5)?;
+            let created_at: String = row.get(6)?;
+            Ok(FeedbackEntry {
+                channel: row.get(0)?,
+                sender: row.get(1)?,
+                outbox_id: row.get(2)?,
+                reply_preview: row.get(3)?,
+                emoji: row.get(4)?,
+                sentiment: Sentiment::parse(&sentiment),
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })?
+    .into_iter()
+    .map(|mut entry: FeedbackEntry| {
+        entry.reply_preview = open_text(workspace_dir, &entry.reply_preview)?;
+        Ok(entry)
+    })
+    .collect()
+}
+
+/// Count reactions since `since`.
+pub fn stats(
+    workspace_dir: &Path,
+    channel: Option<&str>,
+    since: DateTime<Utc>,
+) -> Result<FeedbackStats> {
+    let entries = recent(workspace_dir, channel, None, since, usize::MAX)?;
+    let mut stats = FeedbackStats::default();
+    let mut by_emoji: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in &entries {
+        let per_channel = stats.by_channel.entry(entry.channel.clone()).or_default();
+        match entry.sentiment {
+            Sentiment::Positive => {
+                stats.positive += 1;
+                per_channel.0 += 1;
+            }
+            Sentiment::Negative => {
+                stats.negative += 1;
+                per_channel.1 += 1;
+            }
+            Sentiment::Neutral => {
+                stats.neutral += 1;
+                per_channel.2 += 1;
+            }
+        }
+        *by_emoji.entry(entry.emoji.clone()).or_default() += 1;
+    }
+    stats.by_emoji = by_emoji.into_iter().collect();
+    stats
+        .by_emoji
+        .sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(stats)
+}
+
+/// System prompt section listing the sender's recent reactions, or an empty
+/// string when there are none.
+pub fn prompt_section(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    limit: usize,
+) -> Result<String> {
+    if limit == 0 {
+        return Ok(String::new());
+    }
+    let since = days_ago(PROMPT_WINDOW_DAYS);
+    let entries = recent(workspace_dir, Some(channel), Some(sender), since, limit)?;
+    if entries.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut section = String::from(
+        "## Recent feedback\n\nReactions the user left on your recent replies. \
+         Lean towards what they liked and avoid what they disliked.\n",
+    );
+    for entry in entries {
+        let _ = write!(
+            section,
+            "\n- {} ({}) on: \"{}\"",
+            entry.emoji,
+            entry.sentiment.as_str(),
+            crate::util::truncate_with_ellipsis(
+                &entry.reply_preview.replace('\n', " "),
+                PROMPT_PREVIEW_CHARS
+            )
+        );
+    }
+    Ok(section)
+}
+
+/// The moment `days` days ago, clamped to the Unix epoch when that is out of range.
+fn days_ago(days: i64) -> DateTime<Utc> {
+    Duration::try_days(days)
+        .and_then(|window| Utc::now().checked_sub_signed(window))
+        .unwrap_or(DateTime::UNIX_EPOCH)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_command(command: crate::FeedbackCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::FeedbackCommands::Stats { days, channel } => {
+            let since = days_ago(i64::from(days));
+            let stats = stats(&config.workspace_dir, channel.as_deref(), since)?;
+            if stats.total() == 0 {
+                println!("No reactions on agent replies in the last {days} days.");
+                return Ok(());
+            }
+
+            println!(
+                "💬 Reactions on agent replies, last {days} days: {} total",
+                stats.total()
+            );
+            println!(
+                "  👍 positive {} | 👎 negative {} | neutral {}",
+                stats.positive, stats.negative, stats.neutral
+            );
+            if stats.by_channel.len() > 1 {
+                for (channel, (positive, negative, neutral)) in &stats.by_channel {
+                    println!("  {channel}: +{positive} / -{negative} / ~{neutral}");
+                }
+            }
+            let top = stats
+                .by_emoji
+                .iter()
+                .take(10)
+                .map(|(emoji, count)| format!("{emoji} {count}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            println!("  Top: {top}");
+
+            let disliked: Vec<_> =
+                recent(&config.workspace_dir, channel.as_deref(), None, since, 50)?
+                    .into_iter()
+                    .filter(|entry| entry.sentiment == Sentiment::Negative)
+                    .take(5)
+                    .collect();
+            if !disliked.is_empty() {
+                println!("\nRecently disliked replies:");
+                for entry in disliked {
+                    println!(
+                        "- {} | {} | {} {}",
+                        entry.created_at.to_rfc3339(),
+                        entry.channel,
+                        entry.emoji,
+                        entry.sender
+                    );
+                    println!("    {}", entry.reply_preview);
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("feedback.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open feedback DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS feedback (
+            id            TEXT PRIMARY KEY,
+            channel       TEXT NOT NULL,
+            sender        TEXT NOT NULL,
+            outbox_id     TEXT NOT NULL,
+            reply_preview TEXT NOT NULL,
+            emoji         TEXT NOT NULL,
+            sentiment     TEXT NOT NULL,
+            created_at    TEXT NOT NULL,
+            UNIQUE(channel, outbox_id, sender, emoji)
+        );
+        CREATE INDEX IF NOT EXISTS idx_feedback_created_at ON feedback(created_at);",
+    )
+    .context("Failed to initialize feedback schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::DeliveryStatus;
+    use crate::observability::NoopObserver;
+
+    fn sent_reply(workspace_dir: &Path, platform_id: &str, content: &str) {
+        outbox::record(
+            workspace_dir,
+            &outbox::OutboundMessage {
+                channel: "telegram",
+                recipient: "100",
+                platform_message_id: Some(platform_id),
+                content,
+                status: DeliveryStatus::Sent,
+                receipts: false,
+            },
+            &NoopObserver,
+        )
+        .unwrap();
+    }
+
+    fn reaction(message_id: &str, emoji: &str, removed: bool) -> Reaction {
+        Reaction {
+            message_id: message_id.into(),
+            emoji: emoji.into(),
+            removed,
+        }
+    }
+
+    #[test]
+    fn sentiment_ignores_variation_selectors_and_skin_tones() {
+        assert_eq!(Sentiment::of("👍🏽"), Sentiment::Positive);
+        assert_eq!(Sentiment::of("❤️"), Sentiment::Positive);
+        assert_eq!(Sentiment::of(":-1:"), Sentiment::Negative);
+        assert_eq!(Sentiment::of("👀"), Sentiment::Neutral);
+    }
+
+    #[test]
+    fn reactions_link_to_replies_and_can_be_withdrawn() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ws = tmp.path();
+        sent_reply(ws, "7", "Here is a very long essay about tides");
+
+        // Reactions on messages the agent did not send are ignored.
+        assert!(!record_reaction(ws, "telegram", "alice", &reaction("8", "👍", false)).unwrap());
+
+        assert!(record_reaction(ws, "telegram", "alice", &reaction("7", "👎", false)).unwrap());
+        // Duplicate events are stored once.
+        assert!(!record_reaction(ws, "telegram", "alice", &reaction("7", "👎", false)).unwrap());
+
+        let since = Utc::now() - Duration::days(1);
+        let entries = recent(ws, None, None, since, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].sentiment, Sentiment::Negative);
+        assert_eq!(
+            entries[0].reply_preview,
+            "Here is a very long essay about tides"
+        );
+
+        assert!(record_reaction(ws, "telegram", "alice", &reaction("7", "👎", true)).unwrap());
+        assert!(recent(ws, None, None, since, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn days_ago_clamps_windows_beyond_the_calendar() {
+        assert_eq!(days_ago(i64::from(u32::MAX)), DateTime::UNIX_EPOCH);
+        assert_eq!(days_ago(i64::MAX), DateTime::UNIX_EPOCH);
+        assert!(days_ago(1) < Utc::now());
+    }
+
+    #[test]
+    fn stats_and_prompt_section_summarize_reactions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ws = tmp.path();
+        sent_reply(ws, "1", "Short answer");
+        sent_reply(ws, "2", "Long answer");
+        record_reaction(ws, "telegram", "alice", &reaction("1", "👍", false)).unwrap();
+        record_reaction(ws, "telegram", "alice", &reaction("2", "👎", false)).unwrap();
+        record_reaction(ws, "telegram", "bob", &reaction("1", "👍", false)).unwrap();
+
+        let stats = stats(ws, None, Utc::now() - Duration::days(1)).unwrap();
+        assert_eq!((stats.positive, stats.negative, stats.neutral), (2, 1, 0));
+        assert_eq!(stats.by_emoji[0], ("👍".to_string(), 2));
+
+        let section = prompt_section(ws, "telegram", "alice", 5).unwrap();
+        assert!(section.starts_with("## Recent feedback"));
+        assert!(section.contains("👎 (negative) on: \"Long answer\""));
+        assert!(section.contains("👍 (positive) on: \"Short answer\""));
+        assert!(prompt_section(ws, "telegram", "carol", 5)
+            .unwrap()
+            .is_empty());
+        assert!(prompt_section(ws, "telegram", "alice", 0)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Suggested follow-up questions (`[channels_config.follow_ups]`).
+//!
+//! After a reply, a model proposes up to three short questions the user is
+//! likely to ask next. Channels with interactive buttons send them as quick
+//! replies whose label comes back as the user's next message. Elsewhere they
+//! are appended as a numbered footer, and a reply consisting of just one of
+//! the numbers is replaced with that question before the turn runs.
+
+use crate::config::FollowUpsConfig;
+use crate::providers::Provider;
+use crate::util::truncate_with_ellipsis;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+/// Conversations with pending numbered suggestions before the oldest is forgotten.
+const PENDING_CAPACITY: usize = 4096;
+/// Longest suggestion kept; Telegram shows button labels on a single line.
+const MAX_SUGGESTION_CHARS: usize = 80;
+/// Reply text sent to the suggestion model.
+const MAX_REPLY_CHARS: usize = 4000;
+
+const SUGGESTER_SYSTEM: &str = "You suggest follow-up questions for a chat assistant's user. Given the user's message and the assistant's reply, write short questions the user is likely to ask next, phrased as the user would type them. One question per line, no numbering, no commentary. Write them in the language of the conversation.";
+
+#[derive(Default)]
+struct Pending {
+    by_conversation: HashMap<String, Vec<String>>,
+    order: VecDeque<String>,
+}
+
+/// Generates suggestions and remembers numbered ones per conversation.
+pub struct FollowUps {
+    config: FollowUpsConfig,
+    pending: Mutex<Pending>,
+}
+
+impl FollowUps {
+    /// `None` when suggestions are disabled.
+    pub fn new(config: &FollowUpsConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            config: config.clone(),
+            pending: Mutex::new(Pending::default()),
+        })
+    }
+
+    /// Whether replies on `channel` get suggestions.
+    pub fn applies_to(&self, channel: &str) -> bool {
+        self.config.channels.is_empty() || self.config.channels.iter().any(|c| c == channel)
+    }
+
+    /// Ask the model for follow-up questions to `reply`. Failures yield no
+    /// suggestions rather than delaying or blocking the reply.
+    pub async fn suggest(
+        &self,
+        provider: &dyn Provider,
+        default_model: &str,
+        user_message: &str,
+        reply: &str,
+    ) -> Vec<String> {
+        let model = self.config.model.as_deref().unwrap_or(default_model);
+        let prompt = format!(
+            "Suggest up to {} follow-up questions.\n\nUser: {}\n\nAssistant: {}",
+            self.config.max_suggestions,
+            truncate_with_ellipsis(user_message, MAX_REPLY_CHARS),
+            truncate_with_ellipsis(reply, MAX_REPLY_CHARS)
+        );
+        match provider
+            .chat_with_system(Some(SUGGESTER_SYSTEM), &prompt, model, 0.4)
+            .await
+        {
+            Ok(raw) => parse_suggestions(&raw, self.config.max_suggestions),
+            Err(e) => {
+                tracing::debug!("Follow-up suggestion failed: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Remember numbered suggestions shown in `conversation`.
+    pub fn remember(&self, conversation: &str, suggestions: Vec<String>) {
+        let mut pending = self.pending.lock();
+        if pending
+            .by_conversation
+            .insert(conversation.to_string(), suggestions)
+            .is_none()
+        {
+            pending.order.push_back(conversation.to_string());
+            while pending.order.len() > PENDING_CAPACITY {
+                if let Some(oldest) = pending.order.pop_front() {
+                    pending.by_conversation.remove(&oldest);
+                }
+            }
+        }
+    }
+
+    /// Consume the pending suggestions of `conversation`, returning the one
+    /// picked when `content` is just its number. Any message clears them.
+    pub fn resolve(&self, conversation: &str, content: &str) -> Option<String> {
+        let mut pending = self.pending.lock();
+        let suggestions = pending.by_conversation.remove(conversation)?;
+        pending.order.retain(|key| key != conversation);
+        let pick: usize = content.trim().trim_end_matches('.').parse().ok()?;
+        suggestions.get(pick.checked_sub(1)?).cloned()
+    }
+}
+
+/// Suggestion lines from a model response, without numbering or bullets.
+fn parse_suggestions(raw: &str, max: usize) -> Vec<String> {
+    let mut suggestions: Vec<String> = Vec::new();
+    for line in raw.lines() {
+        let line = line
+            .trim()
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim_start_matches(['.', ')', '-', '*', '•'])
+            .trim()
+            .trim_matches('"');
+        if line.is_empty() || line.chars().count() > MAX_SUGGESTION_CHARS {
+            continue;
+        }
+        if !suggestions.iter().any(|s| s.eq_ignore_ascii_case(line)) {
+            suggestions.push(line.to_string());
+        }
+        if suggestions.len() == max {
+            break;
+        }
+    }
+    suggestions
+}
+
+/// Numbered footer for channels without buttons.
+pub fn footer(suggestions: &[String]) -> String {
+    let mut footer = String::from("You could ask (reply with a number):");
+    for (index, suggestion) in suggestions.iter().enumerate() {
+        let _ = write!(footer, "\n{}. {suggestion}", index + 1);
+    }
+    footer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+
+    struct FixedProvider(&'static str);
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    fn follow_ups(max_suggestions: usize) -> FollowUps {
+        FollowUps::new(&FollowUpsConfig {
+            enabled: true,
+            channels: vec!["telegram".into()],
+            model: None,
+            max_suggestions,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn disabled_config_and_channel_filter() {
+        assert!(FollowUps::new(&FollowUpsConfig::default()).is_none());
+        let follow_ups = follow_ups(3);
+        assert!(follow_ups.applies_to("telegram"));
+        assert!(!follow_ups.applies_to("slack"));
+    }
+
+    #[tokio::test]
+    async fn suggestions_are_cleaned_deduplicated_and_capped() {
+        let provider = FixedProvider(
+            "1. How much does it cost?\n2) how much does it cost?\n- \"Is there a free tier?\"\n\n* Which regions are supported?",
+        );
+        let suggestions = follow_ups(2)
+            .suggest(&provider, "model", "What is it?", "A hosting service.")
+            .await;
+        assert_eq!(
+            suggestions,
+            ["How much does it cost?", "Is there a free tier?"]
+        );
+    }
+
+    #[test]
+    fn numbered_reply_resolves_once() {
+        let follow_ups = follow_ups(3);
+        follow_ups.remember("conv", vec!["First?".into(), "Second?".into()]);
+        assert_eq!(
+            follow_ups.resolve("conv", " 2 ").as_deref(),
+            Some("Second?")
+        );
+        assert_eq!(follow_ups.resolve("conv", "2"), None);
+
+        follow_ups.remember("conv", vec!["First?".into()]);
+        assert_eq!(follow_ups.resolve("conv", "thanks"), None);
+        assert_eq!(follow_ups.resolve("conv", "1"), None);
+    }
+
+    #[test]
+    fn footer_numbers_suggestions() {
+        assert_eq!(
+            footer(&["A?".into(), "B?".into()]),
+            "You could ask (reply with a number):\n1. A?\n2. B?"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Per-group-chat overrides (`[[channels_config.groups]]`).
+//!
+//! One channel account often sits in chats that want different behavior:
+//! the family group gets a friendlier persona and no shell, the ops group
+//! answers only when called by name. Each entry matches a chat by its reply
+//! target (chat id, group id, or chat GUID), optionally on one channel, and
+//! overrides the persona, wake words, tools, ack reactions, and rate limits
+//! for messages in that chat. Entries naming a channel win over entries that
+//! match any channel.
+
+use super::traits::ChannelMessage;
+use crate::config::ChannelGroupConfig;
+use crate::tools::Tool;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Rate window key: channel, group, and the sender for per-sender limits.
+type WindowKey = (String, String, Option<String>);
+
+#[derive(Default)]
+pub(super) struct GroupOverrides {
+    groups: Vec<ChannelGroupConfig>,
+    windows: Mutex<HashMap<WindowKey, VecDeque<Instant>>>,
+}
+
+impl GroupOverrides {
+    pub fn new(groups: Vec<ChannelGroupConfig>) -> Self {
+        Self {
+            groups,
+            windows: Mutex::default(),
+        }
+    }
+
+    /// Overrides for the chat `msg` was sent in, if any.
+    pub fn select(&self, msg: &ChannelMessage) -> Option<&ChannelGroupConfig> {
+        let in_chat = |group: &&ChannelGroupConfig| group.group == msg.reply_target;
+        self.groups
+            .iter()
+            .filter(in_chat)
+            .find(|group| group.channel.as_deref() == Some(msg.channel.as_str()))
+            .or_else(|| {
+                self.groups
+                    .iter()
+                    .filter(in_chat)
+                    .find(|group| group.channel.is_none())
+            })
+    }
+
+    /// Count `msg` against the group's limits. Returns `false`, without
+    /// counting it, when the group or its sender is over the limit.
+    pub fn within_rate_limit(&self, group: &ChannelGroupConfig, msg: &ChannelMessage) -> bool {
+        let limits = [
+            (None, group.max_messages_per_minute),
+            (
+                Some(msg.sender.clone()),
+                group.max_messages_per_sender_per_minute,
+            ),
+        ];
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        let mut admitted = Vec::new();
+        for (sender, limit) in limits {
+            if limit == 0 {
+                continue;
+            }
+            let key = (msg.channel.clone(), msg.reply_target.clone(), sender);
+            let window = windows.entry(key.clone()).or_default();
+            while window
+                .front()
+                .is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW)
+            {
+                window.pop_front();
+            }
+            if window.len() >= usize::try_from(limit).unwrap_or(usize::MAX) {
+                return false;
+            }
+            admitted.push(key);
+        }
+        for key in admitted {
+            windows.entry(key).or_default().push_back(now);
+        }
+        true
+    }
+}
+
+/// Whether `content` contains one of `wake_words` as a whole word, ignoring
+/// case. No wake words means every message qualifies.
+pub(super) fn mentions_wake_word(wake_words: &[String], content: &str) -> bool {
+    if wake_words.is_empty() {
+        return true;
+    }
+    let content = content.to_lowercase();
+    wake_words.iter().any(|word| {
+        let word = word.trim().to_lowercase();
+        content.match_indices(&word).any(|(start, _)| {
+            let before = content[..start].chars().next_back();
+            let after = content[start + word.len()..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+    })
+}
+
+/// Add the tools outside the group's allowlist to `hidden`.
+pub(super) fn hide_tools(
+    group: &ChannelGroupConfig,
+    tools: &[Box<dyn Tool>],
+    hidden: &mut Vec<String>,
+) {
+    let Some(allowed) = group.tools.as_deref() else {
+        return;
+    };
+    for tool in tools {
+        let name = tool.name();
+        if !allowed.iter().any(|a| a == name) && !hidden.iter().any(|h| h == name) {
+            hidden.push(name.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(channel: &str, reply_target: &str, sender: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "1".into(),
+            sender: sender.into(),
+            reply_target: reply_target.into(),
+            content: "hi".into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+            location: None,
+            payload: None,
+        }
+    }
+
+    fn group(channel: Option<&str>, id: &str, persona: &str) -> ChannelGroupConfig {
+        ChannelGroupConfig {
+            group: id.into(),
+            channel: channel.map(str::to_string),
+            persona: Some(persona.into()),
+            ..ChannelGroupConfig::default()
+        }
+    }
+
+    #[test]
+    fn channel_specific_entries_win_and_limits_apply_per_group_and_sender() {
+        let mut ops = group(Some("telegram"), "ops", "ops");
+        ops.max_messages_per_minute = 3;
+        ops.max_messages_per_sender_per_minute = 2;
+        let overrides = GroupOverrides::new(vec![
+            group(None, "ops", "any channel"),
+            ops,
+            group(None, "family", "family"),
+        ]);
+
+        let persona = |msg: &ChannelMessage| {
+            overrides
+                .select(msg)
+                .and_then(|g| g.persona.clone())
+                .unwrap_or_default()
+        };
+        assert_eq!(persona(&message("telegram", "ops", "a")), "ops");
+        assert_eq!(persona(&message("discord", "ops", "a")), "any channel");
+        assert_eq!(persona(&message("imessage", "family", "a")), "family");
+        assert!(overrides.select(&message("telegram", "42", "a")).is_none());
+
+        let alice = message("telegram", "ops", "alice");
+        let bob = message("telegram", "ops", "bob");
+        let ops = overrides.select(&alice).unwrap().clone();
+        assert!(overrides.within_rate_limit(&ops, &alice));
+        assert!(overrides.within_rate_limit(&ops, &alice));
+        assert!(!overrides.within_rate_limit(&ops, &alice));
+        assert!(overrides.within_rate_limit(&ops, &bob));
+        assert!(!overrides.within_rate_limit(&ops, &bob));
+    }
+
+    #[test]
+    fn wake_words_match_whole_words_ignoring_case() {
+        let words = vec!["Claw".to_string(), "hey bot".to_string()];
+        assert!(mentions_wake_word(&words, "claw, what's for dinner?"));
+        assert!(mentions_wake_word(&words, "Hey Bot: status"));
+        assert!(!mentions_wake_word(&words, "clawback the refund"));
+        assert!(!mentions_wake_word(&words, "nothing to see"));
+        assert!(mentions_wake_word(&[], "anything"));
+    }
+}
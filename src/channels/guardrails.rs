@@ -0,0 +1,459 @@
+//! Outbound reply guardrails (`[channels_config.guardrails]`).
+//!
+//! The last stage before a reply is sent: keyword and regex blocklists, an
+//! optional model check for sensitive topics, a length cap, and per-channel
+//! disclaimers. Withheld replies are logged and, when configured, forwarded
+//! to an admin channel for review; the user gets `blocked_reply` instead.
+//!
+//! Channels with an audience (`[channels_config.audiences.<channel>]`) also
+//! get profanity masking and their own restricted topics, even when the
+//! global guardrails are disabled.
+
+use super::profanity::{self, ProfanityFilter};
+use super::traits::{Channel, SendMessage};
+use crate::config::{ChannelAudienceConfig, OutputGuardrailsConfig};
+use crate::providers::Provider;
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const ALERT_PREVIEW_CHARS: usize = 1500;
+
+const POLICY_PROMPT: &str = "You review an assistant's reply before it is sent to a user. \
+Reply BLOCK: <short reason> if the reply gives content on any of these sensitive topics \
+that should not be sent: {topics}. Passing mentions, refusals, and referrals to a \
+professional are fine. Otherwise reply with only ALLOW.";
+
+/// Topics withheld on `family_safe` channels in addition to `restricted_topics`.
+const FAMILY_SAFE_TOPICS: &[&str] = &[
+    "sexual content",
+    "graphic violence or gore",
+    "how to obtain or use illegal drugs",
+    "methods of self-harm",
+];
+
+/// Output settings for one channel's audience.
+struct Audience {
+    profanity: ProfanityFilter,
+    allowed_words: Vec<String>,
+    topics: Vec<String>,
+}
+
+impl Audience {
+    /// `None` when the settings change nothing.
+    fn from_config(config: &ChannelAudienceConfig) -> Option<Self> {
+        let builtin = if config.family_safe {
+            profanity::WORDLIST
+        } else {
+            &[]
+        };
+        let profanity = ProfanityFilter::new(
+            builtin
+                .iter()
+                .copied()
+                .chain(config.extra_words.iter().map(String::as_str)),
+            &config.allowed_words,
+        );
+        let family_topics = if config.family_safe {
+            FAMILY_SAFE_TOPICS
+        } else {
+            &[]
+        };
+        let topics: Vec<String> = family_topics
+            .iter()
+            .map(|topic| (*topic).to_string())
+            .chain(
+                config
+                    .restricted_topics
+                    .iter()
+                    .map(|topic| topic.trim().to_string())
+                    .filter(|topic| !topic.is_empty()),
+            )
+            .collect();
+        if profanity.is_empty() && topics.is_empty() {
+            return None;
+        }
+        Some(Self {
+            profanity,
+            allowed_words: config.allowed_words.clone(),
+            topics,
+        })
+    }
+}
+
+/// Checks replies against `[channels_config.guardrails]`.
+pub struct OutputGuard {
+    config: OutputGuardrailsConfig,
+    keywords: Vec<(String, Regex)>,
+    patterns: Vec<Regex>,
+    audiences: HashMap<String, Audience>,
+    alert: Option<(Arc<dyn Channel>, String)>,
+}
+
+impl OutputGuard {
+    /// `None` when guardrails are disabled and no channel has an audience.
+    pub fn new(
+        config: &OutputGuardrailsConfig,
+        audiences: &HashMap<String, ChannelAudienceConfig>,
+    ) -> Option<Self> {
+        let audiences: HashMap<String, Audience> = audiences
+            .iter()
+            .filter_map(|(channel, audience)| {
+                Audience::from_config(audience).map(|audience| (channel.clone(), audience))
+            })
+            .collect();
+        if !config.enabled && audiences.is_empty() {
+            return None;
+        }
+        // Audiences work without the global guardrails; only the reply and
+        // alert settings carry over then.
+        let config = if config.enabled {
+            config.clone()
+        } else {
+            OutputGuardrailsConfig {
+                blocked_reply: config.blocked_reply.clone(),
+                alert_channel: config.alert_channel.clone(),
+                alert_to: config.alert_to.clone(),
+                ..OutputGuardrailsConfig::default()
+            }
+        };
+        let keywords = config
+            .blocked_keywords
+            .iter()
+            .map(|keyword| keyword.trim())
+            .filter(|keyword| !keyword.is_empty())
+            .filter_map(|keyword| {
+                let pattern = format!(r"(?i)(^|\W){}($|\W)", regex::escape(keyword));
+                Regex::new(&pattern)
+                    .ok()
+                    .map(|re| (keyword.to_string(), re))
+            })
+            .collect();
+        let patterns = config
+            .blocked_patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid guardrail pattern {pattern:?}: {e}");
+                    None
+                }
+            })
+            .collect();
+        Some(Self {
+            keywords,
+            patterns,
+            audiences,
+            config,
+            alert: None,
+        })
+    }
+
+    /// Forward withheld replies to `recipient` on `channel` in addition to logging them.
+    pub fn with_alert_channel(mut self, channel: Arc<dyn Channel>, recipient: String) -> Self {
+        self.alert = Some((channel, recipient));
+        self
+    }
+
+    /// What the user receives instead of a withheld reply. Empty: nothing.
+    pub fn blocked_reply(&self) -> &str {
+        self.config.blocked_reply.trim()
+    }
+
+    /// The blocklist entry a reply matches, if any.
+    pub fn screen(&self, text: &str) -> Option<String> {
+        if let Some((keyword, _)) = self.keywords.iter().find(|(_, re)| re.is_match(text)) {
+            return Some(format!("blocked keyword \"{keyword}\""));
+        }
+        self.patterns
+            .iter()
+            .find(|re| re.is_match(text))
+            .map(|re| format!("blocked pattern /{}/", re.as_str()))
+    }
+
+    /// Ask the policy model whether a reply on `channel` covers a sensitive
+    /// topic. Model errors let the reply through.
+    pub async fn policy_check(
+        &self,
+        channel: &str,
+        text: &str,
+        provider: &dyn Provider,
+        default_model: &str,
+    ) -> Option<String> {
+        let mut topics: Vec<&str> = self
+            .config
+            .policy_topics
+            .iter()
+            .map(String::as_str)
+            .collect();
+        if let Some(audience) = self.audiences.get(channel) {
+            topics.extend(audience.topics.iter().map(String::as_str));
+        }
+        if topics.is_empty() {
+            return None;
+        }
+        let model = self.config.policy_model.as_deref().unwrap_or(default_model);
+        let prompt = POLICY_PROMPT.replace("{topics}", &topics.join(", "));
+        match provider
+            .chat_with_system(Some(&prompt), text, model, 0.0)
+            .await
+        {
+            Ok(reply) => parse_policy_verdict(&reply),
+            Err(e) => {
+                tracing::warn!("Guardrail policy check failed; sending reply: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Why a reply on `channel` must be withheld, if it must.
+    pub async fn check(
+        &self,
+        channel: &str,
+        text: &str,
+        provider: &dyn Provider,
+        default_model: &str,
+    ) -> Option<String> {
+        match self.screen(text) {
+            Some(reason) => Some(reason),
+            None => {
+                self.policy_check(channel, text, provider, default_model)
+                    .await
+            }
+        }
+    }
+
+    /// Mask profanity for the channel's audience, append its disclaimer,
+    /// and enforce `max_chars`.
+    pub fn finish(&self, channel: &str, text: &str) -> String {
+        let masked;
+        let text = match self.audiences.get(channel) {
+            Some(audience) => {
+                masked = audience.profanity.mask(text, &audience.allowed_words);
+                masked.as_str()
+            }
+            None => text,
+        };
+        let disclaimer = self
+            .config
+            .disclaimers
+            .get(channel)
+            .map(|disclaimer| disclaimer.trim())
+            .filter(|disclaimer| !disclaimer.is_empty());
+        let Some(disclaimer) = disclaimer else {
+            return cap_chars(text, self.config.max_chars);
+        };
+        let budget = if self.config.max_chars == 0 {
+            0
+        } else {
+            // Keep at least one character of the reply when the disclaimer
+            // alone would fill the budget.
+            self.config
+                .max_chars
+                .saturating_sub(disclaimer.chars().count() + 2)
+                .max(1)
+        };
+        format!("{}\n\n{disclaimer}", cap_chars(text.trim_end(), budget))
+    }
+
+    /// Log a withheld reply and forward it to the alert channel, if configured.
+    pub async fn report(&self, channel: &str, sender: &str, text: &str, reason: &str) {
+        tracing::warn!(
+            channel,
+            sender,
+            reason,
+            "Reply withheld by output guardrails"
+        );
+        let Some((alert_channel, recipient)) = self.alert.as_ref() else {
+            return;
+        };
+        let preview = crate::util::truncate_with_ellipsis(
+            &crate::agent::loop_::scrub_credentials(text),
+            ALERT_PREVIEW_CHARS,
+        );
+        let notice = format!(
+            "🛡️ Withheld a reply to {sender} on {channel} ({reason}). Review it below and \
+             forward it yourself if it is fine.\n\n{preview}"
+        );
+        if let Err(e) = alert_channel
+            .send(&SendMessage::new(notice, recipient))
+            .await
+        {
+            tracing::warn!(
+                "Failed to send guardrail alert on {}: {e}",
+                alert_channel.name()
+            );
+        }
+    }
+}
+
+/// `BLOCK: reason` → `Some("policy: reason")`; anything else allows.
+fn parse_policy_verdict(reply: &str) -> Option<String> {
+    let reply = reply.trim();
+    let head = reply.get(..5)?;
+    if !head.eq_ignore_ascii_case("block") {
+        return None;
+    }
+    let reason = reply[5..].trim_start_matches([':', ' ', '-']).trim();
+    let reason = reason.lines().next().unwrap_or_default();
+    Some(if reason.is_empty() {
+        "policy check".to_string()
+    } else {
+        format!("policy: {reason}")
+    })
+}
+
+/// Cut `text` to at most `max_chars` characters, ending in `…` when cut.
+/// `0` means unlimited.
+fn cap_chars(text: &str, max_chars: usize) -> String {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars - 1).collect();
+    format!("{}…", kept.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    struct ScriptedProvider {
+        reply: String,
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(self.reply.clone())
+        }
+    }
+
+    fn guard(config: OutputGuardrailsConfig) -> OutputGuard {
+        OutputGuard::new(
+            &OutputGuardrailsConfig {
+                enabled: true,
+                ..config
+            },
+            &HashMap::new(),
+        )
+        .expect("enabled guard")
+    }
+
+    #[test]
+    fn disabled_config_builds_no_guard() {
+        assert!(OutputGuard::new(&OutputGuardrailsConfig::default(), &HashMap::new()).is_none());
+    }
+
+    #[test]
+    fn screen_matches_whole_keywords_and_patterns() {
+        let guard = guard(OutputGuardrailsConfig {
+            blocked_keywords: vec!["Project Falcon".into()],
+            blocked_patterns: vec![r"\b\d{3}-\d{2}-\d{4}\b".into()],
+            ..OutputGuardrailsConfig::default()
+        });
+
+        assert!(guard
+            .screen("The project falcon launch moved.")
+            .unwrap()
+            .contains("Project Falcon"));
+        assert!(guard
+            .screen("SSN on file: 123-45-6789")
+            .unwrap()
+            .contains("pattern"));
+        assert!(guard.screen("Project Falconry is a hobby.").is_none());
+        assert!(guard.screen("All clear.").is_none());
+    }
+
+    #[test]
+    fn finish_appends_disclaimer_within_max_chars() {
+        let guard = guard(OutputGuardrailsConfig {
+            max_chars: 30,
+            disclaimers: HashMap::from([("telegram".to_string(), "Not advice.".to_string())]),
+            ..OutputGuardrailsConfig::default()
+        });
+
+        assert_eq!(guard.finish("telegram", "Short."), "Short.\n\nNot advice.");
+        let long = guard.finish("telegram", &"word ".repeat(20));
+        assert!(long.chars().count() <= 30);
+        assert!(long.ends_with("…\n\nNot advice."));
+        assert_eq!(guard.finish("discord", "Short."), "Short.");
+        assert_eq!(guard.finish("discord", &"x".repeat(40)).chars().count(), 30);
+    }
+
+    #[tokio::test]
+    async fn policy_check_blocks_on_model_verdict() {
+        let checked = guard(OutputGuardrailsConfig {
+            policy_topics: vec!["medical dosing".into()],
+            ..OutputGuardrailsConfig::default()
+        });
+        let block = ScriptedProvider {
+            reply: "BLOCK: gives a dosage".into(),
+        };
+        let allow = ScriptedProvider {
+            reply: "ALLOW".into(),
+        };
+
+        assert_eq!(
+            checked
+                .check("telegram", "Take 800mg", &block, "model")
+                .await
+                .as_deref(),
+            Some("policy: gives a dosage")
+        );
+        assert!(checked
+            .check("telegram", "See a doctor", &allow, "model")
+            .await
+            .is_none());
+        assert!(
+            guard(OutputGuardrailsConfig::default())
+                .check("telegram", "Take 800mg", &block, "model")
+                .await
+                .is_none(),
+            "no topics means no model check"
+        );
+    }
+
+    #[tokio::test]
+    async fn family_safe_audience_applies_only_to_its_channel() {
+        let audiences = HashMap::from([(
+            "whatsapp".to_string(),
+            ChannelAudienceConfig {
+                family_safe: true,
+                restricted_topics: vec!["politics".into()],
+                ..ChannelAudienceConfig::default()
+            },
+        )]);
+        // Global guardrails stay off, so their blocklist is ignored.
+        let guard = OutputGuard::new(
+            &OutputGuardrailsConfig {
+                blocked_keywords: vec!["crap".into()],
+                ..OutputGuardrailsConfig::default()
+            },
+            &audiences,
+        )
+        .expect("audience enables the guard");
+        let block = ScriptedProvider {
+            reply: "BLOCK: election talk".into(),
+        };
+
+        assert_eq!(guard.finish("whatsapp", "Well, crap."), "Well, c***.");
+        assert_eq!(guard.finish("telegram", "Well, crap."), "Well, crap.");
+        assert!(guard.screen("Well, crap.").is_none());
+        assert!(guard
+            .check("whatsapp", "Vote for...", &block, "model")
+            .await
+            .is_some());
+        assert!(guard
+            .check("telegram", "Vote for...", &block, "model")
+            .await
+            .is_none());
+    }
+}
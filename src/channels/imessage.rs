@@ -1,4 +1,5 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::rich_text::{self, RichTextFormat};
+use crate::channels::traits::{Channel, ChannelMessage, GeoLocation, SendMessage};
 use async_trait::async_trait;
 use directories::UserDirs;
 use rusqlite::{Connection, OpenFlags};
@@ -105,7 +106,9 @@ impl Channel for IMessageChannel {
 
         // SECURITY: Escape both message AND target to prevent AppleScript injection
         // See: CWE-78 (OS Command Injection)
-        let escaped_msg = escape_applescript(&message.content);
+        // Messages shows text verbatim, so Markdown is flattened first.
+        let plain = rich_text::render(&message.content, RichTextFormat::Plain);
+        let escaped_msg = escape_applescript(&plain);
         let escaped_target = escape_applescript(&message.recipient);
 
         let script = format!(
@@ -176,29 +179,8 @@ end tell"#
 
             let since = last_rowid;
             let (returned_conn, poll_result) = tokio::task::spawn_blocking(
-                move || -> (Connection, anyhow::Result<Vec<(i64, String, String)>>) {
-                    let result = (|| -> anyhow::Result<Vec<(i64, String, String)>> {
-                        let mut stmt = conn.prepare(
-                            "SELECT m.ROWID, h.id, m.text \
-                     FROM message m \
-                     JOIN handle h ON m.handle_id = h.ROWID \
-                     WHERE m.ROWID > ?1 \
-                     AND m.is_from_me = 0 \
-                     AND m.text IS NOT NULL \
-                     ORDER BY m.ROWID ASC \
-                     LIMIT 20",
-                        )?;
-                        let rows = stmt.query_map([since], |row| {
-                            Ok((
-                                row.get::<_, i64>(0)?,
-                                row.get::<_, String>(1)?,
-                                row.get::<_, String>(2)?,
-                            ))
-                        })?;
-                        let results = rows.collect::<Result<Vec<_>, _>>()?;
-                        Ok(results)
-                    })();
-
+                move || -> (Connection, anyhow::Result<Vec<PolledMessage>>) {
+                    let result = query_new_messages(&conn, since);
                     (conn, result)
                 },
             )
@@ -208,7 +190,7 @@ end tell"#
 
             match poll_result {
                 Ok(messages) => {
-                    for (rowid, sender, text) in messages {
+                    for (rowid, sender, text, thread_guid) in messages {
                         if rowid > last_rowid {
                             last_rowid = rowid;
                         }
@@ -225,13 +207,16 @@ end tell"#
                             id: rowid.to_string(),
                             sender: sender.clone(),
                             reply_target: sender.clone(),
-                            content: text,
                             channel: "imessage".to_string(),
                             timestamp: std::time::SystemTime::now()
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap_or_default()
                                 .as_secs(),
-                            thread_ts: None,
+                            // Inline replies share the GUID of the message that started the thread.
+                            thread_ts: thread_guid,
+                            location: parse_shared_location(&text),
+                            content: text,
+                            payload: None,
                         };
 
                         if tx.send(msg).await.is_err() {
@@ -259,6 +244,41 @@ end tell"#
     }
 }
 
+/// Extract the point from an Apple Maps link, which is how locations shared
+/// from Maps ("Send My Location", pinned places) arrive in message text.
+/// `ll=` carries the coordinates and `q=` the place name.
+fn parse_shared_location(text: &str) -> Option<GeoLocation> {
+    let start = text.find("maps.apple.com/?")?;
+    let query = text[start + "maps.apple.com/?".len()..]
+        .split(char::is_whitespace)
+        .next()?;
+    let mut point = None;
+    let mut label = None;
+    for pair in query.split('&') {
+        let Some((key, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let value = urlencoding::decode(&value.replace('+', " "))
+            .ok()?
+            .into_owned();
+        match key {
+            "ll" => {
+                point = value
+                    .split_once(',')
+                    .map(|(lat, lon)| (lat.to_string(), lon.to_string()));
+            }
+            "q" => label = Some(value),
+            _ => {}
+        }
+    }
+    let (latitude, longitude) = point?;
+    GeoLocation::new(
+        latitude.trim().parse().ok()?,
+        longitude.trim().parse().ok()?,
+    )
+    .map(|location| location.with_label(label))
+}
+
 /// Get the current max ROWID from the messages table.
 /// Uses rusqlite with parameterized queries for security (CWE-89 prevention).
 async fn get_max_rowid(db_path: &Path) -> anyhow::Result<i64> {
@@ -276,40 +296,61 @@ async fn get_max_rowid(db_path: &Path) -> anyhow::Result<i64> {
     Ok(result)
 }
 
-/// Fetch messages newer than `since_rowid`.
+/// A polled inbound message: `(rowid, sender, text, thread originator GUID)`.
+type PolledMessage = (i64, String, String, Option<String>);
+
+/// Query up to 20 inbound messages newer than `since_rowid`.
 /// Uses rusqlite with parameterized queries for security (CWE-89 prevention).
 /// The `since_rowid` parameter is bound safely, preventing SQL injection.
+fn query_new_messages(conn: &Connection, since_rowid: i64) -> anyhow::Result<Vec<PolledMessage>> {
+    // `thread_originator_guid` only exists on macOS 14+ databases.
+    let has_thread_column: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info('message') \
+         WHERE name = 'thread_originator_guid'",
+        [],
+        |row| row.get(0),
+    )?;
+    let thread_column = if has_thread_column {
+        "m.thread_originator_guid"
+    } else {
+        "NULL"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT m.ROWID, h.id, m.text, {thread_column} \
+         FROM message m \
+         JOIN handle h ON m.handle_id = h.ROWID \
+         WHERE m.ROWID > ?1 \
+         AND m.is_from_me = 0 \
+         AND m.text IS NOT NULL \
+         ORDER BY m.ROWID ASC \
+         LIMIT 20"
+    ))?;
+    let rows = stmt.query_map([since_rowid], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?
+                .filter(|guid| !guid.is_empty()),
+        ))
+    })?;
+    rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+}
+
+/// Fetch messages newer than `since_rowid`.
 async fn fetch_new_messages(
     db_path: &Path,
     since_rowid: i64,
-) -> anyhow::Result<Vec<(i64, String, String)>> {
+) -> anyhow::Result<Vec<PolledMessage>> {
     let path = db_path.to_path_buf();
-    let results =
-        tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<(i64, String, String)>> {
-            let conn = Connection::open_with_flags(
-                &path,
-                OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
-            )?;
-            let mut stmt = conn.prepare(
-                "SELECT m.ROWID, h.id, m.text \
-             FROM message m \
-             JOIN handle h ON m.handle_id = h.ROWID \
-             WHERE m.ROWID > ?1 \
-             AND m.is_from_me = 0 \
-             AND m.text IS NOT NULL \
-             ORDER BY m.ROWID ASC \
-             LIMIT 20",
-            )?;
-            let rows = stmt.query_map([since_rowid], |row| {
-                Ok((
-                    row.get::<_, i64>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                ))
-            })?;
-            rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
-        })
-        .await??;
+    let results = tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<PolledMessage>> {
+        let conn = Connection::open_with_flags(
+            &path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        query_new_messages(&conn, since_rowid)
+    })
+    .await??;
     Ok(results)
 }
 
@@ -317,6 +358,27 @@ async fn fetch_new_messages(
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_shared_location_reads_apple_maps_links() {
+        let location = parse_shared_location(
+            "Meet here https://maps.apple.com/?ll=37.331705,-122.030237&q=Apple%20Park thanks",
+        )
+        .unwrap();
+        assert!((location.latitude - 37.331_705).abs() < f64::EPSILON);
+        assert!((location.longitude + 122.030_237).abs() < f64::EPSILON);
+        assert_eq!(location.label.as_deref(), Some("Apple Park"));
+
+        let unlabeled = parse_shared_location("https://maps.apple.com/?ll=1.5,2.5").unwrap();
+        assert_eq!(unlabeled.label, None);
+    }
+
+    #[test]
+    fn parse_shared_location_ignores_other_text() {
+        assert!(parse_shared_location("see you at noon").is_none());
+        assert!(parse_shared_location("https://maps.apple.com/?q=Cupertino").is_none());
+        assert!(parse_shared_location("https://maps.apple.com/?ll=95.0,10.0").is_none());
+    }
+
     #[test]
     fn creates_with_contacts() {
         let ch = IMessageChannel::new(vec!["+1234567890".into()]);
@@ -704,14 +766,20 @@ mod tests {
         assert_eq!(result.len(), 2);
         assert_eq!(
             result[0],
-            (10, "+1234567890".to_string(), "First message".to_string())
+            (
+                10,
+                "+1234567890".to_string(),
+                "First message".to_string(),
+                None
+            )
         );
         assert_eq!(
             result[1],
             (
                 20,
                 "user@example.com".to_string(),
-                "Second message".to_string()
+                "Second message".to_string(),
+                None
             )
         );
     }
@@ -745,6 +813,29 @@ mod tests {
         assert_eq!(result[0].2, "New message");
     }
 
+    #[tokio::test]
+    async fn fetch_new_messages_reads_thread_originator_when_present() {
+        let (_dir, db_path) = create_test_db();
+
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "ALTER TABLE message ADD COLUMN thread_originator_guid TEXT;
+                 INSERT INTO handle (ROWID, id) VALUES (1, '+1234567890');
+                 INSERT INTO message (ROWID, handle_id, text, is_from_me, thread_originator_guid)
+                     VALUES (10, 1, 'Top level', 0, NULL);
+                 INSERT INTO message (ROWID, handle_id, text, is_from_me, thread_originator_guid)
+                     VALUES (20, 1, 'Inline reply', 0, 'GUID-ROOT');",
+            )
+            .unwrap();
+        }
+
+        let result = fetch_new_messages(&db_path, 0).await.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].3, None);
+        assert_eq!(result[1].3.as_deref(), Some("GUID-ROOT"));
+    }
+
     #[tokio::test]
     async fn fetch_new_messages_excludes_sent_messages() {
         let (_dir, db_path) = create_test_db();
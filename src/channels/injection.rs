@@ -0,0 +1,173 @@
+//! Programmatic access to the running channel runtime.
+//!
+//! `start_channels` registers its message queue and conversation histories
+//! here so the gateway REST API (running in the same daemon process) can
+//! inject messages into a channel conversation and read conversation history.
+//! Injected messages go through the same dispatch path as messages a channel
+//! received itself; the reply is sent on that channel to the recipient.
+
+use super::traits::ChannelMessage;
+use super::ConversationHistoryMap;
+use crate::providers::ChatMessage;
+use parking_lot::Mutex;
+use std::sync::LazyLock;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Why a message could not be injected.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InjectError {
+    #[error("channels are not running in this process; start them with `zeroclaw daemon`")]
+    NotRunning,
+    #[error("channel `{0}` is not running")]
+    UnknownChannel(String),
+    #[error("the channel message queue is full; retry shortly")]
+    Busy,
+}
+
+/// A message to inject as if the recipient had sent it.
+#[derive(Debug, Clone)]
+pub struct Injection {
+    pub channel: String,
+    /// Where the reply is sent (chat id, room, address, ...).
+    pub recipient: String,
+    /// Conversation owner; defaults to the recipient.
+    pub sender: Option<String>,
+    pub thread: Option<String>,
+    pub content: String,
+}
+
+/// Where an injected message went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injected {
+    pub message_id: String,
+    pub conversation_id: String,
+}
+
+struct Runtime {
+    tx: mpsc::WeakSender<ChannelMessage>,
+    channels: Vec<String>,
+    histories: ConversationHistoryMap,
+}
+
+static RUNTIME: LazyLock<Mutex<Option<Runtime>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Called by `start_channels`. Holds only a weak handle to the queue so the
+/// dispatch loop still ends when every channel listener stops.
+pub(super) fn register(
+    tx: mpsc::WeakSender<ChannelMessage>,
+    channels: Vec<String>,
+    histories: ConversationHistoryMap,
+) {
+    *RUNTIME.lock() = Some(Runtime {
+        tx,
+        channels,
+        histories,
+    });
+}
+
+/// Queue a message for the agent on a running channel.
+pub fn inject(injection: Injection) -> Result<Injected, InjectError> {
+    let tx = {
+        let runtime = RUNTIME.lock();
+        let runtime = runtime.as_ref().ok_or(InjectError::NotRunning)?;
+        if !runtime.channels.contains(&injection.channel) {
+            return Err(InjectError::UnknownChannel(injection.channel));
+        }
+        runtime.tx.upgrade().ok_or(InjectError::NotRunning)?
+    };
+    let msg = ChannelMessage {
+        id: format!("api_{}", Uuid::new_v4()),
+        sender: injection
+            .sender
+            .unwrap_or_else(|| injection.recipient.clone()),
+        reply_target: injection.recipient,
+        content: injection.content,
+        channel: injection.channel,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        thread_ts: injection.thread,
+        location: None,
+        payload: None,
+    };
+    let injected = Injected {
+        message_id: msg.id.clone(),
+        conversation_id: super::conversation_history_key(&msg),
+    };
+    tx.try_send(msg).map_err(|err| match err {
+        mpsc::error::TrySendError::Full(_) => InjectError::Busy,
+        mpsc::error::TrySendError::Closed(_) => InjectError::NotRunning,
+    })?;
+    Ok(injected)
+}
+
+/// Turns kept for a conversation (`<channel>_<sender>` or
+/// `<channel>_<thread>_<sender>`), or `None` if it has no history.
+pub fn history(conversation_id: &str) -> Result<Option<Vec<ChatMessage>>, InjectError> {
+    let runtime = RUNTIME.lock();
+    let runtime = runtime.as_ref().ok_or(InjectError::NotRunning)?;
+    let histories = runtime.histories.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(histories.get(conversation_id).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn injected_messages_reach_the_dispatch_queue_and_history_is_readable() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let histories: ConversationHistoryMap = Arc::new(std::sync::Mutex::new(HashMap::new()));
+        histories.lock().unwrap().insert(
+            "telegram_alice".into(),
+            vec![ChatMessage::user("hi"), ChatMessage::assistant("hello")],
+        );
+        register(
+            tx.downgrade(),
+            vec!["telegram".into()],
+            Arc::clone(&histories),
+        );
+
+        let injected = inject(Injection {
+            channel: "telegram".into(),
+            recipient: "12345".into(),
+            sender: Some("alice".into()),
+            thread: None,
+            content: "status report please".into(),
+        })
+        .unwrap();
+        assert_eq!(injected.conversation_id, "telegram_alice");
+        let msg = rx.recv().await.unwrap();
+        assert_eq!(msg.reply_target, "12345");
+        assert_eq!(msg.content, "status report please");
+        assert_eq!(msg.id, injected.message_id);
+
+        let unknown = inject(Injection {
+            channel: "discord".into(),
+            recipient: "x".into(),
+            sender: None,
+            thread: None,
+            content: "hi".into(),
+        });
+        assert_eq!(unknown, Err(InjectError::UnknownChannel("discord".into())));
+
+        let turns = history("telegram_alice").unwrap().unwrap();
+        assert_eq!(turns.len(), 2);
+        assert!(history("telegram_bob").unwrap().is_none());
+
+        drop(tx);
+        drop(rx);
+        let closed = inject(Injection {
+            channel: "telegram".into(),
+            recipient: "12345".into(),
+            sender: None,
+            thread: None,
+            content: "hi".into(),
+        });
+        assert_eq!(closed, Err(InjectError::NotRunning));
+    }
+}
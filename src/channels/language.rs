@@ -0,0 +1,254 @@
+//! Inbound language detection and per-conversation language lock
+//! (`[channels_config.language]`).
+//!
+//! Each inbound message long enough to classify is run through `whatlang`.
+//! The first confident detection pins the conversation's reply language; it
+//! moves only after two consecutive messages in another language, so a quoted
+//! phrase or a one-off "merci" does not flip it. The pinned language is
+//! passed to the model as a prompt hint. `/lang <language>` overrides it
+//! through the sender's `language` preference and `/lang auto` returns to
+//! detection.
+
+use super::preferences;
+use crate::config::ChannelLanguageConfig;
+use parking_lot::Mutex;
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use whatlang::Lang;
+
+/// Conversations tracked before the oldest lock is forgotten.
+const LOCK_CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+struct Pinned {
+    language: Lang,
+    /// Another language seen in the latest message, awaiting confirmation.
+    pending: Option<Lang>,
+}
+
+#[derive(Default)]
+struct Locks {
+    by_conversation: HashMap<String, Pinned>,
+    order: VecDeque<String>,
+}
+
+/// Detect the language of `text`. `None` for text shorter than `min_chars`
+/// letters or when the detection is not reliable.
+pub fn detect(text: &str, min_chars: usize) -> Option<Lang> {
+    if text.chars().filter(|c| c.is_alphabetic()).count() < min_chars {
+        return None;
+    }
+    whatlang::detect(text)
+        .filter(whatlang::Info::is_reliable)
+        .map(|info| info.lang())
+}
+
+/// Resolve a user-supplied language name or ISO 639-3 code to its English
+/// name, e.g. "deutsch", "deu", or "German" → "German".
+pub fn resolve_name(input: &str) -> Option<&'static str> {
+    let input = input.trim();
+    Lang::all()
+        .iter()
+        .find(|lang| {
+            lang.eng_name().eq_ignore_ascii_case(input)
+                || lang.name().to_lowercase() == input.to_lowercase()
+                || lang.code().eq_ignore_ascii_case(input)
+        })
+        .map(|lang| lang.eng_name())
+}
+
+/// Prompt hint for a conversation pinned to `language`.
+pub fn prompt_section(language: &str) -> String {
+    format!(
+        "## Reply Language\n\nThis conversation is in {language}. Reply in {language} unless \
+         the user asks for another language."
+    )
+}
+
+/// Per-conversation reply language pinned from detected inbound languages.
+pub struct LanguageLock {
+    min_chars: usize,
+    locks: Mutex<Locks>,
+}
+
+impl LanguageLock {
+    /// `None` when detection is disabled.
+    pub fn new(config: &ChannelLanguageConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            min_chars: config.min_chars,
+            locks: Mutex::new(Locks::default()),
+        })
+    }
+
+    /// Record an inbound message and return the conversation's pinned
+    /// language (English name), if any.
+    pub fn observe(&self, conversation: &str, text: &str) -> Option<&'static str> {
+        let detected = detect(text, self.min_chars);
+        let mut locks = self.locks.lock();
+        let Some(detected) = detected else {
+            return locks
+                .by_conversation
+                .get(conversation)
+                .map(|pinned| pinned.language.eng_name());
+        };
+        if let Some(pinned) = locks.by_conversation.get_mut(conversation) {
+            if pinned.language == detected {
+                pinned.pending = None;
+            } else if pinned.pending == Some(detected) {
+                tracing::debug!(
+                    conversation,
+                    from = pinned.language.eng_name(),
+                    to = detected.eng_name(),
+                    "Conversation language changed"
+                );
+                *pinned = Pinned {
+                    language: detected,
+                    pending: None,
+                };
+            } else {
+                pinned.pending = Some(detected);
+            }
+            return Some(pinned.language.eng_name());
+        }
+        locks.by_conversation.insert(
+            conversation.to_string(),
+            Pinned {
+                language: detected,
+                pending: None,
+            },
+        );
+        locks.order.push_back(conversation.to_string());
+        while locks.order.len() > LOCK_CAPACITY {
+            if let Some(oldest) = locks.order.pop_front() {
+                locks.by_conversation.remove(&oldest);
+            }
+        }
+        Some(detected.eng_name())
+    }
+
+    /// The conversation's pinned language, if any.
+    pub fn current(&self, conversation: &str) -> Option<&'static str> {
+        self.locks
+            .lock()
+            .by_conversation
+            .get(conversation)
+            .map(|pinned| pinned.language.eng_name())
+    }
+
+    /// Forget the conversation's pinned language.
+    pub fn reset(&self, conversation: &str) {
+        let mut locks = self.locks.lock();
+        if locks.by_conversation.remove(conversation).is_some() {
+            locks.order.retain(|key| key != conversation);
+        }
+    }
+}
+
+/// Handle `/lang [language|auto]`.
+pub fn command_response(
+    workspace_dir: &Path,
+    lock: Option<&LanguageLock>,
+    channel: &str,
+    sender: &str,
+    conversation: &str,
+    args: &str,
+) -> String {
+    let args = args.trim();
+    if args.is_empty() {
+        let manual = preferences::get_all(workspace_dir, channel, sender)
+            .ok()
+            .and_then(|prefs| prefs.get("language").cloned());
+        return match (manual, lock.and_then(|lock| lock.current(conversation))) {
+            (Some(language), _) => format!(
+                "Replying in {language} (set with `/lang`). Use `/lang auto` to detect it from your messages."
+            ),
+            (None, Some(language)) => format!(
+                "Replying in {language} (detected). Use `/lang <language>` to choose another."
+            ),
+            (None, None) if lock.is_some() => "No reply language pinned yet; it is detected from your messages. Use `/lang <language>` to choose one.".to_string(),
+            (None, None) => "No reply language set. Use `/lang <language>` to choose one.".to_string(),
+        };
+    }
+
+    if args.eq_ignore_ascii_case("auto") {
+        if let Err(e) = preferences::clear(workspace_dir, channel, sender, Some("language")) {
+            return format!("Failed to clear the language: {e}");
+        }
+        if let Some(lock) = lock {
+            lock.reset(conversation);
+        }
+        return if lock.is_some() {
+            "Language set to automatic; it will be detected from your next message.".to_string()
+        } else {
+            "Language preference cleared.".to_string()
+        };
+    }
+
+    let requested = resolve_name(args).unwrap_or(args);
+    match preferences::set(workspace_dir, channel, sender, "language", requested) {
+        Ok((_, value)) => format!("I'll reply in {value}. Use `/lang auto` to detect it again."),
+        Err(e) => format!("{e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const ENGLISH: &str = "Could you please tell me what the weather will be like tomorrow?";
+    const GERMAN: &str = "Kannst du mir bitte sagen, wie das Wetter morgen sein wird?";
+
+    fn lock() -> LanguageLock {
+        LanguageLock::new(&ChannelLanguageConfig {
+            enabled: true,
+            ..ChannelLanguageConfig::default()
+        })
+        .expect("enabled lock")
+    }
+
+    #[test]
+    fn short_messages_are_not_classified() {
+        assert_eq!(detect("ok danke", 16), None);
+        assert_eq!(detect(GERMAN, 16), Some(Lang::Deu));
+    }
+
+    #[test]
+    fn lock_moves_only_after_two_messages_in_another_language() {
+        let lock = lock();
+        assert_eq!(lock.observe("c1", ENGLISH), Some("English"));
+        assert_eq!(lock.observe("c1", "ok"), Some("English"));
+        assert_eq!(lock.observe("c1", GERMAN), Some("English"));
+        assert_eq!(lock.observe("c1", ENGLISH), Some("English"));
+        assert_eq!(lock.observe("c1", GERMAN), Some("English"));
+        assert_eq!(lock.observe("c1", GERMAN), Some("German"));
+        assert_eq!(lock.current("c2"), None);
+
+        lock.reset("c1");
+        assert_eq!(lock.current("c1"), None);
+    }
+
+    #[test]
+    fn lang_command_sets_and_clears_the_preference() {
+        let tmp = TempDir::new().unwrap();
+        let lock = lock();
+        lock.observe("c1", ENGLISH);
+
+        let set = command_response(
+            tmp.path(),
+            Some(&lock),
+            "telegram",
+            "alice",
+            "c1",
+            "deutsch",
+        );
+        assert!(set.contains("German"), "{set}");
+        let shown = command_response(tmp.path(), Some(&lock), "telegram", "alice", "c1", "");
+        assert!(shown.contains("German (set with `/lang`)"), "{shown}");
+
+        command_response(tmp.path(), Some(&lock), "telegram", "alice", "c1", "auto");
+        assert_eq!(lock.current("c1"), None);
+        let shown = command_response(tmp.path(), Some(&lock), "telegram", "alice", "c1", "");
+        assert!(shown.contains("No reply language pinned"), "{shown}");
+    }
+}
@@ -0,0 +1,355 @@
+//! Outbound link handling: tracking-parameter stripping, shortener expansion,
+//! and Discord embed suppression.
+
+use crate::config::LinkPolicyConfig;
+use regex::Regex;
+use reqwest::Url;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// Query parameters that only identify a campaign or click. A trailing `*`
+/// matches by prefix.
+const TRACKING_PARAMS: &[&str] = &[
+    "utm_*",
+    "fbclid",
+    "gclid",
+    "gbraid",
+    "wbraid",
+    "dclid",
+    "msclkid",
+    "mc_cid",
+    "mc_eid",
+    "igshid",
+    "yclid",
+    "twclid",
+    "_hsenc",
+    "_hsmi",
+    "mkt_tok",
+    "vero_id",
+    "oly_anon_id",
+    "oly_enc_id",
+    "rb_clickid",
+    "s_cid",
+];
+
+/// Hosts whose links are only redirects. Expansion is limited to these so
+/// replies never trigger requests to arbitrary sites.
+const SHORTENER_HOSTS: &[&str] = &[
+    "bit.ly",
+    "buff.ly",
+    "cutt.ly",
+    "goo.gl",
+    "is.gd",
+    "lnkd.in",
+    "ow.ly",
+    "rebrand.ly",
+    "shorturl.at",
+    "t.co",
+    "t.ly",
+    "tinyurl.com",
+];
+
+const MAX_SHORTENER_HOPS: usize = 3;
+const SHORTENER_TIMEOUT_SECS: u64 = 5;
+
+static URL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s<>"'`]+"#).expect("valid URL regex"));
+
+/// Byte ranges of the URLs in `text`, without trailing punctuation or an
+/// unbalanced closing bracket (e.g. the `)` of a Markdown link).
+pub(crate) fn find_urls(text: &str) -> Vec<Range<usize>> {
+    URL_RE
+        .find_iter(text)
+        .map(|found| {
+            let mut url = found.as_str();
+            loop {
+                let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_', '~']);
+                let trimmed = match trimmed.chars().last() {
+                    Some(close @ (')' | ']')) => {
+                        let open = if close == ')' { '(' } else { '[' };
+                        if trimmed.matches(close).count() > trimmed.matches(open).count() {
+                            &trimmed[..trimmed.len() - 1]
+                        } else {
+                            trimmed
+                        }
+                    }
+                    _ => trimmed,
+                };
+                if trimmed.len() == url.len() {
+                    break;
+                }
+                url = trimmed;
+            }
+            found.start()..found.start() + url.len()
+        })
+        .collect()
+}
+
+fn is_tracking_param(name: &str, extra: &[String]) -> bool {
+    TRACKING_PARAMS
+        .iter()
+        .copied()
+        .chain(extra.iter().map(String::as_str))
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name
+                .get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix)),
+            None => name.eq_ignore_ascii_case(pattern),
+        })
+}
+
+/// Remove tracking query parameters from `url`. URLs without tracking
+/// parameters are returned unchanged.
+pub fn strip_tracking_params(url: &str, extra: &[String]) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    let kept: Vec<&(String, String)> = pairs
+        .iter()
+        .filter(|(key, _)| !is_tracking_param(key, extra))
+        .collect();
+    if kept.len() == pairs.len() {
+        return url.to_string();
+    }
+
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    parsed.to_string()
+}
+
+fn is_shortener(url: &Url) -> bool {
+    url.host_str().is_some_and(|host| {
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        SHORTENER_HOSTS
+            .iter()
+            .any(|shortener| host.eq_ignore_ascii_case(shortener))
+    })
+}
+
+/// Whether `url` points at a known link shortener.
+pub(crate) fn is_shortened(url: &str) -> bool {
+    Url::parse(url).is_ok_and(|parsed| is_shortener(&parsed))
+}
+
+/// Follow redirects from known shorteners without fetching the destination.
+/// Any failure keeps the last URL that resolved.
+async fn expand_shortener(client: &reqwest::Client, url: &str) -> String {
+    let mut current = url.to_string();
+    for _ in 0..MAX_SHORTENER_HOPS {
+        let Ok(parsed) = Url::parse(&current) else {
+            break;
+        };
+        if !is_shortener(&parsed) {
+            break;
+        }
+        let Ok(response) = client.head(parsed.as_str()).send().await else {
+            break;
+        };
+        if !response.status().is_redirection() {
+            break;
+        }
+        let Some(next) = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| parsed.join(location).ok())
+            .filter(|next| matches!(next.scheme(), "http" | "https"))
+        else {
+            break;
+        };
+        current = next.to_string();
+    }
+    current
+}
+
+fn shortener_client() -> reqwest::Client {
+    let builder = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(std::time::Duration::from_secs(SHORTENER_TIMEOUT_SECS));
+    crate::config::apply_runtime_proxy_to_builder(builder, "channel.links")
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Apply `[channels_config.links]` to every URL in an outbound reply.
+pub async fn rewrite_urls(text: &str, policy: &LinkPolicyConfig) -> String {
+    if !policy.strip_tracking_params && !policy.expand_shorteners {
+        return text.to_string();
+    }
+    let ranges = find_urls(text);
+    if ranges.is_empty() {
+        return text.to_string();
+    }
+
+    let client = policy.expand_shorteners.then(shortener_client);
+    let mut resolved: HashMap<&str, String> = HashMap::new();
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in ranges {
+        let url = &text[range.clone()];
+        if !resolved.contains_key(url) {
+            let mut rewritten = url.to_string();
+            if let Some(client) = client.as_ref() {
+                rewritten = expand_shortener(client, &rewritten).await;
+            }
+            if policy.strip_tracking_params {
+                rewritten = strip_tracking_params(&rewritten, &policy.extra_tracking_params);
+            }
+            resolved.insert(url, rewritten);
+        }
+        output.push_str(&text[last..range.start]);
+        output.push_str(&resolved[url]);
+        last = range.end;
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+fn wrap_urls(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in find_urls(text) {
+        output.push_str(&text[last..range.start]);
+        if text[..range.start].ends_with('<') {
+            output.push_str(&text[range.clone()]);
+        } else {
+            output.push('<');
+            output.push_str(&text[range.clone()]);
+            output.push('>');
+        }
+        last = range.end;
+    }
+    output.push_str(&text[last..]);
+    output
+}
+
+/// Wrap URLs in `<...>` so Discord does not embed them. Code spans and
+/// fenced blocks are left alone, since brackets would show up literally.
+pub fn suppress_discord_embeds(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() + 16);
+    for (index, block) in text.split("```").enumerate() {
+        if index > 0 {
+            output.push_str("```");
+        }
+        if index % 2 == 1 {
+            output.push_str(block);
+            continue;
+        }
+        for (inline_index, span) in block.split('`').enumerate() {
+            if inline_index > 0 {
+                output.push('`');
+            }
+            if inline_index % 2 == 1 {
+                output.push_str(span);
+            } else {
+                output.push_str(&wrap_urls(span));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_urls_trims_punctuation_and_markdown_brackets() {
+        let text =
+            "See https://example.com/a. Or [docs](https://example.com/wiki/Rust_(lang)), ok?";
+        let urls: Vec<&str> = find_urls(text).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/a",
+                "https://example.com/wiki/Rust_(lang)"
+            ]
+        );
+    }
+
+    #[test]
+    fn strip_tracking_params_keeps_meaningful_query() {
+        assert_eq!(
+            strip_tracking_params(
+                "https://shop.example/item?id=42&utm_source=news&UTM_Medium=mail&fbclid=abc",
+                &[]
+            ),
+            "https://shop.example/item?id=42"
+        );
+        assert_eq!(
+            strip_tracking_params("https://example.com/p?utm_campaign=x#top", &[]),
+            "https://example.com/p#top"
+        );
+        assert_eq!(
+            strip_tracking_params("https://example.com/p?ref=feed&q=a%20b", &["ref".into()]),
+            "https://example.com/p?q=a+b"
+        );
+        // Untouched URLs keep their original encoding.
+        assert_eq!(
+            strip_tracking_params("https://example.com/p?q=a%20b", &[]),
+            "https://example.com/p?q=a%20b"
+        );
+    }
+
+    #[tokio::test]
+    async fn rewrite_urls_is_noop_by_default() {
+        let text = "Read https://example.com/?utm_source=x";
+        assert_eq!(rewrite_urls(text, &LinkPolicyConfig::default()).await, text);
+    }
+
+    #[tokio::test]
+    async fn rewrite_urls_strips_tracking_in_text() {
+        let policy = LinkPolicyConfig {
+            strip_tracking_params: true,
+            ..LinkPolicyConfig::default()
+        };
+        let text = "Read [this](https://example.com/post?utm_source=x&id=1) and https://example.com/?gclid=y.";
+        assert_eq!(
+            rewrite_urls(text, &policy).await,
+            "Read [this](https://example.com/post?id=1) and https://example.com/."
+        );
+    }
+
+    #[test]
+    fn shortener_hosts_are_matched_exactly() {
+        assert!(is_shortener(&Url::parse("https://bit.ly/abc").unwrap()));
+        assert!(is_shortener(
+            &Url::parse("https://www.tinyurl.com/abc").unwrap()
+        ));
+        assert!(!is_shortener(&Url::parse("https://notbit.ly/abc").unwrap()));
+        assert!(!is_shortener(
+            &Url::parse("https://example.com/t.co").unwrap()
+        ));
+    }
+
+    #[test]
+    fn suppress_discord_embeds_wraps_bare_and_masked_links() {
+        assert_eq!(
+            suppress_discord_embeds(
+                "Docs: https://docs.rs/tokio and [crate](https://crates.io/tokio)."
+            ),
+            "Docs: <https://docs.rs/tokio> and [crate](<https://crates.io/tokio>)."
+        );
+        assert_eq!(
+            suppress_discord_embeds("Already <https://example.com>"),
+            "Already <https://example.com>"
+        );
+    }
+
+    #[test]
+    fn suppress_discord_embeds_skips_code() {
+        let text = "Run `curl https://example.com` then\n```\nwget https://example.org\n```\nhttps://example.net";
+        assert_eq!(
+            suppress_discord_embeds(text),
+            "Run `curl https://example.com` then\n```\nwget https://example.org\n```\n<https://example.net>"
+        );
+    }
+}
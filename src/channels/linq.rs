@@ -213,6 +213,8 @@ impl LinqChannel {
             channel: "linq".to_string(),
             timestamp,
             thread_ts: None,
+            location: None,
+            payload: None,
         });
 
         messages
@@ -1,7 +1,6 @@
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use anyhow::{bail, Result};
 use async_trait::async_trait;
-use parking_lot::Mutex;
 
 /// Mattermost channel — polls channel posts via REST API v4.
 /// Mattermost is API-compatible with many Slack patterns but uses a dedicated v4 structure.
@@ -15,8 +14,6 @@ pub struct MattermostChannel {
     thread_replies: bool,
     /// When true, only respond to messages that @-mention the bot.
     mention_only: bool,
-    /// Handle for the background typing-indicator loop (aborted on stop_typing).
-    typing_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
 impl MattermostChannel {
@@ -37,7 +34,6 @@ impl MattermostChannel {
             allowed_users,
             thread_replies,
             mention_only,
-            typing_handle: Mutex::new(None),
         }
     }
 
@@ -48,7 +44,8 @@ impl MattermostChannel {
     /// Check if a user ID is in the allowlist.
     /// Empty list means deny everyone. "*" means allow everyone.
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        let listed = self.allowed_users.iter().any(|u| u == "*" || u == user_id);
+        super::access::check("mattermost", user_id, listed)
     }
 
     /// Get the bot's own user ID and username so we can ignore our own messages
@@ -213,57 +210,29 @@ impl Channel for MattermostChannel {
             .unwrap_or(false)
     }
 
-    async fn start_typing(&self, recipient: &str) -> Result<()> {
-        // Cancel any existing typing loop before starting a new one.
-        self.stop_typing(recipient).await?;
-
-        let client = self.http_client();
-        let token = self.bot_token.clone();
-        let base_url = self.base_url.clone();
-
-        // recipient is "channel_id" or "channel_id:root_id"
-        let (channel_id, parent_id) = match recipient.split_once(':') {
-            Some((channel, parent)) => (channel.to_string(), Some(parent.to_string())),
-            None => (recipient.to_string(), None),
-        };
-
-        let handle = tokio::spawn(async move {
-            let url = format!("{base_url}/api/v4/users/me/typing");
-            loop {
-                let mut body = serde_json::json!({ "channel_id": channel_id });
-                if let Some(ref pid) = parent_id {
-                    body.as_object_mut()
-                        .unwrap()
-                        .insert("parent_id".to_string(), serde_json::json!(pid));
-                }
-
-                if let Ok(r) = client
-                    .post(&url)
-                    .bearer_auth(&token)
-                    .json(&body)
-                    .send()
-                    .await
-                {
-                    if !r.status().is_success() {
-                        tracing::debug!(status = %r.status(), "Mattermost typing indicator failed");
-                    }
-                }
+    /// Mattermost typing events expire after about 6 seconds.
+    fn typing_refresh_interval(&self) -> Option<std::time::Duration> {
+        Some(std::time::Duration::from_secs(4))
+    }
 
-                // Mattermost typing events expire after ~6s; re-fire every 4s.
-                tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+    async fn start_typing(&self, recipient: &str) -> Result<()> {
+        // recipient is "channel_id" or "channel_id:root_id".
+        let body = match recipient.split_once(':') {
+            Some((channel_id, parent_id)) => {
+                serde_json::json!({ "channel_id": channel_id, "parent_id": parent_id })
             }
-        });
-
-        let mut guard = self.typing_handle.lock();
-        *guard = Some(handle);
-
-        Ok(())
-    }
+            None => serde_json::json!({ "channel_id": recipient }),
+        };
 
-    async fn stop_typing(&self, _recipient: &str) -> Result<()> {
-        let mut guard = self.typing_handle.lock();
-        if let Some(handle) = guard.take() {
-            handle.abort();
+        let resp = self
+            .http_client()
+            .post(format!("{}/api/v4/users/me/typing", self.base_url))
+            .bearer_auth(&self.bot_token)
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            bail!("Mattermost typing indicator failed ({})", resp.status());
         }
         Ok(())
     }
@@ -322,6 +291,8 @@ impl MattermostChannel {
             #[allow(clippy::cast_sign_loss)]
             timestamp: (create_at / 1000) as u64,
             thread_ts: None,
+            location: None,
+            payload: None,
         })
     }
 }
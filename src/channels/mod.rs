@@ -14,28 +14,60 @@
 //! To add a new channel, implement [`Channel`] in a new submodule and wire it into
 //! [`start_channels`]. See `AGENTS.md` §7.2 for the full change playbook.
 
+mod access;
+mod agents;
+pub mod broadcast;
 pub mod clawdtalk;
 pub mod cli;
+pub mod conversations;
+pub mod delegation;
 pub mod dingtalk;
 pub mod discord;
+pub mod discord_voice;
 pub mod email_channel;
+pub mod emoji;
+pub mod feedback;
+pub mod follow_ups;
+mod groups;
+pub mod guardrails;
 pub mod imessage;
+pub mod injection;
 pub mod irc;
+pub mod language;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
+pub mod links;
 pub mod linq;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
+pub mod monitor;
 pub mod nextcloud_talk;
 pub mod nostr;
+pub mod outbox;
+pub mod pairing;
+pub mod preferences;
+pub mod presence;
+pub mod profanity;
+mod prompt;
 pub mod qq;
+pub mod quarantine;
+mod queue;
+pub mod review;
+pub mod rich_text;
 pub mod signal;
+mod simulate;
 pub mod slack;
+pub mod spam;
 pub mod telegram;
 pub mod traits;
 pub mod transcription;
+pub mod translation;
+pub mod tts;
+pub mod typing;
+mod undo;
 pub mod wati;
+pub mod web;
 pub mod whatsapp;
 #[cfg(feature = "whatsapp-web")]
 pub mod whatsapp_storage;
@@ -61,18 +93,19 @@ pub use qq::QQChannel;
 pub use signal::SignalChannel;
 pub use slack::SlackChannel;
 pub use telegram::TelegramChannel;
-pub use traits::{Channel, SendMessage};
+pub use traits::{Channel, DeliveryStatus, SendMessage};
 pub use wati::WatiChannel;
+pub use web::WebChannel;
 pub use whatsapp::WhatsAppChannel;
 #[cfg(feature = "whatsapp-web")]
 pub use whatsapp_web::WhatsAppWebChannel;
 
-use crate::agent::loop_::{build_tool_instructions, run_tool_call_loop, scrub_credentials};
-use crate::config::Config;
+use crate::agent::loop_::{run_tool_call_loop, scrub_credentials};
+use crate::config::{AckReactionPolicy, Config};
 use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::{self, runtime_trace, Observer};
-use crate::providers::{self, ChatMessage, Provider};
+use crate::providers::{self, ChatMessage, Provider, SYSTEM_PROMPT_CACHE_BOUNDARY};
 use crate::runtime;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool};
@@ -111,7 +144,6 @@ const CHANNEL_MESSAGE_TIMEOUT_SCALE_CAP: u64 = 4;
 const CHANNEL_PARALLELISM_PER_CHANNEL: usize = 4;
 const CHANNEL_MIN_IN_FLIGHT_MESSAGES: usize = 8;
 const CHANNEL_MAX_IN_FLIGHT_MESSAGES: usize = 64;
-const CHANNEL_TYPING_REFRESH_INTERVAL_SECS: u64 = 4;
 const CHANNEL_HEALTH_HEARTBEAT_SECS: u64 = 30;
 const MODEL_CACHE_FILE: &str = "models_cache.json";
 const MODEL_CACHE_PREVIEW_LIMIT: usize = 10;
@@ -152,6 +184,10 @@ enum ChannelRuntimeCommand {
     ShowModel,
     SetModel(String),
     NewSession,
+    Preferences(String),
+    Language(String),
+    Conversations,
+    Undo,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -205,7 +241,7 @@ struct ChannelRuntimeContext {
     memory: Arc<dyn Memory>,
     tools_registry: Arc<Vec<Box<dyn Tool>>>,
     observer: Arc<dyn Observer>,
-    system_prompt: Arc<String>,
+    system_prompt: prompt::SharedPrompt,
     model: Arc<String>,
     temperature: f64,
     auto_save_memory: bool,
@@ -224,6 +260,38 @@ struct ChannelRuntimeContext {
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
     non_cli_excluded_tools: Arc<Vec<String>>,
+    /// Per-channel tool exposure (`[tools.channels]`).
+    tool_exposure: Arc<crate::config::ToolsConfig>,
+    auto_model: crate::config::AutoModelConfig,
+    tool_output: crate::config::ToolOutputStreamConfig,
+    queue: crate::config::ChannelQueueConfig,
+    links: crate::config::LinkPolicyConfig,
+    citations: crate::config::CitationsConfig,
+    follow_ups: Option<Arc<follow_ups::FollowUps>>,
+    titles: crate::config::ConversationTitlesConfig,
+    review: Option<Arc<review::ReplyReview>>,
+    spam: Option<Arc<spam::SpamFilter>>,
+    guardrails: Option<Arc<guardrails::OutputGuard>>,
+    /// Per-conversation reply language (`[channels_config.language]`).
+    language: Option<Arc<language::LanguageLock>>,
+    translation: Option<Arc<translation::AutoTranslator>>,
+    canary: Option<Arc<crate::security::CanaryGuard>>,
+    /// Record conversation activity for idle check-ins (`[checkins]`).
+    checkins: bool,
+    /// Active goals summarized in the system prompt (0 disables).
+    goals_prompt_limit: usize,
+    /// Name matching contacts in the system prompt (`[contacts].identity_hints`).
+    contacts: Option<Arc<crate::config::ContactsConfig>>,
+    /// Reaction feedback settings; `None` when `[feedback]` is disabled.
+    feedback: Option<crate::config::FeedbackConfig>,
+    /// Enabled A/B experiment, if any (`[[experiments]]`).
+    experiment: Option<Arc<crate::config::ExperimentConfig>>,
+    agents: Arc<agents::AgentRouter>,
+    /// Per-group-chat overrides (`[[channels_config.groups]]`).
+    groups: Arc<groups::GroupOverrides>,
+    typing: Arc<typing::TypingManager>,
+    /// Agent this message was routed to; `None` for the default agent.
+    agent: Option<Arc<agents::ChannelAgent>>,
 }
 
 #[derive(Clone)]
@@ -415,33 +483,48 @@ fn channel_delivery_instructions(channel_name: &str) -> Option<&'static str> {
     }
 }
 
+/// Prompt note for tools that `[tools.channels]` hides on this channel; the
+/// shared tool list in the base prompt still names them.
+fn channel_tools_prompt_section(hidden: &[String]) -> String {
+    format!(
+        "## Channel Tool Limits\n\nThese tools are not available on this channel. Do not call them or offer to use them: {}.",
+        hidden.join(", ")
+    )
+}
+
+/// The shared base prompt comes first and stays byte-identical across
+/// messages; everything per channel or per message goes after
+/// [`SYSTEM_PROMPT_CACHE_BOUNDARY`] so providers can cache the base.
 fn build_channel_system_prompt(
     base_prompt: &str,
     channel_name: &str,
     reply_target: &str,
 ) -> String {
-    let mut prompt = base_prompt.to_string();
-
+    let mut context = Vec::new();
     if let Some(instructions) = channel_delivery_instructions(channel_name) {
-        if prompt.is_empty() {
-            prompt = instructions.to_string();
-        } else {
-            prompt = format!("{prompt}\n\n{instructions}");
-        }
+        context.push(instructions.to_string());
     }
-
     if !reply_target.is_empty() {
-        let context = format!(
-            "\n\nChannel context: You are currently responding on channel={channel_name}, \
+        context.push(format!(
+            "Channel context: You are currently responding on channel={channel_name}, \
              reply_target={reply_target}. When scheduling delayed messages or reminders \
              via cron_add for this conversation, use delivery={{\"mode\":\"announce\",\
              \"channel\":\"{channel_name}\",\"to\":\"{reply_target}\"}} so the message \
              reaches the user."
-        );
-        prompt.push_str(&context);
+        ));
     }
 
-    prompt
+    if base_prompt.is_empty() {
+        return context.join("\n\n");
+    }
+    if context.is_empty() {
+        // Later sections are appended with their own leading blank line.
+        return format!("{base_prompt}{}", SYSTEM_PROMPT_CACHE_BOUNDARY.trim_end());
+    }
+    format!(
+        "{base_prompt}{SYSTEM_PROMPT_CACHE_BOUNDARY}{}",
+        context.join("\n\n")
+    )
 }
 
 fn normalize_cached_channel_turns(turns: Vec<ChatMessage>) -> Vec<ChatMessage> {
@@ -482,10 +565,6 @@ fn supports_runtime_model_switch(channel_name: &str) -> bool {
 }
 
 fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
-    if !supports_runtime_model_switch(channel_name) {
-        return None;
-    }
-
     let trimmed = content.trim();
     if !trimmed.starts_with('/') {
         return None;
@@ -499,6 +578,28 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
         .unwrap_or(command_token)
         .to_ascii_lowercase();
 
+    // Preferences are per sender, so `/prefs`, `/lang`, `/conversations` and
+    // `/undo` work on every channel.
+    if base_command == "/conversations" {
+        return Some(ChannelRuntimeCommand::Conversations);
+    }
+    if base_command == "/undo" {
+        return Some(ChannelRuntimeCommand::Undo);
+    }
+    if base_command == "/prefs" {
+        return Some(ChannelRuntimeCommand::Preferences(
+            parts.collect::<Vec<_>>().join(" "),
+        ));
+    }
+    if base_command == "/lang" {
+        return Some(ChannelRuntimeCommand::Language(
+            parts.collect::<Vec<_>>().join(" "),
+        ));
+    }
+    if !supports_runtime_model_switch(channel_name) {
+        return None;
+    }
+
     match base_command.as_str() {
         "/models" => {
             if let Some(provider) = parts.next() {
@@ -710,9 +811,14 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
 
 fn default_route_selection(ctx: &ChannelRuntimeContext) -> ChannelRouteSelection {
     let defaults = runtime_defaults_snapshot(ctx);
+    let agent = ctx.agent.as_deref();
     ChannelRouteSelection {
-        provider: defaults.default_provider,
-        model: defaults.model,
+        provider: agent
+            .and_then(|agent| agent.provider.clone())
+            .unwrap_or(defaults.default_provider),
+        model: agent
+            .and_then(|agent| agent.model.clone())
+            .unwrap_or(defaults.model),
     }
 }
 
@@ -725,6 +831,13 @@ fn get_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str) -> Channel
         .unwrap_or_else(|| default_route_selection(ctx))
 }
 
+fn has_route_override(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool {
+    ctx.route_overrides
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .contains_key(sender_key)
+}
+
 fn set_route_selection(ctx: &ChannelRuntimeContext, sender_key: &str, next: ChannelRouteSelection) {
     let default_route = default_route_selection(ctx);
     let mut routes = ctx
@@ -743,6 +856,51 @@ fn clear_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) {
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .remove(sender_key);
+    undo::clear(sender_key);
+}
+
+/// Roll a conversation back to before its last exchange (`/undo`).
+async fn undo_last_exchange(
+    ctx: &ChannelRuntimeContext,
+    channel: &dyn Channel,
+    sender_key: &str,
+) -> String {
+    let Some(exchange) = undo::pop(sender_key) else {
+        return "Nothing to undo.".to_string();
+    };
+    {
+        let mut histories = ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if exchange.history.is_empty() {
+            histories.remove(sender_key);
+        } else {
+            histories.insert(sender_key.to_string(), exchange.history);
+        }
+    }
+    if let Some(key) = &exchange.memory_key {
+        if let Err(err) = ctx.memory.forget(key).await {
+            tracing::warn!("Failed to forget undone message {key}: {err}");
+        }
+    }
+
+    let mut deleted = 0;
+    if channel.supports_message_deletion() {
+        for (recipient, message_id) in &exchange.replies {
+            match channel.delete_message(recipient, message_id).await {
+                Ok(()) => deleted += 1,
+                Err(err) => {
+                    tracing::warn!("Failed to delete reply on {}: {err}", channel.name());
+                }
+            }
+        }
+    }
+    if deleted > 0 {
+        "Undid the last exchange and deleted my reply.".to_string()
+    } else {
+        "Undid the last exchange; it is no longer part of this conversation.".to_string()
+    }
 }
 
 fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool {
@@ -997,6 +1155,70 @@ async fn handle_runtime_command_if_needed(
     msg: &traits::ChannelMessage,
     target_channel: Option<&Arc<dyn Channel>>,
 ) -> bool {
+    if let Some(reply_review) = ctx.review.as_ref().filter(|r| r.is_reviewer(msg)) {
+        if let Some(command) = review::parse_command(&msg.content) {
+            let response = handle_review_command(ctx, reply_review, msg, command).await;
+            if let Some(channel) = target_channel {
+                if let Err(err) = channel
+                    .send(
+                        &SendMessage::new(response, &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to send review response on {}: {err}",
+                        channel.name()
+                    );
+                }
+            }
+            return true;
+        }
+    }
+
+    if access::is_admin(msg) {
+        if let Some(command) = access::parse_command(&msg.content) {
+            let response = access::handle_command(command, msg).await;
+            if let Some(channel) = target_channel {
+                if let Err(err) = channel
+                    .send(
+                        &SendMessage::new(response, &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to send allowlist response on {}: {err}",
+                        channel.name()
+                    );
+                }
+            }
+            return true;
+        }
+    }
+
+    if pairing::is_approver(msg) {
+        if let Some(command) = pairing::parse_command(&msg.content) {
+            let approver = format!("{}:{}", msg.channel, msg.sender);
+            let response = pairing::handle_chat_command(command, &approver).await;
+            if let Some(channel) = target_channel {
+                if let Err(err) = channel
+                    .send(
+                        &SendMessage::new(response, &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone()),
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        "Failed to send pairing response on {}: {err}",
+                        channel.name()
+                    );
+                }
+            }
+            return true;
+        }
+    }
+
     let Some(command) = parse_runtime_command(&msg.channel, &msg.content) else {
         return false;
     };
@@ -1057,8 +1279,32 @@ async fn handle_runtime_command_if_needed(
         }
         ChannelRuntimeCommand::NewSession => {
             clear_sender_history(ctx, &sender_key);
+            if let Err(err) = conversations::close(ctx.workspace_dir.as_path(), &sender_key) {
+                tracing::warn!("Failed to close conversation: {err}");
+            }
+            if let Some(lock) = &ctx.language {
+                lock.reset(&sender_key);
+            }
             "Conversation history cleared. Starting fresh.".to_string()
         }
+        ChannelRuntimeCommand::Preferences(args) => preferences::command_response(
+            ctx.workspace_dir.as_path(),
+            &msg.channel,
+            &msg.sender,
+            &args,
+        ),
+        ChannelRuntimeCommand::Language(args) => language::command_response(
+            ctx.workspace_dir.as_path(),
+            ctx.language.as_deref(),
+            &msg.channel,
+            &msg.sender,
+            &sender_key,
+            &args,
+        ),
+        ChannelRuntimeCommand::Conversations => {
+            conversations::command_response(ctx.workspace_dir.as_path(), &msg.channel, &msg.sender)
+        }
+        ChannelRuntimeCommand::Undo => undo_last_exchange(ctx, channel.as_ref(), &sender_key).await,
     };
 
     if let Err(err) = channel
@@ -1074,6 +1320,184 @@ async fn handle_runtime_command_if_needed(
     true
 }
 
+/// Apply a reviewer's command. Approved drafts are sent to their conversation
+/// and logged in the outbox.
+async fn handle_review_command(
+    ctx: &ChannelRuntimeContext,
+    reply_review: &review::ReplyReview,
+    msg: &traits::ChannelMessage,
+    command: review::ReviewCommand,
+) -> String {
+    let workspace_dir = ctx.workspace_dir.as_path();
+    let decided = match command {
+        review::ReviewCommand::List => return review::pending_list(workspace_dir),
+        review::ReviewCommand::Approve(id) => {
+            review::decide(workspace_dir, id, true, None, None, &msg.sender)
+        }
+        review::ReviewCommand::Edit(id, text) => {
+            review::decide(workspace_dir, id, true, Some(&text), None, &msg.sender)
+        }
+        review::ReviewCommand::Reject(id, reason) => review::decide(
+            workspace_dir,
+            id,
+            false,
+            None,
+            reason.as_deref(),
+            &msg.sender,
+        ),
+    };
+    let draft = match decided {
+        Ok(draft) => draft,
+        Err(e) => return format!("⚠️ {e}"),
+    };
+    tracing::info!(
+        draft = draft.id,
+        status = %draft.status,
+        reviewer = %msg.sender,
+        review_chat = %reply_review.admin_to(),
+        "Draft reviewed"
+    );
+    if draft.status == "rejected" {
+        return format!("🗑️ Draft #{} rejected.", draft.id);
+    }
+
+    let reply = &draft.reply;
+    let Some(channel) = ctx.channels_by_name.get(&reply.channel) else {
+        let error = format!("channel {} is not running", reply.channel);
+        let _ = review::record_delivery(workspace_dir, draft.id, None, Some(&error));
+        return format!("⚠️ Draft #{} approved but not sent: {error}.", draft.id);
+    };
+    let sent = channel
+        .send_tracked(
+            &SendMessage::new(&reply.content, &reply.recipient)
+                .in_thread(reply.thread_ts.clone())
+                .replying_to(reply.in_reply_to_id.clone()),
+        )
+        .await;
+    let outbound = outbox::OutboundMessage {
+        channel: &reply.channel,
+        recipient: &reply.recipient,
+        platform_message_id: sent.as_ref().ok().and_then(Option::as_deref),
+        content: &reply.content,
+        status: if sent.is_ok() {
+            DeliveryStatus::Sent
+        } else {
+            DeliveryStatus::Failed
+        },
+        receipts: channel.supports_delivery_receipts(),
+    };
+    let outbox_id = outbox::record(workspace_dir, &outbound, ctx.observer.as_ref())
+        .map_err(|e| {
+            tracing::warn!(
+                "Failed to record outbound message on {}: {e:#}",
+                reply.channel
+            );
+        })
+        .ok();
+    let error = sent.as_ref().err().map(ToString::to_string);
+    if let Err(e) = review::record_delivery(
+        workspace_dir,
+        draft.id,
+        outbox_id.as_deref(),
+        error.as_deref(),
+    ) {
+        tracing::warn!("Failed to record draft delivery: {e}");
+    }
+    match error {
+        None => {
+            crate::events::publish(crate::events::AgentEvent::ReplySent {
+                channel: reply.channel.clone(),
+                recipient: reply.recipient.clone(),
+                content: reply.content.clone(),
+            });
+            format!(
+                "✅ Draft #{} {} and sent to {} / {}.",
+                draft.id, draft.status, reply.channel, reply.recipient
+            )
+        }
+        Some(error) => format!(
+            "⚠️ Draft #{} {} but sending failed: {error}",
+            draft.id, draft.status
+        ),
+    }
+}
+
+/// Store a reply as a draft and post it to the review chat instead of
+/// sending it.
+async fn hold_for_review(
+    ctx: &ChannelRuntimeContext,
+    reply_review: &review::ReplyReview,
+    msg: &traits::ChannelMessage,
+    content: &str,
+) {
+    let held = review::HeldReply {
+        channel: msg.channel.clone(),
+        recipient: msg.reply_target.clone(),
+        thread_ts: msg.thread_ts.clone(),
+        in_reply_to_id: Some(msg.id.clone()),
+        sender: msg.sender.clone(),
+        content: content.to_string(),
+    };
+    let id = match review::submit(ctx.workspace_dir.as_path(), &held) {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::error!("Failed to hold reply on {} for review: {e:#}", msg.channel);
+            return;
+        }
+    };
+    println!(
+        "  📝 Held reply on {} for review as draft #{id}",
+        msg.channel
+    );
+    let Some(admin) = ctx.channels_by_name.get(reply_review.admin_channel()) else {
+        tracing::warn!(
+            "Review chat channel {} is not running; draft #{id} waits for `/drafts`",
+            reply_review.admin_channel()
+        );
+        return;
+    };
+    let mut request = SendMessage::new(review::review_request(id, &held), reply_review.admin_to());
+    if admin.supports_buttons() {
+        request = request.with_buttons(vec![format!("/approve {id}"), format!("/reject {id}")]);
+    }
+    if let Err(e) = admin.send(&request).await {
+        tracing::warn!("Failed to post draft #{id} to the review chat: {e}");
+    }
+}
+
+/// Name a conversation in the background from its cached turns so the
+/// reply is not held up by the extra model call.
+fn spawn_conversation_title(
+    ctx: &ChannelRuntimeContext,
+    provider: Arc<dyn Provider>,
+    route_model: &str,
+    history_key: &str,
+    conversation_id: String,
+) {
+    let turns = ctx
+        .conversation_histories
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(history_key)
+        .cloned()
+        .unwrap_or_default();
+    let model = ctx
+        .titles
+        .model
+        .clone()
+        .unwrap_or_else(|| route_model.to_string());
+    let workspace_dir = Arc::clone(&ctx.workspace_dir);
+    tokio::spawn(async move {
+        let Some(title) = conversations::generate_title(provider.as_ref(), &model, &turns).await
+        else {
+            return;
+        };
+        if let Err(err) = conversations::set_title(&workspace_dir, &conversation_id, &title) {
+            tracing::warn!("Failed to save conversation title: {err}");
+        }
+    });
+}
+
 async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
@@ -1475,34 +1899,14 @@ fn log_worker_join_result(result: Result<(), tokio::task::JoinError>) {
     }
 }
 
-fn spawn_scoped_typing_task(
-    channel: Arc<dyn Channel>,
-    recipient: String,
-    cancellation_token: CancellationToken,
-) -> tokio::task::JoinHandle<()> {
-    let stop_signal = cancellation_token;
-    let refresh_interval = Duration::from_secs(CHANNEL_TYPING_REFRESH_INTERVAL_SECS);
-    let handle = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(refresh_interval);
-        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
-
-        loop {
-            tokio::select! {
-                () = stop_signal.cancelled() => break,
-                _ = interval.tick() => {
-                    if let Err(e) = channel.start_typing(&recipient).await {
-                        tracing::debug!("Failed to start typing on {}: {e}", channel.name());
-                    }
-                }
-            }
-        }
-
-        if let Err(e) = channel.stop_typing(&recipient).await {
-            tracing::debug!("Failed to stop typing on {}: {e}", channel.name());
-        }
-    });
-
-    handle
+/// Receive the next command output preview, or wait forever when streaming is off.
+async fn recv_output_preview(
+    rx: &mut Option<tokio::sync::mpsc::Receiver<crate::tools::shell::ShellOutputPreview>>,
+) -> Option<crate::tools::shell::ShellOutputPreview> {
+    match rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
 }
 
 async fn process_channel_message(
@@ -1535,6 +1939,18 @@ async fn process_channel_message(
             "content_preview": truncate_with_ellipsis(&msg.content, 160),
         }),
     );
+    monitor::monitor().record_message(
+        &msg.channel,
+        &conversation_history_key(&msg),
+        &msg.sender,
+        "user",
+        &msg.content,
+    );
+    crate::events::publish(crate::events::AgentEvent::MessageReceived {
+        channel: msg.channel.clone(),
+        sender: msg.sender.clone(),
+        content: msg.content.clone(),
+    });
 
     // ── Hook: on_message_received (modifying) ────────────
     let msg = if let Some(hooks) = &ctx.hooks {
@@ -1548,6 +1964,18 @@ async fn process_channel_message(
     } else {
         msg
     };
+    // A bare number picks one of the numbered follow-ups offered last turn.
+    let msg =
+        match ctx.follow_ups.as_ref().and_then(|follow_ups| {
+            follow_ups.resolve(&conversation_history_key(&msg), &msg.content)
+        }) {
+            Some(question) => traits::ChannelMessage {
+                content: question,
+                ..msg
+            },
+            None => msg,
+        };
+    let ctx = agents::routed_context(&ctx, &msg);
 
     let target_channel = ctx.channels_by_name.get(&msg.channel).cloned();
     if let Err(err) = maybe_apply_runtime_config_update(ctx.as_ref()).await {
@@ -1557,8 +1985,32 @@ async fn process_channel_message(
         return;
     }
 
+    let group = ctx.groups.select(&msg).cloned();
+    if let Some(group) = group.as_ref() {
+        if !groups::mentions_wake_word(&group.wake_words, &msg.content) {
+            tracing::debug!(
+                channel = %msg.channel,
+                group = %msg.reply_target,
+                "Ignoring group message without a wake word"
+            );
+            return;
+        }
+        if !ctx.groups.within_rate_limit(group, &msg) {
+            tracing::info!(
+                channel = %msg.channel,
+                group = %msg.reply_target,
+                sender = %msg.sender,
+                "Group chat rate limit reached; ignoring message"
+            );
+            return;
+        }
+    }
+    let ack_reactions = group
+        .as_ref()
+        .map_or(AckReactionPolicy::Full, |group| group.ack_reactions);
+
     let history_key = conversation_history_key(&msg);
-    let route = get_route_selection(ctx.as_ref(), &history_key);
+    let mut route = get_route_selection(ctx.as_ref(), &history_key);
     let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
     let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
         Ok(provider) => provider,
@@ -1579,31 +2031,82 @@ async fn process_channel_message(
             return;
         }
     };
+    let mut autosave_key = None;
     if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
-        let autosave_key = conversation_memory_key(&msg);
-        let _ = ctx
+        let key = conversation_memory_key(&msg);
+        let stored = ctx
             .memory
             .store(
-                &autosave_key,
+                &key,
                 &msg.content,
                 crate::memory::MemoryCategory::Conversation,
                 None,
             )
             .await;
+        if stored.is_ok() {
+            autosave_key = Some(key);
+        }
     }
 
+    // Auto-translated channels reply through the translator instead.
+    let pinned_language = match (&ctx.language, &ctx.translation) {
+        (Some(_), Some(translator)) if translator.covers(&msg.channel) => None,
+        (Some(lock), _) => lock.observe(&history_key, &msg.content),
+        (None, _) => None,
+    };
+
+    // Auto-translate: the agent works on a translation of foreign-language
+    // messages, while logs and memory keep what the sender wrote.
+    let inbound_translation = match &ctx.translation {
+        Some(translator) if delegation::reply_tag(&msg.content).is_none() => {
+            translator.inbound(&msg.channel, &msg.content).await
+        }
+        _ => None,
+    };
+    let (msg, reply_language) = match inbound_translation {
+        Some(detected) => {
+            tracing::info!(
+                channel = %msg.channel,
+                sender = %msg.sender,
+                language = %detected.language,
+                original = %truncate_with_ellipsis(&msg.content, 160),
+                "Translated inbound channel message"
+            );
+            let content = format!(
+                "[Translated from {}] {}",
+                detected.language, detected.translation
+            );
+            (
+                traits::ChannelMessage { content, ..msg },
+                Some(detected.language),
+            )
+        }
+        None => (msg, None),
+    };
+
     println!("  ⏳ Processing message...");
     let started_at = Instant::now();
 
-    let had_prior_history = ctx
+    let prior_history = ctx
         .conversation_histories
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .get(&history_key)
-        .is_some_and(|turns| !turns.is_empty());
+        .cloned()
+        .unwrap_or_default();
+    let had_prior_history = !prior_history.is_empty();
+    undo::snapshot(&history_key, prior_history, autosave_key);
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
     append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    let conversation = conversations::record_turn(
+        ctx.workspace_dir.as_path(),
+        &history_key,
+        &msg.channel,
+        &msg.sender,
+    )
+    .map_err(|err| tracing::warn!("Failed to record conversation turn: {err}"))
+    .ok();
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -1627,13 +2130,173 @@ async fn process_channel_message(
         }
     }
 
-    let system_prompt =
-        build_channel_system_prompt(ctx.system_prompt.as_str(), &msg.channel, &msg.reply_target);
+    // An explicit `/model` override for this conversation always wins over
+    // automatic cheap/strong selection.
+    if !has_route_override(ctx.as_ref(), &history_key) {
+        let context_chars = prior_turns.iter().map(|turn| turn.content.len()).sum();
+        let tools_involved = prior_turns
+            .iter()
+            .any(|turn| turn.role == "assistant" && turn.content.starts_with("[Used tools:"));
+        if let Some(decision) = crate::agent::classifier::select_auto_model(
+            &ctx.auto_model,
+            context_chars,
+            tools_involved,
+        ) {
+            tracing::info!(
+                channel = %msg.channel,
+                tier = decision.tier.as_str(),
+                model = decision.model.as_str(),
+                estimated_tokens = decision.estimated_tokens,
+                "Auto-selected model for channel turn"
+            );
+            route.model = decision.model;
+        }
+    }
+
+    let experiment_variant = ctx.experiment.as_ref().and_then(|experiment| {
+        match crate::experiments::assign(ctx.workspace_dir.as_path(), experiment, &history_key) {
+            Ok(variant) => Some(variant),
+            Err(e) => {
+                tracing::warn!("Failed to assign experiment variant: {e:#}");
+                None
+            }
+        }
+    });
+    if let Some(model) = experiment_variant.as_ref().and_then(|v| v.model.clone()) {
+        if !has_route_override(ctx.as_ref(), &history_key) {
+            route.model = model;
+        }
+    }
+
+    if ctx.checkins {
+        if let Err(e) = crate::cron::checkins::record_activity(
+            ctx.workspace_dir.as_path(),
+            &msg.channel,
+            &msg.reply_target,
+        ) {
+            tracing::warn!("Failed to record activity for check-ins: {e}");
+        }
+    }
+
+    let mut system_prompt = build_channel_system_prompt(
+        &ctx.system_prompt.current(),
+        &msg.channel,
+        &msg.reply_target,
+    );
+    if let Some(persona) = group.as_ref().and_then(|group| group.persona.as_deref()) {
+        system_prompt.push_str("\n\n## Group Persona\n\n");
+        system_prompt.push_str(persona);
+    }
+    let mut channel_hidden_tools = tools::channel_exclusions(
+        &ctx.tool_exposure,
+        &msg.channel,
+        ctx.tools_registry.as_ref(),
+    );
+    if let Some(group) = group.as_ref() {
+        groups::hide_tools(
+            group,
+            ctx.tools_registry.as_ref(),
+            &mut channel_hidden_tools,
+        );
+    }
+    if !channel_hidden_tools.is_empty() {
+        system_prompt.push_str("\n\n");
+        system_prompt.push_str(&channel_tools_prompt_section(&channel_hidden_tools));
+    }
+    let mut excluded_tools = if msg.channel == "cli" {
+        Vec::new()
+    } else {
+        ctx.non_cli_excluded_tools.as_ref().clone()
+    };
+    excluded_tools.extend(channel_hidden_tools);
+
+    // Math hints apply to every turn, since each question needs its own numbers.
+    let calc_hint = crate::agent::loop_::calc_hint(
+        &msg.content,
+        ctx.tools_registry
+            .iter()
+            .map(|tool| tool.name())
+            .filter(|name| !excluded_tools.iter().any(|excluded| excluded == name)),
+    );
+    if let Some(last_turn) = prior_turns.last_mut() {
+        if last_turn.role == "user" && !calc_hint.is_empty() {
+            last_turn.content = format!("{calc_hint}{}", last_turn.content);
+        }
+    }
+    if let Some(extra) = experiment_variant
+        .as_ref()
+        .and_then(|v| v.system_prompt.as_deref())
+    {
+        system_prompt.push_str("\n\n");
+        system_prompt.push_str(extra);
+    }
+    if let Some(canary) = &ctx.canary {
+        system_prompt.push_str("\n\n");
+        system_prompt.push_str(&canary.prompt_section(&history_key));
+    }
+    let language_preference =
+        match preferences::get_all(ctx.workspace_dir.as_path(), &msg.channel, &msg.sender) {
+            Ok(prefs) => {
+                if !prefs.is_empty() {
+                    system_prompt.push_str("\n\n");
+                    system_prompt.push_str(&preferences::prompt_section(&prefs));
+                }
+                prefs.contains_key("language")
+            }
+            Err(e) => {
+                tracing::warn!("Failed to load preferences for {}: {e}", msg.sender);
+                false
+            }
+        };
+    // A language chosen with `/lang` is already in the preferences section.
+    if let Some(language) = pinned_language.filter(|_| !language_preference) {
+        system_prompt.push_str("\n\n");
+        system_prompt.push_str(&language::prompt_section(language));
+    }
+    match crate::goals::active_prompt_section(ctx.workspace_dir.as_path(), ctx.goals_prompt_limit) {
+        Ok(section) if !section.is_empty() => {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(&section);
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load goals for the prompt: {e}"),
+    }
+    if let Some(contacts) = &ctx.contacts {
+        if let Some(hint) =
+            crate::contacts::identity_hint(ctx.workspace_dir.as_path(), contacts, &msg.sender)
+        {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(&hint);
+        }
+    }
+    match feedback::prompt_section(
+        ctx.workspace_dir.as_path(),
+        &msg.channel,
+        &msg.sender,
+        ctx.feedback
+            .as_ref()
+            .map_or(0, |feedback| feedback.prompt_limit),
+    ) {
+        Ok(section) if !section.is_empty() => {
+            system_prompt.push_str("\n\n");
+            system_prompt.push_str(&section);
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load reaction feedback for the prompt: {e}"),
+    }
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
-    let use_streaming = target_channel
-        .as_ref()
-        .is_some_and(|ch| ch.supports_draft_updates());
+    // Tasks delegated by another instance get one tagged, non-streamed reply.
+    let delegation_tag = delegation::reply_tag(&msg.content);
+    // Replies held for review must not leak through drafts or interim output.
+    let reply_review = ctx.review.as_ref().filter(|r| r.applies_to(&msg.channel));
+    // Translated replies are sent whole, so skip streaming partial drafts.
+    let use_streaming = delegation_tag.is_none()
+        && reply_language.is_none()
+        && reply_review.is_none()
+        && target_channel
+            .as_ref()
+            .is_some_and(|ch| ch.supports_draft_updates());
 
     tracing::debug!(
         channel = %msg.channel,
@@ -1654,7 +2317,9 @@ async fn process_channel_message(
         if let Some(channel) = target_channel.as_ref() {
             match channel
                 .send_draft(
-                    &SendMessage::new("...", &msg.reply_target).in_thread(msg.thread_ts.clone()),
+                    &SendMessage::new("...", &msg.reply_target)
+                        .in_thread(msg.thread_ts.clone())
+                        .replying_to(Some(msg.id.clone())),
                 )
                 .await
             {
@@ -1671,6 +2336,20 @@ async fn process_channel_message(
         None
     };
 
+    let (output_stream, mut output_rx) =
+        if ctx.tool_output.enabled && reply_review.is_none() && target_channel.is_some() {
+            let (tx, rx) =
+                tokio::sync::mpsc::channel::<crate::tools::shell::ShellOutputPreview>(16);
+            let stream = crate::tools::shell::ShellOutputStream::new(
+                tx,
+                Duration::from_secs(ctx.tool_output.interval_secs.max(1)),
+                ctx.tool_output.max_preview_chars,
+            );
+            (Some(stream), Some(rx))
+        } else {
+            (None, None)
+        };
+
     let draft_updater = if let (Some(mut rx), Some(draft_id_ref), Some(channel_ref)) = (
         delta_rx,
         draft_message_id.as_deref(),
@@ -1679,18 +2358,30 @@ async fn process_channel_message(
         let channel = Arc::clone(channel_ref);
         let reply_target = msg.reply_target.clone();
         let draft_id = draft_id_ref.to_string();
+        let mut output_rx = output_rx.take();
         Some(tokio::spawn(async move {
             let mut accumulated = String::new();
-            while let Some(delta) = rx.recv().await {
-                if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
-                    accumulated.clear();
-                    continue;
-                }
-                accumulated.push_str(&delta);
-                if let Err(e) = channel
-                    .update_draft(&reply_target, &draft_id, &accumulated)
-                    .await
-                {
+            loop {
+                let draft = tokio::select! {
+                    delta = rx.recv() => {
+                        let Some(delta) = delta else {
+                            break;
+                        };
+                        if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
+                            accumulated.clear();
+                            continue;
+                        }
+                        accumulated.push_str(&delta);
+                        accumulated.clone()
+                    }
+                    Some(preview) = recv_output_preview(&mut output_rx) => match preview {
+                        crate::tools::shell::ShellOutputPreview::Output { tail, .. } => {
+                            format!("{accumulated}```\n{tail}\n```\n")
+                        }
+                        crate::tools::shell::ShellOutputPreview::Finished => accumulated.clone(),
+                    },
+                };
+                if let Err(e) = channel.update_draft(&reply_target, &draft_id, &draft).await {
                     tracing::debug!("Draft update failed: {e}");
                 }
             }
@@ -1699,8 +2390,38 @@ async fn process_channel_message(
         None
     };
 
+    // Without a draft to edit, relay command output as periodic status messages.
+    let output_relay = if let (Some(mut rx), Some(channel_ref)) =
+        (output_rx.take(), target_channel.as_ref())
+    {
+        let channel = Arc::clone(channel_ref);
+        let reply_target = msg.reply_target.clone();
+        let thread_ts = msg.thread_ts.clone();
+        Some(tokio::spawn(async move {
+            while let Some(preview) = rx.recv().await {
+                if let crate::tools::shell::ShellOutputPreview::Output { command, tail } = preview {
+                    let status = format!(
+                        "\u{23f3} {}\n```\n{tail}\n```",
+                        truncate_with_ellipsis(&command, 60)
+                    );
+                    if let Err(e) = channel
+                        .send(&SendMessage::new(status, &reply_target).in_thread(thread_ts.clone()))
+                        .await
+                    {
+                        tracing::debug!("Failed to send command output preview: {e}");
+                    }
+                }
+            }
+        }))
+    } else {
+        None
+    };
+
     // React with 👀 to acknowledge the incoming message
-    if let Some(channel) = target_channel.as_ref() {
+    if let Some(channel) = target_channel
+        .as_ref()
+        .filter(|_| ack_reactions == AckReactionPolicy::Full)
+    {
         if let Err(e) = channel
             .add_reaction(&msg.reply_target, &msg.id, "\u{1F440}")
             .await
@@ -1709,18 +2430,13 @@ async fn process_channel_message(
         }
     }
 
-    let typing_cancellation = target_channel.as_ref().map(|_| CancellationToken::new());
-    let typing_task = match (target_channel.as_ref(), typing_cancellation.as_ref()) {
-        (Some(channel), Some(token)) => Some(spawn_scoped_typing_task(
-            Arc::clone(channel),
-            msg.reply_target.clone(),
-            token.clone(),
-        )),
-        _ => None,
-    };
+    let typing = target_channel
+        .as_ref()
+        .map(|channel| ctx.typing.start(Arc::clone(channel), &msg.reply_target));
 
     // Record history length before tool loop so we can extract tool context after.
     let history_len_before_tools = history.len();
+    crate::skills::usage::record_prompt_inclusion();
 
     enum LlmExecutionResult {
         Completed(Result<Result<String, anyhow::Error>, tokio::time::error::Elapsed>),
@@ -1729,50 +2445,96 @@ async fn process_channel_message(
 
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
+    let reminder_route = crate::tools::remind_me::ReminderRoute {
+        channel: msg.channel.clone(),
+        recipient: msg.reply_target.clone(),
+    };
+    let preference_identity = crate::tools::preferences::PreferenceIdentity {
+        channel: msg.channel.clone(),
+        sender: msg.sender.clone(),
+    };
+    let citations = ctx
+        .citations
+        .applies_to(&msg.channel)
+        .then(|| Arc::new(crate::tools::citations::SourceCollector::new()));
+    let tool_loop = Box::pin(crate::tools::preferences::with_identity(
+        preference_identity,
+        crate::tools::remind_me::with_request_route(
+            Some(reminder_route),
+            crate::tools::citations::with_collector(
+                citations.clone(),
+                crate::tools::shell::with_output_stream(
+                    output_stream,
+                    run_tool_call_loop(
+                        active_provider.as_ref(),
+                        &mut history,
+                        ctx.tools_registry.as_ref(),
+                        ctx.observer.as_ref(),
+                        route.provider.as_str(),
+                        route.model.as_str(),
+                        experiment_variant
+                            .as_ref()
+                            .and_then(|v| v.temperature)
+                            .unwrap_or(runtime_defaults.temperature),
+                        true,
+                        None,
+                        msg.channel.as_str(),
+                        &ctx.multimodal,
+                        ctx.max_tool_iterations,
+                        Some(cancellation_token.clone()),
+                        delta_tx,
+                        ctx.hooks.as_deref(),
+                        &excluded_tools,
+                    ),
+                ),
+            ),
+        ),
+    ));
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
-        result = tokio::time::timeout(
-            Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                ctx.observer.as_ref(),
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                None,
-                msg.channel.as_str(),
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                Some(cancellation_token.clone()),
-                delta_tx,
-                ctx.hooks.as_deref(),
-                if msg.channel == "cli" {
-                    &[]
-                } else {
-                    ctx.non_cli_excluded_tools.as_ref()
-                },
-            ),
-        ) => LlmExecutionResult::Completed(result),
+        result = tokio::time::timeout(Duration::from_secs(timeout_budget_secs), tool_loop) => {
+            LlmExecutionResult::Completed(result)
+        }
     };
 
     if let Some(handle) = draft_updater {
         let _ = handle.await;
     }
-
-    if let Some(token) = typing_cancellation.as_ref() {
-        token.cancel();
+    if let Some(handle) = output_relay {
+        let _ = handle.await;
     }
-    if let Some(handle) = typing_task {
-        log_worker_join_result(handle.await);
+
+    if let Some(typing) = typing {
+        typing.finish().await;
     }
 
-    let reaction_done_emoji = match &llm_result {
-        LlmExecutionResult::Completed(Ok(Ok(_))) => "\u{2705}", // ✅
-        _ => "\u{26A0}\u{FE0F}",                                // ⚠️
+    let reply_ok = matches!(&llm_result, LlmExecutionResult::Completed(Ok(Ok(_))));
+    let reaction_done_emoji = if reply_ok {
+        "\u{2705}" // ✅
+    } else {
+        "\u{26A0}\u{FE0F}" // ⚠️
+    };
+    // Failed turns are tagged here; replies are tagged once their outbox id is known.
+    let turn_failed = match &llm_result {
+        LlmExecutionResult::Cancelled | LlmExecutionResult::Completed(Ok(Ok(_))) => false,
+        LlmExecutionResult::Completed(Ok(Err(e))) => {
+            !crate::agent::loop_::is_tool_loop_cancelled(e) && !cancellation_token.is_cancelled()
+        }
+        LlmExecutionResult::Completed(Err(_)) => true,
     };
+    if turn_failed {
+        if let Some(variant) = &experiment_variant {
+            record_experiment_turn(
+                ctx.as_ref(),
+                &msg,
+                &history_key,
+                variant,
+                None,
+                started_at,
+                None,
+            );
+        }
+    }
 
     match llm_result {
         LlmExecutionResult::Cancelled => {
@@ -1870,6 +2632,80 @@ async fn process_channel_message(
             } else {
                 sanitized_response
             };
+            let delivered_response = links::rewrite_urls(&delivered_response, &ctx.links).await;
+            let delivered_response = match &delegation_tag {
+                Some(tag) => format!("{tag} {delivered_response}"),
+                None => delivered_response,
+            };
+            // History keeps the agent's own wording; the sender gets it in
+            // their language.
+            let agent_response = delivered_response.clone();
+            let delivered_response = match (&ctx.translation, &reply_language) {
+                (Some(translator), Some(language)) => {
+                    translator.outbound(&delivered_response, language).await
+                }
+                _ => delivered_response,
+            };
+            let delivered_response = match citations
+                .as_ref()
+                .and_then(|sources| sources.source_list(&agent_response, &ctx.citations))
+            {
+                Some(list) => format!("{delivered_response}\n\n{list}"),
+                None => delivered_response,
+            };
+            let (delivered_response, agent_response, withheld) = match &ctx.guardrails {
+                Some(guard) => {
+                    match guard_outbound(ctx.as_ref(), guard, &msg, &route, &delivered_response)
+                        .await
+                    {
+                        Some(withheld) => (
+                            withheld,
+                            "[Reply withheld by output guardrails]".to_string(),
+                            true,
+                        ),
+                        None => (
+                            guard.finish(&msg.channel, &delivered_response),
+                            agent_response,
+                            false,
+                        ),
+                    }
+                }
+                None => (delivered_response, agent_response, false),
+            };
+            // Follow-up suggestions go out as buttons where the channel has
+            // them. Drafts are finalized in place, so they get the footer.
+            let mut quick_replies = Vec::new();
+            let delivered_response = match &ctx.follow_ups {
+                Some(follow_ups)
+                    if !withheld
+                        && !delivered_response.is_empty()
+                        && follow_ups.applies_to(&msg.channel) =>
+                {
+                    let suggestions = follow_ups
+                        .suggest(
+                            active_provider.as_ref(),
+                            &route.model,
+                            &msg.content,
+                            &agent_response,
+                        )
+                        .await;
+                    let use_buttons = draft_message_id.is_none()
+                        && target_channel
+                            .as_ref()
+                            .is_some_and(|channel| channel.supports_buttons());
+                    if suggestions.is_empty() {
+                        delivered_response
+                    } else if use_buttons {
+                        quick_replies = suggestions;
+                        delivered_response
+                    } else {
+                        let footer = follow_ups::footer(&suggestions);
+                        follow_ups.remember(&history_key, suggestions);
+                        format!("{delivered_response}\n\n{footer}")
+                    }
+                }
+                _ => delivered_response,
+            };
             runtime_trace::record_event(
                 "channel_message_outbound",
                 Some(msg.channel.as_str()),
@@ -1890,9 +2726,9 @@ async fn process_channel_message(
             // of what it did on subsequent turns.
             let tool_summary = extract_tool_context_summary(&history, history_len_before_tools);
             let history_response = if tool_summary.is_empty() || msg.channel == "telegram" {
-                delivered_response.clone()
+                agent_response
             } else {
-                format!("{tool_summary}\n{delivered_response}")
+                format!("{tool_summary}\n{agent_response}")
             };
 
             append_sender_turn(
@@ -1900,33 +2736,89 @@ async fn process_channel_message(
                 &history_key,
                 ChatMessage::assistant(&history_response),
             );
+            if let Some(conversation) = conversation
+                .as_ref()
+                .filter(|c| ctx.titles.wants_title(c.turns, c.title.is_some()))
+            {
+                spawn_conversation_title(
+                    ctx.as_ref(),
+                    Arc::clone(&active_provider),
+                    &route.model,
+                    &history_key,
+                    conversation.id.clone(),
+                );
+            }
             println!(
                 "  🤖 Reply ({}ms): {}",
                 started_at.elapsed().as_millis(),
                 truncate_with_ellipsis(&delivered_response, 80)
             );
-            if let Some(channel) = target_channel.as_ref() {
-                if let Some(ref draft_id) = draft_message_id {
-                    if let Err(e) = channel
+            monitor::monitor().record_message(
+                &msg.channel,
+                &history_key,
+                &msg.sender,
+                "assistant",
+                &delivered_response,
+            );
+            if delivered_response.is_empty() {
+                // Withheld by guardrails with no notice for the user.
+                if let (Some(channel), Some(draft_id)) =
+                    (target_channel.as_ref(), draft_message_id.as_deref())
+                {
+                    if let Err(err) = channel.cancel_draft(&msg.reply_target, draft_id).await {
+                        tracing::debug!("Failed to cancel draft on {}: {err}", channel.name());
+                    }
+                }
+            } else if let Some(reply_review) = reply_review {
+                hold_for_review(ctx.as_ref(), reply_review, &msg, &delivered_response).await;
+            } else if let Some(channel) = target_channel.as_ref() {
+                let reply = SendMessage::new(&delivered_response, &msg.reply_target)
+                    .in_thread(msg.thread_ts.clone())
+                    .replying_to(Some(msg.id.clone()))
+                    .with_buttons(quick_replies);
+                let sent = match draft_message_id {
+                    Some(ref draft_id) => match channel
                         .finalize_draft(&msg.reply_target, draft_id, &delivered_response)
                         .await
                     {
-                        tracing::warn!("Failed to finalize draft: {e}; sending as new message");
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        Ok(()) => Ok(Some(draft_id.clone())),
+                        Err(e) => {
+                            tracing::warn!("Failed to finalize draft: {e}; sending as new message");
+                            channel.send_tracked(&reply).await
+                        }
+                    },
+                    None => channel.send_tracked(&reply).await,
+                };
+                match &sent {
+                    Ok(message_id) => {
+                        if let Some(message_id) = message_id {
+                            undo::record_reply(&history_key, &msg.reply_target, message_id);
+                        }
+                        crate::events::publish(crate::events::AgentEvent::ReplySent {
+                            channel: msg.channel.clone(),
+                            recipient: msg.reply_target.clone(),
+                            content: delivered_response.clone(),
+                        });
                     }
-                } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
-                {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    Err(e) => eprintln!("  ❌ Failed to reply on {}: {e}", channel.name()),
+                }
+                let outbox_id = record_outbound(
+                    ctx.as_ref(),
+                    channel.as_ref(),
+                    &msg,
+                    &delivered_response,
+                    &sent,
+                );
+                if let Some(variant) = &experiment_variant {
+                    record_experiment_turn(
+                        ctx.as_ref(),
+                        &msg,
+                        &history_key,
+                        variant,
+                        outbox_id.as_deref(),
+                        started_at,
+                        Some(&delivered_response),
+                    );
                 }
             }
         }
@@ -2099,12 +2991,157 @@ async fn process_channel_message(
 
     // Swap 👀 → ✅ (or ⚠️ on error) to signal processing is complete
     if let Some(channel) = target_channel.as_ref() {
-        let _ = channel
-            .remove_reaction(&msg.reply_target, &msg.id, "\u{1F440}")
-            .await;
-        let _ = channel
-            .add_reaction(&msg.reply_target, &msg.id, reaction_done_emoji)
-            .await;
+        match ack_reactions {
+            AckReactionPolicy::Full => {
+                let _ = channel
+                    .remove_reaction(&msg.reply_target, &msg.id, "\u{1F440}")
+                    .await;
+                let _ = channel
+                    .add_reaction(&msg.reply_target, &msg.id, reaction_done_emoji)
+                    .await;
+            }
+            AckReactionPolicy::Errors if !reply_ok => {
+                let _ = channel
+                    .add_reaction(&msg.reply_target, &msg.id, reaction_done_emoji)
+                    .await;
+            }
+            AckReactionPolicy::Errors | AckReactionPolicy::Off => {}
+        }
+    }
+}
+
+/// Log an agent reply (and its send outcome) in the outbox.
+/// Run a reply through the output guardrails. Returns what to send instead
+/// (possibly empty) when the reply is withheld, `None` when it may go out.
+async fn guard_outbound(
+    ctx: &ChannelRuntimeContext,
+    guard: &guardrails::OutputGuard,
+    msg: &traits::ChannelMessage,
+    route: &ChannelRouteSelection,
+    reply: &str,
+) -> Option<String> {
+    let reason = guard
+        .check(
+            &msg.channel,
+            reply,
+            ctx.provider.as_ref(),
+            ctx.model.as_str(),
+        )
+        .await?;
+    runtime_trace::record_event(
+        "channel_guardrail_violation",
+        Some(msg.channel.as_str()),
+        Some(route.provider.as_str()),
+        Some(route.model.as_str()),
+        None,
+        Some(false),
+        Some(reason.as_str()),
+        serde_json::json!({
+            "sender": msg.sender,
+            "response": scrub_credentials(reply),
+        }),
+    );
+    guard
+        .report(&msg.channel, &msg.sender, reply, &reason)
+        .await;
+    Some(guard.blocked_reply().to_string())
+}
+
+fn record_outbound(
+    ctx: &ChannelRuntimeContext,
+    channel: &dyn Channel,
+    msg: &traits::ChannelMessage,
+    content: &str,
+    sent: &anyhow::Result<Option<String>>,
+) -> Option<String> {
+    let outbound = outbox::OutboundMessage {
+        channel: &msg.channel,
+        recipient: &msg.reply_target,
+        platform_message_id: sent.as_ref().ok().and_then(Option::as_deref),
+        content,
+        status: if sent.is_ok() {
+            DeliveryStatus::Sent
+        } else {
+            DeliveryStatus::Failed
+        },
+        receipts: channel.supports_delivery_receipts(),
+    };
+    match outbox::record(&ctx.workspace_dir, &outbound, ctx.observer.as_ref()) {
+        Ok(id) => Some(id),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to record outbound message on {}: {e:#}",
+                msg.channel
+            );
+            None
+        }
+    }
+}
+
+/// Tag a finished turn with its experiment variant. `reply` is `None` when
+/// the turn failed.
+fn record_experiment_turn(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    history_key: &str,
+    variant: &crate::config::ExperimentVariantConfig,
+    outbox_id: Option<&str>,
+    started_at: Instant,
+    reply: Option<&str>,
+) {
+    let Some(experiment) = ctx.experiment.as_ref() else {
+        return;
+    };
+    let turn = crate::experiments::TurnRecord {
+        experiment: &experiment.name,
+        variant: &variant.name,
+        conversation: history_key,
+        channel: &msg.channel,
+        outbox_id,
+        latency_ms: u64::try_from(started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+        reply_chars: reply.map_or(0, |reply| reply.chars().count()),
+        success: reply.is_some(),
+    };
+    if let Err(e) = crate::experiments::record_turn(&ctx.workspace_dir, &turn) {
+        tracing::warn!("Failed to record experiment turn: {e:#}");
+    }
+}
+
+/// Store a reaction on an agent reply as feedback.
+fn record_reaction(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    reaction: &traits::Reaction,
+) {
+    if ctx.feedback.is_none() {
+        return;
+    }
+    match feedback::record_reaction(&ctx.workspace_dir, &msg.channel, &msg.sender, reaction) {
+        Ok(true) => tracing::info!(
+            channel = %msg.channel,
+            sender = %msg.sender,
+            emoji = %reaction.emoji,
+            removed = reaction.removed,
+            "Recorded reaction feedback"
+        ),
+        Ok(false) => {}
+        Err(e) => tracing::warn!(
+            "Failed to record reaction feedback on {}: {e:#}",
+            msg.channel
+        ),
+    }
+}
+
+/// Tell a sender their message was queued or declined by the job queue.
+async fn send_queue_notice(ctx: &ChannelRuntimeContext, msg: &traits::ChannelMessage, text: &str) {
+    let Some(channel) = ctx.channels_by_name.get(&msg.channel) else {
+        return;
+    };
+    if let Err(e) = channel
+        .send(&SendMessage::new(text, &msg.reply_target).in_thread(msg.thread_ts.clone()))
+        .await
+    {
+        tracing::debug!("Failed to send queue notice on {}: {e}", channel.name());
     }
 }
 
@@ -2113,7 +3150,11 @@ async fn run_message_dispatch_loop(
     ctx: Arc<ChannelRuntimeContext>,
     max_in_flight_messages: usize,
 ) {
-    let semaphore = Arc::new(tokio::sync::Semaphore::new(max_in_flight_messages));
+    let job_queue = queue::JobQueue::new(
+        &ctx.queue,
+        max_in_flight_messages,
+        Arc::clone(&ctx.observer),
+    );
     let mut workers = tokio::task::JoinSet::new();
     let in_flight_by_sender = Arc::new(tokio::sync::Mutex::new(HashMap::<
         String,
@@ -2122,16 +3163,67 @@ async fn run_message_dispatch_loop(
     let task_sequence = Arc::new(AtomicU64::new(1));
 
     while let Some(msg) = rx.recv().await {
-        let permit = match Arc::clone(&semaphore).acquire_owned().await {
-            Ok(permit) => permit,
-            Err(_) => break,
+        if delegation::resolve(&msg) {
+            continue;
+        }
+        if monitor::monitor().is_paused(&msg.channel) {
+            tracing::info!(
+                channel = %msg.channel,
+                sender = %msg.sender,
+                "Channel paused from the dashboard; ignoring message"
+            );
+            continue;
+        }
+        // Reactions are feedback on earlier replies, not requests.
+        if let Some(traits::MessagePayload::Reaction(reaction)) = &msg.payload {
+            record_reaction(ctx.as_ref(), &msg, reaction);
+            continue;
+        }
+        let spam_review = match ctx
+            .spam
+            .as_ref()
+            .map(|filter| filter.screen(&msg, ctx.observer.as_ref()))
+        {
+            Some(spam::Screening::Blocked) => continue,
+            Some(spam::Screening::Review(assessment)) => Some(assessment),
+            Some(spam::Screening::Allow) | None => None,
         };
-
+        let admission = job_queue.admit(&msg.channel, &conversation_history_key(&msg), &msg.sender);
         let worker_ctx = Arc::clone(&ctx);
+
+        if matches!(admission, queue::Admission::Rejected) {
+            tracing::warn!(
+                channel = %msg.channel,
+                sender = %msg.sender,
+                "Job queue full; rejecting channel message"
+            );
+            workers.spawn(async move {
+                send_queue_notice(
+                    &worker_ctx,
+                    &msg,
+                    "\u{26a0}\u{fe0f} I'm busy with other requests right now. Please try again in a moment.",
+                )
+                .await;
+            });
+            continue;
+        }
+
         let in_flight = Arc::clone(&in_flight_by_sender);
         let task_sequence = Arc::clone(&task_sequence);
         workers.spawn(async move {
-            let _permit = permit;
+            if let (Some(assessment), Some(filter)) = (spam_review, worker_ctx.spam.as_ref()) {
+                let allowed = filter
+                    .review(
+                        &msg,
+                        assessment,
+                        worker_ctx.provider.as_ref(),
+                        worker_ctx.observer.as_ref(),
+                    )
+                    .await;
+                if !allowed {
+                    return;
+                }
+            }
             let interrupt_enabled =
                 worker_ctx.interrupt_on_new_message && msg.channel == "telegram";
             let sender_scope_key = interruption_scope_key(&msg);
@@ -2163,7 +3255,40 @@ async fn run_message_dispatch_loop(
                 }
             }
 
-            process_channel_message(worker_ctx, msg, cancellation_token).await;
+            let permit = match admission {
+                queue::Admission::Ready(permit) => Some(permit),
+                queue::Admission::Queued(job) => {
+                    // An interrupting message replaces the run it waits on; no notice needed.
+                    if !interrupt_enabled {
+                        let notice = format!(
+                            "\u{23f3} Queued (position {}). I'll reply as soon as a slot frees up.",
+                            job.position()
+                        );
+                        send_queue_notice(&worker_ctx, &msg, &notice).await;
+                    }
+                    tokio::select! {
+                        () = cancellation_token.cancelled() => None,
+                        permit = job.wait() => Some(permit),
+                    }
+                }
+                queue::Admission::Rejected => None,
+            };
+
+            if let Some(permit) = permit {
+                let run = monitor::monitor().start_run(
+                    &msg.channel,
+                    &conversation_history_key(&msg),
+                    &msg.sender,
+                    &cancellation_token,
+                );
+                monitor::with_run(
+                    run.id(),
+                    Box::pin(process_channel_message(worker_ctx, msg, cancellation_token)),
+                )
+                .await;
+                drop(run);
+                drop(permit);
+            }
 
             if interrupt_enabled {
                 let mut active = in_flight.lock().await;
@@ -2193,6 +3318,7 @@ fn load_openclaw_bootstrap_files(
     prompt: &mut String,
     workspace_dir: &std::path::Path,
     max_chars_per_file: usize,
+    include_memory: bool,
 ) {
     prompt.push_str(
         "The following workspace files define your identity, behavior, and context. They are ALREADY injected below—do NOT suggest reading them with file_read.\n\n",
@@ -2211,7 +3337,9 @@ fn load_openclaw_bootstrap_files(
     }
 
     // MEMORY.md — curated long-term memory (main session only)
-    inject_workspace_file(prompt, workspace_dir, "MEMORY.md", max_chars_per_file);
+    if include_memory {
+        inject_workspace_file(prompt, workspace_dir, "MEMORY.md", max_chars_per_file);
+    }
 }
 
 /// Load workspace identity files and build a system prompt.
@@ -2260,8 +3388,135 @@ pub fn build_system_prompt_with_mode(
     native_tools: bool,
     skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
 ) -> String {
-    use std::fmt::Write;
     let mut prompt = String::with_capacity(8192);
+    let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
+
+    push_instructions_section(&mut prompt, tools, native_tools);
+    push_skills_section(&mut prompt, skills, workspace_dir, skills_prompt_mode);
+    push_workspace_section(&mut prompt, workspace_dir);
+    push_project_context(&mut prompt, workspace_dir, identity_config, max_chars, true);
+    push_runtime_sections(&mut prompt, model_name);
+
+    if prompt.is_empty() {
+        "You are ZeroClaw, a fast and efficient AI assistant built in Rust. Be helpful, concise, and direct."
+            .to_string()
+    } else {
+        prompt
+    }
+}
+
+/// Build the system prompt honoring `[prompt] parts`; without parts this is
+/// [`build_system_prompt_with_mode`] with the settings from `config`.
+pub fn build_configured_system_prompt(
+    config: &Config,
+    model_name: &str,
+    tools: &[(&str, &str)],
+    skills: &[crate::skills::Skill],
+    native_tools: bool,
+) -> String {
+    use crate::config::PromptPartKind;
+
+    let workspace_dir = &config.workspace_dir;
+    let bootstrap_max_chars = config.agent.compact_context.then_some(6000);
+    if config.prompt.parts.is_empty() {
+        return build_system_prompt_with_mode(
+            workspace_dir,
+            model_name,
+            tools,
+            skills,
+            Some(&config.identity),
+            bootstrap_max_chars,
+            native_tools,
+            config.skills.prompt_injection_mode,
+        );
+    }
+
+    let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
+    let mut prompt = String::with_capacity(8192);
+    for part in &config.prompt.parts {
+        let path = part
+            .path
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        match part.kind {
+            PromptPartKind::Core => {
+                push_instructions_section(&mut prompt, tools, native_tools);
+                push_workspace_section(&mut prompt, workspace_dir);
+                push_runtime_sections(&mut prompt, model_name);
+            }
+            PromptPartKind::Persona => match path {
+                Some(path) => {
+                    prompt.push_str("## Project Context\n\n");
+                    inject_workspace_file(&mut prompt, workspace_dir, path, max_chars);
+                }
+                None => push_project_context(
+                    &mut prompt,
+                    workspace_dir,
+                    Some(&config.identity),
+                    max_chars,
+                    false,
+                ),
+            },
+            PromptPartKind::Skills => push_skills_section(
+                &mut prompt,
+                skills,
+                workspace_dir,
+                config.skills.prompt_injection_mode,
+            ),
+            PromptPartKind::Memory => {
+                prompt.push_str("## Memory\n\n");
+                inject_workspace_file(&mut prompt, workspace_dir, "MEMORY.md", max_chars);
+            }
+            PromptPartKind::Text => {
+                if let Some(text) = part.text.as_deref().map(str::trim) {
+                    prompt.push_str(text);
+                    prompt.push_str("\n\n");
+                }
+            }
+            PromptPartKind::Files => {
+                for file in path
+                    .map(|pattern| prompt_include_files(workspace_dir, pattern))
+                    .unwrap_or_default()
+                {
+                    inject_workspace_file(&mut prompt, workspace_dir, &file, max_chars);
+                }
+            }
+        }
+    }
+    prompt
+}
+
+/// Workspace-relative files matching `pattern`, sorted; matches that resolve
+/// outside the workspace (through symlinks) are skipped.
+fn prompt_include_files(workspace_dir: &std::path::Path, pattern: &str) -> Vec<String> {
+    let Ok(root) = workspace_dir.canonicalize() else {
+        return Vec::new();
+    };
+    let full_pattern = workspace_dir.join(pattern);
+    let Ok(paths) = glob::glob(&full_pattern.to_string_lossy()) else {
+        tracing::warn!("Invalid prompt include pattern: {pattern}");
+        return Vec::new();
+    };
+    let mut files: Vec<String> = paths
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.canonicalize()
+                .is_ok_and(|resolved| resolved.starts_with(&root))
+        })
+        .filter_map(|path| {
+            path.strip_prefix(workspace_dir)
+                .ok()
+                .map(|relative| relative.to_string_lossy().into_owned())
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn push_instructions_section(prompt: &mut String, tools: &[(&str, &str)], native_tools: bool) {
+    use std::fmt::Write;
 
     // ── 1. Tooling ──────────────────────────────────────────────
     if !tools.is_empty() {
@@ -2320,7 +3575,14 @@ pub fn build_system_prompt_with_mode(
          - Prefer `trash` over `rm` (recoverable beats gone forever).\n\
          - When in doubt, ask before acting externally.\n\n",
     );
+}
 
+fn push_skills_section(
+    prompt: &mut String,
+    skills: &[crate::skills::Skill],
+    workspace_dir: &std::path::Path,
+    skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+) {
     // ── 3. Skills (full or compact, based on config) ─────────────
     if !skills.is_empty() {
         prompt.push_str(&crate::skills::skills_to_prompt_with_mode(
@@ -2330,6 +3592,10 @@ pub fn build_system_prompt_with_mode(
         ));
         prompt.push_str("\n\n");
     }
+}
+
+fn push_workspace_section(prompt: &mut String, workspace_dir: &std::path::Path) {
+    use std::fmt::Write;
 
     // ── 4. Workspace ────────────────────────────────────────────
     let _ = writeln!(
@@ -2337,47 +3603,43 @@ pub fn build_system_prompt_with_mode(
         "## Workspace\n\nWorking directory: `{}`\n",
         workspace_dir.display()
     );
+}
 
+/// Identity (AIEOS or the bootstrap files), plus `MEMORY.md` when `include_memory`.
+fn push_project_context(
+    prompt: &mut String,
+    workspace_dir: &std::path::Path,
+    identity_config: Option<&crate::config::IdentityConfig>,
+    max_chars: usize,
+    include_memory: bool,
+) {
     // ── 5. Bootstrap files (injected into context) ──────────────
     prompt.push_str("## Project Context\n\n");
 
-    // Check if AIEOS identity is configured
-    if let Some(config) = identity_config {
-        if identity::is_aieos_configured(config) {
-            // Load AIEOS identity
-            match identity::load_aieos_identity(config, workspace_dir) {
-                Ok(Some(aieos_identity)) => {
-                    let aieos_prompt = identity::aieos_to_system_prompt(&aieos_identity);
-                    if !aieos_prompt.is_empty() {
-                        prompt.push_str(&aieos_prompt);
-                        prompt.push_str("\n\n");
-                    }
-                }
-                Ok(None) => {
-                    // No AIEOS identity loaded (shouldn't happen if is_aieos_configured returned true)
-                    // Fall back to OpenClaw bootstrap files
-                    let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-                    load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
-                }
-                Err(e) => {
-                    // Log error but don't fail - fall back to OpenClaw
-                    eprintln!(
-                        "Warning: Failed to load AIEOS identity: {e}. Using OpenClaw format."
-                    );
-                    let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-                    load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
+    // AIEOS identity replaces the bootstrap files when configured
+    if let Some(config) = identity_config.filter(|c| identity::is_aieos_configured(c)) {
+        match identity::load_aieos_identity(config, workspace_dir) {
+            Ok(Some(aieos_identity)) => {
+                let aieos_prompt = identity::aieos_to_system_prompt(&aieos_identity);
+                if !aieos_prompt.is_empty() {
+                    prompt.push_str(&aieos_prompt);
+                    prompt.push_str("\n\n");
                 }
+                return;
+            }
+            // No AIEOS identity loaded (shouldn't happen if is_aieos_configured returned true)
+            Ok(None) => {}
+            // Log error but don't fail - fall back to OpenClaw
+            Err(e) => {
+                eprintln!("Warning: Failed to load AIEOS identity: {e}. Using OpenClaw format.");
             }
-        } else {
-            // OpenClaw format
-            let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-            load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
         }
-    } else {
-        // No identity config - use OpenClaw format
-        let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-        load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars);
     }
+    load_openclaw_bootstrap_files(prompt, workspace_dir, max_chars, include_memory);
+}
+
+fn push_runtime_sections(prompt: &mut String, model_name: &str) {
+    use std::fmt::Write;
 
     // ── 6. Date & Time ──────────────────────────────────────────
     let now = chrono::Local::now();
@@ -2403,13 +3665,6 @@ pub fn build_system_prompt_with_mode(
     prompt.push_str("- You do NOT need to ask permission to respond — just respond directly.\n");
     prompt.push_str("- NEVER repeat, describe, or echo credentials, tokens, API keys, or secrets in your responses.\n");
     prompt.push_str("- If a tool output contains credentials, they have already been redacted — do not mention them.\n\n");
-
-    if prompt.is_empty() {
-        "You are ZeroClaw, a fast and efficient AI assistant built in Rust. Be helpful, concise, and direct."
-            .to_string()
-    } else {
-        prompt
-    }
 }
 
 /// Inject a single workspace file into the prompt with truncation and missing-file markers.
@@ -2655,6 +3910,21 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
         crate::ChannelCommands::BindTelegram { identity } => {
             bind_telegram_identity(config, &identity).await
         }
+        crate::ChannelCommands::Pairings { limit } => pairing::print_requests(config, limit),
+        crate::ChannelCommands::Pair { code } => pairing::pair_from_cli(config, &code).await,
+        crate::ChannelCommands::Simulate {
+            file,
+            channel,
+            dry_run,
+        } => {
+            Box::pin(simulate::simulate_from_cli(
+                config.clone(),
+                &file,
+                channel,
+                dry_run,
+            ))
+            .await
+        }
     }
 }
 
@@ -2696,6 +3966,8 @@ fn collect_configured_channels(
                     tg.mention_only,
                 )
                 .with_streaming(tg.stream_mode, tg.draft_update_interval_ms)
+                .with_link_previews(tg.link_previews)
+                .with_allowed_topics(tg.allowed_topics.clone())
                 .with_transcription(config.transcription.clone())
                 .with_workspace_dir(config.workspace_dir.clone()),
             ),
@@ -2703,26 +3975,41 @@ fn collect_configured_channels(
     }
 
     if let Some(ref dc) = config.channels_config.discord {
+        let discord = DiscordChannel::new(
+            dc.bot_token.clone(),
+            dc.guild_id.clone(),
+            dc.allowed_users.clone(),
+            dc.listen_to_bots,
+            dc.mention_only,
+        )
+        .with_link_previews(dc.link_previews);
+        let discord = match dc.voice.clone() {
+            Some(voice) => discord.with_voice(voice, config.tts.clone()),
+            None => discord,
+        };
         channels.push(ConfiguredChannel {
             display_name: "Discord",
-            channel: Arc::new(DiscordChannel::new(
-                dc.bot_token.clone(),
-                dc.guild_id.clone(),
-                dc.allowed_users.clone(),
-                dc.listen_to_bots,
-                dc.mention_only,
-            )),
+            channel: Arc::new(discord),
         });
+        #[cfg(not(feature = "channel-discord-voice"))]
+        if dc.voice.is_some() {
+            tracing::warn!(
+                "Discord voice is configured but this build was compiled without `channel-discord-voice`; announcements will fail."
+            );
+        }
     }
 
     if let Some(ref sl) = config.channels_config.slack {
         channels.push(ConfiguredChannel {
             display_name: "Slack",
-            channel: Arc::new(SlackChannel::new(
-                sl.bot_token.clone(),
-                sl.channel_id.clone(),
-                sl.allowed_users.clone(),
-            )),
+            channel: Arc::new(
+                SlackChannel::new(
+                    sl.bot_token.clone(),
+                    sl.channel_id.clone(),
+                    sl.allowed_users.clone(),
+                )
+                .with_status_token(sl.status_token.clone()),
+            ),
         });
     }
 
@@ -2963,6 +4250,13 @@ fn collect_configured_channels(
         });
     }
 
+    if let Some(ref web) = config.channels_config.web {
+        channels.push(ConfiguredChannel {
+            display_name: "Web",
+            channel: Arc::new(WebChannel::new(web.allowed_users.clone(), web.stream)),
+        });
+    }
+
     channels
 }
 
@@ -3034,6 +4328,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
         secrets_encrypt: config.secrets.encrypt,
         reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning_effort: config.runtime.reasoning_effort.clone(),
+        thinking_budget_tokens: config.runtime.thinking_budget_tokens,
+        debug_capture: providers::capture::DebugCapture::from_config(&config),
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -3046,6 +4343,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .await?,
     );
 
+    access::register(&config);
+    pairing::register(&config);
+
     // Warm up the provider connection pool (TLS handshake, DNS, HTTP/2 setup)
     // so the first real message doesn't hit a cold-start timeout.
     if let Err(e) = provider.warmup().await {
@@ -3066,8 +4366,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
+    crate::events::init(&config.events);
+    let observer: Arc<dyn Observer> = Arc::new(monitor::MonitorObserver::new(Box::new(
+        crate::events::EventObserver::new(observability::create_observer(&config.observability)),
+    )));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -3095,7 +4397,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let tools_registry = Arc::new(tools::all_tools_with_runtime(
         Arc::new(config.clone()),
         &security,
-        runtime,
+        Arc::clone(&runtime),
         Arc::clone(&mem),
         composio_key,
         composio_entity_id,
@@ -3108,7 +4410,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config,
     ));
 
-    let skills = crate::skills::load_skills_with_config(&workspace, &config);
+    let skills = crate::skills::load_prompt_skills(&workspace, &config);
 
     // Collect tool descriptions for the prompt
     let mut tool_descs: Vec<(&str, &str)> = vec![
@@ -3158,40 +4460,118 @@ pub async fn start_channels(config: Config) -> Result<()> {
         "pushover",
         "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
     ));
-    if !config.agents.is_empty() {
+    tool_descs.push((
+        "reverse_geocode",
+        "Resolve coordinates to an address, neighbourhood and city. Use when: the user shared a [Location: ...] and asks about places nearby.",
+    ));
+    if !config.agents.is_empty() || !config.remote_agents.is_empty() {
         tool_descs.push((
             "delegate",
-            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single prompt and returns its response.",
+            "Delegate a subtask to a specialized or remote agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation) or must run on another machine. The agent runs the task and returns its response.",
         ));
     }
 
     // Filter out tools excluded for non-CLI channels so the system prompt
     // does not advertise them for channel-driven runs.
     let excluded = &config.autonomy.non_cli_excluded_tools;
-    if !excluded.is_empty() {
-        tool_descs.retain(|(name, _)| !excluded.iter().any(|ex| ex == name));
+    let disabled = &config.tools.disabled;
+    if !excluded.is_empty() || !disabled.is_empty() {
+        tool_descs.retain(|(name, _)| !excluded.iter().chain(disabled).any(|ex| ex == name));
     }
 
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
-    let native_tools = provider.supports_native_tools();
-    let mut system_prompt = build_system_prompt_with_mode(
-        &workspace,
-        &model,
-        &tool_descs,
-        &skills,
-        Some(&config.identity),
-        bootstrap_max_chars,
-        native_tools,
-        config.skills.prompt_injection_mode,
-    );
-    if !native_tools {
-        system_prompt.push_str(&build_tool_instructions(tools_registry.as_ref()));
+    let default_prompt = prompt::PromptSource::new(
+        model.clone(),
+        tool_descs.clone(),
+        Arc::clone(&tools_registry),
+        provider.supports_native_tools(),
+    )
+    .rendered(&config, &skills);
+    let system_prompt = default_prompt.prompt.clone();
+    let mut prompt_sources = vec![default_prompt];
+
+    // Named channel agents: each gets its own prompt, tool set, and memory namespace.
+    let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+    provider_cache_seed.insert(provider_name.clone(), Arc::clone(&provider));
+    let mut channel_agents = Vec::new();
+    for (name, agent_config) in &config.channels_config.agents {
+        let agent_provider = match agent_config.provider.as_deref() {
+            Some(agent_provider) if !provider_cache_seed.contains_key(agent_provider) => {
+                let created: Arc<dyn Provider> = Arc::from(
+                    create_resilient_provider_nonblocking(
+                        agent_provider,
+                        config.api_key.clone(),
+                        None,
+                        config.reliability.clone(),
+                        provider_runtime_options.clone(),
+                    )
+                    .await?,
+                );
+                provider_cache_seed.insert(agent_provider.to_string(), Arc::clone(&created));
+                created
+            }
+            Some(agent_provider) => Arc::clone(&provider_cache_seed[agent_provider]),
+            None => Arc::clone(&provider),
+        };
+        let namespace = agent_config.memory_namespace.as_deref().unwrap_or(name);
+        let agent_memory: Arc<dyn Memory> =
+            Arc::new(memory::NamespacedMemory::new(Arc::clone(&mem), namespace));
+        let mut agent_tools = tools::all_tools_with_runtime(
+            Arc::new(config.clone()),
+            &security,
+            Arc::clone(&runtime),
+            Arc::clone(&agent_memory),
+            composio_key,
+            composio_entity_id,
+            &config.browser,
+            &config.http_request,
+            &config.web_fetch,
+            &workspace,
+            &config.agents,
+            config.api_key.as_deref(),
+            &config,
+        );
+        let agent_skills = agents::select_skills(&skills, agent_config.skills.as_deref());
+        agents::retain_tools(
+            &mut agent_tools,
+            agent_config.tools.as_deref(),
+            &skills,
+            &agent_skills,
+        );
+        let agent_tool_descs: Vec<(&str, &str)> = tool_descs
+            .iter()
+            .copied()
+            .filter(|(tool, _)| agent_tools.iter().any(|t| t.name() == *tool))
+            .collect();
+        let agent_tools = Arc::new(agent_tools);
+        let agent_prompt = prompt::PromptSource::new(
+            agent_config.model.clone().unwrap_or_else(|| model.clone()),
+            agent_tool_descs,
+            Arc::clone(&agent_tools),
+            agent_provider.supports_native_tools(),
+        )
+        .for_agent(
+            agent_config.skills.clone(),
+            agent_config.system_prompt.clone(),
+        )
+        .rendered(&config, &skills);
+        channel_agents.push(agents::ChannelAgent {
+            name: name.clone(),
+            provider: agent_config.provider.clone(),
+            model: agent_config.model.clone(),
+            system_prompt: agent_prompt.prompt.clone(),
+            memory: agent_memory,
+            tools_registry: agent_tools,
+        });
+        prompt_sources.push(agent_prompt);
     }
 
+    // Open-skills sync in the background; rebuild the prompts when it lands.
+    prompt::spawn_skills_reload(
+        crate::skills::open_skills_refreshed(),
+        config.clone(),
+        prompt_sources,
+    );
+
     if !skills.is_empty() {
         println!(
             "  🧩 Skills:   {}",
@@ -3222,6 +4602,11 @@ pub async fn start_channels(config: Config) -> Result<()> {
 
     println!("🦀 ZeroClaw Channel Server");
     println!("  🤖 Model:    {model}");
+    if !channel_agents.is_empty() {
+        let mut names: Vec<&str> = channel_agents.iter().map(|a| a.name.as_str()).collect();
+        names.sort_unstable();
+        println!("  🧭 Agents:   {}", names.join(", "));
+    }
     let effective_backend = memory::effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
@@ -3267,6 +4652,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
             max_backoff_secs,
         ));
     }
+    let injection_tx = tx.downgrade();
     drop(tx); // Drop our copy so rx closes when all channels stop
 
     let channels_by_name = Arc::new(
@@ -3275,12 +4661,19 @@ pub async fn start_channels(config: Config) -> Result<()> {
             .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
             .collect::<HashMap<_, _>>(),
     );
-    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+    if config.gateway.dashboard.enabled {
+        let mut names: Vec<String> = channels_by_name.keys().cloned().collect();
+        names.sort();
+        monitor::monitor().enable(names);
+    }
+    let max_in_flight_messages = config
+        .channels_config
+        .queue
+        .max_concurrent
+        .unwrap_or_else(|| compute_max_in_flight_messages(channels.len()));
 
     println!("  🚦 In-flight message limit: {max_in_flight_messages}");
 
-    let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
-    provider_cache_seed.insert(provider_name.clone(), Arc::clone(&provider));
     let message_timeout_secs =
         effective_channel_message_timeout_secs(config.channels_config.message_timeout_secs);
     let interrupt_on_new_message = config
@@ -3288,6 +4681,45 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .telegram
         .as_ref()
         .is_some_and(|tg| tg.interrupt_on_new_message);
+    let canary = config.security.canary.enabled.then(|| {
+        let canary_config = &config.security.canary;
+        let guard = crate::security::CanaryGuard::new();
+        let alert = canary_config
+            .alert_channel
+            .as_deref()
+            .zip(canary_config.alert_to.clone());
+        Arc::new(match alert {
+            Some((name, to)) => match channels_by_name.get(name) {
+                Some(channel) => guard.with_alert_channel(Arc::clone(channel), to),
+                None => {
+                    tracing::warn!("Canary alert channel '{name}' is not running; alerts will only be logged");
+                    guard
+                }
+            },
+            None => guard,
+        })
+    });
+
+    let guardrails = guardrails::OutputGuard::new(
+        &config.channels_config.guardrails,
+        &config.channels_config.audiences,
+    ).map(|guard| {
+        let guardrails_config = &config.channels_config.guardrails;
+        let alert = guardrails_config
+            .alert_channel
+            .as_deref()
+            .zip(guardrails_config.alert_to.clone());
+        Arc::new(match alert {
+            Some((name, to)) => match channels_by_name.get(name) {
+                Some(channel) => guard.with_alert_channel(Arc::clone(channel), to),
+                None => {
+                    tracing::warn!("Guardrail alert channel '{name}' is not running; violations will only be logged");
+                    guard
+                }
+            },
+            None => guard,
+        })
+    });
 
     let runtime_ctx = Arc::new(ChannelRuntimeContext {
         channels_by_name,
@@ -3296,7 +4728,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         memory: Arc::clone(&mem),
         tools_registry: Arc::clone(&tools_registry),
         observer,
-        system_prompt: Arc::new(system_prompt),
+        system_prompt,
         model: Arc::new(model.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
@@ -3313,17 +4745,75 @@ pub async fn start_channels(config: Config) -> Result<()> {
         message_timeout_secs,
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
-        hooks: if config.hooks.enabled {
+        hooks: if config.hooks.enabled || canary.is_some() {
             let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
+            if config.hooks.enabled && config.hooks.builtin.command_logger {
                 runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
             }
+            if let Some(guard) = &canary {
+                runner.register(Box::new(crate::hooks::builtin::CanaryHook::new(
+                    Arc::clone(guard),
+                )));
+            }
             Some(Arc::new(runner))
         } else {
             None
         },
         non_cli_excluded_tools: Arc::new(config.autonomy.non_cli_excluded_tools.clone()),
+        tool_exposure: Arc::new(config.tools.clone()),
+        auto_model: config.models.auto.clone(),
+        tool_output: config.channels_config.tool_output.clone(),
+        queue: config.channels_config.queue.clone(),
+        links: config.channels_config.links.clone(),
+        citations: config.channels_config.citations.clone(),
+        follow_ups: follow_ups::FollowUps::new(&config.channels_config.follow_ups).map(Arc::new),
+        titles: config.channels_config.titles.clone(),
+        review: review::ReplyReview::new(&config.channels_config.review).map(Arc::new),
+        spam: spam::SpamFilter::new(
+            &config.channels_config.spam,
+            config.channels_config.open_channels(),
+            config.workspace_dir.clone(),
+        )
+        .map(Arc::new),
+        guardrails,
+        language: language::LanguageLock::new(&config.channels_config.language).map(Arc::new),
+        translation: translation::AutoTranslator::from_config(&config).map(Arc::new),
+        canary,
+        checkins: config.checkins.enabled && !config.checkins.targets.is_empty(),
+        goals_prompt_limit: if config.goals.enabled {
+            config.goals.prompt_limit
+        } else {
+            0
+        },
+        contacts: (config.contacts.enabled && config.contacts.identity_hints)
+            .then(|| Arc::new(config.contacts.clone())),
+        feedback: config.feedback.enabled.then(|| config.feedback.clone()),
+        experiment: config
+            .experiments
+            .iter()
+            .find(|experiment| experiment.enabled)
+            .cloned()
+            .map(Arc::new),
+        agents: Arc::new(agents::AgentRouter::new(
+            channel_agents,
+            config.channels_config.agent_routes.clone(),
+        )),
+        groups: Arc::new(groups::GroupOverrides::new(
+            config.channels_config.groups.clone(),
+        )),
+        typing: Arc::default(),
+        agent: None,
     });
+    delegation::register(runtime_ctx.channels_by_name.as_ref().clone());
+    injection::register(
+        injection_tx,
+        runtime_ctx.channels_by_name.keys().cloned().collect(),
+        Arc::clone(&runtime_ctx.conversation_histories),
+    );
+    presence::register(
+        &config.channels_config.presence,
+        runtime_ctx.channels_by_name.values().cloned().collect(),
+    );
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
 
@@ -3338,6 +4828,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agent::loop_::build_tool_instructions;
     use crate::memory::{Memory, MemoryCategory, SqliteMemory};
     use crate::observability::NoopObserver;
     use crate::providers::{ChatMessage, Provider};
@@ -3517,7 +5008,7 @@ mod tests {
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("system".to_string()),
+            system_prompt: prompt::SharedPrompt::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -3536,6 +5027,29 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -3566,7 +5080,7 @@ mod tests {
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("system".to_string()),
+            system_prompt: prompt::SharedPrompt::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -3585,6 +5099,29 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -3618,7 +5155,7 @@ mod tests {
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("system".to_string()),
+            system_prompt: prompt::SharedPrompt::new("system".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -3637,6 +5174,29 @@ mod tests {
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4093,7 +5653,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4110,6 +5670,29 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4124,6 +5707,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4152,7 +5737,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4169,6 +5754,29 @@ BTC is currently around $65,000 based on latest tool output."#
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
         });
@@ -4183,6 +5791,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4225,7 +5835,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4244,6 +5854,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4256,6 +5889,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4284,7 +5919,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4303,6 +5938,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4315,6 +5973,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4352,7 +6012,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4371,6 +6031,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4383,6 +6066,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4441,7 +6126,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4460,32 +6145,151 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
+        });
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-routed-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "hello routed provider".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 2,
+                thread_ts: None,
+                location: None,
+                payload: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
+        assert_eq!(routed_provider_impl.call_count.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            routed_provider_impl
+                .models
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_slice(),
+            &["route-model".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_auto_selects_cheap_model_without_override() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig {
+                enabled: true,
+                cheap: Some("cheap-model".to_string()),
+                strong: Some("strong-model".to_string()),
+                threshold_tokens: 2000,
+            },
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
             runtime_ctx,
             traits::ChannelMessage {
-                id: "msg-routed-1".to_string(),
+                id: "msg-auto-1".to_string(),
                 sender: "alice".to_string(),
                 reply_target: "chat-1".to_string(),
-                content: "hello routed provider".to_string(),
+                content: "thanks!".to_string(),
                 channel: "telegram".to_string(),
-                timestamp: 2,
+                timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
         .await;
 
-        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
-        assert_eq!(routed_provider_impl.call_count.load(Ordering::SeqCst), 1);
         assert_eq!(
-            routed_provider_impl
+            provider_impl
                 .models
                 .lock()
                 .unwrap_or_else(|e| e.into_inner())
                 .as_slice(),
-            &["route-model".to_string()]
+            &["cheap-model".to_string()]
         );
     }
 
@@ -4512,7 +6316,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4531,6 +6335,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4543,6 +6370,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4595,7 +6424,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("startup-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4617,6 +6446,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4629,6 +6481,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4669,7 +6523,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4688,6 +6542,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4700,6 +6577,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4729,7 +6608,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![Box::new(MockPriceTool)]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4748,6 +6627,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -4760,6 +6662,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -4900,7 +6804,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4919,6 +6823,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -4930,6 +6857,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 1,
             thread_ts: None,
+            location: None,
+            payload: None,
         })
         .await
         .unwrap();
@@ -4941,6 +6870,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 2,
             thread_ts: None,
+            location: None,
+            payload: None,
         })
         .await
         .unwrap();
@@ -4980,7 +6911,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -4999,6 +6930,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5011,6 +6965,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             })
             .await
             .unwrap();
@@ -5023,6 +6979,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                location: None,
+                payload: None,
             })
             .await
             .unwrap();
@@ -5072,7 +7030,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -5091,6 +7049,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -5103,6 +7084,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             })
             .await
             .unwrap();
@@ -5115,6 +7098,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                location: None,
+                payload: None,
             })
             .await
             .unwrap();
@@ -5146,7 +7131,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -5165,6 +7150,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -5177,6 +7185,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -5205,7 +7215,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -5224,6 +7234,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -5236,6 +7269,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -5259,6 +7294,62 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(removed[0].2, "\u{1F440}");
     }
 
+    #[test]
+    fn configured_prompt_follows_parts_in_order() {
+        let ws = make_workspace();
+        std::fs::create_dir_all(ws.path().join("prompt.d")).unwrap();
+        std::fs::write(
+            ws.path().join("prompt.d/20-style.md"),
+            "Use short sentences.",
+        )
+        .unwrap();
+        std::fs::write(
+            ws.path().join("prompt.d/10-house.md"),
+            "Never book flights.",
+        )
+        .unwrap();
+        std::fs::write(ws.path().join("persona.md"), "You are Ada.").unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = ws.path().to_path_buf();
+        config.prompt.parts = toml::from_str::<crate::config::PromptConfig>(
+            r#"
+[[parts]]
+kind = "persona"
+path = "persona.md"
+[[parts]]
+kind = "files"
+path = "prompt.d/*.md"
+[[parts]]
+kind = "text"
+text = "Answer in British English."
+[[parts]]
+kind = "memory"
+"#,
+        )
+        .unwrap()
+        .parts;
+
+        let tools = vec![("shell", "Run commands")];
+        let prompt = build_configured_system_prompt(&config, "model", &tools, &[], false);
+        let position = |needle: &str| {
+            prompt
+                .find(needle)
+                .unwrap_or_else(|| panic!("missing {needle:?} in:\n{prompt}"))
+        };
+        assert!(position("You are Ada.") < position("Never book flights."));
+        assert!(position("Never book flights.") < position("Use short sentences."));
+        assert!(position("Use short sentences.") < position("Answer in British English."));
+        assert!(position("Answer in British English.") < position("User likes Rust."));
+        // Only the listed parts: no built-in sections or bootstrap files.
+        assert!(!prompt.contains("## Tools"));
+        assert!(!prompt.contains("Be helpful."));
+
+        config.prompt.parts.clear();
+        let default_prompt = build_configured_system_prompt(&config, "model", &tools, &[], false);
+        assert!(default_prompt.contains("## Tools"));
+        assert!(default_prompt.contains("### MEMORY.md"));
+    }
+
     #[test]
     fn prompt_contains_all_sections() {
         let ws = make_workspace();
@@ -5431,6 +7522,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                parameters: std::collections::BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -5466,6 +7559,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                parameters: std::collections::BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -5507,6 +7602,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                parameters: std::collections::BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
@@ -5594,6 +7691,30 @@ BTC is currently around $65,000 based on latest tool output."#
         );
     }
 
+    #[test]
+    fn channel_prompt_keeps_base_before_cache_boundary() {
+        let base = "Base rules and skills";
+        let telegram = build_channel_system_prompt(base, "telegram", "123");
+        let (stable, rest) = providers::split_system_prompt_for_cache(&telegram);
+        assert_eq!(stable, base);
+        let rest = rest.unwrap();
+        assert!(rest.contains("When responding on Telegram:"));
+        assert!(rest.contains("reply_target=123"));
+
+        let slack = build_channel_system_prompt(base, "slack", "C456");
+        let (stable, _) = providers::split_system_prompt_for_cache(&slack);
+        assert_eq!(stable, base);
+
+        let mut bare = build_channel_system_prompt(base, "cli", "");
+        bare.push_str("\n\n## Group Persona\n\nBe brief.");
+        let (stable, rest) = providers::split_system_prompt_for_cache(&bare);
+        assert_eq!(stable, base);
+        assert_eq!(
+            rest,
+            Some(format!("{SYSTEM_PROMPT_CACHE_BOUNDARY}## Group Persona\n\nBe brief.").as_str())
+        );
+    }
+
     #[test]
     fn prompt_workspace_path() {
         let ws = make_workspace();
@@ -5612,6 +7733,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -5627,6 +7750,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5636,6 +7761,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
 
         assert_ne!(
@@ -5657,6 +7784,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -5666,6 +7795,8 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
 
         mem.store(
@@ -5721,7 +7852,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -5740,6 +7871,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -5752,6 +7906,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -5767,6 +7923,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -5790,6 +7948,250 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(calls[1][3].1.contains("follow up"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_undo_drops_last_exchange() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
+        });
+
+        process_channel_message(
+            runtime_ctx.clone(),
+            traits::ChannelMessage {
+                id: "msg-a".to_string(),
+                sender: "undo-alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "hello".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                location: None,
+                payload: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        process_channel_message(
+            runtime_ctx.clone(),
+            traits::ChannelMessage {
+                id: "msg-undo".to_string(),
+                sender: "undo-alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "/undo".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 2,
+                thread_ts: None,
+                location: None,
+                payload: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        process_channel_message(
+            runtime_ctx,
+            traits::ChannelMessage {
+                id: "msg-b".to_string(),
+                sender: "undo-alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "follow up".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 3,
+                thread_ts: None,
+                location: None,
+                payload: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert!(sent.iter().any(|m| m.contains("Undid the last exchange")));
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 2, "/undo must not reach the model");
+        assert_eq!(calls[1].len(), 2);
+        assert_eq!(calls[1][1].0, "user");
+        assert!(calls[1][1].1.contains("follow up"));
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_uses_routed_agent_prompt() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let coder = agents::ChannelAgent {
+            name: "coder".to_string(),
+            provider: None,
+            model: Some("coder-model".to_string()),
+            system_prompt: prompt::SharedPrompt::new("coder-system-prompt".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+        };
+        let routes = vec![crate::config::ChannelAgentRouteConfig {
+            agent: "coder".to_string(),
+            channel: Some("test-channel".to_string()),
+            sender: Some("alice".to_string()),
+            ..Default::default()
+        }];
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::new(agents::AgentRouter::new(vec![coder], routes)),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
+        });
+
+        for (id, sender) in [("msg-a", "alice"), ("msg-b", "bob")] {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: sender.to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: "hello".to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                    location: None,
+                    payload: None,
+                },
+                CancellationToken::new(),
+            )
+            .await;
+        }
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0][0].1.contains("coder-system-prompt"));
+        assert!(calls[1][0].1.contains("test-system-prompt"));
+        assert!(!calls[1][0].1.contains("coder-system-prompt"));
+        drop(calls);
+
+        let alice = traits::ChannelMessage {
+            id: "msg-c".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-1".to_string(),
+            content: "hello".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 2,
+            thread_ts: None,
+            location: None,
+            payload: None,
+        };
+        let routed = agents::routed_context(&runtime_ctx, &alice);
+        assert_eq!(default_route_selection(&routed).model, "coder-model");
+        assert_eq!(default_route_selection(&runtime_ctx).model, "test-model");
+    }
+
     #[tokio::test]
     async fn process_channel_message_enriches_current_turn_without_persisting_context() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -5806,7 +8208,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(RecallMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -5825,6 +8227,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -5837,6 +8262,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -5865,6 +8292,130 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!turns[0].content.contains("[Memory context]"));
     }
 
+    /// Translation model stub: detects Spanish and translates replies.
+    struct SpanishTranslationProvider;
+
+    #[async_trait::async_trait]
+    impl Provider for SpanishTranslationProvider {
+        async fn chat_with_system(
+            &self,
+            system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            if system_prompt.is_some_and(|prompt| prompt.starts_with("Identify the language")) {
+                return Ok("Spanish\nWhere is the station?".to_string());
+            }
+            Ok(format!("es:{message}"))
+        }
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_auto_translates_foreign_messages() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+        let translator = crate::tools::translate::Translator::new(
+            Arc::new(SpanishTranslationProvider),
+            "translate-model",
+        );
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: Some(Arc::new(translation::AutoTranslator::new(
+                translator,
+                "English",
+                ["test-channel"],
+            ))),
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
+        });
+
+        process_channel_message(
+            runtime_ctx.clone(),
+            traits::ChannelMessage {
+                id: "msg-es-1".to_string(),
+                sender: "maria".to_string(),
+                reply_target: "chat-es".to_string(),
+                content: "¿Dónde está la estación?".to_string(),
+                channel: "test-channel".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+                location: None,
+                payload: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert_eq!(
+            provider_impl
+                .calls
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())[0][1]
+                .1,
+            "[Translated from Spanish] Where is the station?"
+        );
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.as_slice(), ["chat-es:es:response-1"]);
+
+        let histories = runtime_ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let turns = histories.get("test-channel_maria").unwrap();
+        assert_eq!(turns[1].content, "response-1");
+    }
+
     #[tokio::test]
     async fn process_channel_message_telegram_keeps_system_instruction_at_top_only() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -5891,7 +8442,7 @@ BTC is currently around $65,000 based on latest tool output."#
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("test-system-prompt".to_string()),
+            system_prompt: prompt::SharedPrompt::new("test-system-prompt".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -5910,6 +8461,29 @@ BTC is currently around $65,000 based on latest tool output."#
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -5922,6 +8496,8 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -6440,7 +9016,7 @@ This is an example JSON object for profile settings."#;
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("You are a helpful assistant.".to_string()),
+            system_prompt: prompt::SharedPrompt::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -6459,6 +9035,29 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -6472,6 +9071,8 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -6506,7 +9107,7 @@ This is an example JSON object for profile settings."#;
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
             observer: Arc::new(NoopObserver),
-            system_prompt: Arc::new("You are a helpful assistant.".to_string()),
+            system_prompt: prompt::SharedPrompt::new("You are a helpful assistant.".to_string()),
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
@@ -6525,6 +9126,29 @@ This is an example JSON object for profile settings."#;
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_exposure: Arc::default(),
+            auto_model: crate::config::AutoModelConfig::default(),
+            tool_output: crate::config::ToolOutputStreamConfig::default(),
+            queue: crate::config::ChannelQueueConfig::default(),
+            links: crate::config::LinkPolicyConfig::default(),
+            citations: crate::config::CitationsConfig::default(),
+            follow_ups: None,
+            titles: crate::config::ConversationTitlesConfig::default(),
+            review: None,
+            spam: None,
+            guardrails: None,
+            language: None,
+            translation: None,
+            canary: None,
+            checkins: false,
+            goals_prompt_limit: 0,
+            contacts: None,
+            feedback: None,
+            experiment: None,
+            agents: Arc::default(),
+            groups: Arc::default(),
+            typing: Arc::default(),
+            agent: None,
         });
 
         process_channel_message(
@@ -6537,6 +9161,8 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -6552,6 +9178,8 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                location: None,
+                payload: None,
             },
             CancellationToken::new(),
         )
@@ -0,0 +1,483 @@
+//! Live view of channel activity for the gateway dashboard.
+//!
+//! The channel runtime reports inbound messages, replies, active runs, tool
+//! calls, queue depth, and provider latency to a process-wide
+//! [`ChannelMonitor`]. The gateway's `/dashboard` reads snapshots, streams
+//! [`ChannelMonitor::subscribe`] events over SSE, and uses the monitor to
+//! pause channels and cancel runs. Nothing is recorded until
+//! [`ChannelMonitor::enable`] is called, so the monitor costs nothing when the
+//! dashboard is off.
+
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+
+/// Conversations kept before the least recently active is dropped.
+const MAX_CONVERSATIONS: usize = 200;
+/// Messages kept per conversation.
+const MAX_MESSAGES_PER_CONVERSATION: usize = 50;
+/// Tool call traces kept across all runs.
+const MAX_TOOL_TRACES: usize = 200;
+/// Characters of message text kept for display.
+const MAX_TEXT_CHARS: usize = 1000;
+const EVENT_BUFFER: usize = 256;
+
+tokio::task_local! {
+    /// Dashboard run id of the channel message being processed.
+    static CURRENT_RUN: u64;
+}
+
+/// A message shown in a conversation transcript.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptEntry {
+    /// `user` or `assistant`.
+    pub role: &'static str,
+    pub text: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationView {
+    pub channel: String,
+    pub sender: String,
+    pub messages: VecDeque<TranscriptEntry>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An agent run in progress for a channel message.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunView {
+    pub id: u64,
+    pub channel: String,
+    pub conversation: String,
+    pub sender: String,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolTrace {
+    pub run: Option<u64>,
+    pub tool: String,
+    pub duration_ms: u128,
+    pub success: bool,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProviderLatency {
+    pub calls: u64,
+    pub failures: u64,
+    pub last_ms: u128,
+    pub avg_ms: u128,
+}
+
+/// Point-in-time dashboard state.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MonitorSnapshot {
+    pub channels: Vec<String>,
+    pub paused: BTreeSet<String>,
+    pub conversations: BTreeMap<String, ConversationView>,
+    pub runs: Vec<RunView>,
+    pub tool_calls: VecDeque<ToolTrace>,
+    pub queue_depth: u64,
+    pub active_sessions: u64,
+    pub providers: BTreeMap<String, ProviderLatency>,
+}
+
+/// Process-wide channel activity recorder; see the module docs.
+pub struct ChannelMonitor {
+    enabled: AtomicBool,
+    next_run: AtomicU64,
+    state: Mutex<MonitorSnapshot>,
+    /// Conversation keys, least recently active first.
+    recency: Mutex<VecDeque<String>>,
+    cancellations: Mutex<HashMap<u64, CancellationToken>>,
+    events: broadcast::Sender<serde_json::Value>,
+}
+
+static MONITOR: LazyLock<ChannelMonitor> = LazyLock::new(ChannelMonitor::new);
+
+/// The process-wide channel monitor.
+pub fn monitor() -> &'static ChannelMonitor {
+    &MONITOR
+}
+
+/// Run `future` with its tool calls attributed to dashboard run `run_id`.
+pub async fn with_run<F: std::future::Future>(run_id: Option<u64>, future: F) -> F::Output {
+    match run_id {
+        Some(id) => CURRENT_RUN.scope(id, future).await,
+        None => future.await,
+    }
+}
+
+/// Removes its run from the dashboard when dropped.
+pub struct RunGuard {
+    id: Option<u64>,
+}
+
+impl RunGuard {
+    pub fn id(&self) -> Option<u64> {
+        self.id
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            monitor().finish_run(id);
+        }
+    }
+}
+
+impl ChannelMonitor {
+    fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            enabled: AtomicBool::new(false),
+            next_run: AtomicU64::new(1),
+            state: Mutex::new(MonitorSnapshot::default()),
+            recency: Mutex::new(VecDeque::new()),
+            cancellations: Mutex::new(HashMap::new()),
+            events,
+        }
+    }
+
+    /// Start recording activity for `channels`.
+    pub fn enable(&self, channels: Vec<String>) {
+        self.state.lock().channels = channels;
+        self.enabled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Live events, one JSON object per change.
+    pub fn subscribe(&self) -> broadcast::Receiver<serde_json::Value> {
+        self.events.subscribe()
+    }
+
+    pub fn snapshot(&self) -> MonitorSnapshot {
+        self.state.lock().clone()
+    }
+
+    fn emit(&self, event: serde_json::Value) {
+        let _ = self.events.send(event);
+    }
+
+    pub fn has_channel(&self, channel: &str) -> bool {
+        self.state
+            .lock()
+            .channels
+            .iter()
+            .any(|name| name == channel)
+    }
+
+    /// Whether new messages on `channel` should be ignored.
+    pub fn is_paused(&self, channel: &str) -> bool {
+        self.is_enabled() && self.state.lock().paused.contains(channel)
+    }
+
+    /// Pause or resume a channel. Returns `false` when nothing changed.
+    pub fn set_paused(&self, channel: &str, paused: bool) -> bool {
+        let changed = {
+            let mut state = self.state.lock();
+            if paused {
+                state.paused.insert(channel.to_string())
+            } else {
+                state.paused.remove(channel)
+            }
+        };
+        if changed {
+            self.emit(serde_json::json!({
+                "type": if paused { "channel_paused" } else { "channel_resumed" },
+                "channel": channel,
+            }));
+        }
+        changed
+    }
+
+    /// Register a run so it can be shown and cancelled.
+    pub fn start_run(
+        &self,
+        channel: &str,
+        conversation: &str,
+        sender: &str,
+        cancellation: &CancellationToken,
+    ) -> RunGuard {
+        if !self.is_enabled() {
+            return RunGuard { id: None };
+        }
+        let id = self.next_run.fetch_add(1, Ordering::Relaxed);
+        let run = RunView {
+            id,
+            channel: channel.to_string(),
+            conversation: conversation.to_string(),
+            sender: sender.to_string(),
+            started_at: Utc::now(),
+        };
+        self.emit(serde_json::json!({ "type": "run_started", "run": &run }));
+        self.cancellations.lock().insert(id, cancellation.clone());
+        self.state.lock().runs.push(run);
+        RunGuard { id: Some(id) }
+    }
+
+    fn finish_run(&self, id: u64) {
+        self.cancellations.lock().remove(&id);
+        self.state.lock().runs.retain(|run| run.id != id);
+        self.emit(serde_json::json!({ "type": "run_finished", "id": id }));
+    }
+
+    /// Cancel a run. Returns `false` when it is not running.
+    pub fn cancel_run(&self, id: u64) -> bool {
+        let Some(token) = self.cancellations.lock().get(&id).cloned() else {
+            return false;
+        };
+        token.cancel();
+        self.emit(serde_json::json!({ "type": "run_cancelled", "id": id }));
+        true
+    }
+
+    /// Append a message to a conversation transcript.
+    pub fn record_message(
+        &self,
+        channel: &str,
+        conversation: &str,
+        sender: &str,
+        role: &'static str,
+        text: &str,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+        let entry = TranscriptEntry {
+            role,
+            text: crate::util::truncate_with_ellipsis(
+                &crate::agent::loop_::scrub_credentials(text),
+                MAX_TEXT_CHARS,
+            ),
+            at: Utc::now(),
+        };
+        self.emit(serde_json::json!({
+            "type": "message",
+            "channel": channel,
+            "conversation": conversation,
+            "sender": sender,
+            "entry": &entry,
+        }));
+
+        let mut state = self.state.lock();
+        let view = state
+            .conversations
+            .entry(conversation.to_string())
+            .or_insert_with(|| ConversationView {
+                channel: channel.to_string(),
+                sender: sender.to_string(),
+                messages: VecDeque::new(),
+                updated_at: entry.at,
+            });
+        view.updated_at = entry.at;
+        view.messages.push_back(entry);
+        while view.messages.len() > MAX_MESSAGES_PER_CONVERSATION {
+            view.messages.pop_front();
+        }
+
+        let mut recency = self.recency.lock();
+        recency.retain(|key| key != conversation);
+        recency.push_back(conversation.to_string());
+        while recency.len() > MAX_CONVERSATIONS {
+            if let Some(oldest) = recency.pop_front() {
+                state.conversations.remove(&oldest);
+            }
+        }
+    }
+
+    fn record_tool_call(&self, tool: &str, duration_ms: u128, success: bool) {
+        let trace = ToolTrace {
+            run: CURRENT_RUN.try_with(|id| *id).ok(),
+            tool: tool.to_string(),
+            duration_ms,
+            success,
+            at: Utc::now(),
+        };
+        self.emit(serde_json::json!({ "type": "tool_call", "trace": &trace }));
+        let mut state = self.state.lock();
+        state.tool_calls.push_back(trace);
+        while state.tool_calls.len() > MAX_TOOL_TRACES {
+            state.tool_calls.pop_front();
+        }
+    }
+
+    fn record_llm_response(&self, provider: &str, duration_ms: u128, success: bool) {
+        let latency = {
+            let mut state = self.state.lock();
+            let stats = state.providers.entry(provider.to_string()).or_default();
+            stats.calls += 1;
+            if !success {
+                stats.failures += 1;
+            }
+            stats.last_ms = duration_ms;
+            stats.avg_ms = (stats.avg_ms * u128::from(stats.calls - 1) + duration_ms)
+                / u128::from(stats.calls);
+            stats.clone()
+        };
+        self.emit(serde_json::json!({
+            "type": "provider_latency",
+            "provider": provider,
+            "latency": latency,
+        }));
+    }
+
+    fn record_queue(&self, queue_depth: Option<u64>, active_sessions: Option<u64>) {
+        let (queue_depth, active_sessions) = {
+            let mut state = self.state.lock();
+            if let Some(depth) = queue_depth {
+                state.queue_depth = depth;
+            }
+            if let Some(active) = active_sessions {
+                state.active_sessions = active;
+            }
+            (state.queue_depth, state.active_sessions)
+        };
+        self.emit(serde_json::json!({
+            "type": "queue",
+            "queue_depth": queue_depth,
+            "active_sessions": active_sessions,
+        }));
+    }
+}
+
+/// Observer that feeds tool calls, provider latency, and queue metrics to the
+/// monitor before forwarding everything to `inner`.
+pub struct MonitorObserver {
+    inner: Box<dyn Observer>,
+}
+
+impl MonitorObserver {
+    pub fn new(inner: Box<dyn Observer>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Observer for MonitorObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        self.inner.record_event(event);
+        let monitor = monitor();
+        if !monitor.is_enabled() {
+            return;
+        }
+        match event {
+            ObserverEvent::ToolCall {
+                tool,
+                duration,
+                success,
+            } => monitor.record_tool_call(tool, duration.as_millis(), *success),
+            ObserverEvent::LlmResponse {
+                provider,
+                duration,
+                success,
+                ..
+            } => monitor.record_llm_response(provider, duration.as_millis(), *success),
+            _ => {}
+        }
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+        let monitor = monitor();
+        if !monitor.is_enabled() {
+            return;
+        }
+        match metric {
+            ObserverMetric::QueueDepth(depth) => monitor.record_queue(Some(*depth), None),
+            ObserverMetric::ActiveSessions(active) => monitor.record_queue(None, Some(*active)),
+            _ => {}
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        "channel_monitor"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn monitor_tracks_runs_transcripts_and_tool_calls() {
+        let monitor = ChannelMonitor::new();
+        let token = CancellationToken::new();
+        assert!(monitor
+            .start_run("telegram", "telegram_alice", "alice", &token)
+            .id()
+            .is_none());
+
+        monitor.enable(vec!["telegram".into()]);
+        let mut events = monitor.subscribe();
+        monitor.record_message("telegram", "telegram_alice", "alice", "user", "hi");
+        monitor.record_message("telegram", "telegram_alice", "alice", "assistant", "hello");
+
+        let id = monitor.next_run.load(Ordering::Relaxed);
+        monitor.cancellations.lock().insert(id, token.clone());
+        CURRENT_RUN
+            .scope(id, async { monitor.record_tool_call("shell", 12, true) })
+            .await;
+        monitor.record_llm_response("openrouter", 100, true);
+        monitor.record_llm_response("openrouter", 300, false);
+
+        let snapshot = monitor.snapshot();
+        let conversation = &snapshot.conversations["telegram_alice"];
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(conversation.messages[1].role, "assistant");
+        assert_eq!(snapshot.tool_calls[0].run, Some(id));
+        let latency = &snapshot.providers["openrouter"];
+        assert_eq!((latency.calls, latency.failures), (2, 1));
+        assert_eq!((latency.last_ms, latency.avg_ms), (300, 200));
+        assert_eq!(events.recv().await.unwrap()["type"], "message");
+
+        assert!(monitor.cancel_run(id));
+        assert!(token.is_cancelled());
+        assert!(!monitor.cancel_run(id + 1));
+    }
+
+    #[test]
+    fn pausing_is_idempotent_and_ignored_while_disabled() {
+        let monitor = ChannelMonitor::new();
+        assert!(monitor.set_paused("discord", true));
+        assert!(!monitor.is_paused("discord"));
+
+        monitor.enable(vec!["discord".into()]);
+        assert!(monitor.is_paused("discord"));
+        assert!(!monitor.set_paused("discord", true));
+        assert!(monitor.set_paused("discord", false));
+        assert!(!monitor.is_paused("discord"));
+    }
+
+    #[test]
+    fn oldest_conversations_are_evicted() {
+        let monitor = ChannelMonitor::new();
+        monitor.enable(Vec::new());
+        for i in 0..=MAX_CONVERSATIONS {
+            monitor.record_message("cli", &format!("conv{i}"), "user", "user", "x");
+        }
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot.conversations.len(), MAX_CONVERSATIONS);
+        assert!(!snapshot.conversations.contains_key("conv0"));
+    }
+}
@@ -0,0 +1,330 @@
+//! Outbound message log with delivery status.
+//!
+//! Every agent reply sent through a channel is recorded in
+//! `<workspace>/channels/outbox.db`. Platforms that report delivery receipts
+//! (see [`Channel::supports_delivery_receipts`](super::traits::Channel::supports_delivery_receipts))
+//! advance the stored status from `sent` to `delivered`/`read`, so
+//! `zeroclaw outbox list` shows whether a reply actually reached the person.
+
+use super::traits::{DeliveryReceipt, DeliveryStatus};
+use crate::config::Config;
+use crate::memory::encryption::{open_text, seal_text};
+use crate::observability::{Observer, ObserverEvent};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use uuid::Uuid;
+
+/// Characters of message content kept for `outbox list`.
+const CONTENT_PREVIEW_CHARS: usize = 120;
+
+/// A recorded outbound message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub channel: String,
+    pub recipient: String,
+    /// Platform message id, when the channel returned one.
+    pub platform_message_id: Option<String>,
+    pub preview: String,
+    pub status: DeliveryStatus,
+    /// Whether the channel reports receipts; if not, `sent` is final.
+    pub receipts: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Details of an outbound message to record.
+pub struct OutboundMessage<'a> {
+    pub channel: &'a str,
+    pub recipient: &'a str,
+    pub platform_message_id: Option<&'a str>,
+    pub content: &'a str,
+    pub status: DeliveryStatus,
+    pub receipts: bool,
+}
+
+/// Record an outbound message and report it to the observer.
+pub fn record(
+    workspace_dir: &Path,
+    message: &OutboundMessage<'_>,
+    observer: &dyn Observer,
+) -> Result<String> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    let preview = seal_text(
+        workspace_dir,
+        &crate::util::truncate_with_ellipsis(message.content, CONTENT_PREVIEW_CHARS),
+    )?;
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO outbox (id, channel, recipient, platform_message_id, preview, status,
+                                 receipts, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+            params![
+                id,
+                message.channel,
+                message.recipient,
+                message.platform_message_id,
+                preview,
+                message.status.as_str(),
+                message.receipts,
+                now,
+            ],
+        )
+        .context("Failed to record outbound message")?;
+        Ok(())
+    })?;
+    record_status_event(observer, message.channel, message.status);
+    Ok(id)
+}
+
+/// Apply a platform delivery receipt. Returns `true` when a stored message advanced.
+pub fn apply_receipt(
+    workspace_dir: &Path,
+    channel: &str,
+    receipt: &DeliveryReceipt,
+    observer: &dyn Observer,
+) -> Result<bool> {
+    let updated = with_connection(workspace_dir, |conn| {
+        let current: Option<String> = conn
+            .query_row(
+                "SELECT status FROM outbox WHERE channel = ?1 AND platform_message_id = ?2",
+                params![channel, receipt.message_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(current) = current.as_deref().and_then(DeliveryStatus::parse) else {
+            return Ok(false);
+        };
+        if !current.advances_to(receipt.status) {
+            return Ok(false);
+        }
+        conn.execute(
+            "UPDATE outbox SET status = ?1, updated_at = ?2
+             WHERE channel = ?3 AND platform_message_id = ?4",
+            params![
+                receipt.status.as_str(),
+                Utc::now().to_rfc3339(),
+                channel,
+                receipt.message_id
+            ],
+        )?;
+        Ok(true)
+    })?;
+    if updated {
+        record_status_event(observer, channel, receipt.status);
+    }
+    Ok(updated)
+}
+
+/// The recorded reply with the given platform message id, if any.
+pub fn find_by_platform_id(
+    workspace_dir: &Path,
+    channel: &str,
+    platform_message_id: &str,
+) -> Result<Option<OutboxEntry>> {
+    with_connection(workspace_dir, |conn| {
+        conn.query_row(
+            "SELECT id, channel, recipient, platform_message_id, preview, status, receipts,
+                    created_at, updated_at
+             FROM outbox
+             WHERE channel = ?1 AND platform_message_id = ?2
+             ORDER BY created_at DESC
+             LIMIT 1",
+            params![channel, platform_message_id],
+            entry_from_row,
+        )
+        .optional()
+        .map_err(Into::into)
+    })?
+    .map(|entry| opened(workspace_dir, entry))
+    .transpose()
+}
+
+/// Most recent outbound messages, newest first.
+pub fn list(workspace_dir: &Path, channel: Option<&str>, limit: usize) -> Result<Vec<OutboxEntry>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, channel, recipient, platform_message_id, preview, status, receipts,
+                    created_at, updated_at
+             FROM outbox
+             WHERE ?1 IS NULL OR channel = ?1
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?2",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = stmt.query_map(params![channel, limit], entry_from_row)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })?
+    .into_iter()
+    .map(|entry| opened(workspace_dir, entry))
+    .collect()
+}
+
+/// `entry` with its preview decrypted.
+fn opened(workspace_dir: &Path, mut entry: OutboxEntry) -> Result<OutboxEntry> {
+    entry.preview = open_text(workspace_dir, &entry.preview)?;
+    Ok(entry)
+}
+
+fn entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<OutboxEntry> {
+    let status: String = row.get(5)?;
+    Ok(OutboxEntry {
+        id: row.get(0)?,
+        channel: row.get(1)?,
+        recipient: row.get(2)?,
+        platform_message_id: row.get(3)?,
+        preview: row.get(4)?,
+        status: DeliveryStatus::parse(&status).unwrap_or(DeliveryStatus::Sent),
+        receipts: row.get(6)?,
+        created_at: parse_timestamp(&row.get::<_, String>(7)?),
+        updated_at: parse_timestamp(&row.get::<_, String>(8)?),
+    })
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_command(command: crate::OutboxCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::OutboxCommands::List { channel, limit } => {
+            let entries = list(&config.workspace_dir, channel.as_deref(), limit)?;
+            if entries.is_empty() {
+                println!("No outbound messages recorded yet.");
+                return Ok(());
+            }
+
+            println!("📤 Outbound messages ({}):", entries.len());
+            for entry in entries {
+                let status = if entry.receipts || entry.status != DeliveryStatus::Sent {
+                    entry.status.as_str().to_string()
+                } else {
+                    "sent (no receipts)".to_string()
+                };
+                println!(
+                    "- {} | {} → {} | {} | updated {}",
+                    entry.created_at.to_rfc3339(),
+                    entry.channel,
+                    entry.recipient,
+                    status,
+                    entry.updated_at.to_rfc3339(),
+                );
+                println!("    {}", entry.preview);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn record_status_event(observer: &dyn Observer, channel: &str, status: DeliveryStatus) {
+    observer.record_event(&ObserverEvent::ChannelMessage {
+        channel: channel.to_string(),
+        direction: status.as_str().to_string(),
+    });
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("outbox.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open outbox DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS outbox (
+            id                  TEXT PRIMARY KEY,
+            channel             TEXT NOT NULL,
+            recipient           TEXT NOT NULL,
+            platform_message_id TEXT,
+            preview             TEXT NOT NULL,
+            status              TEXT NOT NULL,
+            receipts            INTEGER NOT NULL DEFAULT 0,
+            created_at          TEXT NOT NULL,
+            updated_at          TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_outbox_platform_id
+            ON outbox(channel, platform_message_id);
+        CREATE INDEX IF NOT EXISTS idx_outbox_created_at ON outbox(created_at);",
+    )
+    .context("Failed to initialize outbox schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+
+    fn outbound(id: Option<&str>, receipts: bool) -> OutboundMessage<'_> {
+        OutboundMessage {
+            channel: "whatsapp",
+            recipient: "+15550001",
+            platform_message_id: id,
+            content: "Your order shipped",
+            status: DeliveryStatus::Sent,
+            receipts,
+        }
+    }
+
+    fn receipt(id: &str, status: DeliveryStatus) -> DeliveryReceipt {
+        DeliveryReceipt {
+            message_id: id.to_string(),
+            recipient: "+15550001".to_string(),
+            status,
+        }
+    }
+
+    #[test]
+    fn receipts_advance_recorded_messages() {
+        let tmp = tempfile::tempdir().unwrap();
+        let observer = NoopObserver;
+        record(tmp.path(), &outbound(Some("wamid.1"), true), &observer).unwrap();
+
+        let delivered = receipt("wamid.1", DeliveryStatus::Delivered);
+        assert!(apply_receipt(tmp.path(), "whatsapp", &delivered, &observer).unwrap());
+        let read = receipt("wamid.1", DeliveryStatus::Read);
+        assert!(apply_receipt(tmp.path(), "whatsapp", &read, &observer).unwrap());
+        // A late `delivered` receipt must not regress `read`.
+        assert!(!apply_receipt(tmp.path(), "whatsapp", &delivered, &observer).unwrap());
+
+        let entries = list(tmp.path(), None, 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, DeliveryStatus::Read);
+        assert_eq!(entries[0].preview, "Your order shipped");
+    }
+
+    #[test]
+    fn receipts_for_unknown_messages_are_ignored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let observer = NoopObserver;
+        let delivered = receipt("wamid.missing", DeliveryStatus::Delivered);
+        assert!(!apply_receipt(tmp.path(), "whatsapp", &delivered, &observer).unwrap());
+    }
+
+    #[test]
+    fn list_filters_by_channel_and_limit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let observer = NoopObserver;
+        record(tmp.path(), &outbound(None, false), &observer).unwrap();
+        record(
+            tmp.path(),
+            &OutboundMessage {
+                channel: "telegram",
+                ..outbound(None, false)
+            },
+            &observer,
+        )
+        .unwrap();
+
+        assert_eq!(list(tmp.path(), None, 10).unwrap().len(), 2);
+        assert_eq!(list(tmp.path(), None, 1).unwrap().len(), 1);
+        let telegram = list(tmp.path(), Some("telegram"), 10).unwrap();
+        assert_eq!(telegram.len(), 1);
+        assert!(!telegram[0].receipts);
+    }
+}
@@ -0,0 +1,575 @@
+//! Pairing of unknown senders (`[channels_config.pairing]`).
+//!
+//! On pairing channels, a sender outside the allowlist is answered once with
+//! a six-digit code, stored in `<workspace>/channels/pairing.db`. The code is
+//! confirmed with `zeroclaw channel pair <code>` or with `/pair <code>` from an
+//! approver; the sender is then added to the channel's allowlist in
+//! `config.toml`. Running channels accept senders paired since they started,
+//! so no restart is needed.
+
+use super::traits::{ChannelMessage, SendMessage};
+use crate::config::{ChannelPairingConfig, Config};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// Channels whose unknown senders can be handed a pairing code.
+pub const SUPPORTED_CHANNELS: &[&str] = &["telegram", "discord", "slack"];
+
+/// Requests listed by `/pairings`.
+const CHAT_LIST_LIMIT: usize = 10;
+/// Longest time a code stays valid (`code_ttl_secs` is capped here).
+pub const MAX_CODE_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// A pairing request and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingRequest {
+    pub id: i64,
+    pub channel: String,
+    pub sender: String,
+    /// Chat the code was sent to; the confirmation goes there too.
+    pub reply_target: String,
+    pub code: String,
+    /// `pending` or `approved`.
+    pub status: String,
+    pub approver: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+impl PairingRequest {
+    pub fn is_expired(&self) -> bool {
+        self.status == "pending" && self.expires_at <= Utc::now()
+    }
+}
+
+/// A pairing chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PairingCommand {
+    List,
+    Pair(String),
+}
+
+/// Pairing settings of the running channels.
+struct SenderPairing {
+    config: ChannelPairingConfig,
+    workspace_dir: PathBuf,
+    config_path: PathBuf,
+    /// Approvals before this are already in the loaded allowlists.
+    started_at: DateTime<Utc>,
+}
+
+static ACTIVE: LazyLock<Mutex<Option<Arc<SenderPairing>>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Called by `start_channels`; pairing stays off unless enabled.
+pub(super) fn register(config: &Config) {
+    let pairing = &config.channels_config.pairing;
+    *ACTIVE.lock() = pairing.enabled.then(|| {
+        Arc::new(SenderPairing {
+            config: pairing.clone(),
+            workspace_dir: config.workspace_dir.clone(),
+            config_path: config.config_path.clone(),
+            started_at: Utc::now(),
+        })
+    });
+}
+
+fn active() -> Option<Arc<SenderPairing>> {
+    ACTIVE.lock().clone()
+}
+
+/// Whether `sender` on `channel` was paired while the channels were running.
+/// Checked through [`super::access::check`].
+pub fn is_paired(channel: &str, sender: &str) -> bool {
+    let Some(pairing) = active() else {
+        return false;
+    };
+    paired_since(&pairing.workspace_dir, channel, sender, pairing.started_at).unwrap_or_else(|e| {
+        tracing::warn!("Pairing lookup failed: {e}");
+        false
+    })
+}
+
+/// Whether unknown senders on `channel` are handed pairing codes.
+pub fn applies_to(channel: &str) -> bool {
+    active().is_some_and(|p| p.config.channels.iter().any(|c| c == channel))
+}
+
+/// Reply for an unknown sender on `channel`: a new code and how to confirm
+/// it. `None` when pairing is off for the channel or the sender still holds
+/// a valid code, so repeated messages are not answered again.
+pub async fn challenge(channel: &str, sender: &str, reply_target: &str) -> Option<String> {
+    let pairing = active().filter(|p| p.config.channels.iter().any(|c| c == channel))?;
+    let request = match issue(
+        &pairing.workspace_dir,
+        channel,
+        sender,
+        reply_target,
+        pairing.config.code_ttl_secs,
+    ) {
+        Ok(request) => request?,
+        Err(e) => {
+            tracing::warn!("Failed to issue pairing code: {e}");
+            return None;
+        }
+    };
+    tracing::info!(channel, sender, "Pairing code issued");
+
+    let notify_channel = pairing.config.notify_channel.as_str();
+    if let Some(notify) = super::delegation::running_channel(notify_channel) {
+        let notice = SendMessage::new(request_notice(&request), &pairing.config.notify_to);
+        if let Err(e) = notify.send(&notice).await {
+            tracing::warn!("Failed to post pairing request to {notify_channel}: {e}");
+        }
+    }
+    Some(challenge_message(&request))
+}
+
+/// Whether `msg` may confirm pairing codes: a listed approver, or anyone in
+/// the notification chat.
+pub fn is_approver(msg: &ChannelMessage) -> bool {
+    let Some(pairing) = active() else {
+        return false;
+    };
+    let config = &pairing.config;
+    let qualified = format!("{}:{}", msg.channel, msg.sender);
+    config.approvers.iter().any(|a| a == &qualified)
+        || (!config.notify_to.is_empty()
+            && msg.channel == config.notify_channel
+            && msg.reply_target == config.notify_to)
+}
+
+/// Parse a pairing chat command, ignoring `@bot` suffixes.
+pub fn parse_command(content: &str) -> Option<PairingCommand> {
+    let trimmed = content.trim();
+    let (head, rest) = trimmed
+        .split_once(char::is_whitespace)
+        .unwrap_or((trimmed, ""));
+    let command = head.split('@').next().unwrap_or(head).to_ascii_lowercase();
+    let code = rest.trim();
+    match command.as_str() {
+        "/pairings" => Some(PairingCommand::List),
+        "/pair" if !code.is_empty() => Some(PairingCommand::Pair(code.to_string())),
+        _ => None,
+    }
+}
+
+/// Apply an approver's chat command and return the reply.
+pub async fn handle_chat_command(command: PairingCommand, approver: &str) -> String {
+    let Some(pairing) = active() else {
+        return "Pairing is not enabled.".into();
+    };
+    match command {
+        PairingCommand::List => pending_list(&pairing.workspace_dir),
+        PairingCommand::Pair(code) => {
+            let request = match confirm(
+                &pairing.workspace_dir,
+                &pairing.config_path,
+                &code,
+                approver,
+            )
+            .await
+            {
+                Ok(request) => request,
+                Err(e) => return format!("⚠️ {e:#}"),
+            };
+            if let Some(channel) = super::delegation::running_channel(&request.channel) {
+                let welcome = SendMessage::new(PAIRED_MESSAGE, &request.reply_target);
+                if let Err(e) = channel.send(&welcome).await {
+                    tracing::warn!("Failed to tell paired sender on {}: {e}", request.channel);
+                }
+            }
+            format!("✅ Paired {} sender {}.", request.channel, request.sender)
+        }
+    }
+}
+
+/// Sent to a sender once their code is confirmed.
+const PAIRED_MESSAGE: &str = "✅ You're paired. You can talk to ZeroClaw now.";
+
+/// Confirm `code`, then add its sender to the channel allowlist in the config
+/// file at `config_path`.
+pub async fn confirm(
+    workspace_dir: &Path,
+    config_path: &Path,
+    code: &str,
+    approver: &str,
+) -> Result<PairingRequest> {
+    let request = approve(workspace_dir, code, approver)?;
+    super::access::allow(config_path, &request.channel, &request.sender)
+        .await
+        .context("Paired until restart, but saving the allowlist failed")?;
+    tracing::info!(
+        channel = %request.channel,
+        sender = %request.sender,
+        approver,
+        "Sender paired"
+    );
+    Ok(request)
+}
+
+/// Store a new code for `sender`, or `None` if they still hold a valid one.
+pub fn issue(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    reply_target: &str,
+    ttl_secs: u64,
+) -> Result<Option<PairingRequest>> {
+    with_connection(workspace_dir, |conn| {
+        let now = Utc::now();
+        let outstanding: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM pairings
+                 WHERE channel = ?1 AND sender = ?2 AND status = 'pending' AND expires_at > ?3",
+                params![channel, sender, stamp(now)],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to look up pairing request")?;
+        if outstanding.is_some() {
+            return Ok(None);
+        }
+
+        let code = loop {
+            let code = crate::security::pairing::generate_code();
+            if find_pending_in(conn, &code)?.is_none() {
+                break code;
+            }
+        };
+        let ttl = ttl_secs.min(MAX_CODE_TTL_SECS);
+        let expires_at = now + chrono::Duration::seconds(i64::try_from(ttl).unwrap_or(0));
+        conn.execute(
+            "INSERT INTO pairings (channel, sender, reply_target, code, status, created_at, expires_at)
+             VALUES (?1, ?2, ?3, ?4, 'pending', ?5, ?6)",
+            params![
+                channel,
+                sender,
+                reply_target,
+                code,
+                stamp(now),
+                stamp(expires_at),
+            ],
+        )
+        .context("Failed to store pairing request")?;
+        find_in(conn, conn.last_insert_rowid())
+    })
+}
+
+/// Mark the pending request holding `code` as approved.
+pub fn approve(workspace_dir: &Path, code: &str, approver: &str) -> Result<PairingRequest> {
+    let code = code.trim();
+    with_connection(workspace_dir, |conn| {
+        let Some(request) = find_pending_in(conn, code)? else {
+            bail!("No pairing request with code {code}");
+        };
+        if request.is_expired() {
+            bail!(
+                "Pairing code {code} has expired; the sender gets a new one on their next message"
+            );
+        }
+        conn.execute(
+            "UPDATE pairings SET status = 'approved', approver = ?2, decided_at = ?3 WHERE id = ?1",
+            params![request.id, approver, stamp(Utc::now())],
+        )
+        .context("Failed to approve pairing request")?;
+        find_in(conn, request.id)?.context("Pairing request disappeared")
+    })
+}
+
+/// Whether `sender` on `channel` was approved after `since`.
+fn paired_since(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    since: DateTime<Utc>,
+) -> Result<bool> {
+    with_connection(workspace_dir, |conn| {
+        let found: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM pairings
+                 WHERE channel = ?1 AND sender = ?2 AND status = 'approved' AND decided_at >= ?3
+                 LIMIT 1",
+                params![channel, sender, stamp(since)],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to look up pairing")?;
+        Ok(found.is_some())
+    })
+}
+
+/// Newest requests first.
+pub fn list(workspace_dir: &Path, limit: usize) -> Result<Vec<PairingRequest>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(&format!("{SELECT} ORDER BY id DESC LIMIT ?1"))?;
+        let rows = stmt.query_map(
+            params![i64::try_from(limit).unwrap_or(i64::MAX)],
+            row_to_request,
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list pairing requests")
+    })
+}
+
+/// Reply to an unknown sender holding a new code.
+fn challenge_message(request: &PairingRequest) -> String {
+    let minutes = (request.expires_at - request.created_at)
+        .num_minutes()
+        .max(1);
+    format!(
+        "🔐 This bot only talks to approved senders. Your pairing code is {code} (valid for {minutes} min).\n\nAsk the operator to run `zeroclaw channel pair {code}`, or send `/pair {code}` from an account that can approve it.",
+        code = request.code
+    )
+}
+
+/// Notice posted to the notification chat for a new request.
+fn request_notice(request: &PairingRequest) -> String {
+    format!(
+        "🔐 Pairing request from {} sender {}. Reply /pair {} to approve it.",
+        request.channel, request.sender, request.code
+    )
+}
+
+/// `/pairings` reply listing requests that can still be confirmed.
+fn pending_list(workspace_dir: &Path) -> String {
+    let requests = match list(workspace_dir, CHAT_LIST_LIMIT) {
+        Ok(requests) => requests,
+        Err(e) => return format!("Pairing requests are unavailable: {e}"),
+    };
+    let mut out = String::new();
+    for request in requests
+        .iter()
+        .filter(|r| r.status == "pending" && !r.is_expired())
+    {
+        let _ = write!(
+            out,
+            "\n- {} {} / {}",
+            request.code, request.channel, request.sender
+        );
+    }
+    if out.is_empty() {
+        return "No pairing requests waiting.".into();
+    }
+    format!("Pairing requests waiting:{out}")
+}
+
+/// `zeroclaw channel pairings`: newest requests with their outcome.
+pub fn print_requests(config: &Config, limit: usize) -> Result<()> {
+    let requests = list(&config.workspace_dir, limit)?;
+    if requests.is_empty() {
+        println!("No pairing requests recorded.");
+        return Ok(());
+    }
+
+    println!("🔐 Pairing requests ({}):", requests.len());
+    for request in requests {
+        let status = if request.is_expired() {
+            "expired"
+        } else {
+            request.status.as_str()
+        };
+        let decision = match (&request.approver, request.decided_at) {
+            (Some(approver), Some(at)) => format!(" by {approver} at {}", at.to_rfc3339()),
+            _ => String::new(),
+        };
+        println!(
+            "- {} | {} | {} / {} | {status}{decision}",
+            request.code,
+            request.created_at.to_rfc3339(),
+            request.channel,
+            request.sender,
+        );
+    }
+    Ok(())
+}
+
+/// `zeroclaw channel pair <code>`: confirm a code and tell the sender.
+pub async fn pair_from_cli(config: &Config, code: &str) -> Result<()> {
+    let request = confirm(&config.workspace_dir, &config.config_path, code, "cli").await?;
+    println!("✅ Paired {} sender {}", request.channel, request.sender);
+    println!("   Saved to {}", config.config_path.display());
+    if let Err(e) = crate::cron::scheduler::deliver_announcement(
+        config,
+        &request.channel,
+        &request.reply_target,
+        PAIRED_MESSAGE,
+    )
+    .await
+    {
+        println!("ℹ️ Could not tell the sender they are paired: {e}");
+    }
+    Ok(())
+}
+
+const SELECT: &str =
+    "SELECT id, channel, sender, reply_target, code, status, approver, created_at, expires_at,
+            decided_at
+     FROM pairings";
+
+fn find_in(conn: &Connection, id: i64) -> Result<Option<PairingRequest>> {
+    conn.query_row(
+        &format!("{SELECT} WHERE id = ?1"),
+        params![id],
+        row_to_request,
+    )
+    .optional()
+    .context("Failed to read pairing request")
+}
+
+fn find_pending_in(conn: &Connection, code: &str) -> Result<Option<PairingRequest>> {
+    conn.query_row(
+        &format!("{SELECT} WHERE code = ?1 AND status = 'pending' ORDER BY id DESC LIMIT 1"),
+        params![code],
+        row_to_request,
+    )
+    .optional()
+    .context("Failed to read pairing request")
+}
+
+fn row_to_request(row: &rusqlite::Row<'_>) -> rusqlite::Result<PairingRequest> {
+    let created_at: String = row.get(7)?;
+    let expires_at: String = row.get(8)?;
+    let decided_at: Option<String> = row.get(9)?;
+    Ok(PairingRequest {
+        id: row.get(0)?,
+        channel: row.get(1)?,
+        sender: row.get(2)?,
+        reply_target: row.get(3)?,
+        code: row.get(4)?,
+        status: row.get(5)?,
+        approver: row.get(6)?,
+        created_at: parse_timestamp(&created_at),
+        expires_at: parse_timestamp(&expires_at),
+        decided_at: decided_at.as_deref().map(parse_timestamp),
+    })
+}
+
+/// Fixed-width UTC timestamps, so SQL compares them in time order.
+fn stamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("pairing.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open pairing DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS pairings (
+            id           INTEGER PRIMARY KEY AUTOINCREMENT,
+            channel      TEXT NOT NULL,
+            sender       TEXT NOT NULL,
+            reply_target TEXT NOT NULL,
+            code         TEXT NOT NULL,
+            status       TEXT NOT NULL,
+            approver     TEXT,
+            created_at   TEXT NOT NULL,
+            expires_at   TEXT NOT NULL,
+            decided_at   TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_pairings_code ON pairings(code, status);
+        CREATE INDEX IF NOT EXISTS idx_pairings_sender ON pairings(channel, sender);",
+    )
+    .context("Failed to initialize pairing schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn commands_parse_codes() {
+        assert_eq!(parse_command("/pairings"), Some(PairingCommand::List));
+        assert_eq!(
+            parse_command("/pair@zc_bot 123456"),
+            Some(PairingCommand::Pair("123456".into()))
+        );
+        assert_eq!(parse_command("/pair"), None);
+        assert_eq!(parse_command("pair 123456"), None);
+    }
+
+    #[test]
+    fn codes_are_issued_once_and_approved_once() {
+        let tmp = TempDir::new().unwrap();
+        let started = Utc::now();
+        let request = issue(tmp.path(), "discord", "42", "chan-1", 600)
+            .unwrap()
+            .unwrap();
+        assert_eq!(request.code.len(), 6);
+        assert!(challenge_message(&request).contains("valid for 10 min"));
+        assert!(issue(tmp.path(), "discord", "42", "chan-1", 600)
+            .unwrap()
+            .is_none());
+        assert!(pending_list(tmp.path()).contains(&request.code));
+        assert!(!paired_since(tmp.path(), "discord", "42", started).unwrap());
+
+        assert!(approve(tmp.path(), "000000x", "ops").is_err());
+        let approved = approve(tmp.path(), &request.code, "telegram:ops").unwrap();
+        assert_eq!(approved.status, "approved");
+        assert_eq!(approved.approver.as_deref(), Some("telegram:ops"));
+        assert!(approve(tmp.path(), &request.code, "ops").is_err());
+        assert!(paired_since(tmp.path(), "discord", "42", started).unwrap());
+        assert!(!paired_since(tmp.path(), "slack", "42", started).unwrap());
+        assert_eq!(pending_list(tmp.path()), "No pairing requests waiting.");
+    }
+
+    #[test]
+    fn expired_codes_are_replaced_and_rejected() {
+        let tmp = TempDir::new().unwrap();
+        let stale = issue(tmp.path(), "slack", "U1", "C1", 0).unwrap().unwrap();
+        assert!(stale.is_expired());
+        assert!(approve(tmp.path(), &stale.code, "cli")
+            .unwrap_err()
+            .to_string()
+            .contains("expired"));
+        let fresh = issue(tmp.path(), "slack", "U1", "C1", 600)
+            .unwrap()
+            .unwrap();
+        assert!(!fresh.is_expired());
+        assert_eq!(list(tmp.path(), 10).unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn confirmed_senders_are_saved_to_the_allowlist() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            "default_temperature = 0.7\n[channels_config]\ncli = true\n[channels_config.discord]\nbot_token = \"t\"\nallowed_users = [\"1\"]\n",
+        )
+        .unwrap();
+        let request = issue(tmp.path(), "discord", "42", "chan-1", 600)
+            .unwrap()
+            .unwrap();
+        confirm(tmp.path(), &config_path, &request.code, "cli")
+            .await
+            .unwrap();
+
+        let saved: Config =
+            toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let discord = saved.channels_config.discord.unwrap();
+        assert_eq!(discord.allowed_users, ["1", "42"]);
+
+        let request = issue(tmp.path(), "slack", "U1", "C1", 600)
+            .unwrap()
+            .unwrap();
+        assert!(confirm(tmp.path(), &config_path, &request.code, "cli")
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Per-identity user preferences (tone, verbosity, units, language,
+//! formatting).
+//!
+//! Preferences are keyed by channel and sender and live in
+//! `<workspace>/channels/preferences.db`. They are set through the
+//! `preferences` tool or the `/prefs` chat command and appended to the system
+//! prompt of every turn for that identity.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Longest accepted free-text preference value.
+const MAX_VALUE_CHARS: usize = 100;
+
+/// Supported preference keys with the values they accept. An empty value
+/// list means free text.
+pub const KEYS: &[(&str, &[&str], &str)] = &[
+    ("tone", &[], "e.g. casual, formal, playful"),
+    ("verbosity", &["brief", "normal", "detailed"], ""),
+    ("units", &["metric", "imperial"], ""),
+    (
+        "language",
+        &[],
+        "language to reply in, e.g. English, German",
+    ),
+    ("formatting", &[], "e.g. plain text, markdown, no tables"),
+];
+
+/// Normalize a key/value pair, or explain what is accepted.
+pub fn normalize(key: &str, value: &str) -> Result<(&'static str, String), String> {
+    let key = key.trim().to_ascii_lowercase();
+    let Some((name, allowed, _)) = KEYS.iter().find(|(name, _, _)| *name == key) else {
+        return Err(format!(
+            "Unknown preference '{key}'. Known preferences: {}",
+            KEYS.iter()
+                .map(|(name, _, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    };
+    let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if value.is_empty() {
+        return Err(format!("A value is required for '{name}'"));
+    }
+    if allowed.is_empty() {
+        if value.chars().count() > MAX_VALUE_CHARS {
+            return Err(format!(
+                "'{name}' must be at most {MAX_VALUE_CHARS} characters"
+            ));
+        }
+        return Ok((name, value));
+    }
+    let lowered = value.to_ascii_lowercase();
+    allowed
+        .iter()
+        .find(|option| **option == lowered)
+        .map(|option| (*name, (*option).to_string()))
+        .ok_or_else(|| format!("'{name}' must be one of: {}", allowed.join(", ")))
+}
+
+/// All preferences stored for `sender` on `channel`.
+pub fn get_all(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+) -> Result<BTreeMap<String, String>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt =
+            conn.prepare("SELECT key, value FROM preferences WHERE channel = ?1 AND sender = ?2")?;
+        let rows = stmt.query_map(params![channel, sender], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    })
+}
+
+/// Validate and store one preference. Returns the stored key and value.
+pub fn set(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    key: &str,
+    value: &str,
+) -> Result<(&'static str, String)> {
+    let (key, value) = normalize(key, value).map_err(anyhow::Error::msg)?;
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO preferences (channel, sender, key, value, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(channel, sender, key)
+             DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![channel, sender, key, value, Utc::now().to_rfc3339()],
+        )
+        .context("Failed to store preference")?;
+        Ok(())
+    })?;
+    Ok((key, value))
+}
+
+/// Remove one preference, or all of them when `key` is `None`. Returns the
+/// number removed.
+pub fn clear(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    key: Option<&str>,
+) -> Result<usize> {
+    with_connection(workspace_dir, |conn| {
+        let removed = conn
+            .execute(
+                "DELETE FROM preferences
+                 WHERE channel = ?1 AND sender = ?2 AND (?3 IS NULL OR key = ?3)",
+                params![
+                    channel,
+                    sender,
+                    key.map(|key| key.trim().to_ascii_lowercase())
+                ],
+            )
+            .context("Failed to clear preferences")?;
+        Ok(removed)
+    })
+}
+
+/// System prompt section for stored preferences; empty when none are set.
+pub fn prompt_section(preferences: &BTreeMap<String, String>) -> String {
+    if preferences.is_empty() {
+        return String::new();
+    }
+    let mut section = String::from(
+        "## User Preferences\n\nThe user has set these preferences. Follow them without \
+         asking again unless the user overrides them in this message.\n",
+    );
+    for (key, value) in preferences {
+        let _ = write!(section, "\n- {key}: {value}");
+    }
+    section
+}
+
+/// Human-readable listing used by `/prefs` and the `preferences` tool.
+pub fn describe(preferences: &BTreeMap<String, String>) -> String {
+    let mut text = if preferences.is_empty() {
+        String::from("No preferences set.")
+    } else {
+        let mut text = String::from("Your preferences:");
+        for (key, value) in preferences {
+            let _ = write!(text, "\n- {key}: {value}");
+        }
+        text
+    };
+    text.push_str("\n\nAvailable:");
+    for (name, allowed, hint) in KEYS {
+        if allowed.is_empty() {
+            let _ = write!(text, "\n- {name} ({hint})");
+        } else {
+            let _ = write!(text, "\n- {name}: {}", allowed.join(" | "));
+        }
+    }
+    text
+}
+
+/// Reply to a `/prefs` chat command: `/prefs` lists, `/prefs <key> <value>`
+/// sets, and `/prefs clear [key]` removes.
+pub fn command_response(workspace_dir: &Path, channel: &str, sender: &str, args: &str) -> String {
+    let args = args.trim();
+    let (first, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    let result = match first.to_ascii_lowercase().as_str() {
+        "" | "show" | "list" => {
+            get_all(workspace_dir, channel, sender).map(|prefs| describe(&prefs))
+        }
+        "clear" | "reset" => {
+            let key = Some(rest.trim()).filter(|key| !key.is_empty());
+            clear(workspace_dir, channel, sender, key).map(|removed| match (key, removed) {
+                (Some(key), 0) => format!("No `{key}` preference set."),
+                (Some(key), _) => format!("Cleared `{key}`."),
+                (None, count) => format!("Cleared {count} preference(s)."),
+            })
+        }
+        key => match normalize(key, rest) {
+            Ok(_) => set(workspace_dir, channel, sender, key, rest)
+                .map(|(key, value)| format!("Saved `{key}` = `{value}`.")),
+            Err(e) => {
+                return format!(
+                    "{e}\nUsage: `/prefs`, `/prefs <key> <value>`, `/prefs clear [key]`."
+                )
+            }
+        },
+    };
+    result.unwrap_or_else(|e| format!("Preferences are unavailable: {e}"))
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("preferences.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open preferences DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS preferences (
+            channel    TEXT NOT NULL,
+            sender     TEXT NOT NULL,
+            key        TEXT NOT NULL,
+            value      TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (channel, sender, key)
+        );",
+    )
+    .context("Failed to initialize preferences schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_normalized_and_validated() {
+        assert_eq!(
+            normalize("Units", " Metric ").unwrap(),
+            ("units", "metric".into())
+        );
+        assert_eq!(
+            normalize("tone", "  dry   and witty ").unwrap(),
+            ("tone", "dry and witty".into())
+        );
+        assert!(normalize("units", "furlongs")
+            .unwrap_err()
+            .contains("metric, imperial"));
+        assert!(normalize("color", "blue")
+            .unwrap_err()
+            .starts_with("Unknown preference 'color'"));
+        assert!(normalize("language", " ").is_err());
+    }
+
+    #[test]
+    fn preferences_are_stored_per_identity() {
+        let tmp = tempfile::tempdir().unwrap();
+        set(tmp.path(), "telegram", "alice", "units", "imperial").unwrap();
+        set(tmp.path(), "telegram", "alice", "units", "metric").unwrap();
+        set(tmp.path(), "telegram", "alice", "verbosity", "brief").unwrap();
+        set(tmp.path(), "discord", "alice", "tone", "formal").unwrap();
+
+        let prefs = get_all(tmp.path(), "telegram", "alice").unwrap();
+        assert_eq!(prefs.len(), 2);
+        assert_eq!(prefs["units"], "metric");
+        assert_eq!(
+            prompt_section(&prefs),
+            "## User Preferences\n\nThe user has set these preferences. Follow them without \
+             asking again unless the user overrides them in this message.\n\n- units: metric\n- verbosity: brief"
+        );
+        assert!(get_all(tmp.path(), "telegram", "bob").unwrap().is_empty());
+
+        assert_eq!(
+            clear(tmp.path(), "telegram", "alice", Some("units")).unwrap(),
+            1
+        );
+        assert_eq!(clear(tmp.path(), "telegram", "alice", None).unwrap(), 1);
+        assert_eq!(get_all(tmp.path(), "discord", "alice").unwrap().len(), 1);
+        assert_eq!(prompt_section(&BTreeMap::new()), "");
+    }
+
+    #[test]
+    fn prefs_command_sets_lists_and_clears() {
+        let tmp = tempfile::tempdir().unwrap();
+        let reply = |args: &str| command_response(tmp.path(), "slack", "u1", args);
+
+        assert_eq!(reply("units metric"), "Saved `units` = `metric`.");
+        assert_eq!(
+            reply("tone warm but concise"),
+            "Saved `tone` = `warm but concise`."
+        );
+        assert!(
+            reply("").starts_with("Your preferences:\n- tone: warm but concise\n- units: metric")
+        );
+        assert!(reply("verbosity chatty")
+            .starts_with("'verbosity' must be one of: brief, normal, detailed\nUsage:"));
+        assert_eq!(reply("clear units"), "Cleared `units`.");
+        assert_eq!(reply("clear"), "Cleared 1 preference(s).");
+    }
+}
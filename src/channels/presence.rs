@@ -0,0 +1,176 @@
+//! Bot presence on chat platforms (`[channels_config.presence]`).
+//!
+//! The job queue reports each agent run through [`begin`]; the status shown on
+//! channels that support one (Discord custom status, Slack profile status)
+//! follows: idle, working on a task for the latest sender, or a fixed
+//! maintenance or static text from config. Updates are coalesced and sent at
+//! most every [`MIN_UPDATE_INTERVAL`] to stay inside platform rate limits.
+
+use super::traits::Channel;
+use crate::config::ChannelPresenceConfig;
+use parking_lot::Mutex;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Channels that can show a bot status.
+pub const SUPPORTED_CHANNELS: &[&str] = &["discord", "slack"];
+
+/// Discord allows 5 presence updates a minute.
+const MIN_UPDATE_INTERVAL: Duration = Duration::from_secs(12);
+
+struct PresenceState {
+    config: ChannelPresenceConfig,
+    /// Senders of runs in progress, oldest first.
+    running: Vec<String>,
+    status: watch::Sender<String>,
+}
+
+static STATE: LazyLock<Mutex<Option<PresenceState>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Called by `start_channels` with the running channels.
+pub(super) fn register(config: &ChannelPresenceConfig, channels: Vec<Arc<dyn Channel>>) {
+    if !config.enabled {
+        return;
+    }
+    let channels: Vec<_> = channels
+        .into_iter()
+        .filter(|ch| ch.supports_presence())
+        .filter(|ch| config.channels.is_empty() || config.channels.iter().any(|c| c == ch.name()))
+        .collect();
+    if channels.is_empty() {
+        tracing::warn!("Presence is enabled but no running channel can show a status");
+        return;
+    }
+
+    let (status, rx) = watch::channel(status_text(config, &[]));
+    *STATE.lock() = Some(PresenceState {
+        config: config.clone(),
+        running: Vec::new(),
+        status,
+    });
+    tokio::spawn(publish(rx, channels));
+}
+
+/// A run counted in the bot's status; leaves it on drop.
+pub(crate) struct Activity {
+    sender: Option<String>,
+}
+
+/// Count a run for `sender` in the status until the returned guard drops.
+pub(crate) fn begin(sender: &str) -> Activity {
+    let mut state = STATE.lock();
+    let Some(state) = state.as_mut() else {
+        return Activity { sender: None };
+    };
+    state.running.push(sender.to_string());
+    refresh(state);
+    Activity {
+        sender: Some(sender.to_string()),
+    }
+}
+
+impl Drop for Activity {
+    fn drop(&mut self) {
+        let Some(sender) = self.sender.take() else {
+            return;
+        };
+        let mut state = STATE.lock();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+        if let Some(pos) = state.running.iter().position(|s| *s == sender) {
+            state.running.remove(pos);
+        }
+        refresh(state);
+    }
+}
+
+fn refresh(state: &PresenceState) {
+    let next = status_text(&state.config, &state.running);
+    state.status.send_if_modified(|current| {
+        if *current == next {
+            return false;
+        }
+        *current = next;
+        true
+    });
+}
+
+/// Status for the given runs in progress.
+fn status_text(config: &ChannelPresenceConfig, running: &[String]) -> String {
+    if config.maintenance {
+        return config.maintenance_status.clone();
+    }
+    if !config.status.trim().is_empty() {
+        return config.status.clone();
+    }
+    match running {
+        [] => config.idle.clone(),
+        [.., latest] => {
+            let text = config.working.replace("{sender}", latest);
+            match running.len() - 1 {
+                0 => text,
+                others => format!("{text} (+{others} more)"),
+            }
+        }
+    }
+}
+
+async fn publish(mut rx: watch::Receiver<String>, channels: Vec<Arc<dyn Channel>>) {
+    loop {
+        let status = rx.borrow_and_update().clone();
+        for channel in &channels {
+            if let Err(e) = channel.set_presence(&status).await {
+                tracing::warn!("Failed to set presence on {}: {e}", channel.name());
+            }
+        }
+        tokio::time::sleep(MIN_UPDATE_INTERVAL).await;
+        if rx.changed().await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(senders: &[&str]) -> Vec<String> {
+        senders.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn status_follows_runs_in_progress() {
+        let config = ChannelPresenceConfig::default();
+        assert_eq!(status_text(&config, &[]), "idle");
+        assert_eq!(
+            status_text(&config, &running(&["alice"])),
+            "working on a task for alice"
+        );
+        assert_eq!(
+            status_text(&config, &running(&["alice", "bob", "carol"])),
+            "working on a task for carol (+2 more)"
+        );
+    }
+
+    #[test]
+    fn maintenance_and_static_status_override_runs() {
+        let mut config = ChannelPresenceConfig {
+            status: "ask me anything".into(),
+            ..ChannelPresenceConfig::default()
+        };
+        assert_eq!(
+            status_text(&config, &running(&["alice"])),
+            "ask me anything"
+        );
+        config.maintenance = true;
+        assert_eq!(status_text(&config, &[]), "maintenance");
+    }
+
+    #[test]
+    fn activity_is_inert_without_registration() {
+        let activity = begin("alice");
+        assert!(activity.sender.is_none());
+    }
+}
@@ -0,0 +1,167 @@
+//! Word-level profanity masking for family-safe channels
+//! (`[channels_config.audiences.<channel>]`).
+//!
+//! Entries match whole words case-insensitively; a trailing `*` matches by
+//! prefix (`fuck*` covers "fucking"). Matches keep their first letter and
+//! the rest becomes `*`.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Built-in list used when `family_safe = true`. Prefix entries are kept to
+/// stems that do not start ordinary words.
+pub const WORDLIST: &[&str] = &[
+    "arse",
+    "arsehole*",
+    "ass",
+    "asses",
+    "asshat*",
+    "asshole*",
+    "bastard*",
+    "bitch*",
+    "bollock*",
+    "bullshit*",
+    "cock",
+    "cocks",
+    "cocksucker*",
+    "crap",
+    "crappy",
+    "cunt*",
+    "damn",
+    "dammit",
+    "dick",
+    "dickhead*",
+    "dicks",
+    "dildo*",
+    "douchebag*",
+    "dumbass*",
+    "fag",
+    "faggot*",
+    "fags",
+    "fuck*",
+    "goddamn*",
+    "horseshit",
+    "jackass*",
+    "motherfuck*",
+    "nigga*",
+    "nigger*",
+    "piss",
+    "pissed",
+    "pissing",
+    "prick",
+    "pricks",
+    "pussies",
+    "pussy",
+    "retard",
+    "retarded",
+    "shit*",
+    "shite",
+    "slut*",
+    "twat*",
+    "wank*",
+    "whore*",
+];
+
+static WORD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\p{L}\p{N}]+").expect("valid word regex"));
+
+/// A compiled word list.
+#[derive(Debug, Clone, Default)]
+pub struct ProfanityFilter {
+    exact: Vec<String>,
+    prefixes: Vec<String>,
+}
+
+impl ProfanityFilter {
+    /// Build a filter from `words`, skipping any entry listed in `allowed`.
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a str>, allowed: &[String]) -> Self {
+        let mut filter = Self::default();
+        for word in words {
+            let word = word.trim().to_lowercase();
+            if word.is_empty()
+                || allowed
+                    .iter()
+                    .any(|allowed| allowed.trim().eq_ignore_ascii_case(&word))
+            {
+                continue;
+            }
+            match word.strip_suffix('*') {
+                Some(prefix) if !prefix.is_empty() => filter.prefixes.push(prefix.to_string()),
+                Some(_) => {}
+                None => filter.exact.push(word),
+            }
+        }
+        filter
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exact.is_empty() && self.prefixes.is_empty()
+    }
+
+    fn matches(&self, word: &str, allowed: &[String]) -> bool {
+        let lowered = word.to_lowercase();
+        if allowed
+            .iter()
+            .any(|allowed| allowed.trim().eq_ignore_ascii_case(&lowered))
+        {
+            return false;
+        }
+        self.exact.contains(&lowered)
+            || self
+                .prefixes
+                .iter()
+                .any(|prefix| lowered.starts_with(prefix.as_str()))
+    }
+
+    /// Mask listed words in `text`. Words in `allowed` are left alone even
+    /// when a prefix entry covers them.
+    pub fn mask(&self, text: &str, allowed: &[String]) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+        let mut masked = String::with_capacity(text.len());
+        let mut last = 0;
+        for word in WORD_RE.find_iter(text) {
+            if !self.matches(word.as_str(), allowed) {
+                continue;
+            }
+            masked.push_str(&text[last..word.start()]);
+            let mut chars = word.as_str().chars();
+            if let Some(first) = chars.next() {
+                masked.push(first);
+            }
+            masked.extend(chars.map(|_| '*'));
+            last = word.end();
+        }
+        masked.push_str(&text[last..]);
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_whole_words_and_prefix_entries() {
+        let filter = ProfanityFilter::new(WORDLIST.iter().copied(), &[]);
+        assert_eq!(
+            filter.mask("What the FUCKING hell, this is crap.", &[]),
+            "What the F****** hell, this is c***."
+        );
+        assert_eq!(
+            filter.mask("Assess the cockpit in Scunthorpe.", &[]),
+            "Assess the cockpit in Scunthorpe."
+        );
+    }
+
+    #[test]
+    fn extra_and_allowed_words_adjust_the_list() {
+        let allowed = vec!["damn".to_string(), "shitake".to_string()];
+        let filter = ProfanityFilter::new(WORDLIST.iter().copied().chain(["frak*"]), &allowed);
+        assert_eq!(
+            filter.mask("Damn, frakking shitake shit.", &allowed),
+            "Damn, f******* shitake s***."
+        );
+    }
+}
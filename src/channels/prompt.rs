@@ -0,0 +1,185 @@
+//! Channel system prompts that follow skill changes while channels run.
+//!
+//! Each prompt is built from a [`PromptSource`] and stored in a
+//! [`SharedPrompt`]. When a background open-skills sync lands,
+//! [`spawn_skills_reload`] reloads skills from disk and rebuilds every prompt
+//! in place, so new messages use the new skills without a restart.
+
+use super::{agents, build_configured_system_prompt};
+use crate::agent::loop_::build_tool_instructions;
+use crate::config::Config;
+use crate::skills::Skill;
+use crate::tools::Tool;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+/// A system prompt shared by all message handlers; replaced in place on reload.
+#[derive(Clone)]
+pub(super) struct SharedPrompt(Arc<RwLock<Arc<String>>>);
+
+impl SharedPrompt {
+    pub fn new(prompt: String) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(prompt))))
+    }
+
+    /// The prompt as of now; later replacements do not affect the result.
+    pub fn current(&self) -> Arc<String> {
+        Arc::clone(&self.0.read())
+    }
+
+    fn replace(&self, prompt: String) {
+        *self.0.write() = Arc::new(prompt);
+    }
+}
+
+/// Inputs for one channel system prompt: the default agent or a named one.
+pub(super) struct PromptSource {
+    pub prompt: SharedPrompt,
+    model: String,
+    tool_descs: Vec<(&'static str, &'static str)>,
+    tools_registry: Arc<Vec<Box<dyn Tool>>>,
+    native_tools: bool,
+    /// Skill names the agent may use; `None` offers all of them.
+    skill_names: Option<Vec<String>>,
+    /// Appended as an `## Agent Role` section.
+    role: Option<String>,
+}
+
+impl PromptSource {
+    pub fn new(
+        model: String,
+        tool_descs: Vec<(&'static str, &'static str)>,
+        tools_registry: Arc<Vec<Box<dyn Tool>>>,
+        native_tools: bool,
+    ) -> Self {
+        Self {
+            prompt: SharedPrompt::new(String::new()),
+            model,
+            tool_descs,
+            tools_registry,
+            native_tools,
+            skill_names: None,
+            role: None,
+        }
+    }
+
+    /// Restrict the prompt to `skill_names` and add an agent role.
+    pub fn for_agent(mut self, skill_names: Option<Vec<String>>, role: Option<String>) -> Self {
+        self.skill_names = skill_names;
+        self.role = role;
+        self
+    }
+
+    /// Render the prompt from `skills` now.
+    pub fn rendered(self, config: &Config, skills: &[Skill]) -> Self {
+        self.prompt.replace(self.render(config, skills));
+        self
+    }
+
+    fn render(&self, config: &Config, skills: &[Skill]) -> String {
+        let skills = agents::select_skills(skills, self.skill_names.as_deref());
+        let mut prompt = build_configured_system_prompt(
+            config,
+            &self.model,
+            &self.tool_descs,
+            &skills,
+            self.native_tools,
+        );
+        if !self.native_tools {
+            prompt.push_str(&build_tool_instructions(&self.tools_registry));
+        }
+        if let Some(role) = self.role.as_deref() {
+            prompt.push_str("\n## Agent Role\n\n");
+            prompt.push_str(role.trim());
+            prompt.push('\n');
+        }
+        prompt
+    }
+}
+
+/// Rebuild every prompt in `sources` each time `refreshed` changes.
+pub(super) fn spawn_skills_reload(
+    mut refreshed: tokio::sync::watch::Receiver<u64>,
+    config: Config,
+    sources: Vec<PromptSource>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let config = Arc::new(config);
+        let sources = Arc::new(sources);
+        while refreshed.changed().await.is_ok() {
+            let (config, sources) = (Arc::clone(&config), Arc::clone(&sources));
+            let reloaded = tokio::task::spawn_blocking(move || {
+                let skills = crate::skills::load_prompt_skills(&config.workspace_dir, &config);
+                for source in sources.iter() {
+                    source.prompt.replace(source.render(&config, &skills));
+                }
+                skills.len()
+            })
+            .await;
+            match reloaded {
+                Ok(count) => tracing::info!("open-skills updated; reloaded {count} skills"),
+                Err(e) => tracing::error!("Skill reload task crashed: {e}"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::Duration;
+
+    fn write_skill(workspace: &std::path::Path, name: &str) {
+        let dir = workspace.join("skills").join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("# {name}\nDescribes the {name} skill.\n"),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn prompts_pick_up_new_skills_when_notified() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        write_skill(tmp.path(), "first_skill");
+
+        let skills = crate::skills::load_prompt_skills(tmp.path(), &config);
+        let source = || PromptSource::new("model".to_string(), vec![], Arc::new(Vec::new()), true);
+        let default_source = source().rendered(&config, &skills);
+        let agent_source = source()
+            .for_agent(
+                Some(vec!["first_skill".to_string()]),
+                Some("Be terse.".to_string()),
+            )
+            .rendered(&config, &skills);
+        let (default_prompt, agent_prompt) =
+            (default_source.prompt.clone(), agent_source.prompt.clone());
+        assert!(default_prompt.current().contains("first_skill"));
+        assert!(!default_prompt.current().contains("second_skill"));
+
+        let (tx, rx) = tokio::sync::watch::channel(0);
+        let reload = spawn_skills_reload(rx, config.clone(), vec![default_source, agent_source]);
+        write_skill(tmp.path(), "second_skill");
+        tx.send_modify(|generation| *generation += 1);
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while !default_prompt.current().contains("second_skill") {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "prompt never reloaded"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let agent = agent_prompt.current();
+        assert!(agent.contains("first_skill"));
+        assert!(!agent.contains("second_skill"));
+        assert!(agent.contains("## Agent Role"));
+
+        drop(tx);
+        reload.await.unwrap();
+    }
+}
@@ -461,6 +461,8 @@ impl Channel for QQChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                location: None,
+                                payload: None,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -499,6 +501,8 @@ impl Channel for QQChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                location: None,
+                                payload: None,
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -0,0 +1,320 @@
+//! Admin review queue for inbound messages flagged as spam or abuse, and the
+//! per-sender strike counts that drive the spam filter's rate limits.
+//!
+//! Both live in `<workspace>/channels/quarantine.db`, so `zeroclaw quarantine`
+//! can review messages and pardon senders while the channel server runs.
+
+use crate::config::Config;
+use crate::memory::encryption::{open_text, seal_text};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+use uuid::Uuid;
+
+/// A quarantined inbound message awaiting review.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuarantineEntry {
+    pub id: String,
+    pub channel: String,
+    pub sender: String,
+    pub content: String,
+    pub score: f64,
+    /// Comma-separated signals that contributed to the score.
+    pub reasons: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Store a flagged message for review. Returns its id.
+pub fn quarantine(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    content: &str,
+    score: f64,
+    reasons: &[String],
+) -> Result<String> {
+    let id = Uuid::new_v4().to_string();
+    let content = seal_text(workspace_dir, content)?;
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO quarantine (id, channel, sender, content, score, reasons, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                id,
+                channel,
+                sender,
+                content,
+                score,
+                reasons.join(", "),
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .context("Failed to quarantine message")?;
+        Ok(())
+    })?;
+    Ok(id)
+}
+
+/// Most recent quarantined messages, newest first.
+pub fn list(
+    workspace_dir: &Path,
+    channel: Option<&str>,
+    limit: usize,
+) -> Result<Vec<QuarantineEntry>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, channel, sender, content, score, reasons, created_at
+             FROM quarantine
+             WHERE ?1 IS NULL OR channel = ?1
+             ORDER BY created_at DESC, rowid DESC
+             LIMIT ?2",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = stmt.query_map(params![channel, limit], |row| {
+            Ok(QuarantineEntry {
+                id: row.get(0)?,
+                channel: row.get(1)?,
+                sender: row.get(2)?,
+                content: row.get(3)?,
+                score: row.get(4)?,
+                reasons: row.get(5)?,
+                created_at: parse_timestamp(&row.get::<_, String>(6)?),
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })?
+    .into_iter()
+    .map(|mut entry: QuarantineEntry| {
+        entry.content = open_text(workspace_dir, &entry.content)?;
+        Ok(entry)
+    })
+    .collect()
+}
+
+/// Remove a reviewed message. Returns `false` when the id is unknown.
+pub fn dismiss(workspace_dir: &Path, id: &str) -> Result<bool> {
+    with_connection(workspace_dir, |conn| {
+        Ok(conn.execute("DELETE FROM quarantine WHERE id = ?1", params![id])? > 0)
+    })
+}
+
+/// A sender's stored strike count and when it last changed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Strikes {
+    pub count: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Strikes {
+    /// Strikes that still count; strikes older than `decay` have expired.
+    pub fn current(&self, decay: Duration) -> u32 {
+        if Utc::now() - self.updated_at < decay {
+            self.count
+        } else {
+            0
+        }
+    }
+}
+
+/// Every sender's strikes, keyed by channel and sender.
+pub fn all_strikes(workspace_dir: &Path) -> Result<HashMap<(String, String), Strikes>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare("SELECT channel, sender, count, updated_at FROM strikes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                (row.get(0)?, row.get(1)?),
+                Strikes {
+                    count: row.get(2)?,
+                    updated_at: parse_timestamp(&row.get::<_, String>(3)?),
+                },
+            ))
+        })?;
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(Into::into)
+    })
+}
+
+/// Add a strike to a sender and return the new count.
+pub fn add_strike(
+    workspace_dir: &Path,
+    channel: &str,
+    sender: &str,
+    decay: Duration,
+) -> Result<u32> {
+    with_connection(workspace_dir, |conn| {
+        let count = current_strikes(conn, channel, sender, decay)?.saturating_add(1);
+        conn.execute(
+            "INSERT INTO strikes (channel, sender, count, updated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(channel, sender) DO UPDATE SET count = ?3, updated_at = ?4",
+            params![channel, sender, count, Utc::now().to_rfc3339()],
+        )?;
+        Ok(count)
+    })
+}
+
+/// Clear a sender's strikes. Returns `false` when they had none.
+pub fn pardon(workspace_dir: &Path, channel: &str, sender: &str) -> Result<bool> {
+    with_connection(workspace_dir, |conn| {
+        Ok(conn.execute(
+            "DELETE FROM strikes WHERE channel = ?1 AND sender = ?2",
+            params![channel, sender],
+        )? > 0)
+    })
+}
+
+fn current_strikes(conn: &Connection, channel: &str, sender: &str, decay: Duration) -> Result<u32> {
+    let row: Option<Strikes> = conn
+        .query_row(
+            "SELECT count, updated_at FROM strikes WHERE channel = ?1 AND sender = ?2",
+            params![channel, sender],
+            |row| {
+                Ok(Strikes {
+                    count: row.get(0)?,
+                    updated_at: parse_timestamp(&row.get::<_, String>(1)?),
+                })
+            },
+        )
+        .optional()?;
+    Ok(row.map_or(0, |strikes| strikes.current(decay)))
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_command(command: crate::QuarantineCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::QuarantineCommands::List { channel, limit } => {
+            let entries = list(&config.workspace_dir, channel.as_deref(), limit)?;
+            if entries.is_empty() {
+                println!("No quarantined messages.");
+                return Ok(());
+            }
+
+            println!("🚫 Quarantined messages ({}):", entries.len());
+            for entry in entries {
+                println!(
+                    "- {} | {} | {} ← {} | score {:.2} ({})",
+                    entry.id,
+                    entry.created_at.to_rfc3339(),
+                    entry.channel,
+                    entry.sender,
+                    entry.score,
+                    entry.reasons,
+                );
+                println!(
+                    "    {}",
+                    crate::util::truncate_with_ellipsis(&entry.content, 200)
+                );
+            }
+            Ok(())
+        }
+        crate::QuarantineCommands::Dismiss { id } => {
+            if dismiss(&config.workspace_dir, &id)? {
+                println!("✅ Dismissed quarantined message {id}");
+            } else {
+                println!("No quarantined message with id {id}");
+            }
+            Ok(())
+        }
+        crate::QuarantineCommands::Pardon { channel, sender } => {
+            if pardon(&config.workspace_dir, &channel, &sender)? {
+                println!("✅ Cleared strikes for {sender} on {channel}");
+            } else {
+                println!("{sender} has no strikes on {channel}");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("quarantine.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open quarantine DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS quarantine (
+            id         TEXT PRIMARY KEY,
+            channel    TEXT NOT NULL,
+            sender     TEXT NOT NULL,
+            content    TEXT NOT NULL,
+            score      REAL NOT NULL,
+            reasons    TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_quarantine_created_at ON quarantine(created_at);
+        CREATE TABLE IF NOT EXISTS strikes (
+            channel    TEXT NOT NULL,
+            sender     TEXT NOT NULL,
+            count      INTEGER NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (channel, sender)
+        );",
+    )
+    .context("Failed to initialize quarantine schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quarantined_messages_can_be_listed_and_dismissed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let reasons = vec!["spam phrase".to_string(), "links".to_string()];
+        let id = quarantine(
+            tmp.path(),
+            "telegram",
+            "spammer",
+            "Free crypto!",
+            0.9,
+            &reasons,
+        )
+        .unwrap();
+        quarantine(tmp.path(), "discord", "troll", "...", 0.8, &[]).unwrap();
+
+        let entries = list(tmp.path(), Some("telegram"), 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].sender, "spammer");
+        assert_eq!(entries[0].reasons, "spam phrase, links");
+        assert_eq!(list(tmp.path(), None, 10).unwrap().len(), 2);
+
+        assert!(dismiss(tmp.path(), &id).unwrap());
+        assert!(!dismiss(tmp.path(), &id).unwrap());
+        assert!(list(tmp.path(), Some("telegram"), 10).unwrap().is_empty());
+    }
+
+    fn strikes(workspace_dir: &Path, channel: &str, sender: &str, decay: Duration) -> Result<u32> {
+        Ok(all_strikes(workspace_dir)?
+            .get(&(channel.to_string(), sender.to_string()))
+            .map_or(0, |strikes| strikes.current(decay)))
+    }
+
+    #[test]
+    fn strikes_accumulate_decay_and_can_be_pardoned() {
+        let tmp = tempfile::tempdir().unwrap();
+        let day = Duration::hours(24);
+        assert_eq!(strikes(tmp.path(), "telegram", "alice", day).unwrap(), 0);
+        assert_eq!(add_strike(tmp.path(), "telegram", "alice", day).unwrap(), 1);
+        assert_eq!(add_strike(tmp.path(), "telegram", "alice", day).unwrap(), 2);
+        assert_eq!(strikes(tmp.path(), "discord", "alice", day).unwrap(), 0);
+
+        // A zero decay window means every stored strike has expired.
+        assert_eq!(
+            strikes(tmp.path(), "telegram", "alice", Duration::zero()).unwrap(),
+            0
+        );
+
+        assert!(pardon(tmp.path(), "telegram", "alice").unwrap());
+        assert_eq!(strikes(tmp.path(), "telegram", "alice", day).unwrap(), 0);
+        assert!(!pardon(tmp.path(), "telegram", "alice").unwrap());
+    }
+}
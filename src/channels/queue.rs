@@ -0,0 +1,326 @@
+//! Bounded job queue for channel agent runs.
+//!
+//! Every inbound message becomes a job that must hold, in order, its
+//! conversation lock (one active run per conversation), a per-channel slot,
+//! and a global slot before the agent loop runs. Jobs that cannot start
+//! immediately either wait in the queue or are rejected, depending on
+//! [`QueueOverflow`]. Queue depth and active runs are reported through the
+//! observer as [`ObserverMetric::QueueDepth`] and [`ObserverMetric::ActiveSessions`],
+//! and each running job counts toward the bot's [`presence`](super::presence).
+
+use super::presence;
+use crate::config::{ChannelQueueConfig, QueueOverflow};
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedMutexGuard, OwnedSemaphorePermit, Semaphore};
+
+pub(crate) struct JobQueue {
+    global: Arc<Semaphore>,
+    config: ChannelQueueConfig,
+    channel_slots: Mutex<HashMap<String, Arc<Semaphore>>>,
+    conversation_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    waiting: AtomicUsize,
+    active: AtomicUsize,
+    observer: Arc<dyn Observer>,
+}
+
+/// Outcome of offering a job to the queue.
+pub(crate) enum Admission {
+    /// Capacity is available; the run can start now.
+    Ready(JobPermit),
+    /// The run must wait for capacity.
+    Queued(QueuedJob),
+    /// The queue is full (or overflow is `reject`); the message is declined.
+    Rejected,
+}
+
+/// A job waiting for capacity. Dropping it leaves the queue.
+pub(crate) struct QueuedJob {
+    queue: Arc<JobQueue>,
+    channel: String,
+    conversation: String,
+    sender: String,
+    position: usize,
+}
+
+/// Capacity held by a running job; released on drop.
+pub(crate) struct JobPermit {
+    queue: Arc<JobQueue>,
+    _global: OwnedSemaphorePermit,
+    _channel: Option<OwnedSemaphorePermit>,
+    _conversation: Option<OwnedMutexGuard<()>>,
+    _presence: presence::Activity,
+}
+
+impl JobQueue {
+    pub(crate) fn new(
+        config: &ChannelQueueConfig,
+        max_concurrent: usize,
+        observer: Arc<dyn Observer>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            global: Arc::new(Semaphore::new(max_concurrent.max(1))),
+            config: config.clone(),
+            channel_slots: Mutex::new(HashMap::new()),
+            conversation_locks: Mutex::new(HashMap::new()),
+            waiting: AtomicUsize::new(0),
+            active: AtomicUsize::new(0),
+            observer,
+        })
+    }
+
+    /// Offer a job from `sender` for `channel`/`conversation`, starting it
+    /// immediately when possible.
+    pub(crate) fn admit(
+        self: &Arc<Self>,
+        channel: &str,
+        conversation: &str,
+        sender: &str,
+    ) -> Admission {
+        if let Some(permit) = self.try_acquire(channel, conversation, sender) {
+            return Admission::Ready(permit);
+        }
+
+        let waiting = self.waiting.load(Ordering::SeqCst);
+        if self.config.overflow == QueueOverflow::Reject || waiting >= self.config.max_queued {
+            self.record_channel_event(channel, "rejected");
+            return Admission::Rejected;
+        }
+
+        let position = self.waiting.fetch_add(1, Ordering::SeqCst) + 1;
+        self.record_channel_event(channel, "queued");
+        self.record_metrics();
+        Admission::Queued(QueuedJob {
+            queue: Arc::clone(self),
+            channel: channel.to_string(),
+            conversation: conversation.to_string(),
+            sender: sender.to_string(),
+            position,
+        })
+    }
+
+    fn try_acquire(
+        self: &Arc<Self>,
+        channel: &str,
+        conversation: &str,
+        sender: &str,
+    ) -> Option<JobPermit> {
+        let conversation_guard = match self.conversation_lock(conversation) {
+            Some(lock) => Some(lock.try_lock_owned().ok()?),
+            None => None,
+        };
+        let channel_permit = match self.channel_slots(channel) {
+            Some(slots) => Some(slots.try_acquire_owned().ok()?),
+            None => None,
+        };
+        let global_permit = Arc::clone(&self.global).try_acquire_owned().ok()?;
+        Some(self.start(global_permit, channel_permit, conversation_guard, sender))
+    }
+
+    fn start(
+        self: &Arc<Self>,
+        global: OwnedSemaphorePermit,
+        channel: Option<OwnedSemaphorePermit>,
+        conversation: Option<OwnedMutexGuard<()>>,
+        sender: &str,
+    ) -> JobPermit {
+        self.active.fetch_add(1, Ordering::SeqCst);
+        self.record_metrics();
+        JobPermit {
+            queue: Arc::clone(self),
+            _global: global,
+            _channel: channel,
+            _conversation: conversation,
+            _presence: presence::begin(sender),
+        }
+    }
+
+    fn conversation_lock(&self, conversation: &str) -> Option<Arc<tokio::sync::Mutex<()>>> {
+        if !self.config.serialize_per_conversation {
+            return None;
+        }
+        let mut locks = self
+            .conversation_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        Some(Arc::clone(
+            locks.entry(conversation.to_string()).or_default(),
+        ))
+    }
+
+    fn channel_slots(&self, channel: &str) -> Option<Arc<Semaphore>> {
+        let limit = self
+            .config
+            .channel_limits
+            .get(channel)
+            .copied()
+            .or(self.config.max_concurrent_per_channel)?;
+        let mut slots = self.channel_slots.lock().unwrap_or_else(|e| e.into_inner());
+        Some(Arc::clone(
+            slots
+                .entry(channel.to_string())
+                .or_insert_with(|| Arc::new(Semaphore::new(limit.max(1)))),
+        ))
+    }
+
+    fn release(&self) {
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        self.record_metrics();
+    }
+
+    /// Drop conversation locks nobody holds or waits on.
+    fn prune_conversation_locks(&self) {
+        let mut locks = self
+            .conversation_locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+    }
+
+    fn record_channel_event(&self, channel: &str, direction: &str) {
+        self.observer.record_event(&ObserverEvent::ChannelMessage {
+            channel: channel.to_string(),
+            direction: direction.to_string(),
+        });
+    }
+
+    fn record_metrics(&self) {
+        self.observer.record_metric(&ObserverMetric::QueueDepth(
+            self.waiting.load(Ordering::SeqCst) as u64,
+        ));
+        self.observer.record_metric(&ObserverMetric::ActiveSessions(
+            self.active.load(Ordering::SeqCst) as u64,
+        ));
+    }
+
+    #[cfg(test)]
+    fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::SeqCst)
+    }
+}
+
+impl QueuedJob {
+    /// 1-based position in the queue at admission time.
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Wait until the job may run.
+    pub(crate) async fn wait(mut self) -> JobPermit {
+        let queue = Arc::clone(&self.queue);
+        let conversation = match queue.conversation_lock(&self.conversation) {
+            Some(lock) => Some(lock.lock_owned().await),
+            None => None,
+        };
+        let channel = match queue.channel_slots(&self.channel) {
+            Some(slots) => Some(
+                slots
+                    .acquire_owned()
+                    .await
+                    .expect("channel semaphore is never closed"),
+            ),
+            None => None,
+        };
+        let global = Arc::clone(&queue.global)
+            .acquire_owned()
+            .await
+            .expect("global semaphore is never closed");
+        // Leave the waiting count before counting the run as active.
+        let sender = std::mem::take(&mut self.sender);
+        drop(self);
+        queue.start(global, channel, conversation, &sender)
+    }
+}
+
+impl Drop for QueuedJob {
+    fn drop(&mut self) {
+        self.queue.waiting.fetch_sub(1, Ordering::SeqCst);
+        self.queue.record_metrics();
+    }
+}
+
+impl Drop for JobPermit {
+    fn drop(&mut self) {
+        self.queue.release();
+        // The conversation guard is still held here, so prune on the next release.
+        self.queue.prune_conversation_locks();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+
+    fn queue(config: ChannelQueueConfig, max_concurrent: usize) -> Arc<JobQueue> {
+        JobQueue::new(&config, max_concurrent, Arc::new(NoopObserver))
+    }
+
+    #[tokio::test]
+    async fn serializes_runs_within_a_conversation() {
+        let queue = queue(ChannelQueueConfig::default(), 4);
+
+        let Admission::Ready(first) = queue.admit("telegram", "alice", "alice") else {
+            panic!("first run should start immediately");
+        };
+        assert!(matches!(
+            queue.admit("telegram", "bob", "bob"),
+            Admission::Ready(_)
+        ));
+        let Admission::Queued(second) = queue.admit("telegram", "alice", "alice") else {
+            panic!("second run for the same conversation should queue");
+        };
+        assert_eq!(second.position(), 1);
+
+        drop(first);
+        let _permit = tokio::time::timeout(std::time::Duration::from_secs(1), second.wait())
+            .await
+            .expect("queued run starts once the conversation is free");
+        assert_eq!(queue.waiting(), 0);
+    }
+
+    #[tokio::test]
+    async fn per_channel_limit_and_reject_overflow() {
+        let config = ChannelQueueConfig {
+            channel_limits: HashMap::from([("discord".to_string(), 1)]),
+            overflow: QueueOverflow::Reject,
+            ..ChannelQueueConfig::default()
+        };
+        let queue = queue(config, 4);
+
+        let Admission::Ready(_held) = queue.admit("discord", "alice", "alice") else {
+            panic!("first discord run should start");
+        };
+        assert!(matches!(
+            queue.admit("discord", "bob", "bob"),
+            Admission::Rejected
+        ));
+        assert!(matches!(
+            queue.admit("slack", "bob", "bob"),
+            Admission::Ready(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_when_queue_is_full() {
+        let config = ChannelQueueConfig {
+            max_queued: 1,
+            ..ChannelQueueConfig::default()
+        };
+        let queue = queue(config, 1);
+
+        let Admission::Ready(_held) = queue.admit("telegram", "alice", "alice") else {
+            panic!("first run should start");
+        };
+        let Admission::Queued(_waiting) = queue.admit("telegram", "bob", "bob") else {
+            panic!("second run should queue");
+        };
+        assert!(matches!(
+            queue.admit("telegram", "carol", "carol"),
+            Admission::Rejected
+        ));
+    }
+}
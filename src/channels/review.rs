@@ -0,0 +1,481 @@
+//! Human review of replies before they are sent (`[channels_config.review]`).
+//!
+//! Replies on reviewed channels are stored in `<workspace>/channels/review.db`
+//! as pending drafts and posted to the review chat. A reviewer answers with
+//! `/approve <id>`, `/edit <id> <text>`, or `/reject <id> [reason]`; approved
+//! drafts are sent to the original conversation and logged in the outbox.
+//! Every decision is kept as an audit trail (`zeroclaw review list`).
+
+use super::traits::ChannelMessage;
+use crate::config::{Config, ReplyReviewConfig};
+use crate::memory::encryption::{open_text, seal_text};
+use crate::util::truncate_with_ellipsis;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Drafts listed by `/drafts`.
+const CHAT_LIST_LIMIT: usize = 10;
+/// Characters of each draft shown by `/drafts`.
+const CHAT_PREVIEW_CHARS: usize = 80;
+
+/// Where a held reply should go once approved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeldReply {
+    pub channel: String,
+    pub recipient: String,
+    pub thread_ts: Option<String>,
+    /// Message the reply answers, for channels that quote it.
+    pub in_reply_to_id: Option<String>,
+    pub sender: String,
+    pub content: String,
+}
+
+/// A stored draft and its review outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draft {
+    pub id: i64,
+    pub reply: HeldReply,
+    /// The agent's reply as generated.
+    pub original: String,
+    /// `pending`, `approved`, `edited`, or `rejected`.
+    pub status: String,
+    pub reviewer: Option<String>,
+    /// Rejection reason.
+    pub note: Option<String>,
+    pub outbox_id: Option<String>,
+    pub send_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub decided_at: Option<DateTime<Utc>>,
+}
+
+/// A review chat command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewCommand {
+    List,
+    Approve(i64),
+    Edit(i64, String),
+    Reject(i64, Option<String>),
+}
+
+/// Review settings for the channel runtime.
+pub struct ReplyReview {
+    config: ReplyReviewConfig,
+}
+
+impl ReplyReview {
+    /// `None` when review is disabled.
+    pub fn new(config: &ReplyReviewConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            config: config.clone(),
+        })
+    }
+
+    /// Whether replies on `channel` are held for review.
+    pub fn applies_to(&self, channel: &str) -> bool {
+        self.config.channels.iter().any(|c| c == channel)
+    }
+
+    pub fn admin_channel(&self) -> &str {
+        &self.config.admin_channel
+    }
+
+    pub fn admin_to(&self) -> &str {
+        &self.config.admin_to
+    }
+
+    /// Whether `msg` was sent in the review chat by someone allowed to review.
+    pub fn is_reviewer(&self, msg: &ChannelMessage) -> bool {
+        msg.channel == self.config.admin_channel
+            && msg.reply_target == self.config.admin_to
+            && (self.config.reviewers.is_empty()
+                || self.config.reviewers.iter().any(|r| r == &msg.sender))
+    }
+}
+
+/// Parse a review chat command, ignoring `@bot` suffixes.
+pub fn parse_command(content: &str) -> Option<ReviewCommand> {
+    let trimmed = content.trim();
+    let (head, rest) = trimmed
+        .split_once(char::is_whitespace)
+        .unwrap_or((trimmed, ""));
+    let command = head.split('@').next().unwrap_or(head).to_ascii_lowercase();
+    let rest = rest.trim();
+    let (id, text) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let id = id.trim_start_matches('#').parse::<i64>().ok();
+    let text = text.trim();
+    match command.as_str() {
+        "/drafts" => Some(ReviewCommand::List),
+        "/approve" => Some(ReviewCommand::Approve(id?)),
+        "/edit" if !text.is_empty() => Some(ReviewCommand::Edit(id?, text.to_string())),
+        "/reject" => Some(ReviewCommand::Reject(
+            id?,
+            (!text.is_empty()).then(|| text.to_string()),
+        )),
+        _ => None,
+    }
+}
+
+/// Store `reply` as a pending draft and return its id.
+pub fn submit(workspace_dir: &Path, reply: &HeldReply) -> Result<i64> {
+    let content = seal_text(workspace_dir, &reply.content)?;
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO drafts (channel, recipient, thread_ts, reply_to, sender, original,
+                                 content, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6, 'pending', ?7)",
+            params![
+                reply.channel,
+                reply.recipient,
+                reply.thread_ts,
+                reply.in_reply_to_id,
+                reply.sender,
+                content,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .context("Failed to store draft")?;
+        Ok(conn.last_insert_rowid())
+    })
+}
+
+/// Record a reviewer's decision on a pending draft and return the updated
+/// draft. `edit` replaces the text to send; `approve = false` rejects.
+pub fn decide(
+    workspace_dir: &Path,
+    id: i64,
+    approve: bool,
+    edit: Option<&str>,
+    note: Option<&str>,
+    reviewer: &str,
+) -> Result<Draft> {
+    let status = match (approve, edit) {
+        (false, _) => "rejected",
+        (true, Some(_)) => "edited",
+        (true, None) => "approved",
+    };
+    let edit = edit
+        .map(|text| seal_text(workspace_dir, text))
+        .transpose()?;
+    let draft = with_connection(workspace_dir, |conn| {
+        let updated = conn
+            .execute(
+                "UPDATE drafts
+                 SET status = ?2, content = COALESCE(?3, content), note = ?4, reviewer = ?5,
+                     decided_at = ?6
+                 WHERE id = ?1 AND status = 'pending'",
+                params![id, status, edit, note, reviewer, Utc::now().to_rfc3339()],
+            )
+            .context("Failed to update draft")?;
+        let draft = find_in(conn, id)?;
+        match draft {
+            Some(draft) if updated == 1 => Ok(draft),
+            Some(draft) => bail!("Draft #{id} was already {}", draft.status),
+            None => bail!("No draft #{id}"),
+        }
+    })?;
+    opened(workspace_dir, draft)
+}
+
+/// Record how sending an approved draft went.
+pub fn record_delivery(
+    workspace_dir: &Path,
+    id: i64,
+    outbox_id: Option<&str>,
+    error: Option<&str>,
+) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "UPDATE drafts SET outbox_id = ?2, send_error = ?3 WHERE id = ?1",
+            params![id, outbox_id, error],
+        )
+        .context("Failed to record draft delivery")?;
+        Ok(())
+    })
+}
+
+/// Newest drafts first, optionally with one status.
+pub fn list(workspace_dir: &Path, status: Option<&str>, limit: usize) -> Result<Vec<Draft>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(&format!(
+            "{SELECT} WHERE (?1 IS NULL OR status = ?1) ORDER BY id DESC LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(
+            params![status, i64::try_from(limit).unwrap_or(i64::MAX)],
+            row_to_draft,
+        )?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to list drafts")
+    })?
+    .into_iter()
+    .map(|draft| opened(workspace_dir, draft))
+    .collect()
+}
+
+/// `draft` with its text decrypted.
+fn opened(workspace_dir: &Path, mut draft: Draft) -> Result<Draft> {
+    draft.original = open_text(workspace_dir, &draft.original)?;
+    draft.reply.content = open_text(workspace_dir, &draft.reply.content)?;
+    Ok(draft)
+}
+
+fn find_in(conn: &Connection, id: i64) -> Result<Option<Draft>> {
+    conn.query_row(
+        &format!("{SELECT} WHERE id = ?1"),
+        params![id],
+        row_to_draft,
+    )
+    .optional()
+    .context("Failed to read draft")
+}
+
+/// Message posted to the review chat for a new draft.
+pub fn review_request(id: i64, reply: &HeldReply) -> String {
+    format!(
+        "📝 Draft #{id} for {} / {}:\n\n{}\n\nReply /approve {id}, /edit {id} <text>, or /reject {id} [reason].",
+        reply.channel, reply.recipient, reply.content
+    )
+}
+
+/// `/drafts` reply listing pending drafts.
+pub fn pending_list(workspace_dir: &Path) -> String {
+    let drafts = match list(workspace_dir, Some("pending"), CHAT_LIST_LIMIT) {
+        Ok(drafts) => drafts,
+        Err(e) => return format!("Drafts are unavailable: {e}"),
+    };
+    if drafts.is_empty() {
+        return "No drafts waiting for review.".into();
+    }
+    let mut out = String::from("Drafts waiting for review:");
+    for draft in &drafts {
+        let _ = write!(
+            out,
+            "\n- #{} {} / {}: {}",
+            draft.id,
+            draft.reply.channel,
+            draft.reply.recipient,
+            truncate_with_ellipsis(&draft.reply.content, CHAT_PREVIEW_CHARS)
+        );
+    }
+    out
+}
+
+pub fn handle_command(command: crate::ReviewCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ReviewCommands::List { status, limit } => {
+            let drafts = list(&config.workspace_dir, status.as_deref(), limit)?;
+            if drafts.is_empty() {
+                println!("No drafts recorded.");
+                return Ok(());
+            }
+
+            println!("📝 Drafts ({}):", drafts.len());
+            for draft in drafts {
+                let decision = match (&draft.reviewer, draft.decided_at) {
+                    (Some(reviewer), Some(at)) => format!(" by {reviewer} at {}", at.to_rfc3339()),
+                    _ => String::new(),
+                };
+                let delivery = match (&draft.outbox_id, &draft.send_error) {
+                    (_, Some(error)) => format!(" | send failed: {error}"),
+                    (Some(outbox_id), None) => format!(" | outbox {outbox_id}"),
+                    (None, None) => String::new(),
+                };
+                println!(
+                    "- #{} | {} | {} → {} | {}{decision}{delivery}",
+                    draft.id,
+                    draft.created_at.to_rfc3339(),
+                    draft.reply.channel,
+                    draft.reply.recipient,
+                    draft.status,
+                );
+                println!("    {}", truncate_with_ellipsis(&draft.reply.content, 200));
+                if draft.status == "edited" {
+                    println!(
+                        "    (original) {}",
+                        truncate_with_ellipsis(&draft.original, 200)
+                    );
+                }
+                if let Some(note) = &draft.note {
+                    println!("    (reason) {note}");
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+const SELECT: &str =
+    "SELECT id, channel, recipient, thread_ts, reply_to, sender, original, content,
+                             status, reviewer, note, outbox_id, send_error, created_at, decided_at
+                      FROM drafts";
+
+fn row_to_draft(row: &rusqlite::Row<'_>) -> rusqlite::Result<Draft> {
+    let created_at: String = row.get(13)?;
+    let decided_at: Option<String> = row.get(14)?;
+    Ok(Draft {
+        id: row.get(0)?,
+        reply: HeldReply {
+            channel: row.get(1)?,
+            recipient: row.get(2)?,
+            thread_ts: row.get(3)?,
+            in_reply_to_id: row.get(4)?,
+            sender: row.get(5)?,
+            content: row.get(7)?,
+        },
+        original: row.get(6)?,
+        status: row.get(8)?,
+        reviewer: row.get(9)?,
+        note: row.get(10)?,
+        outbox_id: row.get(11)?,
+        send_error: row.get(12)?,
+        created_at: parse_timestamp(&created_at),
+        decided_at: decided_at.as_deref().map(parse_timestamp),
+    })
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("channels").join("review.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open review DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS drafts (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            channel    TEXT NOT NULL,
+            recipient  TEXT NOT NULL,
+            thread_ts  TEXT,
+            reply_to   TEXT,
+            sender     TEXT NOT NULL,
+            original   TEXT NOT NULL,
+            content    TEXT NOT NULL,
+            status     TEXT NOT NULL,
+            reviewer   TEXT,
+            note       TEXT,
+            outbox_id  TEXT,
+            send_error TEXT,
+            created_at TEXT NOT NULL,
+            decided_at TEXT
+        );
+        CREATE INDEX IF NOT EXISTS idx_drafts_status ON drafts(status);",
+    )
+    .context("Failed to initialize review schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn held(content: &str) -> HeldReply {
+        HeldReply {
+            channel: "whatsapp".into(),
+            recipient: "+15550001".into(),
+            thread_ts: None,
+            in_reply_to_id: Some("wamid.1".into()),
+            sender: "+15550001".into(),
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn commands_parse_ids_and_text() {
+        assert_eq!(parse_command("/drafts"), Some(ReviewCommand::List));
+        assert_eq!(
+            parse_command("/approve@zc_bot #12"),
+            Some(ReviewCommand::Approve(12))
+        );
+        assert_eq!(
+            parse_command("/edit 3 Thanks, your order ships today."),
+            Some(ReviewCommand::Edit(
+                3,
+                "Thanks, your order ships today.".into()
+            ))
+        );
+        assert_eq!(
+            parse_command("/reject 4"),
+            Some(ReviewCommand::Reject(4, None))
+        );
+        assert_eq!(
+            parse_command("/reject 4 wrong price"),
+            Some(ReviewCommand::Reject(4, Some("wrong price".into())))
+        );
+        assert_eq!(parse_command("/edit 3"), None);
+        assert_eq!(parse_command("/approve"), None);
+        assert_eq!(parse_command("approve 3"), None);
+    }
+
+    #[test]
+    fn reviewers_must_write_in_the_review_chat() {
+        let review = ReplyReview::new(&ReplyReviewConfig {
+            enabled: true,
+            channels: vec!["whatsapp".into()],
+            admin_channel: "telegram".into(),
+            admin_to: "-100".into(),
+            reviewers: vec!["ops".into()],
+        })
+        .unwrap();
+        let msg = |reply_target: &str, sender: &str| ChannelMessage {
+            id: "1".into(),
+            sender: sender.into(),
+            reply_target: reply_target.into(),
+            content: "/approve 1".into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+            location: None,
+            payload: None,
+        };
+        assert!(review.applies_to("whatsapp"));
+        assert!(!review.applies_to("telegram"));
+        assert!(review.is_reviewer(&msg("-100", "ops")));
+        assert!(!review.is_reviewer(&msg("-100", "mallory")));
+        assert!(!review.is_reviewer(&msg("ops", "ops")));
+        assert!(ReplyReview::new(&ReplyReviewConfig::default()).is_none());
+    }
+
+    #[test]
+    fn decisions_are_recorded_once_with_the_original_kept() {
+        let tmp = TempDir::new().unwrap();
+        let first = submit(tmp.path(), &held("Your refund is approved.")).unwrap();
+        let second = submit(tmp.path(), &held("We close at 5.")).unwrap();
+        assert!(pending_list(tmp.path()).contains(&format!("#{first} whatsapp")));
+
+        let edited = decide(
+            tmp.path(),
+            first,
+            true,
+            Some("Your refund is being reviewed."),
+            None,
+            "ops",
+        )
+        .unwrap();
+        assert_eq!(edited.status, "edited");
+        assert_eq!(edited.reply.content, "Your refund is being reviewed.");
+        assert_eq!(edited.original, "Your refund is approved.");
+        assert_eq!(edited.reply.in_reply_to_id.as_deref(), Some("wamid.1"));
+        assert!(decide(tmp.path(), first, true, None, None, "ops")
+            .unwrap_err()
+            .to_string()
+            .contains("already edited"));
+
+        let rejected = decide(tmp.path(), second, false, None, Some("hours"), "ops").unwrap();
+        assert_eq!(rejected.status, "rejected");
+        assert_eq!(rejected.note.as_deref(), Some("hours"));
+        assert!(decide(tmp.path(), 99, true, None, None, "ops").is_err());
+
+        record_delivery(tmp.path(), first, Some("outbox-1"), None).unwrap();
+        let drafts = list(tmp.path(), Some("edited"), 10).unwrap();
+        assert_eq!(drafts[0].outbox_id.as_deref(), Some("outbox-1"));
+        assert_eq!(pending_list(tmp.path()), "No drafts waiting for review.");
+    }
+}
@@ -0,0 +1,597 @@
+//! Shared rich-text model for outbound replies.
+//!
+//! Model output is Markdown. [`parse`] turns it into a small line-oriented
+//! AST once, and [`render`] emits it in a channel's native format, so
+//! escaping and formatting rules live here rather than in each channel.
+//!
+//! The dialect is deliberately narrow: headings, bullets, block quotes,
+//! fenced code, and single-level `**bold**` / `__bold__`, `*italic*`,
+//! `~~strike~~`, `` `code` `` and `[text](https://…)` links. A single `_`
+//! is never emphasis, so `snake_case` identifiers survive. `__text__` is bold
+//! everywhere except Discord, where it means underline and is kept as typed.
+
+use super::emoji;
+use std::borrow::Cow;
+use std::fmt::Write;
+
+/// A parsed Markdown document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub blocks: Vec<Block>,
+}
+
+impl Document {
+    /// Replace `:shortcode:` emoji in text spans; code is left verbatim.
+    pub fn expand_emoji_shortcodes(&mut self) {
+        let expand = |spans: &mut Vec<Inline>| {
+            for span in spans {
+                match span {
+                    Inline::Text(text)
+                    | Inline::Bold(text)
+                    | Inline::Underscored(text)
+                    | Inline::Italic(text)
+                    | Inline::Strike(text)
+                    | Inline::Link { text, .. } => {
+                        if let Cow::Owned(expanded) = emoji::expand_shortcodes(text) {
+                            *text = expanded;
+                        }
+                    }
+                    Inline::Code(_) => {}
+                }
+            }
+        };
+        for block in &mut self.blocks {
+            match block {
+                Block::Line(spans)
+                | Block::Bullet(spans)
+                | Block::Heading { content: spans, .. } => expand(spans),
+                Block::Quote(lines) => lines.iter_mut().for_each(expand),
+                Block::Code { .. } => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// A line of text; an empty line is a paragraph break.
+    Line(Vec<Inline>),
+    Heading {
+        level: u8,
+        content: Vec<Inline>,
+    },
+    Bullet(Vec<Inline>),
+    /// Consecutive `>` lines.
+    Quote(Vec<Vec<Inline>>),
+    /// Fenced code block. Contents are kept verbatim.
+    Code {
+        language: Option<String>,
+        code: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    Text(String),
+    Bold(String),
+    /// `__text__`: bold in Markdown, underline on Discord.
+    Underscored(String),
+    Italic(String),
+    Strike(String),
+    Code(String),
+    Link {
+        text: String,
+        url: String,
+    },
+}
+
+/// Output format of a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RichTextFormat {
+    /// Telegram `parse_mode = "HTML"`.
+    TelegramHtml,
+    /// Discord-flavoured Markdown.
+    DiscordMarkdown,
+    /// Slack `mrkdwn`.
+    SlackMrkdwn,
+    /// No markup, for channels that show text as-is (SMS-like, iMessage).
+    Plain,
+}
+
+/// Parse Markdown and render it for `format`. `:shortcode:` emoji are
+/// converted to Unicode except for Slack, which renders them itself.
+pub fn render(markdown: &str, format: RichTextFormat) -> String {
+    let mut document = parse(markdown);
+    if format != RichTextFormat::SlackMrkdwn {
+        document.expand_emoji_shortcodes();
+    }
+    match format {
+        RichTextFormat::TelegramHtml => render_telegram_html(&document),
+        RichTextFormat::DiscordMarkdown => render_discord_markdown(&document),
+        RichTextFormat::SlackMrkdwn => render_slack_mrkdwn(&document),
+        RichTextFormat::Plain => render_plain(&document),
+    }
+}
+
+pub fn parse(markdown: &str) -> Document {
+    let mut blocks = Vec::new();
+    let mut lines = markdown.split('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+
+        if let Some(info) = trimmed.strip_prefix("```") {
+            let language = info.trim();
+            let mut code = Vec::new();
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                code.push(line);
+            }
+            blocks.push(Block::Code {
+                language: (!language.is_empty()).then(|| language.to_string()),
+                code: code.join("\n").trim_end().to_string(),
+            });
+            continue;
+        }
+
+        if let Some((level, title)) = heading(line) {
+            blocks.push(Block::Heading {
+                level,
+                content: parse_inline(title.trim()),
+            });
+            continue;
+        }
+
+        if let Some(quoted) = quote_line(trimmed) {
+            let mut quote = vec![parse_inline(quoted)];
+            while let Some(quoted) = lines.peek().and_then(|next| quote_line(next.trim_start())) {
+                quote.push(parse_inline(quoted));
+                lines.next();
+            }
+            blocks.push(Block::Quote(quote));
+            continue;
+        }
+
+        if let Some(item) = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| trimmed.strip_prefix(marker))
+        {
+            blocks.push(Block::Bullet(parse_inline(item)));
+            continue;
+        }
+
+        blocks.push(Block::Line(parse_inline(line)));
+    }
+
+    Document { blocks }
+}
+
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let title = line.trim_start_matches('#');
+    let level = line.len() - title.len();
+    if level == 0 || level > 6 || !title.starts_with(' ') {
+        return None;
+    }
+    u8::try_from(level).ok().map(|level| (level, title))
+}
+
+fn quote_line(line: &str) -> Option<&str> {
+    line.strip_prefix("> ")
+        .or_else(|| (line == ">").then_some(""))
+}
+
+/// Split a line into inline spans. Unterminated markers stay literal text.
+fn parse_inline(line: &str) -> Vec<Inline> {
+    let mut spans = Vec::new();
+    let mut text = String::new();
+    let bytes = line.as_bytes();
+    let mut i = 0;
+
+    while i < line.len() {
+        let rest = &line[i..];
+        let span = if rest.starts_with("**") {
+            delimited(rest, 2, "**").map(|(inner, used)| (Inline::Bold(inner), used))
+        } else if rest.starts_with("__") {
+            delimited(rest, 2, "__").map(|(inner, used)| (Inline::Underscored(inner), used))
+        } else if rest.starts_with("~~") {
+            delimited(rest, 2, "~~").map(|(inner, used)| (Inline::Strike(inner), used))
+        } else if bytes[i] == b'*' && (i == 0 || bytes[i - 1] != b'*') {
+            delimited(rest, 1, "*")
+                .filter(|(inner, _)| !inner.is_empty())
+                .map(|(inner, used)| (Inline::Italic(inner), used))
+        } else if bytes[i] == b'`' && (i == 0 || bytes[i - 1] != b'`') {
+            delimited(rest, 1, "`").map(|(inner, used)| (Inline::Code(inner), used))
+        } else if bytes[i] == b'[' {
+            link(rest)
+        } else {
+            None
+        };
+
+        match span {
+            Some((span, used)) => {
+                if !text.is_empty() {
+                    spans.push(Inline::Text(std::mem::take(&mut text)));
+                }
+                spans.push(span);
+                i += used;
+            }
+            None => {
+                let ch = rest.chars().next().unwrap_or_default();
+                text.push(ch);
+                i += ch.len_utf8();
+            }
+        }
+    }
+
+    if !text.is_empty() {
+        spans.push(Inline::Text(text));
+    }
+    spans
+}
+
+/// Content between an opening marker of `width` bytes and the next `close`,
+/// plus the total bytes consumed.
+fn delimited(rest: &str, width: usize, close: &str) -> Option<(String, usize)> {
+    let end = rest[width..].find(close)?;
+    Some((
+        rest[width..width + end].to_string(),
+        width + end + close.len(),
+    ))
+}
+
+fn link(rest: &str) -> Option<(Inline, usize)> {
+    let text_end = rest[1..].find(']')? + 1;
+    let after = rest[text_end + 1..].strip_prefix('(')?;
+    let url_end = after.find(')')?;
+    let url = &after[..url_end];
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return None;
+    }
+    Some((
+        Inline::Link {
+            text: rest[1..text_end].to_string(),
+            url: url.to_string(),
+        },
+        text_end + 2 + url_end + 1,
+    ))
+}
+
+fn join_blocks(document: &Document, mut block: impl FnMut(&Block) -> String) -> String {
+    document
+        .blocks
+        .iter()
+        .map(&mut block)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_spans(spans: &[Inline], mut span: impl FnMut(&Inline, &mut String)) -> String {
+    let mut out = String::new();
+    for inline in spans {
+        span(inline, &mut out);
+    }
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Telegram HTML supports `<b>`, `<i>`, `<s>`, `<code>`, `<pre>`,
+/// `<blockquote>` and `<a href>`; everything else is escaped.
+fn render_telegram_html(document: &Document) -> String {
+    let inline = |spans: &[Inline]| {
+        render_spans(spans, |span, out| {
+            let _ = match span {
+                Inline::Text(text) => write!(out, "{}", escape_html(text)),
+                Inline::Bold(text) | Inline::Underscored(text) => {
+                    write!(out, "<b>{}</b>", escape_html(text))
+                }
+                Inline::Italic(text) => write!(out, "<i>{}</i>", escape_html(text)),
+                Inline::Strike(text) => write!(out, "<s>{}</s>", escape_html(text)),
+                Inline::Code(text) => write!(out, "<code>{}</code>", escape_html(text)),
+                Inline::Link { text, url } => write!(
+                    out,
+                    "<a href=\"{}\">{}</a>",
+                    escape_html(url),
+                    escape_html(text)
+                ),
+            };
+        })
+    };
+    join_blocks(document, |block| match block {
+        Block::Line(spans) => inline(spans),
+        Block::Heading { content, .. } => format!("<b>{}</b>", inline(content)),
+        Block::Bullet(spans) => format!("• {}", inline(spans)),
+        Block::Quote(lines) => format!(
+            "<blockquote>{}</blockquote>",
+            lines
+                .iter()
+                .map(|l| inline(l))
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        // Telegram rejects class attributes, so the language is dropped.
+        Block::Code { code, .. } => format!("<pre><code>{}</code></pre>", escape_html(code)),
+    })
+}
+
+/// Discord renders Markdown natively; this normalizes the dialect and caps
+/// headings at the three levels Discord supports.
+fn render_discord_markdown(document: &Document) -> String {
+    let inline = |spans: &[Inline]| {
+        render_spans(spans, |span, out| {
+            let _ = match span {
+                Inline::Text(text) => write!(out, "{text}"),
+                Inline::Bold(text) => write!(out, "**{text}**"),
+                Inline::Underscored(text) => write!(out, "__{text}__"),
+                Inline::Italic(text) => write!(out, "*{text}*"),
+                Inline::Strike(text) => write!(out, "~~{text}~~"),
+                Inline::Code(text) => write!(out, "`{text}`"),
+                Inline::Link { text, url } => write!(out, "[{text}]({url})"),
+            };
+        })
+    };
+    join_blocks(document, |block| match block {
+        Block::Line(spans) => inline(spans),
+        Block::Heading { level, content } => {
+            format!(
+                "{} {}",
+                "#".repeat(usize::from(*level.min(&3))),
+                inline(content)
+            )
+        }
+        Block::Bullet(spans) => format!("- {}", inline(spans)),
+        Block::Quote(lines) => lines
+            .iter()
+            .map(|l| format!("> {}", inline(l)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Code { language, code } => format!(
+            "```{}\n{code}\n```",
+            language.as_deref().unwrap_or_default()
+        ),
+    })
+}
+
+fn escape_slack(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Whether `inner` (between `<` and `>`) is a Slack control sequence: a user,
+/// channel, or special mention (`@U…`, `#C…`, `!here`) or a `url|label` link.
+fn is_slack_token(inner: &str) -> bool {
+    let target = inner.split_once('|').map_or(inner, |(target, _)| target);
+    let mention = target
+        .strip_prefix(['@', '#', '!'])
+        .is_some_and(|id| !id.is_empty());
+    let url = ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| target.len() > scheme.len() && target.starts_with(scheme));
+    (mention || url) && !target.contains(char::is_whitespace) && !inner.contains(['<', '\n'])
+}
+
+/// [`escape_slack`], but Slack mentions and links the model wrote as
+/// `<…>` are passed through so Slack still resolves them.
+fn escape_slack_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&escape_slack(&rest[..start]));
+        let after = &rest[start + 1..];
+        match after.find('>').filter(|&end| is_slack_token(&after[..end])) {
+            Some(end) => {
+                out.push_str(&rest[start..=start + 1 + end]);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str("&lt;");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(&escape_slack(rest));
+    out
+}
+
+/// Slack `mrkdwn`: single-character emphasis, `<url|text>` links, and only
+/// `&`, `<`, `>` escaped outside Slack's own `<@U…>`, `<#C…>`, `<!here>` and
+/// `<url|text>` sequences.
+fn render_slack_mrkdwn(document: &Document) -> String {
+    let inline = |spans: &[Inline]| {
+        render_spans(spans, |span, out| {
+            let _ = match span {
+                Inline::Text(text) => write!(out, "{}", escape_slack_text(text)),
+                Inline::Bold(text) | Inline::Underscored(text) => {
+                    write!(out, "*{}*", escape_slack_text(text))
+                }
+                Inline::Italic(text) => write!(out, "_{}_", escape_slack_text(text)),
+                Inline::Strike(text) => write!(out, "~{}~", escape_slack_text(text)),
+                Inline::Code(text) => write!(out, "`{}`", escape_slack(text)),
+                Inline::Link { text, url } => {
+                    write!(out, "<{}|{}>", escape_slack(url), escape_slack(text))
+                }
+            };
+        })
+    };
+    join_blocks(document, |block| match block {
+        Block::Line(spans) => inline(spans),
+        Block::Heading { content, .. } => format!("*{}*", inline(content)),
+        Block::Bullet(spans) => format!("• {}", inline(spans)),
+        Block::Quote(lines) => lines
+            .iter()
+            .map(|l| format!("> {}", inline(l)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Code { code, .. } => format!("```\n{}\n```", escape_slack(code)),
+    })
+}
+
+/// Markup removed; links keep their URL as `text (url)`.
+fn render_plain(document: &Document) -> String {
+    let inline = |spans: &[Inline]| {
+        render_spans(spans, |span, out| {
+            let _ = match span {
+                Inline::Text(text)
+                | Inline::Bold(text)
+                | Inline::Underscored(text)
+                | Inline::Italic(text)
+                | Inline::Strike(text)
+                | Inline::Code(text) => write!(out, "{text}"),
+                Inline::Link { text, url } if text == url => write!(out, "{url}"),
+                Inline::Link { text, url } => write!(out, "{text} ({url})"),
+            };
+        })
+    };
+    join_blocks(document, |block| match block {
+        Block::Line(spans) | Block::Heading { content: spans, .. } => inline(spans),
+        Block::Bullet(spans) => format!("• {}", inline(spans)),
+        Block::Quote(lines) => lines
+            .iter()
+            .map(|l| format!("> {}", inline(l)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Code { code, .. } => code.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "## Plan\n\
+        Use **care** with *this* and ~~that~~ `code`.\n\
+        - see [docs](https://example.com/a?b=1&c=2)\n\
+        > quoted <tag>\n\
+        ```rust\n\
+        let snake_case = \"**not bold**\";\n\
+        ```";
+
+    #[test]
+    fn parse_builds_blocks_and_spans() {
+        let doc = parse(SAMPLE);
+        assert_eq!(doc.blocks.len(), 5);
+        assert!(matches!(&doc.blocks[0], Block::Heading { level: 2, .. }));
+        assert_eq!(
+            doc.blocks[1],
+            Block::Line(vec![
+                Inline::Text("Use ".into()),
+                Inline::Bold("care".into()),
+                Inline::Text(" with ".into()),
+                Inline::Italic("this".into()),
+                Inline::Text(" and ".into()),
+                Inline::Strike("that".into()),
+                Inline::Text(" ".into()),
+                Inline::Code("code".into()),
+                Inline::Text(".".into()),
+            ])
+        );
+        assert_eq!(
+            doc.blocks[4],
+            Block::Code {
+                language: Some("rust".into()),
+                code: "let snake_case = \"**not bold**\";".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_keeps_unterminated_markers_and_snake_case() {
+        let doc = parse("a * b and my_var_name and [x](ftp://host)");
+        assert_eq!(
+            doc.blocks,
+            vec![Block::Line(vec![Inline::Text(
+                "a * b and my_var_name and [x](ftp://host)".into()
+            )])]
+        );
+    }
+
+    #[test]
+    fn renders_telegram_html() {
+        assert_eq!(
+            render(SAMPLE, RichTextFormat::TelegramHtml),
+            "<b>Plan</b>\n\
+             Use <b>care</b> with <i>this</i> and <s>that</s> <code>code</code>.\n\
+             • see <a href=\"https://example.com/a?b=1&amp;c=2\">docs</a>\n\
+             <blockquote>quoted &lt;tag&gt;</blockquote>\n\
+             <pre><code>let snake_case = &quot;**not bold**&quot;;</code></pre>"
+        );
+    }
+
+    #[test]
+    fn renders_slack_mrkdwn() {
+        assert_eq!(
+            render(SAMPLE, RichTextFormat::SlackMrkdwn),
+            "*Plan*\n\
+             Use *care* with _this_ and ~that~ `code`.\n\
+             • see <https://example.com/a?b=1&amp;c=2|docs>\n\
+             > quoted &lt;tag&gt;\n\
+             ```\nlet snake_case = \"**not bold**\";\n```"
+        );
+    }
+
+    #[test]
+    fn slack_mentions_and_links_pass_through() {
+        assert_eq!(
+            render(
+                "hey <@U123> in <#C456|general>, <!here>: see <https://example.com/a?b=1|the docs> **<@U789>**",
+                RichTextFormat::SlackMrkdwn,
+            ),
+            "hey <@U123> in <#C456|general>, <!here>: see <https://example.com/a?b=1|the docs> *<@U789>*"
+        );
+        assert_eq!(
+            render(
+                "if a <b and c> d, <@>, <ftp://x> or <@U1 x>",
+                RichTextFormat::SlackMrkdwn
+            ),
+            "if a &lt;b and c&gt; d, &lt;@&gt;, &lt;ftp://x&gt; or &lt;@U1 x&gt;"
+        );
+        assert_eq!(
+            render("__bold__", RichTextFormat::TelegramHtml),
+            "<b>bold</b>"
+        );
+    }
+
+    #[test]
+    fn renders_discord_markdown() {
+        let rendered = render(
+            "#### Deep\n__underline__ **bold** <@123>",
+            RichTextFormat::DiscordMarkdown,
+        );
+        assert_eq!(rendered, "### Deep\n__underline__ **bold** <@123>");
+        assert_eq!(render(SAMPLE, RichTextFormat::DiscordMarkdown), SAMPLE);
+    }
+
+    #[test]
+    fn shortcodes_expand_outside_code_and_not_for_slack() {
+        let markdown = "Deployed :rocket: with `:rocket:`";
+        assert_eq!(
+            render(markdown, RichTextFormat::TelegramHtml),
+            "Deployed 🚀 with <code>:rocket:</code>"
+        );
+        assert_eq!(
+            render(markdown, RichTextFormat::SlackMrkdwn),
+            "Deployed :rocket: with `:rocket:`"
+        );
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        assert_eq!(
+            render(SAMPLE, RichTextFormat::Plain),
+            "Plan\n\
+             Use care with this and that code.\n\
+             • see docs (https://example.com/a?b=1&c=2)\n\
+             > quoted <tag>\n\
+             let snake_case = \"**not bold**\";"
+        );
+    }
+}
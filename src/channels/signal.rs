@@ -106,10 +106,8 @@ impl SignalChannel {
     }
 
     fn is_sender_allowed(&self, sender: &str) -> bool {
-        if self.allowed_from.iter().any(|u| u == "*") {
-            return true;
-        }
-        self.allowed_from.iter().any(|u| u == sender)
+        let listed = self.allowed_from.iter().any(|u| u == "*" || u == sender);
+        super::access::check("signal", sender, listed)
     }
 
     fn is_e164(recipient: &str) -> bool {
@@ -266,6 +264,8 @@ impl SignalChannel {
             channel: "signal".to_string(),
             timestamp: timestamp / 1000, // millis → secs
             thread_ts: None,
+            location: None,
+            payload: None,
         })
     }
 }
@@ -0,0 +1,283 @@
+//! Channel emulation for `zeroclaw channel simulate`.
+//!
+//! Feeds a saved inbound payload through the channel's own update parser and
+//! the dispatch policies that run before the agent: reaction feedback,
+//! runtime commands, agent routes, and group overrides (wake words, rate
+//! limits, persona, tools, ack reactions). It prints the parsed message, each
+//! decision, and where the reply would go. With `--dry-run` the provider is
+//! never called; otherwise the agent's reply is printed but not sent.
+//!
+//! Telegram updates (`getUpdates` / webhook JSON) are supported. Voice notes
+//! and attachments need file downloads, so they are reported as unparsed.
+
+use super::groups::{self, GroupOverrides};
+use super::telegram::{TelegramChannel, TelegramUpdate};
+use super::traits::{ChannelMessage, MessagePayload};
+use super::{agents, parse_runtime_command};
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+/// Channels `simulate` can parse payloads for.
+const SIMULATED_CHANNELS: &[&str] = &["telegram"];
+
+/// Dispatch decisions for one parsed message.
+struct Simulation {
+    lines: Vec<String>,
+    /// Whether the message would reach the agent.
+    reaches_agent: bool,
+}
+
+/// Guess the channel from the payload shape.
+fn detect_channel(payload: &serde_json::Value) -> Option<&'static str> {
+    payload.get("update_id").map(|_| "telegram")
+}
+
+fn describe_message(msg: &ChannelMessage) -> Vec<String> {
+    let mut lines = vec![
+        format!("  channel:      {}", msg.channel),
+        format!("  id:           {}", msg.id),
+        format!("  sender:       {}", msg.sender),
+        format!("  reply_target: {}", msg.reply_target),
+        format!(
+            "  thread:       {}",
+            msg.thread_ts.as_deref().unwrap_or("(none)")
+        ),
+    ];
+    if let Some(payload) = &msg.payload {
+        lines.push(format!("  payload:      {payload}"));
+    }
+    if let Some(location) = &msg.location {
+        lines.push(format!(
+            "  location:     {}, {}",
+            location.latitude, location.longitude
+        ));
+    }
+    lines.push(format!("  content:      {}", msg.content));
+    lines
+}
+
+/// Run the pre-agent dispatch policies from `config` on `msg`.
+fn simulate_dispatch(config: &Config, msg: &ChannelMessage) -> Simulation {
+    let mut lines = Vec::new();
+    let stop = |mut lines: Vec<String>, reason: String| {
+        lines.push(format!("  ⛔ {reason}"));
+        Simulation {
+            lines,
+            reaches_agent: false,
+        }
+    };
+
+    if let Some(MessagePayload::Reaction(_)) = &msg.payload {
+        return stop(
+            lines,
+            "reaction: recorded as feedback on an earlier reply, no agent run".into(),
+        );
+    }
+    if let Some(command) = parse_runtime_command(&msg.channel, &msg.content) {
+        return stop(
+            lines,
+            format!("runtime command {command:?}: answered without the agent"),
+        );
+    }
+
+    let agent = config
+        .channels_config
+        .agent_routes
+        .iter()
+        .find(|route| agents::route_matches(route, msg))
+        .map_or("default", |route| route.agent.as_str());
+    lines.push(format!("  agent: {agent}"));
+
+    let overrides = GroupOverrides::new(config.channels_config.groups.clone());
+    let Some(group) = overrides.select(msg) else {
+        lines.push("  group overrides: none".into());
+        return Simulation {
+            lines,
+            reaches_agent: true,
+        };
+    };
+    lines.push(format!(
+        "  group overrides: {} ({})",
+        group.group,
+        group.channel.as_deref().unwrap_or("any channel")
+    ));
+    if !groups::mentions_wake_word(&group.wake_words, &msg.content) {
+        return stop(
+            lines,
+            format!(
+                "no wake word ({}): ignored without a reply",
+                group.wake_words.join(", ")
+            ),
+        );
+    }
+    if !overrides.within_rate_limit(group, msg) {
+        return stop(lines, "over the group rate limit: ignored".into());
+    }
+    if let Some(persona) = &group.persona {
+        lines.push(format!("  persona: {persona}"));
+    }
+    if let Some(tools) = &group.tools {
+        lines.push(format!("  tools limited to: {}", tools.join(", ")));
+    }
+    let ack_reactions = format!("{:?}", group.ack_reactions).to_lowercase();
+    lines.push(format!("  ack reactions: {ack_reactions}"));
+    Simulation {
+        lines,
+        reaches_agent: true,
+    }
+}
+
+/// Parse a Telegram update with the configured channel's own parser.
+fn parse_telegram(config: &Config, payload: &serde_json::Value) -> Result<TelegramUpdate> {
+    let Some(tg) = config.channels_config.telegram.as_ref() else {
+        bail!("[channels_config.telegram] is not configured");
+    };
+    let channel = TelegramChannel::new(
+        tg.bot_token.clone(),
+        tg.allowed_users.clone(),
+        tg.mention_only,
+    )
+    .with_allowed_topics(tg.allowed_topics.clone());
+    Ok(channel.classify_update(payload))
+}
+
+pub async fn simulate_from_cli(
+    config: Config,
+    file: &Path,
+    channel: Option<String>,
+    dry_run: bool,
+) -> Result<()> {
+    let raw = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let payload: serde_json::Value =
+        serde_json::from_str(&raw).with_context(|| format!("{} is not JSON", file.display()))?;
+    let channel = match channel.as_deref().map(str::to_ascii_lowercase) {
+        Some(channel) => channel,
+        None => detect_channel(&payload)
+            .context("Could not tell the channel from the payload; pass --channel")?
+            .to_string(),
+    };
+
+    let msg = match channel.as_str() {
+        "telegram" => match parse_telegram(&config, &payload)? {
+            TelegramUpdate::FilteredTopic => {
+                println!("⛔ Dropped: forum topic outside allowed_topics");
+                return Ok(());
+            }
+            TelegramUpdate::Unparsed => {
+                println!(
+                    "⛔ Not parsed: sender not in allowed_users, mention_only without a mention, \
+                     or a voice note/attachment (needs a download, not simulated)"
+                );
+                return Ok(());
+            }
+            TelegramUpdate::Callback(msg, _) => {
+                println!("🔘 Inline keyboard press (the callback query would be answered)");
+                msg
+            }
+            TelegramUpdate::Reaction(msg) | TelegramUpdate::Message(msg) => msg,
+        },
+        other => bail!(
+            "Cannot simulate '{other}' payloads; supported channels: {}",
+            SIMULATED_CHANNELS.join(", ")
+        ),
+    };
+
+    println!("📥 Parsed message:");
+    for line in describe_message(&msg) {
+        println!("{line}");
+    }
+    println!("\n🧭 Policy decisions:");
+    let simulation = simulate_dispatch(&config, &msg);
+    for line in &simulation.lines {
+        println!("{line}");
+    }
+    if !simulation.reaches_agent {
+        return Ok(());
+    }
+
+    println!("\n💬 Reply:");
+    println!(
+        "  would go to {}{}",
+        msg.reply_target,
+        msg.thread_ts
+            .as_deref()
+            .map(|thread| format!(" (thread {thread})"))
+            .unwrap_or_default()
+    );
+    if dry_run {
+        println!("  (dry run: provider not called)");
+        return Ok(());
+    }
+    let reply = crate::agent::process_message(config, &msg.content).await?;
+    println!("  not sent:\n{reply}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Config {
+        toml::from_str(
+            r#"
+default_temperature = 0.7
+[channels_config]
+cli = true
+[channels_config.telegram]
+bot_token = "token"
+allowed_users = ["alice"]
+[[channels_config.groups]]
+group = "-100"
+wake_words = ["claw"]
+persona = "Be brief."
+[[channels_config.agent_routes]]
+agent = "family"
+group = "-100"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn update(username: &str, text: &str) -> serde_json::Value {
+        json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 7,
+                "text": text,
+                "from": { "id": 42, "username": username },
+                "chat": { "id": -100, "type": "group" }
+            }
+        })
+    }
+
+    fn simulate(config: &Config, payload: &serde_json::Value) -> Option<Simulation> {
+        match parse_telegram(config, payload).unwrap() {
+            TelegramUpdate::Message(msg) => Some(simulate_dispatch(config, &msg)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn telegram_updates_go_through_parser_and_group_policies() {
+        let config = config();
+        assert_eq!(detect_channel(&update("alice", "hi")), Some("telegram"));
+        assert!(simulate(&config, &update("mallory", "claw hi")).is_none());
+
+        let quiet = simulate(&config, &update("alice", "hello all")).unwrap();
+        assert!(!quiet.reaches_agent);
+        assert!(quiet.lines.last().unwrap().contains("no wake word"));
+
+        let called = simulate(&config, &update("alice", "Claw, what's for dinner?")).unwrap();
+        assert!(called.reaches_agent);
+        assert!(called.lines.contains(&"  agent: family".to_string()));
+        assert!(called.lines.contains(&"  persona: Be brief.".to_string()));
+        assert!(called.lines.contains(&"  ack reactions: full".to_string()));
+
+        let command = simulate(&config, &update("alice", "/new")).unwrap();
+        assert!(!command.reaches_agent);
+        assert!(command.lines[0].contains("NewSession"));
+    }
+}
@@ -1,13 +1,18 @@
+use super::rich_text::{self, RichTextFormat};
 use super::traits::{Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+/// Slack rejects profile status text longer than this.
+const SLACK_STATUS_MAX_CHARS: usize = 100;
+
 /// Slack channel — polls conversations.history via Web API
 pub struct SlackChannel {
     bot_token: String,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    status_token: Option<String>,
 }
 
 impl SlackChannel {
@@ -16,9 +21,16 @@ impl SlackChannel {
             bot_token,
             channel_id,
             allowed_users,
+            status_token: None,
         }
     }
 
+    /// Set the user token used to show presence as a profile status.
+    pub fn with_status_token(mut self, token: Option<String>) -> Self {
+        self.status_token = token.filter(|t| !t.trim().is_empty());
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.slack")
     }
@@ -27,7 +39,8 @@ impl SlackChannel {
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
     fn is_user_allowed(&self, user_id: &str) -> bool {
-        self.allowed_users.iter().any(|u| u == "*" || u == user_id)
+        let listed = self.allowed_users.iter().any(|u| u == "*" || u == user_id);
+        super::access::check("slack", user_id, listed)
     }
 
     /// Get the bot's own user ID so we can ignore our own messages
@@ -49,6 +62,18 @@ impl SlackChannel {
     }
 
     /// Resolve the thread identifier for inbound Slack messages.
+    /// Thread to post into: the explicit thread, else the answered message's
+    /// `ts` (Slack's reply semantics are threads; ids are `slack_{channel}_{ts}`).
+    fn outbound_thread_ts(message: &SendMessage) -> Option<&str> {
+        message.thread_ts.as_deref().or_else(|| {
+            let id = message.in_reply_to_id.as_deref()?;
+            match id.strip_prefix("slack_") {
+                Some(rest) => rest.split_once('_').map(|(_, ts)| ts),
+                None => Some(id),
+            }
+        })
+    }
+
     /// Replies carry `thread_ts` (root thread id); top-level messages only have `ts`.
     fn inbound_thread_ts(msg: &serde_json::Value, ts: &str) -> Option<String> {
         msg.get("thread_ts")
@@ -184,13 +209,16 @@ impl Channel for SlackChannel {
         "slack"
     }
 
+    // No start_typing: the Web API has no typing call for bot tokens, and
+    // `assistant.threads.setStatus` only works in AI assistant threads.
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let mut body = serde_json::json!({
             "channel": message.recipient,
-            "text": message.content
+            "text": rich_text::render(&message.content, RichTextFormat::SlackMrkdwn)
         });
 
-        if let Some(ref ts) = message.thread_ts {
+        if let Some(ts) = Self::outbound_thread_ts(message) {
             body["thread_ts"] = serde_json::json!(ts);
         }
 
@@ -348,6 +376,14 @@ impl Channel for SlackChannel {
                             tracing::warn!(
                                 "Slack: ignoring message from unauthorized user: {user}"
                             );
+                            if user != "unknown" && ts > last_ts {
+                                last_ts_by_channel.insert(channel_id.clone(), ts.to_string());
+                                if let Some(reply) =
+                                    super::pairing::challenge("slack", user, &channel_id).await
+                                {
+                                    let _ = self.send(&SendMessage::new(reply, &channel_id)).await;
+                                }
+                            }
                             continue;
                         }
 
@@ -369,6 +405,8 @@ impl Channel for SlackChannel {
                                 .unwrap_or_default()
                                 .as_secs(),
                             thread_ts: Self::inbound_thread_ts(msg, ts),
+                            location: None,
+                            payload: None,
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -380,6 +418,37 @@ impl Channel for SlackChannel {
         }
     }
 
+    fn supports_presence(&self) -> bool {
+        self.status_token.is_some()
+    }
+
+    async fn set_presence(&self, status: &str) -> anyhow::Result<()> {
+        let Some(token) = self.status_token.as_deref() else {
+            return Ok(());
+        };
+        let status_text: String = status.chars().take(SLACK_STATUS_MAX_CHARS).collect();
+        let body = serde_json::json!({
+            "profile": { "status_text": status_text, "status_emoji": "" }
+        });
+        let resp: serde_json::Value = self
+            .http_client()
+            .post("https://slack.com/api/users.profile.set")
+            .bearer_auth(token)
+            .json(&body)
+            .send()
+            .await?
+            .json()
+            .await?;
+        if resp.get("ok") != Some(&serde_json::Value::Bool(true)) {
+            let err = resp
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("Slack users.profile.set failed: {err}");
+        }
+        Ok(())
+    }
+
     async fn health_check(&self) -> bool {
         self.http_client()
             .get("https://slack.com/api/auth.test")
@@ -395,6 +464,14 @@ impl Channel for SlackChannel {
 mod tests {
     use super::*;
 
+    #[test]
+    fn presence_requires_a_status_token() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
+        assert!(!ch.supports_presence());
+        let ch = ch.with_status_token(Some("xoxp-fake".into()));
+        assert!(ch.supports_presence());
+    }
+
     #[test]
     fn slack_channel_name() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, vec![]);
@@ -532,6 +609,20 @@ mod tests {
         assert!(id.starts_with("slack_"));
     }
 
+    #[test]
+    fn outbound_thread_ts_falls_back_to_replied_message_ts() {
+        let reply = SendMessage::new("hi", "C123").replying_to(Some("slack_C123_123.002".into()));
+        assert_eq!(SlackChannel::outbound_thread_ts(&reply), Some("123.002"));
+
+        let threaded = reply.clone().in_thread(Some("123.001".into()));
+        assert_eq!(SlackChannel::outbound_thread_ts(&threaded), Some("123.001"));
+
+        assert_eq!(
+            SlackChannel::outbound_thread_ts(&SendMessage::new("hi", "C123")),
+            None
+        );
+    }
+
     #[test]
     fn inbound_thread_ts_prefers_explicit_thread_ts() {
         let msg = serde_json::json!({
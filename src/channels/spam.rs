@@ -0,0 +1,592 @@
+//! Inbound spam/abuse screening for open channels (`[channels_config.spam]`).
+//!
+//! Messages on channels whose allowlist is `"*"` are scored by cheap
+//! heuristics, and borderline scores can be confirmed by a small model.
+//! Flagged messages are quarantined for review (see [`super::quarantine`]) or
+//! dropped, and earn their sender a strike. Strikes shrink the sender's
+//! per-minute rate limit until the sender is muted.
+
+use super::links;
+use super::quarantine;
+use super::traits::ChannelMessage;
+use crate::config::{SpamAction, SpamFilterConfig};
+use crate::observability::{Observer, ObserverEvent};
+use crate::providers::Provider;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+/// Repeats shorter than this ("ok", "thanks") are not treated as flooding.
+const DUPLICATE_MIN_CHARS: usize = 12;
+/// Tracked senders before idle ones are forgotten.
+const SENDER_CAPACITY: usize = 4096;
+/// How often strikes are re-read from disk, so `zeroclaw quarantine pardon`
+/// reaches a running filter.
+const STRIKE_RELOAD: Duration = Duration::from_secs(60);
+
+const SPAM_PHRASES: &[&str] = &[
+    "airdrop",
+    "binary options",
+    "casino",
+    "claim your",
+    "click here",
+    "crypto signal",
+    "dm me for",
+    "double your",
+    "earn money fast",
+    "forex",
+    "free money",
+    "giveaway",
+    "guaranteed profit",
+    "investment opportunity",
+    "limited time offer",
+    "onlyfans",
+    "promo code",
+    "seed phrase",
+    "verify your account",
+    "work from home",
+];
+
+const ABUSE_PHRASES: &[&str] = &[
+    "kill yourself",
+    "kys",
+    "i will kill you",
+    "i know where you live",
+    "go die",
+];
+
+const MODEL_PROMPT: &str = "You are a spam and abuse filter for a public chat assistant. \
+Reply with only a number from 0 to 1: the probability that the user's message is spam, \
+a scam, or abuse. Ordinary questions, even rude or off-topic ones, score low.";
+
+/// Heuristic score for a message, with the signals that contributed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Assessment {
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+impl Assessment {
+    fn add(&mut self, weight: f64, reason: impl Into<String>) {
+        self.score = (self.score + weight).min(1.0);
+        self.reasons.push(reason.into());
+    }
+}
+
+/// Lowercase words separated by single spaces, padded so phrases can be
+/// matched on word boundaries with `contains(" phrase ")`.
+fn normalize_words(content: &str) -> String {
+    let words: Vec<String> = content
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+fn longest_char_run(content: &str) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    let mut previous = None;
+    for c in content.chars() {
+        if Some(c) == previous {
+            current += 1;
+        } else {
+            current = 1;
+            previous = Some(c);
+        }
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// Score one message on content alone.
+pub fn score_message(content: &str) -> Assessment {
+    let mut assessment = Assessment::default();
+    let words = normalize_words(content);
+
+    let spam_hits: Vec<&str> = SPAM_PHRASES
+        .iter()
+        .copied()
+        .filter(|phrase| words.contains(&format!(" {phrase} ")))
+        .collect();
+    if let Some((first, rest)) = spam_hits.split_first() {
+        assessment.add(0.35, format!("spam phrase \"{first}\""));
+        for phrase in rest {
+            assessment.add(0.15, format!("spam phrase \"{phrase}\""));
+        }
+    }
+    if ABUSE_PHRASES
+        .iter()
+        .any(|phrase| words.contains(&format!(" {phrase} ")))
+    {
+        assessment.add(0.7, "abusive language");
+    }
+
+    let urls: Vec<&str> = links::find_urls(content)
+        .into_iter()
+        .map(|range| &content[range])
+        .collect();
+    if urls.len() >= 3 {
+        assessment.add(0.25, format!("{} links", urls.len()));
+    }
+    if urls.iter().any(|url| links::is_shortened(url)) {
+        assessment.add(0.2, "shortened link");
+    }
+
+    let mentions = content
+        .split_whitespace()
+        .filter(|token| token.starts_with('@') && token.len() > 1)
+        .count();
+    if content.contains("@everyone") || content.contains("@here") || mentions >= 5 {
+        assessment.add(0.3, "mass mention");
+    }
+
+    let letters = content.chars().filter(|c| c.is_alphabetic()).count();
+    let upper = content.chars().filter(|c| c.is_uppercase()).count();
+    if letters >= 20 && upper * 10 > letters * 7 {
+        assessment.add(0.2, "shouting");
+    }
+    if longest_char_run(content) >= 10 {
+        assessment.add(0.15, "repeated characters");
+    }
+    assessment
+}
+
+/// Parse the first number in a model reply as a probability.
+fn parse_model_score(reply: &str) -> Option<f64> {
+    reply
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find_map(|token| token.parse::<f64>().ok())
+        .filter(|score| (0.0..=1.0).contains(score))
+}
+
+/// Outcome of screening an inbound message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Screening {
+    /// Clean, or not on an open channel.
+    Allow,
+    /// Borderline; ask the model with [`SpamFilter::review`] before processing.
+    Review(Assessment),
+    /// Rate limited, muted, or flagged. Already quarantined or dropped.
+    Blocked,
+}
+
+#[derive(Default)]
+struct SenderActivity {
+    recent: VecDeque<Instant>,
+    last_content: Option<String>,
+}
+
+/// Strike counts kept in memory so screening does not hit the database.
+#[derive(Default)]
+struct StrikeCache {
+    loaded_at: Option<Instant>,
+    counts: HashMap<(String, String), quarantine::Strikes>,
+}
+
+pub struct SpamFilter {
+    config: SpamFilterConfig,
+    open_channels: HashSet<String>,
+    workspace_dir: PathBuf,
+    senders: Mutex<HashMap<(String, String), SenderActivity>>,
+    strikes: Mutex<StrikeCache>,
+}
+
+impl SpamFilter {
+    /// Build a filter for the given open channels. `None` when screening is
+    /// disabled or every channel has an explicit allowlist.
+    pub fn new<I, S>(
+        config: &SpamFilterConfig,
+        open_channels: I,
+        workspace_dir: PathBuf,
+    ) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let open_channels: HashSet<String> = open_channels.into_iter().map(Into::into).collect();
+        if !config.enabled || open_channels.is_empty() {
+            return None;
+        }
+        Some(Self {
+            config: config.clone(),
+            open_channels,
+            workspace_dir,
+            senders: Mutex::new(HashMap::new()),
+            strikes: Mutex::new(StrikeCache::default()),
+        })
+    }
+
+    fn strike_decay(&self) -> chrono::Duration {
+        i64::try_from(self.config.strike_decay_hours)
+            .ok()
+            .and_then(chrono::Duration::try_hours)
+            .unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Screen a message before it is queued for the agent.
+    pub fn screen(&self, msg: &ChannelMessage, observer: &dyn Observer) -> Screening {
+        if !self.open_channels.contains(&msg.channel) {
+            return Screening::Allow;
+        }
+
+        let strikes = self.strikes(msg);
+        if self.config.max_strikes > 0 && strikes >= self.config.max_strikes {
+            tracing::info!(channel = %msg.channel, sender = %msg.sender, strikes, "Dropping message from muted sender");
+            record_event(observer, &msg.channel, "rate_limited");
+            return Screening::Blocked;
+        }
+
+        let (rate_limited, duplicate) = self.track(msg, strikes);
+        if rate_limited {
+            tracing::info!(channel = %msg.channel, sender = %msg.sender, strikes, "Sender exceeded spam rate limit");
+            record_event(observer, &msg.channel, "rate_limited");
+            return Screening::Blocked;
+        }
+
+        let mut assessment = score_message(&msg.content);
+        if duplicate {
+            assessment.add(0.3, "repeated message");
+        }
+        if assessment.score >= self.config.threshold {
+            self.flag(msg, &assessment, observer);
+            return Screening::Blocked;
+        }
+        if self.config.model.is_some() && assessment.score >= self.config.model_review_threshold {
+            return Screening::Review(assessment);
+        }
+        Screening::Allow
+    }
+
+    /// Ask the configured model about a borderline message. Returns `false`
+    /// when the message was flagged; model errors let the message through.
+    pub async fn review(
+        &self,
+        msg: &ChannelMessage,
+        mut assessment: Assessment,
+        provider: &dyn Provider,
+        observer: &dyn Observer,
+    ) -> bool {
+        let Some(model) = self.config.model.as_deref() else {
+            return true;
+        };
+        let reply = match provider
+            .chat_with_system(Some(MODEL_PROMPT), &msg.content, model, 0.0)
+            .await
+        {
+            Ok(reply) => reply,
+            Err(e) => {
+                tracing::warn!("Spam model review failed; allowing message: {e:#}");
+                return true;
+            }
+        };
+        let Some(score) = parse_model_score(&reply) else {
+            tracing::debug!(reply = %reply, "Spam model returned no score");
+            return true;
+        };
+        if score < self.config.threshold {
+            return true;
+        }
+        assessment.score = assessment.score.max(score);
+        assessment.reasons.push(format!("model score {score:.2}"));
+        self.flag(msg, &assessment, observer);
+        false
+    }
+
+    /// The sender's current strikes, re-reading them from disk every
+    /// [`STRIKE_RELOAD`].
+    fn strikes(&self, msg: &ChannelMessage) -> u32 {
+        let mut cache = self.strikes.lock();
+        if cache
+            .loaded_at
+            .is_none_or(|loaded_at| loaded_at.elapsed() >= STRIKE_RELOAD)
+        {
+            match quarantine::all_strikes(&self.workspace_dir) {
+                Ok(counts) => cache.counts = counts,
+                Err(e) => tracing::warn!("Failed to read spam strikes: {e:#}"),
+            }
+            cache.loaded_at = Some(Instant::now());
+        }
+        cache
+            .counts
+            .get(&(msg.channel.clone(), msg.sender.clone()))
+            .map_or(0, |strikes| strikes.current(self.strike_decay()))
+    }
+
+    /// Record the message in the sender's rate window. Returns whether the
+    /// sender is over their limit and whether this repeats their last message.
+    fn track(&self, msg: &ChannelMessage, strikes: u32) -> (bool, bool) {
+        let now = Instant::now();
+        let content = msg.content.trim().to_lowercase();
+        let mut senders = self.senders.lock();
+        if senders.len() >= SENDER_CAPACITY {
+            senders.retain(|_, activity| {
+                activity
+                    .recent
+                    .back()
+                    .is_some_and(|at| now.duration_since(*at) < RATE_WINDOW)
+            });
+        }
+        let activity = senders
+            .entry((msg.channel.clone(), msg.sender.clone()))
+            .or_default();
+        while activity
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            activity.recent.pop_front();
+        }
+
+        let duplicate = !activity.recent.is_empty()
+            && content.chars().count() >= DUPLICATE_MIN_CHARS
+            && activity.last_content.as_deref() == Some(content.as_str());
+        activity.recent.push_back(now);
+        activity.last_content = Some(content);
+
+        let limit = self.config.max_messages_per_minute / strikes.saturating_add(1);
+        let rate_limited = self.config.max_messages_per_minute > 0
+            && activity.recent.len() > usize::try_from(limit.max(1)).unwrap_or(usize::MAX);
+        (rate_limited, duplicate)
+    }
+
+    fn flag(&self, msg: &ChannelMessage, assessment: &Assessment, observer: &dyn Observer) {
+        let strikes = {
+            let mut cache = self.strikes.lock();
+            let entry = cache
+                .counts
+                .entry((msg.channel.clone(), msg.sender.clone()))
+                .or_insert(quarantine::Strikes {
+                    count: 0,
+                    updated_at: chrono::Utc::now(),
+                });
+            *entry = quarantine::Strikes {
+                count: entry.current(self.strike_decay()).saturating_add(1),
+                updated_at: chrono::Utc::now(),
+            };
+            entry.count
+        };
+        if let Err(e) = quarantine::add_strike(
+            &self.workspace_dir,
+            &msg.channel,
+            &msg.sender,
+            self.strike_decay(),
+        ) {
+            tracing::warn!("Failed to record spam strike: {e:#}");
+        }
+        tracing::warn!(
+            channel = %msg.channel,
+            sender = %msg.sender,
+            score = assessment.score,
+            reasons = %assessment.reasons.join(", "),
+            strikes,
+            "Inbound message flagged as spam"
+        );
+        if self.config.action == SpamAction::Quarantine {
+            if let Err(e) = quarantine::quarantine(
+                &self.workspace_dir,
+                &msg.channel,
+                &msg.sender,
+                &msg.content,
+                assessment.score,
+                &assessment.reasons,
+            ) {
+                tracing::warn!("Failed to quarantine message: {e:#}");
+            }
+        }
+        record_event(observer, &msg.channel, "spam");
+    }
+}
+
+fn record_event(observer: &dyn Observer, channel: &str, direction: &str) {
+    observer.record_event(&ObserverEvent::ChannelMessage {
+        channel: channel.to_string(),
+        direction: direction.to_string(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+
+    fn message(sender: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "1".into(),
+            sender: sender.into(),
+            reply_target: sender.into(),
+            content: content.into(),
+            channel: "telegram".into(),
+            timestamp: 0,
+            thread_ts: None,
+            location: None,
+            payload: None,
+        }
+    }
+
+    fn filter(dir: &std::path::Path, config: SpamFilterConfig) -> SpamFilter {
+        SpamFilter::new(
+            &SpamFilterConfig {
+                enabled: true,
+                ..config
+            },
+            ["telegram"],
+            dir.to_path_buf(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn ordinary_messages_score_low() {
+        for content in [
+            "Can you summarize this article for me?",
+            "What's the weather in Berlin tomorrow?",
+            "My keys are lost, any tips for finding them?",
+        ] {
+            let assessment = score_message(content);
+            assert!(assessment.score < 0.3, "{content}: {assessment:?}");
+        }
+    }
+
+    #[test]
+    fn spam_and_abuse_signals_add_up() {
+        let spam = score_message(
+            "CLAIM YOUR FREE MONEY NOW!!! crypto giveaway https://bit.ly/x https://a.example https://b.example",
+        );
+        assert!(spam.score >= 0.7, "{spam:?}");
+        assert!(spam.reasons.iter().any(|r| r == "shortened link"));
+        assert!(spam.reasons.iter().any(|r| r == "3 links"));
+
+        assert!(score_message("just kys already").score >= 0.7);
+    }
+
+    #[test]
+    fn parse_model_score_reads_first_probability() {
+        assert_eq!(parse_model_score("0.92"), Some(0.92));
+        assert_eq!(parse_model_score("Score: 0.1 (benign)"), Some(0.1));
+        assert_eq!(parse_model_score("spam"), None);
+        assert_eq!(parse_model_score("7"), None);
+    }
+
+    #[test]
+    fn filter_is_disabled_without_open_channels() {
+        let config = SpamFilterConfig {
+            enabled: true,
+            ..SpamFilterConfig::default()
+        };
+        assert!(SpamFilter::new(&config, Vec::<String>::new(), PathBuf::new()).is_none());
+        assert!(
+            SpamFilter::new(&SpamFilterConfig::default(), ["telegram"], PathBuf::new()).is_none()
+        );
+    }
+
+    #[test]
+    fn flagged_messages_are_quarantined_and_mute_after_strikes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let filter = filter(
+            tmp.path(),
+            SpamFilterConfig {
+                max_strikes: 2,
+                ..SpamFilterConfig::default()
+            },
+        );
+        let observer = NoopObserver;
+        let spam = "free money giveaway, claim your prize https://bit.ly/x";
+
+        assert_eq!(
+            filter.screen(&message("bob", spam), &observer),
+            Screening::Blocked
+        );
+        let entries = quarantine::list(tmp.path(), Some("telegram"), 10).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].sender, "bob");
+
+        assert_eq!(
+            filter.screen(&message("bob", "hello there"), &observer),
+            Screening::Allow
+        );
+        assert_eq!(
+            filter.screen(&message("bob", spam), &observer),
+            Screening::Blocked
+        );
+        // Two strikes: even clean messages are now dropped.
+        assert_eq!(
+            filter.screen(&message("bob", "hello again"), &observer),
+            Screening::Blocked
+        );
+        assert_eq!(quarantine::list(tmp.path(), None, 10).unwrap().len(), 2);
+
+        // Pardons from the CLI reach the filter on its next reload.
+        quarantine::pardon(tmp.path(), "telegram", "bob").unwrap();
+        assert_eq!(
+            filter.screen(&message("bob", "still muted"), &observer),
+            Screening::Blocked
+        );
+        filter.strikes.lock().loaded_at = None;
+        assert_eq!(
+            filter.screen(&message("bob", "sorry about that"), &observer),
+            Screening::Allow
+        );
+    }
+
+    #[test]
+    fn strikes_tighten_the_rate_limit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let filter = filter(
+            tmp.path(),
+            SpamFilterConfig {
+                max_messages_per_minute: 4,
+                ..SpamFilterConfig::default()
+            },
+        );
+        let observer = NoopObserver;
+        for i in 0..4 {
+            let msg = message("carol", &format!("question {i}"));
+            assert_eq!(filter.screen(&msg, &observer), Screening::Allow);
+        }
+        assert_eq!(
+            filter.screen(&message("carol", "question 5"), &observer),
+            Screening::Blocked
+        );
+
+        // One strike halves the limit for a fresh sender.
+        quarantine::add_strike(tmp.path(), "telegram", "dave", chrono::Duration::hours(1)).unwrap();
+        filter.strikes.lock().loaded_at = None;
+        for i in 0..2 {
+            let msg = message("dave", &format!("question {i}"));
+            assert_eq!(filter.screen(&msg, &observer), Screening::Allow);
+        }
+        assert_eq!(
+            filter.screen(&message("dave", "question 3"), &observer),
+            Screening::Blocked
+        );
+        // Other channels are not screened.
+        let mut discord = message("dave", "free money giveaway claim your prize");
+        discord.channel = "discord".into();
+        assert_eq!(filter.screen(&discord, &observer), Screening::Allow);
+    }
+
+    #[test]
+    fn borderline_messages_go_to_model_review_when_configured() {
+        let tmp = tempfile::tempdir().unwrap();
+        let filter = filter(
+            tmp.path(),
+            SpamFilterConfig {
+                model: Some("cheap-model".into()),
+                ..SpamFilterConfig::default()
+            },
+        );
+        let observer = NoopObserver;
+        match filter.screen(&message("erin", "check out this giveaway"), &observer) {
+            Screening::Review(assessment) => assert!(assessment.score < 0.7),
+            other => panic!("expected review, got {other:?}"),
+        }
+        assert_eq!(
+            filter.screen(&message("erin", "thanks for the help"), &observer),
+            Screening::Allow
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::emoji::{self, EmojiTarget};
+use super::rich_text::{self, RichTextFormat};
+use super::traits::{
+    Channel, ChannelMessage, ContactCard, GeoLocation, MessagePayload, Poll, Reaction, SendMessage,
+    Sticker,
+};
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -16,7 +21,7 @@ const TELEGRAM_MAX_MESSAGE_LENGTH: usize = 4096;
 /// Reserve space for continuation markers added by send_text_chunks:
 /// worst case is "(continued)\n\n" + chunk + "\n\n(continues...)" = 30 extra chars
 const TELEGRAM_CONTINUATION_OVERHEAD: usize = 30;
-const TELEGRAM_ACK_REACTIONS: &[&str] = &["⚡️", "👌", "👀", "🔥", "👍"];
+const TELEGRAM_ACK_REACTIONS: &[&str] = &["⚡", "👌", "👀", "🔥", "👍"];
 
 /// Metadata for an incoming document or photo attachment.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -92,21 +97,8 @@ fn split_message_for_telegram(message: &str) -> Vec<String> {
     chunks
 }
 
-fn pick_uniform_index(len: usize) -> usize {
-    debug_assert!(len > 0);
-    let upper = len as u64;
-    let reject_threshold = (u64::MAX / upper) * upper;
-
-    loop {
-        let value = rand::random::<u64>();
-        if value < reject_threshold {
-            return (value % upper) as usize;
-        }
-    }
-}
-
 fn random_telegram_ack_reaction() -> &'static str {
-    TELEGRAM_ACK_REACTIONS[pick_uniform_index(TELEGRAM_ACK_REACTIONS.len())]
+    emoji::random_from_pool(TELEGRAM_ACK_REACTIONS)
 }
 
 fn build_telegram_ack_reaction_request(
@@ -293,13 +285,26 @@ fn parse_attachment_markers(message: &str) -> (String, Vec<TelegramAttachment>)
 /// Telegram Bot API maximum file download size (20 MB).
 const TELEGRAM_MAX_FILE_DOWNLOAD_BYTES: u64 = 20 * 1024 * 1024;
 
+/// How the listen loop handles one update before any download or API call.
+#[derive(Debug)]
+pub(crate) enum TelegramUpdate {
+    /// In a forum topic outside `allowed_topics`; dropped.
+    FilteredTopic,
+    Reaction(ChannelMessage),
+    /// Inline keyboard press and the callback query id to answer.
+    Callback(ChannelMessage, String),
+    Message(ChannelMessage),
+    /// Voice notes, attachments, and messages from senders that are not
+    /// allowed (or lack a required mention); these need API calls.
+    Unparsed,
+}
+
 /// Telegram channel — long-polls the Bot API for updates
 pub struct TelegramChannel {
     bot_token: String,
     allowed_users: Arc<RwLock<Vec<String>>>,
     pairing: Option<PairingGuard>,
     client: reqwest::Client,
-    typing_handle: Mutex<Option<tokio::task::JoinHandle<()>>>,
     stream_mode: StreamMode,
     draft_update_interval_ms: u64,
     last_draft_edit: Mutex<std::collections::HashMap<String, std::time::Instant>>,
@@ -311,6 +316,50 @@ pub struct TelegramChannel {
     transcription: Option<crate::config::TranscriptionConfig>,
     voice_transcriptions: Mutex<std::collections::HashMap<String, String>>,
     workspace_dir: Option<std::path::PathBuf>,
+    reply_chains: Mutex<ReplyChains>,
+    link_previews: bool,
+    /// `<chat_id>` or `<chat_id>:<topic_id>` entries; empty allows every topic.
+    allowed_topics: Vec<String>,
+}
+
+/// Marker separating a forum topic from a reply-chain root in `thread_ts`.
+const TELEGRAM_REPLY_THREAD_PREFIX: &str = "reply_";
+/// `callback_data` prefix of quick-reply buttons (`followup:<index>`).
+const TELEGRAM_QUICK_REPLY_PREFIX: &str = "followup:";
+/// Maximum message-to-root mappings kept for reply-chain threading.
+const TELEGRAM_REPLY_CHAIN_CAPACITY: usize = 4096;
+
+/// Maps message ids to the root message of their reply chain, per chat, so a
+/// group conversation continued through replies keeps its own context.
+#[derive(Default)]
+struct ReplyChains {
+    roots: std::collections::HashMap<(String, i64), i64>,
+    order: std::collections::VecDeque<(String, i64)>,
+}
+
+impl ReplyChains {
+    /// Resolve the chain root for `message_id` replying to `parent_id`, and remember it.
+    fn resolve(&mut self, chat_id: &str, message_id: i64, parent_id: i64) -> i64 {
+        let root = self
+            .roots
+            .get(&(chat_id.to_string(), parent_id))
+            .copied()
+            .unwrap_or(parent_id);
+        self.record(chat_id, message_id, root);
+        root
+    }
+
+    fn record(&mut self, chat_id: &str, message_id: i64, root: i64) {
+        let key = (chat_id.to_string(), message_id);
+        if self.roots.insert(key.clone(), root).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > TELEGRAM_REPLY_CHAIN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.roots.remove(&oldest);
+            }
+        }
+    }
 }
 
 impl TelegramChannel {
@@ -335,13 +384,15 @@ impl TelegramChannel {
             stream_mode: StreamMode::Off,
             draft_update_interval_ms: 1000,
             last_draft_edit: Mutex::new(std::collections::HashMap::new()),
-            typing_handle: Mutex::new(None),
             mention_only,
             bot_username: Mutex::new(None),
             api_base: "https://api.telegram.org".to_string(),
             transcription: None,
             voice_transcriptions: Mutex::new(std::collections::HashMap::new()),
             workspace_dir: None,
+            reply_chains: Mutex::new(ReplyChains::default()),
+            link_previews: true,
+            allowed_topics: Vec::new(),
         }
     }
 
@@ -362,6 +413,19 @@ impl TelegramChannel {
         self
     }
 
+    /// Show web page previews for links in outgoing messages.
+    pub fn with_link_previews(mut self, enabled: bool) -> Self {
+        self.link_previews = enabled;
+        self
+    }
+
+    /// Only answer in these forum topics (`<chat_id>:<topic_id>`, or
+    /// `<chat_id>` for all topics of a forum). Empty answers in every topic.
+    pub fn with_allowed_topics(mut self, topics: Vec<String>) -> Self {
+        self.allowed_topics = topics;
+        self
+    }
+
     /// Override the Telegram Bot API base URL.
     /// Useful for local Bot API servers or testing.
     pub fn with_api_base(mut self, api_base: String) -> Self {
@@ -386,6 +450,58 @@ impl TelegramChannel {
         }
     }
 
+    /// Forum topic a message was posted in. Replies in ordinary supergroups
+    /// also carry `message_thread_id`, but only forum topics accept it back.
+    fn forum_topic_id(message: &serde_json::Value) -> Option<String> {
+        message
+            .get("is_topic_message")
+            .and_then(serde_json::Value::as_bool)
+            .filter(|is_topic| *is_topic)?;
+        message
+            .get("message_thread_id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())
+    }
+
+    /// Whether `[channels_config.telegram] allowed_topics` lets the bot answer
+    /// `message`. Messages outside forums are always allowed.
+    fn is_topic_allowed(&self, message: &serde_json::Value) -> bool {
+        let chat = message.get("chat");
+        let is_forum = chat
+            .and_then(|chat| chat.get("is_forum"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.allowed_topics.is_empty() || !is_forum {
+            return true;
+        }
+        let Some(chat_id) = chat
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())
+        else {
+            return true;
+        };
+        // Messages in the General topic carry no thread id; its id is 1.
+        let topic = Self::forum_topic_id(message).unwrap_or_else(|| "1".to_string());
+        let topic_target = format!("{chat_id}:{topic}");
+        self.allowed_topics
+            .iter()
+            .any(|allowed| *allowed == chat_id || *allowed == topic_target)
+    }
+
+    /// `sendChatAction` body showing "typing" in the chat or forum topic.
+    fn typing_request(reply_target: &str) -> serde_json::Value {
+        let (chat_id, thread_id) = Self::parse_reply_target(reply_target);
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "action": "typing"
+        });
+        if let Some(tid) = thread_id {
+            body["message_thread_id"] = serde_json::Value::String(tid);
+        }
+        body
+    }
+
     fn extract_update_message_target(update: &serde_json::Value) -> Option<(String, i64)> {
         let message = update.get("message")?;
         let chat_id = message
@@ -637,18 +753,79 @@ impl TelegramChannel {
             .unwrap_or(false)
     }
 
+    /// Conversation thread for an inbound message: the forum topic, plus the
+    /// reply-chain root when a group message replies to another message.
+    fn conversation_thread(
+        &self,
+        message: &serde_json::Value,
+        chat_id: &str,
+        message_id: i64,
+        topic_id: Option<&str>,
+    ) -> Option<String> {
+        let parent_id = message
+            .get("reply_to_message")
+            .and_then(|reply| reply.get("message_id"))
+            .and_then(serde_json::Value::as_i64)
+            // Topic messages implicitly reply to the topic's opening message.
+            .filter(|parent| topic_id != Some(parent.to_string().as_str()));
+
+        let root = match parent_id {
+            Some(parent) if Self::is_group_message(message) => self
+                .reply_chains
+                .lock()
+                .resolve(chat_id, message_id, parent),
+            _ => return topic_id.map(str::to_string),
+        };
+
+        Some(match topic_id {
+            Some(topic) => format!("{topic}:{TELEGRAM_REPLY_THREAD_PREFIX}{root}"),
+            None => format!("{TELEGRAM_REPLY_THREAD_PREFIX}{root}"),
+        })
+    }
+
+    /// Reply-chain root encoded in an outbound `thread_ts`, if any.
+    fn reply_chain_root(thread_ts: Option<&str>) -> Option<i64> {
+        thread_ts?
+            .rsplit_once(TELEGRAM_REPLY_THREAD_PREFIX)?
+            .1
+            .parse()
+            .ok()
+    }
+
+    /// Attach the bot's own messages to the reply chain they answer, so a user
+    /// replying to the bot stays in the same conversation thread.
+    fn record_reply_chain_messages(&self, chat_id: &str, thread_ts: Option<&str>, ids: &[i64]) {
+        let Some(root) = Self::reply_chain_root(thread_ts) else {
+            return;
+        };
+        let mut chains = self.reply_chains.lock();
+        for id in ids {
+            chains.record(chat_id, *id, root);
+        }
+    }
+
     fn is_user_allowed(&self, username: &str) -> bool {
         let identity = Self::normalize_identity(username);
-        self.allowed_users
+        let listed = self
+            .allowed_users
             .read()
             .map(|users| users.iter().any(|u| u == "*" || u == &identity))
-            .unwrap_or(false)
+            .unwrap_or(false);
+        super::access::check("telegram", &identity, listed)
     }
 
+    /// A block on any of the sender's identities (username or id) wins.
     fn is_any_user_allowed<'a, I>(&self, identities: I) -> bool
     where
         I: IntoIterator<Item = &'a str>,
     {
+        let identities: Vec<&str> = identities.into_iter().collect();
+        if identities
+            .iter()
+            .any(|id| super::access::is_blocked("telegram", &Self::normalize_identity(id)))
+        {
+            return false;
+        }
         identities.into_iter().any(|id| self.is_user_allowed(id))
     }
 
@@ -776,16 +953,25 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             sender_id_str.as_deref().unwrap_or("unknown")
         );
 
-        let suggested_identity = normalized_sender_id
-            .clone()
-            .or_else(|| {
-                if normalized_username.is_empty() || normalized_username == "unknown" {
-                    None
-                } else {
-                    Some(normalized_username.clone())
+        let known_identity = normalized_sender_id.clone().or_else(|| {
+            if normalized_username.is_empty() || normalized_username == "unknown" {
+                None
+            } else {
+                Some(normalized_username.clone())
+            }
+        });
+
+        if let Some(identity) = known_identity.as_deref() {
+            if super::pairing::applies_to("telegram") {
+                if let Some(reply) = super::pairing::challenge("telegram", identity, &chat_id).await
+                {
+                    let _ = self.send(&SendMessage::new(reply, &chat_id)).await;
                 }
-            })
-            .unwrap_or_else(|| "YOUR_TELEGRAM_ID".to_string());
+                return;
+            }
+        }
+
+        let suggested_identity = known_identity.unwrap_or_else(|| "YOUR_TELEGRAM_ID".to_string());
 
         let _ = self
             .send(&SendMessage::new(
@@ -948,10 +1134,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             .and_then(serde_json::Value::as_i64)
             .unwrap_or(0);
 
-        let thread_id = message
-            .get("message_thread_id")
-            .and_then(serde_json::Value::as_i64)
-            .map(|id| id.to_string());
+        let thread_id = Self::forum_topic_id(message);
 
         let reply_target = if let Some(ref tid) = thread_id {
             format!("{}:{}", chat_id, tid)
@@ -1031,7 +1214,14 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
-            thread_ts: thread_id,
+            thread_ts: self.conversation_thread(
+                message,
+                &chat_id,
+                message_id,
+                thread_id.as_deref(),
+            ),
+            location: None,
+            payload: None,
         })
     }
 
@@ -1075,10 +1265,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             .and_then(serde_json::Value::as_i64)
             .unwrap_or(0);
 
-        let thread_id = message
-            .get("message_thread_id")
-            .and_then(serde_json::Value::as_i64)
-            .map(|id| id.to_string());
+        let thread_id = Self::forum_topic_id(message);
 
         let reply_target = if let Some(ref tid) = thread_id {
             format!("{}:{}", chat_id, tid)
@@ -1148,7 +1335,14 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
-            thread_ts: thread_id,
+            thread_ts: self.conversation_thread(
+                message,
+                &chat_id,
+                message_id,
+                thread_id.as_deref(),
+            ),
+            location: None,
+            payload: None,
         })
     }
 
@@ -1214,6 +1408,10 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             "[Video]".to_string()
         } else if reply.get("sticker").is_some() {
             "[Sticker]".to_string()
+        } else if let Some(location) = Self::parse_location(reply) {
+            location.to_string()
+        } else if let Some(payload) = Self::parse_payload(reply) {
+            payload.to_string()
         } else {
             "[Message]".to_string()
         };
@@ -1228,6 +1426,26 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Some(format!("> @{reply_sender}:\n{quoted_lines}"))
     }
 
+    /// Classify `update` the way the listen loop does, without downloads or
+    /// API calls. Also used by `zeroclaw channel simulate`.
+    pub(crate) fn classify_update(&self, update: &serde_json::Value) -> TelegramUpdate {
+        let source = update
+            .get("message")
+            .or_else(|| update.pointer("/callback_query/message"));
+        if source.is_some_and(|message| !self.is_topic_allowed(message)) {
+            return TelegramUpdate::FilteredTopic;
+        }
+        if let Some(reaction) = self.parse_reaction_update(update) {
+            return TelegramUpdate::Reaction(reaction);
+        }
+        if let Some((pressed, query_id)) = self.parse_callback_query(update) {
+            return TelegramUpdate::Callback(pressed, query_id);
+        }
+        self.parse_update_message(update)
+            .or_else(|| self.parse_structured_message(update))
+            .map_or(TelegramUpdate::Unparsed, TelegramUpdate::Message)
+    }
+
     fn parse_update_message(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
         let message = update.get("message")?;
 
@@ -1268,10 +1486,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             .unwrap_or(0);
 
         // Extract thread/topic ID for forum support
-        let thread_id = message
-            .get("message_thread_id")
-            .and_then(serde_json::Value::as_i64)
-            .map(|id| id.to_string());
+        let thread_id = Self::forum_topic_id(message);
 
         // reply_target: chat_id or chat_id:thread_id format
         let reply_target = if let Some(ref tid) = thread_id {
@@ -1300,11 +1515,340 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             reply_target,
             content,
             channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: self.conversation_thread(
+                message,
+                &chat_id,
+                message_id,
+                thread_id.as_deref(),
+            ),
+            location: None,
+            payload: None,
+        })
+    }
+
+    /// Extract the point shared by a `location` or `venue` message.
+    fn parse_location(message: &serde_json::Value) -> Option<GeoLocation> {
+        let venue = message.get("venue");
+        let point = venue
+            .and_then(|venue| venue.get("location"))
+            .or_else(|| message.get("location"))?;
+        let latitude = point.get("latitude").and_then(serde_json::Value::as_f64)?;
+        let longitude = point.get("longitude").and_then(serde_json::Value::as_f64)?;
+        let label = venue.map(|venue| {
+            ["title", "address"]
+                .iter()
+                .filter_map(|key| venue.get(*key).and_then(serde_json::Value::as_str))
+                .filter(|part| !part.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+        GeoLocation::new(latitude, longitude).map(|location| location.with_label(label))
+    }
+
+    /// Extract a contact card, poll, or sticker.
+    fn parse_payload(message: &serde_json::Value) -> Option<MessagePayload> {
+        let text = |value: &serde_json::Value, key: &str| {
+            value
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+        };
+
+        if let Some(contact) = message.get("contact") {
+            let name = [text(contact, "first_name"), text(contact, "last_name")]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            return Some(MessagePayload::Contact(ContactCard {
+                name,
+                phones: text(contact, "phone_number").into_iter().collect(),
+                emails: Vec::new(),
+            }));
+        }
+
+        if let Some(poll) = message.get("poll") {
+            let options = poll
+                .get("options")
+                .and_then(serde_json::Value::as_array)
+                .map(|options| {
+                    options
+                        .iter()
+                        .filter_map(|option| text(option, "text"))
+                        .collect()
+                })
+                .unwrap_or_default();
+            return Some(MessagePayload::Poll(Poll {
+                question: text(poll, "question")?,
+                options,
+                allows_multiple_answers: poll
+                    .get("allows_multiple_answers")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false),
+            }));
+        }
+
+        let sticker = message.get("sticker")?;
+        Some(MessagePayload::Sticker(Sticker {
+            emoji: text(sticker, "emoji"),
+            name: text(sticker, "set_name"),
+        }))
+    }
+
+    /// Parse a location, venue, contact, poll, or sticker message into a text
+    /// marker plus its structured form. Mention-only groups skip these, since
+    /// they cannot mention the bot.
+    fn parse_structured_message(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
+        let message = update.get("message")?;
+        let location = Self::parse_location(message);
+        let payload = if location.is_none() {
+            Self::parse_payload(message)
+        } else {
+            None
+        };
+        let marker = match (&location, &payload) {
+            (Some(location), _) => location.to_string(),
+            (None, Some(payload)) => payload.to_string(),
+            (None, None) => return None,
+        };
+
+        let (username, sender_id, sender_identity) = Self::extract_sender_info(message);
+
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+
+        if self.mention_only && Self::is_group_message(message) {
+            return None;
+        }
+
+        let chat_id = message
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())?;
+
+        let message_id = message
+            .get("message_id")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(0);
+
+        let thread_id = Self::forum_topic_id(message);
+
+        let reply_target = if let Some(ref tid) = thread_id {
+            format!("{}:{}", chat_id, tid)
+        } else {
+            chat_id.clone()
+        };
+
+        let content = if let Some(quote) = self.extract_reply_context(message) {
+            format!("{quote}\n\n{marker}")
+        } else {
+            marker
+        };
+
+        Some(ChannelMessage {
+            id: format!("telegram_{chat_id}_{message_id}"),
+            sender: sender_identity,
+            reply_target,
+            content,
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: self.conversation_thread(
+                message,
+                &chat_id,
+                message_id,
+                thread_id.as_deref(),
+            ),
+            location,
+            payload,
+        })
+    }
+
+    /// Inline keyboard with one button per row for quick replies.
+    fn quick_reply_markup(buttons: &[String]) -> Option<serde_json::Value> {
+        if buttons.is_empty() {
+            return None;
+        }
+        let rows: Vec<serde_json::Value> = buttons
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                serde_json::json!([{
+                    "text": label,
+                    "callback_data": format!("{TELEGRAM_QUICK_REPLY_PREFIX}{index}"),
+                }])
+            })
+            .collect();
+        Some(serde_json::json!({ "inline_keyboard": rows }))
+    }
+
+    /// Build a message from a quick-reply button press. The button's label,
+    /// read back from the keyboard on the pressed message, becomes the text.
+    /// Returns the message and the callback query id to acknowledge.
+    fn parse_callback_query(&self, update: &serde_json::Value) -> Option<(ChannelMessage, String)> {
+        let query = update.get("callback_query")?;
+        let query_id = query.get("id").and_then(serde_json::Value::as_str)?;
+        let data = query.get("data").and_then(serde_json::Value::as_str)?;
+        if !data.starts_with(TELEGRAM_QUICK_REPLY_PREFIX) {
+            return None;
+        }
+        let (username, sender_id, sender_identity) =
+            Self::extract_sender_info(&serde_json::json!({ "from": query.get("from")? }));
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+
+        let message = query.get("message")?;
+        let label = message
+            .pointer("/reply_markup/inline_keyboard")
+            .and_then(serde_json::Value::as_array)?
+            .iter()
+            .filter_map(serde_json::Value::as_array)
+            .flatten()
+            .find(|button| {
+                button
+                    .get("callback_data")
+                    .and_then(serde_json::Value::as_str)
+                    == Some(data)
+            })
+            .and_then(|button| button.get("text"))
+            .and_then(serde_json::Value::as_str)?;
+        let chat_id = message
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)?
+            .to_string();
+        let message_id = message
+            .get("message_id")
+            .and_then(serde_json::Value::as_i64)?;
+        let thread_id = Self::forum_topic_id(message);
+        let reply_target = match &thread_id {
+            Some(tid) => format!("{chat_id}:{tid}"),
+            None => chat_id.clone(),
+        };
+
+        let msg = ChannelMessage {
+            id: format!("telegram_{chat_id}_{message_id}"),
+            sender: sender_identity,
+            reply_target,
+            content: label.to_string(),
+            channel: "telegram".to_string(),
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            location: None,
+            payload: None,
+        };
+        Some((msg, query_id.to_string()))
+    }
+
+    /// Stop the loading indicator on a pressed button.
+    async fn answer_callback_query(&self, query_id: &str) {
+        let result = self
+            .http_client()
+            .post(self.api_url("answerCallbackQuery"))
+            .json(&serde_json::json!({ "callback_query_id": query_id }))
+            .send()
+            .await;
+        if let Err(e) = result {
+            tracing::debug!("Telegram answerCallbackQuery failed: {e}");
+        }
+    }
+
+    /// Build a message from a `message_reaction` update. Telegram sends the
+    /// user's full reaction set, so the change is the first emoji added or,
+    /// failing that, the first one removed. Custom emoji are ignored.
+    fn parse_reaction_update(&self, update: &serde_json::Value) -> Option<ChannelMessage> {
+        let event = update.get("message_reaction")?;
+        let user = event.get("user")?;
+        if user
+            .get("is_bot")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            return None;
+        }
+        let (username, sender_id, sender_identity) =
+            Self::extract_sender_info(&serde_json::json!({ "from": user }));
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+
+        let emojis = |key: &str| -> Vec<String> {
+            event
+                .get(key)
+                .and_then(serde_json::Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter(|item| {
+                            item.get("type").and_then(serde_json::Value::as_str) == Some("emoji")
+                        })
+                        .filter_map(|item| item.get("emoji").and_then(serde_json::Value::as_str))
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let old = emojis("old_reaction");
+        let new = emojis("new_reaction");
+        let (emoji, removed) = match new.iter().find(|emoji| !old.contains(emoji)) {
+            Some(added) => (added.clone(), false),
+            None => (old.iter().find(|emoji| !new.contains(emoji))?.clone(), true),
+        };
+
+        let chat_id = event
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)?
+            .to_string();
+        let message_id = event
+            .get("message_id")
+            .and_then(serde_json::Value::as_i64)?;
+        let payload = MessagePayload::Reaction(Reaction {
+            message_id: message_id.to_string(),
+            emoji,
+            removed,
+        });
+
+        Some(ChannelMessage {
+            id: format!("telegram_{chat_id}_{message_id}"),
+            sender: sender_identity,
+            reply_target: chat_id,
+            content: payload.to_string(),
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+            location: None,
+            payload: Some(payload),
         })
     }
 
@@ -1354,173 +1898,47 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         Ok(format!("data:image/jpeg;base64,{}", b64))
     }
 
-    /// Convert Markdown to Telegram HTML format.
-    /// Telegram HTML supports: <b>, <i>, <u>, <s>, <code>, <pre>, <a href="...">
-    /// This mirrors OpenClaw's markdownToTelegramHtml approach.
+    /// Convert Markdown to Telegram HTML (`parse_mode = "HTML"`).
     fn markdown_to_telegram_html(text: &str) -> String {
-        let lines: Vec<&str> = text.split('\n').collect();
-        let mut result_lines: Vec<String> = Vec::new();
-
-        for line in &lines {
-            let trimmed_line = line.trim_start();
-            if trimmed_line.starts_with("```") {
-                // Preserve fence lines so the second-pass block parser can consume them
-                // without interference from inline backtick handling.
-                result_lines.push(trimmed_line.to_string());
-                continue;
-            }
-
-            let mut line_out = String::new();
-
-            // Handle code blocks (``` ... ```) - handled at text level below
-            // Handle headers: ## Title → <b>Title</b>
-            let stripped = line.trim_start_matches('#');
-            let header_level = line.len() - stripped.len();
-            if header_level > 0 && line.starts_with('#') && stripped.starts_with(' ') {
-                let title = Self::escape_html(stripped.trim());
-                result_lines.push(format!("<b>{title}</b>"));
-                continue;
-            }
-
-            // Inline formatting
-            let mut i = 0;
-            let bytes = line.as_bytes();
-            let len = bytes.len();
-            while i < len {
-                // Bold: **text** or __text__
-                if i + 1 < len && bytes[i] == b'*' && bytes[i + 1] == b'*' {
-                    if let Some(end) = line[i + 2..].find("**") {
-                        let inner = Self::escape_html(&line[i + 2..i + 2 + end]);
-                        line_out.push_str(&format!("<b>{inner}</b>"));
-                        i += 4 + end;
-                        continue;
-                    }
-                }
-                if i + 1 < len && bytes[i] == b'_' && bytes[i + 1] == b'_' {
-                    if let Some(end) = line[i + 2..].find("__") {
-                        let inner = Self::escape_html(&line[i + 2..i + 2 + end]);
-                        line_out.push_str(&format!("<b>{inner}</b>"));
-                        i += 4 + end;
-                        continue;
-                    }
-                }
-                // Italic: *text* or _text_ (single)
-                if bytes[i] == b'*' && (i == 0 || bytes[i - 1] != b'*') {
-                    if let Some(end) = line[i + 1..].find('*') {
-                        if end > 0 {
-                            let inner = Self::escape_html(&line[i + 1..i + 1 + end]);
-                            line_out.push_str(&format!("<i>{inner}</i>"));
-                            i += 2 + end;
-                            continue;
-                        }
-                    }
-                }
-                // Inline code: `code`
-                if bytes[i] == b'`' && (i == 0 || bytes[i - 1] != b'`') {
-                    if let Some(end) = line[i + 1..].find('`') {
-                        let inner = Self::escape_html(&line[i + 1..i + 1 + end]);
-                        line_out.push_str(&format!("<code>{inner}</code>"));
-                        i += 2 + end;
-                        continue;
-                    }
-                }
-                // Markdown link: [text](url)
-                if bytes[i] == b'[' {
-                    if let Some(bracket_end) = line[i + 1..].find(']') {
-                        let text_part = &line[i + 1..i + 1 + bracket_end];
-                        let after_bracket = i + 1 + bracket_end + 1; // position after ']'
-                        if after_bracket < len && bytes[after_bracket] == b'(' {
-                            if let Some(paren_end) = line[after_bracket + 1..].find(')') {
-                                let url = &line[after_bracket + 1..after_bracket + 1 + paren_end];
-                                if url.starts_with("http://") || url.starts_with("https://") {
-                                    let text_html = Self::escape_html(text_part);
-                                    let url_html = Self::escape_html(url);
-                                    line_out.push_str(&format!(
-                                        "<a href=\"{url_html}\">{text_html}</a>"
-                                    ));
-                                    i = after_bracket + 1 + paren_end + 1;
-                                    continue;
-                                }
-                            }
-                        }
-                    }
-                }
-                // Strikethrough: ~~text~~
-                if i + 1 < len && bytes[i] == b'~' && bytes[i + 1] == b'~' {
-                    if let Some(end) = line[i + 2..].find("~~") {
-                        let inner = Self::escape_html(&line[i + 2..i + 2 + end]);
-                        line_out.push_str(&format!("<s>{inner}</s>"));
-                        i += 4 + end;
-                        continue;
-                    }
-                }
-                // Default: escape HTML entities
-                let ch = line[i..].chars().next().unwrap();
-                match ch {
-                    '<' => line_out.push_str("&lt;"),
-                    '>' => line_out.push_str("&gt;"),
-                    '&' => line_out.push_str("&amp;"),
-                    '"' => line_out.push_str("&quot;"),
-                    '\'' => line_out.push_str("&#39;"),
-                    _ => line_out.push(ch),
-                }
-                i += ch.len_utf8();
-            }
-            result_lines.push(line_out);
-        }
-
-        // Second pass: handle ``` code blocks across lines
-        let joined = result_lines.join("\n");
-        let mut final_out = String::with_capacity(joined.len());
-        let mut in_code_block = false;
-        let mut code_buf = String::new();
-
-        for line in joined.split('\n') {
-            let trimmed = line.trim();
-            if trimmed.starts_with("```") {
-                if !in_code_block {
-                    in_code_block = true;
-                    code_buf.clear();
-                } else {
-                    in_code_block = false;
-                    let escaped = code_buf.trim_end_matches('\n');
-                    // Telegram HTML parse mode supports <pre> and <code>, but not class attributes.
-                    final_out.push_str(&format!("<pre><code>{escaped}</code></pre>\n"));
-                    code_buf.clear();
-                }
-            } else if in_code_block {
-                code_buf.push_str(line);
-                code_buf.push('\n');
-            } else {
-                final_out.push_str(line);
-                final_out.push('\n');
-            }
-        }
-        if in_code_block && !code_buf.is_empty() {
-            final_out.push_str(&format!(
-                "<pre><code>{}</code></pre>\n",
-                code_buf.trim_end()
-            ));
-        }
+        rich_text::render(text, RichTextFormat::TelegramHtml)
+    }
 
-        final_out.trim_end_matches('\n').to_string()
+    async fn send_text_chunks(
+        &self,
+        message: &str,
+        chat_id: &str,
+        thread_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.send_text_chunks_with_ids(message, chat_id, thread_id, None)
+            .await
+            .map(|_| ())
     }
 
-    fn escape_html(s: &str) -> String {
-        s.replace('&', "&amp;")
-            .replace('<', "&lt;")
-            .replace('>', "&gt;")
-            .replace('"', "&quot;")
-            .replace('\'', "&#39;")
+    /// Send text in Telegram-sized chunks, returning the ids of the sent messages.
+    /// The first chunk quotes `quote_id` when set.
+    async fn send_text_chunks_with_ids(
+        &self,
+        message: &str,
+        chat_id: &str,
+        thread_id: Option<&str>,
+        quote_id: Option<i64>,
+    ) -> anyhow::Result<Vec<i64>> {
+        self.send_text_chunks_with_markup(message, chat_id, thread_id, quote_id, None)
+            .await
     }
 
-    async fn send_text_chunks(
+    /// Like [`Self::send_text_chunks_with_ids`], attaching `reply_markup` to
+    /// the last chunk.
+    async fn send_text_chunks_with_markup(
         &self,
         message: &str,
         chat_id: &str,
         thread_id: Option<&str>,
-    ) -> anyhow::Result<()> {
+        quote_id: Option<i64>,
+        reply_markup: Option<&serde_json::Value>,
+    ) -> anyhow::Result<Vec<i64>> {
         let chunks = split_message_for_telegram(message);
+        let mut sent_ids = Vec::with_capacity(chunks.len());
 
         for (index, chunk) in chunks.iter().enumerate() {
             let text = if chunks.len() > 1 {
@@ -1545,6 +1963,13 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             if let Some(tid) = thread_id {
                 markdown_body["message_thread_id"] = serde_json::Value::String(tid.to_string());
             }
+            if let Some(reply_id) = quote_id.filter(|_| index == 0) {
+                markdown_body["reply_parameters"] = Self::reply_parameters(reply_id);
+            }
+            if let Some(markup) = reply_markup.filter(|_| index == chunks.len() - 1) {
+                markdown_body["reply_markup"] = markup.clone();
+            }
+            self.apply_link_preview_options(&mut markdown_body);
 
             let markdown_resp = self
                 .http_client()
@@ -1554,6 +1979,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .await?;
 
             if markdown_resp.status().is_success() {
+                sent_ids.extend(Self::sent_message_id(markdown_resp).await);
                 if index < chunks.len() - 1 {
                     tokio::time::sleep(Duration::from_millis(100)).await;
                 }
@@ -1576,6 +2002,13 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             if let Some(tid) = thread_id {
                 plain_body["message_thread_id"] = serde_json::Value::String(tid.to_string());
             }
+            if let Some(reply_id) = quote_id.filter(|_| index == 0) {
+                plain_body["reply_parameters"] = Self::reply_parameters(reply_id);
+            }
+            if let Some(markup) = reply_markup.filter(|_| index == chunks.len() - 1) {
+                plain_body["reply_markup"] = markup.clone();
+            }
+            self.apply_link_preview_options(&mut plain_body);
             let plain_resp = self
                 .http_client()
                 .post(self.api_url("sendMessage"))
@@ -1594,13 +2027,84 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                     plain_err
                 );
             }
+            sent_ids.extend(Self::sent_message_id(plain_resp).await);
+
+            if index < chunks.len() - 1 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+
+        Ok(sent_ids)
+    }
+
+    /// Native message id of the inbound message a response replies to.
+    /// Accepts both `telegram_{chat}_{message}` ids and bare message ids.
+    fn reply_to_native_id(message: &SendMessage) -> Option<i64> {
+        Self::native_message_id(message.in_reply_to_id.as_deref()?)
+    }
+
+    /// Telegram message id from a `telegram_<chat>_<id>` channel id or a bare id.
+    fn native_message_id(id: &str) -> Option<i64> {
+        let raw = match id.strip_prefix("telegram_") {
+            Some(rest) => rest.rsplit_once('_')?.1,
+            None => id,
+        };
+        raw.parse().ok()
+    }
+
+    /// Replace the bot's reaction on a message; `None` clears it. Telegram
+    /// keeps one reaction per bot, so there is no per-emoji removal.
+    async fn set_message_reaction(
+        &self,
+        reply_target: &str,
+        message_id: &str,
+        emoji: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let (chat_id, _) = Self::parse_reply_target(reply_target);
+        let native_id = Self::native_message_id(message_id)
+            .ok_or_else(|| anyhow::anyhow!("Invalid Telegram message id: {message_id}"))?;
+        let body = match emoji {
+            Some(emoji) => build_telegram_ack_reaction_request(&chat_id, native_id, emoji),
+            None => serde_json::json!({
+                "chat_id": chat_id,
+                "message_id": native_id,
+                "reaction": []
+            }),
+        };
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("setMessageReaction"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram setMessageReaction failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
+    /// `reply_parameters` quoting `message_id`, falling back to a plain send if it was deleted.
+    fn reply_parameters(message_id: i64) -> serde_json::Value {
+        serde_json::json!({
+            "message_id": message_id,
+            "allow_sending_without_reply": true
+        })
+    }
 
-            if index < chunks.len() - 1 {
-                tokio::time::sleep(Duration::from_millis(100)).await;
-            }
+    /// Turn off web page previews on a `sendMessage`/`editMessageText` body when configured.
+    fn apply_link_preview_options(&self, body: &mut serde_json::Value) {
+        if !self.link_previews {
+            body["link_preview_options"] = serde_json::json!({ "is_disabled": true });
         }
+    }
 
-        Ok(())
+    /// Extract `result.message_id` from a successful Bot API send response.
+    async fn sent_message_id(resp: reqwest::Response) -> Option<i64> {
+        let body: serde_json::Value = resp.json().await.ok()?;
+        body.get("result")?.get("message_id")?.as_i64()
     }
 
     async fn send_media_by_url(
@@ -2166,6 +2670,10 @@ impl Channel for TelegramChannel {
         if let Some(tid) = thread_id {
             body["message_thread_id"] = serde_json::Value::String(tid.to_string());
         }
+        if let Some(reply_id) = Self::reply_to_native_id(message) {
+            body["reply_parameters"] = Self::reply_parameters(reply_id);
+        }
+        self.apply_link_preview_options(&mut body);
 
         let resp = self
             .client
@@ -2183,8 +2691,11 @@ impl Channel for TelegramChannel {
         let message_id = resp_json
             .get("result")
             .and_then(|r| r.get("message_id"))
-            .and_then(|id| id.as_i64())
-            .map(|id| id.to_string());
+            .and_then(|id| id.as_i64());
+        if let Some(id) = message_id {
+            self.record_reply_chain_messages(&chat_id, message.thread_ts.as_deref(), &[id]);
+        }
+        let message_id = message_id.map(|id| id.to_string());
 
         self.last_draft_edit
             .lock()
@@ -2235,11 +2746,12 @@ impl Channel for TelegramChannel {
             }
         };
 
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "chat_id": chat_id,
             "message_id": message_id_parsed,
             "text": display_text,
         });
+        self.apply_link_preview_options(&mut body);
 
         let resp = self
             .client
@@ -2343,12 +2855,13 @@ impl Channel for TelegramChannel {
         };
 
         // Try editing with HTML formatting
-        let body = serde_json::json!({
+        let mut body = serde_json::json!({
             "chat_id": chat_id,
             "message_id": id,
             "text": Self::markdown_to_telegram_html(text),
             "parse_mode": "HTML",
         });
+        self.apply_link_preview_options(&mut body);
 
         let resp = self
             .client
@@ -2362,11 +2875,12 @@ impl Channel for TelegramChannel {
         }
 
         // Markdown failed — retry without parse_mode
-        let plain_body = serde_json::json!({
+        let mut plain_body = serde_json::json!({
             "chat_id": chat_id,
             "message_id": id,
             "text": text,
         });
+        self.apply_link_preview_options(&mut plain_body);
 
         let resp = self
             .client
@@ -2389,13 +2903,21 @@ impl Channel for TelegramChannel {
         let (chat_id, _) = Self::parse_reply_target(recipient);
         self.last_draft_edit.lock().remove(&chat_id);
 
-        let message_id = match message_id.parse::<i64>() {
-            Ok(id) => id,
-            Err(e) => {
-                tracing::debug!("Invalid Telegram draft message_id '{message_id}': {e}");
-                return Ok(());
-            }
-        };
+        if let Err(e) = self.delete_message(recipient, message_id).await {
+            tracing::debug!("Failed to remove Telegram draft: {e}");
+        }
+        Ok(())
+    }
+
+    fn supports_message_deletion(&self) -> bool {
+        true
+    }
+
+    async fn delete_message(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        let (chat_id, _) = Self::parse_reply_target(recipient);
+        let message_id = message_id
+            .parse::<i64>()
+            .map_err(|e| anyhow::anyhow!("Invalid Telegram message_id '{message_id}': {e}"))?;
 
         let response = self
             .client
@@ -2410,13 +2932,23 @@ impl Channel for TelegramChannel {
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
-            tracing::debug!("Telegram deleteMessage failed ({status}): {body}");
+            anyhow::bail!("Telegram deleteMessage failed ({status}): {body}");
         }
 
         Ok(())
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.send_tracked(message).await.map(|_| ())
+    }
+
+    fn supports_buttons(&self) -> bool {
+        true
+    }
+
+    /// Returns the id of the first text message sent, which reaction
+    /// updates refer to. Attachment-only sends return `None`.
+    async fn send_tracked(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         // Strip tool_call tags before processing to prevent Markdown parsing failures
         let content = strip_tool_call_tags(&message.content);
 
@@ -2438,16 +2970,26 @@ impl Channel for TelegramChannel {
                 self.send_attachment(chat_id, thread_id, attachment).await?;
             }
 
-            return Ok(());
+            return Ok(None);
         }
 
         if let Some(attachment) = parse_path_only_attachment(&content) {
             self.send_attachment(chat_id, thread_id, &attachment)
                 .await?;
-            return Ok(());
+            return Ok(None);
         }
 
-        self.send_text_chunks(&content, chat_id, thread_id).await
+        let sent_ids = self
+            .send_text_chunks_with_markup(
+                &content,
+                chat_id,
+                thread_id,
+                Self::reply_to_native_id(message),
+                Self::quick_reply_markup(&message.buttons).as_ref(),
+            )
+            .await?;
+        self.record_reply_chain_messages(chat_id, message.thread_ts.as_deref(), &sent_ids);
+        Ok(sent_ids.first().map(ToString::to_string))
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -2469,7 +3011,7 @@ impl Channel for TelegramChannel {
             let probe = serde_json::json!({
                 "offset": offset,
                 "timeout": 0,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "message_reaction", "callback_query"]
             });
             match self.http_client().post(&url).json(&probe).send().await {
                 Err(e) => {
@@ -2542,7 +3084,7 @@ impl Channel for TelegramChannel {
             let body = serde_json::json!({
                 "offset": offset,
                 "timeout": 30,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "message_reaction", "callback_query"]
             });
 
             let resp = match self.http_client().post(&url).json(&body).send().await {
@@ -2603,15 +3145,33 @@ Ensure only one `zeroclaw` process is using this bot token."
                         offset = uid + 1;
                     }
 
-                    let msg = if let Some(m) = self.parse_update_message(update) {
-                        m
-                    } else if let Some(m) = self.try_parse_voice_message(update).await {
-                        m
-                    } else if let Some(m) = self.try_parse_attachment_message(update).await {
-                        m
-                    } else {
-                        self.handle_unauthorized_message(update).await;
-                        continue;
+                    let msg = match self.classify_update(update) {
+                        TelegramUpdate::FilteredTopic => continue,
+                        TelegramUpdate::Reaction(reaction) => {
+                            if tx.send(reaction).await.is_err() {
+                                return Ok(());
+                            }
+                            continue;
+                        }
+                        TelegramUpdate::Callback(pressed, query_id) => {
+                            self.answer_callback_query(&query_id).await;
+                            if tx.send(pressed).await.is_err() {
+                                return Ok(());
+                            }
+                            continue;
+                        }
+                        TelegramUpdate::Message(m) => m,
+                        TelegramUpdate::Unparsed => {
+                            if let Some(m) = self.try_parse_voice_message(update).await {
+                                m
+                            } else if let Some(m) = self.try_parse_attachment_message(update).await
+                            {
+                                m
+                            } else {
+                                Box::pin(self.handle_unauthorized_message(update)).await;
+                                continue;
+                            }
+                        }
                     };
 
                     if let Some((reaction_chat_id, reaction_message_id)) =
@@ -2624,10 +3184,7 @@ Ensure only one `zeroclaw` process is using this bot token."
                     }
 
                     // Send "typing" indicator immediately when we receive a message
-                    let typing_body = serde_json::json!({
-                        "chat_id": &msg.reply_target,
-                        "action": "typing"
-                    });
+                    let typing_body = Self::typing_request(&msg.reply_target);
                     let _ = self
                         .http_client()
                         .post(self.api_url("sendChatAction"))
@@ -2664,38 +3221,49 @@ Ensure only one `zeroclaw` process is using this bot token."
         }
     }
 
-    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
-        self.stop_typing(recipient).await?;
-
-        let client = self.http_client();
-        let url = self.api_url("sendChatAction");
-        let chat_id = recipient.to_string();
-
-        let handle = tokio::spawn(async move {
-            loop {
-                let body = serde_json::json!({
-                    "chat_id": &chat_id,
-                    "action": "typing"
-                });
-                let _ = client.post(&url).json(&body).send().await;
-                // Telegram typing indicator expires after 5s; refresh at 4s
-                tokio::time::sleep(Duration::from_secs(4)).await;
-            }
-        });
-
-        let mut guard = self.typing_handle.lock();
-        *guard = Some(handle);
-
-        Ok(())
+    /// Telegram clears the indicator after 5 seconds.
+    fn typing_refresh_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(4))
     }
 
-    async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
-        let mut guard = self.typing_handle.lock();
-        if let Some(handle) = guard.take() {
-            handle.abort();
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        let body = Self::typing_request(recipient);
+        let resp = self
+            .http_client()
+            .post(self.api_url("sendChatAction"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Telegram sendChatAction failed ({})", resp.status());
         }
         Ok(())
     }
+
+    async fn add_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emoji: &str,
+    ) -> anyhow::Result<()> {
+        // Bots may only use Telegram's fixed reaction set.
+        let Some(reaction) = emoji::for_target(emoji, EmojiTarget::TelegramReaction) else {
+            tracing::debug!("Telegram: no allowed reaction close to {emoji}; skipping");
+            return Ok(());
+        };
+        self.set_message_reaction(channel_id, message_id, Some(&reaction))
+            .await
+    }
+
+    async fn remove_reaction(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        _emoji: &str,
+    ) -> anyhow::Result<()> {
+        self.set_message_reaction(channel_id, message_id, None)
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -2708,6 +3276,29 @@ mod tests {
         assert_eq!(ch.name(), "telegram");
     }
 
+    #[test]
+    fn telegram_ack_reactions_are_allowed_reactions() {
+        for reaction in TELEGRAM_ACK_REACTIONS {
+            assert_eq!(
+                emoji::for_target(reaction, EmojiTarget::TelegramReaction).as_deref(),
+                Some(*reaction)
+            );
+        }
+    }
+
+    #[test]
+    fn telegram_link_previews_can_be_disabled() {
+        let mut body = serde_json::json!({ "chat_id": "1", "text": "https://example.com" });
+        TelegramChannel::new("fake-token".into(), vec!["*".into()], false)
+            .apply_link_preview_options(&mut body);
+        assert!(body.get("link_preview_options").is_none());
+
+        TelegramChannel::new("fake-token".into(), vec!["*".into()], false)
+            .with_link_previews(false)
+            .apply_link_preview_options(&mut body);
+        assert_eq!(body["link_preview_options"]["is_disabled"], true);
+    }
+
     #[test]
     fn random_telegram_ack_reaction_is_from_pool() {
         for _ in 0..128 {
@@ -2740,48 +3331,11 @@ mod tests {
     }
 
     #[test]
-    fn typing_handle_starts_as_none() {
-        let ch = TelegramChannel::new("fake-token".into(), vec!["*".into()], false);
-        let guard = ch.typing_handle.lock();
-        assert!(guard.is_none());
-    }
-
-    #[tokio::test]
-    async fn stop_typing_clears_handle() {
-        let ch = TelegramChannel::new("fake-token".into(), vec!["*".into()], false);
-
-        // Manually insert a dummy handle
-        {
-            let mut guard = ch.typing_handle.lock();
-            *guard = Some(tokio::spawn(async {
-                tokio::time::sleep(Duration::from_secs(60)).await;
-            }));
-        }
-
-        // stop_typing should abort and clear
-        ch.stop_typing("123").await.unwrap();
-
-        let guard = ch.typing_handle.lock();
-        assert!(guard.is_none());
-    }
-
-    #[tokio::test]
-    async fn start_typing_replaces_previous_handle() {
+    fn typing_refreshes_before_telegram_expires_it() {
         let ch = TelegramChannel::new("fake-token".into(), vec!["*".into()], false);
-
-        // Insert a dummy handle first
-        {
-            let mut guard = ch.typing_handle.lock();
-            *guard = Some(tokio::spawn(async {
-                tokio::time::sleep(Duration::from_secs(60)).await;
-            }));
-        }
-
-        // start_typing should abort the old handle and set a new one
-        let _ = ch.start_typing("123").await;
-
-        let guard = ch.typing_handle.lock();
-        assert!(guard.is_some());
+        assert!(ch
+            .typing_refresh_interval()
+            .is_some_and(|interval| interval < Duration::from_secs(5)));
     }
 
     #[test]
@@ -3097,9 +3651,12 @@ mod tests {
                     "username": "alice"
                 },
                 "chat": {
-                    "id": -100_200_300
+                    "id": -100_200_300,
+                    "type": "supergroup",
+                    "is_forum": true
                 },
-                "message_thread_id": 789
+                "message_thread_id": 789,
+                "is_topic_message": true
             }
         });
 
@@ -3111,6 +3668,144 @@ mod tests {
         assert_eq!(msg.reply_target, "-100200300:789");
         assert_eq!(msg.content, "hello from topic");
         assert_eq!(msg.id, "telegram_-100200300_42");
+        assert_eq!(msg.thread_ts.as_deref(), Some("789"));
+    }
+
+    #[test]
+    fn reply_to_native_id_accepts_channel_and_bare_ids() {
+        let reply =
+            |id: &str| SendMessage::new("hi", "-100200300").replying_to(Some(id.to_string()));
+        assert_eq!(
+            TelegramChannel::reply_to_native_id(&reply("telegram_-100200300_42")),
+            Some(42)
+        );
+        assert_eq!(TelegramChannel::reply_to_native_id(&reply("42")), Some(42));
+        assert_eq!(
+            TelegramChannel::reply_to_native_id(&reply("not-a-telegram-id")),
+            None
+        );
+        assert_eq!(
+            TelegramChannel::reply_to_native_id(&SendMessage::new("hi", "1")),
+            None
+        );
+    }
+
+    fn group_update(message_id: i64, parent_id: Option<i64>) -> serde_json::Value {
+        let mut message = serde_json::json!({
+            "message_id": message_id,
+            "text": "hello group",
+            "from": { "id": 555, "username": "alice" },
+            "chat": { "id": -100_200_300, "type": "supergroup" }
+        });
+        if let Some(parent) = parent_id {
+            message["reply_to_message"] = serde_json::json!({ "message_id": parent });
+        }
+        serde_json::json!({ "update_id": message_id, "message": message })
+    }
+
+    #[test]
+    fn parse_update_message_threads_group_reply_chains() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+
+        let top = ch.parse_update_message(&group_update(10, None)).unwrap();
+        assert_eq!(top.thread_ts, None);
+
+        let reply = ch
+            .parse_update_message(&group_update(11, Some(10)))
+            .unwrap();
+        assert_eq!(reply.thread_ts.as_deref(), Some("reply_10"));
+
+        // Replying to a reply stays on the chain's root.
+        let nested = ch
+            .parse_update_message(&group_update(12, Some(11)))
+            .unwrap();
+        assert_eq!(nested.thread_ts.as_deref(), Some("reply_10"));
+
+        // Replies to the bot's answer join the chain the answer was sent to.
+        ch.record_reply_chain_messages("-100200300", reply.thread_ts.as_deref(), &[50]);
+        let to_bot = ch
+            .parse_update_message(&group_update(13, Some(50)))
+            .unwrap();
+        assert_eq!(to_bot.thread_ts.as_deref(), Some("reply_10"));
+
+        let other = ch
+            .parse_update_message(&group_update(14, Some(20)))
+            .unwrap();
+        assert_eq!(other.thread_ts.as_deref(), Some("reply_20"));
+    }
+
+    #[test]
+    fn parse_update_message_keeps_topic_for_replies_in_forum_topics() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+
+        let mut update = group_update(42, Some(789));
+        update["message"]["message_thread_id"] = serde_json::json!(789);
+        update["message"]["is_topic_message"] = serde_json::json!(true);
+        let msg = ch.parse_update_message(&update).unwrap();
+        assert_eq!(msg.thread_ts.as_deref(), Some("789"));
+
+        let mut update = group_update(43, Some(42));
+        update["message"]["message_thread_id"] = serde_json::json!(789);
+        update["message"]["is_topic_message"] = serde_json::json!(true);
+        let msg = ch.parse_update_message(&update).unwrap();
+        assert_eq!(msg.thread_ts.as_deref(), Some("789:reply_42"));
+        assert_eq!(msg.reply_target, "-100200300:789");
+    }
+
+    #[test]
+    fn reply_threads_outside_forums_are_not_topics() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        let mut update = group_update(11, Some(10));
+        update["message"]["message_thread_id"] = serde_json::json!(10);
+        let msg = ch.parse_update_message(&update).unwrap();
+        assert_eq!(msg.reply_target, "-100200300");
+        assert_eq!(msg.thread_ts.as_deref(), Some("reply_10"));
+
+        let typing = TelegramChannel::typing_request("-100200300:789");
+        assert_eq!(typing["chat_id"], "-100200300");
+        assert_eq!(typing["message_thread_id"], "789");
+    }
+
+    #[test]
+    fn allowed_topics_restrict_forum_messages_only() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false)
+            .with_allowed_topics(vec!["-100200300:789".into(), "-100999".into()]);
+        let message = |chat: i64, forum: bool, topic: Option<i64>| {
+            let mut message = serde_json::json!({
+                "message_id": 1,
+                "chat": { "id": chat, "type": "supergroup", "is_forum": forum }
+            });
+            if let Some(topic) = topic {
+                message["message_thread_id"] = serde_json::json!(topic);
+                message["is_topic_message"] = serde_json::json!(true);
+            }
+            message
+        };
+        assert!(ch.is_topic_allowed(&message(-100_200_300, true, Some(789))));
+        assert!(!ch.is_topic_allowed(&message(-100_200_300, true, Some(790))));
+        assert!(!ch.is_topic_allowed(&message(-100_200_300, true, None)));
+        assert!(ch.is_topic_allowed(&message(-100_999, true, Some(5))));
+        assert!(ch.is_topic_allowed(&message(-100_555, false, None)));
+    }
+
+    #[test]
+    fn private_chat_replies_do_not_split_context() {
+        let ch = TelegramChannel::new("token".into(), vec!["*".into()], false);
+        let mut update = group_update(11, Some(10));
+        update["message"]["chat"] = serde_json::json!({ "id": 555, "type": "private" });
+        let msg = ch.parse_update_message(&update).unwrap();
+        assert_eq!(msg.thread_ts, None);
+    }
+
+    #[test]
+    fn reply_chains_evict_oldest_entries() {
+        let mut chains = ReplyChains::default();
+        for id in 0..=TELEGRAM_REPLY_CHAIN_CAPACITY as i64 {
+            chains.record("chat", id, 1);
+        }
+        assert_eq!(chains.roots.len(), TELEGRAM_REPLY_CHAIN_CAPACITY);
+        assert_eq!(chains.resolve("chat", 9_999, 0), 0);
+        assert_eq!(chains.resolve("chat", 10_000, 5), 1);
     }
 
     // ── File sending API URL tests ──────────────────────────────────
@@ -4039,6 +4734,233 @@ mod tests {
         );
     }
 
+    #[test]
+    fn quick_reply_button_press_becomes_a_message_with_its_label() {
+        let ch = TelegramChannel::new("t".into(), vec!["alice".into()], false);
+        let markup = TelegramChannel::quick_reply_markup(&[
+            "How long does it take?".to_string(),
+            "What does it cost?".to_string(),
+        ])
+        .unwrap();
+        let press = |user: &str, data: &str| {
+            serde_json::json!({
+                "callback_query": {
+                    "id": "cb1",
+                    "from": { "id": 1, "username": user },
+                    "data": data,
+                    "message": {
+                        "message_id": 55,
+                        "chat": { "id": 100, "type": "private" },
+                        "reply_markup": markup.clone()
+                    }
+                }
+            })
+        };
+
+        let (msg, query_id) = ch
+            .parse_callback_query(&press("alice", "followup:1"))
+            .unwrap();
+        assert_eq!(query_id, "cb1");
+        assert_eq!(msg.content, "What does it cost?");
+        assert_eq!(msg.sender, "alice");
+        assert_eq!(msg.reply_target, "100");
+        assert!(ch
+            .parse_callback_query(&press("mallory", "followup:1"))
+            .is_none());
+        assert!(ch
+            .parse_callback_query(&press("alice", "other:1"))
+            .is_none());
+        assert!(TelegramChannel::quick_reply_markup(&[]).is_none());
+    }
+
+    #[test]
+    fn parse_reaction_update_reports_added_and_removed_emoji() {
+        let ch = TelegramChannel::new("t".into(), vec!["alice".into()], false);
+        let update = |old: serde_json::Value, new: serde_json::Value, user: &str| {
+            serde_json::json!({
+                "message_reaction": {
+                    "chat": { "id": 100, "type": "private" },
+                    "message_id": 77,
+                    "user": { "id": 1, "username": user, "is_bot": false },
+                    "old_reaction": old,
+                    "new_reaction": new
+                }
+            })
+        };
+        let thumbs_up = serde_json::json!([{ "type": "emoji", "emoji": "👍" }]);
+
+        let added = ch
+            .parse_reaction_update(&update(serde_json::json!([]), thumbs_up.clone(), "alice"))
+            .unwrap();
+        assert_eq!(added.sender, "alice");
+        assert_eq!(added.reply_target, "100");
+        assert_eq!(added.content, "[Reaction: 👍]");
+        assert_eq!(
+            added.payload,
+            Some(MessagePayload::Reaction(Reaction {
+                message_id: "77".into(),
+                emoji: "👍".into(),
+                removed: false,
+            }))
+        );
+
+        let removed = ch
+            .parse_reaction_update(&update(thumbs_up.clone(), serde_json::json!([]), "alice"))
+            .unwrap();
+        assert_eq!(removed.content, "[Reaction removed: 👍]");
+
+        assert!(ch
+            .parse_reaction_update(&update(serde_json::json!([]), thumbs_up, "mallory"))
+            .is_none());
+    }
+
+    #[test]
+    fn parse_structured_message_extracts_coordinates() {
+        let ch = TelegramChannel::new("t".into(), vec!["*".into()], false);
+        let update = serde_json::json!({
+            "message": {
+                "message_id": 11,
+                "from": { "id": 1, "username": "alice" },
+                "chat": { "id": 100, "type": "private" },
+                "location": { "latitude": 52.520_008, "longitude": 13.404_954 }
+            }
+        });
+        assert!(ch.parse_update_message(&update).is_none());
+
+        let parsed = ch.parse_structured_message(&update).unwrap();
+        let location = parsed.location.unwrap();
+        assert!((location.latitude - 52.520_008).abs() < f64::EPSILON);
+        assert!((location.longitude - 13.404_954).abs() < f64::EPSILON);
+        assert_eq!(parsed.content, "[Location: 52.520008, 13.404954]");
+        assert_eq!(parsed.reply_target, "100");
+    }
+
+    #[test]
+    fn parse_structured_message_labels_venues() {
+        let ch = TelegramChannel::new("t".into(), vec!["*".into()], false);
+        let update = serde_json::json!({
+            "message": {
+                "message_id": 12,
+                "from": { "id": 1, "username": "alice" },
+                "chat": { "id": 100, "type": "private" },
+                "location": { "latitude": 48.858_37, "longitude": 2.294_481 },
+                "venue": {
+                    "location": { "latitude": 48.858_37, "longitude": 2.294_481 },
+                    "title": "Eiffel Tower",
+                    "address": "Champ de Mars, Paris"
+                }
+            }
+        });
+
+        let parsed = ch.parse_structured_message(&update).unwrap();
+        assert_eq!(
+            parsed.location.unwrap().label.as_deref(),
+            Some("Eiffel Tower, Champ de Mars, Paris")
+        );
+        assert_eq!(
+            parsed.content,
+            "[Location: Eiffel Tower, Champ de Mars, Paris (48.858370, 2.294481)]"
+        );
+    }
+
+    #[test]
+    fn parse_structured_message_rejects_unauthorized_and_out_of_range() {
+        let ch = TelegramChannel::new("t".into(), vec!["bob".into()], false);
+        let update = serde_json::json!({
+            "message": {
+                "message_id": 13,
+                "from": { "id": 1, "username": "alice" },
+                "chat": { "id": 100, "type": "private" },
+                "location": { "latitude": 10.0, "longitude": 20.0 }
+            }
+        });
+        assert!(ch.parse_structured_message(&update).is_none());
+
+        let open = TelegramChannel::new("t".into(), vec!["*".into()], false);
+        let invalid = serde_json::json!({
+            "message": {
+                "message_id": 14,
+                "from": { "id": 1, "username": "alice" },
+                "chat": { "id": 100, "type": "private" },
+                "location": { "latitude": 123.0, "longitude": 20.0 }
+            }
+        });
+        assert!(open.parse_structured_message(&invalid).is_none());
+    }
+
+    #[test]
+    fn parse_structured_message_keeps_contacts_polls_and_stickers() {
+        let ch = TelegramChannel::new("t".into(), vec!["*".into()], false);
+        let update = |body: serde_json::Value| {
+            let mut message = serde_json::json!({
+                "message_id": 20,
+                "from": { "id": 1, "username": "alice" },
+                "chat": { "id": 100, "type": "private" }
+            });
+            message
+                .as_object_mut()
+                .unwrap()
+                .extend(body.as_object().unwrap().clone());
+            serde_json::json!({ "message": message })
+        };
+
+        let contact = ch
+            .parse_structured_message(&update(serde_json::json!({
+                "contact": {
+                    "phone_number": "+15550100",
+                    "first_name": "Jane",
+                    "last_name": "Doe"
+                }
+            })))
+            .unwrap();
+        assert_eq!(
+            contact.payload,
+            Some(MessagePayload::Contact(ContactCard {
+                name: "Jane Doe".into(),
+                phones: vec!["+15550100".into()],
+                emails: vec![],
+            }))
+        );
+        assert_eq!(contact.content, "[Contact: Jane Doe, phone +15550100]");
+
+        let poll = ch
+            .parse_structured_message(&update(serde_json::json!({
+                "poll": {
+                    "id": "5",
+                    "question": "Lunch?",
+                    "options": [{ "text": "Pizza" }, { "text": "Sushi" }],
+                    "allows_multiple_answers": true
+                }
+            })))
+            .unwrap();
+        assert_eq!(
+            poll.content,
+            "[Poll: Lunch? — options: 1. Pizza | 2. Sushi (multiple answers allowed)]"
+        );
+
+        let sticker = ch
+            .parse_structured_message(&update(serde_json::json!({
+                "sticker": { "file_id": "s", "emoji": "😂", "set_name": "Cats" }
+            })))
+            .unwrap();
+        assert_eq!(sticker.content, "[Sticker: 😂 (Cats)]");
+        assert!(sticker.location.is_none());
+    }
+
+    #[test]
+    fn parse_structured_message_ignores_plain_text() {
+        let ch = TelegramChannel::new("t".into(), vec!["*".into()], false);
+        let update = serde_json::json!({
+            "message": {
+                "message_id": 21,
+                "text": "hello",
+                "from": { "id": 1, "username": "alice" },
+                "chat": { "id": 100, "type": "private" }
+            }
+        });
+        assert!(ch.parse_structured_message(&update).is_none());
+    }
+
     #[test]
     fn with_transcription_sets_config_when_enabled() {
         let mut tc = crate::config::TranscriptionConfig::default();
@@ -12,6 +12,155 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Shared location, for channels that deliver location payloads.
+    pub location: Option<GeoLocation>,
+    /// Structured non-text content (contact card, poll, vote, sticker).
+    /// `content` carries its text placeholder for the LLM.
+    pub payload: Option<MessagePayload>,
+}
+
+/// A geographic point shared by the sender (e.g. a Telegram location or venue).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Place name and/or address, when the platform supplies one.
+    pub label: Option<String>,
+}
+
+impl GeoLocation {
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        let valid = (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude);
+        valid.then_some(Self {
+            latitude,
+            longitude,
+            label: None,
+        })
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label.filter(|label| !label.trim().is_empty());
+        self
+    }
+}
+
+impl std::fmt::Display for GeoLocation {
+    /// Renders the `[Location: ...]` marker placed in message content.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.label {
+            Some(label) => write!(
+                f,
+                "[Location: {label} ({:.6}, {:.6})]",
+                self.latitude, self.longitude
+            ),
+            None => write!(f, "[Location: {:.6}, {:.6}]", self.latitude, self.longitude),
+        }
+    }
+}
+
+/// Inbound message kinds that are not plain text.
+///
+/// Channels keep the structured form here and put the [`Display`](std::fmt::Display)
+/// placeholder in [`ChannelMessage::content`], so models without any notion
+/// of these kinds still see what was sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessagePayload {
+    Contact(ContactCard),
+    Poll(Poll),
+    PollVote(PollVote),
+    Sticker(Sticker),
+    Reaction(Reaction),
+}
+
+/// A shared contact card.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContactCard {
+    pub name: String,
+    pub phones: Vec<String>,
+    pub emails: Vec<String>,
+}
+
+/// A poll posted in the conversation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Poll {
+    pub question: String,
+    pub options: Vec<String>,
+    pub allows_multiple_answers: bool,
+}
+
+/// A vote cast on a poll.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PollVote {
+    /// Platform poll identifier (for Discord, the id of the poll message).
+    pub poll_id: String,
+    /// Chosen options: their text when known, otherwise the platform's option label.
+    pub options: Vec<String>,
+}
+
+/// A sticker; platforms describe these with an emoji and/or a name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sticker {
+    pub emoji: Option<String>,
+    pub name: Option<String>,
+}
+
+impl std::fmt::Display for MessagePayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Contact(card) => {
+                let name = if card.name.trim().is_empty() {
+                    "unnamed"
+                } else {
+                    card.name.as_str()
+                };
+                write!(f, "[Contact: {name}")?;
+                for phone in &card.phones {
+                    write!(f, ", phone {phone}")?;
+                }
+                for email in &card.emails {
+                    write!(f, ", email {email}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Poll(poll) => {
+                let options = poll
+                    .options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, option)| format!("{}. {option}", i + 1))
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                write!(f, "[Poll: {} — options: {options}", poll.question)?;
+                if poll.allows_multiple_answers {
+                    write!(f, " (multiple answers allowed)")?;
+                }
+                write!(f, "]")
+            }
+            Self::PollVote(vote) if vote.options.is_empty() => write!(f, "[Poll vote retracted]"),
+            Self::PollVote(vote) => write!(f, "[Poll vote: {}]", vote.options.join(", ")),
+            Self::Sticker(sticker) => match (&sticker.emoji, &sticker.name) {
+                (Some(emoji), Some(name)) => write!(f, "[Sticker: {emoji} ({name})]"),
+                (Some(label), None) | (None, Some(label)) => write!(f, "[Sticker: {label}]"),
+                (None, None) => write!(f, "[Sticker]"),
+            },
+            Self::Reaction(reaction) if reaction.removed => {
+                write!(f, "[Reaction removed: {}]", reaction.emoji)
+            }
+            Self::Reaction(reaction) => write!(f, "[Reaction: {}]", reaction.emoji),
+        }
+    }
+}
+
+/// An emoji reaction added to or removed from an earlier message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reaction {
+    /// Platform id of the message reacted to, as returned by
+    /// [`Channel::send_tracked`] for the agent's own replies.
+    pub message_id: String,
+    pub emoji: String,
+    /// The reaction was taken back.
+    pub removed: bool,
 }
 
 /// Message to send through a channel
@@ -22,6 +171,12 @@ pub struct SendMessage {
     pub subject: Option<String>,
     /// Platform thread identifier for threaded replies (e.g. Slack `thread_ts`).
     pub thread_ts: Option<String>,
+    /// Inbound message this one answers (`ChannelMessage::id`). Channels with
+    /// native reply/quote support attach the response to that message.
+    pub in_reply_to_id: Option<String>,
+    /// Quick-reply buttons, shown by channels where [`Channel::supports_buttons`].
+    /// Pressing one sends its label back as a message from the user.
+    pub buttons: Vec<String>,
 }
 
 impl SendMessage {
@@ -32,6 +187,8 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: None,
             thread_ts: None,
+            in_reply_to_id: None,
+            buttons: Vec::new(),
         }
     }
 
@@ -46,6 +203,8 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: Some(subject.into()),
             thread_ts: None,
+            in_reply_to_id: None,
+            buttons: Vec::new(),
         }
     }
 
@@ -54,6 +213,73 @@ impl SendMessage {
         self.thread_ts = thread_ts;
         self
     }
+
+    /// Quote or reply to the given inbound message where the channel supports it.
+    pub fn replying_to(mut self, message_id: Option<String>) -> Self {
+        self.in_reply_to_id = message_id;
+        self
+    }
+
+    /// Attach quick-reply buttons.
+    pub fn with_buttons(mut self, buttons: Vec<String>) -> Self {
+        self.buttons = buttons;
+        self
+    }
+}
+
+/// Delivery state of an outbound message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Accepted by the platform.
+    Sent,
+    /// Delivered to the recipient's device.
+    Delivered,
+    /// Seen by the recipient.
+    Read,
+    /// The platform reported that delivery failed.
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Delivered => "delivered",
+            Self::Read => "read",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "sent" => Some(Self::Sent),
+            "delivered" => Some(Self::Delivered),
+            "read" => Some(Self::Read),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+
+    /// Whether moving from `self` to `next` is progress. Receipts can arrive out
+    /// of order, so a late `delivered` must not overwrite `read`.
+    pub fn advances_to(self, next: Self) -> bool {
+        let rank = |status: Self| match status {
+            Self::Sent => 0,
+            Self::Delivered => 1,
+            Self::Read => 2,
+            Self::Failed => 3,
+        };
+        rank(next) > rank(self)
+    }
+}
+
+/// A delivery receipt reported by a platform for a previously sent message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryReceipt {
+    /// Platform message id returned by [`Channel::send_tracked`].
+    pub message_id: String,
+    pub recipient: String,
+    pub status: DeliveryStatus,
 }
 
 /// Core channel trait — implement for any messaging platform
@@ -65,6 +291,22 @@ pub trait Channel: Send + Sync {
     /// Send a message through this channel
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()>;
 
+    /// Send a message and return its platform message id, used to match later
+    /// delivery receipts. Channels without receipts return `None`.
+    async fn send_tracked(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        self.send(message).await.map(|()| None)
+    }
+
+    /// Whether the platform reports delivered/read receipts for sent messages.
+    fn supports_delivery_receipts(&self) -> bool {
+        false
+    }
+
+    /// Whether [`SendMessage::buttons`] are rendered as interactive buttons.
+    fn supports_buttons(&self) -> bool {
+        false
+    }
+
     /// Start listening for incoming messages (long-running)
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()>;
 
@@ -73,8 +315,8 @@ pub trait Channel: Send + Sync {
         true
     }
 
-    /// Signal that the bot is processing a response (e.g. "typing" indicator).
-    /// Implementations should repeat the indicator as needed for their platform.
+    /// Send one "typing" indicator. While an agent run is in progress the
+    /// runtime calls this again every [`Self::typing_refresh_interval`], if set.
     async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
         Ok(())
     }
@@ -84,6 +326,23 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Whether [`Self::set_presence`] shows a status on this platform.
+    fn supports_presence(&self) -> bool {
+        false
+    }
+
+    /// Show `status` as the bot's status text (e.g. Discord custom status).
+    async fn set_presence(&self, _status: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// How often to re-send the typing indicator before the platform expires it.
+    /// `None` sends it once per run, for platforms where it stays up until
+    /// [`Self::stop_typing`] or a reply.
+    fn typing_refresh_interval(&self) -> Option<std::time::Duration> {
+        None
+    }
+
     /// Whether this channel supports progressive message updates via draft edits.
     fn supports_draft_updates(&self) -> bool {
         false
@@ -119,6 +378,17 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Whether [`Self::delete_message`] removes sent messages on this platform.
+    fn supports_message_deletion(&self) -> bool {
+        false
+    }
+
+    /// Delete a message this bot sent earlier, by the id returned from
+    /// [`Self::send_tracked`]. Used by `/undo`.
+    async fn delete_message(&self, _recipient: &str, _message_id: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Add a reaction (emoji) to a message.
     ///
     /// `channel_id` is the platform channel/conversation identifier (e.g. Discord channel ID).
@@ -172,6 +442,8 @@ mod tests {
                 channel: "dummy".into(),
                 timestamp: 123,
                 thread_ts: None,
+                location: None,
+                payload: None,
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -188,6 +460,8 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
 
         let cloned = message.clone();
@@ -244,6 +518,80 @@ mod tests {
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
     }
 
+    #[tokio::test]
+    async fn default_message_deletion_is_unsupported() {
+        let channel = DummyChannel;
+
+        assert!(!channel.supports_message_deletion());
+        assert!(channel.delete_message("bob", "msg_1").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn default_send_tracked_has_no_receipts() {
+        let channel = DummyChannel;
+
+        assert!(!channel.supports_delivery_receipts());
+        assert!(channel
+            .send_tracked(&SendMessage::new("hello", "bob"))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn delivery_status_only_advances() {
+        use DeliveryStatus::{Delivered, Failed, Read, Sent};
+
+        assert!(Sent.advances_to(Delivered));
+        assert!(Delivered.advances_to(Read));
+        assert!(!Read.advances_to(Delivered));
+        assert!(Read.advances_to(Failed));
+        assert!(!Sent.advances_to(Sent));
+        assert_eq!(DeliveryStatus::parse("read"), Some(Read));
+        assert_eq!(DeliveryStatus::parse("deleted"), None);
+    }
+
+    #[test]
+    fn message_payload_placeholders_degrade_gracefully() {
+        let unnamed = MessagePayload::Contact(ContactCard::default());
+        assert_eq!(unnamed.to_string(), "[Contact: unnamed]");
+
+        let vote = PollVote {
+            poll_id: "p1".into(),
+            options: vec!["Pizza".into(), "Sushi".into()],
+        };
+        assert_eq!(
+            MessagePayload::PollVote(vote).to_string(),
+            "[Poll vote: Pizza, Sushi]"
+        );
+        assert_eq!(
+            MessagePayload::PollVote(PollVote::default()).to_string(),
+            "[Poll vote retracted]"
+        );
+        assert_eq!(
+            MessagePayload::Sticker(Sticker::default()).to_string(),
+            "[Sticker]"
+        );
+
+        let reaction = Reaction {
+            message_id: "42".into(),
+            emoji: "👍".into(),
+            removed: false,
+        };
+        assert_eq!(
+            MessagePayload::Reaction(reaction.clone()).to_string(),
+            "[Reaction: 👍]"
+        );
+        assert_eq!(
+            MessagePayload::Reaction(Reaction {
+                removed: true,
+                ..reaction
+            })
+            .to_string(),
+            "[Reaction removed: 👍]"
+        );
+    }
+
     #[tokio::test]
     async fn listen_sends_message_to_channel() {
         let channel = DummyChannel;
@@ -0,0 +1,86 @@
+//! Channel auto-translate (`[translate] auto_channels`).
+//!
+//! Inbound messages on listed channels that are not written in the working
+//! language are translated before the agent sees them, and the agent's reply
+//! is translated back into the sender's language. Logs, the activity monitor,
+//! and memory autosave keep the original text.
+
+use crate::config::Config;
+use crate::tools::translate::{Detected, Translator};
+use std::collections::HashSet;
+
+pub struct AutoTranslator {
+    translator: Translator,
+    working_language: String,
+    channels: HashSet<String>,
+}
+
+impl AutoTranslator {
+    pub fn new<I, S>(translator: Translator, working_language: &str, channels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            translator,
+            working_language: working_language.trim().to_string(),
+            channels: channels.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// `None` when no channel opts in or the translation model is unavailable.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let channels = &config.translate.auto_channels;
+        if channels.is_empty() {
+            return None;
+        }
+        match Translator::from_config(config) {
+            Ok(translator) => Some(Self::new(
+                translator,
+                &config.translate.working_language,
+                channels.iter().map(|channel| channel.trim().to_string()),
+            )),
+            Err(e) => {
+                tracing::warn!("Channel auto-translate disabled: {e}");
+                None
+            }
+        }
+    }
+
+    /// Whether replies on `channel` go through the translator.
+    pub fn covers(&self, channel: &str) -> bool {
+        self.channels.contains(channel)
+    }
+
+    /// Translate an inbound message on an opted-in channel. Returns `None`
+    /// when the message is already in the working language or translation
+    /// fails, so the agent sees the original text.
+    pub async fn inbound(&self, channel: &str, text: &str) -> Option<Detected> {
+        if !self.channels.contains(channel) {
+            return None;
+        }
+        match self.translator.detect(text, &self.working_language).await {
+            Ok(detected) => detected,
+            Err(e) => {
+                tracing::warn!(channel, "Inbound translation failed; using original: {e}");
+                None
+            }
+        }
+    }
+
+    /// Translate a reply into the sender's language, falling back to the
+    /// untranslated reply on failure.
+    pub async fn outbound(&self, text: &str, language: &str) -> String {
+        match self
+            .translator
+            .translate(text, language, Some(&self.working_language))
+            .await
+        {
+            Ok(translated) => translated,
+            Err(e) => {
+                tracing::warn!(language, "Reply translation failed; sending original: {e}");
+                text.to_string()
+            }
+        }
+    }
+}
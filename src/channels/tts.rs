@@ -0,0 +1,62 @@
+use anyhow::{bail, Context, Result};
+
+use crate::config::TtsConfig;
+
+/// Synthesize `text` as WAV audio via an OpenAI-compatible speech API.
+///
+/// Text longer than `config.max_chars` is truncated. Uses `config.api_key`,
+/// falling back to `OPENAI_API_KEY` in the environment.
+pub async fn synthesize_speech(text: &str, config: &TtsConfig) -> Result<Vec<u8>> {
+    let text = text.trim();
+    if text.is_empty() {
+        bail!("Nothing to speak");
+    }
+    let text = crate::util::truncate_with_ellipsis(text, config.max_chars);
+
+    let api_key = match config.api_key.as_deref().map(str::trim) {
+        Some(key) if !key.is_empty() => key.to_string(),
+        _ => std::env::var("OPENAI_API_KEY").context(
+            "OPENAI_API_KEY environment variable is not set and [tts] api_key is empty — required for speech",
+        )?,
+    };
+
+    let client = crate::config::build_runtime_proxy_client("tts.speech");
+    let resp = client
+        .post(&config.api_url)
+        .bearer_auth(&api_key)
+        .json(&serde_json::json!({
+            "model": config.model,
+            "voice": config.voice,
+            "input": text,
+            "response_format": "wav",
+        }))
+        .send()
+        .await
+        .context("Failed to send speech request")?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let body: serde_json::Value = resp.json().await.unwrap_or_default();
+        let error_msg = body["error"]["message"].as_str().unwrap_or("unknown error");
+        bail!("Speech API error ({}): {}", status, error_msg);
+    }
+
+    let audio = resp
+        .bytes()
+        .await
+        .context("Failed to read speech response")?;
+    Ok(audio.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_empty_text() {
+        let err = synthesize_speech("   ", &TtsConfig::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Nothing to speak"));
+    }
+}
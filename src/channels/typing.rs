@@ -0,0 +1,243 @@
+//! Typing indicators shared by all channels.
+//!
+//! [`TypingManager::start`] keeps "typing…" visible in one chat while the
+//! agent works. Runs in the same chat share one refresh loop, which sends
+//! the indicator (again every [`Channel::typing_refresh_interval`], if the
+//! channel sets one) and calls
+//! [`Channel::stop_typing`] once the last run ends. A run ends when its
+//! [`TypingGuard`] is finished or dropped, so the indicator stops on
+//! completion, cancellation, and errors alike.
+
+use super::traits::Channel;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// Channel name and recipient.
+type ChatKey = (String, String);
+
+struct ActiveTyping {
+    runs: usize,
+    stop: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+/// Refresh loops for chats with an agent run in progress.
+#[derive(Default)]
+pub struct TypingManager {
+    active: Mutex<HashMap<ChatKey, ActiveTyping>>,
+}
+
+impl TypingManager {
+    /// Show typing to `recipient` on `channel` until the returned guard ends.
+    pub fn start(self: &Arc<Self>, channel: Arc<dyn Channel>, recipient: &str) -> TypingGuard {
+        let key = (channel.name().to_string(), recipient.to_string());
+        let mut active = self.active.lock();
+        if let Some(typing) = active.get_mut(&key) {
+            typing.runs += 1;
+        } else {
+            let stop = CancellationToken::new();
+            let task = spawn_refresh(channel, recipient.to_string(), stop.clone());
+            active.insert(
+                key.clone(),
+                ActiveTyping {
+                    runs: 1,
+                    stop,
+                    task,
+                },
+            );
+        }
+        TypingGuard {
+            manager: Arc::clone(self),
+            key: Some(key),
+        }
+    }
+
+    /// End one run in the chat. Returns the refresh loop when it was the
+    /// last one, after telling the loop to stop.
+    fn release(&self, key: &ChatKey) -> Option<JoinHandle<()>> {
+        let mut active = self.active.lock();
+        let typing = active.get_mut(key)?;
+        typing.runs -= 1;
+        if typing.runs > 0 {
+            return None;
+        }
+        let typing = active.remove(key)?;
+        typing.stop.cancel();
+        Some(typing.task)
+    }
+}
+
+/// One agent run showing typing in a chat.
+pub struct TypingGuard {
+    manager: Arc<TypingManager>,
+    key: Option<ChatKey>,
+}
+
+impl TypingGuard {
+    /// End the run and, if it was the last in the chat, wait until the
+    /// indicator has been stopped.
+    pub async fn finish(mut self) {
+        let task = self.key.take().and_then(|key| self.manager.release(&key));
+        if let Some(task) = task {
+            if let Err(e) = task.await {
+                tracing::error!("Typing indicator task crashed: {e}");
+            }
+        }
+    }
+}
+
+impl Drop for TypingGuard {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            // The loop still stops the indicator; nobody waits for it.
+            let _ = self.manager.release(&key);
+        }
+    }
+}
+
+fn spawn_refresh(
+    channel: Arc<dyn Channel>,
+    recipient: String,
+    stop: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        match channel.typing_refresh_interval() {
+            Some(period) => {
+                let mut interval = tokio::time::interval(period);
+                interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+                loop {
+                    tokio::select! {
+                        () = stop.cancelled() => break,
+                        _ = interval.tick() => {
+                            if let Err(e) = channel.start_typing(&recipient).await {
+                                tracing::debug!("Failed to start typing on {}: {e}", channel.name());
+                            }
+                        }
+                    }
+                }
+            }
+            None => {
+                tokio::select! {
+                    () = stop.cancelled() => {}
+                    result = channel.start_typing(&recipient) => {
+                        if let Err(e) = result {
+                            tracing::debug!("Failed to start typing on {}: {e}", channel.name());
+                        }
+                        stop.cancelled().await;
+                    }
+                }
+            }
+        }
+
+        if let Err(e) = channel.stop_typing(&recipient).await {
+            tracing::debug!("Failed to stop typing on {}: {e}", channel.name());
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::{ChannelMessage, SendMessage};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct CountingChannel {
+        refresh: Option<Duration>,
+        starts: AtomicUsize,
+        stops: AtomicUsize,
+    }
+
+    impl CountingChannel {
+        fn refreshing() -> Self {
+            Self {
+                refresh: Some(Duration::from_millis(20)),
+                ..Self::default()
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Channel for CountingChannel {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        fn typing_refresh_interval(&self) -> Option<Duration> {
+            self.refresh
+        }
+
+        async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn stop_typing(&self, _recipient: &str) -> anyhow::Result<()> {
+            self.stops.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_in_one_chat_share_a_loop_until_the_last_ends() {
+        let manager = Arc::new(TypingManager::default());
+        let channel = Arc::new(CountingChannel::refreshing());
+
+        let first = manager.start(channel.clone(), "chat-1");
+        let second = manager.start(channel.clone(), "chat-1");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(channel.starts.load(Ordering::SeqCst) >= 2);
+        assert_eq!(manager.active.lock().len(), 1);
+
+        first.finish().await;
+        assert_eq!(channel.stops.load(Ordering::SeqCst), 0);
+        second.finish().await;
+        assert_eq!(channel.stops.load(Ordering::SeqCst), 1);
+        assert!(manager.active.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn channels_without_a_refresh_interval_send_typing_once() {
+        let manager = Arc::new(TypingManager::default());
+        let channel = Arc::new(CountingChannel::default());
+
+        let guard = manager.start(channel.clone(), "chat-1");
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(channel.starts.load(Ordering::SeqCst), 1);
+
+        guard.finish().await;
+        assert_eq!(channel.stops.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn dropped_guards_still_stop_typing() {
+        let manager = Arc::new(TypingManager::default());
+        let channel = Arc::new(CountingChannel::default());
+
+        let guard = manager.start(channel.clone(), "chat-1");
+        let other_chat = manager.start(channel.clone(), "chat-2");
+        drop(guard);
+        other_chat.finish().await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(channel.stops.load(Ordering::SeqCst), 2);
+        assert!(manager.active.lock().is_empty());
+    }
+}
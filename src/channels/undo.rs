@@ -0,0 +1,113 @@
+//! Snapshots of conversation state for `/undo`.
+//!
+//! Before each agent turn the runtime stores the sender's history as it was,
+//! the memory key the user message was auto-saved under, and (once sent) the
+//! platform ids of the reply. `/undo` pops the newest snapshot: history is
+//! restored, the memory entry forgotten, and the reply deleted on channels
+//! that support it. Only the last [`MAX_UNDO_DEPTH`] exchanges are kept, and
+//! snapshots live in memory only, so a restart ends the undo trail.
+
+use crate::providers::ChatMessage;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Exchanges per conversation that can be undone in a row.
+pub const MAX_UNDO_DEPTH: usize = 5;
+
+/// State of a conversation before one exchange.
+#[derive(Debug, Clone, Default)]
+pub struct Exchange {
+    /// History before the user's message was appended.
+    pub history: Vec<ChatMessage>,
+    /// Key of the auto-saved user message, if it was saved.
+    pub memory_key: Option<String>,
+    /// `(recipient, platform message id)` of each reply sent for the exchange.
+    pub replies: Vec<(String, String)>,
+}
+
+static SNAPSHOTS: LazyLock<Mutex<HashMap<String, Vec<Exchange>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Record the state of `history_key` before a new exchange.
+pub(super) fn snapshot(history_key: &str, history: Vec<ChatMessage>, memory_key: Option<String>) {
+    let mut snapshots = SNAPSHOTS.lock();
+    let stack = snapshots.entry(history_key.to_string()).or_default();
+    stack.push(Exchange {
+        history,
+        memory_key,
+        replies: Vec::new(),
+    });
+    if stack.len() > MAX_UNDO_DEPTH {
+        stack.remove(0);
+    }
+}
+
+/// Attach a sent reply to the newest exchange of `history_key`.
+pub(super) fn record_reply(history_key: &str, recipient: &str, message_id: &str) {
+    if let Some(exchange) = SNAPSHOTS
+        .lock()
+        .get_mut(history_key)
+        .and_then(|stack| stack.last_mut())
+    {
+        exchange
+            .replies
+            .push((recipient.to_string(), message_id.to_string()));
+    }
+}
+
+/// Take the newest exchange of `history_key`, if any.
+pub(super) fn pop(history_key: &str) -> Option<Exchange> {
+    let mut snapshots = SNAPSHOTS.lock();
+    let stack = snapshots.get_mut(history_key)?;
+    let exchange = stack.pop();
+    if stack.is_empty() {
+        snapshots.remove(history_key);
+    }
+    exchange
+}
+
+/// Drop every snapshot of `history_key`, e.g. when its history is cleared.
+pub(super) fn clear(history_key: &str) {
+    SNAPSHOTS.lock().remove(history_key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pop_returns_newest_exchange_with_its_replies() {
+        let key = "undo-test_pop";
+        snapshot(key, Vec::new(), Some("mem_1".into()));
+        snapshot(key, vec![ChatMessage::user("hi")], None);
+        record_reply(key, "chat", "42");
+
+        let newest = pop(key).unwrap();
+        assert_eq!(newest.history.len(), 1);
+        assert_eq!(newest.replies, vec![("chat".to_string(), "42".to_string())]);
+        let oldest = pop(key).unwrap();
+        assert_eq!(oldest.memory_key.as_deref(), Some("mem_1"));
+        assert!(oldest.replies.is_empty());
+        assert!(pop(key).is_none());
+    }
+
+    #[test]
+    fn depth_is_bounded_and_clear_drops_everything() {
+        let key = "undo-test_depth";
+        for i in 0..MAX_UNDO_DEPTH + 2 {
+            snapshot(key, vec![ChatMessage::user(i.to_string()); i], None);
+        }
+        let mut remaining = 0;
+        while pop(key).is_some() {
+            remaining += 1;
+        }
+        assert_eq!(remaining, MAX_UNDO_DEPTH);
+
+        snapshot(key, Vec::new(), None);
+        clear(key);
+        assert!(pop(key).is_none());
+        record_reply(key, "chat", "1");
+        assert!(pop(key).is_none());
+    }
+}
@@ -163,6 +163,8 @@ impl WatiChannel {
             channel: "wati".to_string(),
             timestamp,
             thread_ts: None,
+            location: None,
+            payload: None,
         });
 
         messages
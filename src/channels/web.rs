@@ -0,0 +1,348 @@
+//! Programmable web channel backed by the gateway's `/ws/channel` WebSocket.
+//!
+//! Third-party frontends connect to the gateway, send user messages, and get
+//! the agent's reply streamed back while it is generated. The gateway and the
+//! channel runtime meet in a process-wide [`WebHub`]: sockets register a
+//! session there and submit messages, and [`WebChannel`] feeds those messages
+//! into the normal channel pipeline and routes replies back to the session
+//! that sent them. Frontends can also subscribe to the conversation events of
+//! their own user.
+
+use super::traits::{Channel, ChannelMessage, SendMessage};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+const CHANNEL_NAME: &str = "web";
+/// Outbound events buffered per socket before new ones are dropped.
+const SESSION_BUFFER: usize = 256;
+const EVENT_BUFFER: usize = 256;
+
+/// Why a frontend message could not be submitted.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SubmitError {
+    #[error("the web channel is not running; start channels with `zeroclaw daemon`")]
+    NotRunning,
+    #[error("user `{0}` is not allowed on the web channel")]
+    NotAllowed(String),
+    #[error("the web channel is busy; try again shortly")]
+    Busy,
+}
+
+#[derive(Default)]
+struct HubState {
+    inbound: Option<mpsc::Sender<ChannelMessage>>,
+    allowed_users: Vec<String>,
+    sessions: HashMap<String, SessionEntry>,
+}
+
+struct SessionEntry {
+    user: String,
+    outbound: mpsc::Sender<serde_json::Value>,
+}
+
+/// Connects gateway sockets to the running [`WebChannel`].
+pub struct WebHub {
+    state: Mutex<HubState>,
+    events: broadcast::Sender<serde_json::Value>,
+}
+
+static HUB: LazyLock<WebHub> = LazyLock::new(WebHub::new);
+
+/// The process-wide web channel hub.
+pub fn hub() -> &'static WebHub {
+    &HUB
+}
+
+/// A connected frontend socket. Unregisters itself when dropped.
+pub struct WebSession {
+    pub id: String,
+    pub user: String,
+    pub outbound: mpsc::Receiver<serde_json::Value>,
+}
+
+impl Drop for WebSession {
+    fn drop(&mut self) {
+        hub().state.lock().sessions.remove(&self.id);
+    }
+}
+
+impl WebHub {
+    fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        Self {
+            state: Mutex::new(HubState::default()),
+            events,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state
+            .lock()
+            .inbound
+            .as_ref()
+            .is_some_and(|tx| !tx.is_closed())
+    }
+
+    pub fn is_user_allowed(&self, user: &str) -> bool {
+        allows(&self.state.lock().allowed_users, user)
+    }
+
+    /// Register a socket for `user`.
+    pub fn connect(&self, user: &str) -> WebSession {
+        let id = Uuid::new_v4().to_string();
+        let (tx, rx) = mpsc::channel(SESSION_BUFFER);
+        self.state.lock().sessions.insert(
+            id.clone(),
+            SessionEntry {
+                user: user.to_string(),
+                outbound: tx,
+            },
+        );
+        WebSession {
+            id,
+            user: user.to_string(),
+            outbound: rx,
+        }
+    }
+
+    /// Conversation events from every web session. Each event carries the
+    /// `user` it belongs to; callers must only forward their own user's.
+    pub fn subscribe(&self) -> broadcast::Receiver<serde_json::Value> {
+        self.events.subscribe()
+    }
+
+    /// Publish the agent's reply to `session_id` as a conversation event.
+    fn publish_reply(&self, session_id: &str, content: &str) {
+        let Some(user) = self
+            .state
+            .lock()
+            .sessions
+            .get(session_id)
+            .map(|entry| entry.user.clone())
+        else {
+            return;
+        };
+        let _ = self.events.send(serde_json::json!({
+            "type": "event",
+            "role": "assistant",
+            "user": user,
+            "session": session_id,
+            "content": content,
+        }));
+    }
+
+    /// Queue a user message from `session` for the agent.
+    pub fn submit(&self, session: &WebSession, content: &str) -> Result<String, SubmitError> {
+        let inbound = {
+            let state = self.state.lock();
+            if !allows(&state.allowed_users, &session.user) {
+                return Err(SubmitError::NotAllowed(session.user.clone()));
+            }
+            state.inbound.clone().ok_or(SubmitError::NotRunning)?
+        };
+        let msg = ChannelMessage {
+            id: Uuid::new_v4().to_string(),
+            sender: session.user.clone(),
+            reply_target: session.id.clone(),
+            content: content.to_string(),
+            channel: CHANNEL_NAME.to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+            location: None,
+            payload: None,
+        };
+        let id = msg.id.clone();
+        inbound.try_send(msg).map_err(|err| match err {
+            mpsc::error::TrySendError::Full(_) => SubmitError::Busy,
+            mpsc::error::TrySendError::Closed(_) => SubmitError::NotRunning,
+        })?;
+        let _ = self.events.send(serde_json::json!({
+            "type": "event",
+            "role": "user",
+            "user": session.user,
+            "session": session.id,
+            "message_id": id,
+            "content": content,
+        }));
+        Ok(id)
+    }
+
+    fn deliver(&self, session_id: &str, event: serde_json::Value) -> anyhow::Result<()> {
+        let tx = self
+            .state
+            .lock()
+            .sessions
+            .get(session_id)
+            .map(|entry| entry.outbound.clone())
+            .ok_or_else(|| anyhow::anyhow!("web session {session_id} is not connected"))?;
+        tx.try_send(event)
+            .map_err(|_| anyhow::anyhow!("web session {session_id} is not reading"))
+    }
+}
+
+fn allows(allowed_users: &[String], user: &str) -> bool {
+    allowed_users
+        .iter()
+        .any(|allowed| allowed == "*" || allowed == user)
+}
+
+/// Web channel — frontends connected through the gateway WebSocket API.
+pub struct WebChannel {
+    stream: bool,
+}
+
+impl WebChannel {
+    pub fn new(allowed_users: Vec<String>, stream: bool) -> Self {
+        hub().state.lock().allowed_users = allowed_users;
+        Self { stream }
+    }
+}
+
+#[async_trait]
+impl Channel for WebChannel {
+    fn name(&self) -> &str {
+        CHANNEL_NAME
+    }
+
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        hub().publish_reply(&message.recipient, &message.content);
+        hub().deliver(
+            &message.recipient,
+            serde_json::json!({ "type": "message", "content": message.content }),
+        )
+    }
+
+    async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        hub().state.lock().inbound = Some(tx.clone());
+        tx.closed().await;
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        true
+    }
+
+    async fn start_typing(&self, recipient: &str) -> anyhow::Result<()> {
+        // A frontend that already disconnected has nothing to show.
+        let _ = hub().deliver(recipient, serde_json::json!({ "type": "typing" }));
+        Ok(())
+    }
+
+    fn supports_draft_updates(&self) -> bool {
+        self.stream
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        let id = Uuid::new_v4().to_string();
+        hub().deliver(
+            &message.recipient,
+            serde_json::json!({ "type": "start", "id": id }),
+        )?;
+        Ok(Some(id))
+    }
+
+    async fn update_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        hub().deliver(
+            recipient,
+            serde_json::json!({ "type": "chunk", "id": message_id, "content": text }),
+        )
+    }
+
+    async fn finalize_draft(
+        &self,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
+    ) -> anyhow::Result<()> {
+        hub().publish_reply(recipient, text);
+        hub().deliver(
+            recipient,
+            serde_json::json!({ "type": "done", "id": message_id, "content": text }),
+        )
+    }
+
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        let _ = hub().deliver(
+            recipient,
+            serde_json::json!({ "type": "cancelled", "id": message_id }),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn messages_round_trip_between_sessions_and_channel() {
+        let channel = WebChannel::new(vec!["alice".into()], true);
+        let (tx, mut rx) = mpsc::channel(8);
+        let listener = tokio::spawn(async move { channel.listen(tx).await });
+        while !hub().is_running() {
+            tokio::task::yield_now().await;
+        }
+
+        let mut alice = hub().connect("alice");
+        let mut events = hub().subscribe();
+        hub().submit(&alice, "hello").unwrap();
+        let inbound = rx.recv().await.unwrap();
+        assert_eq!(inbound.channel, "web");
+        assert_eq!(inbound.sender, "alice");
+        assert_eq!(inbound.reply_target, alice.id);
+        assert_eq!(events.recv().await.unwrap()["content"], "hello");
+
+        let channel = WebChannel::new(vec!["alice".into()], true);
+        let draft = channel
+            .send_draft(&SendMessage::new("...", &alice.id))
+            .await
+            .unwrap()
+            .unwrap();
+        channel.update_draft(&alice.id, &draft, "Hi").await.unwrap();
+        channel
+            .finalize_draft(&alice.id, &draft, "Hi there")
+            .await
+            .unwrap();
+        let kinds: Vec<_> = [
+            alice.outbound.recv().await.unwrap(),
+            alice.outbound.recv().await.unwrap(),
+            alice.outbound.recv().await.unwrap(),
+        ]
+        .iter()
+        .map(|event| event["type"].as_str().unwrap().to_string())
+        .collect();
+        assert_eq!(kinds, ["start", "chunk", "done"]);
+        let reply = events.recv().await.unwrap();
+        assert_eq!(reply["role"], "assistant");
+        assert_eq!(reply["user"], "alice");
+
+        let bob = hub().connect("bob");
+        assert_eq!(
+            hub().submit(&bob, "hi"),
+            Err(SubmitError::NotAllowed("bob".into()))
+        );
+
+        let gone = alice.id.clone();
+        drop(alice);
+        assert!(channel
+            .send(&SendMessage::new("late", &gone))
+            .await
+            .is_err());
+
+        drop(rx);
+        listener.await.unwrap().unwrap();
+        assert!(!hub().is_running());
+    }
+}
@@ -1,4 +1,7 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Channel, ChannelMessage, ContactCard, DeliveryReceipt, DeliveryStatus, MessagePayload,
+    SendMessage, Sticker,
+};
 use async_trait::async_trait;
 use uuid::Uuid;
 
@@ -105,13 +108,16 @@ impl WhatsAppChannel {
                         continue;
                     }
 
-                    // Extract text content (support text messages only for now)
+                    // Text messages, plus contact cards and stickers as placeholders
+                    let payload = parse_message_payload(msg);
                     let content = if let Some(text_obj) = msg.get("text") {
                         text_obj
                             .get("body")
                             .and_then(|b| b.as_str())
                             .unwrap_or("")
                             .to_string()
+                    } else if let Some(payload) = &payload {
+                        payload.to_string()
                     } else {
                         // Could be image, audio, etc. — skip for now
                         tracing::debug!("WhatsApp: skipping non-text message from {from}");
@@ -142,6 +148,8 @@ impl WhatsAppChannel {
                         channel: "whatsapp".to_string(),
                         timestamp,
                         thread_ts: None,
+                        location: None,
+                        payload,
                     });
                 }
             }
@@ -149,6 +157,31 @@ impl WhatsAppChannel {
 
         messages
     }
+
+    /// Parse delivery receipts (`statuses`) from a webhook payload from Meta.
+    pub fn parse_delivery_receipts(&self, payload: &serde_json::Value) -> Vec<DeliveryReceipt> {
+        payload
+            .get("entry")
+            .and_then(|e| e.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.get("changes").and_then(|c| c.as_array()))
+            .flatten()
+            .filter_map(|change| change.get("value")?.get("statuses")?.as_array())
+            .flatten()
+            .filter_map(|status| {
+                Some(DeliveryReceipt {
+                    message_id: status.get("id")?.as_str()?.to_string(),
+                    recipient: status
+                        .get("recipient_id")
+                        .and_then(|r| r.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    status: DeliveryStatus::parse(status.get("status")?.as_str()?)?,
+                })
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -158,6 +191,10 @@ impl Channel for WhatsAppChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        self.send_tracked(message).await.map(|_| ())
+    }
+
+    async fn send_tracked(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         // WhatsApp Cloud API: POST to /v18.0/{phone_number_id}/messages
         let url = format!(
             "https://graph.facebook.com/v18.0/{}/messages",
@@ -199,7 +236,18 @@ impl Channel for WhatsAppChannel {
             anyhow::bail!("WhatsApp API error: {status}");
         }
 
-        Ok(())
+        // Response: { "messages": [{ "id": "wamid..." }] }
+        let body: serde_json::Value = resp.json().await.unwrap_or_default();
+        Ok(body
+            .get("messages")
+            .and_then(|m| m.get(0))
+            .and_then(|m| m.get("id"))
+            .and_then(|id| id.as_str())
+            .map(str::to_string))
+    }
+
+    fn supports_delivery_receipts(&self) -> bool {
+        true
     }
 
     async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -235,6 +283,36 @@ impl Channel for WhatsAppChannel {
     }
 }
 
+/// Structured form of `contacts` and `sticker` messages. Only the first card
+/// of a multi-contact message is kept.
+fn parse_message_payload(msg: &serde_json::Value) -> Option<MessagePayload> {
+    if msg.get("sticker").is_some() {
+        return Some(MessagePayload::Sticker(Sticker::default()));
+    }
+
+    let contact = msg.get("contacts")?.as_array()?.first()?;
+    let values = |list: &str, key: &str| -> Vec<String> {
+        contact
+            .get(list)
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.get(key).and_then(serde_json::Value::as_str))
+            .map(str::to_string)
+            .collect()
+    };
+    Some(MessagePayload::Contact(ContactCard {
+        name: contact
+            .get("name")
+            .and_then(|name| name.get("formatted_name"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        phones: values("phones", "phone"),
+        emails: values("emails", "email"),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -735,6 +813,39 @@ mod tests {
         assert!(msgs.is_empty(), "Status updates should be ignored");
     }
 
+    #[test]
+    fn whatsapp_parse_delivery_receipts() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "entry": [{
+                "changes": [{
+                    "value": {
+                        "statuses": [
+                            {
+                                "id": "wamid.1",
+                                "recipient_id": "1234567890",
+                                "status": "delivered",
+                                "timestamp": "1699999999"
+                            },
+                            { "id": "wamid.2", "status": "read" },
+                            { "id": "wamid.3", "status": "deleted" },
+                            { "status": "sent" }
+                        ]
+                    }
+                }]
+            }]
+        });
+        let receipts = ch.parse_delivery_receipts(&payload);
+        assert_eq!(receipts.len(), 2);
+        assert_eq!(receipts[0].message_id, "wamid.1");
+        assert_eq!(receipts[0].recipient, "1234567890");
+        assert_eq!(receipts[0].status, DeliveryStatus::Delivered);
+        assert_eq!(receipts[1].status, DeliveryStatus::Read);
+        assert!(ch
+            .parse_delivery_receipts(&serde_json::json!({}))
+            .is_empty());
+    }
+
     #[test]
     fn whatsapp_parse_audio_message_skipped() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
@@ -799,7 +910,7 @@ mod tests {
     }
 
     #[test]
-    fn whatsapp_parse_sticker_message_skipped() {
+    fn whatsapp_parse_sticker_message_placeholder() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
         let payload = serde_json::json!({
             "entry": [{
@@ -816,7 +927,12 @@ mod tests {
             }]
         });
         let msgs = ch.parse_webhook_payload(&payload);
-        assert!(msgs.is_empty());
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(msgs[0].content, "[Sticker]");
+        assert_eq!(
+            msgs[0].payload,
+            Some(MessagePayload::Sticker(Sticker::default()))
+        );
     }
 
     #[test]
@@ -841,7 +957,7 @@ mod tests {
     }
 
     #[test]
-    fn whatsapp_parse_contacts_message_skipped() {
+    fn whatsapp_parse_contacts_message_placeholder() {
         let ch = WhatsAppChannel::new("tok".into(), "123".into(), "ver".into(), vec!["*".into()]);
         let payload = serde_json::json!({
             "entry": [{
@@ -851,14 +967,26 @@ mod tests {
                             "from": "111",
                             "timestamp": "1",
                             "type": "contacts",
-                            "contacts": [{ "name": { "formatted_name": "John" } }]
+                            "contacts": [{
+                                "name": { "formatted_name": "John" },
+                                "phones": [{ "phone": "+1 555 0100", "type": "CELL" }],
+                                "emails": [{ "email": "john@example.com" }]
+                            }]
                         }]
                     }
                 }]
             }]
         });
         let msgs = ch.parse_webhook_payload(&payload);
-        assert!(msgs.is_empty());
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(
+            msgs[0].content,
+            "[Contact: John, phone +1 555 0100, email john@example.com]"
+        );
+        assert!(matches!(
+            &msgs[0].payload,
+            Some(MessagePayload::Contact(card)) if card.phones == ["+1 555 0100"]
+        ));
     }
 
     #[test]
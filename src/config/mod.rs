@@ -1,3 +1,4 @@
+pub mod preset;
 pub mod schema;
 pub mod traits;
 
@@ -5,18 +6,34 @@ pub mod traits;
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
     build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig, BrowserConfig,
-    BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config, CostConfig,
-    CronConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
-    EstopConfig, FeishuConfig, GatewayConfig, HardwareConfig, HardwareTransport, HeartbeatConfig,
-    HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig,
-    MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig, ObservabilityConfig,
-    OtpConfig, OtpMethod, PeripheralBoardConfig, PeripheralsConfig, ProxyConfig, ProxyScope,
-    QdrantConfig, QueryClassificationConfig, ReliabilityConfig, ResourceLimitsConfig,
-    RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig, SecretsConfig, SecurityConfig,
-    SkillsConfig, SkillsPromptInjectionMode, SlackConfig, StorageConfig, StorageProviderConfig,
-    StorageProviderSection, StreamMode, TelegramConfig, TranscriptionConfig, TunnelConfig,
-    WebFetchConfig, WebSearchConfig, WebhookConfig,
+    AckReactionPolicy, AgentConfig, ArtifactsConfig, AuditConfig, AutoModelConfig, AutonomyConfig,
+    BackupCheckConfig, BlueBubblesConfig, BroadcastConfig, BrowserComputerUseConfig, BrowserConfig,
+    BuiltinHooksConfig, CanaryConfig, CardDavConfig, ChannelAccessConfig, ChannelAgentConfig,
+    ChannelAgentRouteConfig, ChannelAudienceConfig, ChannelGroupConfig, ChannelLanguageConfig,
+    ChannelPairingConfig, ChannelPresenceConfig, ChannelQueueConfig, ChannelToolsConfig,
+    ChannelsConfig, CheckinTargetConfig, CheckinsConfig, CitationsConfig, ClassificationRule,
+    ComposioConfig, Config, ContactsConfig, ConversationTitlesConfig, CostConfig, CronConfig,
+    CustomSearchProviderConfig, DashboardConfig, DelegateAgentConfig, DeviceActionConfig,
+    DiscordConfig, DiscordVoiceConfig, DiskCheckConfig, DockerRuntimeConfig, EmbeddingRouteConfig,
+    EstopConfig, EventRedactionConfig, EventWebhookConfig, EventsConfig, ExperimentConfig,
+    ExperimentVariantConfig, FeedbackConfig, FeishuConfig, FinanceConfig, FollowUpsConfig,
+    GatewayConfig, GoalsConfig, GoalsReviewConfig, HardwareConfig, HardwareTransport,
+    HeartbeatConfig, HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig,
+    KubernetesRuntimeConfig, LarkConfig, LinkPolicyConfig, MatrixConfig, MemoryConfig,
+    MemoryEncryptionConfig, MemoryKeySource, ModelRouteConfig, ModelsConfig, MonitoringConfig,
+    MultimodalConfig, NetworkDiagConfig, NetworkPolicyConfig, NewsBriefingConfig, NewsConfig,
+    NextcloudTalkConfig, ObservabilityConfig, OtpConfig, OtpMethod, OutputGuardrailsConfig,
+    PeripheralBoardConfig, PeripheralsConfig, PolicyRulesConfig, PromptConfig, PromptPartConfig,
+    PromptPartKind, ProxyConfig, ProxyScope, QdrantConfig, QueryClassificationConfig,
+    QueueOverflow, ReliabilityConfig, RemoteAgentConfig, RemoteAgentTransport, ReplyReviewConfig,
+    ReportsConfig, ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig,
+    SchedulerConfig, ScreenshotConfig, SecretsConfig, SecurityConfig, SendEmailConfig,
+    SendLaterConfig, SignalConfig, SkillsConfig, SkillsPromptInjectionMode, SlackConfig,
+    SpamAction, SpamFilterConfig, SshHostConfig, SshRuntimeConfig, StorageConfig,
+    StorageProviderConfig, StorageProviderSection, StreamMode, TelegramConfig,
+    ToolOutputStreamConfig, ToolResultSummaryConfig, ToolsConfig, TranscriptionConfig,
+    TranslateConfig, TtsConfig, TunnelConfig, UptimeConfig, WatcherConfig, WeatherConfig,
+    WebChannelConfig, WebFetchConfig, WebSearchConfig, WebhookConfig, WorkspaceSearchConfig,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: &Option<T>) -> (&'static str, bool) {
@@ -45,6 +62,8 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            link_previews: true,
+            allowed_topics: Vec::new(),
         };
 
         let discord = DiscordConfig {
@@ -53,6 +72,8 @@ mod tests {
             allowed_users: vec![],
             listen_to_bots: false,
             mention_only: false,
+            link_previews: true,
+            voice: None,
         };
 
         let lark = LarkConfig {
@@ -0,0 +1,399 @@
+//! Shareable channel presets (`zeroclaw config preset export|import`).
+//!
+//! A preset is a TOML file holding the channel settings a community can tune
+//! and pass around: personas (`[channels_config.agents]`), per-channel reply
+//! formatting, link rewriting, guardrails, audiences, language, and tool
+//! output streaming. Tokens, allowlists, and routes stay out of it.
+//!
+//! Importing parses the file against the typed config, applies it to a copy
+//! of the current config, runs [`Config::validate`], and shows a per-key
+//! diff before anything is saved.
+
+use super::schema::{
+    ChannelAgentConfig, ChannelAudienceConfig, ChannelLanguageConfig, ChannelsConfig, Config,
+    LinkPolicyConfig, OutputGuardrailsConfig, StreamMode, ToolOutputStreamConfig,
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Preset format written by this build.
+pub const PRESET_VERSION: u32 = 1;
+
+/// Reply formatting for one channel. Only Telegram, Discord, and Mattermost
+/// have formatting settings, and each accepts just the fields it supports.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelFormatting {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream_mode: Option<StreamMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub draft_update_interval_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_previews: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mention_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_replies: Option<bool>,
+}
+
+/// Shareable subset of `[channels_config]`.
+///
+/// Sections replace the current ones wholesale; `personas`, `audiences`, and
+/// `formatting` entries replace same-named entries and leave others alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub personas: BTreeMap<String, ChannelAgentConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub formatting: BTreeMap<String, ChannelFormatting>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<LinkPolicyConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardrails: Option<OutputGuardrailsConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub audiences: BTreeMap<String, ChannelAudienceConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<ChannelLanguageConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_output: Option<ToolOutputStreamConfig>,
+}
+
+impl Preset {
+    /// Capture the shareable settings of `channels`.
+    pub fn from_channels(channels: &ChannelsConfig) -> Self {
+        let mut formatting = BTreeMap::new();
+        if let Some(telegram) = &channels.telegram {
+            formatting.insert(
+                "telegram".to_string(),
+                ChannelFormatting {
+                    stream_mode: Some(telegram.stream_mode),
+                    draft_update_interval_ms: Some(telegram.draft_update_interval_ms),
+                    link_previews: Some(telegram.link_previews),
+                    mention_only: Some(telegram.mention_only),
+                    thread_replies: None,
+                },
+            );
+        }
+        if let Some(discord) = &channels.discord {
+            formatting.insert(
+                "discord".to_string(),
+                ChannelFormatting {
+                    link_previews: Some(discord.link_previews),
+                    mention_only: Some(discord.mention_only),
+                    ..ChannelFormatting::default()
+                },
+            );
+        }
+        if let Some(mattermost) = &channels.mattermost {
+            formatting.insert(
+                "mattermost".to_string(),
+                ChannelFormatting {
+                    mention_only: mattermost.mention_only,
+                    thread_replies: mattermost.thread_replies,
+                    ..ChannelFormatting::default()
+                },
+            );
+        }
+
+        Self {
+            version: PRESET_VERSION,
+            description: None,
+            personas: channels
+                .agents
+                .iter()
+                .map(|(name, agent)| (name.clone(), agent.clone()))
+                .collect(),
+            formatting,
+            links: Some(channels.links.clone()),
+            guardrails: Some(channels.guardrails.clone()),
+            audiences: channels
+                .audiences
+                .iter()
+                .map(|(name, audience)| (name.clone(), audience.clone()))
+                .collect(),
+            language: Some(channels.language.clone()),
+            tool_output: Some(channels.tool_output.clone()),
+        }
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        let preset: Self = toml::from_str(raw).context("Invalid preset")?;
+        if preset.version != PRESET_VERSION {
+            bail!(
+                "Unsupported preset version {} (this build reads version {PRESET_VERSION})",
+                preset.version
+            );
+        }
+        for (channel, formatting) in &preset.formatting {
+            check_formatting_fields(channel, formatting)?;
+        }
+        Ok(preset)
+    }
+
+    /// Apply onto `channels`. Returns the names of channels whose formatting
+    /// was skipped because they are not configured.
+    pub fn apply(self, channels: &mut ChannelsConfig) -> Vec<String> {
+        channels.agents.extend(self.personas);
+        channels.audiences.extend(self.audiences);
+        if let Some(links) = self.links {
+            channels.links = links;
+        }
+        if let Some(guardrails) = self.guardrails {
+            channels.guardrails = guardrails;
+        }
+        if let Some(language) = self.language {
+            channels.language = language;
+        }
+        if let Some(tool_output) = self.tool_output {
+            channels.tool_output = tool_output;
+        }
+
+        let mut skipped = Vec::new();
+        for (channel, formatting) in self.formatting {
+            let applied = match channel.as_str() {
+                "telegram" => channels.telegram.as_mut().map(|c| {
+                    let f = &formatting;
+                    c.stream_mode = f.stream_mode.unwrap_or(c.stream_mode);
+                    c.draft_update_interval_ms = f
+                        .draft_update_interval_ms
+                        .unwrap_or(c.draft_update_interval_ms);
+                    c.link_previews = f.link_previews.unwrap_or(c.link_previews);
+                    c.mention_only = f.mention_only.unwrap_or(c.mention_only);
+                }),
+                "discord" => channels.discord.as_mut().map(|c| {
+                    c.link_previews = formatting.link_previews.unwrap_or(c.link_previews);
+                    c.mention_only = formatting.mention_only.unwrap_or(c.mention_only);
+                }),
+                "mattermost" => channels.mattermost.as_mut().map(|c| {
+                    c.mention_only = formatting.mention_only.or(c.mention_only);
+                    c.thread_replies = formatting.thread_replies.or(c.thread_replies);
+                }),
+                _ => None,
+            };
+            if applied.is_none() {
+                skipped.push(channel);
+            }
+        }
+        skipped
+    }
+}
+
+fn check_formatting_fields(channel: &str, formatting: &ChannelFormatting) -> Result<()> {
+    let set = [
+        ("stream_mode", formatting.stream_mode.is_some()),
+        (
+            "draft_update_interval_ms",
+            formatting.draft_update_interval_ms.is_some(),
+        ),
+        ("link_previews", formatting.link_previews.is_some()),
+        ("mention_only", formatting.mention_only.is_some()),
+        ("thread_replies", formatting.thread_replies.is_some()),
+    ];
+    let supported: &[&str] = match channel {
+        "telegram" => &[
+            "stream_mode",
+            "draft_update_interval_ms",
+            "link_previews",
+            "mention_only",
+        ],
+        "discord" => &["link_previews", "mention_only"],
+        "mattermost" => &["mention_only", "thread_replies"],
+        other => bail!("formatting.{other}: channel has no formatting settings"),
+    };
+    if let Some((field, _)) = set
+        .iter()
+        .find(|(field, is_set)| *is_set && !supported.contains(field))
+    {
+        bail!("formatting.{channel}.{field} is not supported on {channel}");
+    }
+    Ok(())
+}
+
+/// Flatten a TOML value into `dotted.key = value` pairs.
+fn flatten(prefix: &str, value: &toml::Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, value, out);
+            }
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+/// Per-key changes between two channel configs, as `+`, `-`, and `~` lines.
+pub fn diff(before: &ChannelsConfig, after: &ChannelsConfig) -> Result<Vec<String>> {
+    let mut old = BTreeMap::new();
+    let mut new = BTreeMap::new();
+    flatten("channels_config", &toml::Value::try_from(before)?, &mut old);
+    flatten("channels_config", &toml::Value::try_from(after)?, &mut new);
+
+    let mut lines = Vec::new();
+    for (key, value) in &new {
+        match old.get(key) {
+            None => lines.push(format!("+ {key} = {value}")),
+            Some(previous) if previous != value => {
+                lines.push(format!("~ {key} = {previous} → {value}"));
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, value) in &old {
+        if !new.contains_key(key) {
+            lines.push(format!("- {key} = {value}"));
+        }
+    }
+    lines.sort_by(|a, b| a[2..].cmp(&b[2..]));
+    Ok(lines)
+}
+
+pub fn export(config: &Config, path: &Path) -> Result<()> {
+    let preset = Preset::from_channels(&config.channels_config);
+    let body = toml::to_string_pretty(&preset)?;
+    if path == Path::new("-") {
+        print!("{body}");
+        return Ok(());
+    }
+    std::fs::write(path, body).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("✅ Preset written to {}", path.display());
+    Ok(())
+}
+
+pub async fn import(config: &Config, path: &Path, yes: bool) -> Result<()> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let preset = Preset::parse(&raw)?;
+    if let Some(description) = &preset.description {
+        println!("Preset: {description}");
+    }
+
+    let mut updated = config.clone();
+    let skipped = preset.apply(&mut updated.channels_config);
+    updated
+        .validate()
+        .context("Preset produces an invalid config")?;
+
+    for channel in &skipped {
+        println!("  ⚠️  formatting.{channel} skipped: channel is not configured");
+    }
+    let changes = diff(&config.channels_config, &updated.channels_config)?;
+    if changes.is_empty() {
+        println!("No changes: the preset matches the current config.");
+        return Ok(());
+    }
+    for line in &changes {
+        println!("  {line}");
+    }
+
+    if !yes {
+        let apply = dialoguer::Confirm::new()
+            .with_prompt(format!("Apply {} changes?", changes.len()))
+            .default(false)
+            .interact()?;
+        if !apply {
+            println!("Preset not applied.");
+            return Ok(());
+        }
+    }
+    updated.save().await?;
+    println!("✅ Preset applied to {}", config.config_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TelegramConfig;
+
+    fn telegram() -> TelegramConfig {
+        TelegramConfig {
+            bot_token: "secret".into(),
+            allowed_users: vec!["42".into()],
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            link_previews: true,
+            allowed_topics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn export_round_trips_without_secrets() {
+        let mut channels = ChannelsConfig::default();
+        channels.telegram = Some(telegram());
+        channels.agents.insert(
+            "pirate".into(),
+            ChannelAgentConfig {
+                system_prompt: Some("Talk like a pirate.".into()),
+                ..ChannelAgentConfig::default()
+            },
+        );
+
+        let body = toml::to_string_pretty(&Preset::from_channels(&channels)).unwrap();
+        assert!(!body.contains("secret"));
+        assert!(!body.contains("allowed_users"));
+
+        let preset = Preset::parse(&body).unwrap();
+        let mut target = ChannelsConfig::default();
+        target.telegram = Some(TelegramConfig {
+            link_previews: false,
+            ..telegram()
+        });
+        assert!(preset.apply(&mut target).is_empty());
+        assert!(target.telegram.unwrap().link_previews);
+        assert_eq!(
+            target.agents["pirate"].system_prompt.as_deref(),
+            Some("Talk like a pirate.")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_unknown_and_unsupported_fields() {
+        assert!(Preset::parse("version = 1\nbot_token = \"x\"\n").is_err());
+        assert!(Preset::parse("version = 2\n").is_err());
+        let err = Preset::parse("version = 1\n[formatting.discord]\nstream_mode = \"partial\"\n")
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("not supported on discord"),
+            "{err}"
+        );
+        assert!(Preset::parse("version = 1\n[formatting.slack]\nmention_only = true\n").is_err());
+    }
+
+    #[test]
+    fn apply_skips_unconfigured_channels_and_diff_lists_changes() {
+        let preset = Preset::parse(
+            "version = 1\n\
+             [formatting.telegram]\nmention_only = true\n\
+             [formatting.discord]\nlink_previews = false\n",
+        )
+        .unwrap();
+        let before = ChannelsConfig {
+            telegram: Some(telegram()),
+            ..ChannelsConfig::default()
+        };
+        let mut after = before.clone();
+        assert_eq!(preset.apply(&mut after), ["discord"]);
+        assert!(after.discord.is_none());
+
+        let changes = diff(&before, &after).unwrap();
+        assert_eq!(
+            changes,
+            ["~ channels_config.telegram.mention_only = false → true"]
+        );
+    }
+}
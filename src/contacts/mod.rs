@@ -0,0 +1,630 @@
+//! Contact book.
+//!
+//! Contacts are read from a local folder of vCard files (`vcard_dir`) and/or
+//! synced from a CardDAV address book into `<workspace>/contacts/carddav.vcf`.
+//! The `contacts_lookup` tool searches them, and channel messages whose
+//! sender matches a contact's phone number or email get a short identity
+//! hint in the system prompt. Only the fields listed in `[contacts].fields`
+//! are ever sent to the provider.
+
+use crate::config::{CardDavConfig, ContactsConfig};
+use anyhow::{bail, Context, Result};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Fields that may be listed in `[contacts].fields`. The name is always shown.
+pub const CONTACT_FIELDS: &[&str] = &[
+    "phone", "email", "nickname", "org", "birthday", "address", "note",
+];
+
+/// Cache file for synced CardDAV contacts, relative to the workspace.
+const CARDDAV_CACHE: &str = "contacts/carddav.vcf";
+
+/// Phone numbers are compared on at most this many trailing digits so that
+/// `+49 170 1234567` and `0170 1234567` match.
+const PHONE_MATCH_DIGITS: usize = 9;
+
+/// Shortest digit string treated as a phone number when matching senders.
+const PHONE_MIN_DIGITS: usize = 7;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Labeled {
+    /// vCard TYPE parameters, e.g. `cell` or `work`; may be empty.
+    pub label: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Contact {
+    pub name: String,
+    pub nicknames: Vec<String>,
+    pub phones: Vec<Labeled>,
+    pub emails: Vec<Labeled>,
+    pub org: Option<String>,
+    pub birthday: Option<String>,
+    pub addresses: Vec<Labeled>,
+    pub note: Option<String>,
+}
+
+/// Parse every `BEGIN:VCARD` … `END:VCARD` block in `text`.
+///
+/// Cards without a usable name (neither `FN` nor `N`) are skipped.
+pub fn parse_vcards(text: &str) -> Vec<Contact> {
+    let mut contacts = Vec::new();
+    let mut current: Option<Contact> = None;
+    let mut structured_name = None;
+
+    for line in unfold(text) {
+        let Some((head, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut parts = head.split(';');
+        let property = parts.next().unwrap_or_default();
+        // Apple clients group properties as `item1.TEL`.
+        let property = property
+            .rsplit_once('.')
+            .map_or(property, |(_, name)| name)
+            .to_ascii_uppercase();
+        let label = type_label(parts);
+
+        match property.as_str() {
+            "BEGIN" if value.trim().eq_ignore_ascii_case("VCARD") => {
+                current = Some(Contact::default());
+                structured_name = None;
+            }
+            "END" if value.trim().eq_ignore_ascii_case("VCARD") => {
+                if let Some(mut contact) = current.take() {
+                    if contact.name.is_empty() {
+                        contact.name = structured_name.take().unwrap_or_default();
+                    }
+                    if !contact.name.is_empty() {
+                        contacts.push(contact);
+                    }
+                }
+            }
+            _ => {
+                let Some(contact) = current.as_mut() else {
+                    continue;
+                };
+                match property.as_str() {
+                    "FN" => contact.name = unescape(value),
+                    "N" => structured_name = name_from_components(value),
+                    "NICKNAME" => contact.nicknames.extend(
+                        split_unescaped(value, ',')
+                            .into_iter()
+                            .filter(|nick| !nick.is_empty()),
+                    ),
+                    "TEL" => {
+                        push_labeled(&mut contact.phones, label, value.trim_start_matches("tel:"));
+                    }
+                    "EMAIL" => push_labeled(&mut contact.emails, label, value),
+                    "ORG" => {
+                        let org = join_components(value, " / ");
+                        if !org.is_empty() {
+                            contact.org = Some(org);
+                        }
+                    }
+                    "BDAY" => contact.birthday = Some(value.trim().to_string()),
+                    "ADR" => {
+                        let address = join_components(value, ", ");
+                        if !address.is_empty() {
+                            contact.addresses.push(Labeled {
+                                label,
+                                value: address,
+                            });
+                        }
+                    }
+                    "NOTE" => contact.note = Some(unescape(value)),
+                    _ => {}
+                }
+            }
+        }
+    }
+    contacts
+}
+
+/// Join folded continuation lines (RFC 6350 §3.2).
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push(raw.to_string());
+    }
+    lines
+}
+
+fn type_label<'a>(params: impl Iterator<Item = &'a str>) -> String {
+    let mut labels = Vec::new();
+    for param in params {
+        let (key, value) = param.split_once('=').unwrap_or(("TYPE", param));
+        if !key.eq_ignore_ascii_case("TYPE") {
+            continue;
+        }
+        for kind in value.trim_matches('"').split(',') {
+            let kind = kind.trim().to_ascii_lowercase();
+            if !kind.is_empty() && kind != "pref" && kind != "voice" && kind != "internet" {
+                labels.push(kind);
+            }
+        }
+    }
+    labels.join(",")
+}
+
+fn push_labeled(target: &mut Vec<Labeled>, label: String, value: &str) {
+    let value = unescape(value);
+    if !value.trim().is_empty() {
+        target.push(Labeled {
+            label,
+            value: value.trim().to_string(),
+        });
+    }
+}
+
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            match chars.next() {
+                Some('n' | 'N') => out.push('\n'),
+                Some(other) => out.push(other),
+                None => {}
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Split on `separator` while respecting backslash escapes.
+fn split_unescaped(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for ch in value.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(ch);
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == separator {
+            parts.push(unescape(&current).trim().to_string());
+            current.clear();
+        } else {
+            current.push(ch);
+        }
+    }
+    parts.push(unescape(&current).trim().to_string());
+    parts
+}
+
+fn join_components(value: &str, separator: &str) -> String {
+    split_unescaped(value, ';')
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// `N:Family;Given;Additional;Prefix;Suffix` → `Given Family`.
+fn name_from_components(value: &str) -> Option<String> {
+    let parts = split_unescaped(value, ';');
+    let get = |index: usize| parts.get(index).map(String::as_str).unwrap_or_default();
+    let name = [get(3), get(1), get(2), get(0), get(4)]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!name.is_empty()).then_some(name)
+}
+
+fn carddav_cache_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join(CARDDAV_CACHE)
+}
+
+/// Load contacts from `vcard_dir` and the CardDAV cache without touching the
+/// network.
+pub fn load(workspace_dir: &Path, config: &ContactsConfig) -> Result<Vec<Contact>> {
+    let mut contacts = Vec::new();
+
+    if let Some(dir) = config
+        .vcard_dir
+        .as_deref()
+        .filter(|dir| !dir.trim().is_empty())
+    {
+        let dir = PathBuf::from(shellexpand::tilde(dir.trim()).into_owned());
+        let dir = if dir.is_absolute() {
+            dir
+        } else {
+            workspace_dir.join(dir)
+        };
+        let entries = std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read vCard folder {}", dir.display()))?;
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("vcf"))
+            })
+            .collect();
+        files.sort();
+        for file in files {
+            match std::fs::read_to_string(&file) {
+                Ok(text) => contacts.extend(parse_vcards(&text)),
+                Err(e) => tracing::warn!("Skipping vCard file {}: {e}", file.display()),
+            }
+        }
+    }
+
+    if config.carddav.is_some() {
+        if let Ok(text) = std::fs::read_to_string(carddav_cache_path(workspace_dir)) {
+            contacts.extend(parse_vcards(&text));
+        }
+    }
+
+    contacts.sort_by_key(|contact| contact.name.to_lowercase());
+    Ok(contacts)
+}
+
+/// Refresh the CardDAV cache when it is older than `sync_interval_mins`, then
+/// load all contacts. Sync failures fall back to the existing cache.
+pub async fn load_and_sync(workspace_dir: &Path, config: &ContactsConfig) -> Result<Vec<Contact>> {
+    if let Some(carddav) = config.carddav.as_ref() {
+        if cache_is_stale(workspace_dir, carddav) {
+            if let Err(e) = sync(workspace_dir, carddav).await {
+                tracing::warn!("CardDAV sync failed, using cached contacts: {e:#}");
+            }
+        }
+    }
+    load(workspace_dir, config)
+}
+
+fn cache_is_stale(workspace_dir: &Path, carddav: &CardDavConfig) -> bool {
+    let max_age = Duration::from_secs(carddav.sync_interval_mins.saturating_mul(60));
+    std::fs::metadata(carddav_cache_path(workspace_dir))
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_none_or(|age| age >= max_age)
+}
+
+const ADDRESSBOOK_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<card:addressbook-query xmlns:d="DAV:" xmlns:card="urn:ietf:params:xml:ns:carddav">
+  <d:prop>
+    <d:getetag/>
+    <card:address-data/>
+  </d:prop>
+</card:addressbook-query>"#;
+
+/// Download every card in the address book and replace the local cache.
+/// Returns the number of cards written.
+pub async fn sync(workspace_dir: &Path, carddav: &CardDavConfig) -> Result<usize> {
+    let client =
+        crate::config::build_runtime_proxy_client_with_timeouts("contacts.carddav", 60, 10);
+    let method = reqwest::Method::from_bytes(b"REPORT").expect("valid HTTP method");
+    let mut request = client
+        .request(method, carddav.url.trim())
+        .header("Depth", "1")
+        .header("Content-Type", "application/xml; charset=utf-8")
+        .body(ADDRESSBOOK_QUERY);
+    if let Some(username) = carddav.username.as_deref() {
+        request = request.basic_auth(username, carddav.password.as_deref());
+    }
+
+    let response = request.send().await.context("CardDAV request failed")?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("CardDAV server returned {status}");
+    }
+    let body = response
+        .text()
+        .await
+        .context("Failed to read CardDAV response")?;
+
+    let cards = extract_address_data(&body);
+    let path = carddav_cache_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, cards.join("\r\n"))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(cards.len())
+}
+
+/// Pull the text of every `address-data` element out of a multistatus
+/// response, whatever namespace prefix the server chose.
+fn extract_address_data(xml: &str) -> Vec<String> {
+    let pattern = regex::Regex::new(
+        r"(?s)<(?:[A-Za-z0-9_-]+:)?address-data\b[^>]*>(.*?)</(?:[A-Za-z0-9_-]+:)?address-data>",
+    )
+    .expect("valid regex");
+    pattern
+        .captures_iter(xml)
+        .map(|caps| xml_unescape(caps[1].trim()))
+        .map(|card| {
+            card.strip_prefix("<![CDATA[")
+                .and_then(|inner| inner.strip_suffix("]]>"))
+                .map_or(card.clone(), str::to_string)
+        })
+        .filter(|card| card.contains("BEGIN:VCARD"))
+        .collect()
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// Contacts matching `query` by name, nickname, organisation, email, or phone
+/// number. Every word of a text query must appear somewhere in the contact.
+pub fn search<'a>(contacts: &'a [Contact], query: &str) -> Vec<&'a Contact> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query_digits = digits(&query);
+    let is_phone_query = query_digits.len() >= PHONE_MIN_DIGITS
+        && query
+            .chars()
+            .all(|ch| ch.is_ascii_digit() || " +-()./".contains(ch));
+
+    contacts
+        .iter()
+        .filter(|contact| {
+            if is_phone_query {
+                return contact
+                    .phones
+                    .iter()
+                    .any(|phone| phones_match(&digits(&phone.value), &query_digits));
+            }
+            let haystack = format!(
+                "{} {} {} {}",
+                contact.name,
+                contact.nicknames.join(" "),
+                contact.org.as_deref().unwrap_or_default(),
+                contact
+                    .emails
+                    .iter()
+                    .map(|email| email.value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+            .to_lowercase();
+            query.split_whitespace().all(|word| haystack.contains(word))
+        })
+        .collect()
+}
+
+/// Find the contact a channel sender id (phone number or email) belongs to.
+pub fn match_sender<'a>(contacts: &'a [Contact], sender: &str) -> Option<&'a Contact> {
+    let sender = sender.trim();
+    let sender = sender.strip_prefix("mailto:").unwrap_or(sender);
+    if sender.contains('@') {
+        return contacts.iter().find(|contact| {
+            contact
+                .emails
+                .iter()
+                .any(|email| email.value.eq_ignore_ascii_case(sender))
+        });
+    }
+
+    // Ids like `49170…@s.whatsapp.net` are handled above only when they are
+    // real emails; everything else must look like a phone number.
+    if sender.chars().any(|ch| ch.is_ascii_alphabetic()) {
+        return None;
+    }
+    let sender_digits = digits(sender);
+    if sender_digits.len() < PHONE_MIN_DIGITS {
+        return None;
+    }
+    contacts.iter().find(|contact| {
+        contact
+            .phones
+            .iter()
+            .any(|phone| phones_match(&digits(&phone.value), &sender_digits))
+    })
+}
+
+fn digits(value: &str) -> String {
+    value.chars().filter(char::is_ascii_digit).collect()
+}
+
+fn phones_match(a: &str, b: &str) -> bool {
+    if a.len() < PHONE_MIN_DIGITS || b.len() < PHONE_MIN_DIGITS {
+        return false;
+    }
+    let n = a.len().min(b.len()).min(PHONE_MATCH_DIGITS);
+    a[a.len() - n..] == b[b.len() - n..]
+}
+
+/// Render a contact for the provider, including only the allowed fields.
+pub fn describe(contact: &Contact, fields: &[String]) -> String {
+    let allowed = |field: &str| fields.iter().any(|allowed| allowed == field);
+    let mut out = contact.name.clone();
+
+    let labeled = |out: &mut String, title: &str, items: &[Labeled]| {
+        for item in items {
+            if item.label.is_empty() {
+                let _ = write!(out, "\n  {title}: {}", item.value);
+            } else {
+                let _ = write!(out, "\n  {title} ({}): {}", item.label, item.value);
+            }
+        }
+    };
+
+    if allowed("nickname") && !contact.nicknames.is_empty() {
+        let _ = write!(out, "\n  nickname: {}", contact.nicknames.join(", "));
+    }
+    if allowed("phone") {
+        labeled(&mut out, "phone", &contact.phones);
+    }
+    if allowed("email") {
+        labeled(&mut out, "email", &contact.emails);
+    }
+    if allowed("org") {
+        if let Some(org) = &contact.org {
+            let _ = write!(out, "\n  org: {org}");
+        }
+    }
+    if allowed("birthday") {
+        if let Some(birthday) = &contact.birthday {
+            let _ = write!(out, "\n  birthday: {birthday}");
+        }
+    }
+    if allowed("address") {
+        labeled(&mut out, "address", &contact.addresses);
+    }
+    if allowed("note") {
+        if let Some(note) = &contact.note {
+            let _ = write!(out, "\n  note: {}", note.replace('\n', " "));
+        }
+    }
+    out
+}
+
+/// System prompt line naming the contact a channel sender matches. Only the
+/// name is included; other fields stay local unless looked up explicitly.
+pub fn identity_hint(
+    workspace_dir: &Path,
+    config: &ContactsConfig,
+    sender: &str,
+) -> Option<String> {
+    let contacts = match load(workspace_dir, config) {
+        Ok(contacts) => contacts,
+        Err(e) => {
+            tracing::debug!("Contacts unavailable for identity hint: {e:#}");
+            return None;
+        }
+    };
+    let contact = match_sender(&contacts, sender)?;
+    Some(format!(
+        "## Sender\n\nThe sender of this message ({sender}) matches the contact \"{}\" in the user's address book.",
+        contact.name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SAMPLE: &str = "BEGIN:VCARD\r\nVERSION:3.0\r\nFN:Sara Lee\r\nN:Lee;Sara;;;\r\nNICKNAME:Sassy\r\nTEL;TYPE=CELL:+49 170 1234567\r\nitem1.EMAIL;TYPE=INTERNET:sara@example.com\r\nORG:Acme\\, Inc.;Research\r\nADR;TYPE=home:;;Main St 1;Berlin;;10115;Germany\r\nNOTE:Met at the\r\n  conference\r\nEND:VCARD\r\nBEGIN:VCARD\r\nVERSION:3.0\r\nN:Doe;John;;Dr.;\r\nTEL:0301234567\r\nEND:VCARD\r\n";
+
+    #[test]
+    fn parses_cards_with_folding_groups_and_escapes() {
+        let contacts = parse_vcards(SAMPLE);
+        assert_eq!(contacts.len(), 2);
+
+        let sara = &contacts[0];
+        assert_eq!(sara.name, "Sara Lee");
+        assert_eq!(sara.nicknames, vec!["Sassy"]);
+        assert_eq!(sara.phones[0].label, "cell");
+        assert_eq!(sara.phones[0].value, "+49 170 1234567");
+        assert_eq!(sara.emails[0].value, "sara@example.com");
+        assert_eq!(sara.org.as_deref(), Some("Acme, Inc. / Research"));
+        assert_eq!(sara.addresses[0].value, "Main St 1, Berlin, 10115, Germany");
+        assert_eq!(sara.note.as_deref(), Some("Met at the conference"));
+
+        assert_eq!(contacts[1].name, "Dr. John Doe");
+    }
+
+    #[test]
+    fn search_matches_names_and_phone_numbers() {
+        let contacts = parse_vcards(SAMPLE);
+        assert_eq!(search(&contacts, "sara")[0].name, "Sara Lee");
+        assert_eq!(search(&contacts, "lee sara").len(), 1);
+        assert_eq!(search(&contacts, "sassy").len(), 1);
+        assert_eq!(search(&contacts, "0170 1234567")[0].name, "Sara Lee");
+        assert!(search(&contacts, "nobody").is_empty());
+    }
+
+    #[test]
+    fn match_sender_uses_trailing_phone_digits_and_emails() {
+        let contacts = parse_vcards(SAMPLE);
+        assert_eq!(
+            match_sender(&contacts, "+491701234567").unwrap().name,
+            "Sara Lee"
+        );
+        assert_eq!(
+            match_sender(&contacts, "SARA@example.com").unwrap().name,
+            "Sara Lee"
+        );
+        assert_eq!(
+            match_sender(&contacts, "+49 30 1234567").unwrap().name,
+            "Dr. John Doe"
+        );
+        assert!(match_sender(&contacts, "user_1234567").is_none());
+        assert!(match_sender(&contacts, "12345").is_none());
+    }
+
+    #[test]
+    fn describe_respects_field_allowlist() {
+        let contacts = parse_vcards(SAMPLE);
+        let fields = vec!["phone".to_string()];
+        let text = describe(&contacts[0], &fields);
+        assert!(text.contains("phone (cell): +49 170 1234567"));
+        assert!(!text.contains("sara@example.com"));
+        assert!(!text.contains("Berlin"));
+        assert!(!text.contains("conference"));
+    }
+
+    #[test]
+    fn extracts_address_data_from_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:" xmlns:C="urn:ietf:params:xml:ns:carddav">
+  <d:response><d:propstat><d:prop>
+    <C:address-data>BEGIN:VCARD&#13;
+FN:Tom &amp; Co&#13;
+END:VCARD</C:address-data>
+  </d:prop></d:propstat></d:response>
+  <d:response><d:propstat><d:prop>
+    <address-data xmlns="urn:ietf:params:xml:ns:carddav"><![CDATA[BEGIN:VCARD
+FN:Ann
+END:VCARD]]></address-data>
+  </d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+        let cards = extract_address_data(xml);
+        assert_eq!(cards.len(), 2);
+        let contacts = parse_vcards(&cards.join("\n"));
+        assert_eq!(contacts[0].name, "Tom & Co");
+        assert_eq!(contacts[1].name, "Ann");
+    }
+
+    #[test]
+    fn load_reads_vcard_dir_and_identity_hint_names_sender() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("cards");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("all.vcf"), SAMPLE).unwrap();
+        std::fs::write(dir.join("ignored.txt"), "BEGIN:VCARD\nFN:X\nEND:VCARD").unwrap();
+
+        let config = ContactsConfig {
+            enabled: true,
+            vcard_dir: Some("cards".into()),
+            ..ContactsConfig::default()
+        };
+        let contacts = load(tmp.path(), &config).unwrap();
+        assert_eq!(contacts.len(), 2);
+
+        let hint = identity_hint(tmp.path(), &config, "+491701234567").unwrap();
+        assert!(hint.contains("\"Sara Lee\""));
+        assert!(!hint.contains("sara@example.com"));
+        assert!(identity_hint(tmp.path(), &config, "+15550000000").is_none());
+    }
+}
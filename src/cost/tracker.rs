@@ -149,6 +149,14 @@ impl CostTracker {
             .iter()
             .map(|record| record.usage.total_tokens)
             .sum();
+        let cached_input_tokens: u64 = session_costs
+            .iter()
+            .map(|record| record.usage.cached_input_tokens)
+            .sum();
+        let session_savings: f64 = session_costs
+            .iter()
+            .map(|record| record.usage.savings_usd)
+            .sum();
         let request_count = session_costs.len();
         let by_model = build_session_model_stats(&session_costs);
 
@@ -157,6 +165,8 @@ impl CostTracker {
             daily_cost_usd: daily_cost,
             monthly_cost_usd: monthly_cost,
             total_tokens,
+            cached_input_tokens,
+            session_savings_usd: session_savings,
             request_count,
             by_model,
         })
@@ -214,11 +224,15 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
                 model: record.usage.model.clone(),
                 cost_usd: 0.0,
                 total_tokens: 0,
+                cached_input_tokens: 0,
+                savings_usd: 0.0,
                 request_count: 0,
             });
 
         entry.cost_usd += record.usage.cost_usd;
         entry.total_tokens += record.usage.total_tokens;
+        entry.cached_input_tokens += record.usage.cached_input_tokens;
+        entry.savings_usd += record.usage.savings_usd;
         entry.request_count += 1;
     }
 
@@ -447,6 +461,34 @@ mod tests {
         assert_eq!(summary.by_model.len(), 1);
     }
 
+    #[test]
+    fn summary_reports_prompt_cache_savings() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+
+        tracker
+            .record_usage(TokenUsage::with_cached_input(
+                "cached/model",
+                10_000,
+                8_000,
+                0,
+                3.0,
+                0.3,
+                15.0,
+            ))
+            .unwrap();
+        tracker
+            .record_usage(TokenUsage::new("cached/model", 1000, 0, 3.0, 15.0))
+            .unwrap();
+
+        let summary = tracker.get_summary().unwrap();
+        assert_eq!(summary.cached_input_tokens, 8_000);
+        assert!((summary.session_savings_usd - 0.0216).abs() < 0.000_001);
+        let stats = &summary.by_model["cached/model"];
+        assert_eq!(stats.cached_input_tokens, 8_000);
+        assert!((stats.savings_usd - 0.0216).abs() < 0.000_001);
+    }
+
     #[test]
     fn budget_exceeded_daily_limit() {
         let tmp = TempDir::new().unwrap();
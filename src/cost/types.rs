@@ -5,14 +5,20 @@ use serde::{Deserialize, Serialize};
 pub struct TokenUsage {
     /// Model identifier (e.g., "anthropic/claude-sonnet-4-20250514")
     pub model: String,
-    /// Input/prompt tokens
+    /// Input/prompt tokens, including any served from the prompt cache
     pub input_tokens: u64,
+    /// Input tokens served from the prompt cache (a subset of `input_tokens`)
+    #[serde(default)]
+    pub cached_input_tokens: u64,
     /// Output/completion tokens
     pub output_tokens: u64,
     /// Total tokens
     pub total_tokens: u64,
     /// Calculated cost in USD
     pub cost_usd: f64,
+    /// Amount saved by pricing cached input tokens at the cache rate
+    #[serde(default)]
+    pub savings_usd: f64,
     /// Timestamp of the request
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
@@ -33,23 +39,58 @@ impl TokenUsage {
         output_tokens: u64,
         input_price_per_million: f64,
         output_price_per_million: f64,
+    ) -> Self {
+        Self::with_cached_input(
+            model,
+            input_tokens,
+            0,
+            output_tokens,
+            input_price_per_million,
+            input_price_per_million,
+            output_price_per_million,
+        )
+    }
+
+    /// Create a token usage record where `cached_input_tokens` of the
+    /// `input_tokens` were served from the prompt cache and are billed at
+    /// `cached_input_price_per_million` instead of the full input price.
+    pub fn with_cached_input(
+        model: impl Into<String>,
+        input_tokens: u64,
+        cached_input_tokens: u64,
+        output_tokens: u64,
+        input_price_per_million: f64,
+        cached_input_price_per_million: f64,
+        output_price_per_million: f64,
     ) -> Self {
         let model = model.into();
         let input_price_per_million = Self::sanitize_price(input_price_per_million);
+        let cached_input_price_per_million = Self::sanitize_price(cached_input_price_per_million);
         let output_price_per_million = Self::sanitize_price(output_price_per_million);
+        let cached_input_tokens = cached_input_tokens.min(input_tokens);
+        let uncached_input_tokens = input_tokens - cached_input_tokens;
         let total_tokens = input_tokens.saturating_add(output_tokens);
 
         // Calculate cost: (tokens / 1M) * price_per_million
-        let input_cost = (input_tokens as f64 / 1_000_000.0) * input_price_per_million;
+        let input_cost = (uncached_input_tokens as f64 / 1_000_000.0) * input_price_per_million;
+        let cached_input_cost =
+            (cached_input_tokens as f64 / 1_000_000.0) * cached_input_price_per_million;
         let output_cost = (output_tokens as f64 / 1_000_000.0) * output_price_per_million;
-        let cost_usd = input_cost + output_cost;
+        let cost_usd = input_cost + cached_input_cost + output_cost;
+
+        // A cache rate above the input rate is a cache write premium, not a saving.
+        let savings_usd = ((cached_input_tokens as f64 / 1_000_000.0)
+            * (input_price_per_million - cached_input_price_per_million))
+            .max(0.0);
 
         Self {
             model,
             input_tokens,
+            cached_input_tokens,
             output_tokens,
             total_tokens,
             cost_usd,
+            savings_usd,
             timestamp: chrono::Utc::now(),
         }
     }
@@ -120,6 +161,12 @@ pub struct CostSummary {
     pub monthly_cost_usd: f64,
     /// Total tokens used
     pub total_tokens: u64,
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cached_input_tokens: u64,
+    /// Amount saved by prompt cache hits in the session
+    #[serde(default)]
+    pub session_savings_usd: f64,
     /// Number of requests
     pub request_count: usize,
     /// Breakdown by model
@@ -135,6 +182,12 @@ pub struct ModelStats {
     pub cost_usd: f64,
     /// Total tokens for this model
     pub total_tokens: u64,
+    /// Input tokens served from the prompt cache for this model
+    #[serde(default)]
+    pub cached_input_tokens: u64,
+    /// Amount saved by prompt cache hits for this model
+    #[serde(default)]
+    pub savings_usd: f64,
     /// Number of requests for this model
     pub request_count: usize,
 }
@@ -146,6 +199,8 @@ impl Default for CostSummary {
             daily_cost_usd: 0.0,
             monthly_cost_usd: 0.0,
             total_tokens: 0,
+            cached_input_tokens: 0,
+            session_savings_usd: 0.0,
             request_count: 0,
             by_model: std::collections::HashMap::new(),
         }
@@ -181,6 +236,33 @@ mod tests {
         assert_eq!(usage.total_tokens, 2000);
     }
 
+    #[test]
+    fn cached_input_tokens_are_priced_at_the_cache_rate() {
+        let usage =
+            TokenUsage::with_cached_input("test/model", 10_000, 8_000, 1_000, 3.0, 0.3, 15.0);
+
+        // Expected: (2000/1M)*3 + (8000/1M)*0.3 + (1000/1M)*15 = 0.006 + 0.0024 + 0.015
+        assert!((usage.cost_usd - 0.0234).abs() < 0.000_001);
+        // Expected: (8000/1M)*(3 - 0.3) = 0.0216
+        assert!((usage.savings_usd - 0.0216).abs() < 0.000_001);
+        assert_eq!(usage.cached_input_tokens, 8_000);
+        assert_eq!(usage.total_tokens, 11_000);
+    }
+
+    #[test]
+    fn cached_input_tokens_are_capped_and_never_negative_savings() {
+        let usage = TokenUsage::with_cached_input("test/model", 100, 500, 0, 1.0, 2.0, 1.0);
+        assert_eq!(usage.cached_input_tokens, 100);
+        assert!(usage.savings_usd.abs() < f64::EPSILON);
+
+        let legacy: TokenUsage = serde_json::from_str(
+            r#"{"model":"m","input_tokens":1,"output_tokens":1,"total_tokens":2,"cost_usd":0.1,"timestamp":"2026-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.cached_input_tokens, 0);
+        assert!(legacy.savings_usd.abs() < f64::EPSILON);
+    }
+
     #[test]
     fn cost_record_creation() {
         let usage = TokenUsage::new("test/model", 100, 50, 1.0, 2.0);
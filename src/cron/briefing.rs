@@ -0,0 +1,250 @@
+//! Config-defined scheduled jobs: the news briefing (`[news.briefing]`) and
+//! the goal review (`[goals.review]`).
+//!
+//! Each section is mirrored into a cron agent job with a fixed name when the
+//! scheduler starts: the job is created, updated to match the config, or
+//! removed once the section is gone. Runs and delivery then go through the
+//! regular cron path.
+
+use super::{add_agent_job, list_jobs, remove_job, update_job};
+use super::{CronJobPatch, DeliveryConfig, Schedule, SessionTarget};
+use crate::config::{Config, GoalsReviewConfig, NewsBriefingConfig};
+use anyhow::Result;
+
+pub const JOB_NAME: &str = "news-briefing";
+pub const GOALS_REVIEW_JOB_NAME: &str = "goals-review";
+
+/// Desired state of a config-defined job.
+struct JobSpec {
+    schedule: Schedule,
+    prompt: String,
+    delivery: DeliveryConfig,
+}
+
+fn briefing_prompt(briefing: &NewsBriefingConfig) -> String {
+    let topics: Vec<&str> = briefing
+        .topics
+        .iter()
+        .map(|topic| topic.trim())
+        .filter(|topic| !topic.is_empty())
+        .collect();
+    let mut prompt =
+        String::from("News briefing: use the news tool to fetch today's top headlines");
+    if !topics.is_empty() {
+        prompt.push_str(", then call it once per topic for: ");
+        prompt.push_str(&topics.join(", "));
+    }
+    prompt.push_str(
+        ". Reply with a compact briefing: one line per story with its source and link, \
+grouped by topic, skipping duplicates. No preamble.",
+    );
+    prompt
+}
+
+fn announce(channel: &str, to: &str) -> DeliveryConfig {
+    DeliveryConfig {
+        mode: "announce".into(),
+        channel: Some(channel.trim().to_string()),
+        to: Some(to.trim().to_string()),
+        best_effort: true,
+        recipients: Vec::new(),
+    }
+}
+
+fn briefing_spec(briefing: &NewsBriefingConfig) -> JobSpec {
+    JobSpec {
+        schedule: Schedule::Cron {
+            expr: briefing.schedule.trim().to_string(),
+            tz: briefing.tz.clone(),
+        },
+        prompt: briefing_prompt(briefing),
+        delivery: announce(&briefing.channel, &briefing.to),
+    }
+}
+
+fn review_spec(review: &GoalsReviewConfig) -> JobSpec {
+    JobSpec {
+        schedule: Schedule::Cron {
+            expr: review.schedule.trim().to_string(),
+            tz: review.tz.clone(),
+        },
+        prompt: "Goal review: use the goals tool to list active goals with their milestones and \
+recent progress. Reply with a short summary per goal: progress since the last review, the next \
+milestone, and anything overdue or stalled. End with one encouraging line. No preamble."
+            .into(),
+        delivery: announce(&review.channel, &review.to),
+    }
+}
+
+/// Bring the `news-briefing` and `goals-review` cron jobs in line with the
+/// config.
+pub fn sync(config: &Config) -> Result<()> {
+    let briefing = config.news.briefing.as_ref();
+    if briefing.is_some() && !config.news.enabled {
+        tracing::warn!("[news.briefing] is set but the news tool is disabled; briefing skipped");
+    }
+    sync_job(
+        config,
+        JOB_NAME,
+        briefing.filter(|_| config.news.enabled).map(briefing_spec),
+    )?;
+
+    let review = config.goals.review.as_ref();
+    if review.is_some() && !config.goals.enabled {
+        tracing::warn!("[goals.review] is set but goal tracking is disabled; review skipped");
+    }
+    sync_job(
+        config,
+        GOALS_REVIEW_JOB_NAME,
+        review.filter(|_| config.goals.enabled).map(review_spec),
+    )
+}
+
+fn sync_job(config: &Config, name: &str, spec: Option<JobSpec>) -> Result<()> {
+    let mut existing = list_jobs(config)?
+        .into_iter()
+        .filter(|job| job.name.as_deref() == Some(name));
+    let current = existing.next();
+    for duplicate in existing {
+        remove_job(config, &duplicate.id)?;
+    }
+
+    let Some(JobSpec {
+        schedule,
+        prompt,
+        delivery,
+    }) = spec
+    else {
+        if let Some(job) = current {
+            remove_job(config, &job.id)?;
+            tracing::info!("Removed {name} job");
+        }
+        return Ok(());
+    };
+
+    match current {
+        Some(job)
+            if job.schedule == schedule
+                && job.prompt.as_deref() == Some(prompt.as_str())
+                && job.delivery == delivery
+                && job.enabled => {}
+        Some(job) => {
+            let schedule_changed = job.schedule != schedule;
+            update_job(
+                config,
+                &job.id,
+                CronJobPatch {
+                    schedule: schedule_changed.then_some(schedule),
+                    prompt: Some(prompt),
+                    delivery: Some(delivery),
+                    enabled: Some(true),
+                    ..CronJobPatch::default()
+                },
+            )?;
+            tracing::info!("Updated {name} job");
+        }
+        None => {
+            add_agent_job(
+                config,
+                Some(name.to_string()),
+                schedule,
+                &prompt,
+                SessionTarget::Isolated,
+                None,
+                Some(delivery),
+                false,
+            )?;
+            tracing::info!("Scheduled {name} job");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn briefing_jobs(config: &Config) -> Vec<crate::cron::CronJob> {
+        list_jobs(config)
+            .unwrap()
+            .into_iter()
+            .filter(|job| job.name.as_deref() == Some(JOB_NAME))
+            .collect()
+    }
+
+    #[test]
+    fn sync_creates_updates_and_removes_the_briefing_job() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config.news.briefing = Some(NewsBriefingConfig {
+            schedule: "0 7 * * *".into(),
+            tz: None,
+            channel: "telegram".into(),
+            to: "123".into(),
+            topics: vec!["climate".into()],
+        });
+
+        sync(&config).unwrap();
+        sync(&config).unwrap();
+        let jobs = briefing_jobs(&config);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].expression, "0 7 * * *");
+        assert_eq!(jobs[0].delivery.channel.as_deref(), Some("telegram"));
+        assert!(jobs[0]
+            .prompt
+            .as_deref()
+            .unwrap()
+            .contains("once per topic for: climate"));
+
+        config.news.briefing.as_mut().unwrap().schedule = "30 6 * * 1-5".into();
+        sync(&config).unwrap();
+        let jobs = briefing_jobs(&config);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].expression, "30 6 * * 1-5");
+
+        config.news.briefing = None;
+        sync(&config).unwrap();
+        assert!(briefing_jobs(&config).is_empty());
+    }
+
+    #[test]
+    fn sync_schedules_the_goal_review() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        config.goals.review = Some(GoalsReviewConfig {
+            schedule: "0 18 * * Sun".into(),
+            tz: Some("Europe/Berlin".into()),
+            channel: "telegram".into(),
+            to: "123".into(),
+        });
+
+        sync(&config).unwrap();
+        let reviews: Vec<_> = list_jobs(&config)
+            .unwrap()
+            .into_iter()
+            .filter(|job| job.name.as_deref() == Some(GOALS_REVIEW_JOB_NAME))
+            .collect();
+        assert_eq!(reviews.len(), 1);
+        assert!(reviews[0]
+            .prompt
+            .as_deref()
+            .unwrap()
+            .starts_with("Goal review: use the goals tool"));
+        assert!(briefing_jobs(&config).is_empty());
+
+        config.goals.enabled = false;
+        sync(&config).unwrap();
+        assert!(list_jobs(&config).unwrap().is_empty());
+    }
+}
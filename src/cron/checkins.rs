@@ -0,0 +1,367 @@
+//! Proactive check-ins (`[checkins]`).
+//!
+//! Every minute each `[[checkins.targets]]` entry is checked for a due
+//! trigger: its `schedule` passed since the last check-in, or nobody has
+//! talked in that conversation for `idle_days`. Due check-ins wait out the
+//! target's quiet hours, then the agent writes the message from the template
+//! and its memory and it is delivered like a cron announcement.
+//!
+//! Conversation activity and the time of the last check-in per target are
+//! kept in `<workspace>/cron/checkins.db`.
+
+use super::scheduler::{agent_job_policy_block, deliver_announcement};
+use super::{next_run_for_schedule, Schedule};
+use crate::config::{CheckinTargetConfig, Config};
+use crate::security::SecurityPolicy;
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use tokio::time::{self, Duration};
+
+const POLL_SECS: u64 = 60;
+const CHECKINS_COMPONENT: &str = "checkins";
+
+/// Parse a `"HH:MM-HH:MM"` range. The range may wrap past midnight.
+pub fn parse_quiet_hours(value: &str) -> Result<(NaiveTime, NaiveTime)> {
+    let (start, end) = value.split_once('-').context("expected \"HH:MM-HH:MM\"")?;
+    let parse = |part: &str| {
+        NaiveTime::parse_from_str(part.trim(), "%H:%M")
+            .with_context(|| format!("'{}' is not a HH:MM time", part.trim()))
+    };
+    Ok((parse(start)?, parse(end)?))
+}
+
+fn in_quiet_hours(target: &CheckinTargetConfig, now: DateTime<Utc>) -> bool {
+    let Some((start, end)) = target
+        .quiet_hours
+        .as_deref()
+        .and_then(|value| parse_quiet_hours(value).ok())
+    else {
+        return false;
+    };
+    let local = match target.tz.as_deref().map(str::parse::<chrono_tz::Tz>) {
+        Some(Ok(tz)) => now.with_timezone(&tz).time(),
+        _ => now.time(),
+    };
+    if start <= end {
+        start <= local && local < end
+    } else {
+        local >= start || local < end
+    }
+}
+
+/// Stored activity for one target conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TargetState {
+    first_seen: DateTime<Utc>,
+    last_activity: Option<DateTime<Utc>>,
+    last_checkin: Option<DateTime<Utc>>,
+}
+
+/// Why `target` should get a check-in at `now`, if it should.
+fn due_reason(
+    target: &CheckinTargetConfig,
+    state: &TargetState,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    if let Some(expr) = &target.schedule {
+        let schedule = Schedule::Cron {
+            expr: expr.clone(),
+            tz: target.tz.clone(),
+        };
+        let since = state.last_checkin.unwrap_or(state.first_seen);
+        if next_run_for_schedule(&schedule, since).is_ok_and(|next| next <= now) {
+            return Some("scheduled check-in".into());
+        }
+    }
+    if let (Some(days), Some(last_activity)) = (target.idle_days, state.last_activity) {
+        let since = state
+            .last_checkin
+            .map_or(last_activity, |at| at.max(last_activity));
+        if now - since >= chrono::Duration::days(i64::from(days)) {
+            return Some(format!(
+                "no conversation in {days} day{}",
+                if days == 1 { "" } else { "s" }
+            ));
+        }
+    }
+    None
+}
+
+fn render_prompt(template: &str, target: &CheckinTargetConfig, reason: &str) -> String {
+    let body = if template.contains("{reason}") {
+        template.replace("{reason}", reason)
+    } else {
+        format!("{} ({reason})", template.trim_end())
+    };
+    format!("[checkin:{}:{}] {body}", target.channel, target.to)
+}
+
+/// Note that a conversation happened, for idle check-ins.
+pub fn record_activity(workspace_dir: &Path, channel: &str, recipient: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO checkins (channel, recipient, first_seen, last_activity)
+             VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT(channel, recipient) DO UPDATE SET last_activity = excluded.last_activity",
+            params![channel, recipient, now],
+        )
+        .context("Failed to record conversation activity")?;
+        Ok(())
+    })
+}
+
+fn load_state(workspace_dir: &Path, channel: &str, recipient: &str) -> Result<TargetState> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO checkins (channel, recipient, first_seen) VALUES (?1, ?2, ?3)",
+            params![channel, recipient, Utc::now().to_rfc3339()],
+        )?;
+        let row: (String, Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT first_seen, last_activity, last_checkin FROM checkins
+                 WHERE channel = ?1 AND recipient = ?2",
+                params![channel, recipient],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )
+            .optional()?
+            .context("Check-in state row missing")?;
+        let parse = |value: &str| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|at| at.with_timezone(&Utc))
+                .with_context(|| format!("Invalid timestamp in check-in state: {value}"))
+        };
+        Ok(TargetState {
+            first_seen: parse(&row.0)?,
+            last_activity: row.1.as_deref().map(parse).transpose()?,
+            last_checkin: row.2.as_deref().map(parse).transpose()?,
+        })
+    })
+}
+
+fn record_checkin(
+    workspace_dir: &Path,
+    channel: &str,
+    recipient: &str,
+    at: DateTime<Utc>,
+) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "UPDATE checkins SET last_checkin = ?3 WHERE channel = ?1 AND recipient = ?2",
+            params![channel, recipient, at.to_rfc3339()],
+        )
+        .context("Failed to record check-in")?;
+        Ok(())
+    })
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("cron").join("checkins.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open check-ins DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS checkins (
+            channel       TEXT NOT NULL,
+            recipient     TEXT NOT NULL,
+            first_seen    TEXT NOT NULL,
+            last_activity TEXT,
+            last_checkin  TEXT,
+            PRIMARY KEY (channel, recipient)
+        );",
+    )
+    .context("Failed to initialize check-ins schema")?;
+
+    f(&conn)
+}
+
+/// Check every target once a minute until the task is cancelled.
+pub async fn run(config: Config) -> Result<()> {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let mut interval = time::interval(Duration::from_secs(POLL_SECS));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+
+    crate::health::mark_component_ok(CHECKINS_COMPONENT);
+    loop {
+        interval.tick().await;
+        crate::health::mark_component_ok(CHECKINS_COMPONENT);
+        for target in &config.checkins.targets {
+            let now = Utc::now();
+            let state = match load_state(&config.workspace_dir, &target.channel, &target.to) {
+                Ok(state) => state,
+                Err(e) => {
+                    crate::health::mark_component_error(CHECKINS_COMPONENT, e.to_string());
+                    tracing::warn!(
+                        "Check-in state for {}:{} failed: {e}",
+                        target.channel,
+                        target.to
+                    );
+                    continue;
+                }
+            };
+            let Some(reason) = due_reason(target, &state, now) else {
+                continue;
+            };
+            if in_quiet_hours(target, now) {
+                continue;
+            }
+            // Record first so a failing provider is not retried every minute.
+            if let Err(e) = record_checkin(&config.workspace_dir, &target.channel, &target.to, now)
+            {
+                tracing::warn!(
+                    "Check-in for {}:{} not recorded: {e}",
+                    target.channel,
+                    target.to
+                );
+                continue;
+            }
+            send_checkin(&config, &security, target, &reason).await;
+        }
+    }
+}
+
+async fn send_checkin(
+    config: &Config,
+    security: &SecurityPolicy,
+    target: &CheckinTargetConfig,
+    reason: &str,
+) {
+    tracing::info!(channel = %target.channel, to = %target.to, reason, "Sending check-in");
+    if let Some(blocked) = agent_job_policy_block(security) {
+        tracing::warn!(
+            "Check-in for {}:{} skipped: {blocked}",
+            target.channel,
+            target.to
+        );
+        return;
+    }
+
+    let template = target
+        .template
+        .as_deref()
+        .unwrap_or(&config.checkins.template);
+    let result = Box::pin(crate::agent::run(
+        config.clone(),
+        Some(render_prompt(template, target, reason)),
+        None,
+        None,
+        config.default_temperature,
+        vec![],
+        false,
+    ))
+    .await;
+
+    match result {
+        Ok(message) if message.trim().is_empty() => {
+            tracing::warn!(
+                "Check-in for {}:{} produced no message",
+                target.channel,
+                target.to
+            );
+        }
+        Ok(message) => {
+            if let Err(e) =
+                deliver_announcement(config, &target.channel, &target.to, message.trim()).await
+            {
+                crate::health::mark_component_error(
+                    CHECKINS_COMPONENT,
+                    format!("delivery failed: {e}"),
+                );
+                tracing::warn!(
+                    "Check-in for {}:{} delivery failed: {e}",
+                    target.channel,
+                    target.to
+                );
+            }
+        }
+        Err(e) => {
+            crate::health::mark_component_error(CHECKINS_COMPONENT, e.to_string());
+            tracing::warn!("Check-in for {}:{} failed: {e}", target.channel, target.to);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn target() -> CheckinTargetConfig {
+        CheckinTargetConfig {
+            channel: "telegram".into(),
+            to: "42".into(),
+            tz: Some("Europe/Berlin".into()),
+            quiet_hours: Some("22:00-08:00".into()),
+            idle_days: Some(3),
+            schedule: Some("0 9 * * Mon".into()),
+            template: None,
+        }
+    }
+
+    fn utc(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight_in_the_target_timezone() {
+        let target = target();
+        // Berlin is UTC+1 in early March.
+        assert!(in_quiet_hours(&target, utc(4, 21)));
+        assert!(in_quiet_hours(&target, utc(5, 6)));
+        assert!(!in_quiet_hours(&target, utc(5, 7)));
+        assert!(!in_quiet_hours(&target, utc(5, 12)));
+        assert!(parse_quiet_hours("9pm-7am").is_err());
+    }
+
+    #[test]
+    fn triggers_fire_on_schedule_and_after_idle_days() {
+        let mut target = target();
+        // Wednesday 4 March; next Monday 09:00 Berlin is 9 March 08:00 UTC.
+        let mut state = TargetState {
+            first_seen: utc(4, 12),
+            last_activity: None,
+            last_checkin: None,
+        };
+        assert_eq!(due_reason(&target, &state, utc(8, 12)), None);
+        assert_eq!(
+            due_reason(&target, &state, utc(9, 8)).as_deref(),
+            Some("scheduled check-in")
+        );
+
+        target.schedule = None;
+        state.last_activity = Some(utc(4, 12));
+        assert_eq!(due_reason(&target, &state, utc(7, 11)), None);
+        assert_eq!(
+            due_reason(&target, &state, utc(7, 12)).as_deref(),
+            Some("no conversation in 3 days")
+        );
+        state.last_checkin = Some(utc(7, 12));
+        assert_eq!(due_reason(&target, &state, utc(8, 12)), None);
+    }
+
+    #[test]
+    fn activity_and_checkins_are_persisted_per_target() {
+        let tmp = TempDir::new().unwrap();
+        let first = load_state(tmp.path(), "telegram", "42").unwrap();
+        assert_eq!(first.last_activity, None);
+
+        record_activity(tmp.path(), "telegram", "42").unwrap();
+        record_checkin(tmp.path(), "telegram", "42", utc(4, 12)).unwrap();
+        let state = load_state(tmp.path(), "telegram", "42").unwrap();
+        assert_eq!(state.first_seen, first.first_seen);
+        assert!(state.last_activity.is_some());
+        assert_eq!(state.last_checkin, Some(utc(4, 12)));
+        assert!(load_state(tmp.path(), "telegram", "7")
+            .unwrap()
+            .last_activity
+            .is_none());
+
+        assert_eq!(
+            render_prompt("Say hi ({reason}).", &target(), "scheduled check-in"),
+            "[checkin:telegram:42] Say hi (scheduled check-in)."
+        );
+    }
+}
@@ -6,7 +6,10 @@ mod schedule;
 mod store;
 mod types;
 
+pub mod briefing;
+pub mod checkins;
 pub mod scheduler;
+pub mod watchers;
 
 #[allow(unused_imports)]
 pub use schedule::{
@@ -14,10 +17,14 @@ pub use schedule::{
 };
 #[allow(unused_imports)]
 pub use store::{
-    add_agent_job, add_job, add_shell_job, due_jobs, get_job, list_jobs, list_runs,
-    record_last_run, record_run, remove_job, reschedule_after_run, update_job,
+    add_agent_job, add_job, add_reminder_job, add_shell_job, due_jobs, get_job, list_jobs,
+    list_pending_batches, list_runs, record_last_run, record_pending_batch, record_run, remove_job,
+    remove_pending_batch, reschedule_after_run, update_job,
+};
+pub use types::{
+    CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, PendingBatch, Schedule, SessionTarget,
+    SEND_LATER_NAME_PREFIX, TIMER_NAME_PREFIX,
 };
-pub use types::{CronJob, CronJobPatch, CronRun, DeliveryConfig, JobType, Schedule, SessionTarget};
 
 #[allow(clippy::needless_pass_by_value)]
 pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<()> {
@@ -3,9 +3,12 @@ use crate::channels::{
 };
 use crate::config::Config;
 use crate::cron::{
-    due_jobs, next_run_for_schedule, record_last_run, record_run, remove_job, reschedule_after_run,
-    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, Schedule, SessionTarget,
+    due_jobs, get_job, list_pending_batches, next_run_for_schedule, record_last_run,
+    record_pending_batch, record_run, remove_job, remove_pending_batch, reschedule_after_run,
+    update_job, CronJob, CronJobPatch, DeliveryConfig, JobType, PendingBatch, Schedule,
+    SessionTarget,
 };
+use crate::providers::batch::{self, BatchRequest, BatchStatus};
 use crate::security::SecurityPolicy;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -18,6 +21,10 @@ use tokio::time::{self, Duration};
 const MIN_POLL_SECONDS: u64 = 5;
 const SHELL_JOB_TIMEOUT_SECS: u64 = 120;
 const SCHEDULER_COMPONENT: &str = "scheduler";
+/// Batch APIs complete in minutes to hours; avoid polling them on every tick.
+const BATCH_POLL_INTERVAL_SECS: u64 = 60;
+/// Provider batch windows are 24h; give up on batches that still fail to poll after that.
+const BATCH_MAX_AGE_HOURS: i64 = 25;
 
 pub async fn run(config: Config) -> Result<()> {
     let poll_secs = config.reliability.scheduler_poll_secs.max(MIN_POLL_SECONDS);
@@ -28,13 +35,25 @@ pub async fn run(config: Config) -> Result<()> {
         &config.workspace_dir,
     ));
 
+    if let Err(e) = super::briefing::sync(&config) {
+        tracing::warn!("Scheduled briefing sync failed: {e}");
+    }
+
     crate::health::mark_component_ok(SCHEDULER_COMPONENT);
+    let mut last_batch_poll: Option<std::time::Instant> = None;
 
     loop {
         interval.tick().await;
         // Keep scheduler liveness fresh even when there are no due jobs.
         crate::health::mark_component_ok(SCHEDULER_COMPONENT);
 
+        if last_batch_poll
+            .is_none_or(|at| at.elapsed() >= Duration::from_secs(BATCH_POLL_INTERVAL_SECS))
+        {
+            last_batch_poll = Some(std::time::Instant::now());
+            poll_pending_batches(&config).await;
+        }
+
         let jobs = match due_jobs(&config, Utc::now()) {
             Ok(jobs) => jobs,
             Err(e) => {
@@ -66,6 +85,7 @@ async fn execute_job_with_retry(
         let (success, output) = match job.job_type {
             JobType::Shell => run_job_command(config, security, job).await,
             JobType::Agent => run_agent_job(config, security, job).await,
+            JobType::Reminder => (true, reminder_message(job)),
         };
         last_output = output;
 
@@ -127,6 +147,12 @@ async fn execute_and_persist_job(
     crate::health::mark_component_ok(component);
     warn_if_high_frequency_agent_job(job);
 
+    if job.delivery.is_batch() && matches!(job.job_type, JobType::Agent) {
+        if let Some((success, output)) = submit_batch_job(config, security, job).await {
+            return (job.id.clone(), success, output);
+        }
+    }
+
     let started_at = Utc::now();
     let (success, output) = execute_job_with_retry(config, security, job).await;
     let finished_at = Utc::now();
@@ -135,39 +161,49 @@ async fn execute_and_persist_job(
     (job.id.clone(), success, output)
 }
 
-async fn run_agent_job(
-    config: &Config,
-    security: &SecurityPolicy,
-    job: &CronJob,
-) -> (bool, String) {
+fn reminder_message(job: &CronJob) -> String {
+    if job.is_timer() || job.is_scheduled_send() {
+        // Timers store their full announcement text; scheduled sends the message itself.
+        return job.prompt.clone().unwrap_or_default();
+    }
+    format!("⏰ Reminder: {}", job.prompt.as_deref().unwrap_or_default())
+}
+
+pub(super) fn agent_job_policy_block(security: &SecurityPolicy) -> Option<String> {
     if !security.can_act() {
-        return (
-            false,
-            "blocked by security policy: autonomy is read-only".to_string(),
-        );
+        return Some("blocked by security policy: autonomy is read-only".to_string());
     }
 
     if security.is_rate_limited() {
-        return (
-            false,
-            "blocked by security policy: rate limit exceeded".to_string(),
-        );
+        return Some("blocked by security policy: rate limit exceeded".to_string());
     }
 
     if !security.record_action() {
-        return (
-            false,
-            "blocked by security policy: action budget exhausted".to_string(),
-        );
+        return Some("blocked by security policy: action budget exhausted".to_string());
     }
+    None
+}
+
+fn agent_job_prompt(job: &CronJob) -> String {
     let name = job.name.clone().unwrap_or_else(|| "cron-job".to_string());
     let prompt = job.prompt.clone().unwrap_or_default();
-    let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
+    format!("[cron:{} {name}] {prompt}", job.id)
+}
+
+async fn run_agent_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> (bool, String) {
+    if let Some(blocked) = agent_job_policy_block(security) {
+        return (false, blocked);
+    }
+    let prefixed_prompt = agent_job_prompt(job);
     let model_override = job.model.clone();
 
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
+            Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prefixed_prompt),
                 None,
@@ -175,7 +211,7 @@ async fn run_agent_job(
                 config.default_temperature,
                 vec![],
                 false,
-            )
+            ))
             .await
         }
     };
@@ -250,6 +286,199 @@ async fn persist_job_result(
     success
 }
 
+/// The batch request for an agent job. It carries the synchronous run's
+/// identity, workspace, and skills prompt, without tools: batch requests
+/// cannot call them.
+fn batch_request(config: &Config, job: &CronJob) -> BatchRequest {
+    let model = job
+        .model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_default();
+    let skills = crate::skills::load_prompt_skills(&config.workspace_dir, config);
+    let system_prompt =
+        crate::channels::build_configured_system_prompt(config, &model, &[], &skills, true);
+    BatchRequest {
+        custom_id: job.id.clone(),
+        model,
+        system_prompt: Some(system_prompt),
+        prompt: agent_job_prompt(job),
+        temperature: config.default_temperature,
+    }
+}
+
+/// Submit a batch-mode agent job. Returns `None` when the default provider has
+/// no batch API or `api_url` points elsewhere, in which case the job runs
+/// synchronously instead.
+async fn submit_batch_job(
+    config: &Config,
+    security: &SecurityPolicy,
+    job: &CronJob,
+) -> Option<(bool, String)> {
+    let provider = config.default_provider.as_deref().unwrap_or("openrouter");
+    let Some(base_url) = batch::batch_base_url(provider, config.api_url.as_deref()) else {
+        tracing::warn!(
+            "Cron job '{}' requests batch delivery but provider '{provider}' has no batch API at the configured endpoint; running synchronously",
+            job.id
+        );
+        return None;
+    };
+
+    let started_at = Utc::now();
+    let result = match agent_job_policy_block(security) {
+        Some(blocked) => Err(anyhow::anyhow!(blocked)),
+        None => {
+            let request = batch_request(config, job);
+            batch::submit_batch(provider, base_url, config.api_key.as_deref(), &request).await
+        }
+    };
+
+    let (success, output) = match result {
+        Ok(batch_id) => {
+            let pending = PendingBatch {
+                job_id: job.id.clone(),
+                provider: provider.to_string(),
+                batch_id: batch_id.clone(),
+                submitted_at: started_at,
+            };
+            match record_pending_batch(config, &pending) {
+                Ok(()) => (true, format!("batch submitted: {provider}/{batch_id}")),
+                Err(e) => (false, format!("batch submitted but not tracked: {e}")),
+            }
+        }
+        Err(e) => (false, format!("batch submission failed: {e}")),
+    };
+
+    if success {
+        // Completion (run record + delivery) is handled by `poll_pending_batches`.
+        let persisted = if matches!(job.schedule, Schedule::At { .. }) {
+            // One-shot jobs must not fire again while their batch is still pending.
+            record_last_run(config, &job.id, Utc::now(), true, &output).and_then(|()| {
+                update_job(
+                    config,
+                    &job.id,
+                    CronJobPatch {
+                        enabled: Some(false),
+                        ..CronJobPatch::default()
+                    },
+                )
+                .map(|_| ())
+            })
+        } else {
+            reschedule_after_run(config, job, true, &output)
+        };
+        if let Err(e) = persisted {
+            tracing::warn!("Failed to persist scheduler run result: {e}");
+        }
+    } else {
+        persist_job_result(config, job, false, &output, started_at, Utc::now()).await;
+    }
+    Some((success, output))
+}
+
+/// Check every pending provider batch and finalize the ones that have ended.
+async fn poll_pending_batches(config: &Config) {
+    let pending = match list_pending_batches(config) {
+        Ok(pending) => pending,
+        Err(e) => {
+            tracing::warn!("Failed to list pending cron batches: {e}");
+            return;
+        }
+    };
+
+    for entry in pending {
+        let Some(base_url) = batch::batch_base_url(&entry.provider, config.api_url.as_deref())
+        else {
+            let reason = format!(
+                "provider '{}' no longer has a batch API at the configured endpoint",
+                entry.provider
+            );
+            finalize_batch(config, &entry, BatchStatus::Failed(reason)).await;
+            continue;
+        };
+        let status = match batch::poll_batch(
+            &entry.provider,
+            base_url,
+            config.api_key.as_deref(),
+            &entry.batch_id,
+            &entry.job_id,
+        )
+        .await
+        {
+            Ok(status) => status,
+            // Transient polling errors keep the batch pending until the
+            // provider's completion window has clearly elapsed.
+            Err(e)
+                if Utc::now() - entry.submitted_at
+                    < chrono::Duration::hours(BATCH_MAX_AGE_HOURS) =>
+            {
+                tracing::warn!("Polling cron batch {} failed: {e}", entry.batch_id);
+                continue;
+            }
+            Err(e) => BatchStatus::Failed(format!("batch polling failed: {e}")),
+        };
+        finalize_batch(config, &entry, status).await;
+    }
+}
+
+async fn finalize_batch(config: &Config, entry: &PendingBatch, status: BatchStatus) {
+    let (success, output) = match status {
+        BatchStatus::Pending => return,
+        BatchStatus::Completed(text) if text.trim().is_empty() => {
+            (true, "agent job executed".to_string())
+        }
+        BatchStatus::Completed(text) => (true, text),
+        BatchStatus::Failed(reason) => (false, reason),
+    };
+
+    if let Err(e) = remove_pending_batch(config, &entry.batch_id) {
+        tracing::warn!("Failed to clear pending cron batch {}: {e}", entry.batch_id);
+    }
+
+    let job = match get_job(config, &entry.job_id) {
+        Ok(job) => job,
+        Err(e) => {
+            tracing::warn!(
+                "Cron batch {} finished for missing job {}: {e}",
+                entry.batch_id,
+                entry.job_id
+            );
+            return;
+        }
+    };
+
+    let finished_at = Utc::now();
+    let mut success = success;
+    if success {
+        if let Err(e) = deliver_if_configured(config, &job, &output).await {
+            if job.delivery.best_effort {
+                tracing::warn!("Cron delivery failed (best_effort): {e}");
+            } else {
+                success = false;
+                tracing::warn!("Cron delivery failed: {e}");
+            }
+        }
+    }
+
+    let _ = record_run(
+        config,
+        &job.id,
+        entry.submitted_at,
+        finished_at,
+        if success { "ok" } else { "error" },
+        Some(&output),
+        (finished_at - entry.submitted_at).num_milliseconds(),
+    );
+
+    if is_one_shot_auto_delete(&job) && success {
+        if let Err(e) = remove_job(config, &job.id) {
+            tracing::warn!("Failed to remove one-shot cron job after success: {e}");
+        }
+        return;
+    }
+    let _ = record_last_run(config, &job.id, finished_at, success, &output);
+}
+
 fn is_one_shot_auto_delete(job: &CronJob) -> bool {
     job.delete_after_run && matches!(job.schedule, Schedule::At { .. })
 }
@@ -283,7 +512,26 @@ fn warn_if_high_frequency_agent_job(job: &CronJob) {
 
 async fn deliver_if_configured(config: &Config, job: &CronJob, output: &str) -> Result<()> {
     let delivery: &DeliveryConfig = &job.delivery;
-    if !delivery.mode.eq_ignore_ascii_case("announce") {
+    if delivery.is_batch() {
+        // Batch results are announced only when a target is configured.
+        if delivery.channel.is_none() || delivery.to.is_none() {
+            return Ok(());
+        }
+    } else if !delivery.mode.eq_ignore_ascii_case("announce") {
+        return Ok(());
+    }
+
+    if !delivery.recipients.is_empty() {
+        let mut raw = Vec::with_capacity(delivery.recipients.len() + 1);
+        if let (Some(channel), Some(to)) = (&delivery.channel, &delivery.to) {
+            raw.push(format!("{channel}:{to}"));
+        }
+        raw.extend(delivery.recipients.iter().cloned());
+        let targets = crate::channels::broadcast::parse_targets(&raw, &config.broadcast)?;
+        let report = crate::channels::broadcast::broadcast(config, targets, output).await;
+        if report.failed() > 0 {
+            anyhow::bail!("{}", report.summary());
+        }
         return Ok(());
     }
 
@@ -305,6 +553,13 @@ pub(crate) async fn deliver_announcement(
     target: &str,
     output: &str,
 ) -> Result<()> {
+    let output = &crate::channels::links::rewrite_urls(output, &config.channels_config.links).await;
+    // Prefer the live channel when the daemon runs channels in this process;
+    // this also reaches channels that cannot be built here (e.g. iMessage).
+    if let Some(running) = crate::channels::delegation::running_channel(channel) {
+        running.send(&SendMessage::new(output, target)).await?;
+        return Ok(());
+    }
     match channel.to_ascii_lowercase().as_str() {
         "telegram" => {
             let tg = config
@@ -316,7 +571,8 @@ pub(crate) async fn deliver_announcement(
                 tg.bot_token.clone(),
                 tg.allowed_users.clone(),
                 tg.mention_only,
-            );
+            )
+            .with_link_previews(tg.link_previews);
             channel.send(&SendMessage::new(output, target)).await?;
         }
         "discord" => {
@@ -331,9 +587,11 @@ pub(crate) async fn deliver_announcement(
                 dc.allowed_users.clone(),
                 dc.listen_to_bots,
                 dc.mention_only,
-            );
+            )
+            .with_link_previews(dc.link_previews);
             channel.send(&SendMessage::new(output, target)).await?;
         }
+        "discord_voice" => crate::channels::discord_voice::announce(target, output).await?,
         "slack" => {
             let sl = config
                 .channels_config
@@ -704,6 +962,26 @@ mod tests {
         assert!(output.contains("always_missing_for_retry_test"));
     }
 
+    #[tokio::test]
+    async fn reminder_job_outputs_reminder_text_without_running_anything() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.autonomy.level = crate::security::AutonomyLevel::ReadOnly;
+        let mut job = test_job("");
+        job.job_type = JobType::Reminder;
+        job.prompt = Some("call mom".into());
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+
+        let (success, output) = execute_job_with_retry(&config, &security, &job).await;
+        assert!(success);
+        assert_eq!(output, "⏰ Reminder: call mom");
+
+        job.name = Some("send_later:telegram:42".into());
+        job.prompt = Some("happy birthday!".into());
+        let (_, output) = execute_job_with_retry(&config, &security, &job).await;
+        assert_eq!(output, "happy birthday!");
+    }
+
     #[tokio::test]
     async fn run_agent_job_returns_error_without_provider_key() {
         let tmp = TempDir::new().unwrap();
@@ -909,6 +1187,7 @@ mod tests {
                 channel: Some("telegram".into()),
                 to: Some("123456".into()),
                 best_effort: false,
+                recipients: Vec::new(),
             }),
             false,
         )
@@ -947,6 +1226,7 @@ mod tests {
                 channel: Some("telegram".into()),
                 to: Some("123456".into()),
                 best_effort: true,
+                recipients: Vec::new(),
             }),
             false,
         )
@@ -1007,7 +1287,157 @@ mod tests {
             channel: Some("invalid".into()),
             to: Some("target".into()),
             best_effort: true,
+            recipients: Vec::new(),
+        };
+        let err = deliver_if_configured(&config, &job, "x").await.unwrap_err();
+        assert!(err.to_string().contains("unsupported delivery channel"));
+    }
+
+    #[tokio::test]
+    async fn deliver_if_configured_broadcasts_to_recipients() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.broadcast.per_channel_interval_ms = 0;
+        config.broadcast.allowed_targets = vec!["invalid:*".into()];
+        let mut job = test_job("echo ok");
+        job.delivery = DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("invalid".into()),
+            to: Some("a".into()),
+            best_effort: false,
+            recipients: vec!["invalid:b".into(), "other:c".into()],
+        };
+
+        let err = deliver_if_configured(&config, &job, "x")
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Broadcast delivered to 0 of 3 recipients."));
+        assert!(err.contains("- invalid:b: unsupported delivery channel"));
+        assert!(err.contains("- other:c: not in [broadcast].allowed_targets"));
+    }
+
+    fn batch_delivery() -> DeliveryConfig {
+        DeliveryConfig {
+            mode: "batch".into(),
+            ..DeliveryConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn submit_batch_job_falls_back_when_provider_lacks_batch_api() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp).await;
+        config.default_provider = Some("ollama".into());
+        let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+        let mut job = test_job("");
+        job.job_type = JobType::Agent;
+        job.delivery = batch_delivery();
+
+        assert!(submit_batch_job(&config, &security, &job).await.is_none());
+        assert!(cron::list_pending_batches(&config).unwrap().is_empty());
+
+        config.default_provider = Some("openai".into());
+        config.api_url = Some("https://proxy.example.com/v1".into());
+        assert!(submit_batch_job(&config, &security, &job).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn batch_request_carries_the_agent_system_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("");
+        job.job_type = JobType::Agent;
+        job.prompt = Some("Summarize the day".into());
+
+        let request = batch_request(&config, &job);
+        let system_prompt = request.system_prompt.unwrap();
+        assert!(system_prompt.contains(&format!(
+            "Working directory: `{}`",
+            config.workspace_dir.display()
+        )));
+        assert!(request.prompt.contains("Summarize the day"));
+    }
+
+    #[tokio::test]
+    async fn finalize_batch_records_completed_run_and_clears_pending() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = cron::add_agent_job(
+            &config,
+            Some("nightly-report".into()),
+            crate::cron::Schedule::Cron {
+                expr: "0 3 * * *".into(),
+                tz: None,
+            },
+            "Summarize metrics",
+            SessionTarget::Isolated,
+            None,
+            Some(batch_delivery()),
+            false,
+        )
+        .unwrap();
+        let entry = PendingBatch {
+            job_id: job.id.clone(),
+            provider: "anthropic".into(),
+            batch_id: "msgbatch_1".into(),
+            submitted_at: Utc::now() - ChronoDuration::minutes(5),
         };
+        cron::record_pending_batch(&config, &entry).unwrap();
+
+        finalize_batch(&config, &entry, BatchStatus::Pending).await;
+        assert_eq!(cron::list_pending_batches(&config).unwrap().len(), 1);
+        assert!(cron::list_runs(&config, &job.id, 10).unwrap().is_empty());
+
+        finalize_batch(
+            &config,
+            &entry,
+            BatchStatus::Completed("Report ready".into()),
+        )
+        .await;
+        assert!(cron::list_pending_batches(&config).unwrap().is_empty());
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].status, "ok");
+        assert_eq!(runs[0].output.as_deref(), Some("Report ready"));
+        let updated = cron::get_job(&config, &job.id).unwrap();
+        assert_eq!(updated.last_output.as_deref(), Some("Report ready"));
+    }
+
+    #[tokio::test]
+    async fn finalize_batch_records_failure() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let job = cron::add_job(&config, "*/5 * * * *", "echo ok").unwrap();
+        let entry = PendingBatch {
+            job_id: job.id.clone(),
+            provider: "openai".into(),
+            batch_id: "batch_err".into(),
+            submitted_at: Utc::now(),
+        };
+        cron::record_pending_batch(&config, &entry).unwrap();
+
+        finalize_batch(
+            &config,
+            &entry,
+            BatchStatus::Failed("OpenAI batch expired".into()),
+        )
+        .await;
+        let runs = cron::list_runs(&config, &job.id, 10).unwrap();
+        assert_eq!(runs[0].status, "error");
+        assert!(cron::list_pending_batches(&config).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn deliver_if_configured_skips_batch_without_target() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp).await;
+        let mut job = test_job("echo ok");
+        job.delivery = batch_delivery();
+        assert!(deliver_if_configured(&config, &job, "x").await.is_ok());
+
+        job.delivery.channel = Some("invalid".into());
+        job.delivery.to = Some("target".into());
         let err = deliver_if_configured(&config, &job, "x").await.unwrap_err();
         assert!(err.to_string().contains("unsupported delivery channel"));
     }
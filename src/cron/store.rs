@@ -1,7 +1,7 @@
 use crate::config::Config;
 use crate::cron::{
     next_run_for_schedule, schedule_cron_expression, validate_schedule, CronJob, CronJobPatch,
-    CronRun, DeliveryConfig, JobType, Schedule, SessionTarget,
+    CronRun, DeliveryConfig, JobType, PendingBatch, Schedule, SessionTarget,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -113,6 +113,44 @@ pub fn add_agent_job(
     get_job(config, &id)
 }
 
+/// Add a one-shot reminder that delivers `text` at `at` and then deletes itself.
+pub fn add_reminder_job(
+    config: &Config,
+    name: Option<String>,
+    at: DateTime<Utc>,
+    text: &str,
+    delivery: DeliveryConfig,
+) -> Result<CronJob> {
+    let now = Utc::now();
+    let schedule = Schedule::At { at };
+    validate_schedule(&schedule, now)?;
+    let next_run = next_run_for_schedule(&schedule, now)?;
+    let id = Uuid::new_v4().to_string();
+    let schedule_json = serde_json::to_string(&schedule)?;
+
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT INTO cron_jobs (
+                id, expression, command, schedule, job_type, prompt, name, session_target, model,
+                enabled, delivery, delete_after_run, created_at, next_run
+             ) VALUES (?1, '', '', ?2, 'reminder', ?3, ?4, 'isolated', NULL, 1, ?5, 1, ?6, ?7)",
+            params![
+                id,
+                schedule_json,
+                text,
+                name,
+                serde_json::to_string(&delivery)?,
+                now.to_rfc3339(),
+                next_run.to_rfc3339(),
+            ],
+        )
+        .context("Failed to insert reminder job")?;
+        Ok(())
+    })?;
+
+    get_job(config, &id)
+}
+
 pub fn list_jobs(config: &Config) -> Result<Vec<CronJob>> {
     with_connection(config, |conn| {
         let mut stmt = conn.prepare(
@@ -354,6 +392,59 @@ pub fn record_run(
     })
 }
 
+pub fn record_pending_batch(config: &Config, batch: &PendingBatch) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO cron_batches (batch_id, job_id, provider, submitted_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                batch.batch_id,
+                batch.job_id,
+                batch.provider,
+                batch.submitted_at.to_rfc3339(),
+            ],
+        )
+        .context("Failed to insert pending cron batch")?;
+        Ok(())
+    })
+}
+
+pub fn list_pending_batches(config: &Config) -> Result<Vec<PendingBatch>> {
+    with_connection(config, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT batch_id, job_id, provider, submitted_at
+             FROM cron_batches
+             ORDER BY submitted_at ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PendingBatch {
+                batch_id: row.get(0)?,
+                job_id: row.get(1)?,
+                provider: row.get(2)?,
+                submitted_at: parse_rfc3339(&row.get::<_, String>(3)?)
+                    .map_err(sql_conversion_error)?,
+            })
+        })?;
+
+        let mut batches = Vec::new();
+        for row in rows {
+            batches.push(row?);
+        }
+        Ok(batches)
+    })
+}
+
+pub fn remove_pending_batch(config: &Config, batch_id: &str) -> Result<()> {
+    with_connection(config, |conn| {
+        conn.execute(
+            "DELETE FROM cron_batches WHERE batch_id = ?1",
+            params![batch_id],
+        )
+        .context("Failed to delete pending cron batch")?;
+        Ok(())
+    })
+}
+
 fn truncate_cron_output(output: &str) -> String {
     if output.len() <= MAX_CRON_OUTPUT_BYTES {
         return output.to_string();
@@ -515,12 +606,7 @@ fn add_column_if_missing(conn: &Connection, name: &str, sql_type: &str) -> Resul
 
 fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
     let db_path = config.workspace_dir.join("cron").join("jobs.db");
-    if let Some(parent) = db_path.parent() {
-        std::fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create cron directory: {}", parent.display()))?;
-    }
-
-    let conn = Connection::open(&db_path)
+    let conn = crate::state::open_db(&db_path)
         .with_context(|| format!("Failed to open cron DB: {}", db_path.display()))?;
 
     conn.execute_batch(
@@ -558,7 +644,15 @@ fn with_connection<T>(config: &Config, f: impl FnOnce(&Connection) -> Result<T>)
         );
         CREATE INDEX IF NOT EXISTS idx_cron_runs_job_id ON cron_runs(job_id);
         CREATE INDEX IF NOT EXISTS idx_cron_runs_started_at ON cron_runs(started_at);
-        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);",
+        CREATE INDEX IF NOT EXISTS idx_cron_runs_job_started ON cron_runs(job_id, started_at);
+
+        CREATE TABLE IF NOT EXISTS cron_batches (
+            batch_id     TEXT PRIMARY KEY,
+            job_id       TEXT NOT NULL,
+            provider     TEXT NOT NULL,
+            submitted_at TEXT NOT NULL,
+            FOREIGN KEY (job_id) REFERENCES cron_jobs(id) ON DELETE CASCADE
+        );",
     )
     .context("Failed to initialize cron schema")?;
 
@@ -802,6 +896,28 @@ mod tests {
         assert_eq!(runs.len(), 2);
     }
 
+    #[test]
+    fn add_reminder_job_is_one_shot_reminder() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let at = Utc::now() + ChronoDuration::hours(1);
+        let delivery = DeliveryConfig {
+            mode: "announce".into(),
+            channel: Some("telegram".into()),
+            to: Some("12345".into()),
+            best_effort: true,
+            recipients: Vec::new(),
+        };
+
+        let job =
+            add_reminder_job(&config, Some("call mom".into()), at, "call mom", delivery).unwrap();
+        assert_eq!(job.job_type, JobType::Reminder);
+        assert_eq!(job.prompt.as_deref(), Some("call mom"));
+        assert!(job.delete_after_run);
+        assert_eq!(job.delivery.to.as_deref(), Some("12345"));
+        assert_eq!(job.schedule, Schedule::At { at });
+    }
+
     #[test]
     fn remove_job_cascades_run_history() {
         let tmp = TempDir::new().unwrap();
@@ -862,4 +978,30 @@ mod tests {
         assert!(last_output.ends_with(TRUNCATED_OUTPUT_MARKER));
         assert!(last_output.len() <= MAX_CRON_OUTPUT_BYTES);
     }
+
+    #[test]
+    fn pending_batches_round_trip_and_cascade_with_job() {
+        let tmp = TempDir::new().unwrap();
+        let config = test_config(&tmp);
+        let job = add_job(&config, "*/5 * * * *", "echo batch").unwrap();
+        let batch = PendingBatch {
+            job_id: job.id.clone(),
+            provider: "openai".into(),
+            batch_id: "batch_123".into(),
+            submitted_at: Utc::now(),
+        };
+
+        record_pending_batch(&config, &batch).unwrap();
+        let pending = list_pending_batches(&config).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].batch_id, "batch_123");
+        assert_eq!(pending[0].provider, "openai");
+
+        remove_pending_batch(&config, "batch_123").unwrap();
+        assert!(list_pending_batches(&config).unwrap().is_empty());
+
+        record_pending_batch(&config, &batch).unwrap();
+        remove_job(&config, &job.id).unwrap();
+        assert!(list_pending_batches(&config).unwrap().is_empty());
+    }
 }
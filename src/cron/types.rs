@@ -7,6 +7,8 @@ pub enum JobType {
     #[default]
     Shell,
     Agent,
+    /// Delivers the stored prompt text as-is; no shell command or agent run.
+    Reminder,
 }
 
 impl From<JobType> for &'static str {
@@ -14,6 +16,7 @@ impl From<JobType> for &'static str {
         match value {
             JobType::Shell => "shell",
             JobType::Agent => "agent",
+            JobType::Reminder => "reminder",
         }
     }
 }
@@ -25,8 +28,9 @@ impl TryFrom<&str> for JobType {
         match value.to_lowercase().as_str() {
             "shell" => Ok(JobType::Shell),
             "agent" => Ok(JobType::Agent),
+            "reminder" => Ok(JobType::Reminder),
             _ => Err(format!(
-                "Invalid job type '{}'. Expected one of: 'shell', 'agent'",
+                "Invalid job type '{}'. Expected one of: 'shell', 'agent', 'reminder'",
                 value
             )),
         }
@@ -84,6 +88,17 @@ pub struct DeliveryConfig {
     pub to: Option<String>,
     #[serde(default = "default_true")]
     pub best_effort: bool,
+    /// Further `channel:recipient` targets for announce mode. The job output
+    /// is broadcast to `channel`/`to` and all of these.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recipients: Vec<String>,
+}
+
+impl DeliveryConfig {
+    /// True when the job should be submitted through a provider batch API.
+    pub fn is_batch(&self) -> bool {
+        self.mode.eq_ignore_ascii_case("batch")
+    }
 }
 
 impl Default for DeliveryConfig {
@@ -93,6 +108,7 @@ impl Default for DeliveryConfig {
             channel: None,
             to: None,
             best_effort: true,
+            recipients: Vec::new(),
         }
     }
 }
@@ -122,6 +138,32 @@ pub struct CronJob {
     pub last_output: Option<String>,
 }
 
+/// Name prefix of reminder jobs created by the `timer_start` tool.
+pub const TIMER_NAME_PREFIX: &str = "timer:";
+/// Name prefix of reminder jobs created by the `send_later` tool; the rest
+/// of the name is the requesting conversation (`channel:recipient`).
+pub const SEND_LATER_NAME_PREFIX: &str = "send_later:";
+
+impl CronJob {
+    /// True for a conversation timer (a reminder job named `timer:<label>`).
+    pub fn is_timer(&self) -> bool {
+        self.job_type == JobType::Reminder
+            && self
+                .name
+                .as_deref()
+                .is_some_and(|name| name.starts_with(TIMER_NAME_PREFIX))
+    }
+
+    /// True for a scheduled message (a reminder job named `send_later:<requester>`).
+    pub fn is_scheduled_send(&self) -> bool {
+        self.job_type == JobType::Reminder
+            && self
+                .name
+                .as_deref()
+                .is_some_and(|name| name.starts_with(SEND_LATER_NAME_PREFIX))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CronRun {
     pub id: i64,
@@ -133,6 +175,15 @@ pub struct CronRun {
     pub duration_ms: Option<i64>,
 }
 
+/// Provider batch submitted for a `delivery.mode = "batch"` job, awaiting completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingBatch {
+    pub job_id: String,
+    pub provider: String,
+    pub batch_id: String,
+    pub submitted_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CronJobPatch {
     pub schedule: Option<Schedule>,
@@ -0,0 +1,312 @@
+//! File watcher triggers (`[[watchers]]`).
+//!
+//! Each watcher polls its path for created, modified, and removed files.
+//! Changes are collected until the path has been quiet for `debounce_secs`,
+//! then the watcher's prompt runs as an agent task with the changed files
+//! listed, and the output is delivered like a cron announcement.
+
+use super::scheduler::{agent_job_policy_block, deliver_announcement};
+use crate::config::{Config, WatcherConfig};
+use crate::security::SecurityPolicy;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, SystemTime};
+use tokio::time::{self, Duration};
+
+const POLL_SECS: u64 = 2;
+const WATCHERS_COMPONENT: &str = "watchers";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+impl ChangeKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Modified => "modified",
+            Self::Removed => "removed",
+        }
+    }
+
+    /// Combine an earlier pending change with a newer one; `None` cancels out.
+    fn merge(self, newer: Self) -> Option<Self> {
+        match (self, newer) {
+            (Self::Created, Self::Removed) => None,
+            (Self::Created, _) => Some(Self::Created),
+            (Self::Removed, Self::Created) => Some(Self::Modified),
+            (_, newer) => Some(newer),
+        }
+    }
+}
+
+/// Modification time and size of each watched file.
+type Snapshot = HashMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Glob pattern for a watcher path: a directory watches the files inside it.
+fn watch_pattern(path: &str) -> String {
+    let expanded = shellexpand::tilde(path.trim()).into_owned();
+    if Path::new(&expanded).is_dir() {
+        Path::new(&expanded)
+            .join("*")
+            .to_string_lossy()
+            .into_owned()
+    } else {
+        expanded
+    }
+}
+
+fn snapshot(pattern: &str) -> Snapshot {
+    let Ok(paths) = glob::glob(pattern) else {
+        return Snapshot::new();
+    };
+    paths
+        .filter_map(Result::ok)
+        .filter_map(|path| {
+            let meta = std::fs::metadata(&path).ok()?;
+            meta.is_file()
+                .then(|| (path, (meta.modified().ok(), meta.len())))
+        })
+        .collect()
+}
+
+fn diff(old: &Snapshot, new: &Snapshot) -> Vec<(PathBuf, ChangeKind)> {
+    let mut changes: Vec<(PathBuf, ChangeKind)> = new
+        .iter()
+        .filter_map(|(path, state)| match old.get(path) {
+            None => Some((path.clone(), ChangeKind::Created)),
+            Some(previous) if previous != state => Some((path.clone(), ChangeKind::Modified)),
+            Some(_) => None,
+        })
+        .collect();
+    changes.extend(
+        old.keys()
+            .filter(|path| !new.contains_key(*path))
+            .map(|path| (path.clone(), ChangeKind::Removed)),
+    );
+    changes
+}
+
+/// Polling state of one watcher.
+struct Watch {
+    config: WatcherConfig,
+    pattern: String,
+    files: Snapshot,
+    pending: BTreeMap<PathBuf, ChangeKind>,
+    last_change: Option<Instant>,
+}
+
+impl Watch {
+    /// Start watching; files that already exist do not trigger.
+    fn new(config: WatcherConfig) -> Self {
+        let pattern = watch_pattern(&config.path);
+        let files = snapshot(&pattern);
+        Self {
+            config,
+            pattern,
+            files,
+            pending: BTreeMap::new(),
+            last_change: None,
+        }
+    }
+
+    /// Record changes since the last poll and return the batch to act on
+    /// once the path has been quiet for `debounce_secs`.
+    fn poll(&mut self, now: Instant) -> Option<Vec<(PathBuf, ChangeKind)>> {
+        let files = snapshot(&self.pattern);
+        let changes = diff(&self.files, &files);
+        self.files = files;
+
+        if !changes.is_empty() {
+            self.last_change = Some(now);
+        }
+        for (path, kind) in changes {
+            let merged = match self.pending.get(&path) {
+                Some(earlier) => earlier.merge(kind),
+                None => Some(kind),
+            };
+            match merged {
+                Some(kind) => self.pending.insert(path, kind),
+                None => self.pending.remove(&path),
+            };
+        }
+
+        let quiet = Duration::from_secs(self.config.debounce_secs);
+        if self.pending.is_empty() || self.last_change.is_some_and(|at| now - at < quiet) {
+            return None;
+        }
+        let events = &self.config.events;
+        let batch: Vec<_> = std::mem::take(&mut self.pending)
+            .into_iter()
+            .filter(|(_, kind)| events.iter().any(|e| e == kind.as_str()))
+            .collect();
+        (!batch.is_empty()).then_some(batch)
+    }
+}
+
+fn render_prompt(watcher: &WatcherConfig, changes: &[(PathBuf, ChangeKind)]) -> String {
+    let mut files = String::new();
+    for (path, kind) in changes {
+        let _ = writeln!(files, "- {} ({})", path.display(), kind.as_str());
+    }
+    let files = files.trim_end();
+    let body = if watcher.prompt.contains("{files}") {
+        watcher.prompt.replace("{files}", files)
+    } else {
+        format!("{}\n\nChanged files:\n{files}", watcher.prompt.trim_end())
+    };
+    format!("[watcher:{}] {body}", watcher.name)
+}
+
+/// Poll every configured watcher until the task is cancelled.
+pub async fn run(config: Config) -> Result<()> {
+    let security = SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir);
+    let mut watches: Vec<Watch> = config.watchers.iter().cloned().map(Watch::new).collect();
+    let mut interval = time::interval(Duration::from_secs(POLL_SECS));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+
+    crate::health::mark_component_ok(WATCHERS_COMPONENT);
+    loop {
+        interval.tick().await;
+        for watch in &mut watches {
+            if let Some(changes) = watch.poll(Instant::now()) {
+                trigger(&config, &security, &watch.config, &changes).await;
+            }
+        }
+    }
+}
+
+async fn trigger(
+    config: &Config,
+    security: &SecurityPolicy,
+    watcher: &WatcherConfig,
+    changes: &[(PathBuf, ChangeKind)],
+) {
+    tracing::info!(
+        watcher = %watcher.name,
+        files = changes.len(),
+        "File watcher triggered"
+    );
+    if let Some(blocked) = agent_job_policy_block(security) {
+        tracing::warn!("Watcher '{}' skipped: {blocked}", watcher.name);
+        return;
+    }
+
+    let result = Box::pin(crate::agent::run(
+        config.clone(),
+        Some(render_prompt(watcher, changes)),
+        None,
+        watcher.model.clone(),
+        config.default_temperature,
+        vec![],
+        false,
+    ))
+    .await;
+
+    match result {
+        Ok(output) => {
+            crate::health::mark_component_ok(WATCHERS_COMPONENT);
+            let (Some(channel), Some(to)) = (&watcher.channel, &watcher.to) else {
+                return;
+            };
+            let output = if output.trim().is_empty() {
+                "watcher task executed".to_string()
+            } else {
+                output
+            };
+            if let Err(e) = deliver_announcement(config, channel, to, &output).await {
+                crate::health::mark_component_error(
+                    WATCHERS_COMPONENT,
+                    format!("delivery failed: {e}"),
+                );
+                tracing::warn!("Watcher '{}' delivery failed: {e}", watcher.name);
+            }
+        }
+        Err(e) => {
+            crate::health::mark_component_error(WATCHERS_COMPONENT, e.to_string());
+            tracing::warn!("Watcher '{}' failed: {e}", watcher.name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn watcher(path: &Path, events: &[&str]) -> WatcherConfig {
+        WatcherConfig {
+            name: "downloads".into(),
+            path: path.to_string_lossy().into_owned(),
+            prompt: "Summarize:\n{files}".into(),
+            events: events.iter().map(|e| (*e).to_string()).collect(),
+            debounce_secs: 5,
+            model: None,
+            channel: None,
+            to: None,
+        }
+    }
+
+    #[test]
+    fn changes_fire_once_the_path_is_quiet() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("old.pdf"), "x").unwrap();
+        let mut watch = Watch::new(watcher(&tmp.path().join("*.pdf"), &["created"]));
+        let start = Instant::now();
+        assert!(watch.poll(start).is_none());
+
+        std::fs::write(tmp.path().join("new.pdf"), "report").unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+        assert!(watch.poll(start).is_none(), "still debouncing");
+        std::fs::write(tmp.path().join("new.pdf"), "report v2").unwrap();
+        assert!(watch.poll(start + Duration::from_secs(2)).is_none());
+
+        let batch = watch.poll(start + Duration::from_secs(8)).unwrap();
+        assert_eq!(
+            batch,
+            vec![(tmp.path().join("new.pdf"), ChangeKind::Created)]
+        );
+        assert!(watch.poll(start + Duration::from_secs(20)).is_none());
+
+        // Modifications are filtered out when only `created` is watched.
+        std::fs::write(tmp.path().join("old.pdf"), "changed").unwrap();
+        assert!(watch.poll(start + Duration::from_secs(30)).is_none());
+        assert!(watch.poll(start + Duration::from_secs(40)).is_none());
+    }
+
+    #[test]
+    fn directories_watch_their_files_and_short_lived_files_cancel_out() {
+        let tmp = TempDir::new().unwrap();
+        let mut watch = Watch::new(watcher(tmp.path(), &["created", "removed"]));
+        let start = Instant::now();
+
+        std::fs::write(tmp.path().join("tmp.part"), "x").unwrap();
+        assert!(watch.poll(start).is_none());
+        std::fs::remove_file(tmp.path().join("tmp.part")).unwrap();
+        assert!(watch.poll(start + Duration::from_secs(1)).is_none());
+        assert!(watch.poll(start + Duration::from_secs(10)).is_none());
+    }
+
+    #[test]
+    fn prompt_lists_changed_files() {
+        let changes = vec![
+            (PathBuf::from("/dl/a.pdf"), ChangeKind::Created),
+            (PathBuf::from("/dl/b.pdf"), ChangeKind::Modified),
+        ];
+        let mut config = watcher(Path::new("/dl"), &["created"]);
+        assert_eq!(
+            render_prompt(&config, &changes),
+            "[watcher:downloads] Summarize:\n- /dl/a.pdf (created)\n- /dl/b.pdf (modified)"
+        );
+
+        config.prompt = "Summarize the new PDFs and text me.".into();
+        assert!(render_prompt(&config, &changes).ends_with(
+            "text me.\n\nChanged files:\n- /dl/a.pdf (created)\n- /dl/b.pdf (modified)"
+        ));
+    }
+}
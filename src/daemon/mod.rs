@@ -49,7 +49,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 max_backoff,
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    async move { Box::pin(crate::channels::start_channels(cfg)).await }
                 },
             ));
         } else {
@@ -79,7 +79,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             max_backoff,
             move || {
                 let cfg = scheduler_cfg.clone();
-                async move { crate::cron::scheduler::run(cfg).await }
+                async move { Box::pin(crate::cron::scheduler::run(cfg)).await }
             },
         ));
     } else {
@@ -87,9 +87,71 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.watchers.is_empty() {
+        crate::health::mark_component_ok("watchers");
+    } else {
+        let watchers_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "watchers",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = watchers_cfg.clone();
+                async move { Box::pin(crate::cron::watchers::run(cfg)).await }
+            },
+        ));
+    }
+
+    if config.checkins.enabled && !config.checkins.targets.is_empty() {
+        let checkins_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "checkins",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = checkins_cfg.clone();
+                async move { Box::pin(crate::cron::checkins::run(cfg)).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("checkins");
+    }
+
+    if config.monitoring.enabled {
+        let monitoring_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "monitoring",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = monitoring_cfg.clone();
+                async move { Box::pin(crate::monitoring::run(cfg)).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("monitoring");
+    }
+
+    if config.uptime.ping_url.is_some() {
+        let uptime_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "uptime",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = uptime_cfg.clone();
+                async move { crate::health::uptime::run(cfg).await }
+            },
+        ));
+    } else {
+        crate::health::mark_component_ok("uptime");
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
-    println!("   Components: gateway, channels, heartbeat, scheduler");
+    println!(
+        "   Components: gateway, channels, heartbeat, scheduler, watchers, checkins, monitoring, uptime"
+    );
     println!("   Ctrl+C to stop");
 
     tokio::signal::ctrl_c().await?;
@@ -101,6 +163,7 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     for handle in handles {
         let _ = handle.await;
     }
+    crate::skills::usage::flush().await;
 
     Ok(())
 }
@@ -116,9 +179,6 @@ pub fn state_file_path(config: &Config) -> PathBuf {
 fn spawn_state_writer(config: Config) -> JoinHandle<()> {
     tokio::spawn(async move {
         let path = state_file_path(&config);
-        if let Some(parent) = path.parent() {
-            let _ = tokio::fs::create_dir_all(parent).await;
-        }
 
         let mut interval = tokio::time::interval(Duration::from_secs(STATUS_FLUSH_SECONDS));
         loop {
@@ -131,7 +191,9 @@ fn spawn_state_writer(config: Config) -> JoinHandle<()> {
                 );
             }
             let data = serde_json::to_vec_pretty(&json).unwrap_or_else(|_| b"{}".to_vec());
-            let _ = tokio::fs::write(&path, data).await;
+            let path = path.clone();
+            let _ =
+                tokio::task::spawn_blocking(move || crate::state::write_atomic(&path, data)).await;
         }
     })
 }
@@ -198,7 +260,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
         for task in tasks {
             let prompt = format!("[Heartbeat Task] {task}");
             let temp = config.default_temperature;
-            match crate::agent::run(
+            match Box::pin(crate::agent::run(
                 config.clone(),
                 Some(prompt),
                 None,
@@ -206,7 +268,7 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
                 temp,
                 vec![],
                 false,
-            )
+            ))
             .await
             {
                 Ok(output) => {
@@ -404,6 +466,8 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            link_previews: true,
+            allowed_topics: Vec::new(),
         });
         assert!(has_supervised_channels(&config));
     }
@@ -536,6 +600,8 @@ mod tests {
             draft_update_interval_ms: 1000,
             interrupt_on_new_message: false,
             mention_only: false,
+            link_previews: true,
+            allowed_topics: Vec::new(),
         });
 
         let target = heartbeat_delivery_target(&config).unwrap();
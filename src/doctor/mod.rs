@@ -1281,6 +1281,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
         config.agents.insert(
@@ -1295,6 +1297,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
 
@@ -0,0 +1,432 @@
+//! Agent activity events published to an MQTT or NATS broker (`[events]`).
+//!
+//! The channel runtime reports messages received, replies sent, tools
+//! executed, and errors through [`publish`]. Each event becomes a JSON payload
+//! on a topic under `topic_prefix` (`zeroclaw/message/received/telegram` for
+//! MQTT, `zeroclaw.message.received.telegram` for NATS). Publishing never
+//! blocks the agent: events queue for a background connection task and are
+//! dropped when the broker is unreachable or the queue is full.
+//!
+//! Transports are feature-gated (`events-mqtt`, `events-nats`) and speak the
+//! plain-TCP wire protocols directly, publish-only and fire-and-forget.
+//! Independently of the broker, `[[events.webhooks]]` receive the same events
+//! as signed HTTP POSTs with retry.
+
+#[cfg(feature = "events-mqtt")]
+mod mqtt;
+#[cfg(feature = "events-nats")]
+mod nats;
+mod webhook;
+
+use crate::config::{EventRedactionConfig, EventsConfig};
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Events waiting for the broker before new ones are dropped.
+const QUEUE_CAPACITY: usize = 512;
+/// Longest wait between broker reconnection attempts.
+#[cfg(any(feature = "events-mqtt", feature = "events-nats"))]
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Characters of error messages kept in payloads.
+const MAX_ERROR_CHARS: usize = 500;
+
+/// A normalized agent activity event.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentEvent {
+    MessageReceived {
+        channel: String,
+        sender: String,
+        content: String,
+    },
+    ReplySent {
+        channel: String,
+        recipient: String,
+        content: String,
+    },
+    ToolExecuted {
+        tool: String,
+        success: bool,
+        duration: Duration,
+    },
+    Error {
+        component: String,
+        message: String,
+    },
+}
+
+impl AgentEvent {
+    /// The `[events] kinds` name of this event.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::MessageReceived { .. } => "message_received",
+            Self::ReplySent { .. } => "reply_sent",
+            Self::ToolExecuted { .. } => "tool_executed",
+            Self::Error { .. } => "error",
+        }
+    }
+
+    /// Channel the event happened on, for message events.
+    pub fn channel(&self) -> Option<&str> {
+        match self {
+            Self::MessageReceived { channel, .. } | Self::ReplySent { channel, .. } => {
+                Some(channel)
+            }
+            Self::ToolExecuted { .. } | Self::Error { .. } => None,
+        }
+    }
+
+    fn topic_segments(&self) -> Vec<&str> {
+        match self {
+            Self::MessageReceived { channel, .. } => vec!["message", "received", channel],
+            Self::ReplySent { channel, .. } => vec!["message", "sent", channel],
+            Self::ToolExecuted { tool, .. } => vec!["tool", "executed", tool],
+            Self::Error { component, .. } => vec!["error", component],
+        }
+    }
+
+    /// Topic (MQTT, `/`-separated) or subject (NATS, `.`-separated) for this event.
+    pub fn topic(&self, prefix: &str, separator: char) -> String {
+        let mut topic = prefix.trim().to_string();
+        for segment in self.topic_segments() {
+            topic.push(separator);
+            // Keep wildcard and separator characters out of dynamic segments.
+            topic.extend(segment.chars().map(|c| {
+                if matches!(c, '/' | '.' | '#' | '+' | '*' | '>') || c.is_whitespace() {
+                    '_'
+                } else {
+                    c
+                }
+            }));
+        }
+        topic
+    }
+
+    /// JSON payload with `[events.redaction]` applied.
+    pub fn payload(&self, redaction: &EventRedactionConfig) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "kind": self.kind(),
+            "at": Utc::now().to_rfc3339(),
+        });
+        let text = |content: &str| {
+            redaction.include_content.then(|| {
+                crate::util::truncate_with_ellipsis(
+                    &crate::agent::loop_::scrub_credentials(content),
+                    redaction.max_content_chars,
+                )
+            })
+        };
+        let party = |id: &str| {
+            if redaction.hash_senders {
+                hash_id(id)
+            } else {
+                id.to_string()
+            }
+        };
+        match self {
+            Self::MessageReceived {
+                channel,
+                sender,
+                content,
+            } => {
+                payload["channel"] = channel.as_str().into();
+                payload["sender"] = party(sender).into();
+                payload["length"] = content.chars().count().into();
+                if let Some(content) = text(content) {
+                    payload["content"] = content.into();
+                }
+            }
+            Self::ReplySent {
+                channel,
+                recipient,
+                content,
+            } => {
+                payload["channel"] = channel.as_str().into();
+                payload["recipient"] = party(recipient).into();
+                payload["length"] = content.chars().count().into();
+                if let Some(content) = text(content) {
+                    payload["content"] = content.into();
+                }
+            }
+            Self::ToolExecuted {
+                tool,
+                success,
+                duration,
+            } => {
+                payload["tool"] = tool.as_str().into();
+                payload["success"] = (*success).into();
+                payload["duration_ms"] = u64::try_from(duration.as_millis())
+                    .unwrap_or(u64::MAX)
+                    .into();
+            }
+            Self::Error { component, message } => {
+                payload["component"] = component.as_str().into();
+                payload["message"] = crate::util::truncate_with_ellipsis(
+                    &crate::agent::loop_::scrub_credentials(message),
+                    MAX_ERROR_CHARS,
+                )
+                .into();
+            }
+        }
+        payload
+    }
+}
+
+/// Stable, non-reversible stand-in for a sender or recipient id.
+fn hash_id(id: &str) -> String {
+    let digest = Sha256::digest(id.as_bytes());
+    format!("h_{}", &hex::encode(digest)[..16])
+}
+
+/// A serialized event ready for the broker.
+#[derive(Debug, Clone)]
+struct Published {
+    topic: String,
+    payload: Vec<u8>,
+}
+
+struct Bus {
+    config: EventsConfig,
+    separator: char,
+    tx: mpsc::Sender<Published>,
+}
+
+static BUS: OnceLock<Bus> = OnceLock::new();
+static WEBHOOKS: OnceLock<webhook::Webhooks> = OnceLock::new();
+
+/// Start webhook delivery if any are configured, and broker publishing if
+/// `[events]` is enabled. Later calls are ignored.
+pub fn init(config: &EventsConfig) {
+    if WEBHOOKS.get().is_none() {
+        if let Some(webhooks) = webhook::Webhooks::start(&config.webhooks) {
+            let _ = WEBHOOKS.set(webhooks);
+        }
+    }
+    if !config.enabled || BUS.get().is_some() {
+        return;
+    }
+    let available = (config.backend == "mqtt" && cfg!(feature = "events-mqtt"))
+        || (config.backend == "nats" && cfg!(feature = "events-nats"));
+    if !available {
+        tracing::warn!(
+            "[events] backend `{0}` is not available in this build; rebuild with \
+             `--features events-{0}` to publish events",
+            config.backend
+        );
+        return;
+    }
+    let separator = if config.backend == "nats" { '.' } else { '/' };
+    let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+    #[cfg(any(feature = "events-mqtt", feature = "events-nats"))]
+    tokio::spawn(run_connection(config.clone(), rx));
+    #[cfg(not(any(feature = "events-mqtt", feature = "events-nats")))]
+    drop(rx);
+    let _ = BUS.set(Bus {
+        config: config.clone(),
+        separator,
+        tx,
+    });
+    tracing::info!(
+        backend = %config.backend,
+        url = %config.url,
+        prefix = %config.topic_prefix,
+        "Publishing agent events"
+    );
+}
+
+/// Queue an event for publishing. No-op unless [`init`] enabled publishing
+/// or webhooks.
+pub fn publish(event: AgentEvent) {
+    if let Some(webhooks) = WEBHOOKS.get() {
+        webhooks.dispatch(&event);
+    }
+    let Some(bus) = BUS.get() else {
+        return;
+    };
+    if !bus.config.kinds.iter().any(|kind| kind == event.kind()) {
+        return;
+    }
+    let published = Published {
+        topic: event.topic(&bus.config.topic_prefix, bus.separator),
+        payload: event
+            .payload(&bus.config.redaction)
+            .to_string()
+            .into_bytes(),
+    };
+    if bus.tx.try_send(published).is_err() {
+        tracing::debug!(
+            "Event queue full or closed; dropping {} event",
+            event.kind()
+        );
+    }
+}
+
+/// Keep a broker connection up, reconnecting with backoff.
+#[cfg(any(feature = "events-mqtt", feature = "events-nats"))]
+async fn run_connection(config: EventsConfig, mut rx: mpsc::Receiver<Published>) {
+    let mut backoff = Duration::from_secs(1);
+    loop {
+        // Events queued while disconnected are stale; start fresh.
+        while rx.try_recv().is_ok() {}
+        let connected_at = std::time::Instant::now();
+        let result = match config.backend.as_str() {
+            #[cfg(feature = "events-mqtt")]
+            "mqtt" => mqtt::run(&config, &mut rx).await,
+            #[cfg(feature = "events-nats")]
+            "nats" => nats::run(&config, &mut rx).await,
+            _ => return,
+        };
+        // Both transports only return `Ok` once the queue has closed.
+        match result {
+            Ok(()) => return,
+            Err(e) => {
+                tracing::warn!("Event broker connection failed: {e}; retrying in {backoff:?}");
+            }
+        }
+        if connected_at.elapsed() > MAX_BACKOFF {
+            backoff = Duration::from_secs(1);
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Broker `host` and `port` from an `[events] url`.
+#[cfg(any(feature = "events-mqtt", feature = "events-nats"))]
+fn broker_address(url: &str, default_port: u16) -> anyhow::Result<(String, u16)> {
+    let parsed = reqwest::Url::parse(url.trim())?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("events.url has no host"))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    Ok((host, parsed.port().unwrap_or(default_port)))
+}
+
+/// Observer that publishes tool runs and errors before forwarding to `inner`.
+pub struct EventObserver {
+    inner: Box<dyn Observer>,
+}
+
+impl EventObserver {
+    pub fn new(inner: Box<dyn Observer>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Observer for EventObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        self.inner.record_event(event);
+        match event {
+            ObserverEvent::ToolCall {
+                tool,
+                duration,
+                success,
+            } => publish(AgentEvent::ToolExecuted {
+                tool: tool.clone(),
+                success: *success,
+                duration: *duration,
+            }),
+            ObserverEvent::Error { component, message } => publish(AgentEvent::Error {
+                component: component.clone(),
+                message: message.clone(),
+            }),
+            _ => {}
+        }
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        "events"
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_use_backend_separator_and_sanitize_segments() {
+        let received = AgentEvent::MessageReceived {
+            channel: "telegram".into(),
+            sender: "alice".into(),
+            content: "hi".into(),
+        };
+        assert_eq!(
+            received.topic("zeroclaw", '/'),
+            "zeroclaw/message/received/telegram"
+        );
+        assert_eq!(
+            received.topic("home.agent", '.'),
+            "home.agent.message.received.telegram"
+        );
+
+        let tool = AgentEvent::ToolExecuted {
+            tool: "mcp/fs.read #1".into(),
+            success: true,
+            duration: Duration::from_millis(5),
+        };
+        assert_eq!(tool.topic("zc", '/'), "zc/tool/executed/mcp_fs_read__1");
+    }
+
+    #[test]
+    fn payloads_redact_content_and_hash_senders_by_default() {
+        let event = AgentEvent::MessageReceived {
+            channel: "telegram".into(),
+            sender: "alice".into(),
+            content: "my token: abcdefghijklmnopqrstuvwxyz123456".into(),
+        };
+        let redacted = event.payload(&EventRedactionConfig::default());
+        assert_eq!(redacted["kind"], "message_received");
+        assert_eq!(redacted["sender"], hash_id("alice"));
+        assert_ne!(redacted["sender"], "alice");
+        assert!(redacted.get("content").is_none());
+        assert_eq!(redacted["length"], 42);
+
+        let open = EventRedactionConfig {
+            include_content: true,
+            max_content_chars: 280,
+            hash_senders: false,
+        };
+        let payload = event.payload(&open);
+        assert_eq!(payload["sender"], "alice");
+        let content = payload["content"].as_str().unwrap();
+        assert!(content.starts_with("my token: abcd"));
+        assert!(!content.contains("abcdefghijklmnopqrstuvwxyz123456"));
+    }
+
+    #[test]
+    fn tool_and_error_payloads_carry_outcome() {
+        let tool = AgentEvent::ToolExecuted {
+            tool: "shell".into(),
+            success: false,
+            duration: Duration::from_millis(1500),
+        };
+        let payload = tool.payload(&EventRedactionConfig::default());
+        assert_eq!(payload["success"], false);
+        assert_eq!(payload["duration_ms"], 1500);
+
+        let error = AgentEvent::Error {
+            component: "provider".into(),
+            message: "x".repeat(MAX_ERROR_CHARS * 2),
+        };
+        let payload = error.payload(&EventRedactionConfig::default());
+        assert!(payload["message"].as_str().unwrap().chars().count() <= MAX_ERROR_CHARS + 3);
+    }
+}
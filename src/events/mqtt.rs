@@ -0,0 +1,196 @@
+//! Minimal MQTT 3.1.1 publisher: CONNECT, QoS 0 PUBLISH, and keep-alive pings.
+
+use super::{broker_address, Published};
+use crate::config::EventsConfig;
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+const DEFAULT_PORT: u16 = 1883;
+const KEEP_ALIVE_SECS: u16 = 60;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Publish queued events until the queue closes (`Ok`) or the connection fails.
+pub(super) async fn run(config: &EventsConfig, rx: &mut mpsc::Receiver<Published>) -> Result<()> {
+    let (host, port) = broker_address(&config.url, DEFAULT_PORT)?;
+    let mut stream =
+        tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host.as_str(), port)))
+            .await
+            .context("MQTT connect timed out")??;
+    stream
+        .write_all(&connect_packet(
+            &config.client_id,
+            config.username.as_deref(),
+            config.password.as_deref(),
+        ))
+        .await?;
+
+    let mut connack = [0u8; 4];
+    tokio::time::timeout(CONNECT_TIMEOUT, stream.read_exact(&mut connack))
+        .await
+        .context("MQTT CONNACK timed out")??;
+    if connack[0] != 0x20 || connack[1] != 0x02 {
+        bail!("unexpected MQTT reply to CONNECT");
+    }
+    if connack[3] != 0 {
+        bail!(
+            "MQTT broker refused connection (return code {})",
+            connack[3]
+        );
+    }
+    tracing::info!("Connected to MQTT broker {host}:{port}");
+
+    let (mut reader, mut writer) = stream.into_split();
+    let mut ping = tokio::time::interval(Duration::from_secs(u64::from(KEEP_ALIVE_SECS) / 2));
+    ping.tick().await;
+    let mut inbound = [0u8; 256];
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else {
+                    // Best-effort DISCONNECT; the process is shutting down anyway.
+                    let _ = writer.write_all(&[0xE0, 0x00]).await;
+                    return Ok(());
+                };
+                writer.write_all(&publish_packet(&event.topic, &event.payload)?).await?;
+            }
+            _ = ping.tick() => writer.write_all(&[0xC0, 0x00]).await?,
+            // Only PINGRESP arrives for QoS 0 publishing; EOF means the broker left.
+            read = reader.read(&mut inbound) => {
+                if read? == 0 {
+                    bail!("MQTT broker closed the connection");
+                }
+            }
+        }
+    }
+}
+
+fn encode_remaining_length(mut len: usize, out: &mut Vec<u8>) {
+    loop {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+}
+
+fn push_str(value: &str, out: &mut Vec<u8>) -> Result<()> {
+    let len = u16::try_from(value.len()).context("MQTT string longer than 65535 bytes")?;
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+    Ok(())
+}
+
+fn connect_packet(client_id: &str, username: Option<&str>, password: Option<&str>) -> Vec<u8> {
+    let mut flags = 0x02; // clean session
+    let mut body = Vec::new();
+    body.extend_from_slice(&[0x00, 0x04, b'M', b'Q', b'T', b'T', 0x04]);
+    if username.is_some() {
+        flags |= 0x80;
+    }
+    if password.is_some() {
+        flags |= 0x40;
+    }
+    body.push(flags);
+    body.extend_from_slice(&KEEP_ALIVE_SECS.to_be_bytes());
+    // Config values are short; overlong ones are rejected by the broker.
+    for field in [Some(client_id), username, password].into_iter().flatten() {
+        let _ = push_str(field, &mut body);
+    }
+
+    let mut packet = vec![0x10];
+    encode_remaining_length(body.len(), &mut packet);
+    packet.extend_from_slice(&body);
+    packet
+}
+
+fn publish_packet(topic: &str, payload: &[u8]) -> Result<Vec<u8>> {
+    let mut body = Vec::with_capacity(topic.len() + payload.len() + 2);
+    push_str(topic, &mut body)?;
+    body.extend_from_slice(payload);
+
+    let mut packet = vec![0x30];
+    encode_remaining_length(body.len(), &mut packet);
+    packet.extend_from_slice(&body);
+    Ok(packet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_length_uses_variable_byte_encoding() {
+        for (len, expected) in [
+            (0, vec![0x00]),
+            (127, vec![0x7F]),
+            (128, vec![0x80, 0x01]),
+            (16_383, vec![0xFF, 0x7F]),
+            (2_097_152, vec![0x80, 0x80, 0x80, 0x01]),
+        ] {
+            let mut out = Vec::new();
+            encode_remaining_length(len, &mut out);
+            assert_eq!(out, expected, "length {len}");
+        }
+    }
+
+    #[test]
+    fn connect_and_publish_packets_match_the_spec() {
+        let connect = connect_packet("zc", Some("u"), Some("p"));
+        assert_eq!(
+            connect,
+            [
+                0x10, 20, 0x00, 0x04, b'M', b'Q', b'T', b'T', 0x04, 0xC2, 0x00, 60, 0x00, 0x02,
+                b'z', b'c', 0x00, 0x01, b'u', 0x00, 0x01, b'p',
+            ]
+        );
+
+        let publish = publish_packet("a/b", b"{}").unwrap();
+        assert_eq!(publish, [0x30, 7, 0x00, 0x03, b'a', b'/', b'b', b'{', b'}']);
+    }
+
+    #[tokio::test]
+    async fn publishes_to_a_broker_after_connack() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let broker = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut header = [0u8; 2];
+            socket.read_exact(&mut header).await.unwrap();
+            let mut connect = vec![0u8; usize::from(header[1])];
+            socket.read_exact(&mut connect).await.unwrap();
+            socket.write_all(&[0x20, 0x02, 0x00, 0x00]).await.unwrap();
+            let mut publish = vec![0u8; publish_packet("zc/test", b"{}").unwrap().len()];
+            socket.read_exact(&mut publish).await.unwrap();
+            (socket, publish)
+        });
+
+        let config = EventsConfig {
+            enabled: true,
+            url: format!("mqtt://127.0.0.1:{port}"),
+            ..EventsConfig::default()
+        };
+        let (tx, mut rx) = mpsc::channel(4);
+        tx.send(Published {
+            topic: "zc/test".into(),
+            payload: b"{}".to_vec(),
+        })
+        .await
+        .unwrap();
+        let client = tokio::spawn(async move { run(&config, &mut rx).await });
+
+        // Keep the broker socket open until the client has shut down.
+        let (_socket, publish) = broker.await.unwrap();
+        assert_eq!(publish, publish_packet("zc/test", b"{}").unwrap());
+        drop(tx);
+        assert!(client.await.unwrap().is_ok());
+    }
+}
@@ -0,0 +1,149 @@
+//! Minimal NATS core publisher: CONNECT, PUB, and PING/PONG handling.
+
+use super::{broker_address, Published};
+use crate::config::EventsConfig;
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+
+const DEFAULT_PORT: u16 = 4222;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Publish queued events until the queue closes (`Ok`) or the connection fails.
+pub(super) async fn run(config: &EventsConfig, rx: &mut mpsc::Receiver<Published>) -> Result<()> {
+    let (host, port) = broker_address(&config.url, DEFAULT_PORT)?;
+    let stream = tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect((host.as_str(), port)))
+        .await
+        .context("NATS connect timed out")??;
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let info = tokio::time::timeout(CONNECT_TIMEOUT, lines.next_line())
+        .await
+        .context("NATS INFO timed out")??
+        .unwrap_or_default();
+    if !info.starts_with("INFO ") {
+        bail!("unexpected NATS greeting");
+    }
+    writer.write_all(connect_line(config).as_bytes()).await?;
+    tracing::info!("Connected to NATS server {host}:{port}");
+
+    loop {
+        tokio::select! {
+            event = rx.recv() => {
+                let Some(event) = event else {
+                    return Ok(());
+                };
+                writer.write_all(&pub_frame(&event.topic, &event.payload)).await?;
+            }
+            line = lines.next_line() => {
+                let Some(line) = line? else {
+                    bail!("NATS server closed the connection");
+                };
+                if line.starts_with("PING") {
+                    writer.write_all(b"PONG\r\n").await?;
+                } else if let Some(err) = line.strip_prefix("-ERR") {
+                    bail!("NATS server error:{err}");
+                }
+            }
+        }
+    }
+}
+
+fn connect_line(config: &EventsConfig) -> String {
+    let mut options = serde_json::json!({
+        "verbose": false,
+        "pedantic": false,
+        "name": config.client_id,
+        "lang": "rust",
+        "version": env!("CARGO_PKG_VERSION"),
+    });
+    if let Some(user) = &config.username {
+        options["user"] = user.as_str().into();
+    }
+    if let Some(pass) = &config.password {
+        options["pass"] = pass.as_str().into();
+    }
+    format!("CONNECT {options}\r\n")
+}
+
+fn pub_frame(subject: &str, payload: &[u8]) -> Vec<u8> {
+    let mut frame = format!("PUB {subject} {}\r\n", payload.len()).into_bytes();
+    frame.extend_from_slice(payload);
+    frame.extend_from_slice(b"\r\n");
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    #[test]
+    fn frames_follow_the_text_protocol() {
+        assert_eq!(pub_frame("zc.tool", b"{}"), b"PUB zc.tool 2\r\n{}\r\n");
+
+        let config = EventsConfig {
+            username: Some("agent".into()),
+            password: Some("secret".into()),
+            ..EventsConfig::default()
+        };
+        let line = connect_line(&config);
+        assert!(line.starts_with("CONNECT {") && line.ends_with("}\r\n"));
+        let options: serde_json::Value =
+            serde_json::from_str(line["CONNECT ".len()..].trim_end()).unwrap();
+        assert_eq!(options["user"], "agent");
+        assert_eq!(options["pass"], "secret");
+        assert_eq!(options["verbose"], false);
+    }
+
+    #[tokio::test]
+    async fn publishes_after_info_and_answers_pings() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket
+                .write_all(b"INFO {\"server_id\":\"test\"}\r\nPING\r\n")
+                .await
+                .unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 512];
+            loop {
+                let text = String::from_utf8_lossy(&received);
+                if text.contains("PONG\r\n") && text.contains("{}\r\n") {
+                    break;
+                }
+                let n = socket.read(&mut buf).await.unwrap();
+                assert!(n > 0, "client closed early");
+                received.extend_from_slice(&buf[..n]);
+            }
+            (socket, String::from_utf8(received).unwrap())
+        });
+
+        let config = EventsConfig {
+            enabled: true,
+            backend: "nats".into(),
+            url: format!("nats://127.0.0.1:{port}"),
+            ..EventsConfig::default()
+        };
+        let (tx, mut rx) = mpsc::channel(4);
+        let client = tokio::spawn(async move { run(&config, &mut rx).await });
+        tx.send(Published {
+            topic: "zc.test".into(),
+            payload: b"{}".to_vec(),
+        })
+        .await
+        .unwrap();
+
+        // Keep the server socket open until the client has shut down.
+        let (_socket, received) = server.await.unwrap();
+        assert!(received.starts_with("CONNECT {"));
+        assert!(received.contains("PONG\r\n"));
+        assert!(received.contains("PUB zc.test 2\r\n{}\r\n"));
+        drop(tx);
+        assert!(client.await.unwrap().is_ok());
+    }
+}
@@ -0,0 +1,310 @@
+//! Signed HTTP delivery of events to `[[events.webhooks]]`.
+//!
+//! Each matching event is POSTed as JSON with an `X-Zeroclaw-Signature`
+//! header (`sha256=` + hex HMAC-SHA256 of `<timestamp>.<body>`, keyed with
+//! the webhook secret) and retried with backoff on network errors, 429, and
+//! 5xx responses. Deliveries run in the background; when the queue is full,
+//! new events are dropped.
+
+use super::AgentEvent;
+use crate::config::EventWebhookConfig;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Semaphore};
+
+/// Deliveries waiting for a free slot before new ones are dropped.
+const QUEUE_CAPACITY: usize = 512;
+/// Deliveries in flight at once, across all webhooks.
+const MAX_CONCURRENT: usize = 8;
+/// Longest wait between delivery attempts.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// One event bound for one webhook.
+#[derive(Debug, Clone)]
+struct Delivery {
+    url: String,
+    secret: Option<String>,
+    kind: &'static str,
+    id: String,
+    body: String,
+    max_retries: u32,
+}
+
+pub(super) struct Webhooks {
+    hooks: Vec<EventWebhookConfig>,
+    tx: mpsc::Sender<Delivery>,
+}
+
+impl Webhooks {
+    /// Start the delivery task, or `None` when no webhooks are configured.
+    pub(super) fn start(hooks: &[EventWebhookConfig]) -> Option<Self> {
+        if hooks.is_empty() {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(run(rx));
+        tracing::info!(count = hooks.len(), "Delivering agent events to webhooks");
+        Some(Self {
+            hooks: hooks.to_vec(),
+            tx,
+        })
+    }
+
+    /// Queue `event` for every webhook that wants it.
+    pub(super) fn dispatch(&self, event: &AgentEvent) {
+        for hook in self.hooks.iter().filter(|hook| wants(hook, event)) {
+            let id = uuid::Uuid::new_v4().to_string();
+            let mut payload = event.payload(&hook.redaction);
+            payload["id"] = id.as_str().into();
+            let delivery = Delivery {
+                url: hook.url.trim().to_string(),
+                secret: hook.secret.clone(),
+                kind: event.kind(),
+                id,
+                body: payload.to_string(),
+                max_retries: hook.max_retries,
+            };
+            if self.tx.try_send(delivery).is_err() {
+                tracing::debug!(
+                    "Webhook queue full or closed; dropping {} event",
+                    event.kind()
+                );
+            }
+        }
+    }
+}
+
+/// Whether `hook` subscribes to `event`'s kind and channel.
+fn wants(hook: &EventWebhookConfig, event: &AgentEvent) -> bool {
+    hook.kinds.iter().any(|kind| kind == event.kind())
+        && (hook.channels.is_empty()
+            || event
+                .channel()
+                .is_some_and(|channel| hook.channels.iter().any(|c| c == channel)))
+}
+
+/// `X-Zeroclaw-Signature` value for `body` sent at `timestamp`.
+fn signature(secret: &str, timestamp: i64, body: &str) -> Option<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    Some(format!(
+        "sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    ))
+}
+
+async fn run(mut rx: mpsc::Receiver<Delivery>) {
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "events.webhook",
+        REQUEST_TIMEOUT_SECS,
+        REQUEST_TIMEOUT_SECS,
+    );
+    let slots = Arc::new(Semaphore::new(MAX_CONCURRENT));
+    while let Some(delivery) = rx.recv().await {
+        let Ok(permit) = slots.clone().acquire_owned().await else {
+            return;
+        };
+        let client = client.clone();
+        tokio::spawn(async move {
+            deliver(&client, &delivery, Duration::from_secs(1)).await;
+            drop(permit);
+        });
+    }
+}
+
+/// POST `delivery`, retrying transient failures. Returns whether it was accepted.
+async fn deliver(client: &reqwest::Client, delivery: &Delivery, first_delay: Duration) -> bool {
+    let mut delay = first_delay;
+    for attempt in 0..=delivery.max_retries {
+        if attempt > 0 {
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(MAX_RETRY_DELAY);
+        }
+        let timestamp = chrono::Utc::now().timestamp();
+        let mut request = client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header("X-Zeroclaw-Event", delivery.kind)
+            .header("X-Zeroclaw-Delivery", &delivery.id)
+            .header("X-Zeroclaw-Timestamp", timestamp.to_string())
+            .body(delivery.body.clone());
+        if let Some(signature) = delivery
+            .secret
+            .as_deref()
+            .and_then(|secret| signature(secret, timestamp, &delivery.body))
+        {
+            request = request.header("X-Zeroclaw-Signature", signature);
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => return true,
+            Ok(response)
+                if response.status().as_u16() == 429 || response.status().is_server_error() =>
+            {
+                tracing::debug!(
+                    url = %delivery.url,
+                    attempt,
+                    "Webhook returned {}; will retry",
+                    response.status()
+                );
+            }
+            Ok(response) => {
+                tracing::warn!(
+                    url = %delivery.url,
+                    "Webhook rejected {} event with {}",
+                    delivery.kind,
+                    response.status()
+                );
+                return false;
+            }
+            Err(e) => {
+                tracing::debug!(url = %delivery.url, attempt, "Webhook delivery failed: {e}");
+            }
+        }
+    }
+    tracing::warn!(
+        url = %delivery.url,
+        "Giving up on {} event after {} attempts",
+        delivery.kind,
+        delivery.max_retries + 1
+    );
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn hook(channels: &[&str]) -> EventWebhookConfig {
+        EventWebhookConfig {
+            url: "https://crm.example/hook".into(),
+            secret: Some("s3cret".into()),
+            channels: channels.iter().map(|c| (*c).to_string()).collect(),
+            kinds: vec!["reply_sent".into(), "tool_executed".into()],
+            max_retries: 3,
+            redaction: crate::config::EventRedactionConfig::default(),
+        }
+    }
+
+    fn reply(channel: &str) -> AgentEvent {
+        AgentEvent::ReplySent {
+            channel: channel.into(),
+            recipient: "alice".into(),
+            content: "Hello".into(),
+        }
+    }
+
+    #[test]
+    fn webhooks_filter_by_kind_and_channel() {
+        let tool = AgentEvent::ToolExecuted {
+            tool: "shell".into(),
+            success: true,
+            duration: Duration::from_millis(3),
+        };
+        let received = AgentEvent::MessageReceived {
+            channel: "slack".into(),
+            sender: "alice".into(),
+            content: "Hi".into(),
+        };
+        assert!(wants(&hook(&[]), &reply("slack")));
+        assert!(wants(&hook(&[]), &tool));
+        assert!(!wants(&hook(&[]), &received));
+        assert!(wants(&hook(&["slack"]), &reply("slack")));
+        assert!(!wants(&hook(&["slack"]), &reply("telegram")));
+        assert!(!wants(&hook(&["slack"]), &tool));
+    }
+
+    #[test]
+    fn signature_is_hmac_of_timestamp_and_body() {
+        let sig = signature("s3cret", 1_700_000_000, "{\"kind\":\"reply_sent\"}").unwrap();
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(b"1700000000.{\"kind\":\"reply_sent\"}");
+        assert_eq!(
+            sig,
+            format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+        );
+        assert_ne!(sig, signature("other", 1_700_000_000, "{}").unwrap());
+    }
+
+    /// Answer each request with the next status, returning the raw requests.
+    async fn serve(statuses: Vec<u16>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut raw = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    raw.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&raw);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let length = text
+                            .lines()
+                            .find_map(|l| {
+                                l.to_ascii_lowercase()
+                                    .strip_prefix("content-length: ")
+                                    .map(str::to_string)
+                            })
+                            .and_then(|v| v.trim().parse::<usize>().ok())
+                            .unwrap_or(0);
+                        if raw.len() >= end + 4 + length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                requests.push(String::from_utf8_lossy(&raw).into_owned());
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    fn delivery(url: String, max_retries: u32) -> Delivery {
+        Delivery {
+            url,
+            secret: Some("s3cret".into()),
+            kind: "reply_sent",
+            id: "d-1".into(),
+            body: "{\"kind\":\"reply_sent\"}".into(),
+            max_retries,
+        }
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried_with_signed_requests() {
+        let (url, server) = serve(vec![503, 200]).await;
+        let client = reqwest::Client::new();
+        assert!(deliver(&client, &delivery(url, 2), Duration::from_millis(10)).await);
+
+        let requests = server.await.unwrap();
+        assert_eq!(requests.len(), 2);
+        let request = requests[1].to_ascii_lowercase();
+        assert!(request.starts_with("post /hook"));
+        assert!(request.contains("x-zeroclaw-event: reply_sent"));
+        assert!(request.contains("x-zeroclaw-delivery: d-1"));
+        assert!(request.contains("x-zeroclaw-signature: sha256="));
+        assert!(request.ends_with("{\"kind\":\"reply_sent\"}"));
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (url, server) = serve(vec![400]).await;
+        let client = reqwest::Client::new();
+        assert!(!deliver(&client, &delivery(url, 3), Duration::from_millis(10)).await);
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+}
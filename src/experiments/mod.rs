@@ -0,0 +1,439 @@
+//! A/B prompt experiments.
+//!
+//! An enabled `[[experiments]]` entry splits channel conversations between
+//! its variants, each of which can append to the system prompt or change
+//! the model and temperature. A conversation keeps its variant once
+//! assigned. Every reply is tagged with the variant in
+//! `<workspace>/experiments/experiments.db`, and `zeroclaw experiments report`
+//! compares variants by latency, reply length, failures, and the reactions
+//! users left on the replies (see [`crate::channels::feedback`]).
+
+use crate::channels::feedback::{self, Sentiment};
+use crate::config::{Config, ExperimentConfig, ExperimentVariantConfig};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A finished channel turn to tag with its variant.
+pub struct TurnRecord<'a> {
+    pub experiment: &'a str,
+    pub variant: &'a str,
+    pub conversation: &'a str,
+    pub channel: &'a str,
+    /// Outbox entry of the reply, when one was sent.
+    pub outbox_id: Option<&'a str>,
+    pub latency_ms: u64,
+    pub reply_chars: usize,
+    pub success: bool,
+}
+
+/// Per-variant results.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VariantReport {
+    pub variant: String,
+    pub conversations: usize,
+    pub turns: usize,
+    pub failures: usize,
+    pub avg_latency_ms: u64,
+    pub avg_reply_chars: usize,
+    pub positive: usize,
+    pub negative: usize,
+    pub neutral: usize,
+}
+
+impl VariantReport {
+    /// Share of positive reactions among positive and negative ones.
+    pub fn positive_rate(&self) -> Option<f64> {
+        let rated = self.positive + self.negative;
+        #[allow(clippy::cast_precision_loss)]
+        (rated > 0).then(|| self.positive as f64 / rated as f64)
+    }
+}
+
+/// Pick a variant by weight; `roll` is any random number.
+fn pick_weighted(variants: &[ExperimentVariantConfig], roll: u64) -> &ExperimentVariantConfig {
+    let total: u64 = variants.iter().map(|v| u64::from(v.weight)).sum();
+    let mut point = roll % total.max(1);
+    for variant in variants {
+        let weight = u64::from(variant.weight);
+        if point < weight {
+            return variant;
+        }
+        point -= weight;
+    }
+    &variants[variants.len() - 1]
+}
+
+/// The variant for a conversation, assigning one at random on first contact.
+/// A stored assignment to a variant that was since removed is replaced.
+pub fn assign(
+    workspace_dir: &Path,
+    experiment: &ExperimentConfig,
+    conversation: &str,
+) -> Result<ExperimentVariantConfig> {
+    if experiment.variants.is_empty() {
+        bail!("Experiment '{}' has no variants", experiment.name);
+    }
+    with_connection(workspace_dir, |conn| {
+        let stored: Option<String> = conn
+            .query_row(
+                "SELECT variant FROM assignments WHERE experiment = ?1 AND conversation = ?2",
+                params![experiment.name, conversation],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(variant) = stored
+            .as_deref()
+            .and_then(|name| experiment.variants.iter().find(|v| v.name == name))
+        {
+            return Ok(variant.clone());
+        }
+
+        let variant = pick_weighted(&experiment.variants, rand::random::<u64>());
+        conn.execute(
+            "INSERT OR REPLACE INTO assignments (experiment, conversation, variant, assigned_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                experiment.name,
+                conversation,
+                variant.name,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .context("Failed to store experiment assignment")?;
+        Ok(variant.clone())
+    })
+}
+
+/// Tag a finished turn with its experiment variant.
+pub fn record_turn(workspace_dir: &Path, turn: &TurnRecord<'_>) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO turns (experiment, variant, conversation, channel, outbox_id,
+                                latency_ms, reply_chars, success, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                turn.experiment,
+                turn.variant,
+                turn.conversation,
+                turn.channel,
+                turn.outbox_id,
+                i64::try_from(turn.latency_ms).unwrap_or(i64::MAX),
+                i64::try_from(turn.reply_chars).unwrap_or(i64::MAX),
+                turn.success,
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .context("Failed to record experiment turn")?;
+        Ok(())
+    })
+}
+
+/// Compare the variants of an experiment, in the order they first appear.
+pub fn report(workspace_dir: &Path, experiment: &str) -> Result<Vec<VariantReport>> {
+    struct Turn {
+        variant: String,
+        conversation: String,
+        outbox_id: Option<String>,
+        latency_ms: u64,
+        reply_chars: usize,
+        success: bool,
+    }
+
+    let turns = with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT variant, conversation, outbox_id, latency_ms, reply_chars, success
+             FROM turns WHERE experiment = ?1 ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![experiment], |row| {
+            Ok(Turn {
+                variant: row.get(0)?,
+                conversation: row.get(1)?,
+                outbox_id: row.get(2)?,
+                latency_ms: u64::try_from(row.get::<_, i64>(3)?).unwrap_or_default(),
+                reply_chars: usize::try_from(row.get::<_, i64>(4)?).unwrap_or_default(),
+                success: row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    })?;
+
+    let mut reactions: HashMap<String, Vec<Sentiment>> = HashMap::new();
+    for entry in feedback::recent(
+        workspace_dir,
+        None,
+        None,
+        DateTime::<Utc>::MIN_UTC,
+        usize::MAX,
+    )? {
+        reactions
+            .entry(entry.outbox_id)
+            .or_default()
+            .push(entry.sentiment);
+    }
+
+    let mut reports: Vec<VariantReport> = Vec::new();
+    let mut conversations: HashMap<String, std::collections::HashSet<String>> = HashMap::new();
+    let mut latency_totals: HashMap<String, (u64, usize)> = HashMap::new();
+    for turn in turns {
+        let index = match reports.iter().position(|r| r.variant == turn.variant) {
+            Some(index) => index,
+            None => {
+                reports.push(VariantReport {
+                    variant: turn.variant.clone(),
+                    ..VariantReport::default()
+                });
+                reports.len() - 1
+            }
+        };
+        let report = &mut reports[index];
+        report.turns += 1;
+        conversations
+            .entry(turn.variant.clone())
+            .or_default()
+            .insert(turn.conversation);
+        if !turn.success {
+            report.failures += 1;
+            continue;
+        }
+        let totals = latency_totals.entry(turn.variant).or_default();
+        totals.0 += turn.latency_ms;
+        totals.1 += turn.reply_chars;
+        for sentiment in turn
+            .outbox_id
+            .as_ref()
+            .and_then(|id| reactions.get(id))
+            .into_iter()
+            .flatten()
+        {
+            match sentiment {
+                Sentiment::Positive => report.positive += 1,
+                Sentiment::Negative => report.negative += 1,
+                Sentiment::Neutral => report.neutral += 1,
+            }
+        }
+    }
+
+    for report in &mut reports {
+        report.conversations = conversations.get(&report.variant).map_or(0, |c| c.len());
+        let succeeded = report.turns - report.failures;
+        if let Some((latency, chars)) = latency_totals.get(&report.variant) {
+            if succeeded > 0 {
+                report.avg_latency_ms = latency / succeeded as u64;
+                report.avg_reply_chars = chars / succeeded;
+            }
+        }
+    }
+    Ok(reports)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+pub fn handle_command(command: crate::ExperimentCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ExperimentCommands::Report { name } => {
+            let experiments: Vec<&ExperimentConfig> = match name.as_deref() {
+                Some(name) => match config.experiments.iter().find(|e| e.name == name) {
+                    Some(experiment) => vec![experiment],
+                    None => bail!("No experiment named '{name}' in config"),
+                },
+                None => config.experiments.iter().collect(),
+            };
+            if experiments.is_empty() {
+                println!("No experiments configured. Add an [[experiments]] entry to config.toml.");
+                return Ok(());
+            }
+
+            for experiment in experiments {
+                let state = if experiment.enabled {
+                    "active"
+                } else {
+                    "stopped"
+                };
+                println!("🧪 Experiment {} ({state})", experiment.name);
+                let reports = report(&config.workspace_dir, &experiment.name)?;
+                if reports.is_empty() {
+                    println!("  No turns recorded yet.\n");
+                    continue;
+                }
+                println!(
+                    "  {:<16} {:>6} {:>7} {:>7} {:>9} {:>7} {:>4} {:>4} {:>4} {:>6}",
+                    "variant",
+                    "convs",
+                    "replies",
+                    "failed",
+                    "latency",
+                    "chars",
+                    "+",
+                    "-",
+                    "~",
+                    "liked"
+                );
+                for r in reports {
+                    let liked = r
+                        .positive_rate()
+                        .map_or_else(|| "-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+                    println!(
+                        "  {:<16} {:>6} {:>7} {:>7} {:>8.1}s {:>7} {:>4} {:>4} {:>4} {:>6}",
+                        r.variant,
+                        r.conversations,
+                        r.turns - r.failures,
+                        r.failures,
+                        r.avg_latency_ms as f64 / 1000.0,
+                        r.avg_reply_chars,
+                        r.positive,
+                        r.negative,
+                        r.neutral,
+                        liked
+                    );
+                }
+                println!();
+            }
+            Ok(())
+        }
+    }
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("experiments").join("experiments.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open experiments DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS assignments (
+            experiment   TEXT NOT NULL,
+            conversation TEXT NOT NULL,
+            variant      TEXT NOT NULL,
+            assigned_at  TEXT NOT NULL,
+            PRIMARY KEY (experiment, conversation)
+        );
+        CREATE TABLE IF NOT EXISTS turns (
+            experiment   TEXT NOT NULL,
+            variant      TEXT NOT NULL,
+            conversation TEXT NOT NULL,
+            channel      TEXT NOT NULL,
+            outbox_id    TEXT,
+            latency_ms   INTEGER NOT NULL,
+            reply_chars  INTEGER NOT NULL,
+            success      INTEGER NOT NULL,
+            created_at   TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_turns_experiment ON turns(experiment);",
+    )
+    .context("Failed to initialize experiments schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::outbox;
+    use crate::channels::traits::{DeliveryStatus, Reaction};
+    use crate::observability::NoopObserver;
+
+    fn variant(name: &str, weight: u32) -> ExperimentVariantConfig {
+        ExperimentVariantConfig {
+            name: name.into(),
+            weight,
+            system_prompt: None,
+            model: None,
+            temperature: None,
+        }
+    }
+
+    fn experiment() -> ExperimentConfig {
+        ExperimentConfig {
+            name: "persona".into(),
+            enabled: true,
+            variants: vec![variant("control", 1), variant("concise", 3)],
+        }
+    }
+
+    #[test]
+    fn pick_weighted_follows_weights() {
+        let variants = experiment().variants;
+        assert_eq!(pick_weighted(&variants, 0).name, "control");
+        assert_eq!(pick_weighted(&variants, 1).name, "concise");
+        assert_eq!(pick_weighted(&variants, 3).name, "concise");
+        assert_eq!(pick_weighted(&variants, 4).name, "control");
+    }
+
+    #[test]
+    fn assignment_is_sticky_per_conversation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let experiment = experiment();
+        let first = assign(tmp.path(), &experiment, "telegram_alice").unwrap();
+        for _ in 0..10 {
+            assert_eq!(
+                assign(tmp.path(), &experiment, "telegram_alice")
+                    .unwrap()
+                    .name,
+                first.name
+            );
+        }
+
+        // Removing the assigned variant reassigns the conversation.
+        let mut changed = experiment.clone();
+        changed.variants.retain(|v| v.name != first.name);
+        let reassigned = assign(tmp.path(), &changed, "telegram_alice").unwrap();
+        assert_ne!(reassigned.name, first.name);
+    }
+
+    #[test]
+    fn report_joins_turns_with_reactions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let ws = tmp.path();
+        let outbox_id = outbox::record(
+            ws,
+            &outbox::OutboundMessage {
+                channel: "telegram",
+                recipient: "100",
+                platform_message_id: Some("9"),
+                content: "Short and sweet",
+                status: DeliveryStatus::Sent,
+                receipts: false,
+            },
+            &NoopObserver,
+        )
+        .unwrap();
+        feedback::record_reaction(
+            ws,
+            "telegram",
+            "alice",
+            &Reaction {
+                message_id: "9".into(),
+                emoji: "👍".into(),
+                removed: false,
+            },
+        )
+        .unwrap();
+
+        let turn = |variant, outbox_id, latency_ms, success| TurnRecord {
+            experiment: "persona",
+            variant,
+            conversation: "telegram_alice",
+            channel: "telegram",
+            outbox_id,
+            latency_ms,
+            reply_chars: 100,
+            success,
+        };
+        record_turn(ws, &turn("concise", Some(outbox_id.as_str()), 1000, true)).unwrap();
+        record_turn(ws, &turn("concise", None, 3000, true)).unwrap();
+        record_turn(ws, &turn("control", None, 5000, false)).unwrap();
+
+        let reports = report(ws, "persona").unwrap();
+        assert_eq!(reports.len(), 2);
+        let concise = &reports[0];
+        assert_eq!(concise.variant, "concise");
+        assert_eq!((concise.conversations, concise.turns), (1, 2));
+        assert_eq!(concise.avg_latency_ms, 2000);
+        assert_eq!(concise.positive, 1);
+        assert_eq!(concise.positive_rate(), Some(1.0));
+        let control = &reports[1];
+        assert_eq!(control.failures, 1);
+        assert_eq!(control.positive_rate(), None);
+    }
+}
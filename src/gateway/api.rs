@@ -3,6 +3,7 @@
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
 
 use super::AppState;
+use crate::channels::injection::{inject, InjectError, Injection};
 use axum::{
     extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
@@ -59,6 +60,16 @@ pub struct MemoryStoreBody {
     pub category: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct MessageInjectBody {
+    pub channel: String,
+    pub recipient: String,
+    pub content: String,
+    /// Conversation owner when it differs from the reply recipient.
+    pub sender: Option<String>,
+    pub thread: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct CronAddBody {
     pub name: Option<String>,
@@ -483,6 +494,8 @@ pub async fn handle_api_cost(
                 "daily_cost_usd": 0.0,
                 "monthly_cost_usd": 0.0,
                 "total_tokens": 0,
+                "cached_input_tokens": 0,
+                "session_savings_usd": 0.0,
                 "request_count": 0,
                 "by_model": {},
             }
@@ -518,6 +531,157 @@ pub async fn handle_api_health(
     Json(serde_json::json!({"health": snapshot})).into_response()
 }
 
+/// POST /api/messages — inject a message into a channel conversation
+pub async fn handle_api_messages_post(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Result<Json<MessageInjectBody>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let Ok(Json(body)) = body else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": "Invalid JSON body. Expected: {\"channel\": \"...\", \"recipient\": \"...\", \"content\": \"...\"}"
+            })),
+        )
+            .into_response();
+    };
+    if body.recipient.trim().is_empty() || body.content.trim().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "recipient and content must not be empty"})),
+        )
+            .into_response();
+    }
+
+    match inject(Injection {
+        channel: body.channel,
+        recipient: body.recipient,
+        sender: body.sender.filter(|sender| !sender.trim().is_empty()),
+        thread: body.thread.filter(|thread| !thread.trim().is_empty()),
+        content: body.content,
+    }) {
+        Ok(injected) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({
+                "message_id": injected.message_id,
+                "conversation_id": injected.conversation_id,
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            let status = match e {
+                InjectError::NotRunning => StatusCode::SERVICE_UNAVAILABLE,
+                InjectError::UnknownChannel(_) => StatusCode::NOT_FOUND,
+                InjectError::Busy => StatusCode::TOO_MANY_REQUESTS,
+            };
+            (status, Json(serde_json::json!({"error": e.to_string()}))).into_response()
+        }
+    }
+}
+
+/// GET /api/conversations/{id}/history — turns kept for a channel conversation
+pub async fn handle_api_conversation_history(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    match crate::channels::injection::history(&id) {
+        Ok(Some(turns)) => {
+            let messages: Vec<_> = turns
+                .into_iter()
+                .map(|turn| {
+                    serde_json::json!({
+                        "role": turn.role,
+                        "content": crate::agent::loop_::scrub_credentials(&turn.content),
+                    })
+                })
+                .collect();
+            Json(serde_json::json!({"conversation_id": id, "messages": messages})).into_response()
+        }
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": format!("No history for conversation {id}")})),
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": e.to_string()})),
+        )
+            .into_response(),
+    }
+}
+
+/// GET /api/skills — installed skills with their usage counters
+pub async fn handle_api_skills(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let config = state.config.lock().clone();
+    let usage = crate::skills::usage::load_usage(&config.workspace_dir);
+    let skills: Vec<_> = crate::skills::load_skills_with_config(&config.workspace_dir, &config)
+        .into_iter()
+        .map(|skill| {
+            serde_json::json!({
+                "name": skill.name,
+                "description": skill.description,
+                "version": skill.version,
+                "author": skill.author,
+                "tags": skill.tags,
+                "tools": skill.tools.iter().map(|tool| &tool.name).collect::<Vec<_>>(),
+                "usage": usage.get(&skill.name).cloned().unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    Json(serde_json::json!({"skills": skills})).into_response()
+}
+
+/// GET /api/usage — cost, token, and skill usage
+pub async fn handle_api_usage(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(e) = require_auth(&state, &headers) {
+        return e.into_response();
+    }
+
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    let cost = match state
+        .cost_tracker
+        .as_ref()
+        .map(|tracker| tracker.get_summary())
+    {
+        Some(Ok(summary)) => serde_json::to_value(summary).unwrap_or_default(),
+        Some(Err(e)) => {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({"error": format!("Cost summary failed: {e}")})),
+            )
+                .into_response();
+        }
+        None => serde_json::Value::Null,
+    };
+
+    Json(serde_json::json!({
+        "cost": cost,
+        "skills": crate::skills::usage::load_usage(&workspace_dir),
+    }))
+    .into_response()
+}
+
 // ── Helpers ─────────────────────────────────────────────────────
 
 fn is_masked_secret(value: &str) -> bool {
@@ -703,6 +867,7 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     mask_optional_secret(&mut masked.web_search.brave_api_key);
     mask_optional_secret(&mut masked.storage.provider.config.db_url);
     mask_optional_secret(&mut masked.memory.qdrant.api_key);
+    mask_optional_secret(&mut masked.events.password);
     if let Some(cloudflare) = masked.tunnel.cloudflare.as_mut() {
         mask_required_secret(&mut cloudflare.token);
     }
@@ -713,6 +878,9 @@ fn mask_sensitive_fields(config: &crate::config::Config) -> crate::config::Confi
     for agent in masked.agents.values_mut() {
         mask_optional_secret(&mut agent.api_key);
     }
+    for remote in masked.remote_agents.values_mut() {
+        mask_optional_secret(&mut remote.token);
+    }
     for route in &mut masked.model_routes {
         mask_optional_secret(&mut route.api_key);
     }
@@ -819,6 +987,7 @@ fn restore_masked_sensitive_fields(
         &mut incoming.memory.qdrant.api_key,
         &current.memory.qdrant.api_key,
     );
+    restore_optional_secret(&mut incoming.events.password, &current.events.password);
     if let (Some(incoming_tunnel), Some(current_tunnel)) = (
         incoming.tunnel.cloudflare.as_mut(),
         current.tunnel.cloudflare.as_ref(),
@@ -837,6 +1006,11 @@ fn restore_masked_sensitive_fields(
             restore_optional_secret(&mut agent.api_key, &current_agent.api_key);
         }
     }
+    for (name, remote) in &mut incoming.remote_agents {
+        if let Some(current_remote) = current.remote_agents.get(name) {
+            restore_optional_secret(&mut remote.token, &current_remote.token);
+        }
+    }
     restore_model_route_api_keys(&mut incoming.model_routes, &current.model_routes);
     restore_embedding_route_api_keys(&mut incoming.embedding_routes, &current.embedding_routes);
 
@@ -0,0 +1,342 @@
+//! Live channel dashboard served at `/dashboard`.
+//!
+//! Shows conversations per channel, tool call traces, queue depth, and
+//! provider latency from [`crate::channels::monitor`], and lets an operator
+//! pause channels or cancel runs. Only mounted when `[gateway.dashboard]` is
+//! enabled, and by default only answers direct requests from loopback.
+
+use super::AppState;
+use crate::channels::monitor::monitor;
+use crate::security::pairing::PairingGuard;
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Html, IntoResponse, Response,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
+
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// Header the dashboard page sends with every control request. Browsers only
+/// let cross-origin pages set it after a CORS preflight, which the gateway
+/// never grants, so other sites cannot pause channels or cancel runs.
+const CONTROL_HEADER: &str = "x-zeroclaw-dashboard";
+
+/// Headers that mean the request came through a proxy.
+const FORWARDED_HEADERS: [&str; 4] = [
+    "forwarded",
+    "x-forwarded-for",
+    "x-real-ip",
+    "cf-connecting-ip",
+];
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DashboardQuery {
+    #[serde(default)]
+    token: Option<String>,
+}
+
+/// Dashboard routes, merged into the gateway router when enabled.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/dashboard", get(handle_page))
+        .route("/dashboard/state", get(handle_state))
+        .route("/dashboard/events", get(handle_events))
+        .route("/dashboard/channels/{name}/pause", post(handle_pause))
+        .route("/dashboard/channels/{name}/resume", post(handle_resume))
+        .route("/dashboard/runs/{id}/cancel", post(handle_cancel))
+}
+
+fn is_loopback_host(host: &str) -> bool {
+    let host = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Decide whether a request may use the dashboard.
+fn check_access(
+    peer: SocketAddr,
+    headers: &HeaderMap,
+    token: Option<&str>,
+    allow_remote: bool,
+    pairing: &PairingGuard,
+) -> Result<(), (StatusCode, &'static str)> {
+    // Without pairing there is no credential to check, so stay local-only.
+    if !allow_remote || !pairing.require_pairing() {
+        if !peer.ip().is_loopback() {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Dashboard is only served to localhost",
+            ));
+        }
+        // A loopback peer may still be a local reverse proxy for remote clients.
+        if FORWARDED_HEADERS
+            .iter()
+            .any(|name| headers.contains_key(*name))
+        {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Dashboard is not served through proxies; set gateway.dashboard.allow_remote",
+            ));
+        }
+        // Reject DNS-rebound hostnames that resolve to 127.0.0.1.
+        let host = headers
+            .get(header::HOST)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        if !is_loopback_host(host) {
+            return Err((StatusCode::FORBIDDEN, "Dashboard requires a localhost Host"));
+        }
+        return Ok(());
+    }
+
+    if pairing.require_pairing() {
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "));
+        let token = bearer.or(token).unwrap_or("");
+        if !pairing.is_authenticated(token) {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                "Unauthorized — open /dashboard?token=<bearer token>",
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn authorize(
+    state: &AppState,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+    query: &DashboardQuery,
+) -> Result<(), (StatusCode, &'static str)> {
+    let allow_remote = state.config.lock().gateway.dashboard.allow_remote;
+    check_access(
+        peer,
+        headers,
+        query.token.as_deref(),
+        allow_remote,
+        &state.pairing,
+    )
+}
+
+fn authorize_control(
+    state: &AppState,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+    query: &DashboardQuery,
+) -> Result<(), (StatusCode, &'static str)> {
+    authorize(state, peer, headers, query)?;
+    if headers.get(CONTROL_HEADER).and_then(|v| v.to_str().ok()) != Some("1") {
+        return Err((StatusCode::FORBIDDEN, "Missing X-ZeroClaw-Dashboard header"));
+    }
+    Ok(())
+}
+
+/// GET /dashboard — single-page UI
+async fn handle_page(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<DashboardQuery>,
+) -> Response {
+    if let Err(denied) = authorize(&state, peer, &headers, &query) {
+        return denied.into_response();
+    }
+    (
+        [
+            (header::CACHE_CONTROL, "no-store"),
+            (header::X_FRAME_OPTIONS, "DENY"),
+        ],
+        Html(DASHBOARD_HTML),
+    )
+        .into_response()
+}
+
+/// GET /dashboard/state — current snapshot
+async fn handle_state(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<DashboardQuery>,
+) -> Response {
+    if let Err(denied) = authorize(&state, peer, &headers, &query) {
+        return denied.into_response();
+    }
+    let mut snapshot = serde_json::to_value(monitor().snapshot()).unwrap_or_default();
+    snapshot["channels_running"] = monitor().is_enabled().into();
+    Json(snapshot).into_response()
+}
+
+/// GET /dashboard/events — live monitor events
+async fn handle_events(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<DashboardQuery>,
+) -> Response {
+    if let Err(denied) = authorize(&state, peer, &headers, &query) {
+        return denied.into_response();
+    }
+    let stream = BroadcastStream::new(monitor().subscribe()).filter_map(|result| {
+        result
+            .ok()
+            .map(|value| Ok::<_, Infallible>(Event::default().data(value.to_string())))
+    });
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn set_paused(
+    state: &AppState,
+    peer: SocketAddr,
+    headers: &HeaderMap,
+    query: &DashboardQuery,
+    name: &str,
+    paused: bool,
+) -> Response {
+    if let Err(denied) = authorize_control(state, peer, headers, query) {
+        return denied.into_response();
+    }
+    if !monitor().has_channel(name) {
+        return (StatusCode::NOT_FOUND, "Unknown channel").into_response();
+    }
+    let changed = monitor().set_paused(name, paused);
+    tracing::info!(channel = %name, paused, "Channel pause state changed from the dashboard");
+    Json(serde_json::json!({ "channel": name, "paused": paused, "changed": changed }))
+        .into_response()
+}
+
+/// POST /dashboard/channels/{name}/pause
+async fn handle_pause(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<DashboardQuery>,
+    Path(name): Path<String>,
+) -> Response {
+    set_paused(&state, peer, &headers, &query, &name, true)
+}
+
+/// POST /dashboard/channels/{name}/resume
+async fn handle_resume(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<DashboardQuery>,
+    Path(name): Path<String>,
+) -> Response {
+    set_paused(&state, peer, &headers, &query, &name, false)
+}
+
+/// POST /dashboard/runs/{id}/cancel
+async fn handle_cancel(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Query(query): Query<DashboardQuery>,
+    Path(id): Path<u64>,
+) -> Response {
+    if let Err(denied) = authorize_control(&state, peer, &headers, &query) {
+        return denied.into_response();
+    }
+    if !monitor().cancel_run(id) {
+        return (StatusCode::NOT_FOUND, "No such run").into_response();
+    }
+    tracing::info!(run = id, "Run cancelled from the dashboard");
+    Json(serde_json::json!({ "run": id, "cancelled": true })).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn local_peer() -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], 40_000))
+    }
+
+    fn headers_with_host(host: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, HeaderValue::from_str(host).unwrap());
+        headers
+    }
+
+    #[test]
+    fn local_only_access_rejects_remote_proxied_and_rebound_requests() {
+        let pairing = PairingGuard::new(false, &[]);
+        let local = headers_with_host("127.0.0.1:42617");
+        assert!(check_access(local_peer(), &local, None, false, &pairing).is_ok());
+        assert!(check_access(
+            local_peer(),
+            &headers_with_host("[::1]:42617"),
+            None,
+            false,
+            &pairing
+        )
+        .is_ok());
+        assert!(check_access(
+            local_peer(),
+            &headers_with_host("localhost"),
+            None,
+            false,
+            &pairing
+        )
+        .is_ok());
+
+        let remote = SocketAddr::from(([192, 168, 1, 20], 40_000));
+        assert!(check_access(remote, &local, None, false, &pairing).is_err());
+
+        let mut proxied = local.clone();
+        proxied.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.9"));
+        assert!(check_access(local_peer(), &proxied, None, false, &pairing).is_err());
+
+        let rebound = headers_with_host("attacker.example:42617");
+        assert!(check_access(local_peer(), &rebound, None, false, &pairing).is_err());
+    }
+
+    #[test]
+    fn remote_access_requires_pairing_token_when_pairing_is_on() {
+        let pairing = PairingGuard::new(true, &["zc_secret".to_string()]);
+        let remote = SocketAddr::from(([192, 168, 1, 20], 40_000));
+        let headers = headers_with_host("zeroclaw.lan");
+        let denied = check_access(remote, &headers, None, true, &pairing).unwrap_err();
+        assert_eq!(denied.0, StatusCode::UNAUTHORIZED);
+        assert!(check_access(remote, &headers, Some("zc_secret"), true, &pairing).is_ok());
+
+        let mut bearer = headers.clone();
+        bearer.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer zc_secret"),
+        );
+        assert!(check_access(remote, &bearer, None, true, &pairing).is_ok());
+    }
+
+    #[test]
+    fn remote_access_stays_local_only_without_pairing() {
+        let pairing = PairingGuard::new(false, &[]);
+        let remote = SocketAddr::from(([192, 168, 1, 20], 40_000));
+        let headers = headers_with_host("zeroclaw.lan");
+        let denied = check_access(remote, &headers, None, true, &pairing).unwrap_err();
+        assert_eq!(denied.0, StatusCode::FORBIDDEN);
+        let local = headers_with_host("127.0.0.1:42617");
+        assert!(check_access(local_peer(), &local, None, true, &pairing).is_ok());
+    }
+}
@@ -8,12 +8,14 @@
 //! - Header sanitization (handled by axum/hyper)
 
 pub mod api;
+pub mod dashboard;
 pub mod sse;
 pub mod static_files;
 pub mod ws;
 
 use crate::channels::{
-    Channel, LinqChannel, NextcloudTalkChannel, SendMessage, WatiChannel, WhatsAppChannel,
+    Channel, DeliveryStatus, LinqChannel, NextcloudTalkChannel, SendMessage, WatiChannel,
+    WhatsAppChannel,
 };
 use crate::config::Config;
 use crate::cost::CostTracker;
@@ -349,6 +351,9 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
             secrets_encrypt: config.secrets.encrypt,
             reasoning_enabled: config.runtime.reasoning_enabled,
+            reasoning_effort: config.runtime.reasoning_effort.clone(),
+            thinking_budget_tokens: config.runtime.thinking_budget_tokens,
+            debug_capture: providers::capture::DebugCapture::from_config(&config),
         },
     )?);
     let model = config
@@ -592,8 +597,14 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     }
     println!("  GET  /api/*     — REST API (bearer token required)");
     println!("  GET  /ws/chat   — WebSocket agent chat");
+    if config.channels_config.web.is_some() {
+        println!("  GET  /ws/channel — WebSocket web channel (custom frontends)");
+    }
     println!("  GET  /health    — health check");
     println!("  GET  /metrics   — Prometheus metrics");
+    if config.gateway.dashboard.enabled {
+        println!("  GET  /dashboard — live channel dashboard");
+    }
     if let Some(code) = pairing.pairing_code() {
         println!();
         println!("  🔐 PAIRING REQUIRED — use this one-time code:");
@@ -652,10 +663,17 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/config", put(api::handle_api_config_put))
         .layer(RequestBodyLimitLayer::new(1_048_576));
 
+    let dashboard_router = if config.gateway.dashboard.enabled {
+        dashboard::router()
+    } else {
+        Router::new()
+    };
+
     // Build router with middleware
     let app = Router::new()
         // ── Existing routes ──
         .route("/health", get(handle_health))
+        .route("/healthz", get(handle_healthz))
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
         .route("/webhook", post(handle_webhook))
@@ -683,14 +701,24 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/api/cost", get(api::handle_api_cost))
         .route("/api/cli-tools", get(api::handle_api_cli_tools))
         .route("/api/health", get(api::handle_api_health))
+        .route("/api/messages", post(api::handle_api_messages_post))
+        .route(
+            "/api/conversations/{id}/history",
+            get(api::handle_api_conversation_history),
+        )
+        .route("/api/skills", get(api::handle_api_skills))
+        .route("/api/usage", get(api::handle_api_usage))
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
+        .route("/ws/channel", get(ws::handle_ws_channel))
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
+        // ── Live channel dashboard ([gateway.dashboard]) ──
+        .merge(dashboard_router)
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -725,6 +753,32 @@ async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
     Json(body)
 }
 
+/// GET /healthz — per-subsystem status for external uptime monitors; 503 when down
+async fn handle_healthz(State(state): State<AppState>) -> impl IntoResponse {
+    let config = state.config.lock().clone();
+    if !config.uptime.healthz {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "not found"})),
+        );
+    }
+    let (status, body) =
+        tokio::task::spawn_blocking(move || crate::health::uptime::report(&config))
+            .await
+            .unwrap_or_else(|e| {
+                (
+                    crate::health::uptime::Status::Down,
+                    serde_json::json!({"status": "down", "error": e.to_string()}),
+                )
+            });
+    let code = if status == crate::health::uptime::Status::Down {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (code, Json(body))
+}
+
 /// Prometheus content type for text exposition format.
 const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
 
@@ -835,13 +889,12 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
     // workspace-aware system context before model invocation.
     let system_prompt = {
         let config_guard = state.config.lock();
-        crate::channels::build_system_prompt(
-            &config_guard.workspace_dir,
+        crate::channels::build_configured_system_prompt(
+            &config_guard,
             &state.model,
             &[], // tools - empty for simple chat
             &[], // skills
-            Some(&config_guard.identity),
-            None, // bootstrap_max_chars - use default
+            false,
         )
     };
 
@@ -862,7 +915,7 @@ async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Res
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
 async fn run_gateway_chat_with_tools(state: &AppState, message: &str) -> anyhow::Result<String> {
     let config = state.config.lock().clone();
-    crate::agent::process_message(config, message).await
+    Box::pin(crate::agent::process_message(config, message)).await
 }
 
 /// Webhook request body
@@ -999,6 +1052,8 @@ async fn handle_webhook(
                     error_message: None,
                     input_tokens: None,
                     output_tokens: None,
+                    reasoning_tokens: None,
+                    cached_input_tokens: None,
                 });
             state.observer.record_metric(
                 &crate::observability::traits::ObserverMetric::RequestLatency(duration),
@@ -1030,6 +1085,8 @@ async fn handle_webhook(
                     error_message: Some(sanitized.clone()),
                     input_tokens: None,
                     output_tokens: None,
+                    reasoning_tokens: None,
+                    cached_input_tokens: None,
                 });
             state.observer.record_metric(
                 &crate::observability::traits::ObserverMetric::RequestLatency(duration),
@@ -1165,6 +1222,18 @@ async fn handle_whatsapp_message(
         );
     };
 
+    let workspace_dir = state.config.lock().workspace_dir.clone();
+    for receipt in wa.parse_delivery_receipts(&payload) {
+        if let Err(e) = crate::channels::outbox::apply_receipt(
+            &workspace_dir,
+            "whatsapp",
+            &receipt,
+            state.observer.as_ref(),
+        ) {
+            tracing::warn!("Failed to record WhatsApp delivery receipt: {e:#}");
+        }
+    }
+
     // Parse messages from the webhook payload
     let messages = wa.parse_webhook_payload(&payload);
 
@@ -1190,15 +1259,34 @@ async fn handle_whatsapp_message(
                 .await;
         }
 
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 // Send reply via WhatsApp
-                if let Err(e) = wa
-                    .send(&SendMessage::new(response, &msg.reply_target))
-                    .await
-                {
+                let sent = wa
+                    .send_tracked(&SendMessage::new(&response, &msg.reply_target))
+                    .await;
+                if let Err(e) = &sent {
                     tracing::error!("Failed to send WhatsApp reply: {e}");
                 }
+                let outbound = crate::channels::outbox::OutboundMessage {
+                    channel: "whatsapp",
+                    recipient: &msg.reply_target,
+                    platform_message_id: sent.as_ref().ok().and_then(Option::as_deref),
+                    content: &response,
+                    status: if sent.is_ok() {
+                        DeliveryStatus::Sent
+                    } else {
+                        DeliveryStatus::Failed
+                    },
+                    receipts: wa.supports_delivery_receipts(),
+                };
+                if let Err(e) = crate::channels::outbox::record(
+                    &workspace_dir,
+                    &outbound,
+                    state.observer.as_ref(),
+                ) {
+                    tracing::warn!("Failed to record WhatsApp reply in outbox: {e:#}");
+                }
             }
             Err(e) => {
                 tracing::error!("LLM error for WhatsApp message: {e:#}");
@@ -1298,7 +1386,7 @@ async fn handle_linq_webhook(
         }
 
         // Call the LLM
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 // Send reply via Linq
                 if let Err(e) = linq
@@ -1390,7 +1478,7 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
         }
 
         // Call the LLM
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 // Send reply via WATI
                 if let Err(e) = wati
@@ -1494,7 +1582,7 @@ async fn handle_nextcloud_talk_webhook(
                 .await;
         }
 
-        match run_gateway_chat_with_tools(&state, &msg.content).await {
+        match Box::pin(run_gateway_chat_with_tools(&state, &msg.content)).await {
             Ok(response) => {
                 if let Err(e) = nextcloud_talk
                     .send(&SendMessage::new(response, &msg.reply_target))
@@ -1847,6 +1935,8 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            location: None,
+            payload: None,
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -2316,11 +2406,11 @@ mod tests {
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
 
-        let response = handle_nextcloud_talk_webhook(
+        let response = Box::pin(handle_nextcloud_talk_webhook(
             State(state),
             HeaderMap::new(),
             Bytes::from_static(br#"{"type":"message"}"#),
-        )
+        ))
         .await
         .into_response();
 
@@ -2380,9 +2470,13 @@ mod tests {
             HeaderValue::from_str(invalid_signature).unwrap(),
         );
 
-        let response = handle_nextcloud_talk_webhook(State(state), headers, Bytes::from(body))
-            .await
-            .into_response();
+        let response = Box::pin(handle_nextcloud_talk_webhook(
+            State(state),
+            headers,
+            Bytes::from(body),
+        ))
+        .await
+        .into_response();
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
     }
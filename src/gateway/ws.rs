@@ -1,6 +1,6 @@
-//! WebSocket agent chat handler.
+//! WebSocket handlers.
 //!
-//! Protocol:
+//! `/ws/chat` — single-turn agent chat:
 //! ```text
 //! Client -> Server: {"type":"message","content":"Hello"}
 //! Server -> Client: {"type":"chunk","content":"Hi! "}
@@ -8,6 +8,21 @@
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
+//!
+//! `/ws/channel?user=<name>` — the `web` channel for custom frontends
+//! (see [`crate::channels::web`]):
+//! ```text
+//! Server -> Client: {"type":"session","id":"...","user":"alice"}
+//! Client -> Server: {"type":"message","content":"Hello"}
+//! Server -> Client: {"type":"accepted","message_id":"..."}
+//! Server -> Client: {"type":"typing"}
+//! Server -> Client: {"type":"start","id":"..."}
+//! Server -> Client: {"type":"chunk","id":"...","content":"Hi"}        (accumulated text)
+//! Server -> Client: {"type":"done","id":"...","content":"Hi there!"}
+//! Server -> Client: {"type":"message","content":"..."}                (non-streamed replies)
+//! Client -> Server: {"type":"subscribe"}
+//! Server -> Client: {"type":"event","role":"user"|"assistant","session":"...","content":"..."}
+//! ```
 
 use super::AppState;
 use axum::{
@@ -25,6 +40,12 @@ pub struct WsQuery {
     pub token: Option<String>,
 }
 
+#[derive(Deserialize)]
+pub struct WsChannelQuery {
+    pub token: Option<String>,
+    pub user: Option<String>,
+}
+
 /// GET /ws/chat — WebSocket upgrade for agent chat
 pub async fn handle_ws_chat(
     State(state): State<AppState>,
@@ -96,13 +117,12 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         // Simple single-turn chat (no streaming for now — use provider.chat_with_system)
         let system_prompt = {
             let config_guard = state.config.lock();
-            crate::channels::build_system_prompt(
-                &config_guard.workspace_dir,
+            crate::channels::build_configured_system_prompt(
+                &config_guard,
                 &state.model,
                 &[],
                 &[],
-                Some(&config_guard.identity),
-                None,
+                false,
             )
         };
 
@@ -165,3 +185,142 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
         }
     }
 }
+
+/// GET /ws/channel — WebSocket upgrade for the `web` channel
+pub async fn handle_ws_channel(
+    State(state): State<AppState>,
+    Query(params): Query<WsChannelQuery>,
+    headers: axum::http::HeaderMap,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    // Browsers pass the token as a query param; other clients may use a header.
+    // With pairing on, the user is the paired credential, not a client claim.
+    let paired_user = if state.pairing.require_pairing() {
+        let bearer = headers
+            .get(axum::http::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|auth| auth.strip_prefix("Bearer "));
+        let token = bearer.or(params.token.as_deref()).unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            return (
+                axum::http::StatusCode::UNAUTHORIZED,
+                "Unauthorized — provide ?token=<bearer_token>",
+            )
+                .into_response();
+        }
+        Some(crate::security::pairing::PairingGuard::token_identity(
+            token,
+        ))
+    } else {
+        None
+    };
+
+    let hub = crate::channels::web::hub();
+    if !hub.is_running() {
+        return (
+            axum::http::StatusCode::SERVICE_UNAVAILABLE,
+            "Web channel is not running — configure [channels_config.web] and run `zeroclaw daemon`",
+        )
+            .into_response();
+    }
+    let user = paired_user.unwrap_or_else(|| {
+        params
+            .user
+            .map(|user| user.trim().to_string())
+            .filter(|user| !user.is_empty())
+            .unwrap_or_else(|| "web".to_string())
+    });
+    if !hub.is_user_allowed(&user) {
+        return (
+            axum::http::StatusCode::FORBIDDEN,
+            "User is not in [channels_config.web] allowed_users",
+        )
+            .into_response();
+    }
+
+    ws.on_upgrade(move |socket| handle_channel_socket(socket, user))
+        .into_response()
+}
+
+async fn handle_channel_socket(socket: WebSocket, user: String) {
+    let hub = crate::channels::web::hub();
+    let (mut sender, mut receiver) = socket.split();
+    let mut session = hub.connect(&user);
+    let mut subscription: Option<tokio::sync::broadcast::Receiver<serde_json::Value>> = None;
+
+    let hello = serde_json::json!({"type": "session", "id": session.id, "user": session.user});
+    if sender
+        .send(Message::Text(hello.to_string().into()))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        let outgoing = tokio::select! {
+            incoming = receiver.next() => {
+                let text = match incoming {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                };
+                let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    let err = serde_json::json!({"type": "error", "message": "Invalid JSON"});
+                    let _ = sender.send(Message::Text(err.to_string().into())).await;
+                    continue;
+                };
+                match parsed["type"].as_str().unwrap_or("") {
+                    "message" => {
+                        let content = parsed["content"].as_str().unwrap_or("").trim();
+                        if content.is_empty() {
+                            continue;
+                        }
+                        match hub.submit(&session, content) {
+                            Ok(id) => serde_json::json!({"type": "accepted", "message_id": id}),
+                            Err(e) => serde_json::json!({"type": "error", "message": e.to_string()}),
+                        }
+                    }
+                    "subscribe" => {
+                        subscription = Some(hub.subscribe());
+                        serde_json::json!({"type": "subscribed"})
+                    }
+                    "unsubscribe" => {
+                        subscription = None;
+                        serde_json::json!({"type": "unsubscribed"})
+                    }
+                    _ => continue,
+                }
+            }
+            Some(event) = session.outbound.recv() => event,
+            event = recv_subscription(&mut subscription, &session.user) => event,
+        };
+        if sender
+            .send(Message::Text(outgoing.to_string().into()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Next subscribed event of `user`'s conversations; pending forever while
+/// unsubscribed.
+async fn recv_subscription(
+    subscription: &mut Option<tokio::sync::broadcast::Receiver<serde_json::Value>>,
+    user: &str,
+) -> serde_json::Value {
+    let Some(rx) = subscription.as_mut() else {
+        return std::future::pending().await;
+    };
+    loop {
+        match rx.recv().await {
+            Ok(event) if event["user"] == user => return event,
+            Ok(_) | Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                return std::future::pending().await
+            }
+        }
+    }
+}
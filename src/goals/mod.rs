@@ -0,0 +1,483 @@
+//! Goal tracking.
+//!
+//! Goals with optional milestones and a log of progress notes are kept in
+//! `<workspace>/goals/goals.db`. The `goals` and `goal_update` tools edit
+//! them, active goals are summarized in the system prompt, and the optional
+//! `[goals.review]` schedule runs a periodic progress review.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Progress notes loaded per goal.
+const RECENT_NOTES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalStatus {
+    Active,
+    Done,
+    Dropped,
+}
+
+impl GoalStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Done => "done",
+            Self::Dropped => "dropped",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "active" => Ok(Self::Active),
+            "done" | "completed" | "complete" => Ok(Self::Done),
+            "dropped" | "abandoned" => Ok(Self::Dropped),
+            other => bail!("Unknown goal status '{other}' (expected active, done, or dropped)"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Milestone {
+    pub id: i64,
+    pub title: String,
+    pub done_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProgressNote {
+    pub note: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Goal {
+    pub id: i64,
+    pub title: String,
+    pub status: GoalStatus,
+    pub due: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+    pub milestones: Vec<Milestone>,
+    /// Most recent progress notes, newest first.
+    pub recent_notes: Vec<ProgressNote>,
+}
+
+impl Goal {
+    pub fn milestones_done(&self) -> usize {
+        self.milestones
+            .iter()
+            .filter(|milestone| milestone.done_at.is_some())
+            .count()
+    }
+}
+
+/// Create an active goal with its milestones.
+pub fn create(
+    workspace_dir: &Path,
+    title: &str,
+    due: Option<NaiveDate>,
+    milestones: &[String],
+) -> Result<Goal> {
+    let title = title.trim();
+    if title.is_empty() {
+        bail!("Goal title must not be empty");
+    }
+    let id = with_connection(workspace_dir, |conn| {
+        let now = Utc::now().to_rfc3339();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO goals (title, status, due, created_at, updated_at)
+             VALUES (?1, 'active', ?2, ?3, ?3)",
+            params![title, due.map(|date| date.to_string()), now],
+        )
+        .context("Failed to create goal")?;
+        let id = tx.last_insert_rowid();
+        for milestone in milestones
+            .iter()
+            .map(|milestone| milestone.trim())
+            .filter(|milestone| !milestone.is_empty())
+        {
+            tx.execute(
+                "INSERT INTO milestones (goal_id, title) VALUES (?1, ?2)",
+                params![id, milestone],
+            )?;
+        }
+        tx.commit()?;
+        Ok(id)
+    })?;
+    get(workspace_dir, id)
+}
+
+/// Goals ordered by id; closed goals only when `include_closed` is set.
+pub fn list(workspace_dir: &Path, include_closed: bool) -> Result<Vec<Goal>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt =
+            conn.prepare("SELECT id FROM goals WHERE ?1 OR status = 'active' ORDER BY id")?;
+        let ids = stmt
+            .query_map(params![include_closed], |row| row.get::<_, i64>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        ids.into_iter().map(|id| load(conn, id)).collect()
+    })
+}
+
+pub fn get(workspace_dir: &Path, id: i64) -> Result<Goal> {
+    with_connection(workspace_dir, |conn| load(conn, id))
+}
+
+/// Resolve a goal from its id (`3` or `#3`) or a unique piece of its title,
+/// preferring active goals.
+pub fn find(workspace_dir: &Path, reference: &str) -> Result<Goal> {
+    let reference = reference.trim();
+    if let Ok(id) = reference.trim_start_matches('#').parse::<i64>() {
+        return get(workspace_dir, id);
+    }
+    let needle = reference.to_lowercase();
+    let matching: Vec<Goal> = list(workspace_dir, true)?
+        .into_iter()
+        .filter(|goal| goal.title.to_lowercase().contains(&needle))
+        .collect();
+    let active: Vec<&Goal> = matching
+        .iter()
+        .filter(|goal| goal.status == GoalStatus::Active)
+        .collect();
+    let candidates: Vec<&Goal> = if active.is_empty() {
+        matching.iter().collect()
+    } else {
+        active
+    };
+    match candidates.as_slice() {
+        [goal] => Ok((*goal).clone()),
+        [] => bail!("No goal matches '{reference}'"),
+        many => bail!(
+            "'{reference}' matches several goals: {}",
+            many.iter()
+                .map(|goal| format!("#{} {}", goal.id, goal.title))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+pub fn add_note(workspace_dir: &Path, goal_id: i64, note: &str) -> Result<()> {
+    let note = note.trim();
+    if note.is_empty() {
+        bail!("Progress note must not be empty");
+    }
+    with_connection(workspace_dir, |conn| {
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO progress (goal_id, note, created_at) VALUES (?1, ?2, ?3)",
+            params![goal_id, note, now],
+        )
+        .context("Failed to record progress")?;
+        touch(conn, goal_id, &now)
+    })
+}
+
+pub fn add_milestone(workspace_dir: &Path, goal_id: i64, title: &str) -> Result<()> {
+    let title = title.trim();
+    if title.is_empty() {
+        bail!("Milestone title must not be empty");
+    }
+    with_connection(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO milestones (goal_id, title) VALUES (?1, ?2)",
+            params![goal_id, title],
+        )
+        .context("Failed to add milestone")?;
+        touch(conn, goal_id, &Utc::now().to_rfc3339())
+    })
+}
+
+/// Mark a milestone done, by 1-based position or a piece of its title.
+/// Returns the milestone title.
+pub fn complete_milestone(workspace_dir: &Path, goal_id: i64, reference: &str) -> Result<String> {
+    let goal = get(workspace_dir, goal_id)?;
+    let reference = reference.trim();
+    let milestone = match reference.parse::<usize>() {
+        Ok(position) => goal.milestones.get(position.wrapping_sub(1)),
+        Err(_) => {
+            let needle = reference.to_lowercase();
+            let mut matching = goal
+                .milestones
+                .iter()
+                .filter(|milestone| milestone.title.to_lowercase().contains(&needle));
+            match (matching.next(), matching.next()) {
+                (Some(_), Some(_)) => bail!("'{reference}' matches several milestones"),
+                (first, _) => first,
+            }
+        }
+    }
+    .with_context(|| format!("Goal #{goal_id} has no milestone '{reference}'"))?;
+
+    with_connection(workspace_dir, |conn| {
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE milestones SET done_at = COALESCE(done_at, ?2) WHERE id = ?1",
+            params![milestone.id, now],
+        )?;
+        touch(conn, goal_id, &now)
+    })?;
+    Ok(milestone.title.clone())
+}
+
+pub fn set_status(workspace_dir: &Path, goal_id: i64, status: GoalStatus) -> Result<()> {
+    with_connection(workspace_dir, |conn| {
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE goals SET status = ?2 WHERE id = ?1",
+            params![goal_id, status.as_str()],
+        )?;
+        touch(conn, goal_id, &now)
+    })
+}
+
+pub fn remove(workspace_dir: &Path, goal_id: i64) -> Result<bool> {
+    with_connection(workspace_dir, |conn| {
+        let removed = conn.execute("DELETE FROM goals WHERE id = ?1", params![goal_id])?;
+        Ok(removed > 0)
+    })
+}
+
+/// One goal as a short block: status, milestones, and recent notes.
+pub fn describe(goal: &Goal) -> String {
+    let mut text = format!("#{} {} [{}]", goal.id, goal.title, goal.status.as_str());
+    if let Some(due) = goal.due {
+        let _ = write!(text, " due {due}");
+    }
+    for (i, milestone) in goal.milestones.iter().enumerate() {
+        let mark = if milestone.done_at.is_some() {
+            "x"
+        } else {
+            " "
+        };
+        let _ = write!(text, "\n  {}. [{mark}] {}", i + 1, milestone.title);
+    }
+    for note in &goal.recent_notes {
+        let _ = write!(
+            text,
+            "\n  - {}: {}",
+            note.created_at.format("%Y-%m-%d"),
+            note.note
+        );
+    }
+    text
+}
+
+/// System prompt section listing up to `limit` active goals; empty when
+/// there are none.
+pub fn prompt_section(goals: &[Goal], limit: usize) -> String {
+    let active: Vec<&Goal> = goals
+        .iter()
+        .filter(|goal| goal.status == GoalStatus::Active)
+        .take(limit)
+        .collect();
+    if active.is_empty() {
+        return String::new();
+    }
+    let mut section = String::from(
+        "## Active Goals\n\nThe user is working toward these goals. When they mention progress, \
+         record it with `goal_update`.\n",
+    );
+    for goal in active {
+        let _ = write!(section, "\n- #{} {}", goal.id, goal.title);
+        if !goal.milestones.is_empty() {
+            let _ = write!(
+                section,
+                " ({}/{} milestones",
+                goal.milestones_done(),
+                goal.milestones.len()
+            );
+            if let Some(next) = goal.milestones.iter().find(|m| m.done_at.is_none()) {
+                let _ = write!(section, "; next: {}", next.title);
+            }
+            section.push(')');
+        }
+        if let Some(due) = goal.due {
+            let _ = write!(section, ", due {due}");
+        }
+        if let Some(note) = goal.recent_notes.first() {
+            let _ = write!(
+                section,
+                ". Last update {}: {}",
+                note.created_at.format("%Y-%m-%d"),
+                note.note
+            );
+        }
+    }
+    section
+}
+
+/// Prompt section for the workspace's active goals; empty when `limit` is 0
+/// or nothing is active.
+pub fn active_prompt_section(workspace_dir: &Path, limit: usize) -> Result<String> {
+    if limit == 0 {
+        return Ok(String::new());
+    }
+    Ok(prompt_section(&list(workspace_dir, false)?, limit))
+}
+
+fn touch(conn: &Connection, goal_id: i64, now: &str) -> Result<()> {
+    let updated = conn.execute(
+        "UPDATE goals SET updated_at = ?2 WHERE id = ?1",
+        params![goal_id, now],
+    )?;
+    if updated == 0 {
+        bail!("No goal #{goal_id}");
+    }
+    Ok(())
+}
+
+fn parse_timestamp(value: &str) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+        })
+}
+
+fn load(conn: &Connection, id: i64) -> Result<Goal> {
+    let row = conn
+        .query_row(
+            "SELECT title, status, due, created_at FROM goals WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    parse_timestamp(&row.get::<_, String>(3)?)?,
+                ))
+            },
+        )
+        .optional()?;
+    let Some((title, status, due, created_at)) = row else {
+        bail!("No goal #{id}");
+    };
+
+    let mut stmt =
+        conn.prepare("SELECT id, title, done_at FROM milestones WHERE goal_id = ?1 ORDER BY id")?;
+    let milestones = stmt
+        .query_map(params![id], |row| {
+            Ok(Milestone {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                done_at: row
+                    .get::<_, Option<String>>(2)?
+                    .as_deref()
+                    .map(parse_timestamp)
+                    .transpose()?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT note, created_at FROM progress WHERE goal_id = ?1 ORDER BY id DESC LIMIT ?2",
+    )?;
+    let recent_notes = stmt
+        .query_map(params![id, RECENT_NOTES as i64], |row| {
+            Ok(ProgressNote {
+                note: row.get(0)?,
+                created_at: parse_timestamp(&row.get::<_, String>(1)?)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(Goal {
+        id,
+        title,
+        status: GoalStatus::parse(&status)?,
+        due: due.as_deref().and_then(|due| due.parse().ok()),
+        created_at,
+        milestones,
+        recent_notes,
+    })
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let db_path = workspace_dir.join("goals").join("goals.db");
+    let conn = crate::state::open_db(&db_path)
+        .with_context(|| format!("Failed to open goals DB: {}", db_path.display()))?;
+
+    conn.execute_batch(
+        "PRAGMA foreign_keys = ON;
+         CREATE TABLE IF NOT EXISTS goals (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            title      TEXT NOT NULL,
+            status     TEXT NOT NULL,
+            due        TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+         );
+         CREATE TABLE IF NOT EXISTS milestones (
+            id      INTEGER PRIMARY KEY AUTOINCREMENT,
+            goal_id INTEGER NOT NULL REFERENCES goals(id) ON DELETE CASCADE,
+            title   TEXT NOT NULL,
+            done_at TEXT
+         );
+         CREATE TABLE IF NOT EXISTS progress (
+            id         INTEGER PRIMARY KEY AUTOINCREMENT,
+            goal_id    INTEGER NOT NULL REFERENCES goals(id) ON DELETE CASCADE,
+            note       TEXT NOT NULL,
+            created_at TEXT NOT NULL
+         );",
+    )
+    .context("Failed to initialize goals schema")?;
+
+    f(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn goals_track_milestones_notes_and_status() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        let goal = create(
+            ws,
+            "Run a half marathon",
+            Some(NaiveDate::from_ymd_opt(2026, 10, 4).unwrap()),
+            &["Run 10k".into(), "Run 15k".into(), " ".into()],
+        )
+        .unwrap();
+        assert_eq!(goal.milestones.len(), 2);
+        create(ws, "Learn Spanish", None, &[]).unwrap();
+
+        assert_eq!(complete_milestone(ws, goal.id, "10k").unwrap(), "Run 10k");
+        add_note(ws, goal.id, "Ran 12k on Sunday").unwrap();
+        let goal = find(ws, "marathon").unwrap();
+        assert_eq!(goal.milestones_done(), 1);
+        assert_eq!(goal.recent_notes[0].note, "Ran 12k on Sunday");
+        assert!(complete_milestone(ws, goal.id, "7").is_err());
+
+        let section = prompt_section(&list(ws, false).unwrap(), 5);
+        assert!(section.starts_with("## Active Goals"));
+        assert!(section.contains(
+            "- #1 Run a half marathon (1/2 milestones; next: Run 15k), due 2026-10-04. Last update"
+        ));
+        assert!(section.contains("- #2 Learn Spanish"));
+
+        set_status(ws, goal.id, GoalStatus::Done).unwrap();
+        assert_eq!(list(ws, false).unwrap().len(), 1);
+        assert_eq!(list(ws, true).unwrap().len(), 2);
+        assert!(remove(ws, goal.id).unwrap());
+        assert!(find(ws, "#1").is_err());
+        assert_eq!(prompt_section(&[], 5), "");
+    }
+
+    #[test]
+    fn find_reports_ambiguous_titles() {
+        let tmp = TempDir::new().unwrap();
+        create(tmp.path(), "Read 12 books", None, &[]).unwrap();
+        create(tmp.path(), "Read the Rust book", None, &[]).unwrap();
+        let err = find(tmp.path(), "read").unwrap_err().to_string();
+        assert!(err.contains("#1 Read 12 books, #2 Read the Rust book"));
+        assert_eq!(find(tmp.path(), "rust").unwrap().id, 2);
+    }
+}
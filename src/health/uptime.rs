@@ -0,0 +1,226 @@
+//! External uptime monitoring (`[uptime]`).
+//!
+//! [`report`] groups the health registry into subsystems — the runtime
+//! (daemon, gateway, scheduler, and other workers), channels, and providers —
+//! and adds queue depths read from the workspace stores. The gateway serves
+//! it at `GET /healthz`, and with `ping_url` set the daemon sends it to a
+//! dead-man's-switch service every `interval_secs`, so a monitor alerts both
+//! when a subsystem fails and when zeroclaw stops reporting at all.
+//!
+//! A failed runtime or channel component makes the report `down` (HTTP 503,
+//! `/fail` ping). Provider errors only mark it `degraded`: a rate-limited
+//! model should not page anyone on its own.
+
+use super::{snapshot, ComponentHealth};
+use crate::config::Config;
+use anyhow::Result;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use tokio::time::Duration;
+
+const UPTIME_COMPONENT: &str = "uptime";
+/// Health components recording provider calls are named `provider:<name>`.
+pub const PROVIDER_COMPONENT_PREFIX: &str = "provider:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Degraded,
+    Down,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Degraded => "degraded",
+            Self::Down => "down",
+        }
+    }
+}
+
+fn subsystem_of(component: &str) -> &'static str {
+    if component.starts_with(PROVIDER_COMPONENT_PREFIX) {
+        "providers"
+    } else if component == "channels" || component == "mqtt" || component.starts_with("channel:") {
+        "channels"
+    } else {
+        "runtime"
+    }
+}
+
+fn subsystem_status(components: &BTreeMap<String, ComponentHealth>) -> &'static str {
+    if components.values().any(|c| c.status == "error") {
+        "error"
+    } else if components.values().all(|c| c.status == "ok") {
+        "ok"
+    } else {
+        "starting"
+    }
+}
+
+/// Overall status from the status of each subsystem.
+fn overall(subsystems: &BTreeMap<&'static str, BTreeMap<String, ComponentHealth>>) -> Status {
+    let failed = |name: &str| {
+        subsystems
+            .get(name)
+            .is_some_and(|components| subsystem_status(components) == "error")
+    };
+    if failed("runtime") || failed("channels") {
+        Status::Down
+    } else if failed("providers") {
+        Status::Degraded
+    } else {
+        Status::Ok
+    }
+}
+
+/// Messages waiting in workspace queues. Stores that do not exist yet are
+/// skipped rather than created.
+fn queue_depths(config: &Config) -> Map<String, Value> {
+    let mut queues = Map::new();
+    let workspace_dir = &config.workspace_dir;
+    if workspace_dir.join("channels").join("review.db").exists() {
+        if let Ok(drafts) = crate::channels::review::list(workspace_dir, Some("pending"), 10_000) {
+            queues.insert("review_pending".into(), json!(drafts.len()));
+        }
+    }
+    if config.cron.enabled && workspace_dir.join("cron").join("jobs.db").exists() {
+        if let Ok(due) = crate::cron::due_jobs(config, Utc::now()) {
+            queues.insert("cron_due".into(), json!(due.len()));
+        }
+        if let Ok(batches) = crate::cron::list_pending_batches(config) {
+            queues.insert("batches_pending".into(), json!(batches.len()));
+        }
+    }
+    queues
+}
+
+/// Health report for `/healthz` and heartbeat pings.
+pub fn report(config: &Config) -> (Status, Value) {
+    let health = snapshot();
+    let mut subsystems: BTreeMap<&'static str, BTreeMap<String, ComponentHealth>> = BTreeMap::new();
+    for (name, component) in health.components {
+        subsystems
+            .entry(subsystem_of(&name))
+            .or_default()
+            .insert(name, component);
+    }
+    let status = overall(&subsystems);
+    let subsystems: Map<String, Value> = subsystems
+        .iter()
+        .map(|(name, components)| {
+            (
+                (*name).to_string(),
+                json!({
+                    "status": subsystem_status(components),
+                    "components": components,
+                }),
+            )
+        })
+        .collect();
+    let body = json!({
+        "status": status.as_str(),
+        "pid": health.pid,
+        "uptime_seconds": health.uptime_seconds,
+        "updated_at": health.updated_at,
+        "subsystems": subsystems,
+        "queues": queue_depths(config),
+    });
+    (status, body)
+}
+
+/// URL to ping for a given status, following the healthchecks.io convention.
+fn ping_target(ping_url: &str, status: Status) -> String {
+    let base = ping_url.trim().trim_end_matches('/');
+    match status {
+        Status::Down => format!("{base}/fail"),
+        Status::Ok | Status::Degraded => base.to_string(),
+    }
+}
+
+/// Ping `[uptime] ping_url` until the daemon stops.
+pub async fn run(config: Config) -> Result<()> {
+    let Some(ping_url) = config.uptime.ping_url.clone() else {
+        return Ok(());
+    };
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "uptime.ping",
+        config.uptime.timeout_secs,
+        config.uptime.timeout_secs,
+    );
+    let mut interval = tokio::time::interval(Duration::from_secs(config.uptime.interval_secs));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let (status, body) = report(&config);
+        let target = ping_target(&ping_url, status);
+        match client.post(&target).json(&body).send().await {
+            Ok(response) if response.status().is_success() => {
+                super::mark_component_ok(UPTIME_COMPONENT);
+            }
+            Ok(response) => {
+                let error = format!("ping returned HTTP {}", response.status());
+                tracing::warn!("uptime: {error}");
+                super::mark_component_error(UPTIME_COMPONENT, error);
+            }
+            Err(e) => {
+                tracing::warn!("uptime: ping failed: {e}");
+                super::mark_component_error(UPTIME_COMPONENT, e.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(status: &str) -> ComponentHealth {
+        ComponentHealth {
+            status: status.into(),
+            updated_at: Utc::now().to_rfc3339(),
+            last_ok: None,
+            last_error: None,
+            restart_count: 0,
+        }
+    }
+
+    #[test]
+    fn provider_failures_degrade_and_core_failures_take_down() {
+        let mut subsystems: BTreeMap<&'static str, BTreeMap<String, ComponentHealth>> =
+            BTreeMap::new();
+        for (name, status) in [
+            ("daemon", "ok"),
+            ("channel:telegram", "ok"),
+            ("provider:openrouter", "error"),
+        ] {
+            subsystems
+                .entry(subsystem_of(name))
+                .or_default()
+                .insert(name.to_string(), component(status));
+        }
+        assert_eq!(overall(&subsystems), Status::Degraded);
+
+        subsystems
+            .get_mut("channels")
+            .unwrap()
+            .insert("channel:telegram".into(), component("error"));
+        assert_eq!(overall(&subsystems), Status::Down);
+    }
+
+    #[test]
+    fn pings_follow_healthchecks_convention() {
+        let url = "https://hc-ping.com/abc/";
+        assert_eq!(ping_target(url, Status::Ok), "https://hc-ping.com/abc");
+        assert_eq!(
+            ping_target(url, Status::Degraded),
+            "https://hc-ping.com/abc"
+        );
+        assert_eq!(
+            ping_target(url, Status::Down),
+            "https://hc-ping.com/abc/fail"
+        );
+    }
+}
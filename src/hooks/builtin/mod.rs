@@ -1,3 +1,5 @@
+pub mod canary;
 pub mod command_logger;
 
+pub use canary::CanaryHook;
 pub use command_logger::CommandLoggerHook;
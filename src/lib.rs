@@ -40,14 +40,20 @@ use serde::{Deserialize, Serialize};
 
 pub mod agent;
 pub(crate) mod approval;
+pub(crate) mod artifacts;
 pub(crate) mod auth;
+pub(crate) mod bench;
 pub mod channels;
 pub mod config;
+pub(crate) mod contacts;
 pub(crate) mod cost;
 pub(crate) mod cron;
 pub(crate) mod daemon;
 pub(crate) mod doctor;
+pub(crate) mod events;
+pub(crate) mod experiments;
 pub mod gateway;
+pub(crate) mod goals;
 pub(crate) mod hardware;
 pub(crate) mod health;
 pub(crate) mod heartbeat;
@@ -56,16 +62,21 @@ pub(crate) mod identity;
 pub(crate) mod integrations;
 pub mod memory;
 pub(crate) mod migration;
+pub(crate) mod monitoring;
 pub(crate) mod multimodal;
 pub mod observability;
 pub(crate) mod onboard;
 pub mod peripherals;
+pub(crate) mod privacy;
 pub mod providers;
 pub mod rag;
+pub(crate) mod replay;
 pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
+pub(crate) mod state;
+pub(crate) mod state_migrations;
 pub mod tools;
 pub(crate) mod tunnel;
 pub(crate) mod util;
@@ -136,6 +147,55 @@ Examples:
         /// Telegram identity to allow (username without '@' or numeric user ID)
         identity: String,
     },
+    /// List pairing requests from unknown senders
+    Pairings {
+        /// Maximum number of requests to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Confirm a pairing code and add its sender to the channel allowlist
+    #[command(long_about = "\
+Confirm a pairing code.
+
+With [channels_config.pairing] enabled, unknown senders on pairing \
+channels receive a six-digit code. Confirming it adds the sender to \
+that channel's allowlist in config.toml; running channels accept them \
+right away.
+
+Examples:
+  zeroclaw channel pairings
+  zeroclaw channel pair 482913")]
+    Pair {
+        /// Code the sender received
+        code: String,
+    },
+    /// Feed a saved inbound payload through channel parsing and dispatch policies
+    #[command(long_about = "\
+Feed a saved inbound payload through channel parsing and dispatch policies.
+
+Parses the payload with the configured channel's own parser, then prints \
+the parsed message, the policy decisions made before the agent runs \
+(runtime commands, agent routes, group wake words, rate limits, persona, \
+tools, ack reactions), and where the reply would go. Nothing is sent. \
+With --dry-run the provider is not called; otherwise the agent's reply \
+is printed.
+
+Supported payloads: Telegram updates (getUpdates or webhook JSON).
+
+Examples:
+  zeroclaw channel simulate --file update.json --dry-run
+  zeroclaw channel simulate --file update.json --channel telegram")]
+    Simulate {
+        /// JSON payload file
+        #[arg(long)]
+        file: std::path::PathBuf,
+        /// Channel the payload is for; detected from the payload when omitted
+        #[arg(long)]
+        channel: Option<String>,
+        /// Do not call the provider
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 /// Skills management subcommands
@@ -158,6 +218,17 @@ pub enum SkillCommands {
         /// Skill name to remove
         name: String,
     },
+    /// Print the skills prompt section exactly as it would be injected
+    Prompt {
+        /// Override the configured injection mode (full or compact)
+        #[arg(long, value_parser = ["full", "compact"])]
+        mode: Option<String>,
+        /// Output format: xml (raw prompt), markdown, or json
+        #[arg(long, default_value = "xml", value_parser = ["xml", "markdown", "json"])]
+        format: String,
+    },
+    /// Show per-skill usage: prompt inclusions and tool runs
+    Stats,
 }
 
 /// Migration subcommands
@@ -175,6 +246,21 @@ pub enum MigrateCommands {
     },
 }
 
+/// Import subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ImportCommands {
+    /// Import channels, allowlists, skills, history, and memory from an `OpenClaw` install
+    Openclaw {
+        /// Path to the `OpenClaw` state directory (defaults to ~/.openclaw)
+        #[arg(long)]
+        path: Option<std::path::PathBuf>,
+
+        /// Print what will and won't carry over without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
 /// Cron subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CronCommands {
@@ -329,6 +415,161 @@ pub enum MemoryCommands {
     },
 }
 
+/// A/B experiment subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ExperimentCommands {
+    /// Compare experiment variants by latency, reply length, and reactions
+    Report {
+        /// Experiment name (default: all configured experiments)
+        name: Option<String>,
+    },
+}
+
+/// Reaction feedback subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FeedbackCommands {
+    /// Summarize reactions users left on agent replies
+    Stats {
+        /// Look back this many days
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// Only count reactions on this channel
+        #[arg(long)]
+        channel: Option<String>,
+    },
+}
+
+/// Outbound message log subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OutboxCommands {
+    /// List recent outbound messages with their delivery status
+    List {
+        /// Only show messages sent on this channel
+        #[arg(long)]
+        channel: Option<String>,
+        /// Maximum number of messages to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+/// Conversation index subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HistoryCommands {
+    /// List recent channel conversations with their titles and ids
+    List {
+        /// Only show conversations on this channel
+        #[arg(long)]
+        channel: Option<String>,
+        /// Only show conversations with this sender
+        #[arg(long)]
+        sender: Option<String>,
+        /// Maximum number of conversations to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+/// Spam quarantine review subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum QuarantineCommands {
+    /// List inbound messages held by the spam filter
+    List {
+        /// Only show messages received on this channel
+        #[arg(long)]
+        channel: Option<String>,
+        /// Maximum number of messages to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Remove a reviewed message from the quarantine
+    Dismiss {
+        /// Quarantine entry id (from `quarantine list`)
+        id: String,
+    },
+    /// Clear a sender's spam strikes, lifting their rate limit or mute
+    Pardon {
+        /// Channel name (e.g. telegram)
+        channel: String,
+        /// Sender id as shown by `quarantine list`
+        sender: String,
+    },
+}
+
+/// Reply review subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReviewCommands {
+    /// List held replies with their review decisions
+    List {
+        /// Only show drafts with this status (pending, approved, edited, rejected)
+        #[arg(long)]
+        status: Option<String>,
+        /// Maximum number of drafts to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+/// Personal data subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PrivacyCommands {
+    /// Export everything stored about a person as JSON
+    Export {
+        /// Sender id (`alice`), channel-qualified id (`telegram:alice`), or contact name
+        #[arg(long)]
+        identity: String,
+        /// Output file, or `-` for stdout (default: zeroclaw-export-<identity>-<date>.json)
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Security policy subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PolicyCommands {
+    /// Evaluate a shell command against the configured security policy
+    Check {
+        /// Shell command to evaluate (quote it as one argument)
+        command: String,
+        /// Treat the command as explicitly approved (as `approved=true` in the shell tool)
+        #[arg(long)]
+        approved: bool,
+    },
+    /// List persisted "always allow" command grants
+    Grants,
+    /// Revoke a command grant
+    Revoke {
+        /// Grant id (from `policy grants`)
+        id: String,
+    },
+}
+
+/// Workspace state subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StateCommands {
+    /// Check state databases and JSON state files for corruption
+    Verify,
+}
+
+/// Tool registry subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ToolCommands {
+    /// List registered tools, including skill tools
+    List {
+        /// Print each tool's parameter schema
+        #[arg(long)]
+        schema: bool,
+    },
+    /// Run one tool directly, without a model, and print its result
+    Test {
+        /// Tool name (from `tools list`)
+        name: String,
+        /// Tool arguments as a JSON object
+        #[arg(long, default_value = "{}")]
+        args: String,
+    },
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum IntegrationCommands {
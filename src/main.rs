@@ -50,17 +50,23 @@ fn parse_temperature(s: &str) -> std::result::Result<f64, String> {
 
 mod agent;
 mod approval;
+mod artifacts;
 mod auth;
+mod bench;
 mod channels;
 mod rag {
     pub use zeroclaw::rag::*;
 }
 mod config;
+mod contacts;
 mod cost;
 mod cron;
 mod daemon;
 mod doctor;
+mod events;
+mod experiments;
 mod gateway;
+mod goals;
 mod hardware;
 mod health;
 mod heartbeat;
@@ -69,16 +75,21 @@ mod identity;
 mod integrations;
 mod memory;
 mod migration;
+mod monitoring;
 mod multimodal;
 mod observability;
 mod onboard;
 mod peripherals;
+mod privacy;
 mod providers;
+mod replay;
 mod runtime;
 mod security;
 mod service;
 mod skillforge;
 mod skills;
+mod state;
+mod state_migrations;
 mod tools;
 mod tunnel;
 mod util;
@@ -87,8 +98,10 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    ChannelCommands, CronCommands, ExperimentCommands, FeedbackCommands, HardwareCommands,
+    HistoryCommands, ImportCommands, IntegrationCommands, MigrateCommands, OutboxCommands,
+    PeripheralCommands, PolicyCommands, PrivacyCommands, QuarantineCommands, ReviewCommands,
+    ServiceCommands, SkillCommands, StateCommands, ToolCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -173,7 +186,8 @@ Examples:
   zeroclaw agent                              # interactive session
   zeroclaw agent -m \"Summarize today's logs\"  # single message
   zeroclaw agent -p anthropic --model claude-sonnet-4-20250514
-  zeroclaw agent --peripheral nucleo-f401re:/dev/ttyACM0")]
+  zeroclaw agent --peripheral nucleo-f401re:/dev/ttyACM0
+  zeroclaw agent --debug-capture ./captures   # save provider requests/responses")]
     Agent {
         /// Single message mode (don't enter interactive mode)
         #[arg(short, long)]
@@ -194,6 +208,10 @@ Examples:
         /// Attach a peripheral (board:path, e.g. nucleo-f401re:/dev/ttyACM0)
         #[arg(long)]
         peripheral: Vec<String>,
+
+        /// Write redacted provider requests, responses, and system prompts to this directory
+        #[arg(long, value_name = "DIR")]
+        debug_capture: Option<String>,
     },
 
     /// Start the gateway server (webhooks, websockets)
@@ -261,6 +279,41 @@ Examples:
         doctor_command: Option<DoctorCommands>,
     },
 
+    /// Inspect the assembled system prompt
+    #[command(long_about = "\
+Inspect the assembled system prompt.
+
+Prints the system prompt the agent sends with the current config: \
+built-in instructions, persona, skills, memory, and any \
+[[prompt.parts]] (inline text and workspace include files such as \
+prompt.d/*.md), followed by its size and estimated token count.
+
+Examples:
+  zeroclaw prompt show
+  zeroclaw prompt show > prompt.txt")]
+    Prompt {
+        #[command(subcommand)]
+        prompt_command: PromptCommands,
+    },
+
+    /// Benchmark configured providers
+    #[command(long_about = "\
+Benchmark configured providers.
+
+Sends a short standard prompt to every configured provider/model \
+(default, fallback providers, model routes, delegate agents) and \
+reports p50/p95 latency, time to first token for streaming providers, \
+and the error rate. Calls skip retries and fallbacks.
+
+Examples:
+  zeroclaw bench providers
+  zeroclaw bench providers --iterations 10
+  zeroclaw bench providers --provider ollama")]
+    Bench {
+        #[command(subcommand)]
+        bench_command: BenchCommands,
+    },
+
     /// Show system status (full details)
     Status,
 
@@ -339,7 +392,8 @@ Examples:
   zeroclaw channel doctor
   zeroclaw channel add telegram '{\"bot_token\":\"...\",\"name\":\"my-bot\"}'
   zeroclaw channel remove my-bot
-  zeroclaw channel bind-telegram zeroclaw_user")]
+  zeroclaw channel bind-telegram zeroclaw_user
+  zeroclaw channel pair 482913")]
     Channel {
         #[command(subcommand)]
         channel_command: ChannelCommands,
@@ -357,12 +411,65 @@ Examples:
         skill_command: SkillCommands,
     },
 
+    /// Verify workspace state files
+    #[command(long_about = "\
+Verify workspace state files.
+
+`state verify` runs SQLite's integrity check on every state database \
+(outbox, cron jobs, pairing, memory, and the other channel stores) and \
+checks that the JSON files in `state/` parse. Exits non-zero when a file \
+is damaged.
+
+Examples:
+  zeroclaw state verify")]
+    State {
+        #[command(subcommand)]
+        state_command: StateCommands,
+    },
+
+    /// List tools and run one directly for debugging
+    #[command(long_about = "\
+List registered tools and run one directly.
+
+`tools list` shows every tool the agent would get from the current \
+config, including tools declared by skills. `tools test` executes one \
+tool with JSON arguments under the configured security policy and \
+prints its result, without calling a model. Exits non-zero when the \
+tool reports a failure.
+
+Examples:
+  zeroclaw tools list
+  zeroclaw tools list --schema
+  zeroclaw tools test file_read --args '{\"path\": \"README.md\"}'
+  zeroclaw tools test shell --args '{\"command\": \"git status\"}'")]
+    Tools {
+        #[command(subcommand)]
+        tool_command: ToolCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
         migrate_command: MigrateCommands,
     },
 
+    /// Import channels, skills, and history from other agent runtimes
+    #[command(long_about = "\
+Import state from other agent runtimes.
+
+Converts an OpenClaw install's channel config, allowlists (including \
+approved pairings), skills, session history, and memory into zeroclaw's \
+formats. Channels and skills already set up in zeroclaw are left alone. \
+Use --dry-run to see what will and won't carry over first.
+
+Examples:
+  zeroclaw import openclaw --dry-run
+  zeroclaw import openclaw --path /backups/.openclaw")]
+    Import {
+        #[command(subcommand)]
+        import_command: ImportCommands,
+    },
+
     /// Manage provider subscription authentication profiles
     Auth {
         #[command(subcommand)]
@@ -424,17 +531,220 @@ Examples:
         memory_command: MemoryCommands,
     },
 
+    /// Re-encrypt memory under a new workspace key
+    #[command(long_about = "\
+Re-encrypt stored memory under a new workspace key.
+
+Generates a new key from memory.encryption.key_source (a random key in \
+the OS keyring, or a new passphrase from ZEROCLAW_MEMORY_NEW_PASSPHRASE \
+or a prompt) and re-encrypts every entry. The current key is read from \
+the source it was created with, so rekey also switches key sources. \
+Entries written before encryption was enabled are encrypted too.
+
+With --decrypt, every entry is stored as plaintext again and the key \
+is forgotten.
+
+Examples:
+  zeroclaw rekey
+  ZEROCLAW_MEMORY_PASSPHRASE=old ZEROCLAW_MEMORY_NEW_PASSPHRASE=new zeroclaw rekey
+  zeroclaw rekey --decrypt")]
+    Rekey {
+        /// Decrypt all entries and remove the key instead
+        #[arg(long)]
+        decrypt: bool,
+    },
+
+    /// Re-run a captured provider call and diff the result
+    #[command(long_about = "\
+Re-run a captured provider call and diff the result.
+
+Loads a request written by debug capture \
+([observability] debug_capture_dir or `agent --debug-capture`), sends \
+it through a provider built from the current config, and compares the \
+reply text and tool calls with the captured response. Use it to verify \
+a fix against real traffic or to compare models on the same turn.
+
+Examples:
+  zeroclaw replay captures/20261015T101530.123-001-request.json
+  zeroclaw replay captures/20261015T101530.123-001 --model openai/gpt-4o
+  zeroclaw replay <capture> --provider anthropic --temperature 0")]
+    Replay {
+        /// Capture file (any of its -request.json, -response.json, -system-prompt.md, or their shared prefix)
+        capture: std::path::PathBuf,
+        /// Provider to replay against (default: config default_provider)
+        #[arg(long)]
+        provider: Option<String>,
+        /// Model to replay with (default: the captured model)
+        #[arg(long)]
+        model: Option<String>,
+        /// Temperature (default: the captured temperature)
+        #[arg(long, value_parser = parse_temperature)]
+        temperature: Option<f64>,
+    },
+
+    /// Report on A/B prompt experiments
+    #[command(long_about = "\
+Report on A/B prompt experiments.
+
+Conversations are split between the variants of the enabled \
+[[experiments]] entry. The report compares variants by conversations, \
+replies, failures, average latency and reply length, and the \
+reactions users left on replies.
+
+Examples:
+  zeroclaw experiments report
+  zeroclaw experiments report persona-v2")]
+    Experiments {
+        #[command(subcommand)]
+        experiments_command: ExperimentCommands,
+    },
+
+    /// Summarize reactions on agent replies
+    #[command(long_about = "\
+Summarize reactions on agent replies.
+
+Reactions users place on the agent's messages (on channels that \
+report them, e.g. Telegram and Discord) are stored as feedback. \
+Shows positive/negative counts, the most used emoji, and recently \
+disliked replies.
+
+Examples:
+  zeroclaw feedback stats
+  zeroclaw feedback stats --days 7 --channel telegram")]
+    Feedback {
+        #[command(subcommand)]
+        feedback_command: FeedbackCommands,
+    },
+
+    /// Show outbound messages and their delivery status
+    #[command(long_about = "\
+Show outbound messages and their delivery status.
+
+Every agent reply sent through a channel is recorded with its \
+status: sent, delivered, read, or failed. Delivered/read receipts \
+are tracked where the platform reports them (e.g. WhatsApp).
+
+Examples:
+  zeroclaw outbox list
+  zeroclaw outbox list --channel whatsapp --limit 50")]
+    Outbox {
+        #[command(subcommand)]
+        outbox_command: OutboxCommands,
+    },
+
+    /// List channel conversations by title
+    #[command(long_about = "\
+List channel conversations by title.
+
+Every channel conversation is indexed with a short id. Once it has a \
+few turns ([channels_config.titles].after_turns) a cheap model call \
+gives it a title. Chat users see their own list with /conversations.
+
+Examples:
+  zeroclaw history list
+  zeroclaw history list --channel telegram --sender alice --limit 50")]
+    History {
+        #[command(subcommand)]
+        history_command: HistoryCommands,
+    },
+
+    /// Show replies held for human review and their decisions
+    #[command(long_about = "\
+Show replies held for human review and their decisions.
+
+With [channels_config.review] enabled, replies on the listed channels \
+are posted to the review chat as drafts instead of being sent. \
+Reviewers answer /approve <id>, /edit <id> <text>, or /reject <id> \
+[reason] there. This command lists the drafts with their reviewer, \
+decision time, original text, and outbox entry.
+
+Examples:
+  zeroclaw review list
+  zeroclaw review list --status pending
+  zeroclaw review list --status edited --limit 50")]
+    Review {
+        #[command(subcommand)]
+        review_command: ReviewCommands,
+    },
+
+    /// Review messages held by the spam filter
+    #[command(long_about = "\
+Review inbound messages held by the spam filter.
+
+When [channels_config.spam] is enabled, messages on channels with a \
+\"*\" allowlist that score as spam or abuse are quarantined here \
+and earn their sender a strike. Strikes tighten the sender's rate \
+limit until they are muted; pardon clears them.
+
+Examples:
+  zeroclaw quarantine list
+  zeroclaw quarantine list --channel telegram --limit 50
+  zeroclaw quarantine dismiss <id>
+  zeroclaw quarantine pardon telegram 123456789")]
+    Quarantine {
+        #[command(subcommand)]
+        quarantine_command: QuarantineCommands,
+    },
+
+    /// Export personal data stored about a person
+    #[command(long_about = "\
+Export personal data stored about a person.
+
+Collects preferences, reactions, messages sent to them, quarantined \
+messages, spam strikes, check-in activity, experiment assignments, \
+personal lists, runtime traces, and memories saved from their messages into one JSON \
+file. The identity is a sender id, a channel-qualified id, or the name \
+of a contact (matching every sender with the contact's phone numbers \
+or emails).
+
+Examples:
+  zeroclaw privacy export --identity telegram:123456789
+  zeroclaw privacy export --identity \"Alice Example\" --output alice.json
+  zeroclaw privacy export --identity alice --output -")]
+    Privacy {
+        #[command(subcommand)]
+        privacy_command: PrivacyCommands,
+    },
+
+    /// Check commands against the security policy and manage command grants
+    #[command(long_about = "\
+Check shell commands against the security policy.
+
+Evaluates a command exactly as the shell tool would: [autonomy.rules] \
+block patterns, blocked env vars and network domains, then the command \
+allowlist, risk gates, and path denylist. Exits non-zero when the \
+command would be blocked.
+
+Grants created by answering an approval prompt with \"always allow \
+this command\" are listed by `policy grants` and removed with \
+`policy revoke`.
+
+Examples:
+  zeroclaw policy check \"git status\"
+  zeroclaw policy check \"git push origin main\" --approved
+  zeroclaw policy check \"curl https://example.com\"
+  zeroclaw policy grants
+  zeroclaw policy revoke <id>")]
+    Policy {
+        #[command(subcommand)]
+        policy_command: PolicyCommands,
+    },
+
     /// Manage configuration
     #[command(long_about = "\
 Manage ZeroClaw configuration.
 
 Inspect and export configuration settings. Use 'schema' to dump \
 the full JSON Schema for the config file, which documents every \
-available key, type, and default value.
+available key, type, and default value. Use 'preset' to share \
+personas and channel formatting settings as a TOML file; imports \
+are validated and show a diff before they are applied.
 
 Examples:
   zeroclaw config schema              # print JSON Schema to stdout
-  zeroclaw config schema > schema.json")]
+  zeroclaw config schema > schema.json
+  zeroclaw config preset export community.toml
+  zeroclaw config preset import community.toml")]
     Config {
         #[command(subcommand)]
         config_command: ConfigCommands,
@@ -461,6 +771,28 @@ Examples:
 enum ConfigCommands {
     /// Dump the full configuration JSON Schema to stdout
     Schema,
+    /// Export or import shareable channel presets
+    Preset {
+        #[command(subcommand)]
+        preset_command: PresetCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PresetCommands {
+    /// Write personas and channel formatting settings to a preset file
+    Export {
+        /// Output file (`-` for stdout)
+        file: std::path::PathBuf,
+    },
+    /// Validate a preset, show the changes, and apply it
+    Import {
+        /// Preset file to import
+        file: std::path::PathBuf,
+        /// Apply without asking for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -627,6 +959,25 @@ enum DoctorCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum PromptCommands {
+    /// Print the final system prompt and its token count
+    Show,
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchCommands {
+    /// Measure latency and error rate of each configured provider/model
+    Providers {
+        /// Requests per provider/model
+        #[arg(short = 'n', long, default_value = "5")]
+        iterations: usize,
+        /// Benchmark only models served by this provider
+        #[arg(long)]
+        provider: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum MemoryCommands {
     /// List memory entries with optional filters
@@ -731,18 +1082,18 @@ async fn main() -> Result<()> {
         } else if interactive {
             onboard::run_wizard(force).await
         } else {
-            onboard::run_quick_setup(
+            Box::pin(onboard::run_quick_setup(
                 api_key.as_deref(),
                 provider.as_deref(),
                 model.as_deref(),
                 memory.as_deref(),
                 force,
-            )
+            ))
             .await
         }?;
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
-            channels::start_channels(config).await?;
+            Box::pin(channels::start_channels(config)).await?;
         }
         return Ok(());
     }
@@ -775,17 +1126,24 @@ async fn main() -> Result<()> {
             model,
             temperature,
             peripheral,
-        } => agent::run(
-            config,
-            message,
-            provider,
-            model,
-            temperature,
-            peripheral,
-            true,
-        )
-        .await
-        .map(|_| ()),
+            debug_capture,
+        } => {
+            let mut config = config;
+            if debug_capture.is_some() {
+                config.observability.debug_capture_dir = debug_capture;
+            }
+            Box::pin(agent::run(
+                config,
+                message,
+                provider,
+                model,
+                temperature,
+                peripheral,
+                true,
+            ))
+            .await
+            .map(|_| ())
+        }
 
         Commands::Gateway { port, host } => {
             let port = port.unwrap_or(config.gateway.port);
@@ -912,6 +1270,34 @@ async fn main() -> Result<()> {
 
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
 
+        Commands::Experiments {
+            experiments_command,
+        } => experiments::handle_command(experiments_command, &config),
+
+        Commands::Feedback { feedback_command } => {
+            channels::feedback::handle_command(feedback_command, &config)
+        }
+
+        Commands::Outbox { outbox_command } => {
+            channels::outbox::handle_command(outbox_command, &config)
+        }
+
+        Commands::History { history_command } => {
+            channels::conversations::handle_command(history_command, &config)
+        }
+
+        Commands::Review { review_command } => {
+            channels::review::handle_command(review_command, &config)
+        }
+
+        Commands::Quarantine { quarantine_command } => {
+            channels::quarantine::handle_command(quarantine_command, &config)
+        }
+
+        Commands::Policy { policy_command } => {
+            security::policy::handle_command(policy_command, &config)
+        }
+
         Commands::Models { model_command } => match model_command {
             ModelCommands::Refresh {
                 provider,
@@ -975,6 +1361,27 @@ async fn main() -> Result<()> {
             service::handle_command(&service_command, &config, init_system)
         }
 
+        Commands::Prompt { prompt_command } => match prompt_command {
+            PromptCommands::Show => {
+                let prompt = agent::loop_::system_prompt_preview(&config).await?;
+                println!("{prompt}");
+                // Stats go to stderr so redirecting stdout captures just the prompt.
+                let chars = prompt.chars().count();
+                eprintln!(
+                    "\n── {chars} chars, ~{} tokens (estimate) ──",
+                    agent::classifier::estimate_tokens(chars)
+                );
+                Ok(())
+            }
+        },
+
+        Commands::Bench { bench_command } => match bench_command {
+            BenchCommands::Providers {
+                iterations,
+                provider,
+            } => bench::run_providers(&config, iterations, provider.as_deref()).await,
+        },
+
         Commands::Doctor { doctor_command } => match doctor_command {
             Some(DoctorCommands::Models {
                 provider,
@@ -996,8 +1403,8 @@ async fn main() -> Result<()> {
         },
 
         Commands::Channel { channel_command } => match channel_command {
-            ChannelCommands::Start => channels::start_channels(config).await,
-            ChannelCommands::Doctor => channels::doctor_channels(config).await,
+            ChannelCommands::Start => Box::pin(channels::start_channels(config)).await,
+            ChannelCommands::Doctor => Box::pin(channels::doctor_channels(config)).await,
             other => channels::handle_command(other, &config).await,
         },
 
@@ -1007,14 +1414,35 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
+        Commands::State { state_command } => state::handle_command(state_command, &config),
+
+        Commands::Tools { tool_command } => tools::cli::handle_command(tool_command, &config).await,
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
 
+        Commands::Import { import_command } => {
+            migration::handle_import_command(import_command, &config).await
+        }
+
         Commands::Memory { memory_command } => {
             memory::cli::handle_command(memory_command, &config).await
         }
 
+        Commands::Replay {
+            capture,
+            provider,
+            model,
+            temperature,
+        } => replay::run(&config, &capture, provider, model, temperature).await,
+
+        Commands::Privacy { privacy_command } => {
+            privacy::handle_command(privacy_command, &config).await
+        }
+
+        Commands::Rekey { decrypt } => memory::cli::handle_rekey(&config, decrypt).await,
+
         Commands::Auth { auth_command } => handle_auth_command(auth_command, &config).await,
 
         Commands::Hardware { hardware_command } => {
@@ -1034,6 +1462,12 @@ async fn main() -> Result<()> {
                 );
                 Ok(())
             }
+            ConfigCommands::Preset { preset_command } => match preset_command {
+                PresetCommands::Export { file } => config::preset::export(&config, &file),
+                PresetCommands::Import { file, yes } => {
+                    config::preset::import(&config, &file, yes).await
+                }
+            },
         },
     }
 }
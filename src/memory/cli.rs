@@ -1,9 +1,11 @@
+use super::encryption::{self, KeySource, MemoryCipher};
 use super::traits::{Memory, MemoryCategory};
+use super::EncryptedMemory;
 use super::{
     classify_memory_backend, create_memory_for_migration, effective_memory_backend_name,
     MemoryBackendKind,
 };
-use crate::config::Config;
+use crate::config::{Config, MemoryKeySource};
 #[cfg(feature = "memory-postgres")]
 use anyhow::Context;
 use anyhow::{bail, Result};
@@ -31,7 +33,17 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
 /// CLI commands (list/get/stats/clear) never use vector search, so we skip
 /// embedding provider initialisation for local backends by using the
 /// migration factory.  Postgres still needs its full connection config.
-fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
+pub(crate) fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
+    let mem = create_cli_backend(config)?;
+    if !config.memory.encryption.enabled {
+        return Ok(mem);
+    }
+    let cipher = MemoryCipher::open(&config.workspace_dir, &config.memory.encryption)?;
+    Ok(Box::new(EncryptedMemory::new(mem, cipher)))
+}
+
+/// The backend without the encryption layer.
+fn create_cli_backend(config: &Config) -> Result<Box<dyn Memory>> {
     let backend = effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
@@ -297,6 +309,166 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
     Ok(())
 }
 
+/// Handle `zeroclaw rekey`: re-encrypt every memory entry under a new
+/// workspace key, or with `decrypt` store them as plaintext again.
+pub async fn handle_rekey(config: &Config, decrypt: bool) -> Result<()> {
+    let workspace_dir = &config.workspace_dir;
+    let encryption = &config.memory.encryption;
+    let mem = create_cli_backend(config)?;
+
+    // The current key is read from the source it was created with, which
+    // differs from the configured one when switching key sources.
+    let current = match encryption::recorded_source(workspace_dir)? {
+        Some(kind) => Some(MemoryCipher::open_with(
+            workspace_dir,
+            &KeySource::resolve(kind, workspace_dir, encryption::PASSPHRASE_ENV)?,
+        )?),
+        None => None,
+    };
+
+    if decrypt {
+        let Some(current) = current else {
+            println!("Memory is not encrypted.");
+            return Ok(());
+        };
+        let decrypt = |content: &str| current.decrypt(content);
+        let count = rewrite_contents(&*mem, decrypt).await?
+            + rewrite_channel_texts(workspace_dir, &decrypt)?;
+        encryption::remove_key_state(workspace_dir)?;
+        println!(
+            "{} Decrypted {count} entries. Set memory.encryption.enabled = false to keep new entries in plaintext.",
+            style("✓").green().bold()
+        );
+        return Ok(());
+    }
+
+    if !encryption.enabled {
+        bail!("memory.encryption.enabled is false; enable it first, or run `zeroclaw rekey --decrypt`");
+    }
+    let source = match encryption.key_source {
+        MemoryKeySource::Keyring => {
+            KeySource::resolve(MemoryKeySource::Keyring, workspace_dir, "")?
+        }
+        MemoryKeySource::Passphrase => KeySource::Passphrase(new_passphrase()?),
+    };
+    let cipher = match &current {
+        Some(current) => encryption::rotate(workspace_dir, current, &source)?,
+        None => MemoryCipher::open_with(workspace_dir, &source)?,
+    };
+    let reseal = |content: &str| {
+        cipher
+            .decrypt(content)
+            .and_then(|plain| cipher.encrypt(&plain))
+    };
+    let count =
+        rewrite_contents(&*mem, reseal).await? + rewrite_channel_texts(workspace_dir, &reseal)?;
+    encryption::finish_rotation(workspace_dir)?;
+
+    println!(
+        "{} Encrypted {count} entries with a new {} key.",
+        style("✓").green().bold(),
+        encryption.key_source
+    );
+    if encryption.key_source == MemoryKeySource::Passphrase {
+        println!(
+            "  Set {} to the new passphrase before starting zeroclaw.",
+            encryption::PASSPHRASE_ENV
+        );
+    }
+    Ok(())
+}
+
+/// New passphrase from `ZEROCLAW_MEMORY_NEW_PASSPHRASE`, or prompted.
+fn new_passphrase() -> Result<String> {
+    if let Some(passphrase) = std::env::var(encryption::NEW_PASSPHRASE_ENV)
+        .ok()
+        .filter(|value| !value.is_empty())
+    {
+        return Ok(passphrase);
+    }
+    if !std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+        bail!(
+            "Set {} to the new memory passphrase",
+            encryption::NEW_PASSPHRASE_ENV
+        );
+    }
+    Ok(dialoguer::Password::new()
+        .with_prompt("New memory passphrase")
+        .with_confirmation("Confirm passphrase", "Passphrases do not match")
+        .interact()?)
+}
+
+/// Replace each entry's content with `transform(content)`. Returns the number
+/// of entries rewritten.
+async fn rewrite_contents(
+    mem: &dyn Memory,
+    transform: impl Fn(&str) -> Result<String>,
+) -> Result<usize> {
+    let mut rewritten = 0;
+    for entry in mem.list(None, None).await? {
+        let content = transform(&entry.content)
+            .map_err(|e| anyhow::anyhow!("memory entry '{}': {e}", entry.key))?;
+        if content != entry.content {
+            mem.store(
+                &entry.key,
+                &content,
+                entry.category,
+                entry.session_id.as_deref(),
+            )
+            .await?;
+            rewritten += 1;
+        }
+    }
+    Ok(rewritten)
+}
+
+/// Message text that channel stores seal with the memory key, as
+/// `(database, table, column)`.
+const CHANNEL_TEXT_COLUMNS: &[(&str, &str, &str)] = &[
+    ("outbox.db", "outbox", "preview"),
+    ("feedback.db", "feedback", "reply_preview"),
+    ("quarantine.db", "quarantine", "content"),
+    ("review.db", "drafts", "original"),
+    ("review.db", "drafts", "content"),
+    ("conversations.db", "conversations", "title"),
+];
+
+/// Replace each value in [`CHANNEL_TEXT_COLUMNS`] with `transform(value)`.
+/// Returns the number of values rewritten.
+fn rewrite_channel_texts(
+    workspace_dir: &std::path::Path,
+    transform: &dyn Fn(&str) -> Result<String>,
+) -> Result<usize> {
+    let mut rewritten = 0;
+    for (db, table, column) in CHANNEL_TEXT_COLUMNS {
+        let path = workspace_dir.join("channels").join(db);
+        if !path.exists() {
+            continue;
+        }
+        let conn = crate::state::open_db(&path)?;
+        let values = conn
+            .prepare(&format!(
+                "SELECT rowid, {column} FROM {table} WHERE {column} IS NOT NULL"
+            ))?
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for (rowid, value) in values {
+            let sealed = transform(&value)
+                .map_err(|e| anyhow::anyhow!("{table}.{column} row {rowid}: {e}"))?;
+            if sealed != value {
+                conn.execute(
+                    &format!("UPDATE {table} SET {column} = ?1 WHERE rowid = ?2"),
+                    rusqlite::params![sealed, rowid],
+                )?;
+                rewritten += 1;
+            }
+        }
+    }
+    Ok(rewritten)
+}
+
 fn parse_category(s: &str) -> MemoryCategory {
     match s.trim().to_ascii_lowercase().as_str() {
         "core" => MemoryCategory::Core,
@@ -357,4 +529,30 @@ mod tests {
     fn truncate_content_empty_string() {
         assert_eq!(truncate_content("", 10), "");
     }
+
+    #[test]
+    fn rewrite_channel_texts_decrypts_sealed_store_text() {
+        use crate::channels::quarantine;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cipher =
+            MemoryCipher::open_with(tmp.path(), &KeySource::Passphrase("pw".into())).unwrap();
+        quarantine::quarantine(tmp.path(), "telegram", "alice", "buy now", 0.9, &[]).unwrap();
+        assert_eq!(
+            quarantine::list(tmp.path(), None, 10).unwrap()[0].content,
+            "buy now"
+        );
+
+        let conn = crate::state::open_db(&tmp.path().join("channels/quarantine.db")).unwrap();
+        let stored = || -> String {
+            conn.query_row("SELECT content FROM quarantine", [], |row| row.get(0))
+                .unwrap()
+        };
+        assert!(MemoryCipher::is_encrypted(&stored()));
+
+        let decrypt = |content: &str| cipher.decrypt(content);
+        assert_eq!(rewrite_channel_texts(tmp.path(), &decrypt).unwrap(), 1);
+        assert_eq!(stored(), "buy now");
+        assert_eq!(rewrite_channel_texts(tmp.path(), &decrypt).unwrap(), 0);
+    }
 }
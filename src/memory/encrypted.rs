@@ -0,0 +1,189 @@
+use super::encryption::MemoryCipher;
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use async_trait::async_trait;
+
+/// Memory backend wrapper that encrypts entry content at rest.
+///
+/// Content is sealed before it reaches the backend and opened on every read;
+/// keys, categories, and sessions are stored as-is. The backend cannot search
+/// ciphertext, so recall decrypts the candidate entries and ranks them by
+/// keyword overlap locally.
+pub struct EncryptedMemory {
+    inner: Box<dyn Memory>,
+    cipher: MemoryCipher,
+}
+
+impl EncryptedMemory {
+    pub fn new(inner: Box<dyn Memory>, cipher: MemoryCipher) -> Self {
+        Self { inner, cipher }
+    }
+
+    fn open(&self, mut entry: MemoryEntry) -> anyhow::Result<MemoryEntry> {
+        entry.content = self.cipher.decrypt(&entry.content)?;
+        Ok(entry)
+    }
+
+    /// Decrypt a batch, skipping entries that no longer open.
+    fn open_all(&self, entries: Vec<MemoryEntry>) -> Vec<MemoryEntry> {
+        entries
+            .into_iter()
+            .filter_map(|entry| {
+                let key = entry.key.clone();
+                self.open(entry)
+                    .inspect_err(|e| tracing::warn!(key, "Skipping memory entry: {e}"))
+                    .ok()
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Memory for EncryptedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let sealed = self.cipher.encrypt(content)?;
+        self.inner.store(key, &sealed, category, session_id).await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let query_lower = query.to_lowercase();
+        let keywords: Vec<&str> = query_lower.split_whitespace().collect();
+        if keywords.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entries = self.inner.list(None, session_id).await?;
+        let mut scored: Vec<MemoryEntry> = self
+            .open_all(entries)
+            .into_iter()
+            .filter_map(|mut entry| {
+                let haystack = format!("{} {}", entry.key, entry.content).to_lowercase();
+                let matched = keywords.iter().filter(|kw| haystack.contains(**kw)).count();
+                if matched == 0 {
+                    return None;
+                }
+                #[allow(clippy::cast_precision_loss)]
+                let score = matched as f64 / keywords.len() as f64;
+                entry.score = Some(score);
+                Some(entry)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        scored.truncate(limit);
+        Ok(scored)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        self.inner
+            .get(key)
+            .await?
+            .map(|entry| self.open(entry))
+            .transpose()
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self.inner.list(category, session_id).await?;
+        Ok(self.open_all(entries))
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.inner.forget(key).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        self.inner.count().await
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::encryption::KeySource;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn encrypted(tmp: &TempDir) -> EncryptedMemory {
+        let cipher =
+            MemoryCipher::open_with(tmp.path(), &KeySource::Passphrase("test passphrase".into()))
+                .unwrap();
+        EncryptedMemory::new(Box::new(SqliteMemory::new(tmp.path()).unwrap()), cipher)
+    }
+
+    #[tokio::test]
+    async fn content_is_sealed_in_the_backend_and_opened_on_read() {
+        let tmp = TempDir::new().unwrap();
+        let mem = encrypted(&tmp);
+        mem.store(
+            "diet",
+            "Prefers vegetarian food",
+            MemoryCategory::Core,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let raw = SqliteMemory::new(tmp.path()).unwrap();
+        let stored = raw.get("diet").await.unwrap().unwrap();
+        assert!(MemoryCipher::is_encrypted(&stored.content));
+        assert!(!stored.content.contains("vegetarian"));
+
+        let entry = mem.get("diet").await.unwrap().unwrap();
+        assert_eq!(entry.content, "Prefers vegetarian food");
+        let listed = mem.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        assert_eq!(listed[0].content, "Prefers vegetarian food");
+    }
+
+    #[tokio::test]
+    async fn recall_ranks_decrypted_content_and_reads_legacy_plaintext() {
+        let tmp = TempDir::new().unwrap();
+        SqliteMemory::new(tmp.path())
+            .unwrap()
+            .store(
+                "old",
+                "Rust is the favourite language",
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        let mem = encrypted(&tmp);
+        mem.store("new", "Learning Rust async", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("other", "Enjoys hiking", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let hits = mem.recall("rust language", 5, None).await.unwrap();
+        let keys: Vec<&str> = hits.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, ["old", "new"]);
+        assert!(mem.recall("   ", 5, None).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,725 @@
+//! Workspace key management for memory encryption at rest
+//! (`[memory.encryption]`).
+//!
+//! Memory content is sealed with ChaCha20-Poly1305 under a 256-bit workspace
+//! key and stored as `zcm1:<base64(nonce ‖ ciphertext ‖ tag)>`. Values
+//! without the prefix are plaintext written before encryption was enabled and
+//! are returned unchanged.
+//!
+//! The key comes from the OS keyring (a random key, one entry per workspace)
+//! or is derived with Argon2id from `ZEROCLAW_MEMORY_PASSPHRASE`. Its
+//! metadata lives in `<workspace>/memory/encryption.json`: the passphrase
+//! salt, a check value that proves a key is the right one, and during
+//! `zeroclaw rekey` the previous keys wrapped under the new one, so entries
+//! stay readable if a rekey is interrupted halfway.
+//!
+//! Once a workspace has a key, message text that channel stores keep outside
+//! memory (outbox and feedback previews, quarantined messages, review drafts,
+//! conversation titles) is sealed the same way through [`seal_text`].
+
+use crate::config::{MemoryEncryptionConfig, MemoryKeySource};
+use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Env var holding the passphrase for `key_source = "passphrase"`.
+pub const PASSPHRASE_ENV: &str = "ZEROCLAW_MEMORY_PASSPHRASE";
+
+/// Env var holding the new passphrase for a non-interactive `zeroclaw rekey`.
+pub const NEW_PASSPHRASE_ENV: &str = "ZEROCLAW_MEMORY_NEW_PASSPHRASE";
+
+/// Prefix marking encrypted content.
+const PREFIX: &str = "zcm1:";
+
+/// ChaCha20-Poly1305 nonce length in bytes.
+const NONCE_LEN: usize = 12;
+
+/// Plaintext sealed into the check value.
+const CHECK_PLAINTEXT: &str = "zeroclaw-memory-key-check";
+
+/// Keyring service name; the account is derived from the workspace path.
+const KEYRING_SERVICE: &str = "zeroclaw-memory";
+
+const STATE_FILE: &str = "encryption.json";
+
+type KeyBytes = [u8; 32];
+
+/// Storage for a keyring-held key.
+pub trait KeySlot: Send + Sync {
+    fn load(&self) -> Result<Option<Vec<u8>>>;
+    fn save(&self, key: &[u8]) -> Result<()>;
+}
+
+/// The OS keyring: `security` on macOS, `secret-tool` (libsecret) elsewhere
+/// on Unix.
+pub struct OsKeyring {
+    account: String,
+}
+
+impl OsKeyring {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        let path = workspace_dir
+            .canonicalize()
+            .unwrap_or_else(|_| workspace_dir.to_path_buf());
+        let digest = Sha256::digest(path.to_string_lossy().as_bytes());
+        Self {
+            account: format!("workspace-{}", &hex::encode(digest)[..16]),
+        }
+    }
+}
+
+impl KeySlot for OsKeyring {
+    #[cfg(target_os = "macos")]
+    fn load(&self) -> Result<Option<Vec<u8>>> {
+        let output = std::process::Command::new("security")
+            .args(["find-generic-password", "-s", KEYRING_SERVICE])
+            .args(["-a", &self.account, "-w"])
+            .output()
+            .context("Failed to run `security`")?;
+        // 44 = errSecItemNotFound
+        if output.status.code() == Some(44) {
+            return Ok(None);
+        }
+        if !output.status.success() {
+            anyhow::bail!(
+                "Keychain lookup failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        decode_stored_key(&output.stdout).map(Some)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn save(&self, key: &[u8]) -> Result<()> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        // The command goes through stdin so the key never shows up in `ps`.
+        let mut child = Command::new("security")
+            .arg("-i")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run `security`")?;
+        writeln!(
+            child.stdin.take().context("security stdin unavailable")?,
+            "add-generic-password -U -s {KEYRING_SERVICE} -a {} -w {}",
+            self.account,
+            hex::encode(key)
+        )?;
+        let output = child.wait_with_output()?;
+        // Interactive mode exits 0 even when the command fails.
+        if !output.status.success() || !output.stderr.is_empty() {
+            anyhow::bail!(
+                "Keychain update failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn load(&self) -> Result<Option<Vec<u8>>> {
+        let output = std::process::Command::new("secret-tool")
+            .args([
+                "lookup",
+                "service",
+                KEYRING_SERVICE,
+                "account",
+                &self.account,
+            ])
+            .output()
+            .context(
+                "Failed to run `secret-tool`; install libsecret tools or set \
+                 memory.encryption.key_source = \"passphrase\"",
+            )?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // A missing item exits 1 with no output.
+            if stderr.trim().is_empty() {
+                return Ok(None);
+            }
+            anyhow::bail!("Keyring lookup failed: {}", stderr.trim());
+        }
+        decode_stored_key(&output.stdout).map(Some)
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn save(&self, key: &[u8]) -> Result<()> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("secret-tool")
+            .args(["store", "--label", "ZeroClaw memory key"])
+            .args(["service", KEYRING_SERVICE, "account", &self.account])
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(
+                "Failed to run `secret-tool`; install libsecret tools or set \
+                 memory.encryption.key_source = \"passphrase\"",
+            )?;
+        child
+            .stdin
+            .take()
+            .context("secret-tool stdin unavailable")?
+            .write_all(hex::encode(key).as_bytes())?;
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Keyring update failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn load(&self) -> Result<Option<Vec<u8>>> {
+        anyhow::bail!(
+            "The OS keyring is not supported on this platform; set memory.encryption.key_source = \"passphrase\""
+        )
+    }
+
+    #[cfg(not(unix))]
+    fn save(&self, _key: &[u8]) -> Result<()> {
+        anyhow::bail!(
+            "The OS keyring is not supported on this platform; set memory.encryption.key_source = \"passphrase\""
+        )
+    }
+}
+
+#[cfg(unix)]
+fn decode_stored_key(stdout: &[u8]) -> Result<Vec<u8>> {
+    hex::decode(String::from_utf8_lossy(stdout).trim()).context("Keyring entry is not a hex key")
+}
+
+/// Where a workspace key comes from.
+pub enum KeySource {
+    Keyring(Box<dyn KeySlot>),
+    Passphrase(String),
+}
+
+impl KeySource {
+    /// Key source of `kind`, reading the passphrase from `passphrase_env`.
+    pub fn resolve(
+        kind: MemoryKeySource,
+        workspace_dir: &Path,
+        passphrase_env: &str,
+    ) -> Result<Self> {
+        match kind {
+            MemoryKeySource::Keyring => Ok(Self::Keyring(Box::new(OsKeyring::for_workspace(
+                workspace_dir,
+            )))),
+            MemoryKeySource::Passphrase => std::env::var(passphrase_env)
+                .ok()
+                .filter(|value| !value.is_empty())
+                .map(Self::Passphrase)
+                .with_context(|| {
+                    format!(
+                        "memory.encryption.key_source = \"passphrase\" requires {passphrase_env}"
+                    )
+                }),
+        }
+    }
+
+    pub fn kind(&self) -> MemoryKeySource {
+        match self {
+            Self::Keyring(_) => MemoryKeySource::Keyring,
+            Self::Passphrase(_) => MemoryKeySource::Passphrase,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeyState {
+    source: MemoryKeySource,
+    /// Hex Argon2 salt (passphrase keys only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    salt: Option<String>,
+    /// `CHECK_PLAINTEXT` sealed under the current key.
+    check: String,
+    /// Previous keys sealed under the current key, kept until a rekey has
+    /// re-encrypted every entry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    retired: Vec<String>,
+    /// The current key sealed under the key still in the keyring, while a
+    /// keyring rekey has not yet stored the new key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending: Option<String>,
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("memory").join(STATE_FILE)
+}
+
+fn read_state(workspace_dir: &Path) -> Result<Option<KeyState>> {
+    let path = state_path(workspace_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&raw)
+        .map(Some)
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn write_state(workspace_dir: &Path, state: &KeyState) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+    }
+    fs::rename(&tmp, &path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Key source recorded for the workspace, if its memory has a key.
+pub fn recorded_source(workspace_dir: &Path) -> Result<Option<MemoryKeySource>> {
+    Ok(read_state(workspace_dir)?.map(|state| state.source))
+}
+
+/// Forget the workspace key after `zeroclaw rekey --decrypt`. A keyring entry
+/// is left in place.
+pub fn remove_key_state(workspace_dir: &Path) -> Result<()> {
+    let path = state_path(workspace_dir);
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    opened_ciphers().lock().remove(workspace_dir);
+    Ok(())
+}
+
+/// Ciphers opened in this process, by workspace.
+fn opened_ciphers() -> &'static Mutex<HashMap<PathBuf, MemoryCipher>> {
+    static CIPHERS: OnceLock<Mutex<HashMap<PathBuf, MemoryCipher>>> = OnceLock::new();
+    CIPHERS.get_or_init(Mutex::default)
+}
+
+/// The workspace's cipher, or `None` when its memory has no key. The key is
+/// read from its recorded source the first time.
+fn workspace_cipher(workspace_dir: &Path) -> Result<Option<MemoryCipher>> {
+    if let Some(cipher) = opened_ciphers().lock().get(workspace_dir) {
+        return Ok(Some(cipher.clone()));
+    }
+    let Some(kind) = recorded_source(workspace_dir)? else {
+        return Ok(None);
+    };
+    let source = KeySource::resolve(kind, workspace_dir, PASSPHRASE_ENV)?;
+    MemoryCipher::open_with(workspace_dir, &source).map(Some)
+}
+
+/// Seal message text kept by a channel store when the workspace's memory is
+/// encrypted; otherwise return it unchanged.
+pub fn seal_text(workspace_dir: &Path, text: &str) -> Result<String> {
+    match workspace_cipher(workspace_dir)? {
+        Some(cipher) => cipher.encrypt(text),
+        None => Ok(text.to_string()),
+    }
+}
+
+/// Open text written by [`seal_text`]; plaintext passes through.
+pub fn open_text(workspace_dir: &Path, value: &str) -> Result<String> {
+    if !MemoryCipher::is_encrypted(value) {
+        return Ok(value.to_string());
+    }
+    workspace_cipher(workspace_dir)?
+        .context("Stored text is encrypted but the workspace has no memory key")?
+        .decrypt(value)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<KeyBytes> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive memory key: {e}"))?;
+    Ok(key)
+}
+
+fn to_key(bytes: &[u8]) -> Result<KeyBytes> {
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Memory key must be 32 bytes, got {}", bytes.len()))
+}
+
+fn seal(key: &KeyBytes, plaintext: &[u8]) -> Result<String> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| anyhow::anyhow!("Encryption failed: {e}"))?;
+    let mut blob = nonce.to_vec();
+    blob.extend_from_slice(&ciphertext);
+    Ok(format!("{PREFIX}{}", BASE64.encode(blob)))
+}
+
+fn open_sealed(key: &KeyBytes, value: &str) -> Option<Vec<u8>> {
+    let blob = BASE64.decode(value.strip_prefix(PREFIX)?).ok()?;
+    if blob.len() <= NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .ok()
+}
+
+fn verifies(key: &KeyBytes, check: &str) -> bool {
+    open_sealed(key, check).is_some_and(|plain| plain == CHECK_PLAINTEXT.as_bytes())
+}
+
+fn unwrap_key(key: &KeyBytes, wrapped: &str) -> Result<KeyBytes> {
+    let bytes = open_sealed(key, wrapped).context("Failed to unwrap a memory key")?;
+    to_key(&bytes)
+}
+
+/// Seals and opens memory content under the workspace key.
+#[derive(Clone)]
+pub struct MemoryCipher {
+    /// Current key first, then keys retired by an unfinished rekey.
+    keys: Vec<KeyBytes>,
+}
+
+impl std::fmt::Debug for MemoryCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryCipher")
+            .field("keys", &self.keys.len())
+            .finish()
+    }
+}
+
+impl MemoryCipher {
+    /// Load (or on first use create) the workspace key from `config`.
+    pub fn open(workspace_dir: &Path, config: &MemoryEncryptionConfig) -> Result<Self> {
+        let source = KeySource::resolve(config.key_source, workspace_dir, PASSPHRASE_ENV)?;
+        Self::open_with(workspace_dir, &source)
+    }
+
+    /// Load (or create) the workspace key from an explicit source.
+    pub fn open_with(workspace_dir: &Path, source: &KeySource) -> Result<Self> {
+        let cipher = Self::load(workspace_dir, source)?;
+        cipher.remember(workspace_dir);
+        Ok(cipher)
+    }
+
+    /// Use this cipher for [`seal_text`] in `workspace_dir`.
+    fn remember(&self, workspace_dir: &Path) {
+        opened_ciphers()
+            .lock()
+            .insert(workspace_dir.to_path_buf(), self.clone());
+    }
+
+    fn load(workspace_dir: &Path, source: &KeySource) -> Result<Self> {
+        let Some(mut state) = read_state(workspace_dir)? else {
+            return Self::create(workspace_dir, source);
+        };
+        if state.source != source.kind() {
+            anyhow::bail!(
+                "Memory is encrypted with key_source = \"{}\"; set it back or run `zeroclaw rekey` to switch",
+                state.source
+            );
+        }
+
+        let key = match source {
+            KeySource::Passphrase(passphrase) => {
+                let salt = hex::decode(state.salt.as_deref().unwrap_or_default())
+                    .context("Memory key state has an invalid salt")?;
+                let key = derive_key(passphrase, &salt)?;
+                if !verifies(&key, &state.check) {
+                    anyhow::bail!(
+                        "Wrong memory passphrase (after an interrupted `zeroclaw rekey`, use the new one)"
+                    );
+                }
+                key
+            }
+            KeySource::Keyring(slot) => {
+                let stored = to_key(
+                    &slot
+                        .load()?
+                        .context("Memory key not found in the OS keyring")?,
+                )?;
+                if verifies(&stored, &state.check) {
+                    if state.pending.take().is_some() {
+                        write_state(workspace_dir, &state)?;
+                    }
+                    stored
+                } else {
+                    // A rekey stopped before the new key reached the keyring.
+                    let key = state
+                        .pending
+                        .as_deref()
+                        .and_then(|pending| unwrap_key(&stored, pending).ok())
+                        .filter(|key| verifies(key, &state.check))
+                        .with_context(|| {
+                            format!(
+                                "The OS keyring holds a different memory key than {}",
+                                state_path(workspace_dir).display()
+                            )
+                        })?;
+                    slot.save(&key)?;
+                    state.pending = None;
+                    write_state(workspace_dir, &state)?;
+                    key
+                }
+            }
+        };
+
+        let mut keys = vec![key];
+        for wrapped in &state.retired {
+            keys.push(unwrap_key(&key, wrapped)?);
+        }
+        Ok(Self { keys })
+    }
+
+    fn create(workspace_dir: &Path, source: &KeySource) -> Result<Self> {
+        let (key, salt) = new_key(source)?;
+        if let KeySource::Keyring(slot) = source {
+            slot.save(&key)?;
+        }
+        write_state(
+            workspace_dir,
+            &KeyState {
+                source: source.kind(),
+                salt: salt.map(hex::encode),
+                check: seal(&key, CHECK_PLAINTEXT.as_bytes())?,
+                retired: Vec::new(),
+                pending: None,
+            },
+        )?;
+        tracing::info!(source = %source.kind(), "Created memory encryption key");
+        Ok(Self { keys: vec![key] })
+    }
+
+    /// Whether `value` is sealed content.
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(PREFIX)
+    }
+
+    /// Seal `plaintext` under the current key.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        seal(&self.keys[0], plaintext.as_bytes())
+    }
+
+    /// Open sealed content; plaintext passes through unchanged.
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        if !Self::is_encrypted(value) {
+            return Ok(value.to_string());
+        }
+        let plain = self
+            .keys
+            .iter()
+            .find_map(|key| open_sealed(key, value))
+            .context("Failed to decrypt memory content with the workspace key")?;
+        String::from_utf8(plain).context("Decrypted memory content is not valid UTF-8")
+    }
+
+    /// Whether a rekey left retired keys behind.
+    pub fn has_retired_keys(&self) -> bool {
+        self.keys.len() > 1
+    }
+}
+
+/// A fresh key (and salt, for passphrases) for `source`. Keyring keys are
+/// random.
+fn new_key(source: &KeySource) -> Result<(KeyBytes, Option<[u8; 16]>)> {
+    match source {
+        KeySource::Keyring(_) => Ok((rand::random(), None)),
+        KeySource::Passphrase(passphrase) => {
+            let salt: [u8; 16] = rand::random();
+            Ok((derive_key(passphrase, &salt)?, Some(salt)))
+        }
+    }
+}
+
+/// First half of a rekey: make a new key from `source` the current one and
+/// retire every key of `current`. The returned cipher seals under the new key
+/// and still opens old content; call [`finish_rotation`] once every entry
+/// has been re-encrypted.
+pub fn rotate(
+    workspace_dir: &Path,
+    current: &MemoryCipher,
+    source: &KeySource,
+) -> Result<MemoryCipher> {
+    let previous = read_state(workspace_dir)?.map(|state| state.source);
+    let (key, salt) = new_key(source)?;
+    let mut state = KeyState {
+        source: source.kind(),
+        salt: salt.map(hex::encode),
+        check: seal(&key, CHECK_PLAINTEXT.as_bytes())?,
+        retired: current
+            .keys
+            .iter()
+            .map(|old| seal(&key, old))
+            .collect::<Result<_>>()?,
+        pending: None,
+    };
+
+    if let KeySource::Keyring(slot) = source {
+        if previous == Some(MemoryKeySource::Keyring) {
+            // The keyring still holds the old key until `save` succeeds.
+            state.pending = Some(seal(&current.keys[0], &key)?);
+            write_state(workspace_dir, &state)?;
+            slot.save(&key)?;
+            state.pending = None;
+        } else {
+            slot.save(&key)?;
+        }
+    }
+    write_state(workspace_dir, &state)?;
+
+    let mut keys = vec![key];
+    keys.extend(current.keys.iter().copied());
+    let cipher = MemoryCipher { keys };
+    cipher.remember(workspace_dir);
+    Ok(cipher)
+}
+
+/// Second half of a rekey: drop the retired keys.
+pub fn finish_rotation(workspace_dir: &Path) -> Result<()> {
+    let mut state = read_state(workspace_dir)?.context("Memory has no encryption key")?;
+    state.retired.clear();
+    write_state(workspace_dir, &state)?;
+    if let Some(cipher) = opened_ciphers().lock().get_mut(workspace_dir) {
+        cipher.keys.truncate(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[derive(Clone, Default)]
+    struct MemorySlot(Arc<Mutex<Option<Vec<u8>>>>);
+
+    impl KeySlot for MemorySlot {
+        fn load(&self) -> Result<Option<Vec<u8>>> {
+            Ok(self.0.lock().clone())
+        }
+
+        fn save(&self, key: &[u8]) -> Result<()> {
+            *self.0.lock() = Some(key.to_vec());
+            Ok(())
+        }
+    }
+
+    fn passphrase(value: &str) -> KeySource {
+        KeySource::Passphrase(value.to_string())
+    }
+
+    #[test]
+    fn passphrase_key_roundtrips_and_rejects_wrong_passphrase() {
+        let tmp = TempDir::new().unwrap();
+        let cipher = MemoryCipher::open_with(tmp.path(), &passphrase("correct horse")).unwrap();
+        let sealed = cipher.encrypt("likes oolong tea").unwrap();
+        assert!(MemoryCipher::is_encrypted(&sealed));
+        assert!(!sealed.contains("oolong"));
+
+        let reopened = MemoryCipher::open_with(tmp.path(), &passphrase("correct horse")).unwrap();
+        assert_eq!(reopened.decrypt(&sealed).unwrap(), "likes oolong tea");
+        assert_eq!(reopened.decrypt("plain note").unwrap(), "plain note");
+
+        let err = MemoryCipher::open_with(tmp.path(), &passphrase("wrong")).unwrap_err();
+        assert!(err.to_string().contains("Wrong memory passphrase"), "{err}");
+    }
+
+    #[test]
+    fn keyring_rotation_keeps_old_content_readable_until_finished() {
+        let tmp = TempDir::new().unwrap();
+        let slot = MemorySlot::default();
+        let source = KeySource::Keyring(Box::new(slot.clone()));
+        let old = MemoryCipher::open_with(tmp.path(), &source).unwrap();
+        let sealed_old = old.encrypt("before").unwrap();
+
+        let rotated = rotate(tmp.path(), &old, &source).unwrap();
+        assert!(rotated.has_retired_keys());
+        let reopened = MemoryCipher::open_with(tmp.path(), &source).unwrap();
+        assert_eq!(reopened.decrypt(&sealed_old).unwrap(), "before");
+
+        finish_rotation(tmp.path()).unwrap();
+        let finished = MemoryCipher::open_with(tmp.path(), &source).unwrap();
+        assert!(!finished.has_retired_keys());
+        assert!(finished.decrypt(&sealed_old).is_err());
+        assert_eq!(
+            finished
+                .decrypt(&rotated.encrypt("after").unwrap())
+                .unwrap(),
+            "after"
+        );
+    }
+
+    #[test]
+    fn interrupted_keyring_rotation_recovers_the_new_key() {
+        let tmp = TempDir::new().unwrap();
+        let slot = MemorySlot::default();
+        let source = KeySource::Keyring(Box::new(slot.clone()));
+        let old = MemoryCipher::open_with(tmp.path(), &source).unwrap();
+        let old_key = slot.load().unwrap().unwrap();
+
+        let rotated = rotate(tmp.path(), &old, &source).unwrap();
+        let sealed_new = rotated.encrypt("after").unwrap();
+        // Simulate a crash after the state was written but before the
+        // keyring was updated.
+        let mut state = read_state(tmp.path()).unwrap().unwrap();
+        state.pending = Some(seal(&to_key(&old_key).unwrap(), &rotated.keys[0]).unwrap());
+        write_state(tmp.path(), &state).unwrap();
+        slot.save(&old_key).unwrap();
+
+        let recovered = MemoryCipher::open_with(tmp.path(), &source).unwrap();
+        assert_eq!(recovered.decrypt(&sealed_new).unwrap(), "after");
+        assert_ne!(slot.load().unwrap().unwrap(), old_key);
+        assert!(read_state(tmp.path()).unwrap().unwrap().pending.is_none());
+    }
+
+    #[test]
+    fn switching_key_source_requires_rekey() {
+        let tmp = TempDir::new().unwrap();
+        let old = MemoryCipher::open_with(tmp.path(), &passphrase("one")).unwrap();
+        let keyring = KeySource::Keyring(Box::new(MemorySlot::default()));
+        let err = MemoryCipher::open_with(tmp.path(), &keyring).unwrap_err();
+        assert!(err.to_string().contains("zeroclaw rekey"), "{err}");
+
+        rotate(tmp.path(), &old, &keyring).unwrap();
+        assert_eq!(
+            recorded_source(tmp.path()).unwrap(),
+            Some(MemoryKeySource::Keyring)
+        );
+        assert!(MemoryCipher::open_with(tmp.path(), &keyring).is_ok());
+    }
+
+    #[test]
+    fn channel_text_is_sealed_once_the_workspace_has_a_key() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(seal_text(tmp.path(), "hello").unwrap(), "hello");
+
+        let cipher = MemoryCipher::open_with(tmp.path(), &passphrase("pw")).unwrap();
+        let sealed = seal_text(tmp.path(), "hello").unwrap();
+        assert!(MemoryCipher::is_encrypted(&sealed));
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "hello");
+        assert_eq!(open_text(tmp.path(), &sealed).unwrap(), "hello");
+        assert_eq!(open_text(tmp.path(), "plain").unwrap(), "plain");
+
+        remove_key_state(tmp.path()).unwrap();
+        assert_eq!(seal_text(tmp.path(), "hello").unwrap(), "hello");
+        assert!(open_text(tmp.path(), &sealed).is_err());
+    }
+}
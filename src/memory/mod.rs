@@ -2,9 +2,12 @@ pub mod backend;
 pub mod chunker;
 pub mod cli;
 pub mod embeddings;
+pub mod encrypted;
+pub mod encryption;
 pub mod hygiene;
 pub mod lucid;
 pub mod markdown;
+pub mod namespaced;
 pub mod none;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
@@ -20,8 +23,10 @@ pub use backend::{
     classify_memory_backend, default_memory_backend_key, memory_backend_profile,
     selectable_memory_backends, MemoryBackendKind, MemoryBackendProfile,
 };
+pub use encrypted::EncryptedMemory;
 pub use lucid::LucidMemory;
 pub use markdown::MarkdownMemory;
+pub use namespaced::NamespacedMemory;
 pub use none::NoneMemory;
 #[cfg(feature = "memory-postgres")]
 pub use postgres::PostgresMemory;
@@ -195,6 +200,34 @@ pub fn create_memory_with_storage_and_routes(
     storage_provider: Option<&StorageProviderConfig>,
     workspace_dir: &Path,
     api_key: Option<&str>,
+) -> anyhow::Result<Box<dyn Memory>> {
+    if !config.encryption.enabled {
+        return create_backend_memory(
+            config,
+            embedding_routes,
+            storage_provider,
+            workspace_dir,
+            api_key,
+        );
+    }
+
+    // Embeddings are derived from the plaintext, so they are not stored.
+    let config = MemoryConfig {
+        embedding_provider: "none".into(),
+        embedding_model: String::new(),
+        ..config.clone()
+    };
+    let inner = create_backend_memory(&config, &[], storage_provider, workspace_dir, api_key)?;
+    let cipher = encryption::MemoryCipher::open(workspace_dir, &config.encryption)?;
+    Ok(Box::new(EncryptedMemory::new(inner, cipher)))
+}
+
+fn create_backend_memory(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    storage_provider: Option<&StorageProviderConfig>,
+    workspace_dir: &Path,
+    api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
     let backend_name = effective_memory_backend_name(&config.backend, storage_provider);
     let backend_kind = classify_memory_backend(&backend_name);
@@ -0,0 +1,144 @@
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// View of a shared memory backend restricted to one namespace.
+///
+/// Keys are stored as `<namespace>:<key>` and tagged with the namespace as
+/// their session, so agents sharing a backend cannot read or overwrite each
+/// other's memories. Keys are returned without the prefix.
+pub struct NamespacedMemory {
+    inner: Arc<dyn Memory>,
+    namespace: String,
+    prefix: String,
+}
+
+impl NamespacedMemory {
+    pub fn new(inner: Arc<dyn Memory>, namespace: &str) -> Self {
+        Self {
+            inner,
+            namespace: namespace.to_string(),
+            prefix: format!("{namespace}:"),
+        }
+    }
+
+    fn scoped_key(&self, key: &str) -> String {
+        format!("{}{key}", self.prefix)
+    }
+
+    /// Strip the prefix, or drop entries from other namespaces.
+    fn unscope(&self, mut entry: MemoryEntry) -> Option<MemoryEntry> {
+        entry.key = entry.key.strip_prefix(&self.prefix)?.to_string();
+        Some(entry)
+    }
+}
+
+#[async_trait]
+impl Memory for NamespacedMemory {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        self.inner
+            .store(
+                &self.scoped_key(key),
+                content,
+                category,
+                Some(&self.namespace),
+            )
+            .await
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self
+            .inner
+            .recall(query, limit, Some(&self.namespace))
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| self.unscope(entry))
+            .collect())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let entry = self.inner.get(&self.scoped_key(key)).await?;
+        Ok(entry.and_then(|entry| self.unscope(entry)))
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        _session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self.inner.list(category, Some(&self.namespace)).await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| self.unscope(entry))
+            .collect())
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        self.inner.forget(&self.scoped_key(key)).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        Ok(self.list(None, None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn namespaces_do_not_see_each_other() {
+        let tmp = TempDir::new().unwrap();
+        let shared: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let coder = NamespacedMemory::new(Arc::clone(&shared), "coder");
+        let family = NamespacedMemory::new(Arc::clone(&shared), "family");
+
+        coder
+            .store("editor", "prefers helix", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        family
+            .store("editor", "the fridge list", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            coder.get("editor").await.unwrap().unwrap().content,
+            "prefers helix"
+        );
+        let recalled = family.recall("helix", 5, None).await.unwrap();
+        assert!(recalled.is_empty());
+        let listed = family.list(None, None).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].key, "editor");
+        assert_eq!(coder.count().await.unwrap(), 1);
+
+        assert!(coder.forget("editor").await.unwrap());
+        assert_eq!(
+            family.get("editor").await.unwrap().unwrap().content,
+            "the fridge list"
+        );
+    }
+}
@@ -7,6 +7,8 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod openclaw;
+
 #[derive(Debug, Clone)]
 struct SourceEntry {
     key: String,
@@ -31,6 +33,14 @@ pub async fn handle_command(command: crate::MigrateCommands, config: &Config) ->
     }
 }
 
+pub async fn handle_import_command(command: crate::ImportCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ImportCommands::Openclaw { path, dry_run } => {
+            openclaw::import(config, path, dry_run).await
+        }
+    }
+}
+
 async fn migrate_openclaw_memory(
     config: &Config,
     source_workspace: Option<PathBuf>,
@@ -77,7 +87,27 @@ async fn migrate_openclaw_memory(
     }
 
     let memory = target_memory_backend(config)?;
+    import_entries(memory.as_ref(), entries, &mut stats).await?;
+
+    println!("✅ OpenClaw memory migration complete");
+    println!("  Source: {}", source_workspace.display());
+    println!("  Target: {}", config.workspace_dir.display());
+    println!("  Imported:         {}", stats.imported);
+    println!("  Skipped unchanged:{}", stats.skipped_unchanged);
+    println!("  Renamed conflicts:{}", stats.renamed_conflicts);
+    println!("  Source sqlite rows:{}", stats.from_sqlite);
+    println!("  Source markdown:   {}", stats.from_markdown);
 
+    Ok(())
+}
+
+/// Store entries, skipping ones already present with the same content and
+/// renaming keys that collide with different content.
+async fn import_entries(
+    memory: &dyn Memory,
+    entries: Vec<SourceEntry>,
+    stats: &mut MigrationStats,
+) -> Result<()> {
     for (idx, entry) in entries.into_iter().enumerate() {
         let mut key = entry.key.trim().to_string();
         if key.is_empty() {
@@ -90,7 +120,7 @@ async fn migrate_openclaw_memory(
                 continue;
             }
 
-            let renamed = next_available_key(memory.as_ref(), &key).await?;
+            let renamed = next_available_key(memory, &key).await?;
             key = renamed;
             stats.renamed_conflicts += 1;
         }
@@ -100,16 +130,6 @@ async fn migrate_openclaw_memory(
             .await?;
         stats.imported += 1;
     }
-
-    println!("✅ OpenClaw memory migration complete");
-    println!("  Source: {}", source_workspace.display());
-    println!("  Target: {}", config.workspace_dir.display());
-    println!("  Imported:         {}", stats.imported);
-    println!("  Skipped unchanged:{}", stats.skipped_unchanged);
-    println!("  Renamed conflicts:{}", stats.renamed_conflicts);
-    println!("  Source sqlite rows:{}", stats.from_sqlite);
-    println!("  Source markdown:   {}", stats.from_markdown);
-
     Ok(())
 }
 
@@ -0,0 +1,792 @@
+//! Full-state import from an `OpenClaw` install (`zeroclaw import openclaw`).
+//!
+//! Reads `openclaw.json`, the pairing allowlists under `credentials/`,
+//! skills, session transcripts, and workspace memory, and builds a [`Plan`]
+//! of what maps onto zeroclaw. The plan is also the dry-run report: anything
+//! under "won't carry over" is left for the operator to set up by hand.
+//! Existing zeroclaw channels and skills are never overwritten.
+
+use super::{
+    backup_target_memory, collect_source_entries, import_entries, paths_equal,
+    target_memory_backend, MigrationStats, SourceEntry,
+};
+use crate::config::{
+    ChannelsConfig, Config, DiscordConfig, IMessageConfig, MatrixConfig, SignalConfig, SlackConfig,
+    StreamMode, TelegramConfig,
+};
+use crate::memory::{Memory, MemoryCategory};
+use crate::skills;
+use anyhow::{bail, Context, Result};
+use directories::UserDirs;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILES: [&str; 2] = ["openclaw.json", "clawdbot.json"];
+
+/// Top-level `openclaw.json` keys that carry no settings worth reporting.
+const IGNORED_KEYS: [&str; 3] = ["$schema", "meta", "wizard"];
+
+const ONBOARD_HINT: &str = "set it up with `zeroclaw onboard --channels-only`";
+
+#[derive(Debug)]
+enum ChannelImport {
+    Telegram(TelegramConfig),
+    Discord(DiscordConfig),
+    Slack(SlackConfig),
+    Signal(SignalConfig),
+    IMessage(IMessageConfig),
+    Matrix(MatrixConfig),
+}
+
+impl ChannelImport {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Telegram(_) => "telegram",
+            Self::Discord(_) => "discord",
+            Self::Slack(_) => "slack",
+            Self::Signal(_) => "signal",
+            Self::IMessage(_) => "imessage",
+            Self::Matrix(_) => "matrix",
+        }
+    }
+
+    fn allowlist(&self) -> &[String] {
+        match self {
+            Self::Telegram(c) => &c.allowed_users,
+            Self::Discord(c) => &c.allowed_users,
+            Self::Slack(c) => &c.allowed_users,
+            Self::Signal(c) => &c.allowed_from,
+            Self::IMessage(c) => &c.allowed_contacts,
+            Self::Matrix(c) => &c.allowed_users,
+        }
+    }
+
+    fn is_configured(&self, channels: &ChannelsConfig) -> bool {
+        match self {
+            Self::Telegram(_) => channels.telegram.is_some(),
+            Self::Discord(_) => channels.discord.is_some(),
+            Self::Slack(_) => channels.slack.is_some(),
+            Self::Signal(_) => channels.signal.is_some(),
+            Self::IMessage(_) => channels.imessage.is_some(),
+            Self::Matrix(_) => channels.matrix.is_some(),
+        }
+    }
+
+    fn apply(self, channels: &mut ChannelsConfig) {
+        match self {
+            Self::Telegram(c) => channels.telegram = Some(c),
+            Self::Discord(c) => channels.discord = Some(c),
+            Self::Slack(c) => channels.slack = Some(c),
+            Self::Signal(c) => channels.signal = Some(c),
+            Self::IMessage(c) => channels.imessage = Some(c),
+            Self::Matrix(c) => channels.matrix = Some(c),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Session {
+    name: String,
+    /// `(role, text)` pairs in transcript order.
+    messages: Vec<(String, String)>,
+}
+
+#[derive(Debug, Default)]
+struct Plan {
+    channels: Vec<ChannelImport>,
+    skills: Vec<(String, PathBuf)>,
+    sessions: Vec<Session>,
+    memory: Vec<SourceEntry>,
+    memory_stats: MigrationStats,
+    /// `(item, reason)` for everything that won't carry over.
+    skipped: Vec<(String, String)>,
+}
+
+impl Plan {
+    fn skip(&mut self, item: impl Into<String>, reason: impl Into<String>) {
+        self.skipped.push((item.into(), reason.into()));
+    }
+
+    fn history_messages(&self) -> usize {
+        self.sessions.iter().map(|s| s.messages.len()).sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+            && self.skills.is_empty()
+            && self.sessions.is_empty()
+            && self.memory.is_empty()
+    }
+}
+
+pub(super) async fn import(config: &Config, path: Option<PathBuf>, dry_run: bool) -> Result<()> {
+    let root = match path {
+        Some(path) => path,
+        None => UserDirs::new()
+            .map(|u| u.home_dir().join(".openclaw"))
+            .context("Could not find home directory")?,
+    };
+    if !root.is_dir() {
+        bail!(
+            "OpenClaw install not found at {}. Pass --path <dir> if needed.",
+            root.display()
+        );
+    }
+    let workspace = if root.join("workspace").is_dir() {
+        root.join("workspace")
+    } else {
+        root.clone()
+    };
+    if paths_equal(&workspace, &config.workspace_dir) {
+        bail!("Source workspace matches current ZeroClaw workspace; refusing self-import");
+    }
+
+    let plan = build_plan(config, &root, &workspace)?;
+    print_plan(&plan, &root, config, dry_run);
+    if dry_run {
+        println!();
+        println!("Run without --dry-run to import.");
+        return Ok(());
+    }
+    if plan.is_empty() {
+        return Ok(());
+    }
+
+    apply_plan(config, plan).await?;
+    println!();
+    println!("✅ OpenClaw import complete");
+    Ok(())
+}
+
+fn build_plan(config: &Config, root: &Path, workspace: &Path) -> Result<Plan> {
+    let mut plan = Plan::default();
+    plan_config(&mut plan, config, root)?;
+    plan_skills(&mut plan, config, root, workspace);
+    plan.sessions = read_sessions(&root.join("agents"), &mut plan)?;
+    plan.memory = collect_source_entries(workspace, &mut plan.memory_stats)?;
+    Ok(plan)
+}
+
+fn plan_config(plan: &mut Plan, config: &Config, root: &Path) -> Result<()> {
+    let Some(config_path) = CONFIG_FILES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+    else {
+        plan.skip("config", "no openclaw.json found");
+        return Ok(());
+    };
+    let raw = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let parsed: Value = match serde_json::from_str(&strip_json5(&raw)) {
+        Ok(value) => value,
+        Err(e) => {
+            plan.skip(
+                "config",
+                format!(
+                    "could not parse {} ({e}); only comments and trailing commas are supported",
+                    config_path.display()
+                ),
+            );
+            return Ok(());
+        }
+    };
+    let Some(top) = parsed.as_object() else {
+        plan.skip("config", "openclaw.json is not an object");
+        return Ok(());
+    };
+
+    for key in top.keys() {
+        if key != "channels" && !IGNORED_KEYS.contains(&key.as_str()) {
+            plan.skip(
+                format!("config {key}"),
+                "no zeroclaw equivalent; configure by hand",
+            );
+        }
+    }
+
+    let Some(channels) = top.get("channels").and_then(Value::as_object) else {
+        return Ok(());
+    };
+    for (name, section) in channels {
+        let item = format!("channel {name}");
+        if section.get("enabled").and_then(Value::as_bool) == Some(false) {
+            plan.skip(item, "disabled in OpenClaw");
+            continue;
+        }
+        let allowlist = channel_allowlist(root, name, section);
+        match map_channel(name, section, allowlist) {
+            Ok(channel) if channel.is_configured(&config.channels_config) => {
+                plan.skip(item, "already configured in zeroclaw; left unchanged");
+            }
+            Ok(channel) => plan.channels.push(channel),
+            Err(reason) => plan.skip(item, reason),
+        }
+    }
+    Ok(())
+}
+
+/// `allowFrom` (or `dm.allowFrom`) merged with approved pairings from
+/// `credentials/<channel>-allowFrom.json`.
+fn channel_allowlist(root: &Path, channel: &str, section: &Value) -> Vec<String> {
+    let mut ids = id_list(section.get("allowFrom"));
+    if ids.is_empty() {
+        ids = id_list(section.get("dm").and_then(|dm| dm.get("allowFrom")));
+    }
+    let pairing_path = root
+        .join("credentials")
+        .join(format!("{channel}-allowFrom.json"));
+    if let Ok(raw) = fs::read_to_string(&pairing_path) {
+        if let Ok(pairing) = serde_json::from_str::<Value>(&raw) {
+            ids.extend(id_list(pairing.get("allowFrom")));
+        }
+    }
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    ids
+}
+
+fn map_channel(
+    name: &str,
+    section: &Value,
+    allowlist: Vec<String>,
+) -> Result<ChannelImport, String> {
+    let required = |key: &str| {
+        str_field(section, key).ok_or_else(|| {
+            format!("no `{key}` in openclaw.json (tokens from env or files are not read)")
+        })
+    };
+    let channel = match name {
+        "telegram" => ChannelImport::Telegram(TelegramConfig {
+            bot_token: required("botToken")?,
+            allowed_users: allowlist,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            link_previews: true,
+            allowed_topics: Vec::new(),
+        }),
+        "discord" => {
+            let guilds: Vec<&String> = section
+                .get("guilds")
+                .and_then(Value::as_object)
+                .map(|g| g.keys().collect())
+                .unwrap_or_default();
+            ChannelImport::Discord(DiscordConfig {
+                bot_token: required("token")?,
+                guild_id: match guilds.as_slice() {
+                    [only] => Some((*only).clone()),
+                    _ => None,
+                },
+                allowed_users: allowlist,
+                listen_to_bots: false,
+                mention_only: false,
+                link_previews: true,
+                voice: None,
+            })
+        }
+        "slack" => ChannelImport::Slack(SlackConfig {
+            bot_token: required("botToken")?,
+            app_token: str_field(section, "appToken"),
+            channel_id: None,
+            allowed_users: allowlist,
+            status_token: None,
+        }),
+        "signal" => ChannelImport::Signal(SignalConfig {
+            http_url: str_field(section, "httpUrl").ok_or_else(|| {
+                "no `httpUrl`; the signal-cli daemon OpenClaw manages is not carried over"
+                    .to_string()
+            })?,
+            account: required("account")?,
+            group_id: None,
+            allowed_from: allowlist,
+            ignore_attachments: false,
+            ignore_stories: false,
+        }),
+        "imessage" => ChannelImport::IMessage(IMessageConfig {
+            allowed_contacts: allowlist,
+        }),
+        "matrix" => {
+            let rooms: Vec<&String> = ["rooms", "groups"]
+                .iter()
+                .find_map(|key| section.get(*key).and_then(Value::as_object))
+                .map(|r| r.keys().collect())
+                .unwrap_or_default();
+            let [room] = rooms.as_slice() else {
+                return Err(format!(
+                    "zeroclaw listens in a single Matrix room but {} are configured; {ONBOARD_HINT}",
+                    rooms.len()
+                ));
+            };
+            ChannelImport::Matrix(MatrixConfig {
+                homeserver: required("homeserver")?,
+                access_token: required("accessToken")?,
+                user_id: str_field(section, "userId"),
+                device_id: None,
+                room_id: (*room).clone(),
+                allowed_users: allowlist,
+            })
+        }
+        "whatsapp" => {
+            return Err(format!(
+                "the WhatsApp Web session can't be transferred; {ONBOARD_HINT} and pair again ({} allowed numbers to re-add)",
+                allowlist.len()
+            ))
+        }
+        _ => return Err(format!("not supported by the importer; {ONBOARD_HINT}")),
+    };
+    Ok(channel)
+}
+
+fn plan_skills(plan: &mut Plan, config: &Config, root: &Path, workspace: &Path) {
+    let installed = skills::skills_dir(&config.workspace_dir);
+    // Workspace skills shadow managed ones of the same name, as in OpenClaw.
+    let mut found: BTreeMap<String, PathBuf> = BTreeMap::new();
+    for dir in [root.join("skills"), workspace.join("skills")] {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.join("SKILL.md").is_file() || path.join("SKILL.toml").is_file() {
+                found.insert(entry.file_name().to_string_lossy().into_owned(), path);
+            }
+        }
+    }
+
+    for (name, path) in found {
+        let item = format!("skill {name}");
+        if installed.join(&name).exists() {
+            plan.skip(item, "a skill with this name is already installed");
+            continue;
+        }
+        match skills::audit::audit_skill_directory(&path) {
+            Ok(report) if report.is_clean() => plan.skills.push((name, path)),
+            Ok(report) => plan.skip(item, format!("security audit failed: {}", report.summary())),
+            Err(e) => plan.skip(item, format!("security audit failed: {e}")),
+        }
+    }
+}
+
+/// Transcripts from `agents/<agent>/sessions/*.jsonl`.
+fn read_sessions(agents_dir: &Path, plan: &mut Plan) -> Result<Vec<Session>> {
+    let mut sessions = Vec::new();
+    let Ok(agents) = fs::read_dir(agents_dir) else {
+        return Ok(sessions);
+    };
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for agent in agents.flatten() {
+        let Ok(entries) = fs::read_dir(agent.path().join("sessions")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string();
+            files.push((
+                format!("{}_{stem}", agent.file_name().to_string_lossy()),
+                path,
+            ));
+        }
+    }
+    files.sort();
+
+    let mut unreadable_lines = 0_usize;
+    for (name, path) in files {
+        let raw = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut messages = Vec::new();
+        for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<Value>(line) {
+                Ok(value) => messages.extend(session_message(&value)),
+                Err(_) => unreadable_lines += 1,
+            }
+        }
+        if !messages.is_empty() {
+            sessions.push(Session { name, messages });
+        }
+    }
+    if unreadable_lines > 0 {
+        plan.skip(
+            "history",
+            format!("{unreadable_lines} transcript lines are not valid JSON"),
+        );
+    }
+    Ok(sessions)
+}
+
+/// The user or assistant text of one transcript line. Tool calls, tool
+/// results, and session metadata have no zeroclaw equivalent.
+fn session_message(line: &Value) -> Option<(String, String)> {
+    let message = line.get("message").unwrap_or(line);
+    let role = message.get("role")?.as_str()?;
+    if !matches!(role, "user" | "assistant") {
+        return None;
+    }
+    let text = match message.get("content")? {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|part| part.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| (role.to_string(), text.to_string()))
+}
+
+fn print_plan(plan: &Plan, root: &Path, config: &Config, dry_run: bool) {
+    if dry_run {
+        println!("🔎 Dry run: OpenClaw import plan");
+    } else {
+        println!("📦 OpenClaw import");
+    }
+    println!("  Source: {}", root.display());
+    println!("  Target: {}", config.workspace_dir.display());
+    println!();
+
+    println!("Will import:");
+    if plan.is_empty() {
+        println!("  (nothing)");
+    }
+    for channel in &plan.channels {
+        let allowed = channel.allowlist();
+        let note = if allowed.is_empty() {
+            " (empty allowlist: zeroclaw denies everyone until users are added)".to_string()
+        } else if allowed.iter().any(|id| id == "*") {
+            " (open to everyone)".to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  ✓ channel {}: {} allowed{note}",
+            channel.name(),
+            allowed.len()
+        );
+    }
+    for (name, _) in &plan.skills {
+        println!("  ✓ skill {name}");
+    }
+    if !plan.memory.is_empty() {
+        println!(
+            "  ✓ memory: {} entries ({} sqlite, {} markdown)",
+            plan.memory.len(),
+            plan.memory_stats.from_sqlite,
+            plan.memory_stats.from_markdown
+        );
+    }
+    if !plan.sessions.is_empty() {
+        println!(
+            "  ✓ history: {} messages from {} sessions, as conversation memory",
+            plan.history_messages(),
+            plan.sessions.len()
+        );
+    }
+
+    if !plan.skipped.is_empty() {
+        println!();
+        println!("Won't carry over:");
+        for (item, reason) in &plan.skipped {
+            println!("  ✗ {item}: {reason}");
+        }
+    }
+}
+
+async fn apply_plan(config: &Config, plan: Plan) -> Result<()> {
+    if !plan.channels.is_empty() {
+        let mut updated = config.clone();
+        for channel in plan.channels {
+            channel.apply(&mut updated.channels_config);
+        }
+        updated.save().await?;
+        println!("  Channels written to {}", config.config_path.display());
+    }
+
+    if !plan.skills.is_empty() {
+        let skills_path = skills::skills_dir(&config.workspace_dir);
+        fs::create_dir_all(&skills_path)?;
+        for (name, path) in &plan.skills {
+            if let Err(e) =
+                skills::install_local_skill_source(&path.to_string_lossy(), &skills_path)
+            {
+                println!("  ⚠️  Skill {name} not installed: {e}");
+            }
+        }
+    }
+
+    if plan.memory.is_empty() && plan.sessions.is_empty() {
+        return Ok(());
+    }
+    if let Some(backup_dir) = backup_target_memory(&config.workspace_dir)? {
+        println!("  🛟 Memory backup: {}", backup_dir.display());
+    }
+    let memory = target_memory_backend(config)?;
+    let mut stats = plan.memory_stats;
+    import_entries(memory.as_ref(), plan.memory, &mut stats).await?;
+    let history = import_history(memory.as_ref(), &plan.sessions).await?;
+    println!(
+        "  Memory: {} imported, {} unchanged, {} renamed; history: {history} messages",
+        stats.imported, stats.skipped_unchanged, stats.renamed_conflicts
+    );
+    Ok(())
+}
+
+/// Store transcript messages as conversation memory under stable keys, so
+/// re-running the import does not duplicate them.
+async fn import_history(memory: &dyn Memory, sessions: &[Session]) -> Result<usize> {
+    let mut stored = 0;
+    for session in sessions {
+        for (idx, (role, text)) in session.messages.iter().enumerate() {
+            let key = format!("openclaw_history_{}_{}", session.name, idx + 1);
+            if memory.get(&key).await?.is_some() {
+                continue;
+            }
+            memory
+                .store(
+                    &key,
+                    &format!("{role}: {text}"),
+                    MemoryCategory::Conversation,
+                    None,
+                )
+                .await?;
+            stored += 1;
+        }
+    }
+    Ok(stored)
+}
+
+fn str_field(section: &Value, key: &str) -> Option<String> {
+    section
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Ids given as strings or numbers.
+fn id_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|id| match id {
+            Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Drop `//` and `/* */` comments and trailing commas so JSON5-style
+/// configs parse as JSON. Unquoted keys and single-quoted strings are not
+/// handled.
+fn strip_json5(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => while chars.next_if(|&next| next != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            ('}' | ']', _) => {
+                let trimmed = out.trim_end().len();
+                if out[..trimmed].ends_with(',') {
+                    out.truncate(trimmed - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MemoryConfig;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn test_config(workspace: &Path) -> Config {
+        Config {
+            workspace_dir: workspace.join("workspace"),
+            config_path: workspace.join("config.toml"),
+            memory: MemoryConfig {
+                backend: "sqlite".to_string(),
+                ..MemoryConfig::default()
+            },
+            ..Config::default()
+        }
+    }
+
+    fn openclaw_install(root: &Path) {
+        fs::create_dir_all(root.join("credentials")).unwrap();
+        fs::write(
+            root.join("openclaw.json"),
+            r#"{
+  // JSON5 comments and trailing commas are common here
+  "agents": { "defaults": { "model": "anthropic/claude" } },
+  "channels": {
+    "telegram": { "botToken": "123:abc", "allowFrom": [42, "@alice",], },
+    "whatsapp": { "allowFrom": ["+15551234567"] },
+    "googlechat": { "enabled": true },
+  },
+}"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("credentials/telegram-allowFrom.json"),
+            r#"{"version":1,"allowFrom":["42","77"]}"#,
+        )
+        .unwrap();
+
+        let skill = root.join("workspace/skills/weather");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Weather\nLook up the forecast.\n").unwrap();
+
+        let sessions = root.join("agents/main/sessions");
+        fs::create_dir_all(&sessions).unwrap();
+        fs::write(
+            sessions.join("abc.jsonl"),
+            concat!(
+                r#"{"type":"session","id":"abc"}"#,
+                "\n",
+                r#"{"type":"message","message":{"role":"user","content":[{"type":"text","text":"hi there"}]}}"#,
+                "\n",
+                r#"{"type":"message","message":{"role":"toolResult","content":"ok"}}"#,
+                "\n",
+                r#"{"role":"assistant","content":"hello!"}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn strip_json5_removes_comments_and_trailing_commas_outside_strings() {
+        let raw = "{ /* c */ \"url\": \"http://x//y\", // trailing\n \"list\": [1, 2,], }";
+        let parsed: Value = serde_json::from_str(&strip_json5(raw)).unwrap();
+        assert_eq!(parsed["url"], "http://x//y");
+        assert_eq!(parsed["list"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn session_message_keeps_user_and_assistant_text() {
+        let nested = serde_json::json!({"message": {"role": "user", "content": [
+            {"type": "text", "text": "a"}, {"type": "image"}, {"type": "text", "text": "b"}
+        ]}});
+        assert_eq!(
+            session_message(&nested),
+            Some(("user".into(), "a\nb".into()))
+        );
+        let tool = serde_json::json!({"role": "toolResult", "content": "x"});
+        assert_eq!(session_message(&tool), None);
+    }
+
+    #[test]
+    fn plan_reports_what_carries_over() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("openclaw");
+        openclaw_install(&root);
+        let config = test_config(tmp.path());
+
+        let plan = build_plan(&config, &root, &root.join("workspace")).unwrap();
+        assert_eq!(plan.channels.len(), 1);
+        assert_eq!(plan.channels[0].allowlist(), ["42", "@alice", "77"]);
+        assert_eq!(plan.skills.len(), 1);
+        assert_eq!(plan.history_messages(), 2);
+        let skipped: Vec<&str> = plan.skipped.iter().map(|(item, _)| item.as_str()).collect();
+        assert_eq!(
+            skipped,
+            ["config agents", "channel googlechat", "channel whatsapp"]
+        );
+
+        let mut configured = test_config(tmp.path());
+        configured.channels_config.telegram = Some(TelegramConfig {
+            bot_token: "existing".into(),
+            allowed_users: vec![],
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            link_previews: true,
+            allowed_topics: Vec::new(),
+        });
+        let plan = build_plan(&configured, &root, &root.join("workspace")).unwrap();
+        assert!(plan.channels.is_empty());
+        assert!(plan
+            .skipped
+            .iter()
+            .any(|(item, _)| item == "channel telegram"));
+    }
+
+    #[tokio::test]
+    async fn import_writes_channels_skills_and_history() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path().join("openclaw");
+        openclaw_install(&root);
+        let config = test_config(tmp.path());
+        fs::create_dir_all(&config.workspace_dir).unwrap();
+
+        import(&config, Some(root.clone()), true).await.unwrap();
+        assert!(!config.config_path.exists());
+        assert!(!skills::skills_dir(&config.workspace_dir).exists());
+
+        import(&config, Some(root.clone()), false).await.unwrap();
+        let saved: Config =
+            toml::from_str(&fs::read_to_string(&config.config_path).unwrap()).unwrap();
+        let telegram = saved.channels_config.telegram.unwrap();
+        assert_eq!(telegram.allowed_users, ["42", "@alice", "77"]);
+        assert!(skills::skills_dir(&config.workspace_dir)
+            .join("weather/SKILL.md")
+            .is_file());
+
+        let memory = SqliteMemory::new(&config.workspace_dir).unwrap();
+        let entry = memory
+            .get("openclaw_history_main_abc_1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.content, "user: hi there");
+        assert_eq!(entry.category, MemoryCategory::Conversation);
+
+        // Re-running skips what already landed.
+        import(&config, Some(root), false).await.unwrap();
+        assert_eq!(memory.count().await.unwrap(), 2);
+    }
+}
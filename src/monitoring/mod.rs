@@ -0,0 +1,402 @@
+//! Homelab watchdog (`[monitoring]`).
+//!
+//! On the configured schedule the daemon checks disk usage, S.M.A.R.T.
+//! health (via `smartctl`), and the age of the newest backup in configured
+//! directories or restic repositories. When a check starts failing, or
+//! recovers, a message goes to the configured alert channel. Failing checks
+//! are remembered in memory so a broken disk does not alert on every run.
+
+use crate::config::{BackupCheckConfig, Config, DiskCheckConfig, MonitoringConfig};
+use crate::cron::{next_run_for_schedule, Schedule};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tokio::time::{self, Duration};
+
+const POLL_SECS: u64 = 60;
+const MONITORING_COMPONENT: &str = "monitoring";
+/// Files inspected per backup directory before giving up on finding a newer one.
+const MAX_BACKUP_ENTRIES: usize = 50_000;
+
+/// Outcome of one check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// Stable name used to track the check between runs, e.g. `disk /srv`.
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: String, ok: bool, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// A mounted filesystem: mount point, total bytes, available bytes.
+type Mount = (PathBuf, u64, u64);
+
+fn mounts() -> Vec<Mount> {
+    sysinfo::Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .filter(|disk| disk.total_space() > 0)
+        .map(|disk| {
+            (
+                disk.mount_point().to_path_buf(),
+                disk.total_space(),
+                disk.available_space(),
+            )
+        })
+        .collect()
+}
+
+fn check_disk(check: &DiskCheckConfig, mounts: &[Mount]) -> CheckResult {
+    let name = format!("disk {}", check.path);
+    let path = PathBuf::from(shellexpand::tilde(check.path.trim()).into_owned());
+    let path = path.canonicalize().unwrap_or(path);
+    let Some((mount, total, available)) = mounts
+        .iter()
+        .filter(|(mount, _, _)| path.starts_with(mount))
+        .max_by_key(|(mount, _, _)| mount.components().count())
+    else {
+        return CheckResult::new(name, false, "no mounted filesystem found for this path");
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let used_percent = total.saturating_sub(*available) as f64 / *total as f64 * 100.0;
+    let detail = format!(
+        "{used_percent:.1}% used on {} ({} GB free)",
+        mount.display(),
+        available / 1_000_000_000
+    );
+    CheckResult::new(name, used_percent < check.max_used_percent, detail)
+}
+
+/// Read the overall health verdict from `smartctl -H` output.
+fn parse_smart_health(stdout: &str) -> Option<bool> {
+    stdout.lines().find_map(|line| {
+        let line = line.trim();
+        // ATA: "...self-assessment test result: PASSED"; SCSI: "SMART Health Status: OK".
+        let (label, verdict) = line.rsplit_once(':')?;
+        if !label.contains("self-assessment test result") && !label.contains("SMART Health Status")
+        {
+            return None;
+        }
+        let verdict = verdict.trim();
+        Some(verdict == "PASSED" || verdict == "OK")
+    })
+}
+
+async fn check_smart(device: &str, timeout: Duration) -> CheckResult {
+    let name = format!("smart {device}");
+    let mut command = tokio::process::Command::new("smartctl");
+    command.arg("-H").arg(device).kill_on_drop(true);
+    // smartctl sets status bits for things other than the verdict, so only
+    // the parsed verdict counts.
+    match time::timeout(timeout, command.output()).await {
+        Err(_) => CheckResult::new(name, false, "smartctl timed out"),
+        Ok(Err(e)) => CheckResult::new(name, false, format!("could not run smartctl: {e}")),
+        Ok(Ok(output)) => match parse_smart_health(&String::from_utf8_lossy(&output.stdout)) {
+            Some(true) => CheckResult::new(name, true, "health check passed"),
+            Some(false) => CheckResult::new(name, false, "SMART health check FAILED"),
+            None => CheckResult::new(
+                name,
+                false,
+                format!("no SMART verdict from smartctl ({})", output.status),
+            ),
+        },
+    }
+}
+
+/// Modification time of the newest file under `root`.
+fn newest_file_time(root: &Path) -> Result<Option<DateTime<Utc>>> {
+    let mut newest: Option<SystemTime> = None;
+    let mut pending = vec![root.to_path_buf()];
+    let mut seen = 0usize;
+    while let Some(dir) = pending.pop() {
+        let entries =
+            std::fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries.flatten() {
+            seen += 1;
+            if seen > MAX_BACKUP_ENTRIES {
+                return Ok(newest.map(DateTime::<Utc>::from));
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(entry.path());
+            } else if let Ok(modified) = metadata.modified() {
+                newest = Some(newest.map_or(modified, |at| at.max(modified)));
+            }
+        }
+    }
+    Ok(newest.map(DateTime::<Utc>::from))
+}
+
+/// Time of the newest snapshot in `restic snapshots --json` output.
+fn parse_restic_latest(stdout: &str) -> Result<Option<DateTime<Utc>>> {
+    let snapshots: Vec<serde_json::Value> =
+        serde_json::from_str(stdout.trim()).context("restic did not return a snapshot list")?;
+    Ok(snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.get("time")?.as_str())
+        .filter_map(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.with_timezone(&Utc))
+        .max())
+}
+
+async fn restic_latest(
+    check: &BackupCheckConfig,
+    repo: &str,
+    timeout: Duration,
+) -> Result<Option<DateTime<Utc>>> {
+    let mut command = tokio::process::Command::new("restic");
+    command
+        .args(["snapshots", "--json", "--no-lock", "--latest", "1"])
+        .arg("--repo")
+        .arg(repo);
+    if let Some(password_file) = &check.password_file {
+        command
+            .arg("--password-file")
+            .arg(shellexpand::tilde(password_file.trim()).into_owned());
+    }
+    command.kill_on_drop(true);
+    let output = time::timeout(timeout, command.output())
+        .await
+        .context("restic timed out")?
+        .context("could not run restic")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "restic failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_restic_latest(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn backup_result(
+    check: &BackupCheckConfig,
+    latest: Result<Option<DateTime<Utc>>>,
+    now: DateTime<Utc>,
+) -> CheckResult {
+    let name = format!("backup {}", check.name);
+    match latest {
+        Err(e) => CheckResult::new(name, false, format!("{e:#}")),
+        Ok(None) => CheckResult::new(name, false, "no backup found"),
+        Ok(Some(latest)) => {
+            let age_hours = (now - latest).num_hours();
+            let detail = format!(
+                "last backup {age_hours}h ago ({})",
+                latest.format("%Y-%m-%d %H:%M UTC")
+            );
+            let ok = age_hours < i64::try_from(check.max_age_hours).unwrap_or(i64::MAX);
+            CheckResult::new(name, ok, detail)
+        }
+    }
+}
+
+async fn check_backup(check: &BackupCheckConfig, timeout: Duration) -> CheckResult {
+    let latest = match (&check.path, &check.restic_repo) {
+        (_, Some(repo)) => restic_latest(check, repo, timeout).await,
+        (Some(path), None) => newest_file_time(Path::new(shellexpand::tilde(path.trim()).as_ref())),
+        (None, None) => Err(anyhow::anyhow!("no path or restic_repo configured")),
+    };
+    backup_result(check, latest, Utc::now())
+}
+
+/// Run every configured check once.
+pub async fn run_checks(config: &MonitoringConfig) -> Vec<CheckResult> {
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let mut results = Vec::new();
+    if !config.disks.is_empty() {
+        let mounts = mounts();
+        results.extend(config.disks.iter().map(|check| check_disk(check, &mounts)));
+    }
+    for device in &config.smart_devices {
+        results.push(check_smart(device, timeout).await);
+    }
+    for check in &config.backups {
+        results.push(check_backup(check, timeout).await);
+    }
+    results
+}
+
+/// Alert text for checks that started failing or recovered since the last
+/// run, and the new set of failing checks.
+fn alert_message(
+    failing: &BTreeSet<String>,
+    results: &[CheckResult],
+) -> (Option<String>, BTreeSet<String>) {
+    let mut lines = Vec::new();
+    for result in results {
+        match (result.ok, failing.contains(&result.name)) {
+            (false, false) => lines.push(format!("⚠️ {}: {}", result.name, result.detail)),
+            (true, true) => lines.push(format!("✅ {} recovered: {}", result.name, result.detail)),
+            _ => {}
+        }
+    }
+    let now_failing = results
+        .iter()
+        .filter(|result| !result.ok)
+        .map(|result| result.name.clone())
+        .collect();
+    let message = (!lines.is_empty()).then(|| format!("Monitoring:\n{}", lines.join("\n")));
+    (message, now_failing)
+}
+
+/// Run the checks on `monitoring.schedule` until the task is cancelled.
+pub async fn run(config: Config) -> Result<()> {
+    let monitoring = &config.monitoring;
+    let schedule = Schedule::Cron {
+        expr: monitoring.schedule.clone(),
+        tz: monitoring.tz.clone(),
+    };
+    let (Some(channel), Some(to)) = (&monitoring.channel, &monitoring.to) else {
+        anyhow::bail!("monitoring.channel and monitoring.to must be set");
+    };
+    let mut interval = time::interval(Duration::from_secs(POLL_SECS));
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+    let mut failing = BTreeSet::new();
+    // Check once at startup, then on the schedule.
+    let mut next_run = Utc::now();
+
+    crate::health::mark_component_ok(MONITORING_COMPONENT);
+    loop {
+        interval.tick().await;
+        let now = Utc::now();
+        if now < next_run {
+            continue;
+        }
+        next_run = next_run_for_schedule(&schedule, now)?;
+
+        let results = run_checks(monitoring).await;
+        for result in results.iter().filter(|result| !result.ok) {
+            tracing::warn!(
+                "Monitoring check '{}' failing: {}",
+                result.name,
+                result.detail
+            );
+        }
+        let (message, now_failing) = alert_message(&failing, &results);
+        failing = now_failing;
+        crate::health::mark_component_ok(MONITORING_COMPONENT);
+        let Some(message) = message else {
+            continue;
+        };
+        if let Err(e) =
+            crate::cron::scheduler::deliver_announcement(&config, channel, to, &message).await
+        {
+            crate::health::mark_component_error(
+                MONITORING_COMPONENT,
+                format!("alert delivery failed: {e}"),
+            );
+            tracing::warn!("Monitoring alert delivery failed: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn backup(max_age_hours: u64) -> BackupCheckConfig {
+        BackupCheckConfig {
+            name: "nas".into(),
+            path: Some("/srv/backups".into()),
+            restic_repo: None,
+            password_file: None,
+            max_age_hours,
+        }
+    }
+
+    #[test]
+    fn disk_check_uses_the_deepest_mount() {
+        let mounts = vec![
+            (PathBuf::from("/"), 100, 50),
+            (PathBuf::from("/srv"), 1_000, 50),
+        ];
+        let check = DiskCheckConfig {
+            path: "/srv/media".into(),
+            max_used_percent: 90.0,
+        };
+        let result = check_disk(&check, &mounts);
+        assert!(!result.ok);
+        assert!(result.detail.starts_with("95.0% used on /srv"));
+
+        let check = DiskCheckConfig {
+            path: "/home".into(),
+            max_used_percent: 90.0,
+        };
+        assert!(check_disk(&check, &mounts).ok);
+    }
+
+    #[test]
+    fn smart_verdicts_are_parsed() {
+        let ata = "=== START OF READ SMART DATA SECTION ===\n\
+                   SMART overall-health self-assessment test result: PASSED\n";
+        assert_eq!(parse_smart_health(ata), Some(true));
+        let failed = "SMART overall-health self-assessment test result: FAILED!\n";
+        assert_eq!(parse_smart_health(failed), Some(false));
+        assert_eq!(parse_smart_health("SMART Health Status: OK\n"), Some(true));
+        assert_eq!(
+            parse_smart_health("Smartctl open device: /dev/sdz failed\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn backup_age_comes_from_files_or_restic_snapshots() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("daily")).unwrap();
+        std::fs::write(tmp.path().join("daily/db.tar"), "x").unwrap();
+        let newest = newest_file_time(tmp.path()).unwrap().unwrap();
+        assert!(Utc::now() - newest < chrono::Duration::minutes(5));
+        assert!(newest_file_time(&tmp.path().join("missing")).is_err());
+
+        let snapshots = r#"[{"time":"2026-03-01T02:00:00.123+01:00","id":"a"},
+                            {"time":"2026-03-02T02:00:00Z","id":"b"}]"#;
+        let latest = parse_restic_latest(snapshots).unwrap().unwrap();
+        assert_eq!(latest, Utc.with_ymd_and_hms(2026, 3, 2, 2, 0, 0).unwrap());
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 3, 6, 0, 0).unwrap();
+        assert!(backup_result(&backup(30), Ok(Some(latest)), now).ok);
+        let stale = backup_result(&backup(26), Ok(Some(latest)), now);
+        assert!(!stale.ok);
+        assert!(stale.detail.starts_with("last backup 28h ago"));
+        assert!(!backup_result(&backup(26), Ok(None), now).ok);
+    }
+
+    #[test]
+    fn alerts_only_on_new_failures_and_recoveries() {
+        let result = |name: &str, ok| CheckResult::new(name.into(), ok, "detail");
+        let (message, failing) = alert_message(
+            &BTreeSet::new(),
+            &[result("disk /", true), result("smart /dev/sda", false)],
+        );
+        assert_eq!(message.unwrap(), "Monitoring:\n⚠️ smart /dev/sda: detail");
+
+        let (message, failing) = alert_message(
+            &failing,
+            &[result("disk /", true), result("smart /dev/sda", false)],
+        );
+        assert!(message.is_none());
+
+        let (message, failing) = alert_message(
+            &failing,
+            &[result("disk /", true), result("smart /dev/sda", true)],
+        );
+        assert!(message.unwrap().contains("✅ smart /dev/sda recovered"));
+        assert!(failing.is_empty());
+    }
+}
@@ -38,6 +38,13 @@ impl Observer for LogObserver {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 info!(tool = %tool, duration_ms = ms, success = success, "tool.call");
             }
+            ObserverEvent::ToolError {
+                tool,
+                category,
+                retryable,
+            } => {
+                info!(tool = %tool, category = %category, retryable = retryable, "tool.error");
+            }
             ObserverEvent::TurnComplete => {
                 info!("turn.complete");
             }
@@ -70,6 +77,8 @@ impl Observer for LogObserver {
                 error_message,
                 input_tokens,
                 output_tokens,
+                reasoning_tokens,
+                cached_input_tokens,
             } => {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 info!(
@@ -80,6 +89,8 @@ impl Observer for LogObserver {
                     error = ?error_message,
                     input_tokens = ?input_tokens,
                     output_tokens = ?output_tokens,
+                    reasoning_tokens = ?reasoning_tokens,
+                    cached_input_tokens = ?cached_input_tokens,
                     "llm.response"
                 );
             }
@@ -152,6 +163,8 @@ mod tests {
             error_message: None,
             input_tokens: Some(100),
             output_tokens: Some(50),
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
         obs.record_event(&ObserverEvent::LlmResponse {
             provider: "openrouter".into(),
@@ -161,6 +174,8 @@ mod tests {
             error_message: Some("rate limited".into()),
             input_tokens: None,
             output_tokens: None,
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
         obs.record_event(&ObserverEvent::ToolCall {
             tool: "shell".into(),
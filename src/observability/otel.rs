@@ -20,6 +20,7 @@ pub struct OtelObserver {
     llm_duration: Histogram<f64>,
     tool_calls: Counter<u64>,
     tool_duration: Histogram<f64>,
+    tool_errors: Counter<u64>,
     channel_messages: Counter<u64>,
     heartbeat_ticks: Counter<u64>,
     errors: Counter<u64>,
@@ -116,6 +117,11 @@ impl OtelObserver {
             .with_unit("s")
             .build();
 
+        let tool_errors = meter
+            .u64_counter("zeroclaw.tool.errors")
+            .with_description("Failed tool calls by error category")
+            .build();
+
         let channel_messages = meter
             .u64_counter("zeroclaw.channel.messages")
             .with_description("Total channel messages")
@@ -161,6 +167,7 @@ impl OtelObserver {
             llm_duration,
             tool_calls,
             tool_duration,
+            tool_errors,
             channel_messages,
             heartbeat_ticks,
             errors,
@@ -197,6 +204,8 @@ impl Observer for OtelObserver {
                 error_message: _,
                 input_tokens: _,
                 output_tokens: _,
+                reasoning_tokens: _,
+                cached_input_tokens: _,
             } => {
                 let secs = duration.as_secs_f64();
                 let attrs = [
@@ -307,6 +316,20 @@ impl Observer for OtelObserver {
                 self.tool_duration
                     .record(secs, &[KeyValue::new("tool", tool.clone())]);
             }
+            ObserverEvent::ToolError {
+                tool,
+                category,
+                retryable,
+            } => {
+                self.tool_errors.add(
+                    1,
+                    &[
+                        KeyValue::new("tool", tool.clone()),
+                        KeyValue::new("category", category.clone()),
+                        KeyValue::new("retryable", retryable.to_string()),
+                    ],
+                );
+            }
             ObserverEvent::ChannelMessage { channel, direction } => {
                 self.channel_messages.add(
                     1,
@@ -417,6 +440,8 @@ mod tests {
             error_message: None,
             input_tokens: Some(100),
             output_tokens: Some(50),
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
         obs.record_event(&ObserverEvent::AgentEnd {
             provider: "openrouter".into(),
@@ -445,6 +470,11 @@ mod tests {
             duration: Duration::from_millis(5),
             success: false,
         });
+        obs.record_event(&ObserverEvent::ToolError {
+            tool: "file_read".into(),
+            category: "not_found".into(),
+            retryable: false,
+        });
         obs.record_event(&ObserverEvent::TurnComplete);
         obs.record_event(&ObserverEvent::ChannelMessage {
             channel: "telegram".into(),
@@ -497,6 +527,8 @@ mod tests {
             error_message: Some("404 Not Found".into()),
             input_tokens: None,
             output_tokens: None,
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
     }
 
@@ -12,10 +12,14 @@ pub struct PrometheusObserver {
     llm_requests: IntCounterVec,
     tokens_input_total: IntCounterVec,
     tokens_output_total: IntCounterVec,
+    tokens_reasoning_total: IntCounterVec,
+    tokens_cached_input_total: IntCounterVec,
     tool_calls: IntCounterVec,
+    tool_errors: IntCounterVec,
     channel_messages: IntCounterVec,
     heartbeat_ticks: prometheus::IntCounter,
     errors: IntCounterVec,
+    skill_cache_lookups: IntCounterVec,
 
     // Histograms
     agent_duration: HistogramVec,
@@ -26,6 +30,7 @@ pub struct PrometheusObserver {
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    skill_cache_entries: prometheus::IntGauge,
 }
 
 impl PrometheusObserver {
@@ -59,12 +64,39 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let tokens_reasoning_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_tokens_reasoning_total",
+                "Total reasoning/thinking tokens consumed (subset of output tokens)",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
+        let tokens_cached_input_total = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_tokens_cached_input_total",
+                "Total input tokens served from the provider's prompt cache (subset of input tokens)",
+            ),
+            &["provider", "model"],
+        )
+        .expect("valid metric");
+
         let tool_calls = IntCounterVec::new(
             prometheus::Opts::new("zeroclaw_tool_calls_total", "Total tool calls"),
             &["tool", "success"],
         )
         .expect("valid metric");
 
+        let tool_errors = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_tool_errors_total",
+                "Failed tool calls by error category",
+            ),
+            &["tool", "category", "retryable"],
+        )
+        .expect("valid metric");
+
         let channel_messages = IntCounterVec::new(
             prometheus::Opts::new("zeroclaw_channel_messages_total", "Total channel messages"),
             &["channel", "direction"],
@@ -128,6 +160,21 @@ impl PrometheusObserver {
         )
         .expect("valid metric");
 
+        let skill_cache_lookups = IntCounterVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_skill_cache_lookups_total",
+                "Skill loads served from the parsed-skill cache (hit) or re-read from disk (miss)",
+            ),
+            &["result"],
+        )
+        .expect("valid metric");
+
+        let skill_cache_entries = prometheus::IntGauge::new(
+            "zeroclaw_skill_cache_entries",
+            "Skills held in the parsed-skill cache",
+        )
+        .expect("valid metric");
+
         // Register all metrics
         registry.register(Box::new(agent_starts.clone())).ok();
         registry.register(Box::new(llm_requests.clone())).ok();
@@ -135,7 +182,14 @@ impl PrometheusObserver {
         registry
             .register(Box::new(tokens_output_total.clone()))
             .ok();
+        registry
+            .register(Box::new(tokens_reasoning_total.clone()))
+            .ok();
+        registry
+            .register(Box::new(tokens_cached_input_total.clone()))
+            .ok();
         registry.register(Box::new(tool_calls.clone())).ok();
+        registry.register(Box::new(tool_errors.clone())).ok();
         registry.register(Box::new(channel_messages.clone())).ok();
         registry.register(Box::new(heartbeat_ticks.clone())).ok();
         registry.register(Box::new(errors.clone())).ok();
@@ -145,6 +199,12 @@ impl PrometheusObserver {
         registry.register(Box::new(tokens_used.clone())).ok();
         registry.register(Box::new(active_sessions.clone())).ok();
         registry.register(Box::new(queue_depth.clone())).ok();
+        registry
+            .register(Box::new(skill_cache_lookups.clone()))
+            .ok();
+        registry
+            .register(Box::new(skill_cache_entries.clone()))
+            .ok();
 
         Self {
             registry,
@@ -152,21 +212,38 @@ impl PrometheusObserver {
             llm_requests,
             tokens_input_total,
             tokens_output_total,
+            tokens_reasoning_total,
+            tokens_cached_input_total,
             tool_calls,
+            tool_errors,
             channel_messages,
             heartbeat_ticks,
             errors,
+            skill_cache_lookups,
             agent_duration,
             tool_duration,
             request_latency,
             tokens_used,
             active_sessions,
             queue_depth,
+            skill_cache_entries,
         }
     }
 
+    /// Copy the skill cache's process-wide counters into the registry.
+    fn sync_skill_cache_stats(&self) {
+        let stats = crate::skills::cache::stats();
+        for (result, total) in [("hit", stats.hits), ("miss", stats.misses)] {
+            let counter = self.skill_cache_lookups.with_label_values(&[result]);
+            counter.inc_by(total.saturating_sub(counter.get()));
+        }
+        self.skill_cache_entries
+            .set(i64::try_from(stats.entries).unwrap_or(i64::MAX));
+    }
+
     /// Encode all registered metrics into Prometheus text exposition format.
     pub fn encode(&self) -> String {
+        self.sync_skill_cache_stats();
         let encoder = TextEncoder::new();
         let families = self.registry.gather();
         let mut buf = Vec::new();
@@ -204,6 +281,8 @@ impl Observer for PrometheusObserver {
                 success,
                 input_tokens,
                 output_tokens,
+                reasoning_tokens,
+                cached_input_tokens,
                 ..
             } => {
                 let success_str = if *success { "true" } else { "false" };
@@ -220,6 +299,16 @@ impl Observer for PrometheusObserver {
                         .with_label_values(&[provider.as_str(), model.as_str()])
                         .inc_by(*output);
                 }
+                if let Some(reasoning) = reasoning_tokens {
+                    self.tokens_reasoning_total
+                        .with_label_values(&[provider.as_str(), model.as_str()])
+                        .inc_by(*reasoning);
+                }
+                if let Some(cached) = cached_input_tokens {
+                    self.tokens_cached_input_total
+                        .with_label_values(&[provider.as_str(), model.as_str()])
+                        .inc_by(*cached);
+                }
             }
             ObserverEvent::ToolCallStart { tool: _ }
             | ObserverEvent::TurnComplete
@@ -237,6 +326,16 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[tool.as_str()])
                     .observe(duration.as_secs_f64());
             }
+            ObserverEvent::ToolError {
+                tool,
+                category,
+                retryable,
+            } => {
+                let retryable_str = if *retryable { "true" } else { "false" };
+                self.tool_errors
+                    .with_label_values(&[tool.as_str(), category.as_str(), retryable_str])
+                    .inc();
+            }
             ObserverEvent::ChannelMessage { channel, direction } => {
                 self.channel_messages
                     .with_label_values(&[channel, direction])
@@ -368,6 +467,15 @@ mod tests {
         assert!(output.contains("zeroclaw_request_latency_seconds"));
     }
 
+    #[test]
+    fn encode_exports_skill_cache_stats() {
+        let obs = PrometheusObserver::new();
+        let output = obs.encode();
+        assert!(output.contains("zeroclaw_skill_cache_lookups_total{result=\"hit\"}"));
+        assert!(output.contains("zeroclaw_skill_cache_lookups_total{result=\"miss\"}"));
+        assert!(output.contains("zeroclaw_skill_cache_entries"));
+    }
+
     #[test]
     fn counters_increment_correctly() {
         let obs = PrometheusObserver::new();
@@ -405,6 +513,26 @@ mod tests {
         assert!(output.contains(r#"zeroclaw_tool_calls_total{success="false",tool="shell"} 1"#));
     }
 
+    #[test]
+    fn tool_errors_track_by_category() {
+        let obs = PrometheusObserver::new();
+        for category in ["timeout", "timeout", "invalid_args"] {
+            obs.record_event(&ObserverEvent::ToolError {
+                tool: "http_request".into(),
+                category: category.into(),
+                retryable: category == "timeout",
+            });
+        }
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_tool_errors_total{category="timeout",retryable="true",tool="http_request"} 2"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_tool_errors_total{category="invalid_args",retryable="false",tool="http_request"} 1"#
+        ));
+    }
+
     #[test]
     fn errors_track_by_component() {
         let obs = PrometheusObserver::new();
@@ -448,6 +576,8 @@ mod tests {
             error_message: None,
             input_tokens: Some(100),
             output_tokens: Some(50),
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
         obs.record_event(&ObserverEvent::LlmResponse {
             provider: "openrouter".into(),
@@ -457,6 +587,8 @@ mod tests {
             error_message: None,
             input_tokens: Some(200),
             output_tokens: Some(80),
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
 
         let output = obs.encode();
@@ -483,6 +615,8 @@ mod tests {
             error_message: Some("timeout".into()),
             input_tokens: None,
             output_tokens: None,
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
 
         let output = obs.encode();
@@ -493,4 +627,54 @@ mod tests {
         assert!(!output.contains("zeroclaw_tokens_input_total{"));
         assert!(!output.contains("zeroclaw_tokens_output_total{"));
     }
+
+    #[test]
+    fn llm_response_tracks_reasoning_tokens_separately() {
+        let obs = PrometheusObserver::new();
+
+        obs.record_event(&ObserverEvent::LlmResponse {
+            provider: "openai".into(),
+            model: "o3".into(),
+            duration: Duration::from_millis(400),
+            success: true,
+            error_message: None,
+            input_tokens: Some(20),
+            output_tokens: Some(120),
+            reasoning_tokens: Some(96),
+            cached_input_tokens: None,
+        });
+
+        let output = obs.encode();
+        assert!(
+            output.contains(r#"zeroclaw_tokens_output_total{model="o3",provider="openai"} 120"#)
+        );
+        assert!(
+            output.contains(r#"zeroclaw_tokens_reasoning_total{model="o3",provider="openai"} 96"#)
+        );
+    }
+
+    #[test]
+    fn llm_response_tracks_cached_input_tokens() {
+        let obs = PrometheusObserver::new();
+
+        obs.record_event(&ObserverEvent::LlmResponse {
+            provider: "anthropic".into(),
+            model: "claude-sonnet".into(),
+            duration: Duration::from_millis(300),
+            success: true,
+            error_message: None,
+            input_tokens: Some(5000),
+            output_tokens: Some(60),
+            reasoning_tokens: None,
+            cached_input_tokens: Some(4800),
+        });
+
+        let output = obs.encode();
+        assert!(output.contains(
+            r#"zeroclaw_tokens_input_total{model="claude-sonnet",provider="anthropic"} 5000"#
+        ));
+        assert!(output.contains(
+            r#"zeroclaw_tokens_cached_input_total{model="claude-sonnet",provider="anthropic"} 4800"#
+        ));
+    }
 }
@@ -326,6 +326,8 @@ mod tests {
             runtime_trace_mode: "rolling".to_string(),
             runtime_trace_path: "state/runtime-trace.jsonl".to_string(),
             runtime_trace_max_entries: 3,
+            debug_capture_dir: None,
+            debug_capture_max_turns: 20,
         }
     }
 
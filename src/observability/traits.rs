@@ -28,6 +28,12 @@ pub enum ObserverEvent {
         error_message: Option<String>,
         input_tokens: Option<u64>,
         output_tokens: Option<u64>,
+        /// Reasoning/thinking tokens reported separately by the provider
+        /// (already included in `output_tokens`).
+        reasoning_tokens: Option<u64>,
+        /// Input tokens served from the provider's prompt cache (already
+        /// included in `input_tokens`).
+        cached_input_tokens: Option<u64>,
     },
     /// The agent session has finished.
     ///
@@ -47,6 +53,13 @@ pub enum ObserverEvent {
         duration: Duration,
         success: bool,
     },
+    /// A tool call failed; `category` is a [`ToolErrorKind`](crate::tools::error::ToolErrorKind)
+    /// name such as `"timeout"` or `"invalid_args"`.
+    ToolError {
+        tool: String,
+        category: String,
+        retryable: bool,
+    },
     /// The agent produced a final answer for the current user message.
     TurnComplete,
     /// A message was sent or received through a channel.
@@ -148,6 +148,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        watchers: Vec::new(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
@@ -158,17 +159,45 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
+        screenshot: crate::config::ScreenshotConfig::default(),
+        broadcast: crate::config::BroadcastConfig::default(),
+        send_later: crate::config::SendLaterConfig::default(),
+        tool_result_summary: crate::config::ToolResultSummaryConfig::default(),
         web_fetch: crate::config::WebFetchConfig::default(),
+        network_diag: crate::config::NetworkDiagConfig::default(),
+        weather: crate::config::WeatherConfig::default(),
+        translate: crate::config::TranslateConfig::default(),
+        finance: crate::config::FinanceConfig::default(),
+        news: crate::config::NewsConfig::default(),
+        checkins: crate::config::CheckinsConfig::default(),
+        goals: crate::config::GoalsConfig::default(),
+        device_actions: Vec::new(),
+        monitoring: crate::config::MonitoringConfig::default(),
+        uptime: crate::config::UptimeConfig::default(),
+        reports: crate::config::ReportsConfig::default(),
+        send_email: crate::config::SendEmailConfig::default(),
+        bluebubbles: crate::config::BlueBubblesConfig::default(),
+        contacts: crate::config::ContactsConfig::default(),
+        workspace_search: crate::config::WorkspaceSearchConfig::default(),
+        tools: crate::config::ToolsConfig::default(),
+        artifacts: crate::config::ArtifactsConfig::default(),
+        feedback: crate::config::FeedbackConfig::default(),
+        experiments: Vec::new(),
         web_search: crate::config::WebSearchConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
+        prompt: crate::config::PromptConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
+        remote_agents: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
+        models: crate::config::ModelsConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        tts: crate::config::TtsConfig::default(),
+        events: crate::config::EventsConfig::default(),
     };
 
     println!(
@@ -389,6 +418,7 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
         qdrant: crate::config::QdrantConfig::default(),
+        encryption: crate::config::MemoryEncryptionConfig::default(),
     }
 }
 
@@ -499,6 +529,7 @@ async fn run_quick_setup_with_home(
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        watchers: Vec::new(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
@@ -509,17 +540,45 @@ async fn run_quick_setup_with_home(
         browser: BrowserConfig::default(),
         http_request: crate::config::HttpRequestConfig::default(),
         multimodal: crate::config::MultimodalConfig::default(),
+        screenshot: crate::config::ScreenshotConfig::default(),
+        broadcast: crate::config::BroadcastConfig::default(),
+        send_later: crate::config::SendLaterConfig::default(),
+        tool_result_summary: crate::config::ToolResultSummaryConfig::default(),
         web_fetch: crate::config::WebFetchConfig::default(),
+        network_diag: crate::config::NetworkDiagConfig::default(),
+        weather: crate::config::WeatherConfig::default(),
+        translate: crate::config::TranslateConfig::default(),
+        finance: crate::config::FinanceConfig::default(),
+        news: crate::config::NewsConfig::default(),
+        checkins: crate::config::CheckinsConfig::default(),
+        goals: crate::config::GoalsConfig::default(),
+        device_actions: Vec::new(),
+        monitoring: crate::config::MonitoringConfig::default(),
+        uptime: crate::config::UptimeConfig::default(),
+        reports: crate::config::ReportsConfig::default(),
+        send_email: crate::config::SendEmailConfig::default(),
+        bluebubbles: crate::config::BlueBubblesConfig::default(),
+        contacts: crate::config::ContactsConfig::default(),
+        workspace_search: crate::config::WorkspaceSearchConfig::default(),
+        tools: crate::config::ToolsConfig::default(),
+        artifacts: crate::config::ArtifactsConfig::default(),
+        feedback: crate::config::FeedbackConfig::default(),
+        experiments: Vec::new(),
         web_search: crate::config::WebSearchConfig::default(),
         proxy: crate::config::ProxyConfig::default(),
         identity: crate::config::IdentityConfig::default(),
+        prompt: crate::config::PromptConfig::default(),
         cost: crate::config::CostConfig::default(),
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
+        remote_agents: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
+        models: crate::config::ModelsConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
+        tts: crate::config::TtsConfig::default(),
+        events: crate::config::EventsConfig::default(),
     };
 
     config.save().await?;
@@ -1592,21 +1651,11 @@ async fn load_model_cache_state(workspace_dir: &Path) -> Result<ModelCacheState>
 
 async fn save_model_cache_state(workspace_dir: &Path, state: &ModelCacheState) -> Result<()> {
     let path = model_cache_path(workspace_dir);
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent).await.with_context(|| {
-            format!(
-                "failed to create model cache directory {}",
-                parent.display()
-            )
-        })?;
-    }
-
     let json = serde_json::to_vec_pretty(state).context("failed to serialize model cache")?;
-    fs::write(&path, json)
+    tokio::task::spawn_blocking(move || crate::state::write_atomic(&path, json))
         .await
-        .with_context(|| format!("failed to write model cache at {}", path.display()))?;
-
-    Ok(())
+        .context("model cache writer panicked")?
+        .context("failed to write model cache")
 }
 
 async fn cache_live_models_for_provider(
@@ -3618,6 +3667,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     draft_update_interval_ms: 1000,
                     interrupt_on_new_message: false,
                     mention_only: false,
+                    link_previews: true,
+                    allowed_topics: Vec::new(),
                 });
             }
             ChannelMenuChoice::Discord => {
@@ -3717,6 +3768,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     allowed_users,
                     listen_to_bots: false,
                     mention_only: false,
+                    link_previews: true,
+                    voice: None,
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -3844,6 +3897,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    status_token: None,
                 });
             }
             ChannelMenuChoice::IMessage => {
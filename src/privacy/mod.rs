@@ -0,0 +1,555 @@
+//! Personal data export (`zeroclaw privacy export`).
+//!
+//! Collects everything the workspace stores about one person — preferences,
+//! reactions, messages sent to them, quarantined messages, spam strikes,
+//! check-in activity, experiment assignments, personal lists, runtime traces
+//! of their turns, and memories saved from their messages — into a single
+//! JSON document.
+//!
+//! A person is given as a sender id (`alice`, any channel), a
+//! channel-qualified id (`telegram:alice`), or the name of a contact, in
+//! which case every sender matching the contact's phone numbers or emails is
+//! included. Stores are opened read-only and missing stores are skipped.
+
+use crate::config::Config;
+use crate::contacts::{self, Contact};
+use crate::memory::encryption;
+use crate::memory::{
+    classify_memory_backend, effective_memory_backend_name, Memory, MemoryBackendKind,
+};
+use crate::observability::runtime_trace;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// How rows of a store are tied to a person.
+#[derive(Clone, Copy)]
+enum Owner {
+    /// `channel` and sender id columns.
+    Sender(&'static str, &'static str),
+    /// A conversation key column (`<channel>_[<thread>_]<sender>`).
+    Conversation(&'static str),
+    /// A list owner column (`user:<channel>:<sender>`).
+    List(&'static str),
+}
+
+struct Store {
+    section: &'static str,
+    db: &'static str,
+    query: &'static str,
+    owner: Owner,
+}
+
+/// Stores in export order. Sender-keyed stores come first so that senders
+/// found through a contact are known when conversation keys are matched.
+const STORES: &[Store] = &[
+    Store {
+        section: "preferences",
+        db: "channels/preferences.db",
+        query: "SELECT channel, sender, key, value, updated_at FROM preferences",
+        owner: Owner::Sender("channel", "sender"),
+    },
+    Store {
+        section: "feedback",
+        db: "channels/feedback.db",
+        query: "SELECT channel, sender, outbox_id, reply_preview, emoji, sentiment, created_at
+                FROM feedback ORDER BY created_at",
+        owner: Owner::Sender("channel", "sender"),
+    },
+    Store {
+        section: "conversations",
+        db: "channels/conversations.db",
+        query: "SELECT id, channel, sender, title, turns, created_at, updated_at
+                FROM conversations ORDER BY created_at",
+        owner: Owner::Sender("channel", "sender"),
+    },
+    Store {
+        section: "review_drafts",
+        db: "channels/review.db",
+        query: "SELECT id, channel, recipient, sender, original, content, status, reviewer,
+                       note, outbox_id, created_at, decided_at
+                FROM drafts ORDER BY created_at",
+        owner: Owner::Sender("channel", "sender"),
+    },
+    Store {
+        section: "quarantine",
+        db: "channels/quarantine.db",
+        query: "SELECT id, channel, sender, content, score, reasons, created_at
+                FROM quarantine ORDER BY created_at",
+        owner: Owner::Sender("channel", "sender"),
+    },
+    Store {
+        section: "spam_strikes",
+        db: "channels/quarantine.db",
+        query: "SELECT channel, sender, count, updated_at FROM strikes",
+        owner: Owner::Sender("channel", "sender"),
+    },
+    Store {
+        section: "checkins",
+        db: "cron/checkins.db",
+        query: "SELECT channel, recipient, first_seen, last_activity, last_checkin FROM checkins",
+        owner: Owner::Sender("channel", "recipient"),
+    },
+    Store {
+        section: "experiment_assignments",
+        db: "experiments/experiments.db",
+        query: "SELECT experiment, conversation, variant, assigned_at FROM assignments",
+        owner: Owner::Conversation("conversation"),
+    },
+    Store {
+        section: "experiment_turns",
+        db: "experiments/experiments.db",
+        query: "SELECT experiment, variant, conversation, channel, outbox_id, latency_ms,
+                       reply_chars, success, created_at
+                FROM turns ORDER BY created_at",
+        owner: Owner::Conversation("conversation"),
+    },
+    Store {
+        section: "lists",
+        db: "lists/lists.db",
+        query: "SELECT l.owner, l.name, l.created_at, i.text, i.checked, i.added_at
+                FROM lists l LEFT JOIN list_items i ON i.list_id = l.id
+                ORDER BY l.id, i.id",
+        owner: Owner::List("owner"),
+    },
+];
+
+/// The person being exported.
+pub struct Identity {
+    label: String,
+    /// `(channel, sender)`; no channel matches the sender on every channel.
+    senders: BTreeSet<(Option<String>, String)>,
+    contact: Option<Contact>,
+}
+
+impl Identity {
+    /// Parse `telegram:alice` or `alice`. Ids such as Matrix's
+    /// `@alice:example.org` are taken as a whole.
+    pub fn parse(input: &str) -> Self {
+        let input = input.trim();
+        let sender = match input.split_once(':') {
+            Some((channel, sender))
+                if !channel.is_empty()
+                    && !sender.is_empty()
+                    && channel
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') =>
+            {
+                (Some(channel.to_string()), sender.to_string())
+            }
+            _ => (None, input.to_string()),
+        };
+        Self {
+            label: input.to_string(),
+            senders: BTreeSet::from([sender]),
+            contact: None,
+        }
+    }
+
+    /// Also match every sender id that belongs to `contact`.
+    pub fn with_contact(mut self, contact: Contact) -> Self {
+        self.contact = Some(contact);
+        self
+    }
+
+    fn matches_sender(&self, channel: &str, sender: &str) -> bool {
+        self.senders
+            .iter()
+            .any(|(ch, id)| id == sender && ch.as_deref().is_none_or(|ch| ch == channel))
+            || self.contact.as_ref().is_some_and(|contact| {
+                contacts::match_sender(std::slice::from_ref(contact), sender).is_some()
+            })
+    }
+
+    fn matches_conversation(&self, key: &str) -> bool {
+        self.senders.iter().any(|(channel, sender)| match channel {
+            Some(channel) => {
+                key.starts_with(&format!("{channel}_")) && key.ends_with(&format!("_{sender}"))
+            }
+            None => key.ends_with(&format!("_{sender}")),
+        })
+    }
+
+    /// Channel autosave keys are `<channel>_[<thread>_]<sender>_<message id>`,
+    /// optionally behind an agent namespace (`<agent>:`).
+    fn matches_memory_key(&self, key: &str) -> bool {
+        self.senders.iter().any(|(channel, sender)| {
+            key.contains(&format!("_{sender}_"))
+                && channel.as_deref().is_none_or(|channel| {
+                    key.starts_with(&format!("{channel}_")) || key.contains(&format!(":{channel}_"))
+                })
+        })
+    }
+
+    fn matches_list_owner(&self, owner: &str) -> bool {
+        owner
+            .strip_prefix("user:")
+            .and_then(|rest| rest.split_once(':'))
+            .is_some_and(|(channel, sender)| self.matches_sender(channel, sender))
+    }
+}
+
+fn row_to_json(
+    row: &rusqlite::Row<'_>,
+    columns: &[String],
+) -> rusqlite::Result<Map<String, Value>> {
+    let mut object = Map::new();
+    for (i, column) in columns.iter().enumerate() {
+        let value = match row.get_ref(i)? {
+            ValueRef::Null => Value::Null,
+            ValueRef::Integer(n) => n.into(),
+            ValueRef::Real(f) => f.into(),
+            ValueRef::Text(text) => String::from_utf8_lossy(text).into_owned().into(),
+            ValueRef::Blob(blob) => hex::encode(blob).into(),
+        };
+        object.insert(column.clone(), value);
+    }
+    Ok(object)
+}
+
+fn read_store(workspace_dir: &Path, store: &Store) -> Result<Vec<Map<String, Value>>> {
+    let path = workspace_dir.join(store.db);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let conn = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut stmt = conn
+        .prepare(store.query)
+        .with_context(|| format!("Failed to read {} from {}", store.section, path.display()))?;
+    let columns: Vec<String> = stmt
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let rows = stmt.query_map([], |row| row_to_json(row, &columns))?;
+    let mut rows: Vec<Map<String, Value>> = rows.collect::<rusqlite::Result<_>>()?;
+    // Message text is sealed when memory encryption is on.
+    for value in rows.iter_mut().flat_map(|row| row.values_mut()) {
+        if let Value::String(text) = value {
+            *text = encryption::open_text(workspace_dir, text)?;
+        }
+    }
+    Ok(rows)
+}
+
+fn text<'a>(row: &'a Map<String, Value>, column: &str) -> &'a str {
+    row.get(column).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Collect everything stored about `identity`. `memory` is searched for
+/// memories saved from their messages and `trace_path` for runtime trace
+/// events of their turns, when given.
+pub async fn collect(
+    workspace_dir: &Path,
+    identity: &mut Identity,
+    memory: Option<&dyn Memory>,
+    trace_path: Option<&Path>,
+) -> Result<Map<String, Value>> {
+    let mut sections = Map::new();
+    let mut outbox_ids = BTreeSet::new();
+
+    for store in STORES {
+        let mut matched = Vec::new();
+        for row in read_store(workspace_dir, store)? {
+            let keep = match store.owner {
+                Owner::Sender(channel, sender) => {
+                    let (channel, sender) = (text(&row, channel), text(&row, sender));
+                    let keep = identity.matches_sender(channel, sender);
+                    if keep && identity.contact.is_some() {
+                        identity
+                            .senders
+                            .insert((Some(channel.to_string()), sender.to_string()));
+                    }
+                    keep
+                }
+                Owner::Conversation(column) => identity.matches_conversation(text(&row, column)),
+                Owner::List(column) => identity.matches_list_owner(text(&row, column)),
+            };
+            if keep {
+                matched.push(Value::Object(row));
+            }
+        }
+        if store.section == "feedback" {
+            outbox_ids.extend(
+                matched
+                    .iter()
+                    .filter_map(|row| row.get("outbox_id").and_then(Value::as_str))
+                    .map(str::to_string),
+            );
+        }
+        sections.insert(store.section.to_string(), Value::Array(matched));
+    }
+
+    // Replies sent to the person, or that they reacted to.
+    let outbox = Store {
+        section: "outbox",
+        db: "channels/outbox.db",
+        query: "SELECT id, channel, recipient, platform_message_id, preview, status, created_at
+                FROM outbox ORDER BY created_at",
+        owner: Owner::Sender("channel", "recipient"),
+    };
+    let sent: Vec<Value> = read_store(workspace_dir, &outbox)?
+        .into_iter()
+        .filter(|row| {
+            identity.matches_sender(text(row, "channel"), text(row, "recipient"))
+                || outbox_ids.contains(text(row, "id"))
+        })
+        .map(Value::Object)
+        .collect();
+    sections.insert("outbox".into(), Value::Array(sent));
+
+    let mut traces = Vec::new();
+    if let Some(path) = trace_path {
+        for event in runtime_trace::load_events(path, usize::MAX, None, None)?
+            .into_iter()
+            .rev()
+        {
+            let sender = event.payload.get("sender").and_then(Value::as_str);
+            if let (Some(channel), Some(sender)) = (event.channel.as_deref(), sender) {
+                if identity.matches_sender(channel, sender) {
+                    traces.push(serde_json::to_value(&event)?);
+                }
+            }
+        }
+    }
+    sections.insert("runtime_traces".into(), Value::Array(traces));
+
+    let mut memories = Vec::new();
+    if let Some(memory) = memory {
+        for entry in memory.list(None, None).await? {
+            if identity.matches_memory_key(&entry.key) {
+                memories.push(json!({
+                    "key": entry.key,
+                    "category": entry.category.to_string(),
+                    "content": entry.content,
+                    "session_id": entry.session_id,
+                    "timestamp": entry.timestamp,
+                }));
+            }
+        }
+    }
+    sections.insert("memories".into(), Value::Array(memories));
+
+    Ok(sections)
+}
+
+fn export_document(identity: &Identity, sections: Map<String, Value>) -> Value {
+    let senders: Vec<Value> = identity
+        .senders
+        .iter()
+        .map(|(channel, sender)| json!({ "channel": channel, "sender": sender }))
+        .collect();
+    let contact = identity.contact.as_ref().map(|contact| {
+        json!({
+            "name": contact.name,
+            "phones": contact.phones.iter().map(|phone| &phone.value).collect::<Vec<_>>(),
+            "emails": contact.emails.iter().map(|email| &email.value).collect::<Vec<_>>(),
+        })
+    });
+    json!({
+        "format": "zeroclaw-privacy-export/1",
+        "identity": identity.label,
+        "exported_at": Utc::now().to_rfc3339(),
+        "senders": senders,
+        "contact": contact,
+        "data": sections,
+    })
+}
+
+fn default_output(label: &str) -> PathBuf {
+    let slug: String = label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    PathBuf::from(format!(
+        "zeroclaw-export-{}-{}.json",
+        slug.trim_matches('-'),
+        Utc::now().format("%Y%m%d")
+    ))
+}
+
+/// Handle `zeroclaw privacy <subcommand>`.
+pub async fn handle_command(command: crate::PrivacyCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::PrivacyCommands::Export { identity, output } => {
+            let mut person = Identity::parse(&identity);
+            if config.contacts.enabled {
+                let book = contacts::load(&config.workspace_dir, &config.contacts)?;
+                if let Some(contact) = book
+                    .into_iter()
+                    .find(|contact| contact.name.eq_ignore_ascii_case(identity.trim()))
+                {
+                    person = person.with_contact(contact);
+                }
+            }
+
+            let backend = effective_memory_backend_name(
+                &config.memory.backend,
+                Some(&config.storage.provider.config),
+            );
+            let memory = if matches!(classify_memory_backend(&backend), MemoryBackendKind::None) {
+                None
+            } else {
+                Some(crate::memory::cli::create_cli_memory(config)?)
+            };
+
+            let sections = collect(
+                &config.workspace_dir,
+                &mut person,
+                memory.as_deref(),
+                Some(&runtime_trace::resolve_trace_path(
+                    &config.observability,
+                    &config.workspace_dir,
+                )),
+            )
+            .await?;
+            let counts: Vec<String> = sections
+                .iter()
+                .filter_map(|(name, rows)| {
+                    let n = rows.as_array().map_or(0, Vec::len);
+                    (n > 0).then(|| format!("{name}: {n}"))
+                })
+                .collect();
+            let document = serde_json::to_string_pretty(&export_document(&person, sections))?;
+
+            let path = output.unwrap_or_else(|| default_output(&person.label));
+            if path.as_os_str() == "-" {
+                println!("{document}");
+                return Ok(());
+            }
+            std::fs::write(&path, document)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+            }
+
+            if counts.is_empty() {
+                println!("Nothing is stored about '{}'.", person.label);
+            } else {
+                println!("Exported {} ({}).", person.label, counts.join(", "));
+            }
+            println!("Written to {}", path.display());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::{preferences, quarantine};
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[test]
+    fn identity_parses_channel_qualified_and_bare_ids() {
+        let qualified = Identity::parse("telegram:alice");
+        assert!(qualified.matches_sender("telegram", "alice"));
+        assert!(!qualified.matches_sender("discord", "alice"));
+        assert!(qualified.matches_conversation("telegram_alice"));
+        assert!(qualified.matches_conversation("telegram_42_alice"));
+        assert!(!qualified.matches_conversation("telegram_malice_bob"));
+        assert!(qualified.matches_memory_key("telegram_alice_msg1"));
+        assert!(qualified.matches_memory_key("research:telegram_alice_msg1"));
+        assert!(!qualified.matches_memory_key("discord_alice_msg1"));
+
+        let matrix = Identity::parse("@alice:example.org");
+        assert!(matrix.matches_sender("matrix", "@alice:example.org"));
+        assert!(matrix.matches_list_owner("user:matrix:@alice:example.org"));
+    }
+
+    #[test]
+    fn contact_identity_matches_senders_by_phone() {
+        let contact = Contact {
+            name: "Alice Example".into(),
+            phones: vec![contacts::Labeled {
+                label: "cell".into(),
+                value: "+49 170 1234567".into(),
+            }],
+            ..Contact::default()
+        };
+        let identity = Identity::parse("Alice Example").with_contact(contact);
+        assert!(identity.matches_sender("whatsapp", "+491701234567"));
+        assert!(!identity.matches_sender("whatsapp", "+491709999999"));
+    }
+
+    #[tokio::test]
+    async fn collect_gathers_rows_and_memories_for_one_sender() {
+        let tmp = TempDir::new().unwrap();
+        let ws = tmp.path();
+        preferences::set(ws, "telegram", "alice", "units", "metric").unwrap();
+        preferences::set(ws, "telegram", "bob", "units", "imperial").unwrap();
+        quarantine::quarantine(ws, "telegram", "alice", "buy now", 0.9, &["links".into()]).unwrap();
+
+        let memory = SqliteMemory::new(ws).unwrap();
+        memory
+            .store(
+                "telegram_alice_m1",
+                "I moved to Berlin",
+                MemoryCategory::Conversation,
+                None,
+            )
+            .await
+            .unwrap();
+        memory
+            .store(
+                "telegram_bob_m2",
+                "Hello",
+                MemoryCategory::Conversation,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let trace = ws.join("trace.jsonl");
+        std::fs::write(
+            &trace,
+            concat!(
+                r#"{"id":"1","timestamp":"t","event_type":"channel_message_inbound","channel":"telegram","payload":{"sender":"alice"}}"#,
+                "\n",
+                r#"{"id":"2","timestamp":"t","event_type":"channel_message_inbound","channel":"telegram","payload":{"sender":"bob"}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let mut identity = Identity::parse("telegram:alice");
+        let sections = collect(
+            ws,
+            &mut identity,
+            Some(&memory as &dyn Memory),
+            Some(&trace),
+        )
+        .await
+        .unwrap();
+
+        let prefs = sections["preferences"].as_array().unwrap();
+        assert_eq!(prefs.len(), 1);
+        assert_eq!(prefs[0]["value"], "metric");
+        assert_eq!(sections["quarantine"].as_array().unwrap().len(), 1);
+        let memories = sections["memories"].as_array().unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0]["content"], "I moved to Berlin");
+        let traces = sections["runtime_traces"].as_array().unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0]["id"], "1");
+        assert!(sections["feedback"].as_array().unwrap().is_empty());
+
+        let document = export_document(&identity, sections);
+        assert_eq!(document["identity"], "telegram:alice");
+        assert_eq!(document["senders"][0]["channel"], "telegram");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    split_system_prompt_for_cache, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -10,8 +10,12 @@ use serde::{Deserialize, Serialize};
 pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
+    thinking_budget_tokens: Option<u32>,
 }
 
+/// Default `max_tokens` for responses when extended thinking is off.
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
 #[derive(Debug, Serialize)]
 struct ChatRequest {
     model: String,
@@ -20,6 +24,16 @@ struct ChatRequest {
     system: Option<String>,
     messages: Vec<Message>,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ThinkingConfig>,
+}
+
+/// Extended thinking request block (`{"type": "enabled", "budget_tokens": N}`).
+#[derive(Debug, Clone, Serialize)]
+struct ThinkingConfig {
+    #[serde(rename = "type")]
+    kind: String,
+    budget_tokens: u32,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,6 +65,8 @@ struct NativeChatRequest<'a> {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<NativeToolSpec<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ThinkingConfig>,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +78,12 @@ struct NativeMessage {
 #[derive(Debug, Serialize)]
 #[serde(tag = "type")]
 enum NativeContentOut {
+    /// Thinking blocks must be echoed back unchanged (with their signature)
+    /// on assistant tool-use turns while extended thinking is enabled.
+    #[serde(rename = "thinking")]
+    Thinking { thinking: String, signature: String },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
     #[serde(rename = "text")]
     Text {
         text: String,
@@ -138,6 +160,26 @@ struct AnthropicUsage {
     input_tokens: Option<u64>,
     #[serde(default)]
     output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+}
+
+impl AnthropicUsage {
+    /// Anthropic's `input_tokens` leaves out tokens written to or read from
+    /// the prompt cache; add them back so it matches other providers' totals.
+    fn total_input_tokens(&self) -> Option<u64> {
+        let parts = [
+            self.input_tokens,
+            self.cache_creation_input_tokens,
+            self.cache_read_input_tokens,
+        ];
+        parts
+            .iter()
+            .any(Option::is_some)
+            .then(|| parts.iter().flatten().sum())
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -152,6 +194,12 @@ struct NativeContentIn {
     name: Option<String>,
     #[serde(default)]
     input: Option<serde_json::Value>,
+    #[serde(default)]
+    thinking: Option<String>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    data: Option<String>,
 }
 
 impl AnthropicProvider {
@@ -170,6 +218,39 @@ impl AnthropicProvider {
                 .filter(|k| !k.is_empty())
                 .map(ToString::to_string),
             base_url,
+            thinking_budget_tokens: None,
+        }
+    }
+
+    /// Enable extended thinking with the given token budget (`None` disables it).
+    pub fn with_thinking_budget(mut self, budget_tokens: Option<u32>) -> Self {
+        self.thinking_budget_tokens = budget_tokens.filter(|budget| *budget > 0);
+        self
+    }
+
+    fn thinking_config(&self) -> Option<ThinkingConfig> {
+        self.thinking_budget_tokens
+            .map(|budget_tokens| ThinkingConfig {
+                kind: "enabled".to_string(),
+                budget_tokens,
+            })
+    }
+
+    /// `max_tokens` must exceed the thinking budget, so leave room for the
+    /// visible answer on top of it.
+    fn max_tokens(&self) -> u32 {
+        self.thinking_budget_tokens
+            .map_or(DEFAULT_MAX_TOKENS, |budget| {
+                budget.saturating_add(DEFAULT_MAX_TOKENS)
+            })
+    }
+
+    /// Anthropic rejects any temperature other than 1 while thinking is enabled.
+    fn effective_temperature(&self, temperature: f64) -> f64 {
+        if self.thinking_budget_tokens.is_some() {
+            1.0
+        } else {
+            temperature
         }
     }
 
@@ -196,6 +277,24 @@ impl AnthropicProvider {
         text.len() > 3072
     }
 
+    /// Mark the stable part of the system prompt (everything before
+    /// [`crate::providers::SYSTEM_PROMPT_CACHE_BOUNDARY`]) as cacheable, so per-message context
+    /// after the boundary does not invalidate the cached prefix.
+    fn system_prompt(text: String) -> SystemPrompt {
+        let (stable, rest) = split_system_prompt_for_cache(&text);
+        if !Self::should_cache_system(stable) {
+            return SystemPrompt::String(text);
+        }
+        let block = |part: &str, cache_control| SystemBlock {
+            block_type: "text".to_string(),
+            text: part.to_string(),
+            cache_control,
+        };
+        let mut blocks = vec![block(stable, Some(CacheControl::ephemeral()))];
+        blocks.extend(rest.map(|rest| block(rest, None)));
+        SystemPrompt::Blocks(blocks)
+    }
+
     /// Cache conversations with more than 4 messages (excluding system)
     fn should_cache_conversation(messages: &[ChatMessage]) -> bool {
         messages.iter().filter(|m| m.role != "system").count() > 4
@@ -210,7 +309,9 @@ impl AnthropicProvider {
                     | NativeContentOut::ToolResult { cache_control, .. } => {
                         *cache_control = Some(CacheControl::ephemeral());
                     }
-                    NativeContentOut::ToolUse { .. } => {}
+                    NativeContentOut::ToolUse { .. }
+                    | NativeContentOut::Thinking { .. }
+                    | NativeContentOut::RedactedThinking { .. } => {}
                 }
             }
         }
@@ -245,7 +346,11 @@ impl AnthropicProvider {
             .get("tool_calls")
             .and_then(|v| serde_json::from_value::<Vec<ProviderToolCall>>(v.clone()).ok())?;
 
-        let mut blocks = Vec::new();
+        let mut blocks = value
+            .get("reasoning_content")
+            .and_then(serde_json::Value::as_str)
+            .map(Self::parse_thinking_blocks)
+            .unwrap_or_default();
         if let Some(text) = value
             .get("content")
             .and_then(serde_json::Value::as_str)
@@ -270,6 +375,33 @@ impl AnthropicProvider {
         Some(blocks)
     }
 
+    /// Restore thinking blocks captured by [`Self::parse_native_response`].
+    fn parse_thinking_blocks(reasoning_content: &str) -> Vec<NativeContentOut> {
+        let Ok(items) = serde_json::from_str::<Vec<serde_json::Value>>(reasoning_content) else {
+            return Vec::new();
+        };
+        items
+            .into_iter()
+            .filter_map(|item| {
+                let field = |name: &str| {
+                    item.get(name)
+                        .and_then(serde_json::Value::as_str)
+                        .map(ToString::to_string)
+                };
+                match item.get("type").and_then(serde_json::Value::as_str)? {
+                    "thinking" => Some(NativeContentOut::Thinking {
+                        thinking: field("thinking")?,
+                        signature: field("signature")?,
+                    }),
+                    "redacted_thinking" => Some(NativeContentOut::RedactedThinking {
+                        data: field("data")?,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
     fn parse_tool_result_message(content: &str) -> Option<NativeMessage> {
         let value = serde_json::from_str::<serde_json::Value>(content).ok()?;
         let tool_use_id = value
@@ -343,18 +475,7 @@ impl AnthropicProvider {
             }
         }
 
-        // Convert system text to SystemPrompt with cache control if large
-        let system_prompt = system_text.map(|text| {
-            if Self::should_cache_system(&text) {
-                SystemPrompt::Blocks(vec![SystemBlock {
-                    block_type: "text".to_string(),
-                    text,
-                    cache_control: Some(CacheControl::ephemeral()),
-                }])
-            } else {
-                SystemPrompt::String(text)
-            }
-        });
+        let system_prompt = system_text.map(Self::system_prompt);
 
         (system_prompt, native_messages)
     }
@@ -371,10 +492,13 @@ impl AnthropicProvider {
     fn parse_native_response(response: NativeChatResponse) -> ProviderChatResponse {
         let mut text_parts = Vec::new();
         let mut tool_calls = Vec::new();
+        let mut thinking_blocks = Vec::new();
 
         let usage = response.usage.map(|u| TokenUsage {
-            input_tokens: u.input_tokens,
+            input_tokens: u.total_input_tokens(),
             output_tokens: u.output_tokens,
+            reasoning_tokens: None,
+            cached_input_tokens: u.cache_read_input_tokens,
         });
 
         for block in response.content {
@@ -400,6 +524,19 @@ impl AnthropicProvider {
                         arguments: arguments.to_string(),
                     });
                 }
+                "thinking" => {
+                    thinking_blocks.push(serde_json::json!({
+                        "type": "thinking",
+                        "thinking": block.thinking.unwrap_or_default(),
+                        "signature": block.signature.unwrap_or_default(),
+                    }));
+                }
+                "redacted_thinking" => {
+                    thinking_blocks.push(serde_json::json!({
+                        "type": "redacted_thinking",
+                        "data": block.data.unwrap_or_default(),
+                    }));
+                }
                 _ => {}
             }
         }
@@ -412,7 +549,11 @@ impl AnthropicProvider {
             },
             tool_calls,
             usage,
-            reasoning_content: None,
+            reasoning_content: if thinking_blocks.is_empty() {
+                None
+            } else {
+                Some(serde_json::Value::Array(thinking_blocks).to_string())
+            },
         }
     }
 
@@ -438,13 +579,14 @@ impl Provider for AnthropicProvider {
 
         let request = ChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens: self.max_tokens(),
             system: system_prompt.map(ToString::to_string),
             messages: vec![Message {
                 role: "user".to_string(),
                 content: message.to_string(),
             }],
-            temperature,
+            temperature: self.effective_temperature(temperature),
+            thinking: self.thinking_config(),
         };
 
         let mut request = self
@@ -487,11 +629,12 @@ impl Provider for AnthropicProvider {
 
         let native_request = NativeChatRequest {
             model: model.to_string(),
-            max_tokens: 4096,
+            max_tokens: self.max_tokens(),
             system: system_prompt,
             messages,
-            temperature,
+            temperature: self.effective_temperature(temperature),
             tools: Self::convert_tools(request.tools),
+            thinking: self.thinking_config(),
         };
 
         let req = self
@@ -725,6 +868,7 @@ mod tests {
                 content: "hello".to_string(),
             }],
             temperature: 0.7,
+            thinking: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(
@@ -746,6 +890,7 @@ mod tests {
                 content: "hello".to_string(),
             }],
             temperature: 0.7,
+            thinking: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"system\":\"You are ZeroClaw\""));
@@ -786,6 +931,7 @@ mod tests {
                 system: None,
                 messages: vec![],
                 temperature: temp,
+                thinking: None,
             };
             let json = serde_json::to_string(&req).unwrap();
             assert!(json.contains(&format!("{temp}")));
@@ -1140,6 +1286,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn convert_messages_caches_only_stable_system_prefix() {
+        use crate::providers::SYSTEM_PROMPT_CACHE_BOUNDARY;
+
+        let stable = "a".repeat(3073);
+        let volatile = format!("{SYSTEM_PROMPT_CACHE_BOUNDARY}Goals for today");
+        let messages = vec![ChatMessage::system(format!("{stable}{volatile}"))];
+
+        let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
+
+        match system_prompt.unwrap() {
+            SystemPrompt::Blocks(blocks) => {
+                assert_eq!(blocks.len(), 2);
+                assert_eq!(blocks[0].text, stable);
+                assert!(blocks[0].cache_control.is_some());
+                assert_eq!(blocks[1].text, volatile);
+                assert!(blocks[1].cache_control.is_none());
+            }
+            SystemPrompt::String(_) => panic!("Expected Blocks variant for large prompt"),
+        }
+
+        // A small stable prefix is not worth caching, even with a large tail.
+        let messages = vec![ChatMessage::system(format!(
+            "short{SYSTEM_PROMPT_CACHE_BOUNDARY}{stable}"
+        ))];
+        let (system_prompt, _) = AnthropicProvider::convert_messages(&messages);
+        assert!(matches!(system_prompt.unwrap(), SystemPrompt::String(_)));
+    }
+
     #[test]
     fn backward_compatibility_native_chat_request() {
         // Test that requests without cache_control serialize identically to old format
@@ -1156,6 +1331,7 @@ mod tests {
             }],
             temperature: 0.7,
             tools: None,
+            thinking: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -1163,6 +1339,73 @@ mod tests {
         assert!(json.contains(r#""system":"System""#));
     }
 
+    #[test]
+    fn thinking_budget_sets_request_thinking_block() {
+        let provider = AnthropicProvider::new(None).with_thinking_budget(Some(2048));
+        let req = NativeChatRequest {
+            model: "claude-sonnet-4".to_string(),
+            max_tokens: provider.max_tokens(),
+            system: None,
+            messages: vec![],
+            temperature: provider.effective_temperature(0.3),
+            tools: None,
+            thinking: provider.thinking_config(),
+        };
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["thinking"]["type"], "enabled");
+        assert_eq!(json["thinking"]["budget_tokens"], 2048);
+        assert_eq!(json["max_tokens"], 2048 + DEFAULT_MAX_TOKENS);
+        assert_eq!(json["temperature"], 1.0);
+    }
+
+    #[test]
+    fn thinking_disabled_by_default() {
+        let provider = AnthropicProvider::new(None);
+        assert!(provider.thinking_config().is_none());
+        assert_eq!(provider.max_tokens(), DEFAULT_MAX_TOKENS);
+        assert!((provider.effective_temperature(0.3) - 0.3).abs() < f64::EPSILON);
+        assert!(AnthropicProvider::new(None)
+            .with_thinking_budget(Some(0))
+            .thinking_config()
+            .is_none());
+    }
+
+    #[test]
+    fn thinking_blocks_round_trip_through_tool_call_history() {
+        let json = r#"{
+            "content": [
+                {"type": "thinking", "thinking": "plan the call", "signature": "sig-1"},
+                {"type": "redacted_thinking", "data": "opaque"},
+                {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": "ls"}}
+            ],
+            "usage": {"input_tokens": 10, "output_tokens": 20}
+        }"#;
+        let response: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let parsed = AnthropicProvider::parse_native_response(response);
+        let reasoning = parsed.reasoning_content.clone().expect("thinking captured");
+        assert!(parsed.text.is_none());
+        assert_eq!(parsed.tool_calls.len(), 1);
+
+        let history = serde_json::json!({
+            "content": "",
+            "tool_calls": parsed.tool_calls,
+            "reasoning_content": reasoning,
+        })
+        .to_string();
+        let blocks = AnthropicProvider::parse_assistant_tool_call_message(&history).unwrap();
+        assert!(matches!(
+            &blocks[0],
+            NativeContentOut::Thinking { thinking, signature }
+                if thinking == "plan the call" && signature == "sig-1"
+        ));
+        assert!(matches!(
+            &blocks[1],
+            NativeContentOut::RedactedThinking { data } if data == "opaque"
+        ));
+        assert!(matches!(&blocks[2], NativeContentOut::ToolUse { .. }));
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = AnthropicProvider::new(None);
@@ -1245,6 +1488,7 @@ mod tests {
         let provider = AnthropicProvider {
             credential: Some("test-key".to_string()),
             base_url: format!("http://{addr}"),
+            thinking_budget_tokens: None,
         };
 
         // Multi-turn conversation: system → user (Go code) → assistant (code response) → user (follow-up)
@@ -1344,6 +1588,26 @@ mod tests {
         let usage = result.usage.unwrap();
         assert_eq!(usage.input_tokens, Some(300));
         assert_eq!(usage.output_tokens, Some(75));
+        assert!(usage.cached_input_tokens.is_none());
+    }
+
+    #[test]
+    fn native_response_counts_cached_input_tokens() {
+        let json = r#"{
+            "content": [{"type": "text", "text": "Hello"}],
+            "usage": {
+                "input_tokens": 40,
+                "cache_creation_input_tokens": 0,
+                "cache_read_input_tokens": 2000,
+                "output_tokens": 75
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = AnthropicProvider::parse_native_response(resp)
+            .usage
+            .unwrap();
+        assert_eq!(usage.input_tokens, Some(2040));
+        assert_eq!(usage.cached_input_tokens, Some(2000));
     }
 
     #[test]
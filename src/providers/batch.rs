@@ -0,0 +1,463 @@
+//! Provider batch APIs (OpenAI `/v1/batches`, Anthropic `/v1/messages/batches`).
+//!
+//! Batch requests are billed at roughly half the synchronous price in exchange
+//! for asynchronous completion (typically minutes, at most 24h). The scheduler
+//! uses this for `delivery.mode = "batch"` cron jobs: submit once, poll on
+//! later ticks, and deliver the result when the batch has ended.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
+const BATCH_MAX_TOKENS: u32 = 4096;
+
+/// A single-turn completion submitted through a provider batch API.
+#[derive(Debug, Clone)]
+pub struct BatchRequest {
+    /// Caller-chosen identifier used to find the result in the batch output.
+    pub custom_id: String,
+    pub model: String,
+    pub system_prompt: Option<String>,
+    pub prompt: String,
+    pub temperature: f64,
+}
+
+/// Polling outcome for a submitted batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchStatus {
+    /// Still validating/in progress.
+    Pending,
+    /// Finished; carries the response text for the request.
+    Completed(String),
+    /// Batch (or the individual request) failed, expired, or was cancelled.
+    Failed(String),
+}
+
+/// Base URL for `provider`'s batch API, resolved from `api_url` the way the
+/// chat providers resolve it. `None` when the provider has no batch API we
+/// know how to drive or the endpoint is not the provider's own, since
+/// proxies and compatible servers rarely implement batches.
+pub fn batch_base_url(provider: &str, api_url: Option<&str>) -> Option<&'static str> {
+    match provider {
+        "openai" => {
+            let configured = api_url.map_or(OPENAI_BASE_URL, |url| url.trim_end_matches('/'));
+            (configured == OPENAI_BASE_URL).then_some(OPENAI_BASE_URL)
+        }
+        // The Anthropic chat provider always uses its own endpoint.
+        "anthropic" => Some(ANTHROPIC_BASE_URL),
+        _ => None,
+    }
+}
+
+fn http_client() -> reqwest::Client {
+    crate::config::build_runtime_proxy_client_with_timeouts("provider.batch", 120, 10)
+}
+
+fn require_credential(provider: &str, credential: Option<&str>) -> Result<String> {
+    super::resolve_provider_credential(provider, credential)
+        .ok_or_else(|| anyhow::anyhow!("{provider} credentials not set for batch submission"))
+}
+
+fn anthropic_request(
+    builder: reqwest::RequestBuilder,
+    credential: &str,
+) -> reqwest::RequestBuilder {
+    let builder = builder.header("anthropic-version", "2023-06-01");
+    if credential.starts_with("sk-ant-oat01-") {
+        builder
+            .header("Authorization", format!("Bearer {credential}"))
+            .header("anthropic-beta", "oauth-2025-04-20")
+    } else {
+        builder.header("x-api-key", credential)
+    }
+}
+
+/// Submit `request` as a one-item batch to `base_url` (see
+/// [`batch_base_url`]) and return the provider batch id.
+pub async fn submit_batch(
+    provider: &str,
+    base_url: &str,
+    credential: Option<&str>,
+    request: &BatchRequest,
+) -> Result<String> {
+    let credential = require_credential(provider, credential)?;
+    let client = http_client();
+
+    match provider {
+        "openai" => {
+            let line = openai_batch_line(request).to_string();
+            let form = reqwest::multipart::Form::new()
+                .text("purpose", "batch")
+                .part(
+                    "file",
+                    reqwest::multipart::Part::bytes(line.into_bytes())
+                        .file_name("batch.jsonl")
+                        .mime_str("application/jsonl")?,
+                );
+            let upload = client
+                .post(format!("{base_url}/files"))
+                .bearer_auth(&credential)
+                .multipart(form)
+                .send()
+                .await?;
+            if !upload.status().is_success() {
+                return Err(super::api_error("OpenAI batch upload", upload).await);
+            }
+            let file: Value = upload.json().await?;
+            let file_id = file
+                .get("id")
+                .and_then(Value::as_str)
+                .context("OpenAI file upload returned no id")?;
+
+            let response = client
+                .post(format!("{base_url}/batches"))
+                .bearer_auth(&credential)
+                .json(&json!({
+                    "input_file_id": file_id,
+                    "endpoint": "/v1/chat/completions",
+                    "completion_window": "24h",
+                }))
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(super::api_error("OpenAI batch", response).await);
+            }
+            batch_id_from(&response.json().await?)
+        }
+        "anthropic" => {
+            let response = anthropic_request(
+                client.post(format!("{base_url}/v1/messages/batches")),
+                &credential,
+            )
+            .json(&anthropic_batch_body(request))
+            .send()
+            .await?;
+            if !response.status().is_success() {
+                return Err(super::api_error("Anthropic batch", response).await);
+            }
+            batch_id_from(&response.json().await?)
+        }
+        other => anyhow::bail!("provider '{other}' does not support batch submission"),
+    }
+}
+
+/// Check a submitted batch and fetch the result for `custom_id` once it has ended.
+pub async fn poll_batch(
+    provider: &str,
+    base_url: &str,
+    credential: Option<&str>,
+    batch_id: &str,
+    custom_id: &str,
+) -> Result<BatchStatus> {
+    let credential = require_credential(provider, credential)?;
+    let client = http_client();
+
+    match provider {
+        "openai" => {
+            let response = client
+                .get(format!("{base_url}/batches/{batch_id}"))
+                .bearer_auth(&credential)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(super::api_error("OpenAI batch", response).await);
+            }
+            let batch: Value = response.json().await?;
+            let output_file_id = match openai_batch_state(&batch) {
+                OpenAiBatchState::Running => return Ok(BatchStatus::Pending),
+                OpenAiBatchState::Ended(reason) => return Ok(BatchStatus::Failed(reason)),
+                OpenAiBatchState::Output(output_file_id) => output_file_id,
+            };
+            let output = client
+                .get(format!("{base_url}/files/{output_file_id}/content"))
+                .bearer_auth(&credential)
+                .send()
+                .await?;
+            if !output.status().is_success() {
+                return Err(super::api_error("OpenAI batch output", output).await);
+            }
+            Ok(parse_openai_batch_output(&output.text().await?, custom_id))
+        }
+        "anthropic" => {
+            let response = anthropic_request(
+                client.get(format!("{base_url}/v1/messages/batches/{batch_id}")),
+                &credential,
+            )
+            .send()
+            .await?;
+            if !response.status().is_success() {
+                return Err(super::api_error("Anthropic batch", response).await);
+            }
+            let batch: Value = response.json().await?;
+            if batch.get("processing_status").and_then(Value::as_str) != Some("ended") {
+                return Ok(BatchStatus::Pending);
+            }
+            let Some(results_url) = batch.get("results_url").and_then(Value::as_str) else {
+                return Ok(BatchStatus::Failed(
+                    "Anthropic batch ended without results".to_string(),
+                ));
+            };
+            let results = anthropic_request(client.get(results_url), &credential)
+                .send()
+                .await?;
+            if !results.status().is_success() {
+                return Err(super::api_error("Anthropic batch results", results).await);
+            }
+            Ok(parse_anthropic_batch_results(
+                &results.text().await?,
+                custom_id,
+            ))
+        }
+        other => anyhow::bail!("provider '{other}' does not support batch polling"),
+    }
+}
+
+fn batch_id_from(value: &Value) -> Result<String> {
+    value
+        .get("id")
+        .and_then(Value::as_str)
+        .map(ToString::to_string)
+        .context("batch response returned no id")
+}
+
+fn user_messages(request: &BatchRequest) -> Vec<Value> {
+    vec![json!({ "role": "user", "content": request.prompt })]
+}
+
+fn openai_batch_line(request: &BatchRequest) -> Value {
+    let mut messages = Vec::new();
+    if let Some(system) = request.system_prompt.as_deref() {
+        messages.push(json!({ "role": "system", "content": system }));
+    }
+    messages.extend(user_messages(request));
+    json!({
+        "custom_id": request.custom_id,
+        "method": "POST",
+        "url": "/v1/chat/completions",
+        "body": {
+            "model": request.model,
+            "messages": messages,
+            "temperature": request.temperature,
+        },
+    })
+}
+
+fn anthropic_batch_body(request: &BatchRequest) -> Value {
+    let mut params = json!({
+        "model": request.model,
+        "max_tokens": BATCH_MAX_TOKENS,
+        "messages": user_messages(request),
+        "temperature": request.temperature,
+    });
+    if let Some(system) = request.system_prompt.as_deref() {
+        params["system"] = json!(system);
+    }
+    json!({ "requests": [{ "custom_id": request.custom_id, "params": params }] })
+}
+
+/// Where an OpenAI batch stands after a status check.
+#[derive(Debug, PartialEq, Eq)]
+enum OpenAiBatchState {
+    /// Not finished yet (validating, in progress, finalizing, cancelling).
+    Running,
+    /// Finished with an output file to read the result from.
+    Output(String),
+    /// Finished for good without output for any request.
+    Ended(String),
+}
+
+fn openai_batch_state(batch: &Value) -> OpenAiBatchState {
+    let status = batch.get("status").and_then(Value::as_str).unwrap_or("");
+    if !matches!(status, "completed" | "failed" | "expired" | "cancelled") {
+        return OpenAiBatchState::Running;
+    }
+    // Expired and cancelled batches still carry output for the requests
+    // that finished before the batch stopped.
+    if let Some(output_file_id) = batch.get("output_file_id").and_then(Value::as_str) {
+        return OpenAiBatchState::Output(output_file_id.to_string());
+    }
+    let detail = batch
+        .pointer("/errors/data/0/message")
+        .and_then(Value::as_str)
+        .map(|message| format!(": {message}"))
+        .unwrap_or_default();
+    OpenAiBatchState::Ended(format!("OpenAI batch {status} without output{detail}"))
+}
+
+fn find_jsonl_entry(jsonl: &str, custom_id: &str) -> Option<Value> {
+    jsonl
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .find(|entry| entry.get("custom_id").and_then(Value::as_str) == Some(custom_id))
+}
+
+fn parse_openai_batch_output(jsonl: &str, custom_id: &str) -> BatchStatus {
+    let Some(entry) = find_jsonl_entry(jsonl, custom_id) else {
+        return BatchStatus::Failed(format!("batch output missing request {custom_id}"));
+    };
+    if let Some(error) = entry.get("error").filter(|e| !e.is_null()) {
+        return BatchStatus::Failed(format!("batch request failed: {error}"));
+    }
+    entry
+        .pointer("/response/body/choices/0/message/content")
+        .and_then(Value::as_str)
+        .map_or_else(
+            || BatchStatus::Failed("batch response had no message content".to_string()),
+            |text| BatchStatus::Completed(text.to_string()),
+        )
+}
+
+fn parse_anthropic_batch_results(jsonl: &str, custom_id: &str) -> BatchStatus {
+    let Some(entry) = find_jsonl_entry(jsonl, custom_id) else {
+        return BatchStatus::Failed(format!("batch results missing request {custom_id}"));
+    };
+    let result = entry.get("result").cloned().unwrap_or(Value::Null);
+    match result.get("type").and_then(Value::as_str) {
+        Some("succeeded") => {
+            let text = result
+                .pointer("/message/content")
+                .and_then(Value::as_array)
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+                        .filter_map(|block| block.get("text").and_then(Value::as_str))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default();
+            BatchStatus::Completed(text)
+        }
+        Some(other) => BatchStatus::Failed(format!("batch request {other}: {result}")),
+        None => BatchStatus::Failed("batch result had no type".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_request() -> BatchRequest {
+        BatchRequest {
+            custom_id: "job-1".into(),
+            model: "gpt-4o-mini".into(),
+            system_prompt: Some("Be brief.".into()),
+            prompt: "Summarize yesterday's metrics".into(),
+            temperature: 0.2,
+        }
+    }
+
+    #[test]
+    fn batch_base_url_requires_a_first_party_endpoint() {
+        assert_eq!(batch_base_url("openai", None), Some(OPENAI_BASE_URL));
+        assert_eq!(
+            batch_base_url("openai", Some("https://api.openai.com/v1/")),
+            Some(OPENAI_BASE_URL)
+        );
+        assert_eq!(
+            batch_base_url("openai", Some("https://proxy.example.com/v1")),
+            None
+        );
+        assert_eq!(batch_base_url("anthropic", None), Some(ANTHROPIC_BASE_URL));
+        assert_eq!(batch_base_url("ollama", None), None);
+    }
+
+    #[test]
+    fn openai_batch_line_targets_chat_completions() {
+        let line = openai_batch_line(&sample_request());
+        assert_eq!(line["custom_id"], "job-1");
+        assert_eq!(line["url"], "/v1/chat/completions");
+        assert_eq!(line["body"]["messages"][0]["role"], "system");
+        assert_eq!(
+            line["body"]["messages"][1]["content"],
+            "Summarize yesterday's metrics"
+        );
+    }
+
+    #[test]
+    fn anthropic_batch_body_wraps_message_params() {
+        let body = anthropic_batch_body(&sample_request());
+        let entry = &body["requests"][0];
+        assert_eq!(entry["custom_id"], "job-1");
+        assert_eq!(entry["params"]["system"], "Be brief.");
+        assert_eq!(entry["params"]["max_tokens"], BATCH_MAX_TOKENS);
+        assert_eq!(entry["params"]["messages"][0]["role"], "user");
+    }
+
+    #[test]
+    fn openai_batch_status_maps_terminal_states() {
+        for status in ["validating", "in_progress", "finalizing", "cancelling"] {
+            let batch = json!({ "status": status });
+            assert_eq!(openai_batch_state(&batch), OpenAiBatchState::Running);
+        }
+
+        let done = json!({ "status": "completed", "output_file_id": "file-out" });
+        assert_eq!(
+            openai_batch_state(&done),
+            OpenAiBatchState::Output("file-out".into())
+        );
+
+        let partial = json!({ "status": "expired", "output_file_id": "file-part" });
+        assert_eq!(
+            openai_batch_state(&partial),
+            OpenAiBatchState::Output("file-part".into())
+        );
+
+        let all_failed = json!({ "status": "completed", "output_file_id": null });
+        assert_eq!(
+            openai_batch_state(&all_failed),
+            OpenAiBatchState::Ended("OpenAI batch completed without output".into())
+        );
+
+        let invalid = json!({
+            "status": "failed",
+            "errors": { "data": [{ "message": "invalid model" }] }
+        });
+        assert_eq!(
+            openai_batch_state(&invalid),
+            OpenAiBatchState::Ended("OpenAI batch failed without output: invalid model".into())
+        );
+
+        for status in ["expired", "cancelled"] {
+            let batch = json!({ "status": status });
+            assert!(matches!(
+                openai_batch_state(&batch),
+                OpenAiBatchState::Ended(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_openai_output_finds_matching_request() {
+        let jsonl = concat!(
+            r#"{"custom_id":"other","response":{"body":{"choices":[{"message":{"content":"no"}}]}}}"#,
+            "\n",
+            r#"{"custom_id":"job-1","error":null,"response":{"body":{"choices":[{"message":{"content":"All green."}}]}}}"#,
+        );
+        assert_eq!(
+            parse_openai_batch_output(jsonl, "job-1"),
+            BatchStatus::Completed("All green.".into())
+        );
+        assert!(matches!(
+            parse_openai_batch_output(jsonl, "missing"),
+            BatchStatus::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn parse_anthropic_results_handles_success_and_errors() {
+        let ok = r#"{"custom_id":"job-1","result":{"type":"succeeded","message":{"content":[{"type":"text","text":"Done."}]}}}"#;
+        assert_eq!(
+            parse_anthropic_batch_results(ok, "job-1"),
+            BatchStatus::Completed("Done.".into())
+        );
+
+        let errored = r#"{"custom_id":"job-1","result":{"type":"errored","error":{"type":"invalid_request"}}}"#;
+        assert!(matches!(
+            parse_anthropic_batch_results(errored, "job-1"),
+            BatchStatus::Failed(_)
+        ));
+    }
+}
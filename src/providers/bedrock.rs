@@ -833,6 +833,8 @@ impl BedrockProvider {
         let usage = response.usage.map(|u| TokenUsage {
             input_tokens: u.input_tokens,
             output_tokens: u.output_tokens,
+            reasoning_tokens: None,
+            cached_input_tokens: None,
         });
 
         if let Some(output) = response.output {
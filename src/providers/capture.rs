@@ -0,0 +1,455 @@
+//! Debug capture of provider calls.
+//!
+//! When `[observability] debug_capture_dir` is set (or `zeroclaw agent
+//! --debug-capture <dir>` is passed), every chat call is written to that
+//! directory as a pretty-printed request/response JSON pair plus the
+//! assembled system prompt, for the first `debug_capture_max_turns` calls of
+//! the process. Captures are taken at the [`Provider`] trait boundary, so
+//! they show what zeroclaw asked for rather than the wire format, and never
+//! include auth headers; message text is scrubbed of credential-like values.
+//! Streaming calls are passed through without capture.
+
+use super::traits::{
+    ChatMessage, ChatRequest, ChatResponse, Provider, ProviderCapabilities, StreamChunk,
+    StreamOptions, StreamResult, ToolsPayload,
+};
+use crate::config::Config;
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use futures_util::stream;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub(crate) const REQUEST_SUFFIX: &str = "-request.json";
+pub(crate) const RESPONSE_SUFFIX: &str = "-response.json";
+pub(crate) const SYSTEM_PROMPT_SUFFIX: &str = "-system-prompt.md";
+/// Stands in for the system message in request files; the prompt itself is
+/// in the `-system-prompt.md` file next to it.
+pub(crate) const SYSTEM_PROMPT_PLACEHOLDER: &str = "(see system-prompt.md)";
+
+/// Shared capture budget and destination for one process.
+#[derive(Debug)]
+pub struct DebugCapture {
+    dir: PathBuf,
+    max_turns: usize,
+    next: AtomicUsize,
+}
+
+impl DebugCapture {
+    pub fn new(dir: PathBuf, max_turns: usize) -> Self {
+        Self {
+            dir,
+            max_turns,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Capture settings from `[observability]`, or `None` when disabled.
+    pub fn from_config(config: &Config) -> Option<Arc<Self>> {
+        let dir = config.observability.debug_capture_dir.as_deref()?.trim();
+        if dir.is_empty() {
+            return None;
+        }
+        let dir = Path::new(dir);
+        let dir = if dir.is_absolute() {
+            dir.to_path_buf()
+        } else {
+            config.workspace_dir.join(dir)
+        };
+        Some(Arc::new(Self::new(
+            dir,
+            config.observability.debug_capture_max_turns,
+        )))
+    }
+
+    /// Reserve the next capture slot, or `None` once the budget is spent.
+    fn claim(&self) -> Option<usize> {
+        let turn = self.next.fetch_add(1, Ordering::SeqCst) + 1;
+        if turn > self.max_turns {
+            if turn == self.max_turns + 1 {
+                tracing::info!(
+                    dir = %self.dir.display(),
+                    "Debug capture limit of {} turns reached; later calls are not captured",
+                    self.max_turns
+                );
+            }
+            return None;
+        }
+        Some(turn)
+    }
+
+    fn write(&self, turn: usize, request: Value, system_prompt: Option<&str>) -> Option<String> {
+        let stem = format!(
+            "{}-{turn:03}",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3f")
+        );
+        let result = (|| -> std::io::Result<()> {
+            std::fs::create_dir_all(&self.dir)?;
+            if let Some(prompt) = system_prompt {
+                std::fs::write(
+                    self.dir.join(format!("{stem}{SYSTEM_PROMPT_SUFFIX}")),
+                    redact(prompt),
+                )?;
+            }
+            std::fs::write(
+                self.dir.join(format!("{stem}{REQUEST_SUFFIX}")),
+                serde_json::to_string_pretty(&request).unwrap_or_default(),
+            )
+        })();
+        match result {
+            Ok(()) => Some(stem),
+            Err(e) => {
+                tracing::warn!(dir = %self.dir.display(), "Debug capture failed: {e}");
+                None
+            }
+        }
+    }
+
+    fn write_response(&self, stem: &str, response: &Value) {
+        let path = self.dir.join(format!("{stem}{RESPONSE_SUFFIX}"));
+        if let Err(e) = std::fs::write(
+            &path,
+            serde_json::to_string_pretty(response).unwrap_or_default(),
+        ) {
+            tracing::warn!(path = %path.display(), "Debug capture failed: {e}");
+        }
+    }
+}
+
+/// Wrap `inner` when capture is enabled.
+pub fn wrap(inner: Box<dyn Provider>, capture: Option<&Arc<DebugCapture>>) -> Box<dyn Provider> {
+    match capture {
+        Some(capture) => Box::new(CapturingProvider {
+            inner,
+            capture: Arc::clone(capture),
+        }),
+        None => inner,
+    }
+}
+
+pub(crate) fn redact(text: &str) -> String {
+    super::scrub_secret_patterns(&crate::agent::loop_::scrub_credentials(text))
+}
+
+fn messages_json(messages: &[ChatMessage]) -> Vec<Value> {
+    messages
+        .iter()
+        .map(|m| {
+            if m.role == "system" {
+                json!({ "role": "system", "content": SYSTEM_PROMPT_PLACEHOLDER })
+            } else {
+                json!({ "role": m.role, "content": redact(&m.content) })
+            }
+        })
+        .collect()
+}
+
+fn system_prompt(messages: &[ChatMessage]) -> Option<&str> {
+    messages
+        .iter()
+        .find(|m| m.role == "system")
+        .map(|m| m.content.as_str())
+}
+
+fn response_json(result: &anyhow::Result<ChatResponse>, started: Instant) -> Value {
+    let elapsed_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    match result {
+        Ok(response) => json!({
+            "elapsed_ms": elapsed_ms,
+            "text": response.text.as_deref().map(redact),
+            "tool_calls": response.tool_calls.iter().map(|call| json!({
+                "id": call.id,
+                "name": call.name,
+                "arguments": redact(&call.arguments),
+            })).collect::<Vec<_>>(),
+            "usage": response.usage.as_ref().map(|usage| json!({
+                "input_tokens": usage.input_tokens,
+                "output_tokens": usage.output_tokens,
+                "reasoning_tokens": usage.reasoning_tokens,
+                "cached_input_tokens": usage.cached_input_tokens,
+            })),
+            "reasoning_content": response.reasoning_content.as_deref().map(redact),
+        }),
+        Err(e) => json!({
+            "elapsed_ms": elapsed_ms,
+            "error": redact(&format!("{e:#}")),
+        }),
+    }
+}
+
+struct CapturingProvider {
+    inner: Box<dyn Provider>,
+    capture: Arc<DebugCapture>,
+}
+
+impl CapturingProvider {
+    /// Write the request and return the file stem for the response, if this
+    /// call is within the capture budget.
+    fn begin(
+        &self,
+        method: &str,
+        model: &str,
+        temperature: f64,
+        messages: &[ChatMessage],
+        tools: Value,
+    ) -> Option<String> {
+        let turn = self.capture.claim()?;
+        let request = json!({
+            "method": method,
+            "model": model,
+            "temperature": temperature,
+            "messages": messages_json(messages),
+            "tools": tools,
+        });
+        self.capture.write(turn, request, system_prompt(messages))
+    }
+
+    fn finish<T>(
+        &self,
+        stem: Option<String>,
+        started: Instant,
+        result: anyhow::Result<T>,
+        to_response: impl FnOnce(&T) -> ChatResponse,
+    ) -> anyhow::Result<T> {
+        if let Some(stem) = stem {
+            let as_response = match &result {
+                Ok(value) => Ok(to_response(value)),
+                Err(e) => Err(anyhow::anyhow!("{e:#}")),
+            };
+            self.capture
+                .write_response(&stem, &response_json(&as_response, started));
+        }
+        result
+    }
+}
+
+fn text_response(text: &str) -> ChatResponse {
+    ChatResponse {
+        text: Some(text.to_string()),
+        tool_calls: Vec::new(),
+        usage: None,
+        reasoning_content: None,
+    }
+}
+
+#[async_trait]
+impl Provider for CapturingProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn convert_tools(&self, tools: &[ToolSpec]) -> ToolsPayload {
+        self.inner.convert_tools(tools)
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        let stem = self.begin(
+            "chat_with_system",
+            model,
+            temperature,
+            &messages,
+            Value::Null,
+        );
+        let started = Instant::now();
+        let result = self
+            .inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await;
+        self.finish(stem, started, result, |text| text_response(text))
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let stem = self.begin(
+            "chat_with_history",
+            model,
+            temperature,
+            messages,
+            Value::Null,
+        );
+        let started = Instant::now();
+        let result = self
+            .inner
+            .chat_with_history(messages, model, temperature)
+            .await;
+        self.finish(stem, started, result, |text| text_response(text))
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let tools = request.tools.map_or(Value::Null, |tools| {
+            json!(tools
+                .iter()
+                .map(|tool| json!({
+                    "name": tool.name,
+                    "description": tool.description,
+                    "parameters": tool.parameters,
+                }))
+                .collect::<Vec<_>>())
+        });
+        let stem = self.begin("chat", model, temperature, request.messages, tools);
+        let started = Instant::now();
+        let result = self.inner.chat(request, model, temperature).await;
+        self.finish(stem, started, result, Clone::clone)
+    }
+
+    fn supports_native_tools(&self) -> bool {
+        self.inner.supports_native_tools()
+    }
+
+    fn supports_vision(&self) -> bool {
+        self.inner.supports_vision()
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[Value],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let stem = self.begin(
+            "chat_with_tools",
+            model,
+            temperature,
+            messages,
+            Value::Array(tools.to_vec()),
+        );
+        let started = Instant::now();
+        let result = self
+            .inner
+            .chat_with_tools(messages, tools, model, temperature)
+            .await;
+        self.finish(stem, started, result, Clone::clone)
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner.supports_streaming()
+    }
+
+    fn stream_chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_system(system_prompt, message, model, temperature, options)
+    }
+
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.inner
+            .stream_chat_with_history(messages, model, temperature, options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    struct EchoProvider;
+
+    #[async_trait]
+    impl Provider for EchoProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(format!("echo: {message}"))
+        }
+    }
+
+    fn files(dir: &Path, suffix: &str) -> Vec<PathBuf> {
+        let mut found: Vec<PathBuf> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.to_string_lossy().ends_with(suffix))
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[tokio::test]
+    async fn captures_redacted_turns_up_to_the_limit() {
+        let tmp = TempDir::new().unwrap();
+        let capture = Arc::new(DebugCapture::new(tmp.path().to_path_buf(), 2));
+        let provider = wrap(Box::new(EchoProvider), Some(&capture));
+        let messages = [
+            ChatMessage::system("You are helpful."),
+            ChatMessage::user("my api_key=abcdef1234567890 please"),
+        ];
+
+        for _ in 0..3 {
+            provider
+                .chat_with_history(&messages, "test-model", 0.2)
+                .await
+                .unwrap();
+        }
+
+        let requests = files(tmp.path(), "-request.json");
+        assert_eq!(requests.len(), 2);
+        assert_eq!(files(tmp.path(), "-response.json").len(), 2);
+        let prompts = files(tmp.path(), "-system-prompt.md");
+        assert_eq!(
+            std::fs::read_to_string(&prompts[0]).unwrap(),
+            "You are helpful."
+        );
+
+        let request = std::fs::read_to_string(&requests[0]).unwrap();
+        assert!(request.contains("\"model\": \"test-model\""), "{request}");
+        assert!(!request.contains("abcdef1234567890"), "{request}");
+        assert!(!request.contains("You are helpful."));
+        let response = std::fs::read_to_string(&files(tmp.path(), "-response.json")[0]).unwrap();
+        assert!(response.contains("echo: my api_key"), "{response}");
+        assert!(!response.contains("abcdef1234567890"), "{response}");
+    }
+
+    #[test]
+    fn from_config_resolves_relative_dir_under_workspace() {
+        let mut config = Config::default();
+        assert!(DebugCapture::from_config(&config).is_none());
+        config.workspace_dir = PathBuf::from("/ws");
+        config.observability.debug_capture_dir = Some("captures".into());
+        let capture = DebugCapture::from_config(&config).unwrap();
+        assert_eq!(capture.dir, PathBuf::from("/ws/captures"));
+        assert_eq!(capture.max_turns, 20);
+    }
+}
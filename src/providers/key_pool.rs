@@ -0,0 +1,314 @@
+//! Health-aware selection among several API keys for one service.
+//!
+//! Providers (through [`ReliableProvider`](super::reliable::ReliableProvider))
+//! and tools that accept more than one key pick a key per request with
+//! [`KeyPool::select`] and report how it went with [`KeyPool::record`].
+//! Selection is smooth weighted round-robin over healthy keys. A rate-limited
+//! key sits out for its `Retry-After` or an exponential backoff. A rejected
+//! key (bad credentials, exhausted quota) sits out for [`MAX_QUARANTINE`].
+//! Repeated errors also quarantine a key. When every key is quarantined, the
+//! one released soonest is used rather than failing outright.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+/// Longest time a key is kept out of rotation.
+pub const MAX_QUARANTINE: Duration = Duration::from_secs(30 * 60);
+
+/// Consecutive errors (other than rate limits and rejections) before a key is quarantined.
+const ERROR_THRESHOLD: u32 = 3;
+
+/// How a request made with a key ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyOutcome {
+    Success,
+    /// HTTP 429; `Some` carries the server's `Retry-After`.
+    RateLimited(Option<Duration>),
+    /// The key itself was refused: bad credentials or exhausted quota.
+    Rejected,
+    /// Any other failure, possibly unrelated to the key.
+    Error,
+}
+
+/// Counters for one key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyStats {
+    pub selected: u64,
+    pub successes: u64,
+    pub rate_limited: u64,
+    pub rejected: u64,
+    pub errors: u64,
+    pub quarantines: u64,
+}
+
+/// Point-in-time view of one key, with the key masked.
+#[derive(Debug, Clone)]
+pub struct KeySnapshot {
+    pub key: String,
+    pub weight: u32,
+    pub quarantined_for: Option<Duration>,
+    pub stats: KeyStats,
+}
+
+#[derive(Default)]
+struct KeyState {
+    current_weight: i64,
+    quarantined_until: Option<Instant>,
+    /// Quarantines since the last success; doubles the next one.
+    strikes: u32,
+    consecutive_errors: u32,
+    stats: KeyStats,
+}
+
+pub struct KeyPool {
+    keys: Vec<String>,
+    weights: Vec<u32>,
+    base_quarantine: Duration,
+    state: Mutex<Vec<KeyState>>,
+}
+
+impl KeyPool {
+    /// Pool over `keys`. `weights[i]` applies to `keys[i]`; missing weights are 1,
+    /// and a key with weight 0 is used only when no weighted key is healthy.
+    pub fn new(keys: Vec<String>, weights: &[u32], base_quarantine: Duration) -> Self {
+        let weights = (0..keys.len())
+            .map(|i| weights.get(i).copied().unwrap_or(1))
+            .collect();
+        let state = keys.iter().map(|_| KeyState::default()).collect();
+        Self {
+            keys,
+            weights,
+            base_quarantine: base_quarantine.max(Duration::from_secs(1)),
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Split a comma-separated key list, as accepted by tools with a single key setting.
+    pub fn parse_keys(list: &str) -> Vec<String> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn key(&self, index: usize) -> &str {
+        &self.keys[index]
+    }
+
+    /// Index of the key to use next, or `None` when the pool is empty.
+    pub fn select(&self) -> Option<usize> {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        let healthy: Vec<usize> = (0..self.keys.len())
+            .filter(|&i| state[i].quarantined_until.is_none_or(|until| until <= now))
+            .collect();
+        let weighted: Vec<usize> = healthy
+            .iter()
+            .copied()
+            .filter(|&i| self.weights[i] > 0)
+            .collect();
+        let candidates = if weighted.is_empty() {
+            healthy
+        } else {
+            weighted
+        };
+
+        let chosen = if candidates.is_empty() {
+            (0..self.keys.len()).min_by_key(|&i| state[i].quarantined_until)?
+        } else {
+            let weight = |i: usize| i64::from(self.weights[i].max(1));
+            let total: i64 = candidates.iter().map(|&i| weight(i)).sum();
+            for &i in &candidates {
+                state[i].current_weight += weight(i);
+            }
+            let best = candidates
+                .iter()
+                .copied()
+                .max_by_key(|&i| (state[i].current_weight, std::cmp::Reverse(i)))?;
+            state[best].current_weight -= total;
+            best
+        };
+        state[chosen].stats.selected += 1;
+        Some(chosen)
+    }
+
+    /// Record how a request with `keys[index]` ended.
+    pub fn record(&self, index: usize, outcome: KeyOutcome) {
+        let mut state = self.state.lock();
+        let Some(key) = state.get_mut(index) else {
+            return;
+        };
+        match outcome {
+            KeyOutcome::Success => {
+                key.stats.successes += 1;
+                key.consecutive_errors = 0;
+                key.strikes = 0;
+                key.quarantined_until = None;
+            }
+            KeyOutcome::RateLimited(retry_after) => {
+                key.stats.rate_limited += 1;
+                let backoff = self.backoff(key.strikes);
+                self.quarantine(index, key, retry_after.map_or(backoff, |d| d.max(backoff)));
+            }
+            KeyOutcome::Rejected => {
+                key.stats.rejected += 1;
+                self.quarantine(index, key, MAX_QUARANTINE);
+            }
+            KeyOutcome::Error => {
+                key.stats.errors += 1;
+                key.consecutive_errors += 1;
+                if key.consecutive_errors >= ERROR_THRESHOLD {
+                    key.consecutive_errors = 0;
+                    let backoff = self.backoff(key.strikes);
+                    self.quarantine(index, key, backoff);
+                }
+            }
+        }
+    }
+
+    /// Whether a key other than `keys[index]` is out of quarantine.
+    pub fn has_healthy_alternative(&self, index: usize) -> bool {
+        let now = Instant::now();
+        let state = self.state.lock();
+        state
+            .iter()
+            .enumerate()
+            .any(|(i, key)| i != index && key.quarantined_until.is_none_or(|until| until <= now))
+    }
+
+    pub fn snapshot(&self) -> Vec<KeySnapshot> {
+        let now = Instant::now();
+        let state = self.state.lock();
+        self.keys
+            .iter()
+            .zip(&self.weights)
+            .zip(state.iter())
+            .map(|((key, weight), key_state)| KeySnapshot {
+                key: mask(key),
+                weight: *weight,
+                quarantined_for: key_state
+                    .quarantined_until
+                    .and_then(|until| until.checked_duration_since(now)),
+                stats: key_state.stats.clone(),
+            })
+            .collect()
+    }
+
+    fn backoff(&self, strikes: u32) -> Duration {
+        self.base_quarantine
+            .saturating_mul(1 << strikes.min(16))
+            .min(MAX_QUARANTINE)
+    }
+
+    fn quarantine(&self, index: usize, key: &mut KeyState, duration: Duration) {
+        let duration = duration.min(MAX_QUARANTINE);
+        key.strikes = key.strikes.saturating_add(1);
+        key.quarantined_until = Some(Instant::now() + duration);
+        key.stats.quarantines += 1;
+        tracing::warn!(
+            key = %mask(&self.keys[index]),
+            secs = duration.as_secs(),
+            "API key quarantined"
+        );
+    }
+}
+
+/// Last four characters of a key, for logs and stats.
+fn mask(key: &str) -> String {
+    let tail: String = key
+        .chars()
+        .rev()
+        .take(4)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    format!("...{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(keys: &[&str], weights: &[u32]) -> KeyPool {
+        KeyPool::new(
+            keys.iter().map(|k| (*k).to_string()).collect(),
+            weights,
+            Duration::from_secs(60),
+        )
+    }
+
+    fn picks(pool: &KeyPool, n: usize) -> Vec<usize> {
+        (0..n).map(|_| pool.select().unwrap()).collect()
+    }
+
+    #[test]
+    fn weighted_round_robin_spreads_by_weight() {
+        let pool = pool(&["a", "b", "c"], &[2, 1]);
+        let chosen = picks(&pool, 8);
+        assert_eq!(chosen.iter().filter(|&&i| i == 0).count(), 4);
+        assert_eq!(chosen.iter().filter(|&&i| i == 1).count(), 2);
+        assert_eq!(chosen.iter().filter(|&&i| i == 2).count(), 2);
+        assert_ne!(chosen[0], chosen[1], "weights are interleaved, not bunched");
+    }
+
+    #[test]
+    fn rate_limited_and_rejected_keys_sit_out() {
+        let pool = pool(&["a", "b", "c"], &[]);
+        pool.record(0, KeyOutcome::RateLimited(Some(Duration::from_secs(5))));
+        pool.record(1, KeyOutcome::Rejected);
+        assert!(picks(&pool, 5).iter().all(|&i| i == 2));
+        assert!(!pool.has_healthy_alternative(2));
+
+        let snapshot = pool.snapshot();
+        assert!(snapshot[1].quarantined_for.unwrap() > Duration::from_secs(29 * 60));
+        assert_eq!(snapshot[0].stats.rate_limited, 1);
+        assert_eq!(snapshot[2].stats.selected, 5);
+        assert_eq!(snapshot[2].key, "...c");
+    }
+
+    #[test]
+    fn repeated_errors_quarantine_and_success_clears() {
+        let pool = pool(&["a", "b"], &[]);
+        pool.record(0, KeyOutcome::Error);
+        pool.record(0, KeyOutcome::Error);
+        assert!(pool.has_healthy_alternative(1));
+        pool.record(0, KeyOutcome::Error);
+        assert!(!pool.has_healthy_alternative(1));
+
+        pool.record(0, KeyOutcome::Success);
+        assert!(pool.has_healthy_alternative(1));
+    }
+
+    #[test]
+    fn falls_back_to_soonest_release_when_all_quarantined() {
+        let pool = pool(&["a", "b"], &[]);
+        pool.record(0, KeyOutcome::Rejected);
+        pool.record(1, KeyOutcome::RateLimited(None));
+        assert_eq!(pool.select(), Some(1));
+        assert_eq!(KeyPool::new(Vec::new(), &[], Duration::ZERO).select(), None);
+    }
+
+    #[test]
+    fn zero_weight_keys_are_standby() {
+        let pool = pool(&["a", "b"], &[1, 0]);
+        assert!(picks(&pool, 4).iter().all(|&i| i == 0));
+        pool.record(0, KeyOutcome::Rejected);
+        assert_eq!(pool.select(), Some(1));
+    }
+
+    #[test]
+    fn parse_keys_splits_and_trims() {
+        assert_eq!(KeyPool::parse_keys(" k1, k2 ,,k3"), vec!["k1", "k2", "k3"]);
+        assert!(KeyPool::parse_keys("  ").is_empty());
+    }
+}
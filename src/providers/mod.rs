@@ -17,10 +17,13 @@
 //! in [`create_provider_with_url`]. See `AGENTS.md` §7.1 for the full change playbook.
 
 pub mod anthropic;
+pub mod batch;
 pub mod bedrock;
+pub mod capture;
 pub mod compatible;
 pub mod copilot;
 pub mod gemini;
+pub mod key_pool;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
@@ -32,8 +35,8 @@ pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    split_system_prompt_for_cache, ChatMessage, ChatRequest, ChatResponse, ConversationMessage,
+    Provider, ProviderCapabilityError, ToolCall, ToolResultMessage, SYSTEM_PROMPT_CACHE_BOUNDARY,
 };
 
 use crate::auth::AuthService;
@@ -41,6 +44,7 @@ use compatible::{AuthStyle, OpenAiCompatibleProvider};
 use reliable::ReliableProvider;
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 const MAX_API_ERROR_CHARS: usize = 200;
 const MINIMAX_INTL_BASE_URL: &str = "https://api.minimax.io/v1";
@@ -676,6 +680,11 @@ pub struct ProviderRuntimeOptions {
     pub zeroclaw_dir: Option<PathBuf>,
     pub secrets_encrypt: bool,
     pub reasoning_enabled: Option<bool>,
+    pub reasoning_effort: Option<String>,
+    pub thinking_budget_tokens: Option<u32>,
+    /// Provider call capture for debugging, shared across every provider built
+    /// with these options.
+    pub debug_capture: Option<Arc<capture::DebugCapture>>,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -686,10 +695,31 @@ impl Default for ProviderRuntimeOptions {
             zeroclaw_dir: None,
             secrets_encrypt: true,
             reasoning_enabled: None,
+            reasoning_effort: None,
+            thinking_budget_tokens: None,
+            debug_capture: None,
         }
     }
 }
 
+impl ProviderRuntimeOptions {
+    /// Reasoning effort to send, unless reasoning was explicitly disabled.
+    pub fn effective_reasoning_effort(&self) -> Option<String> {
+        if self.reasoning_enabled == Some(false) {
+            return None;
+        }
+        self.reasoning_effort.clone()
+    }
+
+    /// Extended thinking budget to request, unless reasoning was explicitly disabled.
+    pub fn effective_thinking_budget_tokens(&self) -> Option<u32> {
+        if self.reasoning_enabled == Some(false) {
+            return None;
+        }
+        self.thinking_budget_tokens
+    }
+}
+
 fn is_secret_char(c: char) -> bool {
     c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':')
 }
@@ -935,7 +965,7 @@ pub fn create_provider_with_url(
 
 /// Factory: create provider with optional base URL and runtime options.
 #[allow(clippy::too_many_lines)]
-fn create_provider_with_url_and_options(
+pub fn create_provider_with_url_and_options(
     name: &str,
     api_key: Option<&str>,
     api_url: Option<&str>,
@@ -969,8 +999,14 @@ fn create_provider_with_url_and_options(
         }
         // ── Primary providers (custom implementations) ───────
         "openrouter" => Ok(Box::new(openrouter::OpenRouterProvider::new(key))),
-        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(key))),
-        "openai" => Ok(Box::new(openai::OpenAiProvider::with_base_url(api_url, key))),
+        "anthropic" => Ok(Box::new(
+            anthropic::AnthropicProvider::new(key)
+                .with_thinking_budget(options.effective_thinking_budget_tokens()),
+        )),
+        "openai" => Ok(Box::new(
+            openai::OpenAiProvider::with_base_url(api_url, key)
+                .with_reasoning_effort(options.effective_reasoning_effort()),
+        )),
         // Ollama uses api_url for custom base URL (e.g. remote Ollama instance)
         "ollama" => Ok(Box::new(ollama::OllamaProvider::new_with_reasoning(
             api_url,
@@ -1289,13 +1325,28 @@ pub fn create_resilient_provider_with_options(
 ) -> anyhow::Result<Box<dyn Provider>> {
     let mut providers: Vec<(String, Box<dyn Provider>)> = Vec::new();
 
-    let primary_provider = match primary_name {
+    let create_primary = |key: Option<&str>| match primary_name {
         "openai-codex" | "openai_codex" | "codex" => {
-            create_provider_with_options(primary_name, api_key, options)?
+            create_provider_with_options(primary_name, key, options)
         }
-        _ => create_provider_with_url_and_options(primary_name, api_key, api_url, options)?,
+        _ => create_provider_with_url_and_options(primary_name, key, api_url, options),
     };
-    providers.push((primary_name.to_string(), primary_provider));
+    providers.push((primary_name.to_string(), create_primary(api_key)?));
+
+    // The primary's own key is pool key 0; each extra key gets its own instance.
+    let key_weight = |index: usize| reliability.api_key_weights.get(index).copied().unwrap_or(1);
+    let mut pool_keys = vec![api_key.unwrap_or_default().to_string()];
+    let mut pool_weights = vec![key_weight(0)];
+    let mut keyed_providers = Vec::new();
+    for (index, key) in reliability.api_keys.iter().enumerate() {
+        let key = key.trim();
+        if key.is_empty() || Some(key) == api_key {
+            continue;
+        }
+        keyed_providers.push(create_primary(Some(key))?);
+        pool_keys.push(key.to_string());
+        pool_weights.push(key_weight(index + 1));
+    }
 
     for fallback in &reliability.fallback_providers {
         if fallback == primary_name || providers.iter().any(|(name, _)| name == fallback) {
@@ -1338,10 +1389,24 @@ pub fn create_resilient_provider_with_options(
         reliability.provider_retries,
         reliability.provider_backoff_ms,
     )
-    .with_api_keys(reliability.api_keys.clone())
     .with_model_fallbacks(reliability.model_fallbacks.clone());
+    let reliable = if keyed_providers.is_empty() {
+        reliable
+    } else {
+        reliable.with_key_pool(
+            key_pool::KeyPool::new(
+                pool_keys,
+                &pool_weights,
+                std::time::Duration::from_secs(reliability.key_quarantine_secs),
+            ),
+            keyed_providers,
+        )
+    };
 
-    Ok(Box::new(reliable))
+    Ok(capture::wrap(
+        Box::new(reliable),
+        options.debug_capture.as_ref(),
+    ))
 }
 
 /// Create a RouterProvider if model routes are configured, otherwise return a
@@ -2449,6 +2514,8 @@ mod tests {
                 "openai".into(),
             ],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2488,6 +2555,8 @@ mod tests {
             provider_backoff_ms: 100,
             fallback_providers: vec!["lmstudio".into(), "ollama".into()],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2510,6 +2579,8 @@ mod tests {
             provider_backoff_ms: 100,
             fallback_providers: vec!["custom:http://host.docker.internal:1234/v1".into()],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2536,6 +2607,8 @@ mod tests {
                 "lmstudio".into(),
             ],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2568,6 +2641,8 @@ mod tests {
             provider_backoff_ms: 100,
             fallback_providers: vec!["osaurus".into(), "lmstudio".into()],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2861,6 +2936,8 @@ mod tests {
             provider_backoff_ms: 100,
             fallback_providers: vec!["openai-codex:second".into()],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2890,6 +2967,8 @@ mod tests {
                 "nonexistent-provider".into(),
             ],
             api_keys: Vec::new(),
+            api_key_weights: Vec::new(),
+            key_quarantine_secs: 60,
             model_fallbacks: std::collections::HashMap::new(),
             channel_initial_backoff_secs: 2,
             channel_max_backoff_secs: 60,
@@ -2900,4 +2979,22 @@ mod tests {
         let provider = create_resilient_provider("ollama", None, None, &reliability);
         assert!(provider.is_ok());
     }
+
+    #[test]
+    fn runtime_options_reasoning_controls_respect_disable_flag() {
+        let mut options = ProviderRuntimeOptions {
+            reasoning_effort: Some("high".into()),
+            thinking_budget_tokens: Some(4096),
+            ..ProviderRuntimeOptions::default()
+        };
+        assert_eq!(
+            options.effective_reasoning_effort().as_deref(),
+            Some("high")
+        );
+        assert_eq!(options.effective_thinking_budget_tokens(), Some(4096));
+
+        options.reasoning_enabled = Some(false);
+        assert!(options.effective_reasoning_effort().is_none());
+        assert!(options.effective_thinking_budget_tokens().is_none());
+    }
 }
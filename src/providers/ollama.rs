@@ -619,6 +619,8 @@ impl Provider for OllamaProvider {
             Some(TokenUsage {
                 input_tokens: response.prompt_eval_count,
                 output_tokens: response.eval_count,
+                reasoning_tokens: None,
+                cached_input_tokens: None,
             })
         } else {
             None
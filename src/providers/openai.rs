@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 pub struct OpenAiProvider {
     base_url: String,
     credential: Option<String>,
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -17,6 +18,8 @@ struct ChatRequest {
     model: String,
     messages: Vec<Message>,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +65,8 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -135,6 +140,38 @@ struct UsageInfo {
     prompt_tokens: Option<u64>,
     #[serde(default)]
     completion_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens_details: Option<CompletionTokensDetails>,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompletionTokensDetails {
+    #[serde(default)]
+    reasoning_tokens: Option<u64>,
+}
+
+/// Prompt caching is automatic on OpenAI-style APIs; `cached_tokens` reports
+/// how much of the prompt prefix was served from cache.
+#[derive(Debug, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+impl UsageInfo {
+    fn reasoning_tokens(&self) -> Option<u64> {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.reasoning_tokens)
+    }
+
+    fn cached_input_tokens(&self) -> Option<u64> {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.cached_tokens)
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -175,9 +212,18 @@ impl OpenAiProvider {
                 .map(|u| u.trim_end_matches('/').to_string())
                 .unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
             credential: credential.map(ToString::to_string),
+            reasoning_effort: None,
         }
     }
 
+    /// Send `reasoning_effort` with every request (for o-series / GPT-5 reasoning models).
+    pub fn with_reasoning_effort(mut self, effort: Option<String>) -> Self {
+        self.reasoning_effort = effort
+            .map(|value| value.trim().to_ascii_lowercase())
+            .filter(|value| !value.is_empty());
+        self
+    }
+
     fn convert_tools(tools: Option<&[ToolSpec]>) -> Option<Vec<NativeToolSpec>> {
         tools.map(|items| {
             items
@@ -326,6 +372,7 @@ impl Provider for OpenAiProvider {
             model: model.to_string(),
             messages,
             temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
         };
 
         let response = self
@@ -367,6 +414,7 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: tools.as_ref().map(|_| "auto".to_string()),
             tools,
+            reasoning_effort: self.reasoning_effort.clone(),
         };
 
         let response = self
@@ -385,6 +433,8 @@ impl Provider for OpenAiProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            reasoning_tokens: u.reasoning_tokens(),
+            cached_input_tokens: u.cached_input_tokens(),
         });
         let message = native_response
             .choices
@@ -430,6 +480,7 @@ impl Provider for OpenAiProvider {
             temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            reasoning_effort: self.reasoning_effort.clone(),
         };
 
         let response = self
@@ -448,6 +499,8 @@ impl Provider for OpenAiProvider {
         let usage = native_response.usage.map(|u| TokenUsage {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
+            reasoning_tokens: u.reasoning_tokens(),
+            cached_input_tokens: u.cached_input_tokens(),
         });
         let message = native_response
             .choices
@@ -527,6 +580,7 @@ mod tests {
                 },
             ],
             temperature: 0.7,
+            reasoning_effort: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"role\":\"system\""));
@@ -543,10 +597,58 @@ mod tests {
                 content: "hello".to_string(),
             }],
             temperature: 0.0,
+            reasoning_effort: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("system"));
         assert!(json.contains("\"temperature\":0.0"));
+        assert!(!json.contains("reasoning_effort"));
+    }
+
+    #[test]
+    fn request_serializes_configured_reasoning_effort() {
+        let provider = OpenAiProvider::new(None).with_reasoning_effort(Some(" High ".into()));
+        let req = ChatRequest {
+            model: "o3".to_string(),
+            messages: vec![],
+            temperature: 1.0,
+            reasoning_effort: provider.reasoning_effort.clone(),
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["reasoning_effort"], "high");
+    }
+
+    #[test]
+    fn usage_captures_reasoning_tokens() {
+        let json = r#"{
+            "choices": [{"message": {"content": "ok"}}],
+            "usage": {
+                "prompt_tokens": 12,
+                "completion_tokens": 40,
+                "completion_tokens_details": {"reasoning_tokens": 32}
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.completion_tokens, Some(40));
+        assert_eq!(usage.reasoning_tokens(), Some(32));
+        assert!(usage.cached_input_tokens().is_none());
+    }
+
+    #[test]
+    fn usage_captures_cached_prompt_tokens() {
+        let json = r#"{
+            "choices": [{"message": {"content": "ok"}}],
+            "usage": {
+                "prompt_tokens": 2100,
+                "completion_tokens": 40,
+                "prompt_tokens_details": {"cached_tokens": 1920}
+            }
+        }"#;
+        let resp: NativeChatResponse = serde_json::from_str(json).unwrap();
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, Some(2100));
+        assert_eq!(usage.cached_input_tokens(), Some(1920));
     }
 
     #[test]
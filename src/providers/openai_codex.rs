@@ -21,6 +21,7 @@ pub struct OpenAiCodexProvider {
     responses_url: String,
     custom_endpoint: bool,
     gateway_api_key: Option<String>,
+    reasoning_effort: Option<String>,
     client: Client,
 }
 
@@ -104,6 +105,7 @@ impl OpenAiCodexProvider {
             custom_endpoint: !is_default_responses_url(&responses_url),
             responses_url,
             gateway_api_key: gateway_api_key.map(ToString::to_string),
+            reasoning_effort: options.reasoning_effort.clone(),
             client: Client::builder()
                 .timeout(std::time::Duration::from_secs(120))
                 .connect_timeout(std::time::Duration::from_secs(10))
@@ -304,10 +306,11 @@ fn clamp_reasoning_effort(model: &str, effort: &str) -> String {
     effort.to_string()
 }
 
-fn resolve_reasoning_effort(model_id: &str) -> String {
+fn resolve_reasoning_effort(model_id: &str, configured: Option<&str>) -> String {
     let raw = std::env::var("ZEROCLAW_CODEX_REASONING_EFFORT")
         .ok()
         .and_then(|value| first_nonempty(Some(&value)))
+        .or_else(|| first_nonempty(configured))
         .unwrap_or_else(|| "xhigh".to_string())
         .to_ascii_lowercase();
     clamp_reasoning_effort(model_id, &raw)
@@ -572,7 +575,10 @@ impl OpenAiCodexProvider {
                 verbosity: "medium".to_string(),
             },
             reasoning: ResponsesReasoningOptions {
-                effort: resolve_reasoning_effort(normalized_model),
+                effort: resolve_reasoning_effort(
+                    normalized_model,
+                    self.reasoning_effort.as_deref(),
+                ),
                 summary: "auto".to_string(),
             },
             include: vec!["reasoning.encrypted_content".to_string()],
@@ -1018,6 +1024,9 @@ data: [DONE]
             secrets_encrypt: false,
             auth_profile_override: None,
             reasoning_enabled: None,
+            reasoning_effort: None,
+            thinking_budget_tokens: None,
+            debug_capture: None,
         };
         let provider =
             OpenAiCodexProvider::new(&options, None).expect("provider should initialize");
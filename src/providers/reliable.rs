@@ -1,3 +1,4 @@
+use super::key_pool::{KeyOutcome, KeyPool};
 use super::traits::{
     ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
 };
@@ -5,7 +6,6 @@ use super::Provider;
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 // ── Error Classification ─────────────────────────────────────────────────
@@ -14,6 +14,20 @@ use std::time::Duration;
 // the retry loop continues, falls back to the next provider, or aborts
 // immediately — avoiding wasted latency on errors that cannot self-heal.
 
+const AUTH_FAILURE_HINTS: [&str; 11] = [
+    "invalid api key",
+    "incorrect api key",
+    "missing api key",
+    "api key not set",
+    "authentication failed",
+    "auth failed",
+    "unauthorized",
+    "forbidden",
+    "permission denied",
+    "access denied",
+    "invalid token",
+];
+
 /// Check if an error is non-retryable (client errors that won't resolve with retries).
 fn is_non_retryable(err: &anyhow::Error) -> bool {
     if is_context_window_exceeded(err) {
@@ -42,21 +56,7 @@ fn is_non_retryable(err: &anyhow::Error) -> bool {
     // Heuristic: detect auth/model failures by keyword when no HTTP status
     // is available (e.g. gRPC or custom transport errors).
     let msg_lower = msg.to_lowercase();
-    let auth_failure_hints = [
-        "invalid api key",
-        "incorrect api key",
-        "missing api key",
-        "api key not set",
-        "authentication failed",
-        "auth failed",
-        "unauthorized",
-        "forbidden",
-        "permission denied",
-        "access denied",
-        "invalid token",
-    ];
-
-    if auth_failure_hints
+    if AUTH_FAILURE_HINTS
         .iter()
         .any(|hint| msg_lower.contains(hint))
     {
@@ -99,6 +99,35 @@ fn is_rate_limited(err: &anyhow::Error) -> bool {
         && (msg.contains("Too Many") || msg.contains("rate") || msg.contains("limit"))
 }
 
+/// Check if the credentials were refused (401/403 or an auth error message).
+fn is_auth_failure(err: &anyhow::Error) -> bool {
+    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+        if let Some(status) = reqwest_err.status() {
+            return matches!(status.as_u16(), 401 | 403);
+        }
+    }
+    let msg = err.to_string();
+    if msg
+        .split(|c: char| !c.is_ascii_digit())
+        .any(|word| word == "401" || word == "403")
+    {
+        return true;
+    }
+    let lower = msg.to_lowercase();
+    AUTH_FAILURE_HINTS.iter().any(|hint| lower.contains(hint))
+}
+
+/// How an error reflects on the API key that made the request.
+fn key_outcome(err: &anyhow::Error) -> KeyOutcome {
+    if is_non_retryable_rate_limit(err) || is_auth_failure(err) {
+        KeyOutcome::Rejected
+    } else if is_rate_limited(err) {
+        KeyOutcome::RateLimited(parse_retry_after_ms(err).map(Duration::from_millis))
+    } else {
+        KeyOutcome::Error
+    }
+}
+
 /// Check if a 429 is a business/quota-plan error that retries cannot fix.
 ///
 /// Examples:
@@ -226,9 +255,10 @@ pub struct ReliableProvider {
     providers: Vec<(String, Box<dyn Provider>)>,
     max_retries: u32,
     base_backoff_ms: u64,
-    /// Extra API keys for rotation (index tracks round-robin position).
-    api_keys: Vec<String>,
-    key_index: AtomicUsize,
+    /// Health-aware choice among the primary provider's API keys.
+    key_pool: Option<KeyPool>,
+    /// Primary provider instances for pool keys 1.. (key 0 is `providers[0]`).
+    keyed_providers: Vec<Box<dyn Provider>>,
     /// Per-model fallback chains: model_name → [fallback_model_1, fallback_model_2, ...]
     model_fallbacks: HashMap<String, Vec<String>>,
 }
@@ -243,24 +273,94 @@ impl ReliableProvider {
             providers,
             max_retries,
             base_backoff_ms: base_backoff_ms.max(50),
-            api_keys: Vec::new(),
-            key_index: AtomicUsize::new(0),
+            key_pool: None,
+            keyed_providers: Vec::new(),
             model_fallbacks: HashMap::new(),
         }
     }
 
-    /// Set additional API keys for round-robin rotation on rate-limit errors.
-    pub fn with_api_keys(mut self, keys: Vec<String>) -> Self {
-        self.api_keys = keys;
+    /// Spread primary-provider calls over a key pool. `keyed_providers[i]` is
+    /// the primary provider built with pool key `i + 1`; key 0 is the primary's own.
+    pub fn with_key_pool(mut self, pool: KeyPool, keyed_providers: Vec<Box<dyn Provider>>) -> Self {
+        if pool.len() == keyed_providers.len() + 1 && !self.providers.is_empty() {
+            self.key_pool = Some(pool);
+            self.keyed_providers = keyed_providers;
+        }
         self
     }
 
+    /// Per-key health and counters, when a key pool is configured.
+    pub fn key_pool(&self) -> Option<&KeyPool> {
+        self.key_pool.as_ref()
+    }
+
     /// Set per-model fallback chains.
     pub fn with_model_fallbacks(mut self, fallbacks: HashMap<String, Vec<String>>) -> Self {
         self.model_fallbacks = fallbacks;
         self
     }
 
+    /// Start a stream on the first provider that supports streaming.
+    fn stream_with_first_capable(
+        &self,
+        model: &str,
+        options: StreamOptions,
+        start: impl FnOnce(&dyn Provider, &str) -> stream::BoxStream<'static, StreamResult<StreamChunk>>,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        // For streaming, we use the first provider that supports it and has streaming enabled
+        let Some((provider_name, provider)) = self
+            .providers
+            .iter()
+            .find(|(_, p)| p.supports_streaming() && options.enabled)
+        else {
+            // No streaming support available
+            return stream::once(async move {
+                Err(super::traits::StreamError::Provider(
+                    "No provider supports streaming".to_string(),
+                ))
+            })
+            .boxed();
+        };
+
+        // Clone provider data for the stream
+        let provider_clone = provider_name.clone();
+
+        // Try the first model in the chain for streaming
+        let current_model = match self.model_chain(model).first() {
+            Some(m) => m.to_string(),
+            None => model.to_string(),
+        };
+
+        // For streaming, we attempt once and propagate errors
+        // The caller can retry the entire request if needed
+        let stream = start(provider.as_ref(), &current_model);
+
+        // Use a channel to bridge the stream with logging
+        let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
+
+        tokio::spawn(async move {
+            let mut stream = stream;
+            while let Some(chunk) = stream.next().await {
+                if let Err(ref e) = chunk {
+                    tracing::warn!(
+                        provider = provider_clone,
+                        model = current_model,
+                        "Streaming error: {e}"
+                    );
+                }
+                if tx.send(chunk).await.is_err() {
+                    break; // Receiver dropped
+                }
+            }
+        });
+
+        // Convert channel receiver to stream
+        stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        })
+        .boxed()
+    }
+
     /// Build the list of models to try: [original, fallback1, fallback2, ...]
     fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
         let mut chain = vec![model];
@@ -270,13 +370,33 @@ impl ReliableProvider {
         chain
     }
 
-    /// Advance to the next API key and return it, or None if no extra keys configured.
-    fn rotate_key(&self) -> Option<&str> {
-        if self.api_keys.is_empty() {
-            return None;
+    /// Instance to call for `providers[index]`, and the pool key it uses.
+    fn keyed_provider<'a>(
+        &'a self,
+        index: usize,
+        provider: &'a dyn Provider,
+    ) -> (&'a dyn Provider, Option<usize>) {
+        let Some(pool) = self.key_pool.as_ref().filter(|_| index == 0) else {
+            return (provider, None);
+        };
+        match pool.select() {
+            Some(0) => (provider, Some(0)),
+            Some(key) => (self.keyed_providers[key - 1].as_ref(), Some(key)),
+            None => (provider, None),
         }
-        let idx = self.key_index.fetch_add(1, Ordering::Relaxed) % self.api_keys.len();
-        Some(&self.api_keys[idx])
+    }
+
+    /// Report a call's result against its pool key. Returns true when the key
+    /// itself failed and another healthy key can take the retry.
+    fn record_key_result(&self, key: Option<usize>, error: Option<&anyhow::Error>) -> bool {
+        let (Some(pool), Some(key)) = (self.key_pool.as_ref(), key) else {
+            return false;
+        };
+        let outcome = error.map_or(KeyOutcome::Success, key_outcome);
+        pool.record(key, outcome);
+        outcome != KeyOutcome::Success
+            && outcome != KeyOutcome::Error
+            && pool.has_healthy_alternative(key)
     }
 
     /// Compute backoff duration, respecting Retry-After if present.
@@ -317,15 +437,17 @@ impl Provider for ReliableProvider {
         // immediately. On non-retryable error, break to next provider. On
         // retryable error, sleep with exponential backoff and retry.
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let (provider, key) = self.keyed_provider(index, provider.as_ref());
                     match provider
                         .chat_with_system(system_prompt, message, current_model, temperature)
                         .await
                     {
                         Ok(resp) => {
+                            self.record_key_result(key, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -354,19 +476,16 @@ impl Provider for ReliableProvider {
                                 &error_detail,
                             );
 
-                            // Rate-limit with rotatable keys: cycle to the next API key
-                            // so the retry hits a different quota bucket.
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
+                            // A refused or rate-limited key sits out; retry at once
+                            // with another key from the pool.
+                            if self.record_key_result(key, Some(&e)) {
+                                tracing::warn!(
+                                    provider = provider_name,
+                                    reason = failure_reason,
+                                    error = %error_detail,
+                                    "API key failed; retrying with another key"
+                                );
+                                continue;
                             }
 
                             if non_retryable {
@@ -437,15 +556,17 @@ impl Provider for ReliableProvider {
         let mut failures = Vec::new();
 
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let (provider, key) = self.keyed_provider(index, provider.as_ref());
                     match provider
                         .chat_with_history(messages, current_model, temperature)
                         .await
                     {
                         Ok(resp) => {
+                            self.record_key_result(key, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -474,17 +595,16 @@ impl Provider for ReliableProvider {
                                 &error_detail,
                             );
 
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
+                            // A refused or rate-limited key sits out; retry at once
+                            // with another key from the pool.
+                            if self.record_key_result(key, Some(&e)) {
+                                tracing::warn!(
+                                    provider = provider_name,
+                                    reason = failure_reason,
+                                    error = %error_detail,
+                                    "API key failed; retrying with another key"
+                                );
+                                continue;
                             }
 
                             if non_retryable {
@@ -561,15 +681,17 @@ impl Provider for ReliableProvider {
         let mut failures = Vec::new();
 
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let (provider, key) = self.keyed_provider(index, provider.as_ref());
                     match provider
                         .chat_with_tools(messages, tools, current_model, temperature)
                         .await
                     {
                         Ok(resp) => {
+                            self.record_key_result(key, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -598,17 +720,16 @@ impl Provider for ReliableProvider {
                                 &error_detail,
                             );
 
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
+                            // A refused or rate-limited key sits out; retry at once
+                            // with another key from the pool.
+                            if self.record_key_result(key, Some(&e)) {
+                                tracing::warn!(
+                                    provider = provider_name,
+                                    reason = failure_reason,
+                                    error = %error_detail,
+                                    "API key failed; retrying with another key"
+                                );
+                                continue;
                             }
 
                             if non_retryable {
@@ -671,16 +792,18 @@ impl Provider for ReliableProvider {
         let mut failures = Vec::new();
 
         for current_model in &models {
-            for (provider_name, provider) in &self.providers {
+            for (index, (provider_name, provider)) in self.providers.iter().enumerate() {
                 let mut backoff_ms = self.base_backoff_ms;
 
                 for attempt in 0..=self.max_retries {
+                    let (provider, key) = self.keyed_provider(index, provider.as_ref());
                     let req = ChatRequest {
                         messages: request.messages,
                         tools: request.tools,
                     };
                     match provider.chat(req, current_model, temperature).await {
                         Ok(resp) => {
+                            self.record_key_result(key, None);
                             if attempt > 0 || *current_model != model {
                                 tracing::info!(
                                     provider = provider_name,
@@ -709,17 +832,16 @@ impl Provider for ReliableProvider {
                                 &error_detail,
                             );
 
-                            if rate_limited && !non_retryable_rate_limit {
-                                if let Some(new_key) = self.rotate_key() {
-                                    tracing::warn!(
-                                        provider = provider_name,
-                                        error = %error_detail,
-                                        "Rate limited; key rotation selected key ending ...{} \
-                                         but cannot apply (Provider trait has no set_api_key). \
-                                         Retrying with original key.",
-                                        &new_key[new_key.len().saturating_sub(4)..]
-                                    );
-                                }
+                            // A refused or rate-limited key sits out; retry at once
+                            // with another key from the pool.
+                            if self.record_key_result(key, Some(&e)) {
+                                tracing::warn!(
+                                    provider = provider_name,
+                                    reason = failure_reason,
+                                    error = %error_detail,
+                                    "API key failed; retrying with another key"
+                                );
+                                continue;
                             }
 
                             if non_retryable {
@@ -792,71 +914,34 @@ impl Provider for ReliableProvider {
         temperature: f64,
         options: StreamOptions,
     ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
-        // Try each provider/model combination for streaming
-        // For streaming, we use the first provider that supports it and has streaming enabled
-        for (provider_name, provider) in &self.providers {
-            if !provider.supports_streaming() || !options.enabled {
-                continue;
-            }
-
-            // Clone provider data for the stream
-            let provider_clone = provider_name.clone();
-
-            // Try the first model in the chain for streaming
-            let current_model = match self.model_chain(model).first() {
-                Some(m) => m.to_string(),
-                None => model.to_string(),
-            };
-
-            // For streaming, we attempt once and propagate errors
-            // The caller can retry the entire request if needed
-            let stream = provider.stream_chat_with_system(
+        self.stream_with_first_capable(model, options, |provider, current_model| {
+            provider.stream_chat_with_system(
                 system_prompt,
                 message,
-                &current_model,
+                current_model,
                 temperature,
                 options,
-            );
-
-            // Use a channel to bridge the stream with logging
-            let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<StreamChunk>>(100);
-
-            tokio::spawn(async move {
-                let mut stream = stream;
-                while let Some(chunk) = stream.next().await {
-                    if let Err(ref e) = chunk {
-                        tracing::warn!(
-                            provider = provider_clone,
-                            model = current_model,
-                            "Streaming error: {e}"
-                        );
-                    }
-                    if tx.send(chunk).await.is_err() {
-                        break; // Receiver dropped
-                    }
-                }
-            });
-
-            // Convert channel receiver to stream
-            return stream::unfold(rx, |mut rx| async move {
-                rx.recv().await.map(|chunk| (chunk, rx))
-            })
-            .boxed();
-        }
+            )
+        })
+    }
 
-        // No streaming support available
-        stream::once(async move {
-            Err(super::traits::StreamError::Provider(
-                "No provider supports streaming".to_string(),
-            ))
+    fn stream_chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+        options: StreamOptions,
+    ) -> stream::BoxStream<'static, StreamResult<StreamChunk>> {
+        self.stream_with_first_capable(model, options, |provider, current_model| {
+            provider.stream_chat_with_history(messages, current_model, temperature, options)
         })
-        .boxed()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
     struct MockProvider {
@@ -1335,34 +1420,132 @@ mod tests {
         assert_eq!(calls.load(Ordering::SeqCst), 1);
     }
 
-    // ── New tests: auth rotation ──
+    // ── Key pool ──
+
+    fn mock(
+        calls: &Arc<AtomicUsize>,
+        fail_until_attempt: usize,
+        error: &'static str,
+    ) -> Box<dyn Provider> {
+        Box::new(MockProvider {
+            calls: Arc::clone(calls),
+            fail_until_attempt,
+            response: "ok",
+            error,
+        })
+    }
 
     #[tokio::test]
-    async fn auth_rotation_cycles_keys() {
+    async fn rate_limited_key_is_skipped_for_the_next_key() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let spare_calls = Arc::new(AtomicUsize::new(0));
         let provider = ReliableProvider::new(
             vec![(
                 "p".into(),
-                Box::new(MockProvider {
-                    calls: Arc::new(AtomicUsize::new(0)),
-                    fail_until_attempt: 0,
-                    response: "ok",
-                    error: "",
-                }),
+                mock(
+                    &primary_calls,
+                    usize::MAX,
+                    "429 Too Many Requests: rate limit",
+                ),
             )],
-            0,
+            1,
             1,
         )
-        .with_api_keys(vec!["key-a".into(), "key-b".into(), "key-c".into()]);
+        .with_key_pool(
+            KeyPool::new(
+                vec!["key-a".into(), "key-b".into()],
+                &[],
+                Duration::from_secs(60),
+            ),
+            vec![mock(&spare_calls, 0, "")],
+        );
 
-        // Rotate 5 times, verify round-robin
-        let keys: Vec<&str> = (0..5).map(|_| provider.rotate_key().unwrap()).collect();
-        assert_eq!(keys, vec!["key-a", "key-b", "key-c", "key-a", "key-b"]);
+        for _ in 0..3 {
+            let result = provider.chat_with_system(None, "hi", "m", 0.0).await;
+            assert_eq!(result.unwrap(), "ok");
+        }
+        // The first call hits key-a once; after that it sits in quarantine.
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(spare_calls.load(Ordering::SeqCst), 3);
+
+        let snapshot = provider.key_pool().unwrap().snapshot();
+        assert_eq!(snapshot[0].stats.rate_limited, 1);
+        assert!(snapshot[0].quarantined_for.is_some());
+        assert_eq!(snapshot[1].stats.successes, 3);
     }
 
     #[tokio::test]
-    async fn auth_rotation_returns_none_when_empty() {
-        let provider = ReliableProvider::new(vec![], 0, 1);
-        assert!(provider.rotate_key().is_none());
+    async fn rejected_key_retries_with_another_key_instead_of_failing_over() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let spare_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "p".into(),
+                    mock(
+                        &primary_calls,
+                        usize::MAX,
+                        "401 Unauthorized: invalid api key",
+                    ),
+                ),
+                ("fallback".into(), mock(&fallback_calls, 0, "")),
+            ],
+            1,
+            1,
+        )
+        .with_key_pool(
+            KeyPool::new(
+                vec!["key-a".into(), "key-b".into()],
+                &[],
+                Duration::from_secs(60),
+            ),
+            vec![mock(&spare_calls, 0, "")],
+        );
+
+        assert_eq!(
+            provider
+                .chat_with_system(None, "hi", "m", 0.0)
+                .await
+                .unwrap(),
+            "ok"
+        );
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(spare_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn key_pool_requires_one_instance_per_extra_key() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(vec![("p".into(), mock(&calls, 0, ""))], 0, 1)
+            .with_key_pool(
+                KeyPool::new(vec!["a".into(), "b".into()], &[], Duration::from_secs(60)),
+                Vec::new(),
+            );
+        assert!(provider.key_pool().is_none());
+    }
+
+    #[test]
+    fn key_outcome_classifies_key_failures() {
+        assert_eq!(
+            key_outcome(&anyhow::anyhow!("API error (401 Unauthorized)")),
+            KeyOutcome::Rejected
+        );
+        assert_eq!(
+            key_outcome(&anyhow::anyhow!(
+                "429 Too Many Requests: insufficient_quota"
+            )),
+            KeyOutcome::Rejected
+        );
+        assert_eq!(
+            key_outcome(&anyhow::anyhow!("429 Too Many Requests, retry-after: 3")),
+            KeyOutcome::RateLimited(Some(Duration::from_secs(3)))
+        );
+        assert_eq!(
+            key_outcome(&anyhow::anyhow!("500 Internal Server Error")),
+            KeyOutcome::Error
+        );
     }
 
     // ── New tests: Retry-After parsing ──
@@ -41,6 +41,22 @@ impl ChatMessage {
     }
 }
 
+/// Heading that separates the stable part of a system prompt (instructions,
+/// tools, skills, workspace files) from context that changes per message.
+/// Providers with prompt caching cache everything before it; to the model it
+/// is an ordinary section heading.
+pub const SYSTEM_PROMPT_CACHE_BOUNDARY: &str = "\n\n## Message Context\n\n";
+
+/// Split a system prompt at [`SYSTEM_PROMPT_CACHE_BOUNDARY`] into its stable
+/// prefix and the per-message rest. The boundary stays with the rest, so the
+/// two halves concatenate back to `system`.
+pub fn split_system_prompt_for_cache(system: &str) -> (&str, Option<&str>) {
+    match system.find(SYSTEM_PROMPT_CACHE_BOUNDARY) {
+        Some(at) => (&system[..at], Some(&system[at..])),
+        None => (system, None),
+    }
+}
+
 /// A tool call requested by the LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -54,6 +70,12 @@ pub struct ToolCall {
 pub struct TokenUsage {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
+    /// Portion of `output_tokens` spent on hidden reasoning/thinking, when the
+    /// provider reports it separately (e.g. OpenAI `reasoning_tokens`).
+    pub reasoning_tokens: Option<u64>,
+    /// Portion of `input_tokens` served from the provider's prompt cache
+    /// (Anthropic `cache_read_input_tokens`, OpenAI `cached_tokens`).
+    pub cached_input_tokens: Option<u64>,
 }
 
 /// An LLM response that may contain text, tool calls, or both.
@@ -560,6 +582,19 @@ mod tests {
         assert!(usage.output_tokens.is_none());
     }
 
+    #[test]
+    fn system_prompt_splits_at_cache_boundary() {
+        let system = format!("Stable rules{SYSTEM_PROMPT_CACHE_BOUNDARY}Today's goals");
+        let (stable, rest) = split_system_prompt_for_cache(&system);
+        assert_eq!(stable, "Stable rules");
+        assert_eq!(format!("{stable}{}", rest.unwrap()), system);
+
+        assert_eq!(
+            split_system_prompt_for_cache("No boundary"),
+            ("No boundary", None)
+        );
+    }
+
     #[test]
     fn chat_response_with_usage() {
         let resp = ChatResponse {
@@ -568,6 +603,8 @@ mod tests {
             usage: Some(TokenUsage {
                 input_tokens: Some(100),
                 output_tokens: Some(50),
+                reasoning_tokens: None,
+                cached_input_tokens: None,
             }),
             reasoning_content: None,
         };
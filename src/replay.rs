@@ -0,0 +1,365 @@
+//! Replay of captured provider calls (`zeroclaw replay <capture-file>`).
+//!
+//! Loads a request written by debug capture (see
+//! [`crate::providers::capture`]), sends it again through a provider built
+//! from the current config, optionally with a different provider, model, or
+//! temperature, and diffs the reply text and tool calls against the captured
+//! response. Tool calls are compared by name and arguments; call ids differ
+//! on every run and are ignored.
+
+use crate::config::Config;
+use crate::providers::capture::{
+    self, REQUEST_SUFFIX, RESPONSE_SUFFIX, SYSTEM_PROMPT_PLACEHOLDER, SYSTEM_PROMPT_SUFFIX,
+};
+use crate::providers::{self, ChatMessage, ChatRequest, Provider};
+use crate::tools::ToolSpec;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Deserialize)]
+struct CapturedRequest {
+    method: String,
+    model: String,
+    temperature: f64,
+    messages: Vec<ChatMessage>,
+    #[serde(default)]
+    tools: Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CapturedResponse {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<CapturedToolCall>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CapturedToolCall {
+    name: String,
+    arguments: String,
+}
+
+/// A captured call, with the system prompt restored.
+#[derive(Debug)]
+struct Capture {
+    stem: PathBuf,
+    request: CapturedRequest,
+    response: Option<CapturedResponse>,
+}
+
+/// Result of running a call once: reply text and `name arguments` lines.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Outcome {
+    text: String,
+    tool_calls: Vec<String>,
+    error: Option<String>,
+}
+
+impl Capture {
+    /// Load from any of a capture's files or their shared stem.
+    fn load(path: &Path) -> Result<Self> {
+        let raw = path.to_string_lossy();
+        let stem = [REQUEST_SUFFIX, RESPONSE_SUFFIX, SYSTEM_PROMPT_SUFFIX]
+            .iter()
+            .find_map(|suffix| raw.strip_suffix(suffix))
+            .unwrap_or(&raw)
+            .to_string();
+        let with_suffix = |suffix: &str| PathBuf::from(format!("{stem}{suffix}"));
+
+        let request_path = with_suffix(REQUEST_SUFFIX);
+        let request_raw = std::fs::read_to_string(&request_path)
+            .with_context(|| format!("Failed to read capture {}", request_path.display()))?;
+        let mut request: CapturedRequest = serde_json::from_str(&request_raw)
+            .with_context(|| format!("Not a debug capture: {}", request_path.display()))?;
+
+        if let Some(system) = request
+            .messages
+            .iter_mut()
+            .find(|m| m.role == "system" && m.content == SYSTEM_PROMPT_PLACEHOLDER)
+        {
+            let prompt_path = with_suffix(SYSTEM_PROMPT_SUFFIX);
+            system.content = std::fs::read_to_string(&prompt_path).with_context(|| {
+                format!("Failed to read system prompt {}", prompt_path.display())
+            })?;
+        }
+
+        let response = match std::fs::read_to_string(with_suffix(RESPONSE_SUFFIX)) {
+            Ok(raw) => Some(serde_json::from_str(&raw).context("Invalid captured response")?),
+            Err(_) => None,
+        };
+
+        Ok(Self {
+            stem: PathBuf::from(stem),
+            request,
+            response,
+        })
+    }
+
+    fn captured_outcome(&self) -> Option<Outcome> {
+        let response = self.response.as_ref()?;
+        Some(Outcome {
+            text: response.text.clone().unwrap_or_default(),
+            tool_calls: response
+                .tool_calls
+                .iter()
+                .map(|call| tool_call_line(&call.name, &call.arguments))
+                .collect(),
+            error: response.error.clone(),
+        })
+    }
+}
+
+/// `name {arguments}` with arguments re-serialized so formatting differences
+/// do not count as changes.
+fn tool_call_line(name: &str, arguments: &str) -> String {
+    let arguments = serde_json::from_str::<Value>(arguments)
+        .map(|value| value.to_string())
+        .unwrap_or_else(|_| arguments.to_string());
+    format!("{name} {arguments}")
+}
+
+async fn run_once(
+    provider: &dyn Provider,
+    request: &CapturedRequest,
+    model: &str,
+    temperature: f64,
+) -> Outcome {
+    let messages = &request.messages;
+    let result = match request.method.as_str() {
+        "chat" => {
+            let tools: Option<Vec<ToolSpec>> = serde_json::from_value(request.tools.clone()).ok();
+            provider
+                .chat(
+                    ChatRequest {
+                        messages,
+                        tools: tools.as_deref(),
+                    },
+                    model,
+                    temperature,
+                )
+                .await
+        }
+        "chat_with_tools" => {
+            let tools = request.tools.as_array().cloned().unwrap_or_default();
+            provider
+                .chat_with_tools(messages, &tools, model, temperature)
+                .await
+        }
+        _ => provider
+            .chat_with_history(messages, model, temperature)
+            .await
+            .map(|text| providers::ChatResponse {
+                text: Some(text),
+                tool_calls: Vec::new(),
+                usage: None,
+                reasoning_content: None,
+            }),
+    };
+
+    match result {
+        Ok(response) => Outcome {
+            text: capture::redact(response.text_or_empty()),
+            tool_calls: response
+                .tool_calls
+                .iter()
+                .map(|call| tool_call_line(&call.name, &capture::redact(&call.arguments)))
+                .collect(),
+            error: None,
+        },
+        Err(e) => Outcome {
+            error: Some(capture::redact(&format!("{e:#}"))),
+            ..Outcome::default()
+        },
+    }
+}
+
+/// Line diff via longest common subsequence; unchanged lines are prefixed
+/// with two spaces, removals with `- `, additions with `+ `.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<String> {
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            out.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+fn print_section(title: &str, old: &[&str], new: &[&str]) {
+    if old == new {
+        println!("{title}: unchanged");
+        return;
+    }
+    println!("{title}: changed");
+    for line in diff_lines(old, new) {
+        println!("  {line}");
+    }
+}
+
+fn print_comparison(captured: &Outcome, replayed: &Outcome) {
+    if captured.error.is_some() || replayed.error.is_some() {
+        let show = |error: &Option<String>| error.as_deref().unwrap_or("none").to_string();
+        println!(
+            "Error: {} → {}",
+            show(&captured.error),
+            show(&replayed.error)
+        );
+    }
+    let old_calls: Vec<&str> = captured.tool_calls.iter().map(String::as_str).collect();
+    let new_calls: Vec<&str> = replayed.tool_calls.iter().map(String::as_str).collect();
+    print_section("Tool calls", &old_calls, &new_calls);
+    let old_text: Vec<&str> = captured.text.lines().collect();
+    let new_text: Vec<&str> = replayed.text.lines().collect();
+    print_section("Reply", &old_text, &new_text);
+}
+
+pub async fn run(
+    config: &Config,
+    capture_path: &Path,
+    provider_override: Option<String>,
+    model_override: Option<String>,
+    temperature_override: Option<f64>,
+) -> Result<()> {
+    let capture = Capture::load(capture_path)?;
+    let provider_name = provider_override
+        .as_deref()
+        .or(config.default_provider.as_deref())
+        .unwrap_or("openrouter");
+    let model = model_override.unwrap_or_else(|| capture.request.model.clone());
+    let temperature = temperature_override.unwrap_or(capture.request.temperature);
+    if capture.request.messages.is_empty() {
+        bail!("Capture {} has no messages", capture.stem.display());
+    }
+
+    let options = providers::ProviderRuntimeOptions {
+        auth_profile_override: None,
+        provider_api_url: config.api_url.clone(),
+        zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+        secrets_encrypt: config.secrets.encrypt,
+        reasoning_enabled: config.runtime.reasoning_enabled,
+        reasoning_effort: config.runtime.reasoning_effort.clone(),
+        thinking_budget_tokens: config.runtime.thinking_budget_tokens,
+        debug_capture: providers::capture::DebugCapture::from_config(config),
+    };
+    let provider = providers::create_routed_provider_with_options(
+        provider_name,
+        config.api_key.as_deref(),
+        config.api_url.as_deref(),
+        &config.reliability,
+        &config.model_routes,
+        &model,
+        &options,
+    )?;
+
+    println!(
+        "🔁 Replaying {} ({}, {} @ {} → {provider_name}/{model} @ {temperature})",
+        capture.stem.display(),
+        capture.request.method,
+        capture.request.model,
+        capture.request.temperature
+    );
+    let replayed = run_once(provider.as_ref(), &capture.request, &model, temperature).await;
+
+    match capture.captured_outcome() {
+        Some(captured) => print_comparison(&captured, &replayed),
+        None => {
+            println!("No captured response to compare against; replayed result:");
+            print_comparison(&Outcome::default(), &replayed);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::capture::DebugCapture;
+    use async_trait::async_trait;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    struct FixedProvider(&'static str);
+
+    #[async_trait]
+    impl Provider for FixedProvider {
+        async fn chat_with_system(
+            &self,
+            system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(format!("{}\n{}", system_prompt.unwrap_or_default(), self.0))
+        }
+    }
+
+    #[test]
+    fn diff_lines_marks_additions_and_removals() {
+        let diff = diff_lines(&["a", "b", "c"], &["a", "x", "c", "d"]);
+        assert_eq!(diff, ["  a", "- b", "+ x", "  c", "+ d"]);
+    }
+
+    #[test]
+    fn tool_call_lines_ignore_argument_formatting() {
+        assert_eq!(
+            tool_call_line("shell", "{ \"command\" : \"ls\" }"),
+            tool_call_line("shell", "{\"command\":\"ls\"}")
+        );
+    }
+
+    #[tokio::test]
+    async fn captured_call_replays_with_its_system_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let capture = Arc::new(DebugCapture::new(tmp.path().to_path_buf(), 5));
+        let provider = providers::capture::wrap(Box::new(FixedProvider("v1")), Some(&capture));
+        provider
+            .chat_with_history(
+                &[ChatMessage::system("Be brief."), ChatMessage::user("hi")],
+                "model-a",
+                0.3,
+            )
+            .await
+            .unwrap();
+
+        let request_file = std::fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.to_string_lossy().ends_with(REQUEST_SUFFIX))
+            .unwrap();
+        let loaded = Capture::load(&request_file).unwrap();
+        assert_eq!(loaded.request.model, "model-a");
+        assert_eq!(loaded.request.messages[0].content, "Be brief.");
+
+        let captured = loaded.captured_outcome().unwrap();
+        let same = run_once(&FixedProvider("v1"), &loaded.request, "model-a", 0.3).await;
+        assert_eq!(captured, same);
+        let changed = run_once(&FixedProvider("v2"), &loaded.request, "model-b", 0.3).await;
+        assert_ne!(captured.text, changed.text);
+        assert_eq!(changed.text, "Be brief.\nv2");
+    }
+}
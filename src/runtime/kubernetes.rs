@@ -0,0 +1,249 @@
+use super::traits::RuntimeAdapter;
+use crate::config::KubernetesRuntimeConfig;
+use crate::util::shell_quote;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Environment `kubectl` needs to find its cluster. The shell tool clears the
+/// environment before spawning, so these are baked into the script instead.
+const KUBECTL_ENV_VARS: &[&str] = &[
+    "KUBECONFIG",
+    "KUBERNETES_SERVICE_HOST",
+    "KUBERNETES_SERVICE_PORT",
+];
+
+/// Kubernetes runtime: every command runs in its own ephemeral Job.
+///
+/// The Job is created with `kubectl`, its pod logs are streamed back as the
+/// command output, the container's exit code becomes the command's exit code,
+/// and the Job is deleted afterwards. Nothing runs in the daemon's own pod.
+#[derive(Debug, Clone)]
+pub struct KubernetesRuntime {
+    config: KubernetesRuntimeConfig,
+}
+
+impl KubernetesRuntime {
+    pub fn new(config: KubernetesRuntimeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Job manifest running `command` with `sh -c`.
+    fn job_manifest(&self, job_name: &str, command: &str) -> serde_json::Value {
+        let mut limits = serde_json::Map::new();
+        if let Some(memory_limit_mb) = self.config.memory_limit_mb.filter(|mb| *mb > 0) {
+            limits.insert("memory".into(), json!(format!("{memory_limit_mb}Mi")));
+        }
+        if let Some(cpu_limit) = self.config.cpu_limit.filter(|cpus| *cpus > 0.0) {
+            limits.insert("cpu".into(), json!(cpu_limit.to_string()));
+        }
+        let labels = json!({ "app.kubernetes.io/managed-by": "zeroclaw" });
+
+        let mut pod_spec = json!({
+            "restartPolicy": "Never",
+            "automountServiceAccountToken": self.config.service_account.is_some(),
+            "containers": [{
+                "name": "shell",
+                "image": self.config.image.trim(),
+                "command": ["sh", "-c", command],
+                "resources": { "limits": limits.clone(), "requests": limits },
+                "securityContext": { "allowPrivilegeEscalation": false },
+            }],
+        });
+        if let Some(account) = &self.config.service_account {
+            pod_spec["serviceAccountName"] = json!(account);
+        }
+
+        json!({
+            "apiVersion": "batch/v1",
+            "kind": "Job",
+            "metadata": { "name": job_name, "labels": labels },
+            "spec": {
+                "backoffLimit": 0,
+                "ttlSecondsAfterFinished": 60,
+                "activeDeadlineSeconds": self.config.active_deadline_secs,
+                "template": {
+                    "metadata": { "labels": labels },
+                    "spec": pod_spec,
+                },
+            },
+        })
+    }
+
+    /// Shell script that creates the Job, streams its logs, exits with the
+    /// container's exit code, and deletes the Job on the way out.
+    fn job_script(&self, job_name: &str, command: &str) -> String {
+        let mut kubectl = String::from("kubectl");
+        if let Some(kubeconfig) = &self.config.kubeconfig {
+            let path = shellexpand::tilde(kubeconfig.trim());
+            let _ = write!(kubectl, " --kubeconfig {}", shell_quote(&path));
+        }
+        if let Some(context) = &self.config.context {
+            let _ = write!(kubectl, " --context {}", shell_quote(context));
+        }
+        let _ = write!(
+            kubectl,
+            " --namespace {}",
+            shell_quote(&self.config.namespace)
+        );
+
+        let mut script = String::new();
+        for var in KUBECTL_ENV_VARS {
+            if let Ok(value) = std::env::var(var) {
+                let _ = writeln!(script, "export {var}={}", shell_quote(&value));
+            }
+        }
+        let manifest = self.job_manifest(job_name, command).to_string();
+        let _ = write!(
+            script,
+            r#"job={job}
+k() {{ {kubectl} "$@"; }}
+trap 'k delete job "$job" --ignore-not-found --wait=false >/dev/null 2>&1' EXIT
+printf '%s' {manifest} | k create -f - >/dev/null || exit 1
+k logs -f --pod-running-timeout={start}s "job/$job"
+for _ in 1 2 3 4 5 6 7 8 9 10; do
+  code=$(k get pods -l "job-name=$job" -o 'jsonpath={{.items[0].status.containerStatuses[0].state.terminated.exitCode}}' 2>/dev/null)
+  [ -n "$code" ] && exit "$code"
+  sleep 1
+done
+echo "kubernetes job $job did not report an exit code" >&2
+exit 1
+"#,
+            job = shell_quote(job_name),
+            manifest = shell_quote(&manifest),
+            start = self.config.pod_start_timeout_secs,
+        );
+        script
+    }
+}
+
+impl RuntimeAdapter for KubernetesRuntime {
+    fn name(&self) -> &str {
+        "kubernetes"
+    }
+
+    fn has_shell_access(&self) -> bool {
+        true
+    }
+
+    fn has_filesystem_access(&self) -> bool {
+        false
+    }
+
+    fn storage_path(&self) -> PathBuf {
+        PathBuf::from("/tmp/.zeroclaw")
+    }
+
+    fn supports_long_running(&self) -> bool {
+        false
+    }
+
+    fn memory_budget(&self) -> u64 {
+        self.config
+            .memory_limit_mb
+            .map_or(0, |mb| mb.saturating_mul(1024 * 1024))
+    }
+
+    fn build_shell_command(
+        &self,
+        command: &str,
+        _workspace_dir: &Path,
+    ) -> anyhow::Result<tokio::process::Command> {
+        let job_name = format!(
+            "zeroclaw-job-{}",
+            &Uuid::new_v4().simple().to_string()[..12]
+        );
+        let mut process = tokio::process::Command::new("sh");
+        process.arg("-c").arg(self.job_script(&job_name, command));
+        Ok(process)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_manifest_applies_limits_and_service_account() {
+        let runtime = KubernetesRuntime::new(KubernetesRuntimeConfig {
+            service_account: Some("zeroclaw-runner".into()),
+            cpu_limit: Some(0.5),
+            ..KubernetesRuntimeConfig::default()
+        });
+        assert_eq!(runtime.name(), "kubernetes");
+        assert_eq!(runtime.memory_budget(), 512 * 1024 * 1024);
+
+        let manifest = runtime.job_manifest("zeroclaw-job-1", "echo hi");
+        assert_eq!(manifest["kind"], "Job");
+        assert_eq!(manifest["spec"]["backoffLimit"], 0);
+        assert_eq!(manifest["spec"]["activeDeadlineSeconds"], 120);
+        let pod = &manifest["spec"]["template"]["spec"];
+        assert_eq!(pod["serviceAccountName"], "zeroclaw-runner");
+        assert_eq!(pod["automountServiceAccountToken"], true);
+        let container = &pod["containers"][0];
+        assert_eq!(container["image"], "alpine:3.20");
+        assert_eq!(container["command"], json!(["sh", "-c", "echo hi"]));
+        assert_eq!(container["resources"]["limits"]["memory"], "512Mi");
+        assert_eq!(container["resources"]["limits"]["cpu"], "0.5");
+
+        let default = KubernetesRuntime::new(KubernetesRuntimeConfig::default());
+        let pod = &default.job_manifest("j", "true")["spec"]["template"]["spec"];
+        assert_eq!(pod["automountServiceAccountToken"], false);
+        assert!(pod["serviceAccountName"].is_null());
+    }
+
+    #[tokio::test]
+    async fn streams_job_logs_and_returns_container_exit_code() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let calls = tmp.path().join("calls.log");
+        let kubectl = tmp.path().join("kubectl");
+        std::fs::write(
+            &kubectl,
+            format!(
+                r#"#!/bin/sh
+echo "$*" >> {calls}
+case "$*" in
+  *" create -f -") cat > /dev/null ;;
+  *" logs "*) echo "hello from the pod" ;;
+  *" get pods "*) printf 3 ;;
+esac
+"#,
+                calls = calls.display()
+            ),
+        )
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&kubectl, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let runtime = KubernetesRuntime::new(KubernetesRuntimeConfig {
+            namespace: "agents".into(),
+            context: Some("prod".into()),
+            ..KubernetesRuntimeConfig::default()
+        });
+        let mut cmd = runtime
+            .build_shell_command("echo hello", Path::new("/ignored"))
+            .unwrap();
+        let path = format!(
+            "{}:{}",
+            tmp.path().display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let output = cmd.env("PATH", path).output().await.unwrap();
+
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "hello from the pod\n"
+        );
+        let calls = std::fs::read_to_string(&calls).unwrap();
+        let calls: Vec<&str> = calls.lines().collect();
+        assert_eq!(calls.len(), 4, "{calls:?}");
+        assert!(calls[0].starts_with("--context prod --namespace agents create"));
+        assert!(calls[1].contains("logs -f --pod-running-timeout=30s job/zeroclaw-job-"));
+        assert!(calls[3].contains("delete job zeroclaw-job-"));
+    }
+}
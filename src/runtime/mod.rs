@@ -1,9 +1,13 @@
 pub mod docker;
+pub mod kubernetes;
 pub mod native;
+pub mod ssh;
 pub mod traits;
 
 pub use docker::DockerRuntime;
+pub use kubernetes::KubernetesRuntime;
 pub use native::NativeRuntime;
+pub use ssh::SshRuntime;
 pub use traits::RuntimeAdapter;
 
 use crate::config::RuntimeConfig;
@@ -13,13 +17,21 @@ pub fn create_runtime(config: &RuntimeConfig) -> anyhow::Result<Box<dyn RuntimeA
     match config.kind.as_str() {
         "native" => Ok(Box::new(NativeRuntime::new())),
         "docker" => Ok(Box::new(DockerRuntime::new(config.docker.clone()))),
+        "kubernetes" => Ok(Box::new(KubernetesRuntime::new(config.kubernetes.clone()))),
+        "ssh" => Ok(Box::new(SshRuntime::from_default_host(&config.ssh)?)),
         "cloudflare" => anyhow::bail!(
             "runtime.kind='cloudflare' is not implemented yet. Use runtime.kind='native' for now."
         ),
         other if other.trim().is_empty() => {
-            anyhow::bail!("runtime.kind cannot be empty. Supported values: native, docker")
+            anyhow::bail!(
+                "runtime.kind cannot be empty. Supported values: native, docker, ssh, kubernetes"
+            )
+        }
+        other => {
+            anyhow::bail!(
+                "Unknown runtime kind '{other}'. Supported values: native, docker, ssh, kubernetes"
+            )
         }
-        other => anyhow::bail!("Unknown runtime kind '{other}'. Supported values: native, docker"),
     }
 }
 
@@ -49,6 +61,29 @@ mod tests {
         assert!(rt.has_shell_access());
     }
 
+    #[test]
+    fn factory_kubernetes() {
+        let cfg = RuntimeConfig {
+            kind: "kubernetes".into(),
+            ..RuntimeConfig::default()
+        };
+        let rt = create_runtime(&cfg).unwrap();
+        assert_eq!(rt.name(), "kubernetes");
+        assert!(!rt.has_filesystem_access());
+    }
+
+    #[test]
+    fn factory_ssh_requires_default_host() {
+        let cfg = RuntimeConfig {
+            kind: "ssh".into(),
+            ..RuntimeConfig::default()
+        };
+        match create_runtime(&cfg) {
+            Err(err) => assert!(err.to_string().contains("default_host")),
+            Ok(_) => panic!("ssh runtime without a host should error"),
+        }
+    }
+
     #[test]
     fn factory_cloudflare_errors() {
         let cfg = RuntimeConfig {
@@ -0,0 +1,184 @@
+use super::traits::RuntimeAdapter;
+use crate::config::{SshHostConfig, SshRuntimeConfig};
+use crate::util::shell_quote;
+use std::path::{Path, PathBuf};
+
+/// Runs commands on an allowlisted remote host over SSH.
+///
+/// Authentication is key-only (`BatchMode`, no password prompts) and the host
+/// key must already be known (`StrictHostKeyChecking=yes`), so a command can
+/// never hang on a prompt or silently trust a new host.
+#[derive(Debug, Clone)]
+pub struct SshRuntime {
+    name: String,
+    host: SshHostConfig,
+    connect_timeout_secs: u64,
+}
+
+impl SshRuntime {
+    /// Runtime for the host named `name` in `[runtime.ssh.hosts]`.
+    pub fn new(config: &SshRuntimeConfig, name: &str) -> anyhow::Result<Self> {
+        let host = config
+            .hosts
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("SSH host '{name}' is not in runtime.ssh.hosts"))?;
+        Ok(Self {
+            name: name.to_string(),
+            host: host.clone(),
+            connect_timeout_secs: config.connect_timeout_secs,
+        })
+    }
+
+    /// Runtime for `runtime.ssh.default_host`.
+    pub fn from_default_host(config: &SshRuntimeConfig) -> anyhow::Result<Self> {
+        let name = config.default_host.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("runtime.kind='ssh' requires runtime.ssh.default_host")
+        })?;
+        Self::new(config, name)
+    }
+
+    /// Name of the configured host this runtime targets.
+    pub fn host_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The command line handed to the remote login shell: always POSIX `sh`,
+    /// started in the host's working directory when one is configured.
+    fn remote_command(&self, command: &str) -> String {
+        let script = match self.host.working_dir.as_deref() {
+            Some(dir) => format!("cd {} && {command}", shell_quote(dir)),
+            None => command.to_string(),
+        };
+        format!("sh -c {}", shell_quote(&script))
+    }
+}
+
+impl RuntimeAdapter for SshRuntime {
+    fn name(&self) -> &str {
+        "ssh"
+    }
+
+    fn has_shell_access(&self) -> bool {
+        true
+    }
+
+    fn has_filesystem_access(&self) -> bool {
+        false
+    }
+
+    fn storage_path(&self) -> PathBuf {
+        self.host.working_dir.as_deref().map_or_else(
+            || PathBuf::from(".zeroclaw"),
+            |dir| Path::new(dir).join(".zeroclaw"),
+        )
+    }
+
+    fn supports_long_running(&self) -> bool {
+        false
+    }
+
+    fn build_shell_command(
+        &self,
+        command: &str,
+        _workspace_dir: &Path,
+    ) -> anyhow::Result<tokio::process::Command> {
+        let identity = shellexpand::tilde(self.host.identity_file.trim()).into_owned();
+        let mut process = tokio::process::Command::new("ssh");
+        process
+            .arg("-T")
+            .args(["-o", "BatchMode=yes"])
+            .args(["-o", "StrictHostKeyChecking=yes"])
+            .args(["-o", "PasswordAuthentication=no"])
+            .args(["-o", "IdentitiesOnly=yes"])
+            .arg("-o")
+            .arg(format!("ConnectTimeout={}", self.connect_timeout_secs))
+            .arg("-i")
+            .arg(identity);
+
+        if let Some(known_hosts) = &self.host.known_hosts_file {
+            process.arg("-o").arg(format!(
+                "UserKnownHostsFile={}",
+                shellexpand::tilde(known_hosts.trim())
+            ));
+        }
+        if let Some(port) = self.host.port {
+            process.arg("-p").arg(port.to_string());
+        }
+        if let Some(user) = &self.host.user {
+            process.arg("-l").arg(user);
+        }
+
+        process
+            .arg("--")
+            .arg(self.host.host.trim())
+            .arg(self.remote_command(command));
+
+        Ok(process)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config() -> SshRuntimeConfig {
+        SshRuntimeConfig {
+            default_host: Some("workstation".into()),
+            hosts: HashMap::from([(
+                "workstation".to_string(),
+                SshHostConfig {
+                    host: "dev.lan".into(),
+                    user: Some("me".into()),
+                    port: Some(2222),
+                    identity_file: "/keys/nas".into(),
+                    known_hosts_file: None,
+                    working_dir: Some("/home/me/src".into()),
+                },
+            )]),
+            ..SshRuntimeConfig::default()
+        }
+    }
+
+    #[test]
+    fn builds_key_only_ssh_command_in_working_dir() {
+        let runtime = SshRuntime::from_default_host(&config()).unwrap();
+        assert_eq!(runtime.name(), "ssh");
+        assert_eq!(runtime.host_name(), "workstation");
+        assert!(!runtime.has_filesystem_access());
+
+        let cmd = runtime
+            .build_shell_command("echo 'hi' | wc -c", Path::new("/ignored"))
+            .unwrap();
+        let std_cmd = cmd.as_std();
+        assert_eq!(std_cmd.get_program(), "ssh");
+        let args: Vec<String> = std_cmd
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        for expected in [
+            "BatchMode=yes",
+            "StrictHostKeyChecking=yes",
+            "PasswordAuthentication=no",
+            "ConnectTimeout=10",
+            "/keys/nas",
+            "2222",
+            "me",
+        ] {
+            assert!(args.iter().any(|arg| arg == expected), "missing {expected}");
+        }
+        let host_at = args.iter().position(|arg| arg == "--").unwrap();
+        assert_eq!(args[host_at + 1], "dev.lan");
+        assert_eq!(
+            args[host_at + 2],
+            r#"sh -c 'cd '\''/home/me/src'\'' && echo '\''hi'\'' | wc -c'"#
+        );
+    }
+
+    #[test]
+    fn unknown_hosts_are_rejected() {
+        let err = SshRuntime::new(&config(), "prod").unwrap_err();
+        assert!(err.to_string().contains("not in runtime.ssh.hosts"));
+        assert!(SshRuntime::from_default_host(&SshRuntimeConfig::default()).is_err());
+    }
+}
@@ -205,6 +205,12 @@ impl PairingGuard {
         tokens.contains(&hashed)
     }
 
+    /// Stable, non-secret name for the client holding `token`, derived from
+    /// its hash so it can be listed in allowlists and shown to the client.
+    pub fn token_identity(token: &str) -> String {
+        format!("paired-{}", &hash_token(token)[..12])
+    }
+
     /// Returns true if the gateway is already paired (has at least one token).
     pub fn is_paired(&self) -> bool {
         let tokens = self.paired_tokens.lock();
@@ -236,7 +242,7 @@ fn prune_failed_attempts(map: &mut HashMap<String, FailedAttemptState>, now: Ins
 }
 
 /// Generate a 6-digit numeric pairing code using cryptographically secure randomness.
-fn generate_code() -> String {
+pub(crate) fn generate_code() -> String {
     // UUID v4 uses getrandom (backed by /dev/urandom on Linux, BCryptGenRandom
     // on Windows) — a CSPRNG. We extract 4 bytes from it for a uniform random
     // number in [0, 1_000_000).
@@ -423,6 +429,14 @@ mod tests {
         assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    async fn token_identity_is_stable_and_hides_the_token() {
+        let id = PairingGuard::token_identity("zc_secret");
+        assert_eq!(id, PairingGuard::token_identity("zc_secret"));
+        assert_ne!(id, PairingGuard::token_identity("zc_other"));
+        assert!(id.starts_with("paired-") && !id.contains("zc_secret"));
+    }
+
     #[test]
     async fn hash_token_is_deterministic() {
         assert_eq!(hash_token("zc_abc"), hash_token("zc_abc"));
@@ -0,0 +1,169 @@
+//! Parsed-skill cache.
+//!
+//! Loading a skill reads its manifest and audits every file in its
+//! directory, and prompts are rebuilt with the skill list for each message.
+//! Results are cached per skill directory (or open-skill markdown file) and
+//! reused while its fingerprint — path, type, size, and modification time of
+//! every entry beneath it — is unchanged. Editing, adding, or removing a file
+//! changes the fingerprint, so the next load re-reads and re-audits it.
+
+use super::Skill;
+use parking_lot::Mutex;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+struct Entry {
+    fingerprint: u64,
+    /// `None` when the skill failed its audit or did not parse.
+    skill: Option<Skill>,
+}
+
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+fn entries() -> &'static Mutex<HashMap<PathBuf, Entry>> {
+    static ENTRIES: OnceLock<Mutex<HashMap<PathBuf, Entry>>> = OnceLock::new();
+    ENTRIES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Skill cache counters, exported as Prometheus metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+}
+
+pub fn stats() -> CacheStats {
+    CacheStats {
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+        entries: entries().lock().len() as u64,
+    }
+}
+
+fn hash_entry(path: &Path, follow: bool, hasher: &mut DefaultHasher) -> std::io::Result<()> {
+    // Symlinks below the root are hashed as links (never followed) so
+    // retargeting one is a change; a symlinked skill root is resolved.
+    let meta = if follow {
+        std::fs::metadata(path)?
+    } else {
+        std::fs::symlink_metadata(path)?
+    };
+    path.hash(hasher);
+    meta.file_type().is_dir().hash(hasher);
+    meta.file_type().is_symlink().hash(hasher);
+    meta.len().hash(hasher);
+    meta.modified().ok().hash(hasher);
+    if meta.is_dir() {
+        let mut children: Vec<PathBuf> = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect();
+        children.sort();
+        for child in children {
+            hash_entry(&child, false, hasher)?;
+        }
+    }
+    Ok(())
+}
+
+fn fingerprint(path: &Path) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    hash_entry(path, true, &mut hasher).ok()?;
+    Some(hasher.finish())
+}
+
+/// Cached result of `load` for `path`, reloading when anything under it changed.
+pub fn get_or_load(path: &Path, load: impl FnOnce() -> Option<Skill>) -> Option<Skill> {
+    let Some(fingerprint) = fingerprint(path) else {
+        return load();
+    };
+    if let Some(entry) = entries().lock().get(path) {
+        if entry.fingerprint == fingerprint {
+            HITS.fetch_add(1, Ordering::Relaxed);
+            return entry.skill.clone();
+        }
+    }
+
+    MISSES.fetch_add(1, Ordering::Relaxed);
+    let skill = load();
+    let mut entries = entries().lock();
+    entries.retain(|cached, _| cached.exists());
+    entries.insert(
+        path.to_path_buf(),
+        Entry {
+            fingerprint,
+            skill: skill.clone(),
+        },
+    );
+    skill
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn skill(name: &str) -> Skill {
+        Skill {
+            name: name.into(),
+            description: String::new(),
+            version: "0.1.0".into(),
+            author: None,
+            tags: Vec::new(),
+            tools: Vec::new(),
+            prompts: Vec::new(),
+            location: None,
+        }
+    }
+
+    #[test]
+    fn reloads_only_when_files_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("weather");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(skill_dir.join("SKILL.md"), "# Weather\n").unwrap();
+
+        let loads = Cell::new(0);
+        let load = || {
+            loads.set(loads.get() + 1);
+            Some(skill("weather"))
+        };
+        assert!(get_or_load(&skill_dir, load).is_some());
+        assert!(get_or_load(&skill_dir, load).is_some());
+        assert_eq!(loads.get(), 1);
+
+        std::fs::write(skill_dir.join("SKILL.md"), "# Weather\nForecasts.\n").unwrap();
+        get_or_load(&skill_dir, load);
+        assert_eq!(loads.get(), 2);
+
+        std::fs::write(skill_dir.join("notes.md"), "extra").unwrap();
+        get_or_load(&skill_dir, load);
+        assert_eq!(loads.get(), 3);
+
+        std::fs::remove_file(skill_dir.join("notes.md")).unwrap();
+        get_or_load(&skill_dir, load);
+        get_or_load(&skill_dir, load);
+        assert_eq!(loads.get(), 4);
+        assert!(stats().hits >= 2);
+    }
+
+    #[test]
+    fn rejected_skills_are_cached_too() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("bad.md"), "rm -rf /").unwrap();
+        let loads = Cell::new(0);
+        let load = || {
+            loads.set(loads.get() + 1);
+            None
+        };
+        let path = dir.path().join("bad.md");
+        assert!(get_or_load(&path, load).is_none());
+        assert!(get_or_load(&path, load).is_none());
+        assert_eq!(loads.get(), 1);
+    }
+}
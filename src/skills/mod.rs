@@ -1,16 +1,20 @@
 use anyhow::{Context, Result};
 use directories::UserDirs;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::{Duration, SystemTime};
+use std::sync::OnceLock;
 
-mod audit;
+pub(crate) mod audit;
+pub(crate) mod cache;
+pub mod usage;
 
 const OPEN_SKILLS_REPO_URL: &str = "https://github.com/besoeasy/open-skills";
-const OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
-const OPEN_SKILLS_SYNC_INTERVAL_SECS: u64 = 60 * 60 * 24 * 7;
+/// Marker file used before the sync time moved to workspace state; removed on the next sync.
+const LEGACY_OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
+const OPEN_SKILLS_SYNC_INTERVAL_SECS: i64 = 60 * 60 * 24 * 7;
 
 /// A skill is a user-defined or community-built capability.
 /// Skills live in `~/.zeroclaw/workspace/skills/<name>/SKILL.md`
@@ -43,6 +47,35 @@ pub struct SkillTool {
     pub command: String,
     #[serde(default)]
     pub args: HashMap<String, String>,
+    /// Typed arguments declared under `[tools.parameters.<name>]`.
+    /// When present, the tool is registered as a native tool with this schema.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, SkillToolParameter>,
+    /// Run the tool on this `[runtime.ssh.hosts]` entry instead of locally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
+
+/// A single typed argument of a skill tool (JSON-schema-like subset).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillToolParameter {
+    /// "string", "integer", "number", or "boolean"
+    #[serde(rename = "type", default = "default_parameter_type")]
+    pub kind: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+    /// Allowed values, if restricted.
+    #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<serde_json::Value>,
+    /// Value used when the argument is omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+}
+
+fn default_parameter_type() -> String {
+    "string".to_string()
 }
 
 /// Skill manifest parsed from SKILL.toml
@@ -73,7 +106,7 @@ fn default_version() -> String {
 
 /// Load all skills from the workspace skills directory
 pub fn load_skills(workspace_dir: &Path) -> Vec<Skill> {
-    load_skills_with_open_skills_config(workspace_dir, None, None)
+    load_skills_with_open_skills_config(workspace_dir, None, None, &HashMap::new())
 }
 
 /// Load skills using runtime config values (preferred at runtime).
@@ -82,26 +115,136 @@ pub fn load_skills_with_config(workspace_dir: &Path, config: &crate::config::Con
         workspace_dir,
         Some(config.skills.open_skills_enabled),
         config.skills.open_skills_dir.as_deref(),
+        &config.skills.tool_overrides,
     )
 }
 
+/// Load the skills injected into the system prompt and start usage tracking for them.
+///
+/// Applies `skills.max_prompt_skills` (usage-ranked) on top of [`load_skills_with_config`].
+pub fn load_prompt_skills(workspace_dir: &Path, config: &crate::config::Config) -> Vec<Skill> {
+    let skills = usage::select_prompt_skills(
+        load_skills_with_config(workspace_dir, config),
+        workspace_dir,
+        config.skills.max_prompt_skills,
+    );
+    usage::init_tracking(workspace_dir, &skills);
+    skills
+}
+
 fn load_skills_with_open_skills_config(
     workspace_dir: &Path,
     config_open_skills_enabled: Option<bool>,
     config_open_skills_dir: Option<&str>,
+    tool_overrides: &HashMap<String, String>,
 ) -> Vec<Skill> {
     let mut skills = Vec::new();
 
-    if let Some(open_skills_dir) =
-        ensure_open_skills_repo(config_open_skills_enabled, config_open_skills_dir)
-    {
+    if let Some(open_skills_dir) = open_skills_repo(
+        workspace_dir,
+        config_open_skills_enabled,
+        config_open_skills_dir,
+    ) {
         skills.extend(load_open_skills(&open_skills_dir));
     }
 
     skills.extend(load_workspace_skills(workspace_dir));
+    resolve_tool_conflicts(&mut skills, tool_overrides);
     skills
 }
 
+/// Namespace skill tools whose names collide across skills.
+///
+/// Each conflicting tool is renamed to `skillname__toolname`, except for the
+/// skill named in `tool_overrides` for that tool, which keeps the bare name.
+/// Conflicts are logged so a newly installed skill cannot silently shadow
+/// another skill's tool.
+fn resolve_tool_conflicts(skills: &mut [Skill], tool_overrides: &HashMap<String, String>) {
+    let mut owners: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, skill) in skills.iter().enumerate() {
+        let mut seen = HashSet::new();
+        for tool in &skill.tools {
+            if seen.insert(tool.name.as_str()) {
+                owners.entry(tool.name.clone()).or_default().push(index);
+            }
+        }
+    }
+
+    let mut conflicts: Vec<(String, Vec<usize>)> = owners
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .collect();
+    conflicts.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (tool_name, indices) in conflicts {
+        let skill_names: Vec<&str> = indices
+            .iter()
+            .map(|&index| skills[index].name.as_str())
+            .collect();
+        let winner = match tool_overrides.get(&tool_name) {
+            Some(preferred) if skill_names.contains(&preferred.as_str()) => {
+                tracing::warn!(
+                    "skill tool `{tool_name}` is defined by {}; `{preferred}` keeps the name via skills.tool_overrides, others are namespaced",
+                    skill_names.join(", ")
+                );
+                Some(preferred.clone())
+            }
+            Some(preferred) => {
+                tracing::warn!(
+                    "skills.tool_overrides names `{preferred}` for tool `{tool_name}`, but it is only defined by {}; namespacing all of them",
+                    skill_names.join(", ")
+                );
+                None
+            }
+            None => {
+                tracing::warn!(
+                    "skill tool `{tool_name}` is defined by {}; namespacing all of them (set skills.tool_overrides to pick a winner)",
+                    skill_names.join(", ")
+                );
+                None
+            }
+        };
+
+        for index in indices {
+            let skill = &mut skills[index];
+            if winner.as_deref() == Some(skill.name.as_str()) {
+                continue;
+            }
+            let qualified = qualified_tool_name(&skill.name, &tool_name);
+            for tool in skill.tools.iter_mut().filter(|tool| tool.name == tool_name) {
+                tool.name.clone_from(&qualified);
+            }
+        }
+    }
+}
+
+/// Longest tool name provider function-calling APIs accept.
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// Namespaced name for a skill tool, kept within the `^[a-zA-Z0-9_-]{1,64}$`
+/// pattern providers require for native tool names.
+pub(crate) fn qualified_tool_name(skill_name: &str, tool_name: &str) -> String {
+    format!("{skill_name}__{tool_name}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_TOOL_NAME_LEN)
+        .collect()
+}
+
+/// Whether `name` can be registered as a native tool with the providers.
+pub(crate) fn is_valid_tool_name(name: &str) -> bool {
+    (1..=MAX_TOOL_NAME_LEN).contains(&name.len())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
 fn load_workspace_skills(workspace_dir: &Path) -> Vec<Skill> {
     let skills_dir = workspace_dir.join("skills");
     load_skills_from_directory(&skills_dir)
@@ -123,42 +266,46 @@ fn load_skills_from_directory(skills_dir: &Path) -> Vec<Skill> {
         if !path.is_dir() {
             continue;
         }
-
-        match audit::audit_skill_directory(&path) {
-            Ok(report) if report.is_clean() => {}
-            Ok(report) => {
-                tracing::warn!(
-                    "skipping insecure skill directory {}: {}",
-                    path.display(),
-                    report.summary()
-                );
-                continue;
-            }
-            Err(err) => {
-                tracing::warn!(
-                    "skipping unauditable skill directory {}: {err}",
-                    path.display()
-                );
-                continue;
-            }
+        if let Some(skill) = cache::get_or_load(&path, || load_skill_directory(&path)) {
+            skills.push(skill);
         }
+    }
 
-        // Try SKILL.toml first, then SKILL.md
-        let manifest_path = path.join("SKILL.toml");
-        let md_path = path.join("SKILL.md");
+    skills
+}
 
-        if manifest_path.exists() {
-            if let Ok(skill) = load_skill_toml(&manifest_path) {
-                skills.push(skill);
-            }
-        } else if md_path.exists() {
-            if let Ok(skill) = load_skill_md(&md_path, &path) {
-                skills.push(skill);
-            }
+/// Audit and parse one skill directory; `None` when it is rejected.
+fn load_skill_directory(path: &Path) -> Option<Skill> {
+    match audit::audit_skill_directory(path) {
+        Ok(report) if report.is_clean() => {}
+        Ok(report) => {
+            tracing::warn!(
+                "skipping insecure skill directory {}: {}",
+                path.display(),
+                report.summary()
+            );
+            return None;
+        }
+        Err(err) => {
+            tracing::warn!(
+                "skipping unauditable skill directory {}: {err}",
+                path.display()
+            );
+            return None;
         }
     }
 
-    skills
+    // Try SKILL.toml first, then SKILL.md
+    let manifest_path = path.join("SKILL.toml");
+    let md_path = path.join("SKILL.md");
+
+    if manifest_path.exists() {
+        load_skill_toml(&manifest_path).ok()
+    } else if md_path.exists() {
+        load_skill_md(&md_path, path).ok()
+    } else {
+        None
+    }
 }
 
 fn load_open_skills(repo_dir: &Path) -> Vec<Skill> {
@@ -198,26 +345,7 @@ fn load_open_skills(repo_dir: &Path) -> Vec<Skill> {
             continue;
         }
 
-        match audit::audit_open_skill_markdown(&path, repo_dir) {
-            Ok(report) if report.is_clean() => {}
-            Ok(report) => {
-                tracing::warn!(
-                    "skipping insecure open-skill file {}: {}",
-                    path.display(),
-                    report.summary()
-                );
-                continue;
-            }
-            Err(err) => {
-                tracing::warn!(
-                    "skipping unauditable open-skill file {}: {err}",
-                    path.display()
-                );
-                continue;
-            }
-        }
-
-        if let Ok(skill) = load_open_skill_md(&path) {
+        if let Some(skill) = cache::get_or_load(&path, || load_open_skill_file(&path, repo_dir)) {
             skills.push(skill);
         }
     }
@@ -225,6 +353,28 @@ fn load_open_skills(repo_dir: &Path) -> Vec<Skill> {
     skills
 }
 
+/// Audit and parse one flat-layout open-skill markdown file.
+fn load_open_skill_file(path: &Path, repo_dir: &Path) -> Option<Skill> {
+    match audit::audit_open_skill_markdown(path, repo_dir) {
+        Ok(report) if report.is_clean() => load_open_skill_md(path).ok(),
+        Ok(report) => {
+            tracing::warn!(
+                "skipping insecure open-skill file {}: {}",
+                path.display(),
+                report.summary()
+            );
+            None
+        }
+        Err(err) => {
+            tracing::warn!(
+                "skipping unauditable open-skill file {}: {err}",
+                path.display()
+            );
+            None
+        }
+    }
+}
+
 fn parse_open_skills_enabled(raw: &str) -> Option<bool> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "1" | "true" | "yes" | "on" => Some(true),
@@ -289,7 +439,14 @@ fn resolve_open_skills_dir(config_open_skills_dir: Option<&str>) -> Option<PathB
     )
 }
 
-fn ensure_open_skills_repo(
+/// Local open-skills checkout, if enabled and present.
+///
+/// Never touches the network: when the checkout is missing or due for an
+/// update, a background sync is started and the current local copy (or none,
+/// on a cold start) is returned right away. Subscribe with
+/// [`open_skills_refreshed`] to learn when a sync lands.
+fn open_skills_repo(
+    workspace_dir: &Path,
     config_open_skills_enabled: Option<bool>,
     config_open_skills_dir: Option<&str>,
 ) -> Option<PathBuf> {
@@ -298,53 +455,130 @@ fn ensure_open_skills_repo(
     }
 
     let repo_dir = resolve_open_skills_dir(config_open_skills_dir)?;
+    if !repo_dir.exists() || should_sync_open_skills(workspace_dir, &repo_dir) {
+        spawn_open_skills_sync(workspace_dir, &repo_dir);
+    }
+    repo_dir.exists().then_some(repo_dir)
+}
 
-    if !repo_dir.exists() {
-        if !clone_open_skills_repo(&repo_dir) {
-            return None;
-        }
-        let _ = mark_open_skills_synced(&repo_dir);
-        return Some(repo_dir);
+fn open_skills_refresh_sender() -> &'static tokio::sync::watch::Sender<u64> {
+    static SENDER: OnceLock<tokio::sync::watch::Sender<u64>> = OnceLock::new();
+    SENDER.get_or_init(|| tokio::sync::watch::channel(0).0)
+}
+
+/// Notified (with a generation counter) each time a background open-skills
+/// sync finishes with new content on disk. Reload skills after a change.
+pub fn open_skills_refreshed() -> tokio::sync::watch::Receiver<u64> {
+    open_skills_refresh_sender().subscribe()
+}
+
+/// Checkouts with a sync in flight, so concurrent loads start one sync each.
+fn open_skills_syncs_in_flight() -> &'static Mutex<HashSet<PathBuf>> {
+    static IN_FLIGHT: OnceLock<Mutex<HashSet<PathBuf>>> = OnceLock::new();
+    IN_FLIGHT.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn spawn_open_skills_sync(workspace_dir: &Path, repo_dir: &Path) {
+    if !open_skills_syncs_in_flight()
+        .lock()
+        .insert(repo_dir.to_path_buf())
+    {
+        return;
+    }
+    let workspace_dir = workspace_dir.to_path_buf();
+    let repo_dir = repo_dir.to_path_buf();
+    let spawned = std::thread::Builder::new()
+        .name("open-skills-sync".into())
+        .spawn({
+            let repo_dir = repo_dir.clone();
+            move || {
+                let synced = sync_open_skills_repo(&workspace_dir, &repo_dir);
+                open_skills_syncs_in_flight().lock().remove(&repo_dir);
+                if synced {
+                    open_skills_refresh_sender().send_modify(|generation| *generation += 1);
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        tracing::warn!("failed to start open-skills sync: {err}");
+        open_skills_syncs_in_flight().lock().remove(&repo_dir);
     }
+}
 
-    if should_sync_open_skills(&repo_dir) {
-        if pull_open_skills_repo(&repo_dir) {
-            let _ = mark_open_skills_synced(&repo_dir);
-        } else {
-            tracing::warn!(
-                "open-skills update failed; using local copy from {}",
-                repo_dir.display()
-            );
-        }
+/// Sync the open-skills checkout in the foreground when it is missing or stale.
+fn sync_open_skills_if_due(workspace_dir: &Path, config: &crate::config::Config) {
+    if !open_skills_enabled(Some(config.skills.open_skills_enabled)) {
+        return;
     }
+    let Some(repo_dir) = resolve_open_skills_dir(config.skills.open_skills_dir.as_deref()) else {
+        return;
+    };
+    if repo_dir.exists() && !should_sync_open_skills(workspace_dir, &repo_dir) {
+        return;
+    }
+    println!("Syncing open-skills into {}...", repo_dir.display());
+    sync_open_skills_repo(workspace_dir, &repo_dir);
+}
 
-    Some(repo_dir)
+/// Clone or update the checkout now. Returns `true` when it synced.
+fn sync_open_skills_repo(workspace_dir: &Path, repo_dir: &Path) -> bool {
+    let synced = if repo_dir.exists() {
+        pull_open_skills_repo(repo_dir)
+    } else {
+        clone_open_skills_repo(repo_dir)
+    };
+    if synced {
+        mark_open_skills_synced(workspace_dir, repo_dir);
+    } else if repo_dir.exists() {
+        tracing::warn!(
+            "open-skills update failed; using local copy from {}",
+            repo_dir.display()
+        );
+    }
+    synced
 }
 
+/// Clone next to `repo_dir` and move the checkout into place once complete,
+/// so an interrupted clone never leaves a partial copy behind.
 fn clone_open_skills_repo(repo_dir: &Path) -> bool {
-    if let Some(parent) = repo_dir.parent() {
-        if let Err(err) = std::fs::create_dir_all(parent) {
-            tracing::warn!(
-                "failed to create open-skills parent directory {}: {err}",
-                parent.display()
-            );
-            return false;
-        }
+    let Some(parent) = repo_dir.parent() else {
+        return false;
+    };
+    if let Err(err) = std::fs::create_dir_all(parent) {
+        tracing::warn!(
+            "failed to create open-skills parent directory {}: {err}",
+            parent.display()
+        );
+        return false;
     }
+    let name = repo_dir
+        .file_name()
+        .map_or_else(|| "open-skills".into(), |name| name.to_string_lossy());
+    let staging_dir = parent.join(format!(".{name}.clone"));
+    let _ = std::fs::remove_dir_all(&staging_dir);
 
     let output = Command::new("git")
         .args(["clone", "--depth", "1", OPEN_SKILLS_REPO_URL])
-        .arg(repo_dir)
+        .arg(&staging_dir)
         .output();
 
     match output {
         Ok(result) if result.status.success() => {
+            if let Err(err) = std::fs::rename(&staging_dir, repo_dir) {
+                tracing::warn!(
+                    "failed to move open-skills into {}: {err}",
+                    repo_dir.display()
+                );
+                let _ = std::fs::remove_dir_all(&staging_dir);
+                return false;
+            }
             tracing::info!("initialized open-skills at {}", repo_dir.display());
             true
         }
         Ok(result) => {
             let stderr = String::from_utf8_lossy(&result.stderr);
             tracing::warn!("failed to clone open-skills: {stderr}");
+            let _ = std::fs::remove_dir_all(&staging_dir);
             false
         }
         Err(err) => {
@@ -380,24 +614,30 @@ fn pull_open_skills_repo(repo_dir: &Path) -> bool {
     }
 }
 
-fn should_sync_open_skills(repo_dir: &Path) -> bool {
-    let marker = repo_dir.join(OPEN_SKILLS_SYNC_MARKER);
-    let Ok(metadata) = std::fs::metadata(marker) else {
-        return true;
-    };
-    let Ok(modified_at) = metadata.modified() else {
-        return true;
-    };
-    let Ok(age) = SystemTime::now().duration_since(modified_at) else {
-        return true;
-    };
+fn open_skills_sync_key(repo_dir: &Path) -> String {
+    format!("open_skills.synced:{}", repo_dir.display())
+}
 
-    age >= Duration::from_secs(OPEN_SKILLS_SYNC_INTERVAL_SECS)
+fn should_sync_open_skills(workspace_dir: &Path, repo_dir: &Path) -> bool {
+    match crate::state::get(workspace_dir, &open_skills_sync_key(repo_dir)) {
+        Ok(Some((_, synced_at))) => {
+            let age = chrono::Utc::now().signed_duration_since(synced_at);
+            age < chrono::Duration::zero()
+                || age >= chrono::Duration::seconds(OPEN_SKILLS_SYNC_INTERVAL_SECS)
+        }
+        Ok(None) => true,
+        Err(err) => {
+            tracing::warn!("failed to read open-skills sync time: {err}");
+            true
+        }
+    }
 }
 
-fn mark_open_skills_synced(repo_dir: &Path) -> Result<()> {
-    std::fs::write(repo_dir.join(OPEN_SKILLS_SYNC_MARKER), b"synced")?;
-    Ok(())
+fn mark_open_skills_synced(workspace_dir: &Path, repo_dir: &Path) {
+    if let Err(err) = crate::state::set(workspace_dir, &open_skills_sync_key(repo_dir), "synced") {
+        tracing::warn!("failed to record open-skills sync time: {err}");
+    }
+    let _ = std::fs::remove_file(repo_dir.join(LEGACY_OPEN_SKILLS_SYNC_MARKER));
 }
 
 /// Load a skill from a SKILL.toml manifest
@@ -527,67 +767,156 @@ pub fn skills_to_prompt_with_mode(
     workspace_dir: &Path,
     mode: crate::config::SkillsPromptInjectionMode,
 ) -> String {
-    use std::fmt::Write;
-
     if skills.is_empty() {
         return String::new();
     }
 
-    let mut prompt = match mode {
-        crate::config::SkillsPromptInjectionMode::Full => String::from(
+    let mut prompt = String::from(skills_prompt_header(mode));
+    for skill in skills {
+        prompt.push_str(&render_skill_prompt_entry(skill, workspace_dir, mode));
+    }
+    prompt.push_str(SKILLS_PROMPT_FOOTER);
+    prompt
+}
+
+const SKILLS_PROMPT_FOOTER: &str = "</available_skills>";
+
+fn skills_prompt_header(mode: crate::config::SkillsPromptInjectionMode) -> &'static str {
+    match mode {
+        crate::config::SkillsPromptInjectionMode::Full => {
             "## Available Skills\n\n\
              Skill instructions and tool metadata are preloaded below.\n\
              Follow these instructions directly; do not read skill files at runtime unless the user asks.\n\n\
-             <available_skills>\n",
-        ),
-        crate::config::SkillsPromptInjectionMode::Compact => String::from(
+             <available_skills>\n"
+        }
+        crate::config::SkillsPromptInjectionMode::Compact => {
             "## Available Skills\n\n\
              Skill summaries are preloaded below to keep context compact.\n\
              Skill instructions are loaded on demand: read the skill file in `location` only when needed.\n\n\
-             <available_skills>\n",
-        ),
-    };
+             <available_skills>\n"
+        }
+    }
+}
 
-    for skill in skills {
-        let _ = writeln!(prompt, "  <skill>");
-        write_xml_text_element(&mut prompt, 4, "name", &skill.name);
-        write_xml_text_element(&mut prompt, 4, "description", &skill.description);
-        let location = render_skill_location(
-            skill,
-            workspace_dir,
-            matches!(mode, crate::config::SkillsPromptInjectionMode::Compact),
-        );
-        write_xml_text_element(&mut prompt, 4, "location", &location);
+/// Render the `<skill>` block a single skill contributes to the skills prompt.
+fn render_skill_prompt_entry(
+    skill: &Skill,
+    workspace_dir: &Path,
+    mode: crate::config::SkillsPromptInjectionMode,
+) -> String {
+    use std::fmt::Write;
 
-        if matches!(mode, crate::config::SkillsPromptInjectionMode::Full) {
-            if !skill.prompts.is_empty() {
-                let _ = writeln!(prompt, "    <instructions>");
-                for instruction in &skill.prompts {
-                    write_xml_text_element(&mut prompt, 6, "instruction", instruction);
-                }
-                let _ = writeln!(prompt, "    </instructions>");
+    let mut prompt = String::new();
+    let _ = writeln!(prompt, "  <skill>");
+    write_xml_text_element(&mut prompt, 4, "name", &skill.name);
+    write_xml_text_element(&mut prompt, 4, "description", &skill.description);
+    let location = render_skill_location(
+        skill,
+        workspace_dir,
+        matches!(mode, crate::config::SkillsPromptInjectionMode::Compact),
+    );
+    write_xml_text_element(&mut prompt, 4, "location", &location);
+
+    if matches!(mode, crate::config::SkillsPromptInjectionMode::Full) {
+        if !skill.prompts.is_empty() {
+            let _ = writeln!(prompt, "    <instructions>");
+            for instruction in &skill.prompts {
+                write_xml_text_element(&mut prompt, 6, "instruction", instruction);
             }
+            let _ = writeln!(prompt, "    </instructions>");
+        }
 
-            if !skill.tools.is_empty() {
-                let _ = writeln!(prompt, "    <tools>");
-                for tool in &skill.tools {
-                    let _ = writeln!(prompt, "      <tool>");
-                    write_xml_text_element(&mut prompt, 8, "name", &tool.name);
-                    write_xml_text_element(&mut prompt, 8, "description", &tool.description);
-                    write_xml_text_element(&mut prompt, 8, "kind", &tool.kind);
-                    let _ = writeln!(prompt, "      </tool>");
+        if !skill.tools.is_empty() {
+            let _ = writeln!(prompt, "    <tools>");
+            for tool in &skill.tools {
+                let _ = writeln!(prompt, "      <tool>");
+                write_xml_text_element(&mut prompt, 8, "name", &tool.name);
+                write_xml_text_element(&mut prompt, 8, "description", &tool.description);
+                write_xml_text_element(&mut prompt, 8, "kind", &tool.kind);
+                if let Some(host) = &tool.host {
+                    write_xml_text_element(&mut prompt, 8, "host", host);
                 }
-                let _ = writeln!(prompt, "    </tools>");
+                let _ = writeln!(prompt, "      </tool>");
             }
+            let _ = writeln!(prompt, "    </tools>");
         }
-
-        let _ = writeln!(prompt, "  </skill>");
     }
 
-    prompt.push_str("</available_skills>");
+    let _ = writeln!(prompt, "  </skill>");
     prompt
 }
 
+/// Render the skills prompt for `zeroclaw skills prompt` in `xml` (exactly as
+/// injected), `markdown`, or `json` form, with an estimated token count per skill.
+pub fn export_skills_prompt(
+    skills: &[Skill],
+    workspace_dir: &Path,
+    mode: crate::config::SkillsPromptInjectionMode,
+    format: &str,
+) -> Result<String> {
+    use std::fmt::Write;
+
+    let prompt = skills_to_prompt_with_mode(skills, workspace_dir, mode);
+    let total_tokens = crate::agent::classifier::estimate_tokens(prompt.chars().count());
+    let entries: Vec<(&Skill, String, usize)> = skills
+        .iter()
+        .map(|skill| {
+            let rendered = render_skill_prompt_entry(skill, workspace_dir, mode);
+            let tokens = crate::agent::classifier::estimate_tokens(rendered.chars().count());
+            (skill, rendered, tokens)
+        })
+        .collect();
+    let mode_name = match mode {
+        crate::config::SkillsPromptInjectionMode::Full => "full",
+        crate::config::SkillsPromptInjectionMode::Compact => "compact",
+    };
+
+    match format {
+        "xml" => Ok(prompt),
+        "markdown" => {
+            let mut out = String::new();
+            let _ = writeln!(
+                out,
+                "# Skills prompt ({mode_name}, {} skills, ~{total_tokens} tokens)\n",
+                entries.len()
+            );
+            let _ = writeln!(out, "| Skill | Tokens |\n|---|---|");
+            for (skill, _, tokens) in &entries {
+                let _ = writeln!(out, "| {} | ~{tokens} |", skill.name);
+            }
+            for (skill, rendered, tokens) in &entries {
+                let _ = write!(
+                    out,
+                    "\n## {} (~{tokens} tokens)\n\n```xml\n{rendered}```\n",
+                    skill.name
+                );
+            }
+            Ok(out)
+        }
+        "json" => {
+            let skills_json: Vec<serde_json::Value> = entries
+                .iter()
+                .map(|(skill, rendered, tokens)| {
+                    serde_json::json!({
+                        "name": skill.name,
+                        "tokens": tokens,
+                        "rendered": rendered,
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string_pretty(&serde_json::json!({
+                "mode": mode_name,
+                "total_tokens": total_tokens,
+                "skills": skills_json,
+                "prompt": prompt,
+            }))?)
+        }
+        other => anyhow::bail!(
+            "Unsupported skills prompt format: {other} (expected xml, markdown, or json)"
+        ),
+    }
+}
+
 /// Get the skills directory path
 pub fn skills_dir(workspace_dir: &Path) -> PathBuf {
     workspace_dir.join("skills")
@@ -772,7 +1101,10 @@ fn copy_dir_recursive_secure(src: &Path, dest: &Path) -> Result<()> {
     Ok(())
 }
 
-fn install_local_skill_source(source: &str, skills_path: &Path) -> Result<(PathBuf, usize)> {
+pub(crate) fn install_local_skill_source(
+    source: &str,
+    skills_path: &Path,
+) -> Result<(PathBuf, usize)> {
     let source_path = PathBuf::from(source);
     if !source_path.exists() {
         anyhow::bail!("Source path does not exist: {source}");
@@ -833,6 +1165,8 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
     let workspace_dir = &config.workspace_dir;
     match command {
         crate::SkillCommands::List => {
+            // The CLI can afford to wait; the background sync would die with the process.
+            sync_open_skills_if_due(workspace_dir, config);
             let skills = load_skills_with_config(workspace_dir, config);
             if skills.is_empty() {
                 println!("No skills installed.");
@@ -963,6 +1297,73 @@ pub fn handle_command(command: crate::SkillCommands, config: &crate::config::Con
             );
             Ok(())
         }
+        crate::SkillCommands::Prompt { mode, format } => {
+            let mode = match mode.as_deref() {
+                Some("full") => crate::config::SkillsPromptInjectionMode::Full,
+                Some("compact") => crate::config::SkillsPromptInjectionMode::Compact,
+                Some(other) => anyhow::bail!("Unsupported skills prompt mode: {other}"),
+                None => config.skills.prompt_injection_mode,
+            };
+            let skills = usage::select_prompt_skills(
+                load_skills_with_config(workspace_dir, config),
+                workspace_dir,
+                config.skills.max_prompt_skills,
+            );
+            if skills.is_empty() {
+                eprintln!("No skills installed; nothing would be injected.");
+                return Ok(());
+            }
+
+            println!(
+                "{}",
+                export_skills_prompt(&skills, workspace_dir, mode, &format)?
+            );
+            if format == "xml" {
+                for skill in &skills {
+                    let rendered = render_skill_prompt_entry(skill, workspace_dir, mode);
+                    eprintln!(
+                        "  {:<32} ~{} tokens",
+                        skill.name,
+                        crate::agent::classifier::estimate_tokens(rendered.chars().count())
+                    );
+                }
+            }
+            Ok(())
+        }
+        crate::SkillCommands::Stats => {
+            let skills = load_skills_with_config(workspace_dir, config);
+            let recorded = usage::load_usage(workspace_dir);
+            if skills.is_empty() {
+                println!("No skills installed.");
+                return Ok(());
+            }
+
+            let ranked = usage::rank_skills_by_usage(skills, &recorded);
+            println!("Skill usage ({} installed):", ranked.len());
+            println!();
+            println!(
+                "  {:<32} {:>10} {:>10}  LAST TOOL RUN",
+                "SKILL", "PROMPTS", "TOOL RUNS"
+            );
+            for skill in &ranked {
+                let stats = recorded.get(&skill.name).cloned().unwrap_or_default();
+                let last_run = stats
+                    .last_tool_run_at
+                    .map_or_else(|| "never".to_string(), |at| at.to_rfc3339());
+                println!(
+                    "  {:<32} {:>10} {:>10}  {}",
+                    skill.name, stats.prompt_inclusions, stats.tool_runs, last_run
+                );
+            }
+
+            let unused = ranked
+                .iter()
+                .filter(|skill| recorded.get(&skill.name).is_none_or(|s| s.tool_runs == 0))
+                .count();
+            println!();
+            println!("  {unused} skill(s) have never had a tool run; consider pruning them.");
+            Ok(())
+        }
     }
 }
 
@@ -1040,6 +1441,108 @@ command = "echo hello"
         assert_eq!(skills[0].tools[0].name, "hello");
     }
 
+    #[test]
+    fn load_skill_toml_parses_tool_parameters() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("skills").join("weather");
+        fs::create_dir_all(&skill_dir).unwrap();
+
+        fs::write(
+            skill_dir.join("SKILL.toml"),
+            r#"
+[skill]
+name = "weather"
+description = "Weather lookups"
+
+[[tools]]
+name = "forecast"
+description = "Forecast for a city"
+kind = "shell"
+command = "curl -s wttr.in/{city}?format={format}"
+
+[tools.parameters.city]
+description = "City name"
+required = true
+
+[tools.parameters.format]
+type = "integer"
+enum = [1, 2, 3]
+default = 3
+"#,
+        )
+        .unwrap();
+
+        let skills = load_skills(dir.path());
+        let parameters = &skills[0].tools[0].parameters;
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters["city"].kind, "string");
+        assert!(parameters["city"].required);
+        assert_eq!(parameters["format"].kind, "integer");
+        assert_eq!(parameters["format"].allowed.len(), 3);
+        assert_eq!(parameters["format"].default, Some(serde_json::json!(3)));
+    }
+
+    #[test]
+    fn resolve_tool_conflicts_namespaces_duplicates_and_honors_overrides() {
+        let tool = |name: &str| SkillTool {
+            name: name.to_string(),
+            description: String::new(),
+            kind: "shell".to_string(),
+            command: format!("echo {name}"),
+            args: HashMap::new(),
+            parameters: BTreeMap::new(),
+            host: None,
+        };
+        let skill = |name: &str, tools: Vec<SkillTool>| Skill {
+            name: name.to_string(),
+            description: String::new(),
+            version: "0.1.0".to_string(),
+            author: None,
+            tags: vec![],
+            tools,
+            prompts: vec![],
+            location: None,
+        };
+        let build = || {
+            vec![
+                skill("alpha", vec![tool("deploy"), tool("status")]),
+                skill("beta", vec![tool("deploy")]),
+            ]
+        };
+
+        let mut skills = build();
+        resolve_tool_conflicts(&mut skills, &HashMap::new());
+        assert_eq!(skills[0].tools[0].name, "alpha__deploy");
+        assert_eq!(skills[0].tools[1].name, "status");
+        assert_eq!(skills[1].tools[0].name, "beta__deploy");
+
+        let mut skills = build();
+        let overrides = HashMap::from([("deploy".to_string(), "beta".to_string())]);
+        resolve_tool_conflicts(&mut skills, &overrides);
+        assert_eq!(skills[0].tools[0].name, "alpha__deploy");
+        assert_eq!(skills[1].tools[0].name, "deploy");
+
+        let mut skills = build();
+        let overrides = HashMap::from([("deploy".to_string(), "gamma".to_string())]);
+        resolve_tool_conflicts(&mut skills, &overrides);
+        assert_eq!(skills[0].tools[0].name, "alpha__deploy");
+        assert_eq!(skills[1].tools[0].name, "beta__deploy");
+    }
+
+    #[test]
+    fn qualified_tool_names_are_valid_native_names() {
+        assert_eq!(
+            qualified_tool_name("my.skill", "deploy"),
+            "my_skill__deploy"
+        );
+        assert!(is_valid_tool_name(&qualified_tool_name(
+            "my skill", "run.it"
+        )));
+        assert_eq!(qualified_tool_name(&"s".repeat(80), "t").len(), 64);
+        assert!(!is_valid_tool_name("alpha.deploy"));
+        assert!(!is_valid_tool_name(""));
+    }
+
     #[test]
     fn load_skill_from_md() {
         let dir = tempfile::tempdir().unwrap();
@@ -1097,6 +1600,8 @@ command = "echo hello"
                 kind: "shell".to_string(),
                 command: "echo hi".to_string(),
                 args: HashMap::new(),
+                parameters: BTreeMap::new(),
+                host: None,
             }],
             prompts: vec!["Do the thing.".to_string()],
             location: Some(PathBuf::from("/tmp/workspace/skills/test/SKILL.md")),
@@ -1116,6 +1621,40 @@ command = "echo hello"
         assert!(!prompt.contains("<tools>"));
     }
 
+    #[test]
+    fn export_skills_prompt_formats_match_injected_prompt() {
+        let skills = vec![Skill {
+            name: "test".to_string(),
+            description: "A test".to_string(),
+            version: "1.0.0".to_string(),
+            author: None,
+            tags: vec![],
+            tools: vec![],
+            prompts: vec!["Do the thing.".to_string()],
+            location: None,
+        }];
+        let workspace = Path::new("/tmp/workspace");
+        let mode = crate::config::SkillsPromptInjectionMode::Full;
+        let injected = skills_to_prompt_with_mode(&skills, workspace, mode);
+
+        let xml = export_skills_prompt(&skills, workspace, mode, "xml").unwrap();
+        assert_eq!(xml, injected);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&export_skills_prompt(&skills, workspace, mode, "json").unwrap())
+                .unwrap();
+        assert_eq!(json["mode"], "full");
+        assert_eq!(json["prompt"], injected.as_str());
+        assert_eq!(json["skills"][0]["name"], "test");
+        assert!(json["skills"][0]["tokens"].as_u64().unwrap() > 0);
+
+        let markdown = export_skills_prompt(&skills, workspace, mode, "markdown").unwrap();
+        assert!(markdown.contains("| test | ~"));
+        assert!(markdown.contains("<instruction>Do the thing.</instruction>"));
+
+        assert!(export_skills_prompt(&skills, workspace, mode, "yaml").is_err());
+    }
+
     #[test]
     fn init_skills_creates_readme() {
         let dir = tempfile::tempdir().unwrap();
@@ -1297,6 +1836,8 @@ description = "Bare minimum"
                 kind: "shell".to_string(),
                 command: "curl wttr.in".to_string(),
                 args: HashMap::new(),
+                parameters: BTreeMap::new(),
+                host: None,
             }],
             prompts: vec![],
             location: None,
@@ -1437,8 +1978,34 @@ description = "Bare minimum"
         assert_eq!(resolve_open_skills_dir_from_sources(None, None, None), None);
     }
 
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit_open_skill(repo: &Path, name: &str) {
+        fs::create_dir_all(repo.join("skills").join(name)).unwrap();
+        fs::write(
+            repo.join("skills").join(name).join("SKILL.md"),
+            format!("# {name}\nOpen skill {name}.\n"),
+        )
+        .unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "--quiet", "--no-gpg-sign", "-m", name]);
+    }
+
     #[test]
-    fn load_skills_with_config_reads_open_skills_dir_without_network() {
+    fn load_skills_with_config_reads_open_skills_dir_and_syncs_in_background() {
         let _env_guard = open_skills_env_lock().lock().unwrap();
         let _enabled_guard = EnvVarGuard::unset("ZEROCLAW_OPEN_SKILLS_ENABLED");
         let _dir_guard = EnvVarGuard::unset("ZEROCLAW_OPEN_SKILLS_DIR");
@@ -1447,29 +2014,53 @@ description = "Bare minimum"
         let workspace_dir = dir.path().join("workspace");
         fs::create_dir_all(workspace_dir.join("skills")).unwrap();
 
+        // A local upstream stands in for the open-skills repository.
+        let upstream = dir.path().join("upstream");
+        fs::create_dir_all(&upstream).unwrap();
+        git(&upstream, &["init", "--quiet"]);
+        fs::write(upstream.join("README.md"), "# open skills\n").unwrap();
+        fs::write(upstream.join("CONTRIBUTING.md"), "# contribution guide\n").unwrap();
+        commit_open_skill(&upstream, "http_request");
+
         let open_skills_dir = dir.path().join("open-skills-local");
-        fs::create_dir_all(open_skills_dir.join("skills/http_request")).unwrap();
-        fs::write(open_skills_dir.join("README.md"), "# open skills\n").unwrap();
-        fs::write(
-            open_skills_dir.join("CONTRIBUTING.md"),
-            "# contribution guide\n",
-        )
-        .unwrap();
-        fs::write(
-            open_skills_dir.join("skills/http_request/SKILL.md"),
-            "# HTTP request\nFetch API responses.\n",
-        )
-        .unwrap();
+        git(
+            dir.path(),
+            &[
+                "clone",
+                "--quiet",
+                &upstream.to_string_lossy(),
+                &open_skills_dir.to_string_lossy(),
+            ],
+        );
+        commit_open_skill(&upstream, "web_search");
 
         let mut config = crate::config::Config::default();
         config.workspace_dir = workspace_dir.clone();
         config.skills.open_skills_enabled = true;
         config.skills.open_skills_dir = Some(open_skills_dir.to_string_lossy().to_string());
 
+        // Loading returns the local checkout right away and pulls in the background.
         let skills = load_skills_with_config(&workspace_dir, &config);
         assert_eq!(skills.len(), 1);
         assert_eq!(skills[0].name, "http_request");
-        assert_ne!(skills[0].name, "CONTRIBUTING");
+
+        let key = open_skills_sync_key(&open_skills_dir);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while crate::state::get(&workspace_dir, &key).unwrap().is_none() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "open-skills sync never finished"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(!should_sync_open_skills(&workspace_dir, &open_skills_dir));
+
+        let mut names: Vec<String> = load_skills_with_config(&workspace_dir, &config)
+            .into_iter()
+            .map(|skill| skill.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["http_request", "web_search"]);
     }
 }
 
@@ -0,0 +1,507 @@
+//! Per-skill usage analytics.
+//!
+//! Counts how often each skill was part of the prompt set for a turn and how
+//! often its tools actually ran. Counters persist to
+//! `<workspace>/state/skill_usage.json` so `zeroclaw skills stats` can show
+//! which skills earn their context budget, and prompt selection can prefer
+//! skills that are actually used.
+//!
+//! Recording only bumps in-memory counters. They are merged into the file
+//! every [`FLUSH_INTERVAL`] on a blocking task, when the skill set changes,
+//! and by [`flush`] at shutdown.
+
+use super::{qualified_tool_name, Skill};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::Duration;
+
+const USAGE_FILE: &str = "skill_usage.json";
+/// How often recorded counters are written to the usage file.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Recorded usage counters for a single skill.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillUsage {
+    /// Turns in which the skill was part of the injected prompt set.
+    #[serde(default)]
+    pub prompt_inclusions: u64,
+    /// Times one of the skill's tools ran.
+    #[serde(default)]
+    pub tool_runs: u64,
+    /// Last time one of the skill's tools ran.
+    #[serde(default)]
+    pub last_tool_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageFile {
+    #[serde(default)]
+    skills: BTreeMap<String, SkillUsage>,
+}
+
+/// Path of the persisted usage counters for a workspace.
+pub fn usage_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(USAGE_FILE)
+}
+
+/// Load usage counters, including ones recorded but not yet flushed.
+/// Missing or unreadable files yield only the unflushed counters.
+pub fn load_usage(workspace_dir: &Path) -> BTreeMap<String, SkillUsage> {
+    let mut usage = read_usage_file(workspace_dir);
+    if let Some(tracker) = current_tracker().filter(|t| t.workspace_dir == workspace_dir) {
+        merge_usage(
+            &mut usage,
+            tracker
+                .pending
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        );
+    }
+    usage
+}
+
+fn read_usage_file(workspace_dir: &Path) -> BTreeMap<String, SkillUsage> {
+    let path = usage_path(workspace_dir);
+    let Ok(raw) = std::fs::read_to_string(&path) else {
+        return BTreeMap::new();
+    };
+    match serde_json::from_str::<UsageFile>(&raw) {
+        Ok(file) => file.skills,
+        Err(err) => {
+            tracing::warn!(
+                "Ignoring malformed skill usage file {}: {err}",
+                path.display()
+            );
+            BTreeMap::new()
+        }
+    }
+}
+
+/// Add the counters in `delta` to `usage`.
+fn merge_usage(usage: &mut BTreeMap<String, SkillUsage>, delta: BTreeMap<String, SkillUsage>) {
+    for (name, delta) in delta {
+        let entry = usage.entry(name).or_default();
+        entry.prompt_inclusions += delta.prompt_inclusions;
+        entry.tool_runs += delta.tool_runs;
+        entry.last_tool_run_at = entry.last_tool_run_at.max(delta.last_tool_run_at);
+    }
+}
+
+fn save_usage(workspace_dir: &Path, skills: &BTreeMap<String, SkillUsage>) -> Result<()> {
+    let file = UsageFile {
+        skills: skills.clone(),
+    };
+    crate::state::write_atomic(
+        &usage_path(workspace_dir),
+        serde_json::to_string_pretty(&file)?,
+    )
+}
+
+/// Order skills for prompt injection using recorded usage.
+///
+/// Skills whose tools have run come first (most runs first). Among unused
+/// skills, those included in fewer prompts rank ahead, so newly installed
+/// skills get a chance before ones that were offered many times and never used.
+pub fn rank_skills_by_usage(
+    mut skills: Vec<Skill>,
+    usage: &BTreeMap<String, SkillUsage>,
+) -> Vec<Skill> {
+    let zero = SkillUsage::default();
+    skills.sort_by(|a, b| {
+        let ua = usage.get(&a.name).unwrap_or(&zero);
+        let ub = usage.get(&b.name).unwrap_or(&zero);
+        ub.tool_runs
+            .cmp(&ua.tool_runs)
+            .then_with(|| ua.prompt_inclusions.cmp(&ub.prompt_inclusions))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    skills
+}
+
+/// Select the skills to inject into the prompt, honoring `skills.max_prompt_skills`.
+pub fn select_prompt_skills(
+    skills: Vec<Skill>,
+    workspace_dir: &Path,
+    max_prompt_skills: Option<usize>,
+) -> Vec<Skill> {
+    let Some(limit) = max_prompt_skills else {
+        return skills;
+    };
+    if skills.len() <= limit {
+        return skills;
+    }
+    let mut ranked = rank_skills_by_usage(skills, &load_usage(workspace_dir));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Lookup data used to attribute tool calls back to skills.
+#[derive(Debug, Clone)]
+struct TrackedSkill {
+    name: String,
+    tool_names: Vec<String>,
+    /// Literal command prefixes of shell/script tools (text before the first placeholder).
+    command_prefixes: Vec<String>,
+}
+
+impl TrackedSkill {
+    fn from_skill(skill: &Skill) -> Self {
+        let command_prefixes = skill
+            .tools
+            .iter()
+            .filter(|tool| matches!(tool.kind.as_str(), "shell" | "script"))
+            .filter_map(|tool| {
+                let prefix = tool.command.split('{').next().unwrap_or_default().trim();
+                (!prefix.is_empty()).then(|| prefix.to_string())
+            })
+            .collect();
+        Self {
+            name: skill.name.clone(),
+            tool_names: skill
+                .tools
+                .iter()
+                .flat_map(|tool| {
+                    // Tools are also addressable as `skillname__toolname`.
+                    let qualified = qualified_tool_name(&skill.name, &tool.name);
+                    let qualified = (qualified != tool.name).then_some(qualified);
+                    std::iter::once(tool.name.clone()).chain(qualified)
+                })
+                .collect(),
+            command_prefixes,
+        }
+    }
+}
+
+/// Attribute a tool call to a skill: either the call names a skill tool
+/// directly, or it is a `shell` call running a skill tool's command.
+fn attribute_tool_call<'a>(
+    skills: &'a [TrackedSkill],
+    tool_name: &str,
+    arguments: &serde_json::Value,
+) -> Option<&'a str> {
+    if let Some(skill) = skills
+        .iter()
+        .find(|skill| skill.tool_names.iter().any(|name| name == tool_name))
+    {
+        return Some(&skill.name);
+    }
+
+    if tool_name != "shell" {
+        return None;
+    }
+    let command = arguments.get("command")?.as_str()?;
+    match_command_prefix(skills, command).map(|(name, _)| name)
+}
+
+/// The skill whose command prefix is the longest match for `command`,
+/// with that prefix.
+fn match_command_prefix<'a>(
+    skills: &'a [TrackedSkill],
+    command: &str,
+) -> Option<(&'a str, &'a str)> {
+    let command = command.trim();
+    skills
+        .iter()
+        .filter_map(|skill| {
+            skill
+                .command_prefixes
+                .iter()
+                .filter(|prefix| command.starts_with(prefix.as_str()))
+                .max_by_key(|prefix| prefix.len())
+                .map(|prefix| (skill.name.as_str(), prefix.as_str()))
+        })
+        .max_by_key(|(_, prefix)| prefix.len())
+}
+
+/// The tracked skill running a shell `command`, and the skill command prefix
+/// it matched.
+pub fn skill_for_command(command: &str) -> Option<(String, String)> {
+    let tracker = current_tracker()?;
+    match_command_prefix(&tracker.skills, command)
+        .map(|(skill, prefix)| (skill.to_string(), prefix.to_string()))
+}
+
+/// Records usage for the skills injected into the active prompt.
+struct SkillUsageTracker {
+    workspace_dir: PathBuf,
+    skills: Vec<TrackedSkill>,
+    /// Counters recorded since the last flush.
+    pending: Mutex<BTreeMap<String, SkillUsage>>,
+    /// Serializes flushes so concurrent merges don't drop counts.
+    write_lock: Mutex<()>,
+}
+
+impl SkillUsageTracker {
+    fn update(&self, apply: impl FnOnce(&mut BTreeMap<String, SkillUsage>)) {
+        apply(&mut self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+
+    /// Merge the pending counters into the usage file. Blocking file I/O.
+    fn flush(&self) {
+        let _guard = self.write_lock.lock().unwrap_or_else(|e| e.into_inner());
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap_or_else(|e| e.into_inner()));
+        if pending.is_empty() {
+            return;
+        }
+        let mut usage = read_usage_file(&self.workspace_dir);
+        merge_usage(&mut usage, pending);
+        if let Err(err) = save_usage(&self.workspace_dir, &usage) {
+            tracing::debug!("Failed to persist skill usage: {err}");
+        }
+    }
+}
+
+/// Flush `tracker` every [`FLUSH_INTERVAL`] until it is replaced.
+fn spawn_flusher(tracker: &Arc<SkillUsageTracker>) {
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    let tracker = Arc::downgrade(tracker);
+    handle.spawn(async move {
+        let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let Some(tracker) = tracker.upgrade() else {
+                break;
+            };
+            let _ = tokio::task::spawn_blocking(move || tracker.flush()).await;
+        }
+    });
+}
+
+static TRACKER: LazyLock<RwLock<Option<Arc<SkillUsageTracker>>>> =
+    LazyLock::new(|| RwLock::new(None));
+
+fn current_tracker() -> Option<Arc<SkillUsageTracker>> {
+    TRACKER.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Start tracking usage for the skills in the active prompt set.
+/// An empty skill set disables tracking.
+pub fn init_tracking(workspace_dir: &Path, skills: &[Skill]) {
+    let tracker = (!skills.is_empty()).then(|| {
+        Arc::new(SkillUsageTracker {
+            workspace_dir: workspace_dir.to_path_buf(),
+            skills: skills.iter().map(TrackedSkill::from_skill).collect(),
+            pending: Mutex::new(BTreeMap::new()),
+            write_lock: Mutex::new(()),
+        })
+    });
+    if let Some(tracker) = &tracker {
+        spawn_flusher(tracker);
+    }
+    let previous = std::mem::replace(
+        &mut *TRACKER.write().unwrap_or_else(|e| e.into_inner()),
+        tracker,
+    );
+    if let Some(previous) = previous {
+        // Keep file I/O off async callers, like the periodic flusher.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => drop(handle.spawn_blocking(move || previous.flush())),
+            Err(_) => previous.flush(),
+        }
+    }
+}
+
+/// Write counters recorded since the last flush to the usage file.
+/// Call before exiting so the last interval's counts are kept.
+pub async fn flush() {
+    let Some(tracker) = current_tracker() else {
+        return;
+    };
+    let _ = tokio::task::spawn_blocking(move || tracker.flush()).await;
+}
+
+/// Record that the tracked skill set was part of the prompt for one turn.
+pub fn record_prompt_inclusion() {
+    let Some(tracker) = current_tracker() else {
+        return;
+    };
+    tracker.update(|usage| {
+        for skill in &tracker.skills {
+            usage
+                .entry(skill.name.clone())
+                .or_default()
+                .prompt_inclusions += 1;
+        }
+    });
+}
+
+/// Record a tool execution, crediting the owning skill when one matches.
+pub fn record_tool_call(tool_name: &str, arguments: &serde_json::Value) {
+    let Some(tracker) = current_tracker() else {
+        return;
+    };
+    let Some(skill_name) = attribute_tool_call(&tracker.skills, tool_name, arguments) else {
+        return;
+    };
+    let skill_name = skill_name.to_string();
+    tracker.update(|usage| {
+        let entry = usage.entry(skill_name).or_default();
+        entry.tool_runs += 1;
+        entry.last_tool_run_at = Some(Utc::now());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::SkillTool;
+    use serde_json::json;
+
+    fn skill(name: &str, tools: Vec<SkillTool>) -> Skill {
+        Skill {
+            name: name.to_string(),
+            description: format!("{name} skill"),
+            version: "0.1.0".to_string(),
+            author: None,
+            tags: vec![],
+            tools,
+            prompts: vec![],
+            location: None,
+        }
+    }
+
+    fn shell_tool(name: &str, command: &str) -> SkillTool {
+        SkillTool {
+            name: name.to_string(),
+            description: String::new(),
+            kind: "shell".to_string(),
+            command: command.to_string(),
+            args: std::collections::HashMap::new(),
+            parameters: BTreeMap::new(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn attribute_tool_call_matches_tool_name_and_shell_prefix() {
+        let tracked: Vec<TrackedSkill> = [
+            skill(
+                "weather",
+                vec![shell_tool("forecast", "curl wttr.in/{city}")],
+            ),
+            skill("git", vec![shell_tool("git_log", "git log --oneline")]),
+        ]
+        .iter()
+        .map(TrackedSkill::from_skill)
+        .collect();
+
+        assert_eq!(
+            attribute_tool_call(&tracked, "forecast", &json!({})),
+            Some("weather")
+        );
+        assert_eq!(
+            attribute_tool_call(&tracked, "weather__forecast", &json!({})),
+            Some("weather")
+        );
+        assert_eq!(
+            attribute_tool_call(
+                &tracked,
+                "shell",
+                &json!({"command": "curl wttr.in/Berlin"})
+            ),
+            Some("weather")
+        );
+        assert_eq!(
+            attribute_tool_call(&tracked, "shell", &json!({"command": "ls -la"})),
+            None
+        );
+        assert_eq!(
+            attribute_tool_call(&tracked, "file_read", &json!({"path": "x"})),
+            None
+        );
+    }
+
+    #[test]
+    fn rank_prefers_used_then_least_offered() {
+        let skills = vec![
+            skill("stale", vec![]),
+            skill("busy", vec![]),
+            skill("new", vec![]),
+        ];
+        let mut usage = BTreeMap::new();
+        usage.insert(
+            "stale".to_string(),
+            SkillUsage {
+                prompt_inclusions: 50,
+                ..SkillUsage::default()
+            },
+        );
+        usage.insert(
+            "busy".to_string(),
+            SkillUsage {
+                prompt_inclusions: 50,
+                tool_runs: 7,
+                last_tool_run_at: None,
+            },
+        );
+
+        let ranked: Vec<String> = rank_skills_by_usage(skills, &usage)
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(ranked, vec!["busy", "new", "stale"]);
+    }
+
+    #[test]
+    fn select_prompt_skills_truncates_only_when_capped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let skills = vec![skill("a", vec![]), skill("b", vec![]), skill("c", vec![])];
+
+        assert_eq!(
+            select_prompt_skills(skills.clone(), tmp.path(), None).len(),
+            3
+        );
+        assert_eq!(select_prompt_skills(skills, tmp.path(), Some(2)).len(), 2);
+    }
+
+    #[test]
+    fn usage_round_trips_through_state_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut usage = BTreeMap::new();
+        usage.insert(
+            "weather".to_string(),
+            SkillUsage {
+                prompt_inclusions: 3,
+                tool_runs: 1,
+                last_tool_run_at: None,
+            },
+        );
+        save_usage(tmp.path(), &usage).unwrap();
+        assert_eq!(load_usage(tmp.path()), usage);
+
+        std::fs::write(usage_path(tmp.path()), "not json").unwrap();
+        assert!(load_usage(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn tracker_buffers_counts_until_flushed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tracker = SkillUsageTracker {
+            workspace_dir: tmp.path().to_path_buf(),
+            skills: vec![],
+            pending: Mutex::new(BTreeMap::new()),
+            write_lock: Mutex::new(()),
+        };
+        let bump = |usage: &mut BTreeMap<String, SkillUsage>| {
+            usage.entry("weather".into()).or_default().tool_runs += 1;
+        };
+
+        tracker.update(bump);
+        tracker.update(bump);
+        assert!(!usage_path(tmp.path()).exists());
+
+        tracker.flush();
+        tracker.update(bump);
+        tracker.flush();
+        assert_eq!(read_usage_file(tmp.path())["weather"].tool_runs, 3);
+        assert!(tracker.pending.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! Crash-safe workspace state (`zeroclaw state verify`).
+//!
+//! Daemon state (outbox queue, cron scheduler, pairing requests, and the
+//! other channel stores) lives in SQLite databases opened through
+//! [`open_db`]: write-ahead journaling with `synchronous = FULL`, so a commit
+//! is on disk before it returns and a write cut off by power loss is rolled
+//! back on the next open. Small values that used to be marker files are kept
+//! in the key-value table of `state/state.db` ([`get`], [`set`]). JSON state
+//! files are replaced with [`write_atomic`]: written to a temporary file,
+//! synced, and renamed over the old copy, so readers see the old or the new
+//! content and never a torn write.
+//!
+//! [`verify`] runs SQLite's integrity check on every state database and
+//! checks that the JSON files in `state/` parse.
+
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// State databases, relative to the workspace.
+pub const DATABASES: &[&str] = &[
+    KV_DB,
+    "channels/outbox.db",
+    "channels/pairing.db",
+    "channels/quarantine.db",
+    "channels/conversations.db",
+    "channels/preferences.db",
+    "channels/feedback.db",
+    "channels/review.db",
+    "cron/jobs.db",
+    "cron/checkins.db",
+    "goals/goals.db",
+    "experiments/experiments.db",
+    "lists/lists.db",
+    "memory/brain.db",
+];
+
+const KV_DB: &str = "state/state.db";
+/// How long a writer waits for another process holding the database lock.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Open (creating if needed) a state database with write-ahead journaling.
+pub fn open_db(path: &Path) -> Result<Connection> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let conn =
+        Connection::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = FULL;",
+    )
+    .with_context(|| format!("Failed to enable journaling for {}", path.display()))?;
+    Ok(conn)
+}
+
+/// Replace `path` with `contents` so that a crash leaves either the old or the
+/// new file, never a partial one.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(parent)
+        .with_context(|| format!("Failed to create {}", parent.display()))?;
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("Not a file path: {}", path.display()))?;
+    let tmp = parent.join(format!(".{}.tmp", file_name.to_string_lossy()));
+
+    let mut file = std::fs::File::create(&tmp)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    file.write_all(contents.as_ref())
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    drop(file);
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))?;
+    // Persist the rename itself; directories cannot be opened on Windows.
+    #[cfg(unix)]
+    if let Ok(dir) = std::fs::File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+fn with_kv<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let conn = open_db(&workspace_dir.join(KV_DB))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS kv (
+            key        TEXT PRIMARY KEY,
+            value      TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );",
+    )
+    .context("Failed to initialize state schema")?;
+    f(&conn)
+}
+
+/// Value stored under `key` and when it was last set.
+pub fn get(workspace_dir: &Path, key: &str) -> Result<Option<(String, DateTime<Utc>)>> {
+    with_kv(workspace_dir, |conn| {
+        let row: Option<(String, String)> = conn
+            .query_row(
+                "SELECT value, updated_at FROM kv WHERE key = ?1",
+                params![key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(row.map(|(value, updated_at)| {
+            let updated_at = DateTime::parse_from_rfc3339(&updated_at)
+                .map_or(DateTime::<Utc>::MIN_UTC, |at| at.with_timezone(&Utc));
+            (value, updated_at)
+        }))
+    })
+}
+
+pub fn set(workspace_dir: &Path, key: &str, value: &str) -> Result<()> {
+    with_kv(workspace_dir, |conn| {
+        conn.execute(
+            "INSERT INTO kv (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![
+                key,
+                value,
+                Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true)
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+/// Result of checking one state file.
+#[derive(Debug)]
+pub struct Check {
+    /// Path relative to the workspace.
+    pub path: String,
+    /// `None` when the file is intact.
+    pub problem: Option<String>,
+}
+
+/// Check every existing state database and JSON state file.
+pub fn verify(workspace_dir: &Path) -> Vec<Check> {
+    let mut checks: Vec<Check> = DATABASES
+        .iter()
+        .filter(|relative| workspace_dir.join(relative).exists())
+        .map(|relative| Check {
+            path: (*relative).to_string(),
+            problem: check_database(&workspace_dir.join(relative)).err(),
+        })
+        .collect();
+
+    let mut json_files: Vec<PathBuf> = std::fs::read_dir(workspace_dir.join("state"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        })
+        .unwrap_or_default();
+    json_files.sort();
+    checks.extend(json_files.into_iter().map(|path| {
+        Check {
+            path: path
+                .strip_prefix(workspace_dir)
+                .unwrap_or(&path)
+                .display()
+                .to_string(),
+            problem: check_json(&path).err(),
+        }
+    }));
+    checks
+}
+
+fn check_database(path: &Path) -> Result<(), String> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        .map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("PRAGMA integrity_check")
+        .map_err(|e| e.to_string())?;
+    let messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<String>>>())
+        .map_err(|e| e.to_string())?;
+    if messages == ["ok"] {
+        Ok(())
+    } else {
+        Err(messages.join("; "))
+    }
+}
+
+fn check_json(path: &Path) -> Result<(), String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str::<serde_json::Value>(&raw)
+        .map(|_| ())
+        .map_err(|e| format!("invalid JSON: {e}"))
+}
+
+pub fn handle_command(command: crate::StateCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::StateCommands::Verify => {
+            let checks = verify(&config.workspace_dir);
+            if checks.is_empty() {
+                println!("No state files in {}.", config.workspace_dir.display());
+                return Ok(());
+            }
+            let mut problems = 0;
+            for check in &checks {
+                match &check.problem {
+                    None => println!("  ✅ {}", check.path),
+                    Some(problem) => {
+                        problems += 1;
+                        println!("  ❌ {}: {problem}", check.path);
+                    }
+                }
+            }
+            if problems > 0 {
+                bail!(
+                    "{problems} of {} state files failed verification; restore them from a backup or remove them to start fresh",
+                    checks.len()
+                );
+            }
+            println!("\n{} state files verified.", checks.len());
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn write_atomic_replaces_contents_without_leftovers() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("state").join("cache.json");
+        write_atomic(&path, "{\"v\":1}").unwrap();
+        write_atomic(&path, "{\"v\":2}").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"v\":2}");
+        let entries = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(entries, 1);
+    }
+
+    #[test]
+    fn kv_values_round_trip_and_are_journaled() {
+        let tmp = TempDir::new().unwrap();
+        assert!(get(tmp.path(), "missing").unwrap().is_none());
+        set(tmp.path(), "open_skills.synced", "a").unwrap();
+        set(tmp.path(), "open_skills.synced", "b").unwrap();
+        let (value, updated_at) = get(tmp.path(), "open_skills.synced").unwrap().unwrap();
+        assert_eq!(value, "b");
+        assert!(Utc::now() - updated_at < chrono::Duration::minutes(1));
+
+        let conn = open_db(&tmp.path().join(KV_DB)).unwrap();
+        let mode: String = conn
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+    }
+
+    #[test]
+    fn verify_reports_damaged_files() {
+        let tmp = TempDir::new().unwrap();
+        set(tmp.path(), "key", "value").unwrap();
+        write_atomic(&tmp.path().join("state").join("good.json"), "{}").unwrap();
+        std::fs::write(tmp.path().join("state").join("bad.json"), "{\"trunc").unwrap();
+        std::fs::create_dir_all(tmp.path().join("cron")).unwrap();
+        std::fs::write(tmp.path().join("cron").join("jobs.db"), "not a database").unwrap();
+
+        let checks = verify(tmp.path());
+        let problem = |path: &str| {
+            checks
+                .iter()
+                .find(|check| check.path == path)
+                .unwrap_or_else(|| panic!("{path} was not checked"))
+                .problem
+                .clone()
+        };
+        assert!(problem(KV_DB).is_none());
+        assert!(problem("state/good.json").is_none());
+        assert!(problem("state/bad.json").unwrap().contains("invalid JSON"));
+        assert!(problem("cron/jobs.db").is_some());
+        assert!(!checks
+            .iter()
+            .any(|check| check.path == "channels/outbox.db"));
+    }
+}
@@ -0,0 +1,281 @@
+//! Versioned migrations for workspace state.
+//!
+//! The workspace records the state version it was last migrated to in
+//! `<workspace>/state/version.json`. On startup, [`run`] applies every newer
+//! migration in order, copying the files each one touches to
+//! `<workspace>/state/backups/<timestamp>-v<from>-v<to>/` first, and stamps
+//! the new version. A workspace with a higher version than this build knows
+//! was written by a newer release and is refused rather than risk
+//! corrupting it.
+//!
+//! Migrations must be safe on a fresh workspace (nothing to migrate) and on
+//! one that was partly migrated before a crash, since the version is only
+//! stamped after all of them succeed.
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// State version written by this build.
+pub const CURRENT_VERSION: u32 = 1;
+
+const VERSION_FILE: &str = "state/version.json";
+const BACKUP_DIR: &str = "state/backups";
+
+/// Files a migration reads and rewrites.
+#[derive(Debug, Clone, Copy)]
+pub enum Target {
+    /// The active `config.toml`.
+    Config,
+    /// A path relative to the workspace.
+    Workspace(&'static str),
+}
+
+/// Paths a migration operates on.
+pub struct StatePaths<'a> {
+    pub config_path: &'a Path,
+    pub workspace_dir: &'a Path,
+}
+
+impl StatePaths<'_> {
+    fn resolve(&self, target: Target) -> PathBuf {
+        match target {
+            Target::Config => self.config_path.to_path_buf(),
+            Target::Workspace(relative) => self.workspace_dir.join(relative),
+        }
+    }
+}
+
+pub struct Migration {
+    /// Version the workspace is at after this migration.
+    pub version: u32,
+    pub description: &'static str,
+    /// Backed up before the migration runs.
+    pub touches: &'static [Target],
+    pub apply: fn(&StatePaths<'_>) -> Result<()>,
+}
+
+/// Ordered by version, starting at 1.
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "start versioning workspace state",
+    touches: &[],
+    apply: stamp_only,
+}];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VersionStamp {
+    version: u32,
+    /// Release that wrote the stamp.
+    zeroclaw: String,
+    updated_at: String,
+}
+
+fn read_version(workspace_dir: &Path) -> Result<u32> {
+    let path = workspace_dir.join(VERSION_FILE);
+    if !path.exists() {
+        return Ok(0);
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let stamp: VersionStamp = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(stamp.version)
+}
+
+fn write_version(workspace_dir: &Path, version: u32) -> Result<()> {
+    let stamp = VersionStamp {
+        version,
+        zeroclaw: env!("CARGO_PKG_VERSION").to_string(),
+        updated_at: Utc::now().to_rfc3339(),
+    };
+    crate::state::write_atomic(
+        &workspace_dir.join(VERSION_FILE),
+        serde_json::to_string_pretty(&stamp)?,
+    )
+}
+
+/// Copy every existing file the pending migrations touch. Returns the
+/// backup directory, or `None` when there was nothing to copy.
+fn back_up(
+    paths: &StatePaths<'_>,
+    pending: &[&Migration],
+    from: u32,
+    to: u32,
+) -> Result<Option<PathBuf>> {
+    let sources: Vec<PathBuf> = pending
+        .iter()
+        .flat_map(|migration| migration.touches.iter())
+        .map(|target| paths.resolve(*target))
+        .filter(|path| path.is_file())
+        .collect();
+    if sources.is_empty() {
+        return Ok(None);
+    }
+
+    let dir = paths.workspace_dir.join(BACKUP_DIR).join(format!(
+        "{}-v{from}-v{to}",
+        Utc::now().format("%Y%m%dT%H%M%S")
+    ));
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create backup directory {}", dir.display()))?;
+    for source in sources {
+        let name = source.strip_prefix(paths.workspace_dir).map_or_else(
+            |_| PathBuf::from(source.file_name().unwrap_or_default()),
+            Path::to_path_buf,
+        );
+        let dest = dir.join(name);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&source, &dest)
+            .with_context(|| format!("Failed to back up {}", source.display()))?;
+    }
+    Ok(Some(dir))
+}
+
+fn run_migrations(paths: &StatePaths<'_>, migrations: &[Migration], current: u32) -> Result<usize> {
+    let stored = read_version(paths.workspace_dir)?;
+    if stored > current {
+        bail!(
+            "Workspace {} has state version {stored}, but this zeroclaw build supports up to {current}. \
+             It was last used by a newer release; upgrade zeroclaw, or restore a backup from {}.",
+            paths.workspace_dir.display(),
+            paths.workspace_dir.join(BACKUP_DIR).display()
+        );
+    }
+
+    let pending: Vec<&Migration> = migrations
+        .iter()
+        .filter(|migration| migration.version > stored && migration.version <= current)
+        .collect();
+    if pending.is_empty() {
+        if stored < current {
+            write_version(paths.workspace_dir, current)?;
+        }
+        return Ok(0);
+    }
+
+    if let Some(dir) = back_up(paths, &pending, stored, current)? {
+        tracing::info!(
+            "Migrating workspace state v{stored} → v{current} (backup: {})",
+            dir.display()
+        );
+    }
+    for migration in &pending {
+        tracing::info!(
+            version = migration.version,
+            "Applying state migration: {}",
+            migration.description
+        );
+        (migration.apply)(paths).with_context(|| {
+            format!(
+                "State migration v{} ({}) failed",
+                migration.version, migration.description
+            )
+        })?;
+    }
+    write_version(paths.workspace_dir, current)?;
+    Ok(pending.len())
+}
+
+/// Bring the workspace state up to [`CURRENT_VERSION`]. Returns the number
+/// of migrations applied.
+pub fn run(config_path: &Path, workspace_dir: &Path) -> Result<usize> {
+    run_migrations(
+        &StatePaths {
+            config_path,
+            workspace_dir,
+        },
+        MIGRATIONS,
+        CURRENT_VERSION,
+    )
+}
+
+/// v1: stamps the version only. Legacy config key spellings are still
+/// accepted as serde aliases, so `config.toml` is left as the user wrote it.
+fn stamp_only(_: &StatePaths<'_>) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    static APPLIED: AtomicUsize = AtomicUsize::new(0);
+
+    fn count_run(_: &StatePaths<'_>) -> Result<()> {
+        APPLIED.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    #[test]
+    fn migrations_are_numbered_in_order() {
+        for (i, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version as usize, i + 1);
+        }
+        assert_eq!(MIGRATIONS.last().map(|m| m.version), Some(CURRENT_VERSION));
+    }
+
+    #[test]
+    fn pending_migrations_run_once_with_a_backup() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        fs::write(&config_path, "model = \"gpt\"\n").unwrap();
+        let paths = StatePaths {
+            config_path: &config_path,
+            workspace_dir: tmp.path(),
+        };
+        let migrations = [
+            Migration {
+                version: 1,
+                description: "first",
+                touches: &[Target::Config],
+                apply: count_run,
+            },
+            Migration {
+                version: 2,
+                description: "second",
+                touches: &[],
+                apply: count_run,
+            },
+        ];
+
+        assert_eq!(run_migrations(&paths, &migrations, 2).unwrap(), 2);
+        assert_eq!(APPLIED.load(Ordering::SeqCst), 2);
+        assert_eq!(read_version(tmp.path()).unwrap(), 2);
+        let backups: Vec<_> = fs::read_dir(tmp.path().join(BACKUP_DIR)).unwrap().collect();
+        assert_eq!(backups.len(), 1);
+        let backup = backups[0].as_ref().unwrap().path();
+        assert!(backup.to_string_lossy().ends_with("-v0-v2"));
+        assert!(backup.join("config.toml").is_file());
+
+        assert_eq!(run_migrations(&paths, &migrations, 2).unwrap(), 0);
+        assert_eq!(APPLIED.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn newer_workspace_is_refused() {
+        let tmp = TempDir::new().unwrap();
+        write_version(tmp.path(), CURRENT_VERSION + 1).unwrap();
+        let err = run(&tmp.path().join("config.toml"), tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("newer release"), "{err}");
+    }
+
+    #[test]
+    fn first_run_stamps_the_version_and_leaves_config_alone() {
+        let tmp = TempDir::new().unwrap();
+        let config_path = tmp.path().join("config.toml");
+        let raw = "# my settings\nmodel_provider = \"openrouter\"  # legacy spelling\n";
+        fs::write(&config_path, raw).unwrap();
+
+        assert_eq!(run(&config_path, tmp.path()).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), raw);
+        assert_eq!(read_version(tmp.path()).unwrap(), CURRENT_VERSION);
+        assert!(!tmp.path().join(BACKUP_DIR).exists());
+    }
+}
@@ -0,0 +1,379 @@
+//! Offloading of large tool output to the artifact store (`[artifacts]`).
+//!
+//! Tools are wrapped in [`ArtifactingTool`] when the registry is built. A
+//! result whose estimated size exceeds the threshold is written to the
+//! [`ArtifactStore`] and replaced by a reference plus a summary (from the
+//! `[tool_result_summary]` model when available, otherwise the first and last
+//! lines). The agent pages through stored content with `artifact_read`.
+
+use super::expand_result::ResultSummarizer;
+use super::traits::{Tool, ToolResult};
+use crate::agent::classifier::{estimate_tokens, APPROX_CHARS_PER_TOKEN};
+use crate::artifacts::ArtifactStore;
+use crate::config::ArtifactsConfig;
+use crate::util::truncate_with_ellipsis;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::sync::Arc;
+
+pub const ARTIFACT_READ_TOOL_NAME: &str = "artifact_read";
+
+/// Default page size for `artifact_read`.
+const DEFAULT_PAGE_CHARS: usize = 8_000;
+/// Artifacts listed when `artifact_read` is called without an id.
+const MAX_LISTED: usize = 20;
+
+/// Shared store and settings for every wrapped tool.
+pub struct ToolOutputArtifacts {
+    store: Arc<ArtifactStore>,
+    summarizer: Option<Arc<ResultSummarizer>>,
+    threshold_tokens: usize,
+    preview_chars: usize,
+}
+
+impl ToolOutputArtifacts {
+    pub fn new(
+        store: Arc<ArtifactStore>,
+        summarizer: Option<Arc<ResultSummarizer>>,
+        config: &ArtifactsConfig,
+    ) -> Self {
+        Self {
+            store,
+            summarizer,
+            threshold_tokens: config.threshold_tokens,
+            preview_chars: config.preview_chars,
+        }
+    }
+
+    /// Largest output, in characters, that is passed through unchanged.
+    fn threshold_chars(&self) -> usize {
+        self.threshold_tokens.saturating_mul(APPROX_CHARS_PER_TOKEN)
+    }
+
+    fn is_oversized(&self, output: &str) -> bool {
+        estimate_tokens(output.chars().count()) > self.threshold_tokens
+    }
+
+    async fn offload(&self, tool: &str, output: String) -> String {
+        let total_chars = output.chars().count();
+        let meta = match self
+            .store
+            .put(tool, None, Some("text/plain"), output.as_bytes())
+        {
+            Ok(meta) => meta,
+            Err(e) => {
+                tracing::warn!(
+                    tool,
+                    "storing tool output as artifact failed, truncating: {e}"
+                );
+                return truncate_with_ellipsis(&output, self.threshold_chars());
+            }
+        };
+        let summary = match &self.summarizer {
+            Some(summarizer) => summarizer.summary(tool, &output).await,
+            None => preview(&output, self.preview_chars),
+        };
+        format!(
+            "[Stored as artifact {id}: output was {total_chars} characters. Call {ARTIFACT_READ_TOOL_NAME} with id \"{id}\" to page through it.]\n{summary}",
+            id = meta.short_id()
+        )
+    }
+}
+
+/// Start and end of `output` within `max_chars`.
+fn preview(output: &str, max_chars: usize) -> String {
+    let total = output.chars().count();
+    if total <= max_chars {
+        return output.to_string();
+    }
+    let tail_chars = max_chars / 4;
+    let head: String = output
+        .chars()
+        .take(max_chars.saturating_sub(tail_chars))
+        .collect();
+    let tail: String = output.chars().skip(total - tail_chars).collect();
+    format!("{head}\n…\n{tail}")
+}
+
+/// Tool wrapper that stores successful results above the threshold.
+pub struct ArtifactingTool {
+    inner: Arc<dyn Tool>,
+    artifacts: Arc<ToolOutputArtifacts>,
+}
+
+impl ArtifactingTool {
+    pub fn new(inner: Arc<dyn Tool>, artifacts: Arc<ToolOutputArtifacts>) -> Self {
+        Self { inner, artifacts }
+    }
+}
+
+#[async_trait]
+impl Tool for ArtifactingTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let mut result = self.inner.execute(args).await?;
+        if result.success && self.artifacts.is_oversized(&result.output) {
+            let output = std::mem::take(&mut result.output);
+            result.output = self.artifacts.offload(self.inner.name(), output).await;
+        }
+        Ok(result)
+    }
+}
+
+/// Wrap every tool in `tools` and append the `artifact_read` tool.
+pub fn wrap_registry(
+    tools: Vec<Arc<dyn Tool>>,
+    artifacts: Arc<ToolOutputArtifacts>,
+) -> Vec<Arc<dyn Tool>> {
+    let mut wrapped: Vec<Arc<dyn Tool>> = tools
+        .into_iter()
+        .map(|tool| Arc::new(ArtifactingTool::new(tool, artifacts.clone())) as Arc<dyn Tool>)
+        .collect();
+    wrapped.push(Arc::new(ArtifactReadTool::new(artifacts)));
+    wrapped
+}
+
+/// Lists stored artifacts and pages through their content.
+pub struct ArtifactReadTool {
+    artifacts: Arc<ToolOutputArtifacts>,
+}
+
+impl ArtifactReadTool {
+    pub fn new(artifacts: Arc<ToolOutputArtifacts>) -> Self {
+        Self { artifacts }
+    }
+
+    fn list(&self) -> anyhow::Result<ToolResult> {
+        let artifacts = self.artifacts.store.list()?;
+        if artifacts.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "No artifacts stored.".into(),
+                error: None,
+            });
+        }
+        let mut output = format!("{} artifact(s), most recently used first:", artifacts.len());
+        for meta in artifacts.iter().take(MAX_LISTED) {
+            let _ = write!(
+                output,
+                "\n- {} from {}, {} bytes",
+                meta.short_id(),
+                meta.source,
+                meta.size_bytes
+            );
+            if let Some(label) = &meta.label {
+                let _ = write!(output, ": {label}");
+            }
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for ArtifactReadTool {
+    fn name(&self) -> &str {
+        ARTIFACT_READ_TOOL_NAME
+    }
+
+    fn description(&self) -> &str {
+        "Read a stored artifact: large tool output or a downloaded file. Pass the id from the artifact note and use offset to page through it. Without an id, lists stored artifacts."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Artifact id from the note, e.g. \"3f2a9c0b71de\"; omit to list artifacts"
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Character offset to start reading from",
+                    "default": 0
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum characters to return",
+                    "default": DEFAULT_PAGE_CHARS
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let Some(id) = args
+            .get("id")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+        else {
+            return self.list();
+        };
+        let failure = |error: String| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        };
+        let (meta, content) = match self.artifacts.store.get(id) {
+            Ok(Some(found)) => found,
+            Ok(None) => {
+                return Ok(failure(format!(
+                    "No artifact with id '{id}'; call {ARTIFACT_READ_TOOL_NAME} without an id to list them"
+                )))
+            }
+            Err(e) => return Ok(failure(format!("Invalid artifact id: {e}"))),
+        };
+
+        let path = self.artifacts.store.content_path(&meta.id);
+        let Ok(text) = String::from_utf8(content) else {
+            return Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "[Artifact {} is binary ({} bytes, {}). Open {} with a tool that understands the format.]",
+                    meta.short_id(),
+                    meta.size_bytes,
+                    meta.content_type.as_deref().unwrap_or("unknown type"),
+                    path.display()
+                ),
+                error: None,
+            });
+        };
+
+        let offset = args
+            .get("offset")
+            .and_then(Value::as_u64)
+            .map_or(0, |v| usize::try_from(v).unwrap_or(usize::MAX));
+        // Pages stay below the threshold so they are never stored again.
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_PAGE_CHARS, |v| {
+                usize::try_from(v).unwrap_or(usize::MAX)
+            })
+            .clamp(1, self.artifacts.threshold_chars().max(1));
+
+        let total = text.chars().count();
+        let page: String = text.chars().skip(offset).take(limit).collect();
+        let end = (offset + page.chars().count()).min(total);
+        let more = if end < total {
+            format!(" Call again with offset {end} for more.")
+        } else {
+            String::new()
+        };
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "[Artifact {} from {}: characters {offset}..{end} of {total}.{more}]\n{page}",
+                meta.short_id(),
+                meta.label.as_deref().unwrap_or(&meta.source)
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the text argument"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args["text"].as_str().unwrap_or_default().to_string(),
+                error: None,
+            })
+        }
+    }
+
+    fn artifacts(dir: &std::path::Path) -> Arc<ToolOutputArtifacts> {
+        let config = ArtifactsConfig {
+            enabled: true,
+            threshold_tokens: 10,
+            preview_chars: 30,
+            ..ArtifactsConfig::default()
+        };
+        let store = Arc::new(ArtifactStore::from_config(&config, dir));
+        Arc::new(ToolOutputArtifacts::new(store, None, &config))
+    }
+
+    #[tokio::test]
+    async fn large_output_is_stored_and_paged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let tools = wrap_registry(vec![Arc::new(EchoTool)], artifacts(tmp.path()));
+        let echo = &tools[0];
+        let read = &tools[1];
+        assert_eq!(read.name(), ARTIFACT_READ_TOOL_NAME);
+
+        let small = echo.execute(json!({ "text": "short" })).await.unwrap();
+        assert_eq!(small.output, "short");
+
+        let raw = "line of output\n".repeat(20);
+        let large = echo.execute(json!({ "text": raw })).await.unwrap();
+        assert!(large.output.starts_with("[Stored as artifact "));
+        assert!(large.output.contains("line of output"));
+        assert!(large.output.chars().count() < raw.len());
+        let id = large.output["[Stored as artifact ".len()..]
+            .split(':')
+            .next()
+            .unwrap()
+            .to_string();
+
+        let page = read
+            .execute(json!({ "id": id, "offset": 0, "limit": 15 }))
+            .await
+            .unwrap();
+        assert!(page.success);
+        assert!(page.output.contains("characters 0..15 of 300"));
+        assert!(page.output.contains("offset 15"));
+
+        let listing = read.execute(json!({})).await.unwrap();
+        assert!(listing
+            .output
+            .contains(&format!("- {id} from echo, 300 bytes")));
+
+        let missing = read.execute(json!({ "id": "deadbeef" })).await.unwrap();
+        assert!(!missing.success);
+    }
+
+    #[test]
+    fn preview_keeps_start_and_end() {
+        let text: String = ('a'..='z').collect();
+        assert_eq!(preview(&text, 100), text);
+        let short = preview(&text, 8);
+        assert!(short.starts_with("abcdef"));
+        assert!(short.ends_with("yz"));
+    }
+}
@@ -0,0 +1,421 @@
+//! iMessage group chat management through a BlueBubbles server
+//! (`bluebubbles_group`).
+//!
+//! Creates, renames, and leaves group chats and adds or removes participants
+//! with the server's Private API, which must be enabled in BlueBubbles. In
+//! supervised mode the first call only describes the change; it is made when
+//! the agent calls again with `approved=true` after the user confirms.
+
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::BlueBubblesConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use async_trait::async_trait;
+use reqwest::Url;
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+const BLUEBUBBLES_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// One group chat change, validated from the tool arguments.
+#[derive(Debug)]
+enum GroupAction {
+    Create {
+        participants: Vec<String>,
+        name: Option<String>,
+        message: Option<String>,
+    },
+    Rename {
+        chat: String,
+        name: String,
+    },
+    AddParticipant {
+        chat: String,
+        address: String,
+    },
+    RemoveParticipant {
+        chat: String,
+        address: String,
+    },
+    Leave {
+        chat: String,
+    },
+}
+
+impl GroupAction {
+    fn parse(args: &serde_json::Value) -> Result<Self, String> {
+        let text = |key: &str| {
+            args.get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let required = |key: &str| text(key).ok_or_else(|| format!("Missing '{key}' parameter"));
+        let action = required("action")?;
+        match action.as_str() {
+            "create" => {
+                let participants: Vec<String> = args
+                    .get("participants")
+                    .and_then(serde_json::Value::as_array)
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(serde_json::Value::as_str)
+                            .map(str::trim)
+                            .filter(|address| !address.is_empty())
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if participants.is_empty() {
+                    return Err("'create' needs at least one address in 'participants'".into());
+                }
+                Ok(Self::Create {
+                    participants,
+                    name: text("name"),
+                    message: text("message"),
+                })
+            }
+            "rename" => Ok(Self::Rename {
+                chat: required("chat")?,
+                name: required("name")?,
+            }),
+            "add_participant" => Ok(Self::AddParticipant {
+                chat: required("chat")?,
+                address: required("address")?,
+            }),
+            "remove_participant" => Ok(Self::RemoveParticipant {
+                chat: required("chat")?,
+                address: required("address")?,
+            }),
+            "leave" => Ok(Self::Leave {
+                chat: required("chat")?,
+            }),
+            other => Err(format!(
+                "Unknown action '{other}'. Use create, rename, add_participant, remove_participant, or leave"
+            )),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Create {
+                participants,
+                name,
+                message,
+            } => {
+                let mut text = format!("Create a group chat with {}", participants.join(", "));
+                if let Some(name) = name {
+                    let _ = write!(text, " named '{name}'");
+                }
+                if let Some(message) = message {
+                    let _ = write!(text, " and send: {message}");
+                }
+                text
+            }
+            Self::Rename { chat, name } => format!("Rename group chat {chat} to '{name}'"),
+            Self::AddParticipant { chat, address } => {
+                format!("Add {address} to group chat {chat}")
+            }
+            Self::RemoveParticipant { chat, address } => {
+                format!("Remove {address} from group chat {chat}")
+            }
+            Self::Leave { chat } => format!("Leave group chat {chat}"),
+        }
+    }
+}
+
+pub struct BlueBubblesGroupTool {
+    config: BlueBubblesConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl BlueBubblesGroupTool {
+    pub fn new(config: BlueBubblesConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    /// `/api/v1/<segments>` on the server, authenticated with the password.
+    fn endpoint(&self, segments: &[&str]) -> anyhow::Result<Url> {
+        let mut url = Url::parse(self.config.server_url.trim())?;
+        url.path_segments_mut()
+            .map_err(|()| anyhow::anyhow!("bluebubbles.server_url cannot have a path"))?
+            .pop_if_empty()
+            .extend(["api", "v1"])
+            .extend(segments);
+        url.query_pairs_mut().append_pair(
+            "password",
+            self.config.password.as_deref().unwrap_or_default(),
+        );
+        Ok(url)
+    }
+
+    /// Send one request and return the `data` field of the response.
+    async fn call(
+        &self,
+        method: reqwest::Method,
+        segments: &[&str],
+        body: serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let client = crate::config::build_runtime_proxy_client("tool.bluebubbles");
+        let response = client
+            .request(method, self.endpoint(segments)?)
+            .json(&body)
+            .timeout(Duration::from_secs(BLUEBUBBLES_REQUEST_TIMEOUT_SECS))
+            .send()
+            .await
+            // The password rides in the query string; keep the URL out of errors.
+            .map_err(|e| anyhow::anyhow!("BlueBubbles request failed: {}", e.without_url()))?;
+        let status = response.status();
+        let payload: serde_json::Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            let detail = payload
+                .pointer("/error/message")
+                .or_else(|| payload.get("message"))
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or("unknown error");
+            anyhow::bail!("BlueBubbles API error ({status}): {detail}");
+        }
+        Ok(payload.get("data").cloned().unwrap_or_default())
+    }
+
+    async fn apply(&self, action: &GroupAction) -> anyhow::Result<String> {
+        use reqwest::Method;
+
+        match action {
+            GroupAction::Create {
+                participants,
+                name,
+                message,
+            } => {
+                let mut body = json!({
+                    "addresses": participants,
+                    "method": "private-api",
+                    "service": "iMessage",
+                });
+                if let Some(message) = message {
+                    body["message"] = json!(message);
+                }
+                let data = self.call(Method::POST, &["chat", "new"], body).await?;
+                let guid = data
+                    .get("guid")
+                    .and_then(serde_json::Value::as_str)
+                    .ok_or_else(|| anyhow::anyhow!("BlueBubbles did not return the new chat"))?
+                    .to_string();
+                if let Some(name) = name {
+                    self.call(
+                        Method::PUT,
+                        &["chat", guid.as_str()],
+                        json!({ "displayName": name }),
+                    )
+                    .await?;
+                }
+                Ok(format!("Created group chat {guid}"))
+            }
+            GroupAction::Rename { chat, name } => {
+                self.call(
+                    Method::PUT,
+                    &["chat", chat.as_str()],
+                    json!({ "displayName": name }),
+                )
+                .await?;
+                Ok(format!("Renamed group chat {chat} to '{name}'"))
+            }
+            GroupAction::AddParticipant { chat, address } => {
+                self.call(
+                    Method::POST,
+                    &["chat", chat.as_str(), "participant", "add"],
+                    json!({ "address": address }),
+                )
+                .await?;
+                Ok(format!("Added {address} to group chat {chat}"))
+            }
+            GroupAction::RemoveParticipant { chat, address } => {
+                self.call(
+                    Method::POST,
+                    &["chat", chat.as_str(), "participant", "remove"],
+                    json!({ "address": address }),
+                )
+                .await?;
+                Ok(format!("Removed {address} from group chat {chat}"))
+            }
+            GroupAction::Leave { chat } => {
+                self.call(Method::POST, &["chat", chat.as_str(), "leave"], json!({}))
+                    .await?;
+                Ok(format!("Left group chat {chat}"))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for BlueBubblesGroupTool {
+    fn name(&self) -> &str {
+        "bluebubbles_group"
+    }
+
+    fn description(&self) -> &str {
+        "Manage iMessage group chats through BlueBubbles, e.g. 'make a group with me and Alex \
+         called Trip Planning'. Actions: create (participants, optional name and first message), \
+         rename, add_participant, remove_participant, leave. 'chat' is the group chat GUID; \
+         participants are phone numbers or emails (look names up with contacts first). In \
+         supervised mode the first call only describes the change; call again with approved=true \
+         only after the user confirms."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create", "rename", "add_participant", "remove_participant", "leave"]
+                },
+                "chat": { "type": "string", "description": "Group chat GUID, e.g. 'iMessage;+;chat123'" },
+                "name": { "type": "string", "description": "Group name (create, rename)" },
+                "participants": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Phone numbers or emails to start the group with (create)"
+                },
+                "address": { "type": "string", "description": "Phone number or email (add_participant, remove_participant)" },
+                "message": { "type": "string", "description": "First message in the new group (create)" },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true only after the user confirmed the described change",
+                    "default": false
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = match GroupAction::parse(&args) {
+            Ok(action) => action,
+            Err(e) => return Ok(failure(e)),
+        };
+        let description = action.describe();
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.security.autonomy == AutonomyLevel::Supervised && !approved {
+            return Ok(ToolResult {
+                success: false,
+                output: description,
+                error: Some(
+                    "Not done: show the user this change and call bluebubbles_group again with \
+                     approved=true once they confirm"
+                        .into(),
+                ),
+            });
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "bluebubbles_group")
+        {
+            return Ok(failure(e));
+        }
+
+        match self.apply(&action).await {
+            Ok(output) => {
+                tracing::info!("bluebubbles_group: {description}");
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            Err(e) => Ok(failure(format!("{description} failed: {e}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(autonomy: AutonomyLevel) -> BlueBubblesGroupTool {
+        let config = BlueBubblesConfig {
+            enabled: true,
+            server_url: "http://bluebubbles.invalid:1234/".into(),
+            password: Some("p&ss".into()),
+        };
+        let security = SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        };
+        BlueBubblesGroupTool::new(config, Arc::new(security))
+    }
+
+    #[test]
+    fn endpoint_escapes_chat_guid_and_password() {
+        let url = tool(AutonomyLevel::Full)
+            .endpoint(&["chat", "iMessage;+;chat42", "participant", "add"])
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://bluebubbles.invalid:1234/api/v1/chat/iMessage;+;chat42/participant/add?password=p%26ss"
+        );
+        let url = tool(AutonomyLevel::Full)
+            .endpoint(&["chat", "a/b"])
+            .unwrap();
+        assert!(url.path().ends_with("/chat/a%2Fb"));
+    }
+
+    #[tokio::test]
+    async fn request_errors_do_not_leak_password() {
+        let mut tool = tool(AutonomyLevel::Full);
+        tool.config.server_url = "http://127.0.0.1:9/".into();
+        let err = tool
+            .call(reqwest::Method::GET, &["chat"], json!({}))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("BlueBubbles request failed"));
+        assert!(!err.contains("p%26ss") && !err.contains("password"));
+    }
+
+    #[tokio::test]
+    async fn supervised_mode_describes_change_until_approved() {
+        let tool = tool(AutonomyLevel::Supervised);
+        let result = tool
+            .execute(json!({
+                "action": "create",
+                "participants": ["+15551230000", "alex@example.com"],
+                "name": "Trip Planning"
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("approved=true"));
+        assert_eq!(
+            result.output,
+            "Create a group chat with +15551230000, alex@example.com named 'Trip Planning'"
+        );
+
+        let missing = tool
+            .execute(json!({"action": "rename", "name": "Trips"}))
+            .await
+            .unwrap();
+        assert_eq!(missing.error.as_deref(), Some("Missing 'chat' parameter"));
+
+        let read_only = BlueBubblesGroupTool {
+            security: Arc::new(SecurityPolicy {
+                autonomy: AutonomyLevel::ReadOnly,
+                ..SecurityPolicy::default()
+            }),
+            ..tool
+        };
+        let blocked = read_only
+            .execute(json!({"action": "leave", "chat": "iMessage;+;chat42"}))
+            .await
+            .unwrap();
+        assert!(blocked.error.unwrap().contains("read-only"));
+    }
+}
@@ -0,0 +1,149 @@
+use super::traits::{Tool, ToolResult};
+use crate::channels::broadcast;
+use crate::config::Config;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Sends one message to several `channel:recipient` targets.
+pub struct BroadcastTool {
+    security: Arc<SecurityPolicy>,
+    config: Arc<Config>,
+}
+
+impl BroadcastTool {
+    pub fn new(security: Arc<SecurityPolicy>, config: Config) -> Self {
+        Self {
+            security,
+            config: Arc::new(config),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for BroadcastTool {
+    fn name(&self) -> &str {
+        "broadcast"
+    }
+
+    fn description(&self) -> &str {
+        "Send one message to several recipients across channels (e.g. telegram:123456, slack:C0123). Sends are rate-limited per channel and the result lists which deliveries failed."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Message to send; each channel renders its own formatting"
+                },
+                "recipients": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Targets as 'channel:recipient', e.g. 'telegram:123456' or 'discord:<channel_id>'"
+                }
+            },
+            "required": ["message", "recipients"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.security.can_act() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: autonomy is read-only".into()),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Action blocked: rate limit exceeded".into()),
+            });
+        }
+
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'message' parameter"))?;
+        let recipients: Vec<&str> = args
+            .get("recipients")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'recipients' parameter"))?
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+
+        let targets = match broadcast::parse_targets(&recipients, &self.config.broadcast) {
+            Ok(targets) => targets,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+
+        let report = broadcast::broadcast(&self.config, targets, message).await;
+        let success = report.delivered() > 0;
+        Ok(ToolResult {
+            success,
+            output: report.summary(),
+            error: (!success).then(|| "No recipient received the broadcast".into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+
+    fn tool(level: AutonomyLevel) -> BroadcastTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: level,
+            ..SecurityPolicy::default()
+        });
+        let mut config = Config::default();
+        config.broadcast.per_channel_interval_ms = 0;
+        config.broadcast.allowed_targets = vec!["telegram:*".into()];
+        BroadcastTool::new(security, config)
+    }
+
+    #[tokio::test]
+    async fn read_only_autonomy_blocks_broadcast() {
+        let result = tool(AutonomyLevel::ReadOnly)
+            .execute(json!({"message": "hi", "recipients": ["telegram:1"]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("read-only"));
+    }
+
+    #[tokio::test]
+    async fn invalid_and_disallowed_targets_are_reported() {
+        let tool = tool(AutonomyLevel::Full);
+        let result = tool
+            .execute(json!({"message": "hi", "recipients": ["nochannel"]}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("channel:recipient"));
+
+        let result = tool
+            .execute(json!({"message": "hi", "recipients": ["slack:C1"]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            result.output,
+            "Broadcast delivered to 0 of 1 recipients.\n- slack:C1: not in [broadcast].allowed_targets"
+        );
+    }
+}
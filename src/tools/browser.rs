@@ -433,6 +433,10 @@ impl BrowserTool {
             anyhow::bail!("Host '{host}' not in browser.allowed_domains");
         }
 
+        self.security
+            .check_network_target(&host)
+            .map_err(anyhow::Error::msg)?;
+
         Ok(())
     }
 
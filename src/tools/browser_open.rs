@@ -49,6 +49,10 @@ impl BrowserOpenTool {
             anyhow::bail!("Host '{host}' is not in browser.allowed_domains");
         }
 
+        self.security
+            .check_network_target(&host)
+            .map_err(anyhow::Error::msg)?;
+
         Ok(url.to_string())
     }
 }
@@ -0,0 +1,982 @@
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const RATES_URL: &str = "https://open.er-api.com/v6/latest/USD";
+const RATES_FILE: &str = "fx_rates.json";
+/// Cached exchange rates are refreshed after this long.
+const RATES_MAX_AGE_SECS: i64 = 12 * 3600;
+const RATES_REQUEST_TIMEOUT_SECS: u64 = 10;
+const MAX_EXPRESSION_CHARS: usize = 500;
+
+/// Exponents of length, mass, time, temperature, data, and currency.
+type Dims = [i8; 6];
+
+const NONE: Dims = [0, 0, 0, 0, 0, 0];
+const LENGTH: Dims = [1, 0, 0, 0, 0, 0];
+const AREA: Dims = [2, 0, 0, 0, 0, 0];
+const VOLUME: Dims = [3, 0, 0, 0, 0, 0];
+const MASS: Dims = [0, 1, 0, 0, 0, 0];
+const TIME: Dims = [0, 0, 1, 0, 0, 0];
+const SPEED: Dims = [1, 0, -1, 0, 0, 0];
+const FORCE: Dims = [1, 1, -2, 0, 0, 0];
+const PRESSURE: Dims = [-1, 1, -2, 0, 0, 0];
+const ENERGY: Dims = [2, 1, -2, 0, 0, 0];
+const POWER: Dims = [2, 1, -3, 0, 0, 0];
+const TEMPERATURE: Dims = [0, 0, 0, 1, 0, 0];
+const DATA: Dims = [0, 0, 0, 0, 1, 0];
+const CURRENCY: Dims = [0, 0, 0, 0, 0, 1];
+
+/// Base unit symbols, in `Dims` order.
+const BASE_UNITS: [&str; 6] = ["m", "kg", "s", "K", "B", "USD"];
+
+/// `(names, factor to base units, dimensions)`. Lookups are case-sensitive so
+/// `B` (byte) and `b` (bit) stay distinct.
+const UNITS: &[(&[&str], f64, Dims)] = &[
+    // Length
+    (&["m", "meter", "meters", "metre", "metres"], 1.0, LENGTH),
+    (
+        &["km", "kilometer", "kilometers", "kilometre", "kilometres"],
+        1e3,
+        LENGTH,
+    ),
+    (&["cm", "centimeter", "centimeters"], 1e-2, LENGTH),
+    (&["mm", "millimeter", "millimeters"], 1e-3, LENGTH),
+    (&["um", "µm", "micrometer", "micrometers"], 1e-6, LENGTH),
+    (&["nm", "nanometer", "nanometers"], 1e-9, LENGTH),
+    (&["mi", "mile", "miles"], 1609.344, LENGTH),
+    (&["yd", "yard", "yards"], 0.9144, LENGTH),
+    (&["ft", "foot", "feet"], 0.3048, LENGTH),
+    (&["in", "inch", "inches"], 0.0254, LENGTH),
+    (&["nmi"], 1852.0, LENGTH),
+    // Area
+    (&["ha", "hectare", "hectares"], 1e4, AREA),
+    (&["acre", "acres"], 4_046.856_422_4, AREA),
+    // Volume
+    (
+        &["l", "L", "liter", "liters", "litre", "litres"],
+        1e-3,
+        VOLUME,
+    ),
+    (&["ml", "mL", "milliliter", "milliliters"], 1e-6, VOLUME),
+    (&["gal", "gallon", "gallons"], 3.785_411_784e-3, VOLUME),
+    (&["qt", "quart", "quarts"], 9.463_529_46e-4, VOLUME),
+    (&["pt", "pint", "pints"], 4.731_764_73e-4, VOLUME),
+    (&["cup", "cups"], 2.365_882_365e-4, VOLUME),
+    (&["floz"], 2.957_352_956_25e-5, VOLUME),
+    (&["tbsp"], 1.478_676_478_125e-5, VOLUME),
+    (&["tsp"], 4.928_921_593_75e-6, VOLUME),
+    // Mass
+    (&["kg", "kilogram", "kilograms"], 1.0, MASS),
+    (&["g", "gram", "grams"], 1e-3, MASS),
+    (&["mg", "milligram", "milligrams"], 1e-6, MASS),
+    (&["t", "tonne", "tonnes"], 1e3, MASS),
+    (&["lb", "lbs", "pound", "pounds"], 0.453_592_37, MASS),
+    (&["oz", "ounce", "ounces"], 0.028_349_523_125, MASS),
+    (&["st", "stone"], 6.350_293_18, MASS),
+    // Time
+    (&["s", "sec", "secs", "second", "seconds"], 1.0, TIME),
+    (&["ms", "millisecond", "milliseconds"], 1e-3, TIME),
+    (&["min", "mins", "minute", "minutes"], 60.0, TIME),
+    (&["h", "hr", "hrs", "hour", "hours"], 3600.0, TIME),
+    (&["day", "days"], 86_400.0, TIME),
+    (&["week", "weeks", "wk"], 604_800.0, TIME),
+    (&["year", "years", "yr"], 31_557_600.0, TIME),
+    // Speed
+    (&["mph"], 0.447_04, SPEED),
+    (&["kph", "kmh"], 1.0 / 3.6, SPEED),
+    (&["knot", "knots", "kn"], 1852.0 / 3600.0, SPEED),
+    // Force, pressure, energy, power
+    (&["N", "newton", "newtons"], 1.0, FORCE),
+    (&["Pa", "pascal"], 1.0, PRESSURE),
+    (&["kPa"], 1e3, PRESSURE),
+    (&["bar"], 1e5, PRESSURE),
+    (&["atm"], 101_325.0, PRESSURE),
+    (&["psi"], 6_894.757_293_168, PRESSURE),
+    (&["J", "joule", "joules"], 1.0, ENERGY),
+    (&["kJ"], 1e3, ENERGY),
+    (&["cal"], 4.184, ENERGY),
+    (&["kcal", "Cal"], 4184.0, ENERGY),
+    (&["Wh"], 3600.0, ENERGY),
+    (&["kWh"], 3.6e6, ENERGY),
+    (&["W", "watt", "watts"], 1.0, POWER),
+    (&["kW"], 1e3, POWER),
+    (&["hp"], 745.699_871_582_270_2, POWER),
+    // Temperature (scale only; offsets apply to plain `<n> <unit> to <unit>`)
+    (&["K", "kelvin"], 1.0, TEMPERATURE),
+    (&["C", "°C", "degC", "celsius"], 1.0, TEMPERATURE),
+    (&["F", "°F", "degF", "fahrenheit"], 5.0 / 9.0, TEMPERATURE),
+    // Data
+    (&["B", "byte", "bytes"], 1.0, DATA),
+    (&["b", "bit", "bits"], 0.125, DATA),
+    (&["KB", "kB"], 1e3, DATA),
+    (&["MB"], 1e6, DATA),
+    (&["GB"], 1e9, DATA),
+    (&["TB"], 1e12, DATA),
+    (&["KiB"], 1024.0, DATA),
+    (&["MiB"], 1_048_576.0, DATA),
+    (&["GiB"], 1_073_741_824.0, DATA),
+    (&["TiB"], 1_099_511_627_776.0, DATA),
+];
+
+/// Offset (in kelvin) of the zero point of each temperature scale.
+fn temperature_offset(unit: &str) -> Option<f64> {
+    match unit {
+        "K" | "kelvin" => Some(0.0),
+        "C" | "°C" | "degC" | "celsius" => Some(273.15),
+        "F" | "°F" | "degF" | "fahrenheit" => Some(459.67 * 5.0 / 9.0),
+        _ => None,
+    }
+}
+
+fn unit(name: &str) -> Option<Quantity> {
+    UNITS
+        .iter()
+        .find(|(names, _, _)| names.contains(&name))
+        .map(|(_, factor, dims)| Quantity {
+            value: *factor,
+            dims: *dims,
+        })
+}
+
+fn currency_symbol(name: &str) -> Option<&'static str> {
+    match name {
+        "$" => Some("USD"),
+        "€" => Some("EUR"),
+        "£" => Some("GBP"),
+        "¥" => Some("JPY"),
+        _ => None,
+    }
+}
+
+fn is_currency_code(name: &str) -> bool {
+    name.len() == 3 && name.chars().all(|c| c.is_ascii_uppercase())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quantity {
+    value: f64,
+    dims: Dims,
+}
+
+impl Quantity {
+    fn scalar(value: f64) -> Self {
+        Self { value, dims: NONE }
+    }
+
+    fn combine(self, other: Self, sign: i8) -> Self {
+        let mut dims = self.dims;
+        for (dim, o) in dims.iter_mut().zip(other.dims) {
+            *dim += sign * o;
+        }
+        Self {
+            value: if sign > 0 {
+                self.value * other.value
+            } else {
+                self.value / other.value
+            },
+            dims,
+        }
+    }
+}
+
+fn format_dims(dims: Dims) -> String {
+    let mut numerator = Vec::new();
+    let mut denominator = Vec::new();
+    for (unit, power) in BASE_UNITS.iter().zip(dims) {
+        let part = |p: i8| {
+            if p == 1 {
+                (*unit).to_string()
+            } else {
+                format!("{unit}^{p}")
+            }
+        };
+        match power {
+            0 => {}
+            p if p > 0 => numerator.push(part(p)),
+            p => denominator.push(part(-p)),
+        }
+    }
+    match (numerator.is_empty(), denominator.is_empty()) {
+        (true, true) => String::new(),
+        (false, true) => numerator.join("*"),
+        (true, false) => format!("1/{}", denominator.join("*")),
+        (false, false) => format!("{}/{}", numerator.join("*"), denominator.join("*")),
+    }
+}
+
+/// Format with up to 12 significant digits, trimming trailing zeros, so the
+/// same input always prints the same way.
+fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        return "0".into();
+    }
+    let magnitude = value.abs();
+    if !(1e-6..1e15).contains(&magnitude) {
+        return format!("{value:.6e}");
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let digits = magnitude.log10().floor() as i32;
+    let decimals = usize::try_from((11 - digits).max(0)).unwrap_or(0);
+    let text = format!("{value:.decimals$}");
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_ascii_digit() || chars[i] == '.' || chars[i] == '_')
+            {
+                i += 1;
+            }
+            // Exponent: 1e3, 2.5E-4
+            if i + 1 < chars.len()
+                && matches!(chars[i], 'e' | 'E')
+                && (chars[i + 1].is_ascii_digit()
+                    || (matches!(chars[i + 1], '+' | '-')
+                        && chars.get(i + 2).is_some_and(char::is_ascii_digit)))
+            {
+                i += 2;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().filter(|c| **c != '_').collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid number '{text}'"))?;
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '°' || c == 'µ' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if currency_symbol(&c.to_string()).is_some() {
+            tokens.push(Token::Ident(c.to_string()));
+            i += 1;
+        } else {
+            let op = match c {
+                '×' | '·' => '*',
+                '÷' => '/',
+                '−' => '-',
+                '*' if chars.get(i + 1) == Some(&'*') => {
+                    i += 1;
+                    '^'
+                }
+                '+' | '-' | '*' | '/' | '^' | '%' | '(' | ')' | ',' => c,
+                other => return Err(format!("Unexpected character '{other}'")),
+            };
+            tokens.push(Token::Op(op));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Words that act as operators rather than names.
+fn is_keyword(name: &str) -> bool {
+    matches!(name, "mod" | "of" | "to" | "as")
+}
+
+const FUNCTIONS: &[&str] = &[
+    "sqrt", "cbrt", "abs", "ln", "log", "log2", "log10", "exp", "sin", "cos", "tan", "asin",
+    "acos", "atan", "floor", "ceil", "round", "min", "max",
+];
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    rates: Option<&'a HashMap<String, f64>>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(name)) if name == word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse(mut self) -> Result<Quantity, String> {
+        let value = self.expr()?;
+        match self.peek() {
+            None => Ok(value),
+            Some(token) => Err(format!("Unexpected {}", describe(token))),
+        }
+    }
+
+    fn expr(&mut self) -> Result<Quantity, String> {
+        let mut left = self.term()?;
+        loop {
+            let sign = if self.eat_op('+') {
+                1.0
+            } else if self.eat_op('-') {
+                -1.0
+            } else {
+                return Ok(left);
+            };
+            let right = self.term()?;
+            if left.dims != right.dims {
+                return Err(format!(
+                    "Cannot add or subtract {} and {}",
+                    describe_dims(left.dims),
+                    describe_dims(right.dims)
+                ));
+            }
+            left.value += sign * right.value;
+        }
+    }
+
+    fn starts_primary(&self) -> bool {
+        match self.peek() {
+            Some(Token::Number(_) | Token::Op('(')) => true,
+            Some(Token::Ident(name)) => !is_keyword(name),
+            _ => false,
+        }
+    }
+
+    fn term(&mut self) -> Result<Quantity, String> {
+        let mut left = self.implicit()?;
+        loop {
+            if self.eat_op('*') || self.eat_word("of") {
+                left = left.combine(self.implicit()?, 1);
+            } else if self.eat_op('/') {
+                let right = self.implicit()?;
+                if right.value == 0.0 {
+                    return Err("Division by zero".into());
+                }
+                left = left.combine(right, -1);
+            } else if self.eat_word("mod") {
+                let right = self.implicit()?;
+                if left.dims != right.dims || right.value == 0.0 {
+                    return Err("mod needs two non-zero values of the same unit".into());
+                }
+                left.value = left.value.rem_euclid(right.value);
+            } else {
+                return Ok(left);
+            }
+        }
+    }
+
+    /// Juxtaposition binds tighter than `*` and `/`, so `100 km/h` and
+    /// `5 kg / 2 m^2` read the way they are written.
+    fn implicit(&mut self) -> Result<Quantity, String> {
+        let mut left = self.unary()?;
+        while self.starts_primary() {
+            left = left.combine(self.power()?, 1);
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Quantity, String> {
+        if self.eat_op('-') {
+            let mut value = self.unary()?;
+            value.value = -value.value;
+            Ok(value)
+        } else if self.eat_op('+') {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Result<Quantity, String> {
+        let base = self.postfix()?;
+        if !self.eat_op('^') {
+            return Ok(base);
+        }
+        let exponent = self.unary()?;
+        if exponent.dims != NONE {
+            return Err("Exponents must be plain numbers".into());
+        }
+        let mut dims = base.dims;
+        if dims != NONE {
+            if exponent.value.fract() != 0.0 || exponent.value.abs() > 8.0 {
+                return Err("Units can only be raised to small whole powers".into());
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let power = exponent.value as i8;
+            for dim in &mut dims {
+                *dim *= power;
+            }
+        }
+        Ok(Quantity {
+            value: base.value.powf(exponent.value),
+            dims,
+        })
+    }
+
+    fn postfix(&mut self) -> Result<Quantity, String> {
+        let mut value = self.primary()?;
+        while self.eat_op('%') {
+            value.value /= 100.0;
+        }
+        Ok(value)
+    }
+
+    fn primary(&mut self) -> Result<Quantity, String> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Quantity::scalar(value)),
+            Some(Token::Op('(')) => {
+                let value = self.expr()?;
+                if !self.eat_op(')') {
+                    return Err("Missing closing parenthesis".into());
+                }
+                Ok(value)
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::Op('(')) => {
+                self.pos += 1;
+                let mut args = vec![self.expr()?];
+                while self.eat_op(',') {
+                    args.push(self.expr()?);
+                }
+                if !self.eat_op(')') {
+                    return Err(format!("Missing closing parenthesis after {name}("));
+                }
+                call(&name, &args)
+            }
+            Some(Token::Ident(name)) => self.name(&name),
+            Some(token) => Err(format!("Unexpected {}", describe(&token))),
+            None => Err("Unexpected end of expression".into()),
+        }
+    }
+
+    fn name(&self, name: &str) -> Result<Quantity, String> {
+        match name {
+            "pi" | "π" => return Ok(Quantity::scalar(std::f64::consts::PI)),
+            "e" => return Ok(Quantity::scalar(std::f64::consts::E)),
+            _ => {}
+        }
+        if let Some(unit) = unit(name) {
+            return Ok(unit);
+        }
+        let code = currency_symbol(name).unwrap_or(name);
+        if is_currency_code(code) {
+            let rates = self
+                .rates
+                .ok_or_else(|| "Currency rates are unavailable".to_string())?;
+            let rate = rates
+                .get(code)
+                .filter(|rate| **rate > 0.0)
+                .ok_or_else(|| format!("Unknown currency '{code}'"))?;
+            return Ok(Quantity {
+                value: 1.0 / rate,
+                dims: CURRENCY,
+            });
+        }
+        Err(format!("Unknown unit or name '{name}'"))
+    }
+}
+
+fn call(name: &str, args: &[Quantity]) -> Result<Quantity, String> {
+    let first = args[0];
+    let plain = |f: fn(f64) -> f64| {
+        if args.len() != 1 {
+            return Err(format!("{name}() takes one argument"));
+        }
+        if first.dims != NONE {
+            return Err(format!("{name}() needs a plain number"));
+        }
+        Ok(Quantity::scalar(f(first.value)))
+    };
+    let keep_units = |f: fn(f64) -> f64| {
+        if args.len() != 1 {
+            return Err(format!("{name}() takes one argument"));
+        }
+        Ok(Quantity {
+            value: f(first.value),
+            dims: first.dims,
+        })
+    };
+    match name {
+        "sqrt" | "cbrt" => {
+            let root: i8 = if name == "sqrt" { 2 } else { 3 };
+            if args.len() != 1 || first.dims.iter().any(|d| d % root != 0) {
+                return Err(format!("{name}() needs one argument with divisible units"));
+            }
+            let mut dims = first.dims;
+            for dim in &mut dims {
+                *dim /= root;
+            }
+            let value = if root == 2 {
+                first.value.sqrt()
+            } else {
+                first.value.cbrt()
+            };
+            Ok(Quantity { value, dims })
+        }
+        "abs" => keep_units(f64::abs),
+        "floor" => keep_units(f64::floor),
+        "ceil" => keep_units(f64::ceil),
+        "round" => keep_units(f64::round),
+        "ln" => plain(f64::ln),
+        "log" | "log10" => plain(f64::log10),
+        "log2" => plain(f64::log2),
+        "exp" => plain(f64::exp),
+        "sin" => plain(f64::sin),
+        "cos" => plain(f64::cos),
+        "tan" => plain(f64::tan),
+        "asin" => plain(f64::asin),
+        "acos" => plain(f64::acos),
+        "atan" => plain(f64::atan),
+        "min" | "max" => {
+            if args.iter().any(|arg| arg.dims != first.dims) {
+                return Err(format!("{name}() arguments must share a unit"));
+            }
+            let pick = args.iter().copied().reduce(|a, b| {
+                let a_wins = if name == "min" {
+                    a.value <= b.value
+                } else {
+                    a.value >= b.value
+                };
+                if a_wins {
+                    a
+                } else {
+                    b
+                }
+            });
+            Ok(pick.unwrap_or(first))
+        }
+        other => Err(format!(
+            "Unknown function '{other}' (available: {})",
+            FUNCTIONS.join(", ")
+        )),
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Number(value) => format!("number {}", format_number(*value)),
+        Token::Ident(name) => format!("'{name}'"),
+        Token::Op(op) => format!("'{op}'"),
+    }
+}
+
+fn describe_dims(dims: Dims) -> String {
+    if dims == NONE {
+        "a plain number".into()
+    } else {
+        format_dims(dims)
+    }
+}
+
+/// Split `<expr> to <units>` (also `as`, or a final `in` followed by units).
+fn split_conversion(tokens: &[Token]) -> (&[Token], Option<&[Token]>) {
+    let position = tokens
+        .iter()
+        .rposition(|t| matches!(t, Token::Ident(w) if w == "to" || w == "as"))
+        .or_else(|| {
+            tokens
+                .iter()
+                .rposition(|t| matches!(t, Token::Ident(w) if w == "in"))
+                .filter(|&i| {
+                    i > 0 && matches!(tokens.get(i + 1), Some(Token::Ident(_) | Token::Op('(')))
+                })
+        });
+    match position {
+        Some(i) if i > 0 && i + 1 < tokens.len() => (&tokens[..i], Some(&tokens[i + 1..])),
+        _ => (tokens, None),
+    }
+}
+
+fn token_text(tokens: &[Token]) -> String {
+    let mut text = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Number(value) => text.push_str(&format_number(*value)),
+            Token::Ident(name) => {
+                if i > 0 && !text.ends_with(['(', '/', '^', '*']) {
+                    text.push(' ');
+                }
+                text.push_str(name);
+            }
+            Token::Op(op) => text.push(*op),
+        }
+    }
+    text
+}
+
+/// `<n> <temperature unit> to <temperature unit>` converts between scale zero
+/// points; anywhere else temperature units only scale differences.
+fn convert_temperature(source: &[Token], to: &str) -> Option<f64> {
+    let (sign, rest) = match source {
+        [Token::Op('-'), rest @ ..] => (-1.0, rest),
+        rest => (1.0, rest),
+    };
+    let [Token::Number(value), Token::Ident(from)] = rest else {
+        return None;
+    };
+    let kelvin = sign * value * unit(from)?.value + temperature_offset(from)?;
+    Some((kelvin - temperature_offset(to)?) / unit(to)?.value)
+}
+
+/// Evaluate an expression, with an optional `to <units>` conversion.
+fn evaluate(expression: &str, rates: Option<&HashMap<String, f64>>) -> Result<String, String> {
+    let tokens = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err("Expression is empty".into());
+    }
+    let (source, target) = split_conversion(&tokens);
+
+    if let Some([Token::Ident(to)]) = target {
+        if let Some(converted) = convert_temperature(source, to) {
+            return Ok(format!("{} {to}", format_number(converted)));
+        }
+    }
+
+    let value = Parser {
+        tokens: source,
+        pos: 0,
+        rates,
+    }
+    .parse()?;
+    if !value.value.is_finite() {
+        return Err("Result is not a finite number".into());
+    }
+
+    match target {
+        Some(target) => {
+            let unit = Parser {
+                tokens: target,
+                pos: 0,
+                rates,
+            }
+            .parse()?;
+            if unit.dims != value.dims {
+                return Err(format!(
+                    "Cannot convert {} to {}",
+                    describe_dims(value.dims),
+                    describe_dims(unit.dims)
+                ));
+            }
+            if unit.value == 0.0 {
+                return Err("Cannot convert to a zero-sized unit".into());
+            }
+            Ok(format!(
+                "{} {}",
+                format_number(value.value / unit.value),
+                token_text(target)
+            ))
+        }
+        None if value.dims == NONE => Ok(format_number(value.value)),
+        None => Ok(format!(
+            "{} {}",
+            format_number(value.value),
+            format_dims(value.dims)
+        )),
+    }
+}
+
+/// Whether evaluating `expression` needs exchange rates.
+fn mentions_currency(expression: &str) -> bool {
+    tokenize(expression).is_ok_and(|tokens| {
+        tokens.iter().any(|token| match token {
+            Token::Ident(name) => {
+                currency_symbol(name).is_some() || (is_currency_code(name) && unit(name).is_none())
+            }
+            _ => false,
+        })
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RatesCache {
+    /// Unix time the rates were published.
+    updated_at: i64,
+    /// Units of each currency per US dollar.
+    rates: HashMap<String, f64>,
+}
+
+#[derive(Deserialize)]
+struct RatesResponse {
+    result: String,
+    time_last_update_unix: i64,
+    rates: HashMap<String, f64>,
+}
+
+/// Deterministic calculator with unit and currency conversion.
+///
+/// Expressions are parsed and evaluated locally; nothing is delegated to the
+/// model. Currency conversion uses exchange rates cached in
+/// `state/fx_rates.json`, refreshed at most every 12 hours.
+pub struct CalcTool {
+    rates_path: PathBuf,
+}
+
+impl CalcTool {
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self {
+            rates_path: workspace_dir.join("state").join(RATES_FILE),
+        }
+    }
+
+    fn read_cache(&self) -> Option<RatesCache> {
+        let raw = std::fs::read_to_string(&self.rates_path).ok()?;
+        serde_json::from_str(&raw).ok()
+    }
+
+    async fn fetch_rates(&self) -> anyhow::Result<RatesCache> {
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.calc",
+            RATES_REQUEST_TIMEOUT_SECS,
+            5,
+        );
+        let response: RatesResponse = client
+            .get(RATES_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if response.result != "success" || response.rates.is_empty() {
+            anyhow::bail!("exchange rate service returned '{}'", response.result);
+        }
+        let cache = RatesCache {
+            updated_at: response.time_last_update_unix,
+            rates: response.rates,
+        };
+        crate::state::write_atomic(&self.rates_path, serde_json::to_string(&cache)?)?;
+        Ok(cache)
+    }
+
+    /// Cached rates, refreshed when older than `RATES_MAX_AGE_SECS`. Falls
+    /// back to stale rates when the refresh fails.
+    async fn rates(&self) -> Result<RatesCache, String> {
+        let cached = self.read_cache();
+        let now = chrono::Utc::now().timestamp();
+        if let Some(cache) = cached {
+            if now - cache.updated_at < RATES_MAX_AGE_SECS {
+                return Ok(cache);
+            }
+            return match self.fetch_rates().await {
+                Ok(fresh) => Ok(fresh),
+                Err(e) => {
+                    tracing::warn!("calc: exchange rate refresh failed, using cached rates: {e}");
+                    Ok(cache)
+                }
+            };
+        }
+        match self.fetch_rates().await {
+            Ok(fresh) => Ok(fresh),
+            Err(e) => Err(format!("Currency rates are unavailable: {e}")),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CalcTool {
+    fn name(&self) -> &str {
+        "calc"
+    }
+
+    fn description(&self) -> &str {
+        "Evaluate arithmetic exactly and convert units or currencies. Always use this instead of doing math yourself. Examples: '17.5% of 2340', '(3 + 4)^2 / 7', 'sqrt(2)', '72 F to C', '5 km/h to mph', '2 GiB to MB', '120 EUR to USD'."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "expression": {
+                    "type": "string",
+                    "description": "Expression such as '2^10 * 3', '15% of 80', '3 ft + 4 in to cm', or '50 USD to JPY'. Operators: + - * / ^ % mod; functions: sqrt, cbrt, abs, ln, log, log2, exp, sin, cos, tan, asin, acos, atan, floor, ceil, round, min, max."
+                }
+            },
+            "required": ["expression"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let expression = args
+            .get("expression")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'expression' parameter"))?
+            .trim();
+        if expression.chars().count() > MAX_EXPRESSION_CHARS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Expression is longer than {MAX_EXPRESSION_CHARS} characters"
+                )),
+            });
+        }
+
+        let rates = if mentions_currency(expression) {
+            match self.rates().await {
+                Ok(rates) => Some(rates),
+                Err(error) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(error),
+                    });
+                }
+            }
+        } else {
+            None
+        };
+
+        match evaluate(expression, rates.as_ref().map(|cache| &cache.rates)) {
+            Ok(mut output) => {
+                if let Some(cache) = rates {
+                    if let Some(at) = chrono::DateTime::from_timestamp(cache.updated_at, 0) {
+                        let _ = write!(
+                            output,
+                            " (exchange rates as of {})",
+                            at.format("%Y-%m-%d %H:%M UTC")
+                        );
+                    }
+                }
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            Err(error) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(error),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn eval(expression: &str) -> String {
+        evaluate(expression, None).unwrap_or_else(|e| format!("error: {e}"))
+    }
+
+    #[test]
+    fn arithmetic_is_exact_and_deterministic() {
+        assert_eq!(eval("1 + 2 * 3"), "7");
+        assert_eq!(eval("(1 + 2) * 3"), "9");
+        assert_eq!(eval("2^10"), "1024");
+        assert_eq!(eval("2 ** 3 ^ 2"), "512");
+        assert_eq!(eval("-3^2"), "-9");
+        assert_eq!(eval("0.1 + 0.2"), "0.3");
+        assert_eq!(eval("10 / 4"), "2.5");
+        assert_eq!(eval("17 mod 5"), "2");
+        assert_eq!(eval("15% of 80"), "12");
+        assert_eq!(eval("2(3 + 4)"), "14");
+        assert_eq!(eval("sqrt(2)"), "1.41421356237");
+        assert_eq!(eval("max(3, 9, 4) - min(2, 1)"), "8");
+        assert_eq!(eval("1_000_000 * 3"), "3000000");
+        assert_eq!(eval("2.5e-3 * 4"), "0.01");
+        assert_eq!(eval("1 / 0"), "error: Division by zero");
+        assert_eq!(eval("2 +"), "error: Unexpected end of expression");
+        assert_eq!(eval("foo(2)"), "error: Unknown function 'foo' (available: sqrt, cbrt, abs, ln, log, log2, log10, exp, sin, cos, tan, asin, acos, atan, floor, ceil, round, min, max)");
+    }
+
+    #[test]
+    fn converts_units() {
+        assert_eq!(eval("5 km to mi"), "3.10685596119 mi");
+        assert_eq!(eval("3 ft + 4 in to cm"), "101.6 cm");
+        assert_eq!(eval("6 ft in m"), "1.8288 m");
+        assert_eq!(eval("3 in"), "0.0762 m");
+        assert_eq!(eval("100 km/h to mph"), "62.1371192237 mph");
+        assert_eq!(eval("2 GiB to MB"), "2147.483648 MB");
+        assert_eq!(eval("1 kWh to J"), "3600000 J");
+        assert_eq!(eval("10 m * 20 m to acre"), "0.0494210762934 acre");
+        assert_eq!(eval("72 F to C"), "22.2222222222 C");
+        assert_eq!(eval("-40 C to F"), "-40 F");
+        assert_eq!(eval("300 K to celsius"), "26.85 celsius");
+        assert_eq!(eval("5 kg / 2 m^2"), "2.5 kg/m^2");
+        assert_eq!(eval("5 kg to m"), "error: Cannot convert kg to m");
+        assert_eq!(eval("1 m + 1 s"), "error: Cannot add or subtract m and s");
+    }
+
+    #[tokio::test]
+    async fn currency_uses_cached_rates() {
+        let tmp = TempDir::new().unwrap();
+        let tool = CalcTool::new(tmp.path());
+        std::fs::create_dir_all(tmp.path().join("state")).unwrap();
+        let cache = RatesCache {
+            updated_at: chrono::Utc::now().timestamp(),
+            rates: HashMap::from([
+                ("USD".into(), 1.0),
+                ("EUR".into(), 0.8),
+                ("JPY".into(), 150.0),
+            ]),
+        };
+        std::fs::write(
+            tmp.path().join("state").join(RATES_FILE),
+            serde_json::to_string(&cache).unwrap(),
+        )
+        .unwrap();
+
+        let result = tool
+            .execute(json!({"expression": "100 EUR to USD"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("125 USD (exchange rates as of "));
+
+        let result = tool
+            .execute(json!({"expression": "$20 + 10 USD to JPY"}))
+            .await
+            .unwrap();
+        assert!(result.output.starts_with("4500 JPY"));
+
+        let result = tool.execute(json!({"expression": "5 XYZ"})).await.unwrap();
+        assert_eq!(result.error.as_deref(), Some("Unknown currency 'XYZ'"));
+
+        // Plain math never touches the rates cache.
+        assert!(!mentions_currency("2 GiB to MB"));
+        let result = tool.execute(json!({"expression": "6 * 7"})).await.unwrap();
+        assert_eq!(result.output, "42");
+    }
+}
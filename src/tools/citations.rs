@@ -0,0 +1,215 @@
+//! Source tracking for web results (`[channels_config.citations]`).
+//!
+//! A channel turn runs its tool loop inside [`with_collector`]. While it
+//! runs, `web_search_tool` and `web_fetch` record the results they return,
+//! and once the reply is ready [`SourceCollector::source_list`] renders the
+//! sources that contributed to it.
+
+use crate::config::CitationsConfig;
+use parking_lot::Mutex;
+use std::fmt::Write;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SourceKind {
+    /// Listed by a search; counts only when the reply refers to it.
+    Search,
+    /// Read in full; always counts.
+    Fetch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Source {
+    kind: SourceKind,
+    title: String,
+    url: String,
+}
+
+/// Web sources seen during one agent turn.
+#[derive(Debug, Default)]
+pub struct SourceCollector {
+    sources: Mutex<Vec<Source>>,
+}
+
+tokio::task_local! {
+    static COLLECTOR: Arc<SourceCollector>;
+}
+
+/// Run `future` with web tools recording their sources into `collector`.
+pub async fn with_collector<F: std::future::Future>(
+    collector: Option<Arc<SourceCollector>>,
+    future: F,
+) -> F::Output {
+    match collector {
+        Some(collector) => COLLECTOR.scope(collector, future).await,
+        None => future.await,
+    }
+}
+
+/// Record a source for the current turn, if one is being tracked.
+pub(super) fn record(kind: SourceKind, title: &str, url: &str) {
+    let url = url.trim();
+    if url.is_empty() {
+        return;
+    }
+    let _ = COLLECTOR.try_with(|collector| {
+        let mut sources = collector.sources.lock();
+        match sources.iter_mut().find(|source| source.url == url) {
+            Some(existing) => {
+                if kind == SourceKind::Fetch {
+                    existing.kind = SourceKind::Fetch;
+                }
+                if existing.title.is_empty() {
+                    existing.title = title.trim().to_string();
+                }
+            }
+            None => sources.push(Source {
+                kind,
+                title: title.trim().to_string(),
+                url: url.to_string(),
+            }),
+        }
+    });
+}
+
+/// Host of `url` without a leading `www.`.
+fn host(url: &str) -> Option<String> {
+    let host = reqwest::Url::parse(url)
+        .ok()?
+        .host_str()?
+        .to_ascii_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+impl SourceCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sources that contributed to `reply`, in the order they were seen.
+    fn contributing(&self, reply: &str) -> Vec<Source> {
+        let reply_lower = reply.to_lowercase();
+        self.sources
+            .lock()
+            .iter()
+            .filter(|source| {
+                source.kind == SourceKind::Fetch
+                    || reply.contains(&source.url)
+                    || host(&source.url).is_some_and(|host| reply_lower.contains(&host))
+                    || (source.title.chars().count() >= 8
+                        && reply_lower.contains(&source.title.to_lowercase()))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Compact source list to append to `reply`, or `None` when no web
+    /// result contributed to it.
+    pub fn source_list(&self, reply: &str, config: &CitationsConfig) -> Option<String> {
+        let sources = self.contributing(reply);
+        if sources.is_empty() {
+            return None;
+        }
+        let mut list = config.heading.trim().to_string();
+        for source in sources.iter().take(config.max_sources) {
+            let title = if source.title.is_empty() {
+                host(&source.url).unwrap_or_default()
+            } else {
+                source.title.clone()
+            };
+            if title.is_empty() {
+                let _ = write!(list, "\n- {}", source.url);
+            } else {
+                let _ = write!(list, "\n- {title}: {}", source.url);
+            }
+        }
+        Some(list.trim_start().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CitationsConfig {
+        CitationsConfig {
+            enabled: true,
+            max_sources: 2,
+            ..CitationsConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn records_only_inside_a_collector_scope() {
+        record(SourceKind::Fetch, "Outside", "https://outside.example/");
+
+        let collector = Arc::new(SourceCollector::new());
+        with_collector(Some(collector.clone()), async {
+            record(
+                SourceKind::Search,
+                "Rust Release Notes",
+                "https://blog.rust-lang.org/1.80",
+            );
+            record(
+                SourceKind::Search,
+                "Unrelated result",
+                "https://other.example/page",
+            );
+            record(SourceKind::Fetch, "", "https://blog.rust-lang.org/1.80");
+        })
+        .await;
+
+        let list = collector
+            .source_list("Rust 1.80 shipped LazyLock.", &config())
+            .unwrap();
+        assert_eq!(
+            list,
+            "Sources:\n- Rust Release Notes: https://blog.rust-lang.org/1.80"
+        );
+    }
+
+    #[test]
+    fn search_results_count_when_the_reply_refers_to_them() {
+        let collector = SourceCollector::new();
+        collector.sources.lock().extend([
+            Source {
+                kind: SourceKind::Search,
+                title: "Weather in Lisbon".into(),
+                url: "https://www.ipma.pt/lisboa".into(),
+            },
+            Source {
+                kind: SourceKind::Search,
+                title: "Tides".into(),
+                url: "https://tides.example/lisbon".into(),
+            },
+        ]);
+
+        assert!(collector
+            .source_list("Sunny all week.", &config())
+            .is_none());
+        let list = collector
+            .source_list("Per ipma.pt it stays sunny.", &config())
+            .unwrap();
+        assert_eq!(
+            list,
+            "Sources:\n- Weather in Lisbon: https://www.ipma.pt/lisboa"
+        );
+    }
+
+    #[test]
+    fn list_is_capped_and_falls_back_to_host_for_untitled_pages() {
+        let collector = SourceCollector::new();
+        for n in 0..3 {
+            collector.sources.lock().push(Source {
+                kind: SourceKind::Fetch,
+                title: String::new(),
+                url: format!("https://docs{n}.example/page"),
+            });
+        }
+        let list = collector.source_list("Done.", &config()).unwrap();
+        assert_eq!(
+            list,
+            "Sources:\n- docs0.example: https://docs0.example/page\n- docs1.example: https://docs1.example/page"
+        );
+    }
+}
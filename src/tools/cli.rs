@@ -0,0 +1,191 @@
+//! `zeroclaw tools` — inspect the tool registry and run a tool without a model.
+//!
+//! The registry is built exactly as for `zeroclaw agent`: same security
+//! policy, runtime, memory, skill tools, and peripherals. `tools test` calls
+//! [`Tool::execute`] directly, so approval prompts do not apply; the policy
+//! checks inside each tool still do.
+
+use super::error::ToolError;
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use crate::memory::{self, Memory};
+use crate::runtime;
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Handle `zeroclaw tools <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::ToolCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::ToolCommands::List { schema } => {
+            let tools = build_registry(config).await?;
+            println!("🔧 Tools ({}):", tools.len());
+            for tool in &tools {
+                let parameters = tool.parameters_schema();
+                println!(
+                    "- {} ({}): {}",
+                    tool.name(),
+                    parameter_summary(&parameters),
+                    first_line(tool.description())
+                );
+                if schema {
+                    println!("{}", serde_json::to_string_pretty(&parameters)?);
+                }
+            }
+            Ok(())
+        }
+        crate::ToolCommands::Test { name, args } => {
+            let args = parse_args(&args)?;
+            let tools = build_registry(config).await?;
+            let Some(tool) = tools.iter().find(|tool| tool.name() == name) else {
+                bail!("Unknown tool `{name}`. Run `zeroclaw tools list` to see registered tools.");
+            };
+            let result = tool
+                .execute(args)
+                .await
+                .with_context(|| format!("Tool `{name}` returned an error"))?;
+            print_result(&name, &result);
+            if !result.success {
+                bail!("Tool `{name}` reported a failure");
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The tool registry `zeroclaw agent` would use with this config.
+async fn build_registry(config: &Config) -> Result<Vec<Box<dyn Tool>>> {
+    let runtime: Arc<dyn runtime::RuntimeAdapter> =
+        Arc::from(runtime::create_runtime(&config.runtime)?);
+    let security = Arc::new(SecurityPolicy::from_config(
+        &config.autonomy,
+        &config.workspace_dir,
+    ));
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+        &config.memory,
+        Some(&config.storage.provider.config),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?);
+    let (composio_key, composio_entity_id) = if config.composio.enabled {
+        (
+            config.composio.api_key.as_deref(),
+            Some(config.composio.entity_id.as_str()),
+        )
+    } else {
+        (None, None)
+    };
+
+    let mut tools = super::all_tools_with_runtime(
+        Arc::new(config.clone()),
+        &security,
+        runtime,
+        mem,
+        composio_key,
+        composio_entity_id,
+        &config.browser,
+        &config.http_request,
+        &config.web_fetch,
+        &config.workspace_dir,
+        &config.agents,
+        config.api_key.as_deref(),
+        config,
+    );
+    tools.extend(crate::peripherals::create_peripheral_tools(&config.peripherals).await?);
+    super::remove_disabled(&mut tools, &config.tools);
+    Ok(tools)
+}
+
+/// Parse `--args`; tools always take a JSON object.
+fn parse_args(raw: &str) -> Result<Value> {
+    let value: Value =
+        serde_json::from_str(raw).context("--args must be valid JSON, e.g. '{\"path\": \"x\"}'")?;
+    if !value.is_object() {
+        bail!("--args must be a JSON object, got: {value}");
+    }
+    Ok(value)
+}
+
+/// Parameter names from a JSON schema, required ones marked with `*`.
+fn parameter_summary(schema: &Value) -> String {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return "no parameters".to_string();
+    };
+    if properties.is_empty() {
+        return "no parameters".to_string();
+    }
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    properties
+        .keys()
+        .map(|name| {
+            if required.contains(&name.as_str()) {
+                format!("{name}*")
+            } else {
+                name.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default().trim()
+}
+
+fn print_result(name: &str, result: &ToolResult) {
+    if result.success {
+        println!("✅ {name} succeeded");
+    } else {
+        println!("❌ {name} failed");
+    }
+    if let Some(error) = &result.error {
+        let error = ToolError::parse(error);
+        println!(
+            "error ({}, retryable: {}): {}",
+            error.category.as_str(),
+            error.retryable,
+            error.message
+        );
+        println!("hint: {}", error.hint);
+    }
+    if !result.output.is_empty() {
+        println!("{}", result.output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_args_requires_a_json_object() {
+        assert_eq!(
+            parse_args(r#"{"path": "a"}"#).unwrap(),
+            json!({"path": "a"})
+        );
+        assert!(parse_args("[1, 2]").is_err());
+        assert!(parse_args("path=a").is_err());
+    }
+
+    #[test]
+    fn parameter_summary_marks_required_parameters() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"path": {"type": "string"}, "limit": {"type": "integer"}},
+            "required": ["path"]
+        });
+        let summary = parameter_summary(&schema);
+        assert!(summary.contains("path*"));
+        assert!(summary.contains("limit") && !summary.contains("limit*"));
+        assert_eq!(
+            parameter_summary(&json!({"type": "object"})),
+            "no parameters"
+        );
+    }
+}
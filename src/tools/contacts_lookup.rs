@@ -0,0 +1,156 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::ContactsConfig;
+use crate::contacts;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+const DEFAULT_LIMIT: usize = 5;
+const MAX_LIMIT: usize = 25;
+
+/// Looks up people in the user's address book (vCard folder or CardDAV).
+pub struct ContactsLookupTool {
+    workspace_dir: PathBuf,
+    config: ContactsConfig,
+}
+
+impl ContactsLookupTool {
+    pub fn new(workspace_dir: PathBuf, config: ContactsConfig) -> Self {
+        Self {
+            workspace_dir,
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ContactsLookupTool {
+    fn name(&self) -> &str {
+        "contacts_lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Look up people in the user's address book by name, nickname, organisation, email, or \
+         phone number, e.g. to answer \"what's Sara's number?\". Returns matching contacts with \
+         the details the user allows to be shared."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Name, nickname, organisation, email, or phone number"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum contacts to return (default 5, max 25)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default();
+        if query.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'query' parameter".into()),
+            });
+        }
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |limit| {
+                usize::try_from(limit)
+                    .unwrap_or(MAX_LIMIT)
+                    .clamp(1, MAX_LIMIT)
+            });
+
+        let all = match contacts::load_and_sync(&self.workspace_dir, &self.config).await {
+            Ok(all) => all,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Contacts unavailable: {e:#}")),
+                })
+            }
+        };
+
+        let matches = contacts::search(&all, query);
+        if matches.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: format!("No contacts match '{query}'."),
+                error: None,
+            });
+        }
+
+        let mut output = matches
+            .iter()
+            .take(limit)
+            .map(|contact| contacts::describe(contact, &self.config.fields))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if matches.len() > limit {
+            let _ = write!(
+                output,
+                "\n({} more; refine the query)",
+                matches.len() - limit
+            );
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn looks_up_contacts_with_allowed_fields_only() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("people.vcf"),
+            "BEGIN:VCARD\nFN:Sara Lee\nTEL;TYPE=cell:+49 170 1234567\n\
+             ADR:;;Main St 1;Berlin;;;\nEND:VCARD\n",
+        )
+        .unwrap();
+        let tool = ContactsLookupTool::new(
+            tmp.path().to_path_buf(),
+            ContactsConfig {
+                enabled: true,
+                vcard_dir: Some(tmp.path().display().to_string()),
+                ..ContactsConfig::default()
+            },
+        );
+
+        let found = tool.execute(json!({"query": "sara"})).await.unwrap();
+        assert!(found.success);
+        assert!(found.output.contains("Sara Lee"));
+        assert!(found.output.contains("+49 170 1234567"));
+        assert!(!found.output.contains("Berlin"));
+
+        let missing = tool.execute(json!({"query": "bob"})).await.unwrap();
+        assert!(missing.success);
+        assert!(missing.output.contains("No contacts match"));
+
+        let empty = tool.execute(json!({})).await.unwrap();
+        assert!(!empty.success);
+    }
+}
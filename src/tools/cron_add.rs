@@ -79,10 +79,11 @@ impl Tool for CronAddTool {
                     "type": "object",
                     "description": "Delivery config to send job output to a channel. Example: {\"mode\":\"announce\",\"channel\":\"discord\",\"to\":\"<channel_id>\"}",
                     "properties": {
-                        "mode": { "type": "string", "enum": ["none", "announce"], "description": "Set to 'announce' to deliver output to a channel" },
+                        "mode": { "type": "string", "enum": ["none", "announce", "batch"], "description": "Set to 'announce' to deliver output to a channel; 'batch' submits agent jobs through the provider batch API (cheaper, delivered when ready)" },
                         "channel": { "type": "string", "enum": ["telegram", "discord", "slack", "mattermost"], "description": "Channel type to deliver to" },
                         "to": { "type": "string", "description": "Target: Discord channel ID, Telegram chat ID, Slack channel, etc." },
-                        "best_effort": { "type": "boolean", "description": "If true, delivery failure does not fail the job" }
+                        "best_effort": { "type": "boolean", "description": "If true, delivery failure does not fail the job" },
+                        "recipients": { "type": "array", "items": { "type": "string" }, "description": "Extra 'channel:recipient' targets to broadcast announce output to (requires [broadcast] enabled)" }
                     }
                 },
                 "delete_after_run": { "type": "boolean" },
@@ -246,6 +247,13 @@ impl Tool for CronAddTool {
                     delete_after_run,
                 )
             }
+            JobType::Reminder => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Use the remind_me tool to create reminders".to_string()),
+                });
+            }
         };
 
         match result {
@@ -1,6 +1,6 @@
 use super::traits::{Tool, ToolResult};
 use crate::agent::loop_::run_tool_call_loop;
-use crate::config::DelegateAgentConfig;
+use crate::config::{DelegateAgentConfig, RemoteAgentConfig, RemoteAgentTransport};
 use crate::observability::traits::{Observer, ObserverEvent, ObserverMetric};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::security::policy::ToolOperation;
@@ -33,6 +33,8 @@ pub struct DelegateTool {
     parent_tools: Arc<Vec<Arc<dyn Tool>>>,
     /// Inherited multimodal handling config for sub-agent loops.
     multimodal_config: crate::config::MultimodalConfig,
+    /// Agents on other instances, reached over a gateway API or a shared channel.
+    remote_agents: Arc<HashMap<String, RemoteAgentConfig>>,
 }
 
 impl DelegateTool {
@@ -63,6 +65,7 @@ impl DelegateTool {
             depth: 0,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            remote_agents: Arc::new(HashMap::new()),
         }
     }
 
@@ -99,6 +102,7 @@ impl DelegateTool {
             depth,
             parent_tools: Arc::new(Vec::new()),
             multimodal_config: crate::config::MultimodalConfig::default(),
+            remote_agents: Arc::new(HashMap::new()),
         }
     }
 
@@ -113,6 +117,12 @@ impl DelegateTool {
         self.multimodal_config = config;
         self
     }
+
+    /// Attach remote agents reachable from this instance.
+    pub fn with_remote_agents(mut self, remote_agents: HashMap<String, RemoteAgentConfig>) -> Self {
+        self.remote_agents = Arc::new(remote_agents);
+        self
+    }
 }
 
 #[async_trait]
@@ -123,12 +133,19 @@ impl Tool for DelegateTool {
 
     fn description(&self) -> &str {
         "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model \
-         (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single \
-         prompt by default; with agentic=true it can iterate with a filtered tool-call loop."
+         (e.g. fast summarization, deep reasoning, code generation) or must run on another machine \
+         (remote agents). The sub-agent runs a single prompt by default; with agentic=true it can \
+         iterate with a filtered tool-call loop. Remote agents answer with their own tools."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
-        let agent_names: Vec<&str> = self.agents.keys().map(|s: &String| s.as_str()).collect();
+        let mut agent_names: Vec<String> = self.agents.keys().cloned().collect();
+        agent_names.extend(self.remote_agents.iter().map(|(name, remote)| {
+            match remote.description.as_deref() {
+                Some(description) => format!("{name} (remote: {description})"),
+                None => format!("{name} (remote)"),
+            }
+        }));
         json!({
             "type": "object",
             "additionalProperties": false,
@@ -194,12 +211,37 @@ impl Tool for DelegateTool {
             .map(str::trim)
             .unwrap_or("");
 
+        // Build the message
+        let full_prompt = if context.is_empty() {
+            prompt.to_string()
+        } else {
+            format!("[Context]\n{context}\n\n[Task]\n{prompt}")
+        };
+
+        if let Some(remote) = self.remote_agents.get(agent_name) {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "delegate")
+            {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+            return Ok(execute_remote(agent_name, remote, &full_prompt).await);
+        }
+
         // Look up agent config
         let agent_config = match self.agents.get(agent_name) {
             Some(cfg) => cfg,
             None => {
-                let available: Vec<&str> =
-                    self.agents.keys().map(|s: &String| s.as_str()).collect();
+                let available: Vec<&str> = self
+                    .agents
+                    .keys()
+                    .chain(self.remote_agents.keys())
+                    .map(|s: &String| s.as_str())
+                    .collect();
                 return Ok(ToolResult {
                     success: false,
                     output: String::new(),
@@ -248,10 +290,12 @@ impl Tool for DelegateTool {
         #[allow(clippy::option_as_ref_deref)]
         let provider_credential = provider_credential_owned.as_ref().map(String::as_str);
 
+        let provider_runtime_options =
+            agent_runtime_options(&self.provider_runtime_options, agent_config);
         let provider: Box<dyn Provider> = match providers::create_provider_with_options(
             &agent_config.provider,
             provider_credential,
-            &self.provider_runtime_options,
+            &provider_runtime_options,
         ) {
             Ok(p) => p,
             Err(e) => {
@@ -266,13 +310,6 @@ impl Tool for DelegateTool {
             }
         };
 
-        // Build the message
-        let full_prompt = if context.is_empty() {
-            prompt.to_string()
-        } else {
-            format!("[Context]\n{context}\n\n[Task]\n{prompt}")
-        };
-
         let temperature = agent_config.temperature.unwrap_or(0.7);
 
         // Agentic mode: run full tool-call loop with allowlisted tools.
@@ -339,6 +376,83 @@ impl Tool for DelegateTool {
     }
 }
 
+/// Hand `prompt` to a remote agent and wait for its answer.
+async fn execute_remote(name: &str, remote: &RemoteAgentConfig, prompt: &str) -> ToolResult {
+    let timeout = Duration::from_secs(remote.timeout_secs);
+    let result = match remote.transport {
+        RemoteAgentTransport::Api => call_remote_gateway(remote, prompt).await,
+        RemoteAgentTransport::Channel => crate::channels::delegation::request(
+            remote.channel.as_deref().unwrap_or_default(),
+            remote.recipient.as_deref().unwrap_or_default(),
+            remote.sender.as_deref().unwrap_or_default(),
+            prompt,
+            timeout,
+        )
+        .await
+        .map_err(anyhow::Error::from),
+    };
+    match result {
+        Ok(reply) => ToolResult {
+            success: true,
+            output: format!(
+                "[Remote agent '{name}']\n{}",
+                if reply.trim().is_empty() {
+                    "[Empty response]"
+                } else {
+                    reply.as_str()
+                }
+            ),
+            error: None,
+        },
+        Err(e) => ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(format!("Remote agent '{name}' failed: {e}")),
+        },
+    }
+}
+
+/// POST the task to a remote gateway's `/webhook`, using a fresh idempotency
+/// key as the correlation id.
+async fn call_remote_gateway(remote: &RemoteAgentConfig, prompt: &str) -> anyhow::Result<String> {
+    let base = remote
+        .url
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("remote agent has no url"))?;
+    let client = crate::config::build_runtime_proxy_client_with_timeouts(
+        "tool.delegate",
+        remote.timeout_secs,
+        10,
+    );
+    let mut request = client
+        .post(format!("{}/webhook", base.trim_end_matches('/')))
+        .header(
+            "X-Idempotency-Key",
+            format!("delegate-{}", uuid::Uuid::new_v4()),
+        )
+        .json(&json!({ "message": prompt }));
+    if let Some(token) = remote.token.as_deref() {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.map_err(|e| {
+        if e.is_timeout() {
+            anyhow::anyhow!("no reply within {}s", remote.timeout_secs)
+        } else {
+            anyhow::anyhow!("request failed: {e}")
+        }
+    })?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let detail = body["error"].as_str().unwrap_or("no details");
+        anyhow::bail!("remote gateway returned {status}: {detail}");
+    }
+    body["response"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("remote gateway reply has no `response` field"))
+}
+
 impl DelegateTool {
     async fn execute_agentic(
         &self,
@@ -449,6 +563,21 @@ impl DelegateTool {
     }
 }
 
+/// Layer per-agent reasoning overrides on top of the global runtime options.
+fn agent_runtime_options(
+    base: &providers::ProviderRuntimeOptions,
+    agent_config: &DelegateAgentConfig,
+) -> providers::ProviderRuntimeOptions {
+    let mut options = base.clone();
+    if let Some(effort) = agent_config.reasoning_effort.as_ref() {
+        options.reasoning_effort = Some(effort.clone());
+    }
+    if let Some(budget) = agent_config.thinking_budget_tokens {
+        options.thinking_budget_tokens = Some(budget);
+    }
+    options
+}
+
 struct ToolArcRef {
     inner: Arc<dyn Tool>,
 }
@@ -519,6 +648,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
         agents.insert(
@@ -533,6 +664,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
         agents
@@ -686,6 +819,8 @@ mod tests {
             agentic: true,
             allowed_tools,
             max_iterations,
+            reasoning_effort: None,
+            thinking_budget_tokens: None,
         }
     }
 
@@ -794,6 +929,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -900,6 +1037,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -935,6 +1074,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
         let tool = DelegateTool::new(agents, None, test_security());
@@ -1099,4 +1240,72 @@ mod tests {
             .unwrap_or("")
             .contains("provider boom"));
     }
+
+    #[test]
+    fn agent_runtime_options_prefer_agent_reasoning_overrides() {
+        let base = providers::ProviderRuntimeOptions {
+            reasoning_effort: Some("low".into()),
+            thinking_budget_tokens: Some(2048),
+            ..providers::ProviderRuntimeOptions::default()
+        };
+        let mut config = agentic_config(Vec::new(), 10);
+
+        let inherited = agent_runtime_options(&base, &config);
+        assert_eq!(inherited.reasoning_effort.as_deref(), Some("low"));
+        assert_eq!(inherited.thinking_budget_tokens, Some(2048));
+
+        config.reasoning_effort = Some("high".into());
+        config.thinking_budget_tokens = Some(8192);
+        let overridden = agent_runtime_options(&base, &config);
+        assert_eq!(overridden.reasoning_effort.as_deref(), Some("high"));
+        assert_eq!(overridden.thinking_budget_tokens, Some(8192));
+    }
+
+    #[tokio::test]
+    async fn remote_api_agent_receives_task_and_returns_reply() {
+        use axum::{http::HeaderMap, routing::post, Json, Router};
+
+        let app = Router::new().route(
+            "/webhook",
+            post(
+                |headers: HeaderMap, Json(body): Json<serde_json::Value>| async move {
+                    let authorized = headers
+                        .get("authorization")
+                        .is_some_and(|v| v == "Bearer zc_remote");
+                    let correlated = headers.contains_key("x-idempotency-key");
+                    Json(json!({
+                        "response": format!(
+                            "{authorized}/{correlated}: {}",
+                            body["message"].as_str().unwrap_or_default()
+                        ),
+                    }))
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let remote = RemoteAgentConfig {
+            transport: RemoteAgentTransport::Api,
+            description: Some("home server".into()),
+            url: Some(format!("http://{addr}/")),
+            token: Some("zc_remote".into()),
+            channel: None,
+            recipient: None,
+            sender: None,
+            timeout_secs: 5,
+        };
+        let tool = DelegateTool::new(HashMap::new(), None, test_security())
+            .with_remote_agents(HashMap::from([("home".to_string(), remote)]));
+        let schema = tool.parameters_schema().to_string();
+        assert!(schema.contains("home (remote: home server)"));
+
+        let result = tool
+            .execute(json!({"agent": "home", "prompt": "check backups"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("true/true: check backups"));
+    }
 }
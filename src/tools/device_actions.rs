@@ -0,0 +1,409 @@
+//! Named device presets (`device_actions`): Wake-on-LAN packets and fixed
+//! SSH commands from `[[device_actions]]`.
+//!
+//! The tool only runs configured presets. Each preset lists the identities
+//! that may run it, and presets with `require_approval` need `approved=true`,
+//! which the agent sets only after the user confirms.
+
+use super::preferences::{current_identity, PreferenceIdentity, LOCAL_IDENTITY};
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::DeviceActionConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_WOL_PORT: u16 = 9;
+const SSH_CONNECT_TIMEOUT_SECS: u64 = 10;
+const MAX_OUTPUT_CHARS: usize = 4000;
+
+/// Parse `aa:bb:cc:dd:ee:ff`, `AA-BB-CC-DD-EE-FF`, or `aabbccddeeff`.
+pub fn parse_mac(value: &str) -> Option<[u8; 6]> {
+    let hex: String = value
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-'))
+        .collect();
+    if hex.len() != 12 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut mac = [0u8; 6];
+    for (i, byte) in mac.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(mac)
+}
+
+/// Six `0xFF` bytes followed by the MAC repeated sixteen times.
+fn magic_packet(mac: [u8; 6]) -> Vec<u8> {
+    let mut packet = vec![0xFF; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&mac);
+    }
+    packet
+}
+
+fn identity_allowed(preset: &DeviceActionConfig, identity: &PreferenceIdentity) -> bool {
+    if identity.channel == LOCAL_IDENTITY.0 && identity.sender == LOCAL_IDENTITY.1 {
+        return true;
+    }
+    preset.allowed_identities.iter().any(|allowed| {
+        let allowed = allowed.trim();
+        allowed == "*"
+            || allowed == format!("{}:*", identity.channel)
+            || allowed == format!("{}:{}", identity.channel, identity.sender)
+    })
+}
+
+fn ssh_command(preset: &DeviceActionConfig) -> tokio::process::Command {
+    let mut process = tokio::process::Command::new("ssh");
+    process
+        .arg("-T")
+        .args(["-o", "BatchMode=yes"])
+        .args(["-o", "StrictHostKeyChecking=yes"])
+        .args(["-o", "PasswordAuthentication=no"])
+        .arg("-o")
+        .arg(format!("ConnectTimeout={SSH_CONNECT_TIMEOUT_SECS}"));
+    if let Some(identity_file) = &preset.identity_file {
+        process
+            .args(["-o", "IdentitiesOnly=yes"])
+            .arg("-i")
+            .arg(shellexpand::tilde(identity_file.trim()).into_owned());
+    }
+    if let Some(port) = preset.port {
+        process.arg("-p").arg(port.to_string());
+    }
+    if let Some(user) = &preset.user {
+        process.arg("-l").arg(user.trim());
+    }
+    process
+        .arg("--")
+        .arg(preset.host.as_deref().unwrap_or_default().trim())
+        .arg(preset.command.as_deref().unwrap_or_default());
+    process.kill_on_drop(true);
+    process
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(MAX_OUTPUT_CHARS) {
+        Some((cut, _)) => format!("{}… (truncated)", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+/// Runs configured Wake-on-LAN and SSH presets.
+pub struct DeviceActionsTool {
+    presets: Vec<DeviceActionConfig>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl DeviceActionsTool {
+    pub fn new(presets: Vec<DeviceActionConfig>, security: Arc<SecurityPolicy>) -> Self {
+        Self { presets, security }
+    }
+
+    fn list(&self, identity: &PreferenceIdentity) -> String {
+        let mut text = String::new();
+        for preset in self
+            .presets
+            .iter()
+            .filter(|preset| identity_allowed(preset, identity))
+        {
+            let _ = write!(text, "\n- {} ({})", preset.name, preset.kind);
+            if let Some(description) = &preset.description {
+                let _ = write!(text, ": {description}");
+            }
+            if preset.require_approval {
+                text.push_str(" [needs confirmation]");
+            }
+        }
+        if text.is_empty() {
+            "No device actions are available to you.".into()
+        } else {
+            format!("Device actions:{text}")
+        }
+    }
+
+    async fn wake(preset: &DeviceActionConfig) -> Result<String, String> {
+        let mac = preset
+            .mac
+            .as_deref()
+            .and_then(parse_mac)
+            .ok_or_else(|| format!("Preset '{}' has no valid MAC address", preset.name))?;
+        let broadcast: Ipv4Addr = preset
+            .broadcast
+            .as_deref()
+            .unwrap_or("255.255.255.255")
+            .parse()
+            .map_err(|e| format!("Invalid broadcast address: {e}"))?;
+        let port = preset.port.unwrap_or(DEFAULT_WOL_PORT);
+
+        let socket = tokio::net::UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+            .await
+            .map_err(|e| format!("Failed to open UDP socket: {e}"))?;
+        socket
+            .set_broadcast(true)
+            .map_err(|e| format!("Failed to enable broadcast: {e}"))?;
+        socket
+            .send_to(&magic_packet(mac), (broadcast, port))
+            .await
+            .map_err(|e| format!("Failed to send magic packet: {e}"))?;
+        Ok(format!(
+            "Sent Wake-on-LAN packet for '{}' to {broadcast}:{port}.",
+            preset.name
+        ))
+    }
+
+    async fn run_ssh(preset: &DeviceActionConfig) -> Result<String, String> {
+        let output = tokio::time::timeout(
+            Duration::from_secs(preset.timeout_secs),
+            ssh_command(preset).output(),
+        )
+        .await
+        .map_err(|_| format!("'{}' timed out after {}s", preset.name, preset.timeout_secs))?
+        .map_err(|e| format!("Failed to start ssh: {e}"))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            return Err(format!(
+                "'{}' failed ({}): {}",
+                preset.name,
+                output.status,
+                truncate(if stderr.trim().is_empty() {
+                    &stdout
+                } else {
+                    &stderr
+                })
+            ));
+        }
+        let stdout = truncate(&stdout);
+        Ok(if stdout.is_empty() {
+            format!("'{}' completed.", preset.name)
+        } else {
+            format!("'{}' completed:\n{stdout}", preset.name)
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for DeviceActionsTool {
+    fn name(&self) -> &str {
+        "device_actions"
+    }
+
+    fn description(&self) -> &str {
+        "Run a configured device preset such as 'wake desktop' (Wake-on-LAN) or 'restart plex' \
+         (fixed SSH command). action='list' shows the presets available to the user; 'run' runs \
+         one by name. Presets marked [needs confirmation] require approved=true, which you may \
+         only set after the user confirms in this conversation. Cannot run arbitrary commands."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": { "type": "string", "enum": ["list", "run"], "default": "list" },
+                "preset": { "type": "string", "description": "Preset name (run)" },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true only after the user confirmed this run",
+                    "default": false
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let identity = current_identity();
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("list");
+        match action {
+            "list" => {
+                return Ok(ToolResult {
+                    success: true,
+                    output: self.list(&identity),
+                    error: None,
+                })
+            }
+            "run" => {}
+            other => return Ok(failure(format!("Unknown action '{other}'"))),
+        }
+
+        let Some(name) = args
+            .get("preset")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        else {
+            return Ok(failure("Missing 'preset'"));
+        };
+        let Some(preset) = self
+            .presets
+            .iter()
+            .find(|preset| preset.name.trim().eq_ignore_ascii_case(name))
+            .filter(|preset| identity_allowed(preset, &identity))
+        else {
+            return Ok(failure(format!(
+                "No device action '{name}' is available to you"
+            )));
+        };
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if preset.require_approval && !approved {
+            return Ok(failure(format!(
+                "'{}' requires confirmation: ask the user, then call again with approved=true",
+                preset.name
+            )));
+        }
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "device_actions")
+        {
+            return Ok(failure(error));
+        }
+
+        tracing::info!(
+            preset = %preset.name,
+            kind = %preset.kind,
+            channel = %identity.channel,
+            sender = %identity.sender,
+            "Running device action"
+        );
+        let result = match preset.kind.as_str() {
+            "wol" => Self::wake(preset).await,
+            "ssh" => Self::run_ssh(preset).await,
+            other => Err(format!("Unsupported preset kind '{other}'")),
+        };
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(error) => failure(error),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::preferences::with_identity;
+
+    fn preset(name: &str, kind: &str) -> DeviceActionConfig {
+        DeviceActionConfig {
+            name: name.into(),
+            description: None,
+            kind: kind.into(),
+            mac: None,
+            broadcast: None,
+            host: None,
+            user: None,
+            port: None,
+            command: None,
+            identity_file: None,
+            timeout_secs: 30,
+            allowed_identities: Vec::new(),
+            require_approval: true,
+        }
+    }
+
+    fn telegram(sender: &str) -> PreferenceIdentity {
+        PreferenceIdentity {
+            channel: "telegram".into(),
+            sender: sender.into(),
+        }
+    }
+
+    #[test]
+    fn mac_addresses_and_magic_packets() {
+        assert_eq!(
+            parse_mac("AA-bb-CC-dd-EE-01"),
+            Some([0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0x01])
+        );
+        assert_eq!(parse_mac("aabbccddee01"), parse_mac("aa:bb:cc:dd:ee:01"));
+        assert!(parse_mac("aa:bb:cc:dd:ee").is_none());
+        assert!(parse_mac("zz:bb:cc:dd:ee:ff").is_none());
+
+        let packet = magic_packet([1, 2, 3, 4, 5, 6]);
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert_eq!(&packet[96..], &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn ssh_presets_run_only_their_fixed_command() {
+        let mut plex = preset("restart plex", "ssh");
+        plex.host = Some("media.local".into());
+        plex.user = Some("pi".into());
+        plex.port = Some(2222);
+        plex.command = Some("sudo systemctl restart plexmediaserver".into());
+        let command = ssh_command(&plex);
+        let args: Vec<_> = command
+            .as_std()
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            args[args.len() - 7..],
+            [
+                "-p",
+                "2222",
+                "-l",
+                "pi",
+                "--",
+                "media.local",
+                "sudo systemctl restart plexmediaserver"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn runs_are_limited_to_allowed_identities_and_confirmed() {
+        let receiver = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut wake = preset("Wake Desktop", "wol");
+        wake.mac = Some("aa:bb:cc:dd:ee:ff".into());
+        wake.broadcast = Some("127.0.0.1".into());
+        wake.port = Some(receiver.local_addr().unwrap().port());
+        wake.allowed_identities = vec!["telegram:alice".into()];
+        let tool = DeviceActionsTool::new(vec![wake], Arc::new(SecurityPolicy::default()));
+        let run = json!({"action": "run", "preset": "wake desktop"});
+
+        let listed = with_identity(telegram("bob"), tool.execute(json!({})))
+            .await
+            .unwrap();
+        assert_eq!(listed.output, "No device actions are available to you.");
+        let denied = with_identity(telegram("bob"), tool.execute(run.clone()))
+            .await
+            .unwrap();
+        assert!(denied.error.unwrap().contains("is available to you"));
+
+        let unconfirmed = with_identity(telegram("alice"), tool.execute(run))
+            .await
+            .unwrap();
+        assert!(unconfirmed.error.unwrap().contains("requires confirmation"));
+
+        let sent = with_identity(
+            telegram("alice"),
+            tool.execute(json!({"action": "run", "preset": "wake desktop", "approved": true})),
+        )
+        .await
+        .unwrap();
+        assert!(sent.success, "{:?}", sent.error);
+        let mut buffer = [0u8; 128];
+        let (len, _) = receiver.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(len, 102);
+        assert_eq!(&buffer[6..12], &[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+}
@@ -0,0 +1,238 @@
+//! Structured tool errors.
+//!
+//! Failed tool calls reach the model as one JSON envelope,
+//! `{"error": {"category", "retryable", "message", "hint"}}`, so it can tell a
+//! typo in an argument from a flaky upstream and decide whether to retry.
+//! Tools can build a [`ToolError`] themselves and return it as a
+//! [`ToolResult`]; free-text errors from other tools are classified by
+//! [`ToolError::classify`] in the agent loop. The category is also reported to
+//! observers as [`ObserverEvent::ToolError`](crate::observability::ObserverEvent::ToolError).
+
+use super::traits::ToolResult;
+use crate::observability::{Observer, ObserverEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Why a tool call failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolErrorKind {
+    /// The file, record, URL, or tool asked for does not exist.
+    NotFound,
+    /// Refused by the security policy, an allowlist, or missing credentials.
+    Permission,
+    Timeout,
+    RateLimit,
+    /// Arguments are missing, malformed, or out of range.
+    InvalidArgs,
+    /// An external service or command failed.
+    Upstream,
+}
+
+impl ToolErrorKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::Permission => "permission",
+            Self::Timeout => "timeout",
+            Self::RateLimit => "rate_limit",
+            Self::InvalidArgs => "invalid_args",
+            Self::Upstream => "upstream",
+        }
+    }
+
+    /// Whether the same call may succeed if repeated.
+    pub fn retryable(self) -> bool {
+        matches!(self, Self::Timeout | Self::RateLimit | Self::Upstream)
+    }
+
+    fn default_hint(self) -> &'static str {
+        match self {
+            Self::NotFound => "Check the name, path, or id; look up what exists before retrying.",
+            Self::Permission => {
+                "Blocked by policy or credentials; do not repeat the call. Try another approach or ask the user."
+            }
+            Self::Timeout => "Retry once, with a smaller request if possible.",
+            Self::RateLimit => "Wait before retrying, or continue without this tool.",
+            Self::InvalidArgs => "Fix the arguments to match the tool's parameter schema, then retry.",
+            Self::Upstream => "The service failed; retry once, then report the failure.",
+        }
+    }
+}
+
+/// A tool failure as the model sees it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolError {
+    pub category: ToolErrorKind,
+    pub retryable: bool,
+    pub message: String,
+    pub hint: String,
+}
+
+impl ToolError {
+    /// Error with the category's default retry flag and hint.
+    pub fn new(category: ToolErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            category,
+            retryable: category.retryable(),
+            message: message.into(),
+            hint: category.default_hint().to_string(),
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = hint.into();
+        self
+    }
+
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Categorize a free-text error message.
+    pub fn classify(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+        let category = if has(&["timed out", "timeout", "deadline exceeded"]) {
+            ToolErrorKind::Timeout
+        } else if has(&[
+            "rate limit",
+            "rate-limit",
+            "too many requests",
+            "429",
+            "quota",
+            "budget exhausted",
+        ]) {
+            ToolErrorKind::RateLimit
+        } else if has(&[
+            "not allowed",
+            "permission",
+            "forbidden",
+            "blocked",
+            "denied",
+            "unauthorized",
+            "401",
+            "403",
+            "security policy",
+            "read-only",
+            "not available on this channel",
+        ]) {
+            ToolErrorKind::Permission
+        } else if has(&[
+            "not found",
+            "no such file",
+            "does not exist",
+            "unknown tool",
+            "404",
+        ]) {
+            ToolErrorKind::NotFound
+        } else if has(&[
+            "missing",
+            "invalid",
+            "required",
+            "must be",
+            "expected",
+            "unknown parameter",
+            "unsupported",
+        ]) {
+            ToolErrorKind::InvalidArgs
+        } else {
+            ToolErrorKind::Upstream
+        };
+        Self::new(category, message.trim())
+    }
+
+    /// Read an envelope produced by [`Self::to_json`], or classify free text.
+    pub fn parse(reason: &str) -> Self {
+        serde_json::from_str::<serde_json::Value>(reason)
+            .ok()
+            .and_then(|value| serde_json::from_value(value.get("error")?.clone()).ok())
+            .unwrap_or_else(|| Self::classify(reason))
+    }
+
+    /// Report this failure of `tool` to observers.
+    pub fn record(&self, observer: &dyn Observer, tool: &str) {
+        observer.record_event(&ObserverEvent::ToolError {
+            tool: tool.to_string(),
+            category: self.category.as_str().to_string(),
+            retryable: self.retryable,
+        });
+    }
+
+    /// The envelope the model receives.
+    pub fn to_json(&self) -> String {
+        json!({ "error": self }).to_string()
+    }
+}
+
+impl std::fmt::Display for ToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.category.as_str())
+    }
+}
+
+impl From<ToolError> for ToolResult {
+    fn from(error: ToolError) -> Self {
+        Self {
+            success: false,
+            output: String::new(),
+            error: Some(error.to_json()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_recognizes_common_messages() {
+        let cases = [
+            ("Command timed out after 60s", ToolErrorKind::Timeout),
+            (
+                "Rate limit exceeded: action budget exhausted",
+                ToolErrorKind::RateLimit,
+            ),
+            (
+                "Path not allowed by security policy: /etc/shadow",
+                ToolErrorKind::Permission,
+            ),
+            (
+                "Failed to read file: No such file or directory (os error 2)",
+                ToolErrorKind::NotFound,
+            ),
+            ("Missing 'path' parameter", ToolErrorKind::InvalidArgs),
+            ("HTTP 502 from api.example.com", ToolErrorKind::Upstream),
+        ];
+        for (message, expected) in cases {
+            assert_eq!(ToolError::classify(message).category, expected, "{message}");
+        }
+    }
+
+    #[test]
+    fn envelope_round_trips_through_tool_result() {
+        let error = ToolError::new(ToolErrorKind::NotFound, "No contact named Bob")
+            .with_hint("Search contacts by phone number instead.");
+        let result: ToolResult = error.clone().into();
+        assert!(!result.success);
+
+        let reason = result.error.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&reason).unwrap();
+        assert_eq!(value["error"]["category"], "not_found");
+        assert_eq!(value["error"]["retryable"], false);
+        assert_eq!(ToolError::parse(&reason), error);
+    }
+
+    #[test]
+    fn parse_falls_back_to_classification() {
+        let error = ToolError::parse("upstream returned 503");
+        assert_eq!(error.category, ToolErrorKind::Upstream);
+        assert!(error.retryable);
+        assert!(!error.hint.is_empty());
+        assert_eq!(
+            ToolError::parse(r#"{"status": "oops"}"#).message,
+            r#"{"status": "oops"}"#
+        );
+    }
+}
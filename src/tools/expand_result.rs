@@ -0,0 +1,391 @@
+//! Summarization of oversized tool results (`[tool_result_summary]`).
+//!
+//! Tools are wrapped in [`SummarizingTool`] when the registry is built. A
+//! result whose estimated size exceeds the configured threshold is replaced
+//! by a short summary from a cheap model, and the raw output is kept in a
+//! bounded [`ResultStore`] so the agent can page through it with the
+//! `expand_result` tool.
+
+use super::traits::{Tool, ToolResult};
+use crate::agent::classifier::estimate_tokens;
+use crate::config::ToolResultSummaryConfig;
+use crate::providers::Provider;
+use crate::util::truncate_with_ellipsis;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub const EXPAND_RESULT_TOOL_NAME: &str = "expand_result";
+
+/// Characters of raw output sent to the summary model.
+const MAX_SUMMARY_SOURCE_CHARS: usize = 60_000;
+/// Default page size for `expand_result`.
+const DEFAULT_PAGE_CHARS: usize = 8_000;
+
+const SUMMARIZER_SYSTEM: &str = "You summarize tool output for an AI agent that is in the middle of a task. Keep exact values the agent is likely to need: errors, file paths, identifiers, counts, status codes, and the first and last relevant lines. Drop repetition and boilerplate. Output plain text only.";
+
+struct StoredResult {
+    id: String,
+    tool: String,
+    output: String,
+}
+
+/// Raw outputs of summarized results, oldest dropped first.
+pub struct ResultStore {
+    entries: Mutex<VecDeque<StoredResult>>,
+    capacity: usize,
+    next: AtomicUsize,
+}
+
+impl ResultStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            next: AtomicUsize::new(1),
+        }
+    }
+
+    fn insert(&self, tool: &str, output: String) -> String {
+        let id = format!("r{}", self.next.fetch_add(1, Ordering::Relaxed));
+        let mut entries = self.entries.lock();
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(StoredResult {
+            id: id.clone(),
+            tool: tool.to_string(),
+            output,
+        });
+        id
+    }
+
+    fn get(&self, id: &str) -> Option<(String, String)> {
+        self.entries
+            .lock()
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| (entry.tool.clone(), entry.output.clone()))
+    }
+}
+
+/// Shared summarization settings and model for every wrapped tool.
+pub struct ResultSummarizer {
+    provider: Box<dyn Provider>,
+    model: String,
+    threshold_tokens: usize,
+    max_summary_chars: usize,
+    store: Arc<ResultStore>,
+}
+
+impl ResultSummarizer {
+    pub fn new(
+        provider: Box<dyn Provider>,
+        model: String,
+        config: &ToolResultSummaryConfig,
+    ) -> Self {
+        Self {
+            provider,
+            model,
+            threshold_tokens: config.threshold_tokens,
+            max_summary_chars: config.max_summary_chars,
+            store: Arc::new(ResultStore::new(config.max_stored_results)),
+        }
+    }
+
+    /// Largest result, in characters, that is passed through unchanged.
+    fn threshold_chars(&self) -> usize {
+        self.threshold_tokens
+            .saturating_mul(crate::agent::classifier::APPROX_CHARS_PER_TOKEN)
+    }
+
+    fn is_oversized(&self, output: &str) -> bool {
+        estimate_tokens(output.chars().count()) > self.threshold_tokens
+    }
+
+    async fn summarize(&self, tool: &str, output: String) -> String {
+        let total_chars = output.chars().count();
+        let summary = self.summary(tool, &output).await;
+        let id = self.store.insert(tool, output);
+        format!(
+            "[Summarized: output was {total_chars} characters. Call {EXPAND_RESULT_TOOL_NAME} with id \"{id}\" to read the raw output.]\n{summary}"
+        )
+    }
+
+    /// Summary of `output` from the cheap model, or its start if that fails.
+    pub(crate) async fn summary(&self, tool: &str, output: &str) -> String {
+        let source = crate::agent::loop_::scrub_credentials(&truncate_with_ellipsis(
+            output,
+            MAX_SUMMARY_SOURCE_CHARS,
+        ));
+        let prompt = format!(
+            "Summarize this output of the `{tool}` tool in at most {} characters.\n\n{source}",
+            self.max_summary_chars
+        );
+        let summary = match self
+            .provider
+            .chat_with_system(Some(SUMMARIZER_SYSTEM), &prompt, &self.model, 0.2)
+            .await
+        {
+            Ok(summary) if !summary.trim().is_empty() => summary,
+            Ok(_) => truncate_with_ellipsis(output, self.max_summary_chars),
+            Err(e) => {
+                tracing::warn!(tool, "tool result summary failed, truncating: {e}");
+                truncate_with_ellipsis(output, self.max_summary_chars)
+            }
+        };
+        truncate_with_ellipsis(summary.trim(), self.max_summary_chars)
+    }
+}
+
+/// Tool wrapper that summarizes successful results above the threshold.
+pub struct SummarizingTool {
+    inner: Arc<dyn Tool>,
+    summarizer: Arc<ResultSummarizer>,
+}
+
+impl SummarizingTool {
+    pub fn new(inner: Arc<dyn Tool>, summarizer: Arc<ResultSummarizer>) -> Self {
+        Self { inner, summarizer }
+    }
+}
+
+#[async_trait]
+impl Tool for SummarizingTool {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn parameters_schema(&self) -> Value {
+        self.inner.parameters_schema()
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let mut result = self.inner.execute(args).await?;
+        if result.success && self.summarizer.is_oversized(&result.output) {
+            let output = std::mem::take(&mut result.output);
+            result.output = self.summarizer.summarize(self.inner.name(), output).await;
+        }
+        Ok(result)
+    }
+}
+
+/// Wrap every tool in `tools` and append the `expand_result` tool.
+pub fn wrap_registry(
+    tools: Vec<Arc<dyn Tool>>,
+    summarizer: Arc<ResultSummarizer>,
+) -> Vec<Arc<dyn Tool>> {
+    let mut wrapped: Vec<Arc<dyn Tool>> = tools
+        .into_iter()
+        .map(|tool| Arc::new(SummarizingTool::new(tool, summarizer.clone())) as Arc<dyn Tool>)
+        .collect();
+    wrapped.push(Arc::new(ExpandResultTool::new(summarizer)));
+    wrapped
+}
+
+/// Pages through the raw output of a summarized tool result.
+pub struct ExpandResultTool {
+    summarizer: Arc<ResultSummarizer>,
+}
+
+impl ExpandResultTool {
+    pub fn new(summarizer: Arc<ResultSummarizer>) -> Self {
+        Self { summarizer }
+    }
+}
+
+#[async_trait]
+impl Tool for ExpandResultTool {
+    fn name(&self) -> &str {
+        EXPAND_RESULT_TOOL_NAME
+    }
+
+    fn description(&self) -> &str {
+        "Read the raw output of a tool result that was summarized because it was too large. Pass the id from the summary note; use offset to page through long output."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "Result id from the summary note, e.g. \"r3\""
+                },
+                "offset": {
+                    "type": "integer",
+                    "description": "Character offset to start reading from",
+                    "default": 0
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum characters to return",
+                    "default": DEFAULT_PAGE_CHARS
+                }
+            },
+            "required": ["id"]
+        })
+    }
+
+    async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        };
+        let Some(id) = args.get("id").and_then(Value::as_str).map(str::trim) else {
+            return Ok(failure("Missing 'id' parameter".into()));
+        };
+        let Some((tool, output)) = self.summarizer.store.get(id) else {
+            return Ok(failure(format!(
+                "No stored result with id '{id}'; only the most recent summarized results are kept"
+            )));
+        };
+
+        let offset = args
+            .get("offset")
+            .and_then(Value::as_u64)
+            .map_or(0, |v| usize::try_from(v).unwrap_or(usize::MAX));
+        // Pages stay below the threshold so they are never summarized again.
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_PAGE_CHARS, |v| {
+                usize::try_from(v).unwrap_or(usize::MAX)
+            })
+            .clamp(1, self.summarizer.threshold_chars().max(1));
+
+        let total = output.chars().count();
+        let page: String = output.chars().skip(offset).take(limit).collect();
+        let end = (offset + page.chars().count()).min(total);
+        let more = if end < total {
+            format!(" Call again with offset {end} for more.")
+        } else {
+            String::new()
+        };
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "[{tool} result {id}: characters {offset}..{end} of {total}.{more}]\n{page}"
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the text argument"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(&self, args: Value) -> anyhow::Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: args["text"].as_str().unwrap_or_default().to_string(),
+                error: None,
+            })
+        }
+    }
+
+    struct SummaryProvider;
+
+    #[async_trait]
+    impl Provider for SummaryProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(format!("summary from {model}"))
+        }
+    }
+
+    fn summarizer(threshold_tokens: usize) -> Arc<ResultSummarizer> {
+        let config = ToolResultSummaryConfig {
+            threshold_tokens,
+            ..ToolResultSummaryConfig::default()
+        };
+        Arc::new(ResultSummarizer::new(
+            Box::new(SummaryProvider),
+            "cheap".into(),
+            &config,
+        ))
+    }
+
+    #[tokio::test]
+    async fn small_results_pass_through_and_large_ones_are_summarized() {
+        let tools = wrap_registry(vec![Arc::new(EchoTool)], summarizer(10));
+        let echo = &tools[0];
+        let expand = &tools[1];
+        assert_eq!(expand.name(), EXPAND_RESULT_TOOL_NAME);
+
+        let small = echo.execute(json!({ "text": "short" })).await.unwrap();
+        assert_eq!(small.output, "short");
+
+        let raw = "line of output\n".repeat(20);
+        let large = echo.execute(json!({ "text": raw })).await.unwrap();
+        assert!(large.output.contains("summary from cheap"));
+        assert!(large.output.contains("id \"r1\""));
+        assert!(!large.output.contains(&raw));
+
+        let page = expand
+            .execute(json!({ "id": "r1", "offset": 0, "limit": 15 }))
+            .await
+            .unwrap();
+        assert!(page.success);
+        assert!(page.output.contains("characters 0..15 of 300"));
+        assert!(page.output.contains("offset 15"));
+        assert!(page.output.ends_with("line of output\n"));
+    }
+
+    #[tokio::test]
+    async fn expand_pages_are_capped_below_the_threshold() {
+        let summarizer = summarizer(10);
+        let id = summarizer.store.insert("shell", "x".repeat(100));
+        let page = ExpandResultTool::new(summarizer)
+            .execute(json!({ "id": id, "limit": 1000 }))
+            .await
+            .unwrap();
+        assert!(page.output.contains("characters 0..40 of 100"));
+    }
+
+    #[tokio::test]
+    async fn store_drops_oldest_results() {
+        let store = ResultStore::new(2);
+        let first = store.insert("shell", "a".into());
+        store.insert("shell", "b".into());
+        store.insert("shell", "c".into());
+        assert!(store.get(&first).is_none());
+        assert_eq!(store.get("r3").unwrap().1, "c");
+
+        let missing = ExpandResultTool::new(summarizer(10))
+            .execute(json!({ "id": "r9" }))
+            .await
+            .unwrap();
+        assert!(!missing.success);
+    }
+}
@@ -105,6 +105,14 @@ impl Tool for FileEditTool {
 
         let full_path = self.security.workspace_dir.join(path);
 
+        if !self.security.is_path_writable(&full_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not in autonomy.rules.writable_paths: {path}")),
+            });
+        }
+
         // ── 5. Canonicalize parent ─────────────────────────────────
         let Some(parent) = full_path.parent() else {
             return Ok(ToolResult {
@@ -147,6 +155,17 @@ impl Tool for FileEditTool {
 
         let resolved_target = resolved_parent.join(file_name);
 
+        if !self.security.is_path_writable(&resolved_target) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Resolved path not in autonomy.rules.writable_paths: {}",
+                    resolved_target.display()
+                )),
+            });
+        }
+
         // ── 7. Symlink check ───────────────────────────────────────
         if let Ok(meta) = tokio::fs::symlink_metadata(&resolved_target).await {
             if meta.file_type().is_symlink() {
@@ -1,3 +1,4 @@
+use super::error::{ToolError, ToolErrorKind};
 use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -17,6 +18,14 @@ impl FileReadTool {
     }
 }
 
+/// The security policy's hourly action budget is spent; retrying won't help.
+fn hourly_budget_error(message: &str) -> ToolResult {
+    ToolError::new(ToolErrorKind::RateLimit, message)
+        .with_retryable(false)
+        .with_hint("The hourly action budget is used up; answer without tools or tell the user.")
+        .into()
+}
+
 #[async_trait]
 impl Tool for FileReadTool {
     fn name(&self) -> &str {
@@ -55,31 +64,27 @@ impl Tool for FileReadTool {
             .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
 
         if self.security.is_rate_limited() {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
-            });
+            return Ok(hourly_budget_error(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
         }
 
         // Security check: validate path is within workspace
         if !self.security.is_path_allowed(path) {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Path not allowed by security policy: {path}")),
-            });
+            return Ok(ToolError::new(
+                ToolErrorKind::Permission,
+                format!("Path not allowed by security policy: {path}"),
+            )
+            .into());
         }
 
         // Record action BEFORE canonicalization so that every non-trivially-rejected
         // request consumes rate limit budget. This prevents attackers from probing
         // path existence (via canonicalize errors) without rate limit cost.
         if !self.security.record_action() {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some("Rate limit exceeded: action budget exhausted".into()),
-            });
+            return Ok(hourly_budget_error(
+                "Rate limit exceeded: action budget exhausted",
+            ));
         }
 
         let full_path = self.security.workspace_dir.join(path);
@@ -88,11 +93,9 @@ impl Tool for FileReadTool {
         let resolved_path = match tokio::fs::canonicalize(&full_path).await {
             Ok(p) => p,
             Err(e) => {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to resolve file path: {e}")),
-                });
+                return Ok(
+                    ToolError::classify(&format!("Failed to resolve file path: {e}")).into(),
+                );
             }
         };
 
@@ -111,14 +114,15 @@ impl Tool for FileReadTool {
         match tokio::fs::metadata(&resolved_path).await {
             Ok(meta) => {
                 if meta.len() > MAX_FILE_SIZE_BYTES {
-                    return Ok(ToolResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some(format!(
+                    return Ok(ToolError::new(
+                        ToolErrorKind::InvalidArgs,
+                        format!(
                             "File too large: {} bytes (limit: {MAX_FILE_SIZE_BYTES} bytes)",
                             meta.len()
-                        )),
-                    });
+                        ),
+                    )
+                    .with_hint("Search the file with content_search instead of reading it whole.")
+                    .into());
                 }
             }
             Err(e) => {
@@ -511,6 +515,10 @@ mod tests {
         let r1 = tool.execute(json!({"path": "nope1.txt"})).await.unwrap();
         assert!(!r1.success);
         assert!(r1.error.as_ref().unwrap().contains("Failed to resolve"));
+        assert_eq!(
+            ToolError::parse(r1.error.as_deref().unwrap()).category,
+            ToolErrorKind::NotFound
+        );
 
         let r2 = tool.execute(json!({"path": "nope2.txt"})).await.unwrap();
         assert!(!r2.success);
@@ -519,6 +527,7 @@ mod tests {
         // Third attempt should be rate limited even though file doesn't exist
         let r3 = tool.execute(json!({"path": "nope3.txt"})).await.unwrap();
         assert!(!r3.success);
+        assert!(!ToolError::parse(r3.error.as_deref().unwrap()).retryable);
         assert!(
             r3.error.as_ref().unwrap().contains("Rate limit"),
             "Expected rate limit error, got: {:?}",
@@ -0,0 +1,831 @@
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::FinanceConfig;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+const YAHOO_CHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart";
+const ALPHA_VANTAGE_URL: &str = "https://www.alphavantage.co/query";
+const MAX_SYMBOLS: usize = 10;
+/// Series longer than this are thinned out in the output (first and last
+/// points are always kept).
+const MAX_SERIES_POINTS: usize = 30;
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+const RANGES: &[(&str, i64)] = &[
+    ("5d", 7),
+    ("1mo", 31),
+    ("3mo", 92),
+    ("6mo", 183),
+    ("1y", 366),
+    ("2y", 731),
+    ("5y", 1827),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interval {
+    Daily,
+    Weekly,
+}
+
+impl Interval {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Quote {
+    symbol: String,
+    price: f64,
+    previous_close: Option<f64>,
+    currency: Option<String>,
+    exchange: Option<String>,
+    as_of: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Series {
+    symbol: String,
+    currency: Option<String>,
+    /// `(date, close)` in chronological order.
+    closes: Vec<(String, f64)>,
+}
+
+/// A market data source.
+#[async_trait]
+trait FinanceProvider: Send + Sync {
+    async fn quote(&self, client: &reqwest::Client, symbol: &str) -> anyhow::Result<Quote>;
+
+    async fn history(
+        &self,
+        client: &reqwest::Client,
+        symbol: &str,
+        range: &str,
+        interval: Interval,
+    ) -> anyhow::Result<Series>;
+}
+
+/// Yahoo Finance chart endpoints (no key; crypto as e.g. `BTC-USD`).
+struct Yahoo;
+
+impl Yahoo {
+    async fn chart(
+        client: &reqwest::Client,
+        symbol: &str,
+        range: &str,
+        interval: &str,
+    ) -> anyhow::Result<Value> {
+        let response = client
+            .get(format!("{YAHOO_CHART_URL}/{}", urlencoding::encode(symbol)))
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("zeroclaw/", env!("CARGO_PKG_VERSION")),
+            )
+            .query(&[("range", range), ("interval", interval)])
+            .send()
+            .await?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or_default();
+        if let Some(description) = body["chart"]["error"]["description"].as_str() {
+            anyhow::bail!("Yahoo Finance: {description}");
+        }
+        if !status.is_success() {
+            anyhow::bail!("Yahoo Finance returned status {status}");
+        }
+        Ok(body)
+    }
+}
+
+#[async_trait]
+impl FinanceProvider for Yahoo {
+    async fn quote(&self, client: &reqwest::Client, symbol: &str) -> anyhow::Result<Quote> {
+        parse_yahoo_quote(&Self::chart(client, symbol, "1d", "1d").await?)
+    }
+
+    async fn history(
+        &self,
+        client: &reqwest::Client,
+        symbol: &str,
+        range: &str,
+        interval: Interval,
+    ) -> anyhow::Result<Series> {
+        let interval = match interval {
+            Interval::Daily => "1d",
+            Interval::Weekly => "1wk",
+        };
+        parse_yahoo_series(&Self::chart(client, symbol, range, interval).await?)
+    }
+}
+
+fn yahoo_result(body: &Value) -> anyhow::Result<&Value> {
+    let result = &body["chart"]["result"][0];
+    if result.is_null() {
+        anyhow::bail!("Yahoo Finance returned no data");
+    }
+    Ok(result)
+}
+
+fn local_date(timestamp: i64, offset: i64, format: &str) -> Option<String> {
+    chrono::DateTime::from_timestamp(timestamp + offset, 0).map(|at| at.format(format).to_string())
+}
+
+fn parse_yahoo_quote(body: &Value) -> anyhow::Result<Quote> {
+    let meta = &yahoo_result(body)?["meta"];
+    let price = meta["regularMarketPrice"]
+        .as_f64()
+        .ok_or_else(|| anyhow::anyhow!("Yahoo Finance response has no price"))?;
+    let offset = meta["gmtoffset"].as_i64().unwrap_or(0);
+    let timezone = meta["exchangeTimezoneName"].as_str().unwrap_or("UTC");
+    Ok(Quote {
+        symbol: meta["symbol"].as_str().unwrap_or_default().to_string(),
+        price,
+        previous_close: meta["chartPreviousClose"]
+            .as_f64()
+            .or_else(|| meta["previousClose"].as_f64()),
+        currency: meta["currency"].as_str().map(str::to_string),
+        exchange: meta["fullExchangeName"]
+            .as_str()
+            .or_else(|| meta["exchangeName"].as_str())
+            .map(str::to_string),
+        as_of: meta["regularMarketTime"]
+            .as_i64()
+            .and_then(|time| local_date(time, offset, "%Y-%m-%d %H:%M"))
+            .map(|time| format!("{time} {timezone}")),
+    })
+}
+
+fn parse_yahoo_series(body: &Value) -> anyhow::Result<Series> {
+    let result = yahoo_result(body)?;
+    let meta = &result["meta"];
+    let offset = meta["gmtoffset"].as_i64().unwrap_or(0);
+    let timestamps = result["timestamp"].as_array().cloned().unwrap_or_default();
+    let closes = &result["indicators"]["quote"][0]["close"];
+    let closes = timestamps
+        .iter()
+        .enumerate()
+        .filter_map(|(index, time)| {
+            let date = local_date(time.as_i64()?, offset, "%Y-%m-%d")?;
+            Some((date, closes[index].as_f64()?))
+        })
+        .collect();
+    Ok(Series {
+        symbol: meta["symbol"].as_str().unwrap_or_default().to_string(),
+        currency: meta["currency"].as_str().map(str::to_string),
+        closes,
+    })
+}
+
+/// Alpha Vantage (API key; crypto as e.g. `BTC-USD`).
+struct AlphaVantage {
+    api_key: String,
+}
+
+/// `BTC-USD` style pairs are routed to Alpha Vantage's digital currency API.
+fn crypto_pair(symbol: &str) -> Option<(&str, &str)> {
+    let (base, market) = symbol.split_once('-')?;
+    let is_code =
+        |code: &str| (2..=6).contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphabetic());
+    (is_code(base) && is_code(market)).then_some((base, market))
+}
+
+impl AlphaVantage {
+    async fn call(
+        &self,
+        client: &reqwest::Client,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<Value> {
+        let body: Value = client
+            .get(ALPHA_VANTAGE_URL)
+            .query(query)
+            .query(&[("apikey", self.api_key.as_str())])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        // Errors and throttling come back as 200 with a message field.
+        for key in ["Error Message", "Note", "Information"] {
+            if let Some(message) = body[key].as_str() {
+                anyhow::bail!("Alpha Vantage: {message}");
+            }
+        }
+        Ok(body)
+    }
+}
+
+#[async_trait]
+impl FinanceProvider for AlphaVantage {
+    async fn quote(&self, client: &reqwest::Client, symbol: &str) -> anyhow::Result<Quote> {
+        match crypto_pair(symbol) {
+            Some((base, market)) => {
+                let body = self
+                    .call(
+                        client,
+                        &[
+                            ("function", "CURRENCY_EXCHANGE_RATE"),
+                            ("from_currency", base),
+                            ("to_currency", market),
+                        ],
+                    )
+                    .await?;
+                parse_alpha_vantage_rate(symbol, &body)
+            }
+            None => {
+                let body = self
+                    .call(client, &[("function", "GLOBAL_QUOTE"), ("symbol", symbol)])
+                    .await?;
+                parse_alpha_vantage_quote(&body)
+            }
+        }
+    }
+
+    async fn history(
+        &self,
+        client: &reqwest::Client,
+        symbol: &str,
+        range: &str,
+        interval: Interval,
+    ) -> anyhow::Result<Series> {
+        // The free daily series holds ~100 trading days; longer ranges use
+        // weekly closes.
+        let days = range_days(range).unwrap_or(31);
+        let interval = if days > 140 {
+            Interval::Weekly
+        } else {
+            interval
+        };
+        let body = match (crypto_pair(symbol), interval) {
+            (Some((base, market)), _) => {
+                let function = match interval {
+                    Interval::Daily => "DIGITAL_CURRENCY_DAILY",
+                    Interval::Weekly => "DIGITAL_CURRENCY_WEEKLY",
+                };
+                self.call(
+                    client,
+                    &[("function", function), ("symbol", base), ("market", market)],
+                )
+                .await?
+            }
+            (None, Interval::Daily) => {
+                self.call(
+                    client,
+                    &[("function", "TIME_SERIES_DAILY"), ("symbol", symbol)],
+                )
+                .await?
+            }
+            (None, Interval::Weekly) => {
+                self.call(
+                    client,
+                    &[("function", "TIME_SERIES_WEEKLY"), ("symbol", symbol)],
+                )
+                .await?
+            }
+        };
+        let since = (chrono::Utc::now() - chrono::Duration::days(days))
+            .format("%Y-%m-%d")
+            .to_string();
+        parse_alpha_vantage_series(symbol, &body, &since)
+    }
+}
+
+fn av_number(value: &Value) -> Option<f64> {
+    value.as_str()?.trim().parse().ok()
+}
+
+fn parse_alpha_vantage_quote(body: &Value) -> anyhow::Result<Quote> {
+    let quote = &body["Global Quote"];
+    let price = av_number(&quote["05. price"])
+        .ok_or_else(|| anyhow::anyhow!("Alpha Vantage returned no quote (unknown symbol?)"))?;
+    Ok(Quote {
+        symbol: quote["01. symbol"].as_str().unwrap_or_default().to_string(),
+        price,
+        previous_close: av_number(&quote["08. previous close"]),
+        currency: None,
+        exchange: None,
+        as_of: quote["07. latest trading day"].as_str().map(str::to_string),
+    })
+}
+
+fn parse_alpha_vantage_rate(symbol: &str, body: &Value) -> anyhow::Result<Quote> {
+    let rate = &body["Realtime Currency Exchange Rate"];
+    let price = av_number(&rate["5. Exchange Rate"])
+        .ok_or_else(|| anyhow::anyhow!("Alpha Vantage returned no exchange rate"))?;
+    Ok(Quote {
+        symbol: symbol.to_string(),
+        price,
+        previous_close: None,
+        currency: rate["3. To_Currency Code"].as_str().map(str::to_string),
+        exchange: None,
+        as_of: rate["6. Last Refreshed"]
+            .as_str()
+            .map(|time| format!("{time} {}", rate["7. Time Zone"].as_str().unwrap_or("UTC"))),
+    })
+}
+
+fn parse_alpha_vantage_series(symbol: &str, body: &Value, since: &str) -> anyhow::Result<Series> {
+    let series = body
+        .as_object()
+        .and_then(|object| {
+            object
+                .iter()
+                .find(|(key, _)| key.contains("Time Series"))
+                .and_then(|(_, series)| series.as_object())
+        })
+        .ok_or_else(|| anyhow::anyhow!("Alpha Vantage returned no time series"))?;
+    // Equity series use "4. close"; digital currency series have used both
+    // "4. close" and "4a. close (USD)".
+    let mut closes: Vec<(String, f64)> = series
+        .iter()
+        .filter(|(date, _)| date.as_str() >= since)
+        .filter_map(|(date, point)| {
+            let close = point.as_object()?.iter().find_map(|(key, value)| {
+                (key.starts_with('4') && key.contains("close"))
+                    .then(|| av_number(value))
+                    .flatten()
+            })?;
+            Some((date.clone(), close))
+        })
+        .collect();
+    closes.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(Series {
+        symbol: symbol.to_string(),
+        currency: crypto_pair(symbol).map(|(_, market)| market.to_string()),
+        closes,
+    })
+}
+
+fn range_days(range: &str) -> Option<i64> {
+    RANGES
+        .iter()
+        .find(|(name, _)| *name == range)
+        .map(|(_, days)| *days)
+}
+
+/// Ticker symbols: letters, digits, and `.-^=` (e.g. `BRK.B`, `^GSPC`, `EURUSD=X`).
+fn valid_symbol(symbol: &str) -> bool {
+    (1..=20).contains(&symbol.len())
+        && symbol
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '^' | '='))
+}
+
+fn format_price(value: f64) -> String {
+    if value.abs() >= 1.0 {
+        format!("{value:.2}")
+    } else {
+        format!("{value:.6}")
+    }
+}
+
+fn format_change(from: f64, to: f64) -> String {
+    let change = to - from;
+    let percent = if from == 0.0 {
+        0.0
+    } else {
+        change / from * 100.0
+    };
+    format!(
+        "{}{}, {percent:+.2}%",
+        if change >= 0.0 { "+" } else { "" },
+        format_price(change)
+    )
+}
+
+fn format_quote(quote: &Quote) -> String {
+    let mut line = format!("{}: {}", quote.symbol, format_price(quote.price));
+    if let Some(currency) = &quote.currency {
+        let _ = write!(line, " {currency}");
+    }
+    if let Some(previous) = quote.previous_close {
+        let _ = write!(line, " ({} today)", format_change(previous, quote.price));
+    }
+    if let Some(as_of) = &quote.as_of {
+        let _ = write!(line, " as of {as_of}");
+    }
+    if let Some(exchange) = &quote.exchange {
+        let _ = write!(line, " [{exchange}]");
+    }
+    line
+}
+
+fn format_series(series: &Series, range: &str, interval: Interval) -> String {
+    let (Some(first), Some(last)) = (series.closes.first(), series.closes.last()) else {
+        return format!("{}: no price history for {range}", series.symbol);
+    };
+    let currency = series
+        .currency
+        .as_deref()
+        .map_or_else(String::new, |currency| format!(" ({currency})"));
+    let low = series
+        .closes
+        .iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or(first);
+    let high = series
+        .closes
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or(first);
+    let mut output = format!(
+        "{} {} closes over {range}{currency}, {} to {}\n\
+         Start {}, end {} ({}); low {} on {}, high {} on {}\n",
+        series.symbol,
+        interval.as_str(),
+        first.0,
+        last.0,
+        format_price(first.1),
+        format_price(last.1),
+        format_change(first.1, last.1),
+        format_price(low.1),
+        low.0,
+        format_price(high.1),
+        high.0,
+    );
+    let step = series.closes.len().div_ceil(MAX_SERIES_POINTS).max(1);
+    let last_index = series.closes.len() - 1;
+    for (index, (date, close)) in series.closes.iter().enumerate() {
+        if index % step == 0 || index == last_index {
+            let _ = write!(output, "\n{date}: {}", format_price(*close));
+        }
+    }
+    output
+}
+
+/// Caps provider calls within a sliding one-minute window.
+struct RateLimiter {
+    max_per_minute: usize,
+    calls: Mutex<VecDeque<Instant>>,
+}
+
+impl RateLimiter {
+    fn new(max_per_minute: u32) -> Self {
+        Self {
+            max_per_minute: usize::try_from(max_per_minute).unwrap_or(usize::MAX).max(1),
+            calls: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record a call, or return how long until one is allowed.
+    fn acquire(&self, now: Instant) -> Result<(), Duration> {
+        let mut calls = self.calls.lock();
+        while calls
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW)
+        {
+            calls.pop_front();
+        }
+        if calls.len() >= self.max_per_minute {
+            let oldest = calls.front().copied().unwrap_or(now);
+            return Err(RATE_WINDOW.saturating_sub(now.duration_since(oldest)));
+        }
+        calls.push_back(now);
+        Ok(())
+    }
+}
+
+/// Stock and crypto quotes and closing-price history.
+pub struct FinanceTool {
+    provider: Box<dyn FinanceProvider>,
+    provider_name: &'static str,
+    timeout_secs: u64,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<String, (Instant, String)>>,
+    limiter: RateLimiter,
+}
+
+impl FinanceTool {
+    pub fn new(config: &FinanceConfig) -> Self {
+        let (provider, provider_name): (Box<dyn FinanceProvider>, _) = match config.provider.trim()
+        {
+            "alpha_vantage" => (
+                Box::new(AlphaVantage {
+                    api_key: config.api_key.clone().unwrap_or_default(),
+                }),
+                "Alpha Vantage",
+            ),
+            _ => (Box::new(Yahoo), "Yahoo Finance"),
+        };
+        Self {
+            provider,
+            provider_name,
+            timeout_secs: config.timeout_secs,
+            cache_ttl: Duration::from_secs(config.cache_ttl_secs),
+            cache: Mutex::new(HashMap::new()),
+            limiter: RateLimiter::new(config.max_requests_per_minute),
+        }
+    }
+
+    fn cached(&self, key: &str) -> Option<String> {
+        let cache = self.cache.lock();
+        cache
+            .get(key)
+            .filter(|(at, _)| at.elapsed() < self.cache_ttl)
+            .map(|(_, output)| output.clone())
+    }
+
+    fn remember(&self, key: String, output: String) {
+        let mut cache = self.cache.lock();
+        let ttl = self.cache_ttl;
+        cache.retain(|_, (at, _)| at.elapsed() < ttl);
+        cache.insert(key, (Instant::now(), output));
+    }
+
+    fn rate_limit_message(&self, wait: Duration) -> String {
+        format!(
+            "{} rate limit reached; try again in {}s",
+            self.provider_name,
+            wait.as_secs().max(1)
+        )
+    }
+
+    async fn quotes(&self, client: &reqwest::Client, symbols: &[String]) -> (Vec<String>, bool) {
+        let mut lines = Vec::with_capacity(symbols.len());
+        let mut any_ok = false;
+        for symbol in symbols {
+            let key = format!("quote:{symbol}");
+            if let Some(line) = self.cached(&key) {
+                lines.push(line);
+                any_ok = true;
+                continue;
+            }
+            if let Err(wait) = self.limiter.acquire(Instant::now()) {
+                lines.push(format!("{symbol}: {}", self.rate_limit_message(wait)));
+                continue;
+            }
+            match self.provider.quote(client, symbol).await {
+                Ok(mut quote) => {
+                    if quote.symbol.is_empty() {
+                        quote.symbol.clone_from(symbol);
+                    }
+                    let line = format_quote(&quote);
+                    self.remember(key, line.clone());
+                    lines.push(line);
+                    any_ok = true;
+                }
+                Err(e) => lines.push(format!("{symbol}: {e}")),
+            }
+        }
+        (lines, any_ok)
+    }
+
+    async fn history(
+        &self,
+        client: &reqwest::Client,
+        symbol: &str,
+        range: &str,
+        interval: Interval,
+    ) -> Result<String, String> {
+        let key = format!("history:{symbol}:{range}:{}", interval.as_str());
+        if let Some(output) = self.cached(&key) {
+            return Ok(output);
+        }
+        self.limiter
+            .acquire(Instant::now())
+            .map_err(|wait| self.rate_limit_message(wait))?;
+        let mut series = self
+            .provider
+            .history(client, symbol, range, interval)
+            .await
+            .map_err(|e| format!("{symbol}: {e}"))?;
+        if series.symbol.is_empty() {
+            series.symbol = symbol.to_string();
+        }
+        let output = format_series(&series, range, interval);
+        self.remember(key, output.clone());
+        Ok(output)
+    }
+}
+
+#[async_trait]
+impl Tool for FinanceTool {
+    fn name(&self) -> &str {
+        "finance"
+    }
+
+    fn description(&self) -> &str {
+        "Get live stock, ETF, index, or crypto quotes (action=quote, up to 10 symbols like AAPL, ^GSPC, BTC-USD) or closing-price history with change, low, and high (action=history). Use for portfolio and market questions instead of web search."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["quote", "history"],
+                    "default": "quote"
+                },
+                "symbols": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Ticker symbols; crypto as BASE-QUOTE (BTC-USD). history uses the first."
+                },
+                "range": {
+                    "type": "string",
+                    "enum": RANGES.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+                    "description": "history: period to cover",
+                    "default": "1mo"
+                },
+                "interval": {
+                    "type": "string",
+                    "enum": ["daily", "weekly"],
+                    "description": "history: spacing of closes (default daily up to 6mo, weekly beyond)"
+                }
+            },
+            "required": ["symbols"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let symbols: Vec<String> = match args.get("symbols") {
+            Some(Value::String(symbol)) => vec![symbol.clone()],
+            Some(Value::Array(items)) => items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+        .into_iter()
+        .map(|symbol| symbol.trim().to_ascii_uppercase())
+        .filter(|symbol| !symbol.is_empty())
+        .collect();
+        if symbols.is_empty() {
+            return Ok(failure("Missing 'symbols' parameter"));
+        }
+        if symbols.len() > MAX_SYMBOLS {
+            return Ok(failure(format!(
+                "Too many symbols (max {MAX_SYMBOLS} per call)"
+            )));
+        }
+        if let Some(bad) = symbols.iter().find(|symbol| !valid_symbol(symbol)) {
+            return Ok(failure(format!("Invalid symbol '{bad}'")));
+        }
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.finance",
+            self.timeout_secs,
+            10,
+        );
+
+        match args
+            .get("action")
+            .and_then(Value::as_str)
+            .unwrap_or("quote")
+        {
+            "quote" => {
+                let (lines, any_ok) = self.quotes(&client, &symbols).await;
+                let output = lines.join("\n");
+                Ok(if any_ok {
+                    ToolResult {
+                        success: true,
+                        output: format!("{output}\nSource: {}", self.provider_name),
+                        error: None,
+                    }
+                } else {
+                    failure(output)
+                })
+            }
+            "history" => {
+                let range = args.get("range").and_then(Value::as_str).unwrap_or("1mo");
+                let Some(days) = range_days(range) else {
+                    return Ok(failure(format!("Unknown range '{range}'")));
+                };
+                let interval = match args.get("interval").and_then(Value::as_str) {
+                    Some("daily") => Interval::Daily,
+                    Some("weekly") => Interval::Weekly,
+                    Some(other) => {
+                        return Ok(failure(format!(
+                            "Unknown interval '{other}' (use daily or weekly)"
+                        )))
+                    }
+                    None if days > 183 => Interval::Weekly,
+                    None => Interval::Daily,
+                };
+                match self.history(&client, &symbols[0], range, interval).await {
+                    Ok(output) => Ok(ToolResult {
+                        success: true,
+                        output: format!("{output}\nSource: {}", self.provider_name),
+                        error: None,
+                    }),
+                    Err(error) => Ok(failure(error)),
+                }
+            }
+            other => Ok(failure(format!(
+                "Unknown action '{other}' (use quote or history)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yahoo_chart_responses_are_normalized() {
+        let body = json!({ "chart": { "error": null, "result": [{
+            "meta": {
+                "symbol": "AAPL", "currency": "USD", "fullExchangeName": "NasdaqGS",
+                "regularMarketPrice": 189.84, "chartPreviousClose": 187.5,
+                "regularMarketTime": 1_792_094_400_i64, "gmtoffset": -14_400,
+                "exchangeTimezoneName": "America/New_York"
+            },
+            "timestamp": [1_791_984_600_i64, 1_792_071_000_i64, 1_792_157_400_i64],
+            "indicators": { "quote": [{ "close": [185.0, null, 189.84] }] }
+        }]}});
+        let quote = parse_yahoo_quote(&body).unwrap();
+        assert_eq!(
+            format_quote(&quote),
+            "AAPL: 189.84 USD (+2.34, +1.25% today) as of 2026-10-15 16:00 America/New_York [NasdaqGS]"
+        );
+
+        let series = parse_yahoo_series(&body).unwrap();
+        assert_eq!(
+            series.closes,
+            vec![
+                ("2026-10-14".to_string(), 185.0),
+                ("2026-10-16".to_string(), 189.84)
+            ]
+        );
+        let output = format_series(&series, "5d", Interval::Daily);
+        assert!(output.starts_with(
+            "AAPL daily closes over 5d (USD), 2026-10-14 to 2026-10-16\n\
+             Start 185.00, end 189.84 (+4.84, +2.62%); low 185.00 on 2026-10-14, high 189.84 on 2026-10-16\n"
+        ));
+    }
+
+    #[test]
+    fn alpha_vantage_series_are_filtered_and_sorted() {
+        let body = json!({
+            "Meta Data": { "2. Digital Currency Code": "BTC" },
+            "Time Series (Digital Currency Daily)": {
+                "2026-10-15": { "1. open": "1", "4. close": "67000.5" },
+                "2026-10-14": { "1. open": "1", "4a. close (USD)": "66000" },
+                "2026-09-01": { "1. open": "1", "4. close": "59000" }
+            }
+        });
+        let series = parse_alpha_vantage_series("BTC-USD", &body, "2026-10-01").unwrap();
+        assert_eq!(series.currency.as_deref(), Some("USD"));
+        assert_eq!(
+            series.closes,
+            vec![
+                ("2026-10-14".to_string(), 66000.0),
+                ("2026-10-15".to_string(), 67000.5)
+            ]
+        );
+
+        let quote = parse_alpha_vantage_quote(&json!({ "Global Quote": {
+            "01. symbol": "IBM", "05. price": "231.2000", "08. previous close": "233.0000",
+            "07. latest trading day": "2026-10-14"
+        }}))
+        .unwrap();
+        assert_eq!(
+            format_quote(&quote),
+            "IBM: 231.20 (-1.80, -0.77% today) as of 2026-10-14"
+        );
+        assert_eq!(crypto_pair("BTC-USD"), Some(("BTC", "USD")));
+        assert_eq!(crypto_pair("BRK-B"), None);
+    }
+
+    #[test]
+    fn rate_limiter_caps_calls_per_minute() {
+        let limiter = RateLimiter::new(2);
+        let start = Instant::now();
+        assert!(limiter.acquire(start).is_ok());
+        assert!(limiter.acquire(start + Duration::from_secs(10)).is_ok());
+        let wait = limiter
+            .acquire(start + Duration::from_secs(20))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(40));
+        assert!(limiter.acquire(start + Duration::from_secs(61)).is_ok());
+    }
+
+    #[tokio::test]
+    async fn cached_quotes_skip_the_provider() {
+        let tool = FinanceTool::new(&FinanceConfig {
+            max_requests_per_minute: 1,
+            ..FinanceConfig::default()
+        });
+        tool.remember("quote:MSFT".into(), "MSFT: 420.00 USD".into());
+        let _ = tool.limiter.acquire(Instant::now());
+
+        let result = tool
+            .execute(json!({"symbols": ["msft", "NVDA"]}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .starts_with("MSFT: 420.00 USD\nNVDA: Yahoo Finance rate limit reached"));
+
+        let bad = tool.execute(json!({"symbols": ["AAPL;rm"]})).await.unwrap();
+        assert_eq!(bad.error.as_deref(), Some("Invalid symbol 'AAPL;RM'"));
+    }
+}
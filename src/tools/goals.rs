@@ -0,0 +1,320 @@
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::goals::{self, GoalStatus};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn success(output: String) -> ToolResult {
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
+fn non_empty_str<'a>(args: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn mutation_blocked(security: &SecurityPolicy, action: &str) -> Option<ToolResult> {
+    if !security.can_act() {
+        return Some(failure(format!(
+            "Security policy: read-only mode, cannot perform '{action}'"
+        )));
+    }
+    if !security.record_action() {
+        return Some(failure("Rate limit exceeded: action budget exhausted"));
+    }
+    None
+}
+
+/// Creates, lists, and removes tracked goals.
+pub struct GoalsTool {
+    workspace_dir: PathBuf,
+    security: Arc<SecurityPolicy>,
+}
+
+impl GoalsTool {
+    pub fn new(workspace_dir: PathBuf, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            workspace_dir,
+            security,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for GoalsTool {
+    fn name(&self) -> &str {
+        "goals"
+    }
+
+    fn description(&self) -> &str {
+        "Track the user's goals. action='create' with a title, optional due date (YYYY-MM-DD), \
+         and milestones; 'list' shows active goals (all=true includes finished ones); 'show' \
+         gives one goal with milestones and recent progress; 'add_milestone' and 'remove' edit \
+         a goal. Record progress with goal_update."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["create", "list", "show", "add_milestone", "remove"],
+                    "default": "list"
+                },
+                "title": { "type": "string", "description": "Goal title (create) or milestone title (add_milestone)" },
+                "due": { "type": "string", "description": "Due date, YYYY-MM-DD (create)" },
+                "milestones": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Milestones in order (create)"
+                },
+                "goal": { "type": "string", "description": "Goal id or part of its title (show, add_milestone, remove)" },
+                "all": { "type": "boolean", "description": "Include finished and dropped goals (list)" }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = non_empty_str(&args, "action").unwrap_or("list");
+        if matches!(action, "create" | "add_milestone" | "remove") {
+            if let Some(blocked) = mutation_blocked(&self.security, action) {
+                return Ok(blocked);
+            }
+        }
+        let ws = self.workspace_dir.as_path();
+
+        match action {
+            "create" => {
+                let Some(title) = non_empty_str(&args, "title") else {
+                    return Ok(failure("'create' needs a 'title'"));
+                };
+                let due = match non_empty_str(&args, "due").map(str::parse::<NaiveDate>) {
+                    Some(Ok(due)) => Some(due),
+                    Some(Err(_)) => return Ok(failure("'due' must be a YYYY-MM-DD date")),
+                    None => None,
+                };
+                let milestones: Vec<String> = args
+                    .get("milestones")
+                    .and_then(serde_json::Value::as_array)
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(serde_json::Value::as_str)
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let goal = goals::create(ws, title, due, &milestones)?;
+                Ok(success(format!(
+                    "Created goal:\n{}",
+                    goals::describe(&goal)
+                )))
+            }
+            "list" => {
+                let all = args
+                    .get("all")
+                    .and_then(serde_json::Value::as_bool)
+                    .unwrap_or(false);
+                let listed = goals::list(ws, all)?;
+                if listed.is_empty() {
+                    return Ok(success("No goals tracked yet.".into()));
+                }
+                Ok(success(
+                    listed
+                        .iter()
+                        .map(goals::describe)
+                        .collect::<Vec<_>>()
+                        .join("\n\n"),
+                ))
+            }
+            "show" | "add_milestone" | "remove" => {
+                let Some(reference) = non_empty_str(&args, "goal") else {
+                    return Ok(failure(format!("'{action}' needs a 'goal'")));
+                };
+                let goal = match goals::find(ws, reference) {
+                    Ok(goal) => goal,
+                    Err(e) => return Ok(failure(e.to_string())),
+                };
+                match action {
+                    "show" => Ok(success(goals::describe(&goal))),
+                    "add_milestone" => {
+                        let Some(title) = non_empty_str(&args, "title") else {
+                            return Ok(failure("'add_milestone' needs a 'title'"));
+                        };
+                        goals::add_milestone(ws, goal.id, title)?;
+                        Ok(success(goals::describe(&goals::get(ws, goal.id)?)))
+                    }
+                    _ => {
+                        goals::remove(ws, goal.id)?;
+                        Ok(success(format!(
+                            "Removed goal #{} {}.",
+                            goal.id, goal.title
+                        )))
+                    }
+                }
+            }
+            other => Ok(failure(format!("Unknown action '{other}'"))),
+        }
+    }
+}
+
+/// Records progress on a goal: a note, a finished milestone, or a new status.
+pub struct GoalUpdateTool {
+    workspace_dir: PathBuf,
+    security: Arc<SecurityPolicy>,
+}
+
+impl GoalUpdateTool {
+    pub fn new(workspace_dir: PathBuf, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            workspace_dir,
+            security,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for GoalUpdateTool {
+    fn name(&self) -> &str {
+        "goal_update"
+    }
+
+    fn description(&self) -> &str {
+        "Record progress on one of the user's goals, including progress mentioned in passing \
+         (\"ran 12k today\"). Give the goal id or part of its title plus any of: a short progress \
+         note, a milestone (number or title) that is now done, or a new status \
+         (active|done|dropped)."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "goal": { "type": "string", "description": "Goal id or part of its title" },
+                "note": { "type": "string", "description": "What happened, in a sentence" },
+                "milestone": { "type": "string", "description": "Milestone number or title that was completed" },
+                "status": { "type": "string", "enum": ["active", "done", "dropped"] }
+            },
+            "required": ["goal"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(reference) = non_empty_str(&args, "goal") else {
+            return Ok(failure("Missing 'goal'"));
+        };
+        let note = non_empty_str(&args, "note");
+        let milestone = non_empty_str(&args, "milestone");
+        let status = match non_empty_str(&args, "status").map(GoalStatus::parse) {
+            Some(Ok(status)) => Some(status),
+            Some(Err(e)) => return Ok(failure(e.to_string())),
+            None => None,
+        };
+        if note.is_none() && milestone.is_none() && status.is_none() {
+            return Ok(failure("Give a 'note', 'milestone', or 'status' to record"));
+        }
+        if let Some(blocked) = mutation_blocked(&self.security, "goal_update") {
+            return Ok(blocked);
+        }
+
+        let ws = self.workspace_dir.as_path();
+        let goal = match goals::find(ws, reference) {
+            Ok(goal) => goal,
+            Err(e) => return Ok(failure(e.to_string())),
+        };
+        let mut recorded = Vec::new();
+        if let Some(milestone) = milestone {
+            match goals::complete_milestone(ws, goal.id, milestone) {
+                Ok(title) => recorded.push(format!("milestone done: {title}")),
+                Err(e) => return Ok(failure(e.to_string())),
+            }
+        }
+        if let Some(note) = note {
+            goals::add_note(ws, goal.id, note)?;
+            recorded.push("note added".into());
+        }
+        if let Some(status) = status {
+            goals::set_status(ws, goal.id, status)?;
+            recorded.push(format!("status: {}", status.as_str()));
+        }
+
+        let goal = goals::get(ws, goal.id)?;
+        let mut output = format!(
+            "Updated ({}):\n{}",
+            recorded.join(", "),
+            goals::describe(&goal)
+        );
+        if goal.status == GoalStatus::Active
+            && !goal.milestones.is_empty()
+            && goal.milestones_done() == goal.milestones.len()
+        {
+            output
+                .push_str("\nAll milestones are done; ask the user whether the goal is complete.");
+        }
+        Ok(success(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn create_then_record_progress_in_conversation() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let goals_tool = GoalsTool::new(tmp.path().to_path_buf(), security.clone());
+        let update = GoalUpdateTool::new(tmp.path().to_path_buf(), security);
+
+        let created = goals_tool
+            .execute(json!({
+                "action": "create",
+                "title": "Ship the garden shed",
+                "due": "2026-06-01",
+                "milestones": ["Pour foundation", "Frame walls"]
+            }))
+            .await
+            .unwrap();
+        assert!(created.success);
+        assert!(created
+            .output
+            .contains("#1 Ship the garden shed [active] due 2026-06-01"));
+
+        let bad_due = goals_tool
+            .execute(json!({"action": "create", "title": "x", "due": "June"}))
+            .await
+            .unwrap();
+        assert!(!bad_due.success);
+
+        let updated = update
+            .execute(json!({"goal": "shed", "milestone": "1", "note": "Concrete is curing"}))
+            .await
+            .unwrap();
+        assert!(updated.success, "{:?}", updated.error);
+        assert!(updated.output.contains("1. [x] Pour foundation"));
+        assert!(updated.output.contains(": Concrete is curing"));
+
+        let finished = update
+            .execute(json!({"goal": "#1", "milestone": "frame"}))
+            .await
+            .unwrap();
+        assert!(finished
+            .output
+            .ends_with("ask the user whether the goal is complete."));
+
+        let nothing = update.execute(json!({"goal": "1"})).await.unwrap();
+        assert!(!nothing.success);
+    }
+}
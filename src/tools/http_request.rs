@@ -1,4 +1,5 @@
 use super::traits::{Tool, ToolResult};
+use crate::artifacts::ArtifactStore;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -12,6 +13,7 @@ pub struct HttpRequestTool {
     allowed_domains: Vec<String>,
     max_response_size: usize,
     timeout_secs: u64,
+    artifacts: Option<Arc<ArtifactStore>>,
 }
 
 impl HttpRequestTool {
@@ -26,9 +28,16 @@ impl HttpRequestTool {
             allowed_domains: normalize_allowed_domains(allowed_domains),
             max_response_size,
             timeout_secs,
+            artifacts: None,
         }
     }
 
+    /// Allow `"save": true` to store response bodies in the artifact store.
+    pub fn with_artifacts(mut self, store: Arc<ArtifactStore>) -> Self {
+        self.artifacts = Some(store);
+        self
+    }
+
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
         let url = raw_url.trim();
 
@@ -60,6 +69,10 @@ impl HttpRequestTool {
             anyhow::bail!("Host '{host}' is not in http_request.allowed_domains");
         }
 
+        self.security
+            .check_network_target(&host)
+            .map_err(anyhow::Error::msg)?;
+
         Ok(url.to_string())
     }
 
@@ -140,6 +153,39 @@ impl HttpRequestTool {
         Ok(request.send().await?)
     }
 
+    /// Store the response body as an artifact and describe where it went.
+    async fn save_body(
+        &self,
+        store: &ArtifactStore,
+        url: &str,
+        mut response: reqwest::Response,
+    ) -> anyhow::Result<String> {
+        let limit = store.max_artifact_bytes();
+        if response.content_length().is_some_and(|len| len > limit) {
+            anyhow::bail!("Response body exceeds the {limit} byte artifact limit");
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if (body.len() + chunk.len()) as u64 > limit {
+                anyhow::bail!("Response body exceeds the {limit} byte artifact limit");
+            }
+            body.extend_from_slice(&chunk);
+        }
+        let meta = store.put(self.name(), Some(url), content_type.as_deref(), &body)?;
+        Ok(format!(
+            "[Saved as artifact {id}: {} bytes, {}. Call artifact_read with id \"{id}\" to read it, or open {} with another tool.]",
+            meta.size_bytes,
+            meta.content_type.as_deref().unwrap_or("unknown type"),
+            store.content_path(&meta.id).display(),
+            id = meta.short_id(),
+        ))
+    }
+
     fn truncate_response(&self, text: &str) -> String {
         // 0 means unlimited — no truncation.
         if self.max_response_size == 0 {
@@ -170,7 +216,7 @@ impl Tool for HttpRequestTool {
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
-        json!({
+        let mut schema = json!({
             "type": "object",
             "properties": {
                 "url": {
@@ -193,7 +239,15 @@ impl Tool for HttpRequestTool {
                 }
             },
             "required": ["url"]
-        })
+        });
+        if self.artifacts.is_some() {
+            schema["properties"]["save"] = json!({
+                "type": "boolean",
+                "description": "Store the full response body as an artifact instead of returning it; use for files and large downloads",
+                "default": false
+            });
+        }
+        schema
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
@@ -205,6 +259,10 @@ impl Tool for HttpRequestTool {
         let method_str = args.get("method").and_then(|v| v.as_str()).unwrap_or("GET");
         let headers_val = args.get("headers").cloned().unwrap_or(json!({}));
         let body = args.get("body").and_then(|v| v.as_str());
+        let save = args
+            .get("save")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
 
         if !self.security.can_act() {
             return Ok(ToolResult {
@@ -268,10 +326,18 @@ impl Tool for HttpRequestTool {
                     .collect::<Vec<_>>()
                     .join(", ");
 
-                // Get response body with size limit
-                let response_text = match response.text().await {
-                    Ok(text) => self.truncate_response(&text),
-                    Err(e) => format!("[Failed to read response body: {e}]"),
+                // Get response body with size limit, or store it whole
+                let response_text = match &self.artifacts {
+                    Some(store) if save && status.is_success() => {
+                        match self.save_body(store, &url, response).await {
+                            Ok(note) => note,
+                            Err(e) => format!("[Failed to save response body: {e}]"),
+                        }
+                    }
+                    _ => match response.text().await {
+                        Ok(text) => self.truncate_response(&text),
+                        Err(e) => format!("[Failed to read response body: {e}]"),
+                    },
                 };
 
                 let output = format!(
@@ -466,6 +532,19 @@ mod tests {
         )
     }
 
+    #[test]
+    fn save_parameter_requires_artifact_store() {
+        let tool = test_tool(vec!["example.com"]);
+        assert!(tool.parameters_schema()["properties"].get("save").is_none());
+
+        let store = ArtifactStore::new(std::env::temp_dir().join("zeroclaw_http_artifacts"), 1, 1);
+        let tool = tool.with_artifacts(Arc::new(store));
+        assert_eq!(
+            tool.parameters_schema()["properties"]["save"]["type"],
+            "boolean"
+        );
+    }
+
     #[test]
     fn normalize_domain_strips_scheme_path_and_case() {
         let got = normalize_domain("  HTTPS://Docs.Example.com/path ").unwrap();
@@ -0,0 +1,473 @@
+//! Calendar invites as `.ics` files (`ics_create`).
+//!
+//! Writes one RFC 5545 event to `<workspace>/exports/calendar/`, so invites
+//! work without a calendar integration: channels send the file through a
+//! `[DOCUMENT:<path>]` marker and `send_email` can attach it.
+
+use super::remind_me::{parse_duration, parse_when};
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use anyhow::Context;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const DEFAULT_DURATION_MINUTES: i64 = 60;
+/// Longest content line in octets before folding (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+/// When an event happens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum EventTime {
+    /// Timed event, stored in UTC.
+    At {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    /// All-day event; `end` is exclusive.
+    AllDay { start: NaiveDate, end: NaiveDate },
+}
+
+/// One calendar event.
+#[derive(Debug, Clone)]
+pub(crate) struct IcsEvent {
+    pub uid: String,
+    pub title: String,
+    pub time: EventTime,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    pub organizer: Option<String>,
+    pub attendees: Vec<String>,
+    pub reminder_minutes: Option<u32>,
+}
+
+/// Escape TEXT values (RFC 5545 §3.3.11).
+fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Append `line` folded at 75 octets, without splitting a UTF-8 character.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            // The leading space counts toward the continuation line.
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn utc_stamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Render `event` as a calendar file. Events with an organizer and
+/// attendees are sent as invitations (`METHOD:REQUEST`).
+pub(crate) fn build_ics(event: &IcsEvent, now: DateTime<Utc>) -> String {
+    let invitation = event.organizer.is_some() && !event.attendees.is_empty();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".into(),
+        "PRODID:-//ZeroClaw//ics_create//EN".into(),
+        "CALSCALE:GREGORIAN".into(),
+        format!("METHOD:{}", if invitation { "REQUEST" } else { "PUBLISH" }),
+        "BEGIN:VEVENT".into(),
+        format!("UID:{}", event.uid),
+        format!("DTSTAMP:{}", utc_stamp(now)),
+    ];
+    match &event.time {
+        EventTime::At { start, end } => {
+            lines.push(format!("DTSTART:{}", utc_stamp(*start)));
+            lines.push(format!("DTEND:{}", utc_stamp(*end)));
+        }
+        EventTime::AllDay { start, end } => {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")));
+            lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+        }
+    }
+    lines.push(format!("SUMMARY:{}", escape_text(&event.title)));
+    if let Some(location) = &event.location {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(description) = &event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+    }
+    if let Some(organizer) = &event.organizer {
+        lines.push(format!("ORGANIZER:mailto:{organizer}"));
+    }
+    for attendee in &event.attendees {
+        lines.push(format!(
+            "ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:{attendee}"
+        ));
+    }
+    if let Some(minutes) = event.reminder_minutes {
+        lines.extend([
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".into(),
+            format!("DESCRIPTION:{}", escape_text(&event.title)),
+            format!("TRIGGER:-PT{minutes}M"),
+            "END:VALARM".into(),
+        ]);
+    }
+    lines.extend(["END:VEVENT".to_string(), "END:VCALENDAR".into()]);
+
+    let mut out = String::new();
+    for line in &lines {
+        push_line(&mut out, line);
+    }
+    out
+}
+
+fn non_empty_str<'a>(args: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn email_list(args: &serde_json::Value, key: &str) -> Result<Vec<String>, String> {
+    let items: Vec<&str> = match args.get(key) {
+        Some(serde_json::Value::Array(items)) => {
+            items.iter().filter_map(serde_json::Value::as_str).collect()
+        }
+        Some(serde_json::Value::String(value)) => value.split(',').collect(),
+        _ => Vec::new(),
+    };
+    items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let address = item.trim_start_matches("mailto:");
+            if address.contains('@') && !address.contains(char::is_whitespace) {
+                Ok(address.to_string())
+            } else {
+                Err(format!("'{item}' is not an email address"))
+            }
+        })
+        .collect()
+}
+
+/// Work out the event time from `start`, `end`/`duration`, and `all_day`.
+fn event_time(args: &serde_json::Value, now: DateTime<Tz>) -> Result<EventTime, String> {
+    let start_text = non_empty_str(args, "start").ok_or("Missing 'start'")?;
+    let end_text = non_empty_str(args, "end");
+    let parse = |text: &str, what: &str| {
+        parse_when(text, now).map_err(|e| format!("Could not understand {what} '{text}': {e}"))
+    };
+
+    if args
+        .get("all_day")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+    {
+        // Plain dates are taken as-is, so an all-day event today still works.
+        let day = |text: &str, what: &str| match NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+            Ok(date) => Ok(date),
+            Err(_) => parse(text, what).map(|at| at.with_timezone(&now.timezone()).date_naive()),
+        };
+        let first = day(start_text, "start")?;
+        // The end date is included.
+        let last = end_text.map_or(Ok(first), |text| day(text, "end"))?;
+        if last < first {
+            return Err("The event must end after it starts".into());
+        }
+        let end = last.succ_opt().ok_or("End date is out of range")?;
+        return Ok(EventTime::AllDay { start: first, end });
+    }
+
+    let start = parse(start_text, "start")?;
+    let end = match (end_text, non_empty_str(args, "duration")) {
+        (Some(text), _) => parse(text, "end")?,
+        (None, Some(duration)) => start + parse_duration(duration)?,
+        (None, None) => start + Duration::minutes(DEFAULT_DURATION_MINUTES),
+    };
+    if end <= start {
+        return Err("The event must end after it starts".into());
+    }
+    Ok(EventTime::At { start, end })
+}
+
+fn file_stem(title: &str) -> String {
+    let slug: String = title
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "event".into()
+    } else {
+        slug
+    }
+}
+
+/// Writes `.ics` calendar files for events the agent schedules.
+pub struct IcsCreateTool {
+    workspace_dir: PathBuf,
+    security: Arc<SecurityPolicy>,
+}
+
+impl IcsCreateTool {
+    pub fn new(workspace_dir: PathBuf, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            workspace_dir,
+            security,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for IcsCreateTool {
+    fn name(&self) -> &str {
+        "ics_create"
+    }
+
+    fn description(&self) -> &str {
+        "Create a calendar invite (.ics file) for an event that works in any calendar app. Give a \
+         title and start ('tomorrow 3pm', '2026-05-04 10:00'), optionally end or duration, tz, \
+         location, description, attendees, and a reminder. Send it with the returned \
+         [DOCUMENT:<path>] marker or attach the path to send_email."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string" },
+                "start": { "type": "string", "description": "Start time, e.g. 'Friday 3pm' or '2026-05-04 10:00'" },
+                "end": { "type": "string", "description": "End time (default: one hour after start)" },
+                "duration": { "type": "string", "description": "Length instead of end, e.g. '90m' or '2 hours'" },
+                "tz": { "type": "string", "description": "IANA timezone for the times (default: UTC)" },
+                "all_day": { "type": "boolean", "description": "All-day event on the start (through end) date" },
+                "location": { "type": "string" },
+                "description": { "type": "string" },
+                "organizer": { "type": "string", "description": "Organizer email; with attendees the file is an invitation" },
+                "attendees": { "type": "array", "items": { "type": "string" }, "description": "Attendee emails" },
+                "reminder_minutes": { "type": "integer", "minimum": 0, "description": "Alert this many minutes before" }
+            },
+            "required": ["title", "start"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(title) = non_empty_str(&args, "title") else {
+            return Ok(failure("Missing 'title'"));
+        };
+        let tz = match non_empty_str(&args, "tz").unwrap_or("UTC").parse::<Tz>() {
+            Ok(tz) => tz,
+            Err(e) => return Ok(failure(format!("Invalid timezone: {e}"))),
+        };
+        let now = Utc::now();
+        let time = match event_time(&args, now.with_timezone(&tz)) {
+            Ok(time) => time,
+            Err(e) => return Ok(failure(e)),
+        };
+        let (attendees, organizer) = match (
+            email_list(&args, "attendees"),
+            email_list(&args, "organizer"),
+        ) {
+            (Ok(attendees), Ok(organizer)) => (attendees, organizer.into_iter().next()),
+            (Err(e), _) | (_, Err(e)) => return Ok(failure(e)),
+        };
+        let reminder_minutes = args
+            .get("reminder_minutes")
+            .and_then(serde_json::Value::as_u64)
+            .map(|minutes| u32::try_from(minutes).unwrap_or(u32::MAX));
+
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "ics_create")
+        {
+            return Ok(failure(e));
+        }
+
+        let event = IcsEvent {
+            uid: format!("{}@zeroclaw", uuid::Uuid::new_v4()),
+            title: title.to_string(),
+            time,
+            location: non_empty_str(&args, "location").map(str::to_string),
+            description: non_empty_str(&args, "description").map(str::to_string),
+            organizer,
+            attendees,
+            reminder_minutes,
+        };
+        let dir = self.workspace_dir.join("exports").join("calendar");
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!("{}.ics", file_stem(title)));
+        std::fs::write(&path, build_ics(&event, now))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        let mut output = format!("Calendar file for '{title}' ");
+        match &event.time {
+            EventTime::At { start, end } => {
+                let _ = write!(
+                    output,
+                    "({} to {})",
+                    start.with_timezone(&tz).format("%Y-%m-%d %H:%M"),
+                    end.with_timezone(&tz).format("%H:%M %Z")
+                );
+            }
+            EventTime::AllDay { start, end } => {
+                let last = end.pred_opt().unwrap_or(*start);
+                if last == *start {
+                    let _ = write!(output, "(all day {start})");
+                } else {
+                    let _ = write!(output, "(all day {start} to {last})");
+                }
+            }
+        }
+        let _ = write!(
+            output,
+            " saved to: {path}\nAttach it with [DOCUMENT:{path}], or pass \
+             exports/calendar/{name} to send_email as an attachment.",
+            path = path.display(),
+            name = path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn builds_folded_escaped_invitation() {
+        let start = Utc.with_ymd_and_hms(2026, 5, 4, 8, 0, 0).unwrap();
+        let event = IcsEvent {
+            uid: "abc@zeroclaw".into(),
+            title: "Quarterly review; budget, hiring".into(),
+            time: EventTime::At {
+                start,
+                end: start + Duration::minutes(90),
+            },
+            location: None,
+            description: Some(format!("Agenda:\n{}", "é".repeat(60))),
+            organizer: Some("ana@example.com".into()),
+            attendees: vec!["bob@example.com".into()],
+            reminder_minutes: Some(15),
+        };
+        let ics = build_ics(&event, start);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.contains("\r\nMETHOD:REQUEST\r\n"));
+        assert!(ics.contains("\r\nDTSTART:20260504T080000Z\r\nDTEND:20260504T093000Z\r\n"));
+        assert!(ics.contains("\r\nSUMMARY:Quarterly review\\; budget\\, hiring\r\n"));
+        assert!(ics.contains("\r\nTRIGGER:-PT15M\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "{line}");
+        }
+        let unfolded = ics.replace("\r\n ", "");
+        assert!(unfolded.contains(&format!("DESCRIPTION:Agenda:\\n{}", "é".repeat(60))));
+    }
+
+    #[test]
+    fn all_day_events_end_the_day_after() {
+        let now = Utc
+            .with_ymd_and_hms(2026, 3, 2, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&chrono_tz::Europe::Berlin);
+        let time = event_time(
+            &json!({"start": "2026-03-10", "end": "2026-03-12", "all_day": true}),
+            now,
+        )
+        .unwrap();
+        assert_eq!(
+            time,
+            EventTime::AllDay {
+                start: NaiveDate::from_ymd_opt(2026, 3, 10).unwrap(),
+                end: NaiveDate::from_ymd_opt(2026, 3, 13).unwrap(),
+            }
+        );
+
+        let timed = event_time(
+            &json!({"start": "2026-03-10 09:30", "duration": "45m"}),
+            now,
+        )
+        .unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 3, 10, 8, 30, 0).unwrap();
+        assert_eq!(
+            timed,
+            EventTime::At {
+                start,
+                end: start + Duration::minutes(45)
+            }
+        );
+        assert!(event_time(
+            &json!({"start": "2026-03-10 09:30", "end": "2026-03-10 09:00"}),
+            now
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn writes_file_under_exports() {
+        let tmp = TempDir::new().unwrap();
+        let tool = IcsCreateTool::new(
+            tmp.path().to_path_buf(),
+            Arc::new(SecurityPolicy::default()),
+        );
+        let result = tool
+            .execute(json!({
+                "title": "Dentist @ 3",
+                "start": "2099-01-05 15:00",
+                "tz": "America/New_York",
+                "attendees": "not an email"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.error.as_deref(),
+            Some("'not an email' is not an email address")
+        );
+
+        let result = tool
+            .execute(json!({
+                "title": "Dentist @ 3",
+                "start": "2099-01-05 15:00",
+                "tz": "America/New_York"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("(2099-01-05 15:00 to 16:00 EST)"));
+        let ics =
+            std::fs::read_to_string(tmp.path().join("exports/calendar/dentist-3.ics")).unwrap();
+        assert!(ics.contains("DTSTART:20990105T200000Z\r\n"));
+        assert!(ics.contains("METHOD:PUBLISH\r\n"));
+    }
+}
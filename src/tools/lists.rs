@@ -0,0 +1,593 @@
+//! Named checklists (`lists`): shopping lists, packing lists, to-dos.
+//!
+//! A list is personal (owned by the requesting sender) or shared by everyone
+//! in the requesting chat. Lists live in `<workspace>/lists/lists.db`;
+//! `export` also writes a markdown copy to `<workspace>/exports/lists/`.
+
+use super::preferences::current_identity;
+use super::remind_me::current_route;
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::json;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const MAX_ITEMS: usize = 200;
+const MAX_ITEM_CHARS: usize = 200;
+
+#[derive(Debug, Clone)]
+struct Item {
+    id: i64,
+    text: String,
+    checked: bool,
+}
+
+#[derive(Debug, Clone)]
+struct List {
+    id: i64,
+    name: String,
+    shared: bool,
+    items: Vec<Item>,
+}
+
+/// Owner keys for the requester: their own lists and the current chat's.
+fn owners() -> (String, Option<String>) {
+    let identity = current_identity();
+    let personal = format!("user:{}:{}", identity.channel, identity.sender);
+    let shared = current_route().map(|route| format!("chat:{}:{}", route.channel, route.recipient));
+    (personal, shared)
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn render(list: &List) -> String {
+    let done = list.items.iter().filter(|item| item.checked).count();
+    let mut text = format!(
+        "{}{} ({} item{}",
+        list.name,
+        if list.shared { " (shared)" } else { "" },
+        list.items.len(),
+        if list.items.len() == 1 { "" } else { "s" }
+    );
+    if done > 0 {
+        let _ = write!(text, ", {done} done");
+    }
+    text.push(')');
+    if list.items.is_empty() {
+        text.push_str("\n(empty)");
+    }
+    for (i, item) in list.items.iter().enumerate() {
+        let mark = if item.checked { "☑" } else { "☐" };
+        let _ = write!(text, "\n{}. {mark} {}", i + 1, item.text);
+    }
+    text
+}
+
+fn to_markdown(list: &List) -> String {
+    let mut text = format!("# {}\n\n", list.name);
+    for item in &list.items {
+        let mark = if item.checked { "x" } else { " " };
+        let _ = writeln!(text, "- [{mark}] {}", item.text);
+    }
+    text
+}
+
+/// Resolve an item by 1-based number or a unique piece of its text.
+fn find_item<'a>(list: &'a List, reference: &str) -> Result<&'a Item> {
+    let reference = reference.trim();
+    if let Ok(position) = reference.parse::<usize>() {
+        return list
+            .items
+            .get(position.wrapping_sub(1))
+            .with_context(|| format!("'{}' has no item {position}", list.name));
+    }
+    let needle = reference.to_lowercase();
+    if let Some(exact) = list
+        .items
+        .iter()
+        .find(|item| item.text.to_lowercase() == needle)
+    {
+        return Ok(exact);
+    }
+    let matching: Vec<&Item> = list
+        .items
+        .iter()
+        .filter(|item| item.text.to_lowercase().contains(&needle))
+        .collect();
+    match matching.as_slice() {
+        [item] => Ok(item),
+        [] => bail!("'{}' has no item matching '{reference}'", list.name),
+        _ => bail!(
+            "'{reference}' matches several items on '{}'; use the item number",
+            list.name
+        ),
+    }
+}
+
+struct ListStore {
+    conn: Connection,
+}
+
+impl ListStore {
+    fn open(workspace_dir: &Path) -> Result<Self> {
+        let db_path = workspace_dir.join("lists").join("lists.db");
+        let conn = crate::state::open_db(&db_path)
+            .with_context(|| format!("Failed to open lists DB: {}", db_path.display()))?;
+        conn.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             CREATE TABLE IF NOT EXISTS lists (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                owner      TEXT NOT NULL,
+                name       TEXT NOT NULL COLLATE NOCASE,
+                created_at TEXT NOT NULL,
+                UNIQUE (owner, name)
+             );
+             CREATE TABLE IF NOT EXISTS list_items (
+                id       INTEGER PRIMARY KEY AUTOINCREMENT,
+                list_id  INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+                text     TEXT NOT NULL,
+                checked  INTEGER NOT NULL DEFAULT 0,
+                added_at TEXT NOT NULL
+             );",
+        )
+        .context("Failed to initialize lists schema")?;
+        Ok(Self { conn })
+    }
+
+    fn load(&self, id: i64, name: String, shared: bool) -> Result<List> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, text, checked FROM list_items WHERE list_id = ?1 ORDER BY id")?;
+        let items = stmt
+            .query_map(params![id], |row| {
+                Ok(Item {
+                    id: row.get(0)?,
+                    text: row.get(1)?,
+                    checked: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(List {
+            id,
+            name,
+            shared,
+            items,
+        })
+    }
+
+    fn get_owned(&self, owner: &str, name: &str) -> Result<Option<List>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT id, name FROM lists WHERE owner = ?1 AND name = ?2",
+                params![owner, name],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        row.map(|(id, name)| self.load(id, name, owner.starts_with("chat:")))
+            .transpose()
+    }
+
+    /// Find `name` among the requester's lists: the chat's shared list when
+    /// `shared` is set, otherwise their own list, then the chat's.
+    fn find(
+        &self,
+        owners: &(String, Option<String>),
+        name: &str,
+        shared: bool,
+    ) -> Result<Option<List>> {
+        let (personal, chat) = owners;
+        if !shared {
+            if let Some(list) = self.get_owned(personal, name)? {
+                return Ok(Some(list));
+            }
+        }
+        match chat {
+            Some(chat) => self.get_owned(chat, name),
+            None => Ok(None),
+        }
+    }
+
+    fn create(&self, owner: &str, name: &str) -> Result<List> {
+        self.conn
+            .execute(
+                "INSERT OR IGNORE INTO lists (owner, name, created_at) VALUES (?1, ?2, ?3)",
+                params![owner, name, Utc::now().to_rfc3339()],
+            )
+            .context("Failed to create list")?;
+        self.get_owned(owner, name)?
+            .context("List missing after create")
+    }
+
+    fn all(&self, owners: &(String, Option<String>)) -> Result<Vec<List>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id, name, owner FROM lists WHERE owner = ?1 OR owner = ?2 ORDER BY owner DESC, name")?;
+        let rows = stmt
+            .query_map(params![owners.0, owners.1], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter()
+            .map(|(id, name, owner)| self.load(id, name, owner.starts_with("chat:")))
+            .collect()
+    }
+
+    fn add_item(&self, list: &List, text: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO list_items (list_id, text, added_at) VALUES (?1, ?2, ?3)",
+            params![list.id, text, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    fn set_checked(&self, item: &Item, checked: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE list_items SET checked = ?2 WHERE id = ?1",
+            params![item.id, checked],
+        )?;
+        Ok(())
+    }
+
+    fn remove_item(&self, item: &Item) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM list_items WHERE id = ?1", params![item.id])?;
+        Ok(())
+    }
+
+    fn clear_checked(&self, list: &List) -> Result<usize> {
+        Ok(self.conn.execute(
+            "DELETE FROM list_items WHERE list_id = ?1 AND checked = 1",
+            params![list.id],
+        )?)
+    }
+
+    fn delete(&self, list: &List) -> Result<()> {
+        self.conn
+            .execute("DELETE FROM lists WHERE id = ?1", params![list.id])?;
+        Ok(())
+    }
+}
+
+/// Manages named checklists for the requester or their chat.
+pub struct ListsTool {
+    workspace_dir: PathBuf,
+    security: Arc<SecurityPolicy>,
+}
+
+impl ListsTool {
+    pub fn new(workspace_dir: PathBuf, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            workspace_dir,
+            security,
+        }
+    }
+
+    fn run(
+        &self,
+        owners: &(String, Option<String>),
+        action: &str,
+        args: &serde_json::Value,
+    ) -> Result<String> {
+        let store = ListStore::open(&self.workspace_dir)?;
+        let shared = args
+            .get("shared")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if shared && owners.1.is_none() {
+            bail!("Shared lists are only available inside a chat");
+        }
+
+        if action == "lists" {
+            let lists = store.all(owners)?;
+            if lists.is_empty() {
+                return Ok("No lists yet.".into());
+            }
+            return Ok(lists
+                .iter()
+                .map(|list| {
+                    let open = list.items.iter().filter(|item| !item.checked).count();
+                    format!(
+                        "- {}{}: {open} open of {}",
+                        list.name,
+                        if list.shared { " (shared)" } else { "" },
+                        list.items.len()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+
+        let name = args
+            .get("list")
+            .and_then(serde_json::Value::as_str)
+            .map(normalize_name)
+            .filter(|name| !name.is_empty())
+            .context("Missing 'list' name")?;
+        let items: Vec<String> = match args.get("items") {
+            Some(serde_json::Value::Array(values)) => values
+                .iter()
+                .filter_map(serde_json::Value::as_str)
+                .map(normalize_name)
+                .filter(|item| !item.is_empty())
+                .collect(),
+            Some(serde_json::Value::String(item)) => vec![normalize_name(item)],
+            _ => Vec::new(),
+        };
+        let existing = store.find(owners, &name, shared)?;
+
+        if matches!(action, "create" | "add") {
+            let owner = if shared {
+                owners.1.as_deref().unwrap_or(&owners.0)
+            } else {
+                &owners.0
+            };
+            let list = match existing {
+                Some(list) => list,
+                None => store.create(owner, &name)?,
+            };
+            if action == "add" && items.is_empty() {
+                bail!("'add' needs 'items'");
+            }
+            if list.items.len() + items.len() > MAX_ITEMS {
+                bail!("Lists hold at most {MAX_ITEMS} items");
+            }
+            for item in &items {
+                if item.chars().count() > MAX_ITEM_CHARS {
+                    bail!("Items must be at most {MAX_ITEM_CHARS} characters");
+                }
+                store.add_item(&list, item)?;
+            }
+            let list = store.load(list.id, list.name, list.shared)?;
+            return Ok(render(&list));
+        }
+
+        let list = existing.with_context(|| format!("No list named '{name}'"))?;
+        match action {
+            "show" => Ok(render(&list)),
+            "check" | "uncheck" | "remove" => {
+                if items.is_empty() {
+                    bail!("'{action}' needs 'items'");
+                }
+                let targets = items
+                    .iter()
+                    .map(|reference| find_item(&list, reference).cloned())
+                    .collect::<Result<Vec<_>>>()?;
+                for item in &targets {
+                    match action {
+                        "remove" => store.remove_item(item)?,
+                        _ => store.set_checked(item, action == "check")?,
+                    }
+                }
+                Ok(render(&store.load(list.id, list.name, list.shared)?))
+            }
+            "clear_checked" => {
+                let removed = store.clear_checked(&list)?;
+                let list = store.load(list.id, list.name, list.shared)?;
+                Ok(format!(
+                    "Removed {removed} checked item(s).\n{}",
+                    render(&list)
+                ))
+            }
+            "delete" => {
+                store.delete(&list)?;
+                Ok(format!("Deleted list '{}'.", list.name))
+            }
+            "export" => {
+                let dir = self.workspace_dir.join("exports").join("lists");
+                std::fs::create_dir_all(&dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+                let slug: String = list
+                    .name
+                    .to_lowercase()
+                    .chars()
+                    .map(|c| if c.is_alphanumeric() { c } else { '-' })
+                    .collect();
+                let path = dir.join(format!("{}.md", slug.trim_matches('-')));
+                let markdown = to_markdown(&list);
+                std::fs::write(&path, &markdown)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                Ok(format!("Exported to {}\n\n{markdown}", path.display()))
+            }
+            other => bail!("Unknown action '{other}'"),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ListsTool {
+    fn name(&self) -> &str {
+        "lists"
+    }
+
+    fn description(&self) -> &str {
+        "Keep named checklists (shopping, packing, to-do). Actions: add (creates the list if \
+         needed), create, show, check/uncheck, remove, clear_checked, delete, export (markdown), \
+         lists (all lists). Items are matched by number or text. Lists are personal unless \
+         shared=true, which uses the current group chat's list. Show the returned list to the \
+         user as-is."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["add", "create", "show", "check", "uncheck", "remove", "clear_checked", "delete", "export", "lists"]
+                },
+                "list": { "type": "string", "description": "List name, e.g. 'shopping'" },
+                "items": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Items to add, or item numbers/text to check, uncheck, or remove"
+                },
+                "shared": { "type": "boolean", "description": "Use the list shared by the current chat" }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("")
+            .trim();
+        if !matches!(action, "show" | "lists") {
+            if !self.security.can_act() {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Security policy: read-only mode, cannot perform '{action}'"
+                    )),
+                });
+            }
+            if !self.security.record_action() {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("Rate limit exceeded: action budget exhausted".into()),
+                });
+            }
+        }
+
+        let result = self.run(&owners(), action, &args);
+
+        Ok(match result {
+            Ok(output) => ToolResult {
+                success: true,
+                output,
+                error: None,
+            },
+            Err(e) => ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(e.to_string()),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::preferences::{with_identity, PreferenceIdentity};
+    use crate::tools::remind_me::{with_request_route, ReminderRoute};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn personal_lists_add_check_and_export() {
+        let tmp = TempDir::new().unwrap();
+        let tool = ListsTool::new(
+            tmp.path().to_path_buf(),
+            Arc::new(SecurityPolicy::default()),
+        );
+
+        let added = tool
+            .execute(
+                json!({"action": "add", "list": "Shopping", "items": ["milk", "eggs", "oat milk"]}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            added.output,
+            "Shopping (3 items)\n1. ☐ milk\n2. ☐ eggs\n3. ☐ oat milk"
+        );
+
+        // "milk" matches exactly even though "oat milk" also contains it.
+        let checked = tool
+            .execute(json!({"action": "check", "list": "shopping", "items": ["milk", "2"]}))
+            .await
+            .unwrap();
+        assert!(checked
+            .output
+            .starts_with("Shopping (3 items, 2 done)\n1. ☑ milk\n2. ☑ eggs"));
+
+        let ambiguous = tool
+            .execute(json!({"action": "remove", "list": "shopping", "items": ["m"]}))
+            .await
+            .unwrap();
+        assert!(ambiguous.error.unwrap().contains("use the item number"));
+
+        let exported = tool
+            .execute(json!({"action": "export", "list": "shopping"}))
+            .await
+            .unwrap();
+        let markdown =
+            std::fs::read_to_string(tmp.path().join("exports/lists/shopping.md")).unwrap();
+        assert_eq!(
+            markdown,
+            "# Shopping\n\n- [x] milk\n- [x] eggs\n- [ ] oat milk\n"
+        );
+        assert!(exported.output.ends_with(&markdown));
+
+        let cleared = tool
+            .execute(json!({"action": "clear_checked", "list": "shopping"}))
+            .await
+            .unwrap();
+        assert!(cleared.output.starts_with("Removed 2 checked item(s)."));
+    }
+
+    #[tokio::test]
+    async fn shared_lists_belong_to_the_chat() {
+        let tmp = TempDir::new().unwrap();
+        let tool = ListsTool::new(
+            tmp.path().to_path_buf(),
+            Arc::new(SecurityPolicy::default()),
+        );
+        let in_chat = |sender: &str, args: serde_json::Value| {
+            with_identity(
+                PreferenceIdentity {
+                    channel: "telegram".into(),
+                    sender: sender.into(),
+                },
+                with_request_route(
+                    Some(ReminderRoute {
+                        channel: "telegram".into(),
+                        recipient: "-100family".into(),
+                    }),
+                    tool.execute(args),
+                ),
+            )
+        };
+
+        in_chat(
+            "alice",
+            json!({"action": "add", "list": "groceries", "items": ["bread"], "shared": true}),
+        )
+        .await
+        .unwrap();
+        in_chat(
+            "bob",
+            json!({"action": "add", "list": "groceries", "items": ["butter"]}),
+        )
+        .await
+        .unwrap();
+        let shown = in_chat("bob", json!({"action": "show", "list": "groceries"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            shown.output,
+            "groceries (shared) (2 items)\n1. ☐ bread\n2. ☐ butter"
+        );
+
+        let outside = tool
+            .execute(json!({"action": "show", "list": "groceries"}))
+            .await
+            .unwrap();
+        assert!(!outside.success);
+    }
+}
@@ -15,10 +15,17 @@
 //! To add a new tool, implement [`Tool`] in a new submodule and register it in
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
+pub mod artifact_read;
+pub mod bluebubbles_group;
+pub mod broadcast;
 pub mod browser;
 pub mod browser_open;
+pub mod calc;
+pub mod citations;
+pub mod cli;
 pub mod cli_discovery;
 pub mod composio;
+pub mod contacts_lookup;
 pub mod content_search;
 pub mod cron_add;
 pub mod cron_list;
@@ -27,11 +34,16 @@ pub mod cron_run;
 pub mod cron_runs;
 pub mod cron_update;
 pub mod delegate;
+pub mod device_actions;
+pub mod error;
+pub mod expand_result;
 pub mod file_edit;
 pub mod file_read;
 pub mod file_write;
+pub mod finance;
 pub mod git_operations;
 pub mod glob_search;
+pub mod goals;
 #[cfg(feature = "hardware")]
 pub mod hardware_board_info;
 #[cfg(feature = "hardware")]
@@ -39,25 +51,50 @@ pub mod hardware_memory_map;
 #[cfg(feature = "hardware")]
 pub mod hardware_memory_read;
 pub mod http_request;
+pub mod ics_create;
 pub mod image_info;
+pub mod lists;
 pub mod memory_forget;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod model_routing_config;
+pub mod network_diag;
+pub mod news;
 pub mod pdf_read;
+pub mod preferences;
+pub mod process;
 pub mod proxy_config;
 pub mod pushover;
+pub mod remind_me;
+pub mod report_render;
+pub mod reverse_geocode;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
+pub mod send_email;
+pub mod send_later;
 pub mod shell;
+pub mod skill_tool;
+pub mod system_info;
+pub mod timer;
 pub mod traits;
+pub mod translate;
+pub(crate) mod util;
+pub mod weather;
 pub mod web_fetch;
+pub mod web_search_custom;
+pub mod web_search_merge;
 pub mod web_search_tool;
+pub mod wiki_lookup;
+pub mod workspace_search;
 
+pub use bluebubbles_group::BlueBubblesGroupTool;
+pub use broadcast::BroadcastTool;
 pub use browser::{BrowserTool, ComputerUseConfig};
 pub use browser_open::BrowserOpenTool;
+pub use calc::CalcTool;
 pub use composio::ComposioTool;
+pub use contacts_lookup::ContactsLookupTool;
 pub use content_search::ContentSearchTool;
 pub use cron_add::CronAddTool;
 pub use cron_list::CronListTool;
@@ -66,11 +103,14 @@ pub use cron_run::CronRunTool;
 pub use cron_runs::CronRunsTool;
 pub use cron_update::CronUpdateTool;
 pub use delegate::DelegateTool;
+pub use device_actions::DeviceActionsTool;
 pub use file_edit::FileEditTool;
 pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
+pub use finance::FinanceTool;
 pub use git_operations::GitOperationsTool;
 pub use glob_search::GlobSearchTool;
+pub use goals::{GoalUpdateTool, GoalsTool};
 #[cfg(feature = "hardware")]
 pub use hardware_board_info::HardwareBoardInfoTool;
 #[cfg(feature = "hardware")]
@@ -78,24 +118,42 @@ pub use hardware_memory_map::HardwareMemoryMapTool;
 #[cfg(feature = "hardware")]
 pub use hardware_memory_read::HardwareMemoryReadTool;
 pub use http_request::HttpRequestTool;
+pub use ics_create::IcsCreateTool;
 pub use image_info::ImageInfoTool;
+pub use lists::ListsTool;
 pub use memory_forget::MemoryForgetTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use model_routing_config::ModelRoutingConfigTool;
+pub use network_diag::NetworkDiagTool;
+pub use news::NewsTool;
 pub use pdf_read::PdfReadTool;
+pub use preferences::PreferencesTool;
+pub use process::ProcessTool;
 pub use proxy_config::ProxyConfigTool;
 pub use pushover::PushoverTool;
+pub use remind_me::RemindMeTool;
+pub use report_render::ReportRenderTool;
+pub use reverse_geocode::ReverseGeocodeTool;
 pub use schedule::ScheduleTool;
 #[allow(unused_imports)]
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
+pub use send_email::SendEmailTool;
+pub use send_later::SendLaterTool;
 pub use shell::ShellTool;
+pub use skill_tool::SkillToolAdapter;
+pub use system_info::SystemInfoTool;
+pub use timer::{TimerStartTool, TimerStatusTool};
 pub use traits::Tool;
 #[allow(unused_imports)]
 pub use traits::{ToolResult, ToolSpec};
+pub use translate::TranslateTool;
+pub use weather::WeatherTool;
 pub use web_fetch::WebFetchTool;
 pub use web_search_tool::WebSearchTool;
+pub use wiki_lookup::WikiLookupTool;
+pub use workspace_search::WorkspaceSearchTool;
 
 use crate::config::{Config, DelegateAgentConfig};
 use crate::memory::Memory;
@@ -209,8 +267,11 @@ pub fn all_tools_with_runtime(
     fallback_api_key: Option<&str>,
     root_config: &crate::config::Config,
 ) -> Vec<Box<dyn Tool>> {
+    let ssh_hosts = Arc::new(root_config.runtime.ssh.clone());
     let mut tool_arcs: Vec<Arc<dyn Tool>> = vec![
-        Arc::new(ShellTool::new(security.clone(), runtime)),
+        Arc::new(
+            ShellTool::new(security.clone(), runtime.clone()).with_ssh_hosts(ssh_hosts.clone()),
+        ),
         Arc::new(FileReadTool::new(security.clone())),
         Arc::new(FileWriteTool::new(security.clone())),
         Arc::new(FileEditTool::new(security.clone())),
@@ -222,6 +283,21 @@ pub fn all_tools_with_runtime(
         Arc::new(CronUpdateTool::new(config.clone(), security.clone())),
         Arc::new(CronRunTool::new(config.clone(), security.clone())),
         Arc::new(CronRunsTool::new(config.clone())),
+        Arc::new(RemindMeTool::new(config.clone(), security.clone())),
+        Arc::new(TimerStartTool::new(config.clone(), security.clone())),
+        Arc::new(TimerStatusTool::new(config.clone())),
+        Arc::new(PreferencesTool::new(
+            workspace_dir.to_path_buf(),
+            security.clone(),
+        )),
+        Arc::new(ListsTool::new(
+            workspace_dir.to_path_buf(),
+            security.clone(),
+        )),
+        Arc::new(IcsCreateTool::new(
+            workspace_dir.to_path_buf(),
+            security.clone(),
+        )),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
         Arc::new(MemoryForgetTool::new(memory, security.clone())),
@@ -239,6 +315,9 @@ pub fn all_tools_with_runtime(
             security.clone(),
             workspace_dir.to_path_buf(),
         )),
+        Arc::new(ReverseGeocodeTool::new()),
+        Arc::new(CalcTool::new(workspace_dir)),
+        Arc::new(WikiLookupTool::new()),
     ];
 
     if browser_config.enabled {
@@ -268,13 +347,24 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    let artifact_store = root_config.artifacts.enabled.then(|| {
+        Arc::new(crate::artifacts::ArtifactStore::from_config(
+            &root_config.artifacts,
+            workspace_dir,
+        ))
+    });
+
     if http_config.enabled {
-        tool_arcs.push(Arc::new(HttpRequestTool::new(
+        let mut http_tool = HttpRequestTool::new(
             security.clone(),
             http_config.allowed_domains.clone(),
             http_config.max_response_size,
             http_config.timeout_secs,
-        )));
+        );
+        if let Some(store) = &artifact_store {
+            http_tool = http_tool.with_artifacts(store.clone());
+        }
+        tool_arcs.push(Arc::new(http_tool));
     }
 
     if web_fetch_config.enabled {
@@ -287,23 +377,154 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    if root_config.network_diag.enabled {
+        tool_arcs.push(Arc::new(NetworkDiagTool::new(
+            security.clone(),
+            runtime.clone(),
+            root_config.network_diag.clone(),
+        )));
+    }
+
+    if root_config.weather.enabled {
+        tool_arcs.push(Arc::new(WeatherTool::new(
+            &root_config.weather,
+            workspace_dir,
+        )));
+    }
+
+    if root_config.translate.enabled {
+        match translate::Translator::from_config(root_config) {
+            Ok(translator) => tool_arcs.push(Arc::new(TranslateTool::new(
+                translator,
+                root_config.translate.working_language.clone(),
+            ))),
+            Err(e) => tracing::warn!("translate tool unavailable: {e}"),
+        }
+    }
+
+    if root_config.finance.enabled {
+        tool_arcs.push(Arc::new(FinanceTool::new(&root_config.finance)));
+    }
+
+    if root_config.news.enabled {
+        tool_arcs.push(Arc::new(NewsTool::new(&root_config.news)));
+    }
+
+    if root_config.goals.enabled {
+        tool_arcs.push(Arc::new(GoalsTool::new(
+            workspace_dir.to_path_buf(),
+            security.clone(),
+        )));
+        tool_arcs.push(Arc::new(GoalUpdateTool::new(
+            workspace_dir.to_path_buf(),
+            security.clone(),
+        )));
+    }
+
+    if !root_config.device_actions.is_empty() {
+        tool_arcs.push(Arc::new(DeviceActionsTool::new(
+            root_config.device_actions.clone(),
+            security.clone(),
+        )));
+    }
+
+    if root_config.reports.enabled {
+        tool_arcs.push(Arc::new(ReportRenderTool::new(
+            workspace_dir.to_path_buf(),
+            root_config.reports.clone(),
+            security.clone(),
+        )));
+    }
+
+    if root_config.broadcast.enabled {
+        tool_arcs.push(Arc::new(BroadcastTool::new(
+            security.clone(),
+            root_config.clone(),
+        )));
+    }
+
+    if root_config.send_later.enabled {
+        tool_arcs.push(Arc::new(SendLaterTool::new(
+            config.clone(),
+            security.clone(),
+        )));
+    }
+
+    if root_config.send_email.enabled {
+        tool_arcs.push(Arc::new(SendEmailTool::new(
+            root_config.send_email.clone(),
+            security.clone(),
+        )));
+    }
+
+    if root_config.bluebubbles.enabled {
+        tool_arcs.push(Arc::new(BlueBubblesGroupTool::new(
+            root_config.bluebubbles.clone(),
+            security.clone(),
+        )));
+    }
+
+    if root_config.contacts.enabled {
+        tool_arcs.push(Arc::new(ContactsLookupTool::new(
+            workspace_dir.to_path_buf(),
+            root_config.contacts.clone(),
+        )));
+    }
+
+    if root_config.workspace_search.enabled {
+        tool_arcs.push(Arc::new(WorkspaceSearchTool::new(
+            workspace_dir.to_path_buf(),
+            root_config.workspace_search.clone(),
+        )));
+    }
+
     // Web search tool (enabled by default for GLM and other models)
     if root_config.web_search.enabled {
-        tool_arcs.push(Arc::new(WebSearchTool::new(
-            root_config.web_search.provider.clone(),
-            root_config.web_search.brave_api_key.clone(),
-            root_config.web_search.max_results,
-            root_config.web_search.timeout_secs,
-        )));
+        let web_search = &root_config.web_search;
+        let mut tool = WebSearchTool::new(
+            web_search.provider.clone(),
+            web_search.brave_api_key.clone(),
+            web_search.max_results,
+            web_search.timeout_secs,
+        )
+        .with_fallback_providers(web_search.fallback_providers.clone())
+        .with_custom_providers(web_search.custom_providers.clone())
+        .with_domain_filter(
+            web_search.allowed_domains.clone(),
+            web_search.blocked_domains.clone(),
+        )
+        .with_default_recency(
+            web_search
+                .recency
+                .as_deref()
+                .and_then(web_search_tool::Recency::parse),
+        );
+        if web_search.strategy.eq_ignore_ascii_case("merge") {
+            tool = tool.with_merge_strategy(
+                web_search.merge_providers.clone(),
+                web_search_merge::MergeRanking::parse(&web_search.merge_ranking)
+                    .unwrap_or(web_search_merge::MergeRanking::Interleave),
+            );
+        }
+        tool_arcs.push(Arc::new(tool));
     }
 
     // PDF extraction (feature-gated at compile time via rag-pdf)
     tool_arcs.push(Arc::new(PdfReadTool::new(security.clone())));
 
-    // Vision tools are always available
-    tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
+    // Screenshots expose the desktop, so they are opt-in
+    if root_config.screenshot.enabled {
+        tool_arcs.push(Arc::new(screenshot_tool(security, root_config)));
+    }
     tool_arcs.push(Arc::new(ImageInfoTool::new(security.clone())));
 
+    // Host metrics describe the daemon's own machine, so only the native
+    // runtime exposes them
+    if runtime.name() == "native" {
+        tool_arcs.push(Arc::new(SystemInfoTool::new()));
+        tool_arcs.push(Arc::new(ProcessTool::new(security.clone())));
+    }
+
     if let Some(key) = composio_key {
         if !key.is_empty() {
             tool_arcs.push(Arc::new(ComposioTool::new(
@@ -314,8 +535,50 @@ pub fn all_tools_with_runtime(
         }
     }
 
-    // Add delegation tool when agents are configured
-    if !agents.is_empty() {
+    // Skill tools that declare typed `[tools.parameters]` become native tools
+    for skill in crate::skills::load_skills_with_config(workspace_dir, root_config) {
+        for tool in skill
+            .tools
+            .iter()
+            .filter(|tool| !tool.parameters.is_empty())
+        {
+            if tool_arcs
+                .iter()
+                .any(|existing| existing.name() == tool.name)
+            {
+                tracing::warn!(
+                    "skill `{}` tool `{}` clashes with a built-in tool; not registering it",
+                    skill.name,
+                    tool.name
+                );
+                continue;
+            }
+            match SkillToolAdapter::new(
+                &skill.name,
+                tool.clone(),
+                security.clone(),
+                runtime.clone(),
+            ) {
+                Ok(adapter) => {
+                    tool_arcs.push(Arc::new(adapter.with_ssh_hosts(ssh_hosts.clone())));
+                }
+                Err(reason) => tracing::warn!(
+                    "skill `{}` tool `{}` not registered as a native tool: {reason}",
+                    skill.name,
+                    tool.name
+                ),
+            }
+        }
+    }
+
+    // `[tools].disabled` applies before delegation so sub-agents never inherit them
+    let disabled = &root_config.tools.disabled;
+    tool_arcs.retain(|tool| !disabled.iter().any(|name| name == tool.name()));
+
+    // Add delegation tool when local or remote agents are configured
+    if (!agents.is_empty() || !root_config.remote_agents.is_empty())
+        && !disabled.iter().any(|name| name == "delegate")
+    {
         let delegate_agents: HashMap<String, DelegateAgentConfig> = agents
             .iter()
             .map(|(name, cfg)| (name.clone(), cfg.clone()))
@@ -338,16 +601,142 @@ pub fn all_tools_with_runtime(
                     .map(std::path::PathBuf::from),
                 secrets_encrypt: root_config.secrets.encrypt,
                 reasoning_enabled: root_config.runtime.reasoning_enabled,
+                reasoning_effort: root_config.runtime.reasoning_effort.clone(),
+                thinking_budget_tokens: root_config.runtime.thinking_budget_tokens,
+                debug_capture: crate::providers::capture::DebugCapture::from_config(root_config),
             },
         )
         .with_parent_tools(parent_tools)
-        .with_multimodal_config(root_config.multimodal.clone());
+        .with_multimodal_config(root_config.multimodal.clone())
+        .with_remote_agents(root_config.remote_agents.clone());
         tool_arcs.push(Arc::new(delegate_tool));
     }
 
+    let summarizer = result_summarizer(root_config).map(Arc::new);
+    if let Some(store) = artifact_store {
+        let artifacts =
+            artifact_read::ToolOutputArtifacts::new(store, summarizer, &root_config.artifacts);
+        tool_arcs = artifact_read::wrap_registry(tool_arcs, Arc::new(artifacts));
+    } else if let Some(summarizer) = summarizer {
+        tool_arcs = expand_result::wrap_registry(tool_arcs, summarizer);
+    }
+
     boxed_registry_from_arcs(tool_arcs)
 }
 
+/// Drop tools listed in `[tools].disabled`, for tools registered outside
+/// [`all_tools_with_runtime`] (e.g. peripherals).
+pub fn remove_disabled(tools: &mut Vec<Box<dyn Tool>>, config: &crate::config::ToolsConfig) {
+    tools.retain(|tool| !config.disabled.iter().any(|name| name == tool.name()));
+}
+
+/// Names of the tools in `tools` that `[tools.channels.<channel>]` hides.
+pub fn channel_exclusions(
+    config: &crate::config::ToolsConfig,
+    channel: &str,
+    tools: &[Box<dyn Tool>],
+) -> Vec<String> {
+    let Some(rules) = config.channels.get(channel) else {
+        return Vec::new();
+    };
+    tools
+        .iter()
+        .map(|tool| tool.name())
+        .filter(|name| {
+            rules
+                .allow
+                .as_ref()
+                .is_some_and(|allow| !allow.iter().any(|allowed| allowed == name))
+                || rules.deny.iter().any(|denied| denied == name)
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Summarizer for oversized tool results from `[tool_result_summary]`.
+fn result_summarizer(
+    root_config: &crate::config::Config,
+) -> Option<expand_result::ResultSummarizer> {
+    let config = &root_config.tool_result_summary;
+    if !config.enabled {
+        return None;
+    }
+    let provider_name = config
+        .provider
+        .as_deref()
+        .or(root_config.default_provider.as_deref())
+        .unwrap_or("openrouter");
+    let model = config
+        .model
+        .clone()
+        .or_else(|| root_config.default_model.clone())
+        .unwrap_or_default();
+    let options = crate::providers::ProviderRuntimeOptions {
+        provider_api_url: root_config.api_url.clone(),
+        zeroclaw_dir: root_config
+            .config_path
+            .parent()
+            .map(std::path::PathBuf::from),
+        secrets_encrypt: root_config.secrets.encrypt,
+        ..crate::providers::ProviderRuntimeOptions::default()
+    };
+    match crate::providers::create_provider_with_options(
+        provider_name,
+        root_config.api_key.as_deref(),
+        &options,
+    ) {
+        Ok(provider) => Some(expand_result::ResultSummarizer::new(
+            provider, model, config,
+        )),
+        Err(e) => {
+            tracing::warn!("tool result summarization unavailable: {e}");
+            None
+        }
+    }
+}
+
+/// Screenshot tool from `[screenshot]`, with a vision model when `ocr = "vision"`.
+fn screenshot_tool(
+    security: &Arc<SecurityPolicy>,
+    root_config: &crate::config::Config,
+) -> ScreenshotTool {
+    let config = &root_config.screenshot;
+    let tool = ScreenshotTool::new(security.clone()).with_config(config.clone());
+    if config.ocr != "vision" {
+        return tool;
+    }
+    let provider_name = config
+        .vision_provider
+        .as_deref()
+        .or(root_config.default_provider.as_deref())
+        .unwrap_or("openrouter");
+    let model = config
+        .vision_model
+        .clone()
+        .or_else(|| root_config.default_model.clone())
+        .unwrap_or_default();
+    let options = crate::providers::ProviderRuntimeOptions {
+        provider_api_url: root_config.api_url.clone(),
+        zeroclaw_dir: root_config
+            .config_path
+            .parent()
+            .map(std::path::PathBuf::from),
+        secrets_encrypt: root_config.secrets.encrypt,
+        ..crate::providers::ProviderRuntimeOptions::default()
+    };
+    match crate::providers::create_provider_with_options(
+        provider_name,
+        root_config.api_key.as_deref(),
+        &options,
+    ) {
+        Ok(provider) => tool.with_vision_ocr(provider, model, root_config.multimodal.clone()),
+        Err(e) => {
+            tracing::warn!("screenshot vision OCR unavailable: {e}");
+            tool
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -550,6 +939,64 @@ mod tests {
         assert_eq!(parsed.description, "A test tool");
     }
 
+    #[test]
+    fn all_tools_skips_tools_disabled_in_config() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let mut cfg = test_config(&tmp);
+        cfg.tools.disabled = vec!["shell".into(), "file_write".into()];
+
+        let tools = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert!(!names.contains(&"shell"));
+        assert!(!names.contains(&"file_write"));
+        assert!(names.contains(&"file_read"));
+    }
+
+    #[test]
+    fn channel_exclusions_apply_allow_then_deny() {
+        let tools = default_tools(Arc::new(SecurityPolicy::default()));
+        let mut config = crate::config::ToolsConfig::default();
+        assert!(channel_exclusions(&config, "discord", &tools).is_empty());
+
+        config.channels.insert(
+            "discord".into(),
+            crate::config::ChannelToolsConfig {
+                allow: Some(vec!["file_read".into(), "shell".into()]),
+                deny: vec!["shell".into()],
+            },
+        );
+        let hidden = channel_exclusions(&config, "discord", &tools);
+        assert_eq!(hidden.len(), tools.len() - 1);
+        assert!(!hidden.contains(&"file_read".to_string()));
+        assert!(hidden.contains(&"shell".to_string()));
+        assert!(channel_exclusions(&config, "imessage", &tools).is_empty());
+
+        let mut tools = tools;
+        config.disabled = vec!["file_read".into()];
+        remove_disabled(&mut tools, &config);
+        assert!(tools.iter().all(|tool| tool.name() != "file_read"));
+    }
+
     #[test]
     fn all_tools_includes_delegate_when_agents_configured() {
         let tmp = TempDir::new().unwrap();
@@ -578,6 +1025,8 @@ mod tests {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: 10,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             },
         );
 
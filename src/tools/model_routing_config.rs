@@ -638,6 +638,8 @@ impl ModelRoutingConfigTool {
                 agentic: false,
                 allowed_tools: Vec::new(),
                 max_iterations: DEFAULT_AGENT_MAX_ITERATIONS,
+                reasoning_effort: None,
+                thinking_budget_tokens: None,
             });
 
         next_agent.provider = provider;
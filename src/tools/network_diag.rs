@@ -0,0 +1,482 @@
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use super::web_fetch::{
+    host_matches_allowlist, is_private_or_local_host, normalize_allowed_domains,
+    validate_resolved_ips_are_public,
+};
+use crate::config::NetworkDiagConfig;
+use crate::runtime::RuntimeAdapter;
+use crate::security::SecurityPolicy;
+use crate::util::shell_quote;
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Maximum number of echo requests per ping.
+const MAX_PING_COUNT: u64 = 5;
+/// Seconds each traceroute hop may wait for a reply.
+const TRACEROUTE_WAIT_SECS: u64 = 2;
+/// Maximum characters of probe output returned to the agent.
+const MAX_OUTPUT_CHARS: usize = 16_384;
+
+/// Network troubleshooting: ping, DNS lookup, HTTP HEAD timing, traceroute.
+///
+/// Targets are validated as plain hostnames or IP literals before anything
+/// runs, filtered through `[network_diag]` allow/deny lists and
+/// `[autonomy.rules.network]`, and must resolve to public addresses unless
+/// `allow_private` is set. `ping` and `traceroute` run through the runtime
+/// adapter with a cleared environment; DNS and HTTP checks run in-process.
+pub struct NetworkDiagTool {
+    security: Arc<SecurityPolicy>,
+    runtime: Arc<dyn RuntimeAdapter>,
+    config: NetworkDiagConfig,
+}
+
+impl NetworkDiagTool {
+    pub fn new(
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+        mut config: NetworkDiagConfig,
+    ) -> Self {
+        config.allowed_targets = normalize_allowed_domains(config.allowed_targets);
+        config.blocked_targets = normalize_allowed_domains(config.blocked_targets);
+        Self {
+            security,
+            runtime,
+            config,
+        }
+    }
+
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs.max(1))
+    }
+
+    /// Apply the allow/deny lists and private-address policy to `host`.
+    fn check_target(&self, host: &str) -> Result<(), String> {
+        if host_matches_allowlist(host, &self.config.blocked_targets) {
+            return Err(format!("Host '{host}' is in network_diag.blocked_targets"));
+        }
+        if !host_matches_allowlist(host, &self.config.allowed_targets) {
+            return Err(format!(
+                "Host '{host}' is not in network_diag.allowed_targets"
+            ));
+        }
+        if !self.config.allow_private && is_private_or_local_host(host) {
+            return Err(format!(
+                "Blocked local/private host: {host} (set network_diag.allow_private to probe LAN hosts)"
+            ));
+        }
+        self.security.check_network_target(host)
+    }
+
+    /// Resolve `host`, refusing non-public answers unless `allow_private`.
+    async fn resolve(&self, host: &str) -> Result<Vec<IpAddr>, String> {
+        let lookup = tokio::time::timeout(self.timeout(), tokio::net::lookup_host((host, 0)))
+            .await
+            .map_err(|_| format!("DNS lookup for '{host}' timed out"))?
+            .map_err(|e| format!("DNS lookup for '{host}' failed: {e}"))?;
+        let mut ips: Vec<IpAddr> = lookup.map(|addr| addr.ip()).collect();
+        ips.sort_unstable();
+        ips.dedup();
+        if !self.config.allow_private {
+            validate_resolved_ips_are_public(host, &ips).map_err(|e| e.to_string())?;
+        }
+        Ok(ips)
+    }
+
+    async fn dns(&self, host: &str) -> ToolResult {
+        let started = Instant::now();
+        match self.resolve(host).await {
+            Ok(ips) => {
+                let mut output =
+                    format!("{host} resolved in {} ms:\n", started.elapsed().as_millis());
+                for ip in ips {
+                    let _ = writeln!(output, "  {ip}");
+                }
+                success(output)
+            }
+            Err(error) => failure(error),
+        }
+    }
+
+    async fn http_head(&self, url: &reqwest::Url) -> ToolResult {
+        let client = match crate::config::apply_runtime_proxy_to_builder(
+            reqwest::Client::builder()
+                .timeout(self.timeout())
+                .redirect(reqwest::redirect::Policy::none()),
+            "tool.network_diag",
+        )
+        .build()
+        {
+            Ok(client) => client,
+            Err(e) => return failure(format!("Failed to build HTTP client: {e}")),
+        };
+
+        let started = Instant::now();
+        match client.head(url.clone()).send().await {
+            Ok(response) => {
+                let mut output = format!(
+                    "HEAD {url} -> {} in {} ms",
+                    response.status(),
+                    started.elapsed().as_millis()
+                );
+                for header in ["location", "server"] {
+                    if let Some(value) =
+                        response.headers().get(header).and_then(|v| v.to_str().ok())
+                    {
+                        let _ = write!(output, "\n{header}: {value}");
+                    }
+                }
+                success(output)
+            }
+            Err(e) => failure(format!(
+                "HEAD {url} failed after {} ms: {e}",
+                started.elapsed().as_millis()
+            )),
+        }
+    }
+
+    /// Run a probe script through the runtime adapter.
+    async fn run_probe(&self, script: &str, timeout: Duration) -> ToolResult {
+        let mut cmd = match self
+            .runtime
+            .build_shell_command(script, &self.security.workspace_dir)
+        {
+            Ok(cmd) => cmd,
+            Err(e) => return failure(format!("Failed to build runtime command: {e}")),
+        };
+        cmd.env_clear();
+        for var in ["PATH", "HOME", "LANG"] {
+            if let Ok(value) = std::env::var(var) {
+                cmd.env(var, value);
+            }
+        }
+        cmd.kill_on_drop(true);
+
+        match tokio::time::timeout(timeout, cmd.output()).await {
+            Ok(Ok(output)) => {
+                let stdout = crate::util::truncate_with_ellipsis(
+                    &String::from_utf8_lossy(&output.stdout),
+                    MAX_OUTPUT_CHARS,
+                );
+                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+                if stderr.contains("NO_TRACEROUTE") {
+                    return failure("Neither traceroute nor tracepath is installed");
+                }
+                ToolResult {
+                    success: output.status.success(),
+                    output: stdout,
+                    error: (!stderr.is_empty()).then_some(stderr),
+                }
+            }
+            Ok(Err(e)) => failure(format!("Failed to run probe: {e}")),
+            Err(_) => failure(format!(
+                "Probe timed out after {}s and was killed",
+                timeout.as_secs()
+            )),
+        }
+    }
+}
+
+/// Accept only hostnames and IP literals, so targets can never be read as
+/// command-line options or shell syntax.
+fn parse_host(raw: &str) -> Result<String, String> {
+    let host = raw.trim().trim_end_matches('.').to_ascii_lowercase();
+    let bare = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(&host);
+    if bare.parse::<IpAddr>().is_ok() {
+        return Ok(bare.to_string());
+    }
+    let valid = !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+    if valid {
+        Ok(host)
+    } else {
+        Err(format!(
+            "Invalid target '{}': expected a hostname or IP address",
+            raw.trim()
+        ))
+    }
+}
+
+/// Parse an `http` target: a full `http(s)` URL or a bare host (HTTPS).
+fn parse_http_target(raw: &str) -> Result<(reqwest::Url, String), String> {
+    let raw = raw.trim();
+    let with_scheme = if raw.contains("://") {
+        raw.to_string()
+    } else {
+        format!("https://{raw}")
+    };
+    let url = reqwest::Url::parse(&with_scheme).map_err(|e| format!("Invalid URL '{raw}': {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Only http:// and https:// URLs are allowed".into());
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err("URL userinfo is not allowed".into());
+    }
+    let host = parse_host(url.host_str().unwrap_or_default())?;
+    Ok((url, host))
+}
+
+fn ping_script(host: &str, count: u64, timeout_secs: u64) -> String {
+    let program = if host.contains(':') { "ping6" } else { "ping" };
+    // Linux `-w` and macOS `-t` both bound the whole run in seconds.
+    let deadline = if cfg!(target_os = "macos") {
+        "-t"
+    } else {
+        "-w"
+    };
+    format!(
+        "{program} -c {count} {deadline} {timeout_secs} {}",
+        shell_quote(host)
+    )
+}
+
+fn traceroute_script(host: &str, max_hops: u8) -> String {
+    let host = shell_quote(host);
+    format!(
+        "if command -v traceroute >/dev/null 2>&1; then \
+             traceroute -n -q 1 -w {TRACEROUTE_WAIT_SECS} -m {max_hops} {host}; \
+         elif command -v tracepath >/dev/null 2>&1; then \
+             tracepath -n -m {max_hops} {host}; \
+         else \
+             echo NO_TRACEROUTE >&2; exit 127; \
+         fi"
+    )
+}
+
+fn success(output: String) -> ToolResult {
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
+#[async_trait]
+impl Tool for NetworkDiagTool {
+    fn name(&self) -> &str {
+        "network_diag"
+    }
+
+    fn description(&self) -> &str {
+        "Troubleshoot connectivity: ping a host, look up its DNS records, time an HTTP HEAD request, or traceroute to it. Compare a failing site with a well-known one to tell a local outage from a site problem."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["ping", "dns", "http", "traceroute"],
+                    "description": "Which check to run"
+                },
+                "target": {
+                    "type": "string",
+                    "description": "Hostname or IP address; for http, a URL or hostname (HTTPS assumed)"
+                },
+                "count": {
+                    "type": "integer",
+                    "description": "ping: number of echo requests (1-5)",
+                    "default": 3
+                }
+            },
+            "required": ["action", "target"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let action = args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'action' parameter"))?;
+        let target = args
+            .get("target")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'target' parameter"))?;
+
+        if !self.security.can_act() {
+            return Ok(failure("Action blocked: autonomy is read-only"));
+        }
+
+        let parsed = match action {
+            "http" => parse_http_target(target).map(|(url, host)| (Some(url), host)),
+            "ping" | "dns" | "traceroute" => parse_host(target).map(|host| (None, host)),
+            other => Err(format!(
+                "Unknown action '{other}' (use ping, dns, http, or traceroute)"
+            )),
+        };
+        let (url, host) = match parsed {
+            Ok(parsed) => parsed,
+            Err(error) => return Ok(failure(error)),
+        };
+        if let Err(error) = self.check_target(&host) {
+            return Ok(failure(error));
+        }
+
+        if !self.security.record_action() {
+            return Ok(failure("Action blocked: rate limit exceeded"));
+        }
+
+        if action == "dns" {
+            return Ok(self.dns(&host).await);
+        }
+        if let Err(error) = self.resolve(&host).await {
+            return Ok(failure(error));
+        }
+
+        let result = match (action, url) {
+            ("http", Some(url)) => self.http_head(&url).await,
+            ("ping", _) => {
+                let count = args
+                    .get("count")
+                    .and_then(serde_json::Value::as_u64)
+                    .unwrap_or(3)
+                    .clamp(1, MAX_PING_COUNT);
+                let timeout_secs = self.config.timeout_secs.max(1);
+                self.run_probe(
+                    &ping_script(&host, count, timeout_secs),
+                    Duration::from_secs(timeout_secs + 5),
+                )
+                .await
+            }
+            _ => {
+                let hops = self.config.max_hops;
+                self.run_probe(
+                    &traceroute_script(&host, hops),
+                    Duration::from_secs(u64::from(hops) * (TRACEROUTE_WAIT_SECS + 1) + 10),
+                )
+                .await
+            }
+        };
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+    use crate::security::AutonomyLevel;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn tool(config: NetworkDiagConfig) -> NetworkDiagTool {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        });
+        NetworkDiagTool::new(security, Arc::new(NativeRuntime::new()), config)
+    }
+
+    #[test]
+    fn targets_must_be_plain_hosts() {
+        assert_eq!(parse_host("Example.COM.").unwrap(), "example.com");
+        assert_eq!(parse_host("1.1.1.1").unwrap(), "1.1.1.1");
+        assert_eq!(parse_host("[2606:4700::1111]").unwrap(), "2606:4700::1111");
+        for bad in [
+            "-oProxyCommand=sh",
+            "a;reboot",
+            "$(id).example.com",
+            "a b",
+            "",
+        ] {
+            assert!(parse_host(bad).is_err(), "{bad} should be rejected");
+        }
+        assert!(parse_http_target("ftp://example.com").is_err());
+        assert!(parse_http_target("https://user:pw@example.com").is_err());
+        let (url, host) = parse_http_target("example.com/status").unwrap();
+        assert_eq!(url.as_str(), "https://example.com/status");
+        assert_eq!(host, "example.com");
+
+        assert_eq!(
+            ping_script("example.com", 3, 10).split(' ').next(),
+            Some("ping")
+        );
+        assert!(ping_script("example.com", 3, 10).ends_with(" 10 'example.com'"));
+        assert!(traceroute_script("example.com", 8).contains("-m 8 'example.com'"));
+    }
+
+    #[test]
+    fn allow_and_deny_lists_and_private_hosts() {
+        let diag = tool(NetworkDiagConfig {
+            enabled: true,
+            allowed_targets: vec!["example.com".into(), "192.168.1.1".into()],
+            blocked_targets: vec!["ads.example.com".into()],
+            ..NetworkDiagConfig::default()
+        });
+        assert!(diag.check_target("www.example.com").is_ok());
+        assert!(diag
+            .check_target("ads.example.com")
+            .unwrap_err()
+            .contains("blocked_targets"));
+        assert!(diag
+            .check_target("example.org")
+            .unwrap_err()
+            .contains("allowed_targets"));
+        assert!(diag
+            .check_target("192.168.1.1")
+            .unwrap_err()
+            .contains("allow_private"));
+
+        let lan = tool(NetworkDiagConfig {
+            enabled: true,
+            allow_private: true,
+            ..NetworkDiagConfig::default()
+        });
+        assert!(lan.check_target("192.168.1.1").is_ok());
+    }
+
+    #[tokio::test]
+    async fn dns_and_http_checks_against_loopback() {
+        let result = tool(NetworkDiagConfig::default())
+            .execute(json!({"action": "dns", "target": "localhost"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Blocked local/private host"));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let _ = socket
+                .write_all(b"HTTP/1.1 204 No Content\r\nServer: test\r\nContent-Length: 0\r\n\r\n")
+                .await;
+        });
+
+        let lan = tool(NetworkDiagConfig {
+            enabled: true,
+            allow_private: true,
+            ..NetworkDiagConfig::default()
+        });
+        let result = lan
+            .execute(json!({"action": "dns", "target": "127.0.0.1"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("  127.0.0.1"));
+
+        let result = lan
+            .execute(json!({"action": "http", "target": format!("http://127.0.0.1:{port}/")}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("-> 204 No Content in"));
+        assert!(result.output.contains("server: test"));
+    }
+}
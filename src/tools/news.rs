@@ -0,0 +1,670 @@
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::NewsConfig;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+const GOOGLE_NEWS_SEARCH_URL: &str = "https://news.google.com/rss/search";
+const NEWSAPI_URL: &str = "https://newsapi.org/v2";
+const MAX_QUERY_CHARS: usize = 200;
+const MAX_LIMIT: usize = 30;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Headline {
+    title: String,
+    url: String,
+    source: String,
+    published: Option<DateTime<Utc>>,
+}
+
+/// Replace XML/HTML character references in `raw`.
+fn decode_entities(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(start) = rest.find('&') {
+        text.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let decoded = tail.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &tail[1..end];
+            let ch = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((ch, end))
+        });
+        match decoded {
+            Some((ch, end)) => {
+                text.push(ch);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                text.push('&');
+                rest = &tail[1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Decode the XML entities and CDATA wrappers found in feed text fields,
+/// dropping any inline markup. Entities are decoded twice because HTML-typed
+/// titles are escaped once more inside the XML (`&amp;amp;`).
+fn decode_text(raw: &str) -> String {
+    let raw = raw.trim();
+    let raw = raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|inner| inner.strip_suffix("]]>"))
+        .unwrap_or(raw);
+    let text = decode_entities(&decode_entities(raw));
+    // Titles occasionally carry escaped markup (`&lt;b&gt;`).
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(ch),
+            _ => {}
+        }
+    }
+    plain.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Byte range of the first `<tag ...>` opening tag in `xml`, skipping tags
+/// that merely share the prefix (`<link>` vs `<linkedin>`).
+fn find_open_tag(xml: &str, tag: &str) -> Option<(usize, usize)> {
+    let needle = format!("<{tag}");
+    let mut offset = 0;
+    while let Some(found) = xml[offset..].find(&needle) {
+        let start = offset + found;
+        let after = start + needle.len();
+        if xml[after..].starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            let end = after + xml[after..].find('>')? + 1;
+            return Some((start, end));
+        }
+        offset = after;
+    }
+    None
+}
+
+/// Raw inner text of the first `<tag>` element.
+fn element<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let (_, open_end) = find_open_tag(xml, tag)?;
+    if xml[..open_end].ends_with("/>") {
+        return None;
+    }
+    let close = xml[open_end..].find(&format!("</{tag}>"))?;
+    Some(&xml[open_end..open_end + close])
+}
+
+fn attribute<'a>(open_tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!("{name}=");
+    let mut offset = 0;
+    while let Some(found) = open_tag[offset..].find(&needle) {
+        let start = offset + found;
+        let preceded_by_space = open_tag[..start].ends_with(char::is_whitespace);
+        let value = &open_tag[start + needle.len()..];
+        if preceded_by_space {
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let value = &value[1..];
+            return value.find(quote).map(|end| &value[..end]);
+        }
+        offset = start + needle.len();
+    }
+    None
+}
+
+/// Pieces of `xml` between successive `<tag>` and `</tag>` pairs.
+fn blocks<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let close = format!("</{tag}>");
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some((start, open_end)) = find_open_tag(rest, tag) {
+        let Some(end) = rest[open_end..].find(&close) else {
+            break;
+        };
+        found.push(&rest[start..open_end + end]);
+        rest = &rest[open_end + end + close.len()..];
+    }
+    found
+}
+
+/// Atom entries link through `<link href=...>`; prefer the alternate link.
+fn atom_link(entry: &str) -> Option<String> {
+    let mut rest = entry;
+    let mut fallback = None;
+    while let Some((start, end)) = find_open_tag(rest, "link") {
+        let open_tag = &rest[start..end];
+        if let Some(href) = attribute(open_tag, "href") {
+            match attribute(open_tag, "rel") {
+                None | Some("alternate") => return Some(decode_text(href)),
+                Some(_) => {
+                    fallback.get_or_insert_with(|| decode_text(href));
+                }
+            }
+        }
+        rest = &rest[end..];
+    }
+    fallback
+}
+
+fn parse_date(raw: &str) -> Option<DateTime<Utc>> {
+    let raw = decode_text(raw);
+    DateTime::parse_from_rfc2822(&raw)
+        .or_else(|_| DateTime::parse_from_rfc3339(&raw))
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Parse an RSS 2.0 or Atom feed. Stories without a per-item `<source>`
+/// are attributed to the feed's own title.
+fn parse_feed(xml: &str) -> Vec<Headline> {
+    let is_atom = find_open_tag(xml, "feed").is_some() && find_open_tag(xml, "rss").is_none();
+    let item_tag = if is_atom { "entry" } else { "item" };
+    let header_end = find_open_tag(xml, item_tag).map_or(xml.len(), |(start, _)| start);
+    let feed_title = element(&xml[..header_end], "title")
+        .map(decode_text)
+        .unwrap_or_default();
+
+    blocks(xml, item_tag)
+        .into_iter()
+        .filter_map(|item| {
+            let title = decode_text(element(item, "title")?);
+            let url = if is_atom {
+                atom_link(item)?
+            } else {
+                element(item, "link")
+                    .map(decode_text)
+                    .filter(|link| !link.is_empty())
+                    .or_else(|| element(item, "guid").map(decode_text))?
+            };
+            let source = element(item, "source")
+                .map(decode_text)
+                .filter(|source| !source.is_empty())
+                .unwrap_or_else(|| feed_title.clone());
+            let published = ["pubDate", "published", "updated", "dc:date"]
+                .iter()
+                .find_map(|tag| element(item, tag).and_then(parse_date));
+            (!title.is_empty() && url.starts_with("http")).then_some(Headline {
+                title,
+                url,
+                source,
+                published,
+            })
+        })
+        .collect()
+}
+
+fn parse_newsapi(body: &Value) -> anyhow::Result<Vec<Headline>> {
+    if body["status"].as_str() == Some("error") {
+        anyhow::bail!(
+            "NewsAPI: {}",
+            body["message"].as_str().unwrap_or("request failed")
+        );
+    }
+    Ok(body["articles"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|article| {
+            let title = article["title"].as_str()?.trim();
+            // Deleted stories stay listed as "[Removed]".
+            if title.is_empty() || title == "[Removed]" {
+                return None;
+            }
+            Some(Headline {
+                title: title.to_string(),
+                url: article["url"].as_str()?.to_string(),
+                source: article["source"]["name"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                published: article["publishedAt"].as_str().and_then(parse_date),
+            })
+        })
+        .collect())
+}
+
+fn host(url: &str) -> Option<String> {
+    let host = reqwest::Url::parse(url)
+        .ok()?
+        .host_str()?
+        .to_ascii_lowercase();
+    Some(host.strip_prefix("www.").unwrap_or(&host).to_string())
+}
+
+/// A filter matches a story when it appears in the source name or is the
+/// story's domain (or a parent of it).
+fn matches_source(headline: &Headline, filter: &str) -> bool {
+    let filter = filter.trim().to_ascii_lowercase();
+    if filter.is_empty() {
+        return false;
+    }
+    if headline.source.to_ascii_lowercase().contains(&filter) {
+        return true;
+    }
+    host(&headline.url).is_some_and(|host| {
+        host == filter
+            || host
+                .strip_suffix(&filter)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+/// Title used for duplicate detection: aggregator suffixes like
+/// " - Reuters" are dropped and case and punctuation ignored.
+fn title_key(headline: &Headline) -> String {
+    let title = headline
+        .title
+        .strip_suffix(headline.source.as_str())
+        .and_then(|title| title.trim_end().strip_suffix(['-', '|', '—']))
+        .unwrap_or(&headline.title);
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Newest first, one entry per story, filtered and capped at `limit`.
+fn select(
+    mut headlines: Vec<Headline>,
+    include: &[String],
+    blocked: &[String],
+    limit: usize,
+) -> Vec<Headline> {
+    headlines.sort_by_key(|headline| std::cmp::Reverse(headline.published));
+    let mut seen_titles = HashSet::new();
+    let mut seen_urls = HashSet::new();
+    headlines
+        .into_iter()
+        .filter(|headline| {
+            !blocked
+                .iter()
+                .any(|filter| matches_source(headline, filter))
+        })
+        .filter(|headline| {
+            include.is_empty()
+                || include
+                    .iter()
+                    .any(|filter| matches_source(headline, filter))
+        })
+        .filter(|headline| {
+            let url = headline.url.split(['?', '#']).next().unwrap_or_default();
+            seen_urls.insert(url.to_string()) && seen_titles.insert(title_key(headline))
+        })
+        .take(limit)
+        .collect()
+}
+
+fn age(published: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (now - published).num_minutes();
+    match minutes {
+        ..=0 => "just now".into(),
+        1..=59 => format!("{minutes}m ago"),
+        60..=1439 => format!("{}h ago", minutes / 60),
+        _ => format!("{}d ago", minutes / 1440),
+    }
+}
+
+fn format_headlines(heading: &str, headlines: &[Headline], now: DateTime<Utc>) -> String {
+    let mut output = format!("{heading} ({}):", headlines.len());
+    for (index, headline) in headlines.iter().enumerate() {
+        let _ = write!(output, "\n{}. {}", index + 1, headline.title);
+        let mut meta = Vec::new();
+        if !headline.source.is_empty() {
+            meta.push(headline.source.clone());
+        }
+        if let Some(published) = headline.published {
+            meta.push(age(published, now));
+        }
+        if !meta.is_empty() {
+            let _ = write!(output, " ({})", meta.join(", "));
+        }
+        let _ = write!(output, "\n   {}", headline.url);
+    }
+    output
+}
+
+/// Top headlines and topic searches from RSS feeds or NewsAPI.
+pub struct NewsTool {
+    config: NewsConfig,
+    cache: Mutex<HashMap<String, (Instant, Vec<Headline>)>>,
+}
+
+impl NewsTool {
+    pub fn new(config: &NewsConfig) -> Self {
+        Self {
+            config: config.clone(),
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.news",
+            self.config.timeout_secs,
+            10,
+        )
+    }
+
+    async fn fetch_feed(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+    ) -> anyhow::Result<Vec<Headline>> {
+        let ttl = Duration::from_secs(self.config.cache_ttl_secs);
+        if let Some((_, headlines)) = self
+            .cache
+            .lock()
+            .get(url)
+            .filter(|(at, _)| at.elapsed() < ttl)
+        {
+            return Ok(headlines.clone());
+        }
+        let xml = client
+            .get(url)
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("zeroclaw/", env!("CARGO_PKG_VERSION")),
+            )
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        let headlines = parse_feed(&xml);
+        if headlines.is_empty() {
+            anyhow::bail!("no stories found (not an RSS/Atom feed?)");
+        }
+        let mut cache = self.cache.lock();
+        cache.retain(|_, (at, _)| at.elapsed() < ttl);
+        cache.insert(url.to_string(), (Instant::now(), headlines.clone()));
+        Ok(headlines)
+    }
+
+    async fn fetch_newsapi(
+        &self,
+        client: &reqwest::Client,
+        query: Option<&str>,
+    ) -> anyhow::Result<Vec<Headline>> {
+        let (endpoint, params) = match query {
+            Some(query) => (
+                "everything",
+                vec![
+                    ("q", query),
+                    ("sortBy", "publishedAt"),
+                    ("language", "en"),
+                    ("pageSize", "50"),
+                ],
+            ),
+            None => ("top-headlines", vec![("country", "us"), ("pageSize", "50")]),
+        };
+        let key = format!("newsapi:{endpoint}:{}", query.unwrap_or_default());
+        let ttl = Duration::from_secs(self.config.cache_ttl_secs);
+        if let Some((_, headlines)) = self
+            .cache
+            .lock()
+            .get(&key)
+            .filter(|(at, _)| at.elapsed() < ttl)
+        {
+            return Ok(headlines.clone());
+        }
+        let body: Value = client
+            .get(format!("{NEWSAPI_URL}/{endpoint}"))
+            .header(
+                "X-Api-Key",
+                self.config.api_key.as_deref().unwrap_or_default(),
+            )
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("zeroclaw/", env!("CARGO_PKG_VERSION")),
+            )
+            .query(&params)
+            .send()
+            .await?
+            .json()
+            .await?;
+        let headlines = parse_newsapi(&body)?;
+        self.cache
+            .lock()
+            .insert(key, (Instant::now(), headlines.clone()));
+        Ok(headlines)
+    }
+
+    /// Gather candidate stories, plus a note for each source that failed.
+    async fn gather(&self, query: Option<&str>) -> (Vec<Headline>, Vec<String>) {
+        let client = self.client();
+        if self.config.provider.trim() == "newsapi" {
+            return match self.fetch_newsapi(&client, query).await {
+                Ok(headlines) => (headlines, Vec::new()),
+                Err(e) => (Vec::new(), vec![e.to_string()]),
+            };
+        }
+        let urls = match query {
+            Some(query) => vec![format!(
+                "{GOOGLE_NEWS_SEARCH_URL}?q={}&hl=en-US&gl=US&ceid=US:en",
+                urlencoding::encode(query)
+            )],
+            None => self.config.feeds.clone(),
+        };
+        let results = join_all(urls.iter().map(|url| self.fetch_feed(&client, url))).await;
+        let mut headlines = Vec::new();
+        let mut errors = Vec::new();
+        for (url, result) in urls.iter().zip(results) {
+            match result {
+                Ok(found) => headlines.extend(found),
+                Err(e) => errors.push(format!("{}: {e}", host(url).unwrap_or_else(|| url.clone()))),
+            }
+        }
+        (headlines, errors)
+    }
+}
+
+#[async_trait]
+impl Tool for NewsTool {
+    fn name(&self) -> &str {
+        "news"
+    }
+
+    fn description(&self) -> &str {
+        "Get current news headlines with links: top stories, or stories on a topic (query). Optionally restrict to sources by name or domain (e.g. [\"bbc\", \"reuters.com\"]). Use for news and current events instead of web search."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Topic to search for; omit for top headlines"
+                },
+                "sources": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only include stories from these sources (name or domain)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_LIMIT,
+                    "description": "Number of headlines (default from config)"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|query| !query.is_empty());
+        if query.is_some_and(|query| query.chars().count() > MAX_QUERY_CHARS) {
+            return Ok(failure(format!(
+                "Query is too long (max {MAX_QUERY_CHARS} characters)"
+            )));
+        }
+        let include: Vec<String> = args
+            .get("sources")
+            .and_then(Value::as_array)
+            .map(|sources| {
+                sources
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|source| source.trim().to_string())
+                    .filter(|source| !source.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let limit = args
+            .get("limit")
+            .and_then(Value::as_u64)
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(self.config.max_items)
+            .clamp(1, MAX_LIMIT);
+
+        let (headlines, errors) = self.gather(query).await;
+        let selected = select(headlines, &include, &self.config.blocked_sources, limit);
+        if selected.is_empty() {
+            let mut error = match query {
+                Some(query) => format!("No headlines found for '{query}'"),
+                None => "No headlines found".to_string(),
+            };
+            if !include.is_empty() {
+                let _ = write!(error, " from {}", include.join(", "));
+            }
+            if !errors.is_empty() {
+                let _ = write!(error, " ({})", errors.join("; "));
+            }
+            return Ok(failure(error));
+        }
+
+        let heading = match query {
+            Some(query) => format!("Headlines for '{query}'"),
+            None => "Top headlines".to_string(),
+        };
+        let mut output = format_headlines(&heading, &selected, Utc::now());
+        if !errors.is_empty() {
+            let _ = write!(output, "\n\nUnavailable: {}", errors.join("; "));
+        }
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0"?>
+<rss version="2.0"><channel>
+  <title>BBC News</title><link>https://www.bbc.co.uk/news</link>
+  <item>
+    <title><![CDATA[Markets rally as rates hold]]></title>
+    <link>https://www.bbc.co.uk/news/business-1?at_medium=RSS</link>
+    <pubDate>Thu, 15 Oct 2026 06:00:00 GMT</pubDate>
+  </item>
+  <item>
+    <title>Storm &amp; floods hit coast - Reuters</title>
+    <link>https://www.reuters.com/world/storm</link>
+    <source url="https://www.reuters.com">Reuters</source>
+    <pubDate>Thu, 15 Oct 2026 04:30:00 +0000</pubDate>
+  </item>
+</channel></rss>"#;
+
+    const ATOM: &str = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>The Verge</title>
+  <entry>
+    <title type="html">Storm &amp;amp; floods hit coast</title>
+    <link rel="self" href="https://example.com/self"/>
+    <link rel="alternate" href="https://www.theverge.com/storm"/>
+    <updated>2026-10-15T05:00:00Z</updated>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn rss_and_atom_feeds_are_parsed() {
+        let rss = parse_feed(RSS);
+        assert_eq!(rss.len(), 2);
+        assert_eq!(rss[0].title, "Markets rally as rates hold");
+        assert_eq!(rss[0].source, "BBC News");
+        assert_eq!(rss[1].title, "Storm & floods hit coast - Reuters");
+        assert_eq!(rss[1].source, "Reuters");
+        assert_eq!(
+            rss[1].published.unwrap().to_rfc3339(),
+            "2026-10-15T04:30:00+00:00"
+        );
+
+        let atom = parse_feed(ATOM);
+        assert_eq!(atom.len(), 1);
+        assert_eq!(atom[0].title, "Storm & floods hit coast");
+        assert_eq!(atom[0].url, "https://www.theverge.com/storm");
+        assert_eq!(atom[0].source, "The Verge");
+    }
+
+    #[test]
+    fn headlines_are_deduplicated_filtered_and_cited() {
+        let mut headlines = parse_feed(RSS);
+        headlines.extend(parse_feed(ATOM));
+
+        let selected = select(headlines.clone(), &[], &[], 10);
+        let titles: Vec<&str> = selected.iter().map(|h| h.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Markets rally as rates hold", "Storm & floods hit coast"]
+        );
+
+        let only_reuters = select(headlines.clone(), &["reuters.com".into()], &[], 10);
+        assert_eq!(only_reuters.len(), 1);
+        assert_eq!(only_reuters[0].source, "Reuters");
+        let without_bbc = select(headlines, &[], &["bbc".into()], 10);
+        assert!(without_bbc.iter().all(|h| h.source != "BBC News"));
+
+        let now = DateTime::parse_from_rfc3339("2026-10-15T07:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            format_headlines("Top headlines", &selected[..1], now),
+            "Top headlines (1):\n1. Markets rally as rates hold (BBC News, 1h ago)\n   https://www.bbc.co.uk/news/business-1?at_medium=RSS"
+        );
+    }
+
+    #[test]
+    fn newsapi_responses_are_parsed() {
+        let body = json!({"status": "ok", "articles": [
+            {"source": {"name": "AP"}, "title": "Vote count begins", "url": "https://apnews.com/a",
+             "publishedAt": "2026-10-15T05:00:00Z"},
+            {"source": {"name": null}, "title": "[Removed]", "url": "https://removed.com"}
+        ]});
+        let headlines = parse_newsapi(&body).unwrap();
+        assert_eq!(headlines.len(), 1);
+        assert_eq!(headlines[0].source, "AP");
+
+        let err =
+            parse_newsapi(&json!({"status": "error", "message": "apiKey invalid"})).unwrap_err();
+        assert_eq!(err.to_string(), "NewsAPI: apiKey invalid");
+    }
+}
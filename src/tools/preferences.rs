@@ -0,0 +1,198 @@
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::channels::preferences;
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Identity used outside channel conversations (interactive CLI).
+pub const LOCAL_IDENTITY: (&str, &str) = ("cli", "local");
+
+/// Channel and sender whose preferences a request reads and edits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreferenceIdentity {
+    pub channel: String,
+    pub sender: String,
+}
+
+tokio::task_local! {
+    static REQUEST_IDENTITY: PreferenceIdentity;
+}
+
+/// Run `future` with the `preferences` tool acting for `identity`.
+pub async fn with_identity<F: std::future::Future>(
+    identity: PreferenceIdentity,
+    future: F,
+) -> F::Output {
+    REQUEST_IDENTITY.scope(identity, future).await
+}
+
+pub(super) fn current_identity() -> PreferenceIdentity {
+    REQUEST_IDENTITY
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| PreferenceIdentity {
+            channel: LOCAL_IDENTITY.0.to_string(),
+            sender: LOCAL_IDENTITY.1.to_string(),
+        })
+}
+
+/// Reads and updates the current user's stored preferences.
+pub struct PreferencesTool {
+    workspace_dir: PathBuf,
+    security: Arc<SecurityPolicy>,
+}
+
+impl PreferencesTool {
+    pub fn new(workspace_dir: PathBuf, security: Arc<SecurityPolicy>) -> Self {
+        Self {
+            workspace_dir,
+            security,
+        }
+    }
+}
+
+fn non_empty_str<'a>(args: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+#[async_trait]
+impl Tool for PreferencesTool {
+    fn name(&self) -> &str {
+        "preferences"
+    }
+
+    fn description(&self) -> &str {
+        "Remember how the user wants replies: tone, verbosity (brief|normal|detailed), units \
+         (metric|imperial), language, and formatting. Use action='set' when the user states a \
+         lasting preference (\"always use metric\"), 'get' to list them, 'clear' to remove one \
+         (key) or all. Stored preferences are applied automatically to later conversations."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["get", "set", "clear"],
+                    "default": "get"
+                },
+                "key": {
+                    "type": "string",
+                    "enum": preferences::KEYS.iter().map(|(name, _, _)| *name).collect::<Vec<_>>()
+                },
+                "value": { "type": "string", "description": "New value (for set)" }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let identity = current_identity();
+        let action = non_empty_str(&args, "action").unwrap_or("get");
+        if action != "get" {
+            if !self.security.can_act() {
+                return Ok(failure(format!(
+                    "Security policy: read-only mode, cannot perform '{action}'"
+                )));
+            }
+            if !self.security.record_action() {
+                return Ok(failure("Rate limit exceeded: action budget exhausted"));
+            }
+        }
+
+        let output = match action {
+            "get" => preferences::describe(&preferences::get_all(
+                &self.workspace_dir,
+                &identity.channel,
+                &identity.sender,
+            )?),
+            "set" => {
+                let (Some(key), Some(value)) =
+                    (non_empty_str(&args, "key"), non_empty_str(&args, "value"))
+                else {
+                    return Ok(failure("'set' needs both 'key' and 'value'"));
+                };
+                if let Err(e) = preferences::normalize(key, value) {
+                    return Ok(failure(e));
+                }
+                let (key, value) = preferences::set(
+                    &self.workspace_dir,
+                    &identity.channel,
+                    &identity.sender,
+                    key,
+                    value,
+                )?;
+                format!("Saved preference {key} = {value}.")
+            }
+            "clear" => {
+                let key = non_empty_str(&args, "key");
+                let removed = preferences::clear(
+                    &self.workspace_dir,
+                    &identity.channel,
+                    &identity.sender,
+                    key,
+                )?;
+                match (key, removed) {
+                    (Some(key), 0) => return Ok(failure(format!("No '{key}' preference set"))),
+                    (Some(key), _) => format!("Cleared preference {key}."),
+                    (None, count) => format!("Cleared {count} preference(s)."),
+                }
+            }
+            other => return Ok(failure(format!("Unknown action '{other}'"))),
+        };
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tool_edits_the_current_identity_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let tool = PreferencesTool::new(tmp.path().to_path_buf(), security);
+        let alice = PreferenceIdentity {
+            channel: "telegram".into(),
+            sender: "alice".into(),
+        };
+
+        let saved = with_identity(
+            alice.clone(),
+            tool.execute(json!({"action": "set", "key": "units", "value": "Imperial"})),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.output, "Saved preference units = imperial.");
+
+        let invalid = with_identity(
+            alice.clone(),
+            tool.execute(json!({"action": "set", "key": "units", "value": "kelvin"})),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            invalid.error.as_deref(),
+            Some("'units' must be one of: metric, imperial")
+        );
+
+        let local = tool.execute(json!({})).await.unwrap();
+        assert!(local.output.starts_with("No preferences set."));
+        let listed = with_identity(alice, tool.execute(json!({"action": "get"})))
+            .await
+            .unwrap();
+        assert!(listed
+            .output
+            .starts_with("Your preferences:\n- units: imperial"));
+    }
+}
@@ -0,0 +1,288 @@
+use super::system_info::{
+    current_pid, sample_system, top_processes, write_process_table, ProcessSort, MAX_PROCESSES,
+};
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+use sysinfo::{Pid, Signal};
+
+/// Default number of processes returned by `list`.
+const DEFAULT_LIST_LIMIT: usize = 10;
+
+/// Signals the agent may send, by name.
+const SIGNALS: &[(&str, Signal)] = &[
+    ("term", Signal::Term),
+    ("int", Signal::Interrupt),
+    ("hup", Signal::Hangup),
+    ("kill", Signal::Kill),
+    ("stop", Signal::Stop),
+    ("cont", Signal::Continue),
+];
+
+/// List processes on the host and send signals to them.
+///
+/// Listing is read-only. Signalling is an action: it needs non-read-only
+/// autonomy, spends action budget, and in supervised mode requires
+/// `approved=true`. The daemon never signals itself or PID 1.
+pub struct ProcessTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl ProcessTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+
+    async fn list(args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let sort = match ProcessSort::parse(args.get("sort").and_then(|v| v.as_str())) {
+            Ok(sort) => sort,
+            Err(error) => return Ok(failure(error)),
+        };
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_LIST_LIMIT, |n| {
+                usize::try_from(n)
+                    .unwrap_or(MAX_PROCESSES)
+                    .clamp(1, MAX_PROCESSES)
+            });
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+
+        let system = sample_system().await?;
+        let rows = top_processes(&system, sort, name, limit);
+        if rows.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: match name {
+                    Some(name) => format!("No processes matching '{name}'"),
+                    None => "No processes found".into(),
+                },
+                error: None,
+            });
+        }
+        let mut output = String::new();
+        write_process_table(&mut output, &rows);
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+
+    async fn signal(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(pid) = args
+            .get("pid")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|pid| u32::try_from(pid).ok())
+        else {
+            return Ok(failure("Missing or invalid 'pid' parameter"));
+        };
+        let signal_name = args
+            .get("signal")
+            .and_then(|v| v.as_str())
+            .map_or("term", str::trim)
+            .to_ascii_lowercase();
+        let Some(&(_, signal)) = SIGNALS.iter().find(|(name, _)| *name == signal_name) else {
+            let names: Vec<&str> = SIGNALS.iter().map(|(name, _)| *name).collect();
+            return Ok(failure(format!(
+                "Unknown signal '{signal_name}' (use one of: {})",
+                names.join(", ")
+            )));
+        };
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+
+        if pid <= 1 || current_pid().is_some_and(|own| own.as_u32() == pid) {
+            return Ok(failure(format!(
+                "Refusing to signal protected process {pid}"
+            )));
+        }
+        if self.security.autonomy == AutonomyLevel::Supervised && !approved {
+            return Ok(failure(
+                "Signalling a process requires explicit approval (approved=true)",
+            ));
+        }
+        if let Err(error) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "process.signal")
+        {
+            return Ok(failure(error));
+        }
+
+        let system = sample_system().await?;
+        let Some(process) = system.process(Pid::from_u32(pid)) else {
+            return Ok(failure(format!("No process with PID {pid}")));
+        };
+        let name = process.name().to_string_lossy().into_owned();
+        match process.kill_with(signal) {
+            Some(true) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Sent SIG{} to {name} (PID {pid})",
+                    signal_name.to_uppercase()
+                ),
+                error: None,
+            }),
+            Some(false) => Ok(failure(format!(
+                "Failed to signal {name} (PID {pid}): permission denied or process exited"
+            ))),
+            None => Ok(failure(format!(
+                "Signal '{signal_name}' is not supported on this platform"
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ProcessTool {
+    fn name(&self) -> &str {
+        "process"
+    }
+
+    fn description(&self) -> &str {
+        "List running processes (filter by name, sort by CPU or memory) or send a signal (term, int, hup, kill, stop, cont) to a process by PID. Signalling requires approval in supervised mode."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "signal"],
+                    "description": "list processes or signal one",
+                    "default": "list"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "list: only processes whose name contains this text"
+                },
+                "sort": {
+                    "type": "string",
+                    "enum": ["cpu", "memory"],
+                    "description": "list: order by CPU or memory usage",
+                    "default": "cpu"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "list: maximum processes to return (max 50)",
+                    "default": DEFAULT_LIST_LIMIT
+                },
+                "pid": {
+                    "type": "integer",
+                    "description": "signal: process ID to signal"
+                },
+                "signal": {
+                    "type": "string",
+                    "enum": SIGNALS.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+                    "description": "signal: which signal to send",
+                    "default": "term"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "signal: set true to explicitly approve in supervised mode",
+                    "default": false
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        match args
+            .get("action")
+            .and_then(|v| v.as_str())
+            .unwrap_or("list")
+        {
+            "list" => Self::list(&args).await,
+            "signal" => self.signal(&args).await,
+            other => Ok(failure(format!(
+                "Unknown action '{other}' (use list or signal)"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(autonomy: AutonomyLevel) -> Arc<SecurityPolicy> {
+        Arc::new(SecurityPolicy {
+            autonomy,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn list_filters_by_name() {
+        let tool = ProcessTool::new(policy(AutonomyLevel::ReadOnly));
+        let result = tool
+            .execute(json!({"name": "no-such-process-zc", "limit": 3}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "No processes matching 'no-such-process-zc'");
+
+        let result = tool.execute(json!({"sort": "memory"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("     PID    CPU%"));
+    }
+
+    #[tokio::test]
+    async fn signal_is_gated_by_autonomy_and_approval() {
+        let args = json!({"action": "signal", "pid": 999_999});
+        let result = ProcessTool::new(policy(AutonomyLevel::ReadOnly))
+            .execute(args.clone())
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("read-only mode"));
+
+        let result = ProcessTool::new(policy(AutonomyLevel::Supervised))
+            .execute(args)
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("approved=true"));
+
+        let own = std::process::id();
+        let result = ProcessTool::new(policy(AutonomyLevel::Full))
+            .execute(json!({"action": "signal", "pid": own, "signal": "kill"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("protected process"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn signal_terminates_an_approved_process() {
+        let mut child = tokio::process::Command::new("sleep")
+            .arg("30")
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let pid = child.id().unwrap();
+
+        let result = ProcessTool::new(policy(AutonomyLevel::Supervised))
+            .execute(json!({"action": "signal", "pid": pid, "approved": true}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("Sent SIGTERM to sleep"));
+
+        let status = tokio::time::timeout(std::time::Duration::from_secs(5), child.wait())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!status.success());
+    }
+}
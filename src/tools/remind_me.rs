@@ -0,0 +1,614 @@
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::Config;
+use crate::cron::{self, DeliveryConfig, JobType};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Channels the scheduler can deliver reminders to.
+pub(super) const DELIVERY_CHANNELS: &[&str] = &["telegram", "discord", "slack", "mattermost"];
+/// Time used when only a day is given ("remind me tomorrow").
+const DEFAULT_HOUR: u32 = 9;
+
+/// Conversation a request came from, used as the default reminder target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderRoute {
+    pub channel: String,
+    pub recipient: String,
+}
+
+tokio::task_local! {
+    static REQUEST_ROUTE: ReminderRoute;
+}
+
+/// Run `future` with reminders defaulting to delivery on `route`.
+pub async fn with_request_route<F: std::future::Future>(
+    route: Option<ReminderRoute>,
+    future: F,
+) -> F::Output {
+    match route {
+        Some(route) => REQUEST_ROUTE.scope(route, future).await,
+        None => future.await,
+    }
+}
+
+pub(super) fn current_route() -> Option<ReminderRoute> {
+    REQUEST_ROUTE.try_with(Clone::clone).ok()
+}
+
+/// Sets, lists, and cancels one-shot reminders delivered through the scheduler.
+pub struct RemindMeTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl RemindMeTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn enforce_mutation_allowed(&self, action: &str) -> Option<ToolResult> {
+        if !self.security.can_act() {
+            return Some(failure(format!(
+                "Security policy: read-only mode, cannot perform '{action}'"
+            )));
+        }
+        if self.security.is_rate_limited() {
+            return Some(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Some(failure("Rate limit exceeded: action budget exhausted"));
+        }
+        None
+    }
+
+    fn handle_set(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(message) = non_empty_str(args, "message") else {
+            return Ok(failure("Missing 'message' parameter"));
+        };
+        let Some(when) = non_empty_str(args, "when") else {
+            return Ok(failure("Missing 'when' parameter"));
+        };
+        let tz = match non_empty_str(args, "tz").unwrap_or("UTC").parse::<Tz>() {
+            Ok(tz) => tz,
+            Err(e) => return Ok(failure(format!("Invalid timezone: {e}"))),
+        };
+        let at = match parse_when(when, Utc::now().with_timezone(&tz)) {
+            Ok(at) => at,
+            Err(e) => return Ok(failure(format!("Could not understand '{when}': {e}"))),
+        };
+        let (channel, to) = match resolve_target(args, current_route()) {
+            Ok(target) => target,
+            Err(e) => return Ok(failure(e)),
+        };
+
+        if let Some(blocked) = self.enforce_mutation_allowed("remind_me") {
+            return Ok(blocked);
+        }
+
+        let delivery = DeliveryConfig {
+            mode: "announce".to_string(),
+            channel: Some(channel.clone()),
+            to: Some(to.clone()),
+            best_effort: true,
+            recipients: Vec::new(),
+        };
+        let name = crate::util::truncate_with_ellipsis(message, 40);
+        match cron::add_reminder_job(&self.config, Some(name), at, message, delivery) {
+            Ok(job) => Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&json!({
+                    "id": job.id,
+                    "message": message,
+                    "remind_at": at.with_timezone(&tz).to_rfc3339(),
+                    "remind_at_utc": at.to_rfc3339(),
+                    "channel": channel,
+                    "to": to,
+                }))?,
+                error: None,
+            }),
+            Err(e) => Ok(failure(e.to_string())),
+        }
+    }
+
+    fn reminders(&self) -> anyhow::Result<Vec<cron::CronJob>> {
+        let route = current_route();
+        Ok(cron::list_jobs(&self.config)?
+            .into_iter()
+            .filter(|job| {
+                job.job_type == JobType::Reminder && !job.is_timer() && !job.is_scheduled_send()
+            })
+            .filter(|job| route.as_ref().is_none_or(|route| targets_route(job, route)))
+            .collect())
+    }
+
+    fn handle_list(&self) -> anyhow::Result<ToolResult> {
+        let reminders: Vec<_> = self
+            .reminders()?
+            .into_iter()
+            .map(|job| {
+                json!({
+                    "id": job.id,
+                    "message": job.prompt,
+                    "remind_at_utc": job.next_run.to_rfc3339(),
+                    "channel": job.delivery.channel,
+                    "to": job.delivery.to,
+                })
+            })
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&reminders)?,
+            error: None,
+        })
+    }
+
+    fn handle_cancel(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(id) = non_empty_str(args, "id") else {
+            return Ok(failure("Missing 'id' parameter"));
+        };
+        if !self.reminders()?.iter().any(|job| job.id == id) {
+            return Ok(failure(format!("No reminder with id '{id}'")));
+        }
+        if let Some(blocked) = self.enforce_mutation_allowed("remind_me") {
+            return Ok(blocked);
+        }
+        match cron::remove_job(&self.config, id) {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Cancelled reminder {id}"),
+                error: None,
+            }),
+            Err(e) => Ok(failure(e.to_string())),
+        }
+    }
+}
+
+pub(super) fn non_empty_str<'a>(args: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+pub(super) fn targets_route(job: &cron::CronJob, route: &ReminderRoute) -> bool {
+    job.delivery.channel.as_deref() == Some(route.channel.as_str())
+        && job.delivery.to.as_deref() == Some(route.recipient.as_str())
+}
+
+/// Explicit `channel`/`to` arguments win; otherwise reply where the request came from.
+pub(super) fn resolve_target(
+    args: &serde_json::Value,
+    route: Option<ReminderRoute>,
+) -> Result<(String, String), String> {
+    let channel = non_empty_str(args, "channel")
+        .map(str::to_ascii_lowercase)
+        .or_else(|| route.as_ref().map(|r| r.channel.clone()))
+        .ok_or("No conversation to deliver to; pass 'channel' and 'to'")?;
+    if !DELIVERY_CHANNELS.contains(&channel.as_str()) {
+        return Err(format!(
+            "Reminders cannot be delivered on '{channel}'; supported channels: {}",
+            DELIVERY_CHANNELS.join(", ")
+        ));
+    }
+    let to = non_empty_str(args, "to")
+        .map(str::to_string)
+        .or_else(|| route.filter(|r| r.channel == channel).map(|r| r.recipient))
+        .ok_or_else(|| format!("Missing 'to' for channel '{channel}'"))?;
+    Ok((channel, to))
+}
+
+/// Parse a natural-language time ("in 2 hours", "tomorrow 9am", "Friday at 3pm",
+/// "2026-03-01 14:00") relative to `now`, interpreting wall-clock times in `now`'s zone.
+pub(super) fn parse_when(input: &str, now: DateTime<Tz>) -> Result<DateTime<Utc>, String> {
+    let text = input.trim().to_ascii_lowercase();
+    let at = if let Ok(at) = DateTime::parse_from_rfc3339(input.trim()) {
+        at.with_timezone(&Utc)
+    } else if let Some(naive) = parse_absolute(&text) {
+        localize(now.timezone(), naive)?
+    } else if let Some(rest) = text.strip_prefix("in ") {
+        now.with_timezone(&Utc)
+            .checked_add_signed(parse_duration(rest)?)
+            .ok_or_else(|| "that time is too far in the future".to_string())?
+    } else {
+        parse_day_and_time(&text, now)?
+    };
+    if at <= now.with_timezone(&Utc) {
+        return Err("that time is in the past".to_string());
+    }
+    Ok(at)
+}
+
+fn parse_absolute(text: &str) -> Option<NaiveDateTime> {
+    [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(DEFAULT_HOUR, 0, 0)
+    })
+}
+
+pub(super) fn parse_duration(text: &str) -> Result<Duration, String> {
+    let mut total = Duration::zero();
+    let mut pending: Option<i64> = None;
+    for part in duration_parts(text) {
+        if part.starts_with(|c: char| c.is_ascii_digit()) {
+            pending = Some(part.parse().map_err(|_| format!("bad number '{part}'"))?);
+            continue;
+        }
+        match part.as_str() {
+            "a" | "an" => pending = Some(1),
+            "and" => {}
+            unit => {
+                let amount = pending
+                    .take()
+                    .ok_or_else(|| format!("missing amount before '{unit}'"))?;
+                let step = match unit {
+                    "s" | "sec" | "secs" | "second" | "seconds" => Duration::try_seconds(amount),
+                    "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(amount),
+                    "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(amount),
+                    "d" | "day" | "days" => Duration::try_days(amount),
+                    "w" | "week" | "weeks" => Duration::try_weeks(amount),
+                    other => return Err(format!("unknown unit '{other}'")),
+                };
+                total = step
+                    .and_then(|step| total.checked_add(&step))
+                    .ok_or_else(|| "that duration is too long".to_string())?;
+            }
+        }
+    }
+    if pending.is_some() || total <= Duration::zero() {
+        return Err("expected a duration like 'in 2 hours' or 'in 90m'".to_string());
+    }
+    Ok(total)
+}
+
+/// Split "1h30m" and "2 hours" alike into alternating number and word parts.
+fn duration_parts(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    for token in text.split_whitespace() {
+        let mut current = String::new();
+        for c in token.chars() {
+            let boundary = current
+                .chars()
+                .last()
+                .is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit());
+            if boundary {
+                parts.push(std::mem::take(&mut current));
+            }
+            current.push(c);
+        }
+        parts.push(current);
+    }
+    parts
+}
+
+fn parse_day_and_time(text: &str, now: DateTime<Tz>) -> Result<DateTime<Utc>, String> {
+    let today = now.date_naive();
+    let mut day: Option<NaiveDate> = None;
+    let mut weekday: Option<chrono::Weekday> = None;
+    let mut default_time: Option<NaiveTime> = None;
+    let mut next = false;
+    let mut time_text = String::new();
+
+    for token in text.split(|c: char| c.is_whitespace() || c == ',') {
+        match token {
+            "" | "at" | "on" | "this" | "the" => {}
+            "next" => next = true,
+            "today" => day = Some(today),
+            "tomorrow" | "tmrw" => day = today.succ_opt(),
+            "tonight" => {
+                day = Some(today);
+                default_time = NaiveTime::from_hms_opt(20, 0, 0);
+            }
+            "morning" => default_time = NaiveTime::from_hms_opt(9, 0, 0),
+            "afternoon" => default_time = NaiveTime::from_hms_opt(15, 0, 0),
+            "evening" | "night" => default_time = NaiveTime::from_hms_opt(19, 0, 0),
+            other => match parse_weekday(other) {
+                Some(parsed) => weekday = Some(parsed),
+                None => time_text.push_str(other),
+            },
+        }
+    }
+
+    let time = if time_text.is_empty() {
+        default_time
+    } else {
+        Some(parse_time(&time_text).ok_or_else(|| format!("unrecognized time '{time_text}'"))?)
+    };
+    if day.is_none() && weekday.is_none() && time.is_none() {
+        return Err("expected a day or time like 'tomorrow 9am' or 'Friday at 3pm'".to_string());
+    }
+    let time = time
+        .or_else(|| NaiveTime::from_hms_opt(DEFAULT_HOUR, 0, 0))
+        .unwrap_or_default();
+    let now_utc = now.with_timezone(&Utc);
+    let at_on = |date: NaiveDate| localize(now.timezone(), date.and_time(time));
+
+    if let Some(weekday) = weekday {
+        let ahead =
+            (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+        let date = today + Duration::days(i64::from(ahead));
+        let at = at_on(date)?;
+        // Today's weekday means later today, unless it already passed or "next" was said.
+        if ahead == 0 && (next || at <= now_utc) {
+            return at_on(date + Duration::days(7));
+        }
+        return Ok(at);
+    }
+    if let Some(day) = day {
+        return at_on(day);
+    }
+    // A bare time that already passed today means tomorrow.
+    let at = at_on(today)?;
+    if at <= now_utc {
+        return at_on(today + Duration::days(1));
+    }
+    Ok(at)
+}
+
+fn parse_weekday(token: &str) -> Option<chrono::Weekday> {
+    match token {
+        "mon" | "monday" => Some(chrono::Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(chrono::Weekday::Tue),
+        "wed" | "wednesday" => Some(chrono::Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(chrono::Weekday::Thu),
+        "fri" | "friday" => Some(chrono::Weekday::Fri),
+        "sat" | "saturday" => Some(chrono::Weekday::Sat),
+        "sun" | "sunday" => Some(chrono::Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Parse "3pm", "3:30pm", "15:30", "noon", or "midnight" (spaces already removed).
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    match text {
+        "noon" | "midday" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+    let (clock, meridiem) = if let Some(clock) = text.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = text.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (text, None)
+    };
+    let (hour, minute) = match clock.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (clock.parse::<u32>().ok()?, 0),
+    };
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(false) => hour % 12,
+        Some(true) => hour % 12 + 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Resolve a wall-clock time in `tz`, skipping forward over DST gaps.
+fn localize(tz: Tz, naive: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+    tz.from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(naive + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc))
+        .ok_or_else(|| format!("{naive} does not exist in {tz}"))
+}
+
+#[async_trait]
+impl Tool for RemindMeTool {
+    fn name(&self) -> &str {
+        "remind_me"
+    }
+
+    fn description(&self) -> &str {
+        "Set, list, or cancel reminders for the user. For \"remind me Friday at 3pm to call mom\" \
+         use action='set', when='Friday at 3pm', message='call mom'. 'when' accepts phrases like \
+         'in 20 minutes', 'tomorrow 9am', 'tonight', 'next monday at 14:30', or '2030-01-01 09:00'. \
+         Pass the user's IANA timezone in 'tz' when known. Reminders are delivered to the \
+         conversation the request came from unless 'channel' and 'to' are given."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["set", "list", "cancel"],
+                    "default": "set"
+                },
+                "message": { "type": "string", "description": "What to remind the user about" },
+                "when": { "type": "string", "description": "When to deliver the reminder, in natural language" },
+                "tz": { "type": "string", "description": "IANA timezone for wall-clock times (default UTC)" },
+                "channel": { "type": "string", "enum": DELIVERY_CHANNELS },
+                "to": { "type": "string", "description": "Chat/channel ID to deliver to" },
+                "id": { "type": "string", "description": "Reminder ID (for cancel)" }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.cron.enabled {
+            return Ok(failure(
+                "reminders need the scheduler, which is disabled by config (cron.enabled=false)",
+            ));
+        }
+        match args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("set")
+        {
+            "set" => self.handle_set(&args),
+            "list" => self.handle_list(),
+            "cancel" => self.handle_cancel(&args),
+            other => Ok(failure(format!("Unknown action '{other}'"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::AutonomyLevel;
+    use tempfile::TempDir;
+
+    /// Wednesday 2026-03-04 10:00 in New York.
+    fn now() -> DateTime<Tz> {
+        chrono_tz::America::New_York
+            .with_ymd_and_hms(2026, 3, 4, 10, 0, 0)
+            .unwrap()
+    }
+
+    fn local(input: &str) -> String {
+        parse_when(input, now())
+            .unwrap()
+            .with_timezone(&chrono_tz::America::New_York)
+            .format("%a %Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn parses_relative_durations() {
+        assert_eq!(local("in 20 minutes"), "Wed 2026-03-04 10:20");
+        assert_eq!(local("in 1h30m"), "Wed 2026-03-04 11:30");
+        assert_eq!(local("in an hour and 15 mins"), "Wed 2026-03-04 11:15");
+        assert_eq!(local("in 2 days"), "Fri 2026-03-06 10:00");
+        assert!(parse_when("in soon", now()).is_err());
+    }
+
+    #[test]
+    fn rejects_durations_that_overflow() {
+        assert!(parse_duration("9223372036854775807 weeks").is_err());
+        assert!(parse_duration("10000000000 weeks and 10000000000 weeks").is_err());
+        assert!(parse_when("in 10000000000 weeks", now()).is_err());
+    }
+
+    #[test]
+    fn parses_days_and_times() {
+        assert_eq!(local("Friday at 3pm"), "Fri 2026-03-06 15:00");
+        assert_eq!(local("tomorrow 9:30am"), "Thu 2026-03-05 09:30");
+        assert_eq!(local("tomorrow"), "Thu 2026-03-05 09:00");
+        assert_eq!(local("tonight"), "Wed 2026-03-04 20:00");
+        assert_eq!(local("at 15:45"), "Wed 2026-03-04 15:45");
+        // Times that already passed today roll over to tomorrow.
+        assert_eq!(local("at 8am"), "Thu 2026-03-05 08:00");
+        // Today's weekday: later today, or next week once passed / with "next".
+        assert_eq!(local("wednesday at 5 pm"), "Wed 2026-03-04 17:00");
+        assert_eq!(local("wednesday 9am"), "Wed 2026-03-11 09:00");
+        assert_eq!(local("next wednesday at 5pm"), "Wed 2026-03-11 17:00");
+        assert_eq!(local("monday morning"), "Mon 2026-03-09 09:00");
+    }
+
+    #[test]
+    fn parses_absolute_times_in_timezone() {
+        assert_eq!(local("2026-03-10 14:00"), "Tue 2026-03-10 14:00");
+        assert_eq!(
+            parse_when("2026-03-10T18:00:00Z", now())
+                .unwrap()
+                .to_rfc3339(),
+            "2026-03-10T18:00:00+00:00"
+        );
+        assert!(parse_when("2026-03-01 09:00", now()).is_err());
+        assert!(parse_when("whenever", now()).is_err());
+        assert!(parse_when("at 25pm", now()).is_err());
+    }
+
+    #[test]
+    fn resolve_target_prefers_explicit_then_route() {
+        let route = ReminderRoute {
+            channel: "telegram".into(),
+            recipient: "12345".into(),
+        };
+        assert_eq!(
+            resolve_target(&json!({}), Some(route.clone())).unwrap(),
+            ("telegram".to_string(), "12345".to_string())
+        );
+        assert_eq!(
+            resolve_target(
+                &json!({"channel": "discord", "to": "99"}),
+                Some(route.clone())
+            )
+            .unwrap(),
+            ("discord".to_string(), "99".to_string())
+        );
+        assert!(resolve_target(&json!({"channel": "discord"}), Some(route)).is_err());
+        assert!(resolve_target(&json!({}), None).is_err());
+        let cli = ReminderRoute {
+            channel: "cli".into(),
+            recipient: "user".into(),
+        };
+        assert!(resolve_target(&json!({}), Some(cli)).is_err());
+    }
+
+    #[tokio::test]
+    async fn set_list_and_cancel_reminders_for_the_current_conversation() {
+        let tmp = TempDir::new().unwrap();
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            ..SecurityPolicy::default()
+        });
+        let tool = RemindMeTool::new(Arc::new(config), security);
+        let route = |recipient: &str| ReminderRoute {
+            channel: "telegram".into(),
+            recipient: recipient.into(),
+        };
+
+        let set = with_request_route(
+            Some(route("alice")),
+            tool.execute(json!({"when": "in 2 hours", "message": "call mom"})),
+        )
+        .await
+        .unwrap();
+        assert!(set.success, "{:?}", set.error);
+        let id = serde_json::from_str::<serde_json::Value>(&set.output).unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let listed = with_request_route(
+            Some(route("alice")),
+            tool.execute(json!({"action": "list"})),
+        )
+        .await
+        .unwrap();
+        assert!(listed.output.contains("call mom"));
+        let others =
+            with_request_route(Some(route("bob")), tool.execute(json!({"action": "list"})))
+                .await
+                .unwrap();
+        assert!(!others.output.contains("call mom"));
+
+        let cancelled = with_request_route(
+            Some(route("alice")),
+            tool.execute(json!({"action": "cancel", "id": id})),
+        )
+        .await
+        .unwrap();
+        assert!(cancelled.success, "{:?}", cancelled.error);
+        assert_eq!(tool.reminders().unwrap().len(), 0);
+    }
+}
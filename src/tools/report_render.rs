@@ -0,0 +1,418 @@
+//! Template-based reports (`report_render`).
+//!
+//! Templates are Jinja-style HTML files in `<workspace>/templates` (Tera and
+//! Jinja2 syntax both work for the usual tags and filters). The agent passes
+//! structured data, the report is written to `<workspace>/exports/reports/`,
+//! and with `format = "pdf"` it is printed by headless Chromium or
+//! WeasyPrint. Channels send the file when the reply carries a
+//! `[DOCUMENT:<path>]` marker.
+
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::ReportsConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::SecurityPolicy;
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Template file suffixes, tried in order when a name has no extension.
+const TEMPLATE_SUFFIXES: &[&str] = &[".html", ".html.j2", ".html.tera", ".htm"];
+const CHROMIUM_BINARIES: &[&str] = &[
+    "chromium",
+    "chromium-browser",
+    "google-chrome",
+    "google-chrome-stable",
+];
+
+fn is_template_file(name: &str, suffixes: &[&str]) -> bool {
+    suffixes.iter().any(|suffix| name.ends_with(suffix))
+}
+
+/// Names of the templates in `dir` ending in one of `suffixes`, sorted.
+pub(super) fn list_templates(dir: &Path, suffixes: &[&str]) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_template_file(name, suffixes))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Find the template file for `name` (`weekly` → `weekly.html`).
+pub(super) fn resolve_template(
+    dir: &Path,
+    name: &str,
+    suffixes: &[&str],
+) -> anyhow::Result<String> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.starts_with(['/', '\\'])
+        || name.split(['/', '\\']).any(|part| part == "..")
+    {
+        bail!("Invalid template name '{name}'");
+    }
+    let candidates = std::iter::once(name.to_string())
+        .filter(|name| is_template_file(name, suffixes))
+        .chain(suffixes.iter().map(|suffix| format!("{name}{suffix}")));
+    for candidate in candidates {
+        if dir.join(&candidate).is_file() {
+            return Ok(candidate);
+        }
+    }
+    let available = list_templates(dir, suffixes);
+    if available.is_empty() {
+        bail!(
+            "Template '{name}' not found; {} has no templates",
+            dir.display()
+        );
+    }
+    bail!(
+        "Template '{name}' not found; available: {}",
+        available.join(", ")
+    )
+}
+
+/// Render `template` from `dir`; `.html` templates escape their values.
+pub(super) fn render(
+    dir: &Path,
+    template: &str,
+    data: &serde_json::Value,
+) -> anyhow::Result<String> {
+    let mut env = minijinja::Environment::new();
+    env.set_loader(minijinja::path_loader(dir));
+    let template = env
+        .get_template(template)
+        .with_context(|| format!("Failed to load template '{template}'"))?;
+    template
+        .render(data)
+        .map_err(|e| anyhow::anyhow!("Failed to render template: {e:#}"))
+}
+
+/// File stem for a report: the requested name or the template plus a timestamp.
+fn output_stem(name: Option<&str>, template: &str) -> String {
+    let base = name.map_or_else(
+        || {
+            let stem = template.split(['/', '.']).find(|part| !part.is_empty());
+            format!(
+                "{}-{}",
+                stem.unwrap_or("report"),
+                chrono::Local::now().format("%Y%m%d-%H%M%S")
+            )
+        },
+        str::to_string,
+    );
+    let slug: String = base
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "report".into()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// PDF renderer command for `engine` (`auto` picks the first one installed).
+fn pdf_command(
+    config: &ReportsConfig,
+    html: &Path,
+    pdf: &Path,
+) -> anyhow::Result<tokio::process::Command> {
+    let chromium = || {
+        config
+            .chromium_path
+            .as_ref()
+            .map(|path| PathBuf::from(shellexpand::tilde(path.trim()).into_owned()))
+            .or_else(|| {
+                CHROMIUM_BINARIES
+                    .iter()
+                    .find_map(|binary| which::which(binary).ok())
+            })
+    };
+    let engine = match config.pdf_engine.as_str() {
+        "auto" => {
+            if let Some(binary) = chromium() {
+                ("chromium", binary)
+            } else if let Ok(binary) = which::which("weasyprint") {
+                ("weasyprint", binary)
+            } else {
+                bail!("PDF output needs Chromium or WeasyPrint installed");
+            }
+        }
+        "chromium" => (
+            "chromium",
+            chromium().context("Chromium not found; set reports.chromium_path")?,
+        ),
+        "weasyprint" => (
+            "weasyprint",
+            which::which("weasyprint").context("weasyprint not found")?,
+        ),
+        _ => bail!("PDF output is disabled (reports.pdf_engine = \"none\")"),
+    };
+
+    let mut command = tokio::process::Command::new(engine.1);
+    if engine.0 == "chromium" {
+        command
+            .args(["--headless", "--disable-gpu", "--no-pdf-header-footer"])
+            .arg(format!("--print-to-pdf={}", pdf.display()))
+            .arg(format!("file://{}", html.display()));
+    } else {
+        command.arg(html).arg(pdf);
+    }
+    command.kill_on_drop(true);
+    Ok(command)
+}
+
+/// Fills workspace templates with data and writes HTML or PDF reports.
+pub struct ReportRenderTool {
+    workspace_dir: PathBuf,
+    config: ReportsConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl ReportRenderTool {
+    pub fn new(
+        workspace_dir: PathBuf,
+        config: ReportsConfig,
+        security: Arc<SecurityPolicy>,
+    ) -> Self {
+        Self {
+            workspace_dir,
+            config,
+            security,
+        }
+    }
+
+    fn templates_dir(&self) -> PathBuf {
+        self.workspace_dir.join("templates")
+    }
+
+    async fn write_pdf(&self, html: &Path) -> anyhow::Result<PathBuf> {
+        let pdf = html.with_extension("pdf");
+        // A stale file from an earlier run must not pass as this run's output.
+        let _ = std::fs::remove_file(&pdf);
+        let output = tokio::time::timeout(
+            Duration::from_secs(self.config.timeout_secs),
+            pdf_command(&self.config, html, &pdf)?.output(),
+        )
+        .await
+        .with_context(|| {
+            format!(
+                "PDF rendering timed out after {}s",
+                self.config.timeout_secs
+            )
+        })?
+        .context("Failed to start the PDF renderer")?;
+        if !output.status.success() || !pdf.is_file() {
+            bail!(
+                "PDF rendering failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(pdf)
+    }
+}
+
+#[async_trait]
+impl Tool for ReportRenderTool {
+    fn name(&self) -> &str {
+        "report_render"
+    }
+
+    fn description(&self) -> &str {
+        "Fill a report template from the workspace templates folder with structured data and save \
+         it as HTML or PDF (weekly summaries, invoices). Omit 'template' to list templates. To \
+         send the result, put the returned [DOCUMENT:<path>] marker in your reply."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "template": { "type": "string", "description": "Template name, e.g. 'weekly' for templates/weekly.html; omit to list templates" },
+                "data": { "type": "object", "description": "Values the template uses" },
+                "format": { "type": "string", "enum": ["html", "pdf"], "default": "html" },
+                "name": { "type": "string", "description": "Output file name without extension (default: template name and time)" }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let dir = self.templates_dir();
+        let Some(template) = args
+            .get("template")
+            .and_then(serde_json::Value::as_str)
+            .filter(|name| !name.trim().is_empty())
+        else {
+            let templates = list_templates(&dir, TEMPLATE_SUFFIXES);
+            let output = if templates.is_empty() {
+                format!("No templates yet; add HTML templates to {}", dir.display())
+            } else {
+                format!("Templates: {}", templates.join(", "))
+            };
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        };
+        let format = args
+            .get("format")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("html");
+        if !matches!(format, "html" | "pdf") {
+            return Ok(failure(format!(
+                "Unknown format '{format}'; use html or pdf"
+            )));
+        }
+        let data = args.get("data").cloned().unwrap_or_else(|| json!({}));
+        if !data.is_object() {
+            return Ok(failure("'data' must be an object"));
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "report_render")
+        {
+            return Ok(failure(e));
+        }
+
+        let template = match resolve_template(&dir, template, TEMPLATE_SUFFIXES) {
+            Ok(template) => template,
+            Err(e) => return Ok(failure(e.to_string())),
+        };
+        let html = match render(&dir, &template, &data) {
+            Ok(html) => html,
+            Err(e) => return Ok(failure(format!("{e:#}"))),
+        };
+
+        let out_dir = self.workspace_dir.join("exports").join("reports");
+        std::fs::create_dir_all(&out_dir)
+            .with_context(|| format!("Failed to create {}", out_dir.display()))?;
+        let name = args.get("name").and_then(serde_json::Value::as_str);
+        let html_path = out_dir.join(format!("{}.html", output_stem(name, &template)));
+        std::fs::write(&html_path, html)
+            .with_context(|| format!("Failed to write {}", html_path.display()))?;
+
+        let path = if format == "pdf" {
+            match self.write_pdf(&html_path).await {
+                Ok(pdf) => pdf,
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: format!("HTML report saved to: {}", html_path.display()),
+                        error: Some(format!("{e:#}")),
+                    })
+                }
+            }
+        } else {
+            html_path
+        };
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "Report saved to: {path}\nAttach it with [DOCUMENT:{path}]",
+                path = path.display()
+            ),
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(workspace: &Path) -> ReportRenderTool {
+        ReportRenderTool::new(
+            workspace.to_path_buf(),
+            ReportsConfig::default(),
+            Arc::new(SecurityPolicy::default()),
+        )
+    }
+
+    #[tokio::test]
+    async fn renders_html_with_escaping_and_includes() {
+        let tmp = TempDir::new().unwrap();
+        let templates = tmp.path().join("templates");
+        std::fs::create_dir_all(&templates).unwrap();
+        std::fs::write(
+            templates.join("base.html"),
+            "<h1>{{ title }}</h1>{% block body %}{% endblock %}",
+        )
+        .unwrap();
+        std::fs::write(
+            templates.join("invoice.html"),
+            "{% extends \"base.html\" %}{% block body %}<ul>{% for item in items %}\
+             <li>{{ item.name }}: {{ item.amount }}</li>{% endfor %}</ul>{% endblock %}",
+        )
+        .unwrap();
+        let tool = tool(tmp.path());
+
+        let listed = tool.execute(json!({})).await.unwrap();
+        assert_eq!(listed.output, "Templates: base.html, invoice.html");
+
+        let result = tool
+            .execute(json!({
+                "template": "invoice",
+                "name": "Invoice 2026/03",
+                "data": {
+                    "title": "Invoice <March>",
+                    "items": [{"name": "Hosting", "amount": 12.5}]
+                }
+            }))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let path = tmp.path().join("exports/reports/Invoice-2026-03.html");
+        assert!(result
+            .output
+            .ends_with(&format!("[DOCUMENT:{}]", path.display())));
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "<h1>Invoice &lt;March&gt;</h1><ul><li>Hosting: 12.5</li></ul>"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_and_escaping_templates() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("templates")).unwrap();
+        std::fs::write(tmp.path().join("secret.html"), "x").unwrap();
+        let tool = tool(tmp.path());
+
+        let missing = tool.execute(json!({"template": "weekly"})).await.unwrap();
+        assert!(missing.error.unwrap().contains("has no templates"));
+        let escaping = tool
+            .execute(json!({"template": "../secret"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            escaping.error.as_deref(),
+            Some("Invalid template name '../secret'")
+        );
+    }
+
+    #[test]
+    fn output_stem_defaults_to_template_and_time() {
+        assert!(output_stem(None, "weekly.html").starts_with("weekly-20"));
+        assert_eq!(output_stem(Some(" ../x "), "weekly.html"), "x");
+        assert_eq!(output_stem(Some("///"), "weekly.html"), "report");
+    }
+}
@@ -0,0 +1,277 @@
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+const NOMINATIM_REVERSE_URL: &str = "https://nominatim.openstreetmap.org/reverse";
+const NOMINATIM_REQUEST_TIMEOUT_SECS: u64 = 15;
+/// Nominatim's usage policy allows at most one request per second.
+const NOMINATIM_MIN_INTERVAL: Duration = Duration::from_secs(1);
+/// Coordinates are cached at 4 decimal places (~11 m), enough to reuse a
+/// lookup when the same person shares their location again.
+const CACHE_PRECISION: f64 = 10_000.0;
+const CACHE_CAPACITY: usize = 512;
+
+type CacheKey = (i64, i64);
+
+/// Reverse-geocode coordinates to a street address via OpenStreetMap Nominatim.
+///
+/// Channels put shared locations in message content as `[Location: lat, lon]`;
+/// this tool turns them into a neighbourhood and city so the agent can answer
+/// "what's good to eat near me?" without guessing.
+pub struct ReverseGeocodeTool {
+    cache: Mutex<HashMap<CacheKey, String>>,
+    last_request: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl ReverseGeocodeTool {
+    pub fn new() -> Self {
+        Self {
+            cache: Mutex::new(HashMap::new()),
+            last_request: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    fn cache_key(latitude: f64, longitude: f64) -> CacheKey {
+        #[allow(clippy::cast_possible_truncation)]
+        let round = |value: f64| (value * CACHE_PRECISION).round() as i64;
+        (round(latitude), round(longitude))
+    }
+
+    fn cached(&self, key: CacheKey) -> Option<String> {
+        self.cache.lock().get(&key).cloned()
+    }
+
+    fn remember(&self, key: CacheKey, output: String) {
+        let mut cache = self.cache.lock();
+        if cache.len() >= CACHE_CAPACITY && !cache.contains_key(&key) {
+            cache.clear();
+        }
+        cache.insert(key, output);
+    }
+
+    async fn lookup(&self, latitude: f64, longitude: f64) -> anyhow::Result<String> {
+        {
+            let mut last_request = self.last_request.lock().await;
+            if let Some(wait) =
+                last_request.and_then(|at| NOMINATIM_MIN_INTERVAL.checked_sub(at.elapsed()))
+            {
+                tokio::time::sleep(wait).await;
+            }
+            *last_request = Some(Instant::now());
+        }
+
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.reverse_geocode",
+            NOMINATIM_REQUEST_TIMEOUT_SECS,
+            10,
+        );
+        let response = client
+            .get(NOMINATIM_REVERSE_URL)
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("zeroclaw/", env!("CARGO_PKG_VERSION")),
+            )
+            .query(&[
+                ("format", "jsonv2"),
+                ("lat", &latitude.to_string()),
+                ("lon", &longitude.to_string()),
+                ("addressdetails", "1"),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Nominatim returned status {}", response.status());
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        format_place(&body)
+    }
+}
+
+impl Default for ReverseGeocodeTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Summarize a Nominatim `jsonv2` reverse response as the address plus the
+/// components most useful for local recommendations.
+fn format_place(body: &serde_json::Value) -> anyhow::Result<String> {
+    if let Some(error) = body.get("error").and_then(serde_json::Value::as_str) {
+        anyhow::bail!("Nominatim could not resolve this point: {error}");
+    }
+    let display_name = body
+        .get("display_name")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("Nominatim response has no display_name"))?;
+
+    let address = body.get("address");
+    let component = |keys: &[&str]| {
+        keys.iter().find_map(|key| {
+            address
+                .and_then(|address| address.get(*key))
+                .and_then(serde_json::Value::as_str)
+        })
+    };
+    let output = json!({
+        "address": display_name,
+        "neighbourhood": component(&["neighbourhood", "suburb", "quarter"]),
+        "city": component(&["city", "town", "village", "municipality"]),
+        "state": component(&["state", "region"]),
+        "country": component(&["country"]),
+        "country_code": component(&["country_code"]),
+    });
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+pub(super) fn coordinate(args: &serde_json::Value, key: &str, limit: f64) -> Result<f64, String> {
+    let value = args
+        .get(key)
+        .and_then(serde_json::Value::as_f64)
+        .ok_or_else(|| format!("Missing '{key}' parameter"))?;
+    if !(-limit..=limit).contains(&value) {
+        return Err(format!("'{key}' must be between -{limit} and {limit}"));
+    }
+    Ok(value)
+}
+
+#[async_trait]
+impl Tool for ReverseGeocodeTool {
+    fn name(&self) -> &str {
+        "reverse_geocode"
+    }
+
+    fn description(&self) -> &str {
+        "Resolve latitude/longitude (e.g. from a [Location: lat, lon] message) to an address, neighbourhood, city and country using OpenStreetMap. Use before recommending nearby places."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "latitude": {
+                    "type": "number",
+                    "description": "Latitude in decimal degrees (-90 to 90)"
+                },
+                "longitude": {
+                    "type": "number",
+                    "description": "Longitude in decimal degrees (-180 to 180)"
+                }
+            },
+            "required": ["latitude", "longitude"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let point = coordinate(&args, "latitude", 90.0)
+            .and_then(|lat| coordinate(&args, "longitude", 180.0).map(|lon| (lat, lon)));
+        let (latitude, longitude) = match point {
+            Ok(point) => point,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                })
+            }
+        };
+
+        let key = Self::cache_key(latitude, longitude);
+        if let Some(output) = self.cached(key) {
+            return Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            });
+        }
+
+        match self.lookup(latitude, longitude).await {
+            Ok(output) => {
+                self.remember(key, output.clone());
+                Ok(ToolResult {
+                    success: true,
+                    output,
+                    error: None,
+                })
+            }
+            Err(error) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Reverse geocoding failed: {error}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_place_extracts_address_components() {
+        let body = json!({
+            "display_name": "Kottbusser Tor, Kreuzberg, Berlin, 10999, Deutschland",
+            "address": {
+                "suburb": "Kreuzberg",
+                "city": "Berlin",
+                "state": "Berlin",
+                "country": "Deutschland",
+                "country_code": "de"
+            }
+        });
+        let output: serde_json::Value =
+            serde_json::from_str(&format_place(&body).unwrap()).unwrap();
+        assert_eq!(output["neighbourhood"], "Kreuzberg");
+        assert_eq!(output["city"], "Berlin");
+        assert_eq!(output["country_code"], "de");
+        assert!(output["address"]
+            .as_str()
+            .unwrap()
+            .starts_with("Kottbusser Tor"));
+    }
+
+    #[test]
+    fn format_place_reports_nominatim_errors() {
+        let body = json!({ "error": "Unable to geocode" });
+        let error = format_place(&body).unwrap_err().to_string();
+        assert!(error.contains("Unable to geocode"));
+    }
+
+    #[tokio::test]
+    async fn cached_lookups_skip_the_network() {
+        let tool = ReverseGeocodeTool::new();
+        tool.remember(
+            ReverseGeocodeTool::cache_key(52.52, 13.405),
+            "cached".into(),
+        );
+
+        // Within ~11 m of the cached point.
+        let result = tool
+            .execute(json!({ "latitude": 52.520_01, "longitude": 13.405_01 }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "cached");
+    }
+
+    #[tokio::test]
+    async fn rejects_missing_or_out_of_range_coordinates() {
+        let tool = ReverseGeocodeTool::new();
+        let missing = tool.execute(json!({ "latitude": 10.0 })).await.unwrap();
+        assert_eq!(
+            missing.error.as_deref(),
+            Some("Missing 'longitude' parameter")
+        );
+
+        let invalid = tool
+            .execute(json!({ "latitude": 91.0, "longitude": 0.0 }))
+            .await
+            .unwrap();
+        assert!(!invalid.success);
+        assert!(invalid.error.unwrap().contains("between -90 and 90"));
+    }
+}
@@ -1,59 +1,153 @@
 use super::traits::{Tool, ToolResult};
-use crate::security::SecurityPolicy;
+use crate::config::{MultimodalConfig, ScreenshotConfig};
+use crate::providers::{ChatMessage, ChatRequest, Provider};
+use crate::security::{AutonomyLevel, SecurityPolicy};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 /// Maximum time to wait for a screenshot command to complete.
 const SCREENSHOT_TIMEOUT_SECS: u64 = 15;
+/// Maximum time to wait for text extraction.
+const OCR_TIMEOUT_SECS: u64 = 60;
 /// Maximum base64 payload size to return (2 MB of base64 ≈ 1.5 MB image).
 const MAX_BASE64_BYTES: usize = 2_097_152;
+/// Instruction sent with the image for `ocr = "vision"`.
+const VISION_OCR_PROMPT: &str = "Transcribe all text visible in this screenshot, \
+keeping its reading order and line breaks. Reply with the text only.";
+
+/// Linux capture script; `$1` is the output path and `$2` an optional window name.
+const LINUX_CAPTURE_SCRIPT: &str = r#"out="$1"; win="$2"
+if [ -n "$win" ]; then
+    if ! command -v xdotool >/dev/null 2>&1 || ! command -v import >/dev/null 2>&1; then
+        echo 'NO_WINDOW_TOOL' >&2; exit 1
+    fi
+    id=$(xdotool search --name "$win" | head -n 1)
+    [ -n "$id" ] || { echo 'NO_SUCH_WINDOW' >&2; exit 1; }
+    exec import -window "$id" "$out"
+fi
+if command -v gnome-screenshot >/dev/null 2>&1; then
+    gnome-screenshot -f "$out"
+elif command -v scrot >/dev/null 2>&1; then
+    scrot "$out"
+elif command -v import >/dev/null 2>&1; then
+    import -window root "$out"
+else
+    echo 'NO_SCREENSHOT_TOOL' >&2; exit 1
+fi"#;
+
+/// macOS window capture script; `$1` is the output path and `$2` an app name.
+const MACOS_WINDOW_SCRIPT: &str = r#"id=$(osascript -e 'on run argv' -e 'tell application (item 1 of argv) to id of window 1' -e 'end run' "$2" 2>/dev/null)
+[ -n "$id" ] || { echo 'NO_SUCH_WINDOW' >&2; exit 1; }
+exec screencapture -x -l "$id" "$1""#;
+
+/// Vision model used to read text out of screenshots.
+struct VisionOcr {
+    provider: Box<dyn Provider>,
+    model: String,
+    multimodal: MultimodalConfig,
+}
 
 /// Tool for capturing screenshots using platform-native commands.
 ///
 /// macOS: `screencapture`
-/// Linux: tries `gnome-screenshot`, `scrot`, `import` (`ImageMagick`) in order.
+/// Linux: tries `gnome-screenshot`, `scrot`, `import` (`ImageMagick`) in order;
+/// named windows use `xdotool` + `import`.
+///
+/// Captures can optionally be run through OCR (`tesseract` or a vision model)
+/// so the agent gets the on-screen text instead of image data.
 pub struct ScreenshotTool {
     security: Arc<SecurityPolicy>,
+    config: ScreenshotConfig,
+    vision: Option<VisionOcr>,
 }
 
 impl ScreenshotTool {
     pub fn new(security: Arc<SecurityPolicy>) -> Self {
-        Self { security }
+        Self {
+            security,
+            config: ScreenshotConfig::default(),
+            vision: None,
+        }
+    }
+
+    /// Apply `[screenshot]` settings (autonomy gate, OCR engine).
+    pub fn with_config(mut self, config: ScreenshotConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Model used when `screenshot.ocr = "vision"`.
+    pub fn with_vision_ocr(
+        mut self,
+        provider: Box<dyn Provider>,
+        model: String,
+        multimodal: MultimodalConfig,
+    ) -> Self {
+        self.vision = Some(VisionOcr {
+            provider,
+            model,
+            multimodal,
+        });
+        self
     }
 
     /// Determine the screenshot command for the current platform.
-    fn screenshot_command(output_path: &str) -> Option<Vec<String>> {
+    fn screenshot_command(output_path: &str, window: Option<&str>) -> Option<Vec<String>> {
         if cfg!(target_os = "macos") {
-            Some(vec![
-                "screencapture".into(),
-                "-x".into(), // no sound
-                output_path.into(),
-            ])
+            match window {
+                Some(app) => Some(vec![
+                    "sh".into(),
+                    "-c".into(),
+                    MACOS_WINDOW_SCRIPT.into(),
+                    "sh".into(),
+                    output_path.into(),
+                    app.into(),
+                ]),
+                None => Some(vec![
+                    "screencapture".into(),
+                    "-x".into(), // no sound
+                    output_path.into(),
+                ]),
+            }
         } else if cfg!(target_os = "linux") {
             Some(vec![
                 "sh".into(),
                 "-c".into(),
-                format!(
-                    "if command -v gnome-screenshot >/dev/null 2>&1; then \
-                         gnome-screenshot -f '{output_path}'; \
-                     elif command -v scrot >/dev/null 2>&1; then \
-                         scrot '{output_path}'; \
-                     elif command -v import >/dev/null 2>&1; then \
-                         import -window root '{output_path}'; \
-                     else \
-                         echo 'NO_SCREENSHOT_TOOL' >&2; exit 1; \
-                     fi"
-                ),
+                LINUX_CAPTURE_SCRIPT.into(),
+                "sh".into(),
+                output_path.into(),
+                window.unwrap_or_default().into(),
             ])
         } else {
             None
         }
     }
 
+    /// Why the current autonomy level may not take screenshots, if it may not.
+    fn autonomy_block(&self) -> Option<String> {
+        let rank = |level: AutonomyLevel| match level {
+            AutonomyLevel::ReadOnly => 0,
+            AutonomyLevel::Supervised => 1,
+            AutonomyLevel::Full => 2,
+        };
+        if !self.security.can_act() {
+            return Some("Action blocked: autonomy is read-only".into());
+        }
+        (rank(self.security.autonomy) < rank(self.config.min_autonomy)).then(|| {
+            format!(
+                "Action blocked: screenshots require autonomy level '{}'",
+                match self.config.min_autonomy {
+                    AutonomyLevel::Full => "full",
+                    _ => "supervised",
+                }
+            )
+        })
+    }
+
     /// Execute the screenshot capture and return the result.
     async fn capture(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
@@ -83,7 +177,21 @@ impl ScreenshotTool {
         let output_path = self.security.workspace_dir.join(&safe_name);
         let output_str = output_path.to_string_lossy().to_string();
 
-        let Some(mut cmd_args) = Self::screenshot_command(&output_str) else {
+        let window = args
+            .get("window")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|w| !w.is_empty());
+        let ocr = args.get("ocr").and_then(|v| v.as_bool()).unwrap_or(false);
+        if ocr && self.config.ocr == "none" {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("OCR is disabled (screenshot.ocr = \"none\")".into()),
+            });
+        }
+
+        let Some(mut cmd_args) = Self::screenshot_command(&output_str, window) else {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
@@ -92,7 +200,7 @@ impl ScreenshotTool {
         };
 
         // macOS region flags
-        if cfg!(target_os = "macos") {
+        if cfg!(target_os = "macos") && window.is_none() {
             if let Some(region) = args.get("region").and_then(|v| v.as_str()) {
                 match region {
                     "selection" => cmd_args.insert(1, "-s".into()),
@@ -115,6 +223,25 @@ impl ScreenshotTool {
             Ok(Ok(output)) => {
                 if !output.status.success() {
                     let stderr = String::from_utf8_lossy(&output.stderr);
+                    if stderr.contains("NO_WINDOW_TOOL") {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some(
+                                "Window capture needs xdotool and ImageMagick (import).".into(),
+                            ),
+                        });
+                    }
+                    if stderr.contains("NO_SUCH_WINDOW") {
+                        return Ok(ToolResult {
+                            success: false,
+                            output: String::new(),
+                            error: Some(format!(
+                                "No window found for '{}'",
+                                window.unwrap_or_default()
+                            )),
+                        });
+                    }
                     if stderr.contains("NO_SCREENSHOT_TOOL") {
                         return Ok(ToolResult {
                             success: false,
@@ -132,7 +259,11 @@ impl ScreenshotTool {
                     });
                 }
 
-                Self::read_and_encode(&output_path).await
+                if ocr {
+                    self.extract_text(&output_path).await
+                } else {
+                    Self::read_and_encode(&output_path).await
+                }
             }
             Ok(Err(e)) => Ok(ToolResult {
                 success: false,
@@ -149,6 +280,64 @@ impl ScreenshotTool {
         }
     }
 
+    /// Run the configured OCR engine over a capture and return its text.
+    async fn extract_text(&self, image: &Path) -> anyhow::Result<ToolResult> {
+        let text = tokio::time::timeout(Duration::from_secs(OCR_TIMEOUT_SECS), async {
+            match self.config.ocr.as_str() {
+                "vision" => self.vision_text(image).await,
+                _ => tesseract_text(image, &self.config.ocr_languages).await,
+            }
+        })
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after {OCR_TIMEOUT_SECS}s")));
+
+        match text {
+            Ok(text) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Screenshot saved to: {}\nText ({}):\n{}",
+                    image.display(),
+                    self.config.ocr,
+                    text.trim()
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: format!("Screenshot saved to: {}", image.display()),
+                error: Some(format!("OCR failed: {e}")),
+            }),
+        }
+    }
+
+    async fn vision_text(&self, image: &Path) -> anyhow::Result<String> {
+        let vision = self
+            .vision
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("no vision model is configured"))?;
+        if !vision.provider.supports_vision() {
+            anyhow::bail!("model '{}' does not accept images", vision.model);
+        }
+        let messages = [ChatMessage::user(format!(
+            "{VISION_OCR_PROMPT}\n[IMAGE:{}]",
+            image.display()
+        ))];
+        let prepared =
+            crate::multimodal::prepare_messages_for_provider(&messages, &vision.multimodal).await?;
+        let response = vision
+            .provider
+            .chat(
+                ChatRequest {
+                    messages: &prepared.messages,
+                    tools: None,
+                },
+                &vision.model,
+                0.0,
+            )
+            .await?;
+        Ok(response.text_or_empty().to_string())
+    }
+
     /// Read the screenshot file and return base64-encoded result.
     async fn read_and_encode(output_path: &std::path::Path) -> anyhow::Result<ToolResult> {
         // Check file size before reading to prevent OOM on large screenshots
@@ -211,6 +400,28 @@ impl ScreenshotTool {
     }
 }
 
+async fn tesseract_text(image: &Path, languages: &str) -> anyhow::Result<String> {
+    let output = tokio::process::Command::new("tesseract")
+        .arg(image)
+        .arg("stdout")
+        .arg("-l")
+        .arg(languages)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow::anyhow!("tesseract is not installed")
+            } else {
+                e.into()
+            }
+        })?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 #[async_trait]
 impl Tool for ScreenshotTool {
     fn name(&self) -> &str {
@@ -218,7 +429,7 @@ impl Tool for ScreenshotTool {
     }
 
     fn description(&self) -> &str {
-        "Capture a screenshot of the current screen. Returns the file path and base64-encoded PNG data."
+        "Capture a screenshot of the current screen or a named window. Returns the file path and base64-encoded PNG data, or the on-screen text when ocr is true."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -232,17 +443,33 @@ impl Tool for ScreenshotTool {
                 "region": {
                     "type": "string",
                     "description": "Optional region for macOS: 'selection' for interactive crop, 'window' for front window. Ignored on Linux."
+                },
+                "window": {
+                    "type": "string",
+                    "description": "Capture only this window: a window title on Linux, an application name on macOS."
+                },
+                "ocr": {
+                    "type": "boolean",
+                    "description": "Return the text on screen instead of image data.",
+                    "default": false
                 }
             }
         })
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        if !self.security.can_act() {
+        if let Some(reason) = self.autonomy_block() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(reason),
+            });
+        }
+        if !self.security.record_action() {
             return Ok(ToolResult {
                 success: false,
                 output: String::new(),
-                error: Some("Action blocked: autonomy is read-only".into()),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
             });
         }
         self.capture(args).await
@@ -281,6 +508,8 @@ mod tests {
         let schema = tool.parameters_schema();
         assert!(schema["properties"]["filename"].is_object());
         assert!(schema["properties"]["region"].is_object());
+        assert!(schema["properties"]["window"].is_object());
+        assert!(schema["properties"]["ocr"].is_object());
     }
 
     #[test]
@@ -294,7 +523,7 @@ mod tests {
     #[test]
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     fn screenshot_command_exists() {
-        let cmd = ScreenshotTool::screenshot_command("/tmp/test.png");
+        let cmd = ScreenshotTool::screenshot_command("/tmp/test.png", None);
         assert!(cmd.is_some());
         let args = cmd.unwrap();
         assert!(!args.is_empty());
@@ -313,11 +542,44 @@ mod tests {
 
     #[test]
     fn screenshot_command_contains_output_path() {
-        let cmd = ScreenshotTool::screenshot_command("/tmp/my_screenshot.png").unwrap();
+        let cmd = ScreenshotTool::screenshot_command("/tmp/my_screenshot.png", None).unwrap();
         let joined = cmd.join(" ");
         assert!(
             joined.contains("/tmp/my_screenshot.png"),
             "Command should contain the output path"
         );
     }
+
+    #[tokio::test]
+    async fn screenshot_respects_min_autonomy_and_disabled_ocr() {
+        let supervised = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Supervised,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        });
+        let tool = ScreenshotTool::new(supervised.clone()).with_config(ScreenshotConfig::default());
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .contains("require autonomy level 'full'"));
+
+        let tool = ScreenshotTool::new(supervised).with_config(ScreenshotConfig {
+            min_autonomy: AutonomyLevel::Supervised,
+            ocr: "none".into(),
+            ..ScreenshotConfig::default()
+        });
+        let result = tool.execute(json!({"ocr": true})).await.unwrap();
+        assert!(result.error.unwrap().contains("OCR is disabled"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn window_name_is_passed_as_an_argument() {
+        let cmd =
+            ScreenshotTool::screenshot_command("/tmp/w.png", Some("Firefox'; rm -rf ~")).unwrap();
+        assert_eq!(cmd[2], LINUX_CAPTURE_SCRIPT);
+        assert_eq!(&cmd[4..], ["/tmp/w.png", "Firefox'; rm -rf ~"]);
+    }
 }
@@ -0,0 +1,474 @@
+//! Outbound email (`send_email`), separate from the inbound email channel.
+//!
+//! Bodies are written by the agent or rendered from a template in
+//! `<workspace>/templates`, and workspace files can be attached. In
+//! supervised mode the first call only returns the rendered email; it is
+//! sent when the agent calls again with `approved=true` after the user
+//! confirms.
+
+use super::report_render::{render, resolve_template};
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::SendEmailConfig;
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use anyhow::Context;
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde_json::json;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Email template suffixes, tried in order when a name has no extension.
+const EMAIL_TEMPLATE_SUFFIXES: &[&str] = &[".txt", ".txt.j2", ".html", ".html.j2"];
+const SMTP_TIMEOUT_SECS: u64 = 30;
+
+fn recipient_allowed(allowed: &[String], address: &str) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+    let address = address.to_ascii_lowercase();
+    let domain = address.rsplit_once('@').map_or("", |(_, domain)| domain);
+    allowed.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        // "@example.com" and "example.com" both allow the whole domain.
+        let entry_domain = entry.strip_prefix('@').unwrap_or(&entry);
+        entry == "*" || entry == address || (!entry_domain.contains('@') && entry_domain == domain)
+    })
+}
+
+fn string_list(args: &serde_json::Value, key: &str) -> Vec<String> {
+    match args.get(key) {
+        Some(serde_json::Value::String(value)) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// A file from the workspace, ready to attach.
+struct EmailAttachment {
+    name: String,
+    content_type: String,
+    bytes: Vec<u8>,
+}
+
+/// Everything needed to show and send one email.
+struct Draft {
+    to: Vec<Mailbox>,
+    cc: Vec<Mailbox>,
+    subject: String,
+    body: String,
+    html: bool,
+    attachments: Vec<EmailAttachment>,
+}
+
+impl Draft {
+    fn preview(&self, from: &str) -> String {
+        let join = |boxes: &[Mailbox]| {
+            boxes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut text = format!("From: {from}\nTo: {}\n", join(&self.to));
+        if !self.cc.is_empty() {
+            let _ = writeln!(text, "Cc: {}", join(&self.cc));
+        }
+        let _ = writeln!(text, "Subject: {}", self.subject);
+        for attachment in &self.attachments {
+            let _ = writeln!(
+                text,
+                "Attachment: {} ({} KB)",
+                attachment.name,
+                attachment.bytes.len().div_ceil(1024)
+            );
+        }
+        let _ = write!(text, "\n{}", self.body);
+        text
+    }
+
+    fn message(&self, from: Mailbox) -> anyhow::Result<Message> {
+        let mut builder = Message::builder().from(from).subject(self.subject.clone());
+        for to in &self.to {
+            builder = builder.to(to.clone());
+        }
+        for cc in &self.cc {
+            builder = builder.cc(cc.clone());
+        }
+        let body = if self.html {
+            SinglePart::html(self.body.clone())
+        } else {
+            SinglePart::plain(self.body.clone())
+        };
+        if self.attachments.is_empty() {
+            return Ok(builder.singlepart(body)?);
+        }
+        let mut parts = MultiPart::mixed().singlepart(body);
+        for attachment in &self.attachments {
+            let content_type = ContentType::parse(&attachment.content_type)
+                .unwrap_or(ContentType::parse("application/octet-stream")?);
+            parts = parts.singlepart(
+                Attachment::new(attachment.name.clone())
+                    .body(attachment.bytes.clone(), content_type),
+            );
+        }
+        Ok(builder.multipart(parts)?)
+    }
+}
+
+/// Sends email over SMTP from `[send_email]`.
+pub struct SendEmailTool {
+    config: SendEmailConfig,
+    security: Arc<SecurityPolicy>,
+}
+
+impl SendEmailTool {
+    pub fn new(config: SendEmailConfig, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn mailboxes(&self, addresses: &[String]) -> Result<Vec<Mailbox>, String> {
+        addresses
+            .iter()
+            .map(|address| {
+                let mailbox: Mailbox = address
+                    .parse()
+                    .map_err(|_| format!("'{address}' is not an email address"))?;
+                if !recipient_allowed(&self.config.allowed_recipients, mailbox.email.as_ref()) {
+                    return Err(format!(
+                        "'{}' is not in send_email.allowed_recipients",
+                        mailbox.email
+                    ));
+                }
+                Ok(mailbox)
+            })
+            .collect()
+    }
+
+    async fn attachment(&self, path: &str) -> Result<EmailAttachment, String> {
+        if !self.security.is_path_allowed(path) {
+            return Err(format!("Path not allowed by security policy: {path}"));
+        }
+        let full_path = self.security.workspace_dir.join(path);
+        let resolved = tokio::fs::canonicalize(&full_path)
+            .await
+            .map_err(|e| format!("Attachment '{path}' not found: {e}"))?;
+        if !self.security.is_resolved_path_allowed(&resolved) || !resolved.is_file() {
+            return Err(format!("Attachment '{path}' is not an allowed file"));
+        }
+        let bytes = tokio::fs::read(&resolved)
+            .await
+            .map_err(|e| format!("Failed to read '{path}': {e}"))?;
+        let limit = self.config.max_attachment_mb.saturating_mul(1024 * 1024);
+        if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > limit {
+            return Err(format!(
+                "Attachment '{path}' is larger than {} MB",
+                self.config.max_attachment_mb
+            ));
+        }
+        Ok(EmailAttachment {
+            name: resolved.file_name().map_or_else(
+                || "attachment".into(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            content_type: mime_guess::from_path(&resolved)
+                .first_or_octet_stream()
+                .to_string(),
+            bytes,
+        })
+    }
+
+    async fn draft(&self, args: &serde_json::Value) -> Result<Draft, String> {
+        let to = self.mailboxes(&string_list(args, "to"))?;
+        if to.is_empty() {
+            return Err("Missing 'to'".into());
+        }
+        let cc = self.mailboxes(&string_list(args, "cc"))?;
+        let subject = args
+            .get("subject")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|subject| !subject.is_empty())
+            .ok_or("Missing 'subject'")?
+            .to_string();
+
+        let template = args
+            .get("template")
+            .and_then(serde_json::Value::as_str)
+            .filter(|name| !name.trim().is_empty());
+        let (body, html) = if let Some(template) = template {
+            let dir = self.security.workspace_dir.join("templates");
+            let template = resolve_template(&dir, template, EMAIL_TEMPLATE_SUFFIXES)
+                .map_err(|e| e.to_string())?;
+            let data = args.get("data").cloned().unwrap_or_else(|| json!({}));
+            let body = render(&dir, &template, &data).map_err(|e| format!("{e:#}"))?;
+            let html = template.ends_with(".html") || template.ends_with(".html.j2");
+            (body, html)
+        } else {
+            let body = args
+                .get("body")
+                .and_then(serde_json::Value::as_str)
+                .filter(|body| !body.trim().is_empty())
+                .ok_or("Give a 'body' or a 'template'")?;
+            (body.to_string(), false)
+        };
+
+        let mut attachments = Vec::new();
+        for path in string_list(args, "attachments") {
+            attachments.push(self.attachment(&path).await?);
+        }
+        Ok(Draft {
+            to,
+            cc,
+            subject,
+            body,
+            html,
+            attachments,
+        })
+    }
+
+    fn transport(&self) -> anyhow::Result<SmtpTransport> {
+        let host = self.config.smtp_host.trim();
+        let builder = if self.config.smtp_tls {
+            SmtpTransport::relay(host)?
+        } else {
+            SmtpTransport::builder_dangerous(host)
+        };
+        Ok(builder
+            .port(self.config.smtp_port)
+            .credentials(Credentials::new(
+                self.config.username.clone(),
+                self.config.password.clone().unwrap_or_default(),
+            ))
+            .timeout(Some(Duration::from_secs(SMTP_TIMEOUT_SECS)))
+            .build())
+    }
+}
+
+#[async_trait]
+impl Tool for SendEmailTool {
+    fn name(&self) -> &str {
+        "send_email"
+    }
+
+    fn description(&self) -> &str {
+        "Send an email from the user's configured address, e.g. 'email this summary to my \
+         accountant'. Give to, subject, and either a body or a template from the workspace \
+         templates folder with data; attachments are workspace file paths. In supervised mode the \
+         first call returns the full email for the user to review; call again with approved=true \
+         only after they confirm."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "to": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Recipient addresses"
+                },
+                "cc": { "type": "array", "items": { "type": "string" } },
+                "subject": { "type": "string" },
+                "body": { "type": "string", "description": "Plain-text body (when no template is used)" },
+                "template": { "type": "string", "description": "Template name, e.g. 'invoice' for templates/invoice.txt or .html" },
+                "data": { "type": "object", "description": "Values the template uses" },
+                "attachments": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Workspace file paths to attach"
+                },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true only after the user confirmed the previewed email",
+                    "default": false
+                }
+            },
+            "required": ["to", "subject"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let draft = match self.draft(&args).await {
+            Ok(draft) => draft,
+            Err(e) => return Ok(failure(e)),
+        };
+        let preview = draft.preview(&self.config.from_address);
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.security.autonomy == AutonomyLevel::Supervised && !approved {
+            return Ok(ToolResult {
+                success: false,
+                output: preview,
+                error: Some(
+                    "Not sent: show the user this email and call send_email again with \
+                     approved=true once they confirm"
+                        .into(),
+                ),
+            });
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "send_email")
+        {
+            return Ok(failure(e));
+        }
+
+        let from: Mailbox = self
+            .config
+            .from_address
+            .parse()
+            .context("send_email.from_address is not an email address")?;
+        let message = match draft.message(from) {
+            Ok(message) => message,
+            Err(e) => return Ok(failure(format!("Failed to build email: {e}"))),
+        };
+        let transport = self.transport()?;
+        let sent = tokio::task::spawn_blocking(move || transport.send(&message)).await?;
+        match sent {
+            Ok(_) => {
+                let recipients: Vec<String> =
+                    draft.to.iter().map(|to| to.email.to_string()).collect();
+                tracing::info!(
+                    "send_email: sent '{}' to {}",
+                    draft.subject,
+                    recipients.join(", ")
+                );
+                Ok(ToolResult {
+                    success: true,
+                    output: format!("Sent:\n{preview}"),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(failure(format!("SMTP send failed: {e}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tool(workspace: &std::path::Path, autonomy: AutonomyLevel) -> SendEmailTool {
+        let config = SendEmailConfig {
+            enabled: true,
+            smtp_host: "smtp.invalid".into(),
+            username: "me@example.com".into(),
+            password: Some("secret".into()),
+            from_address: "Me <me@example.com>".into(),
+            allowed_recipients: vec!["@accounting.example".into()],
+            ..SendEmailConfig::default()
+        };
+        let security = SecurityPolicy {
+            autonomy,
+            workspace_dir: workspace.to_path_buf(),
+            ..SecurityPolicy::default()
+        };
+        SendEmailTool::new(config, Arc::new(security))
+    }
+
+    #[test]
+    fn recipients_match_addresses_and_domains() {
+        let allowed = vec!["bob@example.com".into(), "@accounting.example".into()];
+        assert!(recipient_allowed(&allowed, "Bob@Example.com"));
+        assert!(recipient_allowed(&allowed, "carol@accounting.example"));
+        assert!(!recipient_allowed(&allowed, "eve@example.com"));
+        assert!(recipient_allowed(
+            &["example.com".into()],
+            "eve@example.com"
+        ));
+        assert!(recipient_allowed(&[], "anyone@anywhere.example"));
+    }
+
+    #[tokio::test]
+    async fn supervised_mode_returns_rendered_preview_with_attachments() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join("templates")).unwrap();
+        std::fs::write(
+            tmp.path().join("templates/summary.txt"),
+            "Hi {{ name }},\n\nTotal this month: {{ total }}",
+        )
+        .unwrap();
+        std::fs::write(tmp.path().join("march.pdf"), vec![0u8; 2048]).unwrap();
+        let tool = tool(tmp.path(), AutonomyLevel::Supervised);
+
+        let result = tool
+            .execute(json!({
+                "to": ["tax@accounting.example"],
+                "subject": "March summary",
+                "template": "summary",
+                "data": {"name": "Ana", "total": "1,200 EUR"},
+                "attachments": ["march.pdf"]
+            }))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("approved=true"));
+        assert_eq!(
+            result.output,
+            "From: Me <me@example.com>\nTo: tax@accounting.example\nSubject: March summary\n\
+             Attachment: march.pdf (2 KB)\n\nHi Ana,\n\nTotal this month: 1,200 EUR"
+        );
+
+        let blocked = tool
+            .execute(json!({"to": "eve@example.com", "subject": "x", "body": "y"}))
+            .await
+            .unwrap();
+        assert!(blocked.error.unwrap().contains("allowed_recipients"));
+
+        let outside = tool
+            .execute(json!({
+                "to": "tax@accounting.example",
+                "subject": "x",
+                "body": "y",
+                "attachments": ["/etc/passwd"]
+            }))
+            .await
+            .unwrap();
+        assert!(!outside.success);
+        assert!(outside.output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn message_carries_html_body_and_attachment() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("notes.txt"), "line").unwrap();
+        let tool = tool(tmp.path(), AutonomyLevel::Full);
+        let draft = tool
+            .draft(&json!({
+                "to": "tax@accounting.example",
+                "subject": "Notes",
+                "body": "See attached.",
+                "attachments": "notes.txt"
+            }))
+            .await
+            .unwrap();
+        let message = draft
+            .message("me@example.com".parse().unwrap())
+            .unwrap()
+            .formatted();
+        let message = String::from_utf8_lossy(&message);
+        assert!(message.contains("Subject: Notes"));
+        assert!(message.contains("filename=\"notes.txt\""));
+        assert!(message.contains("Content-Type: text/plain"));
+    }
+}
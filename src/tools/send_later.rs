@@ -0,0 +1,384 @@
+//! Scheduled outgoing messages (`send_later`).
+//!
+//! "At 9am tomorrow text Jamie happy birthday" becomes a one-shot reminder
+//! job named `send_later:<requester>` that the scheduler delivers verbatim
+//! to the recipient, through the running channel when the daemon is up.
+//! Messages are listed and cancelled per requesting conversation, which is
+//! also what `[send_later]` limits apply to. In supervised mode the first
+//! call only returns the resolved time; the message is scheduled when the
+//! agent calls again with `approved=true` after the user confirms.
+
+use super::remind_me::{current_route, non_empty_str, parse_when};
+use super::timer::identity;
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::Config;
+use crate::cron::{self, DeliveryConfig, SEND_LATER_NAME_PREFIX};
+use crate::security::policy::ToolOperation;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Schedules, lists, and cancels messages sent later on the user's behalf.
+pub struct SendLaterTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl SendLaterTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    /// Job name for messages scheduled from the current conversation.
+    fn job_name() -> String {
+        format!(
+            "{SEND_LATER_NAME_PREFIX}{}",
+            identity(current_route().as_ref())
+        )
+    }
+
+    fn pending(&self) -> anyhow::Result<Vec<cron::CronJob>> {
+        let name = Self::job_name();
+        Ok(cron::list_jobs(&self.config)?
+            .into_iter()
+            .filter(|job| job.is_scheduled_send() && job.name.as_deref() == Some(name.as_str()))
+            .collect())
+    }
+
+    fn channel(&self, args: &serde_json::Value) -> Result<String, String> {
+        let channel = non_empty_str(args, "channel")
+            .map(str::to_ascii_lowercase)
+            .or_else(|| current_route().map(|route| route.channel))
+            .ok_or("Missing 'channel' parameter")?;
+        let allowed = &self.config.send_later.channels;
+        if !allowed.iter().any(|c| c.eq_ignore_ascii_case(&channel)) {
+            return Err(format!(
+                "Messages cannot be scheduled on '{channel}'; allowed channels: {}",
+                allowed.join(", ")
+            ));
+        }
+        Ok(channel)
+    }
+
+    fn handle_set(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(message) = non_empty_str(args, "message") else {
+            return Ok(failure("Missing 'message' parameter"));
+        };
+        let Some(when) = non_empty_str(args, "when") else {
+            return Ok(failure("Missing 'when' parameter"));
+        };
+        let Some(to) = non_empty_str(args, "to") else {
+            return Ok(failure("Missing 'to' parameter"));
+        };
+        let channel = match self.channel(args) {
+            Ok(channel) => channel,
+            Err(e) => return Ok(failure(e)),
+        };
+        let tz = match non_empty_str(args, "tz").unwrap_or("UTC").parse::<Tz>() {
+            Ok(tz) => tz,
+            Err(e) => return Ok(failure(format!("Invalid timezone: {e}"))),
+        };
+        let at = match parse_when(when, Utc::now().with_timezone(&tz)) {
+            Ok(at) => at,
+            Err(e) => return Ok(failure(format!("Could not understand '{when}': {e}"))),
+        };
+        let max_days = self.config.send_later.max_days_ahead;
+        let latest = Utc::now().checked_add_signed(Duration::days(i64::from(max_days)));
+        if latest.is_some_and(|latest| at > latest) {
+            return Ok(failure(format!(
+                "Messages can be scheduled at most {max_days} days ahead"
+            )));
+        }
+        let max_pending = self.config.send_later.max_pending_per_identity;
+        if self.pending()?.len() >= max_pending {
+            return Ok(failure(format!(
+                "Too many scheduled messages (max {max_pending}); cancel one first"
+            )));
+        }
+
+        let local = at.with_timezone(&tz);
+        let resolved = format!("{} ({tz})", local.format("%a %Y-%m-%d %H:%M"));
+        let approved = args
+            .get("approved")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        if self.security.autonomy == AutonomyLevel::Supervised && !approved {
+            return Ok(ToolResult {
+                success: false,
+                output: format!("Send to {to} on {channel} at {resolved}:\n{message}"),
+                error: Some(
+                    "Not scheduled: confirm the time and message with the user and call \
+                     send_later again with approved=true"
+                        .into(),
+                ),
+            });
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "send_later")
+        {
+            return Ok(failure(e));
+        }
+
+        let delivery = DeliveryConfig {
+            mode: "announce".to_string(),
+            channel: Some(channel.clone()),
+            to: Some(to.to_string()),
+            best_effort: true,
+            recipients: Vec::new(),
+        };
+        match cron::add_reminder_job(&self.config, Some(Self::job_name()), at, message, delivery) {
+            Ok(job) => Ok(ToolResult {
+                success: true,
+                output: serde_json::to_string_pretty(&json!({
+                    "id": job.id,
+                    "scheduled_for": resolved,
+                    "send_at": local.to_rfc3339(),
+                    "send_at_utc": at.to_rfc3339(),
+                    "channel": channel,
+                    "to": to,
+                    "message": message,
+                }))?,
+                error: None,
+            }),
+            Err(e) => Ok(failure(e.to_string())),
+        }
+    }
+
+    fn handle_list(&self) -> anyhow::Result<ToolResult> {
+        let messages: Vec<_> = self
+            .pending()?
+            .into_iter()
+            .map(|job| {
+                json!({
+                    "id": job.id,
+                    "message": job.prompt,
+                    "send_at_utc": job.next_run.to_rfc3339(),
+                    "channel": job.delivery.channel,
+                    "to": job.delivery.to,
+                })
+            })
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&messages)?,
+            error: None,
+        })
+    }
+
+    fn handle_cancel(&self, args: &serde_json::Value) -> anyhow::Result<ToolResult> {
+        let Some(id) = non_empty_str(args, "id") else {
+            return Ok(failure("Missing 'id' parameter"));
+        };
+        if !self.pending()?.iter().any(|job| job.id == id) {
+            return Ok(failure(format!("No scheduled message with id '{id}'")));
+        }
+        if let Err(e) = self
+            .security
+            .enforce_tool_operation(ToolOperation::Act, "send_later")
+        {
+            return Ok(failure(e));
+        }
+        match cron::remove_job(&self.config, id) {
+            Ok(()) => Ok(ToolResult {
+                success: true,
+                output: format!("Cancelled scheduled message {id}"),
+                error: None,
+            }),
+            Err(e) => Ok(failure(e.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for SendLaterTool {
+    fn name(&self) -> &str {
+        "send_later"
+    }
+
+    fn description(&self) -> &str {
+        "Schedule a message to someone, or list and cancel scheduled messages. For \"at 9am \
+         tomorrow text Jamie happy birthday\" use action='set', when='tomorrow 9am', \
+         message='Happy birthday!', and Jamie's chat ID or number in 'to' (look it up with \
+         contacts first). 'when' accepts the same phrases as remind_me; pass the user's IANA \
+         timezone in 'tz' when known. In supervised mode the first call returns the resolved time \
+         for the user to confirm; call again with approved=true only after they do."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["set", "list", "cancel"],
+                    "default": "set"
+                },
+                "message": { "type": "string", "description": "Text to send, exactly as it should arrive" },
+                "when": { "type": "string", "description": "When to send it, in natural language" },
+                "tz": { "type": "string", "description": "IANA timezone for wall-clock times (default UTC)" },
+                "channel": { "type": "string", "enum": self.config.send_later.channels },
+                "to": { "type": "string", "description": "Recipient chat ID, phone number, or email" },
+                "id": { "type": "string", "description": "Scheduled message ID (for cancel)" },
+                "approved": {
+                    "type": "boolean",
+                    "description": "Set true only after the user confirmed the resolved time",
+                    "default": false
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        if !self.config.cron.enabled {
+            return Ok(failure(
+                "scheduled messages need the scheduler, which is disabled by config (cron.enabled=false)",
+            ));
+        }
+        match args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("set")
+        {
+            "set" => self.handle_set(&args),
+            "list" => self.handle_list(),
+            "cancel" => self.handle_cancel(&args),
+            other => Ok(failure(format!("Unknown action '{other}'"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SendLaterConfig;
+    use crate::tools::remind_me::{with_request_route, ReminderRoute};
+    use tempfile::TempDir;
+
+    fn tool(tmp: &TempDir, autonomy: AutonomyLevel) -> SendLaterTool {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            send_later: SendLaterConfig {
+                enabled: true,
+                max_pending_per_identity: 2,
+                ..SendLaterConfig::default()
+            },
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let security = Arc::new(SecurityPolicy {
+            autonomy,
+            ..SecurityPolicy::default()
+        });
+        SendLaterTool::new(Arc::new(config), security)
+    }
+
+    fn route(recipient: &str) -> Option<ReminderRoute> {
+        Some(ReminderRoute {
+            channel: "telegram".into(),
+            recipient: recipient.into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn supervised_mode_returns_resolved_time_until_approved() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Supervised);
+        let args = json!({
+            "when": "tomorrow 9am",
+            "tz": "America/New_York",
+            "to": "jamie",
+            "message": "Happy birthday!"
+        });
+
+        let preview = with_request_route(route("me"), tool.execute(args.clone()))
+            .await
+            .unwrap();
+        assert!(!preview.success);
+        assert!(preview.error.unwrap().contains("approved=true"));
+        assert!(preview.output.starts_with("Send to jamie on telegram at "));
+        assert!(preview
+            .output
+            .ends_with(" 09:00 (America/New_York):\nHappy birthday!"));
+        assert!(tool.pending().unwrap().is_empty());
+
+        let mut approved = args;
+        approved["approved"] = json!(true);
+        approved["when"] = json!("in 2 hours");
+        let scheduled = with_request_route(route("me"), tool.execute(approved))
+            .await
+            .unwrap();
+        assert!(scheduled.success, "{:?}", scheduled.error);
+
+        let slack = with_request_route(
+            route("me"),
+            tool.execute(json!({
+                "when": "in 1 hour",
+                "channel": "slack",
+                "to": "C1",
+                "message": "hi",
+                "approved": true
+            })),
+        )
+        .await
+        .unwrap();
+        assert!(slack
+            .error
+            .unwrap()
+            .contains("cannot be scheduled on 'slack'"));
+    }
+
+    #[tokio::test]
+    async fn limits_list_and_cancel_apply_per_requesting_conversation() {
+        let tmp = TempDir::new().unwrap();
+        let tool = tool(&tmp, AutonomyLevel::Full);
+        let set = |when: &str| json!({ "when": when, "to": "jamie", "message": "see you soon" });
+
+        let first = with_request_route(route("me"), tool.execute(set("in 1 hour")))
+            .await
+            .unwrap();
+        assert!(first.success, "{:?}", first.error);
+        let id = serde_json::from_str::<serde_json::Value>(&first.output).unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        with_request_route(route("me"), tool.execute(set("in 2 hours")))
+            .await
+            .unwrap();
+        let over = with_request_route(route("me"), tool.execute(set("in 3 hours")))
+            .await
+            .unwrap();
+        assert!(over.error.unwrap().contains("Too many scheduled messages"));
+        let too_far = with_request_route(route("other"), tool.execute(set("in 90 days")))
+            .await
+            .unwrap();
+        assert!(too_far.error.unwrap().contains("at most 30 days"));
+
+        let others = with_request_route(route("other"), tool.execute(json!({"action": "list"})))
+            .await
+            .unwrap();
+        assert_eq!(others.output, "[]");
+        let not_theirs = with_request_route(
+            route("other"),
+            tool.execute(json!({"action": "cancel", "id": id})),
+        )
+        .await
+        .unwrap();
+        assert!(!not_theirs.success);
+
+        let cancelled = with_request_route(
+            route("me"),
+            tool.execute(json!({"action": "cancel", "id": id})),
+        )
+        .await
+        .unwrap();
+        assert!(cancelled.success, "{:?}", cancelled.error);
+        let remaining = with_request_route(route("me"), async { tool.pending().unwrap() }).await;
+        assert_eq!(remaining.len(), 1);
+    }
+}
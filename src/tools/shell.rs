@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::runtime::RuntimeAdapter;
+use crate::config::SshRuntimeConfig;
+use crate::runtime::{RuntimeAdapter, SshRuntime};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -17,15 +18,168 @@ const SAFE_ENV_VARS: &[&str] = &[
     "PATH", "HOME", "TERM", "LANG", "LC_ALL", "LC_CTYPE", "USER", "SHELL", "TMPDIR",
 ];
 
+/// Interim output of a running shell command, published to a [`ShellOutputStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShellOutputPreview {
+    /// Trailing stdout of the command so far.
+    Output { command: String, tail: String },
+    /// The command exited (or timed out); any displayed preview can be cleared.
+    Finished,
+}
+
+/// Sink for live stdout previews of long-running commands.
+///
+/// Installed per agent run with [`with_output_stream`]; [`ShellTool`] publishes
+/// the trailing output every `interval` while a command is still running.
+#[derive(Debug, Clone)]
+pub struct ShellOutputStream {
+    tx: tokio::sync::mpsc::Sender<ShellOutputPreview>,
+    interval: Duration,
+    max_preview_chars: usize,
+}
+
+impl ShellOutputStream {
+    pub fn new(
+        tx: tokio::sync::mpsc::Sender<ShellOutputPreview>,
+        interval: Duration,
+        max_preview_chars: usize,
+    ) -> Self {
+        Self {
+            tx,
+            interval,
+            max_preview_chars,
+        }
+    }
+
+    fn publish(&self, command: &str, stdout: &[u8]) {
+        let text = crate::agent::loop_::scrub_credentials(&String::from_utf8_lossy(stdout));
+        let text = text.trim_end();
+        let char_count = text.chars().count();
+        let tail = if char_count > self.max_preview_chars {
+            let skipped: String = text
+                .chars()
+                .skip(char_count - self.max_preview_chars)
+                .collect();
+            format!("...{skipped}")
+        } else {
+            text.to_string()
+        };
+        // Previews are best-effort: drop them rather than stall the command.
+        let _ = self.tx.try_send(ShellOutputPreview::Output {
+            command: command.to_string(),
+            tail,
+        });
+    }
+}
+
+tokio::task_local! {
+    static SHELL_OUTPUT_STREAM: ShellOutputStream;
+}
+
+/// Run `future` with `stream` receiving live output from shell commands it executes.
+pub async fn with_output_stream<F: std::future::Future>(
+    stream: Option<ShellOutputStream>,
+    future: F,
+) -> F::Output {
+    match stream {
+        Some(stream) => SHELL_OUTPUT_STREAM.scope(stream, future).await,
+        None => future.await,
+    }
+}
+
+/// Run `cmd` to completion, publishing stdout previews to `stream` when present.
+async fn run_command(
+    cmd: &mut tokio::process::Command,
+    command: &str,
+    stream: Option<&ShellOutputStream>,
+) -> std::io::Result<std::process::Output> {
+    use tokio::io::AsyncReadExt;
+
+    let Some(stream) = stream else {
+        return cmd.output().await;
+    };
+
+    cmd.stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = cmd.spawn()?;
+    let mut stdout_pipe = child
+        .stdout
+        .take()
+        .ok_or_else(|| std::io::Error::other("stdout not captured"))?;
+    let stderr_pipe = child.stderr.take();
+    let stderr_task = tokio::spawn(async move {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = stderr_pipe {
+            let _ = pipe.read_to_end(&mut buf).await;
+        }
+        buf
+    });
+
+    let mut stdout = Vec::new();
+    let mut chunk = [0_u8; 4096];
+    let mut ticker = tokio::time::interval(stream.interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick fires immediately; skip it so fast commands stay silent.
+    ticker.tick().await;
+    let mut pending_preview = false;
+    loop {
+        tokio::select! {
+            read = stdout_pipe.read(&mut chunk) => match read? {
+                0 => break,
+                n => {
+                    stdout.extend_from_slice(&chunk[..n]);
+                    pending_preview = true;
+                }
+            },
+            _ = ticker.tick() => {
+                if pending_preview {
+                    stream.publish(command, &stdout);
+                    pending_preview = false;
+                }
+            }
+        }
+    }
+
+    let status = child.wait().await?;
+    let stderr = stderr_task.await.unwrap_or_default();
+    Ok(std::process::Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
 /// Shell command execution tool with sandboxing
 pub struct ShellTool {
     security: Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
+    /// Remote hosts a call may target with `host`.
+    ssh_hosts: Arc<SshRuntimeConfig>,
 }
 
 impl ShellTool {
     pub fn new(security: Arc<SecurityPolicy>, runtime: Arc<dyn RuntimeAdapter>) -> Self {
-        Self { security, runtime }
+        Self {
+            security,
+            runtime,
+            ssh_hosts: Arc::new(SshRuntimeConfig::default()),
+        }
+    }
+
+    /// Allow calls to run on the hosts in `[runtime.ssh.hosts]`.
+    pub fn with_ssh_hosts(mut self, ssh_hosts: Arc<SshRuntimeConfig>) -> Self {
+        self.ssh_hosts = ssh_hosts;
+        self
+    }
+
+    /// The runtime for a call: the named SSH host, or the default runtime.
+    fn runtime_for(&self, host: Option<&str>) -> anyhow::Result<Arc<dyn RuntimeAdapter>> {
+        match host {
+            Some(host) => Ok(Arc::new(SshRuntime::new(&self.ssh_hosts, host)?)),
+            None => Ok(Arc::clone(&self.runtime)),
+        }
     }
 }
 
@@ -47,7 +201,10 @@ fn collect_allowed_shell_env_vars(security: &SecurityPolicy) -> Vec<String> {
         .chain(security.shell_env_passthrough.iter().map(|s| s.as_str()))
     {
         let candidate = key.trim();
-        if candidate.is_empty() || !is_valid_env_var_name(candidate) {
+        if candidate.is_empty()
+            || !is_valid_env_var_name(candidate)
+            || security.is_env_var_blocked(candidate)
+        {
             continue;
         }
         if seen.insert(candidate.to_string()) {
@@ -68,7 +225,7 @@ impl Tool for ShellTool {
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
-        json!({
+        let mut schema = json!({
             "type": "object",
             "properties": {
                 "command": {
@@ -82,7 +239,17 @@ impl Tool for ShellTool {
                 }
             },
             "required": ["command"]
-        })
+        });
+        if !self.ssh_hosts.hosts.is_empty() {
+            let mut hosts: Vec<&String> = self.ssh_hosts.hosts.keys().collect();
+            hosts.sort();
+            schema["properties"]["host"] = json!({
+                "type": "string",
+                "enum": hosts,
+                "description": "Run the command on this remote host over SSH instead of locally"
+            });
+        }
+        schema
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
@@ -94,6 +261,16 @@ impl Tool for ShellTool {
             .get("approved")
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
+        let runtime = match self.runtime_for(args.get("host").and_then(|v| v.as_str())) {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                });
+            }
+        };
 
         if self.security.is_rate_limited() {
             return Ok(ToolResult {
@@ -133,10 +310,7 @@ impl Tool for ShellTool {
         // Execute with timeout to prevent hanging commands.
         // Clear the environment to prevent leaking API keys and other secrets
         // (CWE-200), then re-add only safe, functional variables.
-        let mut cmd = match self
-            .runtime
-            .build_shell_command(command, &self.security.workspace_dir)
-        {
+        let mut cmd = match runtime.build_shell_command(command, &self.security.workspace_dir) {
             Ok(cmd) => cmd,
             Err(e) => {
                 return Ok(ToolResult {
@@ -154,8 +328,15 @@ impl Tool for ShellTool {
             }
         }
 
-        let result =
-            tokio::time::timeout(Duration::from_secs(SHELL_TIMEOUT_SECS), cmd.output()).await;
+        let stream = SHELL_OUTPUT_STREAM.try_with(Clone::clone).ok();
+        let result = tokio::time::timeout(
+            Duration::from_secs(SHELL_TIMEOUT_SECS),
+            run_command(&mut cmd, command, stream.as_ref()),
+        )
+        .await;
+        if let Some(stream) = &stream {
+            let _ = stream.tx.send(ShellOutputPreview::Finished).await;
+        }
 
         match result {
             Ok(Ok(output)) => {
@@ -216,6 +397,69 @@ mod tests {
         Arc::new(NativeRuntime::new())
     }
 
+    #[tokio::test]
+    async fn run_command_streams_output_previews() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+        let stream = ShellOutputStream::new(tx, Duration::from_millis(50), 8);
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg("echo first-line; sleep 0.3; echo second");
+
+        let output = run_command(&mut cmd, "demo", Some(&stream)).await.unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "first-line\nsecond\n"
+        );
+
+        let preview = rx.try_recv().expect("preview published while running");
+        assert_eq!(
+            preview,
+            ShellOutputPreview::Output {
+                command: "demo".into(),
+                tail: "...rst-line".into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn run_command_without_stream_publishes_nothing() {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg("echo quick");
+        let output = run_command(&mut cmd, "demo", None).await.unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "quick\n");
+    }
+
+    #[tokio::test]
+    async fn shell_host_must_be_a_configured_ssh_host() {
+        let ssh_hosts = SshRuntimeConfig {
+            hosts: std::collections::HashMap::from([(
+                "workstation".to_string(),
+                crate::config::SshHostConfig {
+                    host: "dev.lan".into(),
+                    identity_file: "/keys/nas".into(),
+                    ..crate::config::SshHostConfig::default()
+                },
+            )]),
+            ..SshRuntimeConfig::default()
+        };
+        let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime())
+            .with_ssh_hosts(Arc::new(ssh_hosts));
+        assert_eq!(
+            tool.parameters_schema()["properties"]["host"]["enum"],
+            json!(["workstation"])
+        );
+
+        let result = tool
+            .execute(json!({"command": "echo hi", "host": "prod"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not in runtime.ssh.hosts"));
+
+        let plain = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
+        assert!(plain.parameters_schema()["properties"]["host"].is_null());
+    }
+
     #[test]
     fn shell_tool_name() {
         let tool = ShellTool::new(test_security(AutonomyLevel::Supervised), test_runtime());
@@ -0,0 +1,381 @@
+use super::shell::ShellTool;
+use super::traits::{Tool, ToolResult};
+use crate::config::SshRuntimeConfig;
+use crate::runtime::RuntimeAdapter;
+use crate::security::SecurityPolicy;
+use crate::skills::{is_valid_tool_name, SkillTool, SkillToolParameter};
+use crate::util::shell_quote;
+use async_trait::async_trait;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const SUPPORTED_PARAMETER_TYPES: &[&str] = &["string", "integer", "number", "boolean"];
+
+/// Exposes a skill's shell/script tool as a native tool with a typed schema.
+///
+/// Arguments are validated against the `[tools.parameters]` declared in
+/// `SKILL.toml`, substituted into the command's `{name}` placeholders
+/// (shell-quoted), and run through [`ShellTool`] so the usual security
+/// policy, rate limits, and sandboxing apply. A tool with a `host` runs on
+/// that `[runtime.ssh.hosts]` entry.
+pub struct SkillToolAdapter {
+    skill_name: String,
+    tool: SkillTool,
+    shell: ShellTool,
+}
+
+impl SkillToolAdapter {
+    /// Build an adapter, or explain why the skill tool cannot be exposed natively.
+    pub fn new(
+        skill_name: &str,
+        tool: SkillTool,
+        security: Arc<SecurityPolicy>,
+        runtime: Arc<dyn RuntimeAdapter>,
+    ) -> Result<Self, String> {
+        if !matches!(tool.kind.as_str(), "shell" | "script") {
+            return Err(format!("unsupported tool kind `{}`", tool.kind));
+        }
+        if !is_valid_tool_name(&tool.name) {
+            return Err("tool names must match ^[a-zA-Z0-9_-]{1,64}$".into());
+        }
+        for (name, parameter) in &tool.parameters {
+            if !SUPPORTED_PARAMETER_TYPES.contains(&parameter.kind.as_str()) {
+                return Err(format!(
+                    "parameter `{name}` has unsupported type `{}`",
+                    parameter.kind
+                ));
+            }
+            if let Some(default) = &parameter.default {
+                check_value(name, parameter, default)?;
+            }
+        }
+        Ok(Self {
+            skill_name: skill_name.to_string(),
+            tool,
+            shell: ShellTool::new(security, runtime),
+        })
+    }
+
+    /// Allow the tool's `host` to resolve against `[runtime.ssh.hosts]`.
+    pub fn with_ssh_hosts(mut self, ssh_hosts: Arc<SshRuntimeConfig>) -> Self {
+        self.shell = self.shell.with_ssh_hosts(ssh_hosts);
+        self
+    }
+
+    /// Validate `args` and render the command with placeholders substituted.
+    fn render_command(&self, args: &serde_json::Value) -> Result<String, String> {
+        let empty = serde_json::Map::new();
+        let provided = match args {
+            serde_json::Value::Object(map) => map,
+            serde_json::Value::Null => &empty,
+            _ => return Err("Arguments must be a JSON object".into()),
+        };
+
+        if let Some(unknown) = provided
+            .keys()
+            .find(|key| !self.tool.parameters.contains_key(key.as_str()))
+        {
+            return Err(format!("Unknown parameter '{unknown}'"));
+        }
+
+        let mut values = HashMap::new();
+        for (name, parameter) in &self.tool.parameters {
+            let value = match provided.get(name).or(parameter.default.as_ref()) {
+                Some(value) => {
+                    check_value(name, parameter, value)?;
+                    shell_quote(&value_to_string(value))
+                }
+                None if parameter.required => {
+                    return Err(format!("Missing required parameter '{name}'"));
+                }
+                None => "''".to_string(),
+            };
+            values.insert(name.as_str(), value);
+        }
+        Ok(substitute_placeholders(&self.tool.command, &values))
+    }
+}
+
+/// Replace `{name}` placeholders in one pass over the template, so text
+/// inserted for one parameter is never scanned for another placeholder.
+fn substitute_placeholders(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after
+            .find('}')
+            .and_then(|end| values.get(&after[..end]).map(|value| (end, value)))
+        {
+            Some((end, value)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn check_value(
+    name: &str,
+    parameter: &SkillToolParameter,
+    value: &serde_json::Value,
+) -> Result<(), String> {
+    let type_matches = match parameter.kind.as_str() {
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        _ => false,
+    };
+    if !type_matches {
+        return Err(format!(
+            "Parameter '{name}' must be of type {}",
+            parameter.kind
+        ));
+    }
+    if !parameter.allowed.is_empty() && !parameter.allowed.contains(value) {
+        return Err(format!(
+            "Parameter '{name}' must be one of: {}",
+            parameter
+                .allowed
+                .iter()
+                .map(value_to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    Ok(())
+}
+
+fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[async_trait]
+impl Tool for SkillToolAdapter {
+    fn name(&self) -> &str {
+        &self.tool.name
+    }
+
+    fn description(&self) -> &str {
+        &self.tool.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let mut properties = serde_json::Map::new();
+        let mut required = Vec::new();
+        for (name, parameter) in &self.tool.parameters {
+            let mut property = json!({ "type": parameter.kind });
+            if !parameter.description.is_empty() {
+                property["description"] = json!(parameter.description);
+            }
+            if !parameter.allowed.is_empty() {
+                property["enum"] = json!(parameter.allowed);
+            }
+            if let Some(default) = &parameter.default {
+                property["default"] = default.clone();
+            }
+            properties.insert(name.clone(), property);
+            if parameter.required {
+                required.push(name.clone());
+            }
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let command = match self.render_command(&args) {
+            Ok(command) => command,
+            Err(reason) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Invalid arguments for skill tool '{}' ({}): {reason}",
+                        self.tool.name, self.skill_name
+                    )),
+                });
+            }
+        };
+        let mut shell_args = json!({ "command": command });
+        if let Some(host) = &self.tool.host {
+            shell_args["host"] = json!(host);
+        }
+        self.shell.execute(shell_args).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::NativeRuntime;
+    use crate::security::AutonomyLevel;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn parameter(kind: &str, required: bool) -> SkillToolParameter {
+        SkillToolParameter {
+            kind: kind.to_string(),
+            description: String::new(),
+            required,
+            allowed: vec![],
+            default: None,
+        }
+    }
+
+    fn adapter(
+        command: &str,
+        parameters: BTreeMap<String, SkillToolParameter>,
+    ) -> SkillToolAdapter {
+        let security = Arc::new(SecurityPolicy {
+            autonomy: AutonomyLevel::Full,
+            workspace_dir: std::env::temp_dir(),
+            ..SecurityPolicy::default()
+        });
+        SkillToolAdapter::new(
+            "demo",
+            SkillTool {
+                name: "greet".to_string(),
+                description: "Greets someone".to_string(),
+                kind: "shell".to_string(),
+                command: command.to_string(),
+                args: HashMap::new(),
+                parameters,
+                host: None,
+            },
+            security,
+            Arc::new(NativeRuntime::new()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn schema_reflects_declared_parameters() {
+        let mut style = parameter("string", false);
+        style.allowed = vec![json!("short"), json!("long")];
+        style.default = Some(json!("short"));
+        let tool = adapter(
+            "echo {name} {style}",
+            BTreeMap::from([
+                ("name".to_string(), parameter("string", true)),
+                ("style".to_string(), style),
+            ]),
+        );
+
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        assert_eq!(
+            schema["properties"]["style"]["enum"],
+            json!(["short", "long"])
+        );
+        assert_eq!(schema["properties"]["style"]["default"], "short");
+        assert_eq!(schema["required"], json!(["name"]));
+    }
+
+    #[test]
+    fn render_command_validates_and_quotes_arguments() {
+        let tool = adapter(
+            "echo {name} x{count}",
+            BTreeMap::from([
+                ("name".to_string(), parameter("string", true)),
+                ("count".to_string(), parameter("integer", false)),
+            ]),
+        );
+
+        assert_eq!(
+            tool.render_command(&json!({"name": "it's", "count": 3}))
+                .unwrap(),
+            r"echo 'it'\''s' x'3'"
+        );
+        assert_eq!(
+            tool.render_command(&json!({"name": "a"})).unwrap(),
+            "echo 'a' x''"
+        );
+        assert!(tool.render_command(&json!({})).is_err());
+        assert!(tool
+            .render_command(&json!({"name": "a", "count": "3"}))
+            .is_err());
+        assert!(tool
+            .render_command(&json!({"name": "a", "extra": true}))
+            .is_err());
+    }
+
+    #[test]
+    fn render_command_does_not_expand_placeholders_inside_values() {
+        let tool = adapter(
+            "echo {first} {second} {unknown}",
+            BTreeMap::from([
+                ("first".to_string(), parameter("string", true)),
+                ("second".to_string(), parameter("string", true)),
+            ]),
+        );
+
+        assert_eq!(
+            tool.render_command(&json!({"first": "{second}", "second": "x; rm -rf ~"}))
+                .unwrap(),
+            "echo '{second}' 'x; rm -rf ~' {unknown}"
+        );
+    }
+
+    #[test]
+    fn new_rejects_unsupported_kinds_and_types() {
+        let security = Arc::new(SecurityPolicy::default());
+        let tool = |kind: &str, parameter_type: &str| SkillTool {
+            name: "t".to_string(),
+            description: String::new(),
+            kind: kind.to_string(),
+            command: "echo".to_string(),
+            args: HashMap::new(),
+            parameters: BTreeMap::from([("p".to_string(), parameter(parameter_type, false))]),
+            host: None,
+        };
+        let runtime = || Arc::new(NativeRuntime::new()) as Arc<dyn RuntimeAdapter>;
+
+        assert!(
+            SkillToolAdapter::new("s", tool("http", "string"), security.clone(), runtime())
+                .is_err()
+        );
+        assert!(
+            SkillToolAdapter::new("s", tool("shell", "object"), security.clone(), runtime())
+                .is_err()
+        );
+        assert!(
+            SkillToolAdapter::new("s", tool("script", "boolean"), security.clone(), runtime())
+                .is_ok()
+        );
+        let dotted = SkillTool {
+            name: "s.t".to_string(),
+            ..tool("shell", "string")
+        };
+        assert!(SkillToolAdapter::new("s", dotted, security, runtime()).is_err());
+    }
+
+    #[tokio::test]
+    async fn execute_runs_rendered_command() {
+        let tool = adapter(
+            "echo hello {name}",
+            BTreeMap::from([("name".to_string(), parameter("string", true))]),
+        );
+        let result = tool.execute(json!({"name": "world"})).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.output.trim(), "hello world");
+
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Missing required parameter"));
+    }
+}
@@ -0,0 +1,382 @@
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::json;
+use std::fmt::Write;
+use sysinfo::{Disks, Pid, ProcessesToUpdate, System};
+
+/// Default number of processes listed by `system_info`.
+const DEFAULT_TOP_PROCESSES: usize = 5;
+/// Upper bound for process listings.
+pub(super) const MAX_PROCESSES: usize = 50;
+
+/// One row of a process listing.
+#[derive(Debug, Clone)]
+pub(super) struct ProcessRow {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+/// Process listing order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ProcessSort {
+    Cpu,
+    Memory,
+}
+
+impl ProcessSort {
+    pub(super) fn parse(value: Option<&str>) -> Result<Self, String> {
+        match value.map(str::trim) {
+            None | Some("" | "cpu") => Ok(Self::Cpu),
+            Some("memory") => Ok(Self::Memory),
+            Some(other) => Err(format!("Unknown sort '{other}' (use cpu or memory)")),
+        }
+    }
+}
+
+/// Refresh CPU and process usage twice, `MINIMUM_CPU_UPDATE_INTERVAL` apart,
+/// so per-process CPU percentages reflect current load rather than zero.
+pub(super) async fn sample_system() -> anyhow::Result<System> {
+    tokio::task::spawn_blocking(|| {
+        let mut system = System::new();
+        system.refresh_cpu_usage();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        system.refresh_cpu_usage();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        system.refresh_memory();
+        system
+    })
+    .await
+    .map_err(Into::into)
+}
+
+/// Processes matching `name` (case-insensitive substring), heaviest first.
+pub(super) fn top_processes(
+    system: &System,
+    sort: ProcessSort,
+    name: Option<&str>,
+    limit: usize,
+) -> Vec<ProcessRow> {
+    let filter = name.map(str::to_lowercase);
+    let mut rows: Vec<ProcessRow> = system
+        .processes()
+        .values()
+        .filter(|process| process.thread_kind().is_none())
+        .map(|process| ProcessRow {
+            pid: process.pid().as_u32(),
+            name: process.name().to_string_lossy().into_owned(),
+            cpu_percent: process.cpu_usage(),
+            memory_bytes: process.memory(),
+        })
+        .filter(|row| {
+            filter
+                .as_deref()
+                .is_none_or(|filter| row.name.to_lowercase().contains(filter))
+        })
+        .collect();
+    match sort {
+        ProcessSort::Cpu => rows.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent)),
+        ProcessSort::Memory => rows.sort_by_key(|row| std::cmp::Reverse(row.memory_bytes)),
+    }
+    rows.truncate(limit);
+    rows
+}
+
+pub(super) fn write_process_table(out: &mut String, rows: &[ProcessRow]) {
+    let _ = writeln!(out, "{:>8}  {:>6}  {:>9}  NAME", "PID", "CPU%", "MEM");
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{:>8}  {:>6.1}  {:>9}  {}",
+            row.pid,
+            row.cpu_percent,
+            format_bytes(row.memory_bytes),
+            row.name
+        );
+    }
+}
+
+pub(super) fn current_pid() -> Option<Pid> {
+    sysinfo::get_current_pid().ok()
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+/// Battery charge and state, if the machine has a battery.
+async fn battery_status() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        let output = tokio::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .await
+            .ok()?;
+        return parse_pmset(&String::from_utf8_lossy(&output.stdout));
+    }
+    let mut batteries = Vec::new();
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return None;
+    };
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |file: &str| {
+            std::fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        if read("type").as_deref() != Some("Battery") {
+            continue;
+        }
+        if let Some(capacity) = read("capacity") {
+            let status = read("status").unwrap_or_else(|| "Unknown".into());
+            batteries.push(format!("{capacity}% ({})", status.to_lowercase()));
+        }
+    }
+    (!batteries.is_empty()).then(|| batteries.join(", "))
+}
+
+/// Parse `pmset -g batt` output: the battery line is tab-separated, e.g.
+/// `-InternalBattery-0 (id=1)` then `87%; discharging; 4:12 remaining`.
+fn parse_pmset(output: &str) -> Option<String> {
+    let line = output
+        .lines()
+        .find(|line| line.contains("InternalBattery"))?;
+    let mut fields = line.split('\t').nth(1)?.split(';').map(str::trim);
+    let percent = fields.next()?;
+    let state = fields.next().unwrap_or("unknown");
+    Some(format!("{percent} ({state})"))
+}
+
+/// Read-only snapshot of the host: CPU, memory, disks, uptime, battery, and
+/// the heaviest processes.
+///
+/// Reports the machine the daemon runs on, so it is only registered with the
+/// native runtime.
+pub struct SystemInfoTool;
+
+impl SystemInfoTool {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn report(top: usize, sort: ProcessSort) -> anyhow::Result<String> {
+        let system = sample_system().await?;
+        let mut out = String::new();
+
+        let host = System::host_name().unwrap_or_else(|| "unknown".into());
+        let os = System::long_os_version().unwrap_or_else(|| std::env::consts::OS.into());
+        let _ = writeln!(out, "Host: {host} ({os}, {})", System::cpu_arch());
+        let _ = writeln!(out, "Uptime: {}", format_duration(System::uptime()));
+
+        let load = System::load_average();
+        let _ = writeln!(
+            out,
+            "CPU: {:.1}% across {} cores (load {:.2} {:.2} {:.2})",
+            system.global_cpu_usage(),
+            system.cpus().len(),
+            load.one,
+            load.five,
+            load.fifteen
+        );
+        let _ = writeln!(
+            out,
+            "Memory: {} used / {} total ({} available)",
+            format_bytes(system.used_memory()),
+            format_bytes(system.total_memory()),
+            format_bytes(system.available_memory())
+        );
+        if system.total_swap() > 0 {
+            let _ = writeln!(
+                out,
+                "Swap: {} used / {} total",
+                format_bytes(system.used_swap()),
+                format_bytes(system.total_swap())
+            );
+        }
+
+        let disks = Disks::new_with_refreshed_list();
+        let mut disks: Vec<_> = disks
+            .list()
+            .iter()
+            .filter(|disk| disk.total_space() > 0)
+            .collect();
+        disks.sort_by(|a, b| a.mount_point().cmp(b.mount_point()));
+        disks.dedup_by(|a, b| a.name() == b.name() && a.total_space() == b.total_space());
+        if !disks.is_empty() {
+            out.push_str("Disks:\n");
+            for disk in disks {
+                let used = disk.total_space().saturating_sub(disk.available_space());
+                let _ = writeln!(
+                    out,
+                    "  {}: {} used / {} ({} free)",
+                    disk.mount_point().display(),
+                    format_bytes(used),
+                    format_bytes(disk.total_space()),
+                    format_bytes(disk.available_space())
+                );
+            }
+        }
+
+        if let Some(battery) = battery_status().await {
+            let _ = writeln!(out, "Battery: {battery}");
+        }
+
+        if top > 0 {
+            let label = match sort {
+                ProcessSort::Cpu => "CPU",
+                ProcessSort::Memory => "memory",
+            };
+            let _ = writeln!(out, "Top processes by {label}:");
+            write_process_table(&mut out, &top_processes(&system, sort, None, top));
+        }
+        Ok(out)
+    }
+}
+
+impl Default for SystemInfoTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for SystemInfoTool {
+    fn name(&self) -> &str {
+        "system_info"
+    }
+
+    fn description(&self) -> &str {
+        "Report host health: CPU usage and load, memory, disks, uptime, battery, and the top processes by CPU or memory. Use to answer questions like why the machine is slow, hot, or out of space."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "top": {
+                    "type": "integer",
+                    "description": "Number of top processes to include (0 to skip, max 50)",
+                    "default": DEFAULT_TOP_PROCESSES
+                },
+                "sort": {
+                    "type": "string",
+                    "enum": ["cpu", "memory"],
+                    "description": "Order top processes by CPU or memory usage",
+                    "default": "cpu"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let top = args.get("top").and_then(serde_json::Value::as_u64).map_or(
+            DEFAULT_TOP_PROCESSES,
+            |n| {
+                usize::try_from(n)
+                    .unwrap_or(MAX_PROCESSES)
+                    .min(MAX_PROCESSES)
+            },
+        );
+        let sort = match ProcessSort::parse(args.get("sort").and_then(|v| v.as_str())) {
+            Ok(sort) => sort,
+            Err(error) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(error),
+                });
+            }
+        };
+
+        match Self::report(top, sort).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to read system info: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes_and_durations() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+        assert_eq!(format_duration(59), "0m");
+        assert_eq!(format_duration(3 * 3600 + 120), "3h 2m");
+        assert_eq!(format_duration(2 * 86_400 + 3600), "2d 1h 0m");
+    }
+
+    #[test]
+    fn parses_pmset_battery_line() {
+        let output = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=4653155)\t87%; discharging; 4:12 remaining present: true\n";
+        assert_eq!(parse_pmset(output).as_deref(), Some("87% (discharging)"));
+        assert!(parse_pmset("Now drawing from 'AC Power'\n").is_none());
+    }
+
+    #[tokio::test]
+    async fn reports_host_and_lists_current_process() {
+        let system = sample_system().await.unwrap();
+        let pid = current_pid().unwrap();
+        let name = system.process(pid).unwrap().name().to_string_lossy();
+        let rows = top_processes(&system, ProcessSort::Memory, Some(&name), MAX_PROCESSES);
+        assert!(rows.iter().any(|row| row.pid == pid.as_u32()));
+
+        let result = SystemInfoTool::new()
+            .execute(json!({"top": 3, "sort": "memory"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        for expected in [
+            "Host:",
+            "Uptime:",
+            "CPU:",
+            "Memory:",
+            "Top processes by memory:",
+        ] {
+            assert!(result.output.contains(expected), "missing {expected}");
+        }
+
+        let result = SystemInfoTool::new()
+            .execute(json!({"sort": "threads"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().contains("Unknown sort"));
+    }
+}
@@ -0,0 +1,531 @@
+//! Conversation timers and stopwatches (`timer_start` / `timer_status`).
+//!
+//! A timer is a one-shot reminder job named `timer:<label>`, so it survives
+//! restarts and is announced back into the conversation that started it by
+//! the scheduler. Stopwatches never fire; their start times are kept in
+//! `state/stopwatches.json`. Both are scoped to the requesting conversation
+//! (channel and recipient).
+
+use super::remind_me::{
+    current_route, parse_duration, resolve_target, targets_route, ReminderRoute,
+};
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::Config;
+use crate::cron::{self, DeliveryConfig, TIMER_NAME_PREFIX};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const STOPWATCHES_FILE: &str = "stopwatches.json";
+/// Longer waits belong in `remind_me`.
+const MAX_TIMER_HOURS: i64 = 24;
+const MAX_STOPWATCHES: usize = 20;
+
+/// Serializes read-modify-write cycles on the stopwatch file.
+static STOPWATCH_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Stopwatch {
+    id: String,
+    label: String,
+    started_at: DateTime<Utc>,
+}
+
+/// Stopwatches keyed by conversation (`channel:recipient`, or `local`).
+type Stopwatches = BTreeMap<String, Vec<Stopwatch>>;
+
+pub(super) fn identity(route: Option<&ReminderRoute>) -> String {
+    route.map_or_else(
+        || "local".to_string(),
+        |route| format!("{}:{}", route.channel, route.recipient),
+    )
+}
+
+fn stopwatches_path(config: &Config) -> PathBuf {
+    config.workspace_dir.join("state").join(STOPWATCHES_FILE)
+}
+
+fn load_stopwatches(config: &Config) -> Stopwatches {
+    std::fs::read_to_string(stopwatches_path(config))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_stopwatches(config: &Config, stopwatches: &Stopwatches) -> anyhow::Result<()> {
+    crate::state::write_atomic(
+        &stopwatches_path(config),
+        serde_json::to_string(stopwatches)?,
+    )
+}
+
+/// "1h 05m", "20m", "45s".
+fn format_span(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    let (hours, minutes, secs) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{secs}s"),
+        (0, _) if secs == 0 => format!("{minutes}m"),
+        (0, _) => format!("{minutes}m {secs:02}s"),
+        _ => format!("{hours}h {minutes:02}m"),
+    }
+}
+
+fn timer_label(job: &cron::CronJob) -> &str {
+    job.name
+        .as_deref()
+        .and_then(|name| name.strip_prefix(TIMER_NAME_PREFIX))
+        .unwrap_or_default()
+}
+
+fn success(output: String) -> ToolResult {
+    ToolResult {
+        success: true,
+        output,
+        error: None,
+    }
+}
+
+fn label_arg(args: &serde_json::Value) -> Option<String> {
+    args.get("label")
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(|label| crate::util::truncate_with_ellipsis(label, 40))
+}
+
+/// Starts a countdown timer or a stopwatch.
+pub struct TimerStartTool {
+    config: Arc<Config>,
+    security: Arc<SecurityPolicy>,
+}
+
+impl TimerStartTool {
+    pub fn new(config: Arc<Config>, security: Arc<SecurityPolicy>) -> Self {
+        Self { config, security }
+    }
+
+    fn start_timer(
+        &self,
+        args: &serde_json::Value,
+        duration: &str,
+        label: String,
+    ) -> anyhow::Result<ToolResult> {
+        if !self.config.cron.enabled {
+            return Ok(failure(
+                "timers need the scheduler, which is disabled by config (cron.enabled=false)",
+            ));
+        }
+        let text = duration.trim().to_ascii_lowercase();
+        let length = match parse_duration(text.strip_prefix("in ").unwrap_or(&text)) {
+            Ok(length) => length,
+            Err(e) => return Ok(failure(format!("Could not understand '{duration}': {e}"))),
+        };
+        if length > chrono::Duration::hours(MAX_TIMER_HOURS) {
+            return Ok(failure(format!(
+                "Timers run for at most {MAX_TIMER_HOURS} hours; use remind_me for later times"
+            )));
+        }
+        let (channel, to) = match resolve_target(args, current_route()) {
+            Ok(target) => target,
+            Err(e) => return Ok(failure(e)),
+        };
+        if let Some(blocked) = self.enforce_mutation_allowed() {
+            return Ok(blocked);
+        }
+
+        let span = format_span(length.num_seconds());
+        let at = Utc::now() + length;
+        let message = format!("⏲️ Timer done: {label} ({span})");
+        let delivery = DeliveryConfig {
+            mode: "announce".to_string(),
+            channel: Some(channel),
+            to: Some(to),
+            best_effort: true,
+            recipients: Vec::new(),
+        };
+        let job = cron::add_reminder_job(
+            &self.config,
+            Some(format!("{TIMER_NAME_PREFIX}{label}")),
+            at,
+            &message,
+            delivery,
+        )?;
+        Ok(success(format!(
+            "Timer '{label}' set for {span}; it goes off at {} UTC (id {}).",
+            at.format("%H:%M:%S"),
+            job.id
+        )))
+    }
+
+    fn start_stopwatch(&self, label: String) -> anyhow::Result<ToolResult> {
+        let _guard = STOPWATCH_LOCK.lock();
+        let mut stopwatches = load_stopwatches(&self.config);
+        let running = stopwatches
+            .entry(identity(current_route().as_ref()))
+            .or_default();
+        if running.iter().any(|stopwatch| stopwatch.label == label) {
+            return Ok(failure(format!(
+                "A stopwatch named '{label}' is already running"
+            )));
+        }
+        if running.len() >= MAX_STOPWATCHES {
+            return Ok(failure(format!(
+                "Too many stopwatches running (max {MAX_STOPWATCHES}); stop one first"
+            )));
+        }
+        let stopwatch = Stopwatch {
+            id: uuid::Uuid::new_v4().to_string()[..8].to_string(),
+            label,
+            started_at: Utc::now(),
+        };
+        let output = format!(
+            "Stopwatch '{}' started (id {}).",
+            stopwatch.label, stopwatch.id
+        );
+        running.push(stopwatch);
+        save_stopwatches(&self.config, &stopwatches)?;
+        Ok(success(output))
+    }
+
+    fn enforce_mutation_allowed(&self) -> Option<ToolResult> {
+        if !self.security.can_act() {
+            return Some(failure(
+                "Security policy: read-only mode, cannot perform 'timer_start'",
+            ));
+        }
+        if self.security.is_rate_limited() {
+            return Some(failure(
+                "Rate limit exceeded: too many actions in the last hour",
+            ));
+        }
+        if !self.security.record_action() {
+            return Some(failure("Rate limit exceeded: action budget exhausted"));
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl Tool for TimerStartTool {
+    fn name(&self) -> &str {
+        "timer_start"
+    }
+
+    fn description(&self) -> &str {
+        "Start a countdown timer (\"set a 20 minute tea timer\": duration='20 minutes', label='tea') \
+         that messages this conversation when it ends, or a stopwatch (kind='stopwatch') to time \
+         something. Check or cancel them with timer_status."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "kind": {
+                    "type": "string",
+                    "enum": ["timer", "stopwatch"],
+                    "default": "timer"
+                },
+                "duration": {
+                    "type": "string",
+                    "description": "Timer length, e.g. '20 minutes', '1h30m', '90s' (max 24h)"
+                },
+                "label": { "type": "string", "description": "What the timer is for" },
+                "channel": { "type": "string", "enum": super::remind_me::DELIVERY_CHANNELS },
+                "to": { "type": "string", "description": "Chat/channel ID to notify (defaults to this conversation)" }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let duration = args
+            .get("duration")
+            .and_then(serde_json::Value::as_str)
+            .filter(|duration| !duration.trim().is_empty());
+        match args
+            .get("kind")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("timer")
+        {
+            "timer" => match duration {
+                Some(duration) => {
+                    let label = label_arg(&args).unwrap_or_else(|| "timer".to_string());
+                    self.start_timer(&args, duration, label)
+                }
+                None => Ok(failure("Missing 'duration' parameter")),
+            },
+            "stopwatch" => {
+                self.start_stopwatch(label_arg(&args).unwrap_or_else(|| "stopwatch".to_string()))
+            }
+            other => Ok(failure(format!("Unknown kind '{other}'"))),
+        }
+    }
+}
+
+/// Lists and cancels the conversation's timers and stopwatches.
+pub struct TimerStatusTool {
+    config: Arc<Config>,
+}
+
+impl TimerStatusTool {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+
+    fn timers(&self, route: Option<&ReminderRoute>) -> anyhow::Result<Vec<cron::CronJob>> {
+        if !self.config.cron.enabled {
+            return Ok(Vec::new());
+        }
+        Ok(cron::list_jobs(&self.config)?
+            .into_iter()
+            .filter(cron::CronJob::is_timer)
+            .filter(|job| route.is_none_or(|route| targets_route(job, route)))
+            .collect())
+    }
+
+    fn list(&self, now: DateTime<Utc>) -> anyhow::Result<ToolResult> {
+        let route = current_route();
+        let timers = self.timers(route.as_ref())?;
+        let stopwatches = load_stopwatches(&self.config)
+            .remove(&identity(route.as_ref()))
+            .unwrap_or_default();
+        if timers.is_empty() && stopwatches.is_empty() {
+            return Ok(success("No timers or stopwatches running.".to_string()));
+        }
+        let mut output = String::new();
+        for job in &timers {
+            let _ = writeln!(
+                output,
+                "Timer '{}': {} left (id {})",
+                timer_label(job),
+                format_span((job.next_run - now).num_seconds()),
+                job.id
+            );
+        }
+        for stopwatch in &stopwatches {
+            let _ = writeln!(
+                output,
+                "Stopwatch '{}': {} elapsed (id {})",
+                stopwatch.label,
+                format_span((now - stopwatch.started_at).num_seconds()),
+                stopwatch.id
+            );
+        }
+        Ok(success(output.trim_end().to_string()))
+    }
+
+    /// Cancel a timer or stop a stopwatch, matched by id or label.
+    fn cancel(&self, target: &str, now: DateTime<Utc>) -> anyhow::Result<ToolResult> {
+        let route = current_route();
+        if let Some(job) = self
+            .timers(route.as_ref())?
+            .into_iter()
+            .find(|job| job.id == target || timer_label(job) == target)
+        {
+            cron::remove_job(&self.config, &job.id)?;
+            return Ok(success(format!(
+                "Cancelled timer '{}' with {} left.",
+                timer_label(&job),
+                format_span((job.next_run - now).num_seconds())
+            )));
+        }
+
+        let _guard = STOPWATCH_LOCK.lock();
+        let mut stopwatches = load_stopwatches(&self.config);
+        let key = identity(route.as_ref());
+        let running = stopwatches.entry(key.clone()).or_default();
+        let Some(index) = running
+            .iter()
+            .position(|stopwatch| stopwatch.id == target || stopwatch.label == target)
+        else {
+            return Ok(failure(format!(
+                "No timer or stopwatch matching '{target}'"
+            )));
+        };
+        let stopwatch = running.remove(index);
+        if running.is_empty() {
+            stopwatches.remove(&key);
+        }
+        save_stopwatches(&self.config, &stopwatches)?;
+        Ok(success(format!(
+            "Stopped stopwatch '{}' at {}.",
+            stopwatch.label,
+            format_span((now - stopwatch.started_at).num_seconds())
+        )))
+    }
+}
+
+#[async_trait]
+impl Tool for TimerStatusTool {
+    fn name(&self) -> &str {
+        "timer_status"
+    }
+
+    fn description(&self) -> &str {
+        "List this conversation's running timers (time left) and stopwatches (time elapsed), or \
+         cancel a timer / stop a stopwatch by id or label (action='cancel')."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["list", "cancel"],
+                    "default": "list"
+                },
+                "id": { "type": "string", "description": "Timer or stopwatch id or label (for cancel)" }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let now = Utc::now();
+        match args
+            .get("action")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or("list")
+        {
+            "list" => self.list(now),
+            "cancel" => match args
+                .get("id")
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+            {
+                Some(target) => self.cancel(target, now),
+                None => Ok(failure("Missing 'id' parameter")),
+            },
+            other => Ok(failure(format!("Unknown action '{other}'"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::remind_me::with_request_route;
+    use tempfile::TempDir;
+
+    fn setup(tmp: &TempDir) -> (TimerStartTool, TimerStatusTool) {
+        let config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        std::fs::create_dir_all(&config.workspace_dir).unwrap();
+        let security = Arc::new(SecurityPolicy::from_config(
+            &config.autonomy,
+            &config.workspace_dir,
+        ));
+        let config = Arc::new(config);
+        (
+            TimerStartTool::new(config.clone(), security),
+            TimerStatusTool::new(config),
+        )
+    }
+
+    fn route(recipient: &str) -> Option<ReminderRoute> {
+        Some(ReminderRoute {
+            channel: "telegram".into(),
+            recipient: recipient.into(),
+        })
+    }
+
+    #[test]
+    fn spans_are_formatted_compactly() {
+        assert_eq!(format_span(45), "45s");
+        assert_eq!(format_span(1200), "20m");
+        assert_eq!(format_span(95), "1m 35s");
+        assert_eq!(format_span(3900), "1h 05m");
+        assert_eq!(format_span(-3), "0s");
+    }
+
+    #[tokio::test]
+    async fn timers_fire_into_the_originating_conversation_and_are_scoped() {
+        let tmp = TempDir::new().unwrap();
+        let (start, status) = setup(&tmp);
+
+        let result = with_request_route(
+            route("alice"),
+            start.execute(json!({"duration": "20 minutes", "label": "tea"})),
+        )
+        .await
+        .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.starts_with("Timer 'tea' set for 20m"));
+
+        let job = cron::list_jobs(&start.config).unwrap().remove(0);
+        assert!(job.is_timer());
+        assert_eq!(job.prompt.as_deref(), Some("⏲️ Timer done: tea (20m)"));
+        assert_eq!(job.delivery.to.as_deref(), Some("alice"));
+
+        let listed = with_request_route(route("alice"), status.execute(json!({})))
+            .await
+            .unwrap();
+        assert!(listed.output.starts_with("Timer 'tea': "));
+        assert!(listed.output.ends_with(&format!("left (id {})", job.id)));
+        let other = with_request_route(route("bob"), status.execute(json!({})))
+            .await
+            .unwrap();
+        assert_eq!(other.output, "No timers or stopwatches running.");
+
+        let cancelled = with_request_route(
+            route("alice"),
+            status.execute(json!({"action": "cancel", "id": "tea"})),
+        )
+        .await
+        .unwrap();
+        assert!(cancelled.output.starts_with("Cancelled timer 'tea'"));
+        assert!(cron::list_jobs(&start.config).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stopwatches_persist_per_conversation() {
+        let tmp = TempDir::new().unwrap();
+        let (start, status) = setup(&tmp);
+
+        let started = with_request_route(
+            route("alice"),
+            start.execute(json!({"kind": "stopwatch", "label": "run"})),
+        )
+        .await
+        .unwrap();
+        assert!(started.success);
+        let again = with_request_route(
+            route("alice"),
+            start.execute(json!({"kind": "stopwatch", "label": "run"})),
+        )
+        .await
+        .unwrap();
+        assert!(!again.success);
+
+        let stored = load_stopwatches(&start.config);
+        assert_eq!(stored["telegram:alice"][0].label, "run");
+
+        let stopped = with_request_route(
+            route("alice"),
+            status.execute(json!({"action": "cancel", "id": "run"})),
+        )
+        .await
+        .unwrap();
+        assert!(stopped.output.starts_with("Stopped stopwatch 'run' at "));
+        assert!(load_stopwatches(&start.config).is_empty());
+
+        let missing = start.execute(json!({"label": "tea"})).await.unwrap();
+        assert_eq!(
+            missing.error.as_deref(),
+            Some("Missing 'duration' parameter")
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use super::traits::{Tool, ToolResult};
+use crate::providers::Provider;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Texts longer than this are refused rather than sent to the model.
+const MAX_TEXT_CHARS: usize = 20_000;
+
+/// Model-backed translation shared by the `translate` tool and channel
+/// auto-translate.
+pub struct Translator {
+    provider: Arc<dyn Provider>,
+    model: String,
+}
+
+/// A message detected as foreign, with its translation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detected {
+    /// English name of the detected language, e.g. "Spanish".
+    pub language: String,
+    pub translation: String,
+}
+
+impl Translator {
+    pub fn new(provider: Arc<dyn Provider>, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+        }
+    }
+
+    /// Build a translator from `[translate]`, falling back to the default
+    /// provider and model.
+    pub fn from_config(config: &crate::config::Config) -> anyhow::Result<Self> {
+        let provider_name = config
+            .translate
+            .provider
+            .as_deref()
+            .or(config.default_provider.as_deref())
+            .unwrap_or("openrouter");
+        let model = config
+            .translate
+            .model
+            .clone()
+            .or_else(|| config.default_model.clone())
+            .unwrap_or_default();
+        let options = crate::providers::ProviderRuntimeOptions {
+            provider_api_url: config.api_url.clone(),
+            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            ..crate::providers::ProviderRuntimeOptions::default()
+        };
+        let provider = crate::providers::create_provider_with_options(
+            provider_name,
+            config.api_key.as_deref(),
+            &options,
+        )?;
+        Ok(Self::new(Arc::from(provider), model))
+    }
+
+    /// Translate `text` into `target`, from `source` when known.
+    pub async fn translate(
+        &self,
+        text: &str,
+        target: &str,
+        source: Option<&str>,
+    ) -> anyhow::Result<String> {
+        let from = source.map_or_else(String::new, |source| format!(" from {source}"));
+        let prompt = format!(
+            "You are a translator. Translate the user's text{from} into {target}. \
+Preserve meaning, tone, formatting, Markdown, code, URLs, and names. \
+Reply with only the translation."
+        );
+        let reply = self
+            .provider
+            .chat_with_system(Some(&prompt), text, &self.model, 0.0)
+            .await?;
+        let reply = reply.trim();
+        if reply.is_empty() {
+            anyhow::bail!("translation model returned an empty reply");
+        }
+        Ok(reply.to_string())
+    }
+
+    /// Detect whether `text` is written in `working_language` and, if not,
+    /// translate it in the same call. Returns `None` for text that needs no
+    /// translation.
+    pub async fn detect(
+        &self,
+        text: &str,
+        working_language: &str,
+    ) -> anyhow::Result<Option<Detected>> {
+        if !text.chars().any(char::is_alphabetic) {
+            return Ok(None);
+        }
+        let prompt = format!(
+            "Identify the language of the user's message. If it is written in \
+{working_language}, reply with exactly: {working_language}\n\
+Otherwise reply with the language's English name on the first line, then a \
+faithful {working_language} translation of the message on the following lines. \
+Preserve formatting, code, URLs, and names."
+        );
+        let reply = self
+            .provider
+            .chat_with_system(Some(&prompt), text, &self.model, 0.0)
+            .await?;
+        Ok(parse_detection(&reply, working_language))
+    }
+}
+
+fn parse_detection(reply: &str, working_language: &str) -> Option<Detected> {
+    let reply = reply.trim();
+    let (language, translation) = reply.split_once('\n').unwrap_or((reply, ""));
+    let language = language
+        .trim()
+        .trim_start_matches("Language:")
+        .trim()
+        .trim_end_matches('.');
+    let translation = translation.trim();
+    if language.is_empty()
+        || language.eq_ignore_ascii_case(working_language.trim())
+        || translation.is_empty()
+    {
+        return None;
+    }
+    Some(Detected {
+        language: language.to_string(),
+        translation: translation.to_string(),
+    })
+}
+
+/// Translate text between languages with the configured model.
+pub struct TranslateTool {
+    translator: Translator,
+    working_language: String,
+}
+
+impl TranslateTool {
+    pub fn new(translator: Translator, working_language: impl Into<String>) -> Self {
+        Self {
+            translator,
+            working_language: working_language.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for TranslateTool {
+    fn name(&self) -> &str {
+        "translate"
+    }
+
+    fn description(&self) -> &str {
+        "Translate text into another language, preserving formatting, code, and names. Use when the user asks for a translation or a message must be written in a language other than your own."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "text": {
+                    "type": "string",
+                    "description": "Text to translate"
+                },
+                "target_language": {
+                    "type": "string",
+                    "description": "Language to translate into (e.g. \"Spanish\", \"ja\"). Defaults to the working language."
+                },
+                "source_language": {
+                    "type": "string",
+                    "description": "Language of the text, if known"
+                }
+            },
+            "required": ["text"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let string = |key: &str| {
+            args.get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let Some(text) = string("text") else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'text' parameter".into()),
+            });
+        };
+        if text.chars().count() > MAX_TEXT_CHARS {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Text is too long to translate in one call (max {MAX_TEXT_CHARS} characters)"
+                )),
+            });
+        }
+        let target = string("target_language").unwrap_or(&self.working_language);
+
+        match self
+            .translator
+            .translate(text, target, string("source_language"))
+            .await
+        {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Translation failed: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// Returns a fixed reply and records the system prompts it was given.
+    #[derive(Default)]
+    struct ScriptedProvider {
+        reply: String,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.prompts
+                .lock()
+                .push(system_prompt.unwrap_or_default().to_string());
+            Ok(self.reply.clone())
+        }
+    }
+
+    fn translator(reply: &str) -> (Arc<ScriptedProvider>, Translator) {
+        let provider = Arc::new(ScriptedProvider {
+            reply: reply.into(),
+            ..ScriptedProvider::default()
+        });
+        (provider.clone(), Translator::new(provider, "test-model"))
+    }
+
+    #[test]
+    fn detection_replies_are_parsed() {
+        assert_eq!(
+            parse_detection("Spanish\n¿Dónde está? → Where is it?", "English")
+                .unwrap()
+                .language,
+            "Spanish"
+        );
+        let detected = parse_detection("Language: German.\n\nGood morning", "English").unwrap();
+        assert_eq!(detected.language, "German");
+        assert_eq!(detected.translation, "Good morning");
+        assert!(parse_detection("English", "English").is_none());
+        assert!(parse_detection("english\nhello", "English").is_none());
+        assert!(parse_detection("French", "English").is_none());
+    }
+
+    #[tokio::test]
+    async fn detect_skips_text_without_letters() {
+        let (provider, translator) = translator("Spanish\nhi");
+        assert!(translator
+            .detect("👍 42", "English")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(provider.prompts.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn tool_translates_into_requested_or_working_language() {
+        let (provider, translator) = translator("  Hola mundo \n");
+        let tool = TranslateTool::new(translator, "English");
+
+        let result = tool
+            .execute(json!({"text": "Hello world", "target_language": "Spanish"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "Hola mundo");
+
+        tool.execute(json!({"text": "Hallo", "source_language": "German"}))
+            .await
+            .unwrap();
+        {
+            let prompts = provider.prompts.lock();
+            assert!(prompts[0].contains("into Spanish."));
+            assert!(prompts[1].contains("from German into English."));
+        }
+
+        let missing = tool.execute(json!({"text": "  "})).await.unwrap();
+        assert_eq!(missing.error.as_deref(), Some("Missing 'text' parameter"));
+    }
+}
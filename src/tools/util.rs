@@ -0,0 +1,12 @@
+//! Helpers shared by tool implementations.
+
+use super::traits::ToolResult;
+
+/// A failed [`ToolResult`] carrying `error` and no output.
+pub(crate) fn failure(error: impl Into<String>) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error.into()),
+    }
+}
@@ -0,0 +1,735 @@
+use super::reverse_geocode::coordinate;
+use super::traits::{Tool, ToolResult};
+use super::util::failure;
+use crate::config::WeatherConfig;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+const OPEN_METEO_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const OPENWEATHERMAP_URL: &str = "https://api.openweathermap.org/data/2.5";
+const PLACES_FILE: &str = "weather_places.json";
+const PLACES_CAPACITY: usize = 256;
+const DEFAULT_DAYS: usize = 3;
+const MAX_DAYS: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Units {
+    Metric,
+    Imperial,
+}
+
+impl Units {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "metric" => Some(Self::Metric),
+            "imperial" => Some(Self::Imperial),
+            _ => None,
+        }
+    }
+
+    fn temperature(self) -> &'static str {
+        match self {
+            Self::Metric => "°C",
+            Self::Imperial => "°F",
+        }
+    }
+
+    fn speed(self) -> &'static str {
+        match self {
+            Self::Metric => "km/h",
+            Self::Imperial => "mph",
+        }
+    }
+
+    fn precipitation(self) -> &'static str {
+        match self {
+            Self::Metric => "mm",
+            Self::Imperial => "in",
+        }
+    }
+}
+
+/// A geocoded location.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Place {
+    label: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Conditions {
+    temperature: f64,
+    feels_like: Option<f64>,
+    humidity: Option<f64>,
+    wind_speed: Option<f64>,
+    precipitation: Option<f64>,
+    summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DailyForecast {
+    date: String,
+    high: f64,
+    low: f64,
+    precipitation_chance: Option<f64>,
+    precipitation: Option<f64>,
+    summary: String,
+}
+
+/// Provider-independent weather report, already in the requested units.
+#[derive(Debug, Clone, PartialEq)]
+struct Report {
+    current: Conditions,
+    daily: Vec<DailyForecast>,
+}
+
+/// A weather data source.
+#[async_trait]
+trait WeatherProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn report(
+        &self,
+        client: &reqwest::Client,
+        place: &Place,
+        days: usize,
+        units: Units,
+    ) -> anyhow::Result<Report>;
+}
+
+struct OpenMeteo;
+
+#[async_trait]
+impl WeatherProvider for OpenMeteo {
+    fn name(&self) -> &'static str {
+        "Open-Meteo"
+    }
+
+    async fn report(
+        &self,
+        client: &reqwest::Client,
+        place: &Place,
+        days: usize,
+        units: Units,
+    ) -> anyhow::Result<Report> {
+        let mut query = vec![
+            ("latitude", place.latitude.to_string()),
+            ("longitude", place.longitude.to_string()),
+            (
+                "current",
+                "temperature_2m,apparent_temperature,relative_humidity_2m,precipitation,weather_code,wind_speed_10m".into(),
+            ),
+            (
+                "daily",
+                "weather_code,temperature_2m_max,temperature_2m_min,precipitation_probability_max,precipitation_sum".into(),
+            ),
+            ("timezone", "auto".into()),
+            ("forecast_days", days.to_string()),
+        ];
+        if units == Units::Imperial {
+            query.push(("temperature_unit", "fahrenheit".into()));
+            query.push(("wind_speed_unit", "mph".into()));
+            query.push(("precipitation_unit", "inch".into()));
+        }
+        let body: serde_json::Value = client
+            .get(OPEN_METEO_URL)
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_open_meteo(&body)
+    }
+}
+
+struct OpenWeatherMap {
+    api_key: String,
+}
+
+#[async_trait]
+impl WeatherProvider for OpenWeatherMap {
+    fn name(&self) -> &'static str {
+        "OpenWeatherMap"
+    }
+
+    async fn report(
+        &self,
+        client: &reqwest::Client,
+        place: &Place,
+        days: usize,
+        units: Units,
+    ) -> anyhow::Result<Report> {
+        let query = [
+            ("lat", place.latitude.to_string()),
+            ("lon", place.longitude.to_string()),
+            ("appid", self.api_key.clone()),
+            (
+                "units",
+                match units {
+                    Units::Metric => "metric",
+                    Units::Imperial => "imperial",
+                }
+                .into(),
+            ),
+        ];
+        let mut bodies = Vec::with_capacity(2);
+        for endpoint in ["weather", "forecast"] {
+            let response = client
+                .get(format!("{OPENWEATHERMAP_URL}/{endpoint}"))
+                .query(&query)
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                anyhow::bail!("OpenWeatherMap returned status {}", response.status());
+            }
+            bodies.push(response.json::<serde_json::Value>().await?);
+        }
+        parse_openweathermap(&bodies[0], &bodies[1], days, units)
+    }
+}
+
+/// Describe a WMO weather interpretation code as used by Open-Meteo.
+fn wmo_summary(code: i64) -> &'static str {
+    match code {
+        0 => "clear sky",
+        1 => "mainly clear",
+        2 => "partly cloudy",
+        3 => "overcast",
+        45 | 48 => "fog",
+        51 | 53 | 55 => "drizzle",
+        56 | 57 => "freezing drizzle",
+        61 => "light rain",
+        63 => "rain",
+        65 => "heavy rain",
+        66 | 67 => "freezing rain",
+        71 => "light snow",
+        73 => "snow",
+        75 => "heavy snow",
+        77 => "snow grains",
+        80..=82 => "rain showers",
+        85 | 86 => "snow showers",
+        95 => "thunderstorm",
+        96 | 99 => "thunderstorm with hail",
+        _ => "unknown conditions",
+    }
+}
+
+fn number(value: &serde_json::Value, key: &str) -> Option<f64> {
+    value.get(key).and_then(serde_json::Value::as_f64)
+}
+
+fn parse_open_meteo(body: &serde_json::Value) -> anyhow::Result<Report> {
+    if let Some(reason) = body.get("reason").and_then(serde_json::Value::as_str) {
+        anyhow::bail!("Open-Meteo error: {reason}");
+    }
+    let current = body
+        .get("current")
+        .ok_or_else(|| anyhow::anyhow!("Open-Meteo response has no current conditions"))?;
+    let code = |value: &serde_json::Value| value.as_i64().map_or("unknown conditions", wmo_summary);
+    let current = Conditions {
+        temperature: number(current, "temperature_2m")
+            .ok_or_else(|| anyhow::anyhow!("Open-Meteo response has no temperature"))?,
+        feels_like: number(current, "apparent_temperature"),
+        humidity: number(current, "relative_humidity_2m"),
+        wind_speed: number(current, "wind_speed_10m"),
+        precipitation: number(current, "precipitation"),
+        summary: code(&current["weather_code"]).into(),
+    };
+
+    let daily = &body["daily"];
+    let column = |key: &str, index: usize| daily[key].get(index).and_then(|v| v.as_f64());
+    let dates = daily["time"].as_array().cloned().unwrap_or_default();
+    let daily = dates
+        .iter()
+        .enumerate()
+        .filter_map(|(index, date)| {
+            Some(DailyForecast {
+                date: date.as_str()?.to_string(),
+                high: column("temperature_2m_max", index)?,
+                low: column("temperature_2m_min", index)?,
+                precipitation_chance: column("precipitation_probability_max", index),
+                precipitation: column("precipitation_sum", index),
+                summary: code(&daily["weather_code"][index]).into(),
+            })
+        })
+        .collect();
+    Ok(Report { current, daily })
+}
+
+/// Combine OpenWeatherMap's current weather with its 3-hourly forecast,
+/// grouped into local calendar days.
+fn parse_openweathermap(
+    current: &serde_json::Value,
+    forecast: &serde_json::Value,
+    days: usize,
+    units: Units,
+) -> anyhow::Result<Report> {
+    let description = |value: &serde_json::Value| {
+        value["weather"][0]["description"]
+            .as_str()
+            .unwrap_or("unknown conditions")
+            .to_string()
+    };
+    // Rain and snow volumes are always reported in millimetres; metric wind
+    // speed is in m/s.
+    let precipitation = |value: &serde_json::Value, window: &str| {
+        let total: f64 = ["rain", "snow"]
+            .iter()
+            .filter_map(|kind| value[*kind][window].as_f64())
+            .sum();
+        match units {
+            Units::Metric => total,
+            Units::Imperial => total / 25.4,
+        }
+    };
+    let main = &current["main"];
+    let wind_speed = number(&current["wind"], "speed").map(|speed| match units {
+        Units::Metric => speed * 3.6,
+        Units::Imperial => speed,
+    });
+    let current_conditions = Conditions {
+        temperature: number(main, "temp")
+            .ok_or_else(|| anyhow::anyhow!("OpenWeatherMap response has no temperature"))?,
+        feels_like: number(main, "feels_like"),
+        humidity: number(main, "humidity"),
+        wind_speed,
+        precipitation: Some(precipitation(current, "1h")),
+        summary: description(current),
+    };
+
+    let offset = forecast["city"]["timezone"].as_i64().unwrap_or(0);
+    let mut by_date: BTreeMap<String, Vec<&serde_json::Value>> = BTreeMap::new();
+    for entry in forecast["list"].as_array().into_iter().flatten() {
+        let Some(local) = entry["dt"]
+            .as_i64()
+            .and_then(|dt| chrono::DateTime::from_timestamp(dt + offset, 0))
+        else {
+            continue;
+        };
+        by_date
+            .entry(local.format("%Y-%m-%d").to_string())
+            .or_default()
+            .push(entry);
+    }
+    let daily = by_date
+        .into_iter()
+        .take(days)
+        .filter_map(|(date, entries)| {
+            let temps =
+                |key: &'static str| entries.iter().filter_map(move |e| e["main"][key].as_f64());
+            let high = temps("temp_max").reduce(f64::max)?;
+            let low = temps("temp_min").reduce(f64::min)?;
+            let mut summaries: HashMap<String, usize> = HashMap::new();
+            for entry in &entries {
+                *summaries.entry(description(entry)).or_default() += 1;
+            }
+            let summary = summaries
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map(|(summary, _)| summary)?;
+            Some(DailyForecast {
+                date,
+                high,
+                low,
+                precipitation_chance: entries
+                    .iter()
+                    .filter_map(|e| e["pop"].as_f64())
+                    .reduce(f64::max)
+                    .map(|pop| pop * 100.0),
+                precipitation: Some(entries.iter().map(|e| precipitation(e, "3h")).sum()),
+                summary,
+            })
+        })
+        .collect();
+    Ok(Report {
+        current: current_conditions,
+        daily,
+    })
+}
+
+fn format_report(place: &Place, provider: &str, report: &Report, units: Units) -> String {
+    let (t, speed, precip) = (units.temperature(), units.speed(), units.precipitation());
+    let now = &report.current;
+    let mut output = format!(
+        "Weather for {} ({:.2}, {:.2}) via {provider}\nNow: {:.1}{t}",
+        place.label, place.latitude, place.longitude, now.temperature
+    );
+    if let Some(feels_like) = now.feels_like {
+        let _ = write!(output, " (feels like {feels_like:.1}{t})");
+    }
+    let _ = write!(output, ", {}", now.summary);
+    if let Some(humidity) = now.humidity {
+        let _ = write!(output, ", humidity {humidity:.0}%");
+    }
+    if let Some(wind) = now.wind_speed {
+        let _ = write!(output, ", wind {wind:.1} {speed}");
+    }
+    if let Some(amount) = now.precipitation.filter(|amount| *amount > 0.0) {
+        let _ = write!(output, ", precipitation {amount:.1} {precip}");
+    }
+    if !report.daily.is_empty() {
+        output.push_str("\nForecast:");
+    }
+    for day in &report.daily {
+        let _ = write!(
+            output,
+            "\n- {}: {:.0}–{:.0}{t}, {}",
+            day.date, day.low, day.high, day.summary
+        );
+        if let Some(chance) = day.precipitation_chance {
+            let _ = write!(output, ", {chance:.0}% chance of precipitation");
+        }
+        if let Some(amount) = day.precipitation.filter(|amount| *amount > 0.0) {
+            let _ = write!(output, " ({amount:.1} {precip})");
+        }
+    }
+    output
+}
+
+/// Pick the geocoding result that matches the qualifiers after the first
+/// comma, so "Paris, Texas" does not resolve to France.
+fn pick_place(body: &serde_json::Value, qualifiers: &[String]) -> Option<Place> {
+    body["results"]
+        .as_array()?
+        .iter()
+        .find(|result| {
+            qualifiers.iter().all(|qualifier| {
+                ["country", "country_code", "admin1", "admin2"]
+                    .iter()
+                    .filter_map(|key| result[*key].as_str())
+                    .any(|value| value.eq_ignore_ascii_case(qualifier))
+            })
+        })
+        .and_then(|result| {
+            let mut label = vec![result["name"].as_str()?];
+            for key in ["admin1", "country"] {
+                if let Some(part) = result[key].as_str() {
+                    if !label.contains(&part) {
+                        label.push(part);
+                    }
+                }
+            }
+            Some(Place {
+                label: label.join(", "),
+                latitude: result["latitude"].as_f64()?,
+                longitude: result["longitude"].as_f64()?,
+            })
+        })
+}
+
+/// Current conditions and forecasts by place name or coordinates.
+///
+/// Place names are geocoded through Open-Meteo and cached in
+/// `state/weather_places.json`, so repeated questions about the same city
+/// cost one forecast request.
+pub struct WeatherTool {
+    provider: Box<dyn WeatherProvider>,
+    units: Units,
+    timeout_secs: u64,
+    places_path: PathBuf,
+    places: parking_lot::Mutex<Option<HashMap<String, Place>>>,
+}
+
+impl WeatherTool {
+    pub fn new(config: &WeatherConfig, workspace_dir: &Path) -> Self {
+        let provider: Box<dyn WeatherProvider> = match config.provider.trim() {
+            "openweathermap" => Box::new(OpenWeatherMap {
+                api_key: config.api_key.clone().unwrap_or_default(),
+            }),
+            _ => Box::new(OpenMeteo),
+        };
+        Self {
+            provider,
+            units: Units::parse(&config.units).unwrap_or(Units::Metric),
+            timeout_secs: config.timeout_secs,
+            places_path: workspace_dir.join("state").join(PLACES_FILE),
+            places: parking_lot::Mutex::new(None),
+        }
+    }
+
+    fn client(&self) -> reqwest::Client {
+        crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.weather",
+            self.timeout_secs,
+            10,
+        )
+    }
+
+    fn load_places(&self) -> HashMap<String, Place> {
+        std::fs::read_to_string(&self.places_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn cached_place(&self, key: &str) -> Option<Place> {
+        let mut places = self.places.lock();
+        places
+            .get_or_insert_with(|| self.load_places())
+            .get(key)
+            .cloned()
+    }
+
+    fn remember_place(&self, key: String, place: Place) {
+        let mut places = self.places.lock();
+        let places = places.get_or_insert_with(|| self.load_places());
+        if places.len() >= PLACES_CAPACITY && !places.contains_key(&key) {
+            places.clear();
+        }
+        places.insert(key, place);
+        let write = crate::state::write_atomic(
+            &self.places_path,
+            serde_json::to_string(&*places).unwrap_or_default(),
+        );
+        if let Err(e) = write {
+            tracing::warn!("weather: failed to persist geocoding cache: {e}");
+        }
+    }
+
+    async fn geocode(&self, location: &str) -> anyhow::Result<Place> {
+        let key = location.to_lowercase();
+        if let Some(place) = self.cached_place(&key) {
+            return Ok(place);
+        }
+        let mut parts = location.split(',').map(str::trim);
+        let name = parts.next().unwrap_or_default();
+        let qualifiers: Vec<String> = parts.filter(|p| !p.is_empty()).map(Into::into).collect();
+        let body: serde_json::Value = self
+            .client()
+            .get(GEOCODING_URL)
+            .query(&[
+                ("name", name),
+                ("count", "10"),
+                ("language", "en"),
+                ("format", "json"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let place = pick_place(&body, &qualifiers)
+            .ok_or_else(|| anyhow::anyhow!("no place found matching '{location}'"))?;
+        self.remember_place(key, place.clone());
+        Ok(place)
+    }
+
+    async fn resolve_place(&self, args: &serde_json::Value) -> Result<Place, String> {
+        if let Some(location) = args
+            .get("location")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|location| !location.is_empty())
+        {
+            return self
+                .geocode(location)
+                .await
+                .map_err(|e| format!("Geocoding failed: {e}"));
+        }
+        let latitude =
+            coordinate(args, "latitude", 90.0).map_err(|e| format!("{e} (or pass 'location')"))?;
+        let longitude = coordinate(args, "longitude", 180.0)?;
+        Ok(Place {
+            label: format!("{latitude}, {longitude}"),
+            latitude,
+            longitude,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for WeatherTool {
+    fn name(&self) -> &str {
+        "weather"
+    }
+
+    fn description(&self) -> &str {
+        "Get current weather and a daily forecast (up to 7 days) for a place name (e.g. \"Lisbon\" or \"Paris, Texas\") or latitude/longitude. Use this instead of web search for weather questions."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "location": {
+                    "type": "string",
+                    "description": "Place name, optionally qualified with region or country after a comma"
+                },
+                "latitude": {
+                    "type": "number",
+                    "description": "Latitude in decimal degrees (used when location is omitted)"
+                },
+                "longitude": {
+                    "type": "number",
+                    "description": "Longitude in decimal degrees (used when location is omitted)"
+                },
+                "days": {
+                    "type": "integer",
+                    "description": "Forecast days including today (0 for current conditions only, max 7)",
+                    "default": DEFAULT_DAYS
+                },
+                "units": {
+                    "type": "string",
+                    "enum": ["metric", "imperial"],
+                    "description": "Override the configured units"
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let units = match args.get("units").and_then(serde_json::Value::as_str) {
+            Some(value) => match Units::parse(value) {
+                Some(units) => units,
+                None => return Ok(failure(format!("Unknown units '{value}'"))),
+            },
+            None => self.units,
+        };
+        let days = args
+            .get("days")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(DEFAULT_DAYS, |days| {
+                usize::try_from(days).unwrap_or(MAX_DAYS).min(MAX_DAYS)
+            });
+        let place = match self.resolve_place(&args).await {
+            Ok(place) => place,
+            Err(error) => return Ok(failure(error)),
+        };
+
+        // Providers always return today; trim it away for current-only requests.
+        match self
+            .provider
+            .report(&self.client(), &place, days.max(1), units)
+            .await
+        {
+            Ok(mut report) => {
+                report.daily.truncate(days);
+                Ok(ToolResult {
+                    success: true,
+                    output: format_report(&place, self.provider.name(), &report, units),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(failure(format!(
+                "{} request failed: {e}",
+                self.provider.name()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_meteo_responses_are_normalized() {
+        let body = json!({
+            "current": {
+                "temperature_2m": 14.24,
+                "apparent_temperature": 12.9,
+                "relative_humidity_2m": 71,
+                "precipitation": 0.0,
+                "weather_code": 2,
+                "wind_speed_10m": 11.2
+            },
+            "daily": {
+                "time": ["2026-10-15", "2026-10-16"],
+                "weather_code": [61, 0],
+                "temperature_2m_max": [16.1, 18.0],
+                "temperature_2m_min": [9.4, 8.2],
+                "precipitation_probability_max": [60, 5],
+                "precipitation_sum": [2.1, 0.0]
+            }
+        });
+        let report = parse_open_meteo(&body).unwrap();
+        assert_eq!(report.daily.len(), 2);
+        assert_eq!(report.daily[0].summary, "light rain");
+
+        let place = Place {
+            label: "Berlin, Germany".into(),
+            latitude: 52.52,
+            longitude: 13.41,
+        };
+        assert_eq!(
+            format_report(&place, "Open-Meteo", &report, Units::Metric),
+            "Weather for Berlin, Germany (52.52, 13.41) via Open-Meteo\n\
+             Now: 14.2°C (feels like 12.9°C), partly cloudy, humidity 71%, wind 11.2 km/h\n\
+             Forecast:\n\
+             - 2026-10-15: 9–16°C, light rain, 60% chance of precipitation (2.1 mm)\n\
+             - 2026-10-16: 8–18°C, clear sky, 5% chance of precipitation"
+        );
+    }
+
+    #[test]
+    fn openweathermap_forecast_is_grouped_by_local_day() {
+        let current = json!({
+            "main": { "temp": 71.6, "feels_like": 70.0, "humidity": 40 },
+            "wind": { "speed": 5.0 },
+            "weather": [{ "description": "few clouds" }]
+        });
+        // UTC-5: 2026-10-16 03:00 UTC is still the 15th locally.
+        let forecast = json!({
+            "city": { "timezone": -18000 },
+            "list": [
+                { "dt": 1_792_083_600_i64, "main": { "temp_min": 60.0, "temp_max": 75.0 }, "pop": 0.2,
+                  "weather": [{ "description": "few clouds" }] },
+                { "dt": 1_792_119_600_i64, "main": { "temp_min": 58.0, "temp_max": 62.0 }, "pop": 0.7,
+                  "rain": { "3h": 25.4 }, "weather": [{ "description": "light rain" }] },
+                { "dt": 1_792_141_200_i64, "main": { "temp_min": 55.0, "temp_max": 66.0 }, "pop": 0.1,
+                  "weather": [{ "description": "clear sky" }] }
+            ]
+        });
+        let report = parse_openweathermap(&current, &forecast, 1, Units::Imperial).unwrap();
+        assert_eq!(report.current.wind_speed, Some(5.0));
+        assert_eq!(report.daily.len(), 1);
+        let today = &report.daily[0];
+        assert_eq!(today.date, "2026-10-15");
+        assert!((today.low - 58.0).abs() < f64::EPSILON);
+        assert!((today.high - 75.0).abs() < f64::EPSILON);
+        assert_eq!(today.precipitation_chance, Some(70.0));
+        assert_eq!(today.precipitation, Some(1.0));
+    }
+
+    #[test]
+    fn geocoding_results_honour_qualifiers() {
+        let body = json!({ "results": [
+            { "name": "Paris", "admin1": "Île-de-France", "country": "France",
+              "country_code": "FR", "latitude": 48.85, "longitude": 2.35 },
+            { "name": "Paris", "admin1": "Texas", "country": "United States",
+              "country_code": "US", "latitude": 33.66, "longitude": -95.56 }
+        ]});
+        assert_eq!(
+            pick_place(&body, &[]).unwrap().label,
+            "Paris, Île-de-France, France"
+        );
+        let texas = pick_place(&body, &["texas".into()]).unwrap();
+        assert_eq!(texas.label, "Paris, Texas, United States");
+        assert!(pick_place(&body, &["Ontario".into()]).is_none());
+    }
+
+    #[tokio::test]
+    async fn place_names_are_served_from_the_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let tool = WeatherTool::new(&WeatherConfig::default(), tmp.path());
+        let lisbon = Place {
+            label: "Lisbon, Portugal".into(),
+            latitude: 38.72,
+            longitude: -9.14,
+        };
+        tool.remember_place("lisbon".into(), lisbon.clone());
+
+        let reloaded = WeatherTool::new(&WeatherConfig::default(), tmp.path());
+        assert_eq!(reloaded.geocode("Lisbon").await.unwrap(), lisbon);
+
+        let missing = reloaded.execute(json!({ "days": 2 })).await.unwrap();
+        assert!(missing.error.unwrap().contains("or pass 'location'"));
+    }
+}
@@ -40,12 +40,16 @@ impl WebFetchTool {
     }
 
     fn validate_url(&self, raw_url: &str) -> anyhow::Result<String> {
-        validate_target_url(
+        let url = validate_target_url(
             raw_url,
             &self.allowed_domains,
             &self.blocked_domains,
             "web_fetch",
-        )
+        )?;
+        self.security
+            .check_network_target(&url)
+            .map_err(anyhow::Error::msg)?;
+        Ok(url)
     }
 
     fn truncate_response(&self, text: &str) -> String {
@@ -150,17 +154,21 @@ impl Tool for WebFetchTool {
 
         let allowed_domains = self.allowed_domains.clone();
         let blocked_domains = self.blocked_domains.clone();
+        let security = self.security.clone();
         let redirect_policy = reqwest::redirect::Policy::custom(move |attempt| {
             if attempt.previous().len() >= 10 {
                 return attempt.error(std::io::Error::other("Too many redirects (max 10)"));
             }
 
-            if let Err(err) = validate_target_url(
+            let verdict = validate_target_url(
                 attempt.url().as_str(),
                 &allowed_domains,
                 &blocked_domains,
                 "web_fetch",
-            ) {
+            )
+            .map_err(|err| err.to_string())
+            .and_then(|_| security.check_network_target(attempt.url().as_str()));
+            if let Err(err) = verdict {
                 return attempt.error(std::io::Error::new(
                     std::io::ErrorKind::PermissionDenied,
                     format!("Blocked redirect target: {err}"),
@@ -248,6 +256,13 @@ impl Tool for WebFetchTool {
             }
         };
 
+        let title = if body_mode == "html" {
+            html_title(&body)
+        } else {
+            String::new()
+        };
+        super::citations::record(super::citations::SourceKind::Fetch, &title, &url);
+
         let text = if body_mode == "html" {
             nanohtml2text::html2text(&body)
         } else {
@@ -264,7 +279,25 @@ impl Tool for WebFetchTool {
     }
 }
 
-// ── Helper functions (independent from http_request.rs per DRY rule-of-three) ──
+/// Text of the page's `<title>` element, or empty when there is none.
+fn html_title(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let Some(open) = lower.find("<title") else {
+        return String::new();
+    };
+    let Some(start) = lower[open..].find('>').map(|i| open + i + 1) else {
+        return String::new();
+    };
+    let Some(end) = lower[start..].find("</title>").map(|i| start + i) else {
+        return String::new();
+    };
+    nanohtml2text::html2text(&html[start..end])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// ── Helper functions (independent from http_request.rs per DRY rule-of-three; shared with network_diag.rs) ──
 
 fn validate_target_url(
     raw_url: &str,
@@ -327,7 +360,7 @@ fn append_chunk_with_cap(buffer: &mut Vec<u8>, chunk: &[u8], hard_cap: usize) ->
     buffer.len() >= hard_cap
 }
 
-fn normalize_allowed_domains(domains: Vec<String>) -> Vec<String> {
+pub(super) fn normalize_allowed_domains(domains: Vec<String>) -> Vec<String> {
     let mut normalized = domains
         .into_iter()
         .filter_map(|d| normalize_domain(&d))
@@ -404,7 +437,7 @@ fn extract_host(url: &str) -> anyhow::Result<String> {
     Ok(host)
 }
 
-fn host_matches_allowlist(host: &str, allowed_domains: &[String]) -> bool {
+pub(super) fn host_matches_allowlist(host: &str, allowed_domains: &[String]) -> bool {
     if allowed_domains.iter().any(|domain| domain == "*") {
         return true;
     }
@@ -417,7 +450,7 @@ fn host_matches_allowlist(host: &str, allowed_domains: &[String]) -> bool {
     })
 }
 
-fn is_private_or_local_host(host: &str) -> bool {
+pub(super) fn is_private_or_local_host(host: &str) -> bool {
     let bare = host
         .strip_prefix('[')
         .and_then(|h| h.strip_suffix(']'))
@@ -461,7 +494,10 @@ fn validate_resolved_host_is_public(_host: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn validate_resolved_ips_are_public(host: &str, ips: &[std::net::IpAddr]) -> anyhow::Result<()> {
+pub(super) fn validate_resolved_ips_are_public(
+    host: &str,
+    ips: &[std::net::IpAddr],
+) -> anyhow::Result<()> {
     if ips.is_empty() {
         anyhow::bail!("Failed to resolve host '{host}'");
     }
@@ -511,6 +547,17 @@ mod tests {
     use super::*;
     use crate::security::{AutonomyLevel, SecurityPolicy};
 
+    #[test]
+    fn html_title_extracts_and_collapses_whitespace() {
+        assert_eq!(
+            html_title(
+                "<html><head><TITLE lang=\"en\">\n  Rust &amp; Cargo\n</TITLE></head></html>"
+            ),
+            "Rust & Cargo"
+        );
+        assert_eq!(html_title("<p>no title</p>"), "");
+    }
+
     fn test_tool(allowed_domains: Vec<&str>) -> WebFetchTool {
         test_tool_with_blocklist(allowed_domains, vec![])
     }
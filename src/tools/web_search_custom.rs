@@ -0,0 +1,214 @@
+//! Search APIs defined in config (`[[web_search.custom_providers]]`).
+//!
+//! The request is built from the provider's URL and body templates; results
+//! are read from a JSON response with simple dotted paths (`$.web.results`,
+//! `meta.title`, `items[0]`), or from any body with a regex whose named groups
+//! are `url`, `title`, and `snippet`.
+//! A `{recency}` placeholder carries the provider's own value for the
+//! requested period (`recency_values`).
+
+use super::web_search_tool::SearchHit;
+use crate::config::CustomSearchProviderConfig;
+use regex::Regex;
+use serde_json::Value;
+
+/// Run `query` against `provider` and return at most `max_results` hits.
+/// `recency` ("day", "week", …) is looked up in the provider's
+/// `recency_values`; `{recency}` becomes the mapped value or nothing.
+pub async fn search(
+    provider: &CustomSearchProviderConfig,
+    client: &reqwest::Client,
+    query: &str,
+    max_results: usize,
+    recency: Option<&str>,
+) -> anyhow::Result<Vec<SearchHit>> {
+    let recency = recency
+        .and_then(|r| provider.recency_values.get(r))
+        .map_or("", String::as_str);
+    let url = provider
+        .url
+        .replace("{query}", &urlencoding::encode(query))
+        .replace("{max_results}", &max_results.to_string())
+        .replace("{recency}", &urlencoding::encode(recency));
+
+    let mut request = if provider.method.eq_ignore_ascii_case("POST") {
+        let mut request = client.post(&url);
+        if let Some(body) = &provider.body {
+            let escaped = serde_json::to_string(query)?;
+            let escaped = &escaped[1..escaped.len() - 1];
+            let body = body
+                .replace("{query}", escaped)
+                .replace("{max_results}", &max_results.to_string())
+                .replace("{recency}", recency);
+            if !provider
+                .headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case("content-type"))
+            {
+                request = request.header("Content-Type", "application/json");
+            }
+            request = request.body(body);
+        }
+        request
+    } else {
+        client.get(&url)
+    };
+    for (name, value) in &provider.headers {
+        request = request.header(name, value);
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("{} search failed with status: {status}", provider.name);
+    }
+    let body = response.text().await?;
+    extract(provider, &body, max_results)
+}
+
+/// Read hits out of a response body.
+fn extract(
+    provider: &CustomSearchProviderConfig,
+    body: &str,
+    max_results: usize,
+) -> anyhow::Result<Vec<SearchHit>> {
+    if let Some(pattern) = &provider.result_regex {
+        let re = Regex::new(pattern)?;
+        let group = |caps: &regex::Captures<'_>, name: &str| {
+            caps.name(name)
+                .map(|m| m.as_str().trim().to_string())
+                .unwrap_or_default()
+        };
+        return Ok(re
+            .captures_iter(body)
+            .map(|caps| SearchHit {
+                title: group(&caps, "title"),
+                url: group(&caps, "url"),
+                snippet: group(&caps, "snippet"),
+            })
+            .filter(|hit| !hit.url.is_empty())
+            .take(max_results)
+            .collect());
+    }
+
+    let json: Value = serde_json::from_str(body).map_err(|e| {
+        anyhow::anyhow!(
+            "{} returned a response that is not JSON: {e}",
+            provider.name
+        )
+    })?;
+    let results = json_path(&json, &provider.results_path)
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} response has no result array at '{}'",
+                provider.name,
+                provider.results_path
+            )
+        })?;
+    Ok(results
+        .iter()
+        .map(|result| SearchHit {
+            title: text_at(result, &provider.title_path),
+            url: text_at(result, &provider.url_path),
+            snippet: text_at(result, &provider.snippet_path),
+        })
+        .filter(|hit| !hit.url.is_empty())
+        .take(max_results)
+        .collect())
+}
+
+/// Follow a dotted path such as `$.data.items[0]`; a leading `$` is optional.
+fn json_path<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let mut current = value;
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, indexes) = match segment.find('[') {
+            Some(pos) => segment.split_at(pos),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indexes.split('[').filter(|s| !s.is_empty()) {
+            let index: usize = index.strip_suffix(']')?.parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+fn text_at(value: &Value, path: &str) -> String {
+    match json_path(value, path) {
+        Some(Value::String(text)) => text.trim().to_string(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn provider() -> CustomSearchProviderConfig {
+        CustomSearchProviderConfig {
+            name: "searx".into(),
+            method: "GET".into(),
+            url: "https://searx.example/search?q={query}".into(),
+            body: None,
+            headers: HashMap::new(),
+            results_path: "$.results".into(),
+            title_path: "title".into(),
+            url_path: "url".into(),
+            snippet_path: "meta.summary".into(),
+            result_regex: None,
+            recency_values: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn json_path_follows_keys_and_indexes() {
+        let value = json!({"data": {"items": [{"name": "a"}, {"name": "b"}]}});
+        assert_eq!(json_path(&value, "$.data.items[1].name"), Some(&json!("b")));
+        assert_eq!(json_path(&value, "$"), Some(&value));
+        assert_eq!(json_path(&value, "data.missing"), None);
+    }
+
+    #[test]
+    fn extracts_hits_from_json() {
+        let body = json!({"results": [
+            {"title": "First", "url": "https://a.example", "meta": {"summary": " one "}},
+            {"title": "No link"},
+            {"title": "Second", "url": "https://b.example"}
+        ]})
+        .to_string();
+        let hits = extract(&provider(), &body, 5).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].snippet, "one");
+        assert_eq!(hits[1].url, "https://b.example");
+        assert_eq!(extract(&provider(), &body, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn extracts_hits_with_regex() {
+        let provider = CustomSearchProviderConfig {
+            result_regex: Some(r#"<a href="(?P<url>[^"]+)">(?P<title>[^<]+)</a>"#.into()),
+            ..provider()
+        };
+        let body = r#"<a href="https://a.example">A</a> <a href="https://b.example">B</a>"#;
+        let hits = extract(&provider, body, 5).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[1].title, "B");
+        assert!(hits[0].snippet.is_empty());
+    }
+
+    #[test]
+    fn missing_result_array_is_an_error() {
+        let err = extract(&provider(), r#"{"items": []}"#, 5).unwrap_err();
+        assert!(err.to_string().contains("$.results"));
+        assert!(extract(&provider(), "<html>", 5).is_err());
+    }
+}
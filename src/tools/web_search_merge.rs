@@ -0,0 +1,171 @@
+//! Combining results from several search providers (`web_search.strategy = "merge"`).
+//!
+//! Lists are merged in provider order, de-duplicated by normalized URL, and
+//! ranked either by interleaving (first result of each provider, then the
+//! second, …) or by reciprocal-rank score, which favours results that several
+//! providers rank highly.
+
+use super::web_search_tool::SearchHit;
+use std::collections::HashMap;
+
+/// Rank constant for reciprocal-rank fusion; damps the weight of top positions.
+const RRF_K: f64 = 60.0;
+
+/// How merged results are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeRanking {
+    Interleave,
+    Score,
+}
+
+impl MergeRanking {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "interleave" => Some(Self::Interleave),
+            "score" => Some(Self::Score),
+            _ => None,
+        }
+    }
+}
+
+/// Merge per-provider result lists (each in the provider's own rank order).
+pub fn merge(lists: Vec<Vec<SearchHit>>, ranking: MergeRanking) -> Vec<SearchHit> {
+    let mut merged: Vec<SearchHit> = Vec::new();
+    let mut index_by_url: HashMap<String, usize> = HashMap::new();
+    let mut scores: Vec<f64> = Vec::new();
+
+    let depth = lists.iter().map(Vec::len).max().unwrap_or(0);
+    for rank in 0..depth {
+        for list in &lists {
+            let Some(hit) = list.get(rank) else {
+                continue;
+            };
+            let score = 1.0 / (RRF_K + rank as f64 + 1.0);
+            let key = normalize_url(&hit.url);
+            match index_by_url.get(&key) {
+                Some(&i) => {
+                    scores[i] += score;
+                    // Keep the first provider's entry; fill in what it lacked.
+                    let existing = &mut merged[i];
+                    if existing.title.trim().is_empty() {
+                        existing.title.clone_from(&hit.title);
+                    }
+                    if existing.snippet.trim().is_empty() {
+                        existing.snippet.clone_from(&hit.snippet);
+                    }
+                }
+                None => {
+                    index_by_url.insert(key, merged.len());
+                    merged.push(hit.clone());
+                    scores.push(score);
+                }
+            }
+        }
+    }
+
+    if ranking == MergeRanking::Score {
+        let mut order: Vec<usize> = (0..merged.len()).collect();
+        // Stable sort keeps interleaved order among equal scores.
+        order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+        let mut slots: Vec<Option<SearchHit>> = merged.into_iter().map(Some).collect();
+        merged = order.into_iter().filter_map(|i| slots[i].take()).collect();
+    }
+    merged
+}
+
+/// URL key for de-duplication: scheme, `www.`, fragment, trailing slash and
+/// `utm_*` parameters are ignored; the host is lowercased.
+pub fn normalize_url(url: &str) -> String {
+    let trimmed = url.trim();
+    let Ok(mut parsed) = reqwest::Url::parse(trimmed) else {
+        return trimmed.to_lowercase();
+    };
+    parsed.set_fragment(None);
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_"))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+
+    let host = parsed.host_str().unwrap_or_default().to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let path = parsed.path().trim_end_matches('/');
+    match parsed.query() {
+        Some(query) => format!("{host}{path}?{query}"),
+        None => format!("{host}{path}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(url: &str) -> SearchHit {
+        SearchHit {
+            title: url.to_string(),
+            url: url.to_string(),
+            snippet: String::new(),
+        }
+    }
+
+    fn urls(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.url.as_str()).collect()
+    }
+
+    #[test]
+    fn normalize_url_ignores_cosmetic_differences() {
+        assert_eq!(
+            normalize_url("https://www.Example.com/docs/?utm_source=x#intro"),
+            normalize_url("http://example.com/docs")
+        );
+        assert_ne!(
+            normalize_url("https://example.com/docs?page=2"),
+            normalize_url("https://example.com/docs")
+        );
+    }
+
+    #[test]
+    fn interleave_alternates_providers_and_drops_duplicates() {
+        let first = vec![hit("https://a.example"), hit("https://b.example")];
+        let second = vec![
+            hit("https://www.a.example/"),
+            hit("https://c.example"),
+            hit("https://d.example"),
+        ];
+        let merged = merge(vec![first, second], MergeRanking::Interleave);
+        assert_eq!(
+            urls(&merged),
+            vec![
+                "https://a.example",
+                "https://b.example",
+                "https://c.example",
+                "https://d.example"
+            ]
+        );
+    }
+
+    #[test]
+    fn score_favours_results_found_by_several_providers() {
+        let first = vec![hit("https://a.example"), hit("https://b.example")];
+        let second = vec![hit("https://c.example"), hit("https://b.example")];
+        let merged = merge(vec![first, second], MergeRanking::Score);
+        assert_eq!(merged[0].url, "https://b.example");
+        assert_eq!(merged.len(), 3);
+    }
+
+    #[test]
+    fn duplicates_fill_in_missing_snippets() {
+        let first = vec![hit("https://a.example")];
+        let mut described = hit("https://a.example");
+        described.snippet = "About A".into();
+        let merged = merge(vec![first, vec![described]], MergeRanking::Interleave);
+        assert_eq!(merged[0].snippet, "About A");
+        assert!(MergeRanking::parse("Score").is_some());
+        assert!(MergeRanking::parse("best").is_none());
+    }
+}
@@ -1,14 +1,197 @@
 use super::traits::{Tool, ToolResult};
+use super::web_fetch::{host_matches_allowlist, normalize_allowed_domains};
+use super::web_search_custom;
+use super::web_search_merge::{self, MergeRanking};
+use crate::config::CustomSearchProviderConfig;
+use crate::providers::key_pool::{KeyOutcome, KeyPool};
 use async_trait::async_trait;
+use futures_util::future::join_all;
 use regex::Regex;
 use serde_json::json;
 use std::time::Duration;
 
+/// Built-in provider names; custom providers may not reuse them.
+pub const BUILTIN_PROVIDERS: &[&str] = &["duckduckgo", "ddg", "brave"];
+
+/// Providers that can search for images.
+const IMAGE_PROVIDERS: &[&str] = &["duckduckgo", "ddg", "brave"];
+
+const DUCKDUCKGO_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+
+/// Results requested from a provider when a domain filter may discard some.
+const FILTERED_FETCH_COUNT: usize = 20;
+
+/// `-site:` operators added to a query, to keep it short.
+const MAX_SITE_EXCLUSIONS: usize = 5;
+
+/// How recent results must be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recency {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Recency {
+    /// Accepted names; "any" (no restriction) is handled by callers.
+    pub const NAMES: &'static [&'static str] = &["day", "week", "month", "year"];
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "day" => Some(Self::Day),
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            "year" => Some(Self::Year),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+            Self::Year => "year",
+        }
+    }
+
+    /// Brave `freshness` value.
+    fn brave(self) -> &'static str {
+        match self {
+            Self::Day => "pd",
+            Self::Week => "pw",
+            Self::Month => "pm",
+            Self::Year => "py",
+        }
+    }
+
+    /// DuckDuckGo `df` value.
+    fn duckduckgo(self) -> &'static str {
+        match self {
+            Self::Day => "d",
+            Self::Week => "w",
+            Self::Month => "m",
+            Self::Year => "y",
+        }
+    }
+}
+
+/// One search as sent to each provider.
+struct SearchRequest<'a> {
+    query: &'a str,
+    /// `site:`/`-site:` operators for engines that understand them.
+    site_operators: Vec<String>,
+    /// Results to ask each provider for.
+    count: usize,
+    recency: Option<Recency>,
+}
+
+/// One search result, before formatting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+}
+
+/// One image search result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImageHit {
+    title: String,
+    image_url: String,
+    /// Page the image appears on.
+    source_url: String,
+    width: Option<u64>,
+    height: Option<u64>,
+}
+
+/// Allow/deny domain lists applied to result URLs.
+#[derive(Debug, Clone, Default)]
+struct DomainFilter {
+    /// Empty means any domain.
+    allowed: Vec<String>,
+    blocked: Vec<String>,
+}
+
+impl DomainFilter {
+    fn new(allowed: Vec<String>, blocked: Vec<String>) -> Self {
+        Self {
+            allowed: normalize_allowed_domains(allowed),
+            blocked: normalize_allowed_domains(blocked),
+        }
+    }
+
+    /// Filter from the `allowed_domains` / `blocked_domains` tool arguments.
+    fn from_args(args: &serde_json::Value) -> Self {
+        let list = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.as_str())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Self::new(list("allowed_domains"), list("blocked_domains"))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.blocked.is_empty()
+    }
+
+    fn permits(&self, url: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let Some(host) = result_host(url) else {
+            return false;
+        };
+        !host_matches_allowlist(&host, &self.blocked)
+            && (self.allowed.is_empty() || host_matches_allowlist(&host, &self.allowed))
+    }
+
+    /// Search operators asking the engine for the same filtering: `site:` for
+    /// a single allowed domain, `-site:` for blocked ones.
+    fn query_operators(&self) -> Vec<String> {
+        let plain = |domain: &&String| !domain.contains('*');
+        let mut operators = Vec::new();
+        if let [domain] = self.allowed.as_slice() {
+            if plain(&domain) {
+                operators.push(format!("site:{domain}"));
+            }
+        }
+        operators.extend(
+            self.blocked
+                .iter()
+                .filter(plain)
+                .take(MAX_SITE_EXCLUSIONS)
+                .map(|domain| format!("-site:{domain}")),
+        );
+        operators
+    }
+}
+
 /// Web search tool for searching the internet.
-/// Supports multiple providers: DuckDuckGo (free), Brave (requires API key).
+/// Supports multiple providers: DuckDuckGo (free), Brave (requires API key),
+/// and search APIs defined in config. Results from every provider pass the
+/// configured and per-call domain filters.
 pub struct WebSearchTool {
     provider: String,
-    brave_api_key: Option<String>,
+    /// Tried in order when `provider` fails.
+    fallback_providers: Vec<String>,
+    custom_providers: Vec<CustomSearchProviderConfig>,
+    /// When set, these providers are queried together and their results merged
+    /// instead of falling back one by one.
+    merge: Option<(Vec<String>, MergeRanking)>,
+    /// Brave keys from the comma-separated `brave_api_key`.
+    brave_keys: KeyPool,
+    domains: DomainFilter,
+    /// Default for the `recency` argument.
+    recency: Option<Recency>,
     max_results: usize,
     timeout_secs: u64,
 }
@@ -22,146 +205,600 @@ impl WebSearchTool {
     ) -> Self {
         Self {
             provider: provider.trim().to_lowercase(),
-            brave_api_key,
+            fallback_providers: Vec::new(),
+            custom_providers: Vec::new(),
+            merge: None,
+            brave_keys: KeyPool::new(
+                KeyPool::parse_keys(brave_api_key.as_deref().unwrap_or_default()),
+                &[],
+                Duration::from_secs(60),
+            ),
+            domains: DomainFilter::default(),
+            recency: None,
             max_results: max_results.clamp(1, 10),
             timeout_secs: timeout_secs.max(1),
         }
     }
 
-    async fn search_duckduckgo(&self, query: &str) -> anyhow::Result<String> {
-        let encoded_query = urlencoding::encode(query);
-        let search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
+    pub fn with_fallback_providers(mut self, providers: Vec<String>) -> Self {
+        self.fallback_providers = providers
+            .iter()
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+        self
+    }
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(self.timeout_secs))
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-            .build()?;
+    pub fn with_custom_providers(mut self, providers: Vec<CustomSearchProviderConfig>) -> Self {
+        self.custom_providers = providers;
+        self
+    }
 
-        let response = client.get(&search_url).send().await?;
+    /// Query `providers` together and merge their results. An empty list
+    /// merges `provider` and the fallbacks.
+    pub fn with_merge_strategy(mut self, providers: Vec<String>, ranking: MergeRanking) -> Self {
+        let providers: Vec<String> = providers
+            .iter()
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+        self.merge = Some((providers, ranking));
+        self
+    }
 
-        if !response.status().is_success() {
-            anyhow::bail!(
-                "DuckDuckGo search failed with status: {}",
-                response.status()
-            );
+    /// Only return results from `allowed_domains` (any when empty) and never
+    /// from `blocked_domains`.
+    pub fn with_domain_filter(
+        mut self,
+        allowed_domains: Vec<String>,
+        blocked_domains: Vec<String>,
+    ) -> Self {
+        self.domains = DomainFilter::new(allowed_domains, blocked_domains);
+        self
+    }
+
+    /// Recency applied when a call does not pass `recency`.
+    pub fn with_default_recency(mut self, recency: Option<Recency>) -> Self {
+        self.recency = recency;
+        self
+    }
+
+    /// `provider` followed by the fallbacks, without repeats.
+    fn provider_chain(&self) -> Vec<&str> {
+        let mut chain: Vec<&str> = Vec::new();
+        for name in std::iter::once(&self.provider).chain(&self.fallback_providers) {
+            if !chain.contains(&name.as_str()) {
+                chain.push(name);
+            }
         }
+        chain
+    }
 
-        let html = response.text().await?;
-        self.parse_duckduckgo_results(&html, query)
+    /// Search with one provider. Built-in engines get the site operators
+    /// appended to the query and the recency as their native filter.
+    async fn search_with(
+        &self,
+        provider: &str,
+        request: &SearchRequest<'_>,
+    ) -> anyhow::Result<Vec<SearchHit>> {
+        let native_query = if request.site_operators.is_empty() {
+            request.query.to_string()
+        } else {
+            format!("{} {}", request.query, request.site_operators.join(" "))
+        };
+        match provider {
+            "duckduckgo" | "ddg" => {
+                self.search_duckduckgo(&native_query, request.recency)
+                    .await
+            }
+            "brave" => {
+                self.search_brave(&native_query, request.count, request.recency)
+                    .await
+            }
+            _ => match self.custom_provider(provider) {
+                Some(custom) => {
+                    let client = reqwest::Client::builder()
+                        .timeout(Duration::from_secs(self.timeout_secs))
+                        .build()?;
+                    web_search_custom::search(
+                        custom,
+                        &client,
+                        request.query,
+                        request.count,
+                        request.recency.map(Recency::as_str),
+                    )
+                    .await
+                }
+                None => anyhow::bail!(
+                    "Unknown search provider: '{}'. Set tools.web_search.provider to 'duckduckgo', 'brave', or a custom provider name in config.toml",
+                    provider
+                ),
+            },
+        }
     }
 
-    fn parse_duckduckgo_results(&self, html: &str, query: &str) -> anyhow::Result<String> {
-        // Extract result links: <a class="result__a" href="...">Title</a>
-        let link_regex = Regex::new(
-            r#"<a[^>]*class="[^"]*result__a[^"]*"[^>]*href="([^"]+)"[^>]*>([\s\S]*?)</a>"#,
-        )?;
+    /// Try the provider chain in order; the first provider that answers wins.
+    async fn search_fallback(
+        &self,
+        request: &SearchRequest<'_>,
+    ) -> anyhow::Result<(String, Vec<SearchHit>)> {
+        let mut failures = Vec::new();
+        for provider in self.provider_chain() {
+            match self.search_with(provider, request).await {
+                Ok(hits) => return Ok((self.provider_label(provider).to_string(), hits)),
+                Err(e) => {
+                    tracing::warn!("Web search via {provider} failed: {e}");
+                    failures.push((provider, e));
+                }
+            }
+        }
+        Err(chain_error(failures))
+    }
 
-        // Extract snippets: <a class="result__snippet">...</a>
-        let snippet_regex = Regex::new(r#"<a class="result__snippet[^"]*"[^>]*>([\s\S]*?)</a>"#)?;
+    /// Query `providers` concurrently and merge what comes back. Providers
+    /// that fail are left out; the search fails only when all of them do.
+    async fn search_merged(
+        &self,
+        providers: &[String],
+        ranking: MergeRanking,
+        request: &SearchRequest<'_>,
+    ) -> anyhow::Result<(String, Vec<SearchHit>)> {
+        let chain = if providers.is_empty() {
+            self.provider_chain()
+        } else {
+            let mut chain: Vec<&str> = Vec::new();
+            for name in providers {
+                if !chain.contains(&name.as_str()) {
+                    chain.push(name);
+                }
+            }
+            chain
+        };
+        let results = join_all(
+            chain
+                .iter()
+                .map(|provider| self.search_with(provider, request)),
+        )
+        .await;
 
-        let link_matches: Vec<_> = link_regex
-            .captures_iter(html)
-            .take(self.max_results + 2)
-            .collect();
+        let mut labels = Vec::new();
+        let mut lists = Vec::new();
+        let mut failures = Vec::new();
+        for (provider, result) in chain.into_iter().zip(results) {
+            match result {
+                Ok(hits) => {
+                    labels.push(self.provider_label(provider));
+                    lists.push(hits);
+                }
+                Err(e) => {
+                    tracing::warn!("Web search via {provider} failed: {e}");
+                    failures.push((provider, e));
+                }
+            }
+        }
+        if lists.is_empty() {
+            return Err(chain_error(failures));
+        }
+        Ok((labels.join(" + "), web_search_merge::merge(lists, ranking)))
+    }
 
-        let snippet_matches: Vec<_> = snippet_regex
-            .captures_iter(html)
-            .take(self.max_results + 2)
+    /// Image search over the providers in the chain that support it.
+    async fn search_images(
+        &self,
+        request: &SearchRequest<'_>,
+    ) -> anyhow::Result<(String, Vec<ImageHit>)> {
+        let chain: Vec<&str> = self
+            .provider_chain()
+            .into_iter()
+            .filter(|provider| IMAGE_PROVIDERS.contains(provider))
             .collect();
+        if chain.is_empty() {
+            anyhow::bail!(
+                "Image search needs the duckduckgo or brave provider in tools.web_search.provider or fallback_providers"
+            );
+        }
+        let query = if request.site_operators.is_empty() {
+            request.query.to_string()
+        } else {
+            format!("{} {}", request.query, request.site_operators.join(" "))
+        };
 
-        if link_matches.is_empty() {
-            return Ok(format!("No results found for: {}", query));
+        let mut failures = Vec::new();
+        for provider in chain {
+            let result = match provider {
+                "brave" => self.search_brave_images(&query, request.count).await,
+                _ => self.search_duckduckgo_images(&query).await,
+            };
+            match result {
+                Ok(hits) => return Ok((self.provider_label(provider).to_string(), hits)),
+                Err(e) => {
+                    tracing::warn!("Image search via {provider} failed: {e}");
+                    failures.push((provider, e));
+                }
+            }
         }
+        Err(chain_error(failures))
+    }
 
-        let mut lines = vec![format!("Search results for: {} (via DuckDuckGo)", query)];
+    fn format_images(
+        &self,
+        provider: &str,
+        hits: &[ImageHit],
+        hidden: usize,
+        query: &str,
+    ) -> String {
+        if hits.is_empty() {
+            return match hidden {
+                0 => format!("No images found for: {}", query),
+                _ => format!(
+                    "No images found for: {} ({} hidden by the domain filter)",
+                    query, hidden
+                ),
+            };
+        }
 
-        let count = link_matches.len().min(self.max_results);
+        let mut lines = vec![format!(
+            "Image results for: {} (via {}). To show one, attach its image URL with an IMAGE marker.",
+            query, provider
+        )];
+        for (i, hit) in hits.iter().take(self.max_results).enumerate() {
+            let title = strip_tags(&hit.title);
+            let title = if title.trim().is_empty() {
+                "Untitled image"
+            } else {
+                title.trim()
+            };
+            match (hit.width, hit.height) {
+                (Some(width), Some(height)) => {
+                    lines.push(format!("{}. {} ({}x{})", i + 1, title, width, height));
+                }
+                _ => lines.push(format!("{}. {}", i + 1, title)),
+            }
+            lines.push(format!("   image: {}", hit.image_url));
+            if !hit.source_url.is_empty() {
+                lines.push(format!("   source: {}", hit.source_url));
+                super::citations::record(
+                    super::citations::SourceKind::Search,
+                    title,
+                    &hit.source_url,
+                );
+            }
+        }
+        lines.join("\n")
+    }
+
+    fn custom_provider(&self, name: &str) -> Option<&CustomSearchProviderConfig> {
+        self.custom_providers
+            .iter()
+            .find(|custom| custom.name.trim().eq_ignore_ascii_case(name))
+    }
 
-        for i in 0..count {
-            let caps = &link_matches[i];
-            let url_str = decode_ddg_redirect_url(&caps[1]);
-            let title = strip_tags(&caps[2]);
+    /// Name shown in "Search results for: … (via …)".
+    fn provider_label<'a>(&'a self, provider: &'a str) -> &'a str {
+        match provider {
+            "duckduckgo" | "ddg" => "DuckDuckGo",
+            "brave" => "Brave",
+            _ => self
+                .custom_provider(provider)
+                .map_or(provider, |custom| custom.name.trim()),
+        }
+    }
 
-            lines.push(format!("{}. {}", i + 1, title.trim()));
-            lines.push(format!("   {}", url_str.trim()));
+    fn format_results(
+        &self,
+        provider: &str,
+        hits: &[SearchHit],
+        hidden: usize,
+        query: &str,
+    ) -> String {
+        if hits.is_empty() {
+            return match hidden {
+                0 => format!("No results found for: {}", query),
+                _ => format!(
+                    "No results found for: {} ({} hidden by the domain filter)",
+                    query, hidden
+                ),
+            };
+        }
 
-            // Add snippet if available
-            if i < snippet_matches.len() {
-                let snippet = strip_tags(&snippet_matches[i][1]);
-                let snippet = snippet.trim();
-                if !snippet.is_empty() {
-                    lines.push(format!("   {}", snippet));
-                }
+        let mut lines = vec![format!("Search results for: {} (via {})", query, provider)];
+        for (i, hit) in hits.iter().take(self.max_results).enumerate() {
+            let title = strip_tags(&hit.title);
+            let title = if title.trim().is_empty() {
+                "No title"
+            } else {
+                title.trim()
+            };
+            lines.push(format!("{}. {}", i + 1, title));
+            lines.push(format!("   {}", hit.url));
+            super::citations::record(super::citations::SourceKind::Search, title, &hit.url);
+            let snippet = strip_tags(&hit.snippet);
+            if !snippet.trim().is_empty() {
+                lines.push(format!("   {}", snippet.trim()));
             }
         }
+        lines.join("\n")
+    }
 
-        Ok(lines.join("\n"))
+    async fn search_duckduckgo(
+        &self,
+        query: &str,
+        recency: Option<Recency>,
+    ) -> anyhow::Result<Vec<SearchHit>> {
+        let encoded_query = urlencoding::encode(query);
+        let mut search_url = format!("https://html.duckduckgo.com/html/?q={}", encoded_query);
+        if let Some(recency) = recency {
+            search_url.push_str("&df=");
+            search_url.push_str(recency.duckduckgo());
+        }
+
+        let client = self.duckduckgo_client()?;
+        let response = client.get(&search_url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "DuckDuckGo search failed with status: {}",
+                response.status()
+            );
+        }
+
+        let html = response.text().await?;
+        parse_duckduckgo_results(&html)
     }
 
-    async fn search_brave(&self, query: &str) -> anyhow::Result<String> {
-        let api_key = self
-            .brave_api_key
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Brave API key not configured"))?;
+    async fn search_brave(
+        &self,
+        query: &str,
+        count: usize,
+        recency: Option<Recency>,
+    ) -> anyhow::Result<Vec<SearchHit>> {
+        if self.brave_keys.is_empty() {
+            anyhow::bail!("Brave API key not configured");
+        }
 
         let encoded_query = urlencoding::encode(query);
-        let search_url = format!(
+        let mut search_url = format!(
             "https://api.search.brave.com/res/v1/web/search?q={}&count={}",
-            encoded_query, self.max_results
+            encoded_query, count
+        );
+        if let Some(recency) = recency {
+            search_url.push_str("&freshness=");
+            search_url.push_str(recency.brave());
+        }
+        parse_brave_results(&self.brave_get(&search_url).await?)
+    }
+
+    /// Image search through Brave's images endpoint.
+    async fn search_brave_images(
+        &self,
+        query: &str,
+        count: usize,
+    ) -> anyhow::Result<Vec<ImageHit>> {
+        if self.brave_keys.is_empty() {
+            anyhow::bail!("Brave API key not configured");
+        }
+        let search_url = format!(
+            "https://api.search.brave.com/res/v1/images/search?q={}&count={}",
+            urlencoding::encode(query),
+            count
         );
+        parse_brave_images(&self.brave_get(&search_url).await?)
+    }
 
+    /// GET a Brave API URL, choosing the key from the pool.
+    async fn brave_get(&self, search_url: &str) -> anyhow::Result<serde_json::Value> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.timeout_secs))
             .build()?;
 
-        let response = client
-            .get(&search_url)
-            .header("Accept", "application/json")
-            .header("X-Subscription-Token", api_key)
+        // One try per key at most; a refused or rate-limited key hands over to the next.
+        for _ in 0..self.brave_keys.len() {
+            let Some(key) = self.brave_keys.select() else {
+                break;
+            };
+            let response = match client
+                .get(search_url)
+                .header("Accept", "application/json")
+                .header("X-Subscription-Token", self.brave_keys.key(key))
+                .send()
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    self.brave_keys.record(key, KeyOutcome::Error);
+                    return Err(e.into());
+                }
+            };
+
+            let status = response.status();
+            if status.is_success() {
+                self.brave_keys.record(key, KeyOutcome::Success);
+                return Ok(response.json().await?);
+            }
+
+            let outcome = brave_key_outcome(status, response.headers());
+            self.brave_keys.record(key, outcome);
+            if outcome == KeyOutcome::Error || !self.brave_keys.has_healthy_alternative(key) {
+                anyhow::bail!("Brave search failed with status: {status}");
+            }
+        }
+        anyhow::bail!("Brave search failed: every API key was refused or rate-limited")
+    }
+
+    /// Image search through DuckDuckGo, which needs a `vqd` token from the
+    /// regular results page before its JSON endpoint answers.
+    async fn search_duckduckgo_images(&self, query: &str) -> anyhow::Result<Vec<ImageHit>> {
+        let client = self.duckduckgo_client()?;
+        let page = client
+            .get("https://duckduckgo.com/")
+            .query(&[("q", query), ("iax", "images"), ("ia", "images")])
             .send()
+            .await?
+            .text()
             .await?;
+        let vqd = extract_vqd(&page)
+            .ok_or_else(|| anyhow::anyhow!("DuckDuckGo image search token not found"))?;
 
+        let response = client
+            .get("https://duckduckgo.com/i.js")
+            .query(&[
+                ("l", "us-en"),
+                ("o", "json"),
+                ("q", query),
+                ("vqd", vqd.as_str()),
+                ("f", ",,,,,"),
+                ("p", "1"),
+            ])
+            .header("Referer", "https://duckduckgo.com/")
+            .send()
+            .await?;
         if !response.status().is_success() {
-            anyhow::bail!("Brave search failed with status: {}", response.status());
+            anyhow::bail!(
+                "DuckDuckGo image search failed with status: {}",
+                response.status()
+            );
         }
+        parse_duckduckgo_images(&response.json().await?)
+    }
 
-        let json: serde_json::Value = response.json().await?;
-        self.parse_brave_results(&json, query)
+    fn duckduckgo_client(&self) -> anyhow::Result<reqwest::Client> {
+        Ok(reqwest::Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .user_agent(DUCKDUCKGO_USER_AGENT)
+            .build()?)
     }
+}
 
-    fn parse_brave_results(&self, json: &serde_json::Value, query: &str) -> anyhow::Result<String> {
-        let results = json
-            .get("web")
-            .and_then(|w| w.get("results"))
-            .and_then(|r| r.as_array())
-            .ok_or_else(|| anyhow::anyhow!("Invalid Brave API response"))?;
+fn parse_duckduckgo_results(html: &str) -> anyhow::Result<Vec<SearchHit>> {
+    // Extract result links: <a class="result__a" href="...">Title</a>
+    let link_regex =
+        Regex::new(r#"<a[^>]*class="[^"]*result__a[^"]*"[^>]*href="([^"]+)"[^>]*>([\s\S]*?)</a>"#)?;
 
-        if results.is_empty() {
-            return Ok(format!("No results found for: {}", query));
-        }
+    // Extract snippets: <a class="result__snippet">...</a>
+    let snippet_regex = Regex::new(r#"<a class="result__snippet[^"]*"[^>]*>([\s\S]*?)</a>"#)?;
 
-        let mut lines = vec![format!("Search results for: {} (via Brave)", query)];
+    let snippets: Vec<_> = snippet_regex.captures_iter(html).collect();
 
-        for (i, result) in results.iter().take(self.max_results).enumerate() {
-            let title = result
-                .get("title")
-                .and_then(|t| t.as_str())
-                .unwrap_or("No title");
-            let url = result.get("url").and_then(|u| u.as_str()).unwrap_or("");
-            let description = result
-                .get("description")
-                .and_then(|d| d.as_str())
-                .unwrap_or("");
+    Ok(link_regex
+        .captures_iter(html)
+        .enumerate()
+        .map(|(i, caps)| SearchHit {
+            title: strip_tags(&caps[2]).trim().to_string(),
+            url: decode_ddg_redirect_url(&caps[1]).trim().to_string(),
+            snippet: snippets
+                .get(i)
+                .map(|snippet| strip_tags(&snippet[1]).trim().to_string())
+                .unwrap_or_default(),
+        })
+        .collect())
+}
 
-            lines.push(format!("{}. {}", i + 1, title));
-            lines.push(format!("   {}", url));
-            if !description.is_empty() {
-                lines.push(format!("   {}", description));
-            }
-        }
+fn parse_brave_results(json: &serde_json::Value) -> anyhow::Result<Vec<SearchHit>> {
+    let results = json
+        .get("web")
+        .and_then(|w| w.get("results"))
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid Brave API response"))?;
+
+    let text = |result: &serde_json::Value, key: &str| {
+        result
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(results
+        .iter()
+        .map(|result| SearchHit {
+            title: text(result, "title"),
+            url: text(result, "url"),
+            snippet: text(result, "description"),
+        })
+        .collect())
+}
+
+fn parse_brave_images(json: &serde_json::Value) -> anyhow::Result<Vec<ImageHit>> {
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid Brave image API response"))?;
+    let text = |value: Option<&serde_json::Value>| {
+        value
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(results
+        .iter()
+        .map(|result| ImageHit {
+            title: text(result.get("title")),
+            image_url: text(result.get("properties").and_then(|p| p.get("url"))),
+            source_url: text(result.get("url")),
+            width: result
+                .get("properties")
+                .and_then(|p| p.get("width"))
+                .and_then(|w| w.as_u64()),
+            height: result
+                .get("properties")
+                .and_then(|p| p.get("height"))
+                .and_then(|h| h.as_u64()),
+        })
+        .filter(|hit| !hit.image_url.is_empty())
+        .collect())
+}
+
+fn parse_duckduckgo_images(json: &serde_json::Value) -> anyhow::Result<Vec<ImageHit>> {
+    let results = json
+        .get("results")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid DuckDuckGo image response"))?;
+    let text = |result: &serde_json::Value, key: &str| {
+        result
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(results
+        .iter()
+        .map(|result| ImageHit {
+            title: text(result, "title"),
+            image_url: text(result, "image"),
+            source_url: text(result, "url"),
+            width: result.get("width").and_then(|w| w.as_u64()),
+            height: result.get("height").and_then(|h| h.as_u64()),
+        })
+        .filter(|hit| !hit.image_url.is_empty())
+        .collect())
+}
 
-        Ok(lines.join("\n"))
+/// The `vqd` token DuckDuckGo embeds in its results page.
+fn extract_vqd(page: &str) -> Option<String> {
+    let re = Regex::new(r#"vqd=["']?([\d-]+)"#).ok()?;
+    re.captures(page).map(|caps| caps[1].to_string())
+}
+
+/// Error for a search where every provider failed; a single failure is
+/// returned as is.
+fn chain_error(mut failures: Vec<(&str, anyhow::Error)>) -> anyhow::Error {
+    if failures.len() == 1 {
+        return failures.remove(0).1;
     }
+    anyhow::anyhow!(
+        "All search providers failed: {}",
+        failures
+            .iter()
+            .map(|(provider, e)| format!("{provider}: {e}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    )
+}
+
+/// Host of a result URL, lowercased.
+fn result_host(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?.trim_end_matches('.').to_lowercase();
+    (!host.is_empty()).then_some(host)
 }
 
 fn decode_ddg_redirect_url(raw_url: &str) -> String {
@@ -181,6 +818,24 @@ fn strip_tags(content: &str) -> String {
     re.replace_all(content, "").to_string()
 }
 
+/// How a failed Brave response reflects on the key that made it.
+fn brave_key_outcome(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> KeyOutcome {
+    match status.as_u16() {
+        401 | 403 => KeyOutcome::Rejected,
+        429 => KeyOutcome::RateLimited(
+            headers
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs),
+        ),
+        _ => KeyOutcome::Error,
+    }
+}
+
 #[async_trait]
 impl Tool for WebSearchTool {
     fn name(&self) -> &str {
@@ -198,6 +853,25 @@ impl Tool for WebSearchTool {
                 "query": {
                     "type": "string",
                     "description": "The search query. Be specific for better results."
+                },
+                "images": {
+                    "type": "boolean",
+                    "description": "Search for images instead of pages. Returns image URLs with the pages they appear on."
+                },
+                "recency": {
+                    "type": "string",
+                    "enum": ["day", "week", "month", "year", "any"],
+                    "description": "Only return results published within this period. Use for news and anything time-sensitive."
+                },
+                "allowed_domains": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Only return results from these domains (subdomains included). Narrows, never widens, the configured filter."
+                },
+                "blocked_domains": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Never return results from these domains (subdomains included)."
                 }
             },
             "required": ["query"]
@@ -216,18 +890,72 @@ impl Tool for WebSearchTool {
 
         tracing::info!("Searching web for: {}", query);
 
-        let result = match self.provider.as_str() {
-            "duckduckgo" | "ddg" => self.search_duckduckgo(query).await?,
-            "brave" => self.search_brave(query).await?,
-            _ => anyhow::bail!(
-                "Unknown search provider: '{}'. Set tools.web_search.provider to 'duckduckgo' or 'brave' in config.toml",
-                self.provider
-            ),
+        // Results must pass both the configured filter and the call's own.
+        let call_domains = DomainFilter::from_args(&args);
+        let filtered = !self.domains.is_empty() || !call_domains.is_empty();
+        let mut site_operators = self.domains.query_operators();
+        site_operators.extend(call_domains.query_operators());
+        let recency = match args.get("recency").and_then(|r| r.as_str()) {
+            None => self.recency,
+            Some(raw) if raw.trim().eq_ignore_ascii_case("any") => None,
+            Some(raw) => Some(Recency::parse(raw).ok_or_else(|| {
+                anyhow::anyhow!("Invalid recency '{raw}': use day, week, month, year, or any")
+            })?),
+        };
+        let request = SearchRequest {
+            query,
+            site_operators,
+            count: if filtered {
+                FILTERED_FETCH_COUNT
+            } else {
+                self.max_results
+            },
+            recency,
+        };
+
+        if args
+            .get("images")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            let (label, hits) = self.search_images(&request).await?;
+            let total = hits.len();
+            let hits: Vec<ImageHit> = hits
+                .into_iter()
+                .filter(|hit| {
+                    let page = if hit.source_url.is_empty() {
+                        &hit.image_url
+                    } else {
+                        &hit.source_url
+                    };
+                    self.domains.permits(page) && call_domains.permits(page)
+                })
+                .collect();
+            return Ok(ToolResult {
+                success: true,
+                output: self.format_images(&label, &hits, total - hits.len(), query),
+                error: None,
+            });
+        }
+
+        let (label, hits) = match &self.merge {
+            Some((providers, ranking)) => self.search_merged(providers, *ranking, &request).await?,
+            None => self.search_fallback(&request).await?,
         };
 
+        let total = hits.len();
+        let hits: Vec<SearchHit> = hits
+            .into_iter()
+            .filter(|hit| self.domains.permits(&hit.url) && call_domains.permits(&hit.url))
+            .collect();
+        let hidden = total - hits.len();
+        if hidden > 0 {
+            tracing::debug!("Domain filter hid {hidden} of {total} search results");
+        }
+
         Ok(ToolResult {
             success: true,
-            output: result,
+            output: self.format_results(&label, &hits, hidden, query),
             error: None,
         })
     }
@@ -255,6 +983,7 @@ mod tests {
         let schema = tool.parameters_schema();
         assert_eq!(schema["type"], "object");
         assert!(schema["properties"]["query"].is_object());
+        assert_eq!(schema["properties"]["blocked_domains"]["type"], "array");
     }
 
     #[test]
@@ -266,9 +995,8 @@ mod tests {
     #[test]
     fn test_parse_duckduckgo_results_empty() {
         let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
-        let result = tool
-            .parse_duckduckgo_results("<html>No results here</html>", "test")
-            .unwrap();
+        let hits = parse_duckduckgo_results("<html>No results here</html>").unwrap();
+        let result = tool.format_results("DuckDuckGo", &hits, 0, "test");
         assert!(result.contains("No results found"));
     }
 
@@ -279,21 +1007,22 @@ mod tests {
             <a class="result__a" href="https://example.com">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
+        let hits = parse_duckduckgo_results(html).unwrap();
+        let result = tool.format_results("DuckDuckGo", &hits, 0, "test");
+        assert!(result.starts_with("Search results for: test (via DuckDuckGo)"));
         assert!(result.contains("Example Title"));
         assert!(result.contains("https://example.com"));
     }
 
     #[test]
     fn test_parse_duckduckgo_results_decodes_redirect_url() {
-        let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
         let html = r#"
             <a class="result__a" href="https://duckduckgo.com/l/?uddg=https%3A%2F%2Fexample.com%2Fpath%3Fa%3D1&amp;rut=test">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
-        assert!(result.contains("https://example.com/path?a=1"));
-        assert!(!result.contains("rut=test"));
+        let hits = parse_duckduckgo_results(html).unwrap();
+        assert_eq!(hits[0].url, "https://example.com/path?a=1");
+        assert_eq!(hits[0].snippet, "This is a description");
     }
 
     #[test]
@@ -303,8 +1032,10 @@ mod tests {
             <a class="result__a" href="https://example.com">Example Title</a>
             <a class="result__snippet">This is a description</a>
         "#;
-        let result = tool.parse_duckduckgo_results(html, "test").unwrap();
+        let hits = parse_duckduckgo_results(html).unwrap();
+        let result = tool.format_results("DuckDuckGo", &hits, 0, "test");
         assert!(result.contains("Example Title"));
+        assert_eq!(tool.max_results, 1);
     }
 
     #[tokio::test]
@@ -321,6 +1052,204 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn brave_key_outcome_maps_key_errors() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(
+            brave_key_outcome(reqwest::StatusCode::TOO_MANY_REQUESTS, &headers),
+            KeyOutcome::RateLimited(Some(Duration::from_secs(7)))
+        );
+        assert_eq!(
+            brave_key_outcome(reqwest::StatusCode::UNAUTHORIZED, &headers),
+            KeyOutcome::Rejected
+        );
+        assert_eq!(
+            brave_key_outcome(reqwest::StatusCode::BAD_GATEWAY, &headers),
+            KeyOutcome::Error
+        );
+    }
+
+    #[test]
+    fn brave_api_key_accepts_a_key_list() {
+        let tool = WebSearchTool::new("brave".to_string(), Some("k1, k2".into()), 5, 15);
+        assert_eq!(tool.brave_keys.len(), 2);
+    }
+
+    #[test]
+    fn provider_chain_appends_fallbacks_once() {
+        let tool = WebSearchTool::new("Brave".to_string(), None, 5, 15)
+            .with_fallback_providers(vec!["searx".into(), " BRAVE ".into(), "ddg".into()]);
+        assert_eq!(tool.provider_chain(), vec!["brave", "searx", "ddg"]);
+    }
+
+    #[test]
+    fn custom_results_are_formatted_like_builtins() {
+        let tool = WebSearchTool::new("searx".to_string(), None, 5, 15);
+        let hits = vec![SearchHit {
+            title: "<b>Example</b>".into(),
+            url: "https://example.com".into(),
+            snippet: "A description".into(),
+        }];
+        let output = tool.format_results("searx", &hits, 0, "test");
+        assert!(output.starts_with("Search results for: test (via searx)"));
+        assert!(output.contains("1. Example\n   https://example.com\n   A description"));
+        assert!(tool
+            .format_results("searx", &[], 2, "test")
+            .contains("2 hidden by the domain filter"));
+    }
+
+    #[tokio::test]
+    async fn merge_strategy_fails_only_when_every_provider_fails() {
+        let tool = WebSearchTool::new("brave".to_string(), None, 5, 15).with_merge_strategy(
+            vec!["brave".into(), "missing".into(), "BRAVE".into()],
+            MergeRanking::Interleave,
+        );
+        let err = tool
+            .execute(json!({"query": "test"}))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("All search providers failed"));
+        assert_eq!(err.matches("brave:").count(), 1);
+    }
+
+    #[test]
+    fn parses_image_results_from_both_providers() {
+        let brave = json!({"results": [
+            {"title": "Capybara", "url": "https://zoo.example/capybara",
+             "properties": {"url": "https://img.example/capy.jpg", "width": 800, "height": 600}},
+            {"title": "No image", "url": "https://zoo.example/none"}
+        ]});
+        let hits = parse_brave_images(&brave).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].image_url, "https://img.example/capy.jpg");
+        assert_eq!(hits[0].source_url, "https://zoo.example/capybara");
+        assert_eq!(hits[0].width, Some(800));
+
+        let ddg = json!({"results": [
+            {"title": "Capybara", "image": "https://img.example/c.png", "url": "https://wiki.example/Capybara"}
+        ]});
+        let hits = parse_duckduckgo_images(&ddg).unwrap();
+        assert_eq!(hits[0].image_url, "https://img.example/c.png");
+        assert!(parse_duckduckgo_images(&json!({})).is_err());
+    }
+
+    #[test]
+    fn image_results_list_image_and_source() {
+        let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
+        let hits = vec![ImageHit {
+            title: "Capybara".into(),
+            image_url: "https://img.example/c.png".into(),
+            source_url: "https://wiki.example/Capybara".into(),
+            width: Some(640),
+            height: Some(480),
+        }];
+        let output = tool.format_images("DuckDuckGo", &hits, 0, "capybara");
+        assert!(output.contains("1. Capybara (640x480)"));
+        assert!(output.contains("   image: https://img.example/c.png"));
+        assert!(output.contains("   source: https://wiki.example/Capybara"));
+        assert!(!output.contains("[IMAGE:"));
+    }
+
+    #[test]
+    fn extract_vqd_reads_the_token() {
+        assert_eq!(
+            extract_vqd(r#"...vqd="4-123456789",..."#).as_deref(),
+            Some("4-123456789")
+        );
+        assert_eq!(extract_vqd("&vqd=4-42&p=1").as_deref(), Some("4-42"));
+        assert!(extract_vqd("<html></html>").is_none());
+    }
+
+    #[tokio::test]
+    async fn image_search_needs_a_capable_provider() {
+        let tool = WebSearchTool::new("searx".to_string(), None, 5, 15);
+        let err = tool
+            .execute(json!({"query": "capybara", "images": true}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Image search needs"));
+    }
+
+    #[test]
+    fn recency_maps_to_native_provider_values() {
+        assert_eq!(Recency::parse(" Week "), Some(Recency::Week));
+        assert_eq!(Recency::parse("decade"), None);
+        assert_eq!(Recency::Day.brave(), "pd");
+        assert_eq!(Recency::Month.duckduckgo(), "m");
+        assert!(Recency::NAMES
+            .iter()
+            .all(|name| Recency::parse(name).is_some_and(|r| r.as_str() == *name)));
+    }
+
+    #[tokio::test]
+    async fn invalid_recency_is_rejected() {
+        let tool = WebSearchTool::new("duckduckgo".to_string(), None, 5, 15);
+        let err = tool
+            .execute(json!({"query": "news", "recency": "decade"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Invalid recency"));
+    }
+
+    #[test]
+    fn domain_filter_matches_subdomains_and_blocks_first() {
+        let filter = DomainFilter::new(
+            vec!["example.com".into(), "docs.rs".into()],
+            vec!["ads.example.com".into()],
+        );
+        assert!(filter.permits("https://www.example.com/page"));
+        assert!(filter.permits("https://docs.rs/regex"));
+        assert!(!filter.permits("https://ads.example.com/x"));
+        assert!(!filter.permits("https://example.org/"));
+        assert!(!filter.permits("not a url"));
+        assert!(DomainFilter::default().permits("not a url"));
+    }
+
+    #[test]
+    fn domain_filter_reads_call_arguments_and_builds_operators() {
+        let filter = DomainFilter::from_args(&json!({
+            "query": "rust",
+            "allowed_domains": ["https://Docs.rs/"],
+            "blocked_domains": ["pinterest.com", "*.spam.example"]
+        }));
+        assert!(filter.permits("https://docs.rs/tokio"));
+        assert_eq!(
+            filter.query_operators(),
+            vec!["site:docs.rs", "-site:pinterest.com"]
+        );
+        assert!(DomainFilter::from_args(&json!({"query": "rust"})).is_empty());
+        assert!(
+            DomainFilter::new(vec!["a.com".into(), "b.com".into()], Vec::new())
+                .query_operators()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn parse_brave_results_reads_hits() {
+        let json = json!({"web": {"results": [
+            {"title": "Example", "url": "https://example.com", "description": "Desc"}
+        ]}});
+        let hits = parse_brave_results(&json).unwrap();
+        assert_eq!(hits[0].snippet, "Desc");
+        assert!(parse_brave_results(&json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn failing_chain_reports_every_provider() {
+        let tool = WebSearchTool::new("brave".to_string(), None, 5, 15)
+            .with_fallback_providers(vec!["missing".into()]);
+        let err = tool
+            .execute(json!({"query": "test"}))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("brave: Brave API key not configured"));
+        assert!(err.contains("missing: Unknown search provider"));
+    }
+
     #[tokio::test]
     async fn test_execute_brave_without_api_key() {
         let tool = WebSearchTool::new("brave".to_string(), None, 5, 15);
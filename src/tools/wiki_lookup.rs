@@ -0,0 +1,621 @@
+use super::traits::{Tool, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+const WIKIDATA_API_URL: &str = "https://www.wikidata.org/w/api.php";
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+const MAX_CANDIDATES: usize = 8;
+/// Values shown per Wikidata property ("occupation" can list dozens).
+const MAX_VALUES_PER_FACT: usize = 5;
+
+/// Wikidata properties worth surfacing for encyclopedic questions, in
+/// display order.
+const FACT_PROPERTIES: &[(&str, &str)] = &[
+    ("P31", "instance of"),
+    ("P279", "subclass of"),
+    ("P17", "country"),
+    ("P131", "located in"),
+    ("P36", "capital"),
+    ("P1082", "population"),
+    ("P2046", "area"),
+    ("P2044", "elevation"),
+    ("P625", "coordinates"),
+    ("P569", "date of birth"),
+    ("P19", "place of birth"),
+    ("P570", "date of death"),
+    ("P20", "place of death"),
+    ("P27", "citizenship"),
+    ("P106", "occupation"),
+    ("P69", "educated at"),
+    ("P166", "award received"),
+    ("P50", "author"),
+    ("P57", "director"),
+    ("P577", "publication date"),
+    ("P571", "inception"),
+    ("P112", "founded by"),
+    ("P159", "headquarters"),
+    ("P169", "chief executive"),
+    ("P856", "official website"),
+];
+
+fn wikipedia_host(language: &str) -> String {
+    format!("https://{language}.wikipedia.org")
+}
+
+/// Wikipedia language editions use short lowercase codes such as `en`,
+/// `de`, `zh-yue`, or `simple`.
+fn valid_language(language: &str) -> bool {
+    (2..=12).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_lowercase() || c == '-')
+        && !language.starts_with('-')
+        && !language.ends_with('-')
+}
+
+/// A Wikipedia page summary from the REST API.
+#[derive(Debug, Clone, PartialEq)]
+struct Summary {
+    title: String,
+    description: Option<String>,
+    extract: String,
+    url: String,
+    wikidata_id: Option<String>,
+    disambiguation: bool,
+}
+
+fn parse_summary(body: &Value) -> Option<Summary> {
+    let title = body["title"].as_str()?.to_string();
+    let url = body["content_urls"]["desktop"]["page"]
+        .as_str()
+        .map(str::to_string)?;
+    Some(Summary {
+        title,
+        description: body["description"].as_str().map(str::to_string),
+        extract: body["extract"]
+            .as_str()
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        url,
+        wikidata_id: body["wikibase_item"].as_str().map(str::to_string),
+        disambiguation: body["type"].as_str() == Some("disambiguation"),
+    })
+}
+
+/// Titles and snippets from a `list=search` response.
+fn parse_search(body: &Value) -> Vec<(String, String)> {
+    body["query"]["search"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|hit| {
+            let title = hit["title"].as_str()?.to_string();
+            let snippet = strip_search_markup(hit["snippet"].as_str().unwrap_or_default());
+            Some((title, snippet))
+        })
+        .collect()
+}
+
+/// Search snippets wrap matches in `<span class="searchmatch">` and escape
+/// quotes; reduce them to plain text.
+fn strip_search_markup(snippet: &str) -> String {
+    let mut text = String::with_capacity(snippet.len());
+    let mut in_tag = false;
+    for c in snippet.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&quot;", "\"")
+        .replace("&#039;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Render one Wikidata claim value. Entity references come back as their
+/// Q-id and are resolved to labels separately.
+fn claim_value(snak: &Value) -> Option<String> {
+    let value = &snak["datavalue"]["value"];
+    match snak["datavalue"]["type"].as_str()? {
+        "wikibase-entityid" => value["id"].as_str().map(str::to_string),
+        "string" => value.as_str().map(str::to_string),
+        "monolingualtext" => value["text"].as_str().map(str::to_string),
+        "time" => {
+            let time = value["time"].as_str()?;
+            let (sign, rest) = time.split_at(1);
+            let date = rest.split('T').next()?;
+            let era = if sign == "-" { " BCE" } else { "" };
+            // Precision 9 = year, 10 = month, 11 = day.
+            let shown = match value["precision"].as_u64().unwrap_or(11) {
+                0..=9 => date.split('-').next()?.trim_start_matches('0').to_string(),
+                10 => date.rsplit_once('-').map(|(month, _)| month)?.to_string(),
+                _ => date.to_string(),
+            };
+            Some(format!("{shown}{era}"))
+        }
+        "quantity" => {
+            let amount = value["amount"].as_str()?.trim_start_matches('+');
+            let unit = value["unit"].as_str().unwrap_or("1");
+            // Units are entity URLs; keep the Q-id so it can be labelled.
+            match unit.rsplit('/').next() {
+                Some(id) if unit != "1" => Some(format!("{amount} {id}")),
+                _ => Some(amount.to_string()),
+            }
+        }
+        "globecoordinate" => Some(format!(
+            "{:.4}, {:.4}",
+            value["latitude"].as_f64()?,
+            value["longitude"].as_f64()?
+        )),
+        _ => None,
+    }
+}
+
+fn is_entity_id(token: &str) -> bool {
+    token.len() > 1 && token.starts_with('Q') && token[1..].chars().all(|c| c.is_ascii_digit())
+}
+
+/// Collect `(label, values)` for the known properties of an entity.
+fn extract_facts(entity: &Value) -> Vec<(&'static str, Vec<String>)> {
+    FACT_PROPERTIES
+        .iter()
+        .filter_map(|(property, label)| {
+            let claims = entity["claims"][*property].as_array()?;
+            // Prefer "preferred" rank when present (e.g. current population).
+            let preferred: Vec<&Value> = claims
+                .iter()
+                .filter(|claim| claim["rank"] == "preferred")
+                .collect();
+            let claims: Vec<&Value> = if preferred.is_empty() {
+                claims
+                    .iter()
+                    .filter(|claim| claim["rank"] != "deprecated")
+                    .collect()
+            } else {
+                preferred
+            };
+            let values: Vec<String> = claims
+                .iter()
+                .filter_map(|claim| claim_value(&claim["mainsnak"]))
+                .take(MAX_VALUES_PER_FACT)
+                .collect();
+            (!values.is_empty()).then_some((*label, values))
+        })
+        .collect()
+}
+
+fn referenced_entities(facts: &[(&str, Vec<String>)]) -> BTreeSet<String> {
+    facts
+        .iter()
+        .flat_map(|(_, values)| values)
+        .flat_map(|value| value.split(' '))
+        .filter(|token| is_entity_id(token))
+        .map(str::to_string)
+        .collect()
+}
+
+fn label_value(value: &str, labels: &Value, language: &str) -> String {
+    value
+        .split(' ')
+        .map(|token| {
+            if !is_entity_id(token) {
+                return token.to_string();
+            }
+            [language, "en"]
+                .iter()
+                .find_map(|lang| labels[token]["labels"][*lang]["value"].as_str())
+                .unwrap_or(token)
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Look up encyclopedic topics on Wikipedia, with structured facts from
+/// Wikidata and source links for citation.
+pub struct WikiLookupTool;
+
+impl WikiLookupTool {
+    pub fn new() -> Self {
+        Self
+    }
+
+    async fn get_json(
+        client: &reqwest::Client,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> anyhow::Result<Option<Value>> {
+        let response = client
+            .get(url)
+            .header(
+                reqwest::header::USER_AGENT,
+                concat!("zeroclaw/", env!("CARGO_PKG_VERSION")),
+            )
+            .query(query)
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("Wikimedia API returned status {}", response.status());
+        }
+        Ok(Some(response.json().await?))
+    }
+
+    async fn summary(
+        client: &reqwest::Client,
+        language: &str,
+        title: &str,
+    ) -> anyhow::Result<Option<Summary>> {
+        let url = format!(
+            "{}/api/rest_v1/page/summary/{}",
+            wikipedia_host(language),
+            urlencoding::encode(&title.replace(' ', "_"))
+        );
+        Ok(Self::get_json(client, &url, &[("redirect", "true")])
+            .await?
+            .as_ref()
+            .and_then(parse_summary))
+    }
+
+    async fn search(
+        client: &reqwest::Client,
+        language: &str,
+        query: &str,
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let url = format!("{}/w/api.php", wikipedia_host(language));
+        let limit = MAX_CANDIDATES.to_string();
+        let body = Self::get_json(
+            client,
+            &url,
+            &[
+                ("action", "query"),
+                ("list", "search"),
+                ("srsearch", query),
+                ("srlimit", &limit),
+                ("format", "json"),
+            ],
+        )
+        .await?;
+        Ok(body.as_ref().map(parse_search).unwrap_or_default())
+    }
+
+    async fn facts(
+        client: &reqwest::Client,
+        language: &str,
+        id: &str,
+    ) -> anyhow::Result<Vec<(&'static str, Vec<String>)>> {
+        let Some(body) = Self::get_json(
+            client,
+            WIKIDATA_API_URL,
+            &[
+                ("action", "wbgetentities"),
+                ("ids", id),
+                ("props", "claims"),
+                ("format", "json"),
+            ],
+        )
+        .await?
+        else {
+            return Ok(Vec::new());
+        };
+        let mut facts = extract_facts(&body["entities"][id]);
+
+        let ids: Vec<String> = referenced_entities(&facts).into_iter().collect();
+        if ids.is_empty() {
+            return Ok(facts);
+        }
+        let languages = format!("{language}|en");
+        let mut labels = json!({});
+        // wbgetentities accepts at most 50 ids per request.
+        for chunk in ids.chunks(50) {
+            let joined = chunk.join("|");
+            if let Some(body) = Self::get_json(
+                client,
+                WIKIDATA_API_URL,
+                &[
+                    ("action", "wbgetentities"),
+                    ("ids", &joined),
+                    ("props", "labels"),
+                    ("languages", &languages),
+                    ("format", "json"),
+                ],
+            )
+            .await?
+            {
+                if let (Some(target), Some(entities)) =
+                    (labels.as_object_mut(), body["entities"].as_object())
+                {
+                    target.extend(entities.clone());
+                }
+            }
+        }
+        for (_, values) in &mut facts {
+            for value in values.iter_mut() {
+                *value = label_value(value, &labels, language);
+            }
+        }
+        Ok(facts)
+    }
+
+    async fn lookup(
+        &self,
+        query: &str,
+        language: &str,
+        include_facts: bool,
+    ) -> anyhow::Result<String> {
+        let client = crate::config::build_runtime_proxy_client_with_timeouts(
+            "tool.wiki_lookup",
+            REQUEST_TIMEOUT_SECS,
+            10,
+        );
+
+        let mut summary = Self::summary(&client, language, query).await?;
+        if summary.is_none() {
+            // Not an exact title: take the best search hit.
+            let hits = Self::search(&client, language, query).await?;
+            let Some((title, _)) = hits.first() else {
+                return Ok(format!(
+                    "No {language}.wikipedia.org article found for '{query}'"
+                ));
+            };
+            summary = Self::summary(&client, language, title).await?;
+        }
+        let Some(summary) = summary else {
+            anyhow::bail!("article summary unavailable for '{query}'");
+        };
+
+        if summary.disambiguation {
+            let candidates = Self::search(&client, language, query).await?;
+            return Ok(format_disambiguation(&summary, &candidates));
+        }
+
+        let facts = match (&summary.wikidata_id, include_facts) {
+            (Some(id), true) => Self::facts(&client, language, id)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::debug!("wiki_lookup: Wikidata facts unavailable: {e}");
+                    Vec::new()
+                }),
+            _ => Vec::new(),
+        };
+        Ok(format_article(&summary, &facts))
+    }
+}
+
+impl Default for WikiLookupTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_article(summary: &Summary, facts: &[(&str, Vec<String>)]) -> String {
+    let mut output = summary.title.clone();
+    if let Some(description) = &summary.description {
+        let _ = write!(output, " — {description}");
+    }
+    let _ = write!(output, "\n\n{} [1]", summary.extract);
+    let wikidata_url = summary
+        .wikidata_id
+        .as_ref()
+        .map(|id| format!("https://www.wikidata.org/wiki/{id}"));
+    if !facts.is_empty() {
+        output.push_str("\n\nFacts [2]:");
+        for (label, values) in facts {
+            let _ = write!(output, "\n- {label}: {}", values.join(", "));
+        }
+    }
+    let _ = write!(output, "\n\nSources:\n[1] {}", summary.url);
+    if let (false, Some(url)) = (facts.is_empty(), wikidata_url) {
+        let _ = write!(output, "\n[2] {url}");
+    }
+    output
+}
+
+fn format_disambiguation(summary: &Summary, candidates: &[(String, String)]) -> String {
+    let mut output = format!(
+        "'{}' is ambiguous on Wikipedia ({}). Call wiki_lookup again with one of these titles:",
+        summary.title, summary.url
+    );
+    let candidates: Vec<_> = candidates
+        .iter()
+        .filter(|(title, _)| *title != summary.title)
+        .collect();
+    if candidates.is_empty() {
+        let _ = write!(output, "\n{}", summary.extract);
+    }
+    for (title, snippet) in candidates {
+        let _ = write!(output, "\n- {title}");
+        if !snippet.is_empty() {
+            let _ = write!(output, ": {snippet}");
+        }
+    }
+    output
+}
+
+#[async_trait]
+impl Tool for WikiLookupTool {
+    fn name(&self) -> &str {
+        "wiki_lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Look up a topic on Wikipedia: returns the article summary, key Wikidata facts (dates, places, population, ...), and source URLs to cite. Cheaper and more reliable than web search for encyclopedic questions. Ambiguous names return a list of candidate titles."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Article title or search terms, e.g. \"Ada Lovelace\" or \"Mercury (planet)\""
+                },
+                "language": {
+                    "type": "string",
+                    "description": "Wikipedia language edition code (en, de, fr, ja, ...)",
+                    "default": "en"
+                },
+                "facts": {
+                    "type": "boolean",
+                    "description": "Include structured facts from Wikidata",
+                    "default": true
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let failure = |error: String| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error),
+        };
+        let Some(query) = args
+            .get("query")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|query| !query.is_empty())
+        else {
+            return Ok(failure("Missing 'query' parameter".into()));
+        };
+        let language = args
+            .get("language")
+            .and_then(Value::as_str)
+            .map_or("en", str::trim)
+            .to_ascii_lowercase();
+        if !valid_language(&language) {
+            return Ok(failure(format!(
+                "Invalid language code '{language}' (use a Wikipedia edition such as en, de, or ja)"
+            )));
+        }
+        let include_facts = args.get("facts").and_then(Value::as_bool).unwrap_or(true);
+
+        match self.lookup(query, &language, include_facts).await {
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output,
+                error: None,
+            }),
+            Err(e) => Ok(failure(format!("Wikipedia lookup failed: {e}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn douglas_adams() -> Summary {
+        parse_summary(&json!({
+            "type": "standard",
+            "title": "Douglas Adams",
+            "description": "English writer and humorist (1952–2001)",
+            "extract": "Douglas Noël Adams was an English author.",
+            "wikibase_item": "Q42",
+            "content_urls": { "desktop": { "page": "https://en.wikipedia.org/wiki/Douglas_Adams" } }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn wikidata_claims_become_labelled_facts() {
+        let entity = json!({ "claims": {
+            "P31": [{ "rank": "normal", "mainsnak": { "datavalue": {
+                "type": "wikibase-entityid", "value": { "id": "Q5" } } } }],
+            "P569": [{ "rank": "normal", "mainsnak": { "datavalue": {
+                "type": "time", "value": { "time": "+1952-03-11T00:00:00Z", "precision": 11 } } } }],
+            "P1082": [
+                { "rank": "normal", "mainsnak": { "datavalue": {
+                    "type": "quantity", "value": { "amount": "+100", "unit": "1" } } } },
+                { "rank": "preferred", "mainsnak": { "datavalue": {
+                    "type": "quantity", "value": { "amount": "+120", "unit": "1" } } } }
+            ],
+            "P2046": [{ "rank": "normal", "mainsnak": { "datavalue": {
+                "type": "quantity",
+                "value": { "amount": "+891.1", "unit": "http://www.wikidata.org/entity/Q712226" } } } }],
+            "P571": [{ "rank": "normal", "mainsnak": { "datavalue": {
+                "type": "time", "value": { "time": "-0753-01-01T00:00:00Z", "precision": 9 } } } }]
+        }});
+        let mut facts = extract_facts(&entity);
+        assert_eq!(
+            referenced_entities(&facts).into_iter().collect::<Vec<_>>(),
+            vec!["Q5", "Q712226"]
+        );
+
+        let labels = json!({
+            "Q5": { "labels": { "en": { "value": "human" } } },
+            "Q712226": { "labels": { "de": { "value": "Quadratkilometer" } } }
+        });
+        for (_, values) in &mut facts {
+            for value in values.iter_mut() {
+                *value = label_value(value, &labels, "de");
+            }
+        }
+        assert_eq!(
+            facts,
+            vec![
+                ("instance of", vec!["human".to_string()]),
+                ("population", vec!["120".to_string()]),
+                ("area", vec!["891.1 Quadratkilometer".to_string()]),
+                ("date of birth", vec!["1952-03-11".to_string()]),
+                ("inception", vec!["753 BCE".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn articles_cite_wikipedia_and_wikidata() {
+        let summary = douglas_adams();
+        let output = format_article(
+            &summary,
+            &[("occupation", vec!["novelist".into(), "screenwriter".into()])],
+        );
+        assert_eq!(
+            output,
+            "Douglas Adams — English writer and humorist (1952–2001)\n\n\
+             Douglas Noël Adams was an English author. [1]\n\n\
+             Facts [2]:\n- occupation: novelist, screenwriter\n\n\
+             Sources:\n[1] https://en.wikipedia.org/wiki/Douglas_Adams\n\
+             [2] https://www.wikidata.org/wiki/Q42"
+        );
+        assert!(!format_article(&summary, &[]).contains("wikidata"));
+    }
+
+    #[test]
+    fn disambiguation_lists_candidate_titles() {
+        let mut summary = douglas_adams();
+        summary.title = "Mercury".into();
+        summary.disambiguation = true;
+        let candidates = parse_search(&json!({ "query": { "search": [
+            { "title": "Mercury", "snippet": "" },
+            { "title": "Mercury (planet)",
+              "snippet": "<span class=\"searchmatch\">Mercury</span> is the first planet" },
+            { "title": "Freddie Mercury", "snippet": "British singer &amp; songwriter" }
+        ]}}));
+        let output = format_disambiguation(&summary, &candidates);
+        assert!(output.starts_with("'Mercury' is ambiguous"));
+        assert!(output.ends_with(
+            "\n- Mercury (planet): Mercury is the first planet\n\
+             - Freddie Mercury: British singer & songwriter"
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_bad_arguments() {
+        let tool = WikiLookupTool::new();
+        let result = tool.execute(json!({})).await.unwrap();
+        assert_eq!(result.error.as_deref(), Some("Missing 'query' parameter"));
+
+        let result = tool
+            .execute(json!({"query": "Rust", "language": "en.evil.com/"}))
+            .await
+            .unwrap();
+        assert!(result.error.unwrap().starts_with("Invalid language code"));
+    }
+}
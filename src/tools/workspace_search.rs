@@ -0,0 +1,595 @@
+// Indexing code is only reachable with the `workspace-search` feature.
+#![cfg_attr(not(feature = "workspace-search"), allow(dead_code))]
+
+use super::traits::{Tool, ToolResult};
+use crate::config::WorkspaceSearchConfig;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Upper bound on files tracked by the index.
+const MAX_INDEXED_FILES: usize = 50_000;
+/// Hard ceiling on results regardless of what the caller requests.
+const MAX_RESULTS: usize = 50;
+/// Characters of context shown per hit.
+const SNIPPET_CHARS: usize = 240;
+
+/// Full-text search over the user's own files in the workspace.
+///
+/// The index lives in `state/workspace_index` and is refreshed before each
+/// search: files are compared to a manifest of sizes and modification times,
+/// and only new, changed, or removed files touch the index.
+///
+/// Indexing requires the `workspace-search` feature flag:
+///   cargo build --features workspace-search
+///
+/// Without the feature the tool is still registered so the LLM receives a
+/// clear, actionable error rather than a missing-tool confusion.
+pub struct WorkspaceSearchTool {
+    workspace_dir: PathBuf,
+    config: WorkspaceSearchConfig,
+    /// One refresh or search at a time; tantivy allows a single writer.
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl WorkspaceSearchTool {
+    pub fn new(workspace_dir: PathBuf, config: WorkspaceSearchConfig) -> Self {
+        Self {
+            workspace_dir,
+            config,
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    fn index_dir(&self) -> PathBuf {
+        self.workspace_dir.join("state").join("workspace_index")
+    }
+}
+
+/// Size and modification time of an indexed file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    modified: u64,
+    len: u64,
+}
+
+/// Workspace-relative path (with `/` separators) to stamp.
+type Manifest = BTreeMap<String, FileStamp>;
+
+/// Index updates needed to match the workspace.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Changes {
+    upsert: Vec<String>,
+    remove: Vec<String>,
+}
+
+impl Changes {
+    fn is_empty(&self) -> bool {
+        self.upsert.is_empty() && self.remove.is_empty()
+    }
+}
+
+fn plan(previous: &Manifest, current: &Manifest) -> Changes {
+    Changes {
+        upsert: current
+            .iter()
+            .filter(|(path, stamp)| previous.get(*path) != Some(*stamp))
+            .map(|(path, _)| path.clone())
+            .collect(),
+        remove: previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Files the config asks to index, with their stamps. Symlinks are not followed.
+fn scan(workspace_dir: &Path, config: &WorkspaceSearchConfig) -> Manifest {
+    let roots: Vec<PathBuf> = if config.paths.is_empty() {
+        vec![workspace_dir.to_path_buf()]
+    } else {
+        config.paths.iter().map(|p| workspace_dir.join(p)).collect()
+    };
+
+    let mut manifest = Manifest::new();
+    let mut pending = roots;
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if file_type.is_dir() {
+                if !config.exclude_dirs.contains(&name) {
+                    pending.push(path);
+                }
+                continue;
+            }
+            if !file_type.is_file() || !has_indexed_extension(&path, &config.extensions) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.len() > config.max_file_bytes {
+                continue;
+            }
+            let Some(relative) = relative_path(workspace_dir, &path) else {
+                continue;
+            };
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_secs());
+            manifest.insert(
+                relative,
+                FileStamp {
+                    modified,
+                    len: metadata.len(),
+                },
+            );
+            if manifest.len() >= MAX_INDEXED_FILES {
+                tracing::warn!("workspace_search: stopped at {MAX_INDEXED_FILES} files");
+                return manifest;
+            }
+        }
+    }
+    manifest
+}
+
+fn has_indexed_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+}
+
+fn relative_path(workspace_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(workspace_dir).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Some(parts.join("/"))
+}
+
+/// Searchable text of a file; HTML is reduced to its text.
+fn read_text(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    let is_html = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+    Some(if is_html {
+        nanohtml2text::html2text(&text)
+    } else {
+        text
+    })
+}
+
+fn load_manifest(index_dir: &Path) -> Manifest {
+    std::fs::read_to_string(index_dir.join("manifest.json"))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_manifest(index_dir: &Path, manifest: &Manifest) -> anyhow::Result<()> {
+    std::fs::write(
+        index_dir.join("manifest.json"),
+        serde_json::to_string(manifest)?,
+    )?;
+    Ok(())
+}
+
+/// One search hit.
+struct Hit {
+    path: String,
+    score: f32,
+    snippet: String,
+}
+
+#[cfg(feature = "workspace-search")]
+mod index {
+    use super::{load_manifest, plan, read_text, save_manifest, scan, Changes, Hit, SNIPPET_CHARS};
+    use crate::config::WorkspaceSearchConfig;
+    use std::path::Path;
+    use tantivy::collector::TopDocs;
+    use tantivy::query::QueryParser;
+    use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+    use tantivy::snippet::SnippetGenerator;
+    use tantivy::{doc, Index, IndexWriter, TantivyDocument, Term};
+
+    const WRITER_HEAP_BYTES: usize = 50_000_000;
+
+    struct Fields {
+        path: Field,
+        title: Field,
+        body: Field,
+    }
+
+    fn schema() -> (Schema, Fields) {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            path: builder.add_text_field("path", STRING | STORED),
+            title: builder.add_text_field("title", TEXT),
+            body: builder.add_text_field("body", TEXT | STORED),
+        };
+        (builder.build(), fields)
+    }
+
+    fn open(index_dir: &Path) -> anyhow::Result<(Index, Fields)> {
+        std::fs::create_dir_all(index_dir)?;
+        let (schema, fields) = schema();
+        let directory = tantivy::directory::MmapDirectory::open(index_dir)?;
+        match Index::open_or_create(directory, schema.clone()) {
+            Ok(index) => Ok((index, fields)),
+            Err(e) => {
+                // An index from an older schema is rebuilt from scratch.
+                tracing::warn!("workspace_search: rebuilding index: {e}");
+                std::fs::remove_dir_all(index_dir)?;
+                std::fs::create_dir_all(index_dir)?;
+                let directory = tantivy::directory::MmapDirectory::open(index_dir)?;
+                Ok((Index::open_or_create(directory, schema)?, fields))
+            }
+        }
+    }
+
+    /// Bring the index up to date and return how many files changed.
+    pub(super) fn refresh(
+        workspace_dir: &Path,
+        index_dir: &Path,
+        config: &WorkspaceSearchConfig,
+    ) -> anyhow::Result<(Index, usize)> {
+        let (index, fields) = open(index_dir)?;
+        let previous = load_manifest(index_dir);
+        let mut current = scan(workspace_dir, config);
+        let changes = plan(&previous, &current);
+        if changes.is_empty() {
+            return Ok((index, 0));
+        }
+        let Changes { upsert, remove } = changes;
+
+        let mut writer: IndexWriter = index.writer(WRITER_HEAP_BYTES)?;
+        for path in remove.iter().chain(&upsert) {
+            writer.delete_term(Term::from_field_text(fields.path, path));
+        }
+        for path in &upsert {
+            let Some(body) = read_text(&workspace_dir.join(path)) else {
+                // Unreadable now; retried on the next refresh.
+                current.remove(path);
+                continue;
+            };
+            let title = Path::new(path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().replace(['-', '_'], " "))
+                .unwrap_or_default();
+            writer.add_document(doc!(
+                fields.path => path.as_str(),
+                fields.title => title,
+                fields.body => body,
+            ))?;
+        }
+        writer.commit()?;
+        save_manifest(index_dir, &current)?;
+        Ok((index, upsert.len() + remove.len()))
+    }
+
+    pub(super) fn search(
+        index: &Index,
+        query: &str,
+        limit: usize,
+        path_prefix: Option<&str>,
+    ) -> anyhow::Result<Vec<Hit>> {
+        let (_, fields) = schema();
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        let mut parser = QueryParser::for_index(index, vec![fields.title, fields.body]);
+        parser.set_field_boost(fields.title, 2.0);
+        let (query, _errors) = parser.parse_query_lenient(query);
+
+        // Over-fetch when filtering by folder so the limit is still met.
+        let fetch = if path_prefix.is_some() {
+            limit * 5
+        } else {
+            limit
+        };
+        let top = searcher.search(&query, &TopDocs::with_limit(fetch))?;
+        let mut snippets = SnippetGenerator::create(&searcher, &*query, fields.body)?;
+        snippets.set_max_num_chars(SNIPPET_CHARS);
+
+        let mut hits = Vec::new();
+        for (score, address) in top {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let Some(path) = doc.get_first(fields.path).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            if path_prefix.is_some_and(|prefix| !path.starts_with(prefix)) {
+                continue;
+            }
+            let snippet = snippets.snippet_from_doc(&doc);
+            hits.push(Hit {
+                path: path.to_string(),
+                score,
+                snippet: snippet
+                    .fragment()
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            });
+            if hits.len() >= limit {
+                break;
+            }
+        }
+        Ok(hits)
+    }
+}
+
+fn format_hits(query: &str, hits: &[Hit], changed: usize) -> String {
+    let mut lines = Vec::new();
+    if changed > 0 {
+        lines.push(format!("(index updated: {changed} files changed)"));
+    }
+    if hits.is_empty() {
+        lines.push(format!("No workspace files match: {query}"));
+        return lines.join("\n");
+    }
+    lines.push(format!("Workspace results for: {query}"));
+    for (i, hit) in hits.iter().enumerate() {
+        lines.push(format!("{}. {} (score {:.2})", i + 1, hit.path, hit.score));
+        if !hit.snippet.is_empty() {
+            lines.push(format!("   {}", hit.snippet));
+        }
+    }
+    lines.join("\n")
+}
+
+#[async_trait]
+impl Tool for WorkspaceSearchTool {
+    fn name(&self) -> &str {
+        "workspace_search"
+    }
+
+    fn description(&self) -> &str {
+        "Full-text search over the user's own files in the workspace (notes, exported \
+         conversations, knowledge-base documents). Returns ranked file paths with matching \
+         excerpts; read a file with file_read for the full text. Supports words, \"exact \
+         phrases\", AND/OR/NOT, and prefix* queries. Requires the 'workspace-search' build feature."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Words or phrases to look for"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Only return files under this workspace folder, e.g. 'notes/2024'"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Maximum number of files to return (default from config, max 50)",
+                    "minimum": 1,
+                    "maximum": MAX_RESULTS
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let query = args
+            .get("query")
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string();
+        if query.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Missing 'query' parameter".into()),
+            });
+        }
+        let limit = args
+            .get("max_results")
+            .and_then(|v| v.as_u64())
+            .map_or(self.config.max_results, |n| {
+                usize::try_from(n).unwrap_or(MAX_RESULTS)
+            })
+            .clamp(1, MAX_RESULTS);
+        let path_prefix = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .map(|p| {
+                p.trim()
+                    .trim_start_matches("./")
+                    .trim_matches('/')
+                    .to_string()
+            })
+            .filter(|p| !p.is_empty() && p != ".");
+
+        #[cfg(feature = "workspace-search")]
+        {
+            let _guard = self.lock.lock().await;
+            let workspace_dir = self.workspace_dir.clone();
+            let index_dir = self.index_dir();
+            let config = self.config.clone();
+            let query_text = query.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                let (index, changed) = index::refresh(&workspace_dir, &index_dir, &config)?;
+                let hits = index::search(&index, &query_text, limit, path_prefix.as_deref())?;
+                anyhow::Ok((hits, changed))
+            })
+            .await;
+
+            return Ok(match result {
+                Ok(Ok((hits, changed))) => ToolResult {
+                    success: true,
+                    output: format_hits(&query, &hits, changed),
+                    error: None,
+                },
+                Ok(Err(e)) => ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Workspace search failed: {e}")),
+                },
+                Err(e) => ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Workspace search task panicked: {e}")),
+                },
+            });
+        }
+
+        #[cfg(not(feature = "workspace-search"))]
+        {
+            let _ = (limit, path_prefix);
+            Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    "Workspace search is not enabled. \
+                     Rebuild with: cargo build --features workspace-search"
+                        .into(),
+                ),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn stamp(modified: u64) -> FileStamp {
+        FileStamp { modified, len: 10 }
+    }
+
+    #[test]
+    fn plan_finds_new_changed_and_removed_files() {
+        let previous = Manifest::from([
+            ("a.md".to_string(), stamp(1)),
+            ("b.md".to_string(), stamp(1)),
+            ("c.md".to_string(), stamp(1)),
+        ]);
+        let current = Manifest::from([
+            ("a.md".to_string(), stamp(1)),
+            ("b.md".to_string(), stamp(2)),
+            ("d.md".to_string(), stamp(1)),
+        ]);
+        let changes = plan(&previous, &current);
+        assert_eq!(changes.upsert, vec!["b.md", "d.md"]);
+        assert_eq!(changes.remove, vec!["c.md"]);
+        assert!(plan(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn scan_respects_paths_extensions_and_exclusions() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("notes/2024")).unwrap();
+        std::fs::create_dir_all(root.join("notes/.git")).unwrap();
+        std::fs::create_dir_all(root.join("other")).unwrap();
+        std::fs::write(root.join("notes/2024/trip.md"), "Lisbon trip").unwrap();
+        std::fs::write(root.join("notes/photo.png"), [0u8; 4]).unwrap();
+        std::fs::write(root.join("notes/.git/config.txt"), "x").unwrap();
+        std::fs::write(root.join("notes/big.txt"), "x".repeat(64)).unwrap();
+        std::fs::write(root.join("other/todo.txt"), "milk").unwrap();
+
+        let config = WorkspaceSearchConfig {
+            paths: vec!["notes".into()],
+            max_file_bytes: 32,
+            ..WorkspaceSearchConfig::default()
+        };
+        let manifest = scan(root, &config);
+        assert_eq!(
+            manifest.keys().collect::<Vec<_>>(),
+            vec!["notes/2024/trip.md"]
+        );
+
+        let everything = scan(root, &WorkspaceSearchConfig::default());
+        assert!(everything.contains_key("other/todo.txt"));
+        assert!(everything.contains_key("notes/big.txt"));
+    }
+
+    #[test]
+    fn html_is_reduced_to_text() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("page.html");
+        std::fs::write(&path, "<html><body><p>Hello <b>there</b></p></body></html>").unwrap();
+        let text = read_text(&path).unwrap();
+        assert!(text.contains("Hello"));
+        assert!(!text.contains("<b>"));
+    }
+
+    #[test]
+    fn format_hits_lists_paths_and_snippets() {
+        let hits = vec![Hit {
+            path: "notes/trip.md".into(),
+            score: 1.5,
+            snippet: "Lisbon trip in May".into(),
+        }];
+        let output = format_hits("lisbon", &hits, 2);
+        assert!(output.starts_with("(index updated: 2 files changed)"));
+        assert!(output.contains("1. notes/trip.md (score 1.50)\n   Lisbon trip in May"));
+        assert!(format_hits("x", &[], 0).contains("No workspace files match"));
+    }
+
+    #[tokio::test]
+    async fn empty_query_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let tool =
+            WorkspaceSearchTool::new(dir.path().to_path_buf(), WorkspaceSearchConfig::default());
+        let result = tool.execute(json!({"query": "  "})).await.unwrap();
+        assert!(!result.success);
+    }
+
+    #[cfg(feature = "workspace-search")]
+    #[tokio::test]
+    async fn indexes_incrementally_and_finds_files() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        std::fs::write(root.join("notes/trip.md"), "Flights to Lisbon in May").unwrap();
+        std::fs::write(root.join("notes/shopping.md"), "Milk and eggs").unwrap();
+        let tool = WorkspaceSearchTool::new(root.to_path_buf(), WorkspaceSearchConfig::default());
+
+        let first = tool.execute(json!({"query": "lisbon"})).await.unwrap();
+        assert!(first.success, "{:?}", first.error);
+        assert!(first.output.contains("index updated: 2 files changed"));
+        assert!(first.output.contains("notes/trip.md"));
+        assert!(!first.output.contains("shopping"));
+
+        let again = tool.execute(json!({"query": "lisbon"})).await.unwrap();
+        assert!(!again.output.contains("index updated"));
+
+        std::fs::remove_file(root.join("notes/trip.md")).unwrap();
+        let gone = tool.execute(json!({"query": "lisbon"})).await.unwrap();
+        assert!(gone.output.contains("No workspace files match"));
+    }
+
+    #[cfg(not(feature = "workspace-search"))]
+    #[tokio::test]
+    async fn without_feature_returns_rebuild_hint() {
+        let dir = TempDir::new().unwrap();
+        let tool =
+            WorkspaceSearchTool::new(dir.path().to_path_buf(), WorkspaceSearchConfig::default());
+        let result = tool.execute(json!({"query": "notes"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("workspace-search"));
+    }
+}
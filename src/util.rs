@@ -43,6 +43,14 @@ pub fn truncate_with_ellipsis(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// Quote `value` as a single POSIX shell word.
+///
+/// Wraps it in single quotes and rewrites embedded quotes as `'\''`, so the
+/// shell passes it through verbatim.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
 /// Utility enum for handling optional values.
 pub enum MaybeSet<T> {
     Set(T),
@@ -54,6 +62,13 @@ pub enum MaybeSet<T> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's $HOME"), r"'it'\''s $HOME'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
     #[test]
     fn test_truncate_ascii_no_truncation() {
         // ASCII string shorter than limit - no change